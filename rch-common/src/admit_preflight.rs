@@ -111,6 +111,7 @@ fn family_token(kind: CompilationKind) -> &'static str {
         CompilationKind::CargoDoc => "cargo_doc",
         CompilationKind::CargoNextest => "cargo_nextest",
         CompilationKind::CargoBench => "cargo_bench",
+        CompilationKind::CargoRun => "cargo_run",
         CompilationKind::Rustc => "rustc",
         CompilationKind::Gcc => "gcc",
         CompilationKind::Gpp => "gpp",
@@ -122,6 +123,8 @@ fn family_token(kind: CompilationKind) -> &'static str {
         CompilationKind::Meson => "meson",
         CompilationKind::BunTest => "bun_test",
         CompilationKind::BunTypecheck => "bun_typecheck",
+        CompilationKind::TrunkBuild => "trunk_build",
+        CompilationKind::WasmPackBuild => "wasm_pack_build",
     }
 }
 
@@ -136,7 +139,10 @@ fn is_rust_kind(kind: CompilationKind) -> bool {
             | CompilationKind::CargoDoc
             | CompilationKind::CargoNextest
             | CompilationKind::CargoBench
+            | CompilationKind::CargoRun
             | CompilationKind::Rustc
+            | CompilationKind::TrunkBuild
+            | CompilationKind::WasmPackBuild
     )
 }
 
@@ -0,0 +1,282 @@
+//! User-supplied classifier rules for custom build tools.
+//!
+//! The built-in classifier only knows the toolchains RCH ships support for.
+//! A rules file teaches it about wrappers such as an in-house `xbuild` script:
+//!
+//! ```toml
+//! [[rule]]
+//! prefix = "xbuild"          # whole leading word(s) of the command
+//! kind = "cargo_build"
+//! confidence = 0.95          # default 0.9
+//!
+//! [[rule]]
+//! regex = '^make-release( |$)'
+//! kind = "make"
+//! priority = 10              # > 0: consulted before the built-in rules
+//! ```
+//!
+//! Rules with a positive `priority` are tried before the built-in classifier
+//! and can override it; all others only apply to commands the built-ins do
+//! not recognize. Higher priority wins, ties keep file order. Commands are
+//! matched after wrapper normalization (`sudo`, `env`, `time`, ...) and the
+//! same shell-structure checks as the built-ins apply, so a piped or chained
+//! command is never offloaded because of a user rule.
+//!
+//! [`ClassifierRules::from_toml`] compiles every rule once up front; matching
+//! is a linear scan over prefixes and pre-built regexes.
+
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::patterns::{
+    Classification, CompilationKind, check_structure, classify_command, normalize_command,
+};
+
+/// Confidence assigned to rules that do not set one.
+pub const DEFAULT_RULE_CONFIDENCE: f64 = 0.9;
+
+/// Errors from parsing or compiling a rules file.
+#[derive(Debug, thiserror::Error)]
+pub enum ClassifierRulesError {
+    /// The file is not valid TOML or has unknown fields.
+    #[error("invalid classifier rules: {0}")]
+    Parse(#[from] toml::de::Error),
+    /// A rule is malformed (numbered from 1 in file order).
+    #[error("classifier rule {index}: {reason}")]
+    InvalidRule { index: usize, reason: String },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RulesFile {
+    #[serde(default, rename = "rule")]
+    rules: Vec<RawRule>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawRule {
+    prefix: Option<String>,
+    regex: Option<String>,
+    kind: String,
+    confidence: Option<f64>,
+    #[serde(default)]
+    priority: i32,
+}
+
+#[derive(Debug)]
+enum Matcher {
+    Prefix(String),
+    Regex(Regex),
+}
+
+impl Matcher {
+    fn matches(&self, cmd: &str) -> bool {
+        match self {
+            Self::Prefix(prefix) => cmd
+                .strip_prefix(prefix.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace)),
+            Self::Regex(regex) => regex.is_match(cmd),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::Prefix(prefix) => format!("prefix '{prefix}'"),
+            Self::Regex(regex) => format!("regex '{}'", regex.as_str()),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CompiledRule {
+    matcher: Matcher,
+    kind: CompilationKind,
+    confidence: f64,
+    priority: i32,
+}
+
+/// A compiled set of user classifier rules, ordered by priority.
+#[derive(Debug, Default)]
+pub struct ClassifierRules {
+    rules: Vec<CompiledRule>,
+}
+
+impl ClassifierRules {
+    /// Parse and compile a rules file.
+    pub fn from_toml(content: &str) -> Result<Self, ClassifierRulesError> {
+        let file: RulesFile = toml::from_str(content)?;
+        let mut rules = file
+            .rules
+            .into_iter()
+            .enumerate()
+            .map(|(idx, raw)| {
+                compile_rule(raw).map_err(|reason| ClassifierRulesError::InvalidRule {
+                    index: idx + 1,
+                    reason,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        // Stable sort: equal priorities keep file order.
+        rules.sort_by_key(|rule| std::cmp::Reverse(rule.priority));
+        Ok(Self { rules })
+    }
+
+    /// Number of rules.
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Whether the file defined no rules.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Classify `cmd` with these rules layered over [`classify_command`].
+    pub fn classify(&self, cmd: &str) -> Classification {
+        let cmd = cmd.trim();
+        if let Some(classification) = self.match_rules(cmd, |priority| priority > 0) {
+            return classification;
+        }
+        let builtin = classify_command(cmd);
+        if builtin.is_compilation {
+            return builtin;
+        }
+        self.match_rules(cmd, |priority| priority <= 0)
+            .unwrap_or(builtin)
+    }
+
+    fn match_rules(&self, cmd: &str, in_tier: impl Fn(i32) -> bool) -> Option<Classification> {
+        if !self.rules.iter().any(|rule| in_tier(rule.priority)) {
+            return None;
+        }
+        let normalized = normalize_command(cmd);
+        let rule = self
+            .rules
+            .iter()
+            .filter(|rule| in_tier(rule.priority))
+            .find(|rule| rule.matcher.matches(&normalized))?;
+        if let Some(reason) = check_structure(cmd) {
+            return Some(Classification::not_compilation(reason));
+        }
+        Some(Classification::compilation(
+            rule.kind,
+            rule.confidence,
+            format!("classifier rule {}", rule.matcher.describe()),
+        ))
+    }
+}
+
+fn compile_rule(raw: RawRule) -> Result<CompiledRule, String> {
+    let kind = CompilationKind::from_name(&raw.kind).ok_or_else(|| {
+        format!(
+            "unknown kind '{}' (expected one of: {})",
+            raw.kind,
+            CompilationKind::ALL
+                .iter()
+                .map(|kind| kind.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    })?;
+    let confidence = raw.confidence.unwrap_or(DEFAULT_RULE_CONFIDENCE);
+    if !(0.0..=1.0).contains(&confidence) {
+        return Err(format!(
+            "confidence {confidence} must be between 0.0 and 1.0"
+        ));
+    }
+    let matcher = match (raw.prefix, raw.regex) {
+        (Some(prefix), None) if !prefix.trim().is_empty() => {
+            Matcher::Prefix(prefix.trim().to_string())
+        }
+        (None, Some(pattern)) => Matcher::Regex(
+            Regex::new(&pattern).map_err(|e| format!("invalid regex '{pattern}': {e}"))?,
+        ),
+        (Some(_), Some(_)) => return Err("set either prefix or regex, not both".to_string()),
+        _ => return Err("a non-empty prefix or a regex is required".to_string()),
+    };
+    Ok(CompiledRule {
+        matcher,
+        kind,
+        confidence,
+        priority: raw.priority,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RULES: &str = r#"
+[[rule]]
+prefix = "xbuild"
+kind = "cargo_build"
+
+[[rule]]
+regex = '^make( |$)'
+kind = "cargo_test"
+confidence = 0.99
+priority = 5
+"#;
+
+    #[test]
+    fn prefix_rule_extends_builtin_classifier() {
+        let rules = ClassifierRules::from_toml(RULES).unwrap();
+        assert_eq!(rules.len(), 2);
+
+        let result = rules.classify("xbuild --release");
+        assert!(result.is_compilation);
+        assert_eq!(result.kind, Some(CompilationKind::CargoBuild));
+        assert_eq!(result.confidence, DEFAULT_RULE_CONFIDENCE);
+        assert!(result.reason.contains("xbuild"));
+
+        assert!(rules.classify("time xbuild").is_compilation);
+        assert!(!rules.classify("xbuilder").is_compilation);
+        assert!(!rules.classify("ls -la").is_compilation);
+        // Built-ins still apply.
+        assert_eq!(
+            rules.classify("cargo check").kind,
+            Some(CompilationKind::CargoCheck)
+        );
+    }
+
+    #[test]
+    fn positive_priority_overrides_builtin_rules() {
+        let rules = ClassifierRules::from_toml(RULES).unwrap();
+        let result = rules.classify("make -j8");
+        assert_eq!(result.kind, Some(CompilationKind::CargoTest));
+        assert_eq!(result.confidence, 0.99);
+    }
+
+    #[test]
+    fn user_rules_respect_shell_structure() {
+        let rules = ClassifierRules::from_toml(RULES).unwrap();
+        assert!(!rules.classify("xbuild | tee log").is_compilation);
+        assert!(!rules.classify("xbuild; rm -rf target").is_compilation);
+    }
+
+    #[test]
+    fn invalid_rules_are_rejected() {
+        let err = ClassifierRules::from_toml("[[rule]]\nprefix = \"x\"\nkind = \"cargo_bild\"\n")
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("classifier rule 1: unknown kind")
+        );
+
+        let err = ClassifierRules::from_toml(
+            "[[rule]]\nprefix = \"x\"\nregex = \"x\"\nkind = \"make\"\n",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("not both"));
+
+        assert!(ClassifierRules::from_toml("[[rule]]\nregex = \"(\"\nkind = \"make\"\n").is_err());
+        assert!(
+            ClassifierRules::from_toml(
+                "[[rule]]\nprefix = \"x\"\nkind = \"make\"\nconfidence = 2.0\n"
+            )
+            .is_err()
+        );
+        assert!(ClassifierRules::from_toml("[[rules]]\nprefix = \"x\"\n").is_err());
+    }
+}
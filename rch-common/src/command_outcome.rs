@@ -0,0 +1,192 @@
+//! Exit-code classification for remotely executed commands.
+//!
+//! The hook (`run_hook`) and `rch exec` (`run_exec`) both turn a remote exit
+//! code plus captured stderr into a decision: report success, fall back to a
+//! local run (missing toolchain), or surface the failure with the remote exit
+//! code preserved. [`classify_exit`] is the single place that decides which
+//! [`CommandOutcome`] a result is, so the two paths cannot drift apart.
+//!
+//! Precedence: `0` is success; `120` is rch-wkr's timeout kill; `128 + N` is
+//! a signal kill; `101` is cargo's
+//! "tests ran but failed"; a recognized rustup/toolchain signature on stderr is
+//! a toolchain failure; `1` is a build error; anything else is `Other`.
+
+/// Exit code for successful cargo command (tests passed, build succeeded).
+pub const EXIT_SUCCESS: i32 = 0;
+
+/// Exit code for build/compilation error.
+pub const EXIT_BUILD_ERROR: i32 = 1;
+
+/// Exit code for cargo test when tests ran but some failed.
+pub const EXIT_TEST_FAILURES: i32 = 101;
+
+/// Exit code `rch-wkr execute --timeout-secs` returns after killing a
+/// command's process group for running past its timeout.
+pub const EXIT_WORKER_TIMED_OUT: i32 = 120;
+
+/// Minimum exit code indicating the process was killed by a signal.
+/// Exit code = 128 + signal number (e.g., 137 = 128 + 9 = SIGKILL).
+pub const EXIT_SIGNAL_BASE: i32 = 128;
+
+/// Classified outcome of a remote command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandOutcome {
+    /// Exit 0.
+    Success,
+    /// Exit 1: compilation failed; a local run would fail the same way.
+    BuildError,
+    /// Exit 101: tests ran and some failed.
+    TestFailures,
+    /// Killed by the given signal number (exit `128 + N`).
+    Signal(i32),
+    /// Exit 120: the worker killed the command for exceeding its timeout.
+    TimedOut,
+    /// The worker is missing the requested toolchain; retry locally.
+    ToolchainFailure,
+    /// Any other non-zero exit code.
+    Other(i32),
+}
+
+impl CommandOutcome {
+    /// True for [`CommandOutcome::Success`].
+    #[must_use]
+    pub fn is_success(self) -> bool {
+        matches!(self, Self::Success)
+    }
+
+    /// True when the failure is infrastructure on the worker and the command
+    /// should be re-run locally instead of reporting the remote exit code.
+    #[must_use]
+    pub fn should_fall_back_locally(self) -> bool {
+        matches!(self, Self::ToolchainFailure)
+    }
+}
+
+/// Classify a remote exit code and its stderr into a [`CommandOutcome`].
+#[must_use]
+pub fn classify_exit(exit_code: i32, stderr: &str) -> CommandOutcome {
+    if exit_code == EXIT_SUCCESS {
+        return CommandOutcome::Success;
+    }
+    if exit_code == EXIT_WORKER_TIMED_OUT {
+        return CommandOutcome::TimedOut;
+    }
+    if let Some(signal) = is_signal_killed(exit_code) {
+        return CommandOutcome::Signal(signal);
+    }
+    if exit_code == EXIT_TEST_FAILURES {
+        return CommandOutcome::TestFailures;
+    }
+    if is_toolchain_failure(stderr, exit_code) {
+        return CommandOutcome::ToolchainFailure;
+    }
+    if exit_code == EXIT_BUILD_ERROR {
+        return CommandOutcome::BuildError;
+    }
+    CommandOutcome::Other(exit_code)
+}
+
+/// Check if the failure is a toolchain-related infrastructure failure.
+///
+/// Returns true if the error indicates a toolchain issue that should
+/// trigger a local fallback rather than denying execution.
+pub fn is_toolchain_failure(stderr: &str, exit_code: i32) -> bool {
+    if exit_code == EXIT_SUCCESS
+        || exit_code == EXIT_TEST_FAILURES
+        || is_signal_killed(exit_code).is_some()
+    {
+        return false;
+    }
+
+    stderr
+        .lines()
+        .map(str::trim)
+        .map(str::to_ascii_lowercase)
+        .any(|line| {
+            line.starts_with("rustup: command not found")
+                || line.starts_with("rustup: not found")
+                || line.contains("error: no default toolchain configured")
+                || line.contains("error: no active toolchain")
+                || (line.contains("error: toolchain ")
+                    && (line.contains(" is not installed")
+                        || line.contains(" is unavailable")
+                        || line.contains(" does not have the binary ")))
+                || (line.contains("error: override toolchain ")
+                    && line.contains(" is not installed"))
+        })
+}
+
+/// Check if the process was killed by a signal.
+///
+/// Exit codes > 128 indicate the process was terminated by a signal.
+/// The signal number is exit_code - 128.
+///
+/// Common signals:
+/// - 137 (SIGKILL = 9): Typically OOM killer
+/// - 143 (SIGTERM = 15): Graceful termination request
+/// - 139 (SIGSEGV = 11): Segmentation fault
+pub fn is_signal_killed(exit_code: i32) -> Option<i32> {
+    if exit_code > EXIT_SIGNAL_BASE {
+        Some(exit_code - EXIT_SIGNAL_BASE)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MISSING_TOOLCHAIN: &str = "error: toolchain 'nightly-2024-01-01' is not installed";
+
+    #[test]
+    fn classify_exit_distinguishes_cargo_codes() {
+        assert_eq!(classify_exit(0, ""), CommandOutcome::Success);
+        assert_eq!(
+            classify_exit(1, "error[E0308]: mismatched types"),
+            CommandOutcome::BuildError
+        );
+        assert_eq!(classify_exit(101, ""), CommandOutcome::TestFailures);
+        assert_eq!(classify_exit(137, ""), CommandOutcome::Signal(9));
+        assert_eq!(classify_exit(120, ""), CommandOutcome::TimedOut);
+        assert_eq!(classify_exit(2, ""), CommandOutcome::Other(2));
+        // Exactly 128 is not a signal exit.
+        assert_eq!(classify_exit(128, ""), CommandOutcome::Other(128));
+    }
+
+    #[test]
+    fn classify_exit_detects_toolchain_failure_only_on_plain_failures() {
+        assert_eq!(
+            classify_exit(1, MISSING_TOOLCHAIN),
+            CommandOutcome::ToolchainFailure
+        );
+        assert_eq!(
+            classify_exit(127, "bash: line 1: rustup: command not found"),
+            CommandOutcome::Other(127)
+        );
+        assert_eq!(
+            classify_exit(127, "rustup: command not found"),
+            CommandOutcome::ToolchainFailure
+        );
+        // Signal kills and test failures win over toolchain-looking stderr.
+        assert_eq!(
+            classify_exit(137, MISSING_TOOLCHAIN),
+            CommandOutcome::Signal(9)
+        );
+        assert_eq!(
+            classify_exit(101, MISSING_TOOLCHAIN),
+            CommandOutcome::TestFailures
+        );
+        assert_eq!(classify_exit(0, MISSING_TOOLCHAIN), CommandOutcome::Success);
+    }
+
+    #[test]
+    fn outcome_predicates() {
+        assert!(CommandOutcome::Success.is_success());
+        assert!(!CommandOutcome::BuildError.is_success());
+        assert!(CommandOutcome::ToolchainFailure.should_fall_back_locally());
+        assert!(!CommandOutcome::Signal(9).should_fall_back_locally());
+        assert!(!CommandOutcome::TimedOut.should_fall_back_locally());
+        assert!(!CommandOutcome::Other(2).should_fall_back_locally());
+    }
+}
@@ -16,7 +16,9 @@ pub mod validate;
 pub use env::{EnvError, EnvParser};
 pub use profiles::Profile;
 pub use source::{ConfigSource, ConfigValueSource, Sourced};
-pub use validate::{ConfigWarning, Severity, validate_config};
+pub use validate::{
+    ConfigToValidate, ConfigWarning, Severity, tracked_artifact_warnings, validate_config,
+};
 
 #[cfg(test)]
 pub(crate) fn env_test_lock() -> std::sync::MutexGuard<'static, ()> {
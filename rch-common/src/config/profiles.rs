@@ -22,6 +22,9 @@ pub enum Profile {
 }
 
 impl Profile {
+    /// The named profiles `rch config profile` can switch between.
+    pub const NAMED: [Profile; 3] = [Profile::Dev, Profile::Prod, Profile::Test];
+
     /// Get the profile from the RCH_PROFILE environment variable.
     pub fn from_env() -> Option<Self> {
         let value = env::var("RCH_PROFILE").ok()?;
@@ -48,13 +51,37 @@ impl Profile {
         }
     }
 
+    /// Parse a named profile, rejecting anything that would map to
+    /// [`Profile::Custom`].
+    pub fn parse_named(s: &str) -> Option<Self> {
+        Some(Self::from_string(s)).filter(|profile| *profile != Profile::Custom)
+    }
+
     /// Get profile defaults as key-value pairs.
     ///
     /// Returns environment variable defaults for this profile.
     /// Only returns variables that are NOT already set in the environment.
     /// The caller is responsible for actually setting them if desired.
     pub fn get_defaults(&self) -> Vec<(&'static str, &'static str)> {
-        let candidates = match self {
+        // Filter out already-set variables
+        self.defaults()
+            .into_iter()
+            .filter(|(key, _)| env::var(key).is_err())
+            .collect()
+    }
+
+    /// Log level this profile defaults to, if it sets one.
+    pub fn log_level(&self) -> Option<&'static str> {
+        self.defaults()
+            .into_iter()
+            .find(|(key, _)| *key == "RCH_LOG_LEVEL")
+            .map(|(_, value)| value)
+    }
+
+    /// All environment variable defaults for this profile, whether or not
+    /// they are already set.
+    pub fn defaults(&self) -> Vec<(&'static str, &'static str)> {
+        match self {
             Profile::Dev => {
                 debug!("Getting dev profile defaults");
                 vec![("RCH_LOG_LEVEL", "debug"), ("RCH_LOG_FORMAT", "pretty")]
@@ -79,13 +106,7 @@ impl Profile {
                 debug!("Custom profile - no automatic defaults");
                 vec![]
             }
-        };
-
-        // Filter out already-set variables
-        candidates
-            .into_iter()
-            .filter(|(key, _)| env::var(key).is_err())
-            .collect()
+        }
     }
 
     /// Get profile-specific description.
@@ -246,4 +267,21 @@ mod tests {
         // Clean up
         remove_env("RCH_LOG_LEVEL");
     }
+
+    #[test]
+    fn test_parse_named_rejects_custom() {
+        assert_eq!(Profile::parse_named("production"), Some(Profile::Prod));
+        assert_eq!(Profile::parse_named("staging"), None);
+        assert_eq!(Profile::parse_named("custom"), None);
+    }
+
+    #[test]
+    fn test_profile_log_level_ignores_environment() {
+        let _guard = env_guard();
+        set_env("RCH_LOG_LEVEL", "error");
+        assert_eq!(Profile::Prod.log_level(), Some("warn"));
+        assert_eq!(Profile::Dev.log_level(), Some("debug"));
+        assert_eq!(Profile::Custom.log_level(), None);
+        remove_env("RCH_LOG_LEVEL");
+    }
 }
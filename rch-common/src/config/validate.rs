@@ -239,6 +239,66 @@ pub fn validate_config(config: &ConfigToValidate) -> Vec<ConfigWarning> {
     warnings
 }
 
+/// Build-output directories that should never be tracked in git.
+///
+/// Rsync excludes keep these off the worker even when tracked, but a tracked
+/// copy still leaks stale artifacts into dependency-closure scans and leaves
+/// the remote tree partial.
+pub const TRACKED_ARTIFACT_DIRS: &[&str] = &[
+    "target",
+    ".rch-target",
+    "node_modules",
+    ".next",
+    ".nuxt",
+    ".turbo",
+    ".parcel-cache",
+    ".nyc_output",
+];
+
+/// Warn about build-output directories tracked in git.
+///
+/// `tracked_files` is `git ls-files` output, one path per line. A path counts
+/// when one of its directory components is in [`TRACKED_ARTIFACT_DIRS`] and no
+/// `src` component precedes it (so a `src/target/` module is not flagged).
+/// Returns at most one warning per artifact dir, naming a sample path.
+pub fn tracked_artifact_warnings(tracked_files: &str) -> Vec<ConfigWarning> {
+    let mut found: Vec<(&str, usize, &str)> = Vec::new();
+    for path in tracked_files
+        .lines()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+    {
+        let mut components: Vec<&str> = path.split('/').collect();
+        components.pop(); // the file name itself
+        let dir = components
+            .iter()
+            .take_while(|component| **component != "src")
+            .find_map(|component| {
+                TRACKED_ARTIFACT_DIRS
+                    .iter()
+                    .find(|dir| *dir == component)
+                    .copied()
+            });
+        let Some(dir) = dir else {
+            continue;
+        };
+        match found.iter_mut().find(|(name, _, _)| *name == dir) {
+            Some((_, count, _)) => *count += 1,
+            None => found.push((dir, 1, path)),
+        }
+    }
+
+    found
+        .into_iter()
+        .map(|(dir, count, sample)| {
+            ConfigWarning::warning(
+                format!("{dir}/"),
+                format!("{count} file(s) under {dir}/ are tracked in git (e.g. {sample})"),
+            )
+        })
+        .collect()
+}
+
 /// Check if there are any errors in the warnings list.
 pub fn has_errors(warnings: &[ConfigWarning]) -> bool {
     warnings
@@ -255,6 +315,26 @@ pub fn filter_by_severity(warnings: &[ConfigWarning], severity: Severity) -> Vec
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_tracked_artifact_warnings() {
+        let tracked = "Cargo.toml\n\
+                       src/main.rs\n\
+                       src/target/mod.rs\n\
+                       target/debug/app\n\
+                       crates/core/target/release/libcore.rlib\n\
+                       web/node_modules/left-pad/index.js\n\
+                       docs/build/index.md\n";
+        let warnings = tracked_artifact_warnings(tracked);
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0].var, "target/");
+        assert!(warnings[0].message.starts_with("2 file(s) under target/"));
+        assert!(warnings[0].message.contains("e.g. target/debug/app"));
+        assert_eq!(warnings[1].var, "node_modules/");
+        assert_eq!(warnings[1].severity, Severity::Warning);
+
+        assert!(tracked_artifact_warnings("Cargo.toml\nsrc/lib.rs\n").is_empty());
+    }
+
     #[test]
     fn test_validate_low_timeout() {
         let config = ConfigToValidate {
@@ -0,0 +1,182 @@
+//! Per-build decision trace (`rch explain <build_id>`).
+//!
+//! A remote build passes through several decisions that are otherwise only
+//! visible in scattered log lines: classification against the confidence
+//! threshold, worker selection, project path normalization, the dependency
+//! closure, per-root sync outcomes, execution and artifact retrieval. Each
+//! decision is recorded as a [`DecisionTraceStep`]; the hook collects its
+//! steps into a [`DecisionTrace`] and sends them with the release request,
+//! the daemon adds the selection step, and the merged trace is stored on the
+//! build's history record.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Maximum number of steps kept per build, so a build with many dependency
+/// roots cannot grow its history record without bound.
+pub const MAX_DECISION_TRACE_STEPS: usize = 64;
+
+/// Pipeline stage a decision belongs to, in pipeline order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionStage {
+    /// Command classification and the confidence threshold check.
+    Classification,
+    /// Worker selection by the daemon.
+    Selection,
+    /// Project path normalization.
+    PathNormalization,
+    /// Dependency closure planning.
+    Dependencies,
+    /// Source sync to the worker.
+    Sync,
+    /// Remote command execution.
+    Execution,
+    /// Artifact retrieval.
+    Artifacts,
+}
+
+impl DecisionStage {
+    /// Stable snake_case name.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Classification => "classification",
+            Self::Selection => "selection",
+            Self::PathNormalization => "path_normalization",
+            Self::Dependencies => "dependencies",
+            Self::Sync => "sync",
+            Self::Execution => "execution",
+            Self::Artifacts => "artifacts",
+        }
+    }
+}
+
+impl fmt::Display for DecisionStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One recorded decision.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecisionTraceStep {
+    /// Stage the decision belongs to.
+    pub stage: DecisionStage,
+    /// Human-readable description of what was decided and why.
+    pub detail: String,
+}
+
+impl DecisionTraceStep {
+    /// Create a step.
+    pub fn new(stage: DecisionStage, detail: impl Into<String>) -> Self {
+        Self {
+            stage,
+            detail: detail.into(),
+        }
+    }
+}
+
+impl fmt::Display for DecisionTraceStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.stage, self.detail)
+    }
+}
+
+/// Ordered decision steps of one build.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DecisionTrace {
+    steps: Vec<DecisionTraceStep>,
+}
+
+impl DecisionTrace {
+    /// Create an empty trace.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a decision.
+    pub fn push(&mut self, stage: DecisionStage, detail: impl Into<String>) {
+        self.steps.push(DecisionTraceStep::new(stage, detail));
+    }
+
+    /// Append steps recorded elsewhere.
+    pub fn extend(&mut self, steps: impl IntoIterator<Item = DecisionTraceStep>) {
+        self.steps.extend(steps);
+    }
+
+    /// Recorded steps in insertion order.
+    pub fn steps(&self) -> &[DecisionTraceStep] {
+        &self.steps
+    }
+
+    /// Whether nothing was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Steps in pipeline order, capped at [`MAX_DECISION_TRACE_STEPS`].
+    ///
+    /// Steps come from both the hook and the daemon, so they are sorted by
+    /// stage; the sort is stable, keeping insertion order within a stage.
+    pub fn into_steps(self) -> Vec<DecisionTraceStep> {
+        let mut steps = self.steps;
+        steps.sort_by_key(|step| step.stage);
+        steps.truncate(MAX_DECISION_TRACE_STEPS);
+        steps
+    }
+}
+
+impl From<Vec<DecisionTraceStep>> for DecisionTrace {
+    fn from(steps: Vec<DecisionTraceStep>) -> Self {
+        Self { steps }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn into_steps_orders_by_stage_and_keeps_insertion_order() {
+        let mut trace = DecisionTrace::new();
+        trace.push(DecisionStage::Sync, "root a synced");
+        trace.push(DecisionStage::Classification, "cargo_build");
+        trace.push(DecisionStage::Sync, "root b skipped");
+        trace.extend([DecisionTraceStep::new(DecisionStage::Selection, "css")]);
+
+        let details: Vec<_> = trace
+            .into_steps()
+            .into_iter()
+            .map(|step| step.to_string())
+            .collect();
+        assert_eq!(
+            details,
+            [
+                "classification: cargo_build",
+                "selection: css",
+                "sync: root a synced",
+                "sync: root b skipped",
+            ]
+        );
+    }
+
+    #[test]
+    fn into_steps_is_bounded() {
+        let mut trace = DecisionTrace::new();
+        for idx in 0..MAX_DECISION_TRACE_STEPS + 10 {
+            trace.push(DecisionStage::Sync, format!("root {idx}"));
+        }
+        assert_eq!(trace.into_steps().len(), MAX_DECISION_TRACE_STEPS);
+    }
+
+    #[test]
+    fn steps_round_trip_as_snake_case() {
+        let step = DecisionTraceStep::new(DecisionStage::PathNormalization, "x");
+        let json = serde_json::to_string(&step).unwrap();
+        assert_eq!(json, r#"{"stage":"path_normalization","detail":"x"}"#);
+        assert_eq!(
+            serde_json::from_str::<DecisionTraceStep>(&json).unwrap(),
+            step
+        );
+    }
+}
@@ -109,6 +109,10 @@ pub fn build_dependency_closure_plan(entrypoint: &Path) -> DependencyClosurePlan
 ///
 /// This function is fail-open by design: resolver/planner failures are converted
 /// into a `FailOpen` plan with structured issues and fallback rationale.
+///
+/// The plan always covers every workspace member: cargo loads each member's
+/// manifest and path dependencies even under `-p`/`--exclude`, so a package
+/// selection cannot shrink the set of roots the worker needs.
 pub fn build_dependency_closure_plan_with_policy(
     entrypoint: &Path,
     policy: &PathTopologyPolicy,
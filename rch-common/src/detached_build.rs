@@ -0,0 +1,166 @@
+//! Detachable remote builds.
+//!
+//! With `transfer.detach_remote_builds`, the remote command runs in a
+//! background subshell that ignores SIGHUP and writes its stdout, stderr and
+//! final exit status to files next to the build's pgid file. The SSH session
+//! only *follows* those files, so losing the client (closed terminal, dropped
+//! SSH link) no longer takes the build down with it. `rch attach` re-runs
+//! [`follow_command`] to replay the output and pick up the exit code, and the
+//! daemon polls [`exit_probe_command`] to release the build's slots once a
+//! detached build finishes without its hook.
+
+use shell_escape::escape;
+use std::borrow::Cow;
+
+/// Exit status reported by [`follow_command`] when the build vanished
+/// without recording an exit status (e.g. the worker rebooted).
+pub const LOST_EXIT_CODE: i32 = 255;
+
+/// Remote files backing one detached build.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetachedBuildFiles {
+    /// Process-group leader PID file (shared with cancellation).
+    pub pgid: String,
+    /// Captured stdout.
+    pub stdout: String,
+    /// Captured stderr.
+    pub stderr: String,
+    /// Final exit status, written once the build finishes.
+    pub exit: String,
+}
+
+impl DetachedBuildFiles {
+    /// Derive the output files from the build's `<run dir>/<build_id>.pgid` path.
+    pub fn for_pgid_file(pgid_file: &str) -> Self {
+        let stem = pgid_file.strip_suffix(".pgid").unwrap_or(pgid_file);
+        Self {
+            pgid: pgid_file.to_string(),
+            stdout: format!("{stem}.out"),
+            stderr: format!("{stem}.err"),
+            exit: format!("{stem}.exit"),
+        }
+    }
+}
+
+fn esc(path: &str) -> Cow<'_, str> {
+    escape(Cow::from(path))
+}
+
+/// Wrap `invocation` so it keeps running after the SSH session goes away,
+/// then follow its output until it finishes and exit with its status.
+pub fn detach_command(files: &DetachedBuildFiles, invocation: &str) -> String {
+    let out = esc(&files.stdout);
+    let err = esc(&files.stderr);
+    let exit = esc(&files.exit);
+    format!(
+        "{{ : > {out}; : > {err}; rm -f {exit} {exit}.tmp; \
+( trap '' HUP; ( {invocation} ) > {out} 2> {err} < /dev/null; \
+echo $? > {exit}.tmp && mv {exit}.tmp {exit} ) >/dev/null 2>&1 </dev/null & \
+{follow}; }}",
+        follow = follow_command(files),
+    )
+}
+
+/// Stream a detached build's stdout/stderr from the start until it finishes,
+/// then exit with its recorded status ([`LOST_EXIT_CODE`] if none was written).
+///
+/// The watcher also gives up when the output file is gone or the recorded
+/// process group has died without leaving an exit status behind.
+pub fn follow_command(files: &DetachedBuildFiles) -> String {
+    let pgid = esc(&files.pgid);
+    let out = esc(&files.stdout);
+    let err = esc(&files.stderr);
+    let exit = esc(&files.exit);
+    format!(
+        "( while [ ! -f {exit} ]; do [ -f {out} ] || break; \
+if [ -s {pgid} ] && ! kill -0 -\"$(cat {pgid})\" 2>/dev/null; then sleep 1; break; fi; \
+sleep 1; done ) >/dev/null 2>&1 </dev/null & __w=$!; \
+tail -n +1 -f --pid=\"$__w\" {out} & __o=$!; \
+tail -n +1 -f --pid=\"$__w\" {err} >&2 & __e=$!; \
+wait \"$__w\"; wait \"$__o\" \"$__e\"; \
+if [ -f {exit} ]; then exit \"$(cat {exit})\"; fi; exit {LOST_EXIT_CODE}"
+    )
+}
+
+/// Print the recorded exit status, or nothing while the build is running.
+pub fn exit_probe_command(files: &DetachedBuildFiles) -> String {
+    format!("cat {} 2>/dev/null || true", esc(&files.exit))
+}
+
+/// Parse the output of [`exit_probe_command`].
+pub fn parse_exit_probe(stdout: &str) -> Option<i32> {
+    stdout.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    fn files_in(dir: &std::path::Path) -> DetachedBuildFiles {
+        DetachedBuildFiles::for_pgid_file(&dir.join("7.pgid").display().to_string())
+    }
+
+    #[test]
+    fn files_derive_from_pgid_path() {
+        let files = DetachedBuildFiles::for_pgid_file("/tmp/rch-run/app-0123/42.pgid");
+        assert_eq!(files.stdout, "/tmp/rch-run/app-0123/42.out");
+        assert_eq!(files.stderr, "/tmp/rch-run/app-0123/42.err");
+        assert_eq!(files.exit, "/tmp/rch-run/app-0123/42.exit");
+    }
+
+    #[test]
+    fn parse_exit_probe_ignores_running_builds() {
+        assert_eq!(parse_exit_probe("3\n"), Some(3));
+        assert_eq!(parse_exit_probe(""), None);
+    }
+
+    #[test]
+    fn detached_build_streams_output_and_can_be_reattached() {
+        let temp = tempfile::tempdir().unwrap();
+        let files = files_in(temp.path());
+
+        let output = Command::new("sh")
+            .arg("-c")
+            .arg(detach_command(
+                &files,
+                "echo built; echo warned >&2; exit 3",
+            ))
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(3));
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "built\n");
+        assert_eq!(String::from_utf8_lossy(&output.stderr), "warned\n");
+        assert_eq!(std::fs::read_to_string(&files.exit).unwrap(), "3\n");
+
+        let replay = Command::new("sh")
+            .arg("-c")
+            .arg(follow_command(&files))
+            .output()
+            .unwrap();
+        assert_eq!(replay.status.code(), Some(3));
+        assert_eq!(String::from_utf8_lossy(&replay.stdout), "built\n");
+
+        let probe = Command::new("sh")
+            .arg("-c")
+            .arg(exit_probe_command(&files))
+            .output()
+            .unwrap();
+        assert_eq!(
+            parse_exit_probe(&String::from_utf8_lossy(&probe.stdout)),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn follow_reports_lost_build_without_output() {
+        let temp = tempfile::tempdir().unwrap();
+        let status = Command::new("sh")
+            .arg("-c")
+            .arg(follow_command(&files_in(temp.path())))
+            .output()
+            .unwrap()
+            .status;
+        assert_eq!(status.code(), Some(LOST_EXIT_CODE));
+    }
+}
@@ -213,6 +213,9 @@ impl TestWorkerEntry {
             total_slots: self.total_slots,
             priority: self.priority,
             tags: self.tags.clone(),
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         }
     }
 }
@@ -880,6 +880,9 @@ mod tests {
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         }
     }
 }
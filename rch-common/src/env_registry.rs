@@ -0,0 +1,953 @@
+//! Registry of the `RCH_*` environment variables read on the local machine.
+//!
+//! The variables are read all over the tree (config overrides in the CLI,
+//! placement controls, logging, the daemon's reaper, the repo_updater trust
+//! policy, the mock transports). This module is the single place that lists
+//! them with their default and effect, so `rch env` can show what is set
+//! without anyone grepping the source.
+//!
+//! Names that already exist as constants (the repo_updater contract) are
+//! referenced rather than retyped. The placement controls keep their own
+//! richer registry in [`crate::placement_controls`]; every canonical name and
+//! alias there is also listed here, which the tests below enforce.
+//!
+//! Worker-side variables (`rch-wkr`, `rch-telemetry` on the worker) are not
+//! listed: their value on this machine says nothing about the worker.
+
+use crate::repo_updater_contract::{
+    REPO_UPDATER_ALLOW_OVERRIDE_ENV, REPO_UPDATER_ALLOWED_HOSTS_ENV, REPO_UPDATER_ALLOWLIST_ENV,
+    REPO_UPDATER_AUTH_CREDENTIAL_ID_ENV, REPO_UPDATER_AUTH_EXPIRES_AT_MS_ENV,
+    REPO_UPDATER_AUTH_ISSUED_AT_MS_ENV, REPO_UPDATER_AUTH_MODE_ENV, REPO_UPDATER_AUTH_REVOKED_ENV,
+    REPO_UPDATER_AUTH_SCOPES_ENV, REPO_UPDATER_AUTH_SOURCE_ENV,
+    REPO_UPDATER_AUTH_VERIFIED_HOSTS_ENV, REPO_UPDATER_OVERRIDE_APPROVED_AT_MS_ENV,
+    REPO_UPDATER_OVERRIDE_AUDIT_EVENT_ID_ENV, REPO_UPDATER_OVERRIDE_JUSTIFICATION_ENV,
+    REPO_UPDATER_OVERRIDE_OPERATOR_ID_ENV, REPO_UPDATER_OVERRIDE_TICKET_REF_ENV,
+    REPO_UPDATER_REQUIRE_HOST_IDENTITY_ENV, REPO_UPDATER_REQUIRED_SCOPES_ENV,
+    REPO_UPDATER_ROTATION_MAX_AGE_SECS_ENV, REPO_UPDATER_TRUSTED_HOST_IDENTITIES_ENV,
+};
+use serde::{Deserialize, Serialize};
+
+/// Value shown in place of a secret variable's contents.
+pub const REDACTED_ENV_VALUE: &str = "<redacted>";
+
+/// Which part of RCH reads a variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EnvVarCategory {
+    /// Overrides a config file key.
+    Config,
+    /// Placement, strict-remote, queueing and visibility controls.
+    Placement,
+    /// Hook and CLI invocation behavior.
+    Hook,
+    /// Hook auto-start and daemon hook install.
+    SelfHealing,
+    /// Log output.
+    Logging,
+    /// The local daemon (`rchd`).
+    Daemon,
+    /// Fleet deployment and load testing.
+    Fleet,
+    /// repo_updater convergence trust policy.
+    RepoUpdater,
+    /// Secrets named by `[[doctor.webhooks.endpoints]]`. These names come from
+    /// config, not this registry; `rch env` lists them and always redacts.
+    Webhook,
+    /// Mock transports for tests and offline verification.
+    Testing,
+}
+
+impl EnvVarCategory {
+    /// Heading used when rendering the registry for humans.
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Config => "config overrides",
+            Self::Placement => "placement",
+            Self::Hook => "hook",
+            Self::SelfHealing => "self-healing",
+            Self::Logging => "logging",
+            Self::Daemon => "daemon",
+            Self::Fleet => "fleet",
+            Self::RepoUpdater => "repo_updater",
+            Self::Webhook => "webhook secrets",
+            Self::Testing => "testing",
+        }
+    }
+}
+
+/// One recognized environment variable.
+#[derive(Debug, Clone, Copy)]
+pub struct EnvVarSpec {
+    /// Variable name.
+    pub name: &'static str,
+    /// Which part of RCH reads it.
+    pub category: EnvVarCategory,
+    /// What applies when it is unset.
+    pub default: &'static str,
+    /// One-line description of the effect.
+    pub description: &'static str,
+    /// Never print the value.
+    pub secret: bool,
+}
+
+impl EnvVarSpec {
+    const fn new(
+        name: &'static str,
+        category: EnvVarCategory,
+        default: &'static str,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            name,
+            category,
+            default,
+            description,
+            secret: false,
+        }
+    }
+
+    const fn secret(mut self) -> Self {
+        self.secret = true;
+        self
+    }
+
+    /// `raw` as it may be displayed: [`REDACTED_ENV_VALUE`] for secrets.
+    #[must_use]
+    pub fn display_value(&self, raw: &str) -> String {
+        if self.secret {
+            REDACTED_ENV_VALUE.to_string()
+        } else {
+            raw.to_string()
+        }
+    }
+}
+
+use EnvVarCategory::{
+    Config, Daemon, Fleet, Hook, Logging, Placement, RepoUpdater, SelfHealing, Testing,
+};
+
+const ENV_VARS: &[EnvVarSpec] = &[
+    // Config overrides (applied after config.toml and .rch/config.toml).
+    EnvVarSpec::new(
+        "RCH_ENABLED",
+        Config,
+        "general.enabled (true)",
+        "Master switch; false runs every command locally.",
+    ),
+    EnvVarSpec::new(
+        "RCH_CONFIG_DIR",
+        Config,
+        "~/.config/rch",
+        "Directory holding config.toml and workers.toml.",
+    ),
+    EnvVarSpec::new(
+        "RCH_DISABLE_CONFIG_CACHE",
+        Config,
+        "unset (cache on)",
+        "Re-parse the TOML config on every run instead of using the cache.",
+    ),
+    EnvVarSpec::new(
+        "RCH_CLASSIFIER_RULES",
+        Config,
+        "<config dir>/classifier.toml",
+        "Path to the user classifier rules file.",
+    ),
+    EnvVarSpec::new(
+        "RCH_SOCKET_PATH",
+        Config,
+        "general.socket_path",
+        "Daemon Unix socket path.",
+    ),
+    EnvVarSpec::new(
+        "RCH_CONFIDENCE_THRESHOLD",
+        Config,
+        "compilation.confidence_threshold",
+        "Minimum classifier confidence (0.0-1.0) to intercept a command.",
+    ),
+    EnvVarSpec::new(
+        "RCH_MIN_LOCAL_TIME_MS",
+        Config,
+        "compilation.min_local_time_ms",
+        "Estimated local runtime below which a build stays local.",
+    ),
+    EnvVarSpec::new(
+        "RCH_REMOTE_SPEEDUP_THRESHOLD",
+        Config,
+        "compilation.remote_speedup_threshold",
+        "Predicted remote speedup ratio required before offloading.",
+    ),
+    EnvVarSpec::new(
+        "RCH_BUILD_SLOTS",
+        Config,
+        "compilation.build_slots",
+        "Worker slots requested for a build.",
+    ),
+    EnvVarSpec::new(
+        "RCH_TEST_SLOTS",
+        Config,
+        "compilation.test_slots",
+        "Worker slots requested for a test run.",
+    ),
+    EnvVarSpec::new(
+        "RCH_CHECK_SLOTS",
+        Config,
+        "compilation.check_slots",
+        "Worker slots requested for check/clippy.",
+    ),
+    EnvVarSpec::new(
+        "RCH_BUILD_TIMEOUT_SEC",
+        Config,
+        "compilation.build_timeout_sec",
+        "Remote build timeout in seconds.",
+    ),
+    EnvVarSpec::new(
+        "RCH_TEST_TIMEOUT_SEC",
+        Config,
+        "compilation.test_timeout_sec",
+        "Remote test timeout in seconds.",
+    ),
+    EnvVarSpec::new(
+        "RCH_BUN_TIMEOUT_SEC",
+        Config,
+        "compilation.bun_timeout_sec",
+        "Remote bun command timeout in seconds.",
+    ),
+    EnvVarSpec::new(
+        "RCH_EXTERNAL_TIMEOUT_ENABLED",
+        Config,
+        "compilation.external_timeout_enabled",
+        "Wrap remote commands in an external timeout so stuck builds free their slot.",
+    ),
+    EnvVarSpec::new(
+        "RCH_COMPRESSION_LEVEL",
+        Config,
+        "transfer.compression_level (3)",
+        "zstd level for transfers; RCH_COMPRESSION is an older alias.",
+    ),
+    EnvVarSpec::new(
+        "RCH_COMPRESSION",
+        Config,
+        "transfer.compression_level (3)",
+        "Alias of RCH_COMPRESSION_LEVEL, used only when that is unset.",
+    ),
+    EnvVarSpec::new(
+        "RCH_SSH_SERVER_ALIVE_INTERVAL_SECS",
+        Config,
+        "transfer.ssh_server_alive_interval_secs",
+        "SSH ServerAliveInterval for worker connections.",
+    ),
+    EnvVarSpec::new(
+        "RCH_SSH_CONTROL_PERSIST_SECS",
+        Config,
+        "transfer.ssh_control_persist_secs",
+        "SSH ControlPersist idle seconds; 0 disables connection reuse.",
+    ),
+    EnvVarSpec::new(
+        "RCH_FLEET",
+        Config,
+        "transfer.fleet (all workers)",
+        "Build only on workers of this named fleet.",
+    ),
+    EnvVarSpec::new(
+        "RCH_CONTAINER_IMAGE",
+        Config,
+        "transfer.container_image (none)",
+        "Run remote builds inside this container image.",
+    ),
+    EnvVarSpec::new(
+        "RCH_ENV_ALLOWLIST",
+        Config,
+        "environment.allowlist",
+        "Comma-separated env vars forwarded to the worker.",
+    ),
+    EnvVarSpec::new(
+        "RCH_STATE_DIR",
+        Config,
+        "self_healing.state_dir ($XDG_RUNTIME_DIR/rch)",
+        "Directory for the hook's auto-start lock and cooldown files.",
+    ),
+    EnvVarSpec::new(
+        "RCH_STATE_HOME",
+        Config,
+        "~/.local/state/rch",
+        "Directory for bypass records and the incident ledger.",
+    ),
+    EnvVarSpec::new(
+        "RCH_CANONICAL_PROJECT_ROOT",
+        Config,
+        "path_topology.canonical_root (/data/projects)",
+        "Canonical projects root mirrored on workers.",
+    ),
+    EnvVarSpec::new(
+        "RCH_ALIAS_PROJECT_ROOT",
+        Config,
+        "path_topology.alias_root (/dp)",
+        "Alias that resolves to the canonical projects root.",
+    ),
+    EnvVarSpec::new(
+        "RCH_WSL_MOUNT_PREFIX",
+        Config,
+        "path_topology.wsl_mount_prefix",
+        "WSL drive mount prefix used when normalizing project paths.",
+    ),
+    EnvVarSpec::new(
+        "RCH_KEEP_LOGICAL_SYMLINKS",
+        Config,
+        "path_topology.keep_logical_symlinks",
+        "Keep the logical path of projects symlinked outside the canonical root.",
+    ),
+    EnvVarSpec::new(
+        "RCH_REMEDIATION_HOOK_EXEC_FAIL_OPEN",
+        Config,
+        "remediation.policy.hook_exec_fail_open",
+        "Run the command locally when the hook itself fails.",
+    ),
+    EnvVarSpec::new(
+        "RCH_REMEDIATION_PROOF_FAIL_CLOSED",
+        Config,
+        "remediation.policy.proof_mode_fail_closed",
+        "Refuse local fallback in proof mode.",
+    ),
+    EnvVarSpec::new(
+        "RCH_REMEDIATION_INCIDENT_MAX_ENTRIES",
+        Config,
+        "remediation incident ledger cap",
+        "Maximum entries kept in the incident ledger.",
+    ),
+    EnvVarSpec::new(
+        "RCH_REMEDIATION_BYPASS_CHECK_INTERVAL_SECS",
+        Config,
+        "remediation bypass check interval",
+        "Seconds between bypass-record consistency checks.",
+    ),
+    EnvVarSpec::new(
+        "RCH_REMEDIATION_TELEMETRY_MAX_AGE_SECS",
+        Config,
+        "remediation telemetry max age",
+        "Age after which worker telemetry counts as stale.",
+    ),
+    // Placement controls (mirrors `placement_controls()`).
+    EnvVarSpec::new(
+        "RCH_WORKER",
+        Placement,
+        "unset (daemon picks)",
+        "Request specific worker(s) by id; RCH_WORKERS is an alias.",
+    ),
+    EnvVarSpec::new(
+        "RCH_WORKERS",
+        Placement,
+        "unset (daemon picks)",
+        "Alias of RCH_WORKER.",
+    ),
+    EnvVarSpec::new(
+        "RCH_PRESET",
+        Placement,
+        "unset",
+        "Request a named execution profile.",
+    ),
+    EnvVarSpec::new(
+        "RCH_REQUIRE_REMOTE",
+        Placement,
+        "0",
+        "Fail closed instead of falling back to a local build.",
+    ),
+    EnvVarSpec::new(
+        "RCH_FORCE_REMOTE",
+        Placement,
+        "general.force_remote (false)",
+        "Always attempt offload, still failing open to local.",
+    ),
+    EnvVarSpec::new(
+        "RCH_QUEUE_WHEN_BUSY",
+        Placement,
+        "1",
+        "Wait for a busy worker instead of building locally; 0 disables.",
+    ),
+    EnvVarSpec::new(
+        "RCH_DAEMON_WAIT_RESPONSE_TIMEOUT_SECS",
+        Placement,
+        "daemon default",
+        "Maximum wait for a queued worker before falling back.",
+    ),
+    EnvVarSpec::new(
+        "RCH_DAEMON_RESPONSE_TIMEOUT_SECS",
+        Placement,
+        "daemon default",
+        "Alias of RCH_DAEMON_WAIT_RESPONSE_TIMEOUT_SECS.",
+    ),
+    EnvVarSpec::new(
+        "RCH_VISIBILITY",
+        Placement,
+        "output.visibility",
+        "Hook output: none, summary or verbose.",
+    ),
+    EnvVarSpec::new(
+        "RCH_QUIET",
+        Placement,
+        "unset",
+        "Truthy forces visibility none; wins over RCH_VISIBILITY.",
+    ),
+    EnvVarSpec::new(
+        "RCH_VERBOSE",
+        Placement,
+        "unset",
+        "Truthy sets visibility verbose when RCH_VISIBILITY is unset.",
+    ),
+    EnvVarSpec::new(
+        "RCH_DISABLE_TARGET_REUSE",
+        Placement,
+        "0 (pooled target dir)",
+        "Use a unique-per-job remote target dir instead of the pooled one.",
+    ),
+    EnvVarSpec::new(
+        "RCH_NO_SELF_HEALING",
+        Placement,
+        "0",
+        "Disable hook auto-start of the daemon and daemon hook install.",
+    ),
+    // Hook / CLI behavior.
+    EnvVarSpec::new(
+        "RCH_HOOK_MODE",
+        Hook,
+        "unset",
+        "Read hook JSON from stdin even when stdin is a terminal.",
+    ),
+    EnvVarSpec::new(
+        "RCH_JSON",
+        Hook,
+        "unset",
+        "Force machine (JSON) output and hook-mode behavior.",
+    ),
+    EnvVarSpec::new(
+        "RCH_OUTPUT_FORMAT",
+        Hook,
+        "unset",
+        "Machine output format: json or toon; implies --json.",
+    ),
+    EnvVarSpec::new(
+        "RCH_PRIORITY",
+        Hook,
+        "normal",
+        "Queue priority for this command: low, normal or high.",
+    ),
+    EnvVarSpec::new(
+        "RCH_SOCKET_AUTODETECT",
+        Hook,
+        "0",
+        "Use a daemon found at a well-known socket path when the configured one is missing.",
+    ),
+    EnvVarSpec::new(
+        "RCH_SESSION_ID",
+        Hook,
+        "unset",
+        "Agent session id; set by the hook and used for queue fairness.",
+    ),
+    EnvVarSpec::new(
+        "RCH_TOOL_USE_ID",
+        Hook,
+        "unset",
+        "Agent tool call id; set by the hook and used to coalesce duplicate builds.",
+    ),
+    EnvVarSpec::new(
+        "RCH_STALE_TARGET_REAP_HOURS",
+        Hook,
+        "12",
+        "Idle hours before a remote per-job target dir is reaped (min 1).",
+    ),
+    EnvVarSpec::new(
+        "RCH_HOOK_SERVER_SOCKET",
+        Hook,
+        "$XDG_RUNTIME_DIR/rch-hook.sock, else ~/.cache/rch/rch-hook.sock",
+        "Socket shared by `rch hook-server` and the hook that forwards to it.",
+    ),
+    EnvVarSpec::new(
+        "RCH_CARGO_WRAPPER_BYPASS",
+        Hook,
+        "unset",
+        "Set by rch on local fallback so a cargo wrapper does not re-offload.",
+    ),
+    EnvVarSpec::new(
+        "RCH_NO_UPDATE_CHECK",
+        Hook,
+        "unset (checks on)",
+        "Skip the background update check.",
+    ),
+    // Self-healing.
+    EnvVarSpec::new(
+        "RCH_HOOK_STARTS_DAEMON",
+        SelfHealing,
+        "self_healing.hook_starts_daemon (true)",
+        "Let the hook start rchd when the socket is missing.",
+    ),
+    EnvVarSpec::new(
+        "RCH_DAEMON_INSTALLS_HOOKS",
+        SelfHealing,
+        "self_healing.daemon_installs_hooks (true)",
+        "Let rchd reinstall missing agent hooks.",
+    ),
+    EnvVarSpec::new(
+        "RCH_AUTO_START_COOLDOWN_SECS",
+        SelfHealing,
+        "self_healing.auto_start_cooldown_secs",
+        "Minimum seconds between hook auto-start attempts.",
+    ),
+    EnvVarSpec::new(
+        "RCH_AUTO_START_TIMEOUT_SECS",
+        SelfHealing,
+        "self_healing.auto_start_timeout_secs",
+        "How long the hook waits for an auto-started daemon.",
+    ),
+    EnvVarSpec::new(
+        "RCH_SELF_HEALING_LOG_LEVEL",
+        SelfHealing,
+        "info",
+        "Log level for self-healing events: debug, info, warn or error.",
+    ),
+    // Logging.
+    EnvVarSpec::new(
+        "RCH_LOG_LEVEL",
+        Logging,
+        "general.log_level (info)",
+        "Log level: trace, debug, info, warn, error or off.",
+    ),
+    EnvVarSpec::new(
+        "RCH_LOG_FORMAT",
+        Logging,
+        "pretty",
+        "Log format: pretty, json or compact.",
+    ),
+    EnvVarSpec::new(
+        "RCH_LOG_FILE",
+        Logging,
+        "unset (stderr only)",
+        "Also write logs to this daily-rotated file.",
+    ),
+    EnvVarSpec::new(
+        "RCH_LOG_MAX_FILES",
+        Logging,
+        "7",
+        "Rotated log files kept for RCH_LOG_FILE.",
+    ),
+    EnvVarSpec::new(
+        "RCH_LOG_TARGETS",
+        Logging,
+        "unset",
+        "Per-target levels, e.g. rch::hook=debug,rchd=warn.",
+    ),
+    EnvVarSpec::new(
+        "RCH_PROFILE",
+        Logging,
+        "unset",
+        "Apply dev, prod or test defaults for unset variables.",
+    ),
+    // Daemon.
+    EnvVarSpec::new(
+        "RCH_OTEL_ENABLED",
+        Daemon,
+        "false",
+        "Export daemon traces and metrics over OTLP.",
+    ),
+    EnvVarSpec::new(
+        "RCH_OTEL_EXPORTER_OTLP_ENDPOINT",
+        Daemon,
+        "OTEL_EXPORTER_OTLP_ENDPOINT",
+        "OTLP endpoint; wins over the standard OTel variable.",
+    ),
+    EnvVarSpec::new(
+        "RCH_OTEL_EXPORT_INTERVAL_SECS",
+        Daemon,
+        "30",
+        "OTLP metric export interval in seconds.",
+    ),
+    EnvVarSpec::new(
+        "RCH_WORKER_REAP_ENABLE",
+        Daemon,
+        "config",
+        "Truthy enables the daemon's stale target-dir sweep.",
+    ),
+    EnvVarSpec::new(
+        "RCH_WORKER_REAP_DISABLE",
+        Daemon,
+        "unset",
+        "Truthy disables the sweep; wins over RCH_WORKER_REAP_ENABLE.",
+    ),
+    EnvVarSpec::new(
+        "RCH_WORKER_REAP_INTERVAL_MINS",
+        Daemon,
+        "120",
+        "Minutes between sweeps (min 1).",
+    ),
+    // Fleet.
+    EnvVarSpec::new(
+        "RCH_FLEET_WAIT_SECS",
+        Fleet,
+        "0 (fail fast)",
+        "Seconds to wait for another fleet operation's lock.",
+    ),
+    EnvVarSpec::new(
+        "RCH_FLEET_PROVENANCE",
+        Fleet,
+        "dev-friendly",
+        "strict fails a deploy on missing or invalid signatures.",
+    ),
+    EnvVarSpec::new(
+        "RCH_LOAD_WAVES",
+        Fleet,
+        "3",
+        "Jobs per schedulable worker in `rch self-test --load`.",
+    ),
+    EnvVarSpec::new(
+        "RCH_LOAD_CONCURRENCY",
+        Fleet,
+        "2x workers, max 8",
+        "Concurrent canary jobs in `rch self-test --load`.",
+    ),
+    // repo_updater trust policy.
+    EnvVarSpec::new(
+        REPO_UPDATER_ALLOW_OVERRIDE_ENV,
+        RepoUpdater,
+        "unset",
+        "Allow an audited operator override of the trust policy.",
+    ),
+    EnvVarSpec::new(
+        REPO_UPDATER_ALLOWLIST_ENV,
+        RepoUpdater,
+        "config",
+        "Comma-separated repos repo convergence may touch.",
+    ),
+    EnvVarSpec::new(
+        REPO_UPDATER_ALLOWED_HOSTS_ENV,
+        RepoUpdater,
+        "config",
+        "Comma-separated hosts repo convergence may fetch from.",
+    ),
+    EnvVarSpec::new(
+        REPO_UPDATER_OVERRIDE_OPERATOR_ID_ENV,
+        RepoUpdater,
+        "unset",
+        "Override metadata: operator identifier.",
+    ),
+    EnvVarSpec::new(
+        REPO_UPDATER_OVERRIDE_JUSTIFICATION_ENV,
+        RepoUpdater,
+        "unset",
+        "Override metadata: justification.",
+    ),
+    EnvVarSpec::new(
+        REPO_UPDATER_OVERRIDE_TICKET_REF_ENV,
+        RepoUpdater,
+        "unset",
+        "Override metadata: ticket or change-request id.",
+    ),
+    EnvVarSpec::new(
+        REPO_UPDATER_OVERRIDE_AUDIT_EVENT_ID_ENV,
+        RepoUpdater,
+        "unset",
+        "Override metadata: audit event id.",
+    ),
+    EnvVarSpec::new(
+        REPO_UPDATER_OVERRIDE_APPROVED_AT_MS_ENV,
+        RepoUpdater,
+        "unset",
+        "Override metadata: approval time (unix ms).",
+    ),
+    EnvVarSpec::new(
+        REPO_UPDATER_AUTH_SOURCE_ENV,
+        RepoUpdater,
+        "unset",
+        "Credential source: gh_cli, token_env or ssh_agent.",
+    ),
+    EnvVarSpec::new(
+        REPO_UPDATER_AUTH_MODE_ENV,
+        RepoUpdater,
+        "inherit_environment",
+        "Required auth mode: inherit_environment, require_gh_auth or require_token_env.",
+    ),
+    EnvVarSpec::new(
+        REPO_UPDATER_AUTH_CREDENTIAL_ID_ENV,
+        RepoUpdater,
+        "unset",
+        "Credential identifier or fingerprint.",
+    )
+    .secret(),
+    EnvVarSpec::new(
+        REPO_UPDATER_AUTH_ISSUED_AT_MS_ENV,
+        RepoUpdater,
+        "unset",
+        "Credential issue time (unix ms).",
+    ),
+    EnvVarSpec::new(
+        REPO_UPDATER_AUTH_EXPIRES_AT_MS_ENV,
+        RepoUpdater,
+        "unset",
+        "Credential expiry time (unix ms).",
+    ),
+    EnvVarSpec::new(
+        REPO_UPDATER_AUTH_SCOPES_ENV,
+        RepoUpdater,
+        "unset",
+        "Comma-separated scopes the credential grants.",
+    ),
+    EnvVarSpec::new(
+        REPO_UPDATER_AUTH_REVOKED_ENV,
+        RepoUpdater,
+        "unset",
+        "Marks the credential as revoked.",
+    ),
+    EnvVarSpec::new(
+        REPO_UPDATER_AUTH_VERIFIED_HOSTS_ENV,
+        RepoUpdater,
+        "unset",
+        "Comma-separated host=fingerprint identities already verified.",
+    ),
+    EnvVarSpec::new(
+        REPO_UPDATER_REQUIRED_SCOPES_ENV,
+        RepoUpdater,
+        "unset",
+        "Comma-separated scopes the credential must grant.",
+    ),
+    EnvVarSpec::new(
+        REPO_UPDATER_ROTATION_MAX_AGE_SECS_ENV,
+        RepoUpdater,
+        "unset",
+        "Maximum credential age before rotation is required.",
+    ),
+    EnvVarSpec::new(
+        REPO_UPDATER_REQUIRE_HOST_IDENTITY_ENV,
+        RepoUpdater,
+        "unset",
+        "Require verified host identities before fetching.",
+    ),
+    EnvVarSpec::new(
+        REPO_UPDATER_TRUSTED_HOST_IDENTITIES_ENV,
+        RepoUpdater,
+        "unset",
+        "Comma-separated host=fingerprint identities to trust.",
+    ),
+    // Mock transports.
+    EnvVarSpec::new(
+        "RCH_MOCK_SSH",
+        Testing,
+        "unset",
+        "Use the in-process mock SSH and rsync transports.",
+    ),
+    EnvVarSpec::new(
+        "RCH_MOCK_SSH_EXIT_CODE",
+        Testing,
+        "0",
+        "Exit code of mocked remote commands.",
+    ),
+    EnvVarSpec::new(
+        "RCH_MOCK_SSH_STDOUT",
+        Testing,
+        "empty",
+        "Stdout of mocked remote commands.",
+    ),
+    EnvVarSpec::new(
+        "RCH_MOCK_SSH_STDERR",
+        Testing,
+        "empty",
+        "Stderr of mocked remote commands.",
+    ),
+    EnvVarSpec::new(
+        "RCH_MOCK_SSH_DELAY_MS",
+        Testing,
+        "0",
+        "Delay before a mocked command returns.",
+    ),
+    EnvVarSpec::new(
+        "RCH_MOCK_SSH_FAIL_CONNECT",
+        Testing,
+        "unset",
+        "Make mock SSH connections fail.",
+    ),
+    EnvVarSpec::new(
+        "RCH_MOCK_SSH_FAIL_CONNECT_ATTEMPTS",
+        Testing,
+        "0",
+        "Fail only the first N mock connections.",
+    ),
+    EnvVarSpec::new(
+        "RCH_MOCK_SSH_FAIL_EXECUTE",
+        Testing,
+        "unset",
+        "Make mocked remote commands fail to execute.",
+    ),
+    EnvVarSpec::new(
+        "RCH_MOCK_SSH_FAIL_EXECUTE_ATTEMPTS",
+        Testing,
+        "0",
+        "Fail only the first N mocked executions.",
+    ),
+    EnvVarSpec::new(
+        "RCH_MOCK_TOOLCHAIN_INSTALL_FAIL",
+        Testing,
+        "unset",
+        "Make mocked toolchain installs fail.",
+    ),
+    EnvVarSpec::new(
+        "RCH_MOCK_NO_RUSTUP",
+        Testing,
+        "unset",
+        "Pretend the mock worker has no rustup.",
+    ),
+    EnvVarSpec::new(
+        "RCH_MOCK_RSYNC_FAIL_SYNC",
+        Testing,
+        "unset",
+        "Make mock project sync fail.",
+    ),
+    EnvVarSpec::new(
+        "RCH_MOCK_RSYNC_FAIL_SYNC_ATTEMPTS",
+        Testing,
+        "0",
+        "Fail only the first N mock syncs.",
+    ),
+    EnvVarSpec::new(
+        "RCH_MOCK_RSYNC_FAIL_ARTIFACTS",
+        Testing,
+        "unset",
+        "Make mock artifact retrieval fail.",
+    ),
+    EnvVarSpec::new(
+        "RCH_MOCK_RSYNC_FAIL_ARTIFACTS_ATTEMPTS",
+        Testing,
+        "0",
+        "Fail only the first N mock artifact retrievals.",
+    ),
+    EnvVarSpec::new(
+        "RCH_MOCK_RSYNC_FILES",
+        Testing,
+        "mock default",
+        "Files reported per mock transfer.",
+    ),
+    EnvVarSpec::new(
+        "RCH_MOCK_RSYNC_BYTES",
+        Testing,
+        "mock default",
+        "Bytes reported per mock transfer.",
+    ),
+    EnvVarSpec::new(
+        "RCH_MOCK_CIRCUIT_OPEN",
+        Testing,
+        "unset",
+        "Make worker selection report every circuit open.",
+    ),
+];
+
+/// Every recognized environment variable, grouped by category.
+///
+/// Adding an env read anywhere in the local binaries means adding it here;
+/// `rch env` enumerates this slice.
+#[must_use]
+pub fn env_vars() -> &'static [EnvVarSpec] {
+    ENV_VARS
+}
+
+/// Look up a registered variable by name.
+#[must_use]
+pub fn lookup_env_var(name: &str) -> Option<&'static EnvVarSpec> {
+    ENV_VARS.iter().find(|spec| spec.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::placement_controls;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn registry_has_no_duplicates() {
+        let names: BTreeSet<_> = env_vars().iter().map(|spec| spec.name).collect();
+        assert_eq!(names.len(), env_vars().len());
+    }
+
+    #[test]
+    fn registry_names_are_well_formed() {
+        for spec in env_vars() {
+            assert!(spec.name.starts_with("RCH_"), "{}", spec.name);
+            assert!(
+                spec.name
+                    .bytes()
+                    .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || b == b'_'),
+                "{}",
+                spec.name
+            );
+            assert!(!spec.description.is_empty(), "{}", spec.name);
+            assert!(!spec.default.is_empty(), "{}", spec.name);
+        }
+    }
+
+    #[test]
+    fn registry_covers_placement_controls() {
+        for control in placement_controls() {
+            assert!(
+                lookup_env_var(control.canonical_env).is_some(),
+                "{} missing from env registry",
+                control.canonical_env
+            );
+            for alias in control.aliases {
+                assert!(
+                    lookup_env_var(alias).is_some(),
+                    "{alias} missing from env registry"
+                );
+            }
+        }
+    }
+
+    /// `RCH_*` names bound to a `*_ENV` constant in the binaries' sources.
+    fn env_constants_in(dir: &std::path::Path, found: &mut Vec<(String, String)>) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                env_constants_in(&path, found);
+                continue;
+            }
+            if path.extension().is_none_or(|ext| ext != "rs") {
+                continue;
+            }
+            let source = std::fs::read_to_string(&path).unwrap();
+            for line in source.lines() {
+                let Some((decl, value)) = line.split_once("_ENV: &str = \"") else {
+                    continue;
+                };
+                if !decl.contains("const ") {
+                    continue;
+                }
+                if let Some(name) = value.strip_suffix("\";")
+                    && name.starts_with("RCH_")
+                {
+                    found.push((name.to_string(), path.display().to_string()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn registry_covers_env_constants_in_binaries() {
+        let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let mut found = Vec::new();
+        for krate in ["rch", "rchd", "rch-wkr"] {
+            env_constants_in(&root.join(krate).join("src"), &mut found);
+        }
+        assert!(!found.is_empty());
+        for (name, path) in found {
+            assert!(
+                lookup_env_var(&name).is_some(),
+                "{name} (declared in {path}) missing from env registry"
+            );
+        }
+    }
+
+    #[test]
+    fn secret_values_are_redacted() {
+        let spec = lookup_env_var(REPO_UPDATER_AUTH_CREDENTIAL_ID_ENV).unwrap();
+        assert!(spec.secret);
+        assert_eq!(spec.display_value("ghp_abc"), REDACTED_ENV_VALUE);
+
+        let spec = lookup_env_var("RCH_FLEET").unwrap();
+        assert_eq!(spec.display_value("gpu"), "gpu");
+        assert!(lookup_env_var("RCH_NOT_A_REAL_VAR").is_none());
+    }
+}
@@ -260,6 +260,8 @@ pub enum ErrorCode {
     DependencyPreflightPolicyViolation,
     /// Dependency preflight planning timed out
     DependencyPreflightTimeout,
+    /// Project exceeds the `transfer.max_project_bytes` ceiling
+    TransferProjectTooLarge,
 
     // =========================================================================
     // Internal Errors (E500-E599)
@@ -399,6 +401,7 @@ impl ErrorCode {
             Self::DependencyPreflightUnknown => 412,
             Self::DependencyPreflightPolicyViolation => 413,
             Self::DependencyPreflightTimeout => 414,
+            Self::TransferProjectTooLarge => 415,
 
             // Internal (500-599)
             Self::InternalDaemonSocket => 500,
@@ -590,6 +593,7 @@ impl ErrorCode {
                 "Remote dependency preflight blocked a topology policy violation"
             }
             Self::DependencyPreflightTimeout => "Remote dependency preflight planning timed out",
+            Self::TransferProjectTooLarge => "Project exceeds the maximum project size",
 
             // Internal
             Self::InternalDaemonSocket => "Failed to connect to daemon socket",
@@ -1075,6 +1079,11 @@ impl ErrorCode {
                 "Inspect cargo metadata latency for the workspace",
                 "Increase planner timeout only after ruling out dependency graph issues",
             ],
+            Self::TransferProjectTooLarge => &[
+                "Exclude large directories via transfer.exclude_patterns (e.g. \"data/\")",
+                "Raise or remove transfer.max_project_bytes if the project really is this large",
+                "Keep the project local with general.force_local = true in .rch/config.toml",
+            ],
 
             // Internal
             Self::InternalDaemonSocket => &[
@@ -1294,6 +1303,7 @@ impl ErrorCode {
             Self::DependencyPreflightUnknown,
             Self::DependencyPreflightPolicyViolation,
             Self::DependencyPreflightTimeout,
+            Self::TransferProjectTooLarge,
             // Internal
             Self::InternalDaemonSocket,
             Self::InternalDaemonProtocol,
@@ -1775,10 +1785,10 @@ mod tests {
     fn test_total_error_code_count() {
         let total = ErrorCode::all().len();
         // 10 config + 12 path-dep/closure + 10 network + 10 worker + 8 storage
-        // + 10 build + 8 process-triage + 6 cancellation + 15 transfer + 10 internal = 99
+        // + 10 build + 8 process-triage + 6 cancellation + 16 transfer + 10 internal = 100
         assert!(
-            total >= 99,
-            "Expected at least 99 error codes (was {}); did a code get accidentally removed?",
+            total >= 100,
+            "Expected at least 100 error codes (was {}); did a code get accidentally removed?",
             total,
         );
     }
@@ -21,13 +21,18 @@ pub mod bypass_recovery;
 pub mod capability_probe;
 pub mod cargo_path_deps;
 pub mod classifier_drift;
+pub mod classifier_rules;
 pub mod closure_explain;
+pub mod command_outcome;
 pub mod config;
+pub mod decision_trace;
 pub mod dependency_closure_planner;
+pub mod detached_build;
 pub mod discovery;
 pub mod disk_pressure_report;
 pub mod disk_reclaim;
 pub mod e2e;
+pub mod env_registry;
 pub mod errors;
 pub mod exec_misuse;
 pub mod exec_policy;
@@ -66,6 +71,7 @@ pub mod remediation_config;
 pub mod remediation_view;
 #[cfg(unix)]
 pub mod remote_compilation;
+pub mod remote_keep;
 #[cfg(unix)]
 pub mod remote_verification;
 pub mod repo_updater_contract;
@@ -171,6 +177,10 @@ pub use closure_explain::{
     ClosureExplainEntry, ClosureExplainReport, RootConvergence, RootSyncOutcome, derive_outcome,
     explain_closure,
 };
+pub use command_outcome::{CommandOutcome, classify_exit};
+pub use decision_trace::{
+    DecisionStage, DecisionTrace, DecisionTraceStep, MAX_DECISION_TRACE_STEPS,
+};
 pub use dependency_closure_planner::{
     DependencyClosurePlan, DependencyClosurePlanState, DependencyPlanIssue, DependencyRiskClass,
     DependencySyncAction, DependencySyncMetadata, DependencySyncReason,
@@ -184,14 +194,16 @@ pub use force_resync::{
 pub use logging::{LogConfig, LogFormat, LoggingGuards, init_logging};
 pub use mock_worker::MockWorkerServer;
 pub use path_topology::{
-    DEFAULT_ALIAS_PROJECT_ROOT, DEFAULT_CANONICAL_PROJECT_ROOT, NormalizationDecision,
-    NormalizedProjectPath, PathNormalizationError, PathNormalizationErrorKind, PathTopologyPolicy,
-    normalize_project_path, normalize_project_path_with_policy,
+    DEFAULT_ALIAS_PROJECT_ROOT, DEFAULT_CANONICAL_PROJECT_ROOT, DEFAULT_WSL_MOUNT_PREFIX,
+    NormalizationDecision, NormalizedProjectPath, PathNormalizationError,
+    PathNormalizationErrorKind, PathTopologyPolicy, logical_current_dir, logical_dir_for,
+    normalize_project_path, normalize_project_path_with_policy, translate_windows_command_args,
+    translate_windows_path,
 };
 pub use patterns::{
     Classification, ClassificationDetails, ClassificationTier, CompilationKind, TierDecision,
     classify_command, classify_command_detailed, declined_compilation_due_to_structure,
-    split_shell_commands,
+    is_optimized_build, split_shell_commands,
 };
 pub use protocol::{HookInput, HookOutput, ToolInput};
 pub use queue_contract::{
@@ -217,30 +229,36 @@ pub use repo_updater_contract::{
 // Platform-independent SSH utilities (available everywhere)
 pub use ssh_utils::{
     CommandResult, EnvPrefix, build_env_prefix, is_retryable_transport_error,
-    is_retryable_transport_error_text, shell_escape_value,
+    is_retryable_transport_error_text, shell_escape_value, ssh_agent_available,
 };
 // Unix-only SSH client (uses openssh crate)
 #[cfg(unix)]
 pub use ssh::{KnownHostsPolicy, SshClient, SshOptions, SshPool};
 pub use test_change::{TestChangeGuard, TestCodeChange};
-pub use toolchain::{ToolchainInfo, wrap_command_with_color, wrap_command_with_toolchain};
+pub use toolchain::{
+    RustcMatch, ToolchainInfo, wrap_command_with_color, wrap_command_with_toolchain,
+};
 pub use transfer_hardening::{
     FirstFailure, RetryDecision, RsyncFailureClass, classify_rsync_outcome, is_ephemeral_path,
 };
 pub use types::{
     AffinityConfig, BuildCancellationMetadata, BuildCancellationWorkerHealth, BuildHeartbeatPhase,
-    BuildHeartbeatRequest, BuildLocation, BuildRecord, BuildStats, CircuitBreakerConfig,
-    CircuitState, CircuitStats, ColorMode, CommandPriority, CommandTimingBreakdown,
-    CompilationConfig, CompilationMetrics, CompilationTimer, CompilationTimingBreakdown,
-    DoctorConfig, DoctorWebhookEndpoint, DoctorWebhookFormat, DoctorWebhooksConfig,
-    EnvironmentConfig, ExecutionConfig, FairnessConfig, FleetConfig, GeneralConfig,
-    MetricsAggregator, OutputConfig, OutputVisibility, PathTopologyConfig, RchConfig,
-    ReleaseRequest, RequiredRuntime, RetryConfig, SELECTION_RESPONSE_PROTOCOL_VERSION,
-    SavedTimeStats, SelectedWorker, SelectionConfig, SelectionDiagnostics, SelectionReason,
-    SelectionRequest, SelectionResponse, SelectionStrategy, SelectionWeightConfig,
-    SelfHealingConfig, SelfHealingLogLevel, SelfTestConfig, SelfTestFailureAction, SelfTestWorkers,
-    TransferConfig, WorkerCapabilities, WorkerConfig, WorkerId, WorkerSelectionDiagnostic,
-    WorkerSelectionDiagnosticDecision, WorkerStatus, default_socket_path, validate_remote_base,
+    BuildHeartbeatRequest, BuildHistoryGroup, BuildLocation, BuildRecord, BuildStats,
+    BuildTailEvent, CircuitBreakerConfig, CircuitState, CircuitStats, ColorMode, CommandPriority,
+    CommandTimingBreakdown, CompilationConfig, CompilationMetrics, CompilationTimer,
+    CompilationTimingBreakdown, ConfidenceOverride, DoctorConfig, DoctorWebhookEndpoint,
+    DoctorWebhookFormat, DoctorWebhooksConfig, EnvironmentConfig, ExecutionConfig,
+    FLEET_TAG_PREFIX, FairnessConfig, FleetConfig, GeneralConfig, HealthFailureReason,
+    IONICE_CLASS_RANGE, IncrementalReuseStats, LoggingConfig, MIN_HEARTBEAT_TIMEOUT_SECS,
+    MetricsAggregator, NICE_LEVEL_RANGE, NotificationsConfig, OutputConfig, OutputVisibility,
+    PathTopologyConfig, QueueFairness, RchConfig, ReleaseRequest, ReleaseWorkerBody,
+    RequiredRuntime, RetryConfig, SELECTION_RESPONSE_PROTOCOL_VERSION, SavedTimeStats,
+    SelectedWorker, SelectionConfig, SelectionDiagnostics, SelectionReason, SelectionRequest,
+    SelectionResponse, SelectionStrategy, SelectionWeightConfig, SelfHealingConfig,
+    SelfHealingLogLevel, SelfTestConfig, SelfTestFailureAction, SelfTestWorkers, TelemetryConfig,
+    TransferBackend, TransferConfig, WorkerCapabilities, WorkerConfig, WorkerId,
+    WorkerSelectionDiagnostic, WorkerSelectionDiagnosticDecision, WorkerStatus,
+    default_socket_path, validate_container_image, validate_remote_base, worker_tool_install_hint,
 };
 
 // Testing module re-exports
@@ -249,7 +267,7 @@ pub use testing::{TestLogEntry, TestLogger, TestPhase, TestResult};
 // Config module re-exports
 pub use config::{
     ConfigSource, ConfigValueSource, ConfigWarning, EnvError, EnvParser, Profile, Severity,
-    Sourced, validate_config,
+    Sourced, tracked_artifact_warnings, validate_config,
 };
 
 // Discovery module re-exports
@@ -271,6 +289,7 @@ pub use capability_probe::{
     WorkerLiveness, assess_admissibility, assess_worker_eligibility, build_capability_probe_script,
     parse_capability_probe, remote_worker_binary_path,
 };
+pub use env_registry::{EnvVarCategory, EnvVarSpec, REDACTED_ENV_VALUE, env_vars, lookup_env_var};
 pub use incident::{
     ControlState, IncidentEvent, IncidentEventType, IncidentReasonCode, IncidentSource,
     SelectedMode, incident_schema_version,
@@ -21,6 +21,12 @@
 //! [`assess_log_pressure`] folds the directory's managed total into a
 //! [`PressureLevel`] for the doctor surface, with [`log_remediation_guidance`]
 //! producing norm-respecting advice.
+//!
+//! The stdout/stderr log of a detached `rchd` (`rchd.log`, see
+//! [`DAEMON_STDIO_LOG_ENV`]) is bounded separately by
+//! [`rotate_stdio_log_if_oversized`]: the daemon keeps writing to it through
+//! an inherited file descriptor, so it is copied aside and truncated in place
+//! rather than renamed.
 
 use std::fs;
 use std::path::Path;
@@ -268,6 +274,41 @@ pub fn rotate_logs(dir: &Path, policy: &LogRetentionPolicy) -> std::io::Result<L
     Ok(receipt)
 }
 
+/// Size at which a detached daemon's stdout/stderr log is rotated.
+pub const DAEMON_STDIO_LOG_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Set by whoever spawns a detached `rchd` to the log file its stdout and
+/// stderr append to, so the daemon can keep that file bounded while it runs.
+pub const DAEMON_STDIO_LOG_ENV: &str = "RCH_DAEMON_STDIO_LOG";
+
+/// The single previous generation of a stdio log: `<log>.1`.
+#[must_use]
+pub fn rotated_stdio_log_path(log_path: &Path) -> std::path::PathBuf {
+    let mut name = log_path.as_os_str().to_os_string();
+    name.push(".1");
+    std::path::PathBuf::from(name)
+}
+
+/// Once `log_path` has reached `max_bytes`, copy it to
+/// [`rotated_stdio_log_path`] (replacing the previous generation) and
+/// truncate it to zero. Writers holding the file open in append mode carry on
+/// at the new end, so this is safe while the daemon is running; lines written
+/// between the copy and the truncate are lost. Returns whether it rotated.
+pub fn rotate_stdio_log_if_oversized(log_path: &Path, max_bytes: u64) -> std::io::Result<bool> {
+    let Ok(meta) = fs::metadata(log_path) else {
+        return Ok(false);
+    };
+    if meta.len() < max_bytes {
+        return Ok(false);
+    }
+    fs::copy(log_path, rotated_stdio_log_path(log_path))?;
+    fs::OpenOptions::new()
+        .write(true)
+        .open(log_path)?
+        .set_len(0)?;
+    Ok(true)
+}
+
 /// Fold a managed-log total into a doctor pressure level.
 #[must_use]
 pub fn assess_log_pressure(managed_total_bytes: u64, policy: &LogRetentionPolicy) -> PressureLevel {
@@ -510,4 +551,30 @@ mod tests {
         let back: LogRotationPlan = serde_json::from_value(value).unwrap();
         assert_eq!(back, plan);
     }
+
+    #[test]
+    fn stdio_log_rotates_in_place_for_open_writers() {
+        use std::io::Write;
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("rchd.log");
+        let mut writer = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log)
+            .unwrap();
+        writer.write_all(b"0123456789").unwrap();
+
+        assert!(!rotate_stdio_log_if_oversized(&log, 11).unwrap());
+        assert!(rotate_stdio_log_if_oversized(&log, 10).unwrap());
+        assert_eq!(
+            fs::read_to_string(rotated_stdio_log_path(&log)).unwrap(),
+            "0123456789"
+        );
+        assert_eq!(fs::metadata(&log).unwrap().len(), 0);
+
+        // The still-open writer continues at the start of the emptied file.
+        writer.write_all(b"after").unwrap();
+        assert_eq!(fs::read_to_string(&log).unwrap(), "after");
+        assert!(!rotate_stdio_log_if_oversized(&dir.path().join("missing.log"), 1).unwrap());
+    }
 }
@@ -942,6 +942,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let mut client = MockSshClient::new_default(worker_config);
@@ -964,6 +967,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let mut client = MockSshClient::new(
@@ -992,6 +998,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let mut client = MockSshClient::new(worker_config, MockConfig::connection_failure());
@@ -1060,6 +1069,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let mut client = MockSshClient::new(worker_config, MockConfig::no_rustup());
@@ -1081,6 +1093,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let mut client = MockSshClient::new(worker_config, MockConfig::toolchain_install_failure());
@@ -1113,6 +1128,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let mut client = MockSshClient::new(worker_config, MockConfig::toolchain_install_failure());
@@ -1142,6 +1160,9 @@ mod tests {
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         assert!(is_mock_worker(&mock_worker));
 
@@ -1153,6 +1174,9 @@ mod tests {
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         assert!(!is_mock_worker(&real_worker));
     }
@@ -1379,6 +1403,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let client = MockSshClient::new_default(worker_config);
@@ -1400,6 +1427,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let mut client = MockSshClient::new(
@@ -1434,6 +1464,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let mut client = MockSshClient::new_default(worker_config);
@@ -1453,6 +1486,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let mut client = MockSshClient::new(
@@ -1475,6 +1511,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let client = MockSshClient::new_default(worker_config);
@@ -1491,6 +1530,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let client = MockSshClient::new_default(worker_config);
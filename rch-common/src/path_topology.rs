@@ -4,6 +4,12 @@
 //! equivalent aliases (for example `/dp` and `/data/projects`) map to one
 //! deterministic identity. It also emits structured decision traces to aid
 //! troubleshooting when path normalization fails.
+//!
+//! On a Windows host, drive paths (`C:\Users\me\proj`) are first translated
+//! to the WSL mount form (`/mnt/c/Users/me/proj`) that WSL workers see, then
+//! checked lexically against the canonical root. Anything that is not a plain
+//! drive path (UNC shares, drive-relative `C:proj`, `..` segments) is left
+//! untranslated and fails normalization, so the hook falls back to local.
 
 use std::fmt;
 use std::path::{Path, PathBuf};
@@ -14,11 +20,16 @@ pub const DEFAULT_CANONICAL_PROJECT_ROOT: &str = "/data/projects";
 /// Alias root expected to point at [`DEFAULT_CANONICAL_PROJECT_ROOT`].
 pub const DEFAULT_ALIAS_PROJECT_ROOT: &str = "/dp";
 
+/// Directory under which WSL mounts Windows drives (`/mnt/c`, `/mnt/d`, ...).
+pub const DEFAULT_WSL_MOUNT_PREFIX: &str = "/mnt";
+
 /// Policy describing canonical and alias roots used for normalization.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PathTopologyPolicy {
     canonical_root: PathBuf,
     alias_root: PathBuf,
+    wsl_mount_prefix: PathBuf,
+    keep_logical_symlinks: bool,
 }
 
 impl PathTopologyPolicy {
@@ -27,9 +38,26 @@ impl PathTopologyPolicy {
         Self {
             canonical_root,
             alias_root,
+            wsl_mount_prefix: PathBuf::from(DEFAULT_WSL_MOUNT_PREFIX),
+            keep_logical_symlinks: false,
         }
     }
 
+    /// Override the WSL drive mount prefix used to translate Windows paths.
+    pub fn with_wsl_mount_prefix(mut self, prefix: PathBuf) -> Self {
+        self.wsl_mount_prefix = prefix;
+        self
+    }
+
+    /// Keep the logical path when a project under the canonical root is a
+    /// symlink whose target lies outside it. The logical path is then used
+    /// for identity and sync instead of failing with
+    /// [`PathNormalizationErrorKind::OutsideCanonicalRoot`].
+    pub fn with_keep_logical_symlinks(mut self, keep: bool) -> Self {
+        self.keep_logical_symlinks = keep;
+        self
+    }
+
     /// Canonical root path.
     pub fn canonical_root(&self) -> &Path {
         &self.canonical_root
@@ -39,14 +67,25 @@ impl PathTopologyPolicy {
     pub fn alias_root(&self) -> &Path {
         &self.alias_root
     }
+
+    /// WSL drive mount prefix (default `/mnt`).
+    pub fn wsl_mount_prefix(&self) -> &Path {
+        &self.wsl_mount_prefix
+    }
+
+    /// Whether symlinked projects resolving outside the root keep their
+    /// logical path (default `false`).
+    pub fn keep_logical_symlinks(&self) -> bool {
+        self.keep_logical_symlinks
+    }
 }
 
 impl Default for PathTopologyPolicy {
     fn default() -> Self {
-        Self {
-            canonical_root: PathBuf::from(DEFAULT_CANONICAL_PROJECT_ROOT),
-            alias_root: PathBuf::from(DEFAULT_ALIAS_PROJECT_ROOT),
-        }
+        Self::new(
+            PathBuf::from(DEFAULT_CANONICAL_PROJECT_ROOT),
+            PathBuf::from(DEFAULT_ALIAS_PROJECT_ROOT),
+        )
     }
 }
 
@@ -69,8 +108,16 @@ pub enum NormalizationDecision {
         alias_root: PathBuf,
         canonical_input: PathBuf,
     },
+    WindowsPathTranslated {
+        windows_path: PathBuf,
+        wsl_path: PathBuf,
+    },
     CanonicalRootResolved(PathBuf),
     CanonicalInputResolved(PathBuf),
+    LogicalPathKept {
+        logical_path: PathBuf,
+        resolved_path: PathBuf,
+    },
     VerifiedWithinCanonicalRoot {
         canonical_path: PathBuf,
         canonical_root: PathBuf,
@@ -116,12 +163,30 @@ impl fmt::Display for NormalizationDecision {
                 alias_root.display(),
                 canonical_input.display()
             ),
+            Self::WindowsPathTranslated {
+                windows_path,
+                wsl_path,
+            } => write!(
+                f,
+                "windows_path_translated={} -> {}",
+                windows_path.display(),
+                wsl_path.display()
+            ),
             Self::CanonicalRootResolved(path) => {
                 write!(f, "canonical_root_resolved={}", path.display())
             }
             Self::CanonicalInputResolved(path) => {
                 write!(f, "canonical_input_resolved={}", path.display())
             }
+            Self::LogicalPathKept {
+                logical_path,
+                resolved_path,
+            } => write!(
+                f,
+                "logical_path_kept={} resolved={}",
+                logical_path.display(),
+                resolved_path.display()
+            ),
             Self::VerifiedWithinCanonicalRoot {
                 canonical_path,
                 canonical_root,
@@ -139,17 +204,33 @@ impl fmt::Display for NormalizationDecision {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NormalizedProjectPath {
     canonical_path: PathBuf,
+    resolved_path: PathBuf,
     canonical_root: PathBuf,
     used_alias_prefix: bool,
+    kept_logical_path: bool,
     decisions: Vec<NormalizationDecision>,
 }
 
 impl NormalizedProjectPath {
-    /// Canonical path for this project root.
+    /// Canonical path for this project root, used for identity and sync.
+    ///
+    /// This is the logical path when [`Self::kept_logical_path`] is set.
     pub fn canonical_path(&self) -> &Path {
         &self.canonical_path
     }
 
+    /// Fully resolved (realpath) location of the project. Equal to
+    /// [`Self::canonical_path`] unless the logical path was kept.
+    pub fn resolved_path(&self) -> &Path {
+        &self.resolved_path
+    }
+
+    /// Whether the logical path was kept because the project is a symlink
+    /// resolving outside the canonical root.
+    pub fn kept_logical_path(&self) -> bool {
+        self.kept_logical_path
+    }
+
     /// Canonical project root used for containment checks.
     pub fn canonical_root(&self) -> &Path {
         &self.canonical_root
@@ -252,6 +333,31 @@ impl fmt::Display for PathNormalizationError {
 
 impl std::error::Error for PathNormalizationError {}
 
+/// The working directory as the invoking shell sees it.
+///
+/// `std::env::current_dir()` is always fully resolved by the kernel, so a
+/// project entered through a symlink loses its logical path before
+/// [`PathTopologyPolicy::keep_logical_symlinks`] can see it. `$PWD` is used
+/// instead when it is absolute and names the same directory; a stale or
+/// unrelated `$PWD` falls back to `current_dir()`.
+pub fn logical_current_dir() -> std::io::Result<PathBuf> {
+    let pwd = std::env::var_os("PWD").map(PathBuf::from);
+    logical_dir_for(pwd.as_deref())
+}
+
+/// [`logical_current_dir`] with an explicit `$PWD` value.
+pub fn logical_dir_for(pwd: Option<&Path>) -> std::io::Result<PathBuf> {
+    let cwd = std::env::current_dir()?;
+    let same_dir = |pwd: &Path| {
+        let resolved = std::fs::canonicalize(pwd).ok();
+        resolved.is_some() && resolved == std::fs::canonicalize(&cwd).ok()
+    };
+    Ok(match pwd {
+        Some(pwd) if pwd.is_absolute() && same_dir(pwd) => pwd.to_path_buf(),
+        _ => cwd,
+    })
+}
+
 /// Normalize a project path using the default `/data/projects` + `/dp` policy.
 pub fn normalize_project_path(
     path: &Path,
@@ -260,12 +366,32 @@ pub fn normalize_project_path(
 }
 
 /// Normalize a project path with explicit topology policy.
+///
+/// On a Windows host, drive paths are translated to their WSL form first (see
+/// [`translate_windows_path`]).
 pub fn normalize_project_path_with_policy(
     path: &Path,
     policy: &PathTopologyPolicy,
+) -> Result<NormalizedProjectPath, PathNormalizationError> {
+    normalize_project_path_for_host(path, policy, cfg!(windows))
+}
+
+fn normalize_project_path_for_host(
+    path: &Path,
+    policy: &PathTopologyPolicy,
+    windows_host: bool,
 ) -> Result<NormalizedProjectPath, PathNormalizationError> {
     let mut decisions = vec![NormalizationDecision::ReceivedInput(path.to_path_buf())];
 
+    if windows_host && let Some(wsl_path) = translate_windows_path(path, policy.wsl_mount_prefix())
+    {
+        decisions.push(NormalizationDecision::WindowsPathTranslated {
+            windows_path: path.to_path_buf(),
+            wsl_path: wsl_path.clone(),
+        });
+        return normalize_translated_windows_path(path, wsl_path, policy, decisions);
+    }
+
     if !path.is_absolute() {
         return Err(PathNormalizationError::new(
             PathNormalizationErrorKind::NotAbsoluteInput,
@@ -306,27 +432,178 @@ pub fn normalize_project_path_with_policy(
         canonical_input.clone(),
     ));
 
+    let mut canonical_path = canonical_input.clone();
+    let mut kept_logical_path = false;
     if !canonical_input.starts_with(&canonical_root) {
+        let logical_path = if policy.keep_logical_symlinks() && !used_alias_prefix {
+            logical_path_within_root(path, policy, &canonical_root)
+        } else {
+            None
+        };
+        let Some(logical_path) = logical_path else {
+            return Err(PathNormalizationError::new(
+                PathNormalizationErrorKind::OutsideCanonicalRoot,
+                path,
+                format!(
+                    "resolved={} root={}",
+                    canonical_input.display(),
+                    canonical_root.display()
+                ),
+                &decisions,
+            ));
+        };
+        decisions.push(NormalizationDecision::LogicalPathKept {
+            logical_path: logical_path.clone(),
+            resolved_path: canonical_input.clone(),
+        });
+        canonical_path = logical_path;
+        kept_logical_path = true;
+    }
+    decisions.push(NormalizationDecision::VerifiedWithinCanonicalRoot {
+        canonical_path: canonical_path.clone(),
+        canonical_root: canonical_root.clone(),
+    });
+
+    Ok(NormalizedProjectPath {
+        canonical_path,
+        resolved_path: canonical_input,
+        canonical_root,
+        used_alias_prefix,
+        kept_logical_path,
+        decisions,
+    })
+}
+
+/// Lexically place `path` under the resolved canonical root, without
+/// following symlinks. Returns `None` for `..` segments or paths outside both
+/// the configured and resolved root, so the logical path can never escape it.
+fn logical_path_within_root(
+    path: &Path,
+    policy: &PathTopologyPolicy,
+    canonical_root: &Path,
+) -> Option<PathBuf> {
+    if path
+        .components()
+        .any(|c| matches!(c, std::path::Component::ParentDir))
+    {
+        return None;
+    }
+    let logical: PathBuf = path.components().collect();
+    if logical.starts_with(canonical_root) {
+        return Some(logical);
+    }
+    let relative = logical.strip_prefix(policy.canonical_root()).ok()?;
+    Some(canonical_root.join(relative))
+}
+
+/// Translate a Windows drive path to the path WSL mounts it at, e.g.
+/// `C:\Users\me\proj` -> `/mnt/c/Users/me/proj` with the default prefix.
+///
+/// Accepts `\` or `/` separators and the `\\?\` verbatim prefix that
+/// `std::fs::canonicalize` produces on Windows. Returns `None` for anything
+/// ambiguous: UNC shares, drive-relative paths (`C:proj`), `..` segments, or
+/// a mount prefix that is not an absolute POSIX path. The result is built
+/// with `/` separators regardless of the host.
+pub fn translate_windows_path(path: &Path, mount_prefix: &Path) -> Option<PathBuf> {
+    let raw = path.to_str()?;
+    let raw = raw.strip_prefix(r"\\?\").unwrap_or(raw);
+    let drive = raw.chars().next().filter(char::is_ascii_alphabetic)?;
+    let rest = raw[1..].strip_prefix(':')?;
+    if !rest.is_empty() && !rest.starts_with(['\\', '/']) {
+        return None;
+    }
+
+    let prefix = mount_prefix.to_str()?;
+    if !prefix.starts_with('/') || prefix.contains('\\') {
+        return None;
+    }
+    let prefix = prefix.trim_end_matches('/');
+    let mut translated = format!("{}/{}", prefix, drive.to_ascii_lowercase());
+    for segment in rest.split(['\\', '/']) {
+        match segment {
+            "" | "." => {}
+            ".." => return None,
+            segment => {
+                translated.push('/');
+                translated.push_str(segment);
+            }
+        }
+    }
+    Some(PathBuf::from(translated))
+}
+
+/// Translate the Windows drive paths in a command's arguments to their WSL
+/// form, e.g. `--manifest-path C:\src\proj\Cargo.toml` ->
+/// `--manifest-path /mnt/c/src/proj/Cargo.toml`.
+///
+/// Whole-argument paths and `--flag=<path>` values are translated with
+/// [`translate_windows_path`]; a bare drive (`C:`) and anything it rejects are
+/// passed through unchanged.
+pub fn translate_windows_command_args(args: &[String], mount_prefix: &Path) -> Vec<String> {
+    let translate = |value: &str| {
+        if value.len() <= 2 {
+            return None;
+        }
+        translate_windows_path(Path::new(value), mount_prefix)
+            .and_then(|path| path.to_str().map(str::to_string))
+    };
+    args.iter()
+        .map(|arg| {
+            if let Some(path) = translate(arg) {
+                return path;
+            }
+            if let Some((flag, value)) = arg.split_once('=')
+                && flag.starts_with('-')
+                && let Some(path) = translate(value)
+            {
+                return format!("{flag}={path}");
+            }
+            arg.clone()
+        })
+        .collect()
+}
+
+/// Finish normalization for a translated Windows path. The WSL path does not
+/// exist on the Windows host, so containment is checked lexically; the
+/// canonical root may itself be configured in either Windows or WSL form.
+fn normalize_translated_windows_path(
+    input_path: &Path,
+    wsl_path: PathBuf,
+    policy: &PathTopologyPolicy,
+    mut decisions: Vec<NormalizationDecision>,
+) -> Result<NormalizedProjectPath, PathNormalizationError> {
+    let canonical_root = translate_windows_path(policy.canonical_root(), policy.wsl_mount_prefix())
+        .unwrap_or_else(|| policy.canonical_root().to_path_buf());
+    decisions.push(NormalizationDecision::CanonicalRootResolved(
+        canonical_root.clone(),
+    ));
+    decisions.push(NormalizationDecision::CanonicalInputResolved(
+        wsl_path.clone(),
+    ));
+
+    if !wsl_path.starts_with(&canonical_root) {
         return Err(PathNormalizationError::new(
             PathNormalizationErrorKind::OutsideCanonicalRoot,
-            path,
+            input_path,
             format!(
                 "resolved={} root={}",
-                canonical_input.display(),
+                wsl_path.display(),
                 canonical_root.display()
             ),
             &decisions,
         ));
     }
     decisions.push(NormalizationDecision::VerifiedWithinCanonicalRoot {
-        canonical_path: canonical_input.clone(),
+        canonical_path: wsl_path.clone(),
         canonical_root: canonical_root.clone(),
     });
 
     Ok(NormalizedProjectPath {
-        canonical_path: canonical_input,
+        canonical_path: wsl_path.clone(),
+        resolved_path: wsl_path,
         canonical_root,
-        used_alias_prefix,
+        used_alias_prefix: false,
+        kept_logical_path: false,
         decisions,
     })
 }
@@ -657,6 +934,113 @@ mod tests {
         );
     }
 
+    #[cfg(unix)]
+    fn symlinked_project_outside_root(fixture: &TestFixture) -> (PathBuf, PathBuf) {
+        let target = fixture.root.join("elsewhere/repo");
+        fs::create_dir_all(&target).expect("create symlink target");
+        let project = fixture.canonical_root.join("repo");
+        symlink(&target, &project).expect("create project symlink");
+        (project, target)
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn reject_symlinked_project_outside_root_by_default() {
+        let fixture = TestFixture::new("symlink-default", false, None);
+        let (project, _) = symlinked_project_outside_root(&fixture);
+
+        let err = normalize_project_path_with_policy(&project, &fixture.policy())
+            .expect_err("symlink escaping root must fail by default");
+        log_normalization_error("reject_symlinked_project_outside_root_by_default", &err);
+        assert_eq!(
+            err.kind(),
+            &PathNormalizationErrorKind::OutsideCanonicalRoot
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn keep_logical_path_for_symlinked_project_outside_root() {
+        let fixture = TestFixture::new("symlink-logical", false, None);
+        let (project, target) = symlinked_project_outside_root(&fixture);
+        let policy = fixture.policy().with_keep_logical_symlinks(true);
+
+        let normalized =
+            normalize_project_path_with_policy(&project, &policy).expect("keep logical path");
+
+        let canonical_root = fixture.canonical_root.canonicalize().expect("root");
+        assert_eq!(normalized.canonical_path(), canonical_root.join("repo"));
+        assert_eq!(
+            normalized.resolved_path(),
+            target.canonicalize().expect("canonicalize target")
+        );
+        assert!(normalized.kept_logical_path());
+        assert!(normalized.decision_trace().iter().any(|d| matches!(
+            d,
+            NormalizationDecision::LogicalPathKept { resolved_path, .. }
+                if resolved_path == normalized.resolved_path()
+        )));
+        assert!(matches!(
+            normalized.decision_trace().last(),
+            Some(NormalizationDecision::VerifiedWithinCanonicalRoot { canonical_path, .. })
+                if canonical_path == normalized.canonical_path()
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn logical_dir_ignores_pwd_for_another_directory() {
+        let fixture = TestFixture::new("symlink-pwd", false, None);
+        let (project, _) = symlinked_project_outside_root(&fixture);
+
+        let cwd = std::env::current_dir().expect("cwd");
+        assert_eq!(logical_dir_for(Some(&project)).expect("logical dir"), cwd);
+        assert_eq!(logical_dir_for(None).expect("logical dir"), cwd);
+        assert_eq!(
+            logical_dir_for(Some(Path::new("relative"))).expect("logical dir"),
+            cwd
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn keep_logical_mode_leaves_regular_projects_unchanged() {
+        let fixture = TestFixture::new("symlink-regular", false, None);
+        let project = fixture.canonical_root.join("plain");
+        fs::create_dir_all(&project).expect("create project");
+        let policy = fixture.policy().with_keep_logical_symlinks(true);
+
+        let normalized = normalize_project_path_with_policy(&project, &policy).expect("normalize");
+
+        assert!(!normalized.kept_logical_path());
+        assert_eq!(normalized.canonical_path(), normalized.resolved_path());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn keep_logical_mode_still_rejects_logical_paths_outside_root() {
+        let fixture = TestFixture::new("symlink-escape", false, None);
+        let (_, target) = symlinked_project_outside_root(&fixture);
+        let outside_link = fixture.root.join("link-outside");
+        symlink(&target, &outside_link).expect("create outside symlink");
+        let policy = fixture.policy().with_keep_logical_symlinks(true);
+
+        let err = normalize_project_path_with_policy(&outside_link, &policy)
+            .expect_err("logical path outside root must fail");
+        assert_eq!(
+            err.kind(),
+            &PathNormalizationErrorKind::OutsideCanonicalRoot
+        );
+
+        let dotdot = fixture.canonical_root.join("repo/../../elsewhere/repo");
+        let err = normalize_project_path_with_policy(&dotdot, &policy)
+            .expect_err("parent segments must not be kept");
+        assert_eq!(
+            err.kind(),
+            &PathNormalizationErrorKind::OutsideCanonicalRoot
+        );
+    }
+
     #[cfg(unix)]
     #[test]
     fn reject_missing_alias_for_alias_prefixed_input() {
@@ -998,4 +1382,116 @@ mod tests {
         );
         assert!(!normalized.used_alias_prefix());
     }
+
+    #[test]
+    fn translate_windows_drive_paths_to_wsl_mounts() {
+        let mnt = Path::new(DEFAULT_WSL_MOUNT_PREFIX);
+        let cases = [
+            (r"C:\Users\me\proj", "/mnt/c/Users/me/proj"),
+            ("D:/src/app/", "/mnt/d/src/app"),
+            (r"\\?\C:\Users\me\.\proj", "/mnt/c/Users/me/proj"),
+            (r"E:\", "/mnt/e"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(
+                translate_windows_path(Path::new(input), mnt),
+                Some(PathBuf::from(expected)),
+                "{input}"
+            );
+        }
+        assert_eq!(
+            translate_windows_path(Path::new(r"C:\proj"), Path::new("/wsl/drives/")),
+            Some(PathBuf::from("/wsl/drives/c/proj"))
+        );
+    }
+
+    #[test]
+    fn translate_windows_path_rejects_ambiguous_inputs() {
+        let mnt = Path::new(DEFAULT_WSL_MOUNT_PREFIX);
+        for input in [
+            r"\\server\share\proj",
+            r"\\?\UNC\server\share\proj",
+            "C:proj",
+            r"C:\Users\..\proj",
+            "/data/projects/proj",
+            "proj",
+        ] {
+            assert_eq!(
+                translate_windows_path(Path::new(input), mnt),
+                None,
+                "{input}"
+            );
+        }
+        assert_eq!(
+            translate_windows_path(Path::new(r"C:\proj"), Path::new("mnt")),
+            None
+        );
+    }
+
+    #[test]
+    fn translate_windows_command_args_rewrites_drive_paths_only() {
+        let args = [
+            "cargo",
+            "build",
+            "--manifest-path",
+            r"C:\src\proj\Cargo.toml",
+            r"--target-dir=D:\tmp\target",
+            "C:",
+            "C:proj",
+            r"\\server\share",
+            "--features=a,b",
+        ]
+        .map(String::from);
+        assert_eq!(
+            translate_windows_command_args(&args, Path::new(DEFAULT_WSL_MOUNT_PREFIX)),
+            vec![
+                "cargo",
+                "build",
+                "--manifest-path",
+                "/mnt/c/src/proj/Cargo.toml",
+                "--target-dir=/mnt/d/tmp/target",
+                "C:",
+                "C:proj",
+                r"\\server\share",
+                "--features=a,b",
+            ]
+        );
+    }
+
+    #[test]
+    fn normalize_windows_project_path_on_windows_host() {
+        let policy = PathTopologyPolicy::new(PathBuf::from(r"C:\src"), PathBuf::from("/dp"));
+        let normalized = normalize_project_path_for_host(Path::new(r"C:\src\proj"), &policy, true)
+            .expect("translated path within canonical root");
+        assert_eq!(normalized.canonical_path(), Path::new("/mnt/c/src/proj"));
+        assert_eq!(normalized.canonical_root(), Path::new("/mnt/c/src"));
+        assert!(!normalized.used_alias_prefix());
+        assert!(
+            normalized
+                .decision_trace()
+                .iter()
+                .any(|d| matches!(d, NormalizationDecision::WindowsPathTranslated { .. }))
+        );
+
+        // A WSL-form canonical root works the same way.
+        let policy = PathTopologyPolicy::new(PathBuf::from("/mnt/c/src"), PathBuf::from("/dp"));
+        let normalized = normalize_project_path_for_host(Path::new("C:/src/proj"), &policy, true)
+            .expect("WSL-form canonical root");
+        assert_eq!(normalized.canonical_path(), Path::new("/mnt/c/src/proj"));
+
+        let err = normalize_project_path_for_host(Path::new(r"D:\other"), &policy, true)
+            .expect_err("outside canonical root");
+        assert_eq!(
+            err.kind(),
+            &PathNormalizationErrorKind::OutsideCanonicalRoot
+        );
+    }
+
+    #[test]
+    fn windows_translation_is_gated_on_windows_host() {
+        let policy = PathTopologyPolicy::new(PathBuf::from("/mnt/c/src"), PathBuf::from("/dp"));
+        let err = normalize_project_path_for_host(Path::new(r"C:\src\proj"), &policy, false)
+            .expect_err("no translation off Windows");
+        assert_eq!(err.kind(), &PathNormalizationErrorKind::NotAbsoluteInput);
+    }
 }
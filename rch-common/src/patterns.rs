@@ -21,8 +21,22 @@ const TIER_FULL_CLASSIFICATION: &str = "full_classification";
 /// Keywords that indicate a potential compilation command.
 /// Used for SIMD-accelerated quick filtering (Tier 2).
 pub static COMPILATION_KEYWORDS: &[&str] = &[
-    "cargo", "rustc", "gcc", "g++", "clang", "clang++", "make", "cmake", "ninja", "meson", "cc",
-    "c++", "bun", "nextest",
+    "cargo",
+    "rustc",
+    "gcc",
+    "g++",
+    "clang",
+    "clang++",
+    "make",
+    "cmake",
+    "ninja",
+    "meson",
+    "cc",
+    "c++",
+    "bun",
+    "nextest",
+    "trunk",
+    "wasm-pack",
 ];
 
 /// Commands that should NEVER be intercepted, even if they contain compilation keywords.
@@ -79,6 +93,15 @@ pub static NEVER_INTERCEPT: &[&str] = &[
     "cargo nextest list",    // Lists tests only, doesn't run them
     "cargo nextest archive", // Creates test archives
     "cargo nextest show",    // Shows config/setup info
+    // Trunk dev server / file watcher - require local interactivity
+    "trunk serve",
+    "trunk watch",
+    "trunk clean",
+    // wasm-pack commands that scaffold or publish locally
+    "wasm-pack new",
+    "wasm-pack login",
+    "wasm-pack pack",
+    "wasm-pack publish",
 ];
 
 /// Result of command classification.
@@ -201,6 +224,8 @@ pub enum CompilationKind {
     CargoNextest,
     /// cargo bench - run benchmarks
     CargoBench,
+    /// cargo run - build remotely, run the binary locally
+    CargoRun,
     /// rustc invocation
     Rustc,
 
@@ -229,9 +254,72 @@ pub enum CompilationKind {
     BunTest,
     /// bun typecheck - Runs TypeScript type checking
     BunTypecheck,
+
+    // WASM toolchains
+    /// trunk build - bundles a Rust/WASM web app into dist/
+    TrunkBuild,
+    /// wasm-pack build - builds a Rust crate into an npm package in pkg/
+    WasmPackBuild,
 }
 
 impl CompilationKind {
+    /// Every kind, in declaration order.
+    pub const ALL: &'static [CompilationKind] = &[
+        Self::CargoBuild,
+        Self::CargoTest,
+        Self::CargoCheck,
+        Self::CargoClippy,
+        Self::CargoDoc,
+        Self::CargoNextest,
+        Self::CargoBench,
+        Self::CargoRun,
+        Self::Rustc,
+        Self::Gcc,
+        Self::Gpp,
+        Self::Clang,
+        Self::Clangpp,
+        Self::Make,
+        Self::CmakeBuild,
+        Self::Ninja,
+        Self::Meson,
+        Self::BunTest,
+        Self::BunTypecheck,
+        Self::TrunkBuild,
+        Self::WasmPackBuild,
+    ];
+
+    /// Stable `snake_case` name (matches the serde form), e.g. `cargo_bench`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::CargoBuild => "cargo_build",
+            Self::CargoTest => "cargo_test",
+            Self::CargoCheck => "cargo_check",
+            Self::CargoClippy => "cargo_clippy",
+            Self::CargoDoc => "cargo_doc",
+            Self::CargoNextest => "cargo_nextest",
+            Self::CargoBench => "cargo_bench",
+            Self::CargoRun => "cargo_run",
+            Self::Rustc => "rustc",
+            Self::Gcc => "gcc",
+            Self::Gpp => "gpp",
+            Self::Clang => "clang",
+            Self::Clangpp => "clangpp",
+            Self::Make => "make",
+            Self::CmakeBuild => "cmake_build",
+            Self::Ninja => "ninja",
+            Self::Meson => "meson",
+            Self::BunTest => "bun_test",
+            Self::BunTypecheck => "bun_typecheck",
+            Self::TrunkBuild => "trunk_build",
+            Self::WasmPackBuild => "wasm_pack_build",
+        }
+    }
+
+    /// Look up a kind by its [`as_str`](Self::as_str) name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|kind| kind.as_str() == name)
+    }
+
     /// Returns true if this is a test-related command.
     ///
     /// Test commands have special cache affinity behavior because test binaries
@@ -259,7 +347,8 @@ impl CompilationKind {
             | CompilationKind::CargoCheck
             | CompilationKind::CargoClippy
             | CompilationKind::CargoDoc
-            | CompilationKind::CargoBench => "cargo",
+            | CompilationKind::CargoBench
+            | CompilationKind::CargoRun => "cargo",
             CompilationKind::CargoNextest => "cargo", // cargo nextest, base is still cargo
             CompilationKind::Rustc => "rustc",
             // C/C++ commands
@@ -274,10 +363,34 @@ impl CompilationKind {
             CompilationKind::Meson => "meson",
             // Bun commands
             CompilationKind::BunTest | CompilationKind::BunTypecheck => "bun",
+            // WASM toolchains
+            CompilationKind::TrunkBuild => "trunk",
+            CompilationKind::WasmPackBuild => "wasm-pack",
         }
     }
 }
 
+/// Whether a command builds an optimized profile: `--release`, cargo's `-r`,
+/// or a `--profile` other than `dev`/`test`. Optimized builds need far more
+/// memory for codegen and linking, and Cargo builds them without incremental
+/// compilation.
+pub fn is_optimized_build(cmd: &str) -> bool {
+    let tokens: Vec<&str> = cmd.split_whitespace().collect();
+    let is_cargo = tokens
+        .iter()
+        .any(|t| *t == "cargo" || t.ends_with("/cargo"));
+    tokens.iter().enumerate().any(|(i, token)| match *token {
+        "--release" => true,
+        "-r" => is_cargo,
+        "--profile" => tokens
+            .get(i + 1)
+            .is_some_and(|p| *p != "dev" && *p != "test"),
+        _ => token
+            .strip_prefix("--profile=")
+            .is_some_and(|p| p != "dev" && p != "test"),
+    })
+}
+
 /// Classify a shell command.
 ///
 /// Implements the 5-tier classification system for maximum precision with
@@ -1544,7 +1657,7 @@ fn has_file_redirect(cmd: &str) -> bool {
 /// 8. && chaining
 /// 9. || chaining
 /// 10. Subshell capture $( or backtick
-fn check_structure(cmd: &str) -> Option<&'static str> {
+pub(crate) fn check_structure(cmd: &str) -> Option<&'static str> {
     let bytes = cmd.as_bytes();
     let len = bytes.len();
 
@@ -1894,6 +2007,43 @@ fn classify_full(cmd: &str) -> Classification {
         }
     }
 
+    // Trunk (only `trunk build`; serve/watch are interactive and stay local)
+    let mut tokens = cmd.split_whitespace();
+    if tokens.next().is_some_and(|token| token.eq("trunk")) {
+        match tokens.find(|token| !token.starts_with('-')) {
+            Some("build") => {
+                if tokens.any(|a| a.eq("-w") || a.eq("--watch")) {
+                    return Classification::not_compilation(
+                        "trunk build --watch is interactive (not intercepted)",
+                    );
+                }
+                return Classification::compilation(
+                    CompilationKind::TrunkBuild,
+                    0.90,
+                    "trunk build command",
+                );
+            }
+            Some("serve" | "watch") => {
+                return Classification::not_compilation(
+                    "trunk serve/watch is interactive (not intercepted)",
+                );
+            }
+            _ => {}
+        }
+    }
+
+    // wasm-pack (only `wasm-pack build`)
+    let mut tokens = cmd.split_whitespace();
+    if tokens.next().is_some_and(|token| token.eq("wasm-pack"))
+        && tokens.find(|token| !token.starts_with('-')) == Some("build")
+    {
+        return Classification::compilation(
+            CompilationKind::WasmPackBuild,
+            0.90,
+            "wasm-pack build command",
+        );
+    }
+
     // Bun commands
     let mut tokens = cmd.split_whitespace();
     if tokens.next().is_some_and(|token| token.eq("bun")) {
@@ -1980,12 +2130,32 @@ fn classify_cargo(cmd: &str) -> Classification {
         "check" | "c" => {
             Classification::compilation(CompilationKind::CargoCheck, 0.90, "cargo check")
         }
-        "clippy" => Classification::compilation(CompilationKind::CargoClippy, 0.90, "cargo clippy"),
+        "clippy" => {
+            // `--fix` rewrites sources in place. Offloaded, the edits would land
+            // in the worker's copy and never come back (retrieval only pulls
+            // build artifacts), so the fixes would be silently lost. Flags after
+            // `--` belong to clippy-driver, not cargo-clippy.
+            if tokens
+                .take_while(|a| *a != "--")
+                .any(|a| a == "--fix" || a.starts_with("--fix="))
+            {
+                return Classification::not_compilation(
+                    "cargo clippy --fix rewrites local sources (not offloaded)",
+                );
+            }
+            Classification::compilation(CompilationKind::CargoClippy, 0.90, "cargo clippy")
+        }
+        // Normally caught by NEVER_INTERCEPT; this covers `cargo --offline fix`
+        // and friends, where a global flag precedes the subcommand.
+        "fix" => {
+            Classification::not_compilation("cargo fix rewrites local sources (not offloaded)")
+        }
         "doc" => Classification::compilation(CompilationKind::CargoDoc, 0.85, "cargo doc"),
         "run" | "r" => {
-            // cargo run compiles first, so it's a compilation command
+            // cargo run compiles first; the hook offloads the build and runs
+            // the binary locally.
             Classification::compilation(
-                CompilationKind::CargoBuild,
+                CompilationKind::CargoRun,
                 0.85,
                 "cargo run (includes build)",
             )
@@ -2127,6 +2297,29 @@ mod tests {
     use super::*;
     use crate::test_guard;
 
+    #[test]
+    fn test_is_optimized_build() {
+        let _guard = test_guard!();
+        assert!(is_optimized_build("cargo build --release"));
+        assert!(is_optimized_build("cargo build -r -p core"));
+        assert!(is_optimized_build("cargo test --profile release"));
+        assert!(is_optimized_build("cargo build --profile=bench"));
+        assert!(!is_optimized_build("cargo build"));
+        assert!(!is_optimized_build("cargo test --profile=test"));
+        assert!(!is_optimized_build("make -r all"));
+    }
+
+    #[test]
+    fn test_compilation_kind_names_match_serde() {
+        let _guard = test_guard!();
+        for kind in CompilationKind::ALL {
+            let serde_name = serde_json::to_value(kind).unwrap();
+            assert_eq!(serde_name.as_str(), Some(kind.as_str()));
+            assert_eq!(CompilationKind::from_name(kind.as_str()), Some(*kind));
+        }
+        assert_eq!(CompilationKind::from_name("cargo_bnech"), None);
+    }
+
     #[test]
     fn test_cargo_build_with_toolchain() {
         let _guard = test_guard!();
@@ -2611,6 +2804,70 @@ mod tests {
         assert!(!result.is_compilation);
     }
 
+    // WASM toolchain classification tests
+
+    #[test]
+    fn test_trunk_build_classification() {
+        let _guard = test_guard!();
+        let result = classify_command("trunk build");
+        assert!(result.is_compilation);
+        assert_eq!(result.kind, Some(CompilationKind::TrunkBuild));
+        assert!((result.confidence - 0.90).abs() < 0.001);
+
+        let result = classify_command("trunk build --release --public-url /app/");
+        assert!(result.is_compilation);
+        assert_eq!(result.kind, Some(CompilationKind::TrunkBuild));
+
+        let result = classify_command("trunk -v build");
+        assert_eq!(result.kind, Some(CompilationKind::TrunkBuild));
+    }
+
+    #[test]
+    fn test_trunk_watch_modes_not_intercepted() {
+        let _guard = test_guard!();
+        // serve/watch are interactive and should NOT be intercepted
+        for cmd in ["trunk serve", "trunk serve --open", "trunk watch"] {
+            let result = classify_command(cmd);
+            assert!(!result.is_compilation, "{cmd} must stay local");
+            assert!(result.reason.contains("never-intercept"), "{cmd}");
+        }
+
+        // Global flags before the subcommand skip the never-intercept prefix
+        let result = classify_command("trunk -v serve");
+        assert!(!result.is_compilation);
+        assert!(result.reason.contains("interactive"));
+
+        let result = classify_command("trunk build --watch");
+        assert!(!result.is_compilation);
+        assert!(result.reason.contains("interactive"));
+
+        let result = classify_command("trunk clean");
+        assert!(!result.is_compilation);
+    }
+
+    #[test]
+    fn test_wasm_pack_build_classification() {
+        let _guard = test_guard!();
+        let result = classify_command("wasm-pack build");
+        assert!(result.is_compilation);
+        assert_eq!(result.kind, Some(CompilationKind::WasmPackBuild));
+        assert!((result.confidence - 0.90).abs() < 0.001);
+
+        let result = classify_command("wasm-pack build --target web --release");
+        assert_eq!(result.kind, Some(CompilationKind::WasmPackBuild));
+
+        for cmd in [
+            "wasm-pack new my-crate",
+            "wasm-pack publish",
+            "wasm-pack test",
+        ] {
+            assert!(
+                !classify_command(cmd).is_compilation,
+                "{cmd} must stay local"
+            );
+        }
+    }
+
     #[test]
     fn test_bun_typecheck_classification() {
         let _guard = test_guard!();
@@ -4962,6 +5219,27 @@ mod regression_classification {
                 reason_contains: "cargo clippy",
                 min_confidence: 0.85,
             },
+            Case {
+                cmd: "cargo clippy --fix --allow-dirty --workspace",
+                expect_compilation: false,
+                expected_kind: None,
+                reason_contains: "rewrites local sources",
+                min_confidence: 0.0,
+            },
+            Case {
+                cmd: "cargo clippy --workspace -- --fix",
+                expect_compilation: true,
+                expected_kind: Some(CompilationKind::CargoClippy),
+                reason_contains: "cargo clippy",
+                min_confidence: 0.85,
+            },
+            Case {
+                cmd: "cargo --offline fix --allow-dirty",
+                expect_compilation: false,
+                expected_kind: None,
+                reason_contains: "rewrites local sources",
+                min_confidence: 0.0,
+            },
             Case {
                 cmd: "cargo doc",
                 expect_compilation: true,
@@ -4979,21 +5257,21 @@ mod regression_classification {
             Case {
                 cmd: "cargo run",
                 expect_compilation: true,
-                expected_kind: Some(CompilationKind::CargoBuild),
+                expected_kind: Some(CompilationKind::CargoRun),
                 reason_contains: "cargo run",
                 min_confidence: 0.80,
             },
             Case {
                 cmd: "cargo run --release",
                 expect_compilation: true,
-                expected_kind: Some(CompilationKind::CargoBuild),
+                expected_kind: Some(CompilationKind::CargoRun),
                 reason_contains: "cargo run",
                 min_confidence: 0.80,
             },
             Case {
                 cmd: "cargo r",
                 expect_compilation: true,
-                expected_kind: Some(CompilationKind::CargoBuild),
+                expected_kind: Some(CompilationKind::CargoRun),
                 reason_contains: "cargo run",
                 min_confidence: 0.80,
             },
@@ -6745,5 +7023,7 @@ mod regression_classification {
         assert_eq!(CompilationKind::Meson.command_base(), "meson");
         assert_eq!(CompilationKind::BunTest.command_base(), "bun");
         assert_eq!(CompilationKind::BunTypecheck.command_base(), "bun");
+        assert_eq!(CompilationKind::TrunkBuild.command_base(), "trunk");
+        assert_eq!(CompilationKind::WasmPackBuild.command_base(), "wasm-pack");
     }
 }
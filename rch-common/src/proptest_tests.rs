@@ -635,6 +635,16 @@ mod tests {
                     proptest::option::of(any::<bool>()),
                     proptest::option::of("[a-z_]{3,64}"),
                     proptest::option::of(1i64..4_102_444_800_000i64),
+                    proptest::collection::vec("[a-z0-9.-]{1,30}", 0..4),
+                    proptest::option::of("[0-9]+\\.[0-9]+\\.[0-9]+"),
+                    proptest::option::of(0.0f64..1024.0f64),
+                    proptest::option::of(0.0f64..1024.0f64),
+                    proptest::option::of("rsync  version [0-9]+\\.[0-9]+\\.[0-9]+"),
+                    proptest::option::of("zstd v[0-9]+\\.[0-9]+\\.[0-9]+"),
+                    proptest::collection::vec(
+                        prop_oneof![Just("rsync".to_string()), Just("zstd".to_string())],
+                        0..2,
+                    ),
                 ),
             )
                 .prop_map(
@@ -651,19 +661,37 @@ mod tests {
                             disk_free_gb,
                             disk_total_gb,
                         ),
-                        (projects_root_ok, projects_root_issue, projects_root_checked_at_unix_ms),
+                        (
+                            projects_root_ok,
+                            projects_root_issue,
+                            projects_root_checked_at_unix_ms,
+                            installed_toolchains,
+                            sccache_version,
+                            mem_total_gb,
+                            mem_available_gb,
+                            rsync_version,
+                            zstd_version,
+                            missing_tools,
+                        ),
                     )| {
                         WorkerCapabilities {
                             rustc_version,
                             bun_version,
                             node_version,
                             npm_version,
+                            installed_toolchains,
+                            sccache_version,
+                            rsync_version,
+                            zstd_version,
+                            missing_tools,
                             num_cpus,
                             load_avg_1,
                             load_avg_5,
                             load_avg_15,
                             disk_free_gb,
                             disk_total_gb,
+                            mem_total_gb,
+                            mem_available_gb,
                             projects_root_ok,
                             projects_root_issue,
                             projects_root_checked_at_unix_ms,
@@ -692,6 +720,9 @@ mod tests {
                         total_slots,
                         priority,
                         tags,
+                        max_concurrent_builds: None,
+                        nice_level: None,
+                        ionice_class: None,
                     },
                 )
         }
@@ -925,6 +956,9 @@ mod tests {
                 total_slots: 16,
                 priority: 200,
                 tags: vec!["gpu".to_string(), "high-memory".to_string()],
+                max_concurrent_builds: None,
+                nice_level: None,
+                ionice_class: None,
             };
 
             let json = serde_json::to_string(&config).unwrap();
@@ -1564,7 +1598,7 @@ mod tests {
     mod worker_selection {
         use super::*;
         use crate::{
-            AffinityConfig, FairnessConfig, SelectionConfig, SelectionStrategy,
+            AffinityConfig, FairnessConfig, QueueFairness, SelectionConfig, SelectionStrategy,
             SelectionWeightConfig,
         };
 
@@ -1586,6 +1620,7 @@ mod tests {
             (
                 0.0f64..=1.0f64, // speedscore
                 0.0f64..=1.0f64, // slots
+                0.0f64..=1.0f64, // low_load
                 0.0f64..=1.0f64, // health
                 0.0f64..=1.0f64, // cache
                 0.0f64..=1.0f64, // network
@@ -1593,10 +1628,20 @@ mod tests {
                 0.0f64..=1.0f64, // half_open_penalty
             )
                 .prop_map(
-                    |(speedscore, slots, health, cache, network, priority, half_open_penalty)| {
+                    |(
+                        speedscore,
+                        slots,
+                        low_load,
+                        health,
+                        cache,
+                        network,
+                        priority,
+                        half_open_penalty,
+                    )| {
                         SelectionWeightConfig {
                             speedscore,
                             slots,
+                            low_load,
                             health,
                             cache,
                             network,
@@ -1614,6 +1659,7 @@ mod tests {
                 |(lookback_secs, max_consecutive_selections)| FairnessConfig {
                     lookback_secs,
                     max_consecutive_selections,
+                    queue: QueueFairness::default(),
                 },
             )
         }
@@ -1674,6 +1720,7 @@ mod tests {
                             affinity,
                             max_load_per_core,
                             min_free_gb,
+                            ..SelectionConfig::default()
                         }
                     },
                 )
@@ -14,6 +14,9 @@ pub struct HookInput {
     /// Optional session ID.
     #[serde(default)]
     pub session_id: Option<String>,
+    /// Optional id of this tool call; unique per hook invocation.
+    #[serde(default)]
+    pub tool_use_id: Option<String>,
 }
 
 /// Tool-specific input for Bash commands.
@@ -143,13 +146,15 @@ mod tests {
                 "command": "cargo build --release",
                 "description": "Build the project"
             },
-            "session_id": "abc123"
+            "session_id": "abc123",
+            "tool_use_id": "toolu_01"
         }"#;
 
         let input: HookInput = serde_json::from_str(json).unwrap();
         assert_eq!(input.tool_name, "Bash");
         assert_eq!(input.tool_input.command, "cargo build --release");
         assert_eq!(input.session_id, Some("abc123".to_string()));
+        assert_eq!(input.tool_use_id.as_deref(), Some("toolu_01"));
     }
 
     #[test]
@@ -182,6 +187,7 @@ mod tests {
         assert_eq!(input.tool_input.command, "ls -la");
         assert!(input.tool_input.description.is_none());
         assert!(input.session_id.is_none());
+        assert!(input.tool_use_id.is_none());
     }
 
     #[test]
@@ -266,6 +272,7 @@ mod tests {
                 description: Some("Run tests".to_string()),
             },
             session_id: Some("session-123".to_string()),
+            tool_use_id: None,
         };
 
         let cloned = original.clone();
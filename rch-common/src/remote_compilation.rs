@@ -580,9 +580,6 @@ impl RemoteCompilationTest {
             self.worker.user, self.worker.host, escaped_remote_path
         );
 
-        let identity_file = shellexpand::tilde(&self.worker.identity_file);
-        let escaped_identity = escape(Cow::from(identity_file.as_ref()));
-
         let mut cmd = Command::new("rsync");
         cmd.arg("-az")
             .arg("--no-owner")
@@ -594,8 +591,8 @@ impl RemoteCompilationTest {
             .arg(".git/")
             .arg("-e")
             .arg(format!(
-                "ssh -i {} -o StrictHostKeyChecking=accept-new -o BatchMode=yes",
-                escaped_identity
+                "{} -o StrictHostKeyChecking=accept-new -o BatchMode=yes",
+                self.worker.rsync_ssh_program()
             ))
             .arg(format!("{}/", self.test_project.display()))
             .arg(&destination)
@@ -729,17 +726,14 @@ impl RemoteCompilationTest {
 
         let remote_target = self.remote_artifact_source(profile);
 
-        let identity_file = shellexpand::tilde(&self.worker.identity_file);
-        let escaped_identity = escape(Cow::from(identity_file.as_ref()));
-
         let mut cmd = Command::new("rsync");
         cmd.arg("-az")
             .arg("--no-owner")
             .arg("--no-group")
             .arg("-e")
             .arg(format!(
-                "ssh -i {} -o StrictHostKeyChecking=accept-new -o BatchMode=yes",
-                escaped_identity
+                "{} -o StrictHostKeyChecking=accept-new -o BatchMode=yes",
+                self.worker.rsync_ssh_program()
             ))
             .arg(&remote_target)
             .arg(format!("{}/", local_dest.display()))
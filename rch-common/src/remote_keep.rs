@@ -0,0 +1,90 @@
+//! Pinning remote project dirs for post-mortem debugging.
+//!
+//! With `transfer.keep_remote_on_failure`, a failed remote build writes a
+//! [`KEEP_MARKER`] file into its remote project dir holding the unix time the
+//! pin expires. The daemon's cache cleanup and disk reclaim skip a dir while
+//! its marker is unexpired, so the failed state survives long enough to be
+//! inspected; once the pin expires the dir is treated like any other cache.
+//! `rch-wkr cleanup` checks the same marker with [`is_pinned`].
+
+/// Marker file name, written at the top of the pinned project dir.
+pub const KEEP_MARKER: &str = ".rch-keep";
+
+/// Shell command that pins `escaped_dir` until `expires_at` (unix seconds).
+///
+/// `escaped_dir` must already be shell-escaped. Does nothing when the dir does
+/// not exist.
+pub fn pin_command(escaped_dir: &str, expires_at: u64) -> String {
+    format!("[ -d {escaped_dir} ] && printf '%s\\n' {expires_at} > {escaped_dir}/{KEEP_MARKER}")
+}
+
+/// Whether `dir` holds a pin that has not expired at `now` (unix seconds).
+/// A marker without a readable timestamp counts as expired, as in
+/// [`skip_pinned_snippet`].
+pub fn is_pinned(dir: &std::path::Path, now: u64) -> bool {
+    std::fs::read_to_string(dir.join(KEEP_MARKER))
+        .ok()
+        .and_then(|contents| contents.lines().next()?.trim().parse::<u64>().ok())
+        .is_some_and(|keep_until| keep_until > now)
+}
+
+/// Shell snippet for a cleanup loop over `"$dir"`: `continue`s when the dir
+/// holds an unexpired pin. A marker without a readable timestamp counts as
+/// expired.
+pub fn skip_pinned_snippet() -> String {
+    format!(
+        "if [ -f \"$dir\"/{KEEP_MARKER} ]; then \
+           keep_until=$(head -n 1 \"$dir\"/{KEEP_MARKER} 2>/dev/null); \
+           case \"$keep_until\" in ''|*[!0-9]*) keep_until=0 ;; esac; \
+           if [ \"$keep_until\" -gt \"$(date +%s)\" ]; then continue; fi; \
+         fi; "
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    fn run_loop(base: &std::path::Path) -> String {
+        let script = format!(
+            "for dir in {}/*; do {}echo \"$(basename \"$dir\")\"; done",
+            base.display(),
+            skip_pinned_snippet()
+        );
+        let output = Command::new("sh").arg("-c").arg(script).output().unwrap();
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    #[test]
+    fn pinned_dirs_are_skipped_until_expiry() {
+        let temp = tempfile::tempdir().unwrap();
+        for name in ["expired", "pinned", "plain"] {
+            std::fs::create_dir(temp.path().join(name)).unwrap();
+        }
+        let escaped = |name: &str| temp.path().join(name).display().to_string();
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        for (name, expires_at) in [("expired", now - 60), ("pinned", now + 3600)] {
+            let status = Command::new("sh")
+                .arg("-c")
+                .arg(pin_command(&escaped(name), expires_at))
+                .status()
+                .unwrap();
+            assert!(status.success());
+        }
+
+        assert_eq!(run_loop(temp.path()), "expired\nplain\n");
+    }
+
+    #[test]
+    fn pin_command_ignores_missing_dir() {
+        let command = pin_command("'/nonexistent/rch/proj'", 1);
+        assert_eq!(
+            command,
+            "[ -d '/nonexistent/rch/proj' ] && printf '%s\\n' 1 > '/nonexistent/rch/proj'/.rch-keep"
+        );
+    }
+}
@@ -473,6 +473,9 @@ mod basic_tests {
             total_slots: 8,
             priority: 100,
             tags: Vec::new(),
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         }
     }
 
@@ -847,6 +847,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec!["rust".to_string()],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let client = SshClient::new(config.clone(), SshOptions::default());
@@ -874,6 +877,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec!["rust".to_string()],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         }
     }
 
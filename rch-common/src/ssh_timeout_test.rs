@@ -28,6 +28,9 @@ mod tests {
             total_slots: 1,
             priority: 1,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let _client = SshClient::new(config, options);
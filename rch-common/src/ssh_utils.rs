@@ -153,6 +153,12 @@ where
     }
 }
 
+/// Whether an ssh-agent is reachable (`SSH_AUTH_SOCK` set and non-empty), so
+/// workers without an `identity_file` can still authenticate.
+pub fn ssh_agent_available() -> bool {
+    std::env::var_os("SSH_AUTH_SOCK").is_some_and(|sock| !sock.is_empty())
+}
+
 /// Check if a string is a valid environment variable key.
 pub fn is_valid_env_key(key: &str) -> bool {
     let mut chars = key.chars();
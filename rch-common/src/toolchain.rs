@@ -3,6 +3,7 @@
 //! Types for representing Rust toolchain information used in the RCH protocol
 //! for toolchain synchronization between local and remote workers.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 
@@ -69,6 +70,67 @@ impl std::fmt::Display for ToolchainInfo {
     }
 }
 
+/// How closely a worker's rustc must match the local one before a build is
+/// offloaded (`compilation.require_rustc_match`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RustcMatch {
+    /// Same `major.minor` release.
+    Minor,
+    /// Same `major.minor.patch` release.
+    Patch,
+    /// Identical `rustc --version` line (channel, commit hash and date).
+    Exact,
+}
+
+impl RustcMatch {
+    /// Config spelling of this granularity.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Minor => "minor",
+            Self::Patch => "patch",
+            Self::Exact => "exact",
+        }
+    }
+
+    /// Whether two `rustc --version` lines agree at this granularity.
+    ///
+    /// Versions that cannot be parsed never match.
+    pub fn versions_match(self, local: &str, remote: &str) -> bool {
+        match self {
+            Self::Exact => !local.trim().is_empty() && local.trim() == remote.trim(),
+            Self::Patch => matches!(
+                (rustc_release(local), rustc_release(remote)),
+                (Some(l), Some(r)) if l == r
+            ),
+            Self::Minor => matches!(
+                (rustc_release(local), rustc_release(remote)),
+                (Some(l), Some(r)) if l[..2] == r[..2]
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for RustcMatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Release number (`[major, minor, patch]`) from a `rustc --version` line
+/// such as `rustc 1.76.0-nightly (abc123def 2024-01-15)` or a bare `1.76.0`.
+pub fn rustc_release(version: &str) -> Option<[u64; 3]> {
+    let mut tokens = version.split_whitespace();
+    let mut token = tokens.next()?;
+    if token == "rustc" {
+        token = tokens.next()?;
+    }
+    let release = token.split('-').next()?;
+    let mut parts = release.split('.').map(|part| part.parse::<u64>().ok());
+    let parsed = [parts.next()??, parts.next()??, parts.next()??];
+    parts.next().is_none().then_some(parsed)
+}
+
 /// Wrap a command to run with a specific toolchain.
 ///
 /// If toolchain is provided, wraps the command with `rustup run <toolchain>`.
@@ -276,6 +338,7 @@ fn skip_shell_whitespace(command: &str, mut offset: usize) -> usize {
 /// - `RUST_LOG_STYLE=always` - Forces env_logger/tracing to use colors
 /// - `CLICOLOR_FORCE=1` - Standard env var for forcing color output
 /// - `FORCE_COLOR=1` - Used by many Node.js/JS tools including Bun
+/// - `CMAKE_COLOR_DIAGNOSTICS=ON` - Makes CMake pass color flags to gcc/clang
 ///
 /// # Arguments
 ///
@@ -294,9 +357,10 @@ pub fn wrap_command_with_color(command: &str, color_mode: ColorMode) -> String {
             // - RUST_LOG_STYLE: env_logger and tracing-subscriber color setting
             // - CLICOLOR_FORCE: de facto standard for forcing colors (clicolors.org)
             // - FORCE_COLOR: Node.js/JS ecosystem standard (used by Bun, chalk, etc.)
+            // - CMAKE_COLOR_DIAGNOSTICS: CMake >= 3.24 adds the compiler's color flag
             // - NO_COLOR: Ensure it's unset (some tools check this first)
             format!(
-                "env -u NO_COLOR CARGO_TERM_COLOR=always RUST_LOG_STYLE=always CLICOLOR_FORCE=1 FORCE_COLOR=1 {}",
+                "env -u NO_COLOR CARGO_TERM_COLOR=always RUST_LOG_STYLE=always CLICOLOR_FORCE=1 FORCE_COLOR=1 CMAKE_COLOR_DIAGNOSTICS=ON {}",
                 command
             )
         }
@@ -643,6 +707,7 @@ mod tests {
         assert!(wrapped.contains("RUST_LOG_STYLE=always"));
         assert!(wrapped.contains("CLICOLOR_FORCE=1"));
         assert!(wrapped.contains("FORCE_COLOR=1"));
+        assert!(wrapped.contains("CMAKE_COLOR_DIAGNOSTICS=ON"));
         assert!(wrapped.contains("env -u NO_COLOR"));
         assert!(wrapped.contains("cargo test"));
     }
@@ -719,4 +784,28 @@ mod tests {
         // Default should be Always to preserve colors in remote SSH
         assert_eq!(ColorMode::default(), ColorMode::Always);
     }
+
+    #[test]
+    fn test_rustc_release_parsing() {
+        assert_eq!(
+            rustc_release("rustc 1.76.0-nightly (abc123def 2024-01-15)"),
+            Some([1, 76, 0])
+        );
+        assert_eq!(rustc_release("1.75.2"), Some([1, 75, 2]));
+        assert_eq!(rustc_release("rustc 1.75"), None);
+        assert_eq!(rustc_release(""), None);
+    }
+
+    #[test]
+    fn test_rustc_match_granularity() {
+        let local = "rustc 1.80.1 (3f5fd8dd4 2024-08-06)";
+        let patch_bump = "rustc 1.80.0 (051478957 2024-07-21)";
+        assert!(RustcMatch::Minor.versions_match(local, patch_bump));
+        assert!(!RustcMatch::Patch.versions_match(local, patch_bump));
+        assert!(RustcMatch::Patch.versions_match(local, "rustc 1.80.1 (ffffffff 2024-08-06)"));
+        assert!(!RustcMatch::Exact.versions_match(local, "rustc 1.80.1 (ffffffff 2024-08-06)"));
+        assert!(RustcMatch::Exact.versions_match(local, &format!("{local}\n")));
+        assert!(!RustcMatch::Minor.versions_match(local, "rustc 1.81.0 (eeb90cda1 2024-09-04)"));
+        assert!(!RustcMatch::Minor.versions_match(local, "unknown"));
+    }
 }
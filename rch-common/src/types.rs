@@ -1,9 +1,14 @@
 //! Common types used across RCH components.
 
-use crate::{CompilationKind, toolchain::ToolchainInfo};
+use crate::{
+    CompilationKind,
+    decision_trace::DecisionTraceStep,
+    toolchain::{RustcMatch, ToolchainInfo},
+};
 use rand::RngExt;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 /// Unique identifier for a worker in the fleet.
@@ -73,6 +78,79 @@ pub enum CircuitState {
     HalfOpen,
 }
 
+/// Why a worker's last health check failed.
+///
+/// Recorded by the daemon's health monitor and surfaced on the status endpoint
+/// so `rch status` can say why a worker is unhealthy without digging through
+/// logs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthFailureReason {
+    /// The worker actively refused the SSH connection.
+    ConnectionRefused,
+    /// SSH authentication or host key verification failed.
+    AuthFailed,
+    /// The host name did not resolve or the network has no route to it.
+    HostUnreachable,
+    /// The connection or the probe command timed out.
+    Timeout,
+    /// The worker answered but has no Rust toolchain.
+    RustcMissing,
+    /// The probe command ran but did not print the expected response.
+    UnexpectedResponse,
+    /// The worker answers, but the deep check (`rch-wkr health --deep`)
+    /// could not build a trivial crate.
+    BuildCheckFailed,
+    /// Any other failure; see the worker's last error for details.
+    Other,
+}
+
+impl HealthFailureReason {
+    /// Classify an SSH connection or command error message.
+    pub fn from_ssh_error(message: &str) -> Self {
+        let message = message.to_lowercase();
+        if message.contains("connection refused") {
+            Self::ConnectionRefused
+        } else if message.contains("permission denied")
+            || message.contains("host key verification failed")
+            || message.contains("authentication")
+            || message.contains("identity file")
+        {
+            Self::AuthFailed
+        } else if message.contains("timed out") || message.contains("timeout") {
+            Self::Timeout
+        } else if message.contains("could not resolve hostname")
+            || message.contains("name resolution")
+            || message.contains("no route to host")
+            || message.contains("network is unreachable")
+        {
+            Self::HostUnreachable
+        } else {
+            Self::Other
+        }
+    }
+
+    /// Short human-readable label, e.g. `ssh auth failed`.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ConnectionRefused => "ssh connection refused",
+            Self::AuthFailed => "ssh auth failed",
+            Self::HostUnreachable => "host unreachable",
+            Self::Timeout => "timed out",
+            Self::RustcMissing => "rustc missing",
+            Self::UnexpectedResponse => "unexpected probe response",
+            Self::BuildCheckFailed => "build check failed",
+            Self::Other => "probe failed",
+        }
+    }
+}
+
+impl std::fmt::Display for HealthFailureReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
 /// Required runtime for command execution.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, JsonSchema)]
 #[serde(rename_all = "lowercase")]
@@ -154,6 +232,29 @@ pub struct SelectionRequest {
     /// Process ID of the hook (for active build tracking).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub hook_pid: Option<u32>,
+    /// Named worker fleet to select from (`transfer.fleet`).
+    ///
+    /// `None` selects from every configured worker.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fleet: Option<String>,
+    /// Minimum free disk space (GB) a worker must report to be selected
+    /// (`transfer.min_free_disk_gb`).
+    ///
+    /// Workers without a disk reading are not excluded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_free_disk_gb: Option<f64>,
+    /// Worker IDs the build may run on (`rch exec --workers`).
+    ///
+    /// Unlike `preferred_workers`, this is a hard restriction: no other worker
+    /// is selected. Empty allows every worker.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_workers: Vec<WorkerId>,
+    /// Key identifying this invocation for duplicate detection (`rch exec`).
+    ///
+    /// A second request with the same key shortly after the first is
+    /// coalesced onto the first build instead of starting another one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
 }
 
 /// Reason for worker selection result.
@@ -169,16 +270,29 @@ pub enum SelectionReason {
     NoWorkersConfigured,
     /// All workers are unreachable (failed health checks).
     AllWorkersUnreachable,
+    /// All workers are draining, drained, or disabled by an operator.
+    AllWorkersDraining,
     /// All workers have circuits open (after repeated failures).
     AllCircuitsOpen,
     /// All workers are at capacity (no available slots).
     AllWorkersBusy,
+    /// Workers with free slots were all at their `max_concurrent_builds` cap.
+    WorkerBuildCapReached,
+    /// Remaining candidates were blocked after consecutive failed builds.
+    WorkerBlockedAfterFailures,
     /// Workers were present but none passed selection health thresholds.
     NoWorkersPassedHealth,
     /// All candidate workers failed hard preflight checks.
     AllWorkersFailedPreflight,
     /// All candidate workers failed repo convergence checks (repos missing/stale/failed).
     AllWorkersFailedConvergence,
+    /// All candidate workers reported less free disk than the requested minimum.
+    InsufficientDiskSpace,
+    /// All candidate workers reported less available memory than
+    /// `selection.min_memory_gb` for a memory-hungry build.
+    InsufficientMemory,
+    /// All candidate workers reported rsync as not installed.
+    WorkersMissingRsync,
     /// Workers exist but admission was blocked by concrete capacity/preflight reasons.
     NoAdmissibleWorkers(String),
     /// No workers match required tags or preferences.
@@ -191,6 +305,14 @@ pub enum SelectionReason {
     AffinityPinned,
     /// Worker assigned via last-success fallback (all others unavailable).
     AffinityFallback,
+    /// Worker assigned after waiting in the round-robin build queue
+    /// (`selection.fairness.queue = "roundrobin"`); `position` is the 1-based
+    /// queue position the build was given when it was enqueued.
+    QueuedFair { position: usize },
+    /// A request with the same idempotency key started a build moments ago;
+    /// the duplicate waits on that build (`build_id`) instead of starting a
+    /// second one.
+    CoalescedDuplicate,
 }
 
 /// Wire protocol version for daemon `/select-worker` responses.
@@ -206,13 +328,34 @@ impl std::fmt::Display for SelectionReason {
             Self::Success => write!(f, "worker assigned successfully"),
             Self::NoWorkersConfigured => write!(f, "no workers configured"),
             Self::AllWorkersUnreachable => write!(f, "all workers unreachable"),
+            Self::AllWorkersDraining => write!(f, "all workers draining or disabled"),
             Self::AllCircuitsOpen => write!(f, "all worker circuits open"),
             Self::AllWorkersBusy => write!(f, "all workers at capacity"),
+            Self::WorkerBuildCapReached => write!(f, "workers at concurrent build cap"),
+            Self::WorkerBlockedAfterFailures => {
+                write!(f, "workers blocked after repeated build failures")
+            }
             Self::NoWorkersPassedHealth => write!(f, "no workers passed health thresholds"),
             Self::AllWorkersFailedPreflight => write!(f, "all workers failed preflight checks"),
             Self::AllWorkersFailedConvergence => {
                 write!(f, "all workers failed repo convergence checks")
             }
+            Self::InsufficientDiskSpace => {
+                write!(f, "all workers below minimum free disk space")
+            }
+            Self::InsufficientMemory => {
+                write!(f, "all workers below minimum available memory")
+            }
+            Self::WorkersMissingRsync => {
+                write!(
+                    f,
+                    "all workers missing rsync ({})",
+                    worker_tool_install_hint("rsync")
+                )
+            }
+            Self::CoalescedDuplicate => {
+                write!(f, "duplicate of an in-flight build, waiting on its result")
+            }
             Self::NoAdmissibleWorkers(summary) => {
                 write!(f, "no admissible workers: {}", summary)
             }
@@ -221,6 +364,13 @@ impl std::fmt::Display for SelectionReason {
             Self::SelectionError(e) => write!(f, "selection error: {}", e),
             Self::AffinityPinned => write!(f, "worker assigned via affinity pinning"),
             Self::AffinityFallback => write!(f, "worker assigned via last-success fallback"),
+            Self::QueuedFair { position } => {
+                write!(
+                    f,
+                    "worker assigned after fair queueing (position {})",
+                    position
+                )
+            }
         }
     }
 }
@@ -310,8 +460,40 @@ pub struct SelectionConfig {
     /// Set to None to disable disk-based filtering.
     #[serde(default = "default_min_free_gb")]
     pub min_free_gb: Option<f64>,
+    /// Minimum available memory in GB a worker must report to receive a
+    /// memory-hungry (release) build, so linking does not OOM a small
+    /// worker. Workers without a memory reading are not excluded. Unset by
+    /// default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_memory_gb: Option<f64>,
+
+    /// Split unfiltered `cargo nextest run` invocations across multiple free
+    /// workers using `--partition count:i/N`. Commands that already pass
+    /// `--partition` or a test filter are never sharded.
+    #[serde(default)]
+    pub shard_tests: bool,
+    /// Upper bound on the number of partitions (workers) per sharded run.
+    #[serde(default = "default_max_test_shards")]
+    pub max_test_shards: u32,
+
+    /// Seconds without a build heartbeat after which the daemon treats a
+    /// remote build whose hook has exited as abandoned and reclaims its slots
+    /// (0 = never). Hooks heartbeat every 5 seconds; values below
+    /// [`MIN_HEARTBEAT_TIMEOUT_SECS`] are raised to it.
+    #[serde(default = "default_heartbeat_timeout_secs")]
+    pub heartbeat_timeout_secs: u64,
+
+    /// Seconds between deep health checks, which build a trivial crate on
+    /// each worker (`rch-wkr health --deep`); a worker that answers SSH but
+    /// fails the build is marked degraded and ranked last (0 = off).
+    #[serde(default)]
+    pub deep_health_check_interval_secs: u64,
 }
 
+/// Smallest effective `selection.heartbeat_timeout_secs`: twelve missed
+/// heartbeats, so a briefly starved hook is never reaped.
+pub const MIN_HEARTBEAT_TIMEOUT_SECS: u64 = 60;
+
 impl Default for SelectionConfig {
     fn default() -> Self {
         Self {
@@ -322,6 +504,11 @@ impl Default for SelectionConfig {
             affinity: AffinityConfig::default(),
             max_load_per_core: default_max_load_per_core(),
             min_free_gb: default_min_free_gb(),
+            min_memory_gb: None,
+            shard_tests: false,
+            max_test_shards: default_max_test_shards(),
+            heartbeat_timeout_secs: default_heartbeat_timeout_secs(),
+            deep_health_check_interval_secs: 0,
         }
     }
 }
@@ -338,22 +525,43 @@ fn default_min_free_gb() -> Option<f64> {
     Some(10.0) // Skip workers with < 10 GB free
 }
 
-/// Weight configuration for the balanced selection strategy.
+fn default_max_test_shards() -> u32 {
+    4
+}
+
+fn default_heartbeat_timeout_secs() -> u64 {
+    300 // 60 missed heartbeats
+}
+
+/// Weight configuration for the balanced selection strategy
+/// (`[selection.weights]`).
+///
+/// The balanced score is the weighted sum of per-worker factors, each
+/// normalized to 0.0-1.0. Weights are relative, need not sum to 1, and must
+/// be non-negative. `speed`, `free_slots` and `affinity` are accepted as
+/// aliases for `speedscore`, `slots` and `cache`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SelectionWeightConfig {
     /// Weight for SpeedScore (0.0-1.0).
-    #[serde(default = "default_weight_speedscore")]
+    #[serde(default = "default_weight_speedscore", alias = "speed")]
     pub speedscore: f64,
     /// Weight for available slots (0.0-1.0).
-    #[serde(default = "default_weight_slots")]
+    #[serde(default = "default_weight_slots", alias = "free_slots")]
     pub slots: f64,
+    /// Weight for a low host load average per core (0.0-1.0).
+    ///
+    /// Unlike `slots`, this sees load RCH did not schedule. Defaults to 0.0
+    /// (off).
+    #[serde(default)]
+    pub low_load: f64,
     /// Weight for health/success rate (0.0-1.0).
     #[serde(default = "default_weight_health")]
     pub health: f64,
     /// Weight for cache affinity (0.0-1.0).
-    #[serde(default = "default_weight_cache")]
+    #[serde(default = "default_weight_cache", alias = "affinity")]
     pub cache: f64,
-    /// Weight for network latency (0.0-1.0).
+    /// Weight for network quality: health-check latency plus measured upload
+    /// bandwidth (0.0-1.0).
     #[serde(default = "default_weight_network")]
     pub network: f64,
     /// Weight for worker priority (0.0-1.0).
@@ -369,6 +577,7 @@ impl Default for SelectionWeightConfig {
         Self {
             speedscore: default_weight_speedscore(),
             slots: default_weight_slots(),
+            low_load: 0.0,
             health: default_weight_health(),
             cache: default_weight_cache(),
             network: default_weight_network(),
@@ -378,6 +587,22 @@ impl Default for SelectionWeightConfig {
     }
 }
 
+impl SelectionWeightConfig {
+    /// Factor weights keyed by config name. `half_open_penalty` is a
+    /// multiplier, not a factor, and is left out.
+    pub fn factor_weights(&self) -> [(&'static str, f64); 7] {
+        [
+            ("speedscore", self.speedscore),
+            ("slots", self.slots),
+            ("low_load", self.low_load),
+            ("health", self.health),
+            ("cache", self.cache),
+            ("network", self.network),
+            ("priority", self.priority),
+        ]
+    }
+}
+
 fn default_weight_speedscore() -> f64 {
     0.5
 }
@@ -406,7 +631,8 @@ fn default_half_open_penalty() -> f64 {
     0.5
 }
 
-/// Fairness settings for the fair_fastest selection strategy.
+/// Fairness settings for the fair_fastest selection strategy and the daemon's
+/// build queue.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FairnessConfig {
     /// Lookback window in seconds for tracking recent selections.
@@ -415,6 +641,9 @@ pub struct FairnessConfig {
     /// Maximum consecutive selections for a single worker before penalty.
     #[serde(default = "default_max_consecutive_selections")]
     pub max_consecutive_selections: u32,
+    /// Order in which builds waiting in the daemon queue (`wait=1`) are served.
+    #[serde(default)]
+    pub queue: QueueFairness,
 }
 
 impl Default for FairnessConfig {
@@ -422,6 +651,32 @@ impl Default for FairnessConfig {
         Self {
             lookback_secs: default_fairness_lookback_secs(),
             max_consecutive_selections: default_max_consecutive_selections(),
+            queue: QueueFairness::default(),
+        }
+    }
+}
+
+/// Scheduling policy for builds waiting in the daemon queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum QueueFairness {
+    /// Every waiter retries selection on each poll; whoever finds a free slot
+    /// first gets it. A session with many queued builds can take every slot.
+    #[default]
+    Fifo,
+    /// Waiters are grouped by Claude Code session (or project when the session
+    /// is unknown) and served one group at a time, least recently served group
+    /// first. Only the waiter at the head of that order may take a slot, unless
+    /// no worker can run it, in which case the next waiter may try.
+    #[serde(alias = "round_robin")]
+    RoundRobin,
+}
+
+impl std::fmt::Display for QueueFairness {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fifo => write!(f, "fifo"),
+            Self::RoundRobin => write!(f, "roundrobin"),
         }
     }
 }
@@ -497,6 +752,29 @@ pub struct SelectedWorker {
     pub slots_available: u32,
     /// Worker's speed score (0-100).
     pub speed_score: f64,
+    /// Upload bandwidth to this worker measured from recent source syncs
+    /// (bytes/sec), if the daemon has a sample.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upload_bandwidth_bps: Option<u64>,
+    /// Last SSH round-trip time to this worker from health checks (ms).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rtt_ms: Option<u64>,
+    /// Worker's `rustc --version` from its last capabilities probe, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rustc_version: Option<String>,
+    /// Worker's sccache version from its last capabilities probe, if installed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sccache_version: Option<String>,
+    /// Transfer tools the worker's last capabilities probe did not find
+    /// (see [`WorkerCapabilities::missing_tools`]).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing_tools: Vec<String>,
+    /// The worker's configured `nice_level`, applied to the remote command.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nice_level: Option<i32>,
+    /// The worker's configured `ionice_class`, applied to the remote command.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ionice_class: Option<u8>,
 }
 
 /// Worker selection response from daemon to hook.
@@ -607,6 +885,60 @@ pub struct ReleaseRequest {
     /// Optional per-phase timing breakdown for the build pipeline.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub timing: Option<CommandTimingBreakdown>,
+    /// Decisions the hook recorded for this build (`rch explain`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub decision_trace: Vec<DecisionTraceStep>,
+    /// Local project root, stored for `rch replay`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_path: Option<String>,
+    /// Rustup toolchain name, stored for `rch replay`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toolchain: Option<String>,
+}
+
+/// Optional JSON body line of `POST /release-worker`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReleaseWorkerBody {
+    /// Per-phase timing breakdown for the build pipeline.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timing: Option<CommandTimingBreakdown>,
+    /// Decisions the hook recorded for this build.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub decision_trace: Vec<DecisionTraceStep>,
+    /// Local project root the build ran from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_path: Option<String>,
+    /// Rustup toolchain name the build used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toolchain: Option<String>,
+}
+
+impl ReleaseWorkerBody {
+    /// Parse a body line.
+    ///
+    /// Hooks that predate decision traces sent a bare
+    /// [`CommandTimingBreakdown`], which is still accepted.
+    pub fn parse(line: &str) -> Option<Self> {
+        let value: serde_json::Value = serde_json::from_str(line).ok()?;
+        if ["timing", "decision_trace", "project_path", "toolchain"]
+            .iter()
+            .any(|key| value.get(key).is_some())
+        {
+            return serde_json::from_value(value).ok();
+        }
+        serde_json::from_value(value).ok().map(|timing| Self {
+            timing: Some(timing),
+            ..Default::default()
+        })
+    }
+
+    /// Whether the body carries nothing and can be omitted.
+    pub fn is_empty(&self) -> bool {
+        self.timing.is_none()
+            && self.decision_trace.is_empty()
+            && self.project_path.is_none()
+            && self.toolchain.is_none()
+    }
 }
 
 /// Build execution phase for daemon heartbeat tracking.
@@ -636,6 +968,12 @@ pub struct BuildHeartbeatRequest {
     /// Remote file containing the process-group leader PID for cancellation.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub remote_pgid_file: Option<String>,
+    /// Whether the remote build runs detached from the hook's SSH session.
+    ///
+    /// The daemon waits for a detached build's recorded exit code instead of
+    /// cancelling it when the hook goes away.
+    #[serde(default)]
+    pub detached: bool,
     /// Current build execution phase.
     pub phase: BuildHeartbeatPhase,
     /// Optional human-readable progress detail.
@@ -650,6 +988,35 @@ pub struct BuildHeartbeatRequest {
     /// Optional progress estimate in [0,100].
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub progress_percent: Option<f64>,
+    /// Remote output lines produced since the previous heartbeat.
+    ///
+    /// The daemon keeps the most recent lines per active build for `rch tail`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub output_lines: Vec<String>,
+}
+
+/// One line of the `GET /tail?build_id=<id>` stream.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum BuildTailEvent {
+    /// The build's heartbeat phase or detail changed.
+    Phase {
+        phase: BuildHeartbeatPhase,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        detail: Option<String>,
+    },
+    /// A line of remote output; `seq` counts lines since the build started.
+    Line { seq: u64, line: String },
+    /// Older lines fell out of the daemon's buffer before they were sent.
+    Skipped { lines: u64 },
+    /// The build is no longer active. `exit_code` is `None` when the daemon
+    /// no longer has a record of it.
+    Finished {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        exit_code: Option<i32>,
+    },
+    /// No active build has this ID.
+    NotFound,
 }
 
 /// Configuration for a remote worker.
@@ -661,7 +1028,8 @@ pub struct WorkerConfig {
     pub host: String,
     /// SSH username.
     pub user: String,
-    /// Path to SSH private key.
+    /// Path to SSH private key. Empty means no `-i`: ssh authenticates
+    /// through the agent (`SSH_AUTH_SOCK`).
     pub identity_file: String,
     /// Total CPU slots available on this worker.
     pub total_slots: u32,
@@ -671,12 +1039,69 @@ pub struct WorkerConfig {
     /// Optional tags for filtering.
     #[serde(default)]
     pub tags: Vec<String>,
+    /// Maximum number of builds run at once, however many slots are free.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_builds: Option<u32>,
+    /// Run remote builds under `nice -n <N>` so they yield CPU to
+    /// interactive work on a shared machine. Unset means no wrapping.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nice_level: Option<i32>,
+    /// Run remote builds under `ionice -c <class>` (2 best-effort, 3 idle).
+    /// Unset means no wrapping.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ionice_class: Option<u8>,
 }
 
 fn default_priority() -> u32 {
     100
 }
 
+/// Values `nice -n` accepts for [`WorkerConfig::nice_level`].
+pub const NICE_LEVEL_RANGE: std::ops::RangeInclusive<i32> = -20..=19;
+
+/// Classes allowed for [`WorkerConfig::ionice_class`]: best-effort and idle.
+/// Class 0 ("none") is the same as leaving it unset, and class 1 (realtime)
+/// needs root and can starve the worker's own I/O.
+pub const IONICE_CLASS_RANGE: std::ops::RangeInclusive<u8> = 2..=3;
+
+/// Tag prefix marking the named fleet a worker belongs to (`fleet:<name>`).
+pub const FLEET_TAG_PREFIX: &str = "fleet:";
+
+impl WorkerConfig {
+    /// Name of the fleet this worker was declared in, if any.
+    pub fn fleet(&self) -> Option<&str> {
+        self.tags
+            .iter()
+            .find_map(|tag| tag.strip_prefix(FLEET_TAG_PREFIX))
+    }
+
+    /// Whether this worker may serve a build requesting `fleet`.
+    ///
+    /// Every worker matches when no fleet is requested.
+    pub fn in_fleet(&self, fleet: Option<&str>) -> bool {
+        fleet.is_none_or(|name| self.fleet() == Some(name))
+    }
+
+    /// Key to pass to ssh as `-i`, with `~` expanded; `None` when
+    /// `identity_file` is empty and the ssh-agent should be used instead.
+    pub fn ssh_identity(&self) -> Option<String> {
+        let identity = self.identity_file.trim();
+        (!identity.is_empty()).then(|| shellexpand::tilde(identity).into_owned())
+    }
+
+    /// Start of an rsync `-e` ssh command: `ssh -i <key>`, or bare `ssh` when
+    /// the worker has no identity file and authenticates through the ssh-agent.
+    pub fn rsync_ssh_program(&self) -> String {
+        match self.ssh_identity() {
+            Some(identity) => format!(
+                "ssh -i {}",
+                shell_escape::escape(std::borrow::Cow::from(identity))
+            ),
+            None => "ssh".to_string(),
+        }
+    }
+}
+
 impl Default for WorkerConfig {
     fn default() -> Self {
         Self {
@@ -687,6 +1112,9 @@ impl Default for WorkerConfig {
             total_slots: 4,
             priority: default_priority(),
             tags: Vec::new(),
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         }
     }
 }
@@ -710,6 +1138,27 @@ pub struct WorkerCapabilities {
     /// npm version (from `npm --version`).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub npm_version: Option<String>,
+    /// Rust toolchains installed via rustup (`rustup toolchain list`), without
+    /// the host triple, e.g. `stable`, `nightly-2024-01-15`, `1.85.0`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub installed_toolchains: Vec<String>,
+    /// sccache version (from `sccache --version`), used by
+    /// `transfer.use_sccache`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sccache_version: Option<String>,
+    /// rsync version (first line of `rsync --version`); every transfer uses it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rsync_version: Option<String>,
+    /// zstd version (first line of `zstd --version`), needed by
+    /// `transfer.artifact_archive`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zstd_version: Option<String>,
+    /// Transfer tools (`rsync`, `zstd`) the probe looked for and did not find.
+    ///
+    /// Empty for workers whose `rch-wkr` predates the check, so those are
+    /// never excluded on this basis.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing_tools: Vec<String>,
 
     // Health metrics (bd-3eaa)
     /// Number of CPU cores on the worker.
@@ -730,6 +1179,12 @@ pub struct WorkerCapabilities {
     /// Total disk space in GB.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub disk_total_gb: Option<f64>,
+    /// Total physical memory in GB.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mem_total_gb: Option<f64>,
+    /// Memory available to new processes in GB (`MemAvailable`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mem_available_gb: Option<f64>,
     /// Canonical path-topology preflight status (`/data/projects` + `/dp` alias).
     ///
     /// `Some(false)` indicates a hard preflight failure that should exclude this
@@ -776,6 +1231,25 @@ impl WorkerCapabilities {
         self.rustc_version.is_some()
     }
 
+    /// Check if this worker has sccache installed.
+    pub fn has_sccache(&self) -> bool {
+        self.sccache_version.is_some()
+    }
+
+    /// Check if the probe reported transfer tool `tool` as not installed.
+    pub fn is_missing_tool(&self, tool: &str) -> bool {
+        self.missing_tools.iter().any(|missing| missing == tool)
+    }
+
+    /// Check if the rustup toolchain `name` (as in `rustup run <name>`) is
+    /// installed. Returns None if the worker did not report its toolchains.
+    pub fn has_toolchain(&self, name: &str) -> Option<bool> {
+        if self.installed_toolchains.is_empty() {
+            return None;
+        }
+        Some(self.installed_toolchains.iter().any(|t| t == name))
+    }
+
     /// Calculate load per core (1-minute load average / num_cpus).
     /// Returns None if metrics are unavailable.
     pub fn load_per_core(&self) -> Option<f64> {
@@ -797,6 +1271,13 @@ impl WorkerCapabilities {
         self.disk_free_gb.map(|free| free < min_free_gb)
     }
 
+    /// Check if worker has low available memory (below threshold).
+    /// Returns None if metrics unavailable (fail-open).
+    pub fn is_low_memory(&self, min_available_gb: f64) -> Option<bool> {
+        self.mem_available_gb
+            .map(|available| available < min_available_gb)
+    }
+
     /// Check if canonical path-topology preflight is healthy.
     ///
     /// Returns `None` when probe data is unavailable (fail-open behavior).
@@ -805,6 +1286,11 @@ impl WorkerCapabilities {
     }
 }
 
+/// How to install a transfer tool a worker is missing, for diagnostics.
+pub fn worker_tool_install_hint(tool: &str) -> String {
+    format!("install it on the worker, e.g. `sudo apt-get install -y {tool}`")
+}
+
 /// Path topology configuration.
 ///
 /// Overrides the default canonical and alias project roots used by
@@ -817,6 +1303,9 @@ impl WorkerCapabilities {
 /// canonical_root = "/Users/me/Projects"
 /// alias_root = "/Users/me/p"
 /// ```
+///
+/// On a Windows host with WSL workers, `canonical_root` may be a drive path
+/// (`C:\src`); it is translated with `wsl_mount_prefix` like the project path.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PathTopologyConfig {
     /// Canonical project root directory.
@@ -828,6 +1317,17 @@ pub struct PathTopologyConfig {
     /// Defaults to `/dp` when absent.
     #[serde(default)]
     pub alias_root: Option<String>,
+
+    /// Where WSL mounts Windows drives, used to translate `C:\...` paths on
+    /// a Windows host. Defaults to `/mnt` when absent.
+    #[serde(default)]
+    pub wsl_mount_prefix: Option<String>,
+
+    /// Keep the logical path of a project that lives under the canonical
+    /// root but is a symlink to somewhere outside it. Defaults to `false`,
+    /// which rejects such projects.
+    #[serde(default)]
+    pub keep_logical_symlinks: Option<bool>,
 }
 
 impl PathTopologyConfig {
@@ -849,10 +1349,17 @@ impl PathTopologyConfig {
             .filter(|s| !s.is_empty())
             .map(|s| shellexpand::tilde(s).into_owned())
             .unwrap_or_else(|| crate::path_topology::DEFAULT_ALIAS_PROJECT_ROOT.to_string());
+        let wsl_mount_prefix = self
+            .wsl_mount_prefix
+            .as_deref()
+            .filter(|s| !s.is_empty())
+            .unwrap_or(crate::path_topology::DEFAULT_WSL_MOUNT_PREFIX);
         crate::path_topology::PathTopologyPolicy::new(
             PathBuf::from(canonical),
             PathBuf::from(alias),
         )
+        .with_wsl_mount_prefix(PathBuf::from(wsl_mount_prefix))
+        .with_keep_logical_symlinks(self.keep_logical_symlinks.unwrap_or(false))
     }
 }
 
@@ -893,6 +1400,15 @@ pub struct RchConfig {
     /// Doctor / reliability subsystem configuration (verdict webhooks).
     #[serde(default)]
     pub doctor: DoctorConfig,
+    /// Slow-build completion notifications (hook).
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+    /// Worker telemetry sampling during remote builds (hook).
+    #[serde(default)]
+    pub telemetry: TelemetryConfig,
+    /// Log redaction rules added to the built-in secret masking.
+    #[serde(default)]
+    pub logging: LoggingConfig,
     /// Session-history remediation knobs: the central schema and default policy
     /// for temporary bypass, auto-rejoin, reconciliation, proof, incident
     /// ledger, build-root, pooled targets, telemetry freshness, log retention,
@@ -901,6 +1417,92 @@ pub struct RchConfig {
     pub remediation: crate::remediation_config::RemediationConfig,
 }
 
+/// Log redaction settings (`[logging]`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LoggingConfig {
+    /// Extra regexes whose matches are replaced with `***` wherever commands
+    /// are logged, after the built-in token/password rules. Entries that do
+    /// not compile are skipped with a warning.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mask_patterns: Vec<String>,
+}
+
+/// Notification sent when a remote build runs longer than a threshold.
+///
+/// Nothing fires until `webhook_url` or `command` is set. Delivery is
+/// best-effort: failures are logged and never change the build's result.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NotificationsConfig {
+    /// Builds taking at least this long (seconds, sync included) notify.
+    #[serde(default = "default_notify_threshold_secs")]
+    pub threshold_secs: u64,
+    /// URL that receives a JSON POST (`project`, `worker`, `duration_ms`,
+    /// `exit_code`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+    /// Shell command to run, with the same fields in `RCH_NOTIFY_*` env vars.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Upper bound on the webhook POST (milliseconds).
+    #[serde(default = "default_notify_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            threshold_secs: default_notify_threshold_secs(),
+            webhook_url: None,
+            command: None,
+            timeout_ms: default_notify_timeout_ms(),
+        }
+    }
+}
+
+fn default_notify_threshold_secs() -> u64 {
+    300
+}
+
+fn default_notify_timeout_ms() -> u64 {
+    2000
+}
+
+/// Telemetry sampled on the worker while a remote build runs.
+///
+/// Every build already reports one snapshot when it finishes. With
+/// `streaming`, a background `rch-telemetry` sampler on the worker also
+/// reports one every `streaming_interval_secs`, so the daemon sees live load
+/// during long builds.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TelemetryConfig {
+    /// Sample telemetry periodically during the build.
+    #[serde(default)]
+    pub streaming: bool,
+    /// Seconds between streamed samples.
+    #[serde(default = "default_telemetry_streaming_interval_secs")]
+    pub streaming_interval_secs: u64,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            streaming: false,
+            streaming_interval_secs: default_telemetry_streaming_interval_secs(),
+        }
+    }
+}
+
+impl TelemetryConfig {
+    /// Sampling interval for a streamed build, or `None` when streaming is off.
+    pub fn streaming_interval(&self) -> Option<u64> {
+        (self.streaming && self.streaming_interval_secs > 0).then_some(self.streaming_interval_secs)
+    }
+}
+
+fn default_telemetry_streaming_interval_secs() -> u64 {
+    30
+}
+
 /// Doctor reliability subsystem configuration.
 ///
 /// Currently carries the verdict-transition webhook surface. Kept as its own
@@ -1192,12 +1794,30 @@ pub struct GeneralConfig {
     /// structural safety checks and NEVER_INTERCEPT patterns.
     #[serde(default)]
     pub force_remote: bool,
+    /// Ask on the controlling terminal before a project's first offload.
+    ///
+    /// Declining runs the build locally and remembers the choice for that
+    /// project. In non-interactive (piped, JSON, CI) contexts nobody can
+    /// confirm, so the build runs locally without prompting.
+    #[serde(default)]
+    pub confirm_first_offload: bool,
+    /// Also run every offloaded build locally and compare the outcomes.
+    ///
+    /// The local run is authoritative (its output, artifacts and exit code
+    /// are what the caller sees); the remote run is silent and only checked
+    /// for divergence. Validation tool: every build runs twice.
+    #[serde(default)]
+    pub shadow_mode: bool,
     /// Log level (trace, debug, info, warn, error).
     #[serde(default = "default_log_level")]
     pub log_level: String,
     /// Path to Unix socket for daemon communication.
     #[serde(default = "default_socket_path")]
     pub socket_path: String,
+    /// Active configuration profile (`dev`, `prod`, `test`), set with
+    /// `rch config profile`. `RCH_PROFILE` overrides it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
 }
 
 /// Environment variable passthrough configuration.
@@ -1214,8 +1834,11 @@ impl Default for GeneralConfig {
             enabled: true,
             force_local: false,
             force_remote: false,
+            confirm_first_offload: false,
+            shadow_mode: false,
             log_level: "info".to_string(),
             socket_path: default_socket_path(),
+            profile: None,
         }
     }
 }
@@ -1262,10 +1885,12 @@ impl std::str::FromStr for OutputVisibility {
 #[serde(rename_all = "lowercase")]
 pub enum ColorMode {
     /// Force color output regardless of terminal detection.
-    /// Sets CARGO_TERM_COLOR=always and similar environment variables.
+    /// Sets CARGO_TERM_COLOR=always and similar environment variables, and
+    /// passes `--color=always` to cargo. Skipped for machine output and CI.
     #[default]
     Always,
-    /// Let the remote command detect terminal capabilities (may lose colors).
+    /// Force color only when local output is colored; otherwise let the
+    /// remote command detect terminal capabilities (may lose colors).
     Auto,
     /// Disable color output entirely.
     Never,
@@ -1312,6 +1937,18 @@ pub struct OutputConfig {
     /// Controls whether ANSI color codes are preserved in remote output.
     #[serde(default = "default_color_mode")]
     pub color_mode: ColorMode,
+    /// In-memory limit for captured remote build output. Output beyond this
+    /// spills to a log file under the rch cache dir instead of being dropped.
+    #[serde(default = "default_output_max_memory_bytes")]
+    pub max_memory_bytes: usize,
+    /// Keep spilled build logs after successful builds (failed builds always
+    /// keep theirs).
+    #[serde(default)]
+    pub keep_logs: bool,
+    /// Forward remote output lines to the daemon with build heartbeats so
+    /// `rch tail <build_id>` can follow the build from another terminal.
+    #[serde(default = "default_true")]
+    pub forward_to_daemon: bool,
 }
 
 impl Default for OutputConfig {
@@ -1320,10 +1957,17 @@ impl Default for OutputConfig {
             visibility: OutputVisibility::None,
             first_run_complete: false,
             color_mode: ColorMode::default(),
+            max_memory_bytes: default_output_max_memory_bytes(),
+            keep_logs: false,
+            forward_to_daemon: true,
         }
     }
 }
 
+fn default_output_max_memory_bytes() -> usize {
+    256 * 1024
+}
+
 // ============================================================================
 // Self-Healing Configuration
 // ============================================================================
@@ -1387,6 +2031,11 @@ pub struct SelfHealingConfig {
     /// Override via `RCH_SELF_HEALING_LOG_LEVEL=debug|info|warn|error`.
     #[serde(default)]
     pub self_healing_log_level: SelfHealingLogLevel,
+    /// Directory for the hook's auto-start lock and cooldown files.
+    /// Default: `$XDG_RUNTIME_DIR/rch`, else `/tmp/rch`.
+    /// Override via `RCH_STATE_DIR=<path>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_dir: Option<String>,
 }
 
 impl Default for SelfHealingConfig {
@@ -1397,6 +2046,7 @@ impl Default for SelfHealingConfig {
             auto_start_cooldown_secs: default_autostart_cooldown_secs(),
             auto_start_timeout_secs: default_autostart_timeout_secs(),
             self_healing_log_level: SelfHealingLogLevel::default(),
+            state_dir: None,
         }
     }
 }
@@ -1410,7 +2060,12 @@ impl SelfHealingConfig {
     /// - `RCH_DAEMON_INSTALLS_HOOKS=0|1` - Control daemon auto-installing hooks
     /// - `RCH_AUTO_START_TIMEOUT_SECS=<seconds>` - Max wait for daemon start
     /// - `RCH_AUTO_START_COOLDOWN_SECS=<seconds>` - Min time between auto-starts
+    /// - `RCH_STATE_DIR=<path>` - Directory for auto-start lock/cooldown files
     pub fn with_env_overrides(mut self) -> Self {
+        if let Ok(val) = std::env::var("RCH_STATE_DIR") {
+            self.state_dir = Some(val.trim().to_string()).filter(|dir| !dir.is_empty());
+        }
+
         // Master disable switch
         if let Ok(val) = std::env::var("RCH_NO_SELF_HEALING")
             && (val == "1" || val.eq_ignore_ascii_case("true"))
@@ -1925,6 +2580,67 @@ pub struct CompilationConfig {
     /// Default: true. Set to false to disable timeout wrapping entirely.
     #[serde(default = "default_external_timeout_enabled")]
     pub external_timeout_enabled: bool,
+    /// Run unoptimized remote Cargo builds with `CARGO_INCREMENTAL=1` so they
+    /// reuse the incremental state left in the worker's target dir by earlier
+    /// builds, even on workers whose environment turns incremental off.
+    /// Optimized builds (`--release`, `--profile` other than dev/test), a
+    /// forwarded `CARGO_INCREMENTAL`, and projects whose profiles set
+    /// `incremental` are left alone. Default: false, since worker cache
+    /// cleanup removes the incremental state along with the project's target
+    /// dir.
+    #[serde(default = "default_remote_incremental")]
+    pub remote_incremental: bool,
+    /// Per-kind timeout overrides in seconds (`[compilation.timeouts]`), keyed
+    /// by kind name such as `cargo_bench` or `cargo_check`. Kinds not listed
+    /// use the build/test/bun timeouts above.
+    #[serde(default)]
+    pub timeouts: BTreeMap<String, u64>,
+    /// Keep builds local unless the selected worker's rustc matches the local
+    /// one at this granularity (`minor`, `patch` or `exact`). Unset (the
+    /// default) skips the check.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub require_rustc_match: Option<RustcMatch>,
+    /// Keep projects with fewer files than this local: for tiny crates the
+    /// sync + SSH overhead dwarfs the compile. Unset (the default) disables
+    /// the check.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_project_files: Option<u64>,
+    /// Keep projects whose files total fewer bytes than this local. Unset
+    /// (the default) disables the check.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_project_bytes: Option<u64>,
+    /// Kinds the project-size gate never applies to, by name (for example
+    /// `cargo_test`): a tiny test suite can still be worth offloading when
+    /// compiling its dependencies is the real cost.
+    #[serde(default = "default_min_project_size_exempt_kinds")]
+    pub min_project_size_exempt_kinds: Vec<String>,
+    /// Per-command confidence overrides (`[compilation.confidence_overrides]`),
+    /// keyed by a substring of the command. Consulted after classification,
+    /// before the `confidence_threshold` gate; the longest matching key wins.
+    #[serde(default)]
+    pub confidence_overrides: BTreeMap<String, ConfidenceOverride>,
+}
+
+/// Value of a `[compilation.confidence_overrides]` entry.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ConfidenceOverride {
+    /// `true` forces confidence 1.0 (always clears the gate); `false` forces
+    /// 0.0 (keeps the command local).
+    Force(bool),
+    /// Use this confidence (0.0-1.0) instead of the classifier's.
+    Confidence(f64),
+}
+
+impl ConfidenceOverride {
+    /// The confidence compared against `confidence_threshold`.
+    pub fn confidence(self) -> f64 {
+        match self {
+            Self::Force(true) => 1.0,
+            Self::Force(false) => 0.0,
+            Self::Confidence(confidence) => confidence,
+        }
+    }
 }
 
 impl Default for CompilationConfig {
@@ -1940,6 +2656,13 @@ impl Default for CompilationConfig {
             test_timeout_sec: default_test_timeout(),
             bun_timeout_sec: default_bun_timeout(),
             external_timeout_enabled: default_external_timeout_enabled(),
+            remote_incremental: default_remote_incremental(),
+            timeouts: BTreeMap::new(),
+            require_rustc_match: None,
+            min_project_files: None,
+            min_project_bytes: None,
+            min_project_size_exempt_kinds: default_min_project_size_exempt_kinds(),
+            confidence_overrides: BTreeMap::new(),
         }
     }
 }
@@ -1980,13 +2703,30 @@ fn default_external_timeout_enabled() -> bool {
     true
 }
 
+fn default_remote_incremental() -> bool {
+    false
+}
+
+/// Default: test and bench kinds are exempt from the project-size gate.
+fn default_min_project_size_exempt_kinds() -> Vec<String> {
+    CompilationKind::ALL
+        .iter()
+        .filter(|kind| kind.is_test_command())
+        .map(|kind| kind.as_str().to_string())
+        .collect()
+}
+
 impl CompilationConfig {
     /// Returns the appropriate external timeout for the given compilation kind.
     ///
+    /// - A `[compilation.timeouts]` entry for the kind wins
     /// - Bun commands get the shorter bun_timeout (to protect against known hang issues)
     /// - Test commands get the longer test_timeout
     /// - All other commands (builds, checks, clippy) get the build_timeout
     pub fn timeout_for_kind(&self, kind: Option<CompilationKind>) -> std::time::Duration {
+        if let Some(&secs) = kind.and_then(|kind| self.timeouts.get(kind.as_str())) {
+            return std::time::Duration::from_secs(secs);
+        }
         let secs = match kind {
             // Bun has known issues where internal timeout doesn't work for CPU hangs
             Some(CompilationKind::BunTest) | Some(CompilationKind::BunTypecheck) => {
@@ -2002,14 +2742,59 @@ impl CompilationConfig {
         std::time::Duration::from_secs(secs)
     }
 
+    /// The confidence override for `command`: the entry whose key is the
+    /// longest substring of it, with that key. Empty keys never match.
+    pub fn confidence_override(&self, command: &str) -> Option<(&str, ConfidenceOverride)> {
+        self.confidence_overrides
+            .iter()
+            .filter(|(pattern, _)| !pattern.is_empty() && command.contains(pattern.as_str()))
+            .max_by_key(|(pattern, _)| pattern.len())
+            .map(|(pattern, value)| (pattern.as_str(), *value))
+    }
+
     /// Returns whether external timeout wrapping is enabled.
     pub fn external_timeout_enabled(&self) -> bool {
         self.external_timeout_enabled
     }
+
+    /// Whether the `min_project_files` / `min_project_bytes` gate applies to
+    /// `kind`: at least one threshold is set and the kind is not exempt.
+    pub fn project_size_gate_applies(&self, kind: Option<CompilationKind>) -> bool {
+        (self.min_project_files.is_some() || self.min_project_bytes.is_some())
+            && !kind.is_some_and(|kind| {
+                self.min_project_size_exempt_kinds
+                    .iter()
+                    .any(|name| name == kind.as_str())
+            })
+    }
+}
+
+/// File sync backend used by the transfer pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferBackend {
+    /// rsync over SSH with zstd compression.
+    #[default]
+    Rsync,
+    /// rclone (e.g. to sync through object storage). Not implemented yet:
+    /// selecting it makes every sync fail, so builds fall back to local.
+    Rclone,
+}
+
+impl std::fmt::Display for TransferBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rsync => write!(f, "rsync"),
+            Self::Rclone => write!(f, "rclone"),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransferConfig {
+    /// Backend that moves files to and from workers. Defaults to rsync.
+    #[serde(default)]
+    pub backend: TransferBackend,
     /// zstd compression level (1-19).
     #[serde(default = "default_compression")]
     pub compression_level: u32,
@@ -2036,9 +2821,9 @@ pub struct TransferConfig {
     pub retry: RetryConfig,
     /// Verify artifact integrity using blake3 hashes after transfer (bd-377q).
     ///
-    /// When enabled, computes blake3 hashes of key artifacts (binaries, test outputs)
-    /// on the worker after build, then verifies them locally after download.
-    /// Disabled by default to avoid extra overhead.
+    /// When enabled, hashes a sample of the retrieved artifacts on the worker
+    /// (`b3sum` over SSH) and locally, and fails the artifact step (not the
+    /// build) on any mismatch. Disabled by default to avoid extra overhead.
     #[serde(default)]
     pub verify_artifacts: bool,
     /// Maximum file size (bytes) for artifact verification (bd-377q).
@@ -2047,6 +2832,38 @@ pub struct TransferConfig {
     /// excessive I/O. Defaults to 100MB.
     #[serde(default = "default_verify_max_size")]
     pub verify_max_size_bytes: u64,
+    /// How many retrieved files to verify, most recently modified first.
+    ///
+    /// `0` verifies every retrieved file. Defaults to 32.
+    #[serde(default = "default_verify_sample_files")]
+    pub verify_sample_files: usize,
+
+    /// Paths artifact retrieval must never write (rsync exclude patterns,
+    /// relative to the destination root: the project, or the forwarded
+    /// `CARGO_TARGET_DIR`). Empty by default.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artifact_deny_patterns: Vec<String>,
+    /// Retrieve artifacts with rsync `--update`, so local files newer than
+    /// the worker's copy (e.g. edited during a long build) are kept.
+    #[serde(default)]
+    pub preserve_newer_local: bool,
+    /// Retrieve artifacts as one tar+zstd archive built on the worker instead
+    /// of rsync's per-file transfer.
+    ///
+    /// Trades worker and client CPU for far fewer round-trips, which pays off
+    /// on high-latency links when a build leaves thousands of small files. The
+    /// same artifact filters and `preserve_newer_local` rule apply; there is
+    /// no per-file progress. Requires GNU tar on the worker.
+    #[serde(default)]
+    pub artifact_archive: bool,
+    /// Extra attempts at artifact retrieval after a transient rsync failure.
+    ///
+    /// The build already succeeded, so re-running the download is cheap
+    /// compared with leaving the user without their binary. Only transport
+    /// drops and partial transfers are retried; a retrieval that found
+    /// nothing to transfer is not. `0` disables. Defaults to 2.
+    #[serde(default = "default_artifact_max_retries")]
+    pub artifact_max_retries: u32,
 
     // =========================================================================
     // Transfer Optimization (bd-3hho)
@@ -2059,6 +2876,15 @@ pub struct TransferConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_transfer_mb: Option<u64>,
 
+    /// Hard ceiling on the project size in bytes.
+    ///
+    /// Unlike `max_transfer_mb`, this is not an rsync estimate: the project
+    /// root is walked (skipping `exclude_patterns` directories) before any
+    /// worker is contacted, and a project over the cap never offloads, even
+    /// with `force_remote`. Set to `None` (default) to disable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_project_bytes: Option<u64>,
+
     /// Maximum estimated transfer time in milliseconds before skipping remote.
     ///
     /// Uses `estimated_bandwidth_bps` (or measured link speed) to calculate
@@ -2069,11 +2895,21 @@ pub struct TransferConfig {
 
     /// Bandwidth limit for rsync in KB/s.
     ///
-    /// Passed as `rsync --bwlimit=<N>` to limit transfer speed and prevent
-    /// network saturation. Set to `None` or `0` (default) for unlimited.
+    /// Passed as `rsync --bwlimit=<N>` to every upload and download to limit
+    /// transfer speed and prevent network saturation. The limit applies per
+    /// rsync invocation; sharded test runs split it across their concurrent
+    /// syncs. Set to `None` or `0` (default) for unlimited.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub bwlimit_kbps: Option<u64>,
 
+    /// Soft limit on concurrent source uploads from this machine.
+    ///
+    /// Independent `rch exec` processes coordinate through lock files in the
+    /// client state directory; a build that finds every slot taken waits for
+    /// one before uploading. Set to `None` or `0` (default) for unlimited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_client_transfers: Option<u32>,
+
     /// Estimated link bandwidth in bytes per second.
     ///
     /// Used for transfer time estimation when `max_transfer_time_ms` is set.
@@ -2081,6 +2917,43 @@ pub struct TransferConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub estimated_bandwidth_bps: Option<u64>,
 
+    /// Maximum estimated sync time in seconds over the measured link.
+    ///
+    /// The daemon measures upload bandwidth to each worker from completed
+    /// syncs and reports it (with the health-check RTT) on selection. When a
+    /// measurement exists, the estimated sync time (`size / bandwidth + RTT`)
+    /// decides the skip instead of `max_transfer_mb` / `max_transfer_time_ms`;
+    /// without one, those thresholds apply as before. `None` (default) disables.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_sync_seconds: Option<u64>,
+
+    /// Named worker fleet to build on (`[[fleet.<name>.workers]]` in
+    /// workers.toml).
+    ///
+    /// Usually set per project in `.rch/config.toml`. `None` (default) selects
+    /// from every configured worker.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fleet: Option<String>,
+
+    /// Minimum free disk space (GB) a worker must report to receive a build.
+    ///
+    /// Workers whose last health probe reported less free space are skipped
+    /// during selection, so a full `/tmp` doesn't fail a build halfway with
+    /// ENOSPC. Workers without a disk reading are not excluded. `0` disables.
+    #[serde(default = "default_min_free_disk_gb")]
+    pub min_free_disk_gb: f64,
+
+    /// Container image to run builds in on the worker (e.g.
+    /// `rust:1.85-bookworm`).
+    ///
+    /// When set, the worker runs the command with `docker run` (or `podman
+    /// run`), bind-mounting the synced project at the same path, so
+    /// artifacts land on the host and come back the usual way. Usually set
+    /// per project in `.rch/config.toml`; `RCH_CONTAINER_IMAGE` overrides it
+    /// for one command. `None` (default) runs builds directly on the worker.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container_image: Option<String>,
+
     // =========================================================================
     // Adaptive Compression (bd-243w)
     // =========================================================================
@@ -2109,11 +2982,83 @@ pub struct TransferConfig {
     /// Defaults to 9 (avoids CPU-intensive levels 10+).
     #[serde(default = "default_max_compression")]
     pub max_compression_level: u32,
+
+    // =========================================================================
+    // .gitignore-aware filtering
+    // =========================================================================
+    /// Skip files ignored by the project's `.gitignore` files when syncing.
+    ///
+    /// Adds an rsync per-directory merge of `.gitignore` after
+    /// `exclude_patterns`, so entries there (e.g. `target/`) stay excluded no
+    /// matter what `.gitignore` says. Git `!` negations are not applied.
+    #[serde(default)]
+    pub respect_gitignore: bool,
+
+    /// Gitignored paths that must still be synced (rsync include patterns),
+    /// checked before the `.gitignore` rules. Empty by default.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gitignore_include: Vec<String>,
+
+    /// Paths that are always synced (rsync include patterns), even when
+    /// `exclude_patterns`, `.rchignore` or `.gitignore` would skip them.
+    /// Emitted before every exclude rule. Empty by default.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub force_include: Vec<String>,
+
+    /// Share one cargo build dir per worker across projects.
+    ///
+    /// Cargo intermediates (`CARGO_BUILD_BUILD_DIR`) go to a directory under
+    /// `remote_base` keyed by toolchain and target triple, so dependencies
+    /// already built for another project are reused. Final artifacts stay in
+    /// the project's own remote target dir and are retrieved as usual.
+    /// Requires cargo 1.91 or newer on the worker.
+    #[serde(default)]
+    pub shared_target_dir: bool,
+
+    /// Pin the remote project dir when a build exits non-zero.
+    ///
+    /// Writes a `.rch-keep` marker that the daemon's cache cleanup respects
+    /// for `keep_remote_hours`, and prints `user@host:path` so the failed
+    /// state can be inspected over SSH.
+    #[serde(default)]
+    pub keep_remote_on_failure: bool,
+
+    /// How long a pinned remote dir is kept (hours). Defaults to 24.
+    #[serde(default = "default_keep_remote_hours")]
+    pub keep_remote_hours: u64,
+
+    /// Run remote builds detached from the SSH session.
+    ///
+    /// The build survives the client disconnecting; its output and exit code
+    /// are kept on the worker so `rch attach <build_id>` can reconnect, and
+    /// the daemon releases its slots once it finishes.
+    #[serde(default)]
+    pub detach_remote_builds: bool,
+
+    /// Compile through sccache on workers that have it.
+    ///
+    /// Sets `RUSTC_WRAPPER=sccache` for remote builds, with one
+    /// `SCCACHE_DIR` per worker under `remote_base` shared by all projects.
+    /// Workers whose capabilities probe found no sccache build without it
+    /// (with a warning).
+    #[serde(default)]
+    pub use_sccache: bool,
+
+    /// Compilation kinds whose artifacts are never retrieved (names as in
+    /// [`CompilationKind::as_str`]).
+    ///
+    /// `cargo check` and `cargo clippy` only produce diagnostics, so by
+    /// default the retrieval step is skipped for them entirely. Set this to
+    /// `[]` in a project config for incremental-check workflows that want the
+    /// check metadata synced back.
+    #[serde(default = "default_skip_artifacts_for")]
+    pub skip_artifacts_for: Vec<String>,
 }
 
 impl Default for TransferConfig {
     fn default() -> Self {
         Self {
+            backend: TransferBackend::default(),
             compression_level: 3,
             exclude_patterns: default_excludes(),
             ssh_server_alive_interval_secs: None,
@@ -2122,20 +3067,68 @@ impl Default for TransferConfig {
             retry: RetryConfig::default(),
             verify_artifacts: false,
             verify_max_size_bytes: default_verify_max_size(),
+            verify_sample_files: default_verify_sample_files(),
+            artifact_deny_patterns: Vec::new(),
+            preserve_newer_local: false,
+            artifact_archive: false,
+            artifact_max_retries: default_artifact_max_retries(),
             // Transfer optimization (bd-3hho)
             max_transfer_mb: None,
+            max_project_bytes: None,
             max_transfer_time_ms: None,
             bwlimit_kbps: None,
+            max_concurrent_client_transfers: None,
             estimated_bandwidth_bps: None,
+            max_sync_seconds: None,
+            fleet: None,
+            min_free_disk_gb: default_min_free_disk_gb(),
+            container_image: None,
             // Adaptive compression (bd-243w)
             adaptive_compression: false,
             min_compression_level: default_min_compression(),
             max_compression_level: default_max_compression(),
+            respect_gitignore: false,
+            gitignore_include: Vec::new(),
+            force_include: Vec::new(),
+            shared_target_dir: false,
+            keep_remote_on_failure: false,
+            keep_remote_hours: default_keep_remote_hours(),
+            detach_remote_builds: false,
+            use_sccache: false,
+            skip_artifacts_for: default_skip_artifacts_for(),
         }
     }
 }
 
+/// Default: check and clippy leave nothing worth retrieving.
+fn default_skip_artifacts_for() -> Vec<String> {
+    [CompilationKind::CargoCheck, CompilationKind::CargoClippy]
+        .iter()
+        .map(|kind| kind.as_str().to_string())
+        .collect()
+}
+
+fn default_min_free_disk_gb() -> f64 {
+    5.0
+}
+
 impl TransferConfig {
+    /// Minimum free disk (GB) to request from the daemon, or `None` when
+    /// the check is disabled.
+    pub fn min_free_disk_requirement(&self) -> Option<f64> {
+        (self.min_free_disk_gb > 0.0).then_some(self.min_free_disk_gb)
+    }
+
+    /// Whether artifact retrieval is skipped for `kind`
+    /// (`skip_artifacts_for`).
+    pub fn skips_artifacts_for(&self, kind: Option<CompilationKind>) -> bool {
+        kind.is_some_and(|kind| {
+            self.skip_artifacts_for
+                .iter()
+                .any(|name| name == kind.as_str())
+        })
+    }
+
     /// Select compression level based on estimated transfer size (bd-243w).
     ///
     /// When adaptive compression is enabled, selects an appropriate level
@@ -2396,6 +3389,32 @@ pub fn validate_remote_base(path: &str) -> Result<String, String> {
     Ok(normalized)
 }
 
+/// Validate a container image reference (`[registry[:port]/]name[:tag][@digest]`).
+///
+/// Only the characters image references use are accepted, so the value can
+/// never smuggle options or shell syntax into the worker's `docker run`.
+pub fn validate_container_image(image: &str) -> Result<(), String> {
+    if image.is_empty() {
+        return Err("container image must not be empty".to_string());
+    }
+    if image.starts_with(['-', '.', '/', ':', '@']) {
+        return Err(format!(
+            "container image must start with a letter or digit, got: {}",
+            image
+        ));
+    }
+    if let Some(bad) = image
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | '/' | ':' | '@')))
+    {
+        return Err(format!(
+            "container image contains invalid character {:?}: {}",
+            bad, image
+        ));
+    }
+    Ok(())
+}
+
 fn default_circuit_failure_threshold() -> u32 {
     3
 }
@@ -2477,6 +3496,16 @@ fn default_verify_max_size() -> u64 {
     100 * 1024 * 1024 // 100MB
 }
 
+/// Default number of retrieved files sampled for artifact verification.
+fn default_verify_sample_files() -> usize {
+    32
+}
+
+/// Default number of artifact retrieval retries after a transient failure.
+fn default_artifact_max_retries() -> u32 {
+    2
+}
+
 /// Default minimum compression level for adaptive mode.
 fn default_min_compression() -> u32 {
     1
@@ -2487,6 +3516,10 @@ fn default_max_compression() -> u32 {
     9
 }
 
+fn default_keep_remote_hours() -> u64 {
+    24
+}
+
 fn default_excludes() -> Vec<String> {
     vec![
         // Rust build artifacts
@@ -2627,6 +3660,15 @@ pub struct BuildRecord {
     /// Structured cancellation metadata for cancelled builds.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cancellation: Option<BuildCancellationMetadata>,
+    /// Ordered decisions that led to this build's outcome (`rch explain`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub decision_trace: Vec<DecisionTraceStep>,
+    /// Local project root the build ran from (`rch replay`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_path: Option<String>,
+    /// Rust toolchain the build used, as a rustup name (`rch replay`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toolchain: Option<String>,
 }
 
 /// Input payload for recording a completed build.
@@ -2656,6 +3698,15 @@ pub struct BuildRecordInput {
     /// Structured cancellation metadata for cancelled builds.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cancellation: Option<BuildCancellationMetadata>,
+    /// Ordered decisions that led to this build's outcome (`rch explain`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub decision_trace: Vec<DecisionTraceStep>,
+    /// Local project root the build ran from (`rch replay`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_path: Option<String>,
+    /// Rust toolchain the build used, as a rustup name (`rch replay`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toolchain: Option<String>,
 }
 
 impl BuildRecordInput {
@@ -2674,6 +3725,9 @@ impl BuildRecordInput {
             bytes_transferred: self.bytes_transferred,
             timing: self.timing,
             cancellation: self.cancellation,
+            decision_trace: self.decision_trace,
+            project_path: self.project_path,
+            toolchain: self.toolchain,
         }
     }
 }
@@ -2695,6 +3749,22 @@ pub struct BuildStats {
     pub avg_duration_ms: u64,
 }
 
+/// Build history aggregated over one group (`rch history --group-by`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildHistoryGroup {
+    /// Group key: project ID, worker ID (`local` for local builds), or
+    /// compilation kind.
+    pub key: String,
+    /// Builds in the group.
+    pub builds: usize,
+    /// Builds that failed (same rule as [`BuildStats::failure_count`]).
+    pub failures: usize,
+    /// Median build duration in milliseconds.
+    pub median_duration_ms: u64,
+    /// Total bytes transferred by the group's remote builds.
+    pub total_bytes: u64,
+}
+
 /// Saved time statistics from remote builds.
 ///
 /// Tracks estimated time savings from offloading builds to remote workers.
@@ -2719,6 +3789,27 @@ pub struct SavedTimeStats {
     pub week_saved_ms: u64,
 }
 
+/// How much faster remote rebuilds run when the worker still holds the
+/// project's incremental state.
+///
+/// A successful remote build is *warm* when the previous successful remote run
+/// of the same command, project and toolchain was on the same worker, and
+/// *cold* when it was on another worker (the state stayed behind). First runs
+/// are not counted.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IncrementalReuseStats {
+    /// Rebuilds that landed on the worker holding their incremental state.
+    pub warm_builds: usize,
+    /// Rebuilds that landed on another worker.
+    pub cold_builds: usize,
+    /// Average warm rebuild duration (milliseconds).
+    pub warm_avg_ms: u64,
+    /// Average cold rebuild duration (milliseconds).
+    pub cold_avg_ms: u64,
+    /// `cold_avg_ms / warm_avg_ms`; 0.0 until both kinds have been seen.
+    pub rebuild_speedup: f64,
+}
+
 // ============================================================================
 // Compilation Timing and Metrics
 // ============================================================================
@@ -3077,6 +4168,49 @@ mod tests {
     use super::*;
     use crate::test_guard;
 
+    #[test]
+    fn test_health_failure_reason_from_ssh_error() {
+        let _guard = test_guard!();
+        let cases = [
+            (
+                "ssh: connect to host 10.0.0.2 port 22: Connection refused",
+                HealthFailureReason::ConnectionRefused,
+            ),
+            (
+                "user@10.0.0.2: Permission denied (publickey).",
+                HealthFailureReason::AuthFailed,
+            ),
+            (
+                "Host key verification failed.",
+                HealthFailureReason::AuthFailed,
+            ),
+            (
+                "ssh: connect to host 10.0.0.2 port 22: Connection timed out",
+                HealthFailureReason::Timeout,
+            ),
+            (
+                "ssh: Could not resolve hostname worker9: Name or service not known",
+                HealthFailureReason::HostUnreachable,
+            ),
+            ("broken pipe", HealthFailureReason::Other),
+        ];
+        for (message, expected) in cases {
+            assert_eq!(
+                HealthFailureReason::from_ssh_error(message),
+                expected,
+                "{message}"
+            );
+        }
+        assert_eq!(
+            serde_json::to_string(&HealthFailureReason::AuthFailed).unwrap(),
+            "\"auth_failed\""
+        );
+        assert_eq!(
+            HealthFailureReason::AuthFailed.to_string(),
+            "ssh auth failed"
+        );
+    }
+
     /// Verdict-webhook config must NEVER carry an inline secret. Operators
     /// reference secrets by env-var name (`*_env`); the resolved value is
     /// read at dispatch time and never persisted to the cache or config TOML.
@@ -3303,6 +4437,17 @@ retry_max = 2
         assert!(caps.has_node());
     }
 
+    #[test]
+    fn test_worker_capabilities_has_toolchain() {
+        let _guard = test_guard!();
+        let mut caps = WorkerCapabilities::new();
+        assert_eq!(caps.has_toolchain("stable"), None);
+
+        caps.installed_toolchains = vec!["stable".to_string(), "nightly-2024-01-15".to_string()];
+        assert_eq!(caps.has_toolchain("nightly-2024-01-15"), Some(true));
+        assert_eq!(caps.has_toolchain("nightly"), Some(false));
+    }
+
     #[test]
     fn test_worker_capabilities_multiple_runtimes() {
         let _guard = test_guard!();
@@ -3429,6 +4574,10 @@ retry_max = 2
             serde_json::to_string(&SelectionReason::AllWorkersUnreachable).unwrap(),
             "\"all_workers_unreachable\""
         );
+        assert_eq!(
+            serde_json::to_string(&SelectionReason::AllWorkersDraining).unwrap(),
+            "\"all_workers_draining\""
+        );
         assert_eq!(
             serde_json::to_string(&SelectionReason::AllCircuitsOpen).unwrap(),
             "\"all_circuits_open\""
@@ -3449,6 +4598,22 @@ retry_max = 2
             serde_json::to_string(&SelectionReason::AllWorkersFailedConvergence).unwrap(),
             "\"all_workers_failed_convergence\""
         );
+        assert_eq!(
+            serde_json::to_string(&SelectionReason::InsufficientDiskSpace).unwrap(),
+            "\"insufficient_disk_space\""
+        );
+        assert_eq!(
+            serde_json::to_string(&SelectionReason::InsufficientMemory).unwrap(),
+            "\"insufficient_memory\""
+        );
+        assert_eq!(
+            serde_json::to_string(&SelectionReason::WorkersMissingRsync).unwrap(),
+            "\"workers_missing_rsync\""
+        );
+        assert_eq!(
+            serde_json::to_string(&SelectionReason::CoalescedDuplicate).unwrap(),
+            "\"coalesced_duplicate\""
+        );
         assert_eq!(
             serde_json::to_string(&SelectionReason::NoAdmissibleWorkers(
                 "critical_pressure=1,insufficient_slots=1".to_string()
@@ -3518,10 +4683,22 @@ retry_max = 2
             SelectionReason::AllWorkersUnreachable.to_string(),
             "all workers unreachable"
         );
+        assert_eq!(
+            SelectionReason::AllWorkersDraining.to_string(),
+            "all workers draining or disabled"
+        );
         assert_eq!(
             SelectionReason::AllWorkersBusy.to_string(),
             "all workers at capacity"
         );
+        assert_eq!(
+            SelectionReason::WorkerBuildCapReached.to_string(),
+            "workers at concurrent build cap"
+        );
+        assert_eq!(
+            SelectionReason::WorkerBlockedAfterFailures.to_string(),
+            "workers blocked after repeated build failures"
+        );
         assert_eq!(
             SelectionReason::NoWorkersPassedHealth.to_string(),
             "no workers passed health thresholds"
@@ -3534,6 +4711,22 @@ retry_max = 2
             SelectionReason::AllWorkersFailedConvergence.to_string(),
             "all workers failed repo convergence checks"
         );
+        assert_eq!(
+            SelectionReason::InsufficientDiskSpace.to_string(),
+            "all workers below minimum free disk space"
+        );
+        assert_eq!(
+            SelectionReason::InsufficientMemory.to_string(),
+            "all workers below minimum available memory"
+        );
+        assert_eq!(
+            SelectionReason::WorkersMissingRsync.to_string(),
+            "all workers missing rsync (install it on the worker, e.g. `sudo apt-get install -y rsync`)"
+        );
+        assert_eq!(
+            SelectionReason::CoalescedDuplicate.to_string(),
+            "duplicate of an in-flight build, waiting on its result"
+        );
         assert_eq!(
             SelectionReason::NoAdmissibleWorkers(
                 "critical_pressure=1,insufficient_slots=1".to_string()
@@ -3558,6 +4751,13 @@ retry_max = 2
                 identity_file: "~/.ssh/id_rsa".to_string(),
                 slots_available: 8,
                 speed_score: 75.0,
+                upload_bandwidth_bps: None,
+                rtt_ms: None,
+                rustc_version: None,
+                sccache_version: None,
+                missing_tools: vec![],
+                nice_level: None,
+                ionice_class: None,
             }),
             reason: SelectionReason::Success,
             build_id: None,
@@ -3651,6 +4851,13 @@ retry_max = 2
                 identity_file: "/path/to/key".to_string(),
                 slots_available: 16,
                 speed_score: 90.5,
+                upload_bandwidth_bps: None,
+                rtt_ms: None,
+                rustc_version: None,
+                sccache_version: None,
+                missing_tools: vec![],
+                nice_level: None,
+                ionice_class: None,
             }),
             reason: SelectionReason::Success,
             build_id: None,
@@ -3685,10 +4892,12 @@ retry_max = 2
             worker_id: WorkerId::new("worker-a"),
             hook_pid: Some(12345),
             remote_pgid_file: Some("/tmp/rch/project/hash/.rch-run/42.pgid".to_string()),
+            detached: false,
             phase: BuildHeartbeatPhase::Execute,
             detail: Some("Compiling crates".to_string()),
             progress_counter: Some(7),
             progress_percent: Some(42.5),
+            output_lines: vec!["   Compiling foo v0.1.0".to_string()],
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -3702,6 +4911,7 @@ retry_max = 2
         assert_eq!(parsed.phase, BuildHeartbeatPhase::Execute);
         assert_eq!(parsed.progress_counter, Some(7));
         assert_eq!(parsed.progress_percent, Some(42.5));
+        assert_eq!(parsed.output_lines, vec!["   Compiling foo v0.1.0"]);
     }
 
     // CircuitStats tests
@@ -4083,6 +5293,54 @@ retry_max = 2
         assert_eq!(parsed.total, timing.total);
     }
 
+    #[test]
+    fn test_release_worker_body_accepts_legacy_timing() {
+        let _guard = test_guard!();
+        let legacy_json = serde_json::to_string(&CommandTimingBreakdown {
+            exec: Some(Duration::from_millis(900)),
+            ..Default::default()
+        })
+        .unwrap();
+        let legacy = ReleaseWorkerBody::parse(&legacy_json).unwrap();
+        assert_eq!(
+            legacy.timing.and_then(|timing| timing.exec),
+            Some(Duration::from_millis(900))
+        );
+        assert!(legacy.decision_trace.is_empty());
+
+        let body = ReleaseWorkerBody {
+            timing: Some(CommandTimingBreakdown {
+                exec: Some(Duration::from_millis(5)),
+                ..Default::default()
+            }),
+            decision_trace: vec![DecisionTraceStep::new(
+                crate::DecisionStage::Execution,
+                "exit 0",
+            )],
+            ..Default::default()
+        };
+        let parsed = ReleaseWorkerBody::parse(&serde_json::to_string(&body).unwrap()).unwrap();
+        assert_eq!(
+            parsed.timing.and_then(|timing| timing.exec),
+            Some(Duration::from_millis(5))
+        );
+        assert_eq!(parsed.decision_trace, body.decision_trace);
+
+        let replay = ReleaseWorkerBody {
+            project_path: Some("/data/projects/app".to_string()),
+            toolchain: Some("nightly-2026-01-15".to_string()),
+            ..Default::default()
+        };
+        assert!(!replay.is_empty());
+        let parsed = ReleaseWorkerBody::parse(&serde_json::to_string(&replay).unwrap()).unwrap();
+        assert!(parsed.timing.is_none());
+        assert_eq!(parsed.project_path.as_deref(), Some("/data/projects/app"));
+        assert_eq!(parsed.toolchain.as_deref(), Some("nightly-2026-01-15"));
+
+        assert!(ReleaseWorkerBody::parse("not json").is_none());
+        assert!(ReleaseWorkerBody::default().is_empty());
+    }
+
     #[test]
     fn test_compilation_metrics_calculate_speedup() {
         let _guard = test_guard!();
@@ -4377,6 +5635,80 @@ retry_max = 2
         );
     }
 
+    #[test]
+    fn test_compilation_config_per_kind_timeout_overrides() {
+        let _guard = test_guard!();
+        let config: CompilationConfig = toml::from_str(
+            "test_timeout_sec = 900\n\
+             [timeouts]\n\
+             cargo_bench = 1800\n\
+             cargo_check = 120\n",
+        )
+        .unwrap();
+
+        // Listed kinds use their override.
+        assert_eq!(
+            config.timeout_for_kind(Some(crate::CompilationKind::CargoBench)),
+            std::time::Duration::from_secs(1800)
+        );
+        assert_eq!(
+            config.timeout_for_kind(Some(crate::CompilationKind::CargoCheck)),
+            std::time::Duration::from_secs(120)
+        );
+        // Everything else falls back to the category timeouts.
+        assert_eq!(
+            config.timeout_for_kind(Some(crate::CompilationKind::CargoTest)),
+            std::time::Duration::from_secs(900)
+        );
+        assert_eq!(
+            config.timeout_for_kind(Some(crate::CompilationKind::CargoBuild)),
+            std::time::Duration::from_secs(300)
+        );
+        assert_eq!(
+            config.timeout_for_kind(None),
+            std::time::Duration::from_secs(300)
+        );
+    }
+
+    #[test]
+    fn test_compilation_config_project_size_gate_is_kind_aware() {
+        let _guard = test_guard!();
+        use crate::CompilationKind;
+
+        let config = CompilationConfig::default();
+        assert!(!config.project_size_gate_applies(Some(CompilationKind::CargoBuild)));
+
+        let config: CompilationConfig = toml::from_str("min_project_files = 20\n").unwrap();
+        assert!(config.project_size_gate_applies(Some(CompilationKind::CargoBuild)));
+        assert!(config.project_size_gate_applies(None));
+        // Test kinds are exempt by default.
+        assert!(!config.project_size_gate_applies(Some(CompilationKind::CargoTest)));
+        assert!(!config.project_size_gate_applies(Some(CompilationKind::CargoNextest)));
+
+        let config: CompilationConfig = toml::from_str(
+            "min_project_bytes = 4096\n\
+             min_project_size_exempt_kinds = [\"cargo_check\"]\n",
+        )
+        .unwrap();
+        assert!(config.project_size_gate_applies(Some(CompilationKind::CargoTest)));
+        assert!(!config.project_size_gate_applies(Some(CompilationKind::CargoCheck)));
+    }
+
+    #[test]
+    fn test_transfer_config_skips_artifacts_for_check_and_clippy() {
+        let _guard = test_guard!();
+        use crate::CompilationKind;
+
+        let config = TransferConfig::default();
+        assert!(config.skips_artifacts_for(Some(CompilationKind::CargoCheck)));
+        assert!(config.skips_artifacts_for(Some(CompilationKind::CargoClippy)));
+        assert!(!config.skips_artifacts_for(Some(CompilationKind::CargoBuild)));
+        assert!(!config.skips_artifacts_for(None));
+
+        let config: TransferConfig = toml::from_str("skip_artifacts_for = []\n").unwrap();
+        assert!(!config.skips_artifacts_for(Some(CompilationKind::CargoCheck)));
+    }
+
     #[test]
     fn test_compilation_config_speedup_threshold_default() {
         let _guard = test_guard!();
@@ -4428,10 +5760,91 @@ retry_max = 2
         assert_eq!(parsed.min_local_time_ms, 5000);
     }
 
+    #[test]
+    fn test_compilation_config_confidence_override_longest_match() {
+        let _guard = test_guard!();
+        let config: CompilationConfig = toml::from_str(
+            r#"
+[confidence_overrides]
+"cargo build -p weird-crate" = 0.95
+"weird-crate" = false
+"cargo build" = true
+"" = true
+"#,
+        )
+        .unwrap();
+
+        let (pattern, value) = config
+            .confidence_override("cargo build -p weird-crate --release")
+            .unwrap();
+        assert_eq!(pattern, "cargo build -p weird-crate");
+        assert_eq!(value, ConfidenceOverride::Confidence(0.95));
+        assert!((value.confidence() - 0.95).abs() < f64::EPSILON);
+
+        let (_, value) = config
+            .confidence_override("cargo test -p weird-crate")
+            .unwrap();
+        assert_eq!(value, ConfidenceOverride::Force(false));
+        assert_eq!(value.confidence(), 0.0);
+
+        let (_, value) = config
+            .confidence_override("cargo build --workspace")
+            .unwrap();
+        assert_eq!(value.confidence(), 1.0);
+
+        // The empty key never matches, so unrelated commands keep their score.
+        assert!(config.confidence_override("cargo check").is_none());
+        assert!(
+            CompilationConfig::default()
+                .confidence_override("cargo build")
+                .is_none()
+        );
+    }
+
     // ========================================================================
     // validate_remote_base Tests
     // ========================================================================
 
+    #[test]
+    fn test_worker_rsync_ssh_program_uses_agent_without_identity() {
+        let _guard = test_guard!();
+        let mut worker = WorkerConfig {
+            identity_file: "/keys/my key".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(worker.rsync_ssh_program(), "ssh -i '/keys/my key'");
+        worker.identity_file = "  ".to_string();
+        assert_eq!(worker.rsync_ssh_program(), "ssh");
+    }
+
+    #[test]
+    fn test_validate_container_image_accepts_references() {
+        let _guard = test_guard!();
+        for image in [
+            "rust",
+            "rust:1.85-bookworm",
+            "ghcr.io/org/builder:latest",
+            "registry.local:5000/team/img@sha256:abc123",
+        ] {
+            assert!(validate_container_image(image).is_ok(), "{image}");
+        }
+    }
+
+    #[test]
+    fn test_validate_container_image_rejects_options_and_shell() {
+        let _guard = test_guard!();
+        for image in [
+            "",
+            "--privileged",
+            "rust; rm -rf /",
+            "rust $(id)",
+            "img name",
+            "/abs",
+        ] {
+            assert!(validate_container_image(image).is_err(), "{image:?}");
+        }
+    }
+
     #[test]
     fn test_validate_remote_base_absolute_path() {
         let _guard = test_guard!();
@@ -4883,6 +6296,7 @@ retry_max = 2
             auto_start_cooldown_secs: 60,
             auto_start_timeout_secs: 10,
             self_healing_log_level: SelfHealingLogLevel::Debug,
+            state_dir: Some("/run/rch-ci".to_string()),
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -4898,6 +6312,7 @@ retry_max = 2
         assert_eq!(parsed.auto_start_cooldown_secs, 60);
         assert_eq!(parsed.auto_start_timeout_secs, 10);
         assert_eq!(parsed.self_healing_log_level, SelfHealingLogLevel::Debug);
+        assert_eq!(parsed.state_dir.as_deref(), Some("/run/rch-ci"));
         // TEST PASS: Full SelfHealingConfig serde
     }
 
@@ -5182,6 +6597,33 @@ retry_max = 2
         assert!(caps.is_low_disk(10.0).is_none());
     }
 
+    #[test]
+    fn test_is_missing_tool() {
+        let _guard = test_guard!();
+        let mut caps = WorkerCapabilities::new();
+        assert!(!caps.is_missing_tool("rsync"));
+
+        caps.missing_tools = vec!["zstd".to_string()];
+        assert!(caps.is_missing_tool("zstd"));
+        assert!(!caps.is_missing_tool("rsync"));
+        assert!(worker_tool_install_hint("zstd").contains("apt-get install -y zstd"));
+    }
+
+    #[test]
+    fn test_is_low_memory() {
+        let _guard = test_guard!();
+        let mut caps = WorkerCapabilities::new();
+        caps.mem_available_gb = Some(6.0);
+
+        assert_eq!(caps.is_low_memory(4.0), Some(false));
+        assert_eq!(caps.is_low_memory(6.0), Some(false)); // equal is OK
+        assert_eq!(caps.is_low_memory(8.0), Some(true));
+
+        // No metrics -> None (fail-open)
+        caps.mem_available_gb = None;
+        assert!(caps.is_low_memory(4.0).is_none());
+    }
+
     #[test]
     fn test_selection_config_preflight_defaults() {
         let _guard = test_guard!();
@@ -50,7 +50,7 @@ pub use icons::Icons;
 pub use progress::{
     AnimatedSpinner, ArtifactSummary, BuildPhase, BuildProfile, CelebrationSummary,
     CompilationProgress, CompletionCelebration, CrateInfo, PipelineProgress, PipelineStage,
-    ProgressContext, RateLimiter, SpinnerResult, SpinnerStyle, StageStatus, TransferDirection,
-    TransferProgress,
+    ProgressContext, RateLimiter, SpinnerResult, SpinnerStyle, StageStatus, TestSummary,
+    TransferDirection, TransferProgress,
 };
 pub use theme::RchTheme;
@@ -9,7 +9,9 @@ use crate::ui::RchTheme;
 use crate::ui::{Icons, OutputContext};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::fs;
+use std::ops::AddAssign;
 use std::path::{Path, PathBuf};
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
@@ -28,6 +30,91 @@ pub struct ArtifactSummary {
     pub bytes: u64,
 }
 
+/// Test counts from a `cargo test` or `cargo nextest` run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TestSummary {
+    pub passed: u64,
+    pub failed: u64,
+    /// Ignored (libtest) or skipped (nextest) tests.
+    pub ignored: u64,
+}
+
+impl TestSummary {
+    /// Parse a result line: libtest's per-binary
+    /// `test result: ok. 3 passed; 0 failed; 1 ignored; ...` or nextest's
+    /// `Summary [ 1.2s] 4 tests run: 3 passed, 1 failed, 2 skipped`.
+    ///
+    /// Colour codes are stripped first. Returns `None` for any other line.
+    #[must_use]
+    pub fn parse_line(line: &str) -> Option<Self> {
+        let line = strip_ansi(line);
+        let line = line.trim();
+        let counts = if let Some(rest) = line.strip_prefix("test result:") {
+            rest
+        } else if line.starts_with("Summary")
+            && let Some((_, rest)) = line.split_once(" run:")
+        {
+            rest
+        } else {
+            return None;
+        };
+
+        let tokens: Vec<&str> = counts.split_whitespace().collect();
+        let mut summary = Self::default();
+        for pair in tokens.windows(2) {
+            let Ok(count) = pair[0].parse::<u64>() else {
+                continue;
+            };
+            match pair[1].trim_end_matches([',', ';']) {
+                "passed" => summary.passed += count,
+                // nextest reports `N timed out` and `N exec failed` separately.
+                "failed" | "timed" | "exec" => summary.failed += count,
+                "ignored" | "skipped" => summary.ignored += count,
+                _ => {}
+            }
+        }
+        Some(summary)
+    }
+}
+
+impl AddAssign for TestSummary {
+    fn add_assign(&mut self, other: Self) {
+        self.passed += other.passed;
+        self.failed += other.failed;
+        self.ignored += other.ignored;
+    }
+}
+
+impl fmt::Display for TestSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} passed, {} failed, {} ignored",
+            self.passed, self.failed, self.ignored
+        )
+    }
+}
+
+/// Drop CSI escape sequences (`ESC [ ... <final byte>`) from a line.
+fn strip_ansi(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\u{1b}' {
+            if chars.next() == Some('[') {
+                for next in chars.by_ref() {
+                    if ('@'..='~').contains(&next) {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+        out.push(ch);
+    }
+    out
+}
+
 /// Summary information for a completed build.
 #[derive(Debug, Clone)]
 pub struct CelebrationSummary {
@@ -36,6 +123,7 @@ pub struct CelebrationSummary {
     pub duration_ms: u64,
     pub crates_compiled: Option<u32>,
     pub artifacts: Option<ArtifactSummary>,
+    pub tests: Option<TestSummary>,
     pub cache_hit: Option<bool>,
     pub target: Option<String>,
     pub quiet: bool,
@@ -51,6 +139,7 @@ impl CelebrationSummary {
             duration_ms,
             crates_compiled: None,
             artifacts: None,
+            tests: None,
             cache_hit: None,
             target: None,
             quiet: false,
@@ -76,6 +165,12 @@ impl CelebrationSummary {
         self
     }
 
+    #[must_use]
+    pub fn tests(mut self, tests: Option<TestSummary>) -> Self {
+        self.tests = tests;
+        self
+    }
+
     #[must_use]
     pub fn cache_hit(mut self, cache_hit: Option<bool>) -> Self {
         self.cache_hit = cache_hit;
@@ -238,6 +333,10 @@ impl CompletionCelebration {
             (None, None) => {}
         }
 
+        if let Some(tests) = &self.summary.tests {
+            lines.push(format!("Tests: {}", tests));
+        }
+
         if let Some(worker) = &self.summary.worker {
             lines.push(format!("Worker: {}", worker));
         }
@@ -624,6 +723,53 @@ mod tests {
         assert!(line.contains("saved ~"));
     }
 
+    #[test]
+    fn test_summary_parses_libtest_and_nextest_lines() {
+        assert_eq!(
+            TestSummary::parse_line(
+                "test result: ok. 12 passed; 0 failed; 2 ignored; 0 measured; 0 filtered out; finished in 0.31s"
+            ),
+            Some(TestSummary {
+                passed: 12,
+                failed: 0,
+                ignored: 2
+            })
+        );
+        assert_eq!(
+            TestSummary::parse_line(
+                "\u{1b}[1mtest result\u{1b}[0m: \u{1b}[31mFAILED\u{1b}[0m. 3 passed; 1 failed; 0 ignored"
+            ),
+            Some(TestSummary {
+                passed: 3,
+                failed: 1,
+                ignored: 0
+            })
+        );
+        assert_eq!(
+            TestSummary::parse_line(
+                "     Summary [   1.204s] 9 tests run: 7 passed (1 flaky), 1 failed, 1 timed out, 4 skipped"
+            ),
+            Some(TestSummary {
+                passed: 7,
+                failed: 2,
+                ignored: 4
+            })
+        );
+        assert_eq!(TestSummary::parse_line("   Compiling foo v0.1.0"), None);
+    }
+
+    #[test]
+    fn test_summary_sums_across_binaries() {
+        let mut total = TestSummary::default();
+        for line in [
+            "test result: ok. 4 passed; 0 failed; 1 ignored; 0 measured; 0 filtered out",
+            "test result: FAILED. 2 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out",
+        ] {
+            total += TestSummary::parse_line(line).expect("result line");
+        }
+        assert_eq!(total.to_string(), "6 passed, 1 failed, 1 ignored");
+    }
+
     #[test]
     fn render_box_uses_ascii_when_unicode_not_supported() {
         let ctx = OutputContext::plain();
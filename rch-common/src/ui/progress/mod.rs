@@ -17,7 +17,7 @@ use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Mutex, OnceLock};
 use std::time::Instant;
 
-pub use celebrate::{ArtifactSummary, CelebrationSummary, CompletionCelebration};
+pub use celebrate::{ArtifactSummary, CelebrationSummary, CompletionCelebration, TestSummary};
 pub use compile::{BuildPhase, BuildProfile, CompilationProgress, CrateInfo};
 pub use pipeline::{PipelineProgress, PipelineStage, StageStatus};
 pub use spinner::{AnimatedSpinner, SpinnerResult, SpinnerStyle};
@@ -1,5 +1,9 @@
 //! Shared utilities for RCH.
 
+use std::sync::RwLock;
+
+use regex::Regex;
+
 /// Truncate a string to at most `max_bytes` bytes, snapping back to the nearest
 /// UTF-8 character boundary.
 ///
@@ -62,11 +66,79 @@ fn find_value_end(s: &str) -> usize {
     end
 }
 
+/// Extra masking rules from `logging.mask_patterns`, with the source strings
+/// they were compiled from and the errors for entries that did not compile.
+struct CustomMaskPatterns {
+    sources: Vec<String>,
+    compiled: Vec<Regex>,
+    errors: Vec<String>,
+}
+
+static CUSTOM_MASK_PATTERNS: RwLock<CustomMaskPatterns> = RwLock::new(CustomMaskPatterns {
+    sources: Vec::new(),
+    compiled: Vec::new(),
+    errors: Vec::new(),
+});
+
+/// Compile `logging.mask_patterns` entries, returning the valid regexes and
+/// one message per entry that failed to compile.
+pub fn compile_mask_patterns(patterns: &[String]) -> (Vec<Regex>, Vec<String>) {
+    let mut compiled = Vec::new();
+    let mut errors = Vec::new();
+    for pattern in patterns {
+        match Regex::new(pattern) {
+            Ok(re) => compiled.push(re),
+            Err(e) => errors.push(format!("invalid mask pattern {:?}: {}", pattern, e)),
+        }
+    }
+    (compiled, errors)
+}
+
+/// Install extra masking rules for [`mask_sensitive_command`], replacing any
+/// installed before. Entries that fail to compile are skipped; the returned
+/// messages (one per skipped entry) are for the caller to log. Re-installing
+/// the same list is cheap and returns the same messages.
+pub fn set_custom_mask_patterns(patterns: &[String]) -> Vec<String> {
+    if let Ok(current) = CUSTOM_MASK_PATTERNS.read()
+        && current.sources == patterns
+    {
+        return current.errors.clone();
+    }
+    let (compiled, errors) = compile_mask_patterns(patterns);
+    if let Ok(mut current) = CUSTOM_MASK_PATTERNS.write() {
+        *current = CustomMaskPatterns {
+            sources: patterns.to_vec(),
+            compiled,
+            errors: errors.clone(),
+        };
+    }
+    errors
+}
+
 /// Mask sensitive patterns in a command string before logging.
 ///
 /// This prevents accidental exposure of API keys, passwords, and tokens
 /// that may be present in environment variables or command arguments.
+/// Matches of any rules installed with [`set_custom_mask_patterns`] are
+/// replaced with `***` after the built-in rules run.
 pub fn mask_sensitive_command(cmd: &str) -> String {
+    let masked = mask_builtin_patterns(cmd);
+    match CUSTOM_MASK_PATTERNS.read() {
+        Ok(custom) if !custom.compiled.is_empty() => mask_custom_patterns(masked, &custom.compiled),
+        _ => masked,
+    }
+}
+
+fn mask_custom_patterns(mut cmd: String, patterns: &[Regex]) -> String {
+    for re in patterns {
+        if let std::borrow::Cow::Owned(replaced) = re.replace_all(&cmd, "***") {
+            cmd = replaced;
+        }
+    }
+    cmd
+}
+
+fn mask_builtin_patterns(cmd: &str) -> String {
     // Patterns to mask (case-insensitive matching would be better, but this is simple)
     // We replace the value part with "***" while keeping the key/flag.
     let patterns = [
@@ -133,6 +205,7 @@ pub fn mask_sensitive_command(cmd: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_guard;
 
     #[test]
     fn test_mask_sensitive_command() {
@@ -168,6 +241,43 @@ mod tests {
         assert!(!masked.contains("secret"));
     }
 
+    #[test]
+    fn test_mask_custom_patterns_after_builtin_rules() {
+        let (patterns, errors) =
+            compile_mask_patterns(&[r"acme_[A-Za-z0-9]{8,}".to_string(), "(".to_string()]);
+        assert_eq!(patterns.len(), 1);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("\"(\""));
+
+        let cmd = "cargo build --token mytoken ACME=acme_0123456789 && echo acme_0123456789";
+        let masked = mask_custom_patterns(mask_builtin_patterns(cmd), &patterns);
+        assert_eq!(masked, "cargo build --token *** ACME=*** && echo ***");
+    }
+
+    #[test]
+    fn test_set_custom_mask_patterns_applies_to_mask_sensitive_command() {
+        let _guard = test_guard!();
+        /// Clears the process-wide patterns even if an assertion fails.
+        struct ResetMaskPatterns;
+        impl Drop for ResetMaskPatterns {
+            fn drop(&mut self) {
+                set_custom_mask_patterns(&[]);
+            }
+        }
+        let _reset = ResetMaskPatterns;
+
+        let errors = set_custom_mask_patterns(&[r"zqxcorp-[0-9]{6}".to_string()]);
+        assert!(errors.is_empty());
+        assert_eq!(
+            mask_sensitive_command("deploy zqxcorp-123456 TOKEN=abc"),
+            "deploy *** TOKEN=***"
+        );
+        assert_eq!(
+            mask_sensitive_command("TOKEN=a TOKEN=b"),
+            "TOKEN=*** TOKEN=***"
+        );
+    }
+
     #[test]
     fn test_truncate_at_char_boundary_short_string_unchanged() {
         assert_eq!(truncate_at_char_boundary("abc", 10), "abc");
@@ -60,6 +60,12 @@ fn write_bin_crate(root: &Path, crate_name: &str, deps: &[(&str, &str)]) {
     .expect("write main.rs");
 }
 
+fn write_lib_crate(root: &Path, crate_name: &str) {
+    fs::create_dir_all(root.join("src")).expect("create crate src");
+    fs::write(root.join("Cargo.toml"), crate_manifest(crate_name, &[])).expect("write manifest");
+    fs::write(root.join("src/lib.rs"), "pub fn value() -> u32 { 1 }\n").expect("write lib.rs");
+}
+
 fn crate_manifest(crate_name: &str, deps: &[(&str, &str)]) -> String {
     let mut dependencies = String::new();
     for (name, path) in deps {
@@ -166,3 +172,44 @@ fn planner_broken_graphs_mark_fail_open_with_issues() {
         );
     }
 }
+
+#[test]
+fn planner_workspace_plan_covers_every_member_closure() {
+    // Cargo loads every member manifest (and its path dependencies) even for
+    // `-p ws_a`, so the plan must include ws_b's closure as well.
+    let fixture = TopologyFixture::new("workspace_members");
+    let ws_root = fixture.canonical_root.join("ws");
+    fs::create_dir_all(&ws_root).expect("create workspace root");
+    fs::write(
+        ws_root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"a\", \"b\"]\nresolver = \"3\"\n",
+    )
+    .expect("write workspace manifest");
+    write_bin_crate(
+        &ws_root.join("a"),
+        "ws_a",
+        &[("shared_a", "../../shared_a")],
+    );
+    write_bin_crate(
+        &ws_root.join("b"),
+        "ws_b",
+        &[("shared_b", "../../shared_b")],
+    );
+    write_lib_crate(&fixture.canonical_root.join("shared_a"), "shared_a");
+    write_lib_crate(&fixture.canonical_root.join("shared_b"), "shared_b");
+
+    let plan = build_dependency_closure_plan_with_policy(&ws_root, &fixture.policy());
+
+    assert!(plan.is_ready(), "workspace plan should be ready: {plan:?}");
+    let names = plan
+        .sync_order
+        .iter()
+        .map(|action| action.package_name.as_str())
+        .collect::<std::collections::BTreeSet<_>>();
+    assert_eq!(
+        names,
+        ["shared_a", "shared_b", "ws_a", "ws_b"]
+            .into_iter()
+            .collect()
+    );
+}
@@ -508,7 +508,10 @@ fn calculate_compilation_score(debug_ms: u64, release_ms: u64, incremental_ms: u
 /// - Trait implementations
 /// - Macro usage
 /// - Computation-heavy code
-fn setup_benchmark_project(base_dir: &Path) -> Result<PathBuf, CompilationBenchmarkError> {
+///
+/// The binary is named [`BENCHMARK_BINARY_NAME`]. Also used by `rch selftest`
+/// as its throwaway project.
+pub fn setup_benchmark_project(base_dir: &Path) -> Result<PathBuf, CompilationBenchmarkError> {
     let project_dir = base_dir.join("rch_benchmark_project");
     let src_dir = project_dir.join("src");
 
@@ -592,6 +595,9 @@ pub fn run_compilation_benchmark_stable()
 // Reference Project Source Files
 // ============================================================================
 
+/// Binary (package) name of the benchmark project.
+pub const BENCHMARK_BINARY_NAME: &str = "rch_benchmark";
+
 /// Cargo.toml for the benchmark project.
 const CARGO_TOML: &str = r#"[package]
 name = "rch_benchmark"
@@ -1432,6 +1438,8 @@ mod tests {
         assert!(project_dir.join("src/traits.rs").exists());
         assert!(project_dir.join("src/macros.rs").exists());
         assert!(project_dir.join("src/compute.rs").exists());
+        let manifest = std::fs::read_to_string(project_dir.join("Cargo.toml")).unwrap();
+        assert!(manifest.contains(&format!("name = \"{}\"", BENCHMARK_BINARY_NAME)));
 
         info!("VERIFY: All expected files exist");
         info!("TEST PASS: test_setup_benchmark_project");
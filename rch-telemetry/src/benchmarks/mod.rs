@@ -18,7 +18,8 @@ pub mod network;
 pub mod retry;
 
 pub use compilation::{
-    CompilationBenchmark, CompilationBenchmarkError, CompilationBenchmarkResult,
+    BENCHMARK_BINARY_NAME, CompilationBenchmark, CompilationBenchmarkError,
+    CompilationBenchmarkResult, setup_benchmark_project,
 };
 pub use cpu::{CpuBenchmark, CpuBenchmarkResult};
 pub use disk::{DiskBenchmark, DiskBenchmarkResult};
@@ -0,0 +1,142 @@
+//! GPU metrics collection via `nvidia-smi`.
+//!
+//! Queries NVIDIA GPUs for utilization and memory so workers used for CUDA
+//! builds (`nvcc`) can report accelerator load. Collection is opt-in
+//! (`--gpu`); hosts without a GPU or without `nvidia-smi` report `None`.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+use tracing::debug;
+
+/// `nvidia-smi` arguments producing one CSV line per GPU:
+/// `utilization.gpu, memory.used, memory.total` (percent, MiB, MiB).
+pub const NVIDIA_SMI_QUERY_ARGS: &[&str] = &[
+    "--query-gpu=utilization.gpu,memory.used,memory.total",
+    "--format=csv,noheader,nounits",
+];
+
+/// Metrics for a single GPU.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GpuDevice {
+    /// Position in `nvidia-smi` output (matches the CUDA device index).
+    pub index: usize,
+    /// GPU utilization (0-100); `None` when the driver reports `[N/A]`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub utilization_pct: Option<f64>,
+    /// Memory in use in MiB.
+    pub memory_used_mb: u64,
+    /// Total memory in MiB.
+    pub memory_total_mb: u64,
+}
+
+/// Aggregated GPU telemetry snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuTelemetry {
+    /// Timestamp of the telemetry collection.
+    pub timestamp: DateTime<Utc>,
+    /// Per-GPU metrics.
+    pub devices: Vec<GpuDevice>,
+    /// Mean utilization across GPUs that report it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_utilization_pct: Option<f64>,
+    /// Memory in use across all GPUs in MiB.
+    pub memory_used_mb: u64,
+    /// Total memory across all GPUs in MiB.
+    pub memory_total_mb: u64,
+}
+
+impl GpuTelemetry {
+    /// Collect GPU telemetry by running `nvidia-smi`.
+    ///
+    /// Returns `None` if `nvidia-smi` is missing, fails (no GPU or driver), or
+    /// produces no parseable rows.
+    pub fn collect() -> Option<Self> {
+        let output = match Command::new("nvidia-smi")
+            .args(NVIDIA_SMI_QUERY_ARGS)
+            .output()
+        {
+            Ok(output) => output,
+            Err(e) => {
+                debug!("nvidia-smi not available: {}", e);
+                return None;
+            }
+        };
+        if !output.status.success() {
+            debug!(
+                status = ?output.status.code(),
+                "nvidia-smi failed; no GPU telemetry"
+            );
+            return None;
+        }
+        let telemetry = Self::parse(&String::from_utf8_lossy(&output.stdout))?;
+        debug!(
+            gpus = telemetry.devices.len(),
+            avg_util = ?telemetry.avg_utilization_pct,
+            mem_used_mb = telemetry.memory_used_mb,
+            "GPU telemetry collected"
+        );
+        Some(telemetry)
+    }
+
+    /// Parse `nvidia-smi` CSV output (see [`NVIDIA_SMI_QUERY_ARGS`]).
+    ///
+    /// Format (one line per GPU):
+    /// ```text
+    /// 87, 10240, 24576
+    /// [N/A], 512, 8192
+    /// ```
+    pub fn parse(content: &str) -> Option<Self> {
+        let devices: Vec<GpuDevice> = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+                if fields.len() < 3 {
+                    return None;
+                }
+                Some((
+                    fields[0].parse::<f64>().ok(),
+                    fields[1].parse::<u64>().unwrap_or(0),
+                    fields[2].parse::<u64>().unwrap_or(0),
+                ))
+            })
+            .enumerate()
+            .map(
+                |(index, (utilization_pct, memory_used_mb, memory_total_mb))| GpuDevice {
+                    index,
+                    utilization_pct,
+                    memory_used_mb,
+                    memory_total_mb,
+                },
+            )
+            .collect();
+        if devices.is_empty() {
+            return None;
+        }
+
+        let reported: Vec<f64> = devices
+            .iter()
+            .filter_map(|device| device.utilization_pct)
+            .collect();
+        let avg_utilization_pct =
+            (!reported.is_empty()).then(|| reported.iter().sum::<f64>() / reported.len() as f64);
+
+        Some(Self {
+            timestamp: Utc::now(),
+            avg_utilization_pct,
+            memory_used_mb: devices.iter().map(|device| device.memory_used_mb).sum(),
+            memory_total_mb: devices.iter().map(|device| device.memory_total_mb).sum(),
+            devices,
+        })
+    }
+
+    /// Percentage of total GPU memory in use (0-100).
+    pub fn memory_used_percent(&self) -> f64 {
+        if self.memory_total_mb == 0 {
+            return 0.0;
+        }
+        self.memory_used_mb as f64 / self.memory_total_mb as f64 * 100.0
+    }
+}
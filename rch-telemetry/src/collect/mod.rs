@@ -5,11 +5,13 @@
 
 pub mod cpu;
 pub mod disk;
+pub mod gpu;
 pub mod memory;
 pub mod network;
 
 use crate::collect::cpu::CpuTelemetry;
 use crate::collect::disk::DiskCollector;
+use crate::collect::gpu::GpuTelemetry;
 use crate::collect::memory::MemoryTelemetry;
 use crate::collect::network::NetworkCollector;
 use crate::protocol::WorkerTelemetry;
@@ -40,6 +42,7 @@ pub fn collect_telemetry(
     sample_ms: u64,
     include_disk: bool,
     include_network: bool,
+    include_gpu: bool,
     worker_id: String,
 ) -> Result<WorkerTelemetry> {
     let start = Instant::now();
@@ -80,14 +83,14 @@ pub fn collect_telemetry(
         None => None,
     };
 
+    // Point-in-time query; nvidia-smi reports its own sampling-window average.
+    let gpu = if include_gpu {
+        GpuTelemetry::collect()
+    } else {
+        None
+    };
+
     let duration_ms = start.elapsed().as_millis() as u64;
 
-    Ok(WorkerTelemetry::new(
-        worker_id,
-        cpu,
-        memory,
-        disk,
-        network,
-        duration_ms,
-    ))
+    Ok(WorkerTelemetry::new(worker_id, cpu, memory, disk, network, duration_ms).with_gpu(gpu))
 }
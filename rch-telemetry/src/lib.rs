@@ -24,7 +24,8 @@ pub mod storage;
 pub use rch_common::{LogConfig, LogFormat, LoggingGuards, init_logging};
 
 pub use benchmarks::compilation::{
-    CompilationBenchmark, CompilationBenchmarkError, CompilationBenchmarkResult,
+    BENCHMARK_BINARY_NAME, CompilationBenchmark, CompilationBenchmarkError,
+    CompilationBenchmarkResult, setup_benchmark_project,
 };
 pub use benchmarks::cpu::{CpuBenchmark, CpuBenchmarkResult};
 pub use benchmarks::disk::{DiskBenchmark, DiskBenchmarkResult};
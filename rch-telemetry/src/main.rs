@@ -37,6 +37,10 @@ enum Commands {
         #[arg(long)]
         no_network: bool,
 
+        /// Collect GPU utilization and memory via nvidia-smi (off by default)
+        #[arg(long)]
+        gpu: bool,
+
         /// Override worker ID (defaults to RCH_WORKER_ID or HOSTNAME)
         #[arg(long)]
         worker_id: Option<String>,
@@ -64,10 +68,11 @@ fn main() -> Result<()> {
             sample_ms,
             no_disk,
             no_network,
+            gpu,
             worker_id,
         } => {
             let worker_id = resolve_worker_id(worker_id);
-            let telemetry = collect_telemetry(sample_ms, !no_disk, !no_network, worker_id)?;
+            let telemetry = collect_telemetry(sample_ms, !no_disk, !no_network, gpu, worker_id)?;
 
             let output = match format {
                 OutputFormat::Json => telemetry.to_json()?,
@@ -78,6 +78,7 @@ const CLASSIFY_KIND_LABELS: &[&str] = &[
     "bun_typecheck",
     "cc",
     "build_system",
+    "wasm_build",
     "non_compilation",
     "unknown",
     "other",
@@ -1000,6 +1001,7 @@ fn normalize_classify_kind(value: &str) -> &'static str {
         "bun_typecheck" | "buntypecheck" => "bun_typecheck",
         "gcc" | "g__" | "clang" | "clang__" | "cc" => "cc",
         "make" | "cmake" | "ninja" | "meson" | "buildsystem" | "build_system" => "build_system",
+        "trunkbuild" | "trunk_build" | "wasmpackbuild" | "wasm_pack_build" => "wasm_build",
         _ => normalize_from(&token, CLASSIFY_KIND_LABELS, "other"),
     }
 }
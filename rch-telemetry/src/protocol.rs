@@ -10,6 +10,7 @@ use std::collections::HashMap;
 
 use crate::collect::cpu::CpuTelemetry;
 use crate::collect::disk::DiskTelemetry;
+use crate::collect::gpu::GpuTelemetry;
 use crate::collect::memory::MemoryTelemetry;
 use crate::collect::network::NetworkTelemetry;
 
@@ -19,6 +20,12 @@ pub const TELEMETRY_PROTOCOL_VERSION: u32 = 1;
 /// Marker used to identify telemetry data piggybacked with build output.
 pub const PIGGYBACK_MARKER: &str = "---RCH-TELEMETRY---";
 
+/// Marker prefixing each snapshot streamed while a build is still running.
+///
+/// Unlike the final trailer, a streamed snapshot is a single line: the marker,
+/// a space, then the JSON payload.
+pub const PIGGYBACK_STREAM_MARKER: &str = "---RCH-TELEMETRY-STREAM---";
+
 /// Unified telemetry snapshot from a worker.
 ///
 /// Combines CPU, memory, disk, and network metrics into a single payload
@@ -41,6 +48,9 @@ pub struct WorkerTelemetry {
     /// Network telemetry (throughput, errors, drops).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub network: Option<NetworkTelemetry>,
+    /// GPU telemetry (utilization, memory); only collected with `--gpu`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gpu: Option<GpuTelemetry>,
     /// Collection duration in milliseconds.
     pub collection_duration_ms: u64,
 }
@@ -63,10 +73,17 @@ impl WorkerTelemetry {
             memory,
             disk,
             network,
+            gpu: None,
             collection_duration_ms,
         }
     }
 
+    /// Attach GPU telemetry to the payload.
+    pub fn with_gpu(mut self, gpu: Option<GpuTelemetry>) -> Self {
+        self.gpu = gpu;
+        self
+    }
+
     /// Serialize to JSON for transmission.
     pub fn to_json(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string(self)
@@ -87,6 +104,11 @@ impl WorkerTelemetry {
         Ok(format!("{}\n{}", PIGGYBACK_MARKER, self.to_json()?))
     }
 
+    /// Format as a single streamed-snapshot line (without trailing newline).
+    pub fn to_stream_line(&self) -> Result<String, serde_json::Error> {
+        Ok(format!("{} {}", PIGGYBACK_STREAM_MARKER, self.to_json()?))
+    }
+
     /// Check if this telemetry is compatible with the current protocol version.
     pub fn is_compatible(&self) -> bool {
         self.version == TELEMETRY_PROTOCOL_VERSION
@@ -102,6 +124,7 @@ impl WorkerTelemetry {
             memory_pressure: self.memory.pressure_score,
             disk_io_percent: self.disk.as_ref().map(|d| d.max_io_utilization_pct),
             network_throughput_mbps: self.network.as_ref().map(|n| n.total_throughput_mbps),
+            gpu_utilization_pct: self.gpu.as_ref().and_then(|g| g.avg_utilization_pct),
             load_1m: self.cpu.load_average.one_min,
         }
     }
@@ -119,6 +142,8 @@ pub struct TelemetrySummary {
     pub disk_io_percent: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub network_throughput_mbps: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gpu_utilization_pct: Option<f64>,
     pub load_1m: f64,
 }
 
@@ -140,6 +165,9 @@ impl std::fmt::Display for TelemetrySummary {
         if let Some(net) = self.network_throughput_mbps {
             write!(f, ", Net: {:.1} Mbps", net)?;
         }
+        if let Some(gpu) = self.gpu_utilization_pct {
+            write!(f, ", GPU: {:.1}%", gpu)?;
+        }
 
         Ok(())
     }
@@ -243,6 +271,36 @@ pub struct PiggybackExtraction {
     pub telemetry: Option<WorkerTelemetry>,
     /// Error message if telemetry extraction failed.
     pub extraction_error: Option<String>,
+    /// Snapshots streamed during the build, in output order.
+    pub streamed: Vec<WorkerTelemetry>,
+}
+
+/// Parse one output line as a streamed telemetry snapshot.
+///
+/// Returns `None` for any line that is not a well-formed streamed snapshot,
+/// so ordinary build output is never mistaken for telemetry.
+pub fn parse_streamed_telemetry_line(line: &str) -> Option<WorkerTelemetry> {
+    let payload = line.strip_prefix(PIGGYBACK_STREAM_MARKER)?.trim();
+    if !payload.starts_with('{') {
+        return None;
+    }
+    WorkerTelemetry::from_json(payload).ok()
+}
+
+/// Split streamed snapshot lines out of `output`, keeping every other line.
+fn split_streamed_telemetry(output: &str) -> (std::borrow::Cow<'_, str>, Vec<WorkerTelemetry>) {
+    if !output.contains(PIGGYBACK_STREAM_MARKER) {
+        return (std::borrow::Cow::Borrowed(output), Vec::new());
+    }
+    let mut kept = String::with_capacity(output.len());
+    let mut streamed = Vec::new();
+    for line in output.split_inclusive('\n') {
+        match parse_streamed_telemetry_line(line) {
+            Some(telemetry) => streamed.push(telemetry),
+            None => kept.push_str(line),
+        }
+    }
+    (std::borrow::Cow::Owned(kept), streamed)
 }
 
 /// Extract piggybacked telemetry from build job output.
@@ -265,7 +323,18 @@ pub struct PiggybackExtraction {
 /// preserve the pre-marker text as the build output and report an
 /// `extraction_error` — the worker clearly intended to send telemetry
 /// but emitted something malformed.
+///
+/// Snapshots streamed during the build ([`PIGGYBACK_STREAM_MARKER`] lines)
+/// are removed from the build output and returned in `streamed`.
 pub fn extract_piggybacked_telemetry(output: &str) -> PiggybackExtraction {
+    let (output, streamed) = split_streamed_telemetry(output);
+    PiggybackExtraction {
+        streamed,
+        ..extract_final_telemetry(&output)
+    }
+}
+
+fn extract_final_telemetry(output: &str) -> PiggybackExtraction {
     if let Some(marker_pos) = output.rfind(PIGGYBACK_MARKER) {
         let at_line_start = marker_pos == 0
             || output.as_bytes()[..marker_pos]
@@ -276,6 +345,7 @@ pub fn extract_piggybacked_telemetry(output: &str) -> PiggybackExtraction {
                 build_output: output.to_string(),
                 telemetry: None,
                 extraction_error: None,
+                streamed: Vec::new(),
             };
         }
 
@@ -288,6 +358,7 @@ pub fn extract_piggybacked_telemetry(output: &str) -> PiggybackExtraction {
                 build_output: output.to_string(),
                 telemetry: None,
                 extraction_error: None,
+                streamed: Vec::new(),
             };
         }
 
@@ -296,11 +367,13 @@ pub fn extract_piggybacked_telemetry(output: &str) -> PiggybackExtraction {
                 build_output: output[..marker_pos].trim_end().to_string(),
                 telemetry: Some(telemetry),
                 extraction_error: None,
+                streamed: Vec::new(),
             },
             Err(e) => PiggybackExtraction {
                 build_output: output[..marker_pos].trim_end().to_string(),
                 telemetry: None,
                 extraction_error: Some(format!("Failed to parse telemetry: {}", e)),
+                streamed: Vec::new(),
             },
         }
     } else {
@@ -309,6 +382,7 @@ pub fn extract_piggybacked_telemetry(output: &str) -> PiggybackExtraction {
             build_output: output.to_string(),
             telemetry: None,
             extraction_error: None,
+            streamed: Vec::new(),
         }
     }
 }
@@ -370,6 +444,7 @@ impl ReceivedTelemetry {
 mod tests {
     use super::*;
     use crate::collect::cpu::{CpuPressureStall, LoadAverage};
+    use rch_common::test_guard;
 
     fn make_test_cpu_telemetry() -> CpuTelemetry {
         CpuTelemetry {
@@ -540,6 +615,41 @@ mod tests {
         assert_eq!(extraction.build_output, build_output);
     }
 
+    #[test]
+    fn test_extract_piggybacked_collects_streamed_snapshots() {
+        let _guard = test_guard!();
+        let telemetry = make_test_worker_telemetry();
+        let stream_line = telemetry.to_stream_line().unwrap();
+        let combined = format!(
+            "Compiling foo v0.1.0\n{stream_line}\nCompiling bar v0.1.0\n{stream_line}\n   Finished\n{}",
+            telemetry.to_piggyback().unwrap()
+        );
+
+        let extraction = extract_piggybacked_telemetry(&combined);
+        assert_eq!(extraction.streamed.len(), 2);
+        assert_eq!(extraction.streamed[0].worker_id, "worker-1");
+        assert!(extraction.telemetry.is_some());
+        assert_eq!(
+            extraction.build_output,
+            "Compiling foo v0.1.0\nCompiling bar v0.1.0\n   Finished"
+        );
+    }
+
+    #[test]
+    fn test_parse_streamed_telemetry_line_rejects_build_output() {
+        let _guard = test_guard!();
+        let telemetry = make_test_worker_telemetry();
+        let line = format!("{}\n", telemetry.to_stream_line().unwrap());
+        assert!(parse_streamed_telemetry_line(&line).is_some());
+
+        assert!(parse_streamed_telemetry_line("Compiling foo v0.1.0\n").is_none());
+        assert!(parse_streamed_telemetry_line(PIGGYBACK_MARKER).is_none());
+        let prose = format!("{} not json", PIGGYBACK_STREAM_MARKER);
+        assert!(parse_streamed_telemetry_line(&prose).is_none());
+        let mid_line = format!("log: {}", telemetry.to_stream_line().unwrap());
+        assert!(parse_streamed_telemetry_line(&mid_line).is_none());
+    }
+
     #[test]
     fn test_telemetry_version_compatibility() {
         let telemetry = make_test_worker_telemetry();
@@ -896,6 +1006,7 @@ mod tests {
             memory_pressure: 55.0,
             disk_io_percent: Some(30.0),
             network_throughput_mbps: Some(100.0),
+            gpu_utilization_pct: Some(87.0),
             load_1m: 2.5,
         };
 
@@ -918,6 +1029,7 @@ mod tests {
             memory_pressure: 55.0,
             disk_io_percent: Some(30.0),
             network_throughput_mbps: Some(100.0),
+            gpu_utilization_pct: Some(87.0),
             load_1m: 2.5,
         };
 
@@ -927,6 +1039,7 @@ mod tests {
         assert!(display.contains("Mem: 60.0%"));
         assert!(display.contains("Disk I/O: 30.0%"));
         assert!(display.contains("Net: 100.0 Mbps"));
+        assert!(display.contains("GPU: 87.0%"));
     }
 
     #[test]
@@ -939,6 +1052,7 @@ mod tests {
             memory_pressure: 55.0,
             disk_io_percent: None,
             network_throughput_mbps: None,
+            gpu_utilization_pct: None,
             load_1m: 2.5,
         };
 
@@ -0,0 +1,102 @@
+mod common;
+
+use common::{fixture, init_test_logging};
+use rch_telemetry::collect::gpu::GpuTelemetry;
+use rch_telemetry::protocol::WorkerTelemetry;
+use tracing::info;
+
+#[test]
+fn test_parse_nvidia_smi_fixture() {
+    init_test_logging();
+    info!(test = "test_parse_nvidia_smi_fixture", phase = "setup");
+
+    let content = fixture("nvidia_smi_gpu_sample.txt");
+    let gpu = GpuTelemetry::parse(content).expect("nvidia-smi parse");
+
+    info!(
+        test = "test_parse_nvidia_smi_fixture",
+        phase = "assert",
+        gpu_count = gpu.devices.len()
+    );
+    assert_eq!(gpu.devices.len(), 3);
+
+    let first = &gpu.devices[0];
+    assert_eq!(first.index, 0);
+    assert_eq!(first.utilization_pct, Some(87.0));
+    assert_eq!(first.memory_used_mb, 10240);
+    assert_eq!(first.memory_total_mb, 24576);
+
+    // `[N/A]` utilization is reported as unknown and left out of the average.
+    assert_eq!(gpu.devices[2].utilization_pct, None);
+    assert_eq!(gpu.avg_utilization_pct, Some(49.5));
+    assert_eq!(gpu.memory_used_mb, 12800);
+    assert_eq!(gpu.memory_total_mb, 57344);
+    assert!((gpu.memory_used_percent() - 22.32).abs() < 0.01);
+
+    info!(
+        test = "test_parse_nvidia_smi_fixture",
+        phase = "complete",
+        status = "passed"
+    );
+}
+
+#[test]
+fn test_parse_nvidia_smi_without_gpus() {
+    init_test_logging();
+    info!(test = "test_parse_nvidia_smi_without_gpus", phase = "setup");
+
+    assert!(GpuTelemetry::parse("").is_none());
+    assert!(GpuTelemetry::parse("No devices were found\n").is_none());
+
+    info!(
+        test = "test_parse_nvidia_smi_without_gpus",
+        phase = "complete",
+        status = "passed"
+    );
+}
+
+#[test]
+fn test_worker_telemetry_gpu_roundtrip() {
+    init_test_logging();
+    info!(
+        test = "test_worker_telemetry_gpu_roundtrip",
+        phase = "setup"
+    );
+
+    let json = serde_json::json!({
+        "version": 1,
+        "worker_id": "gpu-box",
+        "timestamp": "2026-01-01T00:00:00Z",
+        "cpu": {
+            "timestamp": "2026-01-01T00:00:00Z",
+            "overall_percent": 10.0,
+            "per_core_percent": [],
+            "num_cores": 8,
+            "load_average": {"one_min": 0.5, "five_min": 0.4, "fifteen_min": 0.3,
+                             "running_processes": 1, "total_processes": 100},
+            "psi": null
+        },
+        "memory": {
+            "timestamp": "2026-01-01T00:00:00Z",
+            "total_gb": 64.0, "available_gb": 32.0, "used_percent": 50.0,
+            "pressure_score": 10.0, "swap_used_gb": 0.0, "dirty_mb": 0.0
+        },
+        "collection_duration_ms": 5
+    });
+    // Payloads from workers that predate GPU collection have no `gpu` key.
+    let telemetry: WorkerTelemetry = serde_json::from_value(json).expect("legacy payload");
+    assert!(telemetry.gpu.is_none());
+
+    let gpu = GpuTelemetry::parse(fixture("nvidia_smi_gpu_sample.txt"));
+    let telemetry = telemetry.with_gpu(gpu);
+    let parsed =
+        WorkerTelemetry::from_json(&telemetry.to_json().expect("serialize")).expect("deserialize");
+    assert_eq!(parsed.summary().gpu_utilization_pct, Some(49.5));
+    assert_eq!(parsed.gpu.expect("gpu").devices.len(), 3);
+
+    info!(
+        test = "test_worker_telemetry_gpu_roundtrip",
+        phase = "complete",
+        status = "passed"
+    );
+}
@@ -32,6 +32,7 @@ pub fn fixture(name: &str) -> &'static str {
         "proc_meminfo_low.txt" => include_str!("../fixtures/proc_meminfo_low.txt"),
         "proc_diskstats_sample.txt" => include_str!("../fixtures/proc_diskstats_sample.txt"),
         "proc_net_dev_sample.txt" => include_str!("../fixtures/proc_net_dev_sample.txt"),
+        "nvidia_smi_gpu_sample.txt" => include_str!("../fixtures/nvidia_smi_gpu_sample.txt"),
         other => panic!("unknown fixture: {other}"),
     }
 }
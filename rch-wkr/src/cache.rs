@@ -74,6 +74,10 @@ pub async fn cleanup_in(cache_base: &Path, max_age_hours: u64) -> Result<()> {
 
     let max_age = Duration::from_secs(max_age_hours * 3600);
     let now = SystemTime::now();
+    let now_unix = now
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
     let mut cleaned = 0;
     let mut errors = 0;
 
@@ -144,6 +148,14 @@ pub async fn cleanup_in(cache_base: &Path, max_age_hours: u64) -> Result<()> {
                 continue;
             }
 
+            // A failed build pinned for post-mortem debugging stays until its
+            // pin expires (`transfer.keep_remote_on_failure`).
+            if rch_common::remote_keep::is_pinned(&hash_path, now_unix) {
+                debug!("Keeping pinned cache: {:?}", hash_path);
+                active_caches += 1;
+                continue;
+            }
+
             // Check modification time using the newest mtime across the dir AND
             // its descendants — not just the top-dir mtime, which can be stale
             // while a build writes deep files (or preserved-old after rsync -t),
@@ -441,6 +453,42 @@ mod tests {
         println!("TEST PASS: test_cleanup_removes_old_caches");
     }
 
+    #[tokio::test]
+    async fn test_cleanup_skips_pinned_caches_until_expiry() {
+        println!("TEST START: test_cleanup_skips_pinned_caches_until_expiry");
+
+        let cache_base = unique_test_dir("cleanup-pinned");
+        let project_dir = cache_base.join("proj");
+        let pinned = project_dir.join("pinned");
+        let expired = project_dir.join("expired");
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        for (dir, keep_until) in [(&pinned, now + 3600), (&expired, now - 60)] {
+            fs::create_dir_all(dir).unwrap();
+            fs::write(
+                dir.join(rch_common::remote_keep::KEEP_MARKER),
+                format!("{keep_until}\n"),
+            )
+            .unwrap();
+        }
+
+        // Ensure both directories are older than the max_age=0 threshold.
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        cleanup_in(&cache_base, 0).await.unwrap();
+
+        assert!(pinned.exists(), "unexpired pin must keep the cache");
+        assert!(!expired.exists(), "expired pin must not keep the cache");
+        assert!(
+            project_dir.exists(),
+            "project dir still holds a pinned cache"
+        );
+
+        let _ = fs::remove_dir_all(&cache_base);
+        println!("TEST PASS: test_cleanup_skips_pinned_caches_until_expiry");
+    }
+
     #[test]
     fn newest_descendant_mtime_reflects_deep_activity() {
         // Regression (bd-review-wkr-cache-mtime-toctou): a tree whose top-dir
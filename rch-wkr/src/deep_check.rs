@@ -0,0 +1,103 @@
+//! Deep health check (`rch-wkr health --deep`).
+//!
+//! The plain `health` answer only proves SSH reaches the worker. The deep check
+//! writes a one-file crate under the project cache base, the same disk real
+//! builds use, and runs `cargo build` on it, so a full disk, an unwritable cache
+//! or a broken toolchain fails here instead of in someone's build.
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+
+/// Default limit for the whole deep check.
+pub const DEFAULT_DEEP_CHECK_TIMEOUT_SECS: u64 = 120;
+
+const CARGO_TOML: &str = "[package]\nname = \"rch-healthcheck\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[workspace]\n";
+const MAIN_RS: &str = "fn main() {\n    println!(\"ok\");\n}\n";
+
+/// Build a trivial crate in a scratch directory under `base` and return how
+/// long it took, or a one-line reason the build could not complete.
+pub async fn run_deep_check(base: &Path, timeout: Duration) -> Result<Duration, String> {
+    let start = Instant::now();
+    std::fs::create_dir_all(base)
+        .map_err(|e| format!("cannot create {}: {}", base.display(), e))?;
+    let scratch = tempfile::Builder::new()
+        .prefix("rch-healthcheck-")
+        .tempdir_in(base)
+        .map_err(|e| format!("cannot create scratch dir in {}: {}", base.display(), e))?;
+    let src = scratch.path().join("src");
+    std::fs::create_dir_all(&src)
+        .and_then(|()| std::fs::write(scratch.path().join("Cargo.toml"), CARGO_TOML))
+        .and_then(|()| std::fs::write(src.join("main.rs"), MAIN_RS))
+        .map_err(|e| format!("cannot write test crate: {}", e))?;
+
+    let build = Command::new("cargo")
+        .args(["build", "--quiet"])
+        .current_dir(scratch.path())
+        .env("CARGO_TARGET_DIR", scratch.path().join("target"))
+        .kill_on_drop(true)
+        .output();
+    let output = match tokio::time::timeout(timeout, build).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) => return Err(format!("cannot run cargo: {}", e)),
+        Err(_) => {
+            return Err(format!(
+                "cargo build timed out after {}s",
+                timeout.as_secs()
+            ));
+        }
+    };
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let detail = stderr
+            .lines()
+            .map(str::trim)
+            .find(|line| line.starts_with("error"))
+            .or_else(|| stderr.lines().map(str::trim).find(|line| !line.is_empty()))
+            .unwrap_or("no output");
+        return Err(format!(
+            "cargo build exited {}: {}",
+            output.status.code().unwrap_or(-1),
+            detail
+        ));
+    }
+    Ok(start.elapsed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rch_common::test_guard;
+
+    #[tokio::test]
+    async fn test_deep_check_reports_unusable_base_dir() {
+        let _guard = test_guard!();
+        let dir = tempfile::TempDir::new().expect("tempdir");
+        let file = dir.path().join("not-a-dir");
+        std::fs::write(&file, "").expect("write file");
+
+        let err = run_deep_check(&file.join("cache"), Duration::from_secs(5))
+            .await
+            .expect_err("a path under a file cannot hold the test crate");
+        assert!(err.starts_with("cannot create"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_deep_check_builds_trivial_crate() {
+        let _guard = test_guard!();
+        if std::process::Command::new("cargo")
+            .arg("--version")
+            .output()
+            .is_err()
+        {
+            return;
+        }
+        let dir = tempfile::TempDir::new().expect("tempdir");
+
+        run_deep_check(dir.path(), Duration::from_secs(120))
+            .await
+            .expect("trivial crate builds");
+        let leftovers = std::fs::read_dir(dir.path()).expect("read base").count();
+        assert_eq!(leftovers, 0, "scratch crate is removed afterwards");
+    }
+}
@@ -5,9 +5,10 @@ use rch_common::types::RequiredRuntime;
 use std::io::Write as _;
 use std::path::Path;
 use std::process::Stdio;
+use std::time::Duration;
 use thiserror::Error;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
-use tokio::process::Command;
+use tokio::process::{Child, Command};
 use tracing::{debug, error, info};
 
 /// Best-effort write of a worker-side message to the real stderr (the SSH
@@ -40,6 +41,116 @@ pub const EXIT_PREPARE_FAILED: i32 = 121;
 /// rather than a trusted-but-partial result (bd-review-wkr-output-truncation).
 pub const EXIT_OUTPUT_TRUNCATED: i32 = 122;
 
+/// Sentinel exit code returned when `--allowed-commands` is set and the command
+/// does not start with an allowlisted program. The command never ran, so this
+/// must not be confused with a build failure.
+pub const EXIT_COMMAND_NOT_ALLOWED: i32 = 123;
+
+/// Sentinel exit code returned when `--timeout-secs` elapsed and the command's
+/// process group was killed. Distinct from 124 so it cannot be confused with a
+/// `timeout(1)` inside the user's own command.
+pub const EXIT_TIMED_OUT: i32 = rch_common::command_outcome::EXIT_WORKER_TIMED_OUT;
+
+/// How long a timed-out process group gets to exit after SIGTERM before it is
+/// sent SIGKILL.
+const TERMINATE_GRACE: Duration = Duration::from_secs(5);
+
+/// Shell sequences that chain, substitute, background or redirect another
+/// command. With an allowlist in force these are refused outright, since the
+/// first token alone says nothing about what runs after them or which files
+/// they overwrite.
+const SHELL_CONTROL_SEQUENCES: &[&str] = &[";", "&", "|", "`", "$(", "${", ">", "<", "\n", "\r"];
+
+/// Leading `VAR=value` assignments accepted while an allowlist is in force.
+/// Anything else could swap the program that actually runs (`PATH`, `RUSTC`,
+/// `RUSTC_WRAPPER`) or inject code into it (`LD_PRELOAD`). `RUSTFLAGS` is
+/// further checked against [`UNSAFE_RUSTFLAGS`].
+const ALLOWLIST_SAFE_ENV_VARS: &[&str] = &[
+    "CARGO_TARGET_DIR",
+    "RUSTFLAGS",
+    "CARGO_INCREMENTAL",
+    "CARGO_BUILD_JOBS",
+    "CARGO_TERM_COLOR",
+    "RUST_BACKTRACE",
+];
+
+/// rustc options that run a program of the caller's choosing: `-C linker=`,
+/// `-C link-arg(s)=` (e.g. `-fuse-ld=/tmp/x`) and compiler plugins. Matched
+/// against the `RUSTFLAGS` value with quotes and whitespace removed, so
+/// `-C linker=`, `-Clinker=` and `--codegen linker=` are all caught.
+const UNSAFE_RUSTFLAGS: &[&str] = &["linker=", "link-arg", "plugin"];
+
+/// Check `command` against the worker's program allowlist.
+///
+/// An empty allowlist allows everything. Otherwise leading `VAR=value`
+/// assignments must name a variable in [`ALLOWLIST_SAFE_ENV_VARS`], a
+/// `rustup run <toolchain>` runner (added by the worker's own `--toolchain`
+/// handling) is skipped, and the next token must equal an allowlist entry
+/// exactly, so `cargo` does not admit `/tmp/x/cargo`. Returns the reason for
+/// rejection, if any.
+fn allowlist_violation(command: &str, allowed_commands: &[String]) -> Option<String> {
+    if allowed_commands.is_empty() {
+        return None;
+    }
+    if let Some(seq) = SHELL_CONTROL_SEQUENCES
+        .iter()
+        .find(|seq| command.contains(**seq))
+    {
+        return Some(format!(
+            "command contains shell control sequence '{}'",
+            seq.escape_default()
+        ));
+    }
+
+    let program_start = strip_leading_env_assignments(command);
+    let assignments = &command.trim_start()[..command.trim_start().len() - program_start.len()];
+    if let Some(name) = assignments
+        .split_whitespace()
+        .filter_map(|assignment| assignment.split_once('=').map(|(name, _)| name))
+        .find(|name| !ALLOWLIST_SAFE_ENV_VARS.contains(name))
+    {
+        return Some(format!(
+            "env assignment '{name}' is not allowed with an allowlist"
+        ));
+    }
+    if let Some(flag) = unsafe_rustflag(assignments) {
+        return Some(format!(
+            "RUSTFLAGS option '{flag}' is not allowed with an allowlist"
+        ));
+    }
+
+    let mut tokens = program_start.split_whitespace();
+    let mut program = tokens.next();
+    if program == Some("rustup") && tokens.clone().next() == Some("run") {
+        program = tokens.nth(2);
+    }
+    match program {
+        Some(program) if allowed_commands.iter().any(|allowed| allowed == program) => None,
+        Some(program) => Some(format!("program '{program}' is not in the allowlist")),
+        None => Some("command has no program".to_string()),
+    }
+}
+
+/// The first [`UNSAFE_RUSTFLAGS`] entry found in a `RUSTFLAGS=` value within
+/// the leading `assignments`, if any.
+fn unsafe_rustflag(assignments: &str) -> Option<&'static str> {
+    let (_, value) = assignments.split_once("RUSTFLAGS=")?;
+    // The value runs until the next allowed assignment, if one follows.
+    let end = ALLOWLIST_SAFE_ENV_VARS
+        .iter()
+        .filter_map(|name| value.find(&format!(" {name}=")))
+        .min()
+        .unwrap_or(value.len());
+    let compact: String = value[..end]
+        .chars()
+        .filter(|c| !c.is_whitespace() && !matches!(c, '\'' | '"'))
+        .collect();
+    UNSAFE_RUSTFLAGS
+        .iter()
+        .find(|flag| compact.contains(**flag))
+        .copied()
+}
+
 /// Strip leading `VAR=value` shell env assignments from a command.
 /// Returns the remainder. Tokens that look like flags (`-foo`) or that
 /// contain no `=` are not stripped. Path-valued env vars are handled
@@ -372,13 +483,39 @@ mod runtime_detection_tests {
     }
 }
 
+/// Refuse `command` with [`EXIT_COMMAND_NOT_ALLOWED`] if the allowlist does
+/// not admit it (see [`allowlist_violation`]).
+pub fn check_allowed(command: &str, allowed_commands: &[String]) -> Result<()> {
+    if let Some(reason) = allowlist_violation(command, allowed_commands) {
+        error!("Refusing command: {}", reason);
+        eprintln_best_effort(&format!(
+            "rch-wkr: refusing command ({reason}); allowed programs: {}",
+            allowed_commands.join(", ")
+        ));
+        return Err(CommandFailed {
+            exit_code: EXIT_COMMAND_NOT_ALLOWED,
+        }
+        .into());
+    }
+    Ok(())
+}
+
 /// Execute a command in the specified working directory.
 ///
 /// Streams stdout/stderr in real-time and returns Ok on success. For
 /// Bun/Node test runners, runs `prepare::prepare()` first to ensure
 /// `node_modules/` is in place (cache-aware via the dependency
 /// fingerprint stored in `<workdir>/.rch_dep_fingerprint.json`).
-pub async fn execute(workdir: &str, command: &str) -> Result<()> {
+///
+/// The command runs in its own process group. With a `timeout`, the whole
+/// group (the `sh -c` leader and everything the build spawned) is terminated
+/// once it elapses, and the result is [`EXIT_TIMED_OUT`].
+pub async fn execute(
+    workdir: &str,
+    command: &str,
+    allowed_commands: &[String],
+    timeout: Option<Duration>,
+) -> Result<()> {
     info!(
         "Executing in {}: {}",
         workdir,
@@ -389,6 +526,8 @@ pub async fn execute(workdir: &str, command: &str) -> Result<()> {
         anyhow::bail!("Empty command");
     }
 
+    check_allowed(command, allowed_commands)?;
+
     // br-4998x: pre-execution hook for Bun/Node projects.
     let runtime = detect_runtime_from_command(command);
     if matches!(runtime, RequiredRuntime::Bun | RequiredRuntime::Node) {
@@ -450,14 +589,18 @@ pub async fn execute(workdir: &str, command: &str) -> Result<()> {
 
     // Use shell execution to properly handle quoted arguments and shell features
     // This matches how the SSH client executes commands (sh -c "...")
-    let mut child = Command::new("sh")
-        .arg("-c")
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c")
         .arg(command)
         .current_dir(workdir)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
-        .kill_on_drop(true)
-        .spawn()?;
+        .kill_on_drop(true);
+    // Lead a new process group so a timeout reaches rustc, linkers and test
+    // binaries, not just the `sh -c` leader.
+    #[cfg(unix)]
+    cmd.process_group(0);
+    let mut child = cmd.spawn()?;
 
     // Stream stdout
     let mut stdout = child.stdout.take().expect("Failed to capture stdout");
@@ -468,7 +611,7 @@ pub async fn execute(workdir: &str, command: &str) -> Result<()> {
     // a write/flush error (the SSH channel back to the client closed/back-pressured
     // then failed) returns `false` so the caller knows the client saw a truncated
     // stream and must not trust a "success" exit status.
-    let stdout_task = tokio::spawn(async move {
+    let mut stdout_task = tokio::spawn(async move {
         let mut buffer = [0u8; 4096];
         let mut out = tokio::io::stdout();
         loop {
@@ -493,7 +636,7 @@ pub async fn execute(workdir: &str, command: &str) -> Result<()> {
         true
     });
 
-    let stderr_task = tokio::spawn(async move {
+    let mut stderr_task = tokio::spawn(async move {
         let mut buffer = [0u8; 4096];
         let mut err = tokio::io::stderr();
         loop {
@@ -519,7 +662,37 @@ pub async fn execute(workdir: &str, command: &str) -> Result<()> {
     });
 
     // Wait for process to complete
-    let status = child.wait().await?;
+    let status = match timeout {
+        None => child.wait().await?,
+        Some(limit) => match tokio::time::timeout(limit, child.wait()).await {
+            Ok(status) => status?,
+            Err(_) => {
+                error!(
+                    "Command exceeded timeout of {}s, terminating its process group",
+                    limit.as_secs()
+                );
+                eprintln_best_effort(&format!(
+                    "rch-wkr: command exceeded --timeout-secs {}; terminating its process group",
+                    limit.as_secs()
+                ));
+                terminate_process_group(&mut child).await;
+                // The relays end once the group's pipe ends are closed. Don't
+                // hang on anything that left the group and still holds them.
+                let relays = async {
+                    let _ = (&mut stdout_task).await;
+                    let _ = (&mut stderr_task).await;
+                };
+                if tokio::time::timeout(TERMINATE_GRACE, relays).await.is_err() {
+                    stdout_task.abort();
+                    stderr_task.abort();
+                }
+                return Err(CommandFailed {
+                    exit_code: EXIT_TIMED_OUT,
+                }
+                .into());
+            }
+        },
+    };
 
     // Wait for output tasks; a panicked relay task (JoinError) is treated as a
     // non-clean relay.
@@ -564,6 +737,38 @@ pub async fn execute(workdir: &str, command: &str) -> Result<()> {
     }
 }
 
+/// Terminate `child`'s process group: SIGTERM, up to [`TERMINATE_GRACE`] for
+/// the leader to exit, then SIGKILL for whatever is left, and reap the leader.
+async fn terminate_process_group(child: &mut Child) {
+    #[cfg(unix)]
+    if let Some(pgid) = child.id() {
+        signal_process_group(pgid, "TERM").await;
+        let _ = tokio::time::timeout(TERMINATE_GRACE, child.wait()).await;
+        // The leader exiting says nothing about the rest of the group.
+        signal_process_group(pgid, "KILL").await;
+    }
+    let _ = child.start_kill();
+    let _ = child.wait().await;
+}
+
+/// Send `signal` to every process in group `pgid` via kill(1); unsafe code is
+/// forbidden in this crate, so there is no direct `kill(2)`.
+#[cfg(unix)]
+async fn signal_process_group(pgid: u32, signal: &str) {
+    let result = Command::new("kill")
+        .args(["-s", signal, "--", &format!("-{pgid}")])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .await;
+    if let Err(e) = result {
+        error!(
+            "Failed to send SIG{} to process group {}: {}",
+            signal, pgid, e
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -573,7 +778,7 @@ mod tests {
     #[tokio::test]
     async fn test_execute_echo() {
         println!("TEST START: test_execute_echo");
-        let result = execute("/tmp", "echo hello").await;
+        let result = execute("/tmp", "echo hello", &[], None).await;
         assert!(result.is_ok(), "echo should succeed");
         println!("TEST PASS: test_execute_echo");
     }
@@ -581,7 +786,7 @@ mod tests {
     #[tokio::test]
     async fn test_execute_invalid_dir() {
         println!("TEST START: test_execute_invalid_dir");
-        let result = execute("/nonexistent/path", "ls").await;
+        let result = execute("/nonexistent/path", "ls", &[], None).await;
         assert!(result.is_err(), "should fail for nonexistent directory");
         println!("TEST PASS: test_execute_invalid_dir");
     }
@@ -589,7 +794,7 @@ mod tests {
     #[tokio::test]
     async fn test_execute_empty_command() {
         println!("TEST START: test_execute_empty_command");
-        let result = execute("/tmp", "").await;
+        let result = execute("/tmp", "", &[], None).await;
         assert!(result.is_err(), "empty command should fail");
         let err = result.unwrap_err().to_string();
         assert!(
@@ -602,7 +807,7 @@ mod tests {
     #[tokio::test]
     async fn test_execute_whitespace_only_command() {
         println!("TEST START: test_execute_whitespace_only_command");
-        let result = execute("/tmp", "   \t\n  ").await;
+        let result = execute("/tmp", "   \t\n  ", &[], None).await;
         assert!(result.is_err(), "whitespace-only command should fail");
         println!("TEST PASS: test_execute_whitespace_only_command");
     }
@@ -610,7 +815,7 @@ mod tests {
     #[tokio::test]
     async fn test_execute_command_with_arguments() {
         println!("TEST START: test_execute_command_with_arguments");
-        let result = execute("/tmp", "echo -n test").await;
+        let result = execute("/tmp", "echo -n test", &[], None).await;
         assert!(result.is_ok(), "echo with args should succeed");
         println!("TEST PASS: test_execute_command_with_arguments");
     }
@@ -618,7 +823,7 @@ mod tests {
     #[tokio::test]
     async fn test_execute_command_with_quotes() {
         println!("TEST START: test_execute_command_with_quotes");
-        let result = execute("/tmp", "echo 'hello world'").await;
+        let result = execute("/tmp", "echo 'hello world'", &[], None).await;
         assert!(result.is_ok(), "command with single quotes should work");
         println!("TEST PASS: test_execute_command_with_quotes");
     }
@@ -626,7 +831,7 @@ mod tests {
     #[tokio::test]
     async fn test_execute_command_with_double_quotes() {
         println!("TEST START: test_execute_command_with_double_quotes");
-        let result = execute("/tmp", r#"echo "hello world""#).await;
+        let result = execute("/tmp", r#"echo "hello world""#, &[], None).await;
         assert!(result.is_ok(), "command with double quotes should work");
         println!("TEST PASS: test_execute_command_with_double_quotes");
     }
@@ -634,7 +839,7 @@ mod tests {
     #[tokio::test]
     async fn test_execute_piped_commands() {
         println!("TEST START: test_execute_piped_commands");
-        let result = execute("/tmp", "echo hello | cat").await;
+        let result = execute("/tmp", "echo hello | cat", &[], None).await;
         assert!(result.is_ok(), "piped commands should work");
         println!("TEST PASS: test_execute_piped_commands");
     }
@@ -642,7 +847,7 @@ mod tests {
     #[tokio::test]
     async fn test_execute_chained_commands() {
         println!("TEST START: test_execute_chained_commands");
-        let result = execute("/tmp", "echo first && echo second").await;
+        let result = execute("/tmp", "echo first && echo second", &[], None).await;
         assert!(result.is_ok(), "chained commands should work");
         println!("TEST PASS: test_execute_chained_commands");
     }
@@ -650,7 +855,7 @@ mod tests {
     #[tokio::test]
     async fn test_execute_env_variable_expansion() {
         println!("TEST START: test_execute_env_variable_expansion");
-        let result = execute("/tmp", "echo $HOME").await;
+        let result = execute("/tmp", "echo $HOME", &[], None).await;
         assert!(result.is_ok(), "env variable expansion should work");
         println!("TEST PASS: test_execute_env_variable_expansion");
     }
@@ -658,7 +863,7 @@ mod tests {
     #[tokio::test]
     async fn test_execute_command_substitution() {
         println!("TEST START: test_execute_command_substitution");
-        let result = execute("/tmp", "echo $(echo nested)").await;
+        let result = execute("/tmp", "echo $(echo nested)", &[], None).await;
         assert!(result.is_ok(), "command substitution should work");
         println!("TEST PASS: test_execute_command_substitution");
     }
@@ -667,7 +872,7 @@ mod tests {
     async fn test_execute_glob_patterns() {
         println!("TEST START: test_execute_glob_patterns");
         // List all .txt files (may be none, but should not error)
-        let result = execute("/tmp", "ls *.nonexistent 2>/dev/null || true").await;
+        let result = execute("/tmp", "ls *.nonexistent 2>/dev/null || true", &[], None).await;
         assert!(result.is_ok(), "glob pattern command should execute");
         println!("TEST PASS: test_execute_glob_patterns");
     }
@@ -687,7 +892,21 @@ mod tests {
         assert_ne!(EXIT_OUTPUT_TRUNCATED, 0);
         assert_ne!(EXIT_OUTPUT_TRUNCATED, 1);
         assert_ne!(EXIT_PREPARE_FAILED, EXIT_OUTPUT_TRUNCATED);
-        for code in [EXIT_PREPARE_FAILED, EXIT_OUTPUT_TRUNCATED] {
+        assert_ne!(EXIT_COMMAND_NOT_ALLOWED, EXIT_PREPARE_FAILED);
+        assert_ne!(EXIT_COMMAND_NOT_ALLOWED, EXIT_OUTPUT_TRUNCATED);
+        for other in [
+            EXIT_PREPARE_FAILED,
+            EXIT_OUTPUT_TRUNCATED,
+            EXIT_COMMAND_NOT_ALLOWED,
+        ] {
+            assert_ne!(EXIT_TIMED_OUT, other);
+        }
+        for code in [
+            EXIT_PREPARE_FAILED,
+            EXIT_OUTPUT_TRUNCATED,
+            EXIT_COMMAND_NOT_ALLOWED,
+            EXIT_TIMED_OUT,
+        ] {
             assert!(
                 (2..124).contains(&code),
                 "sentinel {code} should be a plain, non-reserved exit code"
@@ -695,10 +914,123 @@ mod tests {
         }
     }
 
+    // === Allowlist Tests ===
+
+    fn allowlist(programs: &[&str]) -> Vec<String> {
+        programs.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn test_allowlist_checks_program_after_env_and_rustup_runner() {
+        let allowed = allowlist(&["cargo", "rustc"]);
+        assert_eq!(allowlist_violation("rm -rf /", &[]), None);
+        assert_eq!(allowlist_violation("cargo build --release", &allowed), None);
+        assert_eq!(
+            allowlist_violation(
+                "RUSTFLAGS=-Dwarnings CARGO_TARGET_DIR=/t cargo test",
+                &allowed
+            ),
+            None
+        );
+        assert_eq!(
+            allowlist_violation("rustup run nightly-2024-01-15 cargo build", &allowed),
+            None
+        );
+        assert!(allowlist_violation("rustup run nightly sh -c id", &allowed).is_some());
+        assert!(allowlist_violation("/tmp/evil/cargo build", &allowed).is_some());
+        assert!(allowlist_violation("python3 x.py", &allowed).is_some());
+    }
+
+    #[test]
+    fn test_allowlist_rejects_chained_commands() {
+        let allowed = allowlist(&["cargo"]);
+        for command in [
+            "cargo build; curl evil",
+            "cargo build && sh",
+            "cargo build | sh",
+            "cargo build `id`",
+            "cargo build $(id)",
+            "cargo build ${HOME}",
+            "cargo build\nid",
+            "cargo build > /home/builder/.bashrc",
+            "cargo build < /etc/shadow",
+        ] {
+            assert!(
+                allowlist_violation(command, &allowed).is_some(),
+                "{command:?} should be refused"
+            );
+        }
+    }
+
+    #[test]
+    fn test_allowlist_rejects_env_assignment_bypasses() {
+        let allowed = allowlist(&["cargo"]);
+        for command in [
+            "PATH=/data/projects/x/bin cargo build",
+            "RUSTC=./evil cargo build",
+            "RUSTC_WRAPPER=/tmp/w cargo build",
+            "LD_PRELOAD=/tmp/evil.so cargo build",
+            "RUSTFLAGS=-Dwarnings PATH=/tmp cargo build",
+        ] {
+            let reason = allowlist_violation(command, &allowed)
+                .unwrap_or_else(|| panic!("{command:?} should be refused"));
+            assert!(reason.contains("env assignment"), "{reason}");
+        }
+    }
+
+    #[test]
+    fn test_allowlist_rejects_rustflags_that_run_programs() {
+        let allowed = allowlist(&["cargo"]);
+        for command in [
+            "RUSTFLAGS=-Clinker=/tmp/x cargo build",
+            "RUSTFLAGS='-C linker=/tmp/x' cargo build",
+            "RUSTFLAGS=\"--codegen linker=/tmp/x\" cargo build",
+            "RUSTFLAGS='-C link-arg=-fuse-ld=/tmp/x' cargo build",
+            "CARGO_TARGET_DIR=/t RUSTFLAGS=-Clink-args=-B/tmp cargo build",
+            "RUSTFLAGS=-Zplugin=/tmp/p.so cargo build",
+        ] {
+            assert!(
+                allowlist_violation(command, &allowed).is_some(),
+                "{command:?} should be refused"
+            );
+        }
+        assert!(
+            allowlist_violation("RUSTFLAGS=-Clinker=/tmp/x cargo build", &allowed)
+                .is_some_and(|reason| reason.contains("RUSTFLAGS"))
+        );
+        assert_eq!(
+            allowlist_violation(
+                "RUSTFLAGS=-Ctarget-cpu=native CARGO_TARGET_DIR=/t/linker=x cargo build",
+                &allowed
+            ),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_refuses_disallowed_command() {
+        let result = execute(
+            "/tmp",
+            "touch /tmp/rch-wkr-should-not-exist",
+            &allowlist(&["cargo"]),
+            None,
+        )
+        .await;
+        let err = result.expect_err("disallowed command should be refused");
+        let failed = err
+            .downcast_ref::<CommandFailed>()
+            .expect("refusal should carry an exit code");
+        assert_eq!(failed.exit_code, EXIT_COMMAND_NOT_ALLOWED);
+        assert!(!Path::new("/tmp/rch-wkr-should-not-exist").exists());
+
+        let result = execute("/tmp", "true", &allowlist(&["true"]), None).await;
+        assert!(result.is_ok(), "allowlisted command should run");
+    }
+
     #[tokio::test]
     async fn test_execute_exit_code_zero() {
         println!("TEST START: test_execute_exit_code_zero");
-        let result = execute("/tmp", "exit 0").await;
+        let result = execute("/tmp", "exit 0", &[], None).await;
         assert!(result.is_ok(), "exit 0 should succeed");
         println!("TEST PASS: test_execute_exit_code_zero");
     }
@@ -706,7 +1038,7 @@ mod tests {
     #[tokio::test]
     async fn test_execute_exit_code_one() {
         println!("TEST START: test_execute_exit_code_one");
-        let result = execute("/tmp", "exit 1").await;
+        let result = execute("/tmp", "exit 1", &[], None).await;
         assert!(result.is_err(), "exit 1 should fail");
 
         let err = result.unwrap_err();
@@ -722,7 +1054,7 @@ mod tests {
     #[tokio::test]
     async fn test_execute_exit_code_42() {
         println!("TEST START: test_execute_exit_code_42");
-        let result = execute("/tmp", "exit 42").await;
+        let result = execute("/tmp", "exit 42", &[], None).await;
         assert!(result.is_err(), "exit 42 should fail");
 
         let err = result.unwrap_err();
@@ -738,7 +1070,7 @@ mod tests {
     #[tokio::test]
     async fn test_execute_exit_code_255() {
         println!("TEST START: test_execute_exit_code_255");
-        let result = execute("/tmp", "exit 255").await;
+        let result = execute("/tmp", "exit 255", &[], None).await;
         assert!(result.is_err(), "exit 255 should fail");
 
         let err = result.unwrap_err();
@@ -753,7 +1085,7 @@ mod tests {
     #[tokio::test]
     async fn test_execute_false_command() {
         println!("TEST START: test_execute_false_command");
-        let result = execute("/tmp", "false").await;
+        let result = execute("/tmp", "false", &[], None).await;
         assert!(result.is_err(), "false command should fail");
 
         let err = result.unwrap_err();
@@ -768,7 +1100,7 @@ mod tests {
     #[tokio::test]
     async fn test_execute_command_not_found() {
         println!("TEST START: test_execute_command_not_found");
-        let result = execute("/tmp", "nonexistent_command_xyz123").await;
+        let result = execute("/tmp", "nonexistent_command_xyz123", &[], None).await;
         assert!(result.is_err(), "nonexistent command should fail");
 
         let err = result.unwrap_err();
@@ -788,7 +1120,7 @@ mod tests {
     async fn test_execute_stdout_output() {
         println!("TEST START: test_execute_stdout_output");
         // The output goes to actual stdout, we just verify the command works
-        let result = execute("/tmp", "echo 'stdout test line'").await;
+        let result = execute("/tmp", "echo 'stdout test line'", &[], None).await;
         assert!(result.is_ok(), "stdout output should work");
         println!("TEST PASS: test_execute_stdout_output");
     }
@@ -797,7 +1129,7 @@ mod tests {
     async fn test_execute_stderr_output() {
         println!("TEST START: test_execute_stderr_output");
         // Redirect to stderr and verify command works
-        let result = execute("/tmp", "echo 'stderr test line' >&2").await;
+        let result = execute("/tmp", "echo 'stderr test line' >&2", &[], None).await;
         assert!(result.is_ok(), "stderr output should work");
         println!("TEST PASS: test_execute_stderr_output");
     }
@@ -805,7 +1137,13 @@ mod tests {
     #[tokio::test]
     async fn test_execute_mixed_stdout_stderr() {
         println!("TEST START: test_execute_mixed_stdout_stderr");
-        let result = execute("/tmp", "echo stdout; echo stderr >&2; echo stdout2").await;
+        let result = execute(
+            "/tmp",
+            "echo stdout; echo stderr >&2; echo stdout2",
+            &[],
+            None,
+        )
+        .await;
         assert!(result.is_ok(), "mixed output should work");
         println!("TEST PASS: test_execute_mixed_stdout_stderr");
     }
@@ -813,7 +1151,7 @@ mod tests {
     #[tokio::test]
     async fn test_execute_multiline_output() {
         println!("TEST START: test_execute_multiline_output");
-        let result = execute("/tmp", "echo line1; echo line2; echo line3").await;
+        let result = execute("/tmp", "echo line1; echo line2; echo line3", &[], None).await;
         assert!(result.is_ok(), "multiline output should work");
         println!("TEST PASS: test_execute_multiline_output");
     }
@@ -822,7 +1160,7 @@ mod tests {
     async fn test_execute_large_output() {
         println!("TEST START: test_execute_large_output");
         // Generate many lines of output
-        let result = execute("/tmp", "seq 1 1000").await;
+        let result = execute("/tmp", "seq 1 1000", &[], None).await;
         assert!(result.is_ok(), "large output should work");
         println!("TEST PASS: test_execute_large_output");
     }
@@ -831,7 +1169,7 @@ mod tests {
     async fn test_execute_binary_like_output() {
         println!("TEST START: test_execute_binary_like_output");
         // Generate some binary-like output (null bytes get handled)
-        let result = execute("/tmp", "printf 'text\\nmore text'").await;
+        let result = execute("/tmp", "printf 'text\\nmore text'", &[], None).await;
         assert!(result.is_ok(), "output with special chars should work");
         println!("TEST PASS: test_execute_binary_like_output");
     }
@@ -847,7 +1185,7 @@ mod tests {
         std::fs::create_dir_all(&temp_dir).unwrap();
         std::fs::write(temp_dir.join("marker.txt"), "exists").unwrap();
 
-        let result = execute(temp_dir.to_str().unwrap(), "test -f marker.txt").await;
+        let result = execute(temp_dir.to_str().unwrap(), "test -f marker.txt", &[], None).await;
         assert!(result.is_ok(), "should find marker file in workdir");
 
         // Cleanup
@@ -859,7 +1197,7 @@ mod tests {
     async fn test_execute_pwd_matches_workdir() {
         println!("TEST START: test_execute_pwd_matches_workdir");
         // pwd should return the workdir
-        let result = execute("/tmp", "pwd").await;
+        let result = execute("/tmp", "pwd", &[], None).await;
         assert!(result.is_ok(), "pwd should work");
         println!("TEST PASS: test_execute_pwd_matches_workdir");
     }
@@ -874,7 +1212,7 @@ mod tests {
         std::fs::write(subdir.join("file.txt"), "test").unwrap();
 
         // Access file via relative path
-        let result = execute(temp_dir.to_str().unwrap(), "cat subdir/file.txt").await;
+        let result = execute(temp_dir.to_str().unwrap(), "cat subdir/file.txt", &[], None).await;
         assert!(result.is_ok(), "relative paths should work");
 
         // Cleanup
@@ -887,7 +1225,7 @@ mod tests {
     #[tokio::test]
     async fn test_execute_quick_command() {
         println!("TEST START: test_execute_quick_command");
-        let result = execute("/tmp", "true").await;
+        let result = execute("/tmp", "true", &[], None).await;
         assert!(result.is_ok(), "true command should succeed immediately");
         println!("TEST PASS: test_execute_quick_command");
     }
@@ -897,7 +1235,7 @@ mod tests {
         println!("TEST START: test_execute_command_with_sleep");
         // Short sleep to verify async execution works
         let start = std::time::Instant::now();
-        let result = execute("/tmp", "sleep 0.1").await;
+        let result = execute("/tmp", "sleep 0.1", &[], None).await;
         let elapsed = start.elapsed();
 
         assert!(result.is_ok(), "sleep command should succeed");
@@ -908,11 +1246,55 @@ mod tests {
         println!("TEST PASS: test_execute_command_with_sleep");
     }
 
+    #[tokio::test]
+    async fn test_execute_timeout_kills_process_group() {
+        println!("TEST START: test_execute_timeout_kills_process_group");
+        let temp_dir = tempfile::tempdir().unwrap();
+        let marker = temp_dir.path().join("survivor");
+        // A backgrounded grandchild that would outlive a kill of `sh -c` alone.
+        let command = format!("(sleep 1; touch {}) & sleep 30", marker.display());
+
+        let start = std::time::Instant::now();
+        let result = execute(
+            temp_dir.path().to_str().unwrap(),
+            &command,
+            &[],
+            Some(Duration::from_millis(200)),
+        )
+        .await;
+        let elapsed = start.elapsed();
+
+        let err = result.expect_err("timed-out command should fail");
+        let failed = err
+            .downcast_ref::<CommandFailed>()
+            .expect("timeout should carry an exit code");
+        assert_eq!(failed.exit_code, EXIT_TIMED_OUT);
+        assert!(
+            elapsed < Duration::from_secs(10),
+            "timeout should not wait for the sleep: {elapsed:?}"
+        );
+
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert!(
+            !marker.exists(),
+            "grandchild in the process group should have been killed"
+        );
+        println!("TEST PASS: test_execute_timeout_kills_process_group");
+    }
+
+    #[tokio::test]
+    async fn test_execute_within_timeout_succeeds() {
+        println!("TEST START: test_execute_within_timeout_succeeds");
+        let result = execute("/tmp", "sleep 0.1", &[], Some(Duration::from_secs(30))).await;
+        assert!(result.is_ok(), "command finishing in time should succeed");
+        println!("TEST PASS: test_execute_within_timeout_succeeds");
+    }
+
     #[tokio::test]
     async fn test_execute_sigpipe_handling() {
         println!("TEST START: test_execute_sigpipe_handling");
         // Generate large output but only read first line - tests SIGPIPE handling
-        let result = execute("/tmp", "yes | head -n 1").await;
+        let result = execute("/tmp", "yes | head -n 1", &[], None).await;
         assert!(result.is_ok(), "sigpipe scenario should handle gracefully");
         println!("TEST PASS: test_execute_sigpipe_handling");
     }
@@ -952,7 +1334,7 @@ mod tests {
     #[tokio::test]
     async fn test_execute_special_characters_in_command() {
         println!("TEST START: test_execute_special_characters_in_command");
-        let result = execute("/tmp", "echo '$HOME' \"$HOME\"").await;
+        let result = execute("/tmp", "echo '$HOME' \"$HOME\"", &[], None).await;
         assert!(result.is_ok(), "special characters should work");
         println!("TEST PASS: test_execute_special_characters_in_command");
     }
@@ -960,7 +1342,7 @@ mod tests {
     #[tokio::test]
     async fn test_execute_backslash_in_command() {
         println!("TEST START: test_execute_backslash_in_command");
-        let result = execute("/tmp", "echo 'back\\slash'").await;
+        let result = execute("/tmp", "echo 'back\\slash'", &[], None).await;
         assert!(result.is_ok(), "backslash should work");
         println!("TEST PASS: test_execute_backslash_in_command");
     }
@@ -972,7 +1354,7 @@ mod tests {
             std::env::temp_dir().join(format!("rch-test-redirect-{}.txt", std::process::id()));
         let cmd = format!("echo 'redirect test' > '{}'", temp_file.display());
 
-        let result = execute("/tmp", &cmd).await;
+        let result = execute("/tmp", &cmd, &[], None).await;
         assert!(result.is_ok(), "redirect should work");
 
         // Verify file was created
@@ -992,7 +1374,7 @@ mod tests {
     async fn test_execute_background_command_in_subshell() {
         println!("TEST START: test_execute_background_command_in_subshell");
         // Background job in subshell should complete
-        let result = execute("/tmp", "(echo bg_test &); sleep 0.1").await;
+        let result = execute("/tmp", "(echo bg_test &); sleep 0.1", &[], None).await;
         assert!(result.is_ok(), "background command should work");
         println!("TEST PASS: test_execute_background_command_in_subshell");
     }
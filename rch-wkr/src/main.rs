@@ -6,6 +6,7 @@
 #![forbid(unsafe_code)]
 
 mod cache;
+mod deep_check;
 mod executor;
 mod prepare;
 mod toolchain;
@@ -51,13 +52,66 @@ enum Commands {
         /// `rustup run <toolchain>`.
         #[arg(short, long)]
         toolchain: Option<String>,
+
+        /// Only run commands whose program is one of these (comma-separated,
+        /// e.g. "cargo,rustc,gcc,clang,make,ninja,bun")
+        ///
+        /// Anything else, or any command that chains further commands with `;`,
+        /// `&`, `|` or substitution, is refused with exit code 123 without
+        /// running. Empty allows everything. The rch client asks for this check
+        /// before every build; it only guards against honest mistakes, since a
+        /// client with SSH access can run commands without it.
+        #[arg(
+            long,
+            env = "RCH_WKR_ALLOWED_COMMANDS",
+            value_delimiter = ',',
+            value_name = "PROGRAMS"
+        )]
+        allowed_commands: Vec<String>,
+
+        /// Check this command against the allowlist instead of --command
+        ///
+        /// For when --command only wraps the build (color env, container
+        /// client): the wrapper runs as given once the build itself passes.
+        #[arg(long, value_name = "COMMAND")]
+        check_command: Option<String>,
+
+        /// Check against the allowlist and exit without running anything
+        ///
+        /// Exits 0 when the command is allowed and 123 when it is refused. Only
+        /// clients that call it are checked; this is not a security boundary.
+        #[arg(long)]
+        check_only: bool,
+
+        /// Kill the command's whole process group after this many seconds
+        ///
+        /// Enforced on the worker, so the build stops even if the client that
+        /// started it is gone. A timeout exits with code 120. 0 disables.
+        #[arg(long, value_name = "SECS")]
+        timeout_secs: Option<u64>,
     },
 
     /// Respond to health check
-    Health,
+    ///
+    /// Prints `OK`. With `--deep`, first builds a trivial crate under the
+    /// project cache and prints `FAILED: <reason>` (exit 1) if that fails.
+    #[command(alias = "healthcheck")]
+    Health {
+        /// Verify a build can complete, not just that the worker answers
+        #[arg(long)]
+        deep: bool,
 
-    /// Report system info (human-readable)
-    Info,
+        /// Give up on the deep check's build after this many seconds
+        #[arg(long, value_name = "SECS", default_value_t = deep_check::DEFAULT_DEEP_CHECK_TIMEOUT_SECS)]
+        timeout_secs: u64,
+    },
+
+    /// Report system info (cores, memory, toolchain and tool versions)
+    Info {
+        /// Output JSON instead of human-readable text
+        #[arg(long)]
+        json: bool,
+    },
 
     /// Report runtime capabilities (JSON output for daemon)
     ///
@@ -91,6 +145,10 @@ enum Commands {
         #[arg(long)]
         no_network: bool,
 
+        /// Collect GPU utilization and memory via nvidia-smi (off by default)
+        #[arg(long)]
+        gpu: bool,
+
         /// Override worker ID (defaults to RCH_WORKER_ID or HOSTNAME)
         #[arg(long)]
         worker_id: Option<String>,
@@ -169,7 +227,34 @@ async fn main() -> Result<()> {
             workdir,
             command,
             toolchain,
+            allowed_commands,
+            check_command,
+            check_only,
+            timeout_secs,
         } => {
+            let mut allowed_commands: Vec<String> = allowed_commands
+                .iter()
+                .map(|program| program.trim().to_string())
+                .filter(|program| !program.is_empty())
+                .collect();
+            if check_only || check_command.is_some() {
+                let checked = check_command.as_deref().unwrap_or(&command);
+                if let Err(err) = executor::check_allowed(checked, &allowed_commands) {
+                    if let Some(failure) = err.downcast_ref::<executor::CommandFailed>() {
+                        std::process::exit(failure.exit_code);
+                    }
+                    return Err(err);
+                }
+                if check_only {
+                    return Ok(());
+                }
+                // The build passed; the wrapper around it is not checked.
+                allowed_commands.clear();
+            }
+            let timeout = timeout_secs
+                .filter(|secs| *secs > 0)
+                .map(std::time::Duration::from_secs);
+
             // Prepare the command, optionally wrapping with toolchain
             let final_command = if let Some(tc_str) = toolchain {
                 // Parse toolchain string and ensure it's available
@@ -192,7 +277,14 @@ async fn main() -> Result<()> {
                         // Touch the project cache to prevent cleanup
                         cache::touch_project(std::path::Path::new(&workdir));
 
-                        return match executor::execute(&workdir, &command).await {
+                        return match executor::execute(
+                            &workdir,
+                            &command,
+                            &allowed_commands,
+                            timeout,
+                        )
+                        .await
+                        {
                             Ok(()) => Ok(()),
                             Err(err) => {
                                 if let Some(failure) = err.downcast_ref::<executor::CommandFailed>()
@@ -214,7 +306,7 @@ async fn main() -> Result<()> {
             // Touch the project cache to prevent cleanup
             cache::touch_project(std::path::Path::new(&workdir));
 
-            match executor::execute(&workdir, &final_command).await {
+            match executor::execute(&workdir, &final_command, &allowed_commands, timeout).await {
                 Ok(()) => Ok(()),
                 Err(err) => {
                     if let Some(failure) = err.downcast_ref::<executor::CommandFailed>() {
@@ -224,12 +316,27 @@ async fn main() -> Result<()> {
                 }
             }
         }
-        Commands::Health => {
+        Commands::Health { deep, timeout_secs } => {
+            if deep {
+                let timeout = std::time::Duration::from_secs(timeout_secs.max(1));
+                match deep_check::run_deep_check(&cache::get_cache_base(), timeout).await {
+                    Ok(elapsed) => info!("Deep health check passed in {:?}", elapsed),
+                    Err(reason) => {
+                        println!("FAILED: {}", reason);
+                        std::process::exit(1);
+                    }
+                }
+            }
             println!("OK");
             Ok(())
         }
-        Commands::Info => {
-            print_system_info();
+        Commands::Info { json } => {
+            let info = probe_system_info();
+            if json {
+                println!("{}", serde_json::to_string(&info)?);
+            } else {
+                print_system_info(&info);
+            }
             Ok(())
         }
         Commands::Capabilities => {
@@ -244,11 +351,12 @@ async fn main() -> Result<()> {
             sample_ms,
             no_disk,
             no_network,
+            gpu,
             worker_id,
         } => {
             use rch_telemetry::collect::{collect_telemetry, resolve_worker_id};
             let worker_id = resolve_worker_id(worker_id);
-            let telemetry = collect_telemetry(sample_ms, !no_disk, !no_network, worker_id)?;
+            let telemetry = collect_telemetry(sample_ms, !no_disk, !no_network, gpu, worker_id)?;
 
             let output = match format {
                 OutputFormat::Json => telemetry.to_json()?,
@@ -290,98 +398,130 @@ async fn main() -> Result<()> {
     }
 }
 
-fn print_system_info() {
-    use std::process::Command;
+/// System info reported by `rch-wkr info`. `None` means the tool is missing
+/// or its version could not be read.
+#[derive(Debug, Default, serde::Serialize)]
+struct SystemInfo {
+    cores: Option<u32>,
+    /// Total memory as reported by `free -h` (e.g. `"62Gi"`).
+    memory: Option<String>,
+    rustc: Option<String>,
+    cargo: Option<String>,
+    gcc: Option<String>,
+    clang: Option<String>,
+    zstd: Option<String>,
+    rsync: Option<String>,
+    sccache: Option<String>,
+    bun: Option<String>,
+    node: Option<String>,
+    npm: Option<String>,
+}
 
-    println!("=== System Info ===");
+/// First non-empty stdout line of a successful `<program> <args>`.
+fn probe_version_line(program: &str, args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new(program)
+        .args(args)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    first_stdout_line(&String::from_utf8_lossy(&output.stdout))
+}
 
-    // CPU cores
-    if let Ok(output) = Command::new("nproc").output()
-        && let Ok(cores) = String::from_utf8_lossy(&output.stdout)
-            .trim()
-            .parse::<u32>()
-    {
-        println!("Cores: {}", cores);
+/// Transfer tools the capabilities probe did not find, by command name.
+fn missing_transfer_tools(capabilities: &WorkerCapabilities) -> Vec<String> {
+    [
+        ("rsync", capabilities.rsync_version.is_none()),
+        ("zstd", capabilities.zstd_version.is_none()),
+    ]
+    .into_iter()
+    .filter(|(_, missing)| *missing)
+    .map(|(tool, _)| tool.to_string())
+    .collect()
+}
+
+fn first_stdout_line(stdout: &str) -> Option<String> {
+    stdout
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+/// Parse the total from the `Mem:` row of `free -h`.
+fn parse_free_total(stdout: &str) -> Option<String> {
+    stdout
+        .lines()
+        .find(|line| line.starts_with("Mem:"))
+        .and_then(|line| line.split_whitespace().nth(1))
+        .map(str::to_string)
+}
+
+fn probe_memory_total() -> Option<String> {
+    let output = std::process::Command::new("free")
+        .args(["-h"])
+        .output()
+        .ok()?;
+    parse_free_total(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Probe everything `rch-wkr info` reports. Shared by the human and JSON
+/// output so the two cannot drift.
+fn probe_system_info() -> SystemInfo {
+    SystemInfo {
+        cores: probe_num_cpus(),
+        memory: probe_memory_total(),
+        rustc: probe_version_line("rustc", &["--version"]),
+        cargo: probe_version_line("cargo", &["--version"]),
+        gcc: probe_version_line("gcc", &["--version"]),
+        clang: probe_version_line("clang", &["--version"]),
+        zstd: probe_version_line("zstd", &["--version"]),
+        rsync: probe_version_line("rsync", &["--version"]),
+        sccache: probe_version_line("sccache", &["--version"]),
+        bun: run_bun_version_command()
+            .filter(|output| output.status.success())
+            .and_then(|output| first_stdout_line(&String::from_utf8_lossy(&output.stdout))),
+        node: probe_version_line("node", &["--version"]),
+        npm: probe_version_line("npm", &["--version"]),
     }
+}
 
-    // Memory
-    if let Ok(output) = Command::new("free").args(["-h"]).output() {
-        let output_str = String::from_utf8_lossy(&output.stdout);
-        for line in output_str.lines() {
-            if line.starts_with("Mem:") {
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() >= 2 {
-                    println!("Memory: {}", parts[1]);
-                }
-            }
+fn print_system_info(info: &SystemInfo) {
+    fn line(label: &str, value: Option<&String>) {
+        if let Some(value) = value {
+            println!("{}: {}", label, value);
         }
     }
+    fn runtime_line(label: &str, value: Option<&String>) {
+        println!(
+            "{}: {}",
+            label,
+            value.map(String::as_str).unwrap_or("not installed")
+        );
+    }
+
+    println!("=== System Info ===");
+    line("Cores", info.cores.map(|cores| cores.to_string()).as_ref());
+    line("Memory", info.memory.as_ref());
 
-    // Rust toolchain
     println!("\n=== Rust ===");
-    if let Ok(output) = Command::new("rustc").args(["--version"]).output() {
-        println!("rustc: {}", String::from_utf8_lossy(&output.stdout).trim());
-    }
-    if let Ok(output) = Command::new("cargo").args(["--version"]).output() {
-        println!("cargo: {}", String::from_utf8_lossy(&output.stdout).trim());
-    }
+    line("rustc", info.rustc.as_ref());
+    line("cargo", info.cargo.as_ref());
 
-    // C/C++ compilers
     println!("\n=== C/C++ ===");
-    if let Ok(output) = Command::new("gcc").args(["--version"]).output() {
-        let first_line = String::from_utf8_lossy(&output.stdout)
-            .lines()
-            .next()
-            .unwrap_or("")
-            .to_string();
-        println!("gcc: {}", first_line);
-    }
-    if let Ok(output) = Command::new("clang").args(["--version"]).output() {
-        let first_line = String::from_utf8_lossy(&output.stdout)
-            .lines()
-            .next()
-            .unwrap_or("")
-            .to_string();
-        println!("clang: {}", first_line);
-    }
-
-    // Tools
+    line("gcc", info.gcc.as_ref());
+    line("clang", info.clang.as_ref());
+
     println!("\n=== Tools ===");
-    if let Ok(output) = Command::new("zstd").args(["--version"]).output() {
-        println!("zstd: {}", String::from_utf8_lossy(&output.stdout).trim());
-    }
-    if let Ok(output) = Command::new("rsync").args(["--version"]).output() {
-        let first_line = String::from_utf8_lossy(&output.stdout)
-            .lines()
-            .next()
-            .unwrap_or("")
-            .to_string();
-        println!("rsync: {}", first_line);
-    }
+    line("zstd", info.zstd.as_ref());
+    line("rsync", info.rsync.as_ref());
+    line("sccache", info.sccache.as_ref());
 
-    // JavaScript/TypeScript runtimes
     println!("\n=== JavaScript Runtimes ===");
-    if let Ok(output) = Command::new("bun").args(["--version"]).output() {
-        if output.status.success() {
-            println!("bun: {}", String::from_utf8_lossy(&output.stdout).trim());
-        }
-    } else {
-        println!("bun: not installed");
-    }
-    if let Ok(output) = Command::new("node").args(["--version"]).output() {
-        if output.status.success() {
-            println!("node: {}", String::from_utf8_lossy(&output.stdout).trim());
-        }
-    } else {
-        println!("node: not installed");
-    }
-    if let Ok(output) = Command::new("npm").args(["--version"]).output() {
-        if output.status.success() {
-            println!("npm: {}", String::from_utf8_lossy(&output.stdout).trim());
-        }
-    } else {
-        println!("npm: not installed");
-    }
+    runtime_line("bun", info.bun.as_ref());
+    runtime_line("node", info.node.as_ref());
+    runtime_line("npm", info.npm.as_ref());
 }
 
 /// Probe runtime capabilities and return structured data.
@@ -431,6 +571,23 @@ fn probe_capabilities() -> WorkerCapabilities {
         }
     }
 
+    // Installed rustup toolchains, so the daemon can prefer workers that
+    // already have the one a build asks for.
+    capabilities.installed_toolchains = toolchain::installed_toolchains();
+
+    // Probe sccache version (transfer.use_sccache)
+    if let Ok(output) = Command::new("sccache").args(["--version"]).output()
+        && output.status.success()
+    {
+        let version = String::from_utf8_lossy(&output.stdout);
+        capabilities.sccache_version = parse_sccache_version_stdout(&version);
+    }
+
+    // Probe the transfer tools; the daemon stops selecting a worker without rsync.
+    capabilities.rsync_version = probe_version_line("rsync", &["--version"]);
+    capabilities.zstd_version = probe_version_line("zstd", &["--version"]);
+    capabilities.missing_tools = missing_transfer_tools(&capabilities);
+
     // Probe system health metrics (bd-3eaa)
     capabilities.num_cpus = probe_num_cpus();
     if let Some((load1, load5, load15)) = probe_load_average() {
@@ -442,6 +599,10 @@ fn probe_capabilities() -> WorkerCapabilities {
         capabilities.disk_free_gb = Some(free_gb);
         capabilities.disk_total_gb = Some(total_gb);
     }
+    if let Some((total_gb, available_gb)) = probe_memory() {
+        capabilities.mem_total_gb = Some(total_gb);
+        capabilities.mem_available_gb = Some(available_gb);
+    }
 
     let (canonical_root, alias_root) = resolved_topology_roots();
     let (topology_ok, topology_issue) = probe_projects_topology(&canonical_root, &alias_root);
@@ -629,6 +790,30 @@ fn probe_disk_space() -> Option<(f64, f64)> {
         .find_map(|path| probe_disk_space_for(path))
 }
 
+/// Probe physical memory (total and available in GB).
+fn probe_memory() -> Option<(f64, f64)> {
+    const KB_PER_GB: f64 = 1024.0 * 1024.0;
+
+    // Try /proc/meminfo first (Linux)
+    if let Ok(contents) = std::fs::read_to_string("/proc/meminfo")
+        && let Some((total_kb, avail_kb)) = parse_proc_meminfo_kb(&contents)
+    {
+        return Some((total_kb as f64 / KB_PER_GB, avail_kb as f64 / KB_PER_GB));
+    }
+
+    // Fallback: free -k (procps without /proc/meminfo access)
+    if let Ok(output) = std::process::Command::new("free").arg("-k").output()
+        && output.status.success()
+    {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if let Some((total_kb, avail_kb)) = parse_free_kb(&stdout) {
+            return Some((total_kb as f64 / KB_PER_GB, avail_kb as f64 / KB_PER_GB));
+        }
+    }
+
+    None
+}
+
 fn probe_disk_space_for(path: &std::path::Path) -> Option<(f64, f64)> {
     use std::process::Command;
 
@@ -651,22 +836,12 @@ fn probe_disk_space_for(path: &std::path::Path) -> Option<(f64, f64)> {
     None
 }
 
+/// The full `rustc --version` line (`rustc 1.87.0 (17067e9ac 2025-05-09)`),
+/// the same form the hook probes locally, so `require_rustc_match = "exact"`
+/// compares like with like.
 fn parse_rustc_version_stdout(stdout: &str) -> Option<String> {
-    let trimmed = stdout.trim();
-    if trimmed.is_empty() {
-        return None;
-    }
-
-    let mut tokens = trimmed.split_whitespace();
-    let first = tokens.next()?;
-    if first == "rustc"
-        && let Some(version) = tokens.next()
-        && !version.is_empty()
-    {
-        return Some(version.to_string());
-    }
-
-    Some(trimmed.to_string())
+    let line = stdout.lines().next()?.trim();
+    (!line.is_empty()).then(|| line.to_string())
 }
 
 fn parse_node_version_stdout(stdout: &str) -> Option<String> {
@@ -677,6 +852,16 @@ fn parse_node_version_stdout(stdout: &str) -> Option<String> {
     Some(trimmed.strip_prefix('v').unwrap_or(trimmed).to_string())
 }
 
+fn parse_sccache_version_stdout(stdout: &str) -> Option<String> {
+    let line = first_stdout_line(stdout)?;
+    Some(
+        line.strip_prefix("sccache ")
+            .map(str::trim)
+            .unwrap_or(&line)
+            .to_string(),
+    )
+}
+
 fn parse_nproc_stdout(stdout: &str) -> Option<u32> {
     stdout.trim().parse::<u32>().ok()
 }
@@ -718,6 +903,32 @@ fn parse_uptime_loadavg(output: &str) -> Option<(f64, f64, f64)> {
     Some((load1, load5, load15))
 }
 
+/// Parse `MemTotal` and `MemAvailable` (kB) from `/proc/meminfo`. Kernels
+/// older than 3.14 lack `MemAvailable`; `MemFree` is used there instead.
+fn parse_proc_meminfo_kb(contents: &str) -> Option<(u64, u64)> {
+    let field = |name: &str| {
+        contents.lines().find_map(|line| {
+            let value = line.strip_prefix(name)?.strip_prefix(':')?;
+            value.split_whitespace().next()?.parse::<u64>().ok()
+        })
+    };
+    let total_kb = field("MemTotal")?;
+    let avail_kb = field("MemAvailable").or_else(|| field("MemFree"))?;
+    Some((total_kb, avail_kb))
+}
+
+fn parse_free_kb(stdout: &str) -> Option<(u64, u64)> {
+    // procps format: "Mem: total used free shared buff/cache available"
+    let line = stdout.lines().find(|line| line.starts_with("Mem:"))?;
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    let [_, total_kb, _, free_kb, rest @ ..] = parts.as_slice() else {
+        return None;
+    };
+    let total_kb = total_kb.parse::<u64>().ok()?;
+    let avail_kb = rest.get(2).unwrap_or(free_kb).parse::<u64>().ok()?;
+    Some((total_kb, avail_kb))
+}
+
 fn parse_df_posix_kb(stdout: &str) -> Option<(u64, u64)> {
     // Skip header line, parse first data line.
     // POSIX format: Filesystem 1024-blocks Used Available Capacity Mounted on
@@ -837,10 +1048,65 @@ mod tests {
         println!("TEST START: test_cli_parses_health");
         let cli = Cli::try_parse_from(["rch-wkr", "health"]).expect("cli parse should succeed");
         assert!(!cli.verbose);
-        assert!(matches!(cli.command, Commands::Health));
+        assert!(matches!(cli.command, Commands::Health { deep: false, .. }));
         println!("TEST PASS: test_cli_parses_health");
     }
 
+    #[test]
+    fn test_cli_parses_healthcheck_deep() {
+        let _guard = test_guard!();
+        let cli = Cli::try_parse_from(["rch-wkr", "healthcheck", "--deep", "--timeout-secs", "30"])
+            .expect("cli parse should succeed");
+        assert!(matches!(
+            cli.command,
+            Commands::Health {
+                deep: true,
+                timeout_secs: 30
+            }
+        ));
+        let cli =
+            Cli::try_parse_from(["rch-wkr", "health", "--deep"]).expect("cli parse should succeed");
+        assert!(matches!(
+            cli.command,
+            Commands::Health {
+                deep: true,
+                timeout_secs: deep_check::DEFAULT_DEEP_CHECK_TIMEOUT_SECS
+            }
+        ));
+    }
+
+    #[test]
+    fn test_cli_parses_info_json() {
+        let _guard = test_guard!();
+        println!("TEST START: test_cli_parses_info_json");
+        let cli = Cli::try_parse_from(["rch-wkr", "info"]).expect("cli parse should succeed");
+        assert!(matches!(cli.command, Commands::Info { json: false }));
+        let cli =
+            Cli::try_parse_from(["rch-wkr", "info", "--json"]).expect("cli parse should succeed");
+        assert!(matches!(cli.command, Commands::Info { json: true }));
+        println!("TEST PASS: test_cli_parses_info_json");
+    }
+
+    #[test]
+    fn test_system_info_json_shape() {
+        let _guard = test_guard!();
+        println!("TEST START: test_system_info_json_shape");
+        let info = SystemInfo {
+            cores: Some(16),
+            memory: parse_free_total(
+                "               total        used\nMem:            62Gi        10Gi\nSwap: 0B 0B\n",
+            ),
+            gcc: first_stdout_line("\ngcc (GCC) 13.2.0\nCopyright\n"),
+            ..SystemInfo::default()
+        };
+        let value = serde_json::to_value(&info).expect("serialize system info");
+        assert_eq!(value["cores"], 16);
+        assert_eq!(value["memory"], "62Gi");
+        assert_eq!(value["gcc"], "gcc (GCC) 13.2.0");
+        assert!(value["bun"].is_null(), "missing tools serialize as null");
+        println!("TEST PASS: test_system_info_json_shape");
+    }
+
     #[test]
     fn test_cli_parses_execute_with_toolchain() -> Result<()> {
         let _guard = test_guard!();
@@ -863,6 +1129,7 @@ mod tests {
             workdir,
             command,
             toolchain,
+            ..
         } = cli.command
         else {
             anyhow::bail!("expected execute command");
@@ -874,6 +1141,79 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_cli_parses_execute_allowed_commands() -> Result<()> {
+        let _guard = test_guard!();
+        let cli = Cli::try_parse_from([
+            "rch-wkr",
+            "execute",
+            "--workdir",
+            "/tmp",
+            "--command",
+            "cargo build",
+            "--allowed-commands",
+            "cargo,rustc,bun",
+        ])
+        .expect("cli parse should succeed");
+        let Commands::Execute {
+            allowed_commands, ..
+        } = cli.command
+        else {
+            anyhow::bail!("expected execute command");
+        };
+        assert_eq!(allowed_commands, ["cargo", "rustc", "bun"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_parses_execute_check_flags() -> Result<()> {
+        let _guard = test_guard!();
+        let cli = Cli::try_parse_from([
+            "rch-wkr",
+            "execute",
+            "--workdir",
+            ".",
+            "--command",
+            "env CARGO_TERM_COLOR=always cargo build",
+            "--check-command",
+            "cargo build",
+            "--check-only",
+        ])
+        .expect("cli parse should succeed");
+        let Commands::Execute {
+            check_command,
+            check_only,
+            ..
+        } = cli.command
+        else {
+            anyhow::bail!("expected execute command");
+        };
+        assert_eq!(check_command.as_deref(), Some("cargo build"));
+        assert!(check_only);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cli_parses_execute_timeout_secs() -> Result<()> {
+        let _guard = test_guard!();
+        let cli = Cli::try_parse_from([
+            "rch-wkr",
+            "execute",
+            "--workdir",
+            "/tmp",
+            "--command",
+            "cargo build",
+            "--timeout-secs",
+            "300",
+        ])
+        .expect("cli parse should succeed");
+        let Commands::Execute { timeout_secs, .. } = cli.command else {
+            anyhow::bail!("expected execute command");
+        };
+        assert_eq!(timeout_secs, Some(300));
+        Ok(())
+    }
+
     #[test]
     fn test_cli_parses_cleanup_default_age() -> Result<()> {
         let _guard = test_guard!();
@@ -888,12 +1228,40 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_rustc_version_stdout_extracts_semver() {
+    fn test_parse_rustc_version_stdout_keeps_full_line() {
         let _guard = test_guard!();
-        println!("TEST START: test_parse_rustc_version_stdout_extracts_semver");
+        println!("TEST START: test_parse_rustc_version_stdout_keeps_full_line");
         let parsed = parse_rustc_version_stdout("rustc 1.87.0-nightly (abc 2026-01-01)\n");
-        assert_eq!(parsed.as_deref(), Some("1.87.0-nightly"));
-        println!("TEST PASS: test_parse_rustc_version_stdout_extracts_semver");
+        assert_eq!(
+            parsed.as_deref(),
+            Some("rustc 1.87.0-nightly (abc 2026-01-01)")
+        );
+        assert_eq!(parse_rustc_version_stdout("\n"), None);
+        println!("TEST PASS: test_parse_rustc_version_stdout_keeps_full_line");
+    }
+
+    #[test]
+    fn test_probed_rustc_version_matches_local_rustc_exactly() {
+        let _guard = test_guard!();
+        println!("TEST START: test_probed_rustc_version_matches_local_rustc_exactly");
+        let Ok(output) = std::process::Command::new("rustc").arg("-V").output() else {
+            println!("TEST SKIP: rustc not on PATH");
+            return;
+        };
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let probed = parse_rustc_version_stdout(&stdout).expect("rustc -V prints a version");
+        // The hook compares the worker's probe against its own `rustc --version`.
+        for granularity in [
+            rch_common::RustcMatch::Exact,
+            rch_common::RustcMatch::Patch,
+            rch_common::RustcMatch::Minor,
+        ] {
+            assert!(
+                granularity.versions_match(stdout.trim(), &probed),
+                "{granularity}: local {stdout:?} vs probed {probed:?}"
+            );
+        }
+        println!("TEST PASS: test_probed_rustc_version_matches_local_rustc_exactly");
     }
 
     #[test]
@@ -905,6 +1273,29 @@ mod tests {
         println!("TEST PASS: test_parse_node_version_stdout_strips_v_prefix");
     }
 
+    #[test]
+    fn test_missing_transfer_tools_lists_unprobed_tools() {
+        let _guard = test_guard!();
+        let mut capabilities = WorkerCapabilities::new();
+        assert_eq!(missing_transfer_tools(&capabilities), vec!["rsync", "zstd"]);
+
+        capabilities.rsync_version = Some("rsync  version 3.2.7  protocol version 31".to_string());
+        assert_eq!(missing_transfer_tools(&capabilities), vec!["zstd"]);
+
+        capabilities.zstd_version = Some("*** Zstandard CLI (64-bit) v1.5.5 ***".to_string());
+        assert!(missing_transfer_tools(&capabilities).is_empty());
+    }
+
+    #[test]
+    fn test_parse_sccache_version_stdout_strips_program_name() {
+        let _guard = test_guard!();
+        println!("TEST START: test_parse_sccache_version_stdout_strips_program_name");
+        let parsed = parse_sccache_version_stdout("sccache 0.8.2\n");
+        assert_eq!(parsed.as_deref(), Some("0.8.2"));
+        assert_eq!(parse_sccache_version_stdout("  \n"), None);
+        println!("TEST PASS: test_parse_sccache_version_stdout_strips_program_name");
+    }
+
     #[test]
     fn test_parse_proc_loadavg_parses_first_three_numbers() {
         let _guard = test_guard!();
@@ -952,6 +1343,30 @@ mod tests {
         println!("TEST PASS: test_parse_df_posix_kb_parses_total_and_available");
     }
 
+    #[test]
+    fn test_parse_proc_meminfo_kb_prefers_mem_available() {
+        let _guard = test_guard!();
+        let sample = "MemTotal:       16318412 kB\nMemFree:          812340 kB\nMemAvailable:    9123456 kB\n";
+        assert_eq!(parse_proc_meminfo_kb(sample), Some((16_318_412, 9_123_456)));
+
+        let old_kernel = "MemTotal:       4000000 kB\nMemFree:        1000000 kB\n";
+        assert_eq!(
+            parse_proc_meminfo_kb(old_kernel),
+            Some((4_000_000, 1_000_000))
+        );
+        assert_eq!(parse_proc_meminfo_kb("garbage"), None);
+    }
+
+    #[test]
+    fn test_parse_free_kb_uses_available_column() {
+        let _guard = test_guard!();
+        let sample = "               total        used        free      shared  buff/cache   available\nMem:        16318412     6000000      812340      120000     9500000     9123456\nSwap:        2097148           0     2097148\n";
+        assert_eq!(parse_free_kb(sample), Some((16_318_412, 9_123_456)));
+
+        let no_available = "             total       used       free     shared    buffers     cached\nMem:       4000000    3000000    1000000\n";
+        assert_eq!(parse_free_kb(no_available), Some((4_000_000, 1_000_000)));
+    }
+
     #[test]
     fn test_benchmark_failure_summary_prefers_stderr() {
         let _guard = test_guard!();
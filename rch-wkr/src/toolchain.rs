@@ -206,6 +206,30 @@ pub fn get_cached_toolchains() -> Vec<String> {
     }
 }
 
+/// List the toolchains rustup has installed, without host triples.
+///
+/// Empty when rustup is missing or reports nothing.
+pub fn installed_toolchains() -> Vec<String> {
+    match Command::new("rustup").args(["toolchain", "list"]).output() {
+        Ok(output) if output.status.success() => {
+            parse_toolchain_list(&String::from_utf8_lossy(&output.stdout))
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Parse `rustup toolchain list` output, e.g.
+/// `nightly-2024-01-15-x86_64-unknown-linux-gnu (default)` -> `nightly-2024-01-15`.
+pub fn parse_toolchain_list(stdout: &str) -> Vec<String> {
+    stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("no installed toolchains"))
+        .filter_map(|line| line.split_whitespace().next())
+        .map(|name| strip_target_triple(name).to_string())
+        .collect()
+}
+
 /// Parse a toolchain string into a ToolchainInfo.
 ///
 /// Handles formats like:
@@ -293,6 +317,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_toolchain_list() {
+        let _guard = test_guard!();
+        let stdout = "stable-x86_64-unknown-linux-gnu (active, default)\n\
+                      nightly-2024-01-15-x86_64-unknown-linux-gnu\n\
+                      1.85.0-aarch64-unknown-linux-gnu\n";
+        assert_eq!(
+            parse_toolchain_list(stdout),
+            vec!["stable", "nightly-2024-01-15", "1.85.0"]
+        );
+        assert!(parse_toolchain_list("no installed toolchains\n").is_empty());
+    }
+
     #[test]
     fn test_strip_target_triple_macos() {
         let _guard = test_guard!();
@@ -0,0 +1,56 @@
+//! `rch-wkr execute --allowed-commands` enforcement at the binary level.
+//!
+//! The allowlist is a worker-side guard, so these tests go through the real
+//! CLI entry point rather than the executor helpers: a refused command must
+//! exit with the not-allowed sentinel (123) without running, and an allowed
+//! one must run normally.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+const EXIT_COMMAND_NOT_ALLOWED: i32 = 123;
+
+fn execute(workdir: &Path, allowed: &str, command: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rch-wkr"))
+        .args(["execute", "--workdir"])
+        .arg(workdir)
+        .args(["--allowed-commands", allowed, "--command", command])
+        .env_remove("RCH_WKR_ALLOWED_COMMANDS")
+        .output()
+        .expect("spawn rch-wkr")
+}
+
+#[test]
+fn execute_refuses_commands_that_escape_the_allowlist() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let marker = dir.path().join("ran");
+    let touch = format!("touch {}", marker.display());
+
+    for command in [
+        touch.clone(),
+        format!("RUSTFLAGS=-Clinker=/tmp/x {touch}"),
+        format!("RUSTFLAGS=-Clink-arg=-fuse-ld=/tmp/x {touch}"),
+        format!("PATH=/tmp {touch}"),
+        format!("{touch} ${{HOME}}"),
+        format!("{touch}; id"),
+    ] {
+        let output = execute(dir.path(), "echo", &command);
+        assert_eq!(
+            output.status.code(),
+            Some(EXIT_COMMAND_NOT_ALLOWED),
+            "{command:?} should be refused: {output:?}"
+        );
+        assert!(!marker.exists(), "{command:?} must not run");
+    }
+}
+
+#[test]
+fn execute_runs_allowlisted_commands() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let marker = dir.path().join("ran");
+    let command = format!("RUSTFLAGS=-Dwarnings touch {}", marker.display());
+
+    let output = execute(dir.path(), "touch", &command);
+    assert!(output.status.success(), "{output:?}");
+    assert!(marker.exists());
+}
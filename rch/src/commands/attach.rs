@@ -0,0 +1,124 @@
+//! `rch attach` command implementation.
+
+use anyhow::{Context, Result};
+use rch_common::WorkerConfig;
+use rch_common::detached_build::{self, DetachedBuildFiles};
+use tokio::process::Command;
+
+use crate::status_types::{ActiveBuildFromApi, DaemonFullStatusResponse, extract_json_body};
+use crate::ui::context::OutputContext;
+
+use super::helpers::load_workers_from_config;
+use super::send_daemon_command;
+
+/// Reconnect to a detached remote build (`transfer.detach_remote_builds`).
+///
+/// Replays the build's output from the start over SSH, follows it until the
+/// build finishes, and exits with the build's exit code.
+pub async fn build_attach(build_id: u64, ctx: &OutputContext) -> Result<()> {
+    let response = send_daemon_command("GET /status\n").await?;
+    let json = extract_json_body(&response)
+        .ok_or_else(|| anyhow::anyhow!("Invalid response format from daemon"))?;
+    let status: DaemonFullStatusResponse =
+        serde_json::from_str(json).context("Failed to parse daemon status")?;
+
+    let build = status
+        .active_builds
+        .iter()
+        .find(|b| b.id == build_id)
+        .ok_or_else(|| anyhow::anyhow!("No active build with ID {build_id}"))?;
+    let pgid_file = attach_target(build)?;
+    let workers = load_workers_from_config()?;
+    let worker = workers
+        .iter()
+        .find(|w| w.id.as_str() == build.worker_id)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Worker {} of build {build_id} is not in workers.toml",
+                build.worker_id
+            )
+        })?;
+
+    if !ctx.is_json() {
+        eprintln!(
+            "{}",
+            ctx.style().muted(&format!(
+                "[rch] attached to build {build_id} on {} (Ctrl-C detaches, the build keeps running)",
+                build.worker_id
+            ))
+        );
+    }
+
+    let follow = detached_build::follow_command(&DetachedBuildFiles::for_pgid_file(pgid_file));
+    let status = attach_ssh_command(worker, &follow)
+        .status()
+        .await
+        .with_context(|| format!("Failed to run ssh to {}", worker.id))?;
+    let exit_code = status.code().unwrap_or(detached_build::LOST_EXIT_CODE);
+    if exit_code != 0 {
+        std::process::exit(exit_code);
+    }
+    Ok(())
+}
+
+/// Remote pgid file of a build that can be attached to.
+fn attach_target(build: &ActiveBuildFromApi) -> Result<&str> {
+    if !build.detached {
+        anyhow::bail!(
+            "Build {} is not detached; enable transfer.detach_remote_builds to make builds reattachable (use 'rch tail {}' to follow it)",
+            build.id,
+            build.id
+        );
+    }
+    build
+        .remote_pgid_file
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("Build {} has not started on its worker yet", build.id))
+}
+
+/// SSH invocation that streams the follow script's stdout/stderr to ours.
+fn attach_ssh_command(worker: &WorkerConfig, remote_cmd: &str) -> Command {
+    let mut cmd = Command::new("ssh");
+    cmd.arg("-o").arg("BatchMode=yes");
+    cmd.arg("-o").arg("ConnectTimeout=10");
+    if let Some(identity) = worker.ssh_identity() {
+        cmd.arg("-i").arg(identity);
+    }
+    cmd.arg(format!("{}@{}", worker.user, worker.host));
+    cmd.arg(remote_cmd);
+    cmd
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(detached: bool, remote_pgid_file: Option<&str>) -> ActiveBuildFromApi {
+        serde_json::from_value(serde_json::json!({
+            "id": 42,
+            "project_id": "app",
+            "worker_id": "w1",
+            "command": "cargo build",
+            "started_at": "2026-01-01T00:00:00Z",
+            "detached": detached,
+            "remote_pgid_file": remote_pgid_file,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn attach_target_requires_detached_build() {
+        let err = attach_target(&build(false, Some("/tmp/rch-run/app-0/42.pgid"))).unwrap_err();
+        assert!(err.to_string().contains("transfer.detach_remote_builds"));
+    }
+
+    #[test]
+    fn attach_target_uses_reported_pgid_file() {
+        let target = build(true, Some("/tmp/rch-run/app-0/42.pgid"));
+        assert_eq!(
+            attach_target(&target).unwrap(),
+            "/tmp/rch-run/app-0/42.pgid"
+        );
+        assert!(attach_target(&build(true, None)).is_err());
+    }
+}
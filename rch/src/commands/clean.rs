@@ -0,0 +1,463 @@
+//! `rch clean --remote`: purge one project's worker-side tree on every healthy
+//! worker.
+//!
+//! Workers keep the project mirror under the canonical root (the same
+//! topology path the hook syncs to), so a messy merge can leave stale files
+//! there that incremental rsync never removes. This command deletes that
+//! directory outright; the next build re-uploads it from scratch.
+//!
+//! Flow:
+//! 1. Resolve the remote directory: `--project <name>` maps to
+//!    `<canonical_root>/<name>`, otherwise the current directory is normalized
+//!    through the configured path topology (the hook's sync target).
+//! 2. Refuse any target that is not strictly under the configured canonical
+//!    root, and any root too broad to trust (`/`, `/home`, system dirs);
+//!    checked again per worker right before the remote `rm`.
+//! 3. Pick healthy workers from the daemon's status; without a daemon, fall
+//!    back to an SSH reachability probe per configured worker.
+//! 4. Confirm interactively unless `--yes`, then measure (`du -sb`) and
+//!    remove the directory on each worker, reporting bytes reclaimed. A
+//!    directory RCH never built in (no `.rch-target*` or `.rch-tmp` entry)
+//!    is reported as foreign and left alone.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use dialoguer::Confirm;
+use rch_common::path_topology::{PathTopologyPolicy, normalize_project_path_with_policy};
+use rch_common::{ApiResponse, WorkerConfig, is_safe_invalidation_target};
+use serde::Serialize;
+
+use crate::cache_gc::human_bytes;
+use crate::config::load_config;
+use crate::status_types::{DaemonFullStatusResponse, extract_json_body};
+use crate::transfer::project_id_from_path;
+use crate::ui::context::OutputContext;
+use crate::ui::theme::StatusIndicator;
+
+use super::load_workers_from_config;
+use super::send_daemon_command;
+use super::workers_setup::run_setup_ssh_command;
+
+/// Marker printed by [`remote_clean_command`] when the directory is missing.
+const ABSENT_MARKER: &str = "absent";
+
+/// Marker printed by [`remote_clean_command`] for a directory that holds no
+/// RCH build output.
+pub(super) const FOREIGN_MARKER: &str = "foreign";
+
+/// Per-worker outcome of a remote clean.
+#[derive(Debug, Clone, Serialize)]
+struct CleanRemoteWorkerResult {
+    worker_id: String,
+    /// `removed`, `absent`, `foreign`, `failed`, or `skipped_unhealthy`.
+    outcome: String,
+    bytes_reclaimed: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// The `rch clean --remote` report.
+#[derive(Debug, Clone, Serialize)]
+struct CleanRemoteResult {
+    project_id: String,
+    remote_path: String,
+    bytes_reclaimed: u64,
+    workers: Vec<CleanRemoteWorkerResult>,
+}
+
+/// Entry point for `rch clean --remote`.
+///
+/// `project` is a project id (a directory name under the canonical root);
+/// it defaults to the current directory's topology path. Nothing is deleted
+/// without confirmation unless `yes` is set.
+pub async fn clean_remote(project: Option<String>, yes: bool, ctx: &OutputContext) -> Result<()> {
+    let style = ctx.theme();
+    let config = load_config()?;
+    let policy = config.path_topology.to_policy();
+
+    let remote_path = match project.as_deref() {
+        Some(name) => remote_dir_for_project_name(name, &policy)?,
+        None => {
+            let cwd = std::env::current_dir()
+                .map_err(|e| anyhow::anyhow!("cannot determine project root from cwd: {e}"))?;
+            remote_dir_for_project_root(&cwd, &policy)?
+        }
+    };
+    let canonical_root = policy.canonical_root();
+    if let Some(reason) = clean_target_refusal(&remote_path, canonical_root) {
+        anyhow::bail!("refusing to delete {}: {}", remote_path.display(), reason);
+    }
+    let project_id = project_id_from_path(&remote_path);
+
+    let workers = load_workers_from_config()?;
+    if workers.is_empty() {
+        anyhow::bail!("no workers configured; run `rch workers add <host>` first");
+    }
+    let healthy = healthy_worker_ids(&workers).await;
+
+    if !yes {
+        if ctx.is_json() {
+            anyhow::bail!("refusing to delete remote caches without confirmation; pass --yes");
+        }
+        println!(
+            "{} This will delete {} on {} healthy worker(s).",
+            StatusIndicator::Warning.display(style),
+            style.highlight(&remote_path.display().to_string()),
+            healthy.len()
+        );
+        let confirmed = Confirm::new()
+            .with_prompt("Delete the remote project directory?")
+            .default(false)
+            .interact()?;
+        if !confirmed {
+            println!("{} Aborted.", StatusIndicator::Info.display(style));
+            return Ok(());
+        }
+    }
+
+    let mut results = Vec::with_capacity(workers.len());
+    for worker in &workers {
+        let result = if healthy.contains(&worker.id.0) {
+            clean_on_worker(worker, &remote_path, canonical_root).await
+        } else {
+            CleanRemoteWorkerResult {
+                worker_id: worker.id.0.clone(),
+                outcome: "skipped_unhealthy".to_string(),
+                bytes_reclaimed: 0,
+                error: None,
+            }
+        };
+        tracing::info!(
+            target: "rch::clean::remote",
+            worker = %result.worker_id,
+            outcome = %result.outcome,
+            bytes = result.bytes_reclaimed,
+            "clean.remote.worker",
+        );
+        results.push(result);
+    }
+
+    let result = CleanRemoteResult {
+        project_id,
+        remote_path: remote_path.display().to_string(),
+        bytes_reclaimed: results.iter().map(|r| r.bytes_reclaimed).sum(),
+        workers: results,
+    };
+
+    if ctx.is_json() {
+        let _ = ctx.json(&ApiResponse::ok("clean", &result));
+        return Ok(());
+    }
+    render_human(&result, ctx);
+    Ok(())
+}
+
+/// Top-level directories that hold the system rather than projects; a
+/// canonical root inside one of them is never cleaned under.
+const SYSTEM_DIRS: &[&str] = &[
+    "bin", "boot", "dev", "etc", "lib", "lib32", "lib64", "proc", "sbin", "sys", "usr",
+];
+
+/// Why `remote_path` may not be deleted under `canonical_root`, if it may
+/// not. The configured root is only trusted when it is absolute, at least two
+/// directories deep (so not `/`, `/home` or `/data`) and outside
+/// [`SYSTEM_DIRS`]; the path must then sit strictly under it.
+pub(super) fn clean_target_refusal(remote_path: &Path, canonical_root: &Path) -> Option<String> {
+    let dirs: Vec<_> = canonical_root
+        .components()
+        .filter_map(|c| match c {
+            std::path::Component::Normal(dir) => Some(dir),
+            _ => None,
+        })
+        .collect();
+    let too_broad = !canonical_root.is_absolute()
+        || dirs.len() < 2
+        || SYSTEM_DIRS.iter().any(|dir| dirs[0] == *dir);
+    if too_broad {
+        return Some(format!(
+            "canonical root {} is too broad to clean under",
+            canonical_root.display()
+        ));
+    }
+    if !is_safe_invalidation_target(remote_path, canonical_root) {
+        return Some(format!(
+            "is not strictly under {}",
+            canonical_root.display()
+        ));
+    }
+    None
+}
+
+/// Map a `--project` name to its directory under the canonical root. The
+/// name must be a plain project id, exactly what `project_id_from_path`
+/// yields for it, so separators and `..` cannot reach another directory.
+pub(super) fn remote_dir_for_project_name(
+    name: &str,
+    policy: &PathTopologyPolicy,
+) -> Result<PathBuf> {
+    if name.is_empty() || project_id_from_path(Path::new(name)) != name {
+        anyhow::bail!("invalid project name {name:?}: expected a single directory name");
+    }
+    Ok(policy.canonical_root().join(name))
+}
+
+/// Map a local project root to the worker-side path the hook syncs it to.
+fn remote_dir_for_project_root(root: &Path, policy: &PathTopologyPolicy) -> Result<PathBuf> {
+    let normalized = normalize_project_path_with_policy(root, policy).map_err(|e| {
+        anyhow::anyhow!(
+            "{} is not under the canonical project root ({e}); pass --project <name>",
+            root.display()
+        )
+    })?;
+    Ok(normalized.canonical_path().to_path_buf())
+}
+
+/// Worker ids eligible for cleaning. The daemon's view wins when it is
+/// running; otherwise every configured worker that answers SSH counts.
+pub(super) async fn healthy_worker_ids(workers: &[WorkerConfig]) -> Vec<String> {
+    if let Ok(response) = send_daemon_command("GET /status\n").await
+        && let Some(json) = extract_json_body(&response)
+        && let Ok(status) = serde_json::from_str::<DaemonFullStatusResponse>(json)
+    {
+        return status
+            .workers
+            .into_iter()
+            .filter(|w| w.status == "healthy")
+            .map(|w| w.id)
+            .collect();
+    }
+
+    let mut reachable = Vec::new();
+    for worker in workers {
+        if let Ok(out) = run_setup_ssh_command(worker, "true").await
+            && out.status.success()
+        {
+            reachable.push(worker.id.0.clone());
+        }
+    }
+    reachable
+}
+
+/// Remote script that prints the directory's size in bytes and removes it,
+/// or prints [`ABSENT_MARKER`] when there is nothing to remove.
+///
+/// The canonical root is shared with whatever else lives on the worker, so
+/// a directory is only removed when RCH has built in it (it holds a
+/// `.rch-target*` or `.rch-tmp` entry); other directories print
+/// [`FOREIGN_MARKER`] and are left alone.
+pub(super) fn remote_clean_command(path: &Path) -> String {
+    let quoted = shell_escape::escape(path.to_string_lossy());
+    format!(
+        "if [ ! -e {quoted} ]; then echo {ABSENT_MARKER}; \
+         elif [ -e {quoted}/.rch-tmp ] || ls -d {quoted}/.rch-target* >/dev/null 2>&1; \
+         then du -sb -- {quoted} | cut -f1; rm -rf -- {quoted}; \
+         else echo {FOREIGN_MARKER}; fi"
+    )
+}
+
+/// Parse [`remote_clean_command`] output: `None` when the directory was
+/// absent or foreign, otherwise the bytes reclaimed (0 if `du` printed
+/// nothing usable).
+pub(super) fn parse_reclaimed_bytes(stdout: &str) -> Option<u64> {
+    let first = stdout.lines().next().unwrap_or("").trim();
+    if first == ABSENT_MARKER || first == FOREIGN_MARKER {
+        return None;
+    }
+    Some(first.parse().unwrap_or(0))
+}
+
+async fn clean_on_worker(
+    worker: &WorkerConfig,
+    remote_path: &Path,
+    canonical_root: &Path,
+) -> CleanRemoteWorkerResult {
+    let failed = |error: String| CleanRemoteWorkerResult {
+        worker_id: worker.id.0.clone(),
+        outcome: "failed".to_string(),
+        bytes_reclaimed: 0,
+        error: Some(error),
+    };
+
+    // Defense-in-depth: re-check at the SSH boundary.
+    if let Some(reason) = clean_target_refusal(remote_path, canonical_root) {
+        return failed(format!(
+            "refused at SSH boundary ({}): {}",
+            remote_path.display(),
+            reason
+        ));
+    }
+
+    match run_setup_ssh_command(worker, &remote_clean_command(remote_path)).await {
+        Ok(out) if out.status.success() => {
+            let stdout = String::from_utf8_lossy(&out.stdout);
+            let reclaimed = parse_reclaimed_bytes(&stdout);
+            let outcome = match reclaimed {
+                Some(_) => "removed",
+                None if stdout.trim() == FOREIGN_MARKER => "foreign",
+                None => "absent",
+            };
+            CleanRemoteWorkerResult {
+                worker_id: worker.id.0.clone(),
+                outcome: outcome.to_string(),
+                bytes_reclaimed: reclaimed.unwrap_or(0),
+                error: None,
+            }
+        }
+        Ok(out) => failed(format!(
+            "exit {}: {}",
+            out.status.code().unwrap_or(-1),
+            String::from_utf8_lossy(&out.stderr).trim()
+        )),
+        Err(e) => failed(format!("ssh error: {e}")),
+    }
+}
+
+fn render_human(result: &CleanRemoteResult, ctx: &OutputContext) {
+    let style = ctx.style();
+    println!("{}", style.format_header("Remote Clean"));
+    println!(
+        "  {} {}",
+        style.key("project:"),
+        style.value(&result.project_id)
+    );
+    println!(
+        "  {} {}",
+        style.key("remote path:"),
+        style.value(&result.remote_path)
+    );
+    for w in &result.workers {
+        let status = match w.outcome.as_str() {
+            "removed" => style.success(&format!(
+                "removed, {} reclaimed",
+                human_bytes(w.bytes_reclaimed)
+            )),
+            "absent" => style.muted("nothing to remove"),
+            "foreign" => style.warning("skipped (no RCH build output; not created by RCH)"),
+            "skipped_unhealthy" => style.warning("skipped (not healthy)"),
+            _ => style.error("failed"),
+        };
+        println!("  {} {}", style.key(&format!("{}:", w.worker_id)), status);
+        if let Some(error) = &w.error {
+            println!("      {}", style.muted(error));
+        }
+    }
+    println!(
+        "  {} {}",
+        style.key("total reclaimed:"),
+        style.value(&human_bytes(result.bytes_reclaimed))
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rch_common::test_guard;
+
+    #[test]
+    fn project_name_maps_under_canonical_root() {
+        let _guard = test_guard!();
+        let policy = PathTopologyPolicy::default();
+        let path = remote_dir_for_project_name("alpha", &policy).expect("valid name");
+        assert_eq!(path, PathBuf::from("/data/projects/alpha"));
+        assert!(is_safe_invalidation_target(&path, policy.canonical_root()));
+    }
+
+    #[test]
+    fn project_name_rejects_paths_and_traversal() {
+        let _guard = test_guard!();
+        let policy = PathTopologyPolicy::default();
+        for name in ["", "..", ".", "a/b", "/etc", "../x"] {
+            assert!(
+                remote_dir_for_project_name(name, &policy).is_err(),
+                "{name:?} must be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn custom_canonical_root_is_validated_against_itself() {
+        let _guard = test_guard!();
+        let policy = PathTopologyPolicy::new(PathBuf::from("/srv/src"), PathBuf::from("/s"));
+        let path = remote_dir_for_project_name("alpha", &policy).expect("valid name");
+        assert_eq!(path, PathBuf::from("/srv/src/alpha"));
+        assert!(is_safe_invalidation_target(&path, policy.canonical_root()));
+        assert!(!is_safe_invalidation_target(
+            policy.canonical_root(),
+            policy.canonical_root()
+        ));
+    }
+
+    #[test]
+    fn broad_or_system_canonical_roots_are_refused() {
+        let _guard = test_guard!();
+        for root in [
+            "/",
+            "/home",
+            "/data",
+            "/usr/local",
+            "/etc/rch",
+            "data/projects",
+        ] {
+            let root = Path::new(root);
+            assert!(
+                clean_target_refusal(&root.join("alpha"), root)
+                    .is_some_and(|reason| reason.contains("too broad")),
+                "{} must be refused",
+                root.display()
+            );
+        }
+        let root = Path::new("/srv/src");
+        assert_eq!(clean_target_refusal(&root.join("alpha"), root), None);
+        assert!(clean_target_refusal(Path::new("/srv/other/alpha"), root).is_some());
+    }
+
+    #[test]
+    fn remote_clean_command_quotes_path_and_reports_size() {
+        let _guard = test_guard!();
+        let cmd = remote_clean_command(Path::new("/data/projects/my proj"));
+        assert_eq!(
+            cmd,
+            "if [ ! -e '/data/projects/my proj' ]; then echo absent; \
+             elif [ -e '/data/projects/my proj'/.rch-tmp ] \
+             || ls -d '/data/projects/my proj'/.rch-target* >/dev/null 2>&1; \
+             then du -sb -- '/data/projects/my proj' | cut -f1; rm -rf -- '/data/projects/my proj'; \
+             else echo foreign; fi"
+        );
+    }
+
+    #[test]
+    fn remote_clean_command_skips_directories_without_rch_output() {
+        let _guard = test_guard!();
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let ours = tmp.path().join("ours");
+        let theirs = tmp.path().join("theirs");
+        std::fs::create_dir_all(ours.join(".rch-target/debug")).expect("mkdir ours");
+        std::fs::create_dir_all(theirs.join("src")).expect("mkdir theirs");
+        std::fs::write(theirs.join("src/main.rs"), "fn main() {}").expect("write theirs");
+
+        let run = |path: &Path| {
+            let out = std::process::Command::new("sh")
+                .arg("-c")
+                .arg(remote_clean_command(path))
+                .output()
+                .expect("run clean script");
+            String::from_utf8_lossy(&out.stdout).trim().to_string()
+        };
+
+        assert_eq!(run(&theirs), FOREIGN_MARKER);
+        assert!(theirs.join("src/main.rs").exists());
+        assert!(parse_reclaimed_bytes(&run(&ours)).is_some());
+        assert!(!ours.exists());
+        assert_eq!(run(&ours), ABSENT_MARKER);
+    }
+
+    #[test]
+    fn parse_reclaimed_bytes_handles_absent_and_sizes() {
+        let _guard = test_guard!();
+        assert_eq!(parse_reclaimed_bytes("absent\n"), None);
+        assert_eq!(parse_reclaimed_bytes("foreign\n"), None);
+        assert_eq!(parse_reclaimed_bytes("1048576\n"), Some(1_048_576));
+        assert_eq!(parse_reclaimed_bytes(""), Some(0));
+    }
+}
@@ -1,7 +1,8 @@
 //! Configuration command implementations.
 
 use anyhow::{Context, Result};
-use rch_common::{ApiResponse, ConfigValueSource, RchConfig};
+use rch_common::config::ConfigToValidate;
+use rch_common::{ApiResponse, ConfigValueSource, Profile, RchConfig, Severity, validate_config};
 use std::path::{Path, PathBuf};
 
 use crate::error::{ConfigError, EditorError};
@@ -13,12 +14,13 @@ use super::helpers::{config_dir, load_workers_from_config};
 use super::types::{
     ConfigCircuitSection, ConfigCompilationSection, ConfigDiffEntry, ConfigDiffResponse,
     ConfigEnvironmentSection, ConfigGeneralSection, ConfigGetResponse, ConfigLintResponse,
-    ConfigOutputSection, ConfigResetResponse, ConfigSelfHealingSection, ConfigSetResponse,
-    ConfigShowResponse, ConfigTransferSection, ConfigValidationIssue, ConfigValidationResponse,
-    ConfigValueSourceInfo, LintIssue, LintSeverity,
+    ConfigOutputSection, ConfigProfileChange, ConfigProfileEntry, ConfigProfileResponse,
+    ConfigResetResponse, ConfigSelfHealingSection, ConfigSetResponse, ConfigShowResponse,
+    ConfigTransferSection, ConfigValidationIssue, ConfigValidationResponse, ConfigValueSourceInfo,
+    LintIssue, LintSeverity,
 };
 
-const SUPPORTED_CONFIG_KEYS: &str = "general.enabled, general.force_local, general.force_remote, general.log_level, general.socket_path, compilation.confidence_threshold, compilation.min_local_time_ms, compilation.remote_speedup_threshold, compilation.build_slots, compilation.test_slots, compilation.check_slots, compilation.build_timeout_sec, compilation.test_timeout_sec, compilation.bun_timeout_sec, compilation.external_timeout_enabled, transfer.compression_level, transfer.exclude_patterns, environment.allowlist, output.visibility, output.first_run_complete, self_healing.hook_starts_daemon, self_healing.daemon_installs_hooks, self_healing.auto_start_cooldown_secs, self_healing.auto_start_timeout_secs";
+const SUPPORTED_CONFIG_KEYS: &str = "general.enabled, general.force_local, general.force_remote, general.confirm_first_offload, general.shadow_mode, general.log_level, general.socket_path, general.profile, compilation.confidence_threshold, compilation.min_local_time_ms, compilation.remote_speedup_threshold, compilation.build_slots, compilation.test_slots, compilation.check_slots, compilation.build_timeout_sec, compilation.test_timeout_sec, compilation.bun_timeout_sec, compilation.external_timeout_enabled, transfer.compression_level, transfer.exclude_patterns, environment.allowlist, output.visibility, output.first_run_complete, self_healing.hook_starts_daemon, self_healing.daemon_installs_hooks, self_healing.auto_start_cooldown_secs, self_healing.auto_start_timeout_secs, self_healing.state_dir";
 
 fn print_file_validation(
     label: &str,
@@ -89,6 +91,10 @@ pub fn config_show(show_sources: bool, ctx: &OutputContext) -> Result<()> {
     let mut sources = vec![
         "Environment variables (RCH_*)".to_string(),
         "Project config: .rch/config.toml".to_string(),
+        format!(
+            "Project overlay: {} (overridable keys only)",
+            config::PROJECT_OVERLAY_FILE
+        ),
     ];
     if let Some(dir) = config_dir() {
         sources.push(format!(
@@ -116,8 +122,11 @@ pub fn config_show(show_sources: bool, ctx: &OutputContext) -> Result<()> {
                 enabled: config.general.enabled,
                 force_local: config.general.force_local,
                 force_remote: config.general.force_remote,
+                confirm_first_offload: config.general.confirm_first_offload,
+                shadow_mode: config.general.shadow_mode,
                 log_level: config.general.log_level.clone(),
                 socket_path: config.general.socket_path.clone(),
+                profile: config.general.profile.clone(),
             },
             compilation: ConfigCompilationSection {
                 confidence_threshold: config.compilation.confidence_threshold,
@@ -130,6 +139,15 @@ pub fn config_show(show_sources: bool, ctx: &OutputContext) -> Result<()> {
                 test_timeout_sec: config.compilation.test_timeout_sec,
                 bun_timeout_sec: config.compilation.bun_timeout_sec,
                 external_timeout_enabled: config.compilation.external_timeout_enabled,
+                timeouts: config.compilation.timeouts.clone(),
+                require_rustc_match: config.compilation.require_rustc_match,
+                min_project_files: config.compilation.min_project_files,
+                min_project_bytes: config.compilation.min_project_bytes,
+                min_project_size_exempt_kinds: config
+                    .compilation
+                    .min_project_size_exempt_kinds
+                    .clone(),
+                confidence_overrides: config.compilation.confidence_overrides.clone(),
             },
             transfer: ConfigTransferSection {
                 compression_level: config.transfer.compression_level,
@@ -137,9 +155,15 @@ pub fn config_show(show_sources: bool, ctx: &OutputContext) -> Result<()> {
                 remote_base: config.transfer.remote_base.clone(),
                 // Transfer optimization (bd-3hho)
                 max_transfer_mb: config.transfer.max_transfer_mb,
+                max_project_bytes: config.transfer.max_project_bytes,
                 max_transfer_time_ms: config.transfer.max_transfer_time_ms,
                 bwlimit_kbps: config.transfer.bwlimit_kbps,
+                max_concurrent_client_transfers: config.transfer.max_concurrent_client_transfers,
                 estimated_bandwidth_bps: config.transfer.estimated_bandwidth_bps,
+                max_sync_seconds: config.transfer.max_sync_seconds,
+                fleet: config.transfer.fleet.clone(),
+                min_free_disk_gb: config.transfer.min_free_disk_gb,
+                container_image: config.transfer.container_image.clone(),
                 // Adaptive compression (bd-243w)
                 adaptive_compression: config.transfer.adaptive_compression,
                 min_compression_level: config.transfer.min_compression_level,
@@ -147,6 +171,21 @@ pub fn config_show(show_sources: bool, ctx: &OutputContext) -> Result<()> {
                 // Artifact verification (bd-377q)
                 verify_artifacts: config.transfer.verify_artifacts,
                 verify_max_size_bytes: config.transfer.verify_max_size_bytes,
+                verify_sample_files: config.transfer.verify_sample_files,
+                artifact_deny_patterns: config.transfer.artifact_deny_patterns.clone(),
+                preserve_newer_local: config.transfer.preserve_newer_local,
+                artifact_archive: config.transfer.artifact_archive,
+                artifact_max_retries: config.transfer.artifact_max_retries,
+                respect_gitignore: config.transfer.respect_gitignore,
+                gitignore_include: config.transfer.gitignore_include.clone(),
+                force_include: config.transfer.force_include.clone(),
+                shared_target_dir: config.transfer.shared_target_dir,
+                keep_remote_on_failure: config.transfer.keep_remote_on_failure,
+                keep_remote_hours: config.transfer.keep_remote_hours,
+                detach_remote_builds: config.transfer.detach_remote_builds,
+                use_sccache: config.transfer.use_sccache,
+                skip_artifacts_for: config.transfer.skip_artifacts_for.clone(),
+                backend: config.transfer.backend,
             },
             environment: ConfigEnvironmentSection {
                 allowlist: config.environment.allowlist.clone(),
@@ -168,6 +207,7 @@ pub fn config_show(show_sources: bool, ctx: &OutputContext) -> Result<()> {
                 daemon_installs_hooks: config.self_healing.daemon_installs_hooks,
                 auto_start_cooldown_secs: config.self_healing.auto_start_cooldown_secs,
                 auto_start_timeout_secs: config.self_healing.auto_start_timeout_secs,
+                state_dir: config.self_healing.state_dir.clone(),
             },
             sources,
             value_sources,
@@ -218,6 +258,24 @@ pub fn config_show(show_sources: bool, ctx: &OutputContext) -> Result<()> {
             &value_sources
         )
     );
+    println!(
+        "  {} = {}",
+        style.key("confirm_first_offload"),
+        format_with_source(
+            "general.confirm_first_offload",
+            &style.value(&config.general.confirm_first_offload.to_string()),
+            &value_sources
+        )
+    );
+    println!(
+        "  {} = {}",
+        style.key("shadow_mode"),
+        format_with_source(
+            "general.shadow_mode",
+            &style.value(&config.general.shadow_mode.to_string()),
+            &value_sources
+        )
+    );
     println!(
         "  {} = {}",
         style.key("log_level"),
@@ -236,6 +294,17 @@ pub fn config_show(show_sources: bool, ctx: &OutputContext) -> Result<()> {
             &value_sources
         )
     );
+    if let Some(profile) = &config.general.profile {
+        println!(
+            "  {} = {}",
+            style.key("profile"),
+            format_with_source(
+                "general.profile",
+                &style.value(&format!("\"{}\"", profile)),
+                &value_sources
+            )
+        );
+    }
 
     println!("\n{}", style.highlight("[compilation]"));
     println!(
@@ -328,6 +397,87 @@ pub fn config_show(show_sources: bool, ctx: &OutputContext) -> Result<()> {
             &value_sources
         )
     );
+    if !config.compilation.timeouts.is_empty() {
+        if let Some(source) = source_label("compilation.timeouts", &value_sources) {
+            println!(
+                "  {} = {{ {}",
+                style.key("timeouts"),
+                style.muted(&format!("# from {}", source))
+            );
+        } else {
+            println!("  {} = {{", style.key("timeouts"));
+        }
+        for (kind, secs) in &config.compilation.timeouts {
+            println!("    {} = {},", kind, style.value(&secs.to_string()));
+        }
+        println!("  }}");
+    }
+    if let Some(granularity) = config.compilation.require_rustc_match {
+        println!(
+            "  {} = {}",
+            style.key("require_rustc_match"),
+            format_with_source(
+                "compilation.require_rustc_match",
+                &style.value(&format!("\"{}\"", granularity)),
+                &value_sources
+            )
+        );
+    }
+    if let Some(min_files) = config.compilation.min_project_files {
+        println!(
+            "  {} = {}",
+            style.key("min_project_files"),
+            format_with_source(
+                "compilation.min_project_files",
+                &style.value(&min_files.to_string()),
+                &value_sources
+            )
+        );
+    }
+    if let Some(min_bytes) = config.compilation.min_project_bytes {
+        println!(
+            "  {} = {}",
+            style.key("min_project_bytes"),
+            format_with_source(
+                "compilation.min_project_bytes",
+                &style.value(&min_bytes.to_string()),
+                &value_sources
+            )
+        );
+    }
+    if config.compilation.project_size_gate_applies(None) {
+        println!(
+            "  {} = {}",
+            style.key("min_project_size_exempt_kinds"),
+            format_with_source(
+                "compilation.min_project_size_exempt_kinds",
+                &style.value(&format!(
+                    "{:?}",
+                    config.compilation.min_project_size_exempt_kinds
+                )),
+                &value_sources
+            )
+        );
+    }
+    if !config.compilation.confidence_overrides.is_empty() {
+        if let Some(source) = source_label("compilation.confidence_overrides", &value_sources) {
+            println!(
+                "  {} = {{ {}",
+                style.key("confidence_overrides"),
+                style.muted(&format!("# from {}", source))
+            );
+        } else {
+            println!("  {} = {{", style.key("confidence_overrides"));
+        }
+        for (pattern, value) in &config.compilation.confidence_overrides {
+            let value = match value {
+                rch_common::ConfidenceOverride::Force(force) => force.to_string(),
+                rch_common::ConfidenceOverride::Confidence(confidence) => confidence.to_string(),
+            };
+            println!("    {:?} = {},", pattern, style.value(&value));
+        }
+        println!("  }}");
+    }
 
     println!("\n{}", style.highlight("[transfer]"));
     println!(
@@ -492,6 +642,17 @@ pub fn config_show(show_sources: bool, ctx: &OutputContext) -> Result<()> {
             &value_sources
         )
     );
+    if let Some(state_dir) = config.self_healing.state_dir.as_deref() {
+        println!(
+            "  {} = {}",
+            style.key("state_dir"),
+            format_with_source(
+                "self_healing.state_dir",
+                &style.value(&format!("\"{state_dir}\"")),
+                &value_sources
+            )
+        );
+    }
 
     // Path topology (issue #10): always show the effective root paths
     // so users can verify that env-var or TOML overrides were picked up.
@@ -530,6 +691,37 @@ pub fn config_show(show_sources: bool, ctx: &OutputContext) -> Result<()> {
             &value_sources
         )
     );
+    println!(
+        "  {} = {}",
+        style.key("wsl_mount_prefix"),
+        format_with_source(
+            "path_topology.wsl_mount_prefix",
+            &style.value(&format!(
+                "\"{}\"",
+                config
+                    .path_topology
+                    .to_policy()
+                    .wsl_mount_prefix()
+                    .display()
+            )),
+            &value_sources
+        )
+    );
+    println!(
+        "  {} = {}",
+        style.key("keep_logical_symlinks"),
+        format_with_source(
+            "path_topology.keep_logical_symlinks",
+            &style.value(
+                &config
+                    .path_topology
+                    .keep_logical_symlinks
+                    .unwrap_or(false)
+                    .to_string()
+            ),
+            &value_sources
+        )
+    );
 
     // Show config file locations
     println!(
@@ -538,16 +730,23 @@ pub fn config_show(show_sources: bool, ctx: &OutputContext) -> Result<()> {
     );
     println!("{}", style.muted("# 1. Environment variables (RCH_*)"));
     println!("{}", style.muted("# 2. Project config: .rch/config.toml"));
+    println!(
+        "{}",
+        style.muted(&format!(
+            "# 3. Project overlay: {} (overridable keys only)",
+            config::PROJECT_OVERLAY_FILE
+        ))
+    );
     if let Some(dir) = config_dir() {
         println!(
             "{}",
             style.muted(&format!(
-                "# 3. User config: {}",
+                "# 4. User config: {}",
                 dir.join("config.toml").display()
             ))
         );
     }
-    println!("{}", style.muted("# 4. Built-in defaults"));
+    println!("{}", style.muted("# 5. Built-in defaults"));
 
     Ok(())
 }
@@ -631,6 +830,18 @@ pub(super) fn collect_value_sources(
         config.general.force_remote.to_string(),
         sources,
     );
+    push_value_source(
+        &mut values,
+        "general.confirm_first_offload",
+        config.general.confirm_first_offload.to_string(),
+        sources,
+    );
+    push_value_source(
+        &mut values,
+        "general.shadow_mode",
+        config.general.shadow_mode.to_string(),
+        sources,
+    );
     push_value_source(
         &mut values,
         "general.log_level",
@@ -643,6 +854,12 @@ pub(super) fn collect_value_sources(
         config.general.socket_path.clone(),
         sources,
     );
+    push_value_source(
+        &mut values,
+        "general.profile",
+        config.general.profile.clone().unwrap_or_default(),
+        sources,
+    );
     push_value_source(
         &mut values,
         "compilation.confidence_threshold",
@@ -793,6 +1010,14 @@ pub(super) fn collect_value_sources(
         config.self_healing.auto_start_timeout_secs.to_string(),
         sources,
     );
+    if let Some(state_dir) = config.self_healing.state_dir.as_ref() {
+        push_value_source(
+            &mut values,
+            "self_healing.state_dir",
+            state_dir.clone(),
+            sources,
+        );
+    }
 
     // Path topology overrides (issue #10). The runtime path-normalization
     // layer already supported these via env var, but the config CLI surface
@@ -831,6 +1056,27 @@ pub(super) fn collect_value_sources(
         alias_root_effective,
         sources,
     );
+    push_value_source(
+        &mut values,
+        "path_topology.wsl_mount_prefix",
+        config
+            .path_topology
+            .to_policy()
+            .wsl_mount_prefix()
+            .display()
+            .to_string(),
+        sources,
+    );
+    push_value_source(
+        &mut values,
+        "path_topology.keep_logical_symlinks",
+        config
+            .path_topology
+            .keep_logical_symlinks
+            .unwrap_or(false)
+            .to_string(),
+        sources,
+    );
 
     values
 }
@@ -906,12 +1152,31 @@ pub fn config_validate(ctx: &OutputContext) -> Result<()> {
         validations.push(missing);
     }
 
+    // project overlay (.rch.toml), validated as merged over the user config
+    let project_overlay = std::env::current_dir()
+        .ok()
+        .as_deref()
+        .and_then(config::find_project_overlay);
+    if let Some(overlay) = &project_overlay {
+        validations.push(config::validate_project_overlay_file(
+            overlay,
+            Some(&config_path),
+        ));
+    }
+
     // project config
     let project_config = PathBuf::from(".rch/config.toml");
     if project_config.exists() {
         validations.push(config::validate_rch_config_file(&project_config));
     }
 
+    // classifier rules
+    if let Some(rules_path) = config::classifier_rules_path()
+        && rules_path.exists()
+    {
+        validations.push(config::validate_classifier_rules_file(&rules_path));
+    }
+
     let mut error_items = Vec::new();
     let mut warning_items = Vec::new();
     for validation in &validations {
@@ -960,6 +1225,10 @@ pub fn config_validate(ctx: &OutputContext) -> Result<()> {
 
     print_file_validation("workers.toml", &validations, style, &workers_path);
 
+    if let Some(overlay) = &project_overlay {
+        print_file_validation(config::PROJECT_OVERLAY_FILE, &validations, style, overlay);
+    }
+
     if project_config.exists() {
         print_file_validation(".rch/config.toml", &validations, style, &project_config);
     }
@@ -1048,12 +1317,22 @@ pub(crate) fn apply_config_set(config_path: &Path, key: &str, value: &str) -> Re
         "general.force_remote" => {
             config.general.force_remote = parse_bool(value, key)?;
         }
+        "general.confirm_first_offload" => {
+            config.general.confirm_first_offload = parse_bool(value, key)?;
+        }
+        "general.shadow_mode" => {
+            config.general.shadow_mode = parse_bool(value, key)?;
+        }
         "general.log_level" => {
             config.general.log_level = value.trim().trim_matches(|c| c == '"').to_string();
         }
         "general.socket_path" => {
             config.general.socket_path = value.trim().trim_matches(|c| c == '"').to_string();
         }
+        "general.profile" => {
+            let name = value.trim().trim_matches(|c| c == '"');
+            config.general.profile = Some(name.to_string()).filter(|name| !name.is_empty());
+        }
         "compilation.confidence_threshold" => {
             let threshold = parse_f64(value, key)?;
             if !(0.0..=1.0).contains(&threshold) {
@@ -1144,6 +1423,10 @@ pub(crate) fn apply_config_set(config_path: &Path, key: &str, value: &str) -> Re
         "self_healing.auto_start_timeout_secs" => {
             config.self_healing.auto_start_timeout_secs = parse_u64(value, key)?;
         }
+        "self_healing.state_dir" => {
+            let dir = value.trim().trim_matches(|c| c == '"');
+            config.self_healing.state_dir = Some(dir.to_string()).filter(|dir| !dir.is_empty());
+        }
         _ => {
             return Err(ConfigError::InvalidValue {
                 field: key.to_string(),
@@ -1162,6 +1445,7 @@ pub(crate) fn apply_config_set(config_path: &Path, key: &str, value: &str) -> Re
         }
         .into());
     }
+    refuse_invalid_config(config_path, key, &config)?;
 
     let contents = toml::to_string_pretty(&config)?;
     std::fs::write(config_path, format!("{}\n", contents))
@@ -1170,6 +1454,30 @@ pub(crate) fn apply_config_set(config_path: &Path, key: &str, value: &str) -> Re
     Ok(())
 }
 
+/// Refuse to persist a config that `rch config validate` would report errors
+/// for. Warnings are allowed through; filesystem checks are left to validate.
+fn refuse_invalid_config(config_path: &Path, key: &str, config: &RchConfig) -> Result<()> {
+    let mut validation = config::FileValidation::new(config_path);
+    config::validate_rch_config_values(config, &mut validation);
+    if validation.errors.is_empty() {
+        return Ok(());
+    }
+    let suggestion = format!(
+        "Fix the listed settings first (`rch config set <key> <value>` or `rch config reset <key>`); {:?} was left unchanged",
+        config_path
+    );
+    Err(ConfigError::InvalidValue {
+        field: key.to_string(),
+        reason: format!(
+            "the resulting config would be invalid: {}. {}",
+            validation.errors.join("; "),
+            suggestion
+        ),
+        suggestion,
+    }
+    .into())
+}
+
 /// Reset a configuration value to its default.
 pub fn config_reset(key: &str, ctx: &OutputContext) -> Result<()> {
     let config_dir = config_dir().context("Could not determine config directory")?;
@@ -1203,6 +1511,14 @@ fn config_reset_at(config_path: &Path, key: &str, ctx: &OutputContext) -> Result
             config.general.force_remote = defaults.general.force_remote;
             config.general.force_remote.to_string()
         }
+        "general.confirm_first_offload" => {
+            config.general.confirm_first_offload = defaults.general.confirm_first_offload;
+            config.general.confirm_first_offload.to_string()
+        }
+        "general.shadow_mode" => {
+            config.general.shadow_mode = defaults.general.shadow_mode;
+            config.general.shadow_mode.to_string()
+        }
         "general.log_level" => {
             config.general.log_level = defaults.general.log_level;
             config.general.log_level.clone()
@@ -1211,6 +1527,10 @@ fn config_reset_at(config_path: &Path, key: &str, ctx: &OutputContext) -> Result
             config.general.socket_path = defaults.general.socket_path;
             config.general.socket_path.clone()
         }
+        "general.profile" => {
+            config.general.profile = defaults.general.profile;
+            String::new()
+        }
         "compilation.confidence_threshold" => {
             config.compilation.confidence_threshold = defaults.compilation.confidence_threshold;
             config.compilation.confidence_threshold.to_string()
@@ -1841,267 +2161,83 @@ fn flatten_json_scalars(
     }
 }
 
-pub fn config_diff(ctx: &OutputContext) -> Result<()> {
-    use rch_common::RchConfig;
-
-    let style = ctx.theme();
-    let loaded = config::load_config_with_sources()?;
-    let config = &loaded.config;
-    let defaults = RchConfig::default();
-    let sources = &loaded.sources;
-
-    let mut entries = Vec::new();
-
-    // Helper to add entry if different
-    macro_rules! diff_field {
-        ($key:expr, $current:expr, $default:expr, $source_key:expr) => {
-            let current_str = format!("{}", $current);
-            let default_str = format!("{}", $default);
-            if current_str != default_str {
-                let source = sources
-                    .get($source_key)
-                    .map(|s| format!("{:?}", s))
-                    .unwrap_or_else(|| "unknown".to_string());
-                entries.push(ConfigDiffEntry {
-                    key: $key.to_string(),
-                    current: current_str,
-                    default: default_str,
-                    source,
-                });
+/// Flatten the effective config and the defaults to dotted keys and pair each
+/// leaf with where its value came from.
+///
+/// Every section is compared, so new config knobs show up without being listed
+/// here. Operator paths in `remediation` and webhook URLs are redacted before
+/// comparison so they are never printed in the clear. Unless `all` is set,
+/// only values that differ from the defaults are returned.
+fn config_diff_entries(
+    config: &RchConfig,
+    sources: &config::ConfigSourceMap,
+    all: bool,
+) -> Vec<ConfigDiffEntry> {
+    let mut current = std::collections::BTreeMap::new();
+    let mut defaults = std::collections::BTreeMap::new();
+    flatten_config_object(&displayable_config(config), &mut current);
+    flatten_config_object(&displayable_config(&RchConfig::default()), &mut defaults);
+
+    current
+        .into_iter()
+        .filter_map(|(key, current)| {
+            let default = defaults.get(&key).cloned().unwrap_or_default();
+            let changed = current != default;
+            if !changed && !all {
+                return None;
             }
-        };
-    }
-
-    // General section
-    diff_field!(
-        "general.enabled",
-        config.general.enabled,
-        defaults.general.enabled,
-        "general.enabled"
-    );
-    diff_field!(
-        "general.log_level",
-        &config.general.log_level,
-        &defaults.general.log_level,
-        "general.log_level"
-    );
-    diff_field!(
-        "general.socket_path",
-        &config.general.socket_path,
-        &defaults.general.socket_path,
-        "general.socket_path"
-    );
-
-    // Compilation section
-    diff_field!(
-        "compilation.confidence_threshold",
-        config.compilation.confidence_threshold,
-        defaults.compilation.confidence_threshold,
-        "compilation.confidence_threshold"
-    );
-    diff_field!(
-        "compilation.min_local_time_ms",
-        config.compilation.min_local_time_ms,
-        defaults.compilation.min_local_time_ms,
-        "compilation.min_local_time_ms"
-    );
-    diff_field!(
-        "compilation.remote_speedup_threshold",
-        config.compilation.remote_speedup_threshold,
-        defaults.compilation.remote_speedup_threshold,
-        "compilation.remote_speedup_threshold"
-    );
-    diff_field!(
-        "compilation.build_slots",
-        config.compilation.build_slots,
-        defaults.compilation.build_slots,
-        "compilation.build_slots"
-    );
-    diff_field!(
-        "compilation.test_slots",
-        config.compilation.test_slots,
-        defaults.compilation.test_slots,
-        "compilation.test_slots"
-    );
-    diff_field!(
-        "compilation.check_slots",
-        config.compilation.check_slots,
-        defaults.compilation.check_slots,
-        "compilation.check_slots"
-    );
-    diff_field!(
-        "compilation.build_timeout_sec",
-        config.compilation.build_timeout_sec,
-        defaults.compilation.build_timeout_sec,
-        "compilation.build_timeout_sec"
-    );
-    diff_field!(
-        "compilation.test_timeout_sec",
-        config.compilation.test_timeout_sec,
-        defaults.compilation.test_timeout_sec,
-        "compilation.test_timeout_sec"
-    );
-    diff_field!(
-        "compilation.bun_timeout_sec",
-        config.compilation.bun_timeout_sec,
-        defaults.compilation.bun_timeout_sec,
-        "compilation.bun_timeout_sec"
-    );
-    diff_field!(
-        "compilation.external_timeout_enabled",
-        config.compilation.external_timeout_enabled,
-        defaults.compilation.external_timeout_enabled,
-        "compilation.external_timeout_enabled"
-    );
+            let source = match sources.get(&key) {
+                Some(source) => source.label(),
+                None if changed => "unknown".to_string(),
+                None => ConfigValueSource::Default.label(),
+            };
+            Some(ConfigDiffEntry {
+                key,
+                current,
+                default,
+                source,
+                changed,
+            })
+        })
+        .collect()
+}
 
-    // Transfer section
-    diff_field!(
-        "transfer.compression_level",
-        config.transfer.compression_level,
-        defaults.transfer.compression_level,
-        "transfer.compression_level"
-    );
-
-    // Compare exclude patterns
-    let current_excludes = config.transfer.exclude_patterns.join(",");
-    let default_excludes = defaults.transfer.exclude_patterns.join(",");
-    if current_excludes != default_excludes {
-        let source = sources
-            .get("transfer.exclude_patterns")
-            .map(|s| format!("{:?}", s))
-            .unwrap_or_else(|| "unknown".to_string());
-        entries.push(ConfigDiffEntry {
-            key: "transfer.exclude_patterns".to_string(),
-            current: format!("[{}]", current_excludes),
-            default: format!("[{}]", default_excludes),
-            source,
-        });
+/// The config as JSON with operator paths, webhook URLs and the notification
+/// command (which may embed tokens) redacted.
+fn displayable_config(config: &RchConfig) -> serde_json::Value {
+    let mut config = config.clone();
+    config.remediation = config.remediation.redacted();
+    for endpoint in &mut config.doctor.webhooks.endpoints {
+        endpoint.url = "<redacted>".to_string();
     }
-
-    // Circuit breaker section
-    diff_field!(
-        "circuit.failure_threshold",
-        config.circuit.failure_threshold,
-        defaults.circuit.failure_threshold,
-        "circuit.failure_threshold"
-    );
-    diff_field!(
-        "circuit.success_threshold",
-        config.circuit.success_threshold,
-        defaults.circuit.success_threshold,
-        "circuit.success_threshold"
-    );
-    diff_field!(
-        "circuit.error_rate_threshold",
-        config.circuit.error_rate_threshold,
-        defaults.circuit.error_rate_threshold,
-        "circuit.error_rate_threshold"
-    );
-    diff_field!(
-        "circuit.window_secs",
-        config.circuit.window_secs,
-        defaults.circuit.window_secs,
-        "circuit.window_secs"
-    );
-    diff_field!(
-        "circuit.open_cooldown_secs",
-        config.circuit.open_cooldown_secs,
-        defaults.circuit.open_cooldown_secs,
-        "circuit.open_cooldown_secs"
-    );
-    diff_field!(
-        "circuit.half_open_max_probes",
-        config.circuit.half_open_max_probes,
-        defaults.circuit.half_open_max_probes,
-        "circuit.half_open_max_probes"
-    );
-
-    // Output section
-    diff_field!(
-        "output.visibility",
-        config.output.visibility,
-        defaults.output.visibility,
-        "output.visibility"
-    );
-    diff_field!(
-        "output.first_run_complete",
-        config.output.first_run_complete,
-        defaults.output.first_run_complete,
-        "output.first_run_complete"
-    );
-
-    // Self-healing section
-    diff_field!(
-        "self_healing.hook_starts_daemon",
-        config.self_healing.hook_starts_daemon,
-        defaults.self_healing.hook_starts_daemon,
-        "self_healing.hook_starts_daemon"
-    );
-    diff_field!(
-        "self_healing.daemon_installs_hooks",
-        config.self_healing.daemon_installs_hooks,
-        defaults.self_healing.daemon_installs_hooks,
-        "self_healing.daemon_installs_hooks"
-    );
-    diff_field!(
-        "self_healing.auto_start_cooldown_secs",
-        config.self_healing.auto_start_cooldown_secs,
-        defaults.self_healing.auto_start_cooldown_secs,
-        "self_healing.auto_start_cooldown_secs"
-    );
-    diff_field!(
-        "self_healing.auto_start_timeout_secs",
-        config.self_healing.auto_start_timeout_secs,
-        defaults.self_healing.auto_start_timeout_secs,
-        "self_healing.auto_start_timeout_secs"
-    );
-
-    // Environment allowlist (compare as sets)
-    if !config.environment.allowlist.is_empty()
-        && config.environment.allowlist != defaults.environment.allowlist
-    {
-        let source = sources
-            .get("environment.allowlist")
-            .map(|s| format!("{:?}", s))
-            .unwrap_or_else(|| "unknown".to_string());
-        entries.push(ConfigDiffEntry {
-            key: "environment.allowlist".to_string(),
-            current: format!("[{}]", config.environment.allowlist.join(", ")),
-            default: format!("[{}]", defaults.environment.allowlist.join(", ")),
-            source,
-        });
+    for secret in [
+        &mut config.notifications.webhook_url,
+        &mut config.notifications.command,
+    ] {
+        if secret.is_some() {
+            *secret = Some("<redacted>".to_string());
+        }
     }
+    serde_json::to_value(&config).unwrap_or_default()
+}
 
-    // Remediation section (bd-...remediation-ocv9i.17.2): a generic redacted
-    // diff so every changed knob is visible — with operator paths redacted —
-    // without hand-listing ~40 fields. The effective value is redacted before
-    // comparison so a changed proof-store/ledger/disk-root/remote-base path is
-    // never exported in the clear.
-    {
-        let current = serde_json::to_value(config.remediation.redacted()).unwrap_or_default();
-        let default = serde_json::to_value(&defaults.remediation).unwrap_or_default();
-        let mut cur_flat = std::collections::BTreeMap::new();
-        let mut def_flat = std::collections::BTreeMap::new();
-        flatten_json_scalars("remediation", &current, &mut cur_flat);
-        flatten_json_scalars("remediation", &default, &mut def_flat);
-        for (key, cur_val) in &cur_flat {
-            let def_val = def_flat.get(key).cloned().unwrap_or_default();
-            if *cur_val != def_val {
-                let source = sources
-                    .get(key.as_str())
-                    .map(|s| format!("{:?}", s))
-                    .unwrap_or_else(|| "config".to_string());
-                entries.push(ConfigDiffEntry {
-                    key: key.clone(),
-                    current: cur_val.clone(),
-                    default: def_val,
-                    source,
-                });
-            }
+fn flatten_config_object(
+    value: &serde_json::Value,
+    out: &mut std::collections::BTreeMap<String, String>,
+) {
+    if let serde_json::Value::Object(sections) = value {
+        for (section, value) in sections {
+            flatten_json_scalars(section, value, out);
         }
     }
+}
+
+pub fn config_diff(all: bool, ctx: &OutputContext) -> Result<()> {
+    let style = ctx.theme();
+    let loaded = config::load_config_with_sources()?;
+    let entries = config_diff_entries(&loaded.config, &loaded.sources, all);
 
-    let total_changes = entries.len();
+    let total_changes = entries.iter().filter(|entry| entry.changed).count();
 
     // Output
     if ctx.is_json() {
@@ -2110,16 +2246,18 @@ pub fn config_diff(ctx: &OutputContext) -> Result<()> {
             total_changes,
         };
         ctx.json(&ApiResponse::ok("config diff", response))?;
-    } else if entries.is_empty() {
+    } else if total_changes == 0 && !all {
         println!(
             "{} All configuration values are at defaults",
             StatusIndicator::Success.display(style)
         );
     } else {
-        println!(
-            "{} Configuration Diff (non-default values)",
-            style.highlight("RCH")
-        );
+        let title = if all {
+            "Configuration (all values)"
+        } else {
+            "Configuration Diff (non-default values)"
+        };
+        println!("{} {}", style.highlight("RCH"), title);
         println!();
 
         // Print header
@@ -2151,9 +2289,15 @@ pub fn config_diff(ctx: &OutputContext) -> Result<()> {
                 entry.default.clone()
             };
 
+            let key = format!("{:<40}", entry.key);
+            let key = if entry.changed {
+                key
+            } else {
+                style.muted(&key).to_string()
+            };
             println!(
-                "{:<40} {:<20} {:<20} {}",
-                entry.key,
+                "{} {:<20} {:<20} {}",
+                key,
                 current,
                 style.muted(&default),
                 entry.source
@@ -2167,6 +2311,220 @@ pub fn config_diff(ctx: &OutputContext) -> Result<()> {
     Ok(())
 }
 
+/// `rch config profile [name]`: list profiles, or switch the persisted one.
+///
+/// Switching validates the merged result (config files, the new profile and
+/// env overrides) first and leaves the config untouched if it has errors.
+pub fn config_profile(name: Option<&str>, ctx: &OutputContext) -> Result<()> {
+    let Some(name) = name else {
+        return config_profile_list(ctx);
+    };
+    let project_path = PathBuf::from(".rch/config.toml");
+    config_profile_switch_at(
+        &default_config_path()?,
+        Some(project_path.as_path()).filter(|path| path.exists()),
+        name,
+        ctx,
+    )
+}
+
+fn profile_entries(active: Option<&str>) -> Vec<ConfigProfileEntry> {
+    let active = active.and_then(Profile::parse_named);
+    Profile::NAMED
+        .iter()
+        .map(|profile| ConfigProfileEntry {
+            name: profile.as_str().to_string(),
+            description: profile.description().to_string(),
+            active: active == Some(*profile),
+        })
+        .collect()
+}
+
+fn config_profile_list(ctx: &OutputContext) -> Result<()> {
+    let style = ctx.theme();
+    let loaded = config::load_config_with_sources()?;
+    let active = loaded.config.general.profile.clone();
+    let source = loaded
+        .sources
+        .get("general.profile")
+        .unwrap_or(&ConfigValueSource::Default)
+        .label();
+    let profiles = profile_entries(active.as_deref());
+
+    if ctx.is_json() {
+        ctx.json(&ApiResponse::ok(
+            "config profile",
+            ConfigProfileResponse {
+                active,
+                source,
+                profiles,
+                config_path: None,
+                changes: Vec::new(),
+                warnings: Vec::new(),
+            },
+        ))?;
+        return Ok(());
+    }
+
+    for entry in &profiles {
+        let marker = if entry.active {
+            StatusIndicator::Success.display(style).to_string()
+        } else {
+            " ".to_string()
+        };
+        println!(
+            "{} {:<5} {}",
+            marker,
+            style.highlight(&entry.name),
+            style.muted(&entry.description)
+        );
+    }
+    println!();
+    match active {
+        Some(name) => println!(
+            "Active profile: {} {}",
+            style.value(&name),
+            style.muted(&format!("# from {}", source))
+        ),
+        None => println!(
+            "No profile active {}",
+            style.muted("(switch with `rch config profile <name>`)")
+        ),
+    }
+    Ok(())
+}
+
+fn config_profile_switch_at(
+    config_path: &Path,
+    project_path: Option<&Path>,
+    name: &str,
+    ctx: &OutputContext,
+) -> Result<()> {
+    let style = ctx.theme();
+    let profile = Profile::parse_named(name).ok_or_else(|| ConfigError::InvalidValue {
+        field: "general.profile".to_string(),
+        reason: format!("unknown profile {:?}", name),
+        suggestion: "Use one of: dev, prod, test (`rch config profile` lists them)".to_string(),
+    })?;
+
+    let user_path = Some(config_path).filter(|path| path.exists());
+    let before = config::load_config_with_user_profile(user_path, project_path, None)?;
+    let after =
+        config::load_config_with_user_profile(user_path, project_path, Some(profile.as_str()))?;
+
+    let mut validation = config::FileValidation::new(config_path);
+    config::validate_rch_config_values(&after, &mut validation);
+    let mut errors = validation.errors;
+    let mut warnings = validation.warnings;
+    for warning in validate_config(&ConfigToValidate {
+        zstd_level: i32::try_from(after.transfer.compression_level).ok(),
+        mock_ssh: profile.uses_mock_ssh(),
+        test_mode: profile == Profile::Test,
+        circuit_failure_threshold: Some(after.circuit.failure_threshold),
+        circuit_reset_timeout_sec: Some(after.circuit.open_cooldown_secs),
+        log_level: Some(after.general.log_level.clone()),
+        ..Default::default()
+    }) {
+        match warning.severity {
+            Severity::Error => errors.push(warning.to_string()),
+            Severity::Warning => warnings.push(warning.to_string()),
+            Severity::Info => {}
+        }
+    }
+    if !errors.is_empty() {
+        return Err(ConfigError::InvalidValue {
+            field: "general.profile".to_string(),
+            reason: format!(
+                "switching to {} would leave an invalid config: {}",
+                profile,
+                errors.join("; ")
+            ),
+            suggestion: format!(
+                "Fix the listed settings first; {:?} was left unchanged",
+                config_path
+            ),
+        }
+        .into());
+    }
+
+    apply_config_set(config_path, "general.profile", profile.as_str())?;
+
+    let source = if std::env::var("RCH_PROFILE").is_ok_and(|value| !value.trim().is_empty()) {
+        ConfigValueSource::EnvVar("RCH_PROFILE".to_string())
+    } else if after.general.profile.as_deref() != Some(profile.as_str())
+        && let Some(project_path) = project_path
+    {
+        ConfigValueSource::ProjectConfig(project_path.to_path_buf())
+    } else {
+        ConfigValueSource::UserConfig(config_path.to_path_buf())
+    };
+    if after.general.profile.as_deref() != Some(profile.as_str()) {
+        warnings.push(format!(
+            "{} is overridden by {}; the effective profile stays {}",
+            profile,
+            source,
+            after.general.profile.as_deref().unwrap_or("unset")
+        ));
+    }
+    let changes = profile_changes(&before, &after);
+
+    if ctx.is_json() {
+        ctx.json(&ApiResponse::ok(
+            "config profile",
+            ConfigProfileResponse {
+                active: after.general.profile.clone(),
+                source: source.label(),
+                profiles: profile_entries(after.general.profile.as_deref()),
+                config_path: Some(config_path.display().to_string()),
+                changes,
+                warnings,
+            },
+        ))?;
+        return Ok(());
+    }
+
+    println!(
+        "{} Switched to profile {} {}",
+        StatusIndicator::Success.display(style),
+        style.highlight(profile.as_str()),
+        style.muted(&format!("({})", profile.description()))
+    );
+    if changes.is_empty() {
+        println!("  {}", style.muted("No effective settings changed"));
+    }
+    for change in &changes {
+        let before = if change.before.is_empty() {
+            "(unset)"
+        } else {
+            change.before.as_str()
+        };
+        println!(
+            "  {}: {} → {}",
+            style.key(&change.key),
+            style.muted(before),
+            style.value(&change.after)
+        );
+    }
+    for warning in &warnings {
+        println!("{} {}", StatusIndicator::Warning.display(style), warning);
+    }
+    Ok(())
+}
+
+/// Effective settings that differ between two loaded configs.
+fn profile_changes(before: &RchConfig, after: &RchConfig) -> Vec<ConfigProfileChange> {
+    let mut old = std::collections::BTreeMap::new();
+    let mut new = std::collections::BTreeMap::new();
+    flatten_config_object(&displayable_config(before), &mut old);
+    flatten_config_object(&displayable_config(after), &mut new);
+    new.into_iter()
+        .filter_map(|(key, after)| {
+            let before = old.remove(&key).unwrap_or_default();
+            (before != after).then_some(ConfigProfileChange { key, before, after })
+        })
+        .collect()
+}
+
 fn parse_bool(value: &str, key: &str) -> Result<bool> {
     value.trim().parse::<bool>().map_err(|_| {
         ConfigError::InvalidValue {
@@ -2268,6 +2626,94 @@ mod tests {
         })
     }
 
+    // -------------------------------------------------------------------------
+    // config_diff_entries Tests
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn config_diff_entries_report_changed_values_with_sources() {
+        let _guard = test_guard!();
+        let mut config = RchConfig::default();
+        config.general.force_remote = true;
+        config.transfer.compression_level = 9;
+        let mut sources = config::ConfigSourceMap::new();
+        sources.insert(
+            "general.force_remote".to_string(),
+            ConfigValueSource::EnvVar("RCH_FORCE_REMOTE".to_string()),
+        );
+
+        let entries = config_diff_entries(&config, &sources, false);
+        assert!(entries.iter().all(|entry| entry.changed));
+        let force_remote = entries
+            .iter()
+            .find(|entry| entry.key == "general.force_remote")
+            .expect("force_remote entry");
+        assert_eq!(force_remote.current, "true");
+        assert_eq!(force_remote.default, "false");
+        assert_eq!(force_remote.source, "env:RCH_FORCE_REMOTE");
+        let level = entries
+            .iter()
+            .find(|entry| entry.key == "transfer.compression_level")
+            .expect("compression_level entry");
+        assert_eq!(level.source, "unknown");
+        assert_eq!(entries.len(), 2);
+    }
+
+    #[test]
+    fn config_diff_entries_all_includes_defaults() {
+        let _guard = test_guard!();
+        let config = RchConfig::default();
+        let sources = config::ConfigSourceMap::new();
+
+        assert!(config_diff_entries(&config, &sources, false).is_empty());
+        let all = config_diff_entries(&config, &sources, true);
+        let enabled = all
+            .iter()
+            .find(|entry| entry.key == "general.enabled")
+            .expect("general.enabled listed with --all");
+        assert!(!enabled.changed);
+        assert_eq!(enabled.source, "default");
+    }
+
+    #[test]
+    fn config_diff_entries_redact_webhook_urls() {
+        let _guard = test_guard!();
+        let mut config = RchConfig::default();
+        config.doctor.webhooks.endpoints.push(
+            serde_json::from_value(serde_json::json!({
+                "name": "ops",
+                "url": "https://hooks.example.com/secret-token",
+            }))
+            .expect("webhook endpoint"),
+        );
+        let entries = config_diff_entries(&config, &config::ConfigSourceMap::new(), false);
+        assert!(!entries.is_empty());
+        assert!(
+            entries
+                .iter()
+                .all(|entry| !entry.current.contains("secret-token"))
+        );
+    }
+
+    #[test]
+    fn config_diff_entries_redact_notification_secrets() {
+        let _guard = test_guard!();
+        let mut config = RchConfig::default();
+        config.notifications.webhook_url =
+            Some("https://hooks.example.com/notify-token".to_string());
+        config.notifications.command = Some("notify --token cmd-token".to_string());
+        let entries = config_diff_entries(&config, &config::ConfigSourceMap::new(), false);
+        let redacted = entries
+            .iter()
+            .filter(|entry| entry.key.starts_with("notifications."))
+            .collect::<Vec<_>>();
+        assert_eq!(redacted.len(), 2);
+        assert!(redacted.iter().all(|entry| entry.current == "<redacted>"));
+        assert!(entries.iter().all(|entry| {
+            !entry.current.contains("notify-token") && !entry.current.contains("cmd-token")
+        }));
+    }
+
     // -------------------------------------------------------------------------
     // parse_bool Tests
     // -------------------------------------------------------------------------
@@ -2550,4 +2996,129 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[test]
+    fn config_set_refuses_force_remote_when_force_local_is_set() {
+        let _guard = test_guard!();
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config_path = dir.path().join("config.toml");
+        let ctx = plain_context();
+
+        config_set_at(&config_path, "general.force_local", "true", &ctx).expect("set force_local");
+        let before = std::fs::read_to_string(&config_path).expect("read config");
+
+        let err = config_set_at(&config_path, "general.force_remote", "true", &ctx)
+            .expect_err("conflicting force flags must be refused");
+        assert!(err.to_string().contains("force_remote"), "{err}");
+        assert_eq!(
+            std::fs::read_to_string(&config_path).expect("read config"),
+            before,
+            "a refused set must not touch the file"
+        );
+    }
+
+    #[test]
+    fn config_set_refuses_values_that_fail_validation() {
+        let _guard = test_guard!();
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config_path = dir.path().join("config.toml");
+        let ctx = plain_context();
+
+        for (key, value, expected) in [
+            (
+                "general.log_level",
+                "loud",
+                "general.log_level must be one of",
+            ),
+            ("compilation.build_slots", "0", "compilation.build_slots"),
+            (
+                "general.socket_path",
+                "\"\"",
+                "general.socket_path cannot be empty",
+            ),
+            (
+                "environment.allowlist",
+                "[\"NOT-A-KEY\"]",
+                "environment.allowlist contains invalid key",
+            ),
+        ] {
+            let err = config_set_at(&config_path, key, value, &ctx)
+                .expect_err("invalid value must be refused");
+            let message = err.to_string();
+            assert!(message.contains(expected), "{key}: {message}");
+            assert!(message.contains("config reset"), "{key}: {message}");
+        }
+        assert!(
+            !config_path.exists(),
+            "refused sets must not create the config file"
+        );
+    }
+
+    #[test]
+    fn config_set_allows_values_that_only_warn() {
+        let _guard = test_guard!();
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config_path = dir.path().join("config.toml");
+        let ctx = plain_context();
+
+        // Compression level 0 is a validation warning, not an error.
+        config_set_at(&config_path, "transfer.compression_level", "0", &ctx)
+            .expect("warning-only value is written");
+        let contents = std::fs::read_to_string(&config_path).expect("read config");
+        let config: RchConfig = toml::from_str(&contents).expect("parse config");
+        assert_eq!(config.transfer.compression_level, 0);
+    }
+
+    #[test]
+    fn config_profile_switch_persists_named_profile() {
+        let _guard = test_guard!();
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config_path = dir.path().join("config.toml");
+        let ctx = plain_context();
+
+        config_profile_switch_at(&config_path, None, "production", &ctx)
+            .expect("valid profile is written");
+        let contents = std::fs::read_to_string(&config_path).expect("read config");
+        let config: RchConfig = toml::from_str(&contents).expect("parse config");
+        assert_eq!(config.general.profile.as_deref(), Some("prod"));
+    }
+
+    #[test]
+    fn config_profile_switch_refuses_unknown_or_invalid_results() {
+        let _guard = test_guard!();
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config_path = dir.path().join("config.toml");
+        let ctx = plain_context();
+
+        let err = config_profile_switch_at(&config_path, None, "staging", &ctx)
+            .expect_err("unknown profile must be refused");
+        assert!(err.to_string().contains("unknown profile"), "{err}");
+        assert!(!config_path.exists());
+
+        // A project config that is already invalid blocks the switch.
+        let project_path = dir.path().join("project.toml");
+        std::fs::write(&project_path, "[compilation]\nbuild_slots = 0\n")
+            .expect("write project config");
+        let err = config_profile_switch_at(&config_path, Some(&project_path), "dev", &ctx)
+            .expect_err("invalid merged config must be refused");
+        let message = err.to_string();
+        assert!(message.contains("compilation.build_slots"), "{message}");
+        assert!(!config_path.exists(), "refused switch must not write");
+    }
+
+    #[test]
+    fn profile_changes_lists_effective_differences() {
+        let _guard = test_guard!();
+        let before = RchConfig::default();
+        let mut after = before.clone();
+        after.general.profile = Some("dev".to_string());
+        after.general.log_level = "debug".to_string();
+
+        let changes = profile_changes(&before, &after);
+        let keys: Vec<_> = changes.iter().map(|change| change.key.as_str()).collect();
+        assert_eq!(keys, vec!["general.log_level", "general.profile"]);
+        assert_eq!(changes[0].before, "info");
+        assert_eq!(changes[0].after, "debug");
+        assert_eq!(changes[1].before, "");
+    }
 }
@@ -622,6 +622,9 @@ fn generate_workers_toml(workers: &[WizardWorker]) -> Result<String> {
                 priority: u32::from(worker.priority),
                 tags: Vec::new(),
                 enabled: true,
+                max_concurrent_builds: None,
+                nice_level: None,
+                ionice_class: None,
             })
             .collect(),
     };
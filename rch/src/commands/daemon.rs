@@ -281,7 +281,8 @@ pub async fn daemon_status(ctx: &OutputContext) -> Result<()> {
 /// Start the daemon.
 pub async fn daemon_start(ctx: &OutputContext) -> Result<()> {
     let style = ctx.theme();
-    let socket_path_str = configured_socket_path()?;
+    let config = crate::config::load_config()?;
+    let socket_path_str = shellexpand::tilde(&config.general.socket_path).into_owned();
     let socket_path = Path::new(&socket_path_str);
 
     if socket_path.exists() && daemon_responds_on_configured_socket().await {
@@ -333,12 +334,15 @@ pub async fn daemon_start(ctx: &OutputContext) -> Result<()> {
 
     // Spawn rchd in background using nohup to detach from terminal
     // This avoids needing unsafe code for setsid()
+    let log_path = crate::hook::configured_daemon_log_path(&config.self_healing);
+    let (stdout, stderr) = crate::hook::daemon_log_stdio(&log_path);
     let mut cmd = Command::new("nohup");
     let daemon_args = daemon_start_args(socket_path);
     cmd.arg(&rchd_path)
         .args(daemon_args)
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
+        .env(rch_common::log_retention::DAEMON_STDIO_LOG_ENV, &log_path)
+        .stdout(stdout)
+        .stderr(stderr)
         .stdin(Stdio::null())
         .kill_on_drop(false);
 
@@ -824,12 +828,15 @@ pub async fn daemon_reload(ctx: &OutputContext) -> Result<()> {
     Ok(())
 }
 
-/// Show daemon logs.
-pub fn daemon_logs(lines: usize, ctx: &OutputContext) -> Result<()> {
+/// Show daemon logs, optionally following the log file as it grows.
+pub fn daemon_logs(lines: usize, follow: bool, ctx: &OutputContext) -> Result<()> {
     let style = ctx.theme();
 
-    // Try common log file locations first
+    // The log written by a detached (auto-started / `rch daemon start`)
+    // daemon comes first, then other common log file locations.
+    let config = crate::config::load_config().unwrap_or_default();
     let log_paths = vec![
+        crate::hook::configured_daemon_log_path(&config.self_healing),
         PathBuf::from("/tmp/rchd.log"),
         config_dir()
             .map(|d| d.join("daemon.log"))
@@ -869,6 +876,10 @@ pub fn daemon_logs(lines: usize, ctx: &OutputContext) -> Result<()> {
                 for line in &all_lines[start..] {
                     println!("{}", line);
                 }
+
+                if follow {
+                    follow_log_file(path, content.len() as u64)?;
+                }
             }
 
             return Ok(());
@@ -955,6 +966,34 @@ pub fn daemon_logs(lines: usize, ctx: &OutputContext) -> Result<()> {
     Ok(())
 }
 
+/// Poll `path` and print lines appended after `offset` until interrupted.
+///
+/// A log that shrinks was rotated by a daemon respawn, so reading restarts
+/// from the beginning of the new file.
+fn follow_log_file(path: &Path, mut offset: u64) -> Result<()> {
+    use std::io::{Read, Seek, SeekFrom, Write};
+
+    loop {
+        std::thread::sleep(std::time::Duration::from_millis(500));
+        let Ok(mut file) = std::fs::File::open(path) else {
+            continue;
+        };
+        let len = file.metadata()?.len();
+        if len < offset {
+            offset = 0;
+        }
+        if len == offset {
+            continue;
+        }
+        file.seek(SeekFrom::Start(offset))?;
+        let mut chunk = Vec::new();
+        offset += file.read_to_end(&mut chunk)? as u64;
+        let mut stdout = std::io::stdout().lock();
+        stdout.write_all(&chunk)?;
+        stdout.flush()?;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,279 @@
+//! `rch env`: list the environment variables RCH recognizes, with their
+//! current value, default, and effect.
+//!
+//! The static list comes from [`rch_common::env_vars`]. Secrets whose names
+//! are chosen by the operator (webhook tokens under `[doctor.webhooks]`) are
+//! added from config and always redacted. Set `RCH_*` variables that match
+//! neither (nor a fault-injection hook from
+//! [`rch_common::testing::injection`]) are reported as unrecognized, which
+//! usually means a typo.
+
+use std::collections::BTreeSet;
+
+use anyhow::Result;
+use rch_common::testing::injection::is_injection_var;
+use rch_common::{ApiResponse, EnvVarCategory, REDACTED_ENV_VALUE, RchConfig, env_vars};
+use serde::Serialize;
+
+use crate::config::load_config;
+use crate::ui::context::OutputContext;
+
+/// One row of `rch env`.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+struct EnvVarEntry {
+    name: String,
+    category: EnvVarCategory,
+    /// Current value (redacted for secrets); `None` when unset.
+    value: Option<String>,
+    default: String,
+    description: String,
+    secret: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct EnvReport {
+    variables: Vec<EnvVarEntry>,
+    /// Set `RCH_*` variables that nothing in RCH reads.
+    unrecognized: Vec<String>,
+}
+
+/// Webhook secret env var names configured under `[doctor.webhooks]`, with
+/// the endpoint and field that reference them.
+fn webhook_secret_names(config: &RchConfig) -> Vec<(String, String)> {
+    let mut names = Vec::new();
+    for endpoint in &config.doctor.webhooks.endpoints {
+        for (field, name) in [
+            ("routing_key_env", &endpoint.routing_key_env),
+            ("bearer_token_env", &endpoint.bearer_token_env),
+            ("signing_secret_env", &endpoint.signing_secret_env),
+        ] {
+            if let Some(name) = name.as_deref().filter(|n| !n.trim().is_empty()) {
+                names.push((
+                    name.to_string(),
+                    format!("{field} of webhook '{}'", endpoint.name),
+                ));
+            }
+        }
+    }
+    names
+}
+
+/// Build the report from a variable lookup. Pure so tests need not touch
+/// the process environment.
+fn build_report(
+    lookup: impl Fn(&str) -> Option<String>,
+    set_rch_vars: impl IntoIterator<Item = String>,
+    webhook_secrets: &[(String, String)],
+    only_set: bool,
+) -> EnvReport {
+    let mut known = BTreeSet::new();
+    let mut variables = Vec::new();
+
+    for spec in env_vars() {
+        known.insert(spec.name.to_string());
+        variables.push(EnvVarEntry {
+            name: spec.name.to_string(),
+            category: spec.category,
+            value: lookup(spec.name).map(|raw| spec.display_value(&raw)),
+            default: spec.default.to_string(),
+            description: spec.description.to_string(),
+            secret: spec.secret,
+        });
+    }
+
+    for (name, reference) in webhook_secrets {
+        if !known.insert(name.clone()) {
+            continue;
+        }
+        variables.push(EnvVarEntry {
+            name: name.clone(),
+            category: EnvVarCategory::Webhook,
+            value: lookup(name).map(|_| REDACTED_ENV_VALUE.to_string()),
+            default: "unset".to_string(),
+            description: format!("Secret read at delivery time ({reference})."),
+            secret: true,
+        });
+    }
+
+    if only_set {
+        variables.retain(|entry| entry.value.is_some());
+    }
+
+    let unrecognized = set_rch_vars
+        .into_iter()
+        .filter(|name| name.starts_with("RCH_") && !known.contains(name) && !is_injection_var(name))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    EnvReport {
+        variables,
+        unrecognized,
+    }
+}
+
+/// `rch env`: print every recognized environment variable.
+pub fn show_env(only_set: bool, ctx: &OutputContext) -> Result<()> {
+    // A broken config should not hide the rest of the listing; it only
+    // costs the webhook secret names.
+    let webhook_secrets = load_config()
+        .map(|config| webhook_secret_names(&config))
+        .unwrap_or_default();
+    let report = build_report(
+        |name| std::env::var(name).ok(),
+        std::env::vars_os().filter_map(|(name, _)| name.into_string().ok()),
+        &webhook_secrets,
+        only_set,
+    );
+
+    if ctx.is_json() {
+        let _ = ctx.json(&ApiResponse::ok("env", &report));
+        return Ok(());
+    }
+
+    render_human(&report, only_set, ctx);
+    Ok(())
+}
+
+fn render_human(report: &EnvReport, only_set: bool, ctx: &OutputContext) {
+    let style = ctx.style();
+    println!("{}", style.format_header("RCH Environment"));
+
+    if report.variables.is_empty() {
+        println!("  {}", style.muted("No recognized variables are set."));
+    }
+
+    let mut current = None;
+    for entry in &report.variables {
+        if current != Some(entry.category) {
+            current = Some(entry.category);
+            println!();
+            println!("  {}", style.key(&format!("[{}]", entry.category.label())));
+        }
+        let value = match &entry.value {
+            Some(value) => style.value(value),
+            None => style.muted("(unset)"),
+        };
+        println!("  {} = {}", style.key(&entry.name), value);
+        println!(
+            "      {} {}",
+            style.muted(&format!("default: {};", entry.default)),
+            style.muted(&entry.description)
+        );
+    }
+
+    if !report.unrecognized.is_empty() {
+        println!();
+        println!("  {}", style.key("[unrecognized]"));
+        for name in &report.unrecognized {
+            println!(
+                "  {} {}",
+                style.warning(name),
+                style.muted("is set but not read by rch (typo?)")
+            );
+        }
+    }
+
+    if !only_set {
+        println!();
+        println!(
+            "  {}",
+            style.muted("Use `rch env --set` to list only the variables that are set.")
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn lookup_from(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let map: HashMap<String, String> = pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect();
+        move |name| map.get(name).cloned()
+    }
+
+    #[test]
+    fn report_lists_registry_with_current_values() {
+        let report = build_report(
+            lookup_from(&[("RCH_WORKER", "css")]),
+            Vec::new(),
+            &[],
+            false,
+        );
+        assert_eq!(report.variables.len(), env_vars().len());
+        let worker = report
+            .variables
+            .iter()
+            .find(|e| e.name == "RCH_WORKER")
+            .expect("RCH_WORKER listed");
+        assert_eq!(worker.value.as_deref(), Some("css"));
+        let unset = report
+            .variables
+            .iter()
+            .find(|e| e.name == "RCH_LOG_LEVEL")
+            .expect("RCH_LOG_LEVEL listed");
+        assert_eq!(unset.value, None);
+    }
+
+    #[test]
+    fn report_redacts_registry_and_webhook_secrets() {
+        let secret = env_vars()
+            .iter()
+            .find(|spec| spec.secret)
+            .expect("registry has a secret");
+        let webhooks = vec![(
+            "PD_ROUTING_KEY".to_string(),
+            "routing_key_env of webhook 'pager'".to_string(),
+        )];
+        let report = build_report(
+            lookup_from(&[(secret.name, "hunter2"), ("PD_ROUTING_KEY", "abc123")]),
+            Vec::new(),
+            &webhooks,
+            true,
+        );
+        assert_eq!(report.variables.len(), 2);
+        for entry in &report.variables {
+            assert!(entry.secret);
+            assert_eq!(entry.value.as_deref(), Some(REDACTED_ENV_VALUE));
+        }
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(!json.contains("hunter2"));
+        assert!(!json.contains("abc123"));
+    }
+
+    #[test]
+    fn report_flags_unrecognized_rch_vars_only() {
+        let report = build_report(
+            lookup_from(&[]),
+            vec![
+                "RCH_WORKRE".to_string(),
+                "RCH_WORKER".to_string(),
+                "HOME".to_string(),
+                "RCH_DOCTOR_INJECT".to_string(),
+                "RCH_WORKRE".to_string(),
+            ],
+            &[],
+            true,
+        );
+        assert!(report.variables.is_empty());
+        assert_eq!(report.unrecognized, vec!["RCH_WORKRE".to_string()]);
+    }
+
+    #[test]
+    fn webhook_secret_names_skip_unset_fields() {
+        let mut config = RchConfig::default();
+        config
+            .doctor
+            .webhooks
+            .endpoints
+            .push(toml::from_str("name = \"ops\"\nurl = \"https://example.invalid\"\nbearer_token_env = \"OPS_TOKEN\"").unwrap());
+        let names = webhook_secret_names(&config);
+        assert_eq!(names.len(), 1);
+        assert_eq!(names[0].0, "OPS_TOKEN");
+        assert!(names[0].1.contains("'ops'"));
+    }
+}
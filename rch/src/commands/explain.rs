@@ -0,0 +1,139 @@
+//! `rch explain` command implementation.
+
+use anyhow::{Context, Result};
+use rch_common::ApiResponse;
+
+use crate::status_types::{BuildRecordFromApi, extract_json_body};
+use crate::ui::context::OutputContext;
+
+use super::send_daemon_command;
+
+/// Print the decision trace of a completed build as an ordered narrative.
+///
+/// The trace (classification, worker selection, path normalization,
+/// dependency closure, sync, execution, artifacts) is stored with the
+/// build's history entry, so only builds still in the daemon's history
+/// buffer can be explained.
+pub async fn build_explain(build_id: u64, ctx: &OutputContext) -> Result<()> {
+    let build = fetch_build_record(build_id).await?;
+
+    if ctx.is_json() {
+        let _ = ctx.json(&ApiResponse::ok("explain", &build));
+        return Ok(());
+    }
+
+    ctx.header(&format!("Build {}", build.id));
+    ctx.key_value("Command", &build.command);
+    ctx.key_value("Project", &build.project_id);
+    ctx.key_value(
+        "Worker",
+        build.worker_id.as_deref().unwrap_or(&build.location),
+    );
+    ctx.key_value("Outcome", &outcome_summary(&build));
+    ctx.print("");
+    if build.decision_trace.is_empty() {
+        ctx.print(&format!(
+            "  {}",
+            ctx.style().muted(
+                "No decision trace recorded (local build, or the hook predates rch explain)."
+            )
+        ));
+        return Ok(());
+    }
+    for line in narrative_lines(&build) {
+        ctx.print(&line);
+    }
+    Ok(())
+}
+
+/// Fetch a completed build from the daemon's history (`GET /builds/{id}`).
+pub(super) async fn fetch_build_record(build_id: u64) -> Result<BuildRecordFromApi> {
+    let response = send_daemon_command(&format!("GET /builds/{}\n", build_id)).await?;
+    let json = extract_json_body(&response)
+        .ok_or_else(|| anyhow::anyhow!("Invalid response format from daemon"))?;
+    let value: serde_json::Value =
+        serde_json::from_str(json).context("Failed to parse daemon build response")?;
+    if let Some(error) = value.get("error").and_then(|error| error.as_str()) {
+        anyhow::bail!("{}", error);
+    }
+    serde_json::from_value(value).context("Failed to parse daemon build response")
+}
+
+fn outcome_summary(build: &BuildRecordFromApi) -> String {
+    let status = match build.exit_code {
+        0 => "succeeded".to_string(),
+        code => format!("failed (exit {})", code),
+    };
+    format!(
+        "{} in {:.1}s, completed {}",
+        status,
+        build.duration_ms as f64 / 1000.0,
+        build.completed_at
+    )
+}
+
+/// One numbered line per trace step, stage names aligned.
+fn narrative_lines(build: &BuildRecordFromApi) -> Vec<String> {
+    let width = build
+        .decision_trace
+        .iter()
+        .map(|step| step.stage.as_str().len())
+        .max()
+        .unwrap_or(0);
+    build
+        .decision_trace
+        .iter()
+        .enumerate()
+        .map(|(idx, step)| {
+            format!(
+                "  {:>2}. {:<width$}  {}",
+                idx + 1,
+                step.stage.as_str(),
+                step.detail,
+                width = width
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rch_common::{DecisionStage, DecisionTraceStep, test_guard};
+
+    #[test]
+    fn narrative_numbers_and_aligns_steps() {
+        let _guard = test_guard!();
+        let build = BuildRecordFromApi {
+            id: 42,
+            started_at: "2026-01-01T00:00:00Z".to_string(),
+            completed_at: "2026-01-01T00:00:12Z".to_string(),
+            project_id: "my-proj".to_string(),
+            worker_id: Some("css".to_string()),
+            command: "cargo build".to_string(),
+            exit_code: 101,
+            duration_ms: 12_345,
+            location: "remote".to_string(),
+            bytes_transferred: None,
+            timing: None,
+            cancellation: None,
+            decision_trace: vec![
+                DecisionTraceStep::new(DecisionStage::Classification, "cargo_build"),
+                DecisionTraceStep::new(DecisionStage::Sync, "1/1 roots synced"),
+            ],
+            project_path: None,
+            toolchain: None,
+        };
+        assert_eq!(
+            narrative_lines(&build),
+            [
+                "   1. classification  cargo_build",
+                "   2. sync            1/1 roots synced",
+            ]
+        );
+        assert_eq!(
+            outcome_summary(&build),
+            "failed (exit 101) in 12.3s, completed 2026-01-01T00:00:12Z"
+        );
+    }
+}
@@ -4,7 +4,7 @@
 use crate::error::PlatformError;
 use crate::error::{DaemonError, SshError};
 use anyhow::{Context, Result};
-use rch_common::{RequiredRuntime, WorkerConfig, WorkerId};
+use rch_common::{FLEET_TAG_PREFIX, RequiredRuntime, WorkerConfig, WorkerId};
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader};
@@ -630,18 +630,33 @@ pub fn load_workers_from_config() -> Result<Vec<WorkerConfig>> {
     let contents = std::fs::read_to_string(&config_path)
         .with_context(|| format!("Failed to read {:?}", config_path))?;
 
-    // Parse the TOML - expect [[workers]] array
+    // Parse the TOML - expect [[workers]] and [[fleet.<name>.workers]] arrays
     let parsed: toml::Value =
         toml::from_str(&contents).with_context(|| format!("Failed to parse {:?}", config_path))?;
 
-    let empty_array = vec![];
-    let workers_array = parsed
-        .get("workers")
-        .and_then(|w| w.as_array())
-        .unwrap_or(&empty_array);
+    let workers_of = |table: &toml::Value| -> Vec<toml::Value> {
+        table
+            .get("workers")
+            .and_then(|w| w.as_array())
+            .cloned()
+            .unwrap_or_default()
+    };
+    let mut entries: Vec<(Option<String>, toml::Value)> = workers_of(&parsed)
+        .into_iter()
+        .map(|entry| (None, entry))
+        .collect();
+    if let Some(fleets) = parsed.get("fleet").and_then(|f| f.as_table()) {
+        for (name, fleet) in fleets {
+            entries.extend(
+                workers_of(fleet)
+                    .into_iter()
+                    .map(|entry| (Some(name.clone()), entry)),
+            );
+        }
+    }
 
     let mut workers = Vec::new();
-    for entry in workers_array {
+    for (fleet, entry) in &entries {
         let enabled = entry
             .get("enabled")
             .and_then(|v| v.as_bool())
@@ -668,7 +683,7 @@ pub fn load_workers_from_config() -> Result<Vec<WorkerConfig>> {
             .unwrap_or("~/.ssh/id_rsa");
         let total_slots = toml_u32_field_or(entry, "total_slots", 8);
         let priority = toml_u32_field_or(entry, "priority", 100);
-        let tags: Vec<String> = entry
+        let mut tags: Vec<String> = entry
             .get("tags")
             .and_then(|v| v.as_array())
             .map(|arr| {
@@ -677,6 +692,10 @@ pub fn load_workers_from_config() -> Result<Vec<WorkerConfig>> {
                     .collect()
             })
             .unwrap_or_default();
+        if let Some(fleet) = fleet {
+            tags.retain(|tag| !tag.starts_with(FLEET_TAG_PREFIX));
+            tags.push(format!("{FLEET_TAG_PREFIX}{fleet}"));
+        }
 
         workers.push(WorkerConfig {
             id: WorkerId::new(id),
@@ -686,6 +705,9 @@ pub fn load_workers_from_config() -> Result<Vec<WorkerConfig>> {
             total_slots,
             priority,
             tags,
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         });
     }
 
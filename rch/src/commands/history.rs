@@ -0,0 +1,246 @@
+//! Build history command implementation.
+
+use anyhow::{Context, Result};
+use rch_common::{ApiResponse, BuildHistoryGroup};
+
+use crate::cache_gc::parse_human_duration;
+use crate::status_types::{
+    BuildHistoryResponseFromApi, BuildRecordFromApi, extract_json_body, format_bytes,
+};
+use crate::ui::context::OutputContext;
+
+use super::helpers::urlencoding_encode;
+use super::send_daemon_command;
+
+/// Show recently completed builds from the daemon's build history.
+///
+/// Reads the daemon's live history buffer, so it works whether or not the
+/// daemon persists history to disk. With `group_by`, the daemon aggregates the
+/// matching builds instead and a summary table is shown.
+pub async fn build_history(
+    project: Option<String>,
+    last: usize,
+    failed_only: bool,
+    since: Option<String>,
+    group_by: Option<String>,
+    ctx: &OutputContext,
+) -> Result<()> {
+    let since_secs = since
+        .as_deref()
+        .map(|value| {
+            parse_human_duration(value)
+                .map(|window| window.as_secs())
+                .map_err(|e| anyhow::anyhow!("invalid --since value: {e}"))
+        })
+        .transpose()?;
+    let command = history_request(
+        project.as_deref(),
+        last,
+        failed_only,
+        since_secs,
+        group_by.as_deref(),
+    );
+    let response = send_daemon_command(&command).await?;
+    let json = extract_json_body(&response)
+        .ok_or_else(|| anyhow::anyhow!("Invalid response format from daemon"))?;
+    let history: BuildHistoryResponseFromApi =
+        serde_json::from_str(json).context("Failed to parse daemon history response")?;
+
+    if ctx.is_json() {
+        let _ = ctx.json(&ApiResponse::ok("history", &history));
+        return Ok(());
+    }
+
+    let style = ctx.style();
+    if let Some(groups) = &history.groups {
+        println!("{}", style.format_header("Build History Summary"));
+        if groups.is_empty() {
+            println!("  {}", style.muted("No matching builds recorded."));
+            return Ok(());
+        }
+        let key_header = match group_by.as_deref() {
+            Some("project") => "Project",
+            Some("kind") => "Kind",
+            _ => "Worker",
+        };
+        let rows: Vec<Vec<String>> = groups.iter().map(group_row).collect();
+        ctx.table(
+            &[key_header, "Builds", "Failed", "Median", "Transferred"],
+            &rows,
+        );
+        return Ok(());
+    }
+
+    println!("{}", style.format_header("Build History"));
+    if history.builds.is_empty() {
+        let message = if failed_only {
+            "No failed builds recorded."
+        } else {
+            "No builds recorded."
+        };
+        println!("  {}", style.muted(message));
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = history.builds.iter().map(history_row).collect();
+    ctx.table(
+        &[
+            "ID",
+            "Completed",
+            "Project",
+            "Worker",
+            "Duration",
+            "Exit",
+            "Cache",
+        ],
+        &rows,
+    );
+    println!(
+        "  {}",
+        style.muted(&format!(
+            "Showing {} of {} recorded builds",
+            history.builds.len(),
+            history.total_recorded
+        ))
+    );
+
+    Ok(())
+}
+
+/// Build the daemon request line for `GET /history`.
+fn history_request(
+    project: Option<&str>,
+    last: usize,
+    failed_only: bool,
+    since_secs: Option<u64>,
+    group_by: Option<&str>,
+) -> String {
+    let mut command = format!("GET /history?limit={}", last);
+    if let Some(project) = project {
+        command.push_str(&format!("&project={}", urlencoding_encode(project)));
+    }
+    if failed_only {
+        command.push_str("&failed_only=1");
+    }
+    if let Some(since_secs) = since_secs {
+        command.push_str(&format!("&since_secs={}", since_secs));
+    }
+    if let Some(group_by) = group_by {
+        command.push_str(&format!("&group_by={}", group_by));
+    }
+    command.push('\n');
+    command
+}
+
+fn group_row(group: &BuildHistoryGroup) -> Vec<String> {
+    vec![
+        group.key.clone(),
+        group.builds.to_string(),
+        group.failures.to_string(),
+        format!("{:.1}s", group.median_duration_ms as f64 / 1000.0),
+        format_bytes(group.total_bytes),
+    ]
+}
+
+fn history_row(build: &BuildRecordFromApi) -> Vec<String> {
+    let cache = match build.cache_hit() {
+        Some(true) => "hit",
+        Some(false) => "miss",
+        None => "-",
+    };
+    vec![
+        build.id.to_string(),
+        build.completed_at.clone(),
+        build.project_id.clone(),
+        build
+            .worker_id
+            .clone()
+            .unwrap_or_else(|| build.location.clone()),
+        format!("{:.1}s", build.duration_ms as f64 / 1000.0),
+        build.exit_code.to_string(),
+        cache.to_string(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rch_common::test_guard;
+
+    fn record(location: &str, worker: Option<&str>, bytes: Option<u64>) -> BuildRecordFromApi {
+        BuildRecordFromApi {
+            id: 7,
+            started_at: "2026-01-01T00:00:00Z".to_string(),
+            completed_at: "2026-01-01T00:00:12Z".to_string(),
+            project_id: "my-proj".to_string(),
+            worker_id: worker.map(str::to_string),
+            command: "cargo build".to_string(),
+            exit_code: 101,
+            duration_ms: 12_345,
+            location: location.to_string(),
+            bytes_transferred: bytes,
+            timing: None,
+            cancellation: None,
+            decision_trace: Vec::new(),
+            project_path: None,
+            toolchain: None,
+        }
+    }
+
+    #[test]
+    fn history_request_encodes_filters() {
+        let _guard = test_guard!();
+        assert_eq!(
+            history_request(None, 20, false, None, None),
+            "GET /history?limit=20\n"
+        );
+        assert_eq!(
+            history_request(Some("my proj"), 5, true, None, None),
+            "GET /history?limit=5&project=my%20proj&failed_only=1\n"
+        );
+        assert_eq!(
+            history_request(None, 20, false, Some(86_400), Some("worker")),
+            "GET /history?limit=20&since_secs=86400&group_by=worker\n"
+        );
+    }
+
+    #[test]
+    fn group_row_summarizes_group() {
+        let _guard = test_guard!();
+        let group = BuildHistoryGroup {
+            key: "css".to_string(),
+            builds: 12,
+            failures: 2,
+            median_duration_ms: 41_300,
+            total_bytes: 3 * 1024 * 1024,
+        };
+        assert_eq!(group_row(&group), ["css", "12", "2", "41.3s", "3.0 MB"]);
+    }
+
+    #[test]
+    fn history_row_reports_cache_status() {
+        let _guard = test_guard!();
+        let hit = history_row(&record("remote", Some("css"), Some(0)));
+        assert_eq!(
+            hit,
+            vec![
+                "7",
+                "2026-01-01T00:00:12Z",
+                "my-proj",
+                "css",
+                "12.3s",
+                "101",
+                "hit"
+            ]
+        );
+        assert_eq!(
+            history_row(&record("remote", Some("css"), Some(4096)))[6],
+            "miss"
+        );
+        assert_eq!(history_row(&record("remote", Some("css"), None))[6], "-");
+
+        let local = history_row(&record("local", None, None));
+        assert_eq!(local[3], "local");
+        assert_eq!(local[6], "-");
+    }
+}
@@ -0,0 +1,92 @@
+//! Spilled build log command implementation.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use rch_common::ApiResponse;
+use serde::Serialize;
+
+use crate::hook::{build_logs, latest_build_log};
+use crate::status_types::format_bytes;
+use crate::ui::context::OutputContext;
+
+/// A spilled build log on disk.
+#[derive(Debug, Serialize)]
+struct BuildLogEntry {
+    path: PathBuf,
+    size_bytes: u64,
+}
+
+/// Contents of the most recent spilled build log.
+#[derive(Debug, Serialize)]
+struct BuildLogContent {
+    path: PathBuf,
+    content: String,
+}
+
+/// List spilled build logs, or print the newest one with `last`.
+///
+/// Logs are written when captured remote output exceeds
+/// `output.max_memory_bytes`; they survive only failed builds unless
+/// `output.keep_logs` is set.
+pub fn build_logs_command(last: bool, ctx: &OutputContext) -> Result<()> {
+    if last {
+        return show_last_log(ctx);
+    }
+
+    let entries: Vec<BuildLogEntry> = build_logs().iter().map(|path| log_entry(path)).collect();
+    if ctx.is_json() {
+        let _ = ctx.json(&ApiResponse::ok("logs", &entries));
+        return Ok(());
+    }
+
+    let style = ctx.style();
+    println!("{}", style.format_header("Build Logs"));
+    if entries.is_empty() {
+        println!("  {}", style.muted("No spilled build logs."));
+        return Ok(());
+    }
+    let rows: Vec<Vec<String>> = entries
+        .iter()
+        .map(|entry| {
+            vec![
+                entry.path.display().to_string(),
+                format_bytes(entry.size_bytes),
+            ]
+        })
+        .collect();
+    ctx.table(&["Path", "Size"], &rows);
+    Ok(())
+}
+
+fn show_last_log(ctx: &OutputContext) -> Result<()> {
+    let Some(path) = latest_build_log() else {
+        if ctx.is_json() {
+            let _ = ctx.json(&ApiResponse::ok("logs", None::<BuildLogContent>));
+        } else {
+            println!("  {}", ctx.style().muted("No spilled build logs found."));
+        }
+        return Ok(());
+    };
+
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read build log {}", path.display()))?;
+    if ctx.is_json() {
+        let _ = ctx.json(&ApiResponse::ok(
+            "logs",
+            Some(BuildLogContent { path, content }),
+        ));
+        return Ok(());
+    }
+
+    eprintln!("{}", ctx.style().muted(&format!("==> {}", path.display())));
+    print!("{}", content);
+    Ok(())
+}
+
+fn log_entry(path: &Path) -> BuildLogEntry {
+    BuildLogEntry {
+        path: path.to_path_buf(),
+        size_bytes: std::fs::metadata(path).map(|meta| meta.len()).unwrap_or(0),
+    }
+}
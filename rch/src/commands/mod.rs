@@ -9,21 +9,34 @@
 
 // Sub-modules
 mod agents;
+mod attach;
+mod clean;
 mod config;
 mod config_doctor;
 mod config_init;
 mod daemon;
+mod env_vars;
+mod explain;
 mod helpers;
+mod history;
 mod hook;
 mod init;
+mod logs;
 mod queue;
+mod replay;
+mod selftest;
 mod speedscore;
 mod status;
+mod status_watch;
 mod sync;
+mod tail;
+mod top;
 pub mod types;
+mod uninstall;
 mod workers;
 mod workers_deploy;
 mod workers_init;
+mod workers_ping;
 mod workers_setup;
 
 // Re-export daemon commands for backward compatibility
@@ -35,11 +48,22 @@ pub use daemon::{
 pub use hook::{hook_install, hook_status, hook_test, hook_uninstall};
 
 // Re-export status/diagnostics commands for backward compatibility
+pub use selftest::pipeline_selftest;
 pub use status::{admit, check, diagnose, self_test, status_overview};
+pub use status_watch::status_watch;
+pub use top::top;
 
 // Re-export queue/cancel commands for backward compatibility
 pub use queue::{cancel_build, queue_status};
 
+// Re-export build history command
+pub use attach::build_attach;
+pub use explain::build_explain;
+pub use history::build_history;
+pub use logs::build_logs_command;
+pub use replay::replay_build;
+pub use tail::build_tail;
+
 // Re-export workers commands for backward compatibility
 pub use workers::{
     workers_benchmark_filtered, workers_capabilities, workers_compare, workers_disable,
@@ -52,8 +76,8 @@ pub use agents::{agents_install_hook, agents_list, agents_status, agents_uninsta
 // Re-export config commands for backward compatibility
 pub(crate) use config::{apply_config_set, default_config_path};
 pub use config::{
-    config_diff, config_edit, config_export, config_get, config_lint, config_reset, config_set,
-    config_show, config_validate,
+    config_diff, config_edit, config_export, config_get, config_lint, config_profile, config_reset,
+    config_set, config_show, config_validate,
 };
 pub use config_doctor::{ConfigDoctorResponse, config_doctor};
 pub use config_init::config_init;
@@ -64,8 +88,18 @@ pub use speedscore::speedscore;
 // Re-export force-resync sync command (bd-apg5l)
 pub use sync::sync_force;
 
+// Re-export remote project cache purge
+pub use clean::clean_remote;
+
+// Re-export full uninstall
+pub use uninstall::uninstall;
+
+// Re-export environment variable listing
+pub use env_vars::show_env;
+
 // Re-export workers init/discover commands for backward compatibility
 pub use workers_init::{workers_discover, workers_init};
+pub use workers_ping::workers_ping;
 
 // Re-export workers deploy command for backward compatibility
 pub use workers_deploy::workers_deploy_binary;
@@ -168,8 +202,8 @@ mod tests {
         build_diagnose_decision, build_diagnose_slot_estimate, build_dry_run_summary,
     };
     use super::workers::{
-        collect_local_capability_warnings, collect_refresh_warnings, has_any_capabilities,
-        summarize_capabilities,
+        collect_local_capability_warnings, collect_missing_tool_warnings, collect_refresh_warnings,
+        has_any_capabilities, summarize_capabilities,
     };
     use super::*;
     use crate::status_types::{
@@ -439,6 +473,9 @@ mod tests {
             total_slots: 16,
             priority: 50,
             tags: vec!["fast".to_string(), "ssd".to_string()],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         let info = WorkerInfo::from(&config);
         assert_eq!(info.id, "test-worker");
@@ -460,6 +497,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         let info = WorkerInfo::from(&config);
         assert!(info.tags.is_empty());
@@ -476,6 +516,9 @@ mod tests {
             total_slots: 4,
             priority: 75,
             tags: vec!["gpu".to_string()],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         let info = WorkerInfo::from(&config);
         let json = serde_json::to_value(&info).unwrap();
@@ -621,8 +664,11 @@ mod tests {
                 enabled: true,
                 force_local: false,
                 force_remote: false,
+                confirm_first_offload: false,
+                shadow_mode: false,
                 log_level: "info".to_string(),
                 socket_path: "/tmp/rch.sock".to_string(),
+                profile: None,
             },
             compilation: ConfigCompilationSection {
                 confidence_threshold: 0.85,
@@ -635,20 +681,47 @@ mod tests {
                 test_timeout_sec: 1800,
                 bun_timeout_sec: 600,
                 external_timeout_enabled: true,
+                timeouts: Default::default(),
+                require_rustc_match: None,
+                min_project_files: None,
+                min_project_bytes: None,
+                min_project_size_exempt_kinds: Vec::new(),
+                confidence_overrides: Default::default(),
             },
             transfer: ConfigTransferSection {
                 compression_level: 3,
                 exclude_patterns: vec!["target/".to_string()],
                 remote_base: "/tmp/rch".to_string(),
                 max_transfer_mb: None,
+                max_project_bytes: None,
                 max_transfer_time_ms: None,
                 bwlimit_kbps: None,
+                max_concurrent_client_transfers: None,
                 estimated_bandwidth_bps: None,
+                max_sync_seconds: None,
+                fleet: None,
+                min_free_disk_gb: 5.0,
+                container_image: None,
                 adaptive_compression: false,
                 min_compression_level: 1,
                 max_compression_level: 19,
                 verify_artifacts: false,
                 verify_max_size_bytes: 100 * 1024 * 1024,
+                verify_sample_files: 32,
+                artifact_deny_patterns: vec![],
+                preserve_newer_local: false,
+                artifact_archive: false,
+                artifact_max_retries: 2,
+                respect_gitignore: false,
+                gitignore_include: vec![],
+                force_include: vec![],
+                shared_target_dir: false,
+                keep_remote_on_failure: false,
+                keep_remote_hours: 24,
+                detach_remote_builds: false,
+                use_sccache: false,
+                skip_artifacts_for: Vec::new(),
+                backend: rch_common::TransferBackend::Rsync,
             },
             environment: ConfigEnvironmentSection {
                 allowlist: vec!["RUSTFLAGS".to_string()],
@@ -670,6 +743,7 @@ mod tests {
                 daemon_installs_hooks: true,
                 auto_start_cooldown_secs: 30,
                 auto_start_timeout_secs: 3,
+                state_dir: None,
             },
             sources: vec!["~/.config/rch/config.toml".to_string()],
             value_sources: None,
@@ -944,6 +1018,26 @@ mod tests {
         assert!(warnings[0].contains("probe timed out"));
     }
 
+    #[test]
+    fn missing_tool_warnings_name_tool_and_install_command() {
+        let _guard = test_guard!();
+        let workers = vec![WorkerCapabilitiesFromApi {
+            id: "bare-worker".to_string(),
+            host: "host".to_string(),
+            user: "user".to_string(),
+            capabilities: WorkerCapabilities {
+                missing_tools: vec!["rsync".to_string()],
+                ..Default::default()
+            },
+            refresh: None,
+        }];
+
+        let warnings = collect_missing_tool_warnings(&workers);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("bare-worker"));
+        assert!(warnings[0].contains("apt-get install -y rsync"));
+    }
+
     #[test]
     fn hook_action_response_success_serializes() {
         let _guard = test_guard!();
@@ -1138,6 +1232,13 @@ mod tests {
             identity_file: "~/.ssh/id_rsa".to_string(),
             slots_available: 4,
             speed_score: 1.5,
+            upload_bandwidth_bps: None,
+            rtt_ms: None,
+            rustc_version: None,
+            sccache_version: None,
+            missing_tools: vec![],
+            nice_level: None,
+            ionice_class: None,
         };
         let worker_selection = DiagnoseWorkerSelection {
             estimated_cores: 4,
@@ -330,7 +330,7 @@ fn render_worker_availability_verbose_line(worker: &WorkerStatusFromApi, style:
     )
 }
 
-fn format_project_id_for_queue(project_id: &str) -> String {
+pub(super) fn format_project_id_for_queue(project_id: &str) -> String {
     if project_id.chars().count() <= PROJECT_DISPLAY_MAX_CHARS {
         return project_id.to_string();
     }
@@ -729,7 +729,7 @@ async fn cancel_build_dry_run(build_id: Option<u64>, all: bool, ctx: &OutputCont
 }
 
 /// Format build duration in human-readable form.
-fn format_build_duration(secs: u64) -> String {
+pub(super) fn format_build_duration(secs: u64) -> String {
     if secs < 60 {
         format!("{}s", secs)
     } else if secs < 3600 {
@@ -0,0 +1,257 @@
+//! `rch replay` command implementation.
+
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result};
+use rch_common::ApiResponse;
+use serde::Serialize;
+
+use crate::status_types::BuildRecordFromApi;
+use crate::ui::context::OutputContext;
+
+use super::explain::fetch_build_record;
+
+/// Result of re-running a recorded build.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+struct ReplayReport {
+    build_id: u64,
+    worker_id: String,
+    project_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    toolchain: Option<String>,
+    /// Command as re-executed (toolchain pinned with `+<toolchain>`).
+    command: String,
+    original_exit_code: i32,
+    replay_exit_code: i32,
+    /// Whether the replay ended with the same exit code as the original.
+    matches: bool,
+}
+
+/// What `rch replay` will run, reconstructed from a history entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ReplayPlan {
+    worker_id: String,
+    project_path: String,
+    toolchain: Option<String>,
+    command: String,
+}
+
+/// Reconstruct the replay from a history entry. Only remote builds recorded
+/// with their project path can be replayed.
+fn replay_plan(build: &BuildRecordFromApi) -> Result<ReplayPlan> {
+    let worker_id = match (build.location.as_str(), build.worker_id.as_deref()) {
+        ("remote", Some(worker)) if !worker.is_empty() => worker.to_string(),
+        _ => anyhow::bail!(
+            "Build {} ran locally; only remote builds can be replayed",
+            build.id
+        ),
+    };
+    let project_path = build.project_path.clone().ok_or_else(|| {
+        anyhow::anyhow!(
+            "Build {} has no recorded project path (the hook that ran it predates rch replay)",
+            build.id
+        )
+    })?;
+    let toolchain = build.toolchain.clone().filter(|t| !t.is_empty());
+    let command = match toolchain.as_deref() {
+        Some(toolchain) => pin_toolchain(&build.command, toolchain),
+        None => build.command.clone(),
+    };
+    Ok(ReplayPlan {
+        worker_id,
+        project_path,
+        toolchain,
+        command,
+    })
+}
+
+/// Insert `+<toolchain>` after the first `cargo` word unless the command
+/// already selects a toolchain. Commands that do not invoke cargo are
+/// returned unchanged; the toolchain file in the project still applies.
+fn pin_toolchain(command: &str, toolchain: &str) -> String {
+    // Edit the string in place rather than re-joining shell words, so
+    // quoting and leading `VAR=value` assignments survive untouched.
+    let cargo_end = command.match_indices("cargo").find_map(|(idx, word)| {
+        let end = idx + word.len();
+        let starts_word = command[..idx]
+            .chars()
+            .next_back()
+            .is_none_or(char::is_whitespace);
+        let ends_word = command[end..]
+            .chars()
+            .next()
+            .is_none_or(char::is_whitespace);
+        (starts_word && ends_word).then_some(end)
+    });
+    let Some(cargo_end) = cargo_end else {
+        return command.to_string();
+    };
+    if command[cargo_end..].trim_start().starts_with('+') {
+        return command.to_string();
+    }
+    format!(
+        "{} +{}{}",
+        &command[..cargo_end],
+        toolchain,
+        &command[cargo_end..]
+    )
+}
+
+/// Re-execute a completed build on the worker that ran it and report
+/// whether the outcome (exit code) matches.
+///
+/// The build runs through `rch exec --workers <worker>` from the recorded
+/// project directory with `RCH_REQUIRE_REMOTE=1`, so it never silently falls
+/// back to a local build.
+pub async fn replay_build(build_id: u64, ctx: &OutputContext) -> Result<()> {
+    let build = fetch_build_record(build_id).await?;
+    let plan = replay_plan(&build)?;
+    if !Path::new(&plan.project_path).is_dir() {
+        anyhow::bail!(
+            "Project directory {} of build {} no longer exists",
+            plan.project_path,
+            build_id
+        );
+    }
+
+    if !ctx.is_json() {
+        ctx.header(&format!("Replaying build {}", build_id));
+        ctx.key_value("Command", &plan.command);
+        ctx.key_value("Project", &plan.project_path);
+        ctx.key_value("Worker", &plan.worker_id);
+        if let Some(toolchain) = &plan.toolchain {
+            ctx.key_value("Toolchain", toolchain);
+        }
+        ctx.print("");
+    }
+
+    let exe = std::env::current_exe().context("Failed to locate the rch executable")?;
+    let mut cmd = Command::new(exe);
+    cmd.arg("exec")
+        .arg("--workers")
+        .arg(&plan.worker_id)
+        .arg("--")
+        .arg(&plan.command)
+        .current_dir(&plan.project_path)
+        .env("RCH_REQUIRE_REMOTE", "1");
+    // Keep the build's own output off stdout in JSON mode.
+    if ctx.is_json() {
+        cmd.stdout(Stdio::null()).stderr(Stdio::null());
+    }
+    let status = tokio::task::spawn_blocking(move || cmd.status())
+        .await
+        .context("Replay task failed")?
+        .context("Failed to run rch exec")?;
+    let replay_exit_code = status.code().unwrap_or(-1);
+
+    let report = ReplayReport {
+        build_id,
+        worker_id: plan.worker_id,
+        project_path: plan.project_path,
+        toolchain: plan.toolchain,
+        command: plan.command,
+        original_exit_code: build.exit_code,
+        replay_exit_code,
+        matches: replay_exit_code == build.exit_code,
+    };
+
+    if ctx.is_json() {
+        let _ = ctx.json(&ApiResponse::ok("replay", &report));
+        return Ok(());
+    }
+
+    ctx.print("");
+    let style = ctx.style();
+    if report.matches {
+        ctx.print(&format!(
+            "  {} outcome matches (exit {})",
+            style.success("✓"),
+            report.replay_exit_code
+        ));
+        Ok(())
+    } else {
+        ctx.print(&format!(
+            "  {} outcome differs: original exit {}, replay exit {}",
+            style.error("✗"),
+            report.original_exit_code,
+            report.replay_exit_code
+        ));
+        anyhow::bail!(
+            "Replay of build {} did not match the original outcome",
+            build_id
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rch_common::test_guard;
+
+    fn record(location: &str, worker: Option<&str>, command: &str) -> BuildRecordFromApi {
+        BuildRecordFromApi {
+            id: 9,
+            started_at: "2026-01-01T00:00:00Z".to_string(),
+            completed_at: "2026-01-01T00:00:12Z".to_string(),
+            project_id: "my-proj".to_string(),
+            worker_id: worker.map(str::to_string),
+            command: command.to_string(),
+            exit_code: 0,
+            duration_ms: 12_345,
+            location: location.to_string(),
+            bytes_transferred: None,
+            timing: None,
+            cancellation: None,
+            decision_trace: Vec::new(),
+            project_path: Some("/data/projects/my-proj".to_string()),
+            toolchain: Some("nightly-2026-01-01".to_string()),
+        }
+    }
+
+    #[test]
+    fn pin_toolchain_inserts_after_cargo_once() {
+        let _guard = test_guard!();
+        assert_eq!(
+            pin_toolchain("cargo build --release", "nightly"),
+            "cargo +nightly build --release"
+        );
+        assert_eq!(
+            pin_toolchain("RUSTFLAGS='-C debuginfo=0' cargo test", "1.80.0"),
+            "RUSTFLAGS='-C debuginfo=0' cargo +1.80.0 test"
+        );
+        assert_eq!(
+            pin_toolchain("cargo +stable build", "nightly"),
+            "cargo +stable build"
+        );
+        assert_eq!(pin_toolchain("cargo", "nightly"), "cargo +nightly");
+        assert_eq!(pin_toolchain("rustc main.rs", "nightly"), "rustc main.rs");
+    }
+
+    #[test]
+    fn replay_plan_pins_worker_project_and_toolchain() {
+        let _guard = test_guard!();
+        let plan = replay_plan(&record("remote", Some("css"), "cargo build")).unwrap();
+        assert_eq!(
+            plan,
+            ReplayPlan {
+                worker_id: "css".to_string(),
+                project_path: "/data/projects/my-proj".to_string(),
+                toolchain: Some("nightly-2026-01-01".to_string()),
+                command: "cargo +nightly-2026-01-01 build".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn replay_plan_rejects_local_and_unrecorded_builds() {
+        let _guard = test_guard!();
+        let local = replay_plan(&record("local", None, "cargo build")).unwrap_err();
+        assert!(local.to_string().contains("ran locally"));
+
+        let mut legacy = record("remote", Some("css"), "cargo build");
+        legacy.project_path = None;
+        let err = replay_plan(&legacy).unwrap_err();
+        assert!(err.to_string().contains("no recorded project path"));
+    }
+}
@@ -0,0 +1,135 @@
+//! `rch selftest` command implementation.
+
+use std::time::Duration;
+
+use anyhow::Result;
+use rch_common::{ApiResponse, CommandTimingBreakdown};
+
+use crate::hook::{PipelineSelftestReport, run_pipeline_selftest};
+use crate::ui::context::OutputContext;
+use crate::ui::theme::StatusIndicator;
+
+/// Offload a throwaway cargo project end to end and report the result.
+///
+/// Exits with status 1 when the remote build fails or its artifact does not
+/// come back; setup and pipeline errors are returned as errors.
+pub async fn pipeline_selftest(ctx: &OutputContext) -> Result<()> {
+    if !ctx.is_json() {
+        eprintln!(
+            "{}",
+            ctx.style()
+                .muted("Offloading a throwaway cargo project through the full pipeline...")
+        );
+    }
+    let report = run_pipeline_selftest().await?;
+
+    if ctx.is_json() {
+        let _ = ctx.json(&ApiResponse::ok("selftest", &report));
+    } else {
+        render_report(&report, ctx);
+    }
+    if !report.passed() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn render_report(report: &PipelineSelftestReport, ctx: &OutputContext) {
+    let style = ctx.style();
+    println!("{}", style.format_header("Pipeline Self-Test"));
+    println!(
+        "  {} {} {} ({})",
+        style.key("Worker"),
+        style.muted(":"),
+        style.highlight(&report.worker_id),
+        report.worker_host
+    );
+    if let Some(build_id) = report.build_id {
+        println!(
+            "  {} {} {}",
+            style.key("Build"),
+            style.muted(":"),
+            style.value(&build_id.to_string())
+        );
+    }
+    println!(
+        "  {} {} {}",
+        style.key("Exit code"),
+        style.muted(":"),
+        if report.exit_code == 0 {
+            style.success("0")
+        } else {
+            style.error(&report.exit_code.to_string())
+        }
+    );
+    println!(
+        "  {} {} {}",
+        style.key("Artifact"),
+        style.muted(":"),
+        if report.artifact_retrieved {
+            style.success("retrieved")
+        } else {
+            style.error("missing")
+        }
+    );
+    println!(
+        "  {} {} {}",
+        style.key("Timing"),
+        style.muted(":"),
+        format_timing(&report.timing)
+    );
+
+    let (indicator, verdict) = if report.passed() {
+        (StatusIndicator::Success, style.success("PASS"))
+    } else {
+        (StatusIndicator::Error, style.error("FAIL"))
+    };
+    println!("\n{} {}", indicator.display(style), verdict);
+}
+
+/// One-line phase breakdown, skipping phases that did not run.
+fn format_timing(timing: &CommandTimingBreakdown) -> String {
+    [
+        ("select", timing.select),
+        ("sync up", timing.sync_up),
+        ("exec", timing.exec),
+        ("sync down", timing.sync_down),
+        ("total", timing.total),
+    ]
+    .into_iter()
+    .filter_map(|(phase, duration)| duration.map(|d| format!("{} {}", phase, format_phase(d))))
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+fn format_phase(duration: Duration) -> String {
+    let ms = duration.as_millis();
+    if ms < 1000 {
+        format!("{}ms", ms)
+    } else {
+        format!("{:.1}s", duration.as_secs_f64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rch_common::test_guard;
+
+    #[test]
+    fn format_timing_lists_recorded_phases() {
+        let _guard = test_guard!();
+        let timing = CommandTimingBreakdown {
+            select: Some(Duration::from_millis(12)),
+            sync_up: Some(Duration::from_millis(1_250)),
+            exec: Some(Duration::from_millis(8_400)),
+            total: Some(Duration::from_millis(10_000)),
+            ..Default::default()
+        };
+        assert_eq!(
+            format_timing(&timing),
+            "select 12ms, sync up 1.2s, exec 8.4s, total 10.0s"
+        );
+        assert_eq!(format_timing(&CommandTimingBreakdown::default()), "");
+    }
+}
@@ -337,9 +337,21 @@ fn build_diagnose_placement(worker_selection: &Option<DiagnoseWorkerSelection>)
     plan
 }
 
-pub async fn diagnose(command: &str, dry_run: bool, ctx: &OutputContext) -> Result<()> {
-    use rch_common::classify_command_detailed;
+/// Classification details for `rch diagnose`, with the user's classifier rules
+/// layered over the built-ins exactly as the hook applies them. The tiers still
+/// describe the built-in classifier.
+fn diagnose_classification(
+    command: &str,
+    rules: Option<&rch_common::classifier_rules::ClassifierRules>,
+) -> rch_common::ClassificationDetails {
+    let mut details = rch_common::classify_command_detailed(command);
+    if let Some(rules) = rules {
+        details.classification = rules.classify(command);
+    }
+    details
+}
 
+pub async fn diagnose(command: &str, dry_run: bool, ctx: &OutputContext) -> Result<()> {
     let style = ctx.theme();
     let loaded = crate::config::load_config_with_sources()?;
     let config = loaded.config;
@@ -348,7 +360,7 @@ pub async fn diagnose(command: &str, dry_run: bool, ctx: &OutputContext) -> Resu
     // the compiled-in `/data/projects` + `/dp` defaults. See GitHub #9.
     let topology_policy = config.path_topology.to_policy();
 
-    let details = classify_command_detailed(command);
+    let details = diagnose_classification(command, crate::config::classifier_rules());
     let threshold = config.compilation.confidence_threshold;
 
     let value_sources = collect_value_sources(&config, &loaded.sources);
@@ -458,6 +470,10 @@ pub async fn diagnose(command: &str, dry_run: bool, ctx: &OutputContext) -> Resu
             None,
             false,
             &preferred_workers,
+            config.transfer.fleet.as_deref(),
+            config.transfer.min_free_disk_requirement(),
+            &[],
+            None,
         )
         .await
         {
@@ -472,6 +488,9 @@ pub async fn diagnose(command: &str, dry_run: bool, ctx: &OutputContext) -> Resu
                         None,
                         None,
                         None, // timing
+                        &[],
+                        None,
+                        None,
                     )
                     .await
                 {
@@ -3396,6 +3415,25 @@ mod tests {
         assert!(output.contains("Known Issues"));
     }
 
+    #[test]
+    fn test_diagnose_classification_applies_user_rules() {
+        let rules = rch_common::classifier_rules::ClassifierRules::from_toml(
+            "[[rule]]\nprefix = \"xbuild\"\nkind = \"cargo_build\"\n",
+        )
+        .unwrap();
+
+        let builtin = diagnose_classification("xbuild --release", None);
+        assert!(!builtin.classification.is_compilation);
+
+        let details = diagnose_classification("xbuild --release", Some(&rules));
+        assert_eq!(details.classification, rules.classify("xbuild --release"));
+        assert!(details.classification.is_compilation);
+        assert_eq!(
+            details.classification.kind,
+            Some(rch_common::CompilationKind::CargoBuild)
+        );
+    }
+
     #[test]
     fn test_check_outcome_ready_requires_hook() {
         let unhealthy = Vec::new();
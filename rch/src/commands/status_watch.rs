@@ -0,0 +1,282 @@
+//! `rch status --watch`: a polling, TUI-less status dashboard.
+//!
+//! Polls the daemon's `GET /status` every `--interval` seconds. On a TTY the
+//! compact dashboard (workers, slots, active builds) is redrawn in place by
+//! moving the cursor back over the previous frame; when stdout is not a TTY
+//! (piped into a file or `tee`) each poll appends one plain summary line
+//! instead. JSON mode emits one compact status object per poll.
+
+use anyhow::{Context, Result};
+use rch_common::ApiResponse;
+use std::io::Write;
+
+use crate::status_types::{DaemonFullStatusResponse, extract_json_body};
+use crate::ui::context::OutputContext;
+use crate::ui::theme::Theme;
+
+use super::queue::{format_build_duration, format_project_id_for_queue};
+use super::send_daemon_command;
+
+/// Poll the daemon and redraw a compact status dashboard until interrupted.
+///
+/// The first poll must succeed; later daemon errors are shown in place of the
+/// dashboard and polling continues, so a daemon restart does not end the watch.
+pub async fn status_watch(interval_secs: u64, ctx: &OutputContext) -> Result<()> {
+    let interval = std::time::Duration::from_secs(interval_secs.max(1));
+    let redraw = ctx.is_tty() && !ctx.is_json();
+    let mut previous_lines = 0usize;
+    let mut first = true;
+
+    loop {
+        let polled = fetch_status().await;
+        let status = match polled {
+            Ok(status) => Some(status),
+            Err(e) if first => return Err(e),
+            Err(e) => {
+                if redraw {
+                    previous_lines = redraw_frame(
+                        previous_lines,
+                        &[format!(
+                            "{} {}",
+                            ctx.style().error("daemon unavailable:"),
+                            e
+                        )],
+                    );
+                } else if !ctx.is_json() {
+                    println!("{} daemon unavailable: {}", timestamp(), e);
+                }
+                None
+            }
+        };
+        first = false;
+
+        if let Some(status) = status {
+            if ctx.is_json() {
+                let _ = ctx.json_compact(&ApiResponse::ok("status-watch", &status));
+            } else if redraw {
+                let lines = render_watch_dashboard(&status, interval_secs, ctx.style());
+                previous_lines = redraw_frame(previous_lines, &lines);
+            } else {
+                println!("{}", render_watch_line(&status));
+            }
+        }
+
+        tokio::time::sleep(interval).await;
+    }
+}
+
+async fn fetch_status() -> Result<DaemonFullStatusResponse> {
+    let response = send_daemon_command("GET /status\n").await?;
+    let json = extract_json_body(&response)
+        .ok_or_else(|| anyhow::anyhow!("Invalid response format from daemon"))?;
+    serde_json::from_str(json).context("Failed to parse daemon status response")
+}
+
+/// Replace the previous frame (of `previous_lines` lines) with `lines` and
+/// return the new frame height.
+pub(super) fn redraw_frame(previous_lines: usize, lines: &[String]) -> usize {
+    let mut stdout = std::io::stdout().lock();
+    if previous_lines > 0 {
+        // Cursor up to the first line of the previous frame, clear to the end.
+        let _ = write!(stdout, "\x1B[{}F\x1B[J", previous_lines);
+    }
+    for line in lines {
+        let _ = writeln!(stdout, "\r{}", line);
+    }
+    let _ = stdout.flush();
+    lines.len()
+}
+
+pub(super) fn timestamp() -> String {
+    chrono::Local::now().format("%H:%M:%S").to_string()
+}
+
+fn slots_used(status: &DaemonFullStatusResponse) -> u32 {
+    status
+        .daemon
+        .slots_total
+        .saturating_sub(status.daemon.slots_available)
+}
+
+/// Multi-line dashboard for in-place redraw on a TTY.
+fn render_watch_dashboard(
+    status: &DaemonFullStatusResponse,
+    interval_secs: u64,
+    style: &Theme,
+) -> Vec<String> {
+    let mut lines = vec![
+        format!(
+            "{} {}",
+            style.highlight("RCH Status"),
+            style.muted(&format!(
+                "({}, every {}s, Ctrl-C to exit)",
+                timestamp(),
+                interval_secs
+            ))
+        ),
+        format!(
+            "  {} {}/{} healthy   {} {}/{} used   {} {} active, {} queued",
+            style.key("Workers"),
+            status.daemon.workers_healthy,
+            status.daemon.workers_total,
+            style.key("Slots"),
+            slots_used(status),
+            status.daemon.slots_total,
+            style.key("Builds"),
+            status.active_builds.len(),
+            status.queued_builds.len()
+        ),
+    ];
+
+    let id_width = status
+        .workers
+        .iter()
+        .map(|worker| worker.id.chars().count())
+        .max()
+        .unwrap_or(0);
+    for worker in &status.workers {
+        // Pad before coloring so ANSI codes do not skew the column width.
+        let padded = format!("{:<11}", worker.status);
+        let state = match worker.status.as_str() {
+            "healthy" => style.success(&padded),
+            "degraded" | "draining" | "drained" => style.warning(&padded),
+            _ => style.error(&padded),
+        };
+        lines.push(format!(
+            "  {:<id_width$}  {} {}/{} slots",
+            worker.id, state, worker.used_slots, worker.total_slots,
+        ));
+    }
+
+    for build in &status.active_builds {
+        let elapsed = chrono::DateTime::parse_from_rfc3339(&build.started_at)
+            .map(|started| {
+                let secs = chrono::Utc::now()
+                    .signed_duration_since(started)
+                    .num_seconds();
+                format_build_duration(secs.max(0) as u64)
+            })
+            .unwrap_or_else(|_| "?".to_string());
+        let command = if build.command.chars().count() > 40 {
+            format!(
+                "{}...",
+                rch_common::util::truncate_at_char_boundary(&build.command, 37)
+            )
+        } else {
+            build.command.clone()
+        };
+        lines.push(format!(
+            "  {} {} {} {} {}",
+            style.info(&format!("#{}", build.id)),
+            style.key(&build.worker_id),
+            style.muted(&format_project_id_for_queue(&build.project_id)),
+            style.value(&command),
+            style.warning(&format!("[{}]", elapsed))
+        ));
+    }
+
+    lines
+}
+
+/// Single plain line per poll for non-TTY output.
+fn render_watch_line(status: &DaemonFullStatusResponse) -> String {
+    let workers = status
+        .workers
+        .iter()
+        .map(|worker| {
+            format!(
+                "{}:{}:{}/{}",
+                worker.id, worker.status, worker.used_slots, worker.total_slots
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!(
+        "{} workers={}/{} slots={}/{} active={} queued={} [{}]",
+        timestamp(),
+        status.daemon.workers_healthy,
+        status.daemon.workers_total,
+        slots_used(status),
+        status.daemon.slots_total,
+        status.active_builds.len(),
+        status.queued_builds.len(),
+        workers
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rch_common::test_guard;
+
+    fn sample_status() -> DaemonFullStatusResponse {
+        serde_json::from_value(serde_json::json!({
+            "daemon": {
+                "pid": 1234,
+                "uptime_secs": 60,
+                "version": "0.1.0",
+                "socket_path": "/tmp/rch.sock",
+                "started_at": "2026-01-16T12:00:00Z",
+                "workers_total": 2,
+                "workers_healthy": 1,
+                "slots_total": 16,
+                "slots_available": 10
+            },
+            "workers": [
+                {
+                    "id": "css", "host": "10.0.0.1", "user": "ubuntu",
+                    "status": "healthy", "circuit_state": "closed",
+                    "used_slots": 6, "total_slots": 8, "speed_score": 90.0,
+                    "last_error": null
+                },
+                {
+                    "id": "fmd", "host": "10.0.0.2", "user": "ubuntu",
+                    "status": "unreachable", "circuit_state": "open",
+                    "used_slots": 0, "total_slots": 8, "speed_score": 0.0,
+                    "last_error": "ssh timeout"
+                }
+            ],
+            "active_builds": [{
+                "id": 42,
+                "project_id": "my-proj",
+                "worker_id": "css",
+                "command": "cargo build --release",
+                "started_at": "2026-01-16T12:00:00Z"
+            }],
+            "recent_builds": [],
+            "issues": [],
+            "stats": {
+                "total_builds": 0, "success_count": 0, "failure_count": 0,
+                "remote_count": 0, "local_count": 0, "avg_duration_ms": 0
+            }
+        }))
+        .expect("sample status")
+    }
+
+    #[test]
+    fn watch_dashboard_shows_workers_slots_and_active_builds() {
+        let _guard = test_guard!();
+        let style = Theme::new(false, true, false);
+        let lines = render_watch_dashboard(&sample_status(), 2, &style);
+
+        assert!(lines[0].contains("every 2s"));
+        assert!(lines[1].contains("Workers 1/2 healthy"));
+        assert!(lines[1].contains("Slots 6/16 used"));
+        assert!(lines[1].contains("Builds 1 active, 0 queued"));
+        assert!(lines[2].contains("css") && lines[2].contains("6/8 slots"));
+        assert!(lines[3].contains("fmd") && lines[3].contains("unreachable"));
+        assert!(lines[4].contains("#42") && lines[4].contains("cargo build --release"));
+        assert_eq!(lines.len(), 5);
+    }
+
+    #[test]
+    fn watch_line_is_single_plain_summary() {
+        let _guard = test_guard!();
+        let line = render_watch_line(&sample_status());
+        assert!(!line.contains('\n'));
+        assert!(!line.contains('\x1B'));
+        assert!(line.ends_with(
+            "workers=1/2 slots=6/16 active=1 queued=0 [css:healthy:6/8 fmd:unreachable:0/8]"
+        ));
+    }
+}
@@ -0,0 +1,166 @@
+//! `rch tail` command implementation.
+
+use anyhow::{Context, Result};
+use rch_common::BuildTailEvent;
+use std::path::Path;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+use crate::config::load_config;
+use crate::error::DaemonError;
+#[cfg(not(unix))]
+use crate::error::PlatformError;
+use crate::ui::context::OutputContext;
+
+#[cfg(unix)]
+use tokio::net::UnixStream;
+
+/// Follow an in-progress build's phase and output (GET /tail).
+///
+/// Output is forwarded to the daemon with the hook's build heartbeats, so it
+/// lags the build by a few seconds. `lines` buffered lines are replayed
+/// first. Runs until the build finishes or the user interrupts with Ctrl-C.
+#[cfg(not(unix))]
+pub async fn build_tail(_build_id: u64, _lines: usize, _ctx: &OutputContext) -> Result<()> {
+    Err(PlatformError::UnixOnly {
+        feature: "build tail".to_string(),
+    })?
+}
+
+#[cfg(unix)]
+pub async fn build_tail(build_id: u64, lines: usize, ctx: &OutputContext) -> Result<()> {
+    let config = load_config()?;
+    let expanded = shellexpand::tilde(&config.general.socket_path);
+    let socket_path = Path::new(expanded.as_ref());
+    if !socket_path.exists() {
+        return Err(DaemonError::SocketNotFound {
+            socket_path: socket_path.display().to_string(),
+        }
+        .into());
+    }
+
+    let stream = UnixStream::connect(socket_path).await?;
+    let (reader, mut writer) = stream.into_split();
+
+    writer
+        .write_all(tail_request(build_id, lines).as_bytes())
+        .await?;
+    writer.flush().await?;
+
+    let mut reader = BufReader::new(reader);
+
+    // Skip the HTTP header (read until empty line)
+    let mut header_line = String::new();
+    loop {
+        header_line.clear();
+        let n = reader.read_line(&mut header_line).await?;
+        if n == 0 || header_line.trim().is_empty() {
+            break;
+        }
+    }
+
+    let style = ctx.style();
+    let mut line = String::new();
+    loop {
+        line.clear();
+        let n = reader.read_line(&mut line).await?;
+        if n == 0 {
+            break; // connection closed
+        }
+        let trimmed = line.trim_end();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        if ctx.is_json() {
+            println!("{trimmed}");
+            continue;
+        }
+
+        let event: BuildTailEvent =
+            serde_json::from_str(trimmed).context("Failed to parse daemon tail event")?;
+        match event {
+            BuildTailEvent::NotFound => {
+                anyhow::bail!("No active or recent build with ID {build_id}");
+            }
+            BuildTailEvent::Line { line, .. } => println!("{line}"),
+            other => {
+                if let Some(status) = format_tail_status(build_id, &other) {
+                    eprintln!("{}", style.muted(&status));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Build the daemon request line for `GET /tail`.
+fn tail_request(build_id: u64, lines: usize) -> String {
+    format!("GET /tail?build_id={build_id}&lines={lines}\n")
+}
+
+/// Status line for a non-output tail event.
+fn format_tail_status(build_id: u64, event: &BuildTailEvent) -> Option<String> {
+    match event {
+        BuildTailEvent::Phase { phase, detail } => {
+            let phase = serde_json::to_value(phase)
+                .ok()
+                .and_then(|value| value.as_str().map(str::to_string))
+                .unwrap_or_default();
+            Some(match detail {
+                Some(detail) => format!("[rch] build {build_id}: {phase} ({detail})"),
+                None => format!("[rch] build {build_id}: {phase}"),
+            })
+        }
+        BuildTailEvent::Skipped { lines } => Some(format!(
+            "[rch] ... {lines} lines not buffered by the daemon"
+        )),
+        BuildTailEvent::Finished {
+            exit_code: Some(code),
+        } => Some(format!("[rch] build {build_id} finished (exit {code})")),
+        BuildTailEvent::Finished { exit_code: None } => {
+            Some(format!("[rch] build {build_id} finished"))
+        }
+        BuildTailEvent::Line { .. } | BuildTailEvent::NotFound => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rch_common::{BuildHeartbeatPhase, test_guard};
+
+    #[test]
+    fn tail_request_includes_replay_lines() {
+        let _guard = test_guard!();
+        assert_eq!(tail_request(42, 50), "GET /tail?build_id=42&lines=50\n");
+    }
+
+    #[test]
+    fn format_tail_status_describes_events() {
+        let _guard = test_guard!();
+        let phase = BuildTailEvent::Phase {
+            phase: BuildHeartbeatPhase::SyncDown,
+            detail: Some("artifacts".to_string()),
+        };
+        assert_eq!(
+            format_tail_status(7, &phase).as_deref(),
+            Some("[rch] build 7: sync_down (artifacts)")
+        );
+        assert_eq!(
+            format_tail_status(
+                7,
+                &BuildTailEvent::Finished {
+                    exit_code: Some(101)
+                }
+            )
+            .as_deref(),
+            Some("[rch] build 7 finished (exit 101)")
+        );
+        let line = BuildTailEvent::Line {
+            seq: 0,
+            line: "Compiling foo".to_string(),
+        };
+        assert_eq!(format_tail_status(7, &line), None);
+    }
+}
@@ -0,0 +1,289 @@
+//! `rch top`: a live, htop-like table of per-worker resource usage.
+//!
+//! Polls the daemon's `GET /telemetry/latest` every `--interval` seconds and
+//! shows the newest telemetry it holds for each worker: CPU, load average,
+//! memory, and disk free (from the last capabilities probe). Unlike
+//! `rch status --watch`, which tracks slots and builds, this is about what the
+//! machines are doing. On a TTY the table is redrawn in place; piped output
+//! gets a timestamped plain table per poll, and JSON mode one compact object.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rch_common::{ApiResponse, WorkerStatus};
+use rch_telemetry::protocol::ReceivedTelemetry;
+use serde::{Deserialize, Serialize};
+
+use crate::status_types::extract_json_body;
+use crate::ui::context::OutputContext;
+use crate::ui::theme::Theme;
+
+use super::send_daemon_command;
+use super::status_watch::{redraw_frame, timestamp};
+
+/// One worker's row from `GET /telemetry/latest`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WorkerResourcesFromApi {
+    worker_id: String,
+    status: WorkerStatus,
+    #[serde(default)]
+    telemetry: Option<ReceivedTelemetry>,
+    #[serde(default)]
+    disk_free_gb: Option<f64>,
+    #[serde(default)]
+    disk_total_gb: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TelemetryLatestFromApi {
+    workers: Vec<WorkerResourcesFromApi>,
+}
+
+/// Poll the daemon and render per-worker resource usage until interrupted, or
+/// once with `once`.
+///
+/// As with `rch status --watch`, only the first poll must succeed; later
+/// daemon errors replace the table and polling continues.
+pub async fn top(interval_secs: u64, once: bool, ctx: &OutputContext) -> Result<()> {
+    let interval = std::time::Duration::from_secs(interval_secs.max(1));
+    let redraw = ctx.is_tty() && !ctx.is_json() && !once;
+    let mut previous_lines = 0usize;
+    let mut first = true;
+
+    loop {
+        match fetch_latest().await {
+            Ok(latest) => {
+                if ctx.is_json() {
+                    let _ = ctx.json_compact(&ApiResponse::ok("top", &latest));
+                } else if redraw {
+                    let mut lines = vec![format!(
+                        "{} {}",
+                        ctx.style().highlight("RCH Top"),
+                        ctx.style().muted(&format!(
+                            "({}, every {}s, Ctrl-C to exit)",
+                            timestamp(),
+                            interval_secs
+                        ))
+                    )];
+                    lines.extend(render_top_table(&latest.workers, ctx.style()));
+                    previous_lines = redraw_frame(previous_lines, &lines);
+                } else {
+                    let plain = Theme::new(false, true, false);
+                    println!("{}", timestamp());
+                    for line in render_top_table(&latest.workers, &plain) {
+                        println!("{}", line);
+                    }
+                }
+            }
+            Err(e) if first => return Err(e),
+            Err(e) => {
+                if redraw {
+                    previous_lines = redraw_frame(
+                        previous_lines,
+                        &[format!(
+                            "{} {}",
+                            ctx.style().error("daemon unavailable:"),
+                            e
+                        )],
+                    );
+                } else if !ctx.is_json() {
+                    println!("{} daemon unavailable: {}", timestamp(), e);
+                }
+            }
+        }
+        first = false;
+        if once {
+            return Ok(());
+        }
+
+        tokio::time::sleep(interval).await;
+    }
+}
+
+async fn fetch_latest() -> Result<TelemetryLatestFromApi> {
+    let response = send_daemon_command("GET /telemetry/latest\n").await?;
+    let json = extract_json_body(&response)
+        .ok_or_else(|| anyhow::anyhow!("Invalid response format from daemon"))?;
+    serde_json::from_str(json).context("Failed to parse daemon telemetry response")
+}
+
+fn status_label(status: WorkerStatus) -> &'static str {
+    match status {
+        WorkerStatus::Healthy => "healthy",
+        WorkerStatus::Degraded => "degraded",
+        WorkerStatus::Unreachable => "unreachable",
+        WorkerStatus::Draining => "draining",
+        WorkerStatus::Drained => "drained",
+        WorkerStatus::Disabled => "disabled",
+    }
+}
+
+/// Seconds or minutes since `received`, e.g. `4s` or `12m`.
+fn format_age(received: &ReceivedTelemetry) -> String {
+    let secs = Utc::now()
+        .signed_duration_since(received.received_at)
+        .num_seconds()
+        .max(0);
+    if secs < 120 {
+        format!("{}s", secs)
+    } else {
+        format!("{}m", secs / 60)
+    }
+}
+
+/// Header plus one row per worker. Workers without telemetry show `-`.
+fn render_top_table(workers: &[WorkerResourcesFromApi], style: &Theme) -> Vec<String> {
+    let id_width = workers
+        .iter()
+        .map(|worker| worker.worker_id.chars().count())
+        .chain(std::iter::once("WORKER".len()))
+        .max()
+        .unwrap_or(0);
+    let mut lines = vec![
+        style
+            .muted(&format!(
+                "  {:<id_width$}  {:<11}  {:>5}  {:>15}  {:>17}  {:>9}  {:>4}",
+                "WORKER", "STATUS", "CPU%", "LOAD 1/5/15", "MEM USED/TOTAL", "DISK FREE", "AGE"
+            ))
+            .to_string(),
+    ];
+    if workers.is_empty() {
+        lines.push(format!("  {}", style.muted("no workers configured")));
+        return lines;
+    }
+
+    for worker in workers {
+        // Pad before coloring so ANSI codes do not skew the column width.
+        let padded = format!("{:<11}", status_label(worker.status));
+        let state = match worker.status {
+            WorkerStatus::Healthy => style.success(&padded),
+            WorkerStatus::Degraded | WorkerStatus::Draining | WorkerStatus::Drained => {
+                style.warning(&padded)
+            }
+            WorkerStatus::Unreachable | WorkerStatus::Disabled => style.error(&padded),
+        };
+        let disk = worker
+            .disk_free_gb
+            .map(|free| format!("{:.0} GB", free))
+            .unwrap_or_else(|| "-".to_string());
+        let (cpu, load, memory, age) = match &worker.telemetry {
+            Some(received) => {
+                let telemetry = &received.telemetry;
+                let load = &telemetry.cpu.load_average;
+                let memory = &telemetry.memory;
+                let cpu = format!("{:>5.1}", telemetry.cpu.overall_percent);
+                let cpu = if telemetry.cpu.overall_percent >= 90.0 {
+                    style.error(&cpu).to_string()
+                } else if telemetry.cpu.overall_percent >= 70.0 {
+                    style.warning(&cpu).to_string()
+                } else {
+                    cpu
+                };
+                (
+                    cpu,
+                    format!(
+                        "{:.1}/{:.1}/{:.1}",
+                        load.one_min, load.five_min, load.fifteen_min
+                    ),
+                    format!(
+                        "{:.1}/{:.1} GB",
+                        (memory.total_gb - memory.available_gb).max(0.0),
+                        memory.total_gb
+                    ),
+                    format_age(received),
+                )
+            }
+            None => (
+                format!("{:>5}", "-"),
+                "-".to_string(),
+                "-".to_string(),
+                "-".to_string(),
+            ),
+        };
+        lines.push(format!(
+            "  {:<id_width$}  {}  {}  {:>15}  {:>17}  {:>9}  {:>4}",
+            worker.worker_id, state, cpu, load, memory, disk, age
+        ));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rch_common::test_guard;
+
+    fn sample_latest() -> TelemetryLatestFromApi {
+        let now = Utc::now().to_rfc3339();
+        serde_json::from_value(serde_json::json!({
+            "workers": [
+                {
+                    "worker_id": "css",
+                    "status": "healthy",
+                    "disk_free_gb": 412.4,
+                    "disk_total_gb": 900.0,
+                    "telemetry": {
+                        "source": "Piggyback",
+                        "received_at": now,
+                        "telemetry": {
+                            "version": 1,
+                            "worker_id": "css",
+                            "timestamp": now,
+                            "cpu": {
+                                "timestamp": now,
+                                "overall_percent": 42.5,
+                                "per_core_percent": [],
+                                "num_cores": 16,
+                                "load_average": {
+                                    "one_min": 3.2, "five_min": 2.1, "fifteen_min": 1.0,
+                                    "running_processes": 2, "total_processes": 300
+                                }
+                            },
+                            "memory": {
+                                "timestamp": now,
+                                "total_gb": 64.0,
+                                "available_gb": 40.0,
+                                "used_percent": 37.5,
+                                "pressure_score": 0.0,
+                                "swap_used_gb": 0.0,
+                                "dirty_mb": 0.0
+                            },
+                            "collection_duration_ms": 12
+                        }
+                    }
+                },
+                { "worker_id": "fmd", "status": "unreachable" }
+            ]
+        }))
+        .expect("sample telemetry")
+    }
+
+    #[test]
+    fn top_table_shows_cpu_load_memory_and_disk() {
+        let _guard = test_guard!();
+        let style = Theme::new(false, true, false);
+        let lines = render_top_table(&sample_latest().workers, &style);
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].contains("WORKER") && lines[0].contains("DISK FREE"));
+        let css = &lines[1];
+        assert!(css.contains("css") && css.contains("healthy"));
+        assert!(css.contains("42.5"));
+        assert!(css.contains("3.2/2.1/1.0"));
+        assert!(css.contains("24.0/64.0 GB"));
+        assert!(css.contains("412 GB"));
+        assert!(css.contains("0s"));
+        assert!(!css.contains('\x1B'));
+    }
+
+    #[test]
+    fn top_table_marks_workers_without_telemetry() {
+        let _guard = test_guard!();
+        let style = Theme::new(false, true, false);
+        let lines = render_top_table(&sample_latest().workers, &style);
+
+        let fmd = &lines[2];
+        assert!(fmd.contains("fmd") && fmd.contains("unreachable"));
+        assert!(fmd.trim_end().ends_with('-'));
+        assert!(!fmd.contains("GB"));
+    }
+}
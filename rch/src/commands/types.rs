@@ -92,6 +92,33 @@ pub struct WorkersProbeResponse {
     pub summary: WorkerProbeSummary,
 }
 
+/// SSH round-trip latency for one worker (`rch workers ping`).
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkerPingResult {
+    pub id: String,
+    pub host: String,
+    /// Latency stats over successful round trips; absent when none succeeded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub median_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_ms: Option<u64>,
+    /// Round trips that failed.
+    pub failures: u32,
+    /// First failure, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Workers ping response envelope, sorted by median latency.
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkersPingResponse {
+    /// Round trips attempted per worker.
+    pub count: u32,
+    pub results: Vec<WorkerPingResult>,
+}
+
 /// Worker capabilities report for JSON output.
 #[derive(Debug, Clone, Serialize)]
 pub struct WorkersCapabilitiesReport {
@@ -234,8 +261,12 @@ pub struct ConfigGeneralSection {
     pub enabled: bool,
     pub force_local: bool,
     pub force_remote: bool,
+    pub confirm_first_offload: bool,
+    pub shadow_mode: bool,
     pub log_level: String,
     pub socket_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
 }
 
 /// Compilation configuration section.
@@ -251,6 +282,15 @@ pub struct ConfigCompilationSection {
     pub test_timeout_sec: u64,
     pub bun_timeout_sec: u64,
     pub external_timeout_enabled: bool,
+    pub timeouts: std::collections::BTreeMap<String, u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub require_rustc_match: Option<rch_common::RustcMatch>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_project_files: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_project_bytes: Option<u64>,
+    pub min_project_size_exempt_kinds: Vec<String>,
+    pub confidence_overrides: std::collections::BTreeMap<String, rch_common::ConfidenceOverride>,
 }
 
 /// Transfer configuration section.
@@ -263,11 +303,22 @@ pub struct ConfigTransferSection {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_transfer_mb: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_project_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max_transfer_time_ms: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bwlimit_kbps: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_client_transfers: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub estimated_bandwidth_bps: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_sync_seconds: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fleet: Option<String>,
+    pub min_free_disk_gb: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container_image: Option<String>,
     // Adaptive compression (bd-243w)
     pub adaptive_compression: bool,
     pub min_compression_level: u32,
@@ -276,6 +327,24 @@ pub struct ConfigTransferSection {
     pub verify_artifacts: bool,
     #[serde(skip_serializing_if = "is_default_verify_size")]
     pub verify_max_size_bytes: u64,
+    pub verify_sample_files: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub artifact_deny_patterns: Vec<String>,
+    pub preserve_newer_local: bool,
+    pub artifact_archive: bool,
+    pub artifact_max_retries: u32,
+    pub respect_gitignore: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub gitignore_include: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub force_include: Vec<String>,
+    pub shared_target_dir: bool,
+    pub keep_remote_on_failure: bool,
+    pub keep_remote_hours: u64,
+    pub detach_remote_builds: bool,
+    pub use_sccache: bool,
+    pub skip_artifacts_for: Vec<String>,
+    pub backend: rch_common::TransferBackend,
 }
 
 /// Helper function for serialization: returns true if value is the default verify size (100 MB).
@@ -314,6 +383,8 @@ pub struct ConfigSelfHealingSection {
     pub daemon_installs_hooks: bool,
     pub auto_start_cooldown_secs: u64,
     pub auto_start_timeout_secs: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_dir: Option<String>,
 }
 
 /// Configuration init response for JSON output.
@@ -354,6 +425,39 @@ pub struct ConfigResetResponse {
     pub config_path: String,
 }
 
+/// One profile in `rch config profile` output.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ConfigProfileEntry {
+    pub name: String,
+    pub description: String,
+    pub active: bool,
+}
+
+/// An effective setting that changed when switching profiles.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ConfigProfileChange {
+    pub key: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// Response for `rch config profile`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ConfigProfileResponse {
+    /// Effective profile after the command, if any.
+    pub active: Option<String>,
+    /// Where the effective profile comes from (`default` when unset).
+    pub source: String,
+    pub profiles: Vec<ConfigProfileEntry>,
+    /// Config file the new profile was written to (switching only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config_path: Option<String>,
+    /// Effective settings changed by the switch.
+    pub changes: Vec<ConfigProfileChange>,
+    /// Validation warnings for the merged result.
+    pub warnings: Vec<String>,
+}
+
 /// Issue severity for config lint.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
@@ -388,6 +492,8 @@ pub struct ConfigDiffEntry {
     pub current: String,
     pub default: String,
     pub source: String,
+    /// False only for unchanged values listed by `rch config diff --all`.
+    pub changed: bool,
 }
 
 /// Response for config diff command.
@@ -0,0 +1,801 @@
+//! `rch uninstall`: remove RCH's footprint from this machine and, with
+//! `--remote`, its project mirrors on the workers.
+//!
+//! Every step checks before it acts, so the command is idempotent: a second
+//! run reports that there is nothing left to remove.
+//!
+//! Flow:
+//! 1. With `--remote`, resolve the mirrors to purge (this machine's projects
+//!    under the canonical root) and the healthy workers, then confirm. This
+//!    happens first because worker health comes from the running daemon.
+//! 2. Stop the daemon so it cannot recreate the socket or rewrite history
+//!    while it is being deleted.
+//! 3. Remove the agent hooks via [`uninstall_hook`].
+//! 4. Delete local state (see [`local_state_targets`]), listing each path.
+//! 5. Purge the remote mirrors, reusing the `rch clean --remote` helpers.
+//!
+//! The config directory is kept: it holds the worker list and settings a
+//! reinstall would want back. On macOS the data and config directories are
+//! the same, so any owned directory that holds the config is left in place.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::Result;
+use dialoguer::Confirm;
+use directories::ProjectDirs;
+use rch_common::path_topology::{DEFAULT_CANONICAL_PROJECT_ROOT, PathTopologyPolicy};
+use rch_common::{ApiResponse, WorkerConfig, is_safe_invalidation_target};
+use serde::Serialize;
+
+use crate::agent::{AgentKind, uninstall_hook};
+use crate::cache_gc::human_bytes;
+use crate::config::load_config;
+use crate::hook::{
+    autostart_cooldown_path, autostart_lock_path, configured_autostart_state_dir, daemon_log_path,
+    rotated_daemon_log_path,
+};
+use crate::state::primitives::IdempotentResult;
+use crate::ui::context::OutputContext;
+use crate::ui::theme::StatusIndicator;
+
+use super::clean::{
+    healthy_worker_ids, parse_reclaimed_bytes, remote_clean_command, remote_dir_for_project_name,
+};
+use super::load_workers_from_config;
+use super::send_daemon_command;
+use super::workers_setup::run_setup_ssh_command;
+
+/// How a local state path is removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Removal {
+    /// A single file (or socket).
+    File,
+    /// A directory RCH owns outright; removed recursively.
+    Tree,
+    /// A directory that may be shared with other tools (an overridden state
+    /// dir, `RCH_STATE_HOME`); removed only once it is empty.
+    DirIfEmpty,
+}
+
+/// One local path `rch uninstall` may delete.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct StateTarget {
+    label: &'static str,
+    path: PathBuf,
+    removal: Removal,
+}
+
+/// Where RCH keeps local state. Resolved once from the environment and
+/// config so [`local_state_targets`] stays a pure function.
+#[derive(Debug, Clone)]
+struct LocalStateLayout {
+    socket_path: PathBuf,
+    autostart_state_dir: PathBuf,
+    /// Whether `autostart_state_dir` is the built-in default (RCH-owned)
+    /// rather than a `self_healing.state_dir` override.
+    autostart_state_dir_is_default: bool,
+    state_home_files: Vec<PathBuf>,
+    /// Cache and data directories named after RCH (`~/.cache/rch`,
+    /// `~/.local/share/rch`, ...).
+    owned_dirs: Vec<PathBuf>,
+    /// The config directory; never removed, even when it sits inside (or is)
+    /// one of the owned directories.
+    config_dir: Option<PathBuf>,
+    /// Files any `rch` invocation recreates (the config cache, written while
+    /// loading config for this very command). They are deleted but not
+    /// reported, so a repeat run still finds nothing to remove.
+    regenerated: Vec<PathBuf>,
+}
+
+impl LocalStateLayout {
+    fn detect(config: &rch_common::RchConfig) -> Self {
+        let autostart_state_dir = configured_autostart_state_dir(&config.self_healing);
+        let mut owned_dirs: Vec<PathBuf> = [
+            dirs::cache_dir().map(|dir| dir.join("rch")),
+            dirs::data_local_dir().map(|dir| dir.join("rch")),
+            dirs::data_dir().map(|dir| dir.join("rch")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if let Some(project_dirs) = ProjectDirs::from("com", "rch", "rch") {
+            owned_dirs.push(project_dirs.cache_dir().to_path_buf());
+            owned_dirs.push(project_dirs.data_local_dir().to_path_buf());
+        }
+
+        Self {
+            socket_path: PathBuf::from(shellexpand::tilde(&config.general.socket_path).as_ref()),
+            autostart_state_dir_is_default: config.self_healing.state_dir.is_none(),
+            autostart_state_dir,
+            state_home_files: vec![
+                rch_common::bypass_record::default_bypass_record_path(),
+                rch_common::incident_ledger::default_ledger_path(),
+            ],
+            owned_dirs,
+            config_dir: crate::config::config_dir(),
+            regenerated: crate::config::cache_file_path().into_iter().collect(),
+        }
+    }
+}
+
+/// Every local path RCH may have written, deduplicated and ordered so files
+/// go before the directories that might contain them.
+///
+/// Directories are only removed wholesale when they are named after RCH;
+/// shared locations lose just the files RCH put there.
+fn local_state_targets(layout: &LocalStateLayout) -> Vec<StateTarget> {
+    let mut targets = vec![StateTarget {
+        label: "daemon socket",
+        path: layout.socket_path.clone(),
+        removal: Removal::File,
+    }];
+
+    let autostart_owned = layout.autostart_state_dir_is_default
+        && is_rch_named(&layout.autostart_state_dir)
+        && !holds_config(layout, &layout.autostart_state_dir);
+    if autostart_owned {
+        targets.push(StateTarget {
+            label: "auto-start lock, cooldown and daemon log",
+            path: layout.autostart_state_dir.clone(),
+            removal: Removal::Tree,
+        });
+    } else {
+        let log_path = daemon_log_path(&layout.autostart_state_dir);
+        for path in [
+            autostart_lock_path(&layout.autostart_state_dir),
+            autostart_cooldown_path(&layout.autostart_state_dir),
+            rotated_daemon_log_path(&log_path),
+            log_path,
+        ] {
+            targets.push(StateTarget {
+                label: "auto-start lock, cooldown and daemon log",
+                path,
+                removal: Removal::File,
+            });
+        }
+    }
+
+    for path in &layout.state_home_files {
+        targets.push(StateTarget {
+            label: "bypass records and incident ledger",
+            path: path.clone(),
+            removal: Removal::File,
+        });
+    }
+
+    for dir in layout
+        .owned_dirs
+        .iter()
+        .filter(|dir| is_rch_named(dir) && !holds_config(layout, dir))
+    {
+        targets.push(StateTarget {
+            label: "caches, timing and build history",
+            path: dir.clone(),
+            removal: Removal::Tree,
+        });
+    }
+
+    if !autostart_owned {
+        targets.push(StateTarget {
+            label: "state directory",
+            path: layout.autostart_state_dir.clone(),
+            removal: Removal::DirIfEmpty,
+        });
+    }
+    let state_homes: HashSet<&Path> = layout
+        .state_home_files
+        .iter()
+        .filter_map(|path| path.parent())
+        .collect();
+    for dir in state_homes {
+        targets.push(StateTarget {
+            label: "state directory",
+            path: dir.to_path_buf(),
+            removal: Removal::DirIfEmpty,
+        });
+    }
+
+    let mut seen = HashSet::new();
+    targets.retain(|target| seen.insert(target.path.clone()));
+    targets.sort_by_key(|target| match target.removal {
+        Removal::File => 0,
+        Removal::Tree => 1,
+        Removal::DirIfEmpty => 2,
+    });
+    targets
+}
+
+/// Whether `dir` is, or contains, the config directory.
+fn holds_config(layout: &LocalStateLayout, dir: &Path) -> bool {
+    layout
+        .config_dir
+        .as_deref()
+        .is_some_and(|config| config.starts_with(dir))
+}
+
+/// Whether the last path component names RCH (`rch`, `com.rch.rch`, ...).
+fn is_rch_named(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name == "rch" || name.ends_with(".rch"))
+}
+
+/// Remove one target. `Ok(false)` means there was nothing to remove; a
+/// directory holding only `regenerated` files is removed without counting.
+fn remove_target(
+    target: &StateTarget,
+    regenerated: &[PathBuf],
+    dry_run: bool,
+) -> std::io::Result<bool> {
+    let Ok(meta) = std::fs::symlink_metadata(&target.path) else {
+        return Ok(false);
+    };
+    match target.removal {
+        Removal::File | Removal::Tree if !meta.is_dir() => {
+            if !dry_run {
+                std::fs::remove_file(&target.path)?;
+            }
+            Ok(true)
+        }
+        Removal::File => Ok(false),
+        Removal::Tree => {
+            let has_state = std::fs::read_dir(&target.path)?
+                .filter_map(Result::ok)
+                .any(|entry| !regenerated.contains(&entry.path()));
+            if !dry_run {
+                std::fs::remove_dir_all(&target.path)?;
+            }
+            Ok(has_state)
+        }
+        Removal::DirIfEmpty => {
+            if !meta.is_dir() || std::fs::read_dir(&target.path)?.next().is_some() {
+                return Ok(false);
+            }
+            if !dry_run {
+                std::fs::remove_dir(&target.path)?;
+            }
+            Ok(true)
+        }
+    }
+}
+
+/// A local path that was (or, in a dry run, would be) removed.
+#[derive(Debug, Clone, Serialize)]
+struct RemovedPath {
+    label: String,
+    path: String,
+}
+
+/// A local path that could not be removed.
+#[derive(Debug, Clone, Serialize)]
+struct FailedPath {
+    path: String,
+    error: String,
+}
+
+/// Per-worker outcome of the remote purge.
+#[derive(Debug, Clone, Serialize)]
+struct UninstallRemoteWorkerResult {
+    worker_id: String,
+    /// `removed`, `absent`, `failed`, or `skipped_unhealthy`.
+    outcome: String,
+    projects_removed: usize,
+    bytes_reclaimed: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// The `rch uninstall` report.
+#[derive(Debug, Clone, Serialize)]
+struct UninstallResult {
+    dry_run: bool,
+    /// `stopped`, `not_running`, or `still_running`.
+    daemon: String,
+    hooks_removed: Vec<String>,
+    removed: Vec<RemovedPath>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    failed: Vec<FailedPath>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    remote_paths: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    remote: Vec<UninstallRemoteWorkerResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    config_dir_kept: Option<String>,
+}
+
+/// Entry point for `rch uninstall`.
+///
+/// With `remote`, this machine's project mirrors under the canonical root are
+/// also purged on every healthy worker; that step needs confirmation unless
+/// `yes` is set. `dry_run` lists what would be removed and changes nothing.
+pub async fn uninstall(remote: bool, yes: bool, dry_run: bool, ctx: &OutputContext) -> Result<()> {
+    let style = ctx.theme();
+    let config = load_config()?;
+
+    let mut remote_plan = None;
+    if remote {
+        let policy = config.path_topology.to_policy();
+        let remote_paths = remote_mirror_paths(&policy)?;
+        let workers = load_workers_from_config()?;
+        let healthy = healthy_worker_ids(&workers).await;
+
+        if !yes && !dry_run && !remote_paths.is_empty() {
+            if ctx.is_json() {
+                anyhow::bail!("refusing to delete remote caches without confirmation; pass --yes");
+            }
+            println!(
+                "{} This will delete {} project director{} under {} on {} healthy worker(s).",
+                StatusIndicator::Warning.display(style),
+                remote_paths.len(),
+                if remote_paths.len() == 1 { "y" } else { "ies" },
+                style.highlight(&policy.canonical_root().display().to_string()),
+                healthy.len()
+            );
+            let confirmed = Confirm::new()
+                .with_prompt("Uninstall RCH and delete the remote project directories?")
+                .default(false)
+                .interact()?;
+            if !confirmed {
+                println!("{} Aborted.", StatusIndicator::Info.display(style));
+                return Ok(());
+            }
+        }
+        remote_plan = Some((remote_paths, workers, healthy));
+    }
+
+    let socket_path = PathBuf::from(shellexpand::tilde(&config.general.socket_path).as_ref());
+    let daemon = if dry_run {
+        if socket_path.exists() {
+            "running"
+        } else {
+            "not_running"
+        }
+    } else {
+        stop_daemon(&socket_path).await
+    };
+
+    let mut hooks_removed = Vec::new();
+    for kind in AgentKind::ALL
+        .iter()
+        .filter(|kind| kind.hook_support().can_install_hook())
+    {
+        match uninstall_hook(*kind, dry_run) {
+            Ok(IdempotentResult::Changed | IdempotentResult::WouldChange(_)) => {
+                hooks_removed.push(kind.name().to_string());
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Failed to remove {} hook: {}", kind.name(), e),
+        }
+    }
+
+    let mut removed = Vec::new();
+    let mut failed = Vec::new();
+    let layout = LocalStateLayout::detect(&config);
+    for target in local_state_targets(&layout) {
+        match remove_target(&target, &layout.regenerated, dry_run) {
+            Ok(true) => removed.push(RemovedPath {
+                label: target.label.to_string(),
+                path: target.path.display().to_string(),
+            }),
+            Ok(false) => {}
+            Err(e) => failed.push(FailedPath {
+                path: target.path.display().to_string(),
+                error: e.to_string(),
+            }),
+        }
+    }
+
+    let mut remote_paths = Vec::new();
+    let mut remote_results = Vec::new();
+    if let Some((paths, workers, healthy)) = remote_plan {
+        let canonical_root = Path::new(DEFAULT_CANONICAL_PROJECT_ROOT);
+        if !dry_run && !paths.is_empty() {
+            for worker in &workers {
+                let result = if healthy.contains(&worker.id.0) {
+                    purge_worker_mirrors(worker, &paths, canonical_root).await
+                } else {
+                    UninstallRemoteWorkerResult {
+                        worker_id: worker.id.0.clone(),
+                        outcome: "skipped_unhealthy".to_string(),
+                        projects_removed: 0,
+                        bytes_reclaimed: 0,
+                        error: None,
+                    }
+                };
+                tracing::info!(
+                    target: "rch::uninstall::remote",
+                    worker = %result.worker_id,
+                    outcome = %result.outcome,
+                    bytes = result.bytes_reclaimed,
+                    "uninstall.remote.worker",
+                );
+                remote_results.push(result);
+            }
+        }
+        remote_paths = paths.iter().map(|p| p.display().to_string()).collect();
+    }
+
+    let result = UninstallResult {
+        dry_run,
+        daemon: daemon.to_string(),
+        hooks_removed,
+        removed,
+        failed,
+        remote_paths,
+        remote: remote_results,
+        config_dir_kept: crate::config::config_dir()
+            .filter(|dir| dir.exists())
+            .map(|dir| dir.display().to_string()),
+    };
+
+    if ctx.is_json() {
+        let _ = ctx.json(&ApiResponse::ok("uninstall", &result));
+        return Ok(());
+    }
+    render_human(&result, ctx);
+    Ok(())
+}
+
+/// Ask the daemon to shut down and wait briefly for its socket to go away.
+async fn stop_daemon(socket_path: &Path) -> &'static str {
+    if !socket_path.exists() {
+        return "not_running";
+    }
+    if send_daemon_command("POST /shutdown\n").await.is_err() {
+        // Nothing answered: a stale socket, removed with the rest of the state.
+        return "not_running";
+    }
+    for _ in 0..20 {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        if !socket_path.exists() {
+            return "stopped";
+        }
+    }
+    "still_running"
+}
+
+/// Worker-side mirrors of this machine's projects: every directory directly
+/// under the local canonical root, mapped to the same path on the workers.
+/// Hidden entries and anything that fails the canonical-root safety check
+/// are left alone.
+fn remote_mirror_paths(policy: &PathTopologyPolicy) -> Result<Vec<PathBuf>> {
+    let canonical_root = Path::new(DEFAULT_CANONICAL_PROJECT_ROOT);
+    if policy.canonical_root() != canonical_root {
+        anyhow::bail!(
+            "refusing remote cleanup: canonical root {} is not {}",
+            policy.canonical_root().display(),
+            canonical_root.display()
+        );
+    }
+    let Ok(entries) = std::fs::read_dir(policy.canonical_root()) else {
+        return Ok(Vec::new());
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| !name.starts_with('.'))
+        .filter_map(|name| remote_dir_for_project_name(&name, policy).ok())
+        .filter(|path| is_safe_invalidation_target(path, canonical_root))
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Remote script removing every path in one SSH round trip; prints one
+/// [`remote_clean_command`] result line per path, so directories RCH never
+/// built in print [`super::clean::FOREIGN_MARKER`] and are left alone.
+fn remote_purge_command(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|path| remote_clean_command(path))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Sum [`remote_purge_command`] output into (directories removed, bytes).
+fn parse_purge_output(stdout: &str) -> (usize, u64) {
+    stdout
+        .lines()
+        .filter_map(parse_reclaimed_bytes)
+        .fold((0, 0), |(count, bytes), reclaimed| {
+            (count + 1, bytes + reclaimed)
+        })
+}
+
+async fn purge_worker_mirrors(
+    worker: &WorkerConfig,
+    paths: &[PathBuf],
+    canonical_root: &Path,
+) -> UninstallRemoteWorkerResult {
+    let failed = |error: String| UninstallRemoteWorkerResult {
+        worker_id: worker.id.0.clone(),
+        outcome: "failed".to_string(),
+        projects_removed: 0,
+        bytes_reclaimed: 0,
+        error: Some(error),
+    };
+
+    // Defense-in-depth: re-check at the SSH boundary.
+    if let Some(path) = paths
+        .iter()
+        .find(|path| !is_safe_invalidation_target(path, canonical_root))
+    {
+        return failed(format!(
+            "refused at SSH boundary: {} is not strictly under {}",
+            path.display(),
+            canonical_root.display()
+        ));
+    }
+
+    match run_setup_ssh_command(worker, &remote_purge_command(paths)).await {
+        Ok(out) if out.status.success() => {
+            let (projects_removed, bytes_reclaimed) =
+                parse_purge_output(&String::from_utf8_lossy(&out.stdout));
+            UninstallRemoteWorkerResult {
+                worker_id: worker.id.0.clone(),
+                outcome: if projects_removed > 0 {
+                    "removed"
+                } else {
+                    "absent"
+                }
+                .to_string(),
+                projects_removed,
+                bytes_reclaimed,
+                error: None,
+            }
+        }
+        Ok(out) => failed(format!(
+            "exit {}: {}",
+            out.status.code().unwrap_or(-1),
+            String::from_utf8_lossy(&out.stderr).trim()
+        )),
+        Err(e) => failed(format!("ssh error: {e}")),
+    }
+}
+
+fn render_human(result: &UninstallResult, ctx: &OutputContext) {
+    let style = ctx.style();
+    let verb = if result.dry_run {
+        "would remove"
+    } else {
+        "removed"
+    };
+    println!(
+        "{}",
+        style.format_header(if result.dry_run {
+            "RCH Uninstall (dry run)"
+        } else {
+            "RCH Uninstall"
+        })
+    );
+    let daemon = match result.daemon.as_str() {
+        "stopped" => style.success("stopped"),
+        "running" => style.warning("running, would be stopped"),
+        "still_running" => style.warning("still shutting down"),
+        _ => style.muted("not running"),
+    };
+    println!("  {} {}", style.key("daemon:"), daemon);
+    for agent in &result.hooks_removed {
+        println!(
+            "  {} {} {}",
+            style.key("hook:"),
+            style.success(verb),
+            style.value(agent)
+        );
+    }
+    for path in &result.removed {
+        println!(
+            "  {} {} {}",
+            style.key(&format!("{}:", path.label)),
+            style.success(verb),
+            style.value(&path.path)
+        );
+    }
+    for path in &result.failed {
+        println!(
+            "  {} {} {}",
+            style.error("failed:"),
+            style.value(&path.path),
+            style.muted(&path.error)
+        );
+    }
+    if result.hooks_removed.is_empty() && result.removed.is_empty() && result.failed.is_empty() {
+        println!("  {}", style.muted("No local RCH state left to remove."));
+    }
+
+    if result.dry_run {
+        for path in &result.remote_paths {
+            println!(
+                "  {} {} {}",
+                style.key("remote:"),
+                style.success(verb),
+                style.value(path)
+            );
+        }
+    }
+    for w in &result.remote {
+        let status = match w.outcome.as_str() {
+            "removed" => style.success(&format!(
+                "removed {} project(s), {} reclaimed",
+                w.projects_removed,
+                human_bytes(w.bytes_reclaimed)
+            )),
+            "absent" => style.muted("nothing to remove"),
+            "skipped_unhealthy" => style.warning("skipped (not healthy)"),
+            _ => style.error("failed"),
+        };
+        println!("  {} {}", style.key(&format!("{}:", w.worker_id)), status);
+        if let Some(error) = &w.error {
+            println!("      {}", style.muted(error));
+        }
+    }
+
+    if let Some(dir) = &result.config_dir_kept {
+        println!(
+            "  {} {}",
+            style.key("kept config:"),
+            style.muted(&format!("{dir} (delete it to forget workers and settings)"))
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout_in(root: &Path, state_dir_override: bool) -> LocalStateLayout {
+        let state_home = root.join("state-home");
+        LocalStateLayout {
+            socket_path: root.join("run/rch.sock"),
+            autostart_state_dir: if state_dir_override {
+                root.join("shared-state")
+            } else {
+                root.join("run/rch")
+            },
+            autostart_state_dir_is_default: !state_dir_override,
+            state_home_files: vec![
+                state_home.join("bypass_records.json"),
+                state_home.join("incidents.jsonl"),
+            ],
+            owned_dirs: vec![root.join("cache/rch"), root.join("cache/rch")],
+            config_dir: Some(root.join("config/rch")),
+            regenerated: vec![root.join("cache/rch/config.cache.json")],
+        }
+    }
+
+    fn touch(path: &Path) {
+        std::fs::create_dir_all(path.parent().unwrap()).expect("mkdir parent");
+        std::fs::write(path, b"x").expect("write file");
+    }
+
+    #[test]
+    fn local_state_targets_dedupe_and_order_files_first() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let targets = local_state_targets(&layout_in(tmp.path(), false));
+
+        let cache_dirs = targets
+            .iter()
+            .filter(|t| t.path == tmp.path().join("cache/rch"))
+            .count();
+        assert_eq!(cache_dirs, 1, "duplicate dirs must collapse");
+        let first_dir = targets
+            .iter()
+            .position(|t| t.removal != Removal::File)
+            .expect("has directories");
+        assert!(
+            targets[first_dir..]
+                .iter()
+                .all(|t| t.removal != Removal::File),
+            "files must precede directories: {targets:?}"
+        );
+    }
+
+    #[test]
+    fn uninstall_removes_owned_state_and_is_idempotent() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let layout = layout_in(tmp.path(), false);
+        touch(&layout.socket_path);
+        touch(&autostart_lock_path(&layout.autostart_state_dir));
+        touch(&tmp.path().join("cache/rch/timing_history.json"));
+        touch(&tmp.path().join("cache/rch/logs/build.log"));
+        touch(&layout.state_home_files[1]);
+
+        let targets = local_state_targets(&layout);
+        let dry: Vec<_> = targets
+            .iter()
+            .filter(|t| remove_target(t, &layout.regenerated, true).unwrap())
+            .collect();
+        assert!(!dry.is_empty());
+        assert!(layout.socket_path.exists(), "dry run must not delete");
+
+        for target in &targets {
+            remove_target(target, &layout.regenerated, false).expect("remove");
+        }
+        assert!(!layout.socket_path.exists());
+        assert!(!layout.autostart_state_dir.exists());
+        assert!(!tmp.path().join("cache/rch").exists());
+        assert!(!tmp.path().join("state-home").exists());
+
+        // Loading config for the next run recreates the cache; that alone
+        // must not count as leftover state.
+        touch(&layout.regenerated[0]);
+        let again: Vec<_> = targets
+            .iter()
+            .filter(|t| remove_target(t, &layout.regenerated, false).unwrap())
+            .collect();
+        assert!(again.is_empty(), "second run removes nothing: {again:?}");
+    }
+
+    #[test]
+    fn shared_state_dirs_keep_foreign_files() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let layout = layout_in(tmp.path(), true);
+        touch(&autostart_cooldown_path(&layout.autostart_state_dir));
+        touch(&daemon_log_path(&layout.autostart_state_dir));
+        touch(&layout.autostart_state_dir.join("unrelated.txt"));
+        touch(&layout.state_home_files[0]);
+        touch(&tmp.path().join("state-home/other-tool.db"));
+
+        for target in local_state_targets(&layout) {
+            remove_target(&target, &layout.regenerated, false).expect("remove");
+        }
+        assert!(!autostart_cooldown_path(&layout.autostart_state_dir).exists());
+        assert!(!daemon_log_path(&layout.autostart_state_dir).exists());
+        assert!(layout.autostart_state_dir.join("unrelated.txt").exists());
+        assert!(!layout.state_home_files[0].exists());
+        assert!(tmp.path().join("state-home/other-tool.db").exists());
+    }
+
+    #[test]
+    fn remote_purge_command_and_output_cover_every_path() {
+        let paths = vec![
+            PathBuf::from("/data/projects/alpha"),
+            PathBuf::from("/data/projects/beta"),
+        ];
+        let cmd = remote_purge_command(&paths);
+        assert!(cmd.contains("rm -rf -- /data/projects/alpha;"));
+        assert!(cmd.contains("rm -rf -- /data/projects/beta;"));
+        assert_eq!(parse_purge_output("1024\nabsent\n2048\n"), (2, 3072));
+        assert_eq!(parse_purge_output("absent\nabsent\n"), (0, 0));
+        assert_eq!(parse_purge_output("foreign\n1024\n"), (1, 1024));
+    }
+
+    #[test]
+    fn remote_purge_skips_directories_without_rch_output() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let ours = tmp.path().join("ours");
+        let theirs = tmp.path().join("theirs");
+        touch(&ours.join(".rch-target/debug/app"));
+        touch(&theirs.join("src/main.rs"));
+
+        let out = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(remote_purge_command(&[ours.clone(), theirs.clone()]))
+            .output()
+            .expect("run purge script");
+        let stdout = String::from_utf8_lossy(&out.stdout);
+        assert_eq!(parse_purge_output(&stdout).0, 1, "stdout: {stdout}");
+        assert!(!ours.exists());
+        assert!(theirs.join("src/main.rs").exists());
+    }
+
+    #[test]
+    fn owned_dir_holding_the_config_is_kept() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let mut layout = layout_in(tmp.path(), false);
+        // macOS: data_local_dir() and config_dir() are the same directory.
+        layout.config_dir = Some(tmp.path().join("cache/rch"));
+        layout.autostart_state_dir = tmp.path().join("cache/rch");
+
+        let targets = local_state_targets(&layout);
+        assert!(
+            targets
+                .iter()
+                .all(|t| !(t.path == tmp.path().join("cache/rch") && t.removal == Removal::Tree)),
+            "config dir must not be removed recursively: {targets:?}"
+        );
+    }
+}
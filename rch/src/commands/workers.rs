@@ -271,6 +271,22 @@ pub(super) fn collect_refresh_warnings(workers: &[WorkerCapabilitiesFromApi]) ->
         .collect()
 }
 
+pub(super) fn collect_missing_tool_warnings(workers: &[WorkerCapabilitiesFromApi]) -> Vec<String> {
+    workers
+        .iter()
+        .flat_map(|worker| {
+            worker.capabilities.missing_tools.iter().map(move |tool| {
+                format!(
+                    "Worker {} is missing {}: {}",
+                    worker.id,
+                    tool,
+                    rch_common::worker_tool_install_hint(tool)
+                )
+            })
+        })
+        .collect()
+}
+
 fn workers_list_verbose_enabled(ctx: &OutputContext) -> bool {
     ctx.is_verbose() && !ctx.is_json()
 }
@@ -623,6 +639,7 @@ pub async fn workers_capabilities(
         ));
     }
     warnings.extend(collect_refresh_warnings(&workers));
+    warnings.extend(collect_missing_tool_warnings(&workers));
 
     if ctx.is_json() {
         let report = WorkersCapabilitiesReport {
@@ -647,7 +664,7 @@ pub async fn workers_capabilities(
     println!("{}", style.format_header("Worker Capabilities"));
     println!();
 
-    let key_width = ["Rust", "Bun", "Node", "npm"]
+    let key_width = ["Rust", "Bun", "Node", "npm", "sccache"]
         .iter()
         .map(|label| label.len())
         .max()
@@ -705,6 +722,11 @@ pub async fn workers_capabilities(
         render("Bun", caps.bun_version.as_ref());
         render("Node", caps.node_version.as_ref());
         render("npm", caps.npm_version.as_ref());
+        if caps.has_sccache() {
+            render("sccache", caps.sccache_version.as_ref());
+        }
+        render("rsync", caps.rsync_version.as_ref());
+        render("zstd", caps.zstd_version.as_ref());
         if let Some(refresh) = worker.refresh.as_ref() {
             let (indicator, label) = if refresh.live {
                 (StatusIndicator::Success, style.value("live refresh"))
@@ -1851,6 +1873,9 @@ mod probe_summary_tests {
             total_slots: 8,
             priority: 100,
             tags: vec!["rust".to_string()],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         }
     }
 
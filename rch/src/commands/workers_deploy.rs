@@ -322,7 +322,9 @@ pub(super) async fn get_remote_version(worker: &WorkerConfig) -> Result<String>
     let mut cmd = Command::new("ssh");
     cmd.arg("-o").arg("BatchMode=yes");
     cmd.arg("-o").arg("ConnectTimeout=10");
-    cmd.arg("-i").arg(&worker.identity_file);
+    if let Some(identity) = worker.ssh_identity() {
+        cmd.arg("-i").arg(identity);
+    }
 
     let target = format!("{}@{}", worker.user, worker.host);
     cmd.arg(&target);
@@ -377,7 +379,9 @@ pub(super) async fn deploy_via_scp(
         let mut mkdir_cmd = Command::new("ssh");
         mkdir_cmd.arg("-o").arg("BatchMode=yes");
         mkdir_cmd.arg("-o").arg("ConnectTimeout=10");
-        mkdir_cmd.arg("-i").arg(&worker.identity_file);
+        if let Some(identity) = worker.ssh_identity() {
+            mkdir_cmd.arg("-i").arg(identity);
+        }
 
         mkdir_cmd.arg(&target);
         mkdir_cmd.arg(remote_mkdir_command(dir)?);
@@ -392,7 +396,9 @@ pub(super) async fn deploy_via_scp(
         let mut scp_cmd = Command::new("scp");
         scp_cmd.arg("-o").arg("BatchMode=yes");
         scp_cmd.arg("-o").arg("ConnectTimeout=30");
-        scp_cmd.arg("-i").arg(&worker.identity_file);
+        if let Some(identity) = worker.ssh_identity() {
+            scp_cmd.arg("-i").arg(identity);
+        }
         scp_cmd.arg(local_binary);
 
         let remote_target = scp_remote_target(worker, remote_path);
@@ -405,7 +411,9 @@ pub(super) async fn deploy_via_scp(
             let mut chmod_cmd = Command::new("ssh");
             chmod_cmd.arg("-o").arg("BatchMode=yes");
             chmod_cmd.arg("-o").arg("ConnectTimeout=10");
-            chmod_cmd.arg("-i").arg(&worker.identity_file);
+            if let Some(identity) = worker.ssh_identity() {
+                chmod_cmd.arg("-i").arg(identity);
+            }
             chmod_cmd.arg(&target);
             chmod_cmd.arg(remote_chmod_command(remote_path)?);
 
@@ -276,6 +276,9 @@ pub async fn workers_init(yes: bool, ctx: &OutputContext) -> Result<()> {
         total_slots: 8, // Default
         priority: 100,  // Default
         tags: vec![],
+        max_concurrent_builds: None,
+        nice_level: None,
+        ionice_class: None,
     };
 
     workers.push(new_worker);
@@ -344,6 +347,9 @@ fn serialize_workers_config(workers: &[WorkerConfig]) -> Result<String> {
                 priority: worker.priority,
                 tags: worker.tags.clone(),
                 enabled: true,
+                max_concurrent_builds: worker.max_concurrent_builds,
+                nice_level: worker.nice_level,
+                ionice_class: worker.ionice_class,
             })
             .collect(),
     };
@@ -605,6 +611,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec!["rust\"fast".to_string(), "gpu\nprod".to_string()],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         }];
 
         let rendered = serialize_workers_config(&workers).expect("serialize workers config");
@@ -624,4 +633,40 @@ mod tests {
             vec!["rust\"fast".to_string(), "gpu\nprod".to_string()]
         );
     }
+
+    #[test]
+    fn serialize_workers_config_keeps_max_concurrent_builds() {
+        let _guard = test_guard!();
+        let workers = vec![WorkerConfig {
+            id: WorkerId::new("capped"),
+            host: "worker.example".to_string(),
+            max_concurrent_builds: Some(2),
+            ..Default::default()
+        }];
+
+        let rendered = serialize_workers_config(&workers).expect("serialize workers config");
+        let parsed: WorkersConfig =
+            toml::from_str(&rendered).expect("serialized workers config must parse");
+
+        assert_eq!(parsed.workers[0].max_concurrent_builds, Some(2));
+    }
+
+    #[test]
+    fn serialize_workers_config_keeps_priority_settings() {
+        let _guard = test_guard!();
+        let workers = vec![WorkerConfig {
+            id: WorkerId::new("shared"),
+            host: "worker.example".to_string(),
+            nice_level: Some(10),
+            ionice_class: Some(3),
+            ..Default::default()
+        }];
+
+        let rendered = serialize_workers_config(&workers).expect("serialize workers config");
+        let parsed: WorkersConfig =
+            toml::from_str(&rendered).expect("serialized workers config must parse");
+
+        assert_eq!(parsed.workers[0].nice_level, Some(10));
+        assert_eq!(parsed.workers[0].ionice_class, Some(3));
+    }
 }
@@ -0,0 +1,192 @@
+//! `rch workers ping` command implementation.
+//!
+//! Runs a trivial `true` over a fresh SSH connection to every configured
+//! worker, `count` times, and reports connect+exec latency. Workers are pinged
+//! a few at a time so a large fleet does not open hundreds of SSH connections
+//! at once; samples for one worker are taken one after another.
+
+use anyhow::Result;
+use futures::stream::{self, StreamExt};
+use rch_common::{ApiResponse, WorkerConfig};
+use std::time::Instant;
+
+use crate::ui::context::OutputContext;
+use crate::ui::theme::StatusIndicator;
+
+use super::load_workers_from_config;
+use super::types::{WorkerPingResult, WorkersPingResponse};
+use super::workers_setup::run_setup_ssh_command;
+
+/// Workers pinged concurrently.
+const MAX_CONCURRENT_PINGS: usize = 8;
+
+/// Measure SSH round-trip latency to every configured worker.
+pub async fn workers_ping(count: u32, ctx: &OutputContext) -> Result<()> {
+    let workers = load_workers_from_config()?;
+    let count = count.max(1);
+    let style = ctx.theme();
+
+    if !ctx.is_json() && !workers.is_empty() {
+        println!(
+            "Pinging {} worker(s), {} round trip(s) each...\n",
+            style.highlight(&workers.len().to_string()),
+            count
+        );
+    }
+
+    let mut results: Vec<WorkerPingResult> = stream::iter(workers)
+        .map(|worker| ping_worker(worker, count))
+        .buffer_unordered(MAX_CONCURRENT_PINGS)
+        .collect()
+        .await;
+    sort_by_median(&mut results);
+
+    if ctx.is_json() {
+        let _ = ctx.json(&ApiResponse::ok(
+            "workers ping",
+            WorkersPingResponse { count, results },
+        ));
+        return Ok(());
+    }
+
+    if results.is_empty() {
+        println!(
+            "{} No workers configured.",
+            StatusIndicator::Info.display(style)
+        );
+        return Ok(());
+    }
+
+    let ms = |value: Option<u64>| value.map_or_else(|| "-".to_string(), |v| format!("{v}ms"));
+    let rows: Vec<Vec<String>> = results
+        .iter()
+        .map(|result| {
+            vec![
+                result.id.clone(),
+                result.host.clone(),
+                ms(result.min_ms),
+                ms(result.median_ms),
+                ms(result.max_ms),
+                format!("{}/{}", result.failures, count),
+            ]
+        })
+        .collect();
+    ctx.table(&["Worker", "Host", "Min", "Median", "Max", "Failed"], &rows);
+
+    for result in &results {
+        if let Some(error) = &result.error {
+            println!(
+                "\n{} {}: {}",
+                StatusIndicator::Error.display(style),
+                style.highlight(&result.id),
+                error
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Take `count` sequential samples from one worker.
+async fn ping_worker(worker: WorkerConfig, count: u32) -> WorkerPingResult {
+    let mut samples = Vec::with_capacity(count as usize);
+    let mut failures = 0;
+    let mut error = None;
+
+    for _ in 0..count {
+        let start = Instant::now();
+        match run_setup_ssh_command(&worker, "true").await {
+            Ok(output) if output.status.success() => {
+                samples.push(u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX));
+            }
+            Ok(output) => {
+                failures += 1;
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                error.get_or_insert_with(|| {
+                    format!(
+                        "ssh exited with {}: {}",
+                        output.status.code().unwrap_or(-1),
+                        stderr.trim()
+                    )
+                });
+            }
+            Err(e) => {
+                failures += 1;
+                error.get_or_insert_with(|| format!("{e:#}"));
+            }
+        }
+    }
+
+    let (min_ms, median_ms, max_ms) = match latency_stats(&mut samples) {
+        Some((min, median, max)) => (Some(min), Some(median), Some(max)),
+        None => (None, None, None),
+    };
+    WorkerPingResult {
+        id: worker.id.as_str().to_string(),
+        host: worker.host.clone(),
+        min_ms,
+        median_ms,
+        max_ms,
+        failures,
+        error,
+    }
+}
+
+/// Min, median and max of the samples, or `None` when there are none.
+fn latency_stats(samples: &mut [u64]) -> Option<(u64, u64, u64)> {
+    samples.sort_unstable();
+    let (&min, &max) = (samples.first()?, samples.last()?);
+    let mid = samples.len() / 2;
+    let median = if samples.len().is_multiple_of(2) {
+        (samples[mid - 1] + samples[mid]) / 2
+    } else {
+        samples[mid]
+    };
+    Some((min, median, max))
+}
+
+/// Fastest median first; workers that never answered go last, by ID.
+fn sort_by_median(results: &mut [WorkerPingResult]) {
+    results.sort_by(|a, b| {
+        let key = |r: &WorkerPingResult| r.median_ms.unwrap_or(u64::MAX);
+        key(a).cmp(&key(b)).then_with(|| a.id.cmp(&b.id))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rch_common::test_guard;
+
+    fn result(id: &str, median_ms: Option<u64>) -> WorkerPingResult {
+        WorkerPingResult {
+            id: id.to_string(),
+            host: format!("{id}.example"),
+            min_ms: median_ms,
+            median_ms,
+            max_ms: median_ms,
+            failures: 0,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn latency_stats_reports_min_median_max() {
+        let _guard = test_guard!();
+        assert_eq!(latency_stats(&mut [40, 10, 30]), Some((10, 30, 40)));
+        assert_eq!(latency_stats(&mut [10, 40, 20, 30]), Some((10, 25, 40)));
+        assert_eq!(latency_stats(&mut []), None);
+    }
+
+    #[test]
+    fn sort_by_median_puts_unreachable_workers_last() {
+        let _guard = test_guard!();
+        let mut results = vec![
+            result("down", None),
+            result("slow", Some(180)),
+            result("fast", Some(12)),
+        ];
+        sort_by_median(&mut results);
+        let order: Vec<&str> = results.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(order, ["fast", "slow", "down"]);
+    }
+}
@@ -914,7 +914,9 @@ pub(crate) async fn run_setup_ssh_command(
     cmd.arg("-o").arg("BatchMode=yes");
     cmd.arg("-o").arg("ConnectTimeout=10");
     cmd.arg("-o").arg("StrictHostKeyChecking=accept-new");
-    cmd.arg("-i").arg(&worker.identity_file);
+    if let Some(identity) = worker.ssh_identity() {
+        cmd.arg("-i").arg(identity);
+    }
     cmd.arg(format!("{}@{}", worker.user, worker.host));
     cmd.arg(remote_cmd);
 
@@ -1411,7 +1413,9 @@ async fn verify_worker_health(worker: &WorkerConfig) -> Result<bool> {
     cmd.arg("-o").arg("BatchMode=yes");
     cmd.arg("-o").arg("ConnectTimeout=10");
     cmd.arg("-o").arg("StrictHostKeyChecking=accept-new");
-    cmd.arg("-i").arg(&worker.identity_file);
+    if let Some(identity) = worker.ssh_identity() {
+        cmd.arg("-i").arg(identity);
+    }
     cmd.arg(format!("{}@{}", worker.user, worker.host));
     cmd.arg("rch-wkr capabilities >/dev/null 2>&1 && echo OK || echo DEGRADED");
 
@@ -1591,7 +1595,9 @@ async fn check_remote_toolchain(worker: &WorkerConfig, toolchain: &str) -> Resul
     cmd.arg("-o").arg("BatchMode=yes");
     cmd.arg("-o").arg("ConnectTimeout=10");
     cmd.arg("-o").arg("StrictHostKeyChecking=accept-new");
-    cmd.arg("-i").arg(&worker.identity_file);
+    if let Some(identity) = worker.ssh_identity() {
+        cmd.arg("-i").arg(identity);
+    }
     cmd.arg(format!("{}@{}", worker.user, worker.host));
     cmd.arg(check_toolchain_command(toolchain));
 
@@ -1606,7 +1612,9 @@ async fn install_remote_toolchain(worker: &WorkerConfig, toolchain: &str) -> Res
     let mut cmd = Command::new("ssh");
     cmd.arg("-o").arg("BatchMode=yes");
     cmd.arg("-o").arg("ConnectTimeout=60"); // Toolchain install can take a while
-    cmd.arg("-i").arg(&worker.identity_file);
+    if let Some(identity) = worker.ssh_identity() {
+        cmd.arg("-i").arg(identity);
+    }
     cmd.arg(format!("{}@{}", worker.user, worker.host));
     cmd.arg(install_toolchain_command(toolchain));
 
@@ -3,18 +3,49 @@
 use crate::error::ConfigError;
 use anyhow::{Context, Result};
 use directories::ProjectDirs;
+use rch_common::classifier_rules::ClassifierRules;
 use rch_common::types::validate_remote_base;
 use rch_common::{
-    ConfigValueSource, OutputVisibility, RchConfig, SelfHealingLogLevel, SelfTestFailureAction,
-    SelfTestWorkers, TransferConfig,
+    CompilationKind, ConfigValueSource, OutputVisibility, Profile, RchConfig, SelfHealingLogLevel,
+    SelfTestFailureAction, SelfTestWorkers, TransferBackend, TransferConfig,
 };
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 use tracing::{debug, warn};
 
 const RCH_CONFIG_DIR_ENV: &str = "RCH_CONFIG_DIR";
+const RCH_CLASSIFIER_RULES_ENV: &str = "RCH_CLASSIFIER_RULES";
+
+/// Project-local overlay, found in the current directory or an ancestor.
+pub const PROJECT_OVERLAY_FILE: &str = ".rch.toml";
+
+/// Keys a [`PROJECT_OVERLAY_FILE`] may set, as `(section, key)`.
+///
+/// Everything else (sockets, state dirs, env forwarding, remote paths,
+/// notification endpoints) only comes from the user config or
+/// `.rch/config.toml`, so a checked-in overlay cannot redirect builds or
+/// leak credentials.
+pub const PROJECT_OVERLAY_KEYS: &[(&str, &str)] = &[
+    ("compilation", "confidence_threshold"),
+    ("compilation", "min_local_time_ms"),
+    ("compilation", "build_timeout_sec"),
+    ("compilation", "test_timeout_sec"),
+    ("compilation", "timeouts"),
+    ("transfer", "fleet"),
+    ("transfer", "exclude_patterns"),
+    ("transfer", "respect_gitignore"),
+    ("transfer", "gitignore_include"),
+    ("transfer", "force_include"),
+    ("transfer", "artifact_deny_patterns"),
+    ("transfer", "skip_artifacts_for"),
+    ("transfer", "min_free_disk_gb"),
+    ("transfer", "shared_target_dir"),
+    ("transfer", "use_sccache"),
+    ("transfer", "keep_remote_on_failure"),
+    ("output", "visibility"),
+];
 
 #[cfg(test)]
 use std::sync::{Mutex, OnceLock};
@@ -49,6 +80,37 @@ fn config_dir_from_env_value(value: Option<&OsStr>) -> Option<PathBuf> {
     Some(PathBuf::from(expanded))
 }
 
+/// Location of the user classifier rules file: `RCH_CLASSIFIER_RULES` if set,
+/// else `classifier.toml` in the config directory.
+///
+/// Resolved without loading the config so the hook's non-compilation hot
+/// path stays cheap.
+pub fn classifier_rules_path() -> Option<PathBuf> {
+    config_dir_from_env_value(std::env::var_os(RCH_CLASSIFIER_RULES_ENV).as_deref())
+        .or_else(|| config_dir().map(|dir| dir.join("classifier.toml")))
+}
+
+/// User classifier rules, read and compiled once per process.
+///
+/// A missing file yields `None`. A malformed one is logged and ignored, so
+/// classification falls back to the built-in rules.
+pub fn classifier_rules() -> Option<&'static ClassifierRules> {
+    static RULES: std::sync::OnceLock<Option<ClassifierRules>> = std::sync::OnceLock::new();
+    RULES
+        .get_or_init(|| {
+            let path = classifier_rules_path()?;
+            let content = std::fs::read_to_string(&path).ok()?;
+            match ClassifierRules::from_toml(&content) {
+                Ok(rules) => Some(rules),
+                Err(e) => {
+                    warn!("Ignoring classifier rules {}: {}", path.display(), e);
+                    None
+                }
+            }
+        })
+        .as_ref()
+}
+
 // ============================================================================
 // Cache (t15) — source-fingerprint-keyed binary cache of parsed RchConfig.
 // ============================================================================
@@ -76,7 +138,7 @@ fn config_dir_from_env_value(value: Option<&OsStr>) -> Option<PathBuf> {
 /// Bumping invalidates every operator's cache on next run — they pay one
 /// TOML parse, then the cache repopulates. Cheap insurance against silent
 /// deserialization drift.
-const CACHE_SCHEMA_VERSION: u32 = 3;
+const CACHE_SCHEMA_VERSION: u32 = 38;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 struct SourceFingerprint {
@@ -101,7 +163,8 @@ fn cache_dir() -> Option<PathBuf> {
     ProjectDirs::from("com", "rch", "rch").map(|dirs| dirs.cache_dir().to_path_buf())
 }
 
-fn cache_file_path() -> Option<PathBuf> {
+/// Config cache file; every `load_config` may recreate it.
+pub(crate) fn cache_file_path() -> Option<PathBuf> {
     cache_dir().map(|d| d.join("config.cache.json"))
 }
 
@@ -136,13 +199,17 @@ fn cache_is_disabled() -> bool {
 /// by the cache and by `load_config_uncached` so the source fingerprints
 /// match the actual files read.
 fn resolved_source_paths() -> Vec<PathBuf> {
-    let mut out = Vec::with_capacity(2);
+    let mut out = Vec::with_capacity(3);
     if let Some(dir) = config_dir() {
         out.push(dir.join("config.toml"));
     }
-    let project_config = std::env::current_dir()
+    let cwd = std::env::current_dir().ok();
+    if let Some(overlay) = cwd.as_deref().and_then(find_project_overlay) {
+        out.push(overlay);
+    }
+    let project_config = cwd
         .map(|dir| dir.join(".rch/config.toml"))
-        .unwrap_or_else(|_| PathBuf::from(".rch/config.toml"));
+        .unwrap_or_else(|| PathBuf::from(".rch/config.toml"));
     out.push(project_config);
     out
 }
@@ -275,21 +342,32 @@ fn load_config_uncached() -> Result<RchConfig> {
     let user_path = config_dir().map(|dir| dir.join("config.toml"));
     let user_path = user_path.as_deref().filter(|path| path.exists());
 
-    let project_path = std::env::current_dir()
+    let cwd = std::env::current_dir().ok();
+    let overlay_path = cwd.as_deref().and_then(find_project_overlay);
+    let project_path = cwd
         .map(|dir| dir.join(".rch/config.toml"))
-        .unwrap_or_else(|_| PathBuf::from(".rch/config.toml"));
+        .unwrap_or_else(|| PathBuf::from(".rch/config.toml"));
     let project_path = if project_path.exists() {
         Some(project_path.as_path())
     } else {
         None
     };
 
-    load_config_uncached_from_paths(user_path, project_path)
+    load_config_uncached_from_layers(user_path, overlay_path.as_deref(), project_path)
 }
 
+#[cfg(test)]
 fn load_config_uncached_from_paths(
     user_path: Option<&Path>,
     project_path: Option<&Path>,
+) -> Result<RchConfig> {
+    load_config_uncached_from_layers(user_path, None, project_path)
+}
+
+fn load_config_uncached_from_layers(
+    user_path: Option<&Path>,
+    overlay_path: Option<&Path>,
+    project_path: Option<&Path>,
 ) -> Result<RchConfig> {
     // Start with defaults
     let mut config = RchConfig::default();
@@ -300,6 +378,12 @@ fn load_config_uncached_from_paths(
         config = load_config_overlay(config, config_path)?;
     }
 
+    // Project overlay: whitelisted keys only
+    if let Some(config_path) = overlay_path {
+        debug!("Loading project overlay from {:?}", config_path);
+        config = load_project_overlay(config, config_path)?;
+    }
+
     // Try to load project config
     if let Some(config_path) = project_path {
         debug!("Loading project config from {:?}", config_path);
@@ -353,9 +437,72 @@ pub fn load_config() -> Result<RchConfig> {
     // (CLI > env > config > defaults). Recorded by main.rs at startup.
     crate::self_healing_overrides::apply_to(&mut config.self_healing);
 
+    install_mask_patterns(&config.logging);
+
+    Ok(config)
+}
+
+/// Install `logging.mask_patterns` for command masking in this process.
+///
+/// Invalid entries are warned about once, and only after the tracing
+/// subscriber exists: the first config load in `main` runs before logging is
+/// initialized, and its warnings would otherwise be lost.
+fn install_mask_patterns(logging: &rch_common::LoggingConfig) {
+    static WARNED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+    let errors = rch_common::util::set_custom_mask_patterns(&logging.mask_patterns);
+    if errors.is_empty()
+        || !tracing::dispatcher::has_been_set()
+        || WARNED.swap(true, std::sync::atomic::Ordering::Relaxed)
+    {
+        return;
+    }
+    for error in errors {
+        warn!("Ignoring logging.mask_patterns entry: {}", error);
+    }
+}
+
+/// Load the effective config from explicit paths, optionally as if `profile`
+/// were persisted as `general.profile` in the user config. A project config
+/// or `RCH_PROFILE` still take precedence, as they would at load time.
+pub(crate) fn load_config_with_user_profile(
+    user_path: Option<&Path>,
+    project_path: Option<&Path>,
+    profile: Option<&str>,
+) -> Result<RchConfig> {
+    load_config_with_user_profile_inner(user_path, project_path, profile, None)
+}
+
+fn load_config_with_user_profile_inner(
+    user_path: Option<&Path>,
+    project_path: Option<&Path>,
+    profile: Option<&str>,
+    env_overrides: Option<&HashMap<String, String>>,
+) -> Result<RchConfig> {
+    let mut config = RchConfig::default();
+    if let Some(path) = user_path {
+        config = load_config_overlay(config, path)?;
+    }
+    if let Some(profile) = profile {
+        config.general.profile = Some(profile.to_string());
+    }
+    if let Some(path) = project_path {
+        config = load_config_overlay(config, path)?;
+    }
+    apply_env_overrides_inner(&mut config, None, env_overrides);
+    crate::self_healing_overrides::apply_to(&mut config.self_healing);
     Ok(config)
 }
 
+/// Check that `dir` accepts new files by creating and removing a probe file.
+pub(crate) fn probe_dir_writable(dir: &Path) -> std::io::Result<()> {
+    let probe = dir.join(format!(".rch-write-probe-{}", std::process::id()));
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)?;
+    std::fs::remove_file(&probe)
+}
+
 /// Validation issues grouped by file.
 #[derive(Debug, Clone, Default)]
 pub struct FileValidation {
@@ -445,6 +592,26 @@ impl FileValidation {
         }
     }
 
+    /// Warn when a state directory is unusable: not a directory, or neither it
+    /// nor (if missing) its nearest existing ancestor accepts new files.
+    pub fn validate_dir_writable(&mut self, key: &str, path: &Path) {
+        if path.exists() && !path.is_dir() {
+            self.warn(format!("{}: not a directory: {}", key, path.display()));
+            return;
+        }
+        let Some(existing) = path.ancestors().find(|p| p.is_dir()) else {
+            return;
+        };
+        if let Err(e) = probe_dir_writable(existing) {
+            self.warn(format!(
+                "{}: directory is not writable: {} ({})",
+                key,
+                existing.display(),
+                e
+            ));
+        }
+    }
+
     /// Validate SSH key file permissions (Unix: should be 600 or 400).
     #[cfg(unix)]
     pub fn validate_ssh_key_permissions(&mut self, key: &str, path: &Path) {
@@ -577,6 +744,8 @@ struct PartialRchConfig {
 struct PartialPathTopologyConfig {
     canonical_root: Option<String>,
     alias_root: Option<String>,
+    wsl_mount_prefix: Option<String>,
+    keep_logical_symlinks: Option<bool>,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -584,8 +753,11 @@ struct PartialGeneralConfig {
     enabled: Option<bool>,
     force_local: Option<bool>,
     force_remote: Option<bool>,
+    confirm_first_offload: Option<bool>,
+    shadow_mode: Option<bool>,
     log_level: Option<String>,
     socket_path: Option<String>,
+    profile: Option<String>,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -600,6 +772,13 @@ struct PartialCompilationConfig {
     test_timeout_sec: Option<u64>,
     bun_timeout_sec: Option<u64>,
     external_timeout_enabled: Option<bool>,
+    remote_incremental: Option<bool>,
+    timeouts: Option<BTreeMap<String, u64>>,
+    require_rustc_match: Option<rch_common::RustcMatch>,
+    min_project_files: Option<u64>,
+    min_project_bytes: Option<u64>,
+    min_project_size_exempt_kinds: Option<Vec<String>>,
+    confidence_overrides: Option<BTreeMap<String, rch_common::ConfidenceOverride>>,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -611,9 +790,15 @@ struct PartialTransferConfig {
     ssh_control_persist_secs: Option<u64>,
     // Transfer optimization (bd-3hho)
     max_transfer_mb: Option<u64>,
+    max_project_bytes: Option<u64>,
     max_transfer_time_ms: Option<u64>,
     bwlimit_kbps: Option<u64>,
+    max_concurrent_client_transfers: Option<u32>,
     estimated_bandwidth_bps: Option<u64>,
+    max_sync_seconds: Option<u64>,
+    fleet: Option<String>,
+    min_free_disk_gb: Option<f64>,
+    container_image: Option<String>,
     // Adaptive compression (bd-243w)
     adaptive_compression: Option<bool>,
     min_compression_level: Option<u32>,
@@ -621,6 +806,21 @@ struct PartialTransferConfig {
     // Artifact verification (bd-377q)
     verify_artifacts: Option<bool>,
     verify_max_size_bytes: Option<u64>,
+    verify_sample_files: Option<usize>,
+    artifact_deny_patterns: Option<Vec<String>>,
+    preserve_newer_local: Option<bool>,
+    artifact_archive: Option<bool>,
+    artifact_max_retries: Option<u32>,
+    respect_gitignore: Option<bool>,
+    gitignore_include: Option<Vec<String>>,
+    force_include: Option<Vec<String>>,
+    shared_target_dir: Option<bool>,
+    keep_remote_on_failure: Option<bool>,
+    keep_remote_hours: Option<u64>,
+    detach_remote_builds: Option<bool>,
+    use_sccache: Option<bool>,
+    skip_artifacts_for: Option<Vec<String>>,
+    backend: Option<TransferBackend>,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -653,6 +853,7 @@ struct PartialSelfHealingConfig {
     #[serde(alias = "daemon_start_timeout", alias = "auto_start_timeout")]
     auto_start_timeout_secs: Option<u64>,
     self_healing_log_level: Option<SelfHealingLogLevel>,
+    state_dir: Option<String>,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -669,6 +870,10 @@ struct PartialSelfTestConfig {
 /// Load configuration with source tracking.
 pub fn load_config_with_sources() -> Result<LoadedConfig> {
     let user_path = config_dir().map(|d| d.join("config.toml"));
+    let overlay_path = std::env::current_dir()
+        .ok()
+        .as_deref()
+        .and_then(find_project_overlay);
     let project_path = PathBuf::from(".rch/config.toml");
 
     let user_path = user_path.as_deref().filter(|p| p.exists());
@@ -679,17 +884,28 @@ pub fn load_config_with_sources() -> Result<LoadedConfig> {
         None
     };
 
-    load_config_with_sources_from_paths(user_path, project_path, None)
+    load_config_with_sources_from_layers(user_path, overlay_path.as_deref(), project_path, None)
 }
 
+#[cfg(test)]
 fn load_config_with_sources_from_paths(
     user_path: Option<&Path>,
     project_path: Option<&Path>,
     env_overrides: Option<&HashMap<String, String>>,
+) -> Result<LoadedConfig> {
+    load_config_with_sources_from_layers(user_path, None, project_path, env_overrides)
+}
+
+fn load_config_with_sources_from_layers(
+    user_path: Option<&Path>,
+    overlay_path: Option<&Path>,
+    project_path: Option<&Path>,
+    env_overrides: Option<&HashMap<String, String>>,
 ) -> Result<LoadedConfig> {
     let user_path = user_path.filter(|path| path.exists());
+    let overlay_path = overlay_path.filter(|path| path.exists());
     let project_path = project_path.filter(|path| path.exists());
-    let mut config = load_config_uncached_from_paths(user_path, project_path)?;
+    let mut config = load_config_uncached_from_layers(user_path, overlay_path, project_path)?;
     let defaults = RchConfig::default();
     let mut sources = default_sources_map();
     let mut source_probe = defaults.clone();
@@ -709,6 +925,21 @@ fn load_config_with_sources_from_paths(
         );
     }
 
+    if let Some(path) = overlay_path {
+        debug!("Loading project overlay with sources from {:?}", path);
+        let (overlay, _) = read_project_overlay(path)?;
+        let layer: PartialRchConfig = overlay
+            .try_into()
+            .with_context(|| format!("Failed to decode partial config for {:?}", path))?;
+        apply_layer(
+            &mut source_probe,
+            &mut sources,
+            &layer,
+            &ConfigValueSource::ProjectConfig(path.to_path_buf()),
+            &defaults,
+        );
+    }
+
     if let Some(path) = project_path {
         debug!("Loading project config with sources from {:?}", path);
         let layer = load_partial_config(path)?;
@@ -748,6 +979,85 @@ fn load_config_overlay(base: RchConfig, path: &Path) -> Result<RchConfig> {
     let mut overlay: toml::Value =
         toml::from_str(&content).with_context(|| format!("Failed to parse {:?}", path))?;
     canonicalize_config_aliases(&mut overlay);
+    merge_config_overlay(base, overlay, path)
+}
+
+/// Nearest [`PROJECT_OVERLAY_FILE`] from `start` upward, stopping at the
+/// repository root (the first directory containing `.git`). Outside a
+/// repository the search stops below the home directory and at the first
+/// directory on another filesystem, so a stray file in `~` or `/` never
+/// applies to every project.
+pub fn find_project_overlay(start: &Path) -> Option<PathBuf> {
+    find_project_overlay_below(start, dirs::home_dir().as_deref())
+}
+
+fn find_project_overlay_below(start: &Path, home: Option<&Path>) -> Option<PathBuf> {
+    use std::os::unix::fs::MetadataExt;
+
+    let device = |dir: &Path| std::fs::metadata(dir).ok().map(|meta| meta.dev());
+    let start_device = device(start);
+    for dir in start.ancestors() {
+        if home == Some(dir) || device(dir) != start_device {
+            break;
+        }
+        let candidate = dir.join(PROJECT_OVERLAY_FILE);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        if dir.join(".git").exists() {
+            break;
+        }
+    }
+    None
+}
+
+/// Parse a project overlay and drop every key outside
+/// [`PROJECT_OVERLAY_KEYS`]. Returns the filtered overlay and the dotted
+/// names of the dropped keys.
+fn read_project_overlay(path: &Path) -> Result<(toml::Value, Vec<String>)> {
+    let content =
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read {:?}", path))?;
+    let mut overlay: toml::Value =
+        toml::from_str(&content).with_context(|| format!("Failed to parse {:?}", path))?;
+    canonicalize_config_aliases(&mut overlay);
+    let ignored = filter_project_overlay(&mut overlay);
+    Ok((overlay, ignored))
+}
+
+fn filter_project_overlay(overlay: &mut toml::Value) -> Vec<String> {
+    let mut ignored = Vec::new();
+    let toml::Value::Table(root) = overlay else {
+        return ignored;
+    };
+    root.retain(|section, value| {
+        let toml::Value::Table(table) = value else {
+            ignored.push(section.to_string());
+            return false;
+        };
+        table.retain(|key, _| {
+            let allowed = PROJECT_OVERLAY_KEYS.contains(&(section, key));
+            if !allowed {
+                ignored.push(format!("{section}.{key}"));
+            }
+            allowed
+        });
+        !table.is_empty()
+    });
+    ignored
+}
+
+fn load_project_overlay(base: RchConfig, path: &Path) -> Result<RchConfig> {
+    let (overlay, ignored) = read_project_overlay(path)?;
+    for key in &ignored {
+        warn!(
+            "Ignoring {} in {:?}: not overridable per project",
+            key, path
+        );
+    }
+    merge_config_overlay(base, overlay, path)
+}
+
+fn merge_config_overlay(base: RchConfig, overlay: toml::Value, path: &Path) -> Result<RchConfig> {
     let mut merged =
         toml::Value::try_from(base).context("Failed to encode base RCH config as TOML")?;
     merge_toml_overlay(&mut merged, overlay);
@@ -760,25 +1070,31 @@ fn canonicalize_config_aliases(value: &mut toml::Value) {
     let toml::Value::Table(root) = value else {
         return;
     };
-    let Some(toml::Value::Table(self_healing)) = root.get_mut("self_healing") else {
-        return;
-    };
+    if let Some(toml::Value::Table(self_healing)) = root.get_mut("self_healing") {
+        canonicalize_table_key(
+            self_healing,
+            "auto_start_cooldown",
+            "auto_start_cooldown_secs",
+        );
+        canonicalize_table_key(
+            self_healing,
+            "daemon_start_timeout",
+            "auto_start_timeout_secs",
+        );
+        canonicalize_table_key(
+            self_healing,
+            "auto_start_timeout",
+            "auto_start_timeout_secs",
+        );
+    }
 
-    canonicalize_table_key(
-        self_healing,
-        "auto_start_cooldown",
-        "auto_start_cooldown_secs",
-    );
-    canonicalize_table_key(
-        self_healing,
-        "daemon_start_timeout",
-        "auto_start_timeout_secs",
-    );
-    canonicalize_table_key(
-        self_healing,
-        "auto_start_timeout",
-        "auto_start_timeout_secs",
-    );
+    if let Some(toml::Value::Table(selection)) = root.get_mut("selection")
+        && let Some(toml::Value::Table(weights)) = selection.get_mut("weights")
+    {
+        canonicalize_table_key(weights, "speed", "speedscore");
+        canonicalize_table_key(weights, "free_slots", "slots");
+        canonicalize_table_key(weights, "affinity", "cache");
+    }
 }
 
 fn canonicalize_table_key(table: &mut toml::Table, alias: &str, canonical: &str) {
@@ -810,6 +1126,20 @@ fn merge_toml_overlay(base: &mut toml::Value, overlay: toml::Value) {
     }
 }
 
+/// Validate a classifier rules file (see [`classifier_rules_path`]).
+pub fn validate_classifier_rules_file(path: &Path) -> FileValidation {
+    let mut validation = FileValidation::new(path);
+    match std::fs::read_to_string(path) {
+        Ok(contents) => {
+            if let Err(err) = ClassifierRules::from_toml(&contents) {
+                validation.error(err.to_string());
+            }
+        }
+        Err(err) => validation.error(format!("Read failed: {}", err)),
+    }
+    validation
+}
+
 /// Validate a standard RCH config file (config.toml or .rch/config.toml).
 pub fn validate_rch_config_file(path: &Path) -> FileValidation {
     let mut validation = FileValidation::new(path);
@@ -829,6 +1159,65 @@ pub fn validate_rch_config_file(path: &Path) -> FileValidation {
         }
     };
 
+    validate_rch_config_values(&config, &mut validation);
+
+    if let Some(state_dir) = config.self_healing.state_dir.as_deref() {
+        let expanded = shellexpand::tilde(state_dir);
+        validation.validate_dir_writable("self_healing.state_dir", Path::new(expanded.as_ref()));
+    }
+    if !config.general.socket_path.trim().is_empty() {
+        let expanded = shellexpand::tilde(&config.general.socket_path);
+        let socket_path = Path::new(expanded.as_ref());
+        if !socket_path.exists() {
+            // Socket doesn't exist yet - check if parent directory is writable (bd-1g3l)
+            validation.validate_path_parent_writable("general.socket_path", socket_path);
+        }
+    }
+
+    validation
+}
+
+/// Validate a [`PROJECT_OVERLAY_FILE`]: keys it may not override are
+/// reported as warnings, and the values are checked as merged on top of the
+/// user config (`user_path`), which is how the overlay takes effect.
+pub fn validate_project_overlay_file(path: &Path, user_path: Option<&Path>) -> FileValidation {
+    let mut validation = FileValidation::new(path);
+    let (overlay, ignored) = match read_project_overlay(path) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            validation.error(format!("{:#}", err));
+            return validation;
+        }
+    };
+    for key in ignored {
+        validation.warn(format!(
+            "{key}: not overridable per project (ignored); set it in the user config"
+        ));
+    }
+
+    let base = user_path
+        .filter(|path| path.exists())
+        .and_then(|path| load_config_overlay(RchConfig::default(), path).ok())
+        .unwrap_or_default();
+    match merge_config_overlay(base, overlay, path) {
+        Ok(merged) => validate_rch_config_values(&merged, &mut validation),
+        Err(err) => validation.error(format!("{:#}", err)),
+    }
+    validation
+}
+
+/// Check the values of an RCH config, without touching the filesystem.
+///
+/// The subset of [`validate_rch_config_file`] that depends only on the config
+/// itself, so `rch config set` can refuse to persist an invalid combination.
+pub fn validate_rch_config_values(config: &RchConfig, validation: &mut FileValidation) {
+    if let Some(profile) = config.general.profile.as_deref()
+        && Profile::parse_named(profile).is_none()
+    {
+        validation.error(format!(
+            "general.profile must be one of dev, prod, test (got {profile:?})"
+        ));
+    }
     if config.compilation.confidence_threshold < 0.0
         || config.compilation.confidence_threshold > 1.0
     {
@@ -847,6 +1236,42 @@ pub fn validate_rch_config_file(path: &Path) -> FileValidation {
     if config.compilation.test_slots == 0 {
         validation.error("compilation.test_slots must be greater than 0".to_string());
     }
+    let weights = &config.selection.weights;
+    for (name, weight) in weights.factor_weights() {
+        if !weight.is_finite() || weight < 0.0 {
+            validation.error(format!(
+                "selection.weights.{name} must be a non-negative number"
+            ));
+        }
+    }
+    if let Some(min_memory) = config.selection.min_memory_gb
+        && (!min_memory.is_finite() || min_memory <= 0.0)
+    {
+        validation.error("selection.min_memory_gb must be a positive number".to_string());
+    }
+    let heartbeat_timeout = config.selection.heartbeat_timeout_secs;
+    if heartbeat_timeout > 0 && heartbeat_timeout < rch_common::MIN_HEARTBEAT_TIMEOUT_SECS {
+        validation.warn(format!(
+            "selection.heartbeat_timeout_secs = {} is below the {}s minimum; the daemon uses {}s",
+            heartbeat_timeout,
+            rch_common::MIN_HEARTBEAT_TIMEOUT_SECS,
+            rch_common::MIN_HEARTBEAT_TIMEOUT_SECS
+        ));
+    }
+    if !(0.0..=1.0).contains(&weights.half_open_penalty) {
+        validation
+            .error("selection.weights.half_open_penalty must be within [0.0, 1.0]".to_string());
+    }
+    if weights
+        .factor_weights()
+        .iter()
+        .all(|(_, weight)| *weight == 0.0)
+    {
+        validation.warn(
+            "selection.weights are all 0, so the balanced strategy cannot tell workers apart"
+                .to_string(),
+        );
+    }
     if config.compilation.check_slots == 0 {
         validation.error("compilation.check_slots must be greater than 0".to_string());
     }
@@ -856,6 +1281,47 @@ pub fn validate_rch_config_file(path: &Path) -> FileValidation {
     if config.compilation.test_timeout_sec == 0 {
         validation.error("compilation.test_timeout_sec must be greater than 0".to_string());
     }
+    for (kind, secs) in &config.compilation.timeouts {
+        if CompilationKind::from_name(kind).is_none() {
+            let known: Vec<&str> = CompilationKind::ALL.iter().map(|k| k.as_str()).collect();
+            validation.warn(format!(
+                "compilation.timeouts.{kind} is not a known compilation kind (expected one of: {})",
+                known.join(", ")
+            ));
+        } else if *secs == 0 {
+            validation.error(format!(
+                "compilation.timeouts.{kind} must be greater than 0"
+            ));
+        }
+    }
+    for kind in &config.compilation.min_project_size_exempt_kinds {
+        if CompilationKind::from_name(kind).is_none() {
+            validation.warn(format!(
+                "compilation.min_project_size_exempt_kinds entry {kind:?} is not a known compilation kind"
+            ));
+        }
+    }
+    for kind in &config.transfer.skip_artifacts_for {
+        if CompilationKind::from_name(kind).is_none() {
+            validation.warn(format!(
+                "transfer.skip_artifacts_for entry {kind:?} is not a known compilation kind"
+            ));
+        }
+    }
+    for (pattern, value) in &config.compilation.confidence_overrides {
+        if pattern.trim().is_empty() {
+            validation.warn(
+                "compilation.confidence_overrides has an empty key, which never matches"
+                    .to_string(),
+            );
+        } else if let rch_common::ConfidenceOverride::Confidence(confidence) = value
+            && !(0.0..=1.0).contains(confidence)
+        {
+            validation.error(format!(
+                "compilation.confidence_overrides.{pattern:?} must be within [0.0, 1.0]"
+            ));
+        }
+    }
 
     if config.self_healing.auto_start_cooldown_secs == 0 {
         validation
@@ -872,6 +1338,27 @@ pub fn validate_rch_config_file(path: &Path) -> FileValidation {
     } else if config.transfer.compression_level == 0 {
         validation.warn("transfer.compression_level is 0 (compression disabled)".to_string());
     }
+    if !config.transfer.min_free_disk_gb.is_finite() || config.transfer.min_free_disk_gb < 0.0 {
+        validation.error("transfer.min_free_disk_gb must be a non-negative number".to_string());
+    }
+
+    if let Some(image) = config.transfer.container_image.as_deref()
+        && let Err(e) = rch_common::validate_container_image(image)
+    {
+        validation.error(format!("transfer.container_image invalid: {}", e));
+    }
+    if config.transfer.max_project_bytes == Some(0) {
+        validation.error(
+            "transfer.max_project_bytes must be greater than 0; remove it to disable the cap"
+                .to_string(),
+        );
+    }
+    if config.transfer.bwlimit_kbps == Some(0) {
+        validation.warn(
+            "transfer.bwlimit_kbps is 0 (no bandwidth limit); set a positive KB/s value or remove it"
+                .to_string(),
+        );
+    }
 
     if let Some(interval) = config.transfer.ssh_server_alive_interval_secs {
         if interval > 0 && interval < 5 {
@@ -897,6 +1384,23 @@ pub fn validate_rch_config_file(path: &Path) -> FileValidation {
         ));
     }
 
+    if let Some(url) = config.notifications.webhook_url.as_deref()
+        && !(url.starts_with("http://") || url.starts_with("https://"))
+    {
+        validation.error("notifications.webhook_url must be an http(s) URL".to_string());
+    }
+
+    for error in rch_common::util::compile_mask_patterns(&config.logging.mask_patterns).1 {
+        validation.error(format!("logging.mask_patterns: {}", error));
+    }
+
+    if config.telemetry.streaming && config.telemetry.streaming_interval_secs == 0 {
+        validation.error(
+            "telemetry.streaming_interval_secs must be > 0 when telemetry.streaming is enabled"
+                .to_string(),
+        );
+    }
+
     if let Err(e) = validate_remote_base(&config.transfer.remote_base) {
         validation.error(format!("transfer.remote_base invalid: {}", e));
     }
@@ -915,13 +1419,6 @@ pub fn validate_rch_config_file(path: &Path) -> FileValidation {
 
     if config.general.socket_path.trim().is_empty() {
         validation.error("general.socket_path cannot be empty".to_string());
-    } else {
-        let expanded = shellexpand::tilde(&config.general.socket_path);
-        let socket_path = Path::new(expanded.as_ref());
-        if !socket_path.exists() {
-            // Socket doesn't exist yet - check if parent directory is writable (bd-1g3l)
-            validation.validate_path_parent_writable("general.socket_path", socket_path);
-        }
     }
 
     if config.general.force_local && config.general.force_remote {
@@ -944,6 +1441,21 @@ pub fn validate_rch_config_file(path: &Path) -> FileValidation {
     for (idx, pattern) in config.transfer.exclude_patterns.iter().enumerate() {
         validation.validate_rsync_pattern(&format!("transfer.exclude_patterns[{}]", idx), pattern);
     }
+    for (idx, pattern) in config.transfer.gitignore_include.iter().enumerate() {
+        validation.validate_rsync_pattern(&format!("transfer.gitignore_include[{}]", idx), pattern);
+    }
+    for (idx, pattern) in config.transfer.force_include.iter().enumerate() {
+        validation.validate_rsync_pattern(&format!("transfer.force_include[{}]", idx), pattern);
+    }
+    for (idx, pattern) in config.transfer.artifact_deny_patterns.iter().enumerate() {
+        validation.validate_rsync_pattern(
+            &format!("transfer.artifact_deny_patterns[{}]", idx),
+            pattern,
+        );
+    }
+    if config.transfer.keep_remote_on_failure && config.transfer.keep_remote_hours == 0 {
+        validation.error("transfer.keep_remote_hours must be greater than 0".to_string());
+    }
 
     // Validate remediation knobs (bd-...remediation-ocv9i.17.1). Errors are
     // genuine misconfigurations; warnings flag operator paths outside the
@@ -958,8 +1470,6 @@ pub fn validate_rch_config_file(path: &Path) -> FileValidation {
             }
         }
     }
-
-    validation
 }
 
 /// Validate a workers configuration file (workers.toml).
@@ -1087,53 +1597,58 @@ pub fn validate_workers_config_file(path: &Path) -> FileValidation {
             }
         };
         if identity_value.trim().is_empty() {
-            validation.error(format!(
-                "workers[{}] {} identity_file cannot be empty",
-                index,
-                if id.is_empty() { "(unknown id)" } else { &id }
-            ));
-        }
-
-        // Expand `~` and `$VAR` / `${VAR}` references so that paths like
-        // `$HOME/.ssh/id_ed25519` are handled. `shellexpand::full` fails if a
-        // referenced env var is not set, which we surface as a config error.
-        let identity_path = match shellexpand::full(identity_value) {
-            Ok(expanded) => PathBuf::from(expanded.into_owned()),
-            Err(err) => {
-                validation.error(format!(
-                    "[RCH-E009] workers[{}] {} identity_file is unresolvable ({:?}): {}",
+            // An empty identity_file means no `-i`: ssh authenticates with
+            // the keys held by the ssh-agent.
+            if !rch_common::ssh_agent_available() {
+                validation.warn(format!(
+                    "workers[{}] {} has no identity_file and SSH_AUTH_SOCK is not set; \
+                     ssh has no key to authenticate with",
                     index,
-                    if id.is_empty() { "(unknown id)" } else { &id },
-                    identity_value,
-                    err
+                    if id.is_empty() { "(unknown id)" } else { &id }
                 ));
-                continue;
             }
-        };
+        } else {
+            // Expand `~` and `$VAR` / `${VAR}` references so that paths like
+            // `$HOME/.ssh/id_ed25519` are handled. `shellexpand::full` fails if a
+            // referenced env var is not set, which we surface as a config error.
+            let identity_path = match shellexpand::full(identity_value) {
+                Ok(expanded) => PathBuf::from(expanded.into_owned()),
+                Err(err) => {
+                    validation.error(format!(
+                        "[RCH-E009] workers[{}] {} identity_file is unresolvable ({:?}): {}",
+                        index,
+                        if id.is_empty() { "(unknown id)" } else { &id },
+                        identity_value,
+                        err
+                    ));
+                    continue;
+                }
+            };
 
-        if !identity_path.exists() {
-            // Defer the error to the post-loop aggregation pass so that
-            // multiple workers referencing the same missing key produce one
-            // combined message rather than N copies.
-            let worker_label = if id.is_empty() {
-                format!("workers[{}]", index)
+            if !identity_path.exists() {
+                // Defer the error to the post-loop aggregation pass so that
+                // multiple workers referencing the same missing key produce one
+                // combined message rather than N copies.
+                let worker_label = if id.is_empty() {
+                    format!("workers[{}]", index)
+                } else {
+                    id.clone()
+                };
+                missing_identity_files
+                    .entry(identity_path.clone())
+                    .or_default()
+                    .push(worker_label);
             } else {
-                id.clone()
-            };
-            missing_identity_files
-                .entry(identity_path.clone())
-                .or_default()
-                .push(worker_label);
-        } else {
-            // SSH key exists - validate permissions (bd-1g3l)
-            validation.validate_ssh_key_permissions(
-                &format!(
-                    "workers[{}] {} identity_file",
-                    index,
-                    if id.is_empty() { "(unknown id)" } else { &id }
-                ),
-                &identity_path,
-            );
+                // SSH key exists - validate permissions (bd-1g3l)
+                validation.validate_ssh_key_permissions(
+                    &format!(
+                        "workers[{}] {} identity_file",
+                        index,
+                        if id.is_empty() { "(unknown id)" } else { &id }
+                    ),
+                    &identity_path,
+                );
+            }
         }
 
         if let Some(total_slots) = table.get("total_slots") {
@@ -1159,14 +1674,39 @@ pub fn validate_workers_config_file(path: &Path) -> FileValidation {
                 if id.is_empty() { "(unknown id)" } else { &id }
             ));
         }
-    }
 
-    // Emit one missing-identity_file error per unique path, listing every
-    // worker that references it. Aggregation is cleaner for the common case
-    // where several workers share one typo'd path, and still produces a
-    // specific message for the single-worker case.
-    for (path, worker_labels) in &missing_identity_files {
-        validation.error(format!(
+        if let Some(nice_level) = table.get("nice_level")
+            && !nice_level
+                .as_integer()
+                .and_then(|value| i32::try_from(value).ok())
+                .is_some_and(|value| rch_common::NICE_LEVEL_RANGE.contains(&value))
+        {
+            validation.error(format!(
+                "workers[{}] {} nice_level must be an integer between -20 and 19",
+                index,
+                if id.is_empty() { "(unknown id)" } else { &id }
+            ));
+        }
+        if let Some(ionice_class) = table.get("ionice_class")
+            && !ionice_class
+                .as_integer()
+                .and_then(|value| u8::try_from(value).ok())
+                .is_some_and(|value| rch_common::IONICE_CLASS_RANGE.contains(&value))
+        {
+            validation.error(format!(
+                "workers[{}] {} ionice_class must be 2 (best-effort) or 3 (idle)",
+                index,
+                if id.is_empty() { "(unknown id)" } else { &id }
+            ));
+        }
+    }
+
+    // Emit one missing-identity_file error per unique path, listing every
+    // worker that references it. Aggregation is cleaner for the common case
+    // where several workers share one typo'd path, and still produces a
+    // specific message for the single-worker case.
+    for (path, worker_labels) in &missing_identity_files {
+        validation.error(format!(
             "[RCH-E009] identity_file {} is missing; referenced by workers: {}. \
              Generate a key with `ssh-keygen -t ed25519 -f {}` or update workers.toml.",
             path.display(),
@@ -1184,8 +1724,11 @@ fn default_sources_map() -> ConfigSourceMap {
         "general.enabled",
         "general.force_local",
         "general.force_remote",
+        "general.confirm_first_offload",
+        "general.shadow_mode",
         "general.log_level",
         "general.socket_path",
+        "general.profile",
         "compilation.confidence_threshold",
         "compilation.min_local_time_ms",
         "compilation.remote_speedup_threshold",
@@ -1196,8 +1739,18 @@ fn default_sources_map() -> ConfigSourceMap {
         "compilation.test_timeout_sec",
         "compilation.bun_timeout_sec",
         "compilation.external_timeout_enabled",
+        "compilation.remote_incremental",
+        "compilation.timeouts",
+        "compilation.require_rustc_match",
+        "compilation.min_project_files",
+        "compilation.min_project_bytes",
+        "compilation.min_project_size_exempt_kinds",
+        "compilation.confidence_overrides",
         "transfer.compression_level",
         "transfer.exclude_patterns",
+        "transfer.min_free_disk_gb",
+        "transfer.container_image",
+        "transfer.artifact_max_retries",
         "environment.allowlist",
         "circuit.failure_threshold",
         "circuit.success_threshold",
@@ -1212,6 +1765,7 @@ fn default_sources_map() -> ConfigSourceMap {
         "self_healing.auto_start_cooldown_secs",
         "self_healing.auto_start_timeout_secs",
         "self_healing.self_healing_log_level",
+        "self_healing.state_dir",
         "self_test.enabled",
         "self_test.schedule",
         "self_test.interval",
@@ -1244,6 +1798,14 @@ fn apply_layer(
         config.general.force_remote = force_remote;
         set_source(sources, "general.force_remote", source.clone());
     }
+    if let Some(confirm) = layer.general.confirm_first_offload {
+        config.general.confirm_first_offload = confirm;
+        set_source(sources, "general.confirm_first_offload", source.clone());
+    }
+    if let Some(shadow) = layer.general.shadow_mode {
+        config.general.shadow_mode = shadow;
+        set_source(sources, "general.shadow_mode", source.clone());
+    }
     if let Some(log_level) = layer.general.log_level.as_ref() {
         config.general.log_level = log_level.clone();
         set_source(sources, "general.log_level", source.clone());
@@ -1252,6 +1814,10 @@ fn apply_layer(
         config.general.socket_path = socket_path.clone();
         set_source(sources, "general.socket_path", source.clone());
     }
+    if let Some(profile) = layer.general.profile.as_ref() {
+        config.general.profile = Some(profile.clone()).filter(|name| !name.is_empty());
+        set_source(sources, "general.profile", source.clone());
+    }
 
     if let Some(threshold) = layer.compilation.confidence_threshold {
         config.compilation.confidence_threshold = threshold;
@@ -1301,6 +1867,48 @@ fn apply_layer(
             source.clone(),
         );
     }
+    if let Some(remote_incremental) = layer.compilation.remote_incremental {
+        config.compilation.remote_incremental = remote_incremental;
+        set_source(sources, "compilation.remote_incremental", source.clone());
+    }
+    // Per-kind timeouts merge key by key, so a project can override one kind
+    // without restating the user-level table.
+    if let Some(timeouts) = &layer.compilation.timeouts {
+        config
+            .compilation
+            .timeouts
+            .extend(timeouts.iter().map(|(kind, secs)| (kind.clone(), *secs)));
+        set_source(sources, "compilation.timeouts", source.clone());
+    }
+    if let Some(require_rustc_match) = layer.compilation.require_rustc_match {
+        config.compilation.require_rustc_match = Some(require_rustc_match);
+        set_source(sources, "compilation.require_rustc_match", source.clone());
+    }
+    if let Some(min_project_files) = layer.compilation.min_project_files {
+        config.compilation.min_project_files = Some(min_project_files);
+        set_source(sources, "compilation.min_project_files", source.clone());
+    }
+    if let Some(min_project_bytes) = layer.compilation.min_project_bytes {
+        config.compilation.min_project_bytes = Some(min_project_bytes);
+        set_source(sources, "compilation.min_project_bytes", source.clone());
+    }
+    if let Some(kinds) = &layer.compilation.min_project_size_exempt_kinds {
+        config.compilation.min_project_size_exempt_kinds = kinds.clone();
+        set_source(
+            sources,
+            "compilation.min_project_size_exempt_kinds",
+            source.clone(),
+        );
+    }
+    // Confidence overrides merge key by key, like the per-kind timeouts.
+    if let Some(overrides) = &layer.compilation.confidence_overrides {
+        config.compilation.confidence_overrides.extend(
+            overrides
+                .iter()
+                .map(|(pattern, value)| (pattern.clone(), *value)),
+        );
+        set_source(sources, "compilation.confidence_overrides", source.clone());
+    }
 
     if let Some(compression) = layer.transfer.compression_level {
         config.transfer.compression_level = compression;
@@ -1340,6 +1948,10 @@ fn apply_layer(
         config.transfer.max_transfer_mb = Some(max_mb);
         set_source(sources, "transfer.max_transfer_mb", source.clone());
     }
+    if let Some(max_bytes) = layer.transfer.max_project_bytes {
+        config.transfer.max_project_bytes = Some(max_bytes);
+        set_source(sources, "transfer.max_project_bytes", source.clone());
+    }
     if let Some(max_time) = layer.transfer.max_transfer_time_ms {
         config.transfer.max_transfer_time_ms = Some(max_time);
         set_source(sources, "transfer.max_transfer_time_ms", source.clone());
@@ -1348,10 +1960,35 @@ fn apply_layer(
         config.transfer.bwlimit_kbps = Some(bwlimit);
         set_source(sources, "transfer.bwlimit_kbps", source.clone());
     }
+    if let Some(limit) = layer.transfer.max_concurrent_client_transfers {
+        config.transfer.max_concurrent_client_transfers = Some(limit);
+        set_source(
+            sources,
+            "transfer.max_concurrent_client_transfers",
+            source.clone(),
+        );
+    }
     if let Some(bandwidth) = layer.transfer.estimated_bandwidth_bps {
         config.transfer.estimated_bandwidth_bps = Some(bandwidth);
         set_source(sources, "transfer.estimated_bandwidth_bps", source.clone());
     }
+    if let Some(max_secs) = layer.transfer.max_sync_seconds {
+        config.transfer.max_sync_seconds = Some(max_secs);
+        set_source(sources, "transfer.max_sync_seconds", source.clone());
+    }
+    if let Some(fleet) = layer.transfer.fleet.as_ref() {
+        config.transfer.fleet = Some(fleet.clone()).filter(|name| !name.is_empty());
+        set_source(sources, "transfer.fleet", source.clone());
+    }
+    if let Some(min_free) = layer.transfer.min_free_disk_gb {
+        config.transfer.min_free_disk_gb = min_free;
+        set_source(sources, "transfer.min_free_disk_gb", source.clone());
+    }
+    if let Some(image) = layer.transfer.container_image.as_ref() {
+        config.transfer.container_image =
+            Some(image.trim().to_string()).filter(|image| !image.is_empty());
+        set_source(sources, "transfer.container_image", source.clone());
+    }
     // Adaptive compression (bd-243w)
     if let Some(adaptive) = layer.transfer.adaptive_compression {
         config.transfer.adaptive_compression = adaptive;
@@ -1374,6 +2011,66 @@ fn apply_layer(
         config.transfer.verify_max_size_bytes = max_size;
         set_source(sources, "transfer.verify_max_size_bytes", source.clone());
     }
+    if let Some(sample) = layer.transfer.verify_sample_files {
+        config.transfer.verify_sample_files = sample;
+        set_source(sources, "transfer.verify_sample_files", source.clone());
+    }
+    if let Some(patterns) = layer.transfer.artifact_deny_patterns.as_ref() {
+        config.transfer.artifact_deny_patterns = patterns.clone();
+        set_source(sources, "transfer.artifact_deny_patterns", source.clone());
+    }
+    if let Some(preserve) = layer.transfer.preserve_newer_local {
+        config.transfer.preserve_newer_local = preserve;
+        set_source(sources, "transfer.preserve_newer_local", source.clone());
+    }
+    if let Some(archive) = layer.transfer.artifact_archive {
+        config.transfer.artifact_archive = archive;
+        set_source(sources, "transfer.artifact_archive", source.clone());
+    }
+    if let Some(retries) = layer.transfer.artifact_max_retries {
+        config.transfer.artifact_max_retries = retries;
+        set_source(sources, "transfer.artifact_max_retries", source.clone());
+    }
+    if let Some(respect) = layer.transfer.respect_gitignore {
+        config.transfer.respect_gitignore = respect;
+        set_source(sources, "transfer.respect_gitignore", source.clone());
+    }
+    if let Some(shared) = layer.transfer.shared_target_dir {
+        config.transfer.shared_target_dir = shared;
+        set_source(sources, "transfer.shared_target_dir", source.clone());
+    }
+    if let Some(keep) = layer.transfer.keep_remote_on_failure {
+        config.transfer.keep_remote_on_failure = keep;
+        set_source(sources, "transfer.keep_remote_on_failure", source.clone());
+    }
+    if let Some(hours) = layer.transfer.keep_remote_hours {
+        config.transfer.keep_remote_hours = hours;
+        set_source(sources, "transfer.keep_remote_hours", source.clone());
+    }
+    if let Some(detach) = layer.transfer.detach_remote_builds {
+        config.transfer.detach_remote_builds = detach;
+        set_source(sources, "transfer.detach_remote_builds", source.clone());
+    }
+    if let Some(use_sccache) = layer.transfer.use_sccache {
+        config.transfer.use_sccache = use_sccache;
+        set_source(sources, "transfer.use_sccache", source.clone());
+    }
+    if let Some(kinds) = &layer.transfer.skip_artifacts_for {
+        config.transfer.skip_artifacts_for = kinds.clone();
+        set_source(sources, "transfer.skip_artifacts_for", source.clone());
+    }
+    if let Some(patterns) = layer.transfer.gitignore_include.as_ref() {
+        config.transfer.gitignore_include = patterns.clone();
+        set_source(sources, "transfer.gitignore_include", source.clone());
+    }
+    if let Some(patterns) = layer.transfer.force_include.as_ref() {
+        config.transfer.force_include = patterns.clone();
+        set_source(sources, "transfer.force_include", source.clone());
+    }
+    if let Some(backend) = layer.transfer.backend {
+        config.transfer.backend = backend;
+        set_source(sources, "transfer.backend", source.clone());
+    }
 
     if let Some(allowlist) = layer.environment.allowlist.as_ref() {
         config.environment.allowlist = allowlist.clone();
@@ -1450,6 +2147,10 @@ fn apply_layer(
             source.clone(),
         );
     }
+    if let Some(state_dir) = layer.self_healing.state_dir.as_ref() {
+        config.self_healing.state_dir = Some(state_dir.clone()).filter(|dir| !dir.is_empty());
+        set_source(sources, "self_healing.state_dir", source.clone());
+    }
 
     if let Some(enabled) = layer.self_test.enabled {
         config.self_test.enabled = enabled;
@@ -1496,6 +2197,20 @@ fn apply_layer(
         config.path_topology.alias_root = Some(alias_root.clone());
         set_source(sources, "path_topology.alias_root", source.clone());
     }
+    if let Some(prefix) = layer.path_topology.wsl_mount_prefix.as_ref()
+        && !prefix.is_empty()
+    {
+        config.path_topology.wsl_mount_prefix = Some(prefix.clone());
+        set_source(sources, "path_topology.wsl_mount_prefix", source.clone());
+    }
+    if let Some(keep) = layer.path_topology.keep_logical_symlinks {
+        config.path_topology.keep_logical_symlinks = Some(keep);
+        set_source(
+            sources,
+            "path_topology.keep_logical_symlinks",
+            source.clone(),
+        );
+    }
 }
 
 fn set_source(sources: &mut ConfigSourceMap, key: &str, source: ConfigValueSource) {
@@ -1575,6 +2290,19 @@ fn merge_config(mut base: RchConfig, overlay: RchConfig) -> RchConfig {
             .alias_root
             .clone_from(&overlay.path_topology.alias_root);
     }
+    if overlay
+        .path_topology
+        .wsl_mount_prefix
+        .as_deref()
+        .is_some_and(|s| !s.is_empty())
+    {
+        base.path_topology
+            .wsl_mount_prefix
+            .clone_from(&overlay.path_topology.wsl_mount_prefix);
+    }
+    if overlay.path_topology.keep_logical_symlinks.is_some() {
+        base.path_topology.keep_logical_symlinks = overlay.path_topology.keep_logical_symlinks;
+    }
 
     base
 }
@@ -1596,12 +2324,21 @@ fn merge_general(
     if overlay.force_remote != default.force_remote {
         base.force_remote = overlay.force_remote;
     }
+    if overlay.confirm_first_offload != default.confirm_first_offload {
+        base.confirm_first_offload = overlay.confirm_first_offload;
+    }
+    if overlay.shadow_mode != default.shadow_mode {
+        base.shadow_mode = overlay.shadow_mode;
+    }
     if overlay.log_level != default.log_level {
         base.log_level.clone_from(&overlay.log_level);
     }
     if overlay.socket_path != default.socket_path {
         base.socket_path.clone_from(&overlay.socket_path);
     }
+    if overlay.profile != default.profile {
+        base.profile.clone_from(&overlay.profile);
+    }
 }
 
 /// Merge CompilationConfig fields.
@@ -1643,6 +2380,33 @@ fn merge_compilation(
     if overlay.external_timeout_enabled != default.external_timeout_enabled {
         base.external_timeout_enabled = overlay.external_timeout_enabled;
     }
+    if overlay.remote_incremental != default.remote_incremental {
+        base.remote_incremental = overlay.remote_incremental;
+    }
+    base.timeouts.extend(
+        overlay
+            .timeouts
+            .iter()
+            .map(|(kind, secs)| (kind.clone(), *secs)),
+    );
+    if overlay.require_rustc_match != default.require_rustc_match {
+        base.require_rustc_match = overlay.require_rustc_match;
+    }
+    if overlay.min_project_files != default.min_project_files {
+        base.min_project_files = overlay.min_project_files;
+    }
+    if overlay.min_project_bytes != default.min_project_bytes {
+        base.min_project_bytes = overlay.min_project_bytes;
+    }
+    if overlay.min_project_size_exempt_kinds != default.min_project_size_exempt_kinds {
+        base.min_project_size_exempt_kinds = overlay.min_project_size_exempt_kinds.clone();
+    }
+    base.confidence_overrides.extend(
+        overlay
+            .confidence_overrides
+            .iter()
+            .map(|(pattern, value)| (pattern.clone(), *value)),
+    );
 }
 
 /// Merge TransferConfig fields.
@@ -1684,15 +2448,33 @@ fn merge_transfer(
     if overlay.max_transfer_mb != default.max_transfer_mb {
         base.max_transfer_mb = overlay.max_transfer_mb;
     }
+    if overlay.max_project_bytes != default.max_project_bytes {
+        base.max_project_bytes = overlay.max_project_bytes;
+    }
     if overlay.max_transfer_time_ms != default.max_transfer_time_ms {
         base.max_transfer_time_ms = overlay.max_transfer_time_ms;
     }
     if overlay.bwlimit_kbps != default.bwlimit_kbps {
         base.bwlimit_kbps = overlay.bwlimit_kbps;
     }
+    if overlay.max_concurrent_client_transfers != default.max_concurrent_client_transfers {
+        base.max_concurrent_client_transfers = overlay.max_concurrent_client_transfers;
+    }
     if overlay.estimated_bandwidth_bps != default.estimated_bandwidth_bps {
         base.estimated_bandwidth_bps = overlay.estimated_bandwidth_bps;
     }
+    if overlay.max_sync_seconds != default.max_sync_seconds {
+        base.max_sync_seconds = overlay.max_sync_seconds;
+    }
+    if overlay.fleet != default.fleet {
+        base.fleet = overlay.fleet.clone();
+    }
+    if overlay.min_free_disk_gb != default.min_free_disk_gb {
+        base.min_free_disk_gb = overlay.min_free_disk_gb;
+    }
+    if overlay.container_image != default.container_image {
+        base.container_image = overlay.container_image.clone();
+    }
     // Adaptive compression (bd-243w)
     if overlay.adaptive_compression != default.adaptive_compression {
         base.adaptive_compression = overlay.adaptive_compression;
@@ -1710,6 +2492,54 @@ fn merge_transfer(
     if overlay.verify_max_size_bytes != default.verify_max_size_bytes {
         base.verify_max_size_bytes = overlay.verify_max_size_bytes;
     }
+    if overlay.verify_sample_files != default.verify_sample_files {
+        base.verify_sample_files = overlay.verify_sample_files;
+    }
+    if overlay.artifact_deny_patterns != default.artifact_deny_patterns {
+        base.artifact_deny_patterns
+            .clone_from(&overlay.artifact_deny_patterns);
+    }
+    if overlay.preserve_newer_local != default.preserve_newer_local {
+        base.preserve_newer_local = overlay.preserve_newer_local;
+    }
+    if overlay.artifact_archive != default.artifact_archive {
+        base.artifact_archive = overlay.artifact_archive;
+    }
+    if overlay.artifact_max_retries != default.artifact_max_retries {
+        base.artifact_max_retries = overlay.artifact_max_retries;
+    }
+    if overlay.respect_gitignore != default.respect_gitignore {
+        base.respect_gitignore = overlay.respect_gitignore;
+    }
+    if overlay.shared_target_dir != default.shared_target_dir {
+        base.shared_target_dir = overlay.shared_target_dir;
+    }
+    if overlay.keep_remote_on_failure != default.keep_remote_on_failure {
+        base.keep_remote_on_failure = overlay.keep_remote_on_failure;
+    }
+    if overlay.keep_remote_hours != default.keep_remote_hours {
+        base.keep_remote_hours = overlay.keep_remote_hours;
+    }
+    if overlay.detach_remote_builds != default.detach_remote_builds {
+        base.detach_remote_builds = overlay.detach_remote_builds;
+    }
+    if overlay.use_sccache != default.use_sccache {
+        base.use_sccache = overlay.use_sccache;
+    }
+    if overlay.skip_artifacts_for != default.skip_artifacts_for {
+        base.skip_artifacts_for
+            .clone_from(&overlay.skip_artifacts_for);
+    }
+    if overlay.gitignore_include != default.gitignore_include {
+        base.gitignore_include
+            .clone_from(&overlay.gitignore_include);
+    }
+    if overlay.force_include != default.force_include {
+        base.force_include.clone_from(&overlay.force_include);
+    }
+    if overlay.backend != default.backend {
+        base.backend = overlay.backend;
+    }
 }
 
 /// Merge EnvironmentConfig fields.
@@ -1786,6 +2616,9 @@ fn merge_self_healing(
     if overlay.auto_start_timeout_secs != default.auto_start_timeout_secs {
         base.auto_start_timeout_secs = overlay.auto_start_timeout_secs;
     }
+    if overlay.state_dir != default.state_dir {
+        base.state_dir.clone_from(&overlay.state_dir);
+    }
 }
 
 /// Merge SelfTestConfig fields.
@@ -1886,6 +2719,42 @@ fn apply_env_overrides_inner(
         }
     }
 
+    // The active profile only fills in the log level when neither a config
+    // file nor RCH_LOG_LEVEL (see below) sets one explicitly.
+    if let Some(val) = get_env("RCH_PROFILE") {
+        config.general.profile = Some(val.trim().to_string()).filter(|name| !name.is_empty());
+        if let Some(ref mut sources) = sources {
+            set_source(
+                sources,
+                "general.profile",
+                ConfigValueSource::EnvVar("RCH_PROFILE".to_string()),
+            );
+        }
+    }
+    let log_level_configured = match sources.as_deref() {
+        Some(sources) => sources
+            .get("general.log_level")
+            .is_some_and(|source| *source != ConfigValueSource::Default),
+        None => config.general.log_level != RchConfig::default().general.log_level,
+    };
+    if !log_level_configured
+        && let Some(level) = config
+            .general
+            .profile
+            .as_deref()
+            .and_then(Profile::parse_named)
+            .and_then(|profile| profile.log_level())
+    {
+        config.general.log_level = level.to_string();
+        if let Some(ref mut sources) = sources {
+            let source = sources
+                .get("general.profile")
+                .cloned()
+                .unwrap_or(ConfigValueSource::Default);
+            set_source(sources, "general.log_level", source);
+        }
+    }
+
     if let Some(val) = get_env("RCH_LOG_LEVEL") {
         config.general.log_level = val;
         if let Some(ref mut sources) = sources {
@@ -2119,6 +2988,29 @@ fn apply_env_overrides_inner(
         }
     }
 
+    if let Some(val) = get_env("RCH_FLEET") {
+        config.transfer.fleet = Some(val.trim().to_string()).filter(|name| !name.is_empty());
+        if let Some(ref mut sources) = sources {
+            set_source(
+                sources,
+                "transfer.fleet",
+                ConfigValueSource::EnvVar("RCH_FLEET".to_string()),
+            );
+        }
+    }
+
+    if let Some(val) = get_env("RCH_CONTAINER_IMAGE") {
+        config.transfer.container_image =
+            Some(val.trim().to_string()).filter(|image| !image.is_empty());
+        if let Some(ref mut sources) = sources {
+            set_source(
+                sources,
+                "transfer.container_image",
+                ConfigValueSource::EnvVar("RCH_CONTAINER_IMAGE".to_string()),
+            );
+        }
+    }
+
     if let Some(val) = get_env("RCH_ENV_ALLOWLIST") {
         let allowlist = parse_allowlist_value(&val);
         config.environment.allowlist = allowlist;
@@ -2164,6 +3056,18 @@ fn apply_env_overrides_inner(
         }
     }
 
+    // Not gated on RCH_NO_SELF_HEALING: the state directory is where, not whether.
+    if let Some(val) = get_env("RCH_STATE_DIR") {
+        config.self_healing.state_dir = Some(val.trim().to_string()).filter(|dir| !dir.is_empty());
+        if let Some(ref mut sources) = sources {
+            set_source(
+                sources,
+                "self_healing.state_dir",
+                ConfigValueSource::EnvVar("RCH_STATE_DIR".to_string()),
+            );
+        }
+    }
+
     let mut self_healing_master_disabled = false;
     if let Some(val) = get_env("RCH_NO_SELF_HEALING")
         && parse_bool(&val).unwrap_or(false)
@@ -2262,6 +3166,28 @@ fn apply_env_overrides_inner(
             );
         }
     }
+    if let Some(val) = get_env("RCH_WSL_MOUNT_PREFIX") {
+        config.path_topology.wsl_mount_prefix = Some(val);
+        if let Some(ref mut sources) = sources {
+            set_source(
+                sources,
+                "path_topology.wsl_mount_prefix",
+                ConfigValueSource::EnvVar("RCH_WSL_MOUNT_PREFIX".to_string()),
+            );
+        }
+    }
+    if let Some(val) = get_env("RCH_KEEP_LOGICAL_SYMLINKS")
+        && let Some(keep) = parse_bool(&val)
+    {
+        config.path_topology.keep_logical_symlinks = Some(keep);
+        if let Some(ref mut sources) = sources {
+            set_source(
+                sources,
+                "path_topology.keep_logical_symlinks",
+                ConfigValueSource::EnvVar("RCH_KEEP_LOGICAL_SYMLINKS".to_string()),
+            );
+        }
+    }
 
     // Remediation knob overrides (bd-...remediation-ocv9i.17.1). A focused set of
     // the highest-signal knobs; the full surface is driven by TOML layering.
@@ -2391,6 +3317,15 @@ pub struct WorkerEntry {
     /// Whether this worker is enabled.
     #[serde(default = "default_true")]
     pub enabled: bool,
+    /// Maximum builds running at once, independent of free slots.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_builds: Option<u32>,
+    /// Niceness (`nice -n`) for remote builds on this worker.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nice_level: Option<i32>,
+    /// I/O scheduling class (`ionice -c`) for remote builds on this worker.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ionice_class: Option<u8>,
 }
 
 fn default_user() -> String {
@@ -2684,6 +3619,299 @@ mod tests {
         info!("TEST PASS: test_validate_threshold_range");
     }
 
+    #[test]
+    fn test_notifications_section_loads_and_validates_url() {
+        let _guard = test_guard!();
+        let temp_dir = tempfile::tempdir().expect("tempdir");
+        let config_path = temp_dir.path().join("config.toml");
+        std::fs::write(
+            &config_path,
+            "[notifications]\nthreshold_secs = 120\nwebhook_url = \"https://hooks.example/rch\"\n",
+        )
+        .expect("write config");
+
+        let loaded = load_config_with_sources_from_paths(Some(&config_path), None, None)
+            .expect("load with sources");
+        assert_eq!(loaded.config.notifications.threshold_secs, 120);
+        assert_eq!(
+            loaded.config.notifications.webhook_url.as_deref(),
+            Some("https://hooks.example/rch")
+        );
+        assert!(validate_rch_config_file(&config_path).errors.is_empty());
+
+        std::fs::write(
+            &config_path,
+            "[notifications]\nwebhook_url = \"hooks.example/rch\"\n",
+        )
+        .expect("write config");
+        let result = validate_rch_config_file(&config_path);
+        assert!(
+            result
+                .errors
+                .iter()
+                .any(|e| e.contains("notifications.webhook_url")),
+            "errors: {:?}",
+            result.errors
+        );
+    }
+
+    #[test]
+    fn test_logging_mask_patterns_load_and_validate() {
+        let _guard = test_guard!();
+        let temp_dir = tempfile::tempdir().expect("tempdir");
+        let config_path = temp_dir.path().join("config.toml");
+        std::fs::write(
+            &config_path,
+            "[logging]\nmask_patterns = [\"corp_tok_[a-z0-9]+\"]\n",
+        )
+        .expect("write config");
+
+        let loaded = load_config_with_sources_from_paths(Some(&config_path), None, None)
+            .expect("load with sources");
+        assert_eq!(
+            loaded.config.logging.mask_patterns,
+            vec!["corp_tok_[a-z0-9]+".to_string()]
+        );
+        assert!(validate_rch_config_file(&config_path).errors.is_empty());
+
+        std::fs::write(&config_path, "[logging]\nmask_patterns = [\"corp_(\"]\n")
+            .expect("write config");
+        let result = validate_rch_config_file(&config_path);
+        assert!(
+            result
+                .errors
+                .iter()
+                .any(|e| e.contains("logging.mask_patterns")),
+            "errors: {:?}",
+            result.errors
+        );
+    }
+
+    #[test]
+    fn test_telemetry_section_loads_and_validates_interval() {
+        let _guard = test_guard!();
+        let temp_dir = tempfile::tempdir().expect("tempdir");
+        let config_path = temp_dir.path().join("config.toml");
+        std::fs::write(
+            &config_path,
+            "[telemetry]\nstreaming = true\nstreaming_interval_secs = 15\n",
+        )
+        .expect("write config");
+
+        let loaded = load_config_with_sources_from_paths(Some(&config_path), None, None)
+            .expect("load with sources");
+        assert_eq!(loaded.config.telemetry.streaming_interval(), Some(15));
+        assert!(validate_rch_config_file(&config_path).errors.is_empty());
+
+        std::fs::write(
+            &config_path,
+            "[telemetry]\nstreaming = true\nstreaming_interval_secs = 0\n",
+        )
+        .expect("write config");
+        let result = validate_rch_config_file(&config_path);
+        assert!(
+            result
+                .errors
+                .iter()
+                .any(|e| e.contains("telemetry.streaming_interval_secs")),
+            "errors: {:?}",
+            result.errors
+        );
+    }
+
+    #[test]
+    fn test_transfer_container_image_loads_and_validates() {
+        let _guard = test_guard!();
+        let temp_dir = tempfile::tempdir().expect("tempdir");
+        let config_path = temp_dir.path().join("config.toml");
+        std::fs::write(
+            &config_path,
+            "[transfer]\ncontainer_image = \"rust:1.85-bookworm\"\n",
+        )
+        .expect("write config");
+
+        let loaded = load_config_with_sources_from_paths(Some(&config_path), None, None)
+            .expect("load with sources");
+        assert_eq!(
+            loaded.config.transfer.container_image.as_deref(),
+            Some("rust:1.85-bookworm")
+        );
+        assert!(validate_rch_config_file(&config_path).errors.is_empty());
+
+        std::fs::write(
+            &config_path,
+            "[transfer]\ncontainer_image = \"rust --privileged\"\n",
+        )
+        .expect("write config");
+        let result = validate_rch_config_file(&config_path);
+        assert!(
+            result
+                .errors
+                .iter()
+                .any(|e| e.contains("transfer.container_image")),
+            "errors: {:?}",
+            result.errors
+        );
+    }
+
+    #[test]
+    fn test_selection_weights_aliases_and_validation() {
+        let _guard = test_guard!();
+        let temp_dir = tempfile::tempdir().expect("tempdir");
+        let config_path = temp_dir.path().join("config.toml");
+        std::fs::write(
+            &config_path,
+            "[selection.weights]\nspeed = 0.2\nfree_slots = 0.3\nlow_load = 0.4\naffinity = 0.9\n",
+        )
+        .expect("write config");
+
+        let loaded = load_config_with_sources_from_paths(Some(&config_path), None, None)
+            .expect("load with sources");
+        let weights = &loaded.config.selection.weights;
+        assert_eq!(weights.speedscore, 0.2);
+        assert_eq!(weights.slots, 0.3);
+        assert_eq!(weights.low_load, 0.4);
+        assert_eq!(weights.cache, 0.9);
+        // Unset weights keep the defaults.
+        assert_eq!(weights.health, 0.3);
+        assert!(validate_rch_config_file(&config_path).errors.is_empty());
+
+        let mut config = RchConfig::default();
+        assert_eq!(config.selection.weights.low_load, 0.0);
+        config.selection.weights.cache = -1.0;
+        config.selection.weights.half_open_penalty = 2.0;
+        let mut validation = FileValidation::new(Path::new("config.toml"));
+        validate_rch_config_values(&config, &mut validation);
+        assert!(
+            validation
+                .errors
+                .iter()
+                .any(|e| e.contains("selection.weights.cache must be a non-negative number"))
+        );
+        assert!(
+            validation
+                .errors
+                .iter()
+                .any(|e| e.contains("selection.weights.half_open_penalty"))
+        );
+    }
+
+    #[test]
+    fn test_transfer_artifact_max_retries_loads() {
+        let _guard = test_guard!();
+        let temp_dir = tempfile::tempdir().expect("tempdir");
+        let config_path = temp_dir.path().join("config.toml");
+        std::fs::write(&config_path, "[transfer]\nartifact_max_retries = 5\n")
+            .expect("write config");
+
+        let loaded = load_config_with_sources_from_paths(Some(&config_path), None, None)
+            .expect("load with sources");
+        assert_eq!(loaded.config.transfer.artifact_max_retries, 5);
+        assert_eq!(RchConfig::default().transfer.artifact_max_retries, 2);
+        assert!(validate_rch_config_file(&config_path).errors.is_empty());
+    }
+
+    #[test]
+    fn test_transfer_artifact_archive_loads_with_source() {
+        let _guard = test_guard!();
+        let temp_dir = tempfile::tempdir().expect("tempdir");
+        let user = temp_dir.path().join("user.toml");
+        let project = temp_dir.path().join("project.toml");
+        std::fs::write(&user, "[transfer]\nartifact_archive = true\n").expect("write user");
+        std::fs::write(&project, "[transfer]\ncompression_level = 5\n").expect("write project");
+
+        let loaded = load_config_with_sources_from_paths(Some(&user), Some(&project), None)
+            .expect("load with sources");
+        assert!(loaded.config.transfer.artifact_archive);
+        assert!(!RchConfig::default().transfer.artifact_archive);
+        assert_eq!(
+            loaded.sources.get("transfer.artifact_archive"),
+            Some(&ConfigValueSource::UserConfig(user))
+        );
+    }
+
+    #[test]
+    fn test_transfer_use_sccache_loads_with_source() {
+        let _guard = test_guard!();
+        let temp_dir = tempfile::tempdir().expect("tempdir");
+        let user = temp_dir.path().join("user.toml");
+        let project = temp_dir.path().join("project.toml");
+        std::fs::write(&user, "[transfer]\ncompression_level = 5\n").expect("write user");
+        std::fs::write(&project, "[transfer]\nuse_sccache = true\n").expect("write project");
+
+        let loaded = load_config_with_sources_from_paths(Some(&user), Some(&project), None)
+            .expect("load with sources");
+        assert!(loaded.config.transfer.use_sccache);
+        assert!(!RchConfig::default().transfer.use_sccache);
+        assert_eq!(
+            loaded.sources.get("transfer.use_sccache"),
+            Some(&ConfigValueSource::ProjectConfig(project))
+        );
+    }
+
+    #[test]
+    fn test_transfer_skip_artifacts_for_project_override() {
+        let _guard = test_guard!();
+        let temp_dir = tempfile::tempdir().expect("tempdir");
+        let user = temp_dir.path().join("user.toml");
+        let project = temp_dir.path().join("project.toml");
+        std::fs::write(
+            &user,
+            "[transfer]\nskip_artifacts_for = [\"cargo_check\"]\n",
+        )
+        .expect("write user");
+        std::fs::write(&project, "[transfer]\nskip_artifacts_for = []\n").expect("write project");
+
+        let loaded = load_config_with_sources_from_paths(Some(&user), Some(&project), None)
+            .expect("load with sources");
+        assert!(loaded.config.transfer.skip_artifacts_for.is_empty());
+        assert_eq!(
+            loaded.sources.get("transfer.skip_artifacts_for"),
+            Some(&ConfigValueSource::ProjectConfig(project))
+        );
+    }
+
+    #[test]
+    fn test_validate_state_dir_warns_when_unusable() {
+        let _guard = test_guard!();
+        let temp_dir = tempfile::tempdir().expect("tempdir");
+        let not_a_dir = temp_dir.path().join("state");
+        std::fs::write(&not_a_dir, "").expect("write file");
+        let config_path = temp_dir.path().join("config.toml");
+
+        std::fs::write(
+            &config_path,
+            format!("[self_healing]\nstate_dir = {:?}\n", not_a_dir),
+        )
+        .expect("write config");
+        let result = validate_rch_config_file(&config_path);
+        assert!(result.errors.is_empty(), "errors: {:?}", result.errors);
+        assert!(
+            result
+                .warnings
+                .iter()
+                .any(|w| w.contains("self_healing.state_dir") && w.contains("not a directory")),
+            "warnings: {:?}",
+            result.warnings
+        );
+
+        // A missing directory under a writable parent is created on demand.
+        std::fs::write(
+            &config_path,
+            format!(
+                "[self_healing]\nstate_dir = {:?}\n",
+                temp_dir.path().join("missing").join("rch")
+            ),
+        )
+        .expect("write config");
+        let result = validate_rch_config_file(&config_path);
+        assert!(
+            result.warnings.is_empty(),
+            "warnings: {:?}",
+            result.warnings
+        );
+    }
+
     #[test]
     fn test_validate_env_allowlist_invalid_key() {
         let _guard = test_guard!();
@@ -2802,6 +4030,33 @@ total_slots = 1
         );
     }
 
+    #[test]
+    fn test_validate_workers_empty_identity_file_uses_agent() {
+        let _guard = test_guard!();
+        let mut file = NamedTempFile::new().expect("create temp file");
+        let workers_toml = r#"
+[[workers]]
+id = "agent-1"
+host = "10.0.0.8"
+user = "builder"
+identity_file = ""
+total_slots = 8
+"#;
+        std::io::Write::write_all(file.as_file_mut(), workers_toml.as_bytes())
+            .expect("write workers config");
+        let result = validate_workers_config_file(file.path());
+        assert!(
+            !result.errors.iter().any(|e| e.contains("identity_file")),
+            "empty identity_file defers to the ssh-agent: {:?}",
+            result.errors
+        );
+        let warned = result
+            .warnings
+            .iter()
+            .any(|w| w.contains("SSH_AUTH_SOCK is not set"));
+        assert_eq!(warned, !rch_common::ssh_agent_available());
+    }
+
     #[test]
     fn test_validate_workers_missing_user() {
         let _guard = test_guard!();
@@ -2822,6 +4077,37 @@ total_slots = 8
         info!("TEST PASS: test_validate_workers_missing_user");
     }
 
+    #[test]
+    fn test_validate_workers_priority_ranges() {
+        let _guard = test_guard!();
+        let mut file = NamedTempFile::new().expect("create temp file");
+        let workers_toml = r#"
+[[workers]]
+id = "desk"
+host = "10.0.0.8"
+user = "builder"
+identity_file = "/tmp/id_ed25519"
+total_slots = 8
+nice_level = 25
+ionice_class = 0
+"#;
+        std::io::Write::write_all(file.as_file_mut(), workers_toml.as_bytes())
+            .expect("write workers config");
+        let result = validate_workers_config_file(file.path());
+        assert!(
+            result
+                .errors
+                .iter()
+                .any(|e| e.contains("desk nice_level must be an integer between -20 and 19"))
+        );
+        assert!(
+            result
+                .errors
+                .iter()
+                .any(|e| e.contains("desk ionice_class must be 2"))
+        );
+    }
+
     #[test]
     fn test_validate_workers_missing_total_slots_warns() {
         let _guard = test_guard!();
@@ -3169,6 +4455,105 @@ min_local_time_ms = 2000
         );
     }
 
+    #[test]
+    fn test_profile_supplies_log_level_below_env() {
+        let _guard = test_guard!();
+        let dir = tempfile::tempdir().expect("tempdir");
+        let user_path = dir.path().join("user.toml");
+        std::fs::write(&user_path, "[general]\nprofile = \"prod\"\n").expect("write user config");
+
+        let no_env = HashMap::new();
+        let loaded = load_config_with_sources_from_paths(Some(&user_path), None, Some(&no_env))
+            .expect("load with sources");
+        assert_eq!(loaded.config.general.profile.as_deref(), Some("prod"));
+        assert_eq!(loaded.config.general.log_level, "warn");
+        assert_eq!(
+            loaded.sources.get("general.log_level"),
+            Some(&ConfigValueSource::UserConfig(user_path.clone()))
+        );
+
+        let mut env = HashMap::new();
+        env.insert("RCH_PROFILE".to_string(), "dev".to_string());
+        let loaded = load_config_with_sources_from_paths(Some(&user_path), None, Some(&env))
+            .expect("load with sources");
+        assert_eq!(loaded.config.general.log_level, "debug");
+        assert_eq!(
+            loaded.sources.get("general.profile"),
+            Some(&ConfigValueSource::EnvVar("RCH_PROFILE".to_string()))
+        );
+
+        env.insert("RCH_LOG_LEVEL".to_string(), "error".to_string());
+        let loaded = load_config_with_sources_from_paths(Some(&user_path), None, Some(&env))
+            .expect("load with sources");
+        assert_eq!(loaded.config.general.log_level, "error");
+    }
+
+    #[test]
+    fn test_explicit_log_level_wins_over_profile() {
+        let _guard = test_guard!();
+        let dir = tempfile::tempdir().expect("tempdir");
+        let user_path = dir.path().join("user.toml");
+        std::fs::write(
+            &user_path,
+            "[general]\nprofile = \"prod\"\nlog_level = \"trace\"\n",
+        )
+        .expect("write user config");
+
+        let no_env = HashMap::new();
+        let loaded = load_config_with_sources_from_paths(Some(&user_path), None, Some(&no_env))
+            .expect("load with sources");
+        assert_eq!(loaded.config.general.log_level, "trace");
+        assert_eq!(
+            loaded.sources.get("general.log_level"),
+            Some(&ConfigValueSource::UserConfig(user_path.clone()))
+        );
+
+        let mut env = HashMap::new();
+        env.insert("RCH_PROFILE".to_string(), "dev".to_string());
+        let loaded = load_config_with_sources_from_paths(Some(&user_path), None, Some(&env))
+            .expect("load with sources");
+        assert_eq!(loaded.config.general.log_level, "trace");
+    }
+
+    #[test]
+    fn test_user_profile_preview_keeps_project_override() {
+        let _guard = test_guard!();
+        let dir = tempfile::tempdir().expect("tempdir");
+        let project_path = dir.path().join("project.toml");
+        let no_env = HashMap::new();
+
+        let preview = load_config_with_user_profile_inner(None, None, Some("dev"), Some(&no_env))
+            .expect("preview without files");
+        assert_eq!(preview.general.profile.as_deref(), Some("dev"));
+        assert_eq!(preview.general.log_level, "debug");
+
+        std::fs::write(&project_path, "[general]\nprofile = \"test\"\n")
+            .expect("write project config");
+        let preview = load_config_with_user_profile_inner(
+            None,
+            Some(&project_path),
+            Some("prod"),
+            Some(&no_env),
+        )
+        .expect("preview with project config");
+        assert_eq!(preview.general.profile.as_deref(), Some("test"));
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_profile() {
+        let _guard = test_guard!();
+        let mut config = RchConfig::default();
+        config.general.profile = Some("staging".to_string());
+        let mut validation = FileValidation::new(Path::new("config.toml"));
+        validate_rch_config_values(&config, &mut validation);
+        assert!(
+            validation
+                .errors
+                .iter()
+                .any(|error| error.contains("general.profile must be one of"))
+        );
+    }
+
     #[test]
     fn test_uncached_config_loader_can_reset_user_values_to_defaults() {
         let _guard = test_guard!();
@@ -3219,6 +4604,7 @@ min_local_time_ms = 2000
 [selection]
 min_success_rate = 0.42
 min_free_gb = 25.0
+min_memory_gb = 8.0
 
 [execution]
 allowlist = ["cargo"]
@@ -3237,6 +4623,7 @@ max_concurrent_workers = 3
 
         assert_eq!(loaded.config.selection.min_success_rate, 0.42);
         assert_eq!(loaded.config.selection.min_free_gb, Some(25.0));
+        assert_eq!(loaded.config.selection.min_memory_gb, Some(8.0));
         assert_eq!(loaded.config.execution.allowlist, vec!["cargo"]);
         assert!(!loaded.config.alerts.enabled);
         assert_eq!(loaded.config.fleet.max_concurrent_workers, 3);
@@ -3605,6 +4992,71 @@ alias_root = "/home/alex/dp"
         );
     }
 
+    #[test]
+    fn test_path_topology_wsl_mount_prefix_loaded_from_toml() {
+        let _guard = test_guard!();
+        let dir = tempfile::tempdir().expect("tempdir");
+        let user_path = dir.path().join("user.toml");
+        std::fs::write(
+            &user_path,
+            r#"
+[path_topology]
+canonical_root = 'C:\src'
+wsl_mount_prefix = "/wsl"
+"#,
+        )
+        .expect("write user config");
+
+        let loaded = load_config_with_sources_from_paths(Some(&user_path), None, None)
+            .expect("load_config_with_sources_from_paths");
+        let policy = loaded.config.path_topology.to_policy();
+        assert_eq!(policy.wsl_mount_prefix(), Path::new("/wsl"));
+        assert!(
+            loaded
+                .sources
+                .contains_key("path_topology.wsl_mount_prefix")
+        );
+        assert_eq!(
+            rch_common::translate_windows_path(policy.canonical_root(), policy.wsl_mount_prefix()),
+            Some(PathBuf::from("/wsl/c/src"))
+        );
+    }
+
+    #[test]
+    fn test_path_topology_keep_logical_symlinks_loaded_from_toml() {
+        let _guard = test_guard!();
+        let dir = tempfile::tempdir().expect("tempdir");
+        let user_path = dir.path().join("user.toml");
+        std::fs::write(
+            &user_path,
+            r#"
+[path_topology]
+keep_logical_symlinks = true
+"#,
+        )
+        .expect("write user config");
+
+        let loaded = load_config_with_sources_from_paths(Some(&user_path), None, None)
+            .expect("load_config_with_sources_from_paths");
+        assert!(
+            loaded
+                .config
+                .path_topology
+                .to_policy()
+                .keep_logical_symlinks()
+        );
+        assert!(
+            loaded
+                .sources
+                .contains_key("path_topology.keep_logical_symlinks")
+        );
+        assert!(
+            !rch_common::PathTopologyConfig::default()
+                .to_policy()
+                .keep_logical_symlinks()
+        );
+    }
+
     /// Regression test for issue #10: env vars must override TOML values.
     /// This was already true at runtime, but the missing PartialRchConfig
     /// field meant the underlying merge order was untested.
@@ -3944,12 +5396,21 @@ check_interval_secs = 70
         env_overrides.insert("RCH_HOOK_STARTS_DAEMON".to_string(), "false".to_string());
         env_overrides.insert("RCH_AUTO_START_COOLDOWN_SECS".to_string(), "45".to_string());
         env_overrides.insert("RCH_AUTO_START_TIMEOUT_SECS".to_string(), "7".to_string());
+        env_overrides.insert("RCH_STATE_DIR".to_string(), "/run/rch-ci".to_string());
 
         apply_env_overrides_inner(&mut config, Some(&mut sources), Some(&env_overrides));
 
         assert!(!config.self_healing.hook_starts_daemon);
         assert_eq!(config.self_healing.auto_start_cooldown_secs, 45);
         assert_eq!(config.self_healing.auto_start_timeout_secs, 7);
+        assert_eq!(
+            config.self_healing.state_dir.as_deref(),
+            Some("/run/rch-ci")
+        );
+        assert_eq!(
+            sources.get("self_healing.state_dir"),
+            Some(&ConfigValueSource::EnvVar("RCH_STATE_DIR".to_string()))
+        );
 
         let source = sources
             .get("self_healing.hook_starts_daemon")
@@ -4566,6 +6027,242 @@ external_timeout_enabled = false
         info!("PASS: Project timeout controls are tracked");
     }
 
+    #[test]
+    fn test_per_kind_timeouts_merge_across_layers() {
+        let _guard = test_guard!();
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let user_path = temp_dir.path().join("config.toml");
+        let project_path = temp_dir.path().join("project_config.toml");
+        std::fs::write(
+            &user_path,
+            "[compilation.timeouts]\ncargo_bench = 1800\ncargo_check = 120\n",
+        )
+        .expect("write user config");
+        std::fs::write(&project_path, "[compilation.timeouts]\ncargo_check = 60\n")
+            .expect("write project config");
+
+        let env_overrides: HashMap<String, String> = HashMap::new();
+        let loaded = load_config_with_sources_from_paths(
+            Some(&user_path),
+            Some(&project_path),
+            Some(&env_overrides),
+        )
+        .expect("load config cascade");
+
+        let compilation = &loaded.config.compilation;
+        assert_eq!(compilation.timeouts.get("cargo_bench"), Some(&1800));
+        assert_eq!(compilation.timeouts.get("cargo_check"), Some(&60));
+        assert_eq!(
+            compilation.timeout_for_kind(Some(CompilationKind::CargoBench)),
+            std::time::Duration::from_secs(1800)
+        );
+        assert_eq!(
+            loaded.sources.get("compilation.timeouts"),
+            Some(&ConfigValueSource::ProjectConfig(project_path.clone()))
+        );
+    }
+
+    #[test]
+    fn test_validate_per_kind_timeouts() {
+        let _guard = test_guard!();
+        let mut config = RchConfig::default();
+        config
+            .compilation
+            .timeouts
+            .insert("cargo_bench".to_string(), 1800);
+        config
+            .compilation
+            .timeouts
+            .insert("cargo_bnech".to_string(), 60);
+        config
+            .compilation
+            .timeouts
+            .insert("cargo_check".to_string(), 0);
+
+        let mut validation = FileValidation::new(Path::new("config.toml"));
+        validate_rch_config_values(&config, &mut validation);
+
+        assert_eq!(
+            validation.warnings,
+            vec![format!(
+                "compilation.timeouts.cargo_bnech is not a known compilation kind (expected one of: {})",
+                CompilationKind::ALL
+                    .iter()
+                    .map(|k| k.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )]
+        );
+        assert_eq!(
+            validation.errors,
+            vec!["compilation.timeouts.cargo_check must be greater than 0".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_confidence_overrides_merge_and_validate() {
+        let _guard = test_guard!();
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let user_path = temp_dir.path().join("config.toml");
+        let project_path = temp_dir.path().join("project_config.toml");
+        std::fs::write(
+            &user_path,
+            "[compilation.confidence_overrides]\n\"cargo build -p weird-crate\" = 0.9\n\"cargo doc\" = false\n",
+        )
+        .expect("write user config");
+        std::fs::write(
+            &project_path,
+            "[compilation.confidence_overrides]\n\"cargo build -p weird-crate\" = true\n",
+        )
+        .expect("write project config");
+
+        let env_overrides: HashMap<String, String> = HashMap::new();
+        let loaded = load_config_with_sources_from_paths(
+            Some(&user_path),
+            Some(&project_path),
+            Some(&env_overrides),
+        )
+        .expect("load config cascade");
+
+        let overrides = &loaded.config.compilation.confidence_overrides;
+        assert_eq!(
+            overrides.get("cargo build -p weird-crate"),
+            Some(&rch_common::ConfidenceOverride::Force(true))
+        );
+        assert_eq!(
+            overrides.get("cargo doc"),
+            Some(&rch_common::ConfidenceOverride::Force(false))
+        );
+        assert_eq!(
+            loaded.sources.get("compilation.confidence_overrides"),
+            Some(&ConfigValueSource::ProjectConfig(project_path.clone()))
+        );
+
+        let mut config = RchConfig::default();
+        config.compilation.confidence_overrides.insert(
+            "cargo build".to_string(),
+            rch_common::ConfidenceOverride::Confidence(1.5),
+        );
+        config
+            .compilation
+            .confidence_overrides
+            .insert(String::new(), rch_common::ConfidenceOverride::Force(true));
+        let mut validation = FileValidation::new(Path::new("config.toml"));
+        validate_rch_config_values(&config, &mut validation);
+        assert_eq!(
+            validation.warnings,
+            vec![
+                "compilation.confidence_overrides has an empty key, which never matches"
+                    .to_string()
+            ]
+        );
+        assert_eq!(
+            validation.errors,
+            vec![
+                "compilation.confidence_overrides.\"cargo build\" must be within [0.0, 1.0]"
+                    .to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_heartbeat_timeout_below_minimum_warns() {
+        let _guard = test_guard!();
+        let mut config = RchConfig::default();
+        config.selection.heartbeat_timeout_secs = 10;
+        let mut validation = FileValidation::new(Path::new("config.toml"));
+        validate_rch_config_values(&config, &mut validation);
+        assert!(
+            validation
+                .warnings
+                .iter()
+                .any(|w| w.starts_with("selection.heartbeat_timeout_secs = 10")),
+            "{:?}",
+            validation.warnings
+        );
+
+        for secs in [0, 300] {
+            config.selection.heartbeat_timeout_secs = secs;
+            let mut validation = FileValidation::new(Path::new("config.toml"));
+            validate_rch_config_values(&config, &mut validation);
+            assert!(
+                !validation
+                    .warnings
+                    .iter()
+                    .any(|w| w.contains("heartbeat_timeout_secs"))
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_bwlimit_zero_warns() {
+        let _guard = test_guard!();
+        let mut config = RchConfig::default();
+        config.transfer.bwlimit_kbps = Some(0);
+        let mut validation = FileValidation::new(Path::new("config.toml"));
+        validate_rch_config_values(&config, &mut validation);
+        assert!(
+            validation
+                .warnings
+                .iter()
+                .any(|w| w.starts_with("transfer.bwlimit_kbps is 0")),
+            "{:?}",
+            validation.warnings
+        );
+
+        config.transfer.bwlimit_kbps = Some(5000);
+        let mut validation = FileValidation::new(Path::new("config.toml"));
+        validate_rch_config_values(&config, &mut validation);
+        assert!(
+            !validation
+                .warnings
+                .iter()
+                .any(|w| w.contains("bwlimit_kbps"))
+        );
+    }
+
+    #[test]
+    fn test_validate_classifier_rules_file() {
+        let _guard = test_guard!();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("classifier.toml");
+
+        std::fs::write(
+            &path,
+            "[[rule]]\nprefix = \"xbuild\"\nkind = \"cargo_build\"\n",
+        )
+        .unwrap();
+        assert!(validate_classifier_rules_file(&path).errors.is_empty());
+
+        std::fs::write(&path, "[[rule]]\nprefix = \"xbuild\"\nkind = \"xbuild\"\n").unwrap();
+        let validation = validate_classifier_rules_file(&path);
+        assert_eq!(validation.errors.len(), 1);
+        assert!(
+            validation.errors[0].starts_with("classifier rule 1: unknown kind 'xbuild'"),
+            "{:?}",
+            validation.errors
+        );
+    }
+
+    #[test]
+    fn test_validate_keep_remote_hours() {
+        let _guard = test_guard!();
+        let mut config = RchConfig::default();
+        config.transfer.keep_remote_hours = 0;
+
+        let mut validation = FileValidation::new(Path::new("config.toml"));
+        validate_rch_config_values(&config, &mut validation);
+        assert!(validation.errors.is_empty());
+
+        config.transfer.keep_remote_on_failure = true;
+        let mut validation = FileValidation::new(Path::new("config.toml"));
+        validate_rch_config_values(&config, &mut validation);
+        assert_eq!(
+            validation.errors,
+            vec!["transfer.keep_remote_hours must be greater than 0".to_string()]
+        );
+    }
+
     #[test]
     fn test_full_config_cascade_with_env_override() {
         let _guard = test_guard!();
@@ -5377,4 +7074,157 @@ confidence_threshold = 0.80
             "runtime env override should still apply after cache lookup"
         );
     }
+
+    #[test]
+    fn test_find_project_overlay_walks_up_to_repo_root() {
+        let _guard = test_guard!();
+        let temp_dir = tempfile::tempdir().expect("tempdir");
+        let repo = temp_dir.path().join("repo");
+        let nested = repo.join("crates/app/src");
+        std::fs::create_dir_all(&nested).expect("create dirs");
+        std::fs::create_dir_all(repo.join(".git")).expect("create .git");
+
+        // Above the repository root: not picked up.
+        std::fs::write(temp_dir.path().join(PROJECT_OVERLAY_FILE), "").expect("write outer");
+        assert_eq!(find_project_overlay(&nested), None);
+
+        let overlay = repo.join(PROJECT_OVERLAY_FILE);
+        std::fs::write(&overlay, "").expect("write overlay");
+        assert_eq!(find_project_overlay(&nested), Some(overlay.clone()));
+        assert_eq!(find_project_overlay(&repo), Some(overlay));
+    }
+
+    #[test]
+    fn test_find_project_overlay_stops_at_home_without_repo() {
+        let _guard = test_guard!();
+        let temp_dir = tempfile::tempdir().expect("tempdir");
+        let home = temp_dir.path().join("home");
+        let nested = home.join("scratch/app/src");
+        std::fs::create_dir_all(&nested).expect("create dirs");
+
+        // No `.git` anywhere: a file in the home directory or above it is
+        // not a project overlay.
+        std::fs::write(home.join(PROJECT_OVERLAY_FILE), "").expect("write home overlay");
+        std::fs::write(temp_dir.path().join(PROJECT_OVERLAY_FILE), "").expect("write outer");
+        assert_eq!(find_project_overlay_below(&nested, Some(&home)), None);
+
+        let overlay = home.join("scratch/app").join(PROJECT_OVERLAY_FILE);
+        std::fs::write(&overlay, "").expect("write overlay");
+        assert_eq!(
+            find_project_overlay_below(&nested, Some(&home)),
+            Some(overlay)
+        );
+    }
+
+    #[test]
+    fn test_project_overlay_applies_only_overridable_keys() {
+        let _guard = test_guard!();
+        let temp_dir = tempfile::tempdir().expect("tempdir");
+        let user = temp_dir.path().join("user.toml");
+        let overlay = temp_dir.path().join(PROJECT_OVERLAY_FILE);
+        std::fs::write(
+            &user,
+            "[general]\nsocket_path = \"/tmp/user.sock\"\n[transfer]\nfleet = \"default\"\n",
+        )
+        .expect("write user");
+        std::fs::write(
+            &overlay,
+            r#"
+[general]
+socket_path = "/tmp/evil.sock"
+
+[transfer]
+fleet = "gpu"
+remote_base = "/tmp/elsewhere"
+skip_artifacts_for = []
+
+[environment]
+allowlist = ["AWS_SECRET_ACCESS_KEY"]
+"#,
+        )
+        .expect("write overlay");
+
+        let config = load_config_uncached_from_layers(Some(&user), Some(&overlay), None)
+            .expect("load layers");
+        assert_eq!(config.transfer.fleet.as_deref(), Some("gpu"));
+        assert!(config.transfer.skip_artifacts_for.is_empty());
+        assert_eq!(config.general.socket_path, "/tmp/user.sock");
+        assert_eq!(
+            config.transfer.remote_base,
+            RchConfig::default().transfer.remote_base
+        );
+        assert!(config.environment.allowlist.is_empty());
+
+        let (_, ignored) = read_project_overlay(&overlay).expect("read overlay");
+        assert_eq!(
+            ignored,
+            vec![
+                "environment.allowlist".to_string(),
+                "general.socket_path".to_string(),
+                "transfer.remote_base".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_project_overlay_loses_to_project_config_and_tracks_source() {
+        let _guard = test_guard!();
+        let temp_dir = tempfile::tempdir().expect("tempdir");
+        let overlay = temp_dir.path().join(PROJECT_OVERLAY_FILE);
+        let project = temp_dir.path().join("project.toml");
+        std::fs::write(
+            &overlay,
+            "[transfer]\nuse_sccache = true\nfleet = \"gpu\"\n",
+        )
+        .expect("write overlay");
+        std::fs::write(&project, "[transfer]\nfleet = \"local\"\n").expect("write project");
+
+        let loaded =
+            load_config_with_sources_from_layers(None, Some(&overlay), Some(&project), None)
+                .expect("load with sources");
+        assert!(loaded.config.transfer.use_sccache);
+        assert_eq!(loaded.config.transfer.fleet.as_deref(), Some("local"));
+        assert_eq!(
+            loaded.sources.get("transfer.use_sccache"),
+            Some(&ConfigValueSource::ProjectConfig(overlay))
+        );
+    }
+
+    #[test]
+    fn test_validate_project_overlay_file_checks_merged_result() {
+        let _guard = test_guard!();
+        let temp_dir = tempfile::tempdir().expect("tempdir");
+        let user = temp_dir.path().join("user.toml");
+        let overlay = temp_dir.path().join(PROJECT_OVERLAY_FILE);
+        std::fs::write(&user, "[general]\nlog_level = \"debug\"\n").expect("write user");
+        std::fs::write(
+            &overlay,
+            "[compilation]\nconfidence_threshold = 1.5\n[general]\nenabled = false\n",
+        )
+        .expect("write overlay");
+
+        let validation = validate_project_overlay_file(&overlay, Some(&user));
+        assert!(
+            validation
+                .errors
+                .iter()
+                .any(|error| error.contains("confidence_threshold")),
+            "errors: {:?}",
+            validation.errors
+        );
+        assert!(
+            validation
+                .warnings
+                .iter()
+                .any(|warning| warning.starts_with("general.enabled: not overridable")),
+            "warnings: {:?}",
+            validation.warnings
+        );
+
+        std::fs::write(&overlay, "[compilation]\nconfidence_threshold = 0.9\n")
+            .expect("rewrite overlay");
+        let validation = validate_project_overlay_file(&overlay, Some(&user));
+        assert!(validation.errors.is_empty(), "{:?}", validation.errors);
+        assert!(validation.warnings.is_empty(), "{:?}", validation.warnings);
+    }
 }
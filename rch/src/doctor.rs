@@ -764,6 +764,7 @@ pub async fn run_doctor(ctx: &OutputContext, options: DoctorOptions) -> Result<(
     // Run all checks
     check_prerequisites(&mut checks, ctx, &options);
     check_configuration(&mut checks, ctx, &options);
+    check_local_paths(&mut checks, ctx, &options, &mut fixes_applied);
     check_ssh_keys(&mut checks, ctx, &options, &mut fixes_applied);
     check_hooks(&mut checks, ctx, &options, &mut fixes_applied);
     check_daemon(&mut checks, ctx, &options, &mut fixes_applied);
@@ -3622,6 +3623,13 @@ fn check_configuration(
         checks.push(result);
     }
 
+    // Build output tracked in git skews dependency-closure scans and leaves
+    // the remote tree partial; flag it for the project doctor runs in.
+    for result in check_tracked_artifact_results(Path::new(".")) {
+        print_check_result(&result, ctx);
+        checks.push(result);
+    }
+
     if !ctx.is_json() {
         println!();
     }
@@ -3678,6 +3686,59 @@ fn check_remediation_results() -> Vec<CheckResult> {
         .collect()
 }
 
+/// Warn when build-output directories (`target/`, `node_modules/`, ...) are
+/// tracked in git in the project at `project_dir`.
+///
+/// Returns one `Warning` per tracked artifact dir, a single `Pass` when none
+/// are tracked, and nothing outside a git work tree.
+fn check_tracked_artifact_results(project_dir: &Path) -> Vec<CheckResult> {
+    let Ok(output) = Command::new("git")
+        .arg("-C")
+        .arg(project_dir)
+        .args(["ls-files", "--full-name"])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+    else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+
+    let warnings = rch_common::tracked_artifact_warnings(&String::from_utf8_lossy(&output.stdout));
+    if warnings.is_empty() {
+        return vec![CheckResult {
+            category: "configuration".to_string(),
+            name: "tracked_artifacts".to_string(),
+            status: CheckStatus::Pass,
+            message: "No build output tracked in git".to_string(),
+            details: None,
+            suggestion: None,
+            fixable: false,
+            fix_applied: false,
+            fix_message: None,
+        }];
+    }
+    warnings
+        .into_iter()
+        .map(|warning| CheckResult {
+            category: "configuration".to_string(),
+            name: "tracked_artifacts".to_string(),
+            status: CheckStatus::Warning,
+            message: format!("Build output tracked in git: {}", warning.var),
+            details: Some(warning.message),
+            suggestion: Some(format!(
+                "Untrack it with `git rm -r --cached <dir>` and add `{}` to .gitignore",
+                warning.var
+            )),
+            fixable: false,
+            fix_applied: false,
+            fix_message: None,
+        })
+        .collect()
+}
+
 fn check_config_directory() -> CheckResult {
     match config_dir() {
         Some(dir) => {
@@ -3885,6 +3946,336 @@ fn check_workers_file() -> CheckResult {
     }
 }
 
+// =============================================================================
+// Local Path Checks
+// =============================================================================
+
+/// One idempotent filesystem change `--fix` may make for a local path check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum LocalPathAction {
+    CreateDir(PathBuf),
+    Symlink { link: PathBuf, target: PathBuf },
+}
+
+impl LocalPathAction {
+    fn describe(&self) -> String {
+        match self {
+            Self::CreateDir(dir) => format!("Created {}", dir.display()),
+            Self::Symlink { link, target } => {
+                format!("Linked {} -> {}", link.display(), target.display())
+            }
+        }
+    }
+
+    fn apply(&self) -> io::Result<()> {
+        match self {
+            Self::CreateDir(dir) => std::fs::create_dir_all(dir),
+            #[cfg(unix)]
+            Self::Symlink { link, target } => std::os::unix::fs::symlink(target, link),
+            #[cfg(not(unix))]
+            Self::Symlink { .. } => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "symlinks are only created on unix hosts",
+            )),
+        }
+    }
+}
+
+/// Pure plan for a local path check (no I/O beyond stat and write probes).
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum LocalPathPlan {
+    /// Nothing to do.
+    Satisfied,
+    /// `--fix` can repair this with the listed actions, in order.
+    Fix(Vec<LocalPathAction>),
+    /// Needs elevated permissions or an operator decision; `--fix` skips it.
+    Manual(String),
+}
+
+/// Whether the nearest existing ancestor of `path` accepts new entries, i.e.
+/// whether `create_dir_all(path)` can succeed without elevated permissions.
+fn can_create_under(path: &Path) -> bool {
+    path.ancestors()
+        .skip(1)
+        .find(|ancestor| ancestor.exists())
+        .is_some_and(|ancestor| crate::config::probe_dir_writable(ancestor).is_ok())
+}
+
+fn plan_state_dir(dir: &Path) -> LocalPathPlan {
+    if dir.is_dir() {
+        LocalPathPlan::Satisfied
+    } else if dir.exists() {
+        LocalPathPlan::Manual(format!(
+            "{} exists but is not a directory; remove it or set self_healing.state_dir",
+            dir.display()
+        ))
+    } else if can_create_under(dir) {
+        LocalPathPlan::Fix(vec![LocalPathAction::CreateDir(dir.to_path_buf())])
+    } else {
+        LocalPathPlan::Manual(format!(
+            "creating {} requires elevated permissions; set self_healing.state_dir to a writable path",
+            dir.display()
+        ))
+    }
+}
+
+fn plan_path_topology(policy: &rch_common::path_topology::PathTopologyPolicy) -> LocalPathPlan {
+    let canonical = policy.canonical_root();
+    let alias = policy.alias_root();
+    let mut actions = Vec::new();
+
+    if !canonical.exists() {
+        if !can_create_under(canonical) {
+            return LocalPathPlan::Manual(format!(
+                "creating {} requires elevated permissions: sudo mkdir -p {} && sudo chown \"$USER\" {}",
+                canonical.display(),
+                canonical.display(),
+                canonical.display()
+            ));
+        }
+        actions.push(LocalPathAction::CreateDir(canonical.to_path_buf()));
+    } else if !canonical.is_dir() {
+        return LocalPathPlan::Manual(format!(
+            "{} exists but is not a directory",
+            canonical.display()
+        ));
+    }
+
+    match std::fs::symlink_metadata(alias) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            if !can_create_under(alias) {
+                return LocalPathPlan::Manual(format!(
+                    "creating {} requires elevated permissions: sudo ln -s {} {}",
+                    alias.display(),
+                    canonical.display(),
+                    alias.display()
+                ));
+            }
+            actions.push(LocalPathAction::Symlink {
+                link: alias.to_path_buf(),
+                target: canonical.to_path_buf(),
+            });
+        }
+        Err(e) => {
+            return LocalPathPlan::Manual(format!("cannot inspect {}: {}", alias.display(), e));
+        }
+        Ok(meta) if meta.file_type().is_symlink() => {
+            let raw_target = std::fs::read_link(alias).unwrap_or_default();
+            let target = if raw_target.is_absolute() {
+                raw_target
+            } else {
+                alias
+                    .parent()
+                    .unwrap_or_else(|| Path::new("/"))
+                    .join(raw_target)
+            };
+            let resolved = std::fs::canonicalize(&target).unwrap_or(target);
+            let canonical_resolved =
+                std::fs::canonicalize(canonical).unwrap_or_else(|_| canonical.to_path_buf());
+            if !resolved.starts_with(&canonical_resolved) {
+                // Repointing an existing link could break whatever relies on
+                // it, so that stays an operator decision.
+                return LocalPathPlan::Manual(format!(
+                    "{} points to {}, not {}; repoint it manually",
+                    alias.display(),
+                    resolved.display(),
+                    canonical.display()
+                ));
+            }
+        }
+        // A real alias directory is a supported layout (entries are mapped
+        // one by one during normalization), so leave it alone.
+        Ok(meta) if meta.is_dir() => {}
+        Ok(_) => {
+            return LocalPathPlan::Manual(format!(
+                "{} exists but is neither a symlink nor a directory",
+                alias.display()
+            ));
+        }
+    }
+
+    if actions.is_empty() {
+        LocalPathPlan::Satisfied
+    } else {
+        LocalPathPlan::Fix(actions)
+    }
+}
+
+/// "create /data/projects and link /dp -> /data/projects"
+fn summarize_local_path_actions(actions: &[LocalPathAction]) -> String {
+    actions
+        .iter()
+        .map(|action| match action {
+            LocalPathAction::CreateDir(dir) => format!("create {}", dir.display()),
+            LocalPathAction::Symlink { link, target } => {
+                format!("link {} -> {}", link.display(), target.display())
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" and ")
+}
+
+fn local_path_check(
+    name: &str,
+    subject: &str,
+    details: String,
+    plan: &LocalPathPlan,
+) -> CheckResult {
+    let (status, message, suggestion, fixable) = match plan {
+        LocalPathPlan::Satisfied => (
+            CheckStatus::Pass,
+            format!("{} is in place", subject),
+            None,
+            false,
+        ),
+        LocalPathPlan::Fix(actions) => (
+            CheckStatus::Warning,
+            format!("{} is missing", subject),
+            Some(format!(
+                "Run 'rch doctor --fix' to {}",
+                summarize_local_path_actions(actions)
+            )),
+            true,
+        ),
+        LocalPathPlan::Manual(reason) => (
+            CheckStatus::Warning,
+            format!("{} needs manual attention", subject),
+            Some(reason.clone()),
+            false,
+        ),
+    };
+    CheckResult {
+        category: "local_paths".to_string(),
+        name: name.to_string(),
+        status,
+        message,
+        details: Some(details),
+        suggestion,
+        fixable,
+        fix_applied: false,
+        fix_message: None,
+    }
+}
+
+/// Apply (or preview) `plan` for `result` under `--fix`. Manual plans are
+/// never attempted; they are reported as skipped with their reason.
+fn apply_local_path_plan(
+    result: &mut CheckResult,
+    plan: &LocalPathPlan,
+    options: &DoctorOptions,
+    fixes_applied: &mut Vec<FixApplied>,
+) -> Option<(StatusIndicator, String)> {
+    if !options.fix {
+        return None;
+    }
+    let actions = match plan {
+        LocalPathPlan::Satisfied => return None,
+        LocalPathPlan::Manual(reason) => {
+            let msg = format!("Skipped: {}", reason);
+            result.fix_message = Some(msg.clone());
+            return Some((StatusIndicator::Warning, msg));
+        }
+        LocalPathPlan::Fix(actions) => actions,
+    };
+
+    if options.dry_run {
+        let msg = format!("Would {}", summarize_local_path_actions(actions));
+        result.fix_message = Some(msg.clone());
+        return Some((StatusIndicator::Pending, format!("Would fix: {}", msg)));
+    }
+
+    let mut done = Vec::new();
+    for action in actions {
+        if let Err(e) = action.apply() {
+            let msg = format!("{} failed: {}", action.describe(), e);
+            result.fix_message = Some(msg.clone());
+            fixes_applied.push(FixApplied {
+                check_name: result.name.clone(),
+                action: action.describe(),
+                success: false,
+                error: Some(e.to_string()),
+            });
+            return Some((StatusIndicator::Error, msg));
+        }
+        done.push(action.describe());
+    }
+
+    let msg = done.join("; ");
+    result.status = CheckStatus::Pass;
+    result.message = format!("{} (fixed)", msg);
+    result.suggestion = None;
+    result.fixable = false;
+    result.fix_applied = true;
+    result.fix_message = Some(msg.clone());
+    fixes_applied.push(FixApplied {
+        check_name: result.name.clone(),
+        action: msg.clone(),
+        success: true,
+        error: None,
+    });
+    Some((StatusIndicator::Success, format!("Fixed: {}", msg)))
+}
+
+fn check_local_paths(
+    checks: &mut Vec<CheckResult>,
+    ctx: &OutputContext,
+    options: &DoctorOptions,
+    fixes_applied: &mut Vec<FixApplied>,
+) {
+    let style = ctx.theme();
+
+    if !ctx.is_json() {
+        println!("{}", style.highlight("Local Paths"));
+        println!();
+    }
+
+    let config = crate::config::load_config().unwrap_or_default();
+
+    let state_dir = crate::hook::configured_autostart_state_dir(&config.self_healing);
+    let state_plan = plan_state_dir(&state_dir);
+    let state_result = local_path_check(
+        "state_directory",
+        "State directory",
+        state_dir.display().to_string(),
+        &state_plan,
+    );
+
+    let policy = config.path_topology.to_policy();
+    let topology_plan = plan_path_topology(&policy);
+    let topology_result = local_path_check(
+        "path_topology",
+        "Project path topology",
+        format!(
+            "{} -> {}",
+            policy.alias_root().display(),
+            policy.canonical_root().display()
+        ),
+        &topology_plan,
+    );
+
+    for (mut result, plan) in [(state_result, state_plan), (topology_result, topology_plan)] {
+        if let Some((indicator, line)) =
+            apply_local_path_plan(&mut result, &plan, options, fixes_applied)
+            && !ctx.is_json()
+        {
+            let rendered = match indicator {
+                StatusIndicator::Success => style.success(&line),
+                StatusIndicator::Pending => style.muted(&line),
+                StatusIndicator::Error => style.error(&line),
+                StatusIndicator::Warning => style.warning(&line),
+                _ => style.info(&line),
+            };
+            println!("  {} {}", indicator.display(style), rendered);
+        }
+        print_check_result(&result, ctx);
+        checks.push(result);
+    }
+
+    if !ctx.is_json() {
+        println!();
+    }
+}
+
 // =============================================================================
 // SSH Key Checks
 // =============================================================================
@@ -4262,7 +4653,7 @@ fn start_rchd_via_systemd_user(socket_path: &Path) -> Result<(), String> {
     }
 }
 
-fn spawn_rchd(rchd_path: &Path, socket_path: &Path) -> Result<(), String> {
+fn spawn_rchd(rchd_path: &Path, socket_path: &Path, log_path: &Path) -> Result<(), String> {
     // Prefer the systemd-managed daemon when one exists. Spawning a detached
     // competing rchd via nohup (below) is what historically caused the
     // restart-storm: an agent's `rch exec` would auto-spawn an rchd that
@@ -4282,12 +4673,14 @@ fn spawn_rchd(rchd_path: &Path, socket_path: &Path) -> Result<(), String> {
         }
     }
 
+    let (stdout, stderr) = crate::hook::daemon_log_stdio(log_path);
     let mut cmd = Command::new("nohup");
     cmd.arg(rchd_path)
         .arg("-s")
         .arg(socket_path)
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
+        .env(rch_common::log_retention::DAEMON_STDIO_LOG_ENV, log_path)
+        .stdout(stdout)
+        .stderr(stderr)
         .stdin(Stdio::null());
 
     let mut child = cmd.spawn().map_err(|e| match e.kind() {
@@ -4335,13 +4728,14 @@ fn wait_for_socket(socket_path: &Path, timeout: Duration) -> bool {
 fn start_daemon_with_binary(
     socket_path: &Path,
     rchd_path: &Path,
+    log_path: &Path,
     timeout: Duration,
 ) -> Result<(), String> {
     if let Some(parent) = socket_path.parent() {
         std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
     }
 
-    spawn_rchd(rchd_path, socket_path)?;
+    spawn_rchd(rchd_path, socket_path, log_path)?;
 
     if wait_for_socket(socket_path, timeout) {
         return Ok(());
@@ -4354,7 +4748,9 @@ fn start_daemon_with_binary(
 }
 
 fn start_daemon_for_doctor(socket_path: &Path, timeout: Duration) -> Result<(), String> {
-    start_daemon_with_binary(socket_path, &which_rchd_path(), timeout)
+    let config = crate::config::load_config().unwrap_or_default();
+    let log_path = crate::hook::configured_daemon_log_path(&config.self_healing);
+    start_daemon_with_binary(socket_path, &which_rchd_path(), &log_path, timeout)
 }
 
 fn check_daemon(
@@ -7135,6 +7531,153 @@ mod tests {
         // TEST PASS: active config socket path used
     }
 
+    fn local_path_fix_options(dry_run: bool) -> DoctorOptions {
+        DoctorOptions {
+            fix: true,
+            dry_run,
+            install_deps: false,
+            reliability: false,
+            check_schemas: false,
+            verbose: false,
+            strict: false,
+            lenient: false,
+            scope: ReliabilityScopeSet::default(),
+            watch: false,
+            watch_interval_secs: 5,
+            transitions_only: false,
+            watch_snapshot: None,
+        }
+    }
+
+    #[test]
+    fn test_state_dir_fix_creates_directory_and_is_idempotent() {
+        let _guard = rch_common::test_guard!();
+        let tmp = TempDir::new().unwrap();
+        let state_dir = tmp.path().join("run").join("rch");
+
+        let plan = plan_state_dir(&state_dir);
+        assert_eq!(
+            plan,
+            LocalPathPlan::Fix(vec![LocalPathAction::CreateDir(state_dir.clone())])
+        );
+
+        let mut result =
+            local_path_check("state_directory", "State directory", String::new(), &plan);
+        let mut fixes = Vec::new();
+        let line = apply_local_path_plan(
+            &mut result,
+            &plan,
+            &local_path_fix_options(true),
+            &mut fixes,
+        );
+        assert!(matches!(line, Some((StatusIndicator::Pending, _))));
+        assert!(!state_dir.exists(), "dry run must not mutate");
+        assert!(fixes.is_empty());
+
+        let line = apply_local_path_plan(
+            &mut result,
+            &plan,
+            &local_path_fix_options(false),
+            &mut fixes,
+        );
+        assert!(matches!(line, Some((StatusIndicator::Success, _))));
+        assert!(state_dir.is_dir());
+        assert_eq!(result.status, CheckStatus::Pass);
+        assert!(result.fix_applied);
+        assert_eq!(fixes.len(), 1);
+
+        assert_eq!(plan_state_dir(&state_dir), LocalPathPlan::Satisfied);
+    }
+
+    #[test]
+    fn test_state_dir_plan_leaves_non_directory_to_operator() {
+        let _guard = rch_common::test_guard!();
+        let tmp = TempDir::new().unwrap();
+        let state_dir = tmp.path().join("rch");
+        std::fs::write(&state_dir, "").unwrap();
+
+        let plan = plan_state_dir(&state_dir);
+        assert!(matches!(plan, LocalPathPlan::Manual(_)), "{plan:?}");
+
+        let mut result =
+            local_path_check("state_directory", "State directory", String::new(), &plan);
+        assert!(!result.fixable);
+        let mut fixes = Vec::new();
+        let line = apply_local_path_plan(
+            &mut result,
+            &plan,
+            &local_path_fix_options(false),
+            &mut fixes,
+        );
+        assert!(
+            matches!(line, Some((StatusIndicator::Warning, ref msg)) if msg.starts_with("Skipped: "))
+        );
+        assert!(fixes.is_empty());
+        assert!(state_dir.is_file());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_path_topology_fix_creates_root_and_alias_symlink() {
+        let _guard = rch_common::test_guard!();
+        let tmp = TempDir::new().unwrap();
+        let canonical = tmp.path().join("data").join("projects");
+        let alias = tmp.path().join("dp");
+        let policy =
+            rch_common::path_topology::PathTopologyPolicy::new(canonical.clone(), alias.clone());
+
+        let plan = plan_path_topology(&policy);
+        assert_eq!(
+            plan,
+            LocalPathPlan::Fix(vec![
+                LocalPathAction::CreateDir(canonical.clone()),
+                LocalPathAction::Symlink {
+                    link: alias.clone(),
+                    target: canonical.clone(),
+                },
+            ])
+        );
+
+        let mut result = local_path_check(
+            "path_topology",
+            "Project path topology",
+            String::new(),
+            &plan,
+        );
+        let mut fixes = Vec::new();
+        apply_local_path_plan(
+            &mut result,
+            &plan,
+            &local_path_fix_options(false),
+            &mut fixes,
+        );
+        assert_eq!(result.status, CheckStatus::Pass);
+        assert!(canonical.is_dir());
+        assert_eq!(std::fs::read_link(&alias).unwrap(), canonical);
+
+        assert_eq!(plan_path_topology(&policy), LocalPathPlan::Satisfied);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_path_topology_plan_does_not_repoint_foreign_alias() {
+        let _guard = rch_common::test_guard!();
+        let tmp = TempDir::new().unwrap();
+        let canonical = tmp.path().join("projects");
+        let elsewhere = tmp.path().join("elsewhere");
+        let alias = tmp.path().join("dp");
+        std::fs::create_dir_all(&canonical).unwrap();
+        std::fs::create_dir_all(&elsewhere).unwrap();
+        std::os::unix::fs::symlink(&elsewhere, &alias).unwrap();
+        let policy = rch_common::path_topology::PathTopologyPolicy::new(canonical, alias);
+
+        let plan = plan_path_topology(&policy);
+        assert!(
+            matches!(&plan, LocalPathPlan::Manual(reason) if reason.contains("repoint it manually")),
+            "{plan:?}"
+        );
+    }
+
     #[cfg(unix)]
     #[test]
     fn test_daemon_check_warns_on_stale_socket_file() {
@@ -7310,6 +7853,46 @@ mod tests {
         // TEST PASS: Daemon check runs
     }
 
+    #[test]
+    fn test_check_tracked_artifact_results() {
+        let tmp = TempDir::new().unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(tmp.path())
+                .args(args)
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .unwrap();
+            assert!(status.success(), "git {args:?}");
+        };
+
+        // Outside a git work tree there is nothing to report.
+        assert!(check_tracked_artifact_results(tmp.path()).is_empty());
+
+        git(&["init", "-q"]);
+        std::fs::write(tmp.path().join("Cargo.toml"), "[package]\n").unwrap();
+        git(&["add", "Cargo.toml"]);
+        let clean = check_tracked_artifact_results(tmp.path());
+        assert_eq!(clean.len(), 1);
+        assert_eq!(clean[0].status, CheckStatus::Pass);
+
+        std::fs::create_dir_all(tmp.path().join("target/debug")).unwrap();
+        std::fs::write(tmp.path().join("target/debug/app"), "bin").unwrap();
+        git(&["add", "-f", "target/debug/app"]);
+        let tracked = check_tracked_artifact_results(tmp.path());
+        assert_eq!(tracked.len(), 1);
+        assert_eq!(tracked[0].status, CheckStatus::Warning);
+        assert!(tracked[0].message.contains("target/"));
+        assert!(
+            tracked[0]
+                .details
+                .as_deref()
+                .is_some_and(|details| details.contains("target/debug/app"))
+        );
+    }
+
     #[test]
     fn test_wait_for_socket_times_out() {
         // TEST START: wait_for_socket times out when socket never appears
@@ -7345,7 +7928,9 @@ exit 0\n"
         perms.set_mode(0o755);
         std::fs::set_permissions(&fake_rchd, perms).unwrap();
 
-        start_daemon_with_binary(&socket_path, &fake_rchd, Duration::from_secs(1)).unwrap();
+        let log_path = tmp.path().join("rchd.log");
+        start_daemon_with_binary(&socket_path, &fake_rchd, &log_path, Duration::from_secs(1))
+            .unwrap();
         assert!(socket_path.exists());
         // TEST PASS: start_daemon_with_binary creates socket file
     }
@@ -7362,8 +7947,10 @@ exit 0\n"
         perms.set_mode(0o755);
         std::fs::set_permissions(&fake_rchd, perms).unwrap();
 
+        let log_path = tmp.path().join("rchd.log");
         let err =
-            start_daemon_with_binary(&socket_path, &fake_rchd, Duration::from_secs(1)).unwrap_err();
+            start_daemon_with_binary(&socket_path, &fake_rchd, &log_path, Duration::from_secs(1))
+                .unwrap_err();
         assert!(
             err.contains("exited unsuccessfully") && err.contains("42"),
             "unexpected error: {err}"
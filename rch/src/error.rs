@@ -9,6 +9,7 @@
 #![allow(unused_assignments)]
 
 use miette::{Diagnostic, NamedSource, SourceSpan};
+use rch_common::ErrorCode;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use thiserror::Error;
@@ -657,6 +658,88 @@ pub enum TransferError {
     TransferSkipped { reason: String },
 }
 
+impl TransferError {
+    /// Map a transfer failure to a stable `ErrorCode` from the shared catalog.
+    ///
+    /// The `RCH-E40x` annotations above only label the miette report; the
+    /// catalog code is what users look up with `rch explain` and what
+    /// automation branches on. `SyncFailed` is refined from rsync's exit code
+    /// and stderr. `TransferSkipped` is not a failure and has no code.
+    pub fn error_code(&self) -> Option<ErrorCode> {
+        let code = match self {
+            Self::BuildFailed { .. } => ErrorCode::BuildCompilationFailed,
+            Self::Timeout { .. } => ErrorCode::BuildTimeout,
+            Self::Cancelled => ErrorCode::CancelGracefulSent,
+            Self::OutputMissing { .. } => ErrorCode::BuildArtifactMissing,
+            Self::ArtifactFailed { source, .. } => match source.kind() {
+                std::io::ErrorKind::PermissionDenied => ErrorCode::TransferPermissionDenied,
+                std::io::ErrorKind::StorageFull => ErrorCode::TransferDiskFull,
+                std::io::ErrorKind::TimedOut => ErrorCode::TransferTimeout,
+                _ => ErrorCode::TransferIncomplete,
+            },
+            Self::SyncFailed {
+                exit_code, stderr, ..
+            } => rsync_failure_code(*exit_code, stderr),
+            Self::WorkingDirNotFound { .. } | Self::NoProjectRoot { .. } => {
+                ErrorCode::TransferSourceMissing
+            }
+            Self::ClassificationFailed { .. } => ErrorCode::BuildUnknownCommand,
+            Self::QueueFull { .. } => ErrorCode::WorkerAtCapacity,
+            Self::TransferSkipped { .. } => return None,
+        };
+        Some(code)
+    }
+
+    /// One-line `"RCH-E404: <message> — <first remediation>"` for summaries.
+    pub fn catalog_summary(&self) -> Option<String> {
+        let code = self.error_code()?;
+        Some(match code.remediation().first() {
+            Some(hint) => format!("{}: {} — {}", code.code_string(), code.message(), hint),
+            None => format!("{}: {}", code.code_string(), code.message()),
+        })
+    }
+}
+
+/// Catalog code for a failed rsync, from its stderr first and exit code second.
+///
+/// rsync runs over SSH, so transport failures (refused, unreachable, bad host
+/// key) surface in the same stderr and are mapped to the network/SSH codes.
+fn rsync_failure_code(exit_code: Option<i32>, stderr: &str) -> ErrorCode {
+    let lower = stderr.to_lowercase();
+    if lower.contains("no space left on device") || lower.contains("disk quota exceeded") {
+        return ErrorCode::TransferDiskFull;
+    }
+    if lower.contains("host key verification failed") {
+        return ErrorCode::SshHostKeyError;
+    }
+    if lower.contains("permission denied (publickey") {
+        return ErrorCode::SshAuthFailed;
+    }
+    if lower.contains("connection refused") {
+        return ErrorCode::NetworkConnectionRefused;
+    }
+    if lower.contains("could not resolve hostname") {
+        return ErrorCode::NetworkDnsError;
+    }
+    if lower.contains("no route to host") || lower.contains("network is unreachable") {
+        return ErrorCode::NetworkUnreachable;
+    }
+    if lower.contains("permission denied") || lower.contains("read-only file system") {
+        return ErrorCode::TransferPermissionDenied;
+    }
+    if lower.contains("protocol version mismatch") || lower.contains("protocol incompatibility") {
+        return ErrorCode::TransferProtocolError;
+    }
+    match exit_code {
+        Some(1 | 2 | 4 | 5 | 12) => ErrorCode::TransferProtocolError,
+        Some(3 | 11) => ErrorCode::TransferDestError,
+        Some(23 | 24) => ErrorCode::TransferIncomplete,
+        Some(30 | 35) => ErrorCode::TransferTimeout,
+        Some(255) => ErrorCode::SshConnectionFailed,
+        _ => ErrorCode::TransferRsyncFailed,
+    }
+}
+
 // =============================================================================
 // Hook Errors
 // =============================================================================
@@ -817,6 +900,9 @@ pub struct ArtifactRetrievalWarning {
     pub rsync_exit_code: Option<i32>,
     /// Worker ID where the artifacts remain.
     pub worker_id: String,
+    /// Retries made after the first attempt before giving up.
+    #[serde(default)]
+    pub retry_attempts: u32,
     /// Suggested actions for remediation.
     pub suggestions: Vec<String>,
 }
@@ -844,6 +930,7 @@ impl ArtifactRetrievalWarning {
             rsync_stderr_snippet,
             rsync_exit_code,
             worker_id: worker_id.into(),
+            retry_attempts: 0,
             suggestions: vec![
                 "Run `rch diagnose` for detailed diagnostics".to_string(),
                 "Check worker connectivity: `rch workers probe`".to_string(),
@@ -852,6 +939,12 @@ impl ArtifactRetrievalWarning {
         }
     }
 
+    /// Record how many retries were made before giving up.
+    pub fn with_retry_attempts(mut self, retry_attempts: u32) -> Self {
+        self.retry_attempts = retry_attempts;
+        self
+    }
+
     /// Add custom suggestions.
     pub fn with_suggestions(mut self, suggestions: Vec<String>) -> Self {
         self.suggestions = suggestions;
@@ -879,6 +972,13 @@ impl ArtifactRetrievalWarning {
             msg.push_str(&format!("\nrsync exit code: {}\n", code));
         }
 
+        if self.retry_attempts > 0 {
+            msg.push_str(&format!(
+                "Retried {} time(s) before giving up\n",
+                self.retry_attempts
+            ));
+        }
+
         msg.push_str(&format!(
             "\nArtifacts remain on worker: {}\n",
             self.worker_id
@@ -902,6 +1002,7 @@ impl ArtifactRetrievalWarning {
             "attempted_patterns": self.attempted_patterns,
             "rsync_stderr_snippet": self.rsync_stderr_snippet,
             "rsync_exit_code": self.rsync_exit_code,
+            "retry_attempts": self.retry_attempts,
             "suggestions": self.suggestions,
         })
     }
@@ -1722,6 +1823,139 @@ mod tests {
         assert_eq!(code, Some("RCH-E401".to_string()));
     }
 
+    fn sync_failed(exit_code: Option<i32>, stderr: &str) -> TransferError {
+        TransferError::SyncFailed {
+            reason: "rsync failed".to_string(),
+            exit_code,
+            stderr: stderr.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_transfer_error_catalog_codes() {
+        let io = |kind| std::io::Error::new(kind, "io");
+        let cases = [
+            (
+                TransferError::BuildFailed {
+                    worker_id: "w1".to_string(),
+                    stderr: String::new(),
+                },
+                ErrorCode::BuildCompilationFailed,
+            ),
+            (
+                TransferError::Timeout { seconds: 5 },
+                ErrorCode::BuildTimeout,
+            ),
+            (TransferError::Cancelled, ErrorCode::CancelGracefulSent),
+            (
+                TransferError::OutputMissing {
+                    expected_path: "target/debug/app".to_string(),
+                },
+                ErrorCode::BuildArtifactMissing,
+            ),
+            (
+                TransferError::ArtifactFailed {
+                    worker_id: "w1".to_string(),
+                    source: io(std::io::ErrorKind::StorageFull),
+                },
+                ErrorCode::TransferDiskFull,
+            ),
+            (
+                TransferError::ArtifactFailed {
+                    worker_id: "w1".to_string(),
+                    source: io(std::io::ErrorKind::Other),
+                },
+                ErrorCode::TransferIncomplete,
+            ),
+            (sync_failed(Some(1), ""), ErrorCode::TransferProtocolError),
+            (
+                TransferError::WorkingDirNotFound {
+                    path: "/gone".to_string(),
+                    source: io(std::io::ErrorKind::NotFound),
+                },
+                ErrorCode::TransferSourceMissing,
+            ),
+            (
+                TransferError::ClassificationFailed {
+                    command: "cargo frob".to_string(),
+                },
+                ErrorCode::BuildUnknownCommand,
+            ),
+            (
+                TransferError::QueueFull { queue_size: 8 },
+                ErrorCode::WorkerAtCapacity,
+            ),
+            (
+                TransferError::NoProjectRoot {
+                    source: io(std::io::ErrorKind::NotFound),
+                },
+                ErrorCode::TransferSourceMissing,
+            ),
+        ];
+        for (err, expected) in cases {
+            assert_eq!(err.error_code(), Some(expected), "{err:?}");
+        }
+        let skipped = TransferError::TransferSkipped {
+            reason: "too large".to_string(),
+        };
+        assert_eq!(skipped.error_code(), None);
+        assert_eq!(skipped.catalog_summary(), None);
+    }
+
+    #[test]
+    fn test_sync_failed_code_from_rsync_output() {
+        let cases = [
+            (
+                Some(12),
+                "ssh: connect to host w1 port 22: Connection refused",
+                ErrorCode::NetworkConnectionRefused,
+            ),
+            (
+                Some(255),
+                "Host key verification failed.",
+                ErrorCode::SshHostKeyError,
+            ),
+            (
+                Some(255),
+                "u@w1: Permission denied (publickey).",
+                ErrorCode::SshAuthFailed,
+            ),
+            (
+                Some(11),
+                "rsync: write failed on \"/data/x\": No space left on device (28)",
+                ErrorCode::TransferDiskFull,
+            ),
+            (
+                Some(23),
+                "rsync: mkdir \"/data/projects/app\" failed: Permission denied (13)",
+                ErrorCode::TransferPermissionDenied,
+            ),
+            (Some(23), "", ErrorCode::TransferIncomplete),
+            (Some(30), "", ErrorCode::TransferTimeout),
+            (Some(12), "", ErrorCode::TransferProtocolError),
+            (Some(255), "", ErrorCode::SshConnectionFailed),
+            (None, "", ErrorCode::TransferRsyncFailed),
+        ];
+        for (exit_code, stderr, expected) in cases {
+            assert_eq!(
+                sync_failed(exit_code, stderr).error_code(),
+                Some(expected),
+                "exit {exit_code:?}, stderr {stderr:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_transfer_catalog_summary_includes_remediation() {
+        let summary = sync_failed(Some(12), "Connection refused")
+            .catalog_summary()
+            .expect("sync failures have a code");
+        let code = ErrorCode::NetworkConnectionRefused;
+        assert!(summary.starts_with(&format!("{}: ", code.code_string())));
+        assert!(summary.contains(code.message()));
+        assert!(summary.ends_with(code.remediation()[0]));
+    }
+
     // =========================================================================
     // HookError Tests
     // =========================================================================
@@ -1996,6 +2230,28 @@ user = "test"
         info!("TEST PASS: test_artifact_retrieval_warning_display_trait");
     }
 
+    #[test]
+    fn test_artifact_retrieval_warning_reports_retry_attempts() {
+        info!("TEST START: test_artifact_retrieval_warning_reports_retry_attempts");
+        let warning = ArtifactRetrievalWarning::new(
+            "worker1",
+            vec!["target/**".to_string()],
+            "rsync: connection unexpectedly closed",
+            Some(12),
+        );
+        assert_eq!(warning.retry_attempts, 0);
+        assert!(!warning.format_warning().contains("Retried"));
+
+        let warning = warning.with_retry_attempts(2);
+        assert!(
+            warning
+                .format_warning()
+                .contains("Retried 2 time(s) before giving up")
+        );
+        assert_eq!(warning.to_json()["retry_attempts"], 2);
+        info!("TEST PASS: test_artifact_retrieval_warning_reports_retry_attempts");
+    }
+
     // =========================================================================
     // Error Code Schema Validation
     // =========================================================================
@@ -2175,6 +2175,9 @@ mod tests {
             total_slots: 4,
             priority: 1,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         }
     }
 
@@ -1303,6 +1303,9 @@ mod tests {
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let backup = manager.create_backup(&worker, "1.0.0").await.unwrap();
@@ -1325,6 +1328,9 @@ mod tests {
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let backup1 = manager.create_backup(&worker, "1.0.0").await.unwrap();
@@ -1685,6 +1691,9 @@ mod tests {
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         }
     }
 
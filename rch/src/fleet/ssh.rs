@@ -259,7 +259,9 @@ impl<'a> SshExecutor<'a> {
         cmd.arg("-o")
             .arg(format!("ConnectTimeout={}", self.connect_timeout.as_secs()));
         cmd.arg("-o").arg("StrictHostKeyChecking=accept-new");
-        cmd.arg("-i").arg(&self.worker.identity_file);
+        if let Some(identity) = self.worker.ssh_identity() {
+            cmd.arg("-i").arg(identity);
+        }
         // All callers wrap `cmd.output()` in `tokio::time::timeout`. On
         // timeout the future is dropped; without this flag the spawned
         // ssh process keeps running and holding the network socket open
@@ -274,7 +276,9 @@ impl<'a> SshExecutor<'a> {
         cmd.arg("-o")
             .arg(format!("ConnectTimeout={}", self.scp_timeout.as_secs()));
         cmd.arg("-o").arg("StrictHostKeyChecking=accept-new");
-        cmd.arg("-i").arg(&self.worker.identity_file);
+        if let Some(identity) = self.worker.ssh_identity() {
+            cmd.arg("-i").arg(identity);
+        }
         // Same rationale as `build_ssh_args`: avoid leaking scp processes
         // when a timeout fires.
         cmd.kill_on_drop(true);
@@ -1056,6 +1060,9 @@ mod tests {
             total_slots: 4,
             priority: 1,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         }
     }
 
@@ -10,7 +10,8 @@ use crate::toolchain::detect_toolchain;
 use crate::transfer::{
     SyncResult, TransferPipeline, compute_project_hash_with_dependency_roots_and_policy,
     default_bun_artifact_patterns, default_c_cpp_artifact_patterns, default_rust_artifact_patterns,
-    default_rust_test_artifact_patterns, project_id_from_path,
+    default_rust_test_artifact_patterns, default_trunk_artifact_patterns,
+    default_wasm_pack_artifact_patterns, project_id_from_path,
 };
 use crate::ui::console::RchConsole;
 use rch_common::errors::catalog::ErrorCode;
@@ -28,26 +29,27 @@ use rch_common::repo_updater_contract::{
     RepoUpdaterOperatorOverride, RepoUpdaterTrustedHostIdentity, RepoUpdaterVerifiedHostIdentity,
 };
 use rch_common::{
-    BuildHeartbeatPhase, BuildHeartbeatRequest, ColorMode, CommandPriority, CommandTimingBreakdown,
-    CompilationKind, ControlState, DependencyClosurePlan, HookInput, HookOutput, IncidentEvent,
+    BuildHeartbeatPhase, BuildHeartbeatRequest, BuildRecord, Classification, CommandPriority,
+    CommandTimingBreakdown, CompilationKind, ControlState, DecisionStage, DecisionTrace,
+    DecisionTraceStep, DependencyClosurePlan, HookInput, HookOutput, IncidentEvent,
     IncidentEventType, IncidentLedger, IncidentLedgerConfig, IncidentReasonCode, IncidentSource,
-    OutputVisibility, REPO_UPDATER_CANONICAL_PROJECTS_ROOT, RepoUpdaterAdapterCommand,
-    RepoUpdaterAdapterContract, RepoUpdaterAdapterRequest, RepoUpdaterOutputFormat,
-    RequiredRuntime, SelectedMode, SelectedWorker, SelectionReason, SelectionResponse,
-    SelfHealingConfig, ToolchainInfo, TransferConfig, WorkerConfig, WorkerId,
+    OutputVisibility, QueueFairness, REPO_UPDATER_CANONICAL_PROJECTS_ROOT, ReleaseWorkerBody,
+    RepoUpdaterAdapterCommand, RepoUpdaterAdapterContract, RepoUpdaterAdapterRequest,
+    RepoUpdaterOutputFormat, RequiredRuntime, SelectedMode, SelectedWorker, SelectionReason,
+    SelectionResponse, SelfHealingConfig, ToolchainInfo, TransferConfig, WorkerConfig, WorkerId,
     build_dependency_closure_plan_with_policy, build_invocation, classify_command,
-    declined_compilation_due_to_structure, default_socket_path, mock,
+    declined_compilation_due_to_structure, default_socket_path, logical_current_dir, mock,
     normalize_project_path_with_policy,
     path_topology::PathTopologyPolicy,
     redaction::{redact_path, redact_secrets},
     ui::{
         ArtifactSummary, CelebrationSummary, CompilationProgress, CompletionCelebration, Icons,
-        OutputContext, RchTheme, TransferProgress,
+        OutputContext, RchTheme, TestSummary, TransferProgress,
     },
 };
 use rch_telemetry::protocol::{
     PIGGYBACK_MARKER, TelemetrySource, TestRunRecord, WorkerTelemetry,
-    extract_piggybacked_telemetry,
+    extract_piggybacked_telemetry, parse_streamed_telemetry_line,
 };
 use serde::{Deserialize, Serialize};
 use std::fs::OpenOptions;
@@ -89,22 +91,10 @@ use rich_rust::renderables::Panel;
 //
 // The only exception is toolchain failures (missing rust version), which
 // should fall back to local in case the local machine has the toolchain.
-
-/// Exit code for successful cargo command (tests passed, build succeeded).
-#[allow(dead_code)]
-const EXIT_SUCCESS: i32 = 0;
-
-/// Exit code for build/compilation error.
-const EXIT_BUILD_ERROR: i32 = 1;
-
-/// Exit code for cargo test when tests ran but some failed.
-#[allow(dead_code)] // Used in run_exec
-const EXIT_TEST_FAILURES: i32 = 101;
-
-/// Minimum exit code indicating the process was killed by a signal.
-/// Exit code = 128 + signal number (e.g., 137 = 128 + 9 = SIGKILL).
-#[allow(dead_code)] // Used in run_exec
-const EXIT_SIGNAL_BASE: i32 = 128;
+//
+// The exit-code constants and their classification (`classify_exit`) live in
+// `rch_common::command_outcome` so `run_hook` and `run_exec` share one policy.
+use rch_common::command_outcome::{CommandOutcome, EXIT_BUILD_ERROR, classify_exit};
 
 /// Process exit code returned when the remote compile SUCCEEDED but the build
 /// artifacts could NOT be transferred back, leaving the local build incomplete
@@ -161,6 +151,19 @@ fn remote_pipeline_failure_summary(worker_id: &WorkerId) -> String {
     )
 }
 
+/// Local-fallback summary line, naming the catalog code and first remediation
+/// step when the pipeline error carries a [`TransferError`].
+fn remote_fallback_summary(label: &str, error: &anyhow::Error) -> String {
+    match error
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<TransferError>())
+        .and_then(TransferError::catalog_summary)
+    {
+        Some(catalog) => format!("[RCH] local ({}) {}", label, catalog),
+        None => format!("[RCH] local ({})", label),
+    }
+}
+
 /// Run the hook, reading from stdin and writing to stdout.
 ///
 /// **Fail-open contract**: this function MUST return `Ok(())` for every
@@ -197,57 +200,66 @@ pub async fn run_hook() -> anyhow::Result<()> {
         return Ok(());
     }
 
-    // Parse the hook input
-    let hook_input: HookInput = match serde_json::from_str(input) {
-        Ok(hi) => hi,
-        Err(e) => {
-            warn!("Failed to parse hook input: {}", e);
-            // On parse error, allow the command (fail-open)
-            return Ok(());
-        }
-    };
-
-    // Process the hook request
-    let output = process_hook(hook_input).await;
-
-    // Write output:
-    //   - Deny: write JSON to block the command
-    //   - AllowWithModifiedCommand: write JSON to replace the command (transparent interception)
-    //   - Allow: output nothing (empty stdout = allow unchanged)
-    //
-    // serde / writeln errors here would be near-impossible (we just
-    // built the value from typed Rust), but if they occur we log and
-    // fall open rather than non-zero-exit and block the agent's Bash.
-    match &output {
-        HookOutput::Deny(_) | HookOutput::AllowWithModifiedCommand(_) => {
-            match serde_json::to_string(&output) {
-                Ok(json) => {
-                    if let Err(e) = writeln!(stdout, "{}", json) {
-                        warn!(target: "rch::hook", error = %e, "stdout write failed; falling open");
-                        return Ok(());
-                    }
-                    if let Err(e) = stdout.flush() {
-                        // Explicit flush: io::stdout() is fully buffered when
-                        // attached to a pipe (Claude Code reads via pipe).
-                        // Without this flush, abnormal exit could lose the JSON.
-                        warn!(target: "rch::hook", error = %e, "stdout flush failed; falling open");
-                        return Ok(());
-                    }
+    // A running `rch hook-server` answers with warm caches; anything short of
+    // a usable answer falls back to handling the request here.
+    let json = match forward_to_hook_server(input).await {
+        Some(json) => json,
+        None => {
+            // Parse the hook input
+            let hook_input: HookInput = match serde_json::from_str(input) {
+                Ok(hi) => hi,
+                Err(e) => {
+                    warn!("Failed to parse hook input: {}", e);
+                    // On parse error, allow the command (fail-open)
+                    return Ok(());
                 }
+            };
+
+            // Process the hook request
+            let output = process_hook(hook_input).await;
+            match hook_output_json(&output) {
+                Ok(json) => json,
                 Err(e) => {
                     warn!(target: "rch::hook", error = %e, "JSON serialization failed; falling open");
                     return Ok(());
                 }
             }
         }
-        HookOutput::Allow(_) => {
-            // Empty stdout = allow command unchanged
+    };
+
+    // Write output (see `hook_output_json`). writeln errors here would be
+    // near-impossible, but if they occur we log and fall open rather than
+    // non-zero-exit and block the agent's Bash.
+    if let Some(json) = json {
+        if let Err(e) = writeln!(stdout, "{}", json) {
+            warn!(target: "rch::hook", error = %e, "stdout write failed; falling open");
+            return Ok(());
+        }
+        if let Err(e) = stdout.flush() {
+            // Explicit flush: io::stdout() is fully buffered when
+            // attached to a pipe (Claude Code reads via pipe).
+            // Without this flush, abnormal exit could lose the JSON.
+            warn!(target: "rch::hook", error = %e, "stdout flush failed; falling open");
+            return Ok(());
         }
     }
 
     Ok(())
 }
 
+/// The JSON line the hook prints for `output`:
+///   - Deny: JSON to block the command
+///   - AllowWithModifiedCommand: JSON to replace the command (transparent interception)
+///   - Allow: nothing (empty stdout = allow unchanged)
+fn hook_output_json(output: &HookOutput) -> serde_json::Result<Option<String>> {
+    match output {
+        HookOutput::Deny(_) | HookOutput::AllowWithModifiedCommand(_) => {
+            serde_json::to_string(output).map(Some)
+        }
+        HookOutput::Allow(_) => Ok(None),
+    }
+}
+
 /// Install a panic hook that suppresses panic output and exits 0 when
 /// the process is invoked as a Claude Code hook. Without this, any
 /// panic in classify / serde / cache propagates as a non-zero exit,
@@ -416,7 +428,32 @@ fn remote_required_refusal_summary(reason: &str) -> String {
     }
 }
 
+/// Run `command` locally and exit with its status, with no remote-required
+/// policy check. Used for an explicit `rch exec --local`.
+fn exit_with_local_command(command: &str, reporter: &HookReporter) -> ! {
+    match local_fallback_command(command).status() {
+        Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+        Err(error) => {
+            reporter.summary(&format!("[RCH] local run failed: {error}"));
+            std::process::exit(EXIT_BUILD_ERROR);
+        }
+    }
+}
+
 fn exit_with_local_fallback(command: &str, reporter: &HookReporter, reason: &str) -> ! {
+    exit_with_timed_local_fallback(command, reporter, reason, None)
+}
+
+/// Run `command` locally and exit with its status, like
+/// [`exit_with_local_fallback`]. When `timing` names a project/kind, a
+/// successful local run is recorded in the timing history so the offload gate
+/// (`evaluate_timing_gate`) learns what the build costs locally.
+fn exit_with_timed_local_fallback(
+    command: &str,
+    reporter: &HookReporter,
+    reason: &str,
+    timing: Option<(&str, Option<CompilationKind>)>,
+) -> ! {
     let mut child = match local_fallback_command_for_policy(command, exec_requires_remote()) {
         Ok(child) => child,
         Err(LocalFallbackRefusal::RemoteRequired) => {
@@ -425,8 +462,18 @@ fn exit_with_local_fallback(command: &str, reporter: &HookReporter, reason: &str
         }
     };
 
+    let local_start = Instant::now();
     match child.status() {
-        Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+        Ok(status) => {
+            if status.success()
+                && let Some((project, kind)) = timing
+            {
+                let duration_ms =
+                    u64::try_from(local_start.elapsed().as_millis()).unwrap_or(u64::MAX);
+                record_build_timing(project, kind, duration_ms, false);
+            }
+            std::process::exit(status.code().unwrap_or(1))
+        }
         Err(error) => {
             reporter.summary(&format!("[RCH] local fallback failed: {error}"));
             std::process::exit(EXIT_BUILD_ERROR);
@@ -688,14 +735,30 @@ fn record_hook_incident(event: &IncidentEvent) {
     }
 }
 
-pub async fn run_exec(command_parts: Vec<String>) -> anyhow::Result<()> {
+pub async fn run_exec(
+    command_parts: Vec<String>,
+    local: bool,
+    workers: Vec<String>,
+) -> anyhow::Result<()> {
     let command = join_exec_command(&command_parts);
     if command.is_empty() {
         anyhow::bail!("No command provided to exec");
     }
 
+    // `rch exec --local`: one-shot override, run here without classifying the
+    // command or asking the daemon. An explicit flag wins over
+    // RCH_REQUIRE_REMOTE, which guards automatic fallbacks.
+    if local {
+        let visibility = load_config()
+            .map(|config| config.output.visibility)
+            .unwrap_or(OutputVisibility::Summary);
+        let reporter = HookReporter::new(visibility);
+        reporter.verbose("[RCH] local (--local)");
+        exit_with_local_command(&command, &reporter);
+    }
+
     // Classify the command
-    let classification = classify_command(&command);
+    let classification = classify_with_user_rules(&command);
     if !classification.is_compilation {
         // This should not normally happen because the hook only rewrites
         // compilations. Preserve the ordinary local behavior, but honor
@@ -715,6 +778,14 @@ pub async fn run_exec(command_parts: Vec<String>) -> anyhow::Result<()> {
     };
 
     let reporter = HookReporter::new(config.output.visibility);
+    let mut trace = DecisionTrace::new();
+    trace.push(
+        DecisionStage::Classification,
+        classification_trace_detail(
+            &classification,
+            (!config.general.force_remote).then_some(config.compilation.confidence_threshold),
+        ),
+    );
 
     // Build path topology policy from loaded config so that any normalization
     // warnings reference the configured roots rather than compiled-in defaults.
@@ -722,13 +793,80 @@ pub async fn run_exec(command_parts: Vec<String>) -> anyhow::Result<()> {
 
     // Extract project name honoring configured path topology.
     let project = extract_project_name_with_policy(&topology_policy);
+    // A local `cargo run` also times the program itself, which says nothing
+    // about what the build costs locally.
+    let local_timing = (classification.kind != Some(CompilationKind::CargoRun))
+        .then_some((project.as_str(), classification.kind));
+
+    // Timing gate: skip offload when history says this build is faster locally.
+    // Fail-open (offload) without history; never applies when remote is required.
+    if !config.general.force_remote && !exec_requires_remote() {
+        let estimate = estimate_timing_for_build(&project, classification.kind, &config);
+        let gate = evaluate_timing_gate(estimate.as_ref(), &config.compilation);
+        if gate.keeps_local() {
+            debug!(
+                "Timing gate kept build local: {}",
+                gate.detail(&config.compilation)
+            );
+            reporter.summary("[RCH] local (not worth offloading)");
+            reporter.verbose(&format!(
+                "[RCH] timing gate: {}",
+                gate.detail(&config.compilation)
+            ));
+            exit_with_timed_local_fallback(
+                &command,
+                &reporter,
+                "not worth offloading",
+                local_timing,
+            );
+        }
+        trace.push(
+            DecisionStage::Classification,
+            format!("timing gate: {}", gate.detail(&config.compilation)),
+        );
+    }
+
+    // Project-size gate: tiny projects are not worth the sync + SSH overhead.
+    // Off unless a threshold is configured; never applies when remote is required.
+    let project_root = std::env::current_dir().ok();
+    let size_root = project_root.as_deref().map(resolve_size_root);
+    if !config.general.force_remote
+        && !exec_requires_remote()
+        && let Some(root) = &size_root
+        && let Some(detail) = evaluate_project_size_gate(root, classification.kind, &config)
+    {
+        debug!("Project-size gate kept build local: {}", detail);
+        reporter.summary("[RCH] local (project too small)");
+        reporter.verbose(&format!("[RCH] project-size gate: {}", detail));
+        exit_with_timed_local_fallback(&command, &reporter, "project too small", local_timing);
+    }
+
+    // Project-size cap: a safety ceiling rather than a transfer estimate, so it
+    // applies even with force_remote and is checked before any worker is
+    // contacted. Printed to stderr so it is seen even with quiet output.
+    if let Some(root) = &size_root
+        && let Some(detail) = evaluate_project_size_cap(root, &config.transfer)
+    {
+        let code = ErrorCode::TransferProjectTooLarge;
+        warn!(
+            "Project-size cap kept build local [{}]: {}",
+            code.code_string(),
+            detail
+        );
+        eprintln!("[RCH] {} {}", code.code_string(), detail);
+        exit_with_timed_local_fallback(
+            &command,
+            &reporter,
+            "project exceeds transfer.max_project_bytes",
+            local_timing,
+        );
+    }
 
     // Estimate cores needed
     let estimated_cores =
         estimate_cores_for_command(classification.kind, &command, &config.compilation);
 
     // Detect toolchain
-    let project_root = std::env::current_dir().ok();
     let toolchain = if let Some(root) = &project_root {
         detect_toolchain(root).ok()
     } else {
@@ -740,18 +878,110 @@ pub async fn run_exec(command_parts: Vec<String>) -> anyhow::Result<()> {
         &reporter,
         Some(&command_parts),
     );
+    // `cargo doc --open` would launch a browser on the worker; build without
+    // it and open the retrieved docs locally once the build succeeds.
+    let command_tokens = normalize_exec_command_parts(&command_parts);
+    let doc_open_tokens = strip_doc_open_flag(classification.kind, &command_tokens);
+    // `cargo run` builds on the worker and runs the binary here; anything the
+    // plan cannot resolve stays local as a whole.
+    let local_run = if classification.kind == Some(CompilationKind::CargoRun) {
+        let manifest_dir = project_root.as_deref().unwrap_or_else(|| Path::new("."));
+        match plan_local_run(&command_tokens, manifest_dir) {
+            Ok(plan) => Some(plan),
+            Err(reason) => {
+                debug!("cargo run kept local: {}", reason);
+                reporter.summary(&format!("[RCH] local (cargo run: {})", reason));
+                exit_with_local_fallback(&command, &reporter, "cargo run not offloadable");
+            }
+        }
+    } else {
+        None
+    };
+    let (base_command, base_tokens) = match (&doc_open_tokens, &local_run) {
+        (Some(tokens), _) => {
+            reporter.verbose("[RCH] removed --open before remote execution");
+            (join_exec_command(tokens), tokens.as_slice())
+        }
+        (None, Some(plan)) => {
+            let build_command = join_exec_command(&plan.build_tokens);
+            reporter.verbose(&format!(
+                "[RCH] building remotely as `{}`, running locally",
+                build_command
+            ));
+            (build_command, plan.build_tokens.as_slice())
+        }
+        (None, None) => (command.clone(), command_parts.as_slice()),
+    };
     let remote_command = rewrite_cargo_target_dir_command_for_remote(
-        &command,
-        Some(&command_parts),
+        &base_command,
+        Some(base_tokens),
         forwarded_cargo_target_dir.as_ref(),
         &reporter,
     );
 
+    // Build-affecting env vars (RUSTFLAGS, RUSTC_WRAPPER, CARGO_INCREMENTAL) must
+    // reach the worker, or the remote build produces different artifacts.
+    let mut env_allowlist = config.environment.allowlist.clone();
+    match resolve_forwarded_build_env(classification.kind, &env_allowlist, &reporter) {
+        BuildEnvForwarding::Refuse(reason) => {
+            warn!("Refusing offload: {}", reason);
+            reporter.summary(&format!("[RCH] local ({})", reason));
+            exit_with_timed_local_fallback(&command, &reporter, &reason, local_timing);
+        }
+        BuildEnvForwarding::Forward(forwarded) => {
+            for key in forwarded {
+                if !env_allowlist_contains(&env_allowlist, &key) {
+                    env_allowlist.push(key);
+                }
+            }
+        }
+    }
+
     // Determine required runtime
     let required_runtime = required_runtime_for_kind(classification.kind);
     let command_priority = command_priority_from_env(&reporter);
     let wait_for_worker = queue_when_busy_enabled();
     let preferred_workers = preferred_workers_from_env();
+    // `rch exec --workers`: a hard restriction, unlike the RCH_WORKER(S) preference.
+    let allowed_workers = dedupe_worker_ids(
+        workers
+            .iter()
+            .flat_map(|value| parse_preferred_workers(value))
+            .collect(),
+    );
+
+    // Opt-in first-offload confirmation. Never applies when remote is required;
+    // a project the user already declined stays local without a prompt.
+    let confirm_first = config.general.confirm_first_offload
+        && !config.general.force_remote
+        && !exec_requires_remote()
+        && is_first_offload(&project);
+    if confirm_first && OffloadConsent::load().is_declined(&project) {
+        reporter.summary("[RCH] local (offload declined for project)");
+        exit_with_timed_local_fallback(
+            &command,
+            &reporter,
+            "offload declined for project",
+            local_timing,
+        );
+    }
+
+    // Duplicate hook invocations of the same tool call coalesce onto one
+    // build. Without a tool call id every run is its own build.
+    let session = std::env::var("RCH_SESSION_ID")
+        .ok()
+        .filter(|session| !session.is_empty());
+    let idempotency_key = std::env::var("RCH_TOOL_USE_ID")
+        .ok()
+        .filter(|tool_use_id| !tool_use_id.is_empty())
+        .map(|tool_use_id| {
+            build_exec_idempotency_key(
+                &project,
+                &std::env::current_dir().unwrap_or_default(),
+                &tool_use_id,
+                session.as_deref(),
+            )
+        });
 
     // Query daemon for worker selection
     let response = match query_daemon(
@@ -766,6 +996,10 @@ pub async fn run_exec(command_parts: Vec<String>) -> anyhow::Result<()> {
         Some(std::process::id()),
         wait_for_worker,
         &preferred_workers,
+        config.transfer.fleet.as_deref(),
+        config.transfer.min_free_disk_requirement(),
+        &allowed_workers,
+        idempotency_key.as_deref(),
     )
     .await
     {
@@ -814,6 +1048,10 @@ pub async fn run_exec(command_parts: Vec<String>) -> anyhow::Result<()> {
                         Some(std::process::id()),
                         wait_for_worker,
                         &preferred_workers,
+                        config.transfer.fleet.as_deref(),
+                        config.transfer.min_free_disk_requirement(),
+                        &allowed_workers,
+                        idempotency_key.as_deref(),
                     )
                     .await
                     .ok()
@@ -858,34 +1096,195 @@ pub async fn run_exec(command_parts: Vec<String>) -> anyhow::Result<()> {
         }
     };
 
+    // A duplicate of a build already running: replay that build's output and
+    // report its result.
+    if let (SelectionReason::CoalescedDuplicate, Some(build_id)) =
+        (&response.reason, response.build_id)
+    {
+        reporter.verbose(&format!(
+            "[RCH] duplicate of build {}, following its output",
+            build_id
+        ));
+        match follow_build_output(&config.general.socket_path, build_id).await {
+            Ok(Some(exit_code)) => {
+                reporter.summary(&format!(
+                    "[RCH] coalesced with build {} (exit {})",
+                    build_id, exit_code
+                ));
+                std::process::exit(exit_code);
+            }
+            Ok(None) => {}
+            Err(e) => warn!("Failed to follow coalesced build {}: {}", build_id, e),
+        }
+        match wait_for_build_result(&config.general.socket_path, build_id).await {
+            Ok(Some(record)) => {
+                reporter.summary(&format!(
+                    "[RCH] coalesced with build {} (exit {})",
+                    build_id, record.exit_code
+                ));
+                std::process::exit(record.exit_code);
+            }
+            Ok(None) => warn!("Coalesced build {} left the daemon history", build_id),
+            Err(e) => warn!("Failed to wait for coalesced build {}: {}", build_id, e),
+        }
+        reporter.summary("[RCH] local (coalesced build result unavailable)");
+        exit_with_timed_local_fallback(
+            &command,
+            &reporter,
+            "coalesced build result unavailable",
+            local_timing,
+        );
+    }
+
     // Check if a worker was assigned
     let Some(worker) = response.worker else {
-        reporter.summary(&format!("[RCH] local ({})", response.reason));
-        exit_with_local_fallback(&command, &reporter, "no worker assigned");
+        if allowed_workers.is_empty() {
+            reporter.summary(&format!("[RCH] local ({})", response.reason));
+        } else {
+            reporter.summary(&format!(
+                "[RCH] local ({}; --workers {})",
+                response.reason,
+                allowed_workers
+                    .iter()
+                    .map(|id| id.as_str())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ));
+        }
+        exit_with_timed_local_fallback(&command, &reporter, "no worker assigned", local_timing);
     };
 
     info!(
         "Selected worker: {} at {}@{} ({} slots remaining after reservation, speed {:.1})",
         worker.id, worker.user, worker.host, worker.slots_available, worker.speed_score
     );
+    if let SelectionReason::QueuedFair { position } = response.reason {
+        reporter.verbose(&format!(
+            "[RCH] queued at position {} (round-robin), assigned {}",
+            position, worker.id
+        ));
+    }
+
+    let consent = if confirm_first {
+        confirm_offload(&project, &worker)
+    } else {
+        ConsentAnswer::Accepted
+    };
+    if consent != ConsentAnswer::Accepted {
+        if let Err(e) = release_worker(
+            &config.general.socket_path,
+            &worker.id,
+            estimated_cores,
+            response.build_id,
+            None,
+            None,
+            None,
+            None,
+            &[],
+            None,
+            None,
+        )
+        .await
+        {
+            warn!("Failed to release worker slots: {}", e);
+        }
+        let reason = if consent == ConsentAnswer::Declined {
+            OffloadConsent::record_decline(&project);
+            "offload declined"
+        } else {
+            "offload not confirmed: no terminal"
+        };
+        reporter.summary(&format!("[RCH] local ({})", reason));
+        exit_with_timed_local_fallback(&command, &reporter, reason, local_timing);
+    }
+
+    // Shadow mode: the local build is authoritative; the worker runs the same
+    // build silently and only its exit code is compared.
+    if config.general.shadow_mode && !exec_requires_remote() {
+        let exit_code = run_shadow_build(
+            &worker,
+            response.build_id,
+            &command,
+            &remote_command,
+            env_allowlist.clone(),
+            forwarded_cargo_target_dir,
+            &config,
+            toolchain.as_ref(),
+            classification.kind,
+            &reporter,
+            &topology_policy,
+            &project,
+            estimated_cores,
+            local_timing,
+        )
+        .await;
+        std::process::exit(exit_code);
+    }
+
+    // Shard unfiltered nextest runs across any extra free workers.
+    if config.selection.shard_tests
+        && is_shardable_nextest_command(classification.kind, &remote_command)
+    {
+        let extra = acquire_shard_workers(
+            &config,
+            &worker,
+            &project,
+            estimated_cores,
+            &remote_command,
+            toolchain.as_ref(),
+            required_runtime,
+            command_priority,
+            &preferred_workers,
+            &allowed_workers,
+        )
+        .await;
+        if !extra.is_empty() {
+            let mut shards = vec![ShardAssignment {
+                worker: worker.clone(),
+                build_id: response.build_id,
+            }];
+            shards.extend(extra);
+            let exit_code = run_sharded_tests(
+                &shards,
+                &command,
+                &remote_command,
+                &env_allowlist,
+                forwarded_cargo_target_dir,
+                &config,
+                toolchain.as_ref(),
+                classification.kind,
+                &reporter,
+                &topology_policy,
+                &project,
+                estimated_cores,
+            )
+            .await;
+            std::process::exit(exit_code);
+        }
+        reporter.verbose("[RCH] test sharding skipped: no additional workers free");
+    }
 
     // Execute remote compilation pipeline (topology_policy was built earlier
     // from the loaded config so diagnostics reference configured roots).
+    let local_target_dir = forwarded_cargo_target_dir.clone();
     let remote_start = Instant::now();
     let result = execute_remote_compilation(
         &worker,
         &remote_command,
         config.transfer.clone(),
-        config.environment.allowlist.clone(),
+        env_allowlist,
         forwarded_cargo_target_dir,
         &config.compilation,
+        &config.self_healing,
         toolchain.as_ref(),
         classification.kind,
         &reporter,
         &config.general.socket_path,
-        config.output.color_mode,
+        &config.output,
+        &config.telemetry,
         response.build_id,
         &topology_policy,
+        RemoteOutput::Forward,
     )
     .await;
     let remote_elapsed = remote_start.elapsed();
@@ -900,6 +1299,7 @@ pub async fn run_exec(command_parts: Vec<String>) -> anyhow::Result<()> {
         timing.total = Some(remote_elapsed);
         timing
     });
+    let release_trace = release_decision_trace(trace, &result);
     if let Err(e) = release_worker(
         &config.general.socket_path,
         &worker.id,
@@ -907,18 +1307,39 @@ pub async fn run_exec(command_parts: Vec<String>) -> anyhow::Result<()> {
         response.build_id,
         Some(release_exit_code),
         None,
-        None,
+        result.as_ref().ok().map(|ok| ok.bytes_transferred),
         release_timing.as_ref(),
+        &release_trace,
+        project_root.as_deref(),
+        toolchain.as_ref(),
     )
     .await
     {
         warn!("Failed to release worker slots: {}", e);
     }
 
+    // Feed the timing gate with successful builds only (a failed build stops
+    // early and would skew the estimate). Recorded synchronously: every branch
+    // below exits.
+    if let Ok(result) = &result {
+        if result.exit_code == 0 {
+            record_build_timing(&project, classification.kind, result.duration_ms, true);
+        }
+        notify_slow_build(
+            &config.notifications,
+            &project,
+            &worker.id,
+            remote_elapsed,
+            result.exit_code,
+        )
+        .await;
+    }
+
     // Handle result and exit with appropriate code
     match result {
         Ok(result) => {
-            if result.exit_code == 0 {
+            let outcome = classify_exit(result.exit_code, &result.stderr);
+            if outcome.is_success() {
                 reporter.summary(&format!(
                     "[RCH] remote {} ({})",
                     worker.id,
@@ -934,12 +1355,31 @@ pub async fn run_exec(command_parts: Vec<String>) -> anyhow::Result<()> {
                 {
                     warn!("Failed to record build: {}", e);
                 }
+                if doc_open_tokens.is_some()
+                    && local_doc_open_enabled(reporter.visibility, |key| std::env::var(key).ok())
+                {
+                    let manifest_dir = project_root.as_deref().unwrap_or_else(|| Path::new("."));
+                    let target_dir =
+                        local_target_dir_or_default(local_target_dir.as_deref(), manifest_dir);
+                    open_local_docs(&target_dir, &command_tokens, manifest_dir, &reporter);
+                }
+                if let Some(plan) = &local_run {
+                    let manifest_dir = project_root.as_deref().unwrap_or_else(|| Path::new("."));
+                    let target_dir =
+                        local_target_dir_or_default(local_target_dir.as_deref(), manifest_dir);
+                    exit_with_local_run(plan, &target_dir, manifest_dir, &command, &reporter);
+                }
                 std::process::exit(0);
-            } else if is_toolchain_failure(&result.stderr, result.exit_code) {
+            } else if outcome.should_fall_back_locally() {
                 // Toolchain failure - fall back to local
                 warn!("Remote toolchain failure, falling back to local");
                 reporter.summary(&format!("[RCH] local (toolchain missing on {})", worker.id));
-                exit_with_local_fallback(&command, &reporter, "remote toolchain missing");
+                exit_with_timed_local_fallback(
+                    &command,
+                    &reporter,
+                    "remote toolchain missing",
+                    local_timing,
+                );
             } else if let Some(env_failure) =
                 detect_worker_system_dependency_failure(&result.stderr, result.exit_code)
             {
@@ -964,9 +1404,10 @@ pub async fn run_exec(command_parts: Vec<String>) -> anyhow::Result<()> {
                 std::process::exit(result.exit_code);
             } else {
                 // Command failed remotely - exit with the same code
-                reporter.summary(&format!(
-                    "[RCH] remote {} failed (exit {})",
-                    worker.id, result.exit_code
+                reporter.summary(&remote_failure_summary(
+                    &worker.id,
+                    outcome,
+                    result.exit_code,
                 ));
                 std::process::exit(result.exit_code);
             }
@@ -987,7 +1428,7 @@ pub async fn run_exec(command_parts: Vec<String>) -> anyhow::Result<()> {
                     preflight_err.report_json()
                 ));
                 let fallback_reason = format!("dependency preflight failed: {evidence_summary}");
-                exit_with_local_fallback(&command, &reporter, &fallback_reason);
+                exit_with_timed_local_fallback(&command, &reporter, &fallback_reason, local_timing);
             }
 
             // Check for transfer skip (not a failure)
@@ -995,7 +1436,12 @@ pub async fn run_exec(command_parts: Vec<String>) -> anyhow::Result<()> {
                 && let TransferError::TransferSkipped { reason } = skip_err
             {
                 reporter.summary(&format!("[RCH] local ({})", reason));
-                exit_with_local_fallback(&command, &reporter, "transfer skipped");
+                exit_with_timed_local_fallback(
+                    &command,
+                    &reporter,
+                    "transfer skipped",
+                    local_timing,
+                );
             }
 
             if classify_remote_pipeline_failure(&e)
@@ -1011,8 +1457,13 @@ pub async fn run_exec(command_parts: Vec<String>) -> anyhow::Result<()> {
 
             // Other errors - run locally
             warn!("Remote execution failed: {}, running locally", e);
-            reporter.summary("[RCH] local (remote execution failed)");
-            exit_with_local_fallback(&command, &reporter, "remote execution failed");
+            reporter.summary(&remote_fallback_summary("remote execution failed", &e));
+            exit_with_timed_local_fallback(
+                &command,
+                &reporter,
+                "remote execution failed",
+                local_timing,
+            );
         }
     }
 }
@@ -1046,8 +1497,13 @@ impl HookReporter {
 //
 // The bounded daemon-autostart cluster (lock/cooldown/spawn/health-probe/
 // socket-wait) lives in the `auto_start` submodule. `try_auto_start_daemon`
-// is its only cross-module entry point (called from `run_exec` below).
+// is its entry point (called from `run_exec` below); `rch doctor` also reads
+// the state directory it resolves.
 mod auto_start;
+pub(crate) use auto_start::{
+    autostart_cooldown_path, autostart_lock_path, configured_autostart_state_dir,
+    configured_daemon_log_path, daemon_log_path, daemon_log_stdio, rotated_daemon_log_path,
+};
 
 // The build-heartbeat / progress-reporting cluster (the periodic snapshot, the
 // background loop, the progress-counter bump, and the socket send) lives in the
@@ -1062,7 +1518,13 @@ mod progress_reporting;
 // `execute_remote_compilation` is imported so `run_hook` / `run_exec` call it
 // unqualified.
 mod transfer_orchestration;
-use transfer_orchestration::execute_remote_compilation;
+use transfer_orchestration::{RemoteOutput, execute_remote_compilation};
+
+// The client-wide upload cap (`transfer.max_concurrent_client_transfers`), a
+// lock-file semaphore in the state directory shared by every `rch exec`, lives
+// in the `transfer_slots` submodule. `execute_remote_compilation` holds a slot
+// for each source sync.
+mod transfer_slots;
 
 // The repo_updater pre-sync subsystem (closure-convergence orchestration +
 // adapter invocation + contract/auth resolution + sync-root detection) lives in
@@ -1094,32 +1556,29 @@ use dependency_closure::{
     DependencyPreflightReport, DependencyPreflightStatus,
 };
 
-// The remote-execution result type (`RemoteExecutionResult`) and the outcome
-// classifiers that interpret it live in the `remote_result` submodule. The four
-// classifier fns below are consumed by `run_hook` / `run_exec`; the sibling
+// The remote-execution result type (`RemoteExecutionResult`) and the failure
+// detail helpers that interpret it live in the `remote_result` submodule. The
+// three fns below are consumed by `run_hook` / `run_exec`; the sibling
 // `transfer_orchestration` constructs and returns `RemoteExecutionResult`
 // directly from `super::remote_result`.
 mod remote_result;
-use remote_result::{
-    detect_worker_system_dependency_failure, is_signal_killed, is_toolchain_failure, signal_name,
-};
+use remote_result::{detect_worker_system_dependency_failure, remote_failure_summary, signal_name};
 
 // The remote cargo target-dir resolution / naming / command-rewrite cluster
 // (CARGO_TARGET_DIR forwarding, the unique-per-job + stable-pooled remote dir
 // names, and the helpers that strip a local target-dir from a delegated command)
-// lives in the `cargo_target_dir` submodule. `run_hook` / `run_exec` call
-// `resolve_forwarded_cargo_target_dir` + `rewrite_cargo_target_dir_command_for_remote`,
-// and `add_cargo_isolation` shares `sanitize_cargo_home_token`, so those three are
-// imported here; the sibling `transfer_orchestration` imports the dir-naming / env
-// helpers it needs directly from `super::cargo_target_dir`.
+// lives in the `cargo_target_dir` submodule. Only the helpers `hook` itself
+// calls are imported here; `transfer_orchestration` imports its own directly.
 mod cargo_target_dir;
 use cargo_target_dir::{
+    BuildEnvForwarding, env_allowlist_contains, resolve_forwarded_build_env,
     resolve_forwarded_cargo_target_dir, rewrite_cargo_target_dir_command_for_remote,
     sanitize_cargo_home_token,
 };
 
 // The remote artifact-pattern selection cluster (which files travel back from a
-// worker, keyed on `CompilationKind`) lives in the `artifact_patterns` submodule.
+// worker, keyed on `CompilationKind` plus report-producing flags such as
+// `--timings`) lives in the `artifact_patterns` submodule.
 // `get_artifact_patterns` / `get_custom_target_artifact_patterns` /
 // `kind_produces_transferable_artifacts` have no non-test caller in `hook` itself —
 // they are consumed by the sibling `transfer_orchestration`
@@ -1137,12 +1596,47 @@ mod selection_response;
 use selection_response::parse_selection_response;
 
 // Build-timing history (persistence + offload-gating estimation) lives in the
-// `timing_history` submodule. `record_build_timing` is the only item the hook
-// hot path calls (two sites in the remote-classification path), so it is
-// re-exported here; the on-disk model, the process-global cache, and the
-// estimator surface stay `pub(super)` for the test suite and otherwise private.
+// `timing_history` submodule. `run_exec` records every successful remote build
+// and every successful local fallback through `record_build_timing`, and consults
+// `estimate_timing_for_build` + `evaluate_timing_gate` before querying the
+// daemon, so those are imported here; the on-disk model and the process-global
+// cache stay `pub(super)` for the test suite and otherwise private.
 mod timing_history;
-use timing_history::record_build_timing;
+use timing_history::{estimate_timing_for_build, evaluate_timing_gate, record_build_timing};
+
+// The `compilation.min_project_files` / `min_project_bytes` gate (a bounded
+// walk of the project root) lives in the `project_size` submodule; `run_exec`
+// consults it next to the timing gate, before querying the daemon.
+mod project_size;
+use project_size::{evaluate_project_size_cap, evaluate_project_size_gate, resolve_size_root};
+
+// The opt-in `general.confirm_first_offload` gate (first-offload detection from
+// the timing history, the `/dev/tty` prompt, and the per-project opt-out store)
+// lives in the `offload_consent` submodule. `run_exec` checks the opt-out before
+// querying the daemon and prompts once a worker is assigned.
+mod offload_consent;
+use offload_consent::{ConsentAnswer, OffloadConsent, confirm_offload, is_first_offload};
+
+// The optional persistent hook server (`rch hook-server`: the socket
+// listener, the one-line JSON wire format, and the hook-side forwarder) lives
+// in the `hook_server` submodule. `run_hook` tries `forward_to_hook_server`
+// before answering on its own; `main` runs `run_hook_server`.
+mod hook_server;
+use hook_server::forward_to_hook_server;
+pub(crate) use hook_server::run_hook_server;
+
+// The opt-in `general.shadow_mode` path (a concurrent local build that stays
+// authoritative, a silent remote shadow, and the divergence log) lives in the
+// `shadow_mode` submodule. `run_exec` hands over to `run_shadow_build` once a
+// worker is assigned, ahead of test sharding.
+mod shadow_mode;
+use shadow_mode::run_shadow_build;
+
+// `rch diff-artifacts` (one remote and one local build into scratch target
+// dirs, compared artifact by artifact) lives in the `diff_artifacts` submodule
+// and reuses the remote pipeline and the local fallback command.
+mod diff_artifacts;
+pub(crate) use diff_artifacts::run_diff_artifacts;
 
 // The daemon IPC client (worker-selection / release / build-record requests
 // over the `rchd` Unix socket, plus request-timeout + queue-when-busy policy
@@ -1151,17 +1645,40 @@ use timing_history::record_build_timing;
 // `record_build` / `queue_when_busy_enabled` are re-exported for the hook hot
 // path. The timeout helpers and `urlencoding_encode` stay `pub(super)` for tests.
 mod daemon_ipc;
+use daemon_ipc::{
+    build_exec_idempotency_key, follow_build_output, queue_when_busy_enabled, record_build,
+    wait_for_build_result,
+};
 pub(crate) use daemon_ipc::{query_daemon, release_worker};
-use daemon_ipc::{queue_when_busy_enabled, record_build};
 
 // Command-string parsing utilities (tokenization + cargo flag/env analyzers +
 // offload core estimation) live in the `command_parsing` submodule.
 // `cargo_job_count_for_command` / `estimate_cores_for_command` are re-exported
 // `pub(crate)` because `commands::status` also calls them; the `--test-threads` /
 // `-j` / `--ignored` / `--exact` / filtered-test detectors stay `pub(super)` for
-// the test suite, and the numeric `parse_*` helpers stay module-private.
+// the test suite, `has_timings_flag` / `nextest_profile` feed `artifact_patterns`, and the numeric
+// `parse_*` helpers stay module-private.
 mod command_parsing;
 pub(crate) use command_parsing::{cargo_job_count_for_command, estimate_cores_for_command};
+use command_parsing::{
+    command_produces_machine_stdout, has_timings_flag, is_shardable_nextest_command,
+    nextest_profile,
+};
+
+// Multi-worker nextest sharding (`selection.shard_tests`): extra-worker
+// acquisition, `--partition` rewriting, concurrent shard execution, and exit-code
+// aggregation live in the `test_sharding` submodule. `run_exec` drives it right
+// after the first worker is assigned; the pure helpers stay `pub(super)` for tests.
+mod test_sharding;
+use test_sharding::{ShardAssignment, acquire_shard_workers, run_sharded_tests};
+
+// Bounded capture of remote output while the compile-progress UI is active
+// (`output.max_memory_bytes`, spilling to `~/.cache/rch/logs`) lives in the
+// `output_capture` submodule. `build_logs` / `latest_build_log` are re-exported
+// `pub(crate)` for `rch logs`; `OutputCapture` is used by transfer orchestration.
+mod output_capture;
+use output_capture::{OutputCapture, build_log_dir};
+pub(crate) use output_capture::{build_logs, latest_build_log};
 
 // Human-facing job-output rendering (compile-summary panel, job banner, and the
 // duration/speed/profile/target formatting + detection helpers) lives in the
@@ -1172,6 +1689,55 @@ pub(crate) use command_parsing::{cargo_job_count_for_command, estimate_cores_for
 mod formatting;
 use formatting::{estimate_local_time_ms, format_duration_ms};
 
+// `cargo doc --open` handling (strip `--open` before the remote build, then
+// open the retrieved docs locally unless output is quiet or machine-readable)
+// lives in the `doc_open` submodule; `run_exec` is its only caller.
+mod doc_open;
+use doc_open::{local_doc_open_enabled, open_local_docs, strip_doc_open_flag};
+
+// `cargo run` handling (build on the worker as the matching `cargo build`, then
+// run the retrieved binary locally with the original arguments) lives in the
+// `cargo_run` submodule; `run_exec` is its only caller.
+mod cargo_run;
+use cargo_run::{exit_with_local_run, plan_local_run};
+
+// Vendored crate registries (`cargo vendor` + `[source.crates-io]
+// replace-with`) that live outside the project are synced as an extra root so
+// `--offline` builds resolve on the worker; see the `cargo_vendor` submodule.
+mod cargo_vendor;
+use cargo_vendor::{command_is_offline, vendor_sync_root, vendored_source_dir};
+
+// Forcing color on the worker (config + local output context, plus the cargo /
+// compiler color flag) lives in the `remote_color` submodule; it is used by
+// `execute_remote_compilation`.
+mod remote_color;
+
+// Feature parity checks (explicit feature flags, plus feature-affecting env
+// vars that the allowlist would drop) live in the `feature_parity` submodule;
+// they are used by `execute_remote_compilation`.
+mod feature_parity;
+
+// Slow-build webhook / command notifications (`[notifications]`) live in the
+// `slow_build_notify` submodule; `run_exec` and `handle_selection_response`
+// call it once per remote build, after the worker is released.
+mod slow_build_notify;
+use slow_build_notify::notify_slow_build;
+
+// The `rch selftest` end-to-end smoke test (throwaway benchmark project, daemon
+// selection with auto-start, and a real `execute_remote_compilation` run) lives
+// in the `pipeline_selftest` submodule. The runner and its report are
+// re-exported `pub(crate)` for `commands::selftest`.
+mod pipeline_selftest;
+pub(crate) use pipeline_selftest::{PipelineSelftestReport, run_pipeline_selftest};
+
+/// Local target directory the retrieved artifacts landed in: the forwarded
+/// `CARGO_TARGET_DIR`, else cargo's default `<manifest_dir>/target`.
+fn local_target_dir_or_default(forwarded: Option<&Path>, manifest_dir: &Path) -> PathBuf {
+    forwarded
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| manifest_dir.join("target"))
+}
+
 fn is_test_kind(kind: Option<CompilationKind>) -> bool {
     matches!(
         kind,
@@ -1207,6 +1773,45 @@ fn emit_first_run_message(worker: &SelectedWorker, remote_ms: u64, local_ms: Opt
     eprintln!();
 }
 
+/// Classify a command with the user's classifier rules, if any, layered over
+/// the built-in classifier.
+pub(crate) fn classify_with_user_rules(cmd: &str) -> Classification {
+    match crate::config::classifier_rules() {
+        Some(rules) => rules.classify(cmd),
+        None => classify_command(cmd),
+    }
+}
+
+/// Classification step of a build's decision trace. `threshold` is `None`
+/// when `force_remote` bypasses the confidence check.
+fn classification_trace_detail(classification: &Classification, threshold: Option<f64>) -> String {
+    let kind = classification.kind.map_or("unknown", |kind| kind.as_str());
+    let check = match threshold {
+        Some(threshold) => format!("threshold {:.2}", threshold),
+        None => "threshold bypassed by force_remote".to_string(),
+    };
+    format!(
+        "{} (confidence {:.2}, {}): {}",
+        kind, classification.confidence, check, classification.reason
+    )
+}
+
+/// Decision trace sent with the release request: the hook's own steps plus
+/// those of the remote pipeline, or why the pipeline failed.
+fn release_decision_trace(
+    mut trace: DecisionTrace,
+    result: &anyhow::Result<remote_result::RemoteExecutionResult>,
+) -> Vec<DecisionTraceStep> {
+    match result {
+        Ok(result) => trace.extend(result.decision_trace.steps().iter().cloned()),
+        Err(e) => trace.push(
+            DecisionStage::Execution,
+            format!("remote pipeline failed: {}", e),
+        ),
+    }
+    trace.into_steps()
+}
+
 /// Process a hook request and return the output.
 async fn process_hook(input: HookInput) -> HookOutput {
     // Tier 0: Only process Bash tool
@@ -1224,7 +1829,7 @@ async fn process_hook(input: HookInput) -> HookOutput {
     // The real hook path bypasses the classification cache because hook
     // invocations are one-shot even when RCH_HOOK_MODE is not set.
     let classify_start = Instant::now();
-    let classification = crate::cache::classify_hook_command(command, classify_command);
+    let classification = crate::cache::classify_hook_command(command, classify_with_user_rules);
     let classification_duration = classify_start.elapsed();
     let classification_duration_us = classification_duration.as_micros() as u64;
 
@@ -1321,17 +1926,31 @@ async fn process_hook(input: HookInput) -> HookOutput {
         classification.kind, classification.confidence
     ));
 
-    // Check confidence threshold
+    // Check confidence threshold. A [compilation.confidence_overrides] entry
+    // replaces the classifier's score for matching commands, so one command
+    // can be admitted (or kept local) without moving the global threshold.
+    let mut confidence = classification.confidence;
+    if let Some((pattern, forced)) = config.compilation.confidence_override(command) {
+        confidence = forced.confidence();
+        debug!(
+            "Confidence override {:?} matched: {:.2} -> {:.2}",
+            pattern, classification.confidence, confidence
+        );
+        reporter.verbose(&format!(
+            "[RCH] confidence override {:?}: {:.2}",
+            pattern, confidence
+        ));
+    }
     let confidence_threshold = if config.general.force_remote {
         reporter.verbose("[RCH] force_remote enabled: bypassing confidence threshold");
         0.0
     } else {
         config.compilation.confidence_threshold
     };
-    if classification.confidence < confidence_threshold {
+    if confidence < confidence_threshold {
         debug!(
             "Confidence {:.2} below threshold {:.2}, allowing local execution",
-            classification.confidence, confidence_threshold
+            confidence, confidence_threshold
         );
         reporter.summary("[RCH] local (confidence below threshold)");
         return HookOutput::allow();
@@ -1373,15 +1992,23 @@ async fn process_hook(input: HookInput) -> HookOutput {
     );
     reporter.verbose("[RCH] delegating to rch exec...");
 
+    // Carry the Claude Code session into `rch exec` when the daemon queue
+    // schedules round-robin across sessions (`selection.fairness.queue`), and
+    // the tool call id so a duplicate hook invocation coalesces onto one build.
+    let session_id = input
+        .session_id
+        .as_deref()
+        .filter(|_| config.selection.fairness.queue == QueueFairness::RoundRobin);
+    let session_env = invocation_env_prefix(session_id, input.tool_use_id.as_deref());
     let modified_command = if let (Some(prefix), Some(extracted)) = (
         &classification.command_prefix,
         &classification.extracted_command,
     ) {
         // Compound command: preserve prefix, wrap only the compilation part
-        format!("{}rch exec -- {}", prefix, extracted)
+        format!("{}{}rch exec -- {}", prefix, session_env, extracted)
     } else {
         // Simple command: wrap the entire command
-        format!("rch exec -- {}", command)
+        format!("{}rch exec -- {}", session_env, command)
     };
 
     HookOutput::allow_with_modified_command(modified_command)
@@ -1443,13 +2070,16 @@ async fn handle_selection_response(
         config.environment.allowlist.clone(),
         forwarded_cargo_target_dir,
         &config.compilation,
+        &config.self_healing,
         toolchain,
         classification_kind,
         reporter,
         &config.general.socket_path,
-        config.output.color_mode,
+        &config.output,
+        &config.telemetry,
         response.build_id,
         &topology_policy,
+        RemoteOutput::Forward,
     )
     .await;
     let remote_elapsed = remote_start.elapsed();
@@ -1465,6 +2095,7 @@ async fn handle_selection_response(
         timing.total = Some(remote_elapsed);
         timing
     });
+    let release_trace = release_decision_trace(DecisionTrace::new(), &result);
     if let Err(e) = release_worker(
         &config.general.socket_path,
         &worker.id,
@@ -1472,17 +2103,32 @@ async fn handle_selection_response(
         response.build_id,
         Some(release_exit_code),
         None,
-        None,
+        result.as_ref().ok().map(|ok| ok.bytes_transferred),
         release_timing.as_ref(),
+        &release_trace,
+        std::env::current_dir().ok().as_deref(),
+        toolchain,
     )
     .await
     {
         warn!("Failed to release worker slots: {}", e);
     }
 
+    if let Ok(result) = &result {
+        notify_slow_build(
+            &config.notifications,
+            project,
+            &worker.id,
+            remote_elapsed,
+            result.exit_code,
+        )
+        .await;
+    }
+
     match result {
         Ok(result) => {
-            if result.exit_code == 0 {
+            let outcome = classify_exit(result.exit_code, &result.stderr);
+            if outcome.is_success() {
                 // Command succeeded remotely - replace with no-op for transparency
                 // The agent already saw output via stderr, artifacts are local
                 // Using allow+modified_command makes this completely transparent to the agent
@@ -1521,7 +2167,7 @@ async fn handle_selection_response(
 
                 // Replace original command with a no-op - agent thinks command ran locally
                 HookOutput::allow_with_modified_command("true")
-            } else if is_toolchain_failure(&result.stderr, result.exit_code) {
+            } else if outcome.should_fall_back_locally() {
                 // Toolchain failure - fall back to local execution
                 warn!(
                     "Remote toolchain failure detected (exit {}), falling back to local",
@@ -1533,58 +2179,53 @@ async fn handle_selection_response(
                 // Command failed remotely - still deny to prevent re-execution
                 // The agent saw the error output via stderr
                 //
-                // Exit code semantics:
+                // Exit code semantics (see `classify_exit`):
                 // - 101: Test failures (cargo test ran but tests failed)
                 // - 1: Build/compilation error
+                // - 120: Killed by rch-wkr for exceeding the external timeout
                 // - 128+N: Process killed by signal N
                 let exit_code = result.exit_code;
 
-                // Check for signal-killed processes (OOM, etc.)
-                if let Some(signal) = is_signal_killed(exit_code) {
-                    warn!(
-                        "Remote command killed by signal {} ({}) on {}, replacing with exit code for transparency",
-                        signal,
-                        signal_name(signal),
-                        worker.id
-                    );
-                    reporter.summary(&format!(
-                        "[RCH] remote {} killed ({})",
-                        worker.id,
-                        signal_name(signal)
-                    ));
-                } else if exit_code == EXIT_TEST_FAILURES {
-                    // Cargo test exit 101: tests ran but some failed
-                    info!(
-                        "Remote tests failed (exit 101) on {}, replacing with exit code for transparency",
-                        worker.id
-                    );
-                    reporter.summary(&format!("[RCH] remote {} tests failed", worker.id));
-                } else if exit_code == EXIT_BUILD_ERROR {
-                    // Build/compilation error
-                    info!(
-                        "Remote build error (exit 1) on {}, replacing with exit code for transparency",
-                        worker.id
-                    );
-                    reporter.summary(&format!("[RCH] remote {} build error", worker.id));
-                } else {
-                    // Other non-zero exit code
-                    info!(
-                        "Remote command failed (exit {}) on {}, replacing with exit code for transparency",
-                        exit_code, worker.id
-                    );
-                    reporter.summary(&format!(
-                        "[RCH] remote {} failed (exit {})",
-                        worker.id, exit_code
-                    ));
+                match outcome {
+                    // Signal-killed processes (OOM, etc.)
+                    CommandOutcome::Signal(signal) => {
+                        warn!(
+                            "Remote command killed by signal {} ({}) on {}, replacing with exit code for transparency",
+                            signal,
+                            signal_name(signal),
+                            worker.id
+                        );
+                    }
+                    CommandOutcome::TimedOut => {
+                        warn!(
+                            "Remote command timed out (exit {}) on {}, replacing with exit code for transparency",
+                            exit_code, worker.id
+                        );
+                    }
+                    CommandOutcome::TestFailures => {
+                        info!(
+                            "Remote tests failed (exit 101) on {}, replacing with exit code for transparency",
+                            worker.id
+                        );
+                    }
+                    CommandOutcome::BuildError => {
+                        info!(
+                            "Remote build error (exit 1) on {}, replacing with exit code for transparency",
+                            worker.id
+                        );
+                    }
+                    _ => {
+                        info!(
+                            "Remote command failed (exit {}) on {}, replacing with exit code for transparency",
+                            exit_code, worker.id
+                        );
+                    }
                 }
+                reporter.summary(&remote_failure_summary(&worker.id, outcome, exit_code));
 
-                // Still record timing for failed builds (useful for predictions)
-                // bd-mnhp: spawn_blocking for file I/O
-                let project_for_timing = project.to_string();
-                let duration = result.duration_ms;
-                tokio::task::spawn_blocking(move || {
-                    record_build_timing(&project_for_timing, classification_kind, duration, true);
-                });
+                // Failed builds are not recorded: a build that stops at the
+                // first error says nothing about how long a full build takes,
+                // and would skew the timing gate toward local.
 
                 // Replace with exit command to preserve the exit code transparently
                 // Agent already saw the error output, now they see the correct exit code
@@ -1640,7 +2281,7 @@ async fn handle_selection_response(
                 "Remote execution pipeline failed: {}, falling back to local",
                 e
             );
-            reporter.summary("[RCH] local (remote pipeline failed)");
+            reporter.summary(&remote_fallback_summary("remote pipeline failed", &e));
             HookOutput::allow()
         }
     }
@@ -1662,7 +2303,15 @@ pub(crate) fn extract_project_name() -> String {
 /// Extract project name from current working directory, honoring the
 /// supplied [`PathTopologyPolicy`].
 pub(crate) fn extract_project_name_with_policy(policy: &PathTopologyPolicy) -> String {
-    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("unknown"));
+    project_name_for_dir(
+        &logical_current_dir().unwrap_or_else(|_| PathBuf::from("unknown")),
+        policy,
+    )
+}
+
+/// Project identity for `cwd`; see [`extract_project_name_with_policy`].
+fn project_name_for_dir(cwd: &Path, policy: &PathTopologyPolicy) -> String {
+    let cwd = cwd.to_path_buf();
     let normalized_cwd = match normalize_project_path_with_policy(&cwd, policy) {
         Ok(normalized) => {
             for decision in normalized.decision_trace() {
@@ -1700,6 +2349,29 @@ pub(crate) fn extract_project_name_with_policy(policy: &PathTopologyPolicy) -> S
     format!("{}-{}", name, short_hash)
 }
 
+/// `RCH_SESSION_ID=<id> RCH_TOOL_USE_ID=<id> ` assignments prepended to the
+/// delegated `rch exec` command; each is omitted when the hook input carries
+/// no usable value.
+///
+/// Only `[A-Za-z0-9._-]` survive so the values never need shell quoting.
+fn invocation_env_prefix(session_id: Option<&str>, tool_use_id: Option<&str>) -> String {
+    [
+        ("RCH_SESSION_ID", session_id),
+        ("RCH_TOOL_USE_ID", tool_use_id),
+    ]
+    .into_iter()
+    .filter_map(|(name, value)| {
+        let sanitized: String = value
+            .unwrap_or_default()
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+            .take(64)
+            .collect();
+        (!sanitized.is_empty()).then(|| format!("{}={} ", name, sanitized))
+    })
+    .collect()
+}
+
 fn command_priority_from_env(reporter: &HookReporter) -> CommandPriority {
     let Ok(raw) = std::env::var("RCH_PRIORITY") else {
         return CommandPriority::Normal;
@@ -1727,6 +2399,9 @@ fn selected_worker_to_config(worker: &SelectedWorker) -> WorkerConfig {
         total_slots: worker.slots_available,
         priority: 100,
         tags: vec![],
+        max_concurrent_builds: None,
+        nice_level: worker.nice_level,
+        ionice_class: worker.ionice_class,
     }
 }
 
@@ -1734,6 +2409,9 @@ fn selected_worker_to_config(worker: &SelectedWorker) -> WorkerConfig {
 struct DependencyRuntimePlan {
     sync_roots: Vec<PathBuf>,
     fail_open_decision: Option<DependencyRuntimeFailOpenDecision>,
+    /// Vendored registry outside the project to sync as an extra root, or
+    /// why the one the build needs cannot be synced.
+    vendor_root: Result<Option<PathBuf>, String>,
 }
 
 #[derive(Debug, Clone)]
@@ -1859,6 +2537,7 @@ fn command_uses_cargo_dependency_graph(kind: Option<CompilationKind>) -> bool {
                 | CompilationKind::CargoTest
                 | CompilationKind::CargoNextest
                 | CompilationKind::CargoBench
+                | CompilationKind::CargoRun
         )
     )
 }
@@ -1882,9 +2561,11 @@ fn build_dependency_runtime_plan(
         return DependencyRuntimePlan {
             sync_roots: vec![normalized_project_root.to_path_buf()],
             fail_open_decision: None,
+            vendor_root: Ok(None),
         };
     }
 
+    let vendor_root = vendor_sync_root(normalized_project_root, reporter, topology_policy);
     let plan = build_dependency_closure_plan_with_policy(normalized_project_root, topology_policy);
     if !plan.is_ready() {
         if let Some(reason) = &plan.fail_open_reason {
@@ -1907,6 +2588,7 @@ fn build_dependency_runtime_plan(
         return DependencyRuntimePlan {
             sync_roots: vec![normalized_project_root.to_path_buf()],
             fail_open_decision: Some(decision),
+            vendor_root,
         };
     }
 
@@ -1936,6 +2618,7 @@ fn build_dependency_runtime_plan(
     DependencyRuntimePlan {
         sync_roots: ordered,
         fail_open_decision: None,
+        vendor_root,
     }
 }
 
@@ -1963,7 +2646,10 @@ pub(crate) fn required_runtime_for_kind(kind: Option<CompilationKind>) -> Requir
             | CompilationKind::CargoDoc
             | CompilationKind::CargoNextest
             | CompilationKind::CargoBench
-            | CompilationKind::Rustc => RequiredRuntime::Rust,
+            | CompilationKind::CargoRun
+            | CompilationKind::Rustc
+            | CompilationKind::TrunkBuild
+            | CompilationKind::WasmPackBuild => RequiredRuntime::Rust,
 
             CompilationKind::BunTest | CompilationKind::BunTypecheck => RequiredRuntime::Bun,
 
@@ -6,7 +6,11 @@
 //!
 //! - [`get_artifact_patterns`] maps a [`CompilationKind`] to the rsync
 //!   include-pattern list for the default project-root sync-back (full `target/`
-//!   outputs for builds, a narrow allowlist for test/diagnostic kinds).
+//!   outputs for builds, a narrow allowlist for test/diagnostic kinds), plus
+//!   [`CARGO_TIMINGS_ARTIFACT_PATTERN`] when the command passes `--timings` so
+//!   cargo's HTML/JSON timing report survives a remote build. For
+//!   `cargo nextest` with an explicit profile, the nextest store pattern is
+//!   narrowed to that profile's directory (where its JUnit XML lands).
 //! - [`get_custom_target_artifact_patterns`] is the variant used when the build
 //!   wrote into a custom `CARGO_TARGET_DIR` (the sync root IS the remote target
 //!   dir): it rebases the same output globs onto the target-dir root and prefixes
@@ -27,13 +31,51 @@
 
 use super::*;
 
-/// Get artifact patterns based on compilation kind.
+/// Where `cargo <cmd> --timings` writes its report, relative to the project root.
+const CARGO_TIMINGS_ARTIFACT_PATTERN: &str = "target/cargo-timings/**";
+
+/// The nextest store (per-profile JUnit XML and run output), relative to the
+/// project root.
+const NEXTEST_STORE_ARTIFACT_PATTERN: &str = "target/nextest/**";
+
+/// Get artifact patterns based on compilation kind and command flags.
 ///
 /// Test and diagnostic commands use minimal patterns since their output is
 /// streamed and the full target/ directory is not needed. This significantly
 /// reduces artifact transfer time for commands that do not produce runnable
-/// build artifacts.
-pub(super) fn get_artifact_patterns(kind: Option<CompilationKind>) -> Vec<String> {
+/// build artifacts. A `--timings` report is retrieved for any kind, since
+/// `cargo check --timings` writes one just like `cargo build --timings`.
+pub(super) fn get_artifact_patterns(kind: Option<CompilationKind>, command: &str) -> Vec<String> {
+    let mut patterns = kind_artifact_patterns(kind);
+    if has_timings_flag(command) {
+        patterns.push(CARGO_TIMINGS_ARTIFACT_PATTERN.to_string());
+    }
+    narrow_to_nextest_profile(&mut patterns, kind, command, NEXTEST_STORE_ARTIFACT_PATTERN);
+    patterns
+}
+
+/// Replace the whole-store nextest pattern with the selected profile's
+/// directory (`target/nextest/<profile>/**`, where `junit.path` is resolved).
+/// Without an explicit profile the whole store is kept.
+fn narrow_to_nextest_profile(
+    patterns: &mut [String],
+    kind: Option<CompilationKind>,
+    command: &str,
+    store_pattern: &str,
+) {
+    if kind != Some(CompilationKind::CargoNextest) {
+        return;
+    }
+    let Some(profile) = nextest_profile(command) else {
+        return;
+    };
+    let store_dir = store_pattern.trim_end_matches("**");
+    for pattern in patterns.iter_mut().filter(|p| *p == store_pattern) {
+        *pattern = format!("{store_dir}{profile}/**");
+    }
+}
+
+fn kind_artifact_patterns(kind: Option<CompilationKind>) -> Vec<String> {
     match kind {
         Some(CompilationKind::BunTest) | Some(CompilationKind::BunTypecheck) => {
             default_bun_artifact_patterns()
@@ -46,6 +88,7 @@ pub(super) fn get_artifact_patterns(kind: Option<CompilationKind>) -> Vec<String
         | Some(CompilationKind::CargoClippy) => default_rust_test_artifact_patterns(),
         Some(CompilationKind::Rustc)
         | Some(CompilationKind::CargoBuild)
+        | Some(CompilationKind::CargoRun)
         | Some(CompilationKind::CargoDoc) => default_rust_artifact_patterns(),
         Some(CompilationKind::Gcc)
         | Some(CompilationKind::Gpp)
@@ -55,6 +98,8 @@ pub(super) fn get_artifact_patterns(kind: Option<CompilationKind>) -> Vec<String
         | Some(CompilationKind::CmakeBuild)
         | Some(CompilationKind::Ninja)
         | Some(CompilationKind::Meson) => default_c_cpp_artifact_patterns(),
+        Some(CompilationKind::TrunkBuild) => default_trunk_artifact_patterns(),
+        Some(CompilationKind::WasmPackBuild) => default_wasm_pack_artifact_patterns(),
         _ => default_rust_artifact_patterns(),
     }
 }
@@ -81,22 +126,46 @@ const CARGO_TARGET_CACHE_EXCLUDES: &[&str] = &[
     "- *.d",
 ];
 
-pub(super) fn get_custom_target_artifact_patterns(kind: Option<CompilationKind>) -> Vec<String> {
+pub(super) fn get_custom_target_artifact_patterns(
+    kind: Option<CompilationKind>,
+    command: &str,
+) -> Vec<String> {
+    let mut patterns = custom_target_kind_artifact_patterns(kind);
+    if has_timings_flag(command) {
+        patterns.push(target_relative(CARGO_TIMINGS_ARTIFACT_PATTERN));
+    }
+    narrow_to_nextest_profile(
+        &mut patterns,
+        kind,
+        command,
+        &target_relative(NEXTEST_STORE_ARTIFACT_PATTERN),
+    );
+    patterns
+}
+
+/// Rebase a `target/`-rooted pattern onto the target-dir root.
+fn target_relative(pattern: &str) -> String {
+    pattern
+        .strip_prefix("target/")
+        .unwrap_or(pattern)
+        .to_string()
+}
+
+fn custom_target_kind_artifact_patterns(kind: Option<CompilationKind>) -> Vec<String> {
     match kind {
+        // Trunk and wasm-pack outputs land in dist/ and pkg/ under the project
+        // root, never in the target dir.
         Some(CompilationKind::CargoTest)
         | Some(CompilationKind::CargoCheck)
-        | Some(CompilationKind::CargoClippy) => Vec::new(),
+        | Some(CompilationKind::CargoClippy)
+        | Some(CompilationKind::TrunkBuild)
+        | Some(CompilationKind::WasmPackBuild) => Vec::new(),
         Some(CompilationKind::CargoNextest) | Some(CompilationKind::CargoBench) => {
             // Test/bench artifacts are already a narrow allowlist; just rebase them
             // onto the target-dir root (the sync root IS the remote target dir).
-            get_artifact_patterns(kind)
-                .into_iter()
-                .map(|pattern| {
-                    pattern
-                        .strip_prefix("target/")
-                        .unwrap_or(pattern.as_str())
-                        .to_string()
-                })
+            kind_artifact_patterns(kind)
+                .iter()
+                .map(|pattern| target_relative(pattern))
                 .collect()
         }
         // CargoBuild / CargoDoc / Rustc (the `_` arm) previously synced the WHOLE
@@ -113,12 +182,11 @@ pub(super) fn get_custom_target_artifact_patterns(kind: Option<CompilationKind>)
                 .iter()
                 .map(|s| (*s).to_string())
                 .collect();
-            patterns.extend(get_artifact_patterns(kind).into_iter().map(|pattern| {
-                pattern
-                    .strip_prefix("target/")
-                    .unwrap_or(pattern.as_str())
-                    .to_string()
-            }));
+            patterns.extend(
+                kind_artifact_patterns(kind)
+                    .iter()
+                    .map(|pattern| target_relative(pattern)),
+            );
             patterns
         }
     }
@@ -136,6 +204,7 @@ pub(super) fn get_custom_target_artifact_patterns(kind: Option<CompilationKind>)
 pub(super) fn kind_produces_transferable_artifacts(kind: Option<CompilationKind>) -> bool {
     match kind {
         Some(CompilationKind::CargoBuild)
+        | Some(CompilationKind::CargoRun)
         | Some(CompilationKind::CargoDoc)
         | Some(CompilationKind::Rustc)
         | Some(CompilationKind::Gcc)
@@ -145,7 +214,9 @@ pub(super) fn kind_produces_transferable_artifacts(kind: Option<CompilationKind>
         | Some(CompilationKind::Make)
         | Some(CompilationKind::CmakeBuild)
         | Some(CompilationKind::Ninja)
-        | Some(CompilationKind::Meson) => true,
+        | Some(CompilationKind::Meson)
+        | Some(CompilationKind::TrunkBuild)
+        | Some(CompilationKind::WasmPackBuild) => true,
         // Test/diagnostic kinds stream results; no required local artifact.
         Some(CompilationKind::CargoTest)
         | Some(CompilationKind::CargoNextest)
@@ -54,7 +54,7 @@ struct HealthResponse {
     status: String,
 }
 
-fn autostart_state_dir() -> PathBuf {
+fn default_autostart_state_dir() -> PathBuf {
     if let Ok(runtime_dir) = std::env::var("XDG_RUNTIME_DIR")
         && !runtime_dir.trim().is_empty()
     {
@@ -63,12 +63,98 @@ fn autostart_state_dir() -> PathBuf {
     PathBuf::from("/tmp").join("rch")
 }
 
-fn autostart_lock_path() -> PathBuf {
-    autostart_state_dir().join("hook_autostart.lock")
+/// Directory the hook would use for auto-start state, without creating or
+/// probing it. `rch doctor` checks (and with `--fix` creates) this path.
+pub(crate) fn configured_autostart_state_dir(config: &SelfHealingConfig) -> PathBuf {
+    config
+        .state_dir
+        .as_deref()
+        .map(|dir| PathBuf::from(shellexpand::tilde(dir).as_ref()))
+        .unwrap_or_else(default_autostart_state_dir)
 }
 
-fn autostart_cooldown_path() -> PathBuf {
-    autostart_state_dir().join("hook_autostart.cooldown")
+/// Directory holding the auto-start lock and cooldown files.
+///
+/// `self_healing.state_dir` (or `RCH_STATE_DIR`) takes precedence so that
+/// containers sharing a host `/tmp` do not contend on one lock. An override
+/// that cannot be created or written is logged and the default is used.
+pub(super) fn autostart_state_dir(config: &SelfHealingConfig) -> PathBuf {
+    if let Some(dir) = config.state_dir.as_deref() {
+        let dir = PathBuf::from(shellexpand::tilde(dir).as_ref());
+        match std::fs::create_dir_all(&dir).and_then(|()| crate::config::probe_dir_writable(&dir)) {
+            Ok(()) => return dir,
+            Err(err) => warn!(
+                target: "rch::hook::auto_start",
+                "self_healing.state_dir {} is not writable ({}); using the default state directory",
+                dir.display(),
+                err
+            ),
+        }
+    }
+    default_autostart_state_dir()
+}
+
+pub(crate) fn autostart_lock_path(state_dir: &Path) -> PathBuf {
+    state_dir.join("hook_autostart.lock")
+}
+
+pub(crate) fn autostart_cooldown_path(state_dir: &Path) -> PathBuf {
+    state_dir.join("hook_autostart.cooldown")
+}
+
+/// Log file receiving stdout/stderr of an `rchd` spawned outside systemd.
+pub(crate) fn daemon_log_path(state_dir: &Path) -> PathBuf {
+    state_dir.join("rchd.log")
+}
+
+/// [`daemon_log_path`] under the state directory `self_healing` configures.
+pub(crate) fn configured_daemon_log_path(self_healing: &SelfHealingConfig) -> PathBuf {
+    daemon_log_path(&configured_autostart_state_dir(self_healing))
+}
+
+/// Previous generation kept by daemon log rotation.
+pub(crate) fn rotated_daemon_log_path(log_path: &Path) -> PathBuf {
+    rch_common::log_retention::rotated_stdio_log_path(log_path)
+}
+
+/// Open the daemon log for appending, rotating it to `<log>.1` first once it
+/// has reached [`DAEMON_STDIO_LOG_MAX_BYTES`]. Only one old generation is
+/// kept, so the log directory holds at most two files. The spawned daemon
+/// keeps the log bounded from then on: spawners pass the path in
+/// [`DAEMON_STDIO_LOG_ENV`] and `rchd` applies the same check on a timer.
+///
+/// [`DAEMON_STDIO_LOG_MAX_BYTES`]: rch_common::log_retention::DAEMON_STDIO_LOG_MAX_BYTES
+/// [`DAEMON_STDIO_LOG_ENV`]: rch_common::log_retention::DAEMON_STDIO_LOG_ENV
+fn open_daemon_log(log_path: &Path) -> std::io::Result<std::fs::File> {
+    if let Some(parent) = log_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    rch_common::log_retention::rotate_stdio_log_if_oversized(
+        log_path,
+        rch_common::log_retention::DAEMON_STDIO_LOG_MAX_BYTES,
+    )?;
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_path)
+}
+
+/// Stdout/stderr handles for a detached `rchd`, both appending to
+/// `log_path`. Falls back to `/dev/null` when the log cannot be opened:
+/// losing logs must never block a daemon start.
+pub(crate) fn daemon_log_stdio(log_path: &Path) -> (Stdio, Stdio) {
+    match open_daemon_log(log_path).and_then(|file| Ok((file.try_clone()?, file))) {
+        Ok((stdout, stderr)) => (Stdio::from(stdout), Stdio::from(stderr)),
+        Err(err) => {
+            warn!(
+                target: "rch::hook::auto_start",
+                "cannot open daemon log {} ({}); discarding daemon output",
+                log_path.display(),
+                err
+            );
+            (Stdio::null(), Stdio::null())
+        }
+    }
 }
 
 fn read_cooldown_timestamp(path: &Path) -> Option<SystemTime> {
@@ -324,11 +410,13 @@ fn which_rchd_path() -> Option<PathBuf> {
     which("rchd").ok()
 }
 
-fn spawn_rchd(path: &Path) -> Result<(), AutoStartError> {
+fn spawn_rchd(path: &Path, log_path: &Path) -> Result<(), AutoStartError> {
+    let (stdout, stderr) = daemon_log_stdio(log_path);
     let mut cmd = std::process::Command::new("nohup");
     cmd.arg(path)
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
+        .env(rch_common::log_retention::DAEMON_STDIO_LOG_ENV, log_path)
+        .stdout(stdout)
+        .stderr(stderr)
         .stdin(Stdio::null());
 
     let mut child = cmd.spawn().map_err(AutoStartError::SpawnFailed)?;
@@ -440,7 +528,8 @@ pub(super) async fn try_auto_start_daemon(
     //   2. Re-probe socket: while waiting for the lock, the prior
     //      lock-holder may have already started the daemon.
     //   3. Only delete the socket if it's confirmed stale UNDER the lock.
-    let _lock = acquire_autostart_lock(&autostart_lock_path())?;
+    let state_dir = autostart_state_dir(config);
+    let _lock = acquire_autostart_lock(&autostart_lock_path(&state_dir))?;
 
     // Re-probe under the lock — another hook may have spawned rchd
     // while we were waiting.
@@ -474,7 +563,7 @@ pub(super) async fn try_auto_start_daemon(
         }
     }
 
-    let cooldown_path = autostart_cooldown_path();
+    let cooldown_path = autostart_cooldown_path(&state_dir);
     if let Some(last_attempt) = read_cooldown_timestamp(&cooldown_path) {
         let elapsed = last_attempt
             .elapsed()
@@ -498,7 +587,7 @@ pub(super) async fn try_auto_start_daemon(
         "Spawning rchd at {}",
         rchd_path.display()
     );
-    spawn_rchd(&rchd_path)?;
+    spawn_rchd(&rchd_path, &daemon_log_path(&state_dir))?;
 
     let timeout_secs = config.auto_start_timeout_secs;
     if !wait_for_socket(socket_path, timeout_secs).await {
@@ -901,7 +990,7 @@ mod tests {
         std::fs::set_permissions(&fake_rchd, perms).expect("chmod fake rchd");
 
         let started = std::time::Instant::now();
-        super::spawn_rchd(&fake_rchd).expect("spawn fake rchd");
+        super::spawn_rchd(&fake_rchd, &temp_dir.path().join("rchd.log")).expect("spawn fake rchd");
         let elapsed = started.elapsed();
         assert!(
             elapsed < std::time::Duration::from_millis(250),
@@ -924,13 +1013,65 @@ mod tests {
         perms.set_mode(0o755);
         std::fs::set_permissions(&fake_rchd, perms).expect("chmod fake rchd");
 
-        let err = super::spawn_rchd(&fake_rchd).expect_err("child failure should surface");
+        let err = super::spawn_rchd(&fake_rchd, &temp_dir.path().join("rchd.log"))
+            .expect_err("child failure should surface");
         assert!(
             matches!(err, super::AutoStartError::WrapperFailed(status) if status.code() == Some(42)),
             "unexpected error: {err:?}"
         );
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_spawn_rchd_writes_daemon_output_to_log() {
+        let _guard = test_guard!();
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = create_test_state_dir();
+        let fake_rchd = temp_dir.path().join("rchd");
+        std::fs::write(
+            &fake_rchd,
+            "#!/usr/bin/env sh\necho daemon-stdout\necho daemon-stderr >&2\nexit 3\n",
+        )
+        .expect("write fake rchd");
+        let mut perms = std::fs::metadata(&fake_rchd)
+            .expect("fake rchd metadata")
+            .permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&fake_rchd, perms).expect("chmod fake rchd");
+
+        let log_path = daemon_log_path(&temp_dir.path().join("state"));
+        let _ = super::spawn_rchd(&fake_rchd, &log_path);
+        let log = std::fs::read_to_string(&log_path).expect("read daemon log");
+        assert!(log.contains("daemon-stdout"), "log: {log}");
+        assert!(log.contains("daemon-stderr"), "log: {log}");
+    }
+
+    #[test]
+    fn test_open_daemon_log_rotates_oversized_log() {
+        let _guard = test_guard!();
+        let temp_dir = create_test_state_dir();
+        let log_path = daemon_log_path(temp_dir.path());
+        let file = std::fs::File::create(&log_path).expect("create log");
+        file.set_len(rch_common::log_retention::DAEMON_STDIO_LOG_MAX_BYTES)
+            .expect("grow log");
+        drop(file);
+
+        let file = open_daemon_log(&log_path).expect("open log");
+        assert_eq!(file.metadata().expect("log metadata").len(), 0);
+        assert_eq!(
+            std::fs::metadata(rotated_daemon_log_path(&log_path))
+                .expect("rotated log")
+                .len(),
+            rch_common::log_retention::DAEMON_STDIO_LOG_MAX_BYTES
+        );
+
+        // Below the cap the log is appended to, not rotated again.
+        std::fs::write(&log_path, "kept\n").expect("write log");
+        drop(open_daemon_log(&log_path).expect("reopen log"));
+        assert_eq!(std::fs::read_to_string(&log_path).expect("read"), "kept\n");
+    }
+
     #[tokio::test]
     async fn test_auto_start_config_disabled() {
         let temp_dir = create_test_state_dir();
@@ -993,7 +1134,7 @@ mod tests {
         let _guard = test_guard!();
         // Basic test that autostart_state_dir returns a valid path
         // (without manipulating env vars which is unsafe)
-        let dir = super::autostart_state_dir();
+        let dir = super::autostart_state_dir(&rch_common::SelfHealingConfig::default());
         assert!(!dir.as_os_str().is_empty(), "Path should not be empty");
         assert!(
             dir.to_string_lossy().contains("rch"),
@@ -1001,10 +1142,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_autostart_state_dir_uses_writable_override() {
+        let _guard = test_guard!();
+        let temp_dir = create_test_state_dir();
+        let state_dir = temp_dir.path().join("nested").join("state");
+        let config = rch_common::SelfHealingConfig {
+            state_dir: Some(state_dir.to_string_lossy().into_owned()),
+            ..Default::default()
+        };
+
+        assert_eq!(super::autostart_state_dir(&config), state_dir);
+        assert!(state_dir.is_dir(), "override should be created on demand");
+    }
+
+    #[test]
+    fn test_autostart_state_dir_falls_back_when_override_unusable() {
+        let _guard = test_guard!();
+        let temp_dir = create_test_state_dir();
+        let file = temp_dir.path().join("not-a-dir");
+        std::fs::write(&file, "").unwrap();
+        let config = rch_common::SelfHealingConfig {
+            state_dir: Some(file.to_string_lossy().into_owned()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            super::autostart_state_dir(&config),
+            super::default_autostart_state_dir()
+        );
+    }
+
     #[test]
     fn test_autostart_lock_path_ends_with_expected_name() {
         let _guard = test_guard!();
-        let path = super::autostart_lock_path();
+        let path = super::autostart_lock_path(&super::default_autostart_state_dir());
         assert!(
             path.file_name()
                 .map(|n| n == "hook_autostart.lock")
@@ -1016,7 +1188,7 @@ mod tests {
     #[test]
     fn test_autostart_cooldown_path_ends_with_expected_name() {
         let _guard = test_guard!();
-        let path = super::autostart_cooldown_path();
+        let path = super::autostart_cooldown_path(&super::default_autostart_state_dir());
         assert!(
             path.file_name()
                 .map(|n| n == "hook_autostart.cooldown")
@@ -1039,7 +1211,7 @@ mod tests {
     // 4. test_auto_start_config_disabled - validates early exit when disabled
     //
     // The integration flow in try_auto_start_daemon is:
-    //   cooldown_path = autostart_cooldown_path()
+    //   cooldown_path = autostart_cooldown_path(&autostart_state_dir(config))
     //   if read_cooldown_timestamp(path).elapsed() < config.cooldown_secs:
     //       return Err(CooldownActive)
     //   ... proceed with daemon start ...
@@ -0,0 +1,360 @@
+//! `cargo run` offload: build on the worker, run the binary locally.
+//!
+//! The program usually needs this machine (local files, ports, a terminal), so
+//! [`plan_local_run`] rewrites `cargo run` into the matching `cargo build`,
+//! remembering which binary cargo would have run and the arguments after
+//! `--`. Once the remote build succeeds and its artifacts are retrieved,
+//! [`exit_with_local_run`] executes that binary here. Anything the plan cannot
+//! pin down (several binaries, a manifest elsewhere, artifacts that are not
+//! retrieved, a binary this machine's loader cannot start) keeps the whole
+//! command local instead of guessing.
+
+use super::*;
+use object::elf::{FileHeader32, FileHeader64};
+use object::read::elf::{FileHeader, ProgramHeader};
+use object::{Endianness, FileKind};
+use std::ffi::OsStr;
+use std::io::Read;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::process::ExitStatusExt;
+
+/// Bytes read from the start of a binary to find its ELF program headers and
+/// `PT_INTERP` path, which linkers place right after the file header.
+const ELF_HEAD_BYTES: u64 = 64 * 1024;
+
+/// Cargo options that take a value as the next token.
+const VALUE_FLAGS: &[&str] = &[
+    "--bin",
+    "--example",
+    "-p",
+    "--package",
+    "-F",
+    "--features",
+    "-j",
+    "--jobs",
+    "--profile",
+    "--target",
+    "--target-dir",
+    "--manifest-path",
+    "--message-format",
+    "--color",
+    "--config",
+    "-Z",
+    "-C",
+    "--lockfile-path",
+];
+
+/// A `cargo run` split into its remote build and its local execution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct LocalRunPlan {
+    /// `cargo build ...` tokens sent to the worker.
+    pub build_tokens: Vec<String>,
+    /// The binary, relative to the cargo target dir.
+    pub binary: PathBuf,
+    /// Arguments after `--`, passed to the binary.
+    pub run_args: Vec<String>,
+    /// Leading `NAME=value` assignments, applied to the binary as well.
+    pub env: Vec<(String, String)>,
+}
+
+/// Plan a `cargo run` for remote build and local execution.
+///
+/// `manifest_dir` is where cargo would look for `Cargo.toml`. Returns the
+/// reason when the command should stay local.
+pub(super) fn plan_local_run(
+    command_tokens: &[String],
+    manifest_dir: &Path,
+) -> Result<LocalRunPlan, String> {
+    let mut env = Vec::new();
+    let mut idx = 0;
+    while let Some((name, value)) = command_tokens.get(idx).and_then(|t| env_assignment(t)) {
+        env.push((name.to_string(), value.to_string()));
+        idx += 1;
+    }
+    if command_tokens.get(idx).map(String::as_str) != Some("cargo") {
+        return Err("not a plain cargo invocation".to_string());
+    }
+    idx += 1;
+
+    // Global flags and +toolchain may precede the subcommand.
+    let run_idx = loop {
+        let Some(token) = command_tokens.get(idx) else {
+            return Err("no cargo subcommand".to_string());
+        };
+        if token.starts_with('+') {
+            idx += 1;
+        } else if token.starts_with('-') {
+            idx += if VALUE_FLAGS.contains(&token.as_str()) {
+                2
+            } else {
+                1
+            };
+        } else if token == "run" || token == "r" {
+            break idx;
+        } else {
+            return Err(format!("unexpected subcommand {token}"));
+        }
+    };
+
+    let end = command_tokens[run_idx..]
+        .iter()
+        .position(|token| token == "--")
+        .map_or(command_tokens.len(), |offset| run_idx + offset);
+    let mut bin = None;
+    let mut example = None;
+    let mut package = false;
+    let mut profile = "dev".to_string();
+    let mut flags = command_tokens[run_idx + 1..end].iter();
+    while let Some(token) = flags.next() {
+        let (flag, inline) = match token.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (token.as_str(), None),
+        };
+        let takes_value = VALUE_FLAGS.contains(&flag);
+        let value = match (takes_value, inline) {
+            (true, Some(value)) => Some(value),
+            (true, None) => Some(
+                flags
+                    .next()
+                    .cloned()
+                    .ok_or_else(|| format!("{flag} is missing its value"))?,
+            ),
+            (false, _) => None,
+        };
+        match flag {
+            "--bin" => bin = value,
+            "--example" => example = value,
+            "-p" | "--package" => package = true,
+            short if short.starts_with("-p") && !short.starts_with("--") => package = true,
+            "--profile" => profile = value.unwrap_or_default(),
+            "-r" | "--release" => profile = "release".to_string(),
+            "--manifest-path" => return Err("--manifest-path is not supported".to_string()),
+            "--target" => return Err("--target artifacts are not retrieved".to_string()),
+            _ if !takes_value && !flag.starts_with('-') => {
+                return Err(format!("program argument {flag} before --"));
+            }
+            _ => {}
+        }
+    }
+
+    let profile_dir = match profile.as_str() {
+        "dev" | "test" => "debug",
+        "release" | "bench" => "release",
+        other => {
+            return Err(format!(
+                "custom profile {other} artifacts are not retrieved"
+            ));
+        }
+    };
+
+    let mut build_tokens = command_tokens[..end].to_vec();
+    build_tokens[run_idx] = "build".to_string();
+    let binary = match (bin, example) {
+        (Some(_), Some(_)) => return Err("both --bin and --example given".to_string()),
+        (Some(name), None) => PathBuf::from(profile_dir).join(name),
+        (None, Some(name)) => PathBuf::from(profile_dir).join("examples").join(name),
+        (None, None) if package => {
+            return Err("--package without --bin or --example".to_string());
+        }
+        (None, None) => {
+            let name = default_run_target(manifest_dir)?;
+            build_tokens.push("--bin".to_string());
+            build_tokens.push(name.clone());
+            PathBuf::from(profile_dir).join(name)
+        }
+    };
+
+    Ok(LocalRunPlan {
+        build_tokens,
+        binary,
+        run_args: command_tokens.get(end + 1..).unwrap_or_default().to_vec(),
+        env,
+    })
+}
+
+fn env_assignment(token: &str) -> Option<(&str, &str)> {
+    let (name, value) = token.split_once('=')?;
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    valid.then_some((name, value))
+}
+
+/// The binary plain `cargo run` would pick for the package in `manifest_dir`:
+/// `default-run`, else the only binary target. Several candidates are an error
+/// here just as they are for cargo.
+fn default_run_target(manifest_dir: &Path) -> Result<String, String> {
+    let manifest = std::fs::read_to_string(manifest_dir.join("Cargo.toml"))
+        .ok()
+        .and_then(|contents| contents.parse::<toml::Table>().ok())
+        .ok_or_else(|| "no readable Cargo.toml".to_string())?;
+    let package = manifest
+        .get("package")
+        .and_then(toml::Value::as_table)
+        .ok_or_else(|| "workspace root without --bin".to_string())?;
+    if let Some(default_run) = package.get("default-run").and_then(toml::Value::as_str) {
+        return Ok(default_run.to_string());
+    }
+
+    let explicit = manifest
+        .get("bin")
+        .and_then(toml::Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let mut candidates: Vec<String> = explicit
+        .iter()
+        .filter_map(|bin| bin.get("name")?.as_str().map(str::to_string))
+        .collect();
+    let autobins = package
+        .get("autobins")
+        .and_then(toml::Value::as_bool)
+        .unwrap_or(true);
+    let main_is_explicit = explicit
+        .iter()
+        .any(|bin| bin.get("path").and_then(toml::Value::as_str) == Some("src/main.rs"));
+    if autobins {
+        if !main_is_explicit
+            && manifest_dir.join("src/main.rs").is_file()
+            && let Some(name) = package.get("name").and_then(toml::Value::as_str)
+            && !candidates.iter().any(|candidate| candidate == name)
+        {
+            candidates.push(name.to_string());
+        }
+        if std::fs::read_dir(manifest_dir.join("src/bin"))
+            .map(|mut entries| entries.next().is_some())
+            .unwrap_or(false)
+        {
+            return Err("src/bin targets need --bin".to_string());
+        }
+    }
+    match candidates.as_slice() {
+        [name] => Ok(name.clone()),
+        [] => Err("no binary target".to_string()),
+        _ => Err("several binaries; pass --bin".to_string()),
+    }
+}
+
+/// Run the retrieved binary here and exit with its status.
+///
+/// Like cargo, the binary runs in the current directory with
+/// `CARGO_MANIFEST_DIR` set. A missing binary (nothing retrieved), one the
+/// dynamic loader rejects (see [`loader_failure`]), or one that cannot be
+/// started here (e.g. built for another architecture) falls back to running
+/// the original command locally.
+pub(super) fn exit_with_local_run(
+    plan: &LocalRunPlan,
+    target_dir: &Path,
+    manifest_dir: &Path,
+    command: &str,
+    reporter: &HookReporter,
+) -> ! {
+    let binary = target_dir.join(&plan.binary);
+    if !binary.is_file() {
+        warn!("cargo run: built binary not found at {}", binary.display());
+        reporter.summary(&format!(
+            "[RCH] local (built binary missing at {})",
+            binary.display()
+        ));
+        exit_with_local_command(command, reporter);
+    }
+    if let Some(reason) = loader_failure(&binary, &plan.env) {
+        warn!(
+            "cargo run: {} does not load here: {}",
+            binary.display(),
+            reason
+        );
+        reporter.summary(&format!(
+            "[RCH] local (built binary does not load here: {reason})"
+        ));
+        exit_with_local_command(command, reporter);
+    }
+
+    reporter.verbose(&format!("[RCH] running {} locally", binary.display()));
+    let status = std::process::Command::new(&binary)
+        .args(&plan.run_args)
+        .envs(plan.env.iter().map(|(k, v)| (k, v)))
+        .env("CARGO_MANIFEST_DIR", manifest_dir)
+        .status();
+    match status {
+        Ok(status) => {
+            let code = status
+                .code()
+                .or_else(|| status.signal().map(|signal| 128 + signal))
+                .unwrap_or(1);
+            std::process::exit(code);
+        }
+        Err(error) => {
+            warn!("cargo run: failed to start {}: {}", binary.display(), error);
+            reporter.summary(&format!(
+                "[RCH] local (failed to run {}: {error})",
+                binary.display()
+            ));
+            exit_with_local_command(command, reporter);
+        }
+    }
+}
+
+/// Why the dynamic loader cannot start `binary` here, if it cannot.
+///
+/// A binary built on the worker may need a newer glibc or a shared library
+/// this machine lacks; the loader then fails before `main` with an exit status
+/// that looks like the program's own. For glibc-linked ELF binaries the loader
+/// resolves every dependency under `LD_TRACE_LOADED_OBJECTS`, which lists them
+/// without running the program. Static, musl, and non-ELF binaries are not
+/// probed, since their loaders would run the program instead.
+pub(super) fn loader_failure(binary: &Path, env: &[(String, String)]) -> Option<String> {
+    let mut head = Vec::new();
+    std::fs::File::open(binary)
+        .ok()?
+        .take(ELF_HEAD_BYTES)
+        .read_to_end(&mut head)
+        .ok()?;
+    let interpreter = elf_interpreter(&head)?;
+    if !interpreter.exists() {
+        return Some(format!("loader {} is missing", interpreter.display()));
+    }
+    let loader = interpreter.file_name()?.to_string_lossy();
+    if !(loader.starts_with("ld-linux") || loader.starts_with("ld64.so")) {
+        return None;
+    }
+
+    let output = std::process::Command::new(binary)
+        .envs(env.iter().map(|(k, v)| (k, v)))
+        .env("LD_TRACE_LOADED_OBJECTS", "1")
+        .stdin(std::process::Stdio::null())
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    stdout
+        .lines()
+        .chain(stderr.lines())
+        .find(|line| line.contains("not found"))
+        .map(|line| line.trim().to_string())
+        .or_else(|| {
+            (!output.status.success()).then(|| format!("loader exited with {}", output.status))
+        })
+}
+
+/// The `PT_INTERP` path of an ELF image, from its first bytes. `None` for
+/// statically linked and non-ELF binaries.
+pub(super) fn elf_interpreter(head: &[u8]) -> Option<PathBuf> {
+    fn interpreter<Elf: FileHeader<Endian = Endianness>>(head: &[u8]) -> Option<PathBuf> {
+        let header = Elf::parse(head).ok()?;
+        let endian = header.endian().ok()?;
+        header
+            .program_headers(endian, head)
+            .ok()?
+            .iter()
+            .find_map(|segment| segment.interpreter(endian, head).ok().flatten())
+            .map(|path| PathBuf::from(OsStr::from_bytes(path)))
+    }
+
+    match FileKind::parse(head).ok()? {
+        FileKind::Elf64 => interpreter::<FileHeader64<Endianness>>(head),
+        FileKind::Elf32 => interpreter::<FileHeader32<Endianness>>(head),
+        _ => None,
+    }
+}
@@ -11,6 +11,13 @@
 //!   environment or the delegated command tokens) so the artifacts can be synced
 //!   back there, plus [`cargo_target_env_allowlist`] / [`cargo_target_env_overrides`]
 //!   that thread `CARGO_TARGET_DIR` through the worker env.
+//! - **Build-env forwarding** — [`resolve_forwarded_build_env`] (core:
+//!   [`resolve_forwarded_build_env_with_lookup`]) applies the same idea to the
+//!   variables in [`BUILD_AFFECTING_ENV_VARS`] (`RUSTFLAGS`, `RUSTC_WRAPPER`,
+//!   `CARGO_INCREMENTAL`): set and allowlisted means forwarded, set but not
+//!   allowlisted means the build stays local ([`BuildEnvForwarding::Refuse`]).
+//!   `RUSTFLAGS` is the exception: it is forwarded without an allowlist entry
+//!   unless it holds host-dependent flags ([`host_dependent_rustflags`]).
 //! - **Remote target-dir naming** — [`remote_cargo_target_dir_name`] (unique
 //!   per-job) and [`remote_cargo_pooled_target_dir_name`] (the stable, cache-warm
 //!   pooled name keyed on toolchain/triple/profile/feature-set), with
@@ -32,6 +39,7 @@
 //!
 //! Items consumed by the parent (`run_hook` / `run_exec` call
 //! `resolve_forwarded_cargo_target_dir` + `rewrite_cargo_target_dir_command_for_remote`,
+//! `run_exec` also calls `resolve_forwarded_build_env`,
 //! and `add_cargo_isolation` shares `sanitize_cargo_home_token`) are re-exported
 //! into `hook`; the sibling `transfer_orchestration` imports the six dir-naming /
 //! env helpers it needs directly from `super::cargo_target_dir`. The remaining
@@ -44,7 +52,7 @@ use super::formatting::detect_target_label;
 use super::*;
 use std::collections::HashMap;
 
-fn env_allowlist_contains(env_allowlist: &[String], key: &str) -> bool {
+pub(super) fn env_allowlist_contains(env_allowlist: &[String], key: &str) -> bool {
     env_allowlist
         .iter()
         .map(|item| item.trim())
@@ -61,7 +69,8 @@ fn cargo_kind_uses_target_dir(kind: Option<CompilationKind>) -> bool {
                 | CompilationKind::CargoDoc
                 | CompilationKind::CargoTest
                 | CompilationKind::CargoNextest
-                | CompilationKind::CargoBench,
+                | CompilationKind::CargoBench
+                | CompilationKind::CargoRun,
         )
     )
 }
@@ -156,6 +165,104 @@ pub(super) fn cargo_target_env_overrides(
     Some(overrides)
 }
 
+/// Local environment variables that change what cargo produces. A remote build
+/// that silently drops them yields different artifacts than a local one.
+pub(super) const BUILD_AFFECTING_ENV_VARS: &[&str] =
+    &["RUSTFLAGS", "RUSTC_WRAPPER", "CARGO_INCREMENTAL"];
+
+/// Whether a cargo build may be offloaded given its build-affecting env vars.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum BuildEnvForwarding {
+    /// Offload is safe; the listed variables are set and will be forwarded.
+    Forward(Vec<String>),
+    /// Offload would change the build; the reason is shown to the user.
+    Refuse(String),
+}
+
+/// `RUSTFLAGS` entries whose meaning depends on the machine that runs rustc,
+/// e.g. `-C target-cpu=native`: on the worker they describe the worker's CPU,
+/// and the artifacts synced back may not run locally.
+pub(super) fn host_dependent_rustflags(rustflags: &str) -> Vec<&str> {
+    rustflags
+        .split_whitespace()
+        .filter(|flag| flag.ends_with("target-cpu=native"))
+        .collect()
+}
+
+/// Check [`BUILD_AFFECTING_ENV_VARS`] against `environment.allowlist`.
+///
+/// A set variable is forwarded when allowlisted (the transfer pipeline copies
+/// allowlisted values into the remote env). `RUSTFLAGS` is forwarded without
+/// an allowlist entry unless it has [`host_dependent_rustflags`]; the caller
+/// adds every forwarded name to the pipeline's allowlist. A set variable that
+/// is not allowlisted, or whose value cannot be shell-forwarded (newline, CR,
+/// NUL), refuses offload instead of building with a different environment.
+pub(super) fn resolve_forwarded_build_env_with_lookup<F>(
+    kind: Option<CompilationKind>,
+    env_allowlist: &[String],
+    reporter: &HookReporter,
+    mut lookup_env: F,
+) -> BuildEnvForwarding
+where
+    F: FnMut(&str) -> Option<String>,
+{
+    if !cargo_kind_uses_target_dir(kind) {
+        return BuildEnvForwarding::Forward(Vec::new());
+    }
+
+    let mut forwarded = Vec::new();
+    let mut not_allowlisted = Vec::new();
+    for key in BUILD_AFFECTING_ENV_VARS {
+        let Some(value) = lookup_env(key).filter(|value| !value.trim().is_empty()) else {
+            continue;
+        };
+        if !env_allowlist_contains(env_allowlist, key) {
+            if *key != "RUSTFLAGS" {
+                not_allowlisted.push(*key);
+                continue;
+            }
+            let host_dependent = host_dependent_rustflags(&value);
+            if !host_dependent.is_empty() {
+                return BuildEnvForwarding::Refuse(format!(
+                    "RUSTFLAGS has host-dependent {} (add RUSTFLAGS to environment.allowlist to forward it anyway)",
+                    host_dependent.join(" ")
+                ));
+            }
+        }
+        if value.contains(['\n', '\r', '\0']) {
+            return BuildEnvForwarding::Refuse(format!(
+                "{} contains characters that cannot be forwarded",
+                key
+            ));
+        }
+        forwarded.push((*key).to_string());
+    }
+
+    if !not_allowlisted.is_empty() {
+        return BuildEnvForwarding::Refuse(format!(
+            "{} set locally but not in environment.allowlist",
+            not_allowlisted.join(", ")
+        ));
+    }
+    if !forwarded.is_empty() {
+        reporter.verbose(&format!(
+            "[RCH] forwarding {} to the worker",
+            forwarded.join(", ")
+        ));
+    }
+    BuildEnvForwarding::Forward(forwarded)
+}
+
+pub(super) fn resolve_forwarded_build_env(
+    kind: Option<CompilationKind>,
+    env_allowlist: &[String],
+    reporter: &HookReporter,
+) -> BuildEnvForwarding {
+    resolve_forwarded_build_env_with_lookup(kind, env_allowlist, reporter, |key| {
+        std::env::var(key).ok()
+    })
+}
+
 /// Reduce an arbitrary token to a path-safe basename component: ASCII
 /// alphanumerics, `-` and `_` are kept; everything else collapses to `-`,
 /// leading/trailing `-` are trimmed, and an empty result falls back to
@@ -339,6 +446,51 @@ pub(super) fn remote_cargo_pooled_target_dir_name(
     format!(".rch-target-{safe_worker_id}-pool-{}", key.as_str())
 }
 
+/// Worker-wide cargo build dir shared across projects (`transfer.shared_target_dir`).
+///
+/// Only cargo's intermediates (`CARGO_BUILD_BUILD_DIR`) are shared; final
+/// artifacts still land in the per-project remote target dir, so retrieval and
+/// the pooled-name reuse above are unchanged. The dir is keyed on toolchain and
+/// target triple: cargo's own metadata hashes separate different crate
+/// versions, features and profiles inside it, and its build-dir lock serializes
+/// concurrent builds that land on the same key.
+pub(super) fn remote_shared_build_dir(
+    remote_base: &str,
+    toolchain: Option<&ToolchainInfo>,
+    command: &str,
+) -> String {
+    let toolchain_id = toolchain
+        .map(ToolchainInfo::rustup_toolchain)
+        .unwrap_or_else(|| "unknown".to_string());
+    let triple = target_triple_for_command(command);
+    format!(
+        "{}/.rch-shared-build/{}-{}",
+        remote_base.trim_end_matches('/'),
+        sanitize_cargo_home_token(&toolchain_id),
+        sanitize_cargo_home_token(&triple)
+    )
+}
+
+/// Remote env for `transfer.use_sccache`, or `None` when the worker's
+/// capabilities probe did not find sccache.
+///
+/// Every project on a worker shares one `SCCACHE_DIR`; sccache keys entries
+/// on compiler, flags and inputs, so crates built for one project are cache
+/// hits for the next.
+pub(super) fn remote_sccache_env(
+    worker: &SelectedWorker,
+    remote_base: &str,
+) -> Option<Vec<(&'static str, String)>> {
+    worker.sccache_version.as_ref()?;
+    Some(vec![
+        ("RUSTC_WRAPPER", "sccache".to_string()),
+        (
+            "SCCACHE_DIR",
+            format!("{}/.rch-sccache", remote_base.trim_end_matches('/')),
+        ),
+    ])
+}
+
 /// Idle threshold (hours) after which an abandoned per-job remote target dir is
 /// eligible for reaping. Defaults to 12h: empirically (ts2 disk-fill incident,
 /// 2026-05) active per-job dirs are touched within ~2h while abandoned ones sit
@@ -0,0 +1,98 @@
+//! Vendored crate registries for offline Cargo builds.
+//!
+//! A project built with `--offline` usually vendors its dependencies
+//! (`cargo vendor`) and points crates.io at the copy from
+//! `.cargo/config.toml`:
+//!
+//! ```toml
+//! [source.crates-io]
+//! replace-with = "vendored-sources"
+//!
+//! [source.vendored-sources]
+//! directory = "../vendor"
+//! ```
+//!
+//! The worker builds with a fresh, isolated `CARGO_HOME`, so it has no
+//! registry cache to fall back on: the vendor directory must reach it. A
+//! directory inside the project already syncs with it; one outside becomes an
+//! extra sync root (`SyncClosureMode::VendoredRegistry`). Only the project's
+//! own `.cargo/config.toml` (or legacy `.cargo/config`) is read, because that
+//! is the config that syncs to the worker along with the project.
+
+use super::*;
+
+/// Config files cargo reads from a project's `.cargo` directory, in priority order.
+const CARGO_CONFIG_FILES: &[&str] = &["config.toml", "config"];
+
+/// Guard against `replace-with` cycles.
+const MAX_REPLACE_WITH_HOPS: usize = 8;
+
+/// Directory of the source that replaces crates.io in the project's cargo
+/// config, resolved against the project root. `None` when crates.io is not
+/// replaced by a `directory` source.
+pub(super) fn vendored_source_dir(project_root: &Path) -> Option<PathBuf> {
+    let content = CARGO_CONFIG_FILES
+        .iter()
+        .find_map(|name| std::fs::read_to_string(project_root.join(".cargo").join(name)).ok())?;
+    let config: toml::Value = toml::from_str(&content).ok()?;
+    let sources = config.get("source")?.as_table()?;
+
+    let mut name = "crates-io";
+    for _ in 0..MAX_REPLACE_WITH_HOPS {
+        let source = sources.get(name)?.as_table()?;
+        if let Some(directory) = source.get("directory").and_then(|dir| dir.as_str()) {
+            // Cargo resolves config paths against the directory holding `.cargo`.
+            return Some(project_root.join(directory));
+        }
+        name = source.get("replace-with")?.as_str()?;
+    }
+    None
+}
+
+/// Whether a cargo command runs without network access (`--offline`,
+/// `--frozen`, or `CARGO_NET_OFFLINE=true`).
+pub(super) fn command_is_offline(command: &str) -> bool {
+    command.split_whitespace().any(|token| {
+        matches!(
+            token,
+            "--offline" | "--frozen" | "CARGO_NET_OFFLINE=true" | "CARGO_NET_OFFLINE=1"
+        )
+    })
+}
+
+/// The vendor directory as an extra sync root.
+///
+/// `Ok(None)` when crates.io is not vendored or the vendor directory is
+/// inside the project (and so synced with it). `Err` explains why a vendor
+/// directory the build needs cannot be synced.
+pub(super) fn vendor_sync_root(
+    normalized_project_root: &Path,
+    reporter: &HookReporter,
+    topology_policy: &PathTopologyPolicy,
+) -> Result<Option<PathBuf>, String> {
+    let Some(directory) = vendored_source_dir(normalized_project_root) else {
+        return Ok(None);
+    };
+    let root = normalize_project_path_with_policy(&directory, topology_policy)
+        .map_err(|e| {
+            format!(
+                "vendored registry {} cannot be synced: {}",
+                directory.display(),
+                e
+            )
+        })?
+        .canonical_path()
+        .to_path_buf();
+    if root.starts_with(normalized_project_root) {
+        reporter.verbose(&format!(
+            "[RCH] vendored registry {} syncs with the project",
+            root.display()
+        ));
+        return Ok(None);
+    }
+    reporter.verbose(&format!(
+        "[RCH] vendored registry {} added to sync closure",
+        root.display()
+    ));
+    Ok(Some(root))
+}
@@ -4,10 +4,16 @@
 //! Pure functions over the raw command string — no daemon/hook state. Principal
 //! items: [`estimate_cores_for_command`] (offload core sizing) and
 //! [`cargo_job_count_for_command`] are `pub(crate)` (also called by
-//! `commands::status`); [`tokenize_command`] is the shared lexer; the
+//! `commands::status`); [`tokenize_command`] is the shared lexer, `pub(super)`
+//! for `feature_parity`; the
 //! `--test-threads` / `-j` / `--ignored` / `--exact` / filtered-test detectors
-//! are `pub(super)` for the test suite. The numeric `parse_*` helpers stay
-//! module-private.
+//! and the `make`/`ninja` [`parse_build_tool_jobs`] parser are `pub(super)`
+//! for the test suite, [`has_timings_flag`] and
+//! [`nextest_profile`] are `pub(super)` for `artifact_patterns`, [`is_shardable_nextest_command`]
+//! is `pub(super)` for `run_exec`'s test-sharding gate, and
+//! [`command_produces_machine_stdout`] is `pub(super)` for the telemetry
+//! piggyback guard. The numeric `parse_*`
+//! helpers stay module-private.
 use super::*;
 
 fn parse_u32(value: &str) -> Option<u32> {
@@ -60,6 +66,53 @@ pub(crate) fn cargo_job_count_for_command(command: &str) -> Option<u32> {
         .or_else(|| read_env_u32("CARGO_BUILD_JOBS"))
 }
 
+/// Most slots a `make`/`ninja` build reserves, whether its `-j` is unbounded
+/// (`make -j`, `ninja -j0`) or an explicit count above this. The hook cannot
+/// see the worker's core count, so a large request is capped rather than
+/// sized to a worker that may not be selected (or may not exist).
+pub(super) const BUILD_TOOL_JOBS_SLOT_CAP: u32 = 16;
+
+/// Parallelism requested by a `make` or `ninja` `-j` flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum BuildToolJobs {
+    Count(u32),
+    Unbounded,
+}
+
+/// Parse `-jN`, `-j N`, `--jobs=N` and `--jobs N` from a `make`/`ninja`
+/// command. A bare `-j` (make) or `-j 0` (ninja) is unbounded; when the flag
+/// repeats, the last occurrence wins, as it does for both tools.
+pub(super) fn parse_build_tool_jobs(command: &str) -> Option<BuildToolJobs> {
+    let tokens = tokenize_command(command);
+    let count = |value: &str| match value.parse::<u32>() {
+        Ok(0) => Some(BuildToolJobs::Unbounded),
+        Ok(n) => Some(BuildToolJobs::Count(n)),
+        Err(_) => None,
+    };
+    let mut jobs = None;
+    let mut idx = 0;
+    while idx < tokens.len() {
+        let token = tokens[idx].as_str();
+        if token == "-j" || token == "--jobs" {
+            match tokens.get(idx + 1).and_then(|next| count(next)) {
+                Some(parsed) => {
+                    jobs = Some(parsed);
+                    idx += 1;
+                }
+                None => jobs = Some(BuildToolJobs::Unbounded),
+            }
+        } else if let Some(parsed) = token
+            .strip_prefix("--jobs=")
+            .or_else(|| token.strip_prefix("-j"))
+            .and_then(count)
+        {
+            jobs = Some(parsed);
+        }
+        idx += 1;
+    }
+    jobs
+}
+
 pub(super) fn parse_test_threads(command: &str) -> Option<u32> {
     let tokens: Vec<&str> = command.split_whitespace().collect();
     for (idx, token) in tokens.iter().enumerate() {
@@ -76,7 +129,11 @@ pub(super) fn parse_test_threads(command: &str) -> Option<u32> {
     None
 }
 
-fn tokenize_command(command: &str) -> Vec<String> {
+/// Split a command string into shell words.
+///
+/// Quotes are removed; a backslash escapes the next character outside single
+/// quotes (inside single quotes it is literal, as in POSIX shells).
+pub(super) fn tokenize_command(command: &str) -> Vec<String> {
     let mut tokens = Vec::new();
     let mut current = String::new();
     let mut in_single = false;
@@ -89,7 +146,7 @@ fn tokenize_command(command: &str) -> Vec<String> {
             escaped = false;
             continue;
         }
-        if c == '\\' {
+        if c == '\\' && !in_single {
             escaped = true;
             continue;
         }
@@ -116,78 +173,173 @@ fn tokenize_command(command: &str) -> Vec<String> {
     tokens
 }
 
+/// Shell control operators that end a simple command.
+const CONTROL_OPERATORS: [&str; 5] = ["&&", "||", ";", "|", "&"];
+
+/// Cargo global flags (before the subcommand) that take a separate argument.
+const CARGO_GLOBAL_FLAGS_WITH_ARGS: [&str; 4] = ["--config", "-C", "-Z", "--color"];
+
+/// `cargo test` / `cargo nextest run` / `bun test` flags that take a separate
+/// argument (not using `=`).
+const TEST_FLAGS_WITH_ARGS: [&str; 30] = [
+    "-p",
+    "--package",
+    "--exclude",
+    "--bin",
+    "--test",
+    "--bench",
+    "--example",
+    "-F",
+    "--features",
+    "--target",
+    "--target-dir",
+    "-j",
+    "--jobs",
+    "--color",
+    "--message-format",
+    "--manifest-path",
+    "--profile",
+    "--config",
+    "-Z",
+    // cargo-nextest
+    "-P",
+    "--cargo-profile",
+    "--partition",
+    "--retries",
+    "--test-threads",
+    "--threads",
+    "--tool-config-file",
+    // bun test
+    "--timeout",
+    "--rerun-each",
+    "--preload",
+    "--coverage-reporter",
+];
+
+/// libtest flags (after `--`) that take a separate argument.
+const LIBTEST_FLAGS_WITH_ARGS: [&str; 7] = [
+    "--test-threads",
+    "--skip",
+    "--format",
+    "--logfile",
+    "--color",
+    "--shuffle-seed",
+    "-Z",
+];
+
+/// Whether `token` is a leading `KEY=value` environment assignment.
+fn is_env_assignment(token: &str) -> bool {
+    token.split_once('=').is_some_and(|(key, _)| {
+        !key.is_empty()
+            && !key.starts_with(|c: char| c.is_ascii_digit())
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+/// Whether `token` is a redirection (`2>&1`, `>out.log`, `<`, `&>`), and
+/// whether it consumes the following word as its target.
+fn redirection(token: &str) -> Option<bool> {
+    let op = token.trim_start_matches(|c: char| c.is_ascii_digit());
+    if !(op.starts_with('>') || op.starts_with('<') || op.starts_with("&>")) {
+        return None;
+    }
+    Some(op.trim_start_matches(['>', '<', '&']).is_empty())
+}
+
+/// Arguments following the test subcommand: `cargo test`, `cargo t`,
+/// `cargo nextest run` or `bun test`.
+///
+/// Leading `KEY=value` assignments (optionally after `env`) are skipped,
+/// redirections are dropped, and each `&&` / `|` / `;` separated command is
+/// considered in turn. Returns `None` when no test invocation is found.
+fn test_command_args(command: &str) -> Option<Vec<String>> {
+    let tokens = tokenize_command(command);
+    tokens
+        .split(|token| CONTROL_OPERATORS.contains(&token.as_str()))
+        .find_map(|segment| {
+            let mut words = Vec::with_capacity(segment.len());
+            let mut iter = segment.iter();
+            while let Some(token) = iter.next() {
+                match redirection(token) {
+                    Some(true) => {
+                        iter.next();
+                    }
+                    Some(false) => {}
+                    None => words.push(token.as_str()),
+                }
+            }
+            test_subcommand_args(&words)
+        })
+}
+
+fn test_subcommand_args(words: &[&str]) -> Option<Vec<String>> {
+    let mut i = 0;
+    while words.get(i).is_some_and(|w| is_env_assignment(w)) {
+        i += 1;
+    }
+    if words.get(i) == Some(&"env") {
+        i += 1;
+        while words.get(i).is_some_and(|w| is_env_assignment(w)) {
+            i += 1;
+        }
+    }
+    let program = words.get(i)?.rsplit('/').next()?;
+    i += 1;
+
+    let rest = match program {
+        "cargo" => {
+            // Skip `+toolchain` and global flags before the subcommand.
+            while let Some(word) = words.get(i)
+                && (word.starts_with('+') || word.starts_with('-'))
+            {
+                i += if CARGO_GLOBAL_FLAGS_WITH_ARGS.contains(word) {
+                    2
+                } else {
+                    1
+                };
+            }
+            match *words.get(i)? {
+                "test" | "t" => i + 1,
+                "nextest" if matches!(words.get(i + 1), Some(&("run" | "r"))) => i + 2,
+                _ => return None,
+            }
+        }
+        "bun" if words.get(i) == Some(&"test") => i + 1,
+        _ => return None,
+    };
+    Some(words[rest..].iter().map(|w| w.to_string()).collect())
+}
+
 /// Detect if a cargo test command has a test name filter.
 ///
 /// Filtered tests (e.g., `cargo test my_test`) typically run fewer tests
-/// and thus require fewer slots than a full test suite.
+/// and thus require fewer slots than a full test suite. A filter is a
+/// positional argument either to cargo or, after `--`, to the test binary;
+/// values of flags such as `-p` or `--skip` are not filters.
 ///
 /// Returns true if the command appears to filter tests by name.
 pub(super) fn is_filtered_test_command(command: &str) -> bool {
-    let tokens = tokenize_command(command);
-
-    // Find the position of "test" or "run" (for nextest) in the command
-    let test_pos = tokens
-        .iter()
-        .position(|t| t == "test" || t == "t" || t == "run");
-    let Some(test_idx) = test_pos else {
+    let Some(args) = test_command_args(command) else {
         return false;
     };
 
-    // Flags that take a separate argument (not using =)
-    let flags_with_args = [
-        "-p",
-        "--package",
-        "--bin",
-        "--test",
-        "--bench",
-        "--example",
-        "--features",
-        "--target",
-        "--target-dir",
-        "-j",
-        "--jobs",
-        "--color",
-        "--message-format",
-        "--manifest-path",
-        "--profile",
-        "--config",
-        "-Z",
-    ];
-
-    let mut i = test_idx + 1;
-    while i < tokens.len() {
-        let token = &tokens[i];
-
-        // Stop at the separator
-        if token == "--" {
-            // Check if there is a positional argument after --
-            if i + 1 < tokens.len() {
-                let next = &tokens[i + 1];
-                if !next.starts_with('-') {
-                    return true;
-                }
-            }
-            break;
-        }
-
-        // Check if this is a flag that takes an argument
-        if flags_with_args.contains(&token.as_str()) {
-            i += 2;
+    let mut args = args.iter();
+    let mut flags_with_args: &[&str] = &TEST_FLAGS_WITH_ARGS;
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            // Everything after the separator goes to the test binary.
+            flags_with_args = &LIBTEST_FLAGS_WITH_ARGS;
             continue;
         }
 
-        // Check if this is a flag=value style
-        if flags_with_args
-            .iter()
-            .any(|&f| token.starts_with(&format!("{}=", f)))
-        {
-            i += 1;
+        // Skip the value of a flag that takes a separate argument
+        if flags_with_args.contains(&arg.as_str()) {
+            args.next();
             continue;
         }
 
-        // Skip any other flag-like tokens
-        if token.starts_with('-') {
-            i += 1;
+        // Skip any other flag-like tokens (including flag=value)
+        if arg.starts_with('-') {
             continue;
         }
 
@@ -218,6 +370,103 @@ pub(super) fn has_exact_flag(command: &str) -> bool {
     tokenize_command(command).iter().any(|t| t == "--exact")
 }
 
+/// Check if the command asks cargo for a `--timings` report (bare or with an
+/// output-format list such as `--timings=html,json`).
+///
+/// Arguments after a bare `--` belong to the test binary, not cargo.
+pub(super) fn has_timings_flag(command: &str) -> bool {
+    tokenize_command(command)
+        .iter()
+        .take_while(|t| t.as_str() != "--")
+        .any(|t| t == "--timings" || t.starts_with("--timings="))
+}
+
+/// Whether the command writes a machine-readable stream to stdout:
+/// `--build-plan` / `--unit-graph` (nightly JSON build graphs) or a JSON
+/// `--message-format` (`json`, `json-render-diagnostics`, ...).
+///
+/// The telemetry piggyback block is appended to the remote command's stdout,
+/// so it is left off for these commands to keep the stream parseable.
+/// Arguments after a bare `--` belong to the test binary, not cargo.
+pub(super) fn command_produces_machine_stdout(command: &str) -> bool {
+    let is_json_format = |value: &str| value.split(',').any(|f| f.starts_with("json"));
+    let tokens = tokenize_command(command);
+    let mut args = tokens.iter().take_while(|t| t.as_str() != "--");
+    while let Some(token) = args.next() {
+        let machine = match token.as_str() {
+            "--build-plan" | "--unit-graph" => true,
+            "--message-format" => args.next().is_some_and(|value| is_json_format(value)),
+            other => other
+                .strip_prefix("--message-format=")
+                .is_some_and(is_json_format),
+        };
+        if machine {
+            return true;
+        }
+    }
+    false
+}
+
+/// Nextest profile selected by a `cargo nextest` command.
+///
+/// Reads `--profile`/`-P` before any `--` separator, falling back to a leading
+/// `NEXTEST_PROFILE=` assignment. Names that could widen or escape a glob
+/// (path separators, wildcards, `..`) are ignored so callers fall back to the
+/// whole nextest store.
+pub(super) fn nextest_profile(command: &str) -> Option<String> {
+    let tokens = tokenize_command(command);
+    let mut args = tokens.iter().take_while(|t| t.as_str() != "--");
+    let mut profile = None;
+    while let Some(token) = args.next() {
+        match token.as_str() {
+            "--profile" | "-P" => profile = args.next().cloned(),
+            other => {
+                if let Some(value) = other.strip_prefix("--profile=") {
+                    profile = Some(value.to_string());
+                }
+            }
+        }
+    }
+    let profile = profile.or_else(|| {
+        tokens
+            .iter()
+            .take_while(|t| t.contains('=') && !t.starts_with('-'))
+            .find_map(|t| t.strip_prefix("NEXTEST_PROFILE="))
+            .map(str::to_string)
+    })?;
+    let valid = !profile.is_empty()
+        && profile != "."
+        && !profile.contains("..")
+        && profile
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    valid.then_some(profile)
+}
+
+/// Check whether a command is an unfiltered `cargo nextest run` that can be
+/// split into `--partition count:i/N` shards.
+///
+/// Commands that already pass `--partition`, a filterset (`-E`), or a test
+/// name filter are left alone: the caller has already narrowed the run.
+pub(super) fn is_shardable_nextest_command(kind: Option<CompilationKind>, command: &str) -> bool {
+    if kind != Some(CompilationKind::CargoNextest) {
+        return false;
+    }
+    let tokens = tokenize_command(command);
+    let is_nextest_run = tokens
+        .windows(2)
+        .any(|pair| pair[0] == "nextest" && pair[1] == "run");
+    if !is_nextest_run {
+        return false;
+    }
+    let narrows_run = tokens.iter().any(|t| {
+        ["--partition", "-E", "--filter-expr", "--filterset"]
+            .iter()
+            .any(|flag| t == flag || t.starts_with(&format!("{}=", flag)))
+    });
+    !narrows_run && !is_filtered_test_command(command)
+}
+
 pub(crate) fn estimate_cores_for_command(
     kind: Option<CompilationKind>,
     command: &str,
@@ -289,6 +538,13 @@ pub(crate) fn estimate_cores_for_command(
         ) => cargo_job_count_for_command(command)
             .unwrap_or(check_default)
             .max(1),
+        Some(CompilationKind::Make | CompilationKind::Ninja) => {
+            match parse_build_tool_jobs(command) {
+                Some(BuildToolJobs::Count(jobs)) => jobs.min(BUILD_TOOL_JOBS_SLOT_CAP),
+                Some(BuildToolJobs::Unbounded) => BUILD_TOOL_JOBS_SLOT_CAP,
+                None => build_default,
+            }
+        }
         Some(_) => cargo_job_count_for_command(command)
             .unwrap_or(build_default)
             .max(1),
@@ -4,10 +4,107 @@
 //!
 //! [`query_daemon`] / [`release_worker`] are `pub(crate)` because
 //! `commands::status` and the daemon hot path both call them;
-//! [`record_build`] is hook-internal. The selection response is parsed via
+//! [`record_build`] is hook-internal. When the configured socket is missing,
+//! [`query_daemon`] looks for a daemon at the well-known socket locations
+//! ([`well_known_socket_paths`]) and warns once with the config fix; with
+//! `RCH_SOCKET_AUTODETECT=1` the hook talks to that daemon instead
+//! ([`effective_socket_path`]). The selection response is parsed via
 //! the parent's re-exported `parse_selection_response`; the timeout helpers
 //! and `urlencoding_encode` stay `pub(super)` for the test suite.
+//! [`build_exec_idempotency_key`] and [`wait_for_build_result`] let a
+//! duplicate `rch exec` coalesce onto the build the daemon already started.
 use super::*;
+use rch_common::BuildTailEvent;
+use std::sync::OnceLock;
+
+/// Socket of a live daemon found away from the configured path, if any.
+static DISCOVERED_SOCKET: OnceLock<Option<String>> = OnceLock::new();
+
+/// Socket locations `default_socket_path` can produce (XDG runtime dir,
+/// `~/.cache/rch`, `/tmp`), minus `configured`. Pure so the candidate list is
+/// testable without touching the environment.
+pub(super) fn well_known_socket_paths(
+    configured: &str,
+    xdg_runtime_dir: Option<&str>,
+    cache_dir: Option<&Path>,
+) -> Vec<String> {
+    let mut candidates = Vec::new();
+    if let Some(runtime_dir) = xdg_runtime_dir.filter(|dir| !dir.trim().is_empty()) {
+        candidates.push(Path::new(runtime_dir).join("rch.sock"));
+    }
+    if let Some(cache_dir) = cache_dir {
+        candidates.push(cache_dir.join("rch").join("rch.sock"));
+    }
+    candidates.push(PathBuf::from("/tmp/rch.sock"));
+
+    let mut paths: Vec<String> = Vec::new();
+    for candidate in candidates {
+        let candidate = candidate.to_string_lossy().to_string();
+        if candidate != configured.trim() && !paths.contains(&candidate) {
+            paths.push(candidate);
+        }
+    }
+    paths
+}
+
+fn socket_autodetect_enabled() -> bool {
+    std::env::var("RCH_SOCKET_AUTODETECT").is_ok_and(|value| env_flag_enabled(&value))
+}
+
+/// Find a daemon accepting connections on a well-known socket other than
+/// `configured`. The first lookup in a process probes and, on a hit, prints
+/// the config fix; later lookups reuse the answer.
+async fn discover_daemon_socket(configured: &str) -> Option<String> {
+    if let Some(found) = DISCOVERED_SOCKET.get() {
+        return found.clone();
+    }
+
+    let xdg_runtime_dir = std::env::var("XDG_RUNTIME_DIR").ok();
+    let cache_dir = dirs::cache_dir();
+    let mut found = None;
+    for candidate in
+        well_known_socket_paths(configured, xdg_runtime_dir.as_deref(), cache_dir.as_deref())
+    {
+        if !Path::new(&candidate).exists() {
+            continue;
+        }
+        if let Ok(Ok(_)) =
+            timeout(Duration::from_millis(300), UnixStream::connect(&candidate)).await
+        {
+            found = Some(candidate);
+            break;
+        }
+    }
+
+    if let Some(path) = &found {
+        warn!(
+            "Daemon socket {} not found, but a daemon is listening on {}",
+            configured, path
+        );
+        let action = if socket_autodetect_enabled() {
+            "using it (RCH_SOCKET_AUTODETECT)"
+        } else {
+            "set RCH_SOCKET_AUTODETECT=1 to use it"
+        };
+        eprintln!(
+            "[RCH] warning: no daemon at configured socket {configured}, but one is running at {path}; \
+             {action}. Fix: set general.socket_path = \"{path}\" in the rch config (or RCH_SOCKET_PATH)."
+        );
+    }
+    DISCOVERED_SOCKET.get_or_init(|| found).clone()
+}
+
+/// Socket the hook should talk to: `configured` unless it is missing and
+/// `RCH_SOCKET_AUTODETECT` chose a discovered daemon.
+pub(super) fn effective_socket_path(configured: &str) -> String {
+    if !Path::new(configured).exists()
+        && socket_autodetect_enabled()
+        && let Some(Some(found)) = DISCOVERED_SOCKET.get()
+    {
+        return found.clone();
+    }
+    configured.to_string()
+}
 
 /// Query the daemon for a worker.
 #[allow(clippy::too_many_arguments)] // Command routing query wires many independent fields.
@@ -23,6 +120,10 @@ pub(crate) async fn query_daemon(
     hook_pid: Option<u32>,
     wait_for_worker: bool,
     preferred_workers: &[WorkerId],
+    fleet: Option<&str>,
+    min_free_disk_gb: Option<f64>,
+    allowed_workers: &[WorkerId],
+    idempotency_key: Option<&str>,
 ) -> anyhow::Result<SelectionResponse> {
     // Mock support: RCH_MOCK_CIRCUIT_OPEN simulates all circuits open
     // This needs to be checked in the hook since the daemon may be started
@@ -37,16 +138,18 @@ pub(crate) async fn query_daemon(
         });
     }
 
-    // Check if socket exists
+    // Check if socket exists; a daemon on another well-known path is
+    // reported, and used with RCH_SOCKET_AUTODETECT.
     if !Path::new(socket_path).exists() {
-        return Err(DaemonError::SocketNotFound {
-            socket_path: socket_path.to_string(),
-        }
-        .into());
+        discover_daemon_socket(socket_path).await;
+    }
+    let socket_path = effective_socket_path(socket_path);
+    if !Path::new(&socket_path).exists() {
+        return Err(DaemonError::SocketNotFound { socket_path }.into());
     }
 
     // Connect to daemon (with timeout to avoid hanging if socket is stuck)
-    let stream = timeout(Duration::from_secs(5), UnixStream::connect(socket_path))
+    let stream = timeout(Duration::from_secs(5), UnixStream::connect(&socket_path))
         .await
         .map_err(|_| anyhow::anyhow!("Daemon connect timed out after 5s"))??;
     let (reader, mut writer) = stream.into_split();
@@ -85,6 +188,13 @@ pub(crate) async fn query_daemon(
         query.push_str(&format!("&hook_pid={}", pid));
     }
 
+    // Session set by the hook's `rch exec` delegation; keys queue fairness.
+    if let Ok(session) = std::env::var("RCH_SESSION_ID")
+        && !session.is_empty()
+    {
+        query.push_str(&format!("&session={}", urlencoding_encode(&session)));
+    }
+
     for worker in preferred_workers {
         query.push_str(&format!("&worker={}", urlencoding_encode(worker.as_str())));
     }
@@ -100,6 +210,30 @@ pub(crate) async fn query_daemon(
         ));
     }
 
+    if let Some(fleet) = fleet {
+        query.push_str(&format!("&fleet={}", urlencoding_encode(fleet)));
+    }
+
+    if let Some(min_free) = min_free_disk_gb {
+        query.push_str(&format!("&min_free_disk_gb={}", min_free));
+    }
+
+    if !allowed_workers.is_empty() {
+        let allowed = allowed_workers
+            .iter()
+            .map(|worker| worker.as_str())
+            .collect::<Vec<_>>()
+            .join(",");
+        query.push_str(&format!(
+            "&allowed_workers={}",
+            urlencoding_encode(&allowed)
+        ));
+    }
+
+    if let Some(key) = idempotency_key {
+        query.push_str(&format!("&idempotency_key={}", urlencoding_encode(key)));
+    }
+
     // When all workers are at capacity, queue the build on the daemon instead of
     // falling back to a local compilation storm. Disable with RCH_QUEUE_WHEN_BUSY=0.
     if wait_for_worker {
@@ -165,6 +299,154 @@ pub(crate) async fn query_daemon(
     Ok(response)
 }
 
+/// Idempotency key for an `rch exec` invocation: the same tool call in the
+/// same project, directory and session hashes to the same key, so the daemon
+/// can coalesce a duplicate hook invocation onto the first build while a
+/// rerun of the same command (a new tool call) builds again.
+pub(super) fn build_exec_idempotency_key(
+    project: &str,
+    cwd: &Path,
+    tool_use_id: &str,
+    session: Option<&str>,
+) -> String {
+    let mut material = project.to_string();
+    material.push('|');
+    material.push_str(&cwd.to_string_lossy());
+    material.push('|');
+    material.push_str(tool_use_id);
+    if let Some(session) = session {
+        material.push('|');
+        material.push_str(session);
+    }
+    let hash = blake3::hash(material.as_bytes()).to_hex();
+    format!("rch-build-{}", &hash[..16])
+}
+
+/// Longest a coalesced duplicate waits for the original build to finish.
+const COALESCED_BUILD_MAX_WAIT: Duration = Duration::from_secs(60 * 60);
+
+/// Buffered output lines replayed when following a coalesced build.
+const COALESCED_REPLAY_LINES: usize = 500;
+
+/// Follow build `build_id` (`GET /tail`), printing its buffered and live
+/// output, and return its exit code once it finishes. `None` when the daemon
+/// does not know the build or did not report an exit code.
+pub(super) async fn follow_build_output(
+    socket_path: &str,
+    build_id: u64,
+) -> anyhow::Result<Option<i32>> {
+    let socket_path = effective_socket_path(socket_path);
+    let stream = timeout(Duration::from_secs(5), UnixStream::connect(&socket_path))
+        .await
+        .map_err(|_| anyhow::anyhow!("Daemon connect timed out after 5s"))??;
+    let (reader, mut writer) = stream.into_split();
+    writer
+        .write_all(
+            format!(
+                "GET /tail?build_id={}&lines={}\n",
+                build_id, COALESCED_REPLAY_LINES
+            )
+            .as_bytes(),
+        )
+        .await?;
+    writer.flush().await?;
+
+    let mut reader = BufReader::new(reader);
+    let follow = async {
+        let mut line = String::new();
+        let mut in_body = false;
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).await? == 0 {
+                return anyhow::Ok(None);
+            }
+            let trimmed = line.trim_end();
+            if !in_body {
+                in_body = trimmed.is_empty();
+                continue;
+            }
+            if trimmed.is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<BuildTailEvent>(trimmed)? {
+                BuildTailEvent::Line { line, .. } => println!("{}", line),
+                BuildTailEvent::Finished { exit_code } => return Ok(exit_code),
+                BuildTailEvent::NotFound => return Ok(None),
+                BuildTailEvent::Phase { .. } | BuildTailEvent::Skipped { .. } => {}
+            }
+        }
+    };
+    timeout(COALESCED_BUILD_MAX_WAIT, follow)
+        .await
+        .map_err(|_| {
+            anyhow::anyhow!(
+                "build {} still running after {}s",
+                build_id,
+                COALESCED_BUILD_MAX_WAIT.as_secs()
+            )
+        })?
+}
+
+/// Wait for build `build_id` to finish (`GET /builds/{id}`) and return its
+/// record, or `None` if the daemon no longer knows the build.
+pub(super) async fn wait_for_build_result(
+    socket_path: &str,
+    build_id: u64,
+) -> anyhow::Result<Option<BuildRecord>> {
+    const POLL_INTERVAL: Duration = Duration::from_millis(500);
+    let socket_path = effective_socket_path(socket_path);
+    let started = Instant::now();
+
+    loop {
+        let stream = timeout(Duration::from_secs(5), UnixStream::connect(&socket_path))
+            .await
+            .map_err(|_| anyhow::anyhow!("Daemon connect timed out after 5s"))??;
+        let (reader, mut writer) = stream.into_split();
+        writer
+            .write_all(format!("GET /builds/{}\n", build_id).as_bytes())
+            .await?;
+        writer.flush().await?;
+
+        let mut reader = BufReader::new(reader);
+        let mut line = String::new();
+        let mut body = String::new();
+        let mut in_body = false;
+        let read_body = async {
+            loop {
+                line.clear();
+                if reader.read_line(&mut line).await? == 0 {
+                    break;
+                }
+                if in_body {
+                    body.push_str(&line);
+                } else if line.trim().is_empty() {
+                    in_body = true;
+                }
+            }
+            anyhow::Ok(())
+        };
+        timeout(Duration::from_secs(5), read_body)
+            .await
+            .map_err(|_| anyhow::anyhow!("Daemon response timed out after 5s"))??;
+
+        let value: serde_json::Value = serde_json::from_str(body.trim())?;
+        match value.get("error").and_then(|error| error.as_str()) {
+            None => return Ok(Some(serde_json::from_value(value)?)),
+            Some(error) if error.ends_with("still running") => {}
+            Some(_) => return Ok(None),
+        }
+
+        if started.elapsed() >= COALESCED_BUILD_MAX_WAIT {
+            anyhow::bail!(
+                "build {} still running after {}s",
+                build_id,
+                COALESCED_BUILD_MAX_WAIT.as_secs()
+            );
+        }
+        sleep(POLL_INTERVAL).await;
+    }
+}
+
 /// Release reserved slots on a worker.
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn release_worker(
@@ -176,12 +458,16 @@ pub(crate) async fn release_worker(
     duration_ms: Option<u64>,
     bytes_transferred: Option<u64>,
     timing: Option<&CommandTimingBreakdown>,
+    decision_trace: &[DecisionTraceStep],
+    project_root: Option<&Path>,
+    toolchain: Option<&ToolchainInfo>,
 ) -> anyhow::Result<()> {
-    if !Path::new(socket_path).exists() {
+    let socket_path = effective_socket_path(socket_path);
+    if !Path::new(&socket_path).exists() {
         return Ok(()); // Ignore if daemon gone
     }
 
-    let stream = match timeout(Duration::from_secs(2), UnixStream::connect(socket_path)).await {
+    let stream = match timeout(Duration::from_secs(2), UnixStream::connect(&socket_path)).await {
         Ok(Ok(s)) => s,
         Ok(Err(e)) => return Err(e.into()),
         Err(_) => return Ok(()), // Timeout connecting — daemon likely busy, don't block hook
@@ -208,9 +494,16 @@ pub(crate) async fn release_worker(
     }
     request.push('\n');
 
-    // Add timing breakdown as JSON body if present
-    if let Some(timing) = timing
-        && let Ok(json) = serde_json::to_string(timing)
+    // Add timing breakdown, decision trace and the replay context (project
+    // path and toolchain, used by `rch replay`) as JSON body if present
+    let body = ReleaseWorkerBody {
+        timing: timing.cloned(),
+        decision_trace: decision_trace.to_vec(),
+        project_path: project_root.map(|root| root.display().to_string()),
+        toolchain: toolchain.map(ToolchainInfo::rustup_toolchain),
+    };
+    if !body.is_empty()
+        && let Ok(json) = serde_json::to_string(&body)
     {
         request.push_str(&json);
         request.push('\n');
@@ -234,11 +527,12 @@ pub(crate) async fn record_build(
     project: &str,
     is_test: bool,
 ) -> anyhow::Result<()> {
-    if !Path::new(socket_path).exists() {
+    let socket_path = effective_socket_path(socket_path);
+    if !Path::new(&socket_path).exists() {
         return Ok(()); // Ignore if daemon gone
     }
 
-    let stream = match timeout(Duration::from_secs(2), UnixStream::connect(socket_path)).await {
+    let stream = match timeout(Duration::from_secs(2), UnixStream::connect(&socket_path)).await {
         Ok(Ok(s)) => s,
         Ok(Err(e)) => return Err(e.into()),
         Err(_) => return Ok(()), // Timeout connecting — daemon likely busy, don't block hook
@@ -47,6 +47,8 @@ pub(super) fn merge_sync_result(base: &SyncResult, extra: &SyncResult) -> SyncRe
             .files_transferred
             .saturating_add(extra.files_transferred),
         duration_ms: base.duration_ms.saturating_add(extra.duration_ms),
+        raw_bytes: base.raw_bytes.saturating_add(extra.raw_bytes),
+        compressed_bytes: base.compressed_bytes.saturating_add(extra.compressed_bytes),
     }
 }
 
@@ -65,6 +67,9 @@ pub(super) struct SyncClosurePlanEntry {
 pub(super) enum SyncClosureMode {
     Full,
     WorkspaceMetadata,
+    /// `cargo vendor` directory backing `[source.crates-io] replace-with`;
+    /// synced in full but holds no manifest of its own.
+    VendoredRegistry,
 }
 
 /// Outcome of syncing a single closure root during multi-root transfer.
@@ -541,6 +546,9 @@ pub(super) fn cargo_workspace_member_source_entrypoints(workspace_root: &Path) -
 pub(super) fn dependency_preflight_checks_for_entry(
     entry: &SyncClosurePlanEntry,
 ) -> Vec<DependencyPreflightCheck> {
+    if entry.mode == SyncClosureMode::VendoredRegistry {
+        return Vec::new();
+    }
     let remote_root = PathBuf::from(&entry.remote_root);
     let manifest = remote_root.join("Cargo.toml").to_string_lossy().to_string();
     let mut checks = Vec::new();
@@ -759,7 +767,7 @@ pub(super) fn canonicalize_sync_root_for_plan(root: &Path, policy: &PathTopology
         .unwrap_or_else(|| root.to_path_buf())
 }
 
-fn manifest_declares_workspace(manifest_path: &Path) -> bool {
+pub(super) fn manifest_declares_workspace(manifest_path: &Path) -> bool {
     let Ok(contents) = std::fs::read_to_string(manifest_path) else {
         return false;
     };
@@ -893,6 +901,43 @@ pub(super) fn build_sync_closure_plan(
         .collect()
 }
 
+/// Append a vendored registry root to the sync plan, unless a fully synced
+/// root already contains it.
+pub(super) fn push_vendor_sync_entry(
+    plan: &mut Vec<SyncClosurePlanEntry>,
+    vendor_root: &Path,
+    topology_policy: &PathTopologyPolicy,
+) -> Result<(), String> {
+    let root = canonicalize_sync_root_for_plan(vendor_root, topology_policy);
+    if !is_within_sync_topology(&root, topology_policy) {
+        return Err(format!(
+            "vendored registry {} is outside allowed topology ({} / {})",
+            root.display(),
+            topology_policy.canonical_root().display(),
+            topology_policy.alias_root().display(),
+        ));
+    }
+    if plan
+        .iter()
+        .any(|entry| entry.mode == SyncClosureMode::Full && root.starts_with(&entry.local_root))
+    {
+        return Ok(());
+    }
+    plan.push(SyncClosurePlanEntry {
+        remote_root: map_sync_root_to_remote_root(&root, topology_policy),
+        project_id: project_id_from_path(&root),
+        root_hash: compute_project_hash_with_dependency_roots_and_policy(
+            &root,
+            &[],
+            topology_policy,
+        ),
+        is_primary: false,
+        mode: SyncClosureMode::VendoredRegistry,
+        local_root: root,
+    });
+    Ok(())
+}
+
 pub(super) fn build_sync_closure_manifest(
     plan: &[SyncClosurePlanEntry],
     normalized_project_root: &Path,
@@ -0,0 +1,539 @@
+//! `rch diff-artifacts`: build once remotely and once locally, then compare.
+//!
+//! Both builds write into scratch target dirs under
+//! `target/rch-diff-artifacts/` (`remote/` is filled by the usual custom
+//! `CARGO_TARGET_DIR` sync, `local/` by a local run with `CARGO_TARGET_DIR`
+//! set), so the project's own `target/` is left alone. Final artifacts
+//! (binaries, rlibs, static and shared libraries) are matched by relative path
+//! and compared by blake3 hash.
+//!
+//! Differences that are expected rather than a parity bug are noted, not
+//! failed on: an artifact that embeds its own build path (the remote project
+//! dir, the local scratch dir, or cargo/rustup sources under a home directory
+//! that differs between the two machines) cannot match byte for byte. An
+//! archive or PE image that matches once its header timestamps are zeroed is
+//! reported as a timestamp-only difference, also without failing. Anything
+//! else that differs, or exists on one side only, fails the command.
+
+use super::*;
+
+use crate::ui::context::OutputContext;
+use std::collections::BTreeMap;
+
+/// Scratch directory under the project's `target/`.
+const SCRATCH_DIR: &str = "rch-diff-artifacts";
+
+/// Extensions of final build artifacts worth comparing.
+const ARTIFACT_EXTENSIONS: &[&str] = &["rlib", "so", "dylib", "a", "dll", "lib", "exe", "wasm"];
+
+/// Target-dir subdirectories holding intermediate state, never compared.
+const SKIPPED_DIRS: &[&str] = &["build", "incremental", ".fingerprint"];
+
+/// How one artifact compares between the two builds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub(super) enum ArtifactComparison {
+    /// Same blake3 hash.
+    Identical,
+    /// Differs, but embeds a build-specific path, so it could not match.
+    ExpectedDifference { reason: String },
+    /// Differs only in archive member or PE header timestamps.
+    TimestampOnly,
+    /// Differs with no known cause.
+    Differs { detail: String },
+    /// Built remotely only.
+    OnlyRemote,
+    /// Built locally only.
+    OnlyLocal,
+}
+
+impl ArtifactComparison {
+    fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            Self::Differs { .. } | Self::OnlyRemote | Self::OnlyLocal
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct ArtifactDiff {
+    pub path: String,
+    #[serde(flatten)]
+    pub comparison: ArtifactComparison,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct DiffArtifactsReport {
+    command: String,
+    worker: String,
+    remote_dir: PathBuf,
+    local_dir: PathBuf,
+    identical: usize,
+    expected_differences: usize,
+    timestamp_differences: usize,
+    failures: usize,
+    artifacts: Vec<ArtifactDiff>,
+}
+
+/// Whether `path` (relative to a target dir) is a final artifact.
+fn is_artifact(relative: &Path, absolute: &Path) -> bool {
+    if relative
+        .components()
+        .any(|c| SKIPPED_DIRS.iter().any(|dir| c.as_os_str() == *dir))
+    {
+        return false;
+    }
+    match relative.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) => ARTIFACT_EXTENSIONS.contains(&ext),
+        // Executables: the hashed copies in `deps/` duplicate the top-level ones.
+        None => {
+            use std::os::unix::fs::PermissionsExt;
+            !relative.components().any(|c| c.as_os_str() == "deps")
+                && std::fs::metadata(absolute)
+                    .map(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+                    .unwrap_or(false)
+        }
+    }
+}
+
+/// Final artifacts under `root`, keyed by their `/`-separated relative path.
+pub(super) fn collect_artifacts(root: &Path) -> BTreeMap<String, PathBuf> {
+    fn walk(root: &Path, dir: &Path, found: &mut BTreeMap<String, PathBuf>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                walk(root, &path, found);
+            } else if file_type.is_file()
+                && let Ok(relative) = path.strip_prefix(root)
+                && is_artifact(relative, &path)
+            {
+                let key = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                found.insert(key, path);
+            }
+        }
+    }
+    let mut found = BTreeMap::new();
+    walk(root, root, &mut found);
+    found
+}
+
+/// Home-relative directories whose paths end up in artifacts (dependency
+/// sources and the toolchain's std sources).
+const HOME_BUILD_DIRS: &[&str] = &[".cargo/registry/", ".cargo/git/", ".rustup/toolchains/"];
+
+/// Build-path markers under `home`. A whole home directory is too broad a
+/// marker: any string mentioning it would excuse a real difference.
+pub(super) fn home_build_markers(home: &str) -> Vec<String> {
+    let home = home.trim_end_matches('/');
+    HOME_BUILD_DIRS
+        .iter()
+        .map(|dir| format!("{}/{}", home, dir))
+        .collect()
+}
+
+const AR_MAGIC: &[u8] = b"!<arch>\n";
+const AR_HEADER_LEN: usize = 60;
+/// Byte range of the decimal mtime field inside an ar member header.
+const AR_MTIME: std::ops::Range<usize> = 16..28;
+/// Byte range of the decimal size field inside an ar member header.
+const AR_SIZE: std::ops::Range<usize> = 48..58;
+
+/// A copy of `bytes` with build timestamps blanked, for archives (`.rlib`,
+/// `.a`, `.lib`) and PE images (`.exe`, `.dll`). `None` for other formats.
+fn without_timestamps(bytes: &[u8]) -> Option<Vec<u8>> {
+    let mut out = bytes.to_vec();
+    if out.starts_with(AR_MAGIC) {
+        let mut offset = AR_MAGIC.len();
+        while offset + AR_HEADER_LEN <= out.len() {
+            let header = offset..offset + AR_HEADER_LEN;
+            let size: usize =
+                std::str::from_utf8(&out[header.start + AR_SIZE.start..header.start + AR_SIZE.end])
+                    .ok()?
+                    .trim()
+                    .parse()
+                    .ok()?;
+            out[header.start + AR_MTIME.start..header.start + AR_MTIME.end].fill(b' ');
+            offset = header.end + size + size % 2;
+        }
+        return Some(out);
+    }
+    if out.starts_with(b"MZ") && out.len() >= 0x40 {
+        let pe = u32::from_le_bytes(out[0x3c..0x40].try_into().ok()?) as usize;
+        // COFF TimeDateStamp, and the optional-header CheckSum that covers it.
+        if out.get(pe..pe + 4) == Some(b"PE\0\0".as_slice()) && out.len() >= pe + 92 {
+            out[pe + 8..pe + 12].fill(0);
+            out[pe + 88..pe + 92].fill(0);
+            return Some(out);
+        }
+    }
+    None
+}
+
+fn contains_bytes(haystack: &[u8], needle: &[u8]) -> bool {
+    !needle.is_empty()
+        && haystack
+            .windows(needle.len())
+            .any(|window| window == needle)
+}
+
+/// Compare one artifact present in both builds. `remote_markers` and
+/// `local_markers` are build-specific paths that, when embedded, explain a
+/// difference.
+pub(super) fn compare_artifact(
+    remote: &[u8],
+    local: &[u8],
+    remote_markers: &[String],
+    local_markers: &[String],
+) -> ArtifactComparison {
+    if blake3::hash(remote) == blake3::hash(local) {
+        return ArtifactComparison::Identical;
+    }
+    if remote.len() == local.len()
+        && let (Some(remote), Some(local)) = (without_timestamps(remote), without_timestamps(local))
+        && remote == local
+    {
+        return ArtifactComparison::TimestampOnly;
+    }
+    let embedded = remote_markers
+        .iter()
+        .find(|marker| contains_bytes(remote, marker.as_bytes()))
+        .or_else(|| {
+            local_markers
+                .iter()
+                .find(|marker| contains_bytes(local, marker.as_bytes()))
+        });
+    if let Some(marker) = embedded {
+        return ArtifactComparison::ExpectedDifference {
+            reason: format!("embeds build path {}", marker),
+        };
+    }
+    let detail = if remote.len() == local.len() {
+        let changed = remote.iter().zip(local).filter(|(a, b)| a != b).count();
+        format!(
+            "same size, {} byte(s) differ (embedded timestamp or hash?)",
+            changed
+        )
+    } else {
+        format!(
+            "{} bytes remote vs {} bytes local",
+            remote.len(),
+            local.len()
+        )
+    };
+    ArtifactComparison::Differs { detail }
+}
+
+/// Match and compare the artifacts of two target dirs.
+pub(super) fn diff_target_dirs(
+    remote_dir: &Path,
+    local_dir: &Path,
+    remote_markers: &[String],
+    local_markers: &[String],
+) -> Vec<ArtifactDiff> {
+    let remote = collect_artifacts(remote_dir);
+    let local = collect_artifacts(local_dir);
+    let mut paths: Vec<&String> = remote.keys().chain(local.keys()).collect();
+    paths.sort();
+    paths.dedup();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let comparison = match (remote.get(path), local.get(path)) {
+                (Some(remote_path), Some(local_path)) => {
+                    match (std::fs::read(remote_path), std::fs::read(local_path)) {
+                        (Ok(remote_bytes), Ok(local_bytes)) => compare_artifact(
+                            &remote_bytes,
+                            &local_bytes,
+                            remote_markers,
+                            local_markers,
+                        ),
+                        (Err(e), _) | (_, Err(e)) => ArtifactComparison::Differs {
+                            detail: format!("unreadable: {}", e),
+                        },
+                    }
+                }
+                (Some(_), None) => ArtifactComparison::OnlyRemote,
+                (None, _) => ArtifactComparison::OnlyLocal,
+            };
+            ArtifactDiff {
+                path: path.clone(),
+                comparison,
+            }
+        })
+        .collect()
+}
+
+/// Run `rch diff-artifacts -- <command>`.
+pub async fn run_diff_artifacts(
+    command_parts: Vec<String>,
+    ctx: &OutputContext,
+) -> anyhow::Result<()> {
+    let command = join_exec_command(&command_parts);
+    let classification = classify_with_user_rules(&command);
+    // Only `cargo build` brings its whole `<profile>/` output back from the
+    // worker; test and bench runs retrieve a narrow allowlist.
+    if classification.kind != Some(CompilationKind::CargoBuild) {
+        anyhow::bail!(
+            "diff-artifacts compares `cargo build` outputs (e.g. cargo build --release), got `{}`",
+            command
+        );
+    }
+
+    let config = load_config()?;
+    let reporter = HookReporter::new(if ctx.is_json() {
+        OutputVisibility::None
+    } else {
+        config.output.visibility
+    });
+    let project_root = std::env::current_dir()?;
+    let scratch = project_root.join("target").join(SCRATCH_DIR);
+    let remote_dir = scratch.join("remote");
+    let local_dir = scratch.join("local");
+    for dir in [&remote_dir, &local_dir] {
+        if dir.exists() {
+            std::fs::remove_dir_all(dir)?;
+        }
+        std::fs::create_dir_all(dir)?;
+    }
+
+    // Both builds run the command without any target-dir setting of its own.
+    let tokens = normalize_exec_command_parts(&command_parts);
+    let build_command = rewrite_cargo_target_dir_command_for_remote(
+        &command,
+        Some(&tokens),
+        Some(&remote_dir),
+        &reporter,
+    );
+
+    let topology_policy = config.path_topology.to_policy();
+    let project = extract_project_name_with_policy(&topology_policy);
+    let toolchain = detect_toolchain(&project_root).ok();
+    let cores =
+        estimate_cores_for_command(classification.kind, &build_command, &config.compilation);
+    // The local build inherits RUSTFLAGS and friends; the remote one must be
+    // compiled with the same flags or every artifact differs.
+    let mut env_allowlist = config.environment.allowlist.clone();
+    match resolve_forwarded_build_env(classification.kind, &env_allowlist, &reporter) {
+        BuildEnvForwarding::Refuse(reason) => {
+            anyhow::bail!("cannot build remotely with the local build env: {}", reason);
+        }
+        BuildEnvForwarding::Forward(forwarded) => {
+            for key in forwarded {
+                if !env_allowlist_contains(&env_allowlist, &key) {
+                    env_allowlist.push(key);
+                }
+            }
+        }
+    }
+    let response = query_daemon(
+        &config.general.socket_path,
+        &project,
+        cores,
+        &build_command,
+        toolchain.as_ref(),
+        required_runtime_for_kind(classification.kind),
+        CommandPriority::Normal,
+        0,
+        Some(std::process::id()),
+        queue_when_busy_enabled(),
+        &preferred_workers_from_env(),
+        config.transfer.fleet.as_deref(),
+        config.transfer.min_free_disk_requirement(),
+        &[],
+        None,
+    )
+    .await?;
+    let Some(worker) = response.worker else {
+        anyhow::bail!("no worker available ({})", response.reason);
+    };
+
+    if !ctx.is_json() {
+        eprintln!(
+            "[RCH] building remotely on {}: {}",
+            worker.id, build_command
+        );
+    }
+    let remote = execute_remote_compilation(
+        &worker,
+        &build_command,
+        config.transfer.clone(),
+        env_allowlist,
+        Some(remote_dir.clone()),
+        &config.compilation,
+        &config.self_healing,
+        toolchain.as_ref(),
+        classification.kind,
+        &reporter,
+        &config.general.socket_path,
+        &config.output,
+        &config.telemetry,
+        response.build_id,
+        &topology_policy,
+        RemoteOutput::Discard,
+    )
+    .await;
+    if let Err(e) = release_worker(
+        &config.general.socket_path,
+        &worker.id,
+        cores,
+        response.build_id,
+        Some(
+            remote
+                .as_ref()
+                .map(|ok| ok.exit_code)
+                .unwrap_or(EXIT_BUILD_ERROR),
+        ),
+        None,
+        remote.as_ref().ok().map(|ok| ok.bytes_transferred),
+        remote.as_ref().ok().map(|ok| &ok.timing),
+        &release_decision_trace(DecisionTrace::new(), &remote),
+        None,
+        None,
+    )
+    .await
+    {
+        warn!("Failed to release worker slots: {}", e);
+    }
+    let remote = remote?;
+    if remote.exit_code != 0 {
+        anyhow::bail!(
+            "remote build failed on {} (exit {}): {}",
+            worker.id,
+            remote.exit_code,
+            remote.stderr.lines().last().unwrap_or_default()
+        );
+    }
+
+    if !ctx.is_json() {
+        eprintln!("[RCH] building locally: {}", build_command);
+    }
+    let local = local_fallback_command(&build_command)
+        .env("CARGO_TARGET_DIR", &local_dir)
+        .stdout(Stdio::null())
+        .output()?;
+    if !local.status.success() {
+        anyhow::bail!(
+            "local build failed (exit {}): {}",
+            local.status.code().unwrap_or(1),
+            String::from_utf8_lossy(&local.stderr)
+                .lines()
+                .last()
+                .unwrap_or_default()
+        );
+    }
+
+    let mut remote_markers = vec![config.transfer.remote_base.clone()];
+    let mut local_markers = vec![
+        project_root.to_string_lossy().to_string(),
+        local_dir.to_string_lossy().to_string(),
+    ];
+    // Home-relative paths only explain a difference when the homes differ.
+    let remote_home = if worker.user == "root" {
+        "/root".to_string()
+    } else {
+        format!("/home/{}", worker.user)
+    };
+    let local_home = dirs::home_dir().map(|home| home.to_string_lossy().into_owned());
+    if local_home.as_deref() != Some(remote_home.as_str()) {
+        remote_markers.extend(home_build_markers(&remote_home));
+        if let Some(home) = local_home.as_deref() {
+            local_markers.extend(home_build_markers(home));
+        }
+    }
+    let artifacts = diff_target_dirs(&remote_dir, &local_dir, &remote_markers, &local_markers);
+
+    let count = |pred: fn(&ArtifactComparison) -> bool| {
+        artifacts
+            .iter()
+            .filter(|artifact| pred(&artifact.comparison))
+            .count()
+    };
+    let report = DiffArtifactsReport {
+        command: build_command,
+        worker: worker.id.to_string(),
+        remote_dir,
+        local_dir,
+        identical: count(|c| *c == ArtifactComparison::Identical),
+        expected_differences: count(|c| matches!(c, ArtifactComparison::ExpectedDifference { .. })),
+        timestamp_differences: count(|c| *c == ArtifactComparison::TimestampOnly),
+        failures: count(ArtifactComparison::is_failure),
+        artifacts,
+    };
+
+    if ctx.is_json() {
+        let _ = ctx.json(&rch_common::ApiResponse::ok("diff-artifacts", &report));
+    } else {
+        print_report(&report, ctx);
+    }
+    if report.failures > 0 {
+        anyhow::bail!(
+            "{} artifact(s) differ between the remote and local builds",
+            report.failures
+        );
+    }
+    if report.artifacts.is_empty() {
+        anyhow::bail!("neither build produced artifacts to compare");
+    }
+    Ok(())
+}
+
+fn print_report(report: &DiffArtifactsReport, ctx: &OutputContext) {
+    let style = ctx.style();
+    for artifact in &report.artifacts {
+        let line = match &artifact.comparison {
+            ArtifactComparison::Identical => {
+                format!("  {} {}", style.success("same    "), artifact.path)
+            }
+            ArtifactComparison::ExpectedDifference { reason } => format!(
+                "  {} {} {}",
+                style.warning("expected"),
+                artifact.path,
+                style.muted(&format!("({})", reason))
+            ),
+            ArtifactComparison::TimestampOnly => format!(
+                "  {} {} {}",
+                style.warning("time    "),
+                artifact.path,
+                style.muted("(header timestamps only)")
+            ),
+            ArtifactComparison::Differs { detail } => format!(
+                "  {} {} {}",
+                style.error("differs "),
+                artifact.path,
+                style.muted(&format!("({})", detail))
+            ),
+            ArtifactComparison::OnlyRemote => {
+                format!("  {} {}", style.error("remote  "), artifact.path)
+            }
+            ArtifactComparison::OnlyLocal => {
+                format!("  {} {}", style.error("local   "), artifact.path)
+            }
+        };
+        println!("{}", line);
+    }
+    println!(
+        "{} identical, {} expected difference(s), {} timestamp-only, {} mismatch(es) [{} vs local]",
+        report.identical,
+        report.expected_differences,
+        report.timestamp_differences,
+        report.failures,
+        report.worker
+    );
+    println!(
+        "Scratch builds kept in {} and {}",
+        report.remote_dir.display(),
+        report.local_dir.display()
+    );
+}
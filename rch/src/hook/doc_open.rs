@@ -0,0 +1,143 @@
+//! `cargo doc --open` support for offloaded doc builds.
+//!
+//! Launching a browser on the worker is meaningless and can hang the remote
+//! build, so [`strip_doc_open_flag`] drops `--open` from the command before it
+//! is sent. Doc builds already retrieve `target/doc/**` with their artifacts;
+//! after a successful build [`open_local_docs`] opens the retrieved crate docs
+//! locally, the way cargo would have. Quiet and machine-output runs skip the
+//! local open ([`local_doc_open_enabled`]).
+
+use super::*;
+
+/// Remove `--open` from a `cargo doc` command.
+///
+/// Returns the rewritten tokens, or `None` when the command is not a doc build
+/// or does not ask to open the docs. Tokens after `--` are left alone.
+pub(super) fn strip_doc_open_flag(
+    kind: Option<CompilationKind>,
+    command_tokens: &[String],
+) -> Option<Vec<String>> {
+    if kind != Some(CompilationKind::CargoDoc) {
+        return None;
+    }
+    let end = command_tokens
+        .iter()
+        .position(|token| token == "--")
+        .unwrap_or(command_tokens.len());
+    if !command_tokens[..end].iter().any(|token| token == "--open") {
+        return None;
+    }
+    Some(
+        command_tokens
+            .iter()
+            .enumerate()
+            .filter(|(idx, token)| *idx >= end || *token != "--open")
+            .map(|(_, token)| token.clone())
+            .collect(),
+    )
+}
+
+/// Whether the retrieved docs should be opened locally.
+///
+/// `output.visibility = "none"` and machine output (`RCH_JSON`,
+/// `RCH_OUTPUT_FORMAT`) stand in for cargo's lack of a `--no-open`.
+pub(super) fn local_doc_open_enabled(
+    visibility: OutputVisibility,
+    lookup_env: impl Fn(&str) -> Option<String>,
+) -> bool {
+    let machine_output = lookup_env("RCH_JSON").is_some_and(|v| !v.is_empty() && v != "0")
+        || lookup_env("RCH_OUTPUT_FORMAT").is_some_and(|v| !v.is_empty());
+    visibility != OutputVisibility::None && !machine_output
+}
+
+/// Index page cargo would open for this doc build.
+///
+/// Prefers the first `-p`/`--package` crate, then the package named in
+/// `manifest_dir/Cargo.toml`, then the first documented crate in `doc_dir`.
+pub(super) fn doc_index_path(
+    doc_dir: &Path,
+    command_tokens: &[String],
+    manifest_dir: &Path,
+) -> Option<PathBuf> {
+    let crate_index = |name: &str| doc_dir.join(name.replace('-', "_")).join("index.html");
+
+    let mut tokens = command_tokens.iter().take_while(|token| *token != "--");
+    while let Some(token) = tokens.next() {
+        let package = match token.as_str() {
+            "-p" | "--package" => tokens.next().map(String::as_str),
+            other => other.strip_prefix("--package="),
+        };
+        if let Some(index) = package.map(crate_index).filter(|index| index.is_file()) {
+            return Some(index);
+        }
+    }
+
+    let manifest_package = std::fs::read_to_string(manifest_dir.join("Cargo.toml"))
+        .ok()
+        .and_then(|contents| contents.parse::<toml::Table>().ok())
+        .and_then(|manifest| {
+            manifest
+                .get("package")?
+                .get("name")?
+                .as_str()
+                .map(str::to_string)
+        });
+    if let Some(index) = manifest_package
+        .as_deref()
+        .map(crate_index)
+        .filter(|index| index.is_file())
+    {
+        return Some(index);
+    }
+
+    let mut documented: Vec<PathBuf> = std::fs::read_dir(doc_dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path().join("index.html"))
+        .filter(|index| index.is_file())
+        .collect();
+    documented.sort();
+    documented.into_iter().next()
+}
+
+/// Open the retrieved docs in the local browser.
+///
+/// Honors `BROWSER` like cargo does, otherwise uses the platform opener.
+/// Failures are reported, never fatal: the build itself already succeeded.
+pub(super) fn open_local_docs(
+    target_dir: &Path,
+    command_tokens: &[String],
+    manifest_dir: &Path,
+    reporter: &HookReporter,
+) {
+    let doc_dir = target_dir.join("doc");
+    let Some(index) = doc_index_path(&doc_dir, command_tokens, manifest_dir) else {
+        reporter.summary(&format!(
+            "[RCH] no retrieved docs to open under {}",
+            doc_dir.display()
+        ));
+        return;
+    };
+
+    reporter.verbose(&format!("[RCH] opening {}", index.display()));
+    let mut opener = match std::env::var("BROWSER") {
+        Ok(browser) if !browser.trim().is_empty() => std::process::Command::new(browser.trim()),
+        _ if cfg!(target_os = "macos") => std::process::Command::new("open"),
+        _ if cfg!(target_os = "windows") => {
+            let mut cmd = std::process::Command::new("cmd");
+            cmd.args(["/C", "start", ""]);
+            cmd
+        }
+        _ => std::process::Command::new("xdg-open"),
+    };
+    if let Err(e) = opener
+        .arg(&index)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        warn!("Failed to open {}: {}", index.display(), e);
+        reporter.summary(&format!("[RCH] docs at {}", index.display()));
+    }
+}
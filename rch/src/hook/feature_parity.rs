@@ -0,0 +1,86 @@
+//! Cargo feature parity between the local command and the remote build.
+//!
+//! Feature flags on the command line (`--features`, `-F`,
+//! `--no-default-features`, `--all-features`) reach the worker verbatim,
+//! because the whole command string is sent. What can still diverge is the
+//! environment: `CARGO_ENCODED_RUSTFLAGS` with a `--cfg feature="x"`, a
+//! `CARGO_UNSTABLE_*` switch, or `CARGO_BUILD_TARGET` changes the compiled
+//! feature set, but only variables on `environment.allowlist` are forwarded.
+//! [`unforwarded_feature_env_vars`] finds the ones that are set locally and
+//! would be dropped, so `execute_remote_compilation` can warn before the
+//! builds disagree. `RUSTFLAGS` and the rest of [`BUILD_AFFECTING_ENV_VARS`]
+//! are stricter: they keep the build local instead (see
+//! `resolve_forwarded_build_env`).
+use super::cargo_target_dir::{BUILD_AFFECTING_ENV_VARS, env_allowlist_contains};
+use super::command_parsing::tokenize_command;
+
+/// Variables that change cargo's resolved features or `cfg` set.
+const FEATURE_ENV_VARS: [&str; 3] = [
+    "CARGO_ENCODED_RUSTFLAGS",
+    "CARGO_BUILD_RUSTFLAGS",
+    "CARGO_BUILD_TARGET",
+];
+
+/// Prefixes of variable families that change the feature or `cfg` set
+/// (`-Z` switches and the feature resolver).
+const FEATURE_ENV_PREFIXES: [&str; 2] = ["CARGO_UNSTABLE_", "CARGO_RESOLVER_"];
+
+/// Whether `name` can change the feature set a cargo build compiles with.
+/// `CARGO_TARGET_<triple>_RUSTFLAGS` counts; other `CARGO_TARGET_*` keys
+/// (linker, runner) do not, and neither do [`BUILD_AFFECTING_ENV_VARS`],
+/// which are gated separately.
+pub(super) fn is_feature_affecting_env_var(name: &str) -> bool {
+    if BUILD_AFFECTING_ENV_VARS.contains(&name) {
+        return false;
+    }
+    FEATURE_ENV_VARS.contains(&name)
+        || FEATURE_ENV_PREFIXES
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+        || (name.starts_with("CARGO_TARGET_") && name.ends_with("_RUSTFLAGS"))
+}
+
+/// Feature-affecting variables set (non-empty) in `env` that the allowlist
+/// does not forward, sorted.
+pub(super) fn unforwarded_feature_env_vars(
+    env: impl IntoIterator<Item = (String, String)>,
+    env_allowlist: &[String],
+) -> Vec<String> {
+    let mut names: Vec<String> = env
+        .into_iter()
+        .filter(|(name, value)| {
+            !value.is_empty()
+                && is_feature_affecting_env_var(name)
+                && !env_allowlist_contains(env_allowlist, name)
+        })
+        .map(|(name, _)| name)
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// The feature flags of a cargo command as written, before any `--`
+/// separator (arguments after it belong to the test binary).
+pub(super) fn explicit_feature_flags(command: &str) -> Vec<String> {
+    let mut flags = Vec::new();
+    let words = tokenize_command(command);
+    let mut tokens = words
+        .iter()
+        .map(String::as_str)
+        .take_while(|token| *token != "--");
+    while let Some(token) = tokens.next() {
+        match token {
+            "--features" | "-F" => match tokens.next() {
+                Some(value) => flags.push(format!("{token} {value}")),
+                None => flags.push(token.to_string()),
+            },
+            "--all-features" | "--no-default-features" => flags.push(token.to_string()),
+            _ if token.starts_with("--features=") || token.starts_with("-F=") => {
+                flags.push(token.to_string());
+            }
+            _ => {}
+        }
+    }
+    flags
+}
@@ -131,6 +131,8 @@ pub(super) fn render_compile_summary(
     exec_ms: u64,
     artifacts: Option<&SyncResult>,
     artifacts_failed: bool,
+    artifacts_skipped_for: Option<CompilationKind>,
+    tests: Option<&TestSummary>,
     cache_hit: bool,
     success: bool,
 ) {
@@ -144,11 +146,15 @@ pub(super) fn render_compile_summary(
     let total_duration = format_duration_ms(Duration::from_millis(total_ms));
 
     let sync_bytes = format_bytes(sync.bytes_transferred);
-    let sync_speed = format_speed(sync.bytes_transferred, sync.duration_ms);
+    let sync_speed =
+        with_compression_ratio(format_speed(sync.bytes_transferred, sync.duration_ms), sync);
 
     let (artifact_line, artifact_duration) = if let Some(artifact) = artifacts {
         let bytes = format_bytes(artifact.bytes_transferred);
-        let speed = format_speed(artifact.bytes_transferred, artifact.duration_ms);
+        let speed = with_compression_ratio(
+            format_speed(artifact.bytes_transferred, artifact.duration_ms),
+            artifact,
+        );
         let duration = format_duration_ms(Duration::from_millis(artifact.duration_ms));
         (
             format!(
@@ -162,6 +168,11 @@ pub(super) fn render_compile_summary(
         )
     } else if artifacts_failed {
         ("Artifacts: failed".to_string(), "--".to_string())
+    } else if let Some(kind) = artifacts_skipped_for {
+        (
+            format!("Artifacts: skipped ({})", kind.as_str()),
+            "skipped".to_string(),
+        )
     } else {
         ("Artifacts: skipped".to_string(), "--".to_string())
     };
@@ -192,14 +203,34 @@ pub(super) fn render_compile_summary(
         sync_speed
     );
     let compile_line = format!("{} Compile: {}", Icons::compile(ctx), exec_duration);
+    let tests_line = tests.map(|tests| {
+        let icon = if tests.failed == 0 {
+            Icons::check(ctx)
+        } else {
+            Icons::cross(ctx)
+        };
+        format!("{} Tests: {}", icon, tests)
+    });
 
     let cache_text = if cache_hit { "HIT" } else { "MISS" };
     let cache_line_plain = format!("{} Cache: {}", Icons::transfer(ctx), cache_text);
 
-    let content_plain = format!(
-        "{}\n{}\n{}\n{}\n{}\n{}",
-        worker_line, timing_line, sync_line, compile_line, artifact_line, cache_line_plain
-    );
+    let lines_with_cache = |cache_line: &str| {
+        [
+            Some(worker_line.as_str()),
+            Some(timing_line.as_str()),
+            Some(sync_line.as_str()),
+            Some(compile_line.as_str()),
+            tests_line.as_deref(),
+            Some(artifact_line.as_str()),
+            Some(cache_line),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("\n")
+    };
+    let content_plain = lines_with_cache(&cache_line_plain);
 
     #[cfg(all(feature = "rich-ui", unix))]
     if console.is_rich() {
@@ -209,10 +240,7 @@ pub(super) fn render_compile_summary(
             format!("[bold {}]MISS[/]", RchTheme::WARNING)
         };
         let cache_line = format!("{} Cache: {}", Icons::transfer(ctx), cache_rich);
-        let content = format!(
-            "{}\n{}\n{}\n{}\n{}\n{}",
-            worker_line, timing_line, sync_line, compile_line, artifact_line, cache_line
-        );
+        let content = lines_with_cache(&cache_line);
         let title = if success {
             "Compilation Complete"
         } else {
@@ -234,6 +262,15 @@ pub(super) fn render_compile_summary(
     console.print_plain(&content_plain);
 }
 
+/// Append the achieved compression ratio (e.g. `, 3.2x compressed`) to a
+/// transfer's speed when rsync reported both raw and wire bytes.
+pub(super) fn with_compression_ratio(speed: String, transfer: &SyncResult) -> String {
+    match transfer.compression_ratio() {
+        Some(ratio) => format!("{speed}, {ratio:.1}x compressed"),
+        None => speed,
+    }
+}
+
 #[allow(dead_code)] // May be used for timing estimates in run_exec
 pub(super) fn estimate_local_time_ms(remote_ms: u64, worker_speed_score: f64) -> Option<u64> {
     if remote_ms == 0 || worker_speed_score <= 0.0 {
@@ -0,0 +1,285 @@
+//! Optional persistent hook server (`rch hook-server`).
+//!
+//! Every PreToolUse invocation normally starts a fresh `rch` that loads the
+//! config and classifier rules before it can answer. With a hook server
+//! running, [`forward_to_hook_server`] hands the raw hook input to it over a
+//! Unix socket and the hook prints whatever the server answers, so the
+//! decision is made by one long-lived process whose config cache, user rules
+//! and allocations stay warm.
+//!
+//! Wire format: one JSON line each way. The request carries the client's
+//! version, its working directory (project config and classifier rules are
+//! resolved from it) and the raw hook input; the response carries the exact
+//! JSON the hook should print, or nothing for "allow unchanged". The server
+//! answers one request at a time because it switches its own working
+//! directory for each, and decisions come from the server's environment, not
+//! the agent's.
+//!
+//! The hook handles the request itself whenever the socket is missing,
+//! refuses the connection, answers too slowly, or reports a version mismatch,
+//! so stopping the server is always safe. A running server does decide with
+//! its own environment: `RCH_*` overrides exported after it started are not
+//! seen until it is restarted.
+//!
+//! The hook only trusts a socket owned by its own user with no group or
+//! other permissions, and only when the process on the other end runs as
+//! that user too. The default socket never lives in a shared directory such
+//! as `/tmp`; without a per-user directory there is no default socket.
+
+use super::*;
+
+use anyhow::Context;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+
+/// Overrides the hook server socket path for both the server and the hook.
+pub(crate) const HOOK_SERVER_SOCKET_ENV: &str = "RCH_HOOK_SERVER_SOCKET";
+
+/// How long the hook waits for the server before answering on its own.
+const HOOK_SERVER_CLIENT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// How long the server waits for a connected client to send its request.
+const HOOK_SERVER_READ_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Same cap as the hook's own stdin read.
+const MAX_HOOK_REQUEST_BYTES: u64 = 10 * 1024 * 1024;
+
+/// One hook invocation forwarded to the server.
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct HookServerRequest {
+    /// `rch` version of the forwarding hook; must match the server's.
+    pub version: String,
+    /// Working directory of the forwarding hook.
+    pub cwd: PathBuf,
+    /// Hook input exactly as read from stdin.
+    pub input: String,
+}
+
+/// The server's answer to a [`HookServerRequest`].
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(super) struct HookServerResponse {
+    /// JSON line the hook prints; `None` allows the command unchanged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    /// Set when the server could not answer; the hook then answers itself.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl HookServerResponse {
+    fn error(message: String) -> Self {
+        Self {
+            output: None,
+            error: Some(message),
+        }
+    }
+}
+
+/// Socket the hook server listens on: `RCH_HOOK_SERVER_SOCKET`, else
+/// `rch-hook.sock` in `$XDG_RUNTIME_DIR` or `~/.cache/rch`. `None` when
+/// neither per-user directory is available.
+pub(crate) fn hook_server_socket_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os(HOOK_SERVER_SOCKET_ENV).filter(|path| !path.is_empty()) {
+        return Some(PathBuf::from(path));
+    }
+    if let Some(runtime_dir) = std::env::var_os("XDG_RUNTIME_DIR").filter(|dir| !dir.is_empty()) {
+        return Some(PathBuf::from(runtime_dir).join("rch-hook.sock"));
+    }
+    dirs::cache_dir().map(|dir| dir.join("rch").join("rch-hook.sock"))
+}
+
+/// Uid this process runs as, read back from a connected socket pair.
+fn current_uid() -> Option<u32> {
+    let (ours, _) = UnixStream::pair().ok()?;
+    Some(ours.peer_cred().ok()?.uid())
+}
+
+/// Whether `path` is a socket owned by `uid` that no other user can open.
+pub(super) fn socket_is_private(path: &Path, uid: u32) -> bool {
+    use std::os::unix::fs::{FileTypeExt, MetadataExt};
+    std::fs::symlink_metadata(path).is_ok_and(|meta| {
+        meta.file_type().is_socket() && meta.uid() == uid && meta.mode() & 0o077 == 0
+    })
+}
+
+/// Answer one forwarded hook invocation the way `run_hook` would.
+pub(super) async fn answer_hook_request(request: HookServerRequest) -> HookServerResponse {
+    let version = env!("CARGO_PKG_VERSION");
+    if request.version != version {
+        return HookServerResponse::error(format!(
+            "version mismatch: server {}, hook {}",
+            version, request.version
+        ));
+    }
+    if let Err(e) = std::env::set_current_dir(&request.cwd) {
+        return HookServerResponse::error(format!("cannot enter {}: {}", request.cwd.display(), e));
+    }
+
+    let input = request.input.trim();
+    if input.is_empty() {
+        return HookServerResponse::default();
+    }
+    let hook_input: HookInput = match serde_json::from_str(input) {
+        Ok(hook_input) => hook_input,
+        Err(e) => {
+            warn!("Failed to parse forwarded hook input: {}", e);
+            return HookServerResponse::default();
+        }
+    };
+    match hook_output_json(&process_hook(hook_input).await) {
+        Ok(output) => HookServerResponse {
+            output,
+            error: None,
+        },
+        Err(e) => {
+            warn!(target: "rch::hook", error = %e, "JSON serialization failed; falling open");
+            HookServerResponse::default()
+        }
+    }
+}
+
+/// Forward raw hook input to a running hook server and return the JSON line
+/// to print (`Some(None)` allows unchanged). `None` means no usable server
+/// answered and the caller must handle the request itself.
+pub(super) async fn forward_to_hook_server(input: &str) -> Option<Option<String>> {
+    forward_to_socket(&hook_server_socket_path()?, input).await
+}
+
+/// [`forward_to_hook_server`] against an explicit socket path.
+pub(super) async fn forward_to_socket(path: &Path, input: &str) -> Option<Option<String>> {
+    if !path.exists() {
+        return None;
+    }
+    let uid = current_uid()?;
+    if !socket_is_private(path, uid) {
+        warn!(
+            "Ignoring hook server socket {}: not a private socket owned by this user",
+            path.display()
+        );
+        return None;
+    }
+    let request = HookServerRequest {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        cwd: std::env::current_dir().ok()?,
+        input: input.to_string(),
+    };
+    let mut line = serde_json::to_string(&request).ok()?;
+    line.push('\n');
+
+    let exchange = async {
+        let mut stream = UnixStream::connect(path).await?;
+        let peer_uid = stream.peer_cred()?.uid();
+        if peer_uid != uid {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                format!("server runs as uid {peer_uid}, not {uid}"),
+            ));
+        }
+        stream.write_all(line.as_bytes()).await?;
+        let mut reply = String::new();
+        BufReader::new(stream).read_line(&mut reply).await?;
+        Ok::<_, std::io::Error>(reply)
+    };
+    let reply = match timeout(HOOK_SERVER_CLIENT_TIMEOUT, exchange).await {
+        Ok(Ok(reply)) => reply,
+        Ok(Err(e)) => {
+            debug!("Hook server at {} unavailable: {}", path.display(), e);
+            return None;
+        }
+        Err(_) => {
+            debug!("Hook server at {} timed out", path.display());
+            return None;
+        }
+    };
+    let response: HookServerResponse = match serde_json::from_str(&reply) {
+        Ok(response) => response,
+        Err(e) => {
+            debug!("Malformed hook server response: {}", e);
+            return None;
+        }
+    };
+    if let Some(error) = response.error {
+        debug!("Hook server declined request: {}", error);
+        return None;
+    }
+    Some(response.output)
+}
+
+/// Run `rch hook-server` until interrupted.
+pub async fn run_hook_server(socket: Option<PathBuf>) -> anyhow::Result<()> {
+    let path = socket.or_else(hook_server_socket_path).context(
+        "no per-user directory for the hook server socket; pass --socket or set RCH_HOOK_SERVER_SOCKET",
+    )?;
+    if path.exists() {
+        if UnixStream::connect(&path).await.is_ok() {
+            anyhow::bail!("a hook server is already listening on {}", path.display());
+        }
+        std::fs::remove_file(&path)
+            .with_context(|| format!("Failed to remove stale socket {}", path.display()))?;
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let listener = UnixListener::bind(&path)
+        .with_context(|| format!("Failed to bind hook server socket {}", path.display()))?;
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))
+            .with_context(|| format!("Failed to restrict {}", path.display()))?;
+    }
+    info!("Hook server listening on {}", path.display());
+    eprintln!("rch hook-server listening on {}", path.display());
+
+    let mut terminate = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())?;
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => {
+                    // A panic while answering ends that request (the hook falls
+                    // back to answering itself), not the server.
+                    match tokio::spawn(serve_hook_connection(stream)).await {
+                        Ok(Ok(())) => {}
+                        Ok(Err(e)) => debug!("Hook server connection failed: {}", e),
+                        Err(e) => warn!("Hook server request panicked: {}", e),
+                    }
+                }
+                Err(e) => warn!("Hook server accept failed: {}", e),
+            },
+            _ = tokio::signal::ctrl_c() => break,
+            _ = terminate.recv() => break,
+        }
+    }
+
+    if let Err(e) = std::fs::remove_file(&path) {
+        debug!(
+            "Failed to remove hook server socket {}: {}",
+            path.display(),
+            e
+        );
+    }
+    info!("Hook server stopped");
+    Ok(())
+}
+
+/// Read one request from `stream`, answer it, and close the connection.
+pub(super) async fn serve_hook_connection(stream: UnixStream) -> anyhow::Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut line = String::new();
+    timeout(
+        HOOK_SERVER_READ_TIMEOUT,
+        BufReader::new(reader.take(MAX_HOOK_REQUEST_BYTES)).read_line(&mut line),
+    )
+    .await
+    .context("hook request timed out")??;
+
+    let response = match serde_json::from_str::<HookServerRequest>(&line) {
+        Ok(request) => answer_hook_request(request).await,
+        Err(e) => HookServerResponse::error(format!("malformed request: {}", e)),
+    };
+    let mut reply = serde_json::to_string(&response)?;
+    reply.push('\n');
+    writer.write_all(reply.as_bytes()).await?;
+    writer.shutdown().await?;
+    Ok(())
+}
@@ -0,0 +1,170 @@
+//! Opt-in "confirm before first offload" gate (`general.confirm_first_offload`).
+//!
+//! A project counts as never offloaded while the timing history holds no
+//! remote sample for it. For such a project `run_exec` asks on the
+//! controlling terminal (`/dev/tty`) once a worker has been selected; a "no"
+//! releases the worker, runs the build locally, and records a per-project
+//! opt-out in `~/.cache/rch/offload_consent.json` so later builds of that
+//! project stay local without asking again.
+//!
+//! The gate fails closed: whenever nobody can answer the prompt — stdout is
+//! not a TTY, machine (JSON) or quiet output is active, `CI` is set, or
+//! `/dev/tty` cannot be opened or read — the build runs locally. No opt-out
+//! is recorded in that case, so the next interactive build still asks.
+use super::*;
+
+use super::timing_history::timing_cache;
+use crate::ui::context::OutputContext;
+use std::collections::BTreeSet;
+use std::io::BufRead;
+
+/// Projects whose first offload the user declined.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(super) struct OffloadConsent {
+    /// Project ids (as reported by the hook) that stay local.
+    #[serde(default)]
+    pub declined: BTreeSet<String>,
+}
+
+impl OffloadConsent {
+    /// Load the opt-out store. Returns an empty store on any error.
+    pub(super) fn load() -> Self {
+        offload_consent_path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Whether the user declined offloading `project`.
+    pub(super) fn is_declined(&self, project: &str) -> bool {
+        self.declined.contains(project)
+    }
+
+    /// Persist an opt-out for `project`. Logs and continues on I/O errors.
+    pub(super) fn record_decline(project: &str) {
+        let Some(path) = offload_consent_path() else {
+            return;
+        };
+        let mut consent = Self::load();
+        if !consent.declined.insert(project.to_string()) {
+            return;
+        }
+        if let Some(parent) = path.parent()
+            && let Err(e) = std::fs::create_dir_all(parent)
+        {
+            warn!(
+                "Failed to create offload consent directory {}: {}",
+                parent.display(),
+                e
+            );
+            return;
+        }
+        let content = match serde_json::to_string_pretty(&consent) {
+            Ok(content) => content,
+            Err(e) => {
+                warn!("Failed to serialize offload consent: {}", e);
+                return;
+            }
+        };
+        let temp_path = path.with_extension("tmp");
+        if let Err(e) =
+            std::fs::write(&temp_path, content).and_then(|()| std::fs::rename(&temp_path, &path))
+        {
+            warn!(
+                "Failed to write offload consent to {}: {}",
+                path.display(),
+                e
+            );
+        }
+    }
+}
+
+/// Get the path to the offload opt-out store.
+fn offload_consent_path() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("rch").join("offload_consent.json"))
+}
+
+/// Whether `project` has never completed a remote build on this machine.
+pub(super) fn is_first_offload(project: &str) -> bool {
+    timing_cache()
+        .read()
+        .map(|history| !history.has_remote_samples(project))
+        .unwrap_or(false)
+}
+
+/// Whether an interactive prompt can be answered in this context.
+pub(super) fn prompt_allowed(ctx: &OutputContext, ci: bool) -> bool {
+    ctx.is_tty() && !ctx.is_json() && !ctx.is_quiet() && !ci
+}
+
+/// Outcome of the first-offload prompt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ConsentAnswer {
+    /// The user agreed to offload.
+    Accepted,
+    /// The user declined; remembered for the project.
+    Declined,
+    /// Nobody could answer; the build stays local this time only.
+    Unanswered,
+}
+
+/// Interpret a prompt answer; anything but an explicit yes declines.
+pub(super) fn parse_consent_answer(answer: &str) -> bool {
+    matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes")
+}
+
+/// Ask whether to offload `project` to `worker` on `/dev/tty`.
+///
+/// Returns [`ConsentAnswer::Unanswered`] without prompting when nobody can
+/// answer in this context, so enabling the gate never hangs a
+/// non-interactive build and never offloads one unconfirmed.
+pub(super) fn confirm_offload(project: &str, worker: &SelectedWorker) -> ConsentAnswer {
+    let ctx = crate::ui::context::default_context();
+    let question = format!(
+        "Offload {} to {}@{}? [y/N] ",
+        project, worker.id, worker.host
+    );
+    confirm_offload_with(
+        &ctx,
+        std::env::var_os("CI").is_some(),
+        &question,
+        ask_on_tty,
+    )
+}
+
+/// [`confirm_offload`] with the context and terminal exchange injected.
+pub(super) fn confirm_offload_with(
+    ctx: &OutputContext,
+    ci: bool,
+    question: &str,
+    ask: impl FnOnce(&str) -> io::Result<String>,
+) -> ConsentAnswer {
+    if !prompt_allowed(ctx, ci) {
+        debug!("confirm_first_offload: non-interactive context, keeping build local");
+        return ConsentAnswer::Unanswered;
+    }
+    match ask(question) {
+        Ok(answer) if parse_consent_answer(&answer) => ConsentAnswer::Accepted,
+        Ok(_) => ConsentAnswer::Declined,
+        Err(e) => {
+            debug!("confirm_first_offload: no answer from terminal ({})", e);
+            ConsentAnswer::Unanswered
+        }
+    }
+}
+
+/// Write `question` to the controlling terminal and read one line back.
+fn ask_on_tty(question: &str) -> io::Result<String> {
+    let tty = OpenOptions::new().read(true).write(true).open("/dev/tty")?;
+    let mut writer = &tty;
+    writer.write_all(question.as_bytes())?;
+    writer.flush()?;
+    let mut answer = String::new();
+    if io::BufReader::new(&tty).read_line(&mut answer)? == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "terminal closed",
+        ));
+    }
+    Ok(answer)
+}
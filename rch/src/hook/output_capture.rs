@@ -0,0 +1,205 @@
+//! Bounded capture of remote build output with on-disk overflow.
+//!
+//! When the compile-progress UI is active, remote output is not streamed to
+//! the terminal; it is captured so it can be replayed if the build fails.
+//! [`OutputCapture`] keeps the first `output.max_memory_bytes` in memory and,
+//! once that threshold is crossed, spills the whole log to a file under
+//! [`build_log_dir`] so long runs (e.g. clippy) keep their tail. Spill files
+//! are removed after a successful build unless `output.keep_logs` is set, and
+//! `rch logs` lists them via [`build_logs`] (`--last` prints the newest).
+//! Retained logs are pruned whenever a new one is spilled: anything older than
+//! [`BUILD_LOG_MAX_AGE`] goes, and at most [`MAX_BUILD_LOGS`] are kept.
+
+use super::*;
+
+/// Most spilled build logs kept on disk; older ones are pruned.
+pub(super) const MAX_BUILD_LOGS: usize = 20;
+
+/// Spilled build logs older than this are pruned.
+pub(super) const BUILD_LOG_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Directory holding spilled build logs (`~/.cache/rch/logs`).
+pub(crate) fn build_log_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("rch").join("logs"))
+}
+
+/// Spilled build logs, newest first.
+pub(crate) fn build_logs() -> Vec<PathBuf> {
+    build_log_dir()
+        .map(|dir| build_logs_in(&dir))
+        .unwrap_or_default()
+}
+
+/// Most recently modified spilled build log, if any.
+pub(crate) fn latest_build_log() -> Option<PathBuf> {
+    build_logs().into_iter().next()
+}
+
+pub(super) fn build_logs_in(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut logs: Vec<(SystemTime, PathBuf)> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "log"))
+        .filter_map(|entry| {
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some((modified, entry.path()))
+        })
+        .collect();
+    logs.sort_by(|a, b| b.cmp(a));
+    logs.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Remove spilled build logs in `dir` that are older than `max_age`, then all
+/// but the newest `max_count`. Only `build-*.log` files are touched. Returns
+/// the number of logs removed.
+pub(super) fn prune_build_logs_in(dir: &Path, max_count: usize, max_age: Duration) -> usize {
+    let now = SystemTime::now();
+    let mut removed = 0;
+    let logs = build_logs_in(dir).into_iter().filter(|path| {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with("build-"))
+    });
+    for (index, path) in logs.enumerate() {
+        let expired = std::fs::metadata(&path)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+            .is_some_and(|age| age > max_age);
+        if (index >= max_count || expired) && std::fs::remove_file(&path).is_ok() {
+            removed += 1;
+        }
+    }
+    removed
+}
+
+/// Captured remote output: an in-memory head plus an optional spill file.
+pub(super) struct OutputCapture {
+    head: String,
+    max_memory_bytes: usize,
+    spill_dir: Option<PathBuf>,
+    spill: Option<(PathBuf, std::fs::File)>,
+    /// Set when spilling was needed but the file could not be written; the
+    /// capture then degrades to the old truncating behavior.
+    truncated: bool,
+}
+
+impl OutputCapture {
+    pub(super) fn new(max_memory_bytes: usize, spill_dir: Option<PathBuf>) -> Self {
+        Self {
+            head: String::new(),
+            max_memory_bytes,
+            spill_dir,
+            spill: None,
+            truncated: false,
+        }
+    }
+
+    /// Append one output line (including its trailing newline, if any).
+    pub(super) fn push(&mut self, line: &str) {
+        if let Some((_, file)) = self.spill.as_mut() {
+            if file.write_all(line.as_bytes()).is_err() {
+                self.truncated = true;
+            }
+            return;
+        }
+        if self.truncated {
+            return;
+        }
+        if self.head.len() + line.len() <= self.max_memory_bytes {
+            self.head.push_str(line);
+            return;
+        }
+        match self.open_spill() {
+            Some((path, mut file)) => {
+                let written = file
+                    .write_all(self.head.as_bytes())
+                    .and_then(|()| file.write_all(line.as_bytes()));
+                if let Err(e) = written {
+                    warn!("Failed to spill build output to {}: {}", path.display(), e);
+                    let _ = std::fs::remove_file(&path);
+                    self.truncated = true;
+                } else {
+                    self.spill = Some((path, file));
+                }
+            }
+            None => self.truncated = true,
+        }
+    }
+
+    fn open_spill(&self) -> Option<(PathBuf, std::fs::File)> {
+        let dir = self.spill_dir.as_ref()?;
+        std::fs::create_dir_all(dir).ok()?;
+        let stamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or(0);
+        let path = dir.join(format!("build-{}-{}.log", stamp, std::process::id()));
+        match std::fs::File::create(&path) {
+            Ok(file) => {
+                // The new log is the newest, so pruning never removes it.
+                let pruned = prune_build_logs_in(dir, MAX_BUILD_LOGS, BUILD_LOG_MAX_AGE);
+                if pruned > 0 {
+                    debug!("Pruned {} old build log(s) from {}", pruned, dir.display());
+                }
+                Some((path, file))
+            }
+            Err(e) => {
+                warn!("Failed to create build log {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    /// The in-memory head of the output (always the start of the log).
+    pub(super) fn head(&self) -> &str {
+        &self.head
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.head.is_empty()
+    }
+
+    /// Print the full captured output to stderr.
+    pub(super) fn replay_to_stderr(&mut self) {
+        match self.spill.as_mut() {
+            Some((path, file)) => {
+                let _ = file.flush();
+                match std::fs::File::open(&*path) {
+                    Ok(mut log) => {
+                        let _ = io::copy(&mut log, &mut io::stderr());
+                    }
+                    Err(e) => {
+                        warn!("Failed to read build log {}: {}", path.display(), e);
+                        eprintln!("{}", self.head);
+                    }
+                }
+                eprintln!("[RCH] full build log: {}", path.display());
+            }
+            None => {
+                eprintln!("{}", self.head);
+                if self.truncated {
+                    eprintln!(
+                        "[RCH] output truncated (could not spill log; raise output.max_memory_bytes)"
+                    );
+                }
+            }
+        }
+    }
+
+    /// Close the capture. The spill file is removed after a successful build
+    /// unless `keep_logs` is set; the path of a retained log is returned.
+    pub(super) fn finish(&mut self, success: bool, keep_logs: bool) -> Option<PathBuf> {
+        let (path, file) = self.spill.take()?;
+        drop(file);
+        if success && !keep_logs {
+            if let Err(e) = std::fs::remove_file(&path) {
+                warn!("Failed to remove build log {}: {}", path.display(), e);
+            }
+            return None;
+        }
+        Some(path)
+    }
+}
@@ -0,0 +1,225 @@
+//! End-to-end pipeline smoke test (`rch selftest`).
+//!
+//! Unlike `rch self-test`, which asks the daemon to build a canary on each
+//! worker, this drives the client side of a real offload. The benchmark
+//! reference project is generated in a throwaway directory under the canonical
+//! project root, and a worker is selected through the daemon. The daemon is
+//! auto-started first when `self_healing.hook_starts_daemon` allows it. The
+//! project's `cargo build` then runs through [`execute_remote_compilation`],
+//! exactly as a hooked build would.
+//!
+//! The run passes when the remote exit code is 0 and the binary came back to
+//! the local `target/debug`. The local project is removed afterwards; the
+//! worker's copy is left to the worker cache cleanup.
+use super::*;
+
+use anyhow::Context;
+use rch_telemetry::benchmarks::{BENCHMARK_BINARY_NAME, setup_benchmark_project};
+
+/// Command the smoke test runs on the worker.
+const SELFTEST_COMMAND: &str = "cargo build";
+
+/// Outcome of one `rch selftest` run.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct PipelineSelftestReport {
+    pub worker_id: String,
+    pub worker_host: String,
+    pub build_id: Option<u64>,
+    pub exit_code: i32,
+    /// Local path the artifact was expected at (removed with the project).
+    pub artifact: PathBuf,
+    pub artifact_retrieved: bool,
+    pub timing: CommandTimingBreakdown,
+}
+
+impl PipelineSelftestReport {
+    /// Whether the remote build succeeded and its artifact came back.
+    pub(crate) fn passed(&self) -> bool {
+        self.exit_code == 0 && self.artifact_retrieved
+    }
+}
+
+/// Throwaway project directory, removed on drop.
+struct SelftestDir(PathBuf);
+
+impl Drop for SelftestDir {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_dir_all(&self.0) {
+            warn!(
+                "Failed to remove self-test project {}: {}",
+                self.0.display(),
+                e
+            );
+        }
+    }
+}
+
+/// Restores the working directory on drop.
+struct CurrentDirGuard(PathBuf);
+
+impl Drop for CurrentDirGuard {
+    fn drop(&mut self) {
+        if let Err(e) = std::env::set_current_dir(&self.0) {
+            warn!(
+                "Failed to restore working directory {}: {}",
+                self.0.display(),
+                e
+            );
+        }
+    }
+}
+
+/// Directory the throwaway project is generated in.
+///
+/// It has to live under the canonical root, or path normalization refuses to
+/// offload it.
+pub(super) fn selftest_project_base(policy: &PathTopologyPolicy, pid: u32) -> PathBuf {
+    policy
+        .canonical_root()
+        .join(format!(".rch-selftest-{}", pid))
+}
+
+/// Run the end-to-end smoke test and report what happened.
+///
+/// Errors are setup or pipeline failures (no daemon, no worker, sync or SSH
+/// errors); a build that ran but failed is reported, not returned as an error.
+pub(crate) async fn run_pipeline_selftest() -> anyhow::Result<PipelineSelftestReport> {
+    let total_start = Instant::now();
+    let config = load_config()?;
+    let topology_policy = config.path_topology.to_policy();
+    let canonical_root = topology_policy.canonical_root();
+    if !canonical_root.is_dir() {
+        anyhow::bail!(
+            "Canonical project root {} does not exist (set path_topology.canonical_root)",
+            canonical_root.display()
+        );
+    }
+
+    let base = selftest_project_base(&topology_policy, std::process::id());
+    std::fs::create_dir(&base)
+        .with_context(|| format!("Failed to create self-test directory {}", base.display()))?;
+    let base = SelftestDir(base);
+    let project_dir = setup_benchmark_project(&base.0)
+        .map_err(|e| anyhow::anyhow!("Failed to generate self-test project: {}", e.message))?;
+    let _cwd = CurrentDirGuard(std::env::current_dir()?);
+    std::env::set_current_dir(&project_dir)?;
+
+    let kind = Some(CompilationKind::CargoBuild);
+    let project = extract_project_name_with_policy(&topology_policy);
+    let cores = estimate_cores_for_command(kind, SELFTEST_COMMAND, &config.compilation);
+    let toolchain = detect_toolchain(&project_dir).ok();
+    let socket_path = config.general.socket_path.clone();
+    let preferred_workers = preferred_workers_from_env();
+    let query = || {
+        query_daemon(
+            &socket_path,
+            &project,
+            cores,
+            SELFTEST_COMMAND,
+            toolchain.as_ref(),
+            RequiredRuntime::Rust,
+            CommandPriority::Normal,
+            0,
+            Some(std::process::id()),
+            false,
+            &preferred_workers,
+            config.transfer.fleet.as_deref(),
+            config.transfer.min_free_disk_requirement(),
+            &[],
+            None,
+        )
+    };
+
+    let select_start = Instant::now();
+    let response = match query().await {
+        Ok(response) => response,
+        Err(e) => {
+            debug!(
+                "Self-test daemon query failed: {}, attempting auto-start",
+                e
+            );
+            auto_start::try_auto_start_daemon(&config.self_healing, Path::new(&socket_path))
+                .await
+                .map_err(|start| {
+                    anyhow::anyhow!(
+                        "Daemon unreachable ({}) and auto-start failed: {}",
+                        e,
+                        start
+                    )
+                })?;
+            query().await?
+        }
+    };
+    let select = select_start.elapsed();
+    let Some(worker) = response.worker else {
+        anyhow::bail!("No worker available ({})", response.reason);
+    };
+
+    let reporter = HookReporter::new(config.output.visibility);
+    let remote_start = Instant::now();
+    let result = execute_remote_compilation(
+        &worker,
+        SELFTEST_COMMAND,
+        config.transfer.clone(),
+        config.environment.allowlist.clone(),
+        None,
+        &config.compilation,
+        &config.self_healing,
+        toolchain.as_ref(),
+        kind,
+        &reporter,
+        &socket_path,
+        &config.output,
+        &config.telemetry,
+        response.build_id,
+        &topology_policy,
+        RemoteOutput::Forward,
+    )
+    .await;
+
+    let exit_code = result
+        .as_ref()
+        .map(|ok| ok.exit_code)
+        .unwrap_or(EXIT_BUILD_ERROR);
+    let mut timing = result
+        .as_ref()
+        .map(|ok| ok.timing.clone())
+        .unwrap_or_default();
+    timing.select = Some(select);
+    timing.total = Some(remote_start.elapsed());
+    if let Err(e) = release_worker(
+        &socket_path,
+        &worker.id,
+        cores,
+        response.build_id,
+        Some(exit_code),
+        None,
+        result.as_ref().ok().map(|ok| ok.bytes_transferred),
+        Some(&timing),
+        &release_decision_trace(DecisionTrace::new(), &result),
+        None,
+        None,
+    )
+    .await
+    {
+        warn!("Failed to release worker slots: {}", e);
+    }
+    let result = result.with_context(|| format!("Remote pipeline failed on {}", worker.id))?;
+
+    let artifact = project_dir
+        .join("target")
+        .join("debug")
+        .join(BENCHMARK_BINARY_NAME);
+    let artifact_retrieved = artifact.is_file();
+    timing.total = Some(total_start.elapsed());
+
+    Ok(PipelineSelftestReport {
+        worker_id: worker.id.to_string(),
+        worker_host: worker.host.clone(),
+        build_id: response.build_id,
+        exit_code: result.exit_code,
+        artifact,
+        artifact_retrieved,
+        timing,
+    })
+}
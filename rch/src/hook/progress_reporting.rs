@@ -6,8 +6,10 @@
 //! mutable snapshot of build phase/progress ([`BuildHeartbeatSnapshot`]), the
 //! background loop that ticks every [`BUILD_HEARTBEAT_INTERVAL`] and on demand
 //! ([`BuildHeartbeatLoop`]), the progress-counter bump used by output-streaming
-//! callbacks ([`mark_heartbeat_progress`]), and the single fire-and-forget
-//! socket send ([`send_build_heartbeat`]).
+//! callbacks ([`mark_heartbeat_progress`], or [`record_heartbeat_output`] when
+//! `output.forward_to_daemon` is set, which also queues the line for the next
+//! heartbeat so `rch tail` can follow the build), and the single
+//! fire-and-forget socket send ([`send_build_heartbeat`]).
 //!
 //! The loop is driven from the hook's `execute_remote_compilation` path; the
 //! principal items it calls (`BuildHeartbeatLoop` and `mark_heartbeat_progress`)
@@ -15,10 +17,21 @@
 //! `HookReporter` (human/agent-facing progress UI) is a separate concern that
 //! stays in the parent module.
 
+use super::daemon_ipc::effective_socket_path;
 use super::*;
 
+use std::collections::VecDeque;
+
 const BUILD_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
 
+/// Output bytes queued between two heartbeats; older lines are dropped first.
+/// Keeps the heartbeat body well under the daemon's 64 KiB request line limit
+/// even after JSON escaping.
+const HEARTBEAT_OUTPUT_MAX_BYTES: usize = 24 * 1024;
+
+/// Longest output line forwarded to the daemon; longer lines are truncated.
+const HEARTBEAT_OUTPUT_LINE_MAX_BYTES: usize = 1024;
+
 #[derive(Debug, Clone)]
 pub(super) struct BuildHeartbeatSnapshot {
     phase: BuildHeartbeatPhase,
@@ -26,6 +39,9 @@ pub(super) struct BuildHeartbeatSnapshot {
     progress_counter: u64,
     progress_percent: Option<f64>,
     remote_pgid_file: Option<String>,
+    detached: bool,
+    pending_output: VecDeque<String>,
+    pending_output_bytes: usize,
 }
 
 impl BuildHeartbeatSnapshot {
@@ -36,6 +52,9 @@ impl BuildHeartbeatSnapshot {
             progress_counter: 0,
             progress_percent: None,
             remote_pgid_file: None,
+            detached: false,
+            pending_output: VecDeque::new(),
+            pending_output_bytes: 0,
         }
     }
 
@@ -49,8 +68,50 @@ impl BuildHeartbeatSnapshot {
         self.progress_counter = self.progress_counter.saturating_add(1);
     }
 
-    fn set_remote_pgid_file(&mut self, remote_pgid_file: Option<String>) {
+    fn set_remote_pgid_file(&mut self, remote_pgid_file: Option<String>, detached: bool) {
         self.remote_pgid_file = remote_pgid_file;
+        self.detached = detached;
+    }
+
+    fn push_output(&mut self, line: &str) {
+        let mut line = line.trim_end_matches(['\r', '\n']);
+        if line.len() > HEARTBEAT_OUTPUT_LINE_MAX_BYTES {
+            let mut cut = HEARTBEAT_OUTPUT_LINE_MAX_BYTES;
+            while !line.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            line = &line[..cut];
+        }
+        self.pending_output_bytes += line.len();
+        self.pending_output.push_back(line.to_string());
+        while self.pending_output_bytes > HEARTBEAT_OUTPUT_MAX_BYTES {
+            let Some(dropped) = self.pending_output.pop_front() else {
+                break;
+            };
+            self.pending_output_bytes -= dropped.len();
+        }
+    }
+
+    /// Build the next heartbeat, handing over the queued output lines.
+    fn take_heartbeat(
+        &mut self,
+        build_id: u64,
+        worker_id: &WorkerId,
+        hook_pid: u32,
+    ) -> BuildHeartbeatRequest {
+        self.pending_output_bytes = 0;
+        BuildHeartbeatRequest {
+            build_id,
+            worker_id: worker_id.clone(),
+            hook_pid: Some(hook_pid),
+            remote_pgid_file: self.remote_pgid_file.clone(),
+            detached: self.detached,
+            phase: self.phase.clone(),
+            detail: self.detail.clone(),
+            progress_counter: Some(self.progress_counter),
+            progress_percent: self.progress_percent,
+            output_lines: self.pending_output.drain(..).collect(),
+        }
     }
 }
 
@@ -79,22 +140,10 @@ impl BuildHeartbeatLoop {
             loop {
                 tokio::select! {
                     _ = ticker.tick() => {
-                        let snapshot = {
-                            state_for_task
-                                .lock()
-                                .unwrap_or_else(|e| e.into_inner())
-                                .clone()
-                        };
-                        let heartbeat = BuildHeartbeatRequest {
-                            build_id,
-                            worker_id: worker_id_owned.clone(),
-                            hook_pid: Some(hook_pid),
-                            remote_pgid_file: snapshot.remote_pgid_file.clone(),
-                            phase: snapshot.phase,
-                            detail: snapshot.detail,
-                            progress_counter: Some(snapshot.progress_counter),
-                            progress_percent: snapshot.progress_percent,
-                        };
+                        let heartbeat = state_for_task
+                            .lock()
+                            .unwrap_or_else(|e| e.into_inner())
+                            .take_heartbeat(build_id, &worker_id_owned, hook_pid);
                         if let Err(e) = send_build_heartbeat(&socket_path_owned, &heartbeat).await {
                             debug!("build heartbeat send failed for build {}: {}", build_id, e);
                         }
@@ -126,25 +175,19 @@ impl BuildHeartbeatLoop {
             .update_phase(phase, detail);
     }
 
-    pub(super) fn set_remote_pgid_file(&self, remote_pgid_file: Option<String>) {
+    pub(super) fn set_remote_pgid_file(&self, remote_pgid_file: Option<String>, detached: bool) {
         self.state
             .lock()
             .unwrap_or_else(|e| e.into_inner())
-            .set_remote_pgid_file(remote_pgid_file);
+            .set_remote_pgid_file(remote_pgid_file, detached);
     }
 
     pub(super) async fn flush(&self) {
-        let snapshot = { self.state.lock().unwrap_or_else(|e| e.into_inner()).clone() };
-        let heartbeat = BuildHeartbeatRequest {
-            build_id: self.build_id,
-            worker_id: self.worker_id.clone(),
-            hook_pid: Some(self.hook_pid),
-            remote_pgid_file: snapshot.remote_pgid_file.clone(),
-            phase: snapshot.phase,
-            detail: snapshot.detail,
-            progress_counter: Some(snapshot.progress_counter),
-            progress_percent: snapshot.progress_percent,
-        };
+        let heartbeat = self
+            .state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take_heartbeat(self.build_id, &self.worker_id, self.hook_pid);
         if let Err(e) = send_build_heartbeat(&self.socket_path, &heartbeat).await {
             debug!(
                 "build heartbeat flush failed for build {}: {}",
@@ -183,15 +226,23 @@ pub(super) fn mark_heartbeat_progress(state: &Arc<Mutex<BuildHeartbeatSnapshot>>
         .note_progress();
 }
 
+/// Count progress and queue `line` for the daemon's `rch tail` buffer.
+pub(super) fn record_heartbeat_output(state: &Arc<Mutex<BuildHeartbeatSnapshot>>, line: &str) {
+    let mut snapshot = state.lock().unwrap_or_else(|e| e.into_inner());
+    snapshot.note_progress();
+    snapshot.push_output(line);
+}
+
 async fn send_build_heartbeat(
     socket_path: &str,
     heartbeat: &BuildHeartbeatRequest,
 ) -> anyhow::Result<()> {
-    if !Path::new(socket_path).exists() {
+    let socket_path = effective_socket_path(socket_path);
+    if !Path::new(&socket_path).exists() {
         return Ok(());
     }
 
-    let stream = match timeout(Duration::from_secs(2), UnixStream::connect(socket_path)).await {
+    let stream = match timeout(Duration::from_secs(2), UnixStream::connect(&socket_path)).await {
         Ok(Ok(s)) => s,
         Ok(Err(e)) => return Err(e.into()),
         Err(_) => return Ok(()), // Timeout connecting — don't block hook
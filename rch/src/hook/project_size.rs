@@ -0,0 +1,149 @@
+//! Minimum-project-size offload gate (`compilation.min_project_files` /
+//! `compilation.min_project_bytes`).
+//!
+//! For tiny projects the sync + SSH round trip costs more than the compile,
+//! so `run_exec` keeps them local before asking the daemon for a worker. The
+//! walk starts at the enclosing Cargo workspace root ([`resolve_size_root`]),
+//! since a build started from a member crate or a subdirectory still syncs
+//! the whole workspace. It uses the same directory excludes the transfer uses,
+//! and the walk stops as soon as every configured threshold is reached, so a
+//! large tree costs no more than a small one.
+//!
+//! The same walk, from the same root, enforces the `transfer.max_project_bytes`
+//! ceiling: a project over it never offloads, so a huge tree fails fast with `RCH-E415` instead
+//! of hanging in sync.
+use super::*;
+
+/// Files and bytes seen while walking a project, capped by the walk limits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) struct ProjectSize {
+    pub(super) files: u64,
+    pub(super) bytes: u64,
+}
+
+/// The directory to measure for a build started in `cwd`: the nearest
+/// ancestor whose `Cargo.toml` declares `[workspace]`, else the nearest
+/// package root, else `cwd` itself.
+pub(super) fn resolve_size_root(cwd: &Path) -> PathBuf {
+    let mut package_root = None;
+    for candidate in cwd.ancestors() {
+        let manifest = candidate.join("Cargo.toml");
+        if !manifest.is_file() {
+            continue;
+        }
+        if super::dependency_closure::manifest_declares_workspace(&manifest) {
+            return candidate.to_path_buf();
+        }
+        package_root.get_or_insert(candidate);
+    }
+    package_root.unwrap_or(cwd).to_path_buf()
+}
+
+/// Directory names the transfer never syncs (`transfer.exclude_patterns`
+/// entries of the form `name/`), skipped so build output doesn't count.
+fn excluded_dir_names(exclude_patterns: &[String]) -> Vec<&str> {
+    exclude_patterns
+        .iter()
+        .filter_map(|pattern| pattern.strip_suffix('/'))
+        .map(|name| name.trim_start_matches('/'))
+        .filter(|name| !name.is_empty() && !name.contains(['/', '*', '?', '[']))
+        .collect()
+}
+
+/// Walk `root` counting regular files and their sizes, skipping excluded
+/// directories and symlinks. Stops early once both `file_limit` and
+/// `byte_limit` are reached; the result is then a lower bound.
+pub(super) fn measure_project_size(
+    root: &Path,
+    exclude_patterns: &[String],
+    file_limit: u64,
+    byte_limit: u64,
+) -> ProjectSize {
+    let excluded = excluded_dir_names(exclude_patterns);
+    let mut size = ProjectSize::default();
+    let mut pending = vec![root.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if size.files >= file_limit && size.bytes >= byte_limit {
+                return size;
+            }
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                let name = entry.file_name();
+                if !excluded.iter().any(|skip| name == *skip) {
+                    pending.push(entry.path());
+                }
+            } else if file_type.is_file() {
+                size.files += 1;
+                size.bytes += entry.metadata().map(|meta| meta.len()).unwrap_or(0);
+            }
+        }
+    }
+    size
+}
+
+/// Why the size gate kept a project local, or `None` to offload.
+///
+/// A project is too small when it falls below any configured threshold.
+/// Kinds listed in `min_project_size_exempt_kinds` are never gated.
+pub(super) fn evaluate_project_size_gate(
+    root: &Path,
+    kind: Option<CompilationKind>,
+    config: &rch_common::RchConfig,
+) -> Option<String> {
+    let compilation = &config.compilation;
+    if !compilation.project_size_gate_applies(kind) {
+        return None;
+    }
+    let min_files = compilation.min_project_files.unwrap_or(0);
+    let min_bytes = compilation.min_project_bytes.unwrap_or(0);
+    let size = measure_project_size(
+        root,
+        &config.transfer.exclude_patterns,
+        min_files,
+        min_bytes,
+    );
+    project_size_shortfall(size, min_files, min_bytes)
+}
+
+fn project_size_shortfall(size: ProjectSize, min_files: u64, min_bytes: u64) -> Option<String> {
+    if size.files < min_files {
+        Some(format!(
+            "{} files < min_project_files {}",
+            size.files, min_files
+        ))
+    } else if size.bytes < min_bytes {
+        Some(format!(
+            "{} bytes < min_project_bytes {}",
+            size.bytes, min_bytes
+        ))
+    } else {
+        None
+    }
+}
+
+/// Why the project exceeds `transfer.max_project_bytes`, or `None` when it
+/// fits (or no cap is set). The walk stops as soon as the cap is passed, so
+/// the reported size is a lower bound.
+pub(super) fn evaluate_project_size_cap(
+    root: &Path,
+    transfer: &rch_common::TransferConfig,
+) -> Option<String> {
+    let cap = transfer.max_project_bytes?;
+    let size = measure_project_size(root, &transfer.exclude_patterns, 0, cap.saturating_add(1));
+    (size.bytes > cap).then(|| {
+        format!(
+            "project exceeds {} (at least {} in {} files); exclude large dirs via \
+             transfer.exclude_patterns or raise transfer.max_project_bytes",
+            format_bytes(cap),
+            format_bytes(size.bytes),
+            size.files
+        )
+    })
+}
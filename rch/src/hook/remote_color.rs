@@ -0,0 +1,141 @@
+//! Color forcing for remote build output.
+//!
+//! Workers run the command without a TTY, so cargo and the C/C++ compilers
+//! fall back to plain output. [`remote_color_mode`] decides from
+//! `output.color_mode` and the local output context whether color should be
+//! forced; [`with_color_flag`] then adds the tool's own color flag when the
+//! command runs that tool and does not already pick a color. The env side (`CARGO_TERM_COLOR`,
+//! `CLICOLOR_FORCE`, ...) is applied by the transfer pipeline.
+
+use super::*;
+use rch_common::ColorMode;
+
+/// Color mode to apply on the worker for this build.
+///
+/// `never` always wins. Machine output (hook or `RCH_JSON`) and CI runs never
+/// get forced color, whatever the config says. `auto` forces color when the
+/// local output would be colored, since the worker cannot see the terminal.
+pub(super) fn remote_color_mode(
+    configured: ColorMode,
+    output_ctx: OutputContext,
+    ci: bool,
+) -> ColorMode {
+    match configured {
+        ColorMode::Never => ColorMode::Never,
+        _ if output_ctx.is_machine() || ci => ColorMode::Auto,
+        ColorMode::Always => ColorMode::Always,
+        ColorMode::Auto if output_ctx.supports_color() => ColorMode::Always,
+        ColorMode::Auto => ColorMode::Auto,
+    }
+}
+
+/// Add the tool's color flag to `command` when color is forced.
+///
+/// Cargo and rustc get `--color=always`, gcc and clang
+/// `-fdiagnostics-color=always`. Build systems and bun rely on the color env
+/// alone. The flag goes before a `--` separator so it is not passed on to
+/// test binaries, and is skipped when the command already sets color.
+///
+/// The kind alone is not enough: a user classifier rule can give a wrapper
+/// such as `xbuild` a cargo kind, and the wrapper may not accept the flag. The
+/// flag is only added when the command's program is the tool itself.
+pub(super) fn with_color_flag(
+    kind: Option<CompilationKind>,
+    command: &str,
+    color_mode: ColorMode,
+) -> String {
+    if color_mode != ColorMode::Always {
+        return command.to_string();
+    }
+    let (flag, existing): (&str, &[&str]) = match kind {
+        Some(
+            CompilationKind::CargoBuild
+            | CompilationKind::CargoTest
+            | CompilationKind::CargoCheck
+            | CompilationKind::CargoClippy
+            | CompilationKind::CargoDoc
+            | CompilationKind::CargoNextest
+            | CompilationKind::CargoBench
+            | CompilationKind::CargoRun
+            | CompilationKind::Rustc,
+        ) => ("--color=always", &["--color"]),
+        Some(
+            CompilationKind::Gcc
+            | CompilationKind::Gpp
+            | CompilationKind::Clang
+            | CompilationKind::Clangpp,
+        ) => (
+            "-fdiagnostics-color=always",
+            &[
+                "-fdiagnostics-color",
+                "-fno-diagnostics-color",
+                "-fcolor-diagnostics",
+                "-fno-color-diagnostics",
+            ],
+        ),
+        _ => return command.to_string(),
+    };
+    if !kind.is_some_and(|kind| runs_tool_for_kind(kind, command)) {
+        return command.to_string();
+    }
+
+    let mut separator = None;
+    let mut offset = 0;
+    for token in command.split(' ') {
+        if token == "--" {
+            separator = Some(offset);
+            break;
+        }
+        if existing
+            .iter()
+            .any(|prefix| token == *prefix || token.starts_with(&format!("{prefix}=")))
+        {
+            return command.to_string();
+        }
+        offset += token.len() + 1;
+    }
+
+    match separator {
+        Some(at) => format!("{}{} {}", &command[..at], flag, &command[at..]),
+        None => format!("{} {}", command.trim_end(), flag),
+    }
+}
+
+/// Whether `command` (after wrappers such as `env` or `time`) runs the tool
+/// `kind` names rather than some other program classified as that kind.
+fn runs_tool_for_kind(kind: CompilationKind, command: &str) -> bool {
+    let normalized = rch_common::patterns::normalize_command(command);
+    let Some(program) = normalized
+        .split_whitespace()
+        .next()
+        .map(|program| program.rsplit('/').next().unwrap_or(program))
+    else {
+        return false;
+    };
+    match kind {
+        CompilationKind::Rustc => program == "rustc",
+        CompilationKind::Gcc
+        | CompilationKind::Gpp
+        | CompilationKind::Clang
+        | CompilationKind::Clangpp => {
+            ["gcc", "g++", "cc", "c++", "clang", "clang++"]
+                .iter()
+                .any(|tool| {
+                    // Versioned or target-prefixed: gcc-13, x86_64-linux-gnu-g++-13.
+                    program == *tool
+                        || program.starts_with(&format!("{tool}-"))
+                        || program.ends_with(&format!("-{tool}"))
+                        || program.contains(&format!("-{tool}-"))
+                })
+        }
+        CompilationKind::CargoBuild
+        | CompilationKind::CargoTest
+        | CompilationKind::CargoCheck
+        | CompilationKind::CargoClippy
+        | CompilationKind::CargoDoc
+        | CompilationKind::CargoNextest
+        | CompilationKind::CargoBench
+        | CompilationKind::CargoRun => program == "cargo",
+        _ => false,
+    }
+}
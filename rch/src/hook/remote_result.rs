@@ -1,29 +1,33 @@
-//! Remote-execution result type and outcome classification for the hook.
+//! Remote-execution result type and failure reporting for the hook.
 //!
 //! This submodule owns the data that describes the outcome of a remote build and
-//! the predicates that interpret it, extracted from `hook.rs` per bead
+//! the helpers that report a failed one, extracted from `hook.rs` per bead
 //! `remote_compilation_helper-zcecy.14`:
 //!
 //! - [`RemoteExecutionResult`] — the exit code / stderr / duration / per-phase
 //!   timing produced by `transfer_orchestration::execute_remote_compilation` and
 //!   consumed by `run_hook` / `run_exec`.
-//! - **Toolchain-failure detection** — [`is_toolchain_failure`] recognizes the
-//!   stderr signatures of a missing/misconfigured rustup toolchain so the hook can
-//!   fall back to local execution rather than deny.
 //! - **Worker system-dependency detection** — [`detect_worker_system_dependency_failure`]
 //!   parses pkg-config / system-library errors into a
 //!   [`WorkerSystemDependencyFailure`] carrying an operator-facing summary,
 //!   remediation, and log detail.
-//! - **Signal classification** — [`is_signal_killed`] / [`signal_name`] decode the
-//!   `128 + N` signal-exit convention.
+//! - **Failure summaries** — [`remote_failure_summary`] renders the `[RCH]`
+//!   line for a classified failure, using [`signal_name`] for the signal number
+//!   carried by `CommandOutcome::Signal`.
 //!
-//! It reaches its inputs from the parent via `use super::*` (the `EXIT_*`
-//! exit-code consts and `CommandTimingBreakdown`). Items consumed by the parent
+//! Nothing here decides what a result means. `run_hook` and `run_exec` both
+//! pass `exit_code` and `stderr` to `rch_common::command_outcome::classify_exit`
+//! and act on the [`CommandOutcome`]: `Success` is reported as-is,
+//! `ToolchainFailure` falls back to a local run, and every other outcome keeps
+//! the remote exit code and prints [`remote_failure_summary`].
+//!
+//! It reaches its inputs from the parent via `use super::*` (`CommandOutcome`,
+//! `WorkerId` and `CommandTimingBreakdown`). Items consumed by the parent
 //! (`run_hook` / `run_exec`) and by the sibling `transfer_orchestration`
 //! (`RemoteExecutionResult`, which it constructs and returns) are `pub(super)`;
 //! `extract_tick_quoted_value` (a detection-only helper) stays private. The
-//! cluster's unit tests remain in `hook::tests` and reach the classifier fns via
-//! the test module's `use super::*` (the parent re-exports them).
+//! cluster's unit tests remain in `hook::tests`; the exit-code classifier's own
+//! tests live beside it in `rch_common::command_outcome`.
 
 use super::*;
 
@@ -38,33 +42,10 @@ pub(super) struct RemoteExecutionResult {
     pub(super) duration_ms: u64,
     /// Per-phase timing breakdown.
     pub(super) timing: CommandTimingBreakdown,
-}
-
-/// Check if the failure is a toolchain-related infrastructure failure.
-///
-/// Returns true if the error indicates a toolchain issue that should
-/// trigger a local fallback rather than denying execution.
-pub(super) fn is_toolchain_failure(stderr: &str, exit_code: i32) -> bool {
-    if exit_code == 0 || exit_code == EXIT_TEST_FAILURES || is_signal_killed(exit_code).is_some() {
-        return false;
-    }
-
-    stderr
-        .lines()
-        .map(str::trim)
-        .map(str::to_ascii_lowercase)
-        .any(|line| {
-            line.starts_with("rustup: command not found")
-                || line.starts_with("rustup: not found")
-                || line.contains("error: no default toolchain configured")
-                || line.contains("error: no active toolchain")
-                || (line.contains("error: toolchain ")
-                    && (line.contains(" is not installed")
-                        || line.contains(" is unavailable")
-                        || line.contains(" does not have the binary ")))
-                || (line.contains("error: override toolchain ")
-                    && line.contains(" is not installed"))
-        })
+    /// Bytes uploaded by the source sync (0 means the worker copy was already current).
+    pub(super) bytes_transferred: u64,
+    /// Path, dependency, sync, execution and artifact decisions (`rch explain`).
+    pub(super) decision_trace: DecisionTrace,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -181,26 +162,28 @@ fn extract_tick_quoted_value(line: &str, prefix: &str) -> Option<String> {
     }
 }
 
-/// Check if the process was killed by a signal.
-///
-/// Exit codes > 128 indicate the process was terminated by a signal.
-/// The signal number is exit_code - 128.
-///
-/// Common signals:
-/// - 137 (SIGKILL = 9): Typically OOM killer
-/// - 143 (SIGTERM = 15): Graceful termination request
-/// - 139 (SIGSEGV = 11): Segmentation fault
-#[allow(dead_code)]
-pub(super) fn is_signal_killed(exit_code: i32) -> Option<i32> {
-    if exit_code > EXIT_SIGNAL_BASE {
-        Some(exit_code - EXIT_SIGNAL_BASE)
-    } else {
-        None
+/// One-line `[RCH]` summary for a remote command that failed with `outcome`.
+pub(super) fn remote_failure_summary(
+    worker_id: &WorkerId,
+    outcome: CommandOutcome,
+    exit_code: i32,
+) -> String {
+    match outcome {
+        CommandOutcome::Signal(signal) => {
+            format!(
+                "[RCH] remote {} killed ({})",
+                worker_id,
+                signal_name(signal)
+            )
+        }
+        CommandOutcome::TimedOut => format!("[RCH] remote {} timed out", worker_id),
+        CommandOutcome::TestFailures => format!("[RCH] remote {} tests failed", worker_id),
+        CommandOutcome::BuildError => format!("[RCH] remote {} build error", worker_id),
+        _ => format!("[RCH] remote {} failed (exit {})", worker_id, exit_code),
     }
 }
 
 /// Format a signal number as a human-readable name.
-#[allow(dead_code)]
 pub(super) fn signal_name(signal: i32) -> &'static str {
     match signal {
         1 => "SIGHUP",
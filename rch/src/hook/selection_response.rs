@@ -54,6 +54,7 @@ enum SelectionReasonWire {
     NoAdmissibleWorkers { no_admissible_workers: String },
     NoWorkersWithRuntime { no_workers_with_runtime: String },
     SelectionError { selection_error: String },
+    QueuedFair { position: usize },
     Unit(UnitSelectionReasonWire),
     Unknown(serde_json::Value),
 }
@@ -91,6 +92,15 @@ impl<'de> Deserialize<'de> for SelectionReasonWire {
                         selection_error: error.to_string(),
                     });
                 }
+                if let Some(position) = object
+                    .get("queued_fair")
+                    .and_then(|fair| fair.get("position"))
+                    .and_then(serde_json::Value::as_u64)
+                {
+                    return Ok(Self::QueuedFair {
+                        position: position as usize,
+                    });
+                }
             }
             serde_json::Value::String(_) => {
                 let unit = serde_json::from_value::<UnitSelectionReasonWire>(value.clone())
@@ -119,6 +129,7 @@ impl From<SelectionReasonWire> for SelectionReason {
             SelectionReasonWire::SelectionError { selection_error } => {
                 Self::SelectionError(selection_error)
             }
+            SelectionReasonWire::QueuedFair { position } => Self::QueuedFair { position },
             SelectionReasonWire::Unit(unit) => unit.into(),
             SelectionReasonWire::Unknown(value) => Self::SelectionError(format!(
                 "unknown daemon selection reason: {}",
@@ -134,11 +145,18 @@ enum UnitSelectionReasonWire {
     Success,
     NoWorkersConfigured,
     AllWorkersUnreachable,
+    AllWorkersDraining,
     AllCircuitsOpen,
     AllWorkersBusy,
+    WorkerBuildCapReached,
+    WorkerBlockedAfterFailures,
     NoWorkersPassedHealth,
     AllWorkersFailedPreflight,
     AllWorkersFailedConvergence,
+    InsufficientDiskSpace,
+    InsufficientMemory,
+    WorkersMissingRsync,
+    CoalescedDuplicate,
     NoMatchingWorkers,
     AffinityPinned,
     AffinityFallback,
@@ -152,13 +170,20 @@ impl From<UnitSelectionReasonWire> for SelectionReason {
             UnitSelectionReasonWire::Success => Self::Success,
             UnitSelectionReasonWire::NoWorkersConfigured => Self::NoWorkersConfigured,
             UnitSelectionReasonWire::AllWorkersUnreachable => Self::AllWorkersUnreachable,
+            UnitSelectionReasonWire::AllWorkersDraining => Self::AllWorkersDraining,
             UnitSelectionReasonWire::AllCircuitsOpen => Self::AllCircuitsOpen,
             UnitSelectionReasonWire::AllWorkersBusy => Self::AllWorkersBusy,
+            UnitSelectionReasonWire::WorkerBuildCapReached => Self::WorkerBuildCapReached,
+            UnitSelectionReasonWire::WorkerBlockedAfterFailures => Self::WorkerBlockedAfterFailures,
             UnitSelectionReasonWire::NoWorkersPassedHealth => Self::NoWorkersPassedHealth,
             UnitSelectionReasonWire::AllWorkersFailedPreflight => Self::AllWorkersFailedPreflight,
             UnitSelectionReasonWire::AllWorkersFailedConvergence => {
                 Self::AllWorkersFailedConvergence
             }
+            UnitSelectionReasonWire::InsufficientDiskSpace => Self::InsufficientDiskSpace,
+            UnitSelectionReasonWire::InsufficientMemory => Self::InsufficientMemory,
+            UnitSelectionReasonWire::WorkersMissingRsync => Self::WorkersMissingRsync,
+            UnitSelectionReasonWire::CoalescedDuplicate => Self::CoalescedDuplicate,
             UnitSelectionReasonWire::NoMatchingWorkers => Self::NoMatchingWorkers,
             UnitSelectionReasonWire::AffinityPinned => Self::AffinityPinned,
             UnitSelectionReasonWire::AffinityFallback => Self::AffinityFallback,
@@ -0,0 +1,275 @@
+//! Opt-in shadow mode (`general.shadow_mode`): offload, but trust local.
+//!
+//! `run_exec` reaches [`run_shadow_build`] once a worker is assigned. The
+//! build then runs twice at the same time: locally, as the authoritative run
+//! (its output reaches the terminal, its artifacts stay in the tree, and its
+//! exit code is returned), and on the worker as a silent shadow that streams
+//! nothing and retrieves no artifacts. When both finish the exit codes are
+//! compared; a divergence is logged, reported on stderr, and appended to
+//! `~/.cache/rch/shadow_divergence.jsonl` so environment-parity bugs can be
+//! collected before anyone relies on the remote result.
+//!
+//! A shadow run that could not execute the command at all (toolchain missing,
+//! transfer skipped, pipeline error) is "not compared" rather than divergent:
+//! the normal path would have fallen back to a local build there too.
+
+use super::*;
+
+use std::io::Write;
+
+/// Remote half of a shadowed build.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum ShadowRemoteOutcome {
+    /// The command ran on the worker and exited with `exit_code`.
+    Completed { exit_code: i32 },
+    /// The worker never ran the command; there is nothing to compare.
+    Unavailable { reason: String },
+}
+
+/// Describe how the remote run diverged from the local one, or `None` when it
+/// matched or never ran.
+pub(super) fn shadow_divergence(local_exit: i32, remote: &ShadowRemoteOutcome) -> Option<String> {
+    match remote {
+        ShadowRemoteOutcome::Completed { exit_code } if *exit_code != local_exit => Some(format!(
+            "remote exit {} != local exit {}",
+            exit_code, local_exit
+        )),
+        _ => None,
+    }
+}
+
+/// One line of `shadow_divergence.jsonl`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct ShadowDivergence {
+    /// When the comparison finished (Unix milliseconds).
+    pub timestamp_ms: u64,
+    pub project: String,
+    /// The command as run locally, with secrets redacted.
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    pub worker: String,
+    pub local_exit_code: i32,
+    pub remote_exit_code: i32,
+    pub local_ms: u64,
+    pub remote_ms: u64,
+}
+
+/// Path of the append-only divergence log.
+pub(super) fn shadow_divergence_log_path() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("rch").join("shadow_divergence.jsonl"))
+}
+
+/// Append `record` to the divergence log. Logs and continues on I/O errors.
+fn append_shadow_divergence(record: &ShadowDivergence) {
+    let Some(path) = shadow_divergence_log_path() else {
+        return;
+    };
+    let line = match serde_json::to_string(record) {
+        Ok(line) => line,
+        Err(e) => {
+            warn!("Failed to serialize shadow divergence: {}", e);
+            return;
+        }
+    };
+    let written = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| {
+            std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+        })
+        .and_then(|mut file| writeln!(file, "{}", line));
+    if let Err(e) = written {
+        warn!(
+            "Failed to append shadow divergence to {}: {}",
+            path.display(),
+            e
+        );
+    }
+}
+
+/// Transfer settings for the remote half of a shadowed build: artifact
+/// retrieval is skipped so the local tree only ever holds local output.
+pub(super) fn shadow_transfer_config(
+    transfer: &TransferConfig,
+    kind: Option<CompilationKind>,
+) -> TransferConfig {
+    let mut config = transfer.clone();
+    if let Some(kind) = kind
+        && !config.skips_artifacts_for(Some(kind))
+    {
+        config.skip_artifacts_for.push(kind.as_str().to_string());
+    }
+    config
+}
+
+/// Run `command` locally and `remote_command` on `worker` concurrently, release
+/// the worker, compare the outcomes, and return the local exit code.
+///
+/// `local_timing` names the project/kind a successful local run is recorded
+/// under, as for `exit_with_timed_local_fallback`. `env_allowlist` must
+/// already carry the forwarded build env (RUSTFLAGS and friends), so the
+/// shadow is compiled with the same flags as the local build.
+#[allow(clippy::too_many_arguments)] // Pipeline wiring favors explicit params
+pub(super) async fn run_shadow_build(
+    worker: &SelectedWorker,
+    build_id: Option<u64>,
+    command: &str,
+    remote_command: &str,
+    env_allowlist: Vec<String>,
+    forwarded_cargo_target_dir: Option<PathBuf>,
+    config: &rch_common::RchConfig,
+    toolchain: Option<&ToolchainInfo>,
+    kind: Option<CompilationKind>,
+    reporter: &HookReporter,
+    topology_policy: &PathTopologyPolicy,
+    project: &str,
+    cores: u32,
+    local_timing: Option<(&str, Option<CompilationKind>)>,
+) -> i32 {
+    reporter.verbose(&format!(
+        "[RCH] shadow: building locally, shadowed on {}",
+        worker.id
+    ));
+
+    let local_start = Instant::now();
+    let local = local_fallback_command(command).spawn().map(|mut child| {
+        tokio::task::spawn_blocking(move || {
+            child.wait().map(|status| (status, local_start.elapsed()))
+        })
+    });
+
+    let remote_start = Instant::now();
+    let remote = execute_remote_compilation(
+        worker,
+        remote_command,
+        shadow_transfer_config(&config.transfer, kind),
+        env_allowlist,
+        forwarded_cargo_target_dir,
+        &config.compilation,
+        &config.self_healing,
+        toolchain,
+        kind,
+        &HookReporter::new(OutputVisibility::None),
+        &config.general.socket_path,
+        &config.output,
+        &config.telemetry,
+        build_id,
+        topology_policy,
+        RemoteOutput::Discard,
+    )
+    .await;
+    let remote_elapsed = remote_start.elapsed();
+
+    let release_timing = remote.as_ref().ok().map(|ok| {
+        let mut timing = ok.timing.clone();
+        timing.total = Some(remote_elapsed);
+        timing
+    });
+    if let Err(e) = release_worker(
+        &config.general.socket_path,
+        &worker.id,
+        cores,
+        build_id,
+        Some(
+            remote
+                .as_ref()
+                .map(|ok| ok.exit_code)
+                .unwrap_or(EXIT_BUILD_ERROR),
+        ),
+        None,
+        remote.as_ref().ok().map(|ok| ok.bytes_transferred),
+        release_timing.as_ref(),
+        &release_decision_trace(DecisionTrace::new(), &remote),
+        None,
+        None,
+    )
+    .await
+    {
+        warn!("Failed to release worker slots: {}", e);
+    }
+
+    let local = match local {
+        Ok(handle) => handle.await.map_err(std::io::Error::other).and_then(|r| r),
+        Err(e) => Err(e),
+    };
+    let (local_exit, local_elapsed) = match local {
+        Ok((status, elapsed)) => (status.code().unwrap_or(1), elapsed),
+        Err(e) => {
+            reporter.summary(&format!("[RCH] local run failed: {e}"));
+            return EXIT_BUILD_ERROR;
+        }
+    };
+
+    if local_exit == 0
+        && let Some((project, kind)) = local_timing
+    {
+        record_build_timing(project, kind, duration_ms(local_elapsed), false);
+    }
+    let outcome = match &remote {
+        Ok(result)
+            if classify_exit(result.exit_code, &result.stderr).should_fall_back_locally() =>
+        {
+            ShadowRemoteOutcome::Unavailable {
+                reason: "remote toolchain missing".to_string(),
+            }
+        }
+        Ok(result) => {
+            if result.exit_code == 0 {
+                record_build_timing(project, kind, result.duration_ms, true);
+            }
+            ShadowRemoteOutcome::Completed {
+                exit_code: result.exit_code,
+            }
+        }
+        Err(e) => ShadowRemoteOutcome::Unavailable {
+            reason: e.to_string(),
+        },
+    };
+
+    match (shadow_divergence(local_exit, &outcome), &outcome) {
+        (Some(detail), ShadowRemoteOutcome::Completed { exit_code }) => {
+            warn!(
+                "Shadow build diverged on {} for {}: {}",
+                worker.id, project, detail
+            );
+            reporter.summary(&format!(
+                "[RCH] shadow: {} diverged ({})",
+                worker.id, detail
+            ));
+            append_shadow_divergence(&ShadowDivergence {
+                timestamp_ms: now_unix_ms(),
+                project: project.to_string(),
+                command: redact_secrets(command),
+                kind: kind.map(|kind| kind.as_str().to_string()),
+                worker: worker.id.as_str().to_string(),
+                local_exit_code: local_exit,
+                remote_exit_code: *exit_code,
+                local_ms: duration_ms(local_elapsed),
+                remote_ms: duration_ms(remote_elapsed),
+            });
+        }
+        (_, ShadowRemoteOutcome::Unavailable { reason }) => {
+            info!("Shadow build on {} not compared: {}", worker.id, reason);
+            reporter.verbose(&format!(
+                "[RCH] shadow: {} not compared ({})",
+                worker.id, reason
+            ));
+        }
+        (_, ShadowRemoteOutcome::Completed { .. }) => {
+            reporter.verbose(&format!(
+                "[RCH] shadow: {} matched local (exit {})",
+                worker.id, local_exit
+            ));
+        }
+    }
+
+    local_exit
+}
+
+fn duration_ms(elapsed: Duration) -> u64 {
+    u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX)
+}
@@ -0,0 +1,92 @@
+//! Slow-build notifications (`[notifications]`).
+//!
+//! When a remote build takes at least `threshold_secs`, the hook POSTs a small
+//! JSON payload to `webhook_url` and/or runs `command`. Both are best-effort:
+//! the command is spawned and not waited on, the POST is bounded by
+//! `timeout_ms`, and failures are only logged.
+
+use super::*;
+use rch_common::NotificationsConfig;
+
+/// Payload describing the finished build.
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct SlowBuildNotice {
+    pub project: String,
+    pub worker: String,
+    pub duration_ms: u64,
+    pub exit_code: i32,
+}
+
+/// Whether a build that took `elapsed` should notify.
+pub(super) fn should_notify(config: &NotificationsConfig, elapsed: Duration) -> bool {
+    (config.webhook_url.is_some() || config.command.is_some())
+        && elapsed >= Duration::from_secs(config.threshold_secs)
+}
+
+/// Environment handed to the notification command.
+pub(super) fn notice_env(notice: &SlowBuildNotice) -> Vec<(&'static str, String)> {
+    vec![
+        ("RCH_NOTIFY_PROJECT", notice.project.clone()),
+        ("RCH_NOTIFY_WORKER", notice.worker.clone()),
+        ("RCH_NOTIFY_DURATION_MS", notice.duration_ms.to_string()),
+        ("RCH_NOTIFY_EXIT_CODE", notice.exit_code.to_string()),
+    ]
+}
+
+/// Send the configured notifications if the build was slow enough.
+pub(super) async fn notify_slow_build(
+    config: &NotificationsConfig,
+    project: &str,
+    worker: &WorkerId,
+    elapsed: Duration,
+    exit_code: i32,
+) {
+    if !should_notify(config, elapsed) {
+        return;
+    }
+    let notice = SlowBuildNotice {
+        project: project.to_string(),
+        worker: worker.to_string(),
+        duration_ms: elapsed.as_millis() as u64,
+        exit_code,
+    };
+
+    if let Some(command) = config.command.as_deref() {
+        let spawned = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .envs(notice_env(&notice))
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        if let Err(e) = spawned {
+            warn!("Failed to run slow-build notification command: {}", e);
+        }
+    }
+
+    if let Some(url) = config.webhook_url.as_deref() {
+        let timeout = Duration::from_millis(config.timeout_ms.max(1));
+        let client = match reqwest::Client::builder()
+            .timeout(timeout)
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+        {
+            Ok(client) => client,
+            Err(e) => {
+                warn!("Failed to build slow-build webhook client: {}", e);
+                return;
+            }
+        };
+        match client.post(url).json(&notice).send().await {
+            Ok(response) if !response.status().is_success() => {
+                warn!(
+                    "Slow-build webhook returned HTTP {}",
+                    response.status().as_u16()
+                );
+            }
+            Ok(_) => debug!("Slow-build webhook delivered"),
+            Err(e) => warn!("Slow-build webhook failed: {}", e),
+        }
+    }
+}
@@ -43,7 +43,6 @@ pub(super) async fn run_offload_ssh_command(
     remote_cmd: &str,
     timeout_duration: Duration,
 ) -> anyhow::Result<Output> {
-    let identity_file = shellexpand::tilde(&worker.identity_file);
     let destination = format!("{}@{}", worker.user, worker.host);
 
     let mut cmd = Command::new("ssh");
@@ -53,7 +52,10 @@ pub(super) async fn run_offload_ssh_command(
         "ConnectTimeout={}",
         timeout_duration.as_secs().max(1)
     ));
-    cmd.arg("-i").arg(identity_file.as_ref());
+    // No identity file: leave authentication to the ssh-agent.
+    if let Some(identity) = worker.ssh_identity() {
+        cmd.arg("-i").arg(identity);
+    }
     cmd.arg(&destination);
     cmd.arg(build_remote_shell_command(remote_cmd));
     cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
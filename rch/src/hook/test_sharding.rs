@@ -0,0 +1,305 @@
+//! Multi-worker test sharding for `cargo nextest run` (`selection.shard_tests`).
+//!
+//! `run_exec` already holds one worker when it reaches this path. If the
+//! command is an unfiltered nextest run (see `is_shardable_nextest_command`),
+//! [`acquire_shard_workers`] asks the daemon for more free workers without
+//! queueing, up to `selection.max_test_shards` in total. With two or more
+//! workers, [`run_sharded_tests`] rewrites the command into one
+//! `--partition count:i/N` invocation per worker, runs them concurrently
+//! through `execute_remote_compilation` with `[<worker-id>]` output prefixes,
+//! releases every worker, and aggregates the exit codes (the run fails if any
+//! shard fails). Only Rust nextest is sharded today.
+
+use super::*;
+
+/// One worker (and its daemon build id) participating in a sharded run.
+pub(super) struct ShardAssignment {
+    pub(super) worker: SelectedWorker,
+    pub(super) build_id: Option<u64>,
+}
+
+/// Add `--partition count:<index>/<total>` (1-based) to a nextest command,
+/// keeping it ahead of any `--` test-binary argument separator.
+pub(super) fn nextest_partition_command(command: &str, index: usize, total: usize) -> String {
+    let partition = format!("--partition count:{}/{}", index, total);
+    match command.find(" -- ") {
+        Some(pos) => format!("{} {}{}", &command[..pos], partition, &command[pos..]),
+        None => format!("{} {}", command.trim_end(), partition),
+    }
+}
+
+/// Combine per-shard exit codes: the first non-zero code wins, otherwise 0.
+pub(super) fn aggregate_shard_exit_codes(codes: &[i32]) -> i32 {
+    codes.iter().copied().find(|code| *code != 0).unwrap_or(0)
+}
+
+/// Transfer settings for one of `total` concurrent shards.
+///
+/// `transfer.bwlimit_kbps` caps a single rsync, so it is split across the
+/// shards' concurrent syncs to keep the combined rate within the limit.
+pub(super) fn shard_transfer_config(transfer: &TransferConfig, total: usize) -> TransferConfig {
+    let mut config = transfer.clone();
+    if let Some(limit) = config.bwlimit_kbps.filter(|limit| *limit > 0) {
+        config.bwlimit_kbps = Some(limit.div_ceil(total.max(1) as u64));
+    }
+    config
+}
+
+/// Reserve additional free workers for a sharded run.
+///
+/// Never queues: stops at the first selection that returns no worker, so the
+/// run degrades to fewer shards (or none) instead of waiting. The daemon skips
+/// workers already building this project, so each reservation is distinct; a
+/// duplicate is released defensively and ends the search.
+#[allow(clippy::too_many_arguments)] // Mirrors query_daemon's parameter list
+pub(super) async fn acquire_shard_workers(
+    config: &rch_common::RchConfig,
+    first: &SelectedWorker,
+    project: &str,
+    cores: u32,
+    command: &str,
+    toolchain: Option<&ToolchainInfo>,
+    required_runtime: RequiredRuntime,
+    command_priority: CommandPriority,
+    preferred_workers: &[WorkerId],
+    allowed_workers: &[WorkerId],
+) -> Vec<ShardAssignment> {
+    let max_shards = config.selection.max_test_shards.max(1) as usize;
+    let socket_path = &config.general.socket_path;
+    let mut extra: Vec<ShardAssignment> = Vec::new();
+
+    while extra.len() + 1 < max_shards {
+        let response = match query_daemon(
+            socket_path,
+            project,
+            cores,
+            command,
+            toolchain,
+            required_runtime,
+            command_priority,
+            0,
+            Some(std::process::id()),
+            false,
+            preferred_workers,
+            config.transfer.fleet.as_deref(),
+            config.transfer.min_free_disk_requirement(),
+            allowed_workers,
+            None,
+        )
+        .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                debug!("Stopping shard worker acquisition: {}", e);
+                break;
+            }
+        };
+        let Some(worker) = response.worker else {
+            debug!("No further shard workers free ({})", response.reason);
+            break;
+        };
+        if worker.id == first.id || extra.iter().any(|shard| shard.worker.id == worker.id) {
+            if let Err(e) = release_worker(
+                socket_path,
+                &worker.id,
+                cores,
+                response.build_id,
+                None,
+                None,
+                None,
+                None,
+                &[],
+                None,
+                None,
+            )
+            .await
+            {
+                warn!("Failed to release duplicate shard worker: {}", e);
+            }
+            break;
+        }
+        extra.push(ShardAssignment {
+            worker,
+            build_id: response.build_id,
+        });
+    }
+
+    extra
+}
+
+/// Run a nextest command as one partition per shard and return the exit code.
+///
+/// Pipeline errors on any shard (sync failure, transfer skip, toolchain
+/// missing) fall back to running the whole command locally, except SSH
+/// timeouts, which fail closed exactly like the single-worker path. Every
+/// shard gets `env_allowlist`, the allowlist already extended with the
+/// forwarded build env.
+#[allow(clippy::too_many_arguments)] // Pipeline wiring favors explicit params
+pub(super) async fn run_sharded_tests(
+    shards: &[ShardAssignment],
+    command: &str,
+    remote_command: &str,
+    env_allowlist: &[String],
+    forwarded_cargo_target_dir: Option<PathBuf>,
+    config: &rch_common::RchConfig,
+    toolchain: Option<&ToolchainInfo>,
+    kind: Option<CompilationKind>,
+    reporter: &HookReporter,
+    topology_policy: &PathTopologyPolicy,
+    project: &str,
+    cores: u32,
+) -> i32 {
+    let total = shards.len();
+    reporter.verbose(&format!(
+        "[RCH] sharding tests across {} workers: {}",
+        total,
+        shards
+            .iter()
+            .map(|shard| shard.worker.id.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    ));
+
+    let transfer_config = shard_transfer_config(&config.transfer, total);
+    let remote_start = Instant::now();
+    let runs = shards.iter().enumerate().map(|(idx, shard)| {
+        let shard_command = nextest_partition_command(remote_command, idx + 1, total);
+        let forwarded = forwarded_cargo_target_dir.clone();
+        let transfer_config = &transfer_config;
+        async move {
+            execute_remote_compilation(
+                &shard.worker,
+                &shard_command,
+                transfer_config.clone(),
+                env_allowlist.to_vec(),
+                forwarded,
+                &config.compilation,
+                &config.self_healing,
+                toolchain,
+                kind,
+                reporter,
+                &config.general.socket_path,
+                &config.output,
+                &config.telemetry,
+                shard.build_id,
+                topology_policy,
+                RemoteOutput::Prefixed(shard.worker.id.as_str()),
+            )
+            .await
+        }
+    });
+    let results = futures::future::join_all(runs).await;
+    let remote_elapsed = remote_start.elapsed();
+
+    for (shard, result) in shards.iter().zip(&results) {
+        let release_timing = result.as_ref().ok().map(|ok| {
+            let mut timing = ok.timing.clone();
+            timing.total = Some(remote_elapsed);
+            timing
+        });
+        let exit_code = result
+            .as_ref()
+            .map(|ok| ok.exit_code)
+            .unwrap_or(EXIT_BUILD_ERROR);
+        if let Err(e) = release_worker(
+            &config.general.socket_path,
+            &shard.worker.id,
+            cores,
+            shard.build_id,
+            Some(exit_code),
+            None,
+            result.as_ref().ok().map(|ok| ok.bytes_transferred),
+            release_timing.as_ref(),
+            &release_decision_trace(DecisionTrace::new(), result),
+            None,
+            None,
+        )
+        .await
+        {
+            warn!("Failed to release shard worker {}: {}", shard.worker.id, e);
+        }
+    }
+
+    let mut exit_codes = Vec::with_capacity(total);
+    let mut pipeline_failed = false;
+    for (idx, (shard, result)) in shards.iter().zip(&results).enumerate() {
+        match result {
+            Ok(result)
+                if classify_exit(result.exit_code, &result.stderr).should_fall_back_locally() =>
+            {
+                warn!(
+                    "Shard {}/{} hit a toolchain failure on {}",
+                    idx + 1,
+                    total,
+                    shard.worker.id
+                );
+                pipeline_failed = true;
+            }
+            Ok(result) => exit_codes.push(result.exit_code),
+            Err(e) => {
+                if classify_remote_pipeline_failure(e)
+                    == RemotePipelineFailurePolicy::FailClosedNoLocalFallback
+                {
+                    warn!(
+                        "Shard {}/{} failed on {} with SSH timeout; refusing local fallback: {}",
+                        idx + 1,
+                        total,
+                        shard.worker.id,
+                        e
+                    );
+                    reporter.summary(&remote_pipeline_failure_summary(&shard.worker.id));
+                    return EXIT_BUILD_ERROR;
+                }
+                warn!(
+                    "Shard {}/{} failed on {}: {}",
+                    idx + 1,
+                    total,
+                    shard.worker.id,
+                    e
+                );
+                pipeline_failed = true;
+            }
+        }
+    }
+
+    if pipeline_failed {
+        reporter.summary("[RCH] local (sharded remote execution failed)");
+        exit_with_timed_local_fallback(
+            command,
+            reporter,
+            "sharded remote execution failed",
+            Some((project, kind)),
+        );
+    }
+
+    let exit_code = aggregate_shard_exit_codes(&exit_codes);
+    if exit_code == 0 {
+        record_build_timing(project, kind, remote_elapsed.as_millis() as u64, true);
+        reporter.summary(&format!(
+            "[RCH] remote sharded x{} ({})",
+            total,
+            format_duration_ms(remote_elapsed)
+        ));
+        for shard in shards {
+            if let Err(e) =
+                record_build(&config.general.socket_path, &shard.worker.id, project, true).await
+            {
+                warn!("Failed to record build: {}", e);
+            }
+        }
+    } else {
+        let failed = shards
+            .iter()
+            .zip(&exit_codes)
+            .filter(|(_, code)| **code != 0)
+            .map(|(shard, _)| shard.worker.id.as_str())
+            .collect::<Vec<_>>();
+        reporter.summary(&format!(
+            "[RCH] remote sharded x{} failed (exit {} on {})",
+            total,
+            exit_code,
+            failed.join(", ")
+        ));
+    }
+    exit_code
+}
@@ -6,21 +6,27 @@ use super::artifact_patterns::{
     get_artifact_patterns, get_custom_target_artifact_patterns,
     kind_produces_transferable_artifacts,
 };
+use super::cargo_run::{elf_interpreter, loader_failure, plan_local_run};
 use super::cargo_target_dir::{
-    extract_cargo_target_dir_from_command_tokens, feature_set_for_command,
-    parse_stale_target_reap_idle_hours, remote_cargo_pooled_target_dir_name,
-    remote_cargo_target_dir_name, resolve_forwarded_cargo_target_dir_with_lookup,
+    BUILD_AFFECTING_ENV_VARS, extract_cargo_target_dir_from_command_tokens,
+    feature_set_for_command, parse_stale_target_reap_idle_hours,
+    remote_cargo_pooled_target_dir_name, remote_cargo_target_dir_name, remote_sccache_env,
+    remote_shared_build_dir, resolve_forwarded_build_env_with_lookup,
+    resolve_forwarded_cargo_target_dir_with_lookup,
     strip_cargo_target_dir_assignments_from_command_tokens,
     strip_cargo_target_dir_flags_from_command_tokens, target_reuse_disabled_from_value,
     target_triple_for_command,
 };
+use super::cargo_vendor::{command_is_offline, vendored_source_dir};
 use super::command_parsing::{
-    has_exact_flag, has_ignored_only_flag, is_filtered_test_command, parse_jobs_flag,
-    parse_test_threads,
+    BUILD_TOOL_JOBS_SLOT_CAP, BuildToolJobs, command_produces_machine_stdout, has_exact_flag,
+    has_ignored_only_flag, has_timings_flag, is_filtered_test_command, nextest_profile,
+    parse_build_tool_jobs, parse_jobs_flag, parse_test_threads,
 };
 use super::daemon_ipc::{
     DEFAULT_DAEMON_RESPONSE_TIMEOUT_SECS, DEFAULT_DAEMON_WAIT_RESPONSE_TIMEOUT_SECS,
-    daemon_response_timeout_for, queue_when_busy_enabled_from, urlencoding_encode,
+    build_exec_idempotency_key, daemon_response_timeout_for, queue_when_busy_enabled_from,
+    urlencoding_encode, well_known_socket_paths,
 };
 use super::dependency_closure::{
     DEPENDENCY_PREFLIGHT_CODE_MISSING, DEPENDENCY_PREFLIGHT_CODE_STALE,
@@ -31,20 +37,54 @@ use super::dependency_closure::{
     build_sync_closure_manifest, build_sync_closure_plan, canonicalize_sync_root_for_plan,
     cargo_package_source_entrypoints, cargo_workspace_member_source_entrypoints,
     dependency_preflight_checks_for_entry, is_within_sync_topology,
-    parse_dependency_preflight_probe_output, synced_dependency_preflight_checks,
-    verify_remote_dependency_manifests,
+    parse_dependency_preflight_probe_output, push_vendor_sync_entry,
+    synced_dependency_preflight_checks, verify_remote_dependency_manifests,
 };
+use super::diff_artifacts::{
+    ArtifactComparison, collect_artifacts, compare_artifact, diff_target_dirs, home_build_markers,
+};
+use super::doc_open::{doc_index_path, local_doc_open_enabled, strip_doc_open_flag};
+use super::feature_parity::{
+    explicit_feature_flags, is_feature_affecting_env_var, unforwarded_feature_env_vars,
+};
+use super::hook_server::{
+    HookServerRequest, HookServerResponse, answer_hook_request, forward_to_socket,
+    serve_hook_connection, socket_is_private,
+};
+use super::offload_consent::{
+    ConsentAnswer, OffloadConsent, confirm_offload_with, parse_consent_answer, prompt_allowed,
+};
+use super::output_capture::{OutputCapture, build_logs_in, prune_build_logs_in};
+use super::pipeline_selftest::selftest_project_base;
+use super::project_size::{
+    evaluate_project_size_cap, evaluate_project_size_gate, measure_project_size, resolve_size_root,
+};
+use super::remote_color::{remote_color_mode, with_color_flag};
 use super::repo_updater::{
     auto_tune_repo_updater_contract, build_repo_sync_idempotency_key_for_command,
     collect_repo_updater_roots_and_specs, hydrate_repo_updater_auth_context_defaults,
     infer_repo_updater_auth_context_with_env_lookup, repo_updater_command_name,
 };
+use super::shadow_mode::{
+    ShadowDivergence, ShadowRemoteOutcome, shadow_divergence, shadow_transfer_config,
+};
+use super::slow_build_notify::{SlowBuildNotice, notice_env, should_notify};
+use super::test_sharding::{
+    aggregate_shard_exit_codes, nextest_partition_command, shard_transfer_config,
+};
 use super::timing_history::{
-    MAX_TIMING_SAMPLES, ProjectTimingData, TimingEstimate, TimingHistory, TimingRecord,
-    estimate_timing_for_build, record_build_timing, timing_cache,
+    MAX_TIMING_SAMPLES, ProjectTimingData, TimingEstimate, TimingGateDecision, TimingHistory,
+    TimingRecord, corrupt_timing_history_path, estimate_timing_for_build,
+    estimate_timing_from_history, record_build_timing, timing_cache,
+};
+use super::transfer_orchestration::{
+    rustc_parity_skip_reason, sync_trace_steps, wrap_command_with_telemetry,
 };
-use super::transfer_orchestration::wrap_command_with_telemetry;
+use super::transfer_slots::{acquire_transfer_slot_in, transfer_slot_path};
 use proptest::prelude::*;
+use rch_common::command_outcome::{
+    EXIT_SIGNAL_BASE, EXIT_SUCCESS, EXIT_TEST_FAILURES, is_signal_killed, is_toolchain_failure,
+};
 use rch_common::mock::{
     self, MockConfig, MockRsyncConfig, clear_mock_overrides, set_mock_enabled_override,
     set_mock_rsync_config_override, set_mock_ssh_config_override,
@@ -407,6 +447,7 @@ async fn test_non_bash_allowed() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -422,6 +463,7 @@ async fn test_non_compilation_allowed() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -439,6 +481,7 @@ async fn test_process_hook_allows_beads_comment_with_embedded_build_text() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -458,6 +501,7 @@ async fn test_process_hook_allows_env_prefixed_beads_comment_with_embedded_build
                 description: None,
             },
             session_id: None,
+            tool_use_id: None,
         };
 
     let output = process_hook(input).await;
@@ -477,6 +521,7 @@ async fn test_process_hook_bypasses_classification_cache_without_env_flag() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -501,6 +546,7 @@ async fn test_compilation_detected() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     // Without daemon, should fail-open and allow local execution
@@ -561,139 +607,1525 @@ fn test_timing_gating_thresholds_default() {
     assert!((config.remote_speedup_threshold - 1.2).abs() < 0.001);
 }
 
+fn seeded_timing_history(project: &str, local_ms: &[u64], remote_ms: &[u64]) -> TimingHistory {
+    let mut history = TimingHistory::default();
+    for &ms in local_ms {
+        history.record(project, Some(CompilationKind::CargoBuild), ms, false);
+    }
+    for &ms in remote_ms {
+        history.record(project, Some(CompilationKind::CargoBuild), ms, true);
+    }
+    history
+}
+
+fn gate_for(history: &TimingHistory, config: &rch_common::CompilationConfig) -> TimingGateDecision {
+    let estimate =
+        estimate_timing_from_history(history, "gate-project", Some(CompilationKind::CargoBuild));
+    evaluate_timing_gate(estimate.as_ref(), config)
+}
+
 #[test]
-fn test_urlencoding_encode_basic() {
+fn test_timing_gate_offloads_without_history() {
     let _guard = test_guard!();
-    assert_eq!(urlencoding_encode("hello world"), "hello%20world");
-    assert_eq!(urlencoding_encode("path/to/file"), "path%2Fto%2Ffile");
-    assert_eq!(urlencoding_encode("foo:bar"), "foo%3Abar");
+    let config = rch_common::CompilationConfig::default();
+    let history = TimingHistory::default();
+    assert_eq!(gate_for(&history, &config), TimingGateDecision::Offload);
+    assert_eq!(
+        evaluate_timing_gate(None, &config),
+        TimingGateDecision::Offload
+    );
 }
 
 #[test]
-fn test_urlencoding_encode_special_chars() {
+fn test_timing_gate_offloads_with_remote_only_history() {
     let _guard = test_guard!();
-    assert_eq!(urlencoding_encode("a&b=c"), "a%26b%3Dc");
-    assert_eq!(urlencoding_encode("100%"), "100%25");
-    assert_eq!(urlencoding_encode("hello+world"), "hello%2Bworld");
+    let config = rch_common::CompilationConfig::default();
+    // Remote samples alone cannot predict the local cost: fail open.
+    let history = seeded_timing_history("gate-project", &[], &[100, 120]);
+    assert_eq!(gate_for(&history, &config), TimingGateDecision::Offload);
+}
+
+fn size_gate_config(min_files: Option<u64>, min_bytes: Option<u64>) -> rch_common::RchConfig {
+    let mut config = rch_common::RchConfig::default();
+    config.compilation.min_project_files = min_files;
+    config.compilation.min_project_bytes = min_bytes;
+    config
 }
 
 #[test]
-fn test_urlencoding_encode_no_encoding_needed() {
+fn test_project_size_gate_off_by_default() {
     let _guard = test_guard!();
-    assert_eq!(urlencoding_encode("simple"), "simple");
+    let dir = tempfile::TempDir::new().unwrap();
+    let config = rch_common::RchConfig::default();
     assert_eq!(
-        urlencoding_encode("with-dash_underscore.dot~tilde"),
-        "with-dash_underscore.dot~tilde"
+        evaluate_project_size_gate(dir.path(), Some(CompilationKind::CargoBuild), &config),
+        None
     );
-    assert_eq!(urlencoding_encode("ABC123"), "ABC123");
 }
 
 #[test]
-fn test_urlencoding_encode_unicode() {
+fn test_project_size_gate_keeps_tiny_project_local() {
     let _guard = test_guard!();
-    // Unicode characters should be encoded as UTF-8 bytes
-    let encoded = urlencoding_encode("café");
-    assert!(encoded.contains("%")); // 'é' should be encoded
-    assert!(encoded.starts_with("caf")); // ASCII part preserved
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join("Cargo.toml"), "[package]\n").unwrap();
+    std::fs::create_dir(dir.path().join("src")).unwrap();
+    std::fs::write(dir.path().join("src/main.rs"), "fn main() {}\n").unwrap();
+    // Build output is excluded from sync, so it does not make a project big.
+    std::fs::create_dir_all(dir.path().join("target/debug")).unwrap();
+    for i in 0..10 {
+        std::fs::write(dir.path().join(format!("target/debug/{i}.o")), "x").unwrap();
+    }
+
+    let config = size_gate_config(Some(5), None);
+    let detail = evaluate_project_size_gate(dir.path(), Some(CompilationKind::CargoBuild), &config);
+    assert_eq!(detail.as_deref(), Some("2 files < min_project_files 5"));
+
+    let config = size_gate_config(None, Some(1024));
+    let detail = evaluate_project_size_gate(dir.path(), Some(CompilationKind::CargoBuild), &config)
+        .expect("below min_project_bytes");
+    assert!(detail.contains("< min_project_bytes 1024"), "{detail}");
+
+    // Test kinds are exempt by default: dependency compiles can dominate.
+    let config = size_gate_config(Some(5), None);
+    assert_eq!(
+        evaluate_project_size_gate(dir.path(), Some(CompilationKind::CargoTest), &config),
+        None
+    );
 }
 
 #[test]
-fn test_parse_jobs_flag_variants() {
+fn test_resolve_size_root_prefers_enclosing_workspace() {
     let _guard = test_guard!();
-    assert_eq!(parse_jobs_flag("cargo build -j 8"), Some(8));
-    assert_eq!(parse_jobs_flag("cargo build -j8"), Some(8));
-    assert_eq!(parse_jobs_flag("cargo build --jobs 4"), Some(4));
-    assert_eq!(parse_jobs_flag("cargo build --jobs=12"), Some(12));
-    assert_eq!(parse_jobs_flag("cargo build -j=16"), Some(16));
-    assert_eq!(parse_jobs_flag("cargo build --jobs=12"), Some(12));
-    assert_eq!(parse_jobs_flag("cargo build -j"), None);
-    assert_eq!(parse_jobs_flag("cargo build --jobs"), None);
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+    std::fs::write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"app\"]\n",
+    )
+    .unwrap();
+    std::fs::create_dir_all(root.join("app/src")).unwrap();
+    std::fs::write(root.join("app/Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+    std::fs::create_dir_all(root.join("solo/src")).unwrap();
+
+    assert_eq!(resolve_size_root(&root.join("app/src")), root);
+    assert_eq!(resolve_size_root(&root.join("app")), root);
+
+    // Outside any workspace: the nearest package root, else the cwd itself.
+    let other = tempfile::TempDir::new().unwrap();
+    std::fs::create_dir_all(other.path().join("crate/src")).unwrap();
+    std::fs::write(other.path().join("crate/Cargo.toml"), "[package]\n").unwrap();
+    assert_eq!(
+        resolve_size_root(&other.path().join("crate/src")),
+        other.path().join("crate")
+    );
+    std::fs::create_dir(other.path().join("loose")).unwrap();
+    assert_eq!(
+        resolve_size_root(&other.path().join("loose")),
+        other.path().join("loose")
+    );
 }
 
 #[test]
-fn test_parse_test_threads_variants() {
+fn test_project_size_gate_offloads_large_project_and_stops_early() {
     let _guard = test_guard!();
+    let dir = tempfile::TempDir::new().unwrap();
+    for i in 0..20 {
+        std::fs::write(dir.path().join(format!("{i}.rs")), "fn f() {}\n").unwrap();
+    }
+    let config = size_gate_config(Some(5), Some(16));
     assert_eq!(
-        parse_test_threads("cargo test -- --test-threads=4"),
-        Some(4)
-    );
-    assert_eq!(
-        parse_test_threads("cargo test -- --test-threads 2"),
-        Some(2)
+        evaluate_project_size_gate(dir.path(), Some(CompilationKind::CargoBuild), &config),
+        None
     );
-    assert_eq!(parse_test_threads("cargo test"), None);
+    let size = measure_project_size(dir.path(), &[], 5, 16);
+    assert_eq!(size.files, 5, "walk stops once both thresholds are met");
 }
 
 #[test]
-fn test_estimate_cores_for_command() {
+fn test_project_size_cap_counts_whole_workspace_from_member() {
     let _guard = test_guard!();
-    let config = rch_common::CompilationConfig {
-        build_slots: 6,
-        test_slots: 10,
-        check_slots: 3,
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+    std::fs::write(
+        root.join("Cargo.toml"),
+        "[workspace]\nmembers = [\"app\"]\n",
+    )
+    .unwrap();
+    std::fs::write(root.join("assets.bin"), vec![0u8; 4096]).unwrap();
+    std::fs::create_dir_all(root.join("app/src")).unwrap();
+    std::fs::write(root.join("app/Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+
+    let transfer = rch_common::TransferConfig {
+        max_project_bytes: Some(1024),
         ..Default::default()
     };
+    let member = root.join("app");
+    assert_eq!(evaluate_project_size_cap(&member, &transfer), None);
+    assert!(evaluate_project_size_cap(&resolve_size_root(&member), &transfer).is_some());
+}
 
-    let build =
-        estimate_cores_for_command(Some(CompilationKind::CargoBuild), "cargo build", &config);
-    assert_eq!(build, 6);
+#[test]
+fn test_project_size_cap_rejects_oversized_project() {
+    let _guard = test_guard!();
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join("big.bin"), vec![0u8; 4096]).unwrap();
+    // Excluded build output does not count towards the cap.
+    std::fs::create_dir(dir.path().join("target")).unwrap();
+    std::fs::write(dir.path().join("target/huge.rlib"), vec![0u8; 65536]).unwrap();
 
-    let build_jobs = estimate_cores_for_command(
-        Some(CompilationKind::CargoBuild),
-        "cargo build -j 12",
-        &config,
+    let mut transfer = rch_common::TransferConfig::default();
+    assert_eq!(evaluate_project_size_cap(dir.path(), &transfer), None);
+
+    transfer.max_project_bytes = Some(8192);
+    assert_eq!(evaluate_project_size_cap(dir.path(), &transfer), None);
+
+    transfer.max_project_bytes = Some(1024);
+    let detail = evaluate_project_size_cap(dir.path(), &transfer).expect("over the cap");
+    assert!(
+        detail.contains("raise transfer.max_project_bytes"),
+        "{detail}"
     );
-    assert_eq!(build_jobs, 12);
+}
 
-    let test_default =
-        estimate_cores_for_command(Some(CompilationKind::CargoTest), "cargo test", &config);
-    assert_eq!(test_default, 10);
+#[test]
+fn test_first_offload_detected_from_remote_samples() {
+    let _guard = test_guard!();
+    let history = seeded_timing_history("consent-project", &[5000], &[]);
+    assert!(!history.has_remote_samples("consent-project"));
 
-    let test_threads = estimate_cores_for_command(
-        Some(CompilationKind::CargoTest),
-        "cargo test -- --test-threads=4",
-        &config,
+    let history = seeded_timing_history("consent-project", &[], &[1200]);
+    assert!(history.has_remote_samples("consent-project"));
+    // Project ids are matched whole, not by prefix.
+    assert!(!history.has_remote_samples("consent"));
+    assert!(!history.has_remote_samples("consent-project-2"));
+}
+
+#[test]
+fn test_offload_consent_answer_defaults_to_no() {
+    let _guard = test_guard!();
+    assert!(parse_consent_answer("y\n"));
+    assert!(parse_consent_answer(" YES \n"));
+    assert!(!parse_consent_answer("\n"));
+    assert!(!parse_consent_answer(""));
+    assert!(!parse_consent_answer("n\n"));
+    assert!(!parse_consent_answer("yep\n"));
+}
+
+#[test]
+fn test_offload_consent_prompt_skipped_in_machine_contexts() {
+    let _guard = test_guard!();
+    use crate::ui::context::{OutputConfig, OutputContext};
+    use crate::ui::writer::SharedOutputBuffer;
+
+    let context = |config: OutputConfig, is_tty: bool| {
+        OutputContext::with_writers(
+            config,
+            SharedOutputBuffer::new().as_writer(is_tty),
+            SharedOutputBuffer::new().as_writer(is_tty),
+        )
+    };
+    let interactive = OutputConfig {
+        color: crate::ui::context::ColorChoice::Always,
+        ..OutputConfig::default()
+    };
+    assert!(prompt_allowed(&context(interactive.clone(), true), false));
+    assert!(!prompt_allowed(&context(interactive.clone(), true), true));
+    assert!(!prompt_allowed(&context(interactive, false), false));
+    let json = OutputConfig {
+        json: true,
+        ..OutputConfig::default()
+    };
+    assert!(!prompt_allowed(&context(json, true), false));
+}
+
+#[test]
+fn test_offload_consent_fails_closed_without_terminal() {
+    let _guard = test_guard!();
+    use crate::ui::context::{OutputConfig, OutputContext};
+    use crate::ui::writer::SharedOutputBuffer;
+
+    let context = |is_tty: bool| {
+        OutputContext::with_writers(
+            OutputConfig {
+                color: crate::ui::context::ColorChoice::Always,
+                ..OutputConfig::default()
+            },
+            SharedOutputBuffer::new().as_writer(is_tty),
+            SharedOutputBuffer::new().as_writer(is_tty),
+        )
+    };
+    let never_asked = |_: &str| -> io::Result<String> { panic!("prompted without a TTY") };
+
+    // Piped output or CI: no prompt, and the build is not offloaded.
+    assert_eq!(
+        confirm_offload_with(&context(false), false, "Offload?", never_asked),
+        ConsentAnswer::Unanswered
     );
-    assert_eq!(test_threads, 4);
+    assert_eq!(
+        confirm_offload_with(&context(true), true, "Offload?", never_asked),
+        ConsentAnswer::Unanswered
+    );
+    // A terminal that cannot be opened or read is unanswered too.
+    assert_eq!(
+        confirm_offload_with(&context(true), false, "Offload?", |_| Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "no /dev/tty"
+        ))),
+        ConsentAnswer::Unanswered
+    );
+    assert_eq!(
+        confirm_offload_with(&context(true), false, "Offload?", |_| Ok("y\n".into())),
+        ConsentAnswer::Accepted
+    );
+    assert_eq!(
+        confirm_offload_with(&context(true), false, "Offload?", |_| Ok("\n".into())),
+        ConsentAnswer::Declined
+    );
+}
 
-    let test_jobs = estimate_cores_for_command(
-        Some(CompilationKind::CargoTest),
-        "cargo test -j 1 -p rchd --lib",
-        &config,
+#[test]
+fn test_offload_consent_store_roundtrip() {
+    let _guard = test_guard!();
+    let consent: OffloadConsent =
+        serde_json::from_str(r#"{"declined": ["alpha-1234"]}"#).expect("parse consent");
+    assert!(consent.is_declined("alpha-1234"));
+    assert!(!consent.is_declined("beta-5678"));
+    let empty: OffloadConsent = serde_json::from_str("{}").expect("parse empty consent");
+    assert!(empty.declined.is_empty());
+}
+
+#[test]
+fn test_pipeline_selftest_project_lives_under_canonical_root() {
+    let _guard = test_guard!();
+    let policy = PathTopologyPolicy::new(PathBuf::from("/data/projects"), PathBuf::from("/dp"));
+    let base = selftest_project_base(&policy, 4242);
+    assert_eq!(base, PathBuf::from("/data/projects/.rch-selftest-4242"));
+    assert!(base.starts_with(policy.canonical_root()));
+}
+
+fn split_tokens(command: &str) -> Vec<String> {
+    shell_words::split(command).expect("split command")
+}
+
+#[test]
+fn test_strip_doc_open_flag_only_rewrites_doc_open() {
+    let _guard = test_guard!();
+    let doc = Some(CompilationKind::CargoDoc);
+    assert_eq!(
+        strip_doc_open_flag(doc, &split_tokens("cargo doc --open --no-deps -p my-crate")),
+        Some(split_tokens("cargo doc --no-deps -p my-crate"))
     );
-    assert_eq!(test_jobs, 1);
+    assert_eq!(
+        strip_doc_open_flag(doc, &split_tokens("cargo doc --no-deps")),
+        None
+    );
+    assert_eq!(
+        strip_doc_open_flag(doc, &split_tokens("cargo doc -- --open")),
+        None
+    );
+    assert_eq!(
+        strip_doc_open_flag(
+            Some(CompilationKind::CargoBuild),
+            &split_tokens("cargo build --open")
+        ),
+        None
+    );
+}
 
-    let test_long_jobs = estimate_cores_for_command(
-        Some(CompilationKind::CargoTest),
-        "cargo test --jobs=3 -p rchd --lib",
-        &config,
+#[test]
+fn test_doc_open_target_dir_defaults_to_manifest_target() {
+    let _guard = test_guard!();
+    let manifest_dir = Path::new("/data/projects/app");
+    assert_eq!(
+        local_target_dir_or_default(None, manifest_dir),
+        PathBuf::from("/data/projects/app/target")
+    );
+    assert_eq!(
+        local_target_dir_or_default(Some(Path::new("/tmp/shared-target")), manifest_dir),
+        PathBuf::from("/tmp/shared-target")
     );
-    assert_eq!(test_long_jobs, 3);
 
-    let test_jobs_override_threads = estimate_cores_for_command(
-        Some(CompilationKind::CargoTest),
-        "cargo test -j 1 -- --test-threads=8",
-        &config,
+    // Docs retrieved into the default target dir are found there.
+    let tmp = tempfile::tempdir().unwrap();
+    let index = tmp.path().join("target/doc/my_crate/index.html");
+    std::fs::create_dir_all(index.parent().unwrap()).unwrap();
+    std::fs::write(&index, "<html></html>").unwrap();
+    let target_dir = local_target_dir_or_default(None, tmp.path());
+    assert_eq!(
+        doc_index_path(
+            &target_dir.join("doc"),
+            &split_tokens("cargo doc --open"),
+            tmp.path()
+        ),
+        Some(index)
     );
-    assert_eq!(test_jobs_override_threads, 1);
+}
 
-    let test_build_jobs_env = estimate_cores_for_command(
-        Some(CompilationKind::CargoTest),
-        "CARGO_BUILD_JOBS=2 cargo test -p rchd --lib",
-        &config,
+#[test]
+fn test_local_doc_open_respects_quiet_and_machine_output() {
+    let _guard = test_guard!();
+    let env = |vars: &'static [(&'static str, &'static str)]| {
+        move |key: &str| {
+            vars.iter()
+                .find(|(name, _)| *name == key)
+                .map(|(_, value)| value.to_string())
+        }
+    };
+    assert!(local_doc_open_enabled(OutputVisibility::Summary, env(&[])));
+    assert!(local_doc_open_enabled(
+        OutputVisibility::Summary,
+        env(&[("RCH_JSON", "0")])
+    ));
+    assert!(!local_doc_open_enabled(OutputVisibility::None, env(&[])));
+    assert!(!local_doc_open_enabled(
+        OutputVisibility::Verbose,
+        env(&[("RCH_JSON", "1")])
+    ));
+    assert!(!local_doc_open_enabled(
+        OutputVisibility::Summary,
+        env(&[("RCH_OUTPUT_FORMAT", "toon")])
+    ));
+}
+
+#[test]
+fn test_doc_index_path_prefers_package_then_manifest() {
+    let _guard = test_guard!();
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let doc_dir = tmp.path().join("target").join("doc");
+    for krate in ["alpha", "my_app", "util_lib"] {
+        std::fs::create_dir_all(doc_dir.join(krate)).expect("create crate doc dir");
+        std::fs::write(doc_dir.join(krate).join("index.html"), "").expect("write index");
+    }
+    std::fs::write(
+        tmp.path().join("Cargo.toml"),
+        "[package]\nname = \"my-app\"\nversion = \"0.1.0\"\n",
+    )
+    .expect("write manifest");
+
+    assert_eq!(
+        doc_index_path(&doc_dir, &split_tokens("cargo doc -p util-lib"), tmp.path()),
+        Some(doc_dir.join("util_lib").join("index.html"))
     );
-    assert_eq!(test_build_jobs_env, 2);
+    assert_eq!(
+        doc_index_path(&doc_dir, &split_tokens("cargo doc --no-deps"), tmp.path()),
+        Some(doc_dir.join("my_app").join("index.html"))
+    );
+    // No manifest package: fall back to the first documented crate.
+    let elsewhere = tempfile::tempdir().expect("tempdir");
+    assert_eq!(
+        doc_index_path(&doc_dir, &split_tokens("cargo doc"), elsewhere.path()),
+        Some(doc_dir.join("alpha").join("index.html"))
+    );
+    assert_eq!(
+        doc_index_path(
+            &elsewhere.path().join("doc"),
+            &split_tokens("cargo doc"),
+            tmp.path()
+        ),
+        None
+    );
+}
 
-    let test_env = estimate_cores_for_command(
-        Some(CompilationKind::CargoTest),
-        "RUST_TEST_THREADS=3 cargo test",
-        &config,
+fn write_run_manifest(dir: &Path, manifest: &str, main_rs: bool) {
+    std::fs::write(dir.join("Cargo.toml"), manifest).expect("write manifest");
+    if main_rs {
+        std::fs::create_dir_all(dir.join("src")).expect("create src");
+        std::fs::write(dir.join("src").join("main.rs"), "fn main() {}\n").expect("write main");
+    }
+}
+
+#[test]
+fn test_plan_local_run_builds_remotely_and_keeps_program_args() {
+    let _guard = test_guard!();
+    let tmp = tempfile::tempdir().expect("tempdir");
+    write_run_manifest(
+        tmp.path(),
+        "[package]\nname = \"my-app\"\nversion = \"0.1.0\"\n",
+        true,
+    );
+
+    let plan = plan_local_run(
+        &split_tokens("RUST_LOG=debug cargo run --release --features cli -- --port 8080 -v"),
+        tmp.path(),
+    )
+    .expect("plannable");
+    assert_eq!(
+        plan.build_tokens,
+        split_tokens("RUST_LOG=debug cargo build --release --features cli --bin my-app")
+    );
+    assert_eq!(plan.binary, PathBuf::from("release/my-app"));
+    assert_eq!(plan.run_args, split_tokens("--port 8080 -v"));
+    assert_eq!(
+        plan.env,
+        vec![("RUST_LOG".to_string(), "debug".to_string())]
+    );
+
+    let example = plan_local_run(&split_tokens("cargo r --example demo"), tmp.path())
+        .expect("example plannable");
+    assert_eq!(
+        example.build_tokens,
+        split_tokens("cargo build --example demo")
+    );
+    assert_eq!(example.binary, PathBuf::from("debug/examples/demo"));
+    assert!(example.run_args.is_empty());
+
+    let default_run = tempfile::tempdir().expect("tempdir");
+    write_run_manifest(
+        default_run.path(),
+        "[package]\nname = \"tools\"\nversion = \"0.1.0\"\ndefault-run = \"serve\"\n\n\
+         [[bin]]\nname = \"serve\"\npath = \"src/serve.rs\"\n\n\
+         [[bin]]\nname = \"migrate\"\npath = \"src/migrate.rs\"\n",
+        false,
+    );
+    let plan = plan_local_run(&split_tokens("cargo +nightly run"), default_run.path())
+        .expect("default-run plannable");
+    assert_eq!(
+        plan.build_tokens,
+        split_tokens("cargo +nightly build --bin serve")
+    );
+    assert_eq!(plan.binary, PathBuf::from("debug/serve"));
+}
+
+#[test]
+fn test_plan_local_run_keeps_ambiguous_runs_local() {
+    let _guard = test_guard!();
+    let tmp = tempfile::tempdir().expect("tempdir");
+    write_run_manifest(
+        tmp.path(),
+        "[package]\nname = \"my-app\"\nversion = \"0.1.0\"\n\n\
+         [[bin]]\nname = \"helper\"\npath = \"src/helper.rs\"\n",
+        true,
+    );
+    let workspace = tempfile::tempdir().expect("tempdir");
+    write_run_manifest(workspace.path(), "[workspace]\nmembers = [\"a\"]\n", false);
+
+    for (command, dir) in [
+        ("cargo run", tmp.path()),
+        ("cargo run", workspace.path()),
+        ("cargo run -p member", tmp.path()),
+        ("cargo run --bin a --target wasm32-wasip1", tmp.path()),
+        ("cargo run --bin a --profile profiling", tmp.path()),
+        (
+            "cargo run --bin a --manifest-path other/Cargo.toml",
+            tmp.path(),
+        ),
+        ("cargo run --bin a input.txt", tmp.path()),
+        ("env FOO=1 cargo run --bin a", tmp.path()),
+    ] {
+        assert!(
+            plan_local_run(&split_tokens(command), dir).is_err(),
+            "{command} should stay local"
+        );
+    }
+
+    // An explicit --bin resolves the ambiguity.
+    let plan = plan_local_run(&split_tokens("cargo run --bin helper -- x"), tmp.path())
+        .expect("explicit bin plannable");
+    assert_eq!(plan.binary, PathBuf::from("debug/helper"));
+    assert_eq!(plan.run_args, vec!["x".to_string()]);
+}
+
+#[test]
+fn test_loader_probe_only_probes_dynamic_elf_binaries() {
+    let _guard = test_guard!();
+    let tmp = tempfile::tempdir().expect("tempdir");
+
+    // Not ELF: nothing to probe.
+    let script = tmp.path().join("run.sh");
+    std::fs::write(&script, "#!/bin/sh\nexit 0\n").expect("write script");
+    assert_eq!(elf_interpreter(b"#!/bin/sh\n"), None);
+    assert_eq!(loader_failure(&script, &[]), None);
+
+    // A dynamic ELF binary whose loader is absent here cannot start.
+    let sh = std::fs::read("/bin/sh").expect("read /bin/sh");
+    if let Some(interpreter) = elf_interpreter(&sh) {
+        assert!(interpreter.is_absolute(), "{interpreter:?}");
+        assert_eq!(loader_failure(Path::new("/bin/sh"), &[]), None);
+
+        let needle = interpreter.as_os_str().as_encoded_bytes();
+        let at = sh
+            .windows(needle.len())
+            .position(|window| window == needle)
+            .expect("interpreter path in image");
+        let mut patched = sh.clone();
+        patched[at + 1] = b'X';
+        let binary = tmp.path().join("patched");
+        std::fs::write(&binary, &patched).expect("write patched binary");
+        assert!(
+            loader_failure(&binary, &[]).is_some_and(
+                |reason| reason.starts_with("loader /X") && reason.ends_with("is missing")
+            ),
+            "{:?}",
+            loader_failure(&binary, &[])
+        );
+    }
+}
+
+#[test]
+fn test_remote_color_mode_follows_config_and_context() {
+    let _guard = test_guard!();
+    use rch_common::ColorMode;
+    assert_eq!(
+        remote_color_mode(ColorMode::Always, OutputContext::Plain, false),
+        ColorMode::Always
+    );
+    assert_eq!(
+        remote_color_mode(ColorMode::Auto, OutputContext::Interactive, false),
+        ColorMode::Always
+    );
+    assert_eq!(
+        remote_color_mode(ColorMode::Auto, OutputContext::Plain, false),
+        ColorMode::Auto
+    );
+    // Machine output and CI never get forced color.
+    assert_eq!(
+        remote_color_mode(ColorMode::Always, OutputContext::Hook, false),
+        ColorMode::Auto
+    );
+    assert_eq!(
+        remote_color_mode(ColorMode::Always, OutputContext::Interactive, true),
+        ColorMode::Auto
+    );
+    assert_eq!(
+        remote_color_mode(ColorMode::Never, OutputContext::Interactive, false),
+        ColorMode::Never
+    );
+}
+
+#[test]
+fn test_with_color_flag_respects_existing_choice_and_separator() {
+    let _guard = test_guard!();
+    use rch_common::ColorMode;
+    let cargo_test = Some(CompilationKind::CargoTest);
+    assert_eq!(
+        with_color_flag(cargo_test, "cargo test -p core", ColorMode::Always),
+        "cargo test -p core --color=always"
+    );
+    assert_eq!(
+        with_color_flag(cargo_test, "cargo test -- --nocapture", ColorMode::Always),
+        "cargo test --color=always -- --nocapture"
+    );
+    assert_eq!(
+        with_color_flag(cargo_test, "cargo test --color never", ColorMode::Always),
+        "cargo test --color never"
+    );
+    assert_eq!(
+        with_color_flag(cargo_test, "cargo test --color=never", ColorMode::Always),
+        "cargo test --color=never"
+    );
+    assert_eq!(
+        with_color_flag(cargo_test, "cargo test", ColorMode::Auto),
+        "cargo test"
+    );
+    assert_eq!(
+        with_color_flag(
+            Some(CompilationKind::Gcc),
+            "gcc -c main.c",
+            ColorMode::Always
+        ),
+        "gcc -c main.c -fdiagnostics-color=always"
+    );
+    assert_eq!(
+        with_color_flag(
+            Some(CompilationKind::Clang),
+            "clang -fno-color-diagnostics -c main.c",
+            ColorMode::Always
+        ),
+        "clang -fno-color-diagnostics -c main.c"
+    );
+    assert_eq!(
+        with_color_flag(Some(CompilationKind::Make), "make -j8", ColorMode::Always),
+        "make -j8"
+    );
+}
+
+#[test]
+fn test_with_color_flag_only_rewrites_the_tool_itself() {
+    let _guard = test_guard!();
+    use rch_common::ColorMode;
+    let cargo_build = Some(CompilationKind::CargoBuild);
+    // A user rule mapping a wrapper to a cargo kind must not get cargo flags.
+    assert_eq!(
+        with_color_flag(cargo_build, "xbuild --release", ColorMode::Always),
+        "xbuild --release"
+    );
+    assert_eq!(
+        with_color_flag(cargo_build, "env FOO=1 cargo build", ColorMode::Always),
+        "env FOO=1 cargo build --color=always"
+    );
+    assert_eq!(
+        with_color_flag(cargo_build, "/usr/bin/cargo build", ColorMode::Always),
+        "/usr/bin/cargo build --color=always"
+    );
+    assert_eq!(
+        with_color_flag(
+            Some(CompilationKind::Gpp),
+            "x86_64-linux-gnu-g++-13 -c main.cc",
+            ColorMode::Always
+        ),
+        "x86_64-linux-gnu-g++-13 -c main.cc -fdiagnostics-color=always"
+    );
+    assert_eq!(
+        with_color_flag(
+            Some(CompilationKind::Gcc),
+            "xcc -c main.c",
+            ColorMode::Always
+        ),
+        "xcc -c main.c"
+    );
+}
+
+#[test]
+fn test_slow_build_notify_requires_target_and_threshold() {
+    let _guard = test_guard!();
+    let mut config = rch_common::NotificationsConfig {
+        threshold_secs: 60,
+        ..Default::default()
+    };
+    // No webhook or command configured: never notify.
+    assert!(!should_notify(&config, Duration::from_secs(600)));
+
+    config.command = Some("notify-send done".to_string());
+    assert!(!should_notify(&config, Duration::from_secs(59)));
+    assert!(should_notify(&config, Duration::from_secs(60)));
+
+    let notice = SlowBuildNotice {
+        project: "my-app".to_string(),
+        worker: "w1".to_string(),
+        duration_ms: 61_000,
+        exit_code: 101,
+    };
+    assert_eq!(
+        serde_json::to_value(&notice).expect("serialize notice"),
+        serde_json::json!({
+            "project": "my-app",
+            "worker": "w1",
+            "duration_ms": 61_000,
+            "exit_code": 101,
+        })
+    );
+    assert!(notice_env(&notice).contains(&("RCH_NOTIFY_EXIT_CODE", "101".to_string())));
+}
+
+#[test]
+fn test_timing_gate_min_local_time_boundary() {
+    let _guard = test_guard!();
+    let config = rch_common::CompilationConfig {
+        min_local_time_ms: 2000,
+        remote_speedup_threshold: 1.0,
+        ..Default::default()
+    };
+
+    let below = seeded_timing_history("gate-project", &[1999], &[]);
+    let decision = gate_for(&below, &config);
+    assert_eq!(
+        decision,
+        TimingGateDecision::LocalBuildTooShort {
+            predicted_local_ms: 1999
+        }
+    );
+    assert!(decision.keeps_local());
+    assert!(
+        decision
+            .detail(&config)
+            .contains("1999ms < min_local_time_ms 2000ms")
+    );
+
+    let at = seeded_timing_history("gate-project", &[2000], &[]);
+    assert_eq!(gate_for(&at, &config), TimingGateDecision::Offload);
+}
+
+#[test]
+fn test_timing_gate_speedup_threshold_boundary() {
+    let _guard = test_guard!();
+    let config = rch_common::CompilationConfig {
+        min_local_time_ms: 1000,
+        remote_speedup_threshold: 1.5,
+        ..Default::default()
+    };
+
+    // 2900 / 2000 = 1.45x < 1.5x
+    let below = seeded_timing_history("gate-project", &[2900], &[2000]);
+    match gate_for(&below, &config) {
+        TimingGateDecision::SpeedupTooLow { predicted_speedup } => {
+            assert!((predicted_speedup - 1.45).abs() < 1e-9);
+        }
+        other => panic!("expected SpeedupTooLow, got {other:?}"),
+    }
+
+    // 3000 / 2000 = 1.5x == threshold: still worth offloading.
+    let at = seeded_timing_history("gate-project", &[3000], &[2000]);
+    assert_eq!(gate_for(&at, &config), TimingGateDecision::Offload);
+
+    // Local-only history has no speedup estimate; only min_local_time applies.
+    let local_only = seeded_timing_history("gate-project", &[3000], &[]);
+    assert_eq!(gate_for(&local_only, &config), TimingGateDecision::Offload);
+}
+
+#[test]
+fn test_timing_gate_uses_median_of_seeded_samples() {
+    let _guard = test_guard!();
+    let config = rch_common::CompilationConfig::default();
+    // Median local = 1500ms (< 2000ms default) despite one slow outlier.
+    let history = seeded_timing_history("gate-project", &[1400, 1500, 90_000], &[]);
+    assert_eq!(
+        gate_for(&history, &config),
+        TimingGateDecision::LocalBuildTooShort {
+            predicted_local_ms: 1500
+        }
+    );
+    // A different kind for the same project has no history: fail open.
+    let estimate =
+        estimate_timing_from_history(&history, "gate-project", Some(CompilationKind::CargoTest));
+    assert!(estimate.is_none());
+}
+
+#[test]
+fn test_shardable_nextest_command_detection() {
+    let _guard = test_guard!();
+    let nextest = Some(CompilationKind::CargoNextest);
+    assert!(is_shardable_nextest_command(nextest, "cargo nextest run"));
+    assert!(is_shardable_nextest_command(
+        nextest,
+        "cargo nextest run --workspace -p rch --profile ci"
+    ));
+    assert!(is_shardable_nextest_command(
+        nextest,
+        "cargo nextest run -- --include-ignored"
+    ));
+
+    // Already partitioned or filtered: run unsharded.
+    assert!(!is_shardable_nextest_command(
+        nextest,
+        "cargo nextest run --partition count:1/2"
+    ));
+    assert!(!is_shardable_nextest_command(
+        nextest,
+        "cargo nextest run --partition=hash:1/3"
+    ));
+    assert!(!is_shardable_nextest_command(
+        nextest,
+        "cargo nextest run my_test"
+    ));
+    assert!(!is_shardable_nextest_command(
+        nextest,
+        "cargo nextest run -E 'test(foo)'"
+    ));
+
+    // Only Rust nextest is sharded.
+    assert!(!is_shardable_nextest_command(
+        Some(CompilationKind::CargoTest),
+        "cargo test"
+    ));
+    assert!(!is_shardable_nextest_command(None, "cargo nextest run"));
+}
+
+#[test]
+fn test_nextest_partition_command_placement() {
+    let _guard = test_guard!();
+    assert_eq!(
+        nextest_partition_command("cargo nextest run", 1, 3),
+        "cargo nextest run --partition count:1/3"
+    );
+    assert_eq!(
+        nextest_partition_command("cargo nextest run --workspace -- --include-ignored", 2, 2),
+        "cargo nextest run --workspace --partition count:2/2 -- --include-ignored"
+    );
+}
+
+#[test]
+fn test_aggregate_shard_exit_codes_fails_if_any_shard_fails() {
+    let _guard = test_guard!();
+    assert_eq!(aggregate_shard_exit_codes(&[0, 0, 0]), 0);
+    assert_eq!(aggregate_shard_exit_codes(&[0, 100, 0]), 100);
+    assert_eq!(aggregate_shard_exit_codes(&[0, 4, 101]), 4);
+    assert_eq!(aggregate_shard_exit_codes(&[]), 0);
+}
+
+#[test]
+fn test_shard_transfer_config_splits_bandwidth_limit() {
+    let _guard = test_guard!();
+    let limited = TransferConfig {
+        bwlimit_kbps: Some(1000),
+        ..Default::default()
+    };
+    assert_eq!(shard_transfer_config(&limited, 3).bwlimit_kbps, Some(334));
+    assert_eq!(shard_transfer_config(&limited, 1).bwlimit_kbps, Some(1000));
+
+    let unlimited = TransferConfig {
+        bwlimit_kbps: Some(0),
+        ..Default::default()
+    };
+    assert_eq!(shard_transfer_config(&unlimited, 4).bwlimit_kbps, Some(0));
+    assert_eq!(
+        shard_transfer_config(&TransferConfig::default(), 4).bwlimit_kbps,
+        None
+    );
+}
+
+#[test]
+fn test_shadow_divergence_compares_exit_codes() {
+    let _guard = test_guard!();
+    let completed = |exit_code| ShadowRemoteOutcome::Completed { exit_code };
+    assert_eq!(shadow_divergence(0, &completed(0)), None);
+    assert_eq!(shadow_divergence(101, &completed(101)), None);
+    assert_eq!(
+        shadow_divergence(0, &completed(101)).as_deref(),
+        Some("remote exit 101 != local exit 0")
+    );
+    assert_eq!(
+        shadow_divergence(
+            1,
+            &ShadowRemoteOutcome::Unavailable {
+                reason: "transfer skipped".to_string()
+            }
+        ),
+        None
+    );
+}
+
+#[test]
+fn test_shadow_transfer_config_skips_artifact_retrieval() {
+    let _guard = test_guard!();
+    let transfer = TransferConfig::default();
+    let build = shadow_transfer_config(&transfer, Some(CompilationKind::CargoBuild));
+    assert!(build.skips_artifacts_for(Some(CompilationKind::CargoBuild)));
+
+    // Kinds already skipped are not listed twice.
+    let already = TransferConfig {
+        skip_artifacts_for: vec!["cargo_check".to_string()],
+        ..Default::default()
+    };
+    let check = shadow_transfer_config(&already, Some(CompilationKind::CargoCheck));
+    assert_eq!(check.skip_artifacts_for, vec!["cargo_check".to_string()]);
+}
+
+#[test]
+fn test_shadow_divergence_record_round_trips() {
+    let _guard = test_guard!();
+    let record = ShadowDivergence {
+        timestamp_ms: 1_700_000_000_000,
+        project: "demo".to_string(),
+        command: "cargo build".to_string(),
+        kind: Some("cargo_build".to_string()),
+        worker: "w1".to_string(),
+        local_exit_code: 0,
+        remote_exit_code: 101,
+        local_ms: 1200,
+        remote_ms: 900,
+    };
+    let line = serde_json::to_string(&record).unwrap();
+    assert!(!line.contains('\n'));
+    let parsed: ShadowDivergence = serde_json::from_str(&line).unwrap();
+    assert_eq!(parsed.remote_exit_code, 101);
+    assert_eq!(parsed.kind.as_deref(), Some("cargo_build"));
+}
+
+fn write_artifact(path: &Path, bytes: &[u8]) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, bytes).unwrap();
+}
+
+#[test]
+fn test_collect_artifacts_skips_intermediate_files() {
+    let _guard = test_guard!();
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+    write_artifact(&root.join("release/libdemo.rlib"), b"rlib");
+    write_artifact(&root.join("release/deps/libserde-1a2b.rlib"), b"rlib");
+    write_artifact(&root.join("release/demo.d"), b"deps");
+    write_artifact(&root.join("release/build/serde-9f/out/data.rlib"), b"x");
+    write_artifact(&root.join("release/incremental/x.rlib"), b"x");
+    write_artifact(&root.join("release/demo"), b"bin");
+    write_artifact(&root.join("release/deps/demo-1a2b"), b"bin");
+    {
+        use std::os::unix::fs::PermissionsExt;
+        for bin in ["release/demo", "release/deps/demo-1a2b"] {
+            std::fs::set_permissions(root.join(bin), std::fs::Permissions::from_mode(0o755))
+                .unwrap();
+        }
+    }
+
+    let found: Vec<String> = collect_artifacts(root).into_keys().collect();
+    assert_eq!(
+        found,
+        [
+            "release/demo",
+            "release/deps/libserde-1a2b.rlib",
+            "release/libdemo.rlib"
+        ]
+    );
+}
+
+#[test]
+fn test_compare_artifact_notes_embedded_paths() {
+    let _guard = test_guard!();
+    let remote_markers = vec!["/tmp/rch".to_string()];
+    let local_markers = vec!["/home/dev/proj".to_string()];
+
+    assert_eq!(
+        compare_artifact(b"same", b"same", &remote_markers, &local_markers),
+        ArtifactComparison::Identical
+    );
+    assert!(matches!(
+        compare_artifact(
+            b"panic at /tmp/rch/demo/src/main.rs",
+            b"panic at /home/dev/proj/src/main.rs",
+            &remote_markers,
+            &local_markers
+        ),
+        ArtifactComparison::ExpectedDifference { reason } if reason.contains("/tmp/rch")
+    ));
+    assert_eq!(
+        compare_artifact(
+            b"built 0001",
+            b"built 0002",
+            &remote_markers,
+            &local_markers
+        ),
+        ArtifactComparison::Differs {
+            detail: "same size, 1 byte(s) differ (embedded timestamp or hash?)".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_compare_artifact_home_markers_are_narrow() {
+    let _guard = test_guard!();
+    let remote_markers = home_build_markers("/home/builder/");
+    let local_markers = home_build_markers("/home/dev");
+    assert_eq!(
+        remote_markers,
+        [
+            "/home/builder/.cargo/registry/",
+            "/home/builder/.cargo/git/",
+            "/home/builder/.rustup/toolchains/",
+        ]
+    );
+
+    assert!(matches!(
+        compare_artifact(
+            b"/home/builder/.cargo/registry/src/serde/de.rs",
+            b"/home/dev/.cargo/registry/src/serde/de.rs",
+            &remote_markers,
+            &local_markers
+        ),
+        ArtifactComparison::ExpectedDifference { .. }
+    ));
+    // A bare home path no longer excuses a difference.
+    assert!(matches!(
+        compare_artifact(
+            b"/home/builder/notes.txt v1",
+            b"/home/dev/notes.txt v2",
+            &remote_markers,
+            &local_markers
+        ),
+        ArtifactComparison::Differs { .. }
+    ));
+}
+
+/// A one-member ar archive with the given mtime field and payload.
+fn ar_archive(mtime: &str, payload: &[u8]) -> Vec<u8> {
+    let mut out = b"!<arch>\n".to_vec();
+    out.extend_from_slice(
+        format!(
+            "{:<16}{:<12}{:<6}{:<6}{:<8}{:<10}`\n",
+            "lib.rmeta/",
+            mtime,
+            "0",
+            "0",
+            "644",
+            payload.len()
+        )
+        .as_bytes(),
+    );
+    out.extend_from_slice(payload);
+    if payload.len() % 2 == 1 {
+        out.push(b'\n');
+    }
+    out
+}
+
+#[test]
+fn test_compare_artifact_reports_timestamp_only_differences() {
+    let _guard = test_guard!();
+    let remote = ar_archive("1700000000", b"rmeta");
+    let local = ar_archive("1700000099", b"rmeta");
+    assert_eq!(
+        compare_artifact(&remote, &local, &[], &[]),
+        ArtifactComparison::TimestampOnly
+    );
+
+    // A payload difference still fails even when the timestamps differ too.
+    let changed = ar_archive("1700000099", b"rmetb");
+    assert!(matches!(
+        compare_artifact(&remote, &changed, &[], &[]),
+        ArtifactComparison::Differs { .. }
+    ));
+}
+
+#[test]
+fn test_diff_target_dirs_reports_one_sided_artifacts() {
+    let _guard = test_guard!();
+    let dir = tempfile::TempDir::new().unwrap();
+    let remote = dir.path().join("remote");
+    let local = dir.path().join("local");
+    write_artifact(&remote.join("release/libdemo.rlib"), b"same");
+    write_artifact(&local.join("release/libdemo.rlib"), b"same");
+    write_artifact(&remote.join("release/libextra.rlib"), b"x");
+    write_artifact(&local.join("release/libother.a"), b"y");
+
+    let diffs = diff_target_dirs(&remote, &local, &[], &[]);
+    let by_path: Vec<(&str, &ArtifactComparison)> = diffs
+        .iter()
+        .map(|diff| (diff.path.as_str(), &diff.comparison))
+        .collect();
+    assert_eq!(
+        by_path,
+        [
+            ("release/libdemo.rlib", &ArtifactComparison::Identical),
+            ("release/libextra.rlib", &ArtifactComparison::OnlyRemote),
+            ("release/libother.a", &ArtifactComparison::OnlyLocal),
+        ]
+    );
+}
+
+#[test]
+fn test_hook_output_json_prints_nothing_for_plain_allow() {
+    let _guard = test_guard!();
+    assert_eq!(hook_output_json(&HookOutput::allow()).unwrap(), None);
+
+    let modified = hook_output_json(&HookOutput::allow_with_modified_command("true"))
+        .unwrap()
+        .expect("modified command is printed");
+    assert!(modified.contains("\"updatedInput\""));
+    let denied = hook_output_json(&HookOutput::deny("no"))
+        .unwrap()
+        .expect("deny is printed");
+    assert!(denied.contains("\"deny\""));
+}
+
+#[test]
+fn test_hook_server_messages_round_trip() {
+    let _guard = test_guard!();
+    let request = HookServerRequest {
+        version: "1.2.3".to_string(),
+        cwd: PathBuf::from("/data/projects/app"),
+        input: "{\"tool_name\":\"Bash\"}\n".to_string(),
+    };
+    let line = serde_json::to_string(&request).unwrap();
+    assert!(!line.contains('\n'));
+    let parsed: HookServerRequest = serde_json::from_str(&line).unwrap();
+    assert_eq!(parsed.cwd, request.cwd);
+    assert_eq!(parsed.input, request.input);
+
+    // Plain allow serializes to an empty object.
+    let allow = serde_json::to_string(&HookServerResponse::default()).unwrap();
+    assert_eq!(allow, "{}");
+    let parsed: HookServerResponse = serde_json::from_str(&allow).unwrap();
+    assert!(parsed.output.is_none() && parsed.error.is_none());
+}
+
+#[tokio::test]
+async fn test_hook_server_rejects_other_versions() {
+    let _guard = test_guard!();
+    let response = answer_hook_request(HookServerRequest {
+        version: "0.0.0-other".to_string(),
+        cwd: PathBuf::from("/nonexistent"),
+        input: String::new(),
+    })
+    .await;
+    assert!(response.output.is_none());
+    assert!(response.error.unwrap().contains("version mismatch"));
+}
+
+#[tokio::test]
+async fn test_hook_server_connection_answers_malformed_request_with_error() {
+    let _guard = test_guard!();
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    let (mut client, server) = tokio::net::UnixStream::pair().unwrap();
+    let serve = tokio::spawn(serve_hook_connection(server));
+    client.write_all(b"not json\n").await.unwrap();
+    let mut reply = String::new();
+    BufReader::new(client).read_line(&mut reply).await.unwrap();
+    serve.await.unwrap().unwrap();
+
+    let response: HookServerResponse = serde_json::from_str(&reply).unwrap();
+    assert!(response.error.unwrap().starts_with("malformed request"));
+}
+
+#[tokio::test]
+async fn test_hook_client_ignores_shared_hook_server_socket() {
+    let _guard = test_guard!();
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("rch-hook.sock");
+    let listener = tokio::net::UnixListener::bind(&path).unwrap();
+    let uid = std::fs::metadata(&path).unwrap().uid();
+
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o666)).unwrap();
+    assert!(!socket_is_private(&path, uid));
+    assert!(!socket_is_private(&path, uid + 1));
+    // A world-writable socket is never connected to.
+    assert_eq!(forward_to_socket(&path, "{}").await, None);
+
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+    assert!(socket_is_private(&path, uid));
+    assert!(!socket_is_private(&path, uid + 1));
+    let serve = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        serve_hook_connection(stream).await
+    });
+    // Same user, private socket: the server's answer is used.
+    assert_eq!(forward_to_socket(&path, "").await, Some(None));
+    serve.await.unwrap().unwrap();
+}
+
+#[test]
+fn test_sync_trace_steps_report_roots_that_did_not_sync() {
+    let _guard = test_guard!();
+    let entry = |root: &str, is_primary: bool| SyncClosurePlanEntry {
+        local_root: PathBuf::from(root),
+        remote_root: root.to_string(),
+        project_id: "proj".to_string(),
+        root_hash: "hash".to_string(),
+        is_primary,
+        mode: SyncClosureMode::Full,
+    };
+    let outcomes = vec![
+        (entry("/data/projects/app", true), SyncRootOutcome::Synced),
+        (
+            entry("/data/projects/dep", false),
+            SyncRootOutcome::Skipped {
+                reason: "estimator".to_string(),
+            },
+        ),
+    ];
+    let sync = crate::transfer::SyncResult {
+        bytes_transferred: 0,
+        files_transferred: 0,
+        duration_ms: 40,
+        raw_bytes: 0,
+        compressed_bytes: 0,
+    };
+    let steps: Vec<String> = sync_trace_steps(&outcomes, &sync)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(
+        steps,
+        [
+            "sync: 1/2 roots synced: 0 files, 0 bytes in 40ms (worker copy already current)",
+            "sync: skipped /data/projects/dep: estimator",
+        ]
+    );
+}
+
+#[test]
+fn test_classification_trace_detail_reports_threshold() {
+    let _guard = test_guard!();
+    let classification = classify_command("cargo build");
+    let detail = classification_trace_detail(&classification, Some(0.8));
+    assert!(detail.starts_with("cargo_build (confidence "), "{detail}");
+    assert!(detail.contains("threshold 0.80"), "{detail}");
+    assert!(
+        classification_trace_detail(&classification, None).contains("bypassed by force_remote")
+    );
+}
+
+#[test]
+fn test_output_capture_stays_in_memory_under_limit() {
+    let _guard = test_guard!();
+    let dir = tempfile::tempdir().unwrap();
+    let mut capture = OutputCapture::new(64, Some(dir.path().to_path_buf()));
+    capture.push("Compiling foo\n");
+    capture.push("Compiling bar\n");
+    assert_eq!(capture.head(), "Compiling foo\nCompiling bar\n");
+    assert!(build_logs_in(dir.path()).is_empty());
+    assert!(capture.finish(false, false).is_none());
+}
+
+#[test]
+fn test_output_capture_spills_full_log_past_limit() {
+    let _guard = test_guard!();
+    let dir = tempfile::tempdir().unwrap();
+    let mut capture = OutputCapture::new(16, Some(dir.path().to_path_buf()));
+    capture.push("line one\n");
+    capture.push("line two\n");
+    capture.push("line three\n");
+
+    // The head keeps the start of the log; the spill file holds all of it.
+    assert_eq!(capture.head(), "line one\n");
+    let logs = build_logs_in(dir.path());
+    assert_eq!(logs.len(), 1);
+
+    let kept = capture.finish(false, false).unwrap();
+    assert_eq!(kept, logs[0]);
+    assert_eq!(
+        std::fs::read_to_string(&kept).unwrap(),
+        "line one\nline two\nline three\n"
+    );
+}
+
+#[test]
+fn test_output_capture_removes_log_on_success_unless_kept() {
+    let _guard = test_guard!();
+    let dir = tempfile::tempdir().unwrap();
+
+    let mut capture = OutputCapture::new(4, Some(dir.path().to_path_buf()));
+    capture.push("warning: unused\n");
+    assert!(capture.finish(true, false).is_none());
+    assert!(build_logs_in(dir.path()).is_empty());
+
+    let mut capture = OutputCapture::new(4, Some(dir.path().to_path_buf()));
+    capture.push("warning: unused\n");
+    let kept = capture.finish(true, true).unwrap();
+    assert!(kept.exists());
+}
+
+#[test]
+fn test_prune_build_logs_by_age_and_count() {
+    let _guard = test_guard!();
+    let dir = tempfile::tempdir().unwrap();
+    let now = SystemTime::now();
+    let write_log = |name: &str, age_secs: u64| {
+        let path = dir.path().join(name);
+        let file = std::fs::File::create(&path).unwrap();
+        file.set_modified(now - Duration::from_secs(age_secs))
+            .unwrap();
+        path
+    };
+    let stale = write_log("build-1-1.log", 10 * 24 * 60 * 60);
+    let older = write_log("build-2-1.log", 300);
+    let newer = write_log("build-3-1.log", 200);
+    let newest = write_log("build-4-1.log", 100);
+    let foreign = write_log("notes.log", 20 * 24 * 60 * 60);
+
+    let week = Duration::from_secs(7 * 24 * 60 * 60);
+    assert_eq!(prune_build_logs_in(dir.path(), 2, week), 2);
+    assert!(!stale.exists());
+    assert!(!older.exists());
+    assert!(newer.exists());
+    assert!(newest.exists());
+    assert!(foreign.exists());
+}
+
+#[test]
+fn test_output_capture_without_spill_dir_truncates() {
+    let _guard = test_guard!();
+    let mut capture = OutputCapture::new(8, None);
+    capture.push("abc\n");
+    capture.push("defghijk\n");
+    capture.push("l\n");
+    assert_eq!(capture.head(), "abc\n");
+    assert!(capture.finish(false, false).is_none());
+}
+
+#[test]
+fn test_urlencoding_encode_basic() {
+    let _guard = test_guard!();
+    assert_eq!(urlencoding_encode("hello world"), "hello%20world");
+    assert_eq!(urlencoding_encode("path/to/file"), "path%2Fto%2Ffile");
+    assert_eq!(urlencoding_encode("foo:bar"), "foo%3Abar");
+}
+
+#[test]
+fn test_urlencoding_encode_special_chars() {
+    let _guard = test_guard!();
+    assert_eq!(urlencoding_encode("a&b=c"), "a%26b%3Dc");
+    assert_eq!(urlencoding_encode("100%"), "100%25");
+    assert_eq!(urlencoding_encode("hello+world"), "hello%2Bworld");
+}
+
+#[test]
+fn test_urlencoding_encode_no_encoding_needed() {
+    let _guard = test_guard!();
+    assert_eq!(urlencoding_encode("simple"), "simple");
+    assert_eq!(
+        urlencoding_encode("with-dash_underscore.dot~tilde"),
+        "with-dash_underscore.dot~tilde"
+    );
+    assert_eq!(urlencoding_encode("ABC123"), "ABC123");
+}
+
+#[test]
+fn test_urlencoding_encode_unicode() {
+    let _guard = test_guard!();
+    // Unicode characters should be encoded as UTF-8 bytes
+    let encoded = urlencoding_encode("café");
+    assert!(encoded.contains("%")); // 'é' should be encoded
+    assert!(encoded.starts_with("caf")); // ASCII part preserved
+}
+
+#[test]
+fn test_parse_jobs_flag_variants() {
+    let _guard = test_guard!();
+    assert_eq!(parse_jobs_flag("cargo build -j 8"), Some(8));
+    assert_eq!(parse_jobs_flag("cargo build -j8"), Some(8));
+    assert_eq!(parse_jobs_flag("cargo build --jobs 4"), Some(4));
+    assert_eq!(parse_jobs_flag("cargo build --jobs=12"), Some(12));
+    assert_eq!(parse_jobs_flag("cargo build -j=16"), Some(16));
+    assert_eq!(parse_jobs_flag("cargo build --jobs=12"), Some(12));
+    assert_eq!(parse_jobs_flag("cargo build -j"), None);
+    assert_eq!(parse_jobs_flag("cargo build --jobs"), None);
+}
+
+#[test]
+fn test_parse_test_threads_variants() {
+    let _guard = test_guard!();
+    assert_eq!(
+        parse_test_threads("cargo test -- --test-threads=4"),
+        Some(4)
+    );
+    assert_eq!(
+        parse_test_threads("cargo test -- --test-threads 2"),
+        Some(2)
+    );
+    assert_eq!(parse_test_threads("cargo test"), None);
+}
+
+#[test]
+fn test_estimate_cores_for_command() {
+    let _guard = test_guard!();
+    let config = rch_common::CompilationConfig {
+        build_slots: 6,
+        test_slots: 10,
+        check_slots: 3,
+        ..Default::default()
+    };
+
+    let build =
+        estimate_cores_for_command(Some(CompilationKind::CargoBuild), "cargo build", &config);
+    assert_eq!(build, 6);
+
+    let build_jobs = estimate_cores_for_command(
+        Some(CompilationKind::CargoBuild),
+        "cargo build -j 12",
+        &config,
+    );
+    assert_eq!(build_jobs, 12);
+
+    let test_default =
+        estimate_cores_for_command(Some(CompilationKind::CargoTest), "cargo test", &config);
+    assert_eq!(test_default, 10);
+
+    let test_threads = estimate_cores_for_command(
+        Some(CompilationKind::CargoTest),
+        "cargo test -- --test-threads=4",
+        &config,
+    );
+    assert_eq!(test_threads, 4);
+
+    let test_jobs = estimate_cores_for_command(
+        Some(CompilationKind::CargoTest),
+        "cargo test -j 1 -p rchd --lib",
+        &config,
+    );
+    assert_eq!(test_jobs, 1);
+
+    let test_long_jobs = estimate_cores_for_command(
+        Some(CompilationKind::CargoTest),
+        "cargo test --jobs=3 -p rchd --lib",
+        &config,
+    );
+    assert_eq!(test_long_jobs, 3);
+
+    let test_jobs_override_threads = estimate_cores_for_command(
+        Some(CompilationKind::CargoTest),
+        "cargo test -j 1 -- --test-threads=8",
+        &config,
+    );
+    assert_eq!(test_jobs_override_threads, 1);
+
+    let test_build_jobs_env = estimate_cores_for_command(
+        Some(CompilationKind::CargoTest),
+        "CARGO_BUILD_JOBS=2 cargo test -p rchd --lib",
+        &config,
+    );
+    assert_eq!(test_build_jobs_env, 2);
+
+    let test_env = estimate_cores_for_command(
+        Some(CompilationKind::CargoTest),
+        "RUST_TEST_THREADS=3 cargo test",
+        &config,
+    );
+    assert_eq!(test_env, 3);
+
+    let check_default =
+        estimate_cores_for_command(Some(CompilationKind::CargoCheck), "cargo check", &config);
+    assert_eq!(check_default, 3);
+}
+
+#[test]
+fn test_estimate_cores_for_make_and_ninja() {
+    let _guard = test_guard!();
+    let config = rch_common::CompilationConfig {
+        build_slots: 6,
+        ..Default::default()
+    };
+    let make = Some(CompilationKind::Make);
+    let ninja = Some(CompilationKind::Ninja);
+
+    assert_eq!(estimate_cores_for_command(make, "make", &config), 6);
+    assert_eq!(estimate_cores_for_command(make, "make -j8", &config), 8);
+    assert_eq!(
+        estimate_cores_for_command(make, "make -j 8 all", &config),
+        8
+    );
+    assert_eq!(
+        estimate_cores_for_command(make, "make --jobs=3 install", &config),
+        3
+    );
+    assert_eq!(
+        estimate_cores_for_command(make, "make -j", &config),
+        BUILD_TOOL_JOBS_SLOT_CAP
+    );
+    assert_eq!(
+        estimate_cores_for_command(make, "make -j all", &config),
+        BUILD_TOOL_JOBS_SLOT_CAP
+    );
+    // Cargo's job variable does not size a make build.
+    assert_eq!(
+        estimate_cores_for_command(make, "CARGO_BUILD_JOBS=2 make", &config),
+        6
+    );
+
+    assert_eq!(estimate_cores_for_command(ninja, "ninja", &config), 6);
+    assert_eq!(estimate_cores_for_command(ninja, "ninja -j8", &config), 8);
+    assert_eq!(
+        estimate_cores_for_command(ninja, "ninja -C build -j 4", &config),
+        4
+    );
+    assert_eq!(
+        estimate_cores_for_command(ninja, "ninja -j0", &config),
+        BUILD_TOOL_JOBS_SLOT_CAP
+    );
+
+    // Explicit counts are capped like unbounded ones.
+    assert_eq!(
+        estimate_cores_for_command(make, "make -j512", &config),
+        BUILD_TOOL_JOBS_SLOT_CAP
     );
-    assert_eq!(test_env, 3);
+    assert_eq!(
+        estimate_cores_for_command(ninja, "ninja -j 64", &config),
+        BUILD_TOOL_JOBS_SLOT_CAP
+    );
+}
 
-    let check_default =
-        estimate_cores_for_command(Some(CompilationKind::CargoCheck), "cargo check", &config);
-    assert_eq!(check_default, 3);
+#[test]
+fn test_parse_build_tool_jobs_last_flag_wins() {
+    let _guard = test_guard!();
+    assert_eq!(
+        parse_build_tool_jobs("make -j2 -j6"),
+        Some(BuildToolJobs::Count(6))
+    );
+    assert_eq!(
+        parse_build_tool_jobs("make -j4 -j"),
+        Some(BuildToolJobs::Unbounded)
+    );
+    assert_eq!(parse_build_tool_jobs("make -k"), None);
 }
 
 // =========================================================================
@@ -718,6 +2150,15 @@ fn test_classification_confidence_levels() {
     assert!(!result.is_compilation);
 }
 
+#[test]
+fn test_classification_leaves_rch_exec_local_alone() {
+    let _guard = test_guard!();
+    // `rch exec --local -- cargo build` must reach the shell untouched so the
+    // one-shot local override is not rewritten into a remote exec.
+    let result = classify_command("rch exec --local -- cargo build");
+    assert!(!result.is_compilation);
+}
+
 #[test]
 fn test_classification_bun_commands() {
     let _guard = test_guard!();
@@ -852,6 +2293,43 @@ fn test_extract_project_name() {
     assert!(!project.is_empty());
 }
 
+/// A project entered through a symlink that resolves outside the canonical
+/// root keeps its logical path when `$PWD` still names it, so
+/// `keep_logical_symlinks` applies even though the kernel cwd is resolved.
+#[cfg(unix)]
+#[test]
+fn test_project_name_keeps_logical_path_of_symlinked_cwd() {
+    let _guard = test_guard!();
+    use std::fs;
+
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let root = fs::canonicalize(tmp.path()).expect("canonicalize tmp");
+    let canonical_root = root.join("projects");
+    let target = root.join("elsewhere/repo");
+    fs::create_dir_all(&canonical_root).expect("create canonical root");
+    fs::create_dir_all(&target).expect("create target");
+    let link = canonical_root.join("repo");
+    std::os::unix::fs::symlink(&target, &link).expect("symlink project");
+    let policy = PathTopologyPolicy::new(canonical_root.clone(), canonical_root.clone())
+        .with_keep_logical_symlinks(true);
+
+    let prev_cwd = std::env::current_dir().ok();
+    std::env::set_current_dir(&link).expect("cd through symlink");
+    let resolved_cwd = std::env::current_dir();
+    let logical = rch_common::logical_dir_for(Some(&link));
+    if let Some(prev) = prev_cwd {
+        let _ = std::env::set_current_dir(prev);
+    }
+
+    assert_eq!(resolved_cwd.expect("cwd"), target);
+    let logical = logical.expect("logical dir");
+    assert_eq!(logical, link);
+    let project = project_name_for_dir(&logical, &policy);
+    let hash = blake3::hash(link.to_string_lossy().as_bytes()).to_hex();
+    assert_eq!(project, format!("repo-{}", &hash[..8]));
+    assert_ne!(project, project_name_for_dir(&target, &policy));
+}
+
 /// Regression test for GitHub #9: when a custom [`PathTopologyPolicy`]
 /// is supplied and the cwd lives under the configured canonical root,
 /// normalization must succeed and must not fall back to the
@@ -935,6 +2413,13 @@ fn test_selected_worker_to_config() {
         identity_file: "~/.ssh/id_rsa".to_string(),
         slots_available: 8,
         speed_score: 75.5,
+        upload_bandwidth_bps: None,
+        rtt_ms: None,
+        rustc_version: None,
+        sccache_version: None,
+        missing_tools: vec![],
+        nice_level: None,
+        ionice_class: None,
     };
 
     let config = selected_worker_to_config(&worker);
@@ -971,6 +2456,10 @@ async fn test_daemon_query_missing_socket() {
         None,
         false,
         &[],
+        None,
+        None,
+        &[],
+        None,
     )
     .await;
     assert!(result.is_err());
@@ -1021,6 +2510,13 @@ async fn test_daemon_query_protocol() {
                 identity_file: "~/.ssh/mock_key".to_string(),
                 slots_available: 16,
                 speed_score: 95.0,
+                upload_bandwidth_bps: None,
+                rtt_ms: None,
+                rustc_version: None,
+                sccache_version: None,
+                missing_tools: vec![],
+                nice_level: None,
+                ionice_class: None,
             }),
             reason: SelectionReason::Success,
             build_id: None,
@@ -1055,6 +2551,10 @@ async fn test_daemon_query_protocol() {
         None,
         false,
         &[],
+        None,
+        None,
+        &[],
+        None,
     )
     .await;
 
@@ -1071,7 +2571,7 @@ async fn test_daemon_query_protocol() {
 }
 
 #[tokio::test]
-async fn test_daemon_query_sends_preferred_workers() {
+async fn test_daemon_query_sends_preferred_workers_and_fleet() {
     let socket_path = format!("/tmp/rch_test_daemon_preferred_{}.sock", std::process::id());
     let _ = std::fs::remove_file(&socket_path);
 
@@ -1095,6 +2595,10 @@ async fn test_daemon_query_sends_preferred_workers() {
         assert!(request_line.contains("worker=ts2"));
         assert!(request_line.contains("worker=vmi1264463"));
         assert!(request_line.contains("preferred_workers=ts2%2Cvmi1264463"));
+        assert!(request_line.contains("&fleet=fast"));
+        assert!(request_line.contains("&min_free_disk_gb=7.5"));
+        assert!(request_line.contains("&allowed_workers=ts2%2Cvmi1264463"));
+        assert!(request_line.contains("&idempotency_key=rch-build-0123456789abcdef"));
 
         let response = SelectionResponse {
             worker: Some(SelectedWorker {
@@ -1104,6 +2608,13 @@ async fn test_daemon_query_sends_preferred_workers() {
                 identity_file: "~/.ssh/mock_key".to_string(),
                 slots_available: 16,
                 speed_score: 95.0,
+                upload_bandwidth_bps: None,
+                rtt_ms: None,
+                rustc_version: None,
+                sccache_version: None,
+                missing_tools: vec![],
+                nice_level: None,
+                ionice_class: None,
             }),
             reason: SelectionReason::Success,
             build_id: None,
@@ -1140,6 +2651,10 @@ async fn test_daemon_query_sends_preferred_workers() {
         None,
         false,
         &preferred,
+        Some("fast"),
+        Some(7.5),
+        &preferred,
+        Some("rch-build-0123456789abcdef"),
     )
     .await;
 
@@ -1189,6 +2704,13 @@ async fn test_daemon_query_wait_parameters() {
                 identity_file: "~/.ssh/mock_key".to_string(),
                 slots_available: 16,
                 speed_score: 95.0,
+                upload_bandwidth_bps: None,
+                rtt_ms: None,
+                rustc_version: None,
+                sccache_version: None,
+                missing_tools: vec![],
+                nice_level: None,
+                ionice_class: None,
             }),
             reason: SelectionReason::Success,
             build_id: None,
@@ -1221,6 +2743,10 @@ async fn test_daemon_query_wait_parameters() {
         None,
         true,
         &[],
+        None,
+        None,
+        &[],
+        None,
     )
     .await;
 
@@ -1265,6 +2791,13 @@ async fn test_daemon_query_url_encoding() {
                 identity_file: "i".to_string(),
                 slots_available: 1,
                 speed_score: 1.0,
+                upload_bandwidth_bps: None,
+                rtt_ms: None,
+                rustc_version: None,
+                sccache_version: None,
+                missing_tools: vec![],
+                nice_level: None,
+                ionice_class: None,
             }),
             reason: SelectionReason::Success,
             build_id: None,
@@ -1289,6 +2822,10 @@ async fn test_daemon_query_url_encoding() {
         None,
         false,
         &[],
+        None,
+        None,
+        &[],
+        None,
     )
     .await;
     daemon_handle.await.expect("Daemon task");
@@ -1325,6 +2862,7 @@ async fn test_fail_open_on_invalid_json() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     // With no daemon running, should fail-open to allow
@@ -1349,6 +2887,7 @@ async fn test_fail_open_on_config_error() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -1385,6 +2924,13 @@ async fn test_process_hook_remote_success_mocked() {
             identity_file: "~/.ssh/mock_key".to_string(),
             slots_available: 8,
             speed_score: 90.0,
+            upload_bandwidth_bps: None,
+            rtt_ms: None,
+            rustc_version: None,
+            sccache_version: None,
+            missing_tools: vec![],
+            nice_level: None,
+            ionice_class: None,
         }),
         reason: SelectionReason::Success,
         build_id: None,
@@ -1401,6 +2947,7 @@ async fn test_process_hook_remote_success_mocked() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output: HookOutput = process_hook(input).await;
@@ -1464,6 +3011,13 @@ async fn test_force_local_allows_even_when_remote_available() {
             identity_file: "~/.ssh/mock_key".to_string(),
             slots_available: 8,
             speed_score: 90.0,
+            upload_bandwidth_bps: None,
+            rtt_ms: None,
+            rustc_version: None,
+            sccache_version: None,
+            missing_tools: vec![],
+            nice_level: None,
+            ionice_class: None,
         }),
         reason: SelectionReason::Success,
         build_id: None,
@@ -1480,6 +3034,7 @@ async fn test_force_local_allows_even_when_remote_available() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -1531,6 +3086,13 @@ async fn test_force_remote_bypasses_confidence_threshold() {
             identity_file: "~/.ssh/mock_key".to_string(),
             slots_available: 8,
             speed_score: 90.0,
+            upload_bandwidth_bps: None,
+            rtt_ms: None,
+            rustc_version: None,
+            sccache_version: None,
+            missing_tools: vec![],
+            nice_level: None,
+            ionice_class: None,
         }),
         reason: SelectionReason::Success,
         build_id: None,
@@ -1547,6 +3109,7 @@ async fn test_force_remote_bypasses_confidence_threshold() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -1572,6 +3135,88 @@ async fn test_force_remote_bypasses_confidence_threshold() {
     assert!(ssh_logs.is_empty(), "Hook should not invoke SSH directly");
 }
 
+#[tokio::test]
+#[serial(mock_global)]
+async fn test_confidence_override_admits_only_matching_command() {
+    let _lock = test_lock().lock().await;
+    let socket_path = format!(
+        "/tmp/rch_test_hook_confidence_override_{}_{}.sock",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    );
+
+    let _overrides = TestOverridesGuard::set(
+        &socket_path,
+        MockConfig::default(),
+        MockRsyncConfig::success(),
+    );
+    mock::clear_global_invocations();
+
+    // A threshold no classification reaches on its own.
+    let mut config = rch_common::RchConfig::default();
+    config.general.socket_path = socket_path.to_string();
+    config.compilation.confidence_threshold = 1.0;
+    config.compilation.confidence_overrides.insert(
+        "cargo build -p weird-crate".to_string(),
+        rch_common::ConfidenceOverride::Force(true),
+    );
+    crate::config::set_test_config_override(Some(config));
+
+    let response = SelectionResponse {
+        worker: Some(SelectedWorker {
+            id: rch_common::WorkerId::new("mock-worker"),
+            host: "mock.host.local".to_string(),
+            user: "mockuser".to_string(),
+            identity_file: "~/.ssh/mock_key".to_string(),
+            slots_available: 8,
+            speed_score: 90.0,
+            upload_bandwidth_bps: None,
+            rtt_ms: None,
+            rustc_version: None,
+            sccache_version: None,
+            missing_tools: vec![],
+            nice_level: None,
+            ionice_class: None,
+        }),
+        reason: SelectionReason::Success,
+        build_id: None,
+        diagnostics: None,
+    };
+    spawn_mock_daemon(&socket_path, response).await;
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(25)).await;
+
+    let hook_input = |command: &str| HookInput {
+        tool_name: "Bash".to_string(),
+        tool_input: ToolInput {
+            command: command.to_string(),
+            description: None,
+        },
+        session_id: None,
+        tool_use_id: None,
+    };
+
+    // Miss: the global threshold still applies.
+    let output = process_hook(hook_input("cargo build -p other-crate")).await;
+    assert!(
+        matches!(output, HookOutput::Allow(_)),
+        "non-matching command should stay local"
+    );
+
+    // Hit: the override clears the gate.
+    let output = process_hook(hook_input("cargo build -p weird-crate")).await;
+    let _ = std::fs::remove_file(&socket_path);
+    let cmd = delegated_command(&output);
+    assert!(
+        cmd.starts_with("rch exec -- "),
+        "Should delegate to rch exec: {}",
+        cmd
+    );
+}
+
 #[tokio::test]
 #[serial(mock_global)]
 async fn test_process_hook_delegates_to_rch_exec() {
@@ -1604,6 +3249,7 @@ async fn test_process_hook_delegates_to_rch_exec() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -1655,6 +3301,7 @@ async fn test_process_hook_delegates_env_prefixed_cargo_command() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -1717,6 +3364,13 @@ async fn test_process_hook_remote_nonzero_exit_uses_transparent_interception() {
             identity_file: "~/.ssh/mock_key".to_string(),
             slots_available: 8,
             speed_score: 90.0,
+            upload_bandwidth_bps: None,
+            rtt_ms: None,
+            rustc_version: None,
+            sccache_version: None,
+            missing_tools: vec![],
+            nice_level: None,
+            ionice_class: None,
         }),
         reason: SelectionReason::Success,
         build_id: None,
@@ -1733,6 +3387,7 @@ async fn test_process_hook_remote_nonzero_exit_uses_transparent_interception() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -1767,6 +3422,13 @@ fn test_worker_config_from_selected_worker() {
         identity_file: "/keys/deploy.pem".to_string(),
         slots_available: 32,
         speed_score: 88.8,
+        upload_bandwidth_bps: None,
+        rtt_ms: None,
+        rustc_version: None,
+        sccache_version: None,
+        missing_tools: vec![],
+        nice_level: None,
+        ionice_class: None,
     };
 
     let config = selected_worker_to_config(&worker);
@@ -1780,6 +3442,66 @@ fn test_worker_config_from_selected_worker() {
     assert!(config.tags.is_empty()); // Default empty tags
 }
 
+#[test]
+fn test_rustc_parity_skip_reason() {
+    let _guard = test_guard!();
+    let worker = SelectedWorker {
+        id: rch_common::WorkerId::new("w1"),
+        host: "w1.example.com".to_string(),
+        user: "ubuntu".to_string(),
+        identity_file: "~/.ssh/id_ed25519".to_string(),
+        slots_available: 8,
+        speed_score: 50.0,
+        upload_bandwidth_bps: None,
+        rtt_ms: None,
+        rustc_version: Some("rustc 1.80.0 (051478957 2024-07-21)".to_string()),
+        sccache_version: None,
+        missing_tools: vec![],
+        nice_level: None,
+        ionice_class: None,
+    };
+    let local = || Some("rustc 1.80.1 (3f5fd8dd4 2024-08-06)".to_string());
+    let build = Some(CompilationKind::CargoBuild);
+
+    // Off by default; the local rustc is not even probed.
+    assert_eq!(
+        rustc_parity_skip_reason(None, build, &worker, || unreachable!()),
+        None
+    );
+    assert_eq!(
+        rustc_parity_skip_reason(Some(rch_common::RustcMatch::Minor), build, &worker, local),
+        None
+    );
+    let reason =
+        rustc_parity_skip_reason(Some(rch_common::RustcMatch::Patch), build, &worker, local)
+            .expect("patch mismatch keeps the build local");
+    assert!(reason.contains("rustc 1.80.1"), "{reason}");
+    assert!(reason.contains("w1 has 'rustc 1.80.0"), "{reason}");
+
+    // Non-Rust kinds are not subject to the check.
+    assert_eq!(
+        rustc_parity_skip_reason(
+            Some(rch_common::RustcMatch::Exact),
+            Some(CompilationKind::BunTest),
+            &worker,
+            || unreachable!()
+        ),
+        None
+    );
+
+    // Unverifiable parity stays local.
+    let unknown = SelectedWorker {
+        rustc_version: None,
+        nice_level: None,
+        ionice_class: None,
+        ..worker
+    };
+    assert!(
+        rustc_parity_skip_reason(Some(rch_common::RustcMatch::Minor), build, &unknown, local)
+            .is_some()
+    );
+}
+
 // =========================================================================
 // Local fallback scenario tests (remote_compilation_helper-od4)
 // =========================================================================
@@ -1819,6 +3541,7 @@ async fn test_fallback_no_workers_configured() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -1863,6 +3586,7 @@ async fn test_fallback_all_workers_unreachable() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -1907,6 +3631,7 @@ async fn test_fallback_all_workers_busy() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -1951,6 +3676,7 @@ async fn test_fallback_all_circuits_open() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -1995,6 +3721,7 @@ async fn test_fallback_selection_error() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -2049,6 +3776,7 @@ async fn test_fallback_daemon_error_response() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -2102,6 +3830,7 @@ async fn test_fallback_daemon_malformed_json() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -2148,6 +3877,7 @@ async fn test_fallback_daemon_connection_reset() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -2189,6 +3919,20 @@ fn test_signal_name() {
     assert_eq!(signal_name(99), "UNKNOWN");
 }
 
+#[test]
+fn test_remote_failure_summary_follows_classification() {
+    let _guard = test_guard!();
+    let worker = WorkerId::new("css");
+    let summary = |code: i32, stderr: &str| {
+        remote_failure_summary(&worker, classify_exit(code, stderr), code)
+    };
+    assert_eq!(summary(137, ""), "[RCH] remote css killed (SIGKILL)");
+    assert_eq!(summary(120, ""), "[RCH] remote css timed out");
+    assert_eq!(summary(101, ""), "[RCH] remote css tests failed");
+    assert_eq!(summary(1, "error[E0308]"), "[RCH] remote css build error");
+    assert_eq!(summary(2, ""), "[RCH] remote css failed (exit 2)");
+}
+
 #[test]
 fn test_exit_code_constants() {
     let _guard = test_guard!();
@@ -2372,6 +4116,97 @@ fn test_wrap_command_with_telemetry_handles_comments() {
     assert!(wrapped.contains("exit $status"));
 }
 
+#[test]
+fn test_command_produces_machine_stdout_detects_json_streams() {
+    let _guard = test_guard!();
+    for command in [
+        "cargo +nightly build -Z unstable-options --build-plan",
+        "cargo build --unit-graph -Z unstable-options",
+        "cargo build --message-format=json",
+        "cargo check --message-format json-render-diagnostics",
+        "cargo clippy --message-format=short,json-diagnostic-rendered-ansi",
+    ] {
+        assert!(command_produces_machine_stdout(command), "{command}");
+    }
+    for command in [
+        "cargo build --release",
+        "cargo check --message-format=short",
+        "cargo test -- --build-plan",
+        "cargo test --message-format human -- --format json",
+    ] {
+        assert!(!command_produces_machine_stdout(command), "{command}");
+    }
+}
+
+#[test]
+fn test_feature_affecting_env_vars_detected() {
+    let _guard = test_guard!();
+    for name in [
+        "CARGO_ENCODED_RUSTFLAGS",
+        "CARGO_BUILD_RUSTFLAGS",
+        "CARGO_BUILD_TARGET",
+        "CARGO_UNSTABLE_BUILD_STD",
+        "CARGO_RESOLVER_INCOMPATIBLE_RUST_VERSIONS",
+        "CARGO_TARGET_X86_64_UNKNOWN_LINUX_GNU_RUSTFLAGS",
+    ] {
+        assert!(is_feature_affecting_env_var(name), "{name}");
+    }
+    for name in [
+        "CARGO_HOME",
+        "CARGO_TARGET_DIR",
+        "CARGO_TARGET_X86_64_UNKNOWN_LINUX_GNU_LINKER",
+        // Gated by resolve_forwarded_build_env, which keeps the build local.
+        "RUSTFLAGS",
+        "RUSTC_WRAPPER",
+    ] {
+        assert!(!is_feature_affecting_env_var(name), "{name}");
+    }
+}
+
+#[test]
+fn test_unforwarded_feature_env_vars_respects_allowlist() {
+    let _guard = test_guard!();
+    let env = [
+        ("CARGO_ENCODED_RUSTFLAGS", "--cfg\x1ffeature=\"simd\""),
+        ("CARGO_UNSTABLE_BUILD_STD", "std"),
+        ("CARGO_BUILD_TARGET", ""),
+        ("CARGO_HOME", "/home/u/.cargo"),
+        ("PATH", "/usr/bin"),
+    ]
+    .map(|(k, v)| (k.to_string(), v.to_string()));
+
+    assert_eq!(
+        unforwarded_feature_env_vars(env.clone(), &[]),
+        vec!["CARGO_ENCODED_RUSTFLAGS", "CARGO_UNSTABLE_BUILD_STD"]
+    );
+    assert_eq!(
+        unforwarded_feature_env_vars(env, &["CARGO_ENCODED_RUSTFLAGS".to_string()]),
+        vec!["CARGO_UNSTABLE_BUILD_STD"]
+    );
+}
+
+#[test]
+fn test_explicit_feature_flags_kept_as_written() {
+    let _guard = test_guard!();
+    assert_eq!(
+        explicit_feature_flags(
+            "cargo build --no-default-features --features a,b -F=c --all-features -- --features x"
+        ),
+        vec![
+            "--no-default-features",
+            "--features a,b",
+            "-F=c",
+            "--all-features"
+        ]
+    );
+    assert!(explicit_feature_flags("cargo build --release").is_empty());
+    // Quoted feature lists stay one value.
+    assert_eq!(
+        explicit_feature_flags(r#"cargo build --features "a b" -F 'c d' --release"#),
+        vec!["--features a b", "-F c d"]
+    );
+}
+
 #[test]
 fn test_add_cargo_isolation_adds_unique_cargo_home() {
     let _guard = test_guard!();
@@ -2537,6 +4372,64 @@ fn test_pooled_target_dir_name_shape_is_single_segment_and_reapable() {
     );
 }
 
+#[test]
+fn test_remote_sccache_env_requires_probed_sccache() {
+    let _guard = test_guard!();
+    let mut worker = SelectedWorker {
+        id: WorkerId::new("w1"),
+        host: "host".to_string(),
+        user: "user".to_string(),
+        identity_file: "~/.ssh/id_rsa".to_string(),
+        slots_available: 4,
+        speed_score: 50.0,
+        upload_bandwidth_bps: None,
+        rtt_ms: None,
+        rustc_version: None,
+        sccache_version: None,
+        missing_tools: vec![],
+        nice_level: None,
+        ionice_class: None,
+    };
+    assert_eq!(remote_sccache_env(&worker, "/tmp/rch"), None);
+
+    worker.sccache_version = Some("0.8.2".to_string());
+    assert_eq!(
+        remote_sccache_env(&worker, "/tmp/rch/"),
+        Some(vec![
+            ("RUSTC_WRAPPER", "sccache".to_string()),
+            ("SCCACHE_DIR", "/tmp/rch/.rch-sccache".to_string()),
+        ])
+    );
+}
+
+#[test]
+fn test_shared_build_dir_keyed_on_toolchain_and_triple_only() {
+    let _guard = test_guard!();
+    let tc = ToolchainInfo::new("nightly", Some("2025-11-01".to_string()), "x");
+    let dir = remote_shared_build_dir(
+        "/tmp/rch/",
+        Some(&tc),
+        "cargo build --target wasm32-unknown-unknown",
+    );
+    assert_eq!(
+        dir,
+        "/tmp/rch/.rch-shared-build/nightly-2025-11-01-wasm32-unknown-unknown"
+    );
+
+    // Profile and features stay out of the key; cargo separates them inside
+    // the build dir, which is what lets other projects reuse dependencies.
+    let tc_key = remote_shared_build_dir("/tmp/rch", Some(&tc), "cargo build");
+    assert_eq!(
+        tc_key,
+        remote_shared_build_dir("/tmp/rch", Some(&tc), "cargo build --release --features x")
+    );
+    assert_ne!(
+        tc_key,
+        remote_shared_build_dir("/tmp/rch", None, "cargo build"),
+        "a different toolchain must not share the build dir"
+    );
+}
+
 #[test]
 fn test_target_reuse_opt_out_restores_unique_per_job_name() {
     // (c) The opt-out predicate is honored; under opt-out the legacy
@@ -2622,6 +4515,8 @@ fn test_kind_produces_transferable_artifacts() {
         CompilationKind::Make,
         CompilationKind::CmakeBuild,
         CompilationKind::Ninja,
+        CompilationKind::TrunkBuild,
+        CompilationKind::WasmPackBuild,
     ] {
         assert!(
             kind_produces_transferable_artifacts(Some(kind)),
@@ -2747,8 +4642,137 @@ fn test_resolve_forwarded_cargo_target_dir_reads_env_without_allowlist() {
     );
 
     assert_eq!(
-        resolved,
-        Some(PathBuf::from("/tmp/rch-target-no-allowlist"))
+        resolved,
+        Some(PathBuf::from("/tmp/rch-target-no-allowlist"))
+    );
+}
+
+#[test]
+fn test_resolve_forwarded_build_env_refuses_unallowlisted_vars() {
+    let _guard = test_guard!();
+    let reporter = HookReporter::new(OutputVisibility::None);
+    for key in ["RUSTC_WRAPPER", "CARGO_INCREMENTAL"] {
+        let decision = resolve_forwarded_build_env_with_lookup(
+            Some(CompilationKind::CargoBuild),
+            &["CARGO_TARGET_DIR".to_string()],
+            &reporter,
+            |lookup| (lookup == key).then(|| "sccache".to_string()),
+        );
+        assert_eq!(
+            decision,
+            BuildEnvForwarding::Refuse(format!(
+                "{} set locally but not in environment.allowlist",
+                key
+            ))
+        );
+    }
+}
+
+#[test]
+fn test_resolve_forwarded_build_env_forwards_allowlisted_vars() {
+    let _guard = test_guard!();
+    let reporter = HookReporter::new(OutputVisibility::None);
+    let allowlist: Vec<String> = BUILD_AFFECTING_ENV_VARS
+        .iter()
+        .map(|key| format!(" {} ", key))
+        .collect();
+    for key in BUILD_AFFECTING_ENV_VARS {
+        let decision = resolve_forwarded_build_env_with_lookup(
+            Some(CompilationKind::CargoTest),
+            &allowlist,
+            &reporter,
+            |lookup| (lookup == *key).then(|| "-C target-cpu=native".to_string()),
+        );
+        assert_eq!(decision, BuildEnvForwarding::Forward(vec![key.to_string()]));
+    }
+}
+
+#[test]
+fn test_resolve_forwarded_build_env_forwards_portable_rustflags_by_default() {
+    let _guard = test_guard!();
+    let reporter = HookReporter::new(OutputVisibility::None);
+    let decide = |allowlist: &[String], rustflags: &str| {
+        resolve_forwarded_build_env_with_lookup(
+            Some(CompilationKind::CargoBuild),
+            allowlist,
+            &reporter,
+            |lookup| (lookup == "RUSTFLAGS").then(|| rustflags.to_string()),
+        )
+    };
+
+    assert_eq!(
+        decide(&[], "-D warnings --cfg tokio_unstable"),
+        BuildEnvForwarding::Forward(vec!["RUSTFLAGS".to_string()])
+    );
+    for native in ["-C target-cpu=native", "-Ctarget-cpu=native"] {
+        assert_eq!(
+            decide(&[], native),
+            BuildEnvForwarding::Refuse(format!(
+                "RUSTFLAGS has host-dependent {} (add RUSTFLAGS to environment.allowlist to forward it anyway)",
+                native.trim_start_matches("-C ")
+            ))
+        );
+    }
+    // An explicit allowlist entry forwards host-dependent flags as-is.
+    assert_eq!(
+        decide(&["RUSTFLAGS".to_string()], "-C target-cpu=native"),
+        BuildEnvForwarding::Forward(vec!["RUSTFLAGS".to_string()])
+    );
+}
+
+#[test]
+fn test_resolve_forwarded_build_env_gating_edge_cases() {
+    let _guard = test_guard!();
+    let reporter = HookReporter::new(OutputVisibility::None);
+    let rustflags_only = vec!["RUSTFLAGS".to_string()];
+
+    // Unset and empty values need no forwarding.
+    assert_eq!(
+        resolve_forwarded_build_env_with_lookup(
+            Some(CompilationKind::CargoBuild),
+            &[],
+            &reporter,
+            |lookup| (lookup == "CARGO_INCREMENTAL").then(|| "  ".to_string()),
+        ),
+        BuildEnvForwarding::Forward(Vec::new())
+    );
+
+    // Every unallowlisted var is named, even when others are allowlisted.
+    assert_eq!(
+        resolve_forwarded_build_env_with_lookup(
+            Some(CompilationKind::CargoBuild),
+            &rustflags_only,
+            &reporter,
+            |_| Some("1".to_string()),
+        ),
+        BuildEnvForwarding::Refuse(
+            "RUSTC_WRAPPER, CARGO_INCREMENTAL set locally but not in environment.allowlist"
+                .to_string()
+        )
+    );
+
+    // Allowlisted values that cannot be shell-forwarded refuse offload.
+    assert_eq!(
+        resolve_forwarded_build_env_with_lookup(
+            Some(CompilationKind::CargoBuild),
+            &rustflags_only,
+            &reporter,
+            |lookup| (lookup == "RUSTFLAGS").then(|| "-C\nopt-level=3".to_string()),
+        ),
+        BuildEnvForwarding::Refuse(
+            "RUSTFLAGS contains characters that cannot be forwarded".to_string()
+        )
+    );
+
+    // Non-cargo builds are unaffected.
+    assert_eq!(
+        resolve_forwarded_build_env_with_lookup(
+            Some(CompilationKind::BunTest),
+            &[],
+            &reporter,
+            |_| Some("sccache".to_string()),
+        ),
+        BuildEnvForwarding::Forward(Vec::new())
     );
 }
 
@@ -3336,6 +5360,132 @@ fn test_build_repo_sync_idempotency_key_for_command_distinguishes_commands() {
     assert!(apply_key.starts_with("rch-repo-sync-"));
 }
 
+#[test]
+fn test_build_exec_idempotency_key_is_stable_per_invocation() {
+    let _guard = test_guard!();
+    let cwd = Path::new("/data/projects/app");
+    let key = build_exec_idempotency_key("app", cwd, "toolu_01", Some("s1"));
+
+    assert_eq!(
+        key,
+        build_exec_idempotency_key("app", cwd, "toolu_01", Some("s1"))
+    );
+    assert!(key.starts_with("rch-build-"));
+    assert_eq!(key.len(), "rch-build-".len() + 16);
+    assert_ne!(
+        key,
+        build_exec_idempotency_key("app", cwd, "toolu_02", Some("s1"))
+    );
+    assert_ne!(
+        key,
+        build_exec_idempotency_key(
+            "app",
+            Path::new("/data/projects/app/sub"),
+            "toolu_01",
+            Some("s1")
+        )
+    );
+    assert_ne!(
+        key,
+        build_exec_idempotency_key("app", cwd, "toolu_01", Some("s2"))
+    );
+    assert_ne!(
+        key,
+        build_exec_idempotency_key("app", cwd, "toolu_01", None)
+    );
+}
+
+#[tokio::test]
+async fn test_wait_for_build_result_polls_until_build_completes() {
+    let socket_path = format!("/tmp/rch_test_coalesced_{}.sock", std::process::id());
+    let _ = std::fs::remove_file(&socket_path);
+    let listener = UnixListener::bind(&socket_path).expect("Failed to create test socket");
+
+    let daemon_handle = tokio::spawn(async move {
+        for attempt in 0..2 {
+            let (stream, _) = listener.accept().await.expect("accept");
+            let (reader, mut writer) = stream.into_split();
+            let mut request_line = String::new();
+            TokioBufReader::new(reader)
+                .read_line(&mut request_line)
+                .await
+                .expect("read request");
+            assert_eq!(request_line, "GET /builds/42\n");
+
+            let body = if attempt == 0 {
+                serde_json::json!({ "status": "error", "error": "build 42 is still running" })
+            } else {
+                serde_json::json!({
+                    "id": 42,
+                    "started_at": "2026-01-01T00:00:00Z",
+                    "completed_at": "2026-01-01T00:01:00Z",
+                    "project_id": "app",
+                    "worker_id": "worker-a",
+                    "command": "cargo build",
+                    "exit_code": 101,
+                    "duration_ms": 60000,
+                    "location": "remote",
+                    "bytes_transferred": null,
+                })
+            }
+            .to_string();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            writer.write_all(response.as_bytes()).await.expect("write");
+        }
+    });
+
+    let record = wait_for_build_result(&socket_path, 42)
+        .await
+        .expect("wait should succeed")
+        .expect("build should be found");
+    daemon_handle.await.expect("Daemon task panicked");
+    let _ = std::fs::remove_file(&socket_path);
+
+    assert_eq!(record.id, 42);
+    assert_eq!(record.exit_code, 101);
+}
+
+#[tokio::test]
+async fn test_follow_build_output_returns_finished_exit_code() {
+    let socket_path = format!("/tmp/rch_test_follow_{}.sock", std::process::id());
+    let _ = std::fs::remove_file(&socket_path);
+    let listener = UnixListener::bind(&socket_path).expect("Failed to create test socket");
+
+    let daemon_handle = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.expect("accept");
+        let (reader, mut writer) = stream.into_split();
+        let mut request_line = String::new();
+        TokioBufReader::new(reader)
+            .read_line(&mut request_line)
+            .await
+            .expect("read request");
+        assert_eq!(request_line, "GET /tail?build_id=42&lines=500\n");
+
+        let events = [
+            serde_json::json!({ "event": "phase", "phase": "execute" }),
+            serde_json::json!({ "event": "line", "seq": 0, "line": "Compiling app" }),
+            serde_json::json!({ "event": "finished", "exit_code": 101 }),
+        ];
+        let mut response = "HTTP/1.0 200 OK\r\nContent-Type: application/json\r\n\r\n".to_string();
+        for event in events {
+            response.push_str(&format!("{}\n", event));
+        }
+        writer.write_all(response.as_bytes()).await.expect("write");
+    });
+
+    let exit_code = follow_build_output(&socket_path, 42)
+        .await
+        .expect("follow should succeed");
+    daemon_handle.await.expect("Daemon task panicked");
+    let _ = std::fs::remove_file(&socket_path);
+
+    assert_eq!(exit_code, Some(101));
+}
+
 #[test]
 fn test_build_remote_dependency_preflight_command_empty_roots() {
     let _guard = test_guard!();
@@ -3538,6 +5688,9 @@ fn make_test_worker_config(id: &str) -> WorkerConfig {
         total_slots: 8,
         priority: 100,
         tags: Vec::new(),
+        max_concurrent_builds: None,
+        nice_level: None,
+        ionice_class: None,
     }
 }
 
@@ -4478,6 +6631,95 @@ edition = "2024"
     );
 }
 
+#[test]
+fn test_vendored_source_dir_follows_replace_with_chain() {
+    let _guard = test_guard!();
+    let (temp_dir, _policy) = topology_tempdir();
+    let project_root = temp_dir.path().join("project");
+    std::fs::create_dir_all(project_root.join(".cargo")).expect("create .cargo");
+    assert_eq!(vendored_source_dir(&project_root), None);
+
+    std::fs::write(
+        project_root.join(".cargo/config.toml"),
+        r#"[source.crates-io]
+replace-with = "mirror"
+
+[source.mirror]
+replace-with = "vendored-sources"
+
+[source.vendored-sources]
+directory = "../vendor"
+"#,
+    )
+    .expect("write cargo config");
+    assert_eq!(
+        vendored_source_dir(&project_root),
+        Some(project_root.join("../vendor"))
+    );
+
+    std::fs::write(
+        project_root.join(".cargo/config.toml"),
+        r#"[source.crates-io]
+replace-with = "loop"
+
+[source.loop]
+replace-with = "crates-io"
+"#,
+    )
+    .expect("write cyclic cargo config");
+    assert_eq!(vendored_source_dir(&project_root), None);
+}
+
+#[test]
+fn test_command_is_offline_matches_cargo_offline_forms() {
+    let _guard = test_guard!();
+    assert!(command_is_offline("cargo build --offline"));
+    assert!(command_is_offline("cargo test --frozen -p core"));
+    assert!(command_is_offline("CARGO_NET_OFFLINE=true cargo check"));
+    assert!(!command_is_offline("cargo build --release"));
+    assert!(!command_is_offline("cargo build --offline-mode"));
+}
+
+#[test]
+fn test_push_vendor_sync_entry_adds_registry_root_without_preflight_checks() {
+    let _guard = test_guard!();
+    let (temp_dir, policy) = topology_tempdir();
+    let project_root = temp_dir.path().join("project");
+    let vendor_root = temp_dir.path().join("vendor");
+    std::fs::create_dir_all(&project_root).expect("create project root");
+    std::fs::create_dir_all(vendor_root.join("serde")).expect("create vendor root");
+
+    let mut plan = build_sync_closure_plan(
+        std::slice::from_ref(&project_root),
+        &project_root,
+        "vendor_hash",
+        &policy,
+    );
+    push_vendor_sync_entry(&mut plan, &vendor_root, &policy).expect("vendor within topology");
+    let vendor_entry = plan
+        .iter()
+        .find(|entry| entry.mode == SyncClosureMode::VendoredRegistry)
+        .expect("vendor entry appended");
+    assert_eq!(vendor_entry.local_root, vendor_root);
+    assert!(!vendor_entry.is_primary);
+    assert!(dependency_preflight_checks_for_entry(vendor_entry).is_empty());
+
+    // A vendor directory inside a fully synced root is already covered.
+    let mut plan = build_sync_closure_plan(
+        std::slice::from_ref(&project_root),
+        &project_root,
+        "vendor_hash",
+        &policy,
+    );
+    push_vendor_sync_entry(&mut plan, &project_root.join("vendor"), &policy)
+        .expect("nested vendor within topology");
+    assert_eq!(plan.len(), 1);
+
+    assert!(
+        push_vendor_sync_entry(&mut plan, Path::new("/definitely/elsewhere"), &policy).is_err()
+    );
+}
+
 #[test]
 fn test_build_dependency_runtime_plan_keeps_workspace_member_roots() {
     let _guard = test_guard!();
@@ -4595,6 +6837,13 @@ async fn test_execute_remote_compilation_syncs_custom_cargo_target_dir_artifacts
         identity_file: "~/.ssh/mock_key".to_string(),
         slots_available: 8,
         speed_score: 90.0,
+        upload_bandwidth_bps: None,
+        rtt_ms: None,
+        rustc_version: None,
+        sccache_version: None,
+        missing_tools: vec![],
+        nice_level: None,
+        ionice_class: None,
     };
 
     let reporter = HookReporter::new(OutputVisibility::None);
@@ -4605,13 +6854,16 @@ async fn test_execute_remote_compilation_syncs_custom_cargo_target_dir_artifacts
         Vec::new(),
         Some(PathBuf::from(&custom_target_dir)),
         &rch_common::CompilationConfig::default(),
+        &rch_common::SelfHealingConfig::default(),
         None,
         Some(CompilationKind::CargoBuild),
         &reporter,
         &socket_path,
-        ColorMode::Auto,
+        &rch_common::OutputConfig::default(),
+        &rch_common::TelemetryConfig::default(),
         None,
         &policy,
+        RemoteOutput::Forward,
     )
     .await;
 
@@ -4703,6 +6955,13 @@ async fn test_artifact_sync_failure_fails_an_artifact_producing_build() {
         identity_file: "~/.ssh/mock_key".to_string(),
         slots_available: 8,
         speed_score: 90.0,
+        upload_bandwidth_bps: None,
+        rtt_ms: None,
+        rustc_version: None,
+        sccache_version: None,
+        missing_tools: vec![],
+        nice_level: None,
+        ionice_class: None,
     };
     let reporter = HookReporter::new(OutputVisibility::None);
 
@@ -4714,13 +6973,16 @@ async fn test_artifact_sync_failure_fails_an_artifact_producing_build() {
         Vec::new(),
         None,
         &rch_common::CompilationConfig::default(),
+        &rch_common::SelfHealingConfig::default(),
         None,
         Some(CompilationKind::CargoBuild),
         &reporter,
         &socket_path,
-        ColorMode::Auto,
+        &rch_common::OutputConfig::default(),
+        &rch_common::TelemetryConfig::default(),
         None,
         &policy,
+        RemoteOutput::Forward,
     )
     .await;
 
@@ -4734,13 +6996,16 @@ async fn test_artifact_sync_failure_fails_an_artifact_producing_build() {
         Vec::new(),
         None,
         &rch_common::CompilationConfig::default(),
+        &rch_common::SelfHealingConfig::default(),
         None,
         Some(CompilationKind::CargoTest),
         &reporter,
         &socket_path,
-        ColorMode::Auto,
+        &rch_common::OutputConfig::default(),
+        &rch_common::TelemetryConfig::default(),
         None,
         &policy,
+        RemoteOutput::Forward,
     )
     .await;
 
@@ -4781,6 +7046,7 @@ async fn test_cargo_test_delegates_to_rch_exec() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -4817,6 +7083,7 @@ async fn test_cargo_test_with_args_delegates_correctly() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -4834,6 +7101,40 @@ async fn test_cargo_test_with_args_delegates_correctly() {
     assert!(ssh_logs.is_empty(), "Hook should not invoke SSH");
 }
 
+#[tokio::test]
+#[serial(mock_global)]
+async fn test_delegation_forwards_sanitized_session_id() {
+    let _lock = test_lock().lock().await;
+    let _guard = test_guard!();
+    let input = || HookInput {
+        tool_name: "Bash".to_string(),
+        tool_input: ToolInput {
+            command: "cd /tmp && cargo build".to_string(),
+            description: None,
+        },
+        session_id: Some("abc-123;rm -rf /".to_string()),
+        tool_use_id: Some("toolu_01$(id)".to_string()),
+    };
+
+    // The session only matters to round-robin queueing.
+    let mut config = rch_common::RchConfig::default();
+    crate::config::set_test_config_override(Some(config.clone()));
+    let output = process_hook(input()).await;
+    assert_eq!(
+        delegated_command(&output),
+        "cd /tmp && RCH_TOOL_USE_ID=toolu_01id rch exec -- cargo build"
+    );
+
+    config.selection.fairness.queue = rch_common::QueueFairness::RoundRobin;
+    crate::config::set_test_config_override(Some(config));
+    let output = process_hook(input()).await;
+    crate::config::set_test_config_override(None);
+    assert_eq!(
+        delegated_command(&output),
+        "cd /tmp && RCH_SESSION_ID=abc-123rm-rf RCH_TOOL_USE_ID=toolu_01id rch exec -- cargo build"
+    );
+}
+
 #[tokio::test]
 #[serial(mock_global)]
 async fn test_cargo_test_remote_build_failure() {
@@ -4867,6 +7168,13 @@ async fn test_cargo_test_remote_build_failure() {
             identity_file: "~/.ssh/test_key".to_string(),
             slots_available: 8,
             speed_score: 85.0,
+            upload_bandwidth_bps: None,
+            rtt_ms: None,
+            rustc_version: None,
+            sccache_version: None,
+            missing_tools: vec![],
+            nice_level: None,
+            ionice_class: None,
         }),
         reason: SelectionReason::Success,
         build_id: None,
@@ -4883,6 +7191,7 @@ async fn test_cargo_test_remote_build_failure() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -4928,6 +7237,13 @@ async fn test_cargo_test_with_filter() {
             identity_file: "~/.ssh/test_key".to_string(),
             slots_available: 8,
             speed_score: 85.0,
+            upload_bandwidth_bps: None,
+            rtt_ms: None,
+            rustc_version: None,
+            sccache_version: None,
+            missing_tools: vec![],
+            nice_level: None,
+            ionice_class: None,
         }),
         reason: SelectionReason::Success,
         build_id: None,
@@ -4945,6 +7261,7 @@ async fn test_cargo_test_with_filter() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -4989,6 +7306,13 @@ async fn test_cargo_test_with_test_threads() {
             identity_file: "~/.ssh/test_key".to_string(),
             slots_available: 8,
             speed_score: 85.0,
+            upload_bandwidth_bps: None,
+            rtt_ms: None,
+            rustc_version: None,
+            sccache_version: None,
+            missing_tools: vec![],
+            nice_level: None,
+            ionice_class: None,
         }),
         reason: SelectionReason::Success,
         build_id: None,
@@ -5006,6 +7330,7 @@ async fn test_cargo_test_with_test_threads() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -5055,6 +7380,13 @@ async fn test_cargo_test_signal_killed() {
             identity_file: "~/.ssh/test_key".to_string(),
             slots_available: 8,
             speed_score: 85.0,
+            upload_bandwidth_bps: None,
+            rtt_ms: None,
+            rustc_version: None,
+            sccache_version: None,
+            missing_tools: vec![],
+            nice_level: None,
+            ionice_class: None,
         }),
         reason: SelectionReason::Success,
         build_id: None,
@@ -5071,6 +7403,7 @@ async fn test_cargo_test_signal_killed() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -5121,6 +7454,13 @@ async fn test_cargo_test_signal_killed_with_toolchain_path_does_not_fallback_loc
             identity_file: "~/.ssh/test_key".to_string(),
             slots_available: 8,
             speed_score: 85.0,
+            upload_bandwidth_bps: None,
+            rtt_ms: None,
+            rustc_version: None,
+            sccache_version: None,
+            missing_tools: vec![],
+            nice_level: None,
+            ionice_class: None,
         }),
         reason: SelectionReason::Success,
         build_id: None,
@@ -5137,6 +7477,7 @@ async fn test_cargo_test_signal_killed_with_toolchain_path_does_not_fallback_loc
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -5181,6 +7522,13 @@ async fn test_cargo_test_toolchain_fallback() {
             identity_file: "~/.ssh/test_key".to_string(),
             slots_available: 8,
             speed_score: 85.0,
+            upload_bandwidth_bps: None,
+            rtt_ms: None,
+            rustc_version: None,
+            sccache_version: None,
+            missing_tools: vec![],
+            nice_level: None,
+            ionice_class: None,
         }),
         reason: SelectionReason::Success,
         build_id: None,
@@ -5197,6 +7545,7 @@ async fn test_cargo_test_toolchain_fallback() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -5260,10 +7609,10 @@ fn test_cargo_nextest_classification() {
 fn test_artifact_patterns_for_test_commands() {
     let _guard = test_guard!();
     // Verify test commands use minimal artifact patterns
-    let test_patterns = get_artifact_patterns(Some(CompilationKind::CargoTest));
-    let check_patterns = get_artifact_patterns(Some(CompilationKind::CargoCheck));
-    let clippy_patterns = get_artifact_patterns(Some(CompilationKind::CargoClippy));
-    let build_patterns = get_artifact_patterns(Some(CompilationKind::CargoBuild));
+    let test_patterns = get_artifact_patterns(Some(CompilationKind::CargoTest), "cargo test");
+    let check_patterns = get_artifact_patterns(Some(CompilationKind::CargoCheck), "cargo check");
+    let clippy_patterns = get_artifact_patterns(Some(CompilationKind::CargoClippy), "cargo clippy");
+    let build_patterns = get_artifact_patterns(Some(CompilationKind::CargoBuild), "cargo build");
 
     // Test patterns should be smaller (more targeted)
     // They should include coverage/results but not full target/
@@ -5299,10 +7648,54 @@ fn test_artifact_patterns_for_test_commands() {
     );
 }
 
+#[test]
+fn test_artifact_patterns_retrieve_cargo_timings_report() {
+    let _guard = test_guard!();
+    let plain = get_artifact_patterns(Some(CompilationKind::CargoBuild), "cargo build");
+    assert!(!plain.iter().any(|p| p.contains("cargo-timings")));
+
+    for command in ["cargo build --timings", "cargo build --timings=html,json"] {
+        let patterns = get_artifact_patterns(Some(CompilationKind::CargoBuild), command);
+        assert!(
+            patterns.iter().any(|p| p == "target/cargo-timings/**"),
+            "{command}: timings report must be retrieved: {patterns:?}"
+        );
+    }
+
+    // Streamed kinds still bring the report back, including into a custom
+    // CARGO_TARGET_DIR (where the sync root is the target dir itself).
+    let check = get_artifact_patterns(Some(CompilationKind::CargoCheck), "cargo check --timings");
+    assert!(check.iter().any(|p| p == "target/cargo-timings/**"));
+    let custom = get_custom_target_artifact_patterns(
+        Some(CompilationKind::CargoCheck),
+        "cargo check --timings",
+    );
+    assert_eq!(custom, vec!["cargo-timings/**".to_string()]);
+}
+
+#[test]
+fn test_artifact_patterns_for_wasm_toolchains() {
+    let _guard = test_guard!();
+    assert_eq!(
+        get_artifact_patterns(Some(CompilationKind::TrunkBuild), "trunk build"),
+        vec!["dist/**".to_string()]
+    );
+    assert_eq!(
+        get_artifact_patterns(Some(CompilationKind::WasmPackBuild), "wasm-pack build"),
+        vec!["pkg/**".to_string()]
+    );
+    // Outputs live under the project root, not in a custom CARGO_TARGET_DIR.
+    assert!(
+        get_custom_target_artifact_patterns(Some(CompilationKind::TrunkBuild), "trunk build")
+            .is_empty()
+    );
+}
+
 #[test]
 fn test_custom_target_artifact_patterns_for_cargo_test_are_skipped() {
     let _guard = test_guard!();
-    let patterns = get_custom_target_artifact_patterns(Some(CompilationKind::CargoTest));
+    let patterns =
+        get_custom_target_artifact_patterns(Some(CompilationKind::CargoTest), "cargo test");
 
     assert!(
         patterns.is_empty(),
@@ -5315,11 +7708,13 @@ fn test_custom_target_artifact_patterns_for_diagnostic_commands_are_skipped() {
     let _guard = test_guard!();
 
     assert!(
-        get_custom_target_artifact_patterns(Some(CompilationKind::CargoCheck)).is_empty(),
+        get_custom_target_artifact_patterns(Some(CompilationKind::CargoCheck), "cargo check")
+            .is_empty(),
         "cargo check output is streamed; do not sync a custom target dir"
     );
     assert!(
-        get_custom_target_artifact_patterns(Some(CompilationKind::CargoClippy)).is_empty(),
+        get_custom_target_artifact_patterns(Some(CompilationKind::CargoClippy), "cargo clippy")
+            .is_empty(),
         "cargo clippy output is streamed; do not sync a custom target dir"
     );
 }
@@ -5327,7 +7722,10 @@ fn test_custom_target_artifact_patterns_for_diagnostic_commands_are_skipped() {
 #[test]
 fn test_custom_target_artifact_patterns_for_nextest_are_target_relative() {
     let _guard = test_guard!();
-    let patterns = get_custom_target_artifact_patterns(Some(CompilationKind::CargoNextest));
+    let patterns = get_custom_target_artifact_patterns(
+        Some(CompilationKind::CargoNextest),
+        "cargo nextest run",
+    );
 
     assert!(
         !patterns.iter().any(|p| p == "**"),
@@ -5343,6 +7741,43 @@ fn test_custom_target_artifact_patterns_for_nextest_are_target_relative() {
     );
 }
 
+#[test]
+fn test_nextest_artifact_patterns_retrieve_junit_for_profile() {
+    let _guard = test_guard!();
+    let nextest = Some(CompilationKind::CargoNextest);
+
+    // No explicit profile: the whole nextest store, which holds every
+    // profile's `junit.xml`.
+    let patterns = get_artifact_patterns(nextest, "cargo nextest run");
+    assert!(patterns.iter().any(|p| p == "target/nextest/**"));
+
+    for command in [
+        "cargo nextest run --profile ci",
+        "cargo nextest run --profile=ci --workspace",
+        "cargo nextest run -P ci -- --include-ignored",
+        "NEXTEST_PROFILE=ci cargo nextest run",
+    ] {
+        let patterns = get_artifact_patterns(nextest, command);
+        assert!(
+            patterns.iter().any(|p| p == "target/nextest/ci/**"),
+            "{command}: target/nextest/ci/junit.xml must be retrieved: {patterns:?}"
+        );
+        assert!(!patterns.iter().any(|p| p == "target/nextest/**"));
+    }
+
+    let custom = get_custom_target_artifact_patterns(nextest, "cargo nextest run --profile ci");
+    assert!(custom.iter().any(|p| p == "nextest/ci/**"));
+
+    // Profile parsing stops at `--` and refuses glob-widening names.
+    assert_eq!(nextest_profile("cargo nextest run -- --profile ci"), None);
+    assert_eq!(nextest_profile("cargo nextest run --profile '*'"), None);
+    assert_eq!(nextest_profile("cargo nextest run --profile ../x"), None);
+    assert_eq!(
+        nextest_profile("NEXTEST_PROFILE=nightly cargo nextest run --profile ci").as_deref(),
+        Some("ci")
+    );
+}
+
 #[test]
 fn test_custom_target_artifact_patterns_for_build_commands_capture_outputs_only() {
     let _guard = test_guard!();
@@ -5351,7 +7786,7 @@ fn test_custom_target_artifact_patterns_for_build_commands_capture_outputs_only(
         CompilationKind::CargoDoc,
         CompilationKind::Rustc,
     ] {
-        let patterns = get_custom_target_artifact_patterns(Some(kind));
+        let patterns = get_custom_target_artifact_patterns(Some(kind), "");
 
         // No longer the firehose: must NOT sync the entire per-job target dir.
         assert!(
@@ -5398,7 +7833,8 @@ fn test_custom_target_patterns_match_a_binary_but_not_cache() {
     // the cache trees. Mirrors how the rsync filter chain evaluates them:
     // an explicit `- <pat>` exclude wins over a later `debug/**` include.
     let _guard = test_guard!();
-    let patterns = get_custom_target_artifact_patterns(Some(CompilationKind::CargoBuild));
+    let patterns =
+        get_custom_target_artifact_patterns(Some(CompilationKind::CargoBuild), "cargo build");
 
     let (excludes, includes): (Vec<&String>, Vec<&String>) =
         patterns.iter().partition(|p| p.starts_with("- "));
@@ -5545,6 +7981,56 @@ fn test_is_filtered_test_command_with_separator() {
     );
 }
 
+#[test]
+fn test_is_filtered_test_command_matrix() {
+    let _guard = test_guard!();
+    let cases = [
+        // Leading env assignments are not filters
+        ("FOO=bar cargo test", false),
+        ("RUST_LOG=debug RUST_BACKTRACE=1 cargo test", false),
+        ("env RUSTFLAGS='-C target-cpu=native' cargo test", false),
+        ("FOO=bar cargo test my_test", true),
+        ("CARGO_PROFILE=test cargo build", false),
+        // Everything after `--` belongs to the test binary
+        ("cargo test -- my_test --nocapture", true),
+        ("cargo test -- --nocapture my_test", true),
+        ("cargo test -- --test-threads 4", false),
+        ("cargo test -- --skip slow_tests", false),
+        ("cargo test -- --skip slow_tests my_test", true),
+        ("cargo test -- --format json --exact", false),
+        // cargo flags and their values
+        ("cargo test --features 'serde json' --release", false),
+        ("cargo test -F serde", false),
+        ("cargo test --workspace --exclude rch-wkr", false),
+        ("cargo +nightly test", false),
+        ("cargo +nightly test my_test", true),
+        ("cargo --locked test -p rch", false),
+        ("cargo t my_test", true),
+        ("/usr/local/bin/cargo test my_test", true),
+        // Shell plumbing around the command
+        ("cargo test 2>&1 | tee test.log", false),
+        ("cargo test > out.log", false),
+        ("cd rch && cargo test my_test", true),
+        ("cd my_test && cargo test", false),
+        // nextest and bun
+        ("cargo nextest run", false),
+        ("cargo nextest run --partition count:1/2", false),
+        ("cargo nextest run --retries 2 my_test", true),
+        ("bun test", false),
+        ("bun test --timeout 5000", false),
+        ("bun test auth.test.ts", true),
+        // Not a test command
+        ("cargo build --bin test", false),
+    ];
+    for (command, expected) in cases {
+        assert_eq!(
+            is_filtered_test_command(command),
+            expected,
+            "is_filtered_test_command({command:?})"
+        );
+    }
+}
+
 #[test]
 fn test_has_ignored_only_flag() {
     let _guard = test_guard!();
@@ -5581,6 +8067,18 @@ fn test_has_exact_flag() {
     assert!(!has_exact_flag("cargo test -- --nocapture"), "No --exact");
 }
 
+#[test]
+fn test_has_timings_flag() {
+    let _guard = test_guard!();
+    assert!(has_timings_flag("cargo build --timings"));
+    assert!(has_timings_flag(
+        "cargo build --release --timings=html,json"
+    ));
+    assert!(!has_timings_flag("cargo build --release"));
+    // Arguments after `--` go to the test binary, not cargo.
+    assert!(!has_timings_flag("cargo test -- --timings"));
+}
+
 #[test]
 fn test_estimate_cores_filtered_tests() {
     let _guard = test_guard!();
@@ -5626,6 +8124,17 @@ fn test_estimate_cores_filtered_tests() {
         &config,
     );
     assert_eq!(include_ignored, 10, "--include-ignored uses full slots");
+
+    // Env prefixes and libtest flag values are not filters
+    let env_prefixed = estimate_cores_for_command(
+        Some(CompilationKind::CargoTest),
+        "RUST_BACKTRACE=1 cargo test -- --skip slow",
+        &config,
+    );
+    assert_eq!(
+        env_prefixed, 10,
+        "env prefix and --skip value use full slots"
+    );
 }
 
 #[test]
@@ -5823,6 +8332,23 @@ fn test_parse_selection_response_preserves_unknown_structured_reason_detail() {
     );
 }
 
+#[test]
+fn test_parse_selection_response_reads_fair_queue_position() {
+    let _guard = test_guard!();
+    let json = serde_json::json!({
+        "selection_protocol_version": rch_common::SELECTION_RESPONSE_PROTOCOL_VERSION,
+        "worker": null,
+        "reason": { "queued_fair": { "position": 3 } },
+        "build_id": null,
+        "diagnostics": null
+    })
+    .to_string();
+
+    let response = parse_selection_response(&json).expect("selection response parses");
+
+    assert_eq!(response.reason, SelectionReason::QueuedFair { position: 3 });
+}
+
 #[test]
 fn test_parse_selection_response_rejects_unsupported_protocol_version() {
     let _guard = test_guard!();
@@ -5893,6 +8419,10 @@ async fn test_daemon_query_connect_timeout_fail_open() {
         None,
         false,
         &[],
+        None,
+        None,
+        &[],
+        None,
     )
     .await;
 
@@ -5947,6 +8477,7 @@ async fn test_process_hook_timeout_fail_open() {
             description: None,
         },
         session_id: None,
+        tool_use_id: None,
     };
 
     let output = process_hook(input).await;
@@ -6008,6 +8539,10 @@ async fn test_daemon_query_partial_response_timeout() {
         None,
         false,
         &[],
+        None,
+        None,
+        &[],
+        None,
     )
     .await;
 
@@ -6029,6 +8564,33 @@ fn test_queue_when_busy_enabled_parser() {
     assert!(!queue_when_busy_enabled_from(Some("off")));
 }
 
+#[test]
+fn test_well_known_socket_paths_skip_configured_and_duplicates() {
+    let _guard = test_guard!();
+    assert_eq!(
+        well_known_socket_paths(
+            "/custom/rch.sock",
+            Some("/run/user/1000"),
+            Some(Path::new("/home/u/.cache")),
+        ),
+        vec![
+            "/run/user/1000/rch.sock".to_string(),
+            "/home/u/.cache/rch/rch.sock".to_string(),
+            "/tmp/rch.sock".to_string(),
+        ]
+    );
+    // The configured path is never a candidate, and a blank runtime dir is
+    // ignored like default_socket_path does.
+    assert_eq!(
+        well_known_socket_paths("/tmp/rch.sock", Some("  "), None),
+        Vec::<String>::new()
+    );
+    assert_eq!(
+        well_known_socket_paths("/tmp/other.sock", Some("/tmp"), None),
+        vec!["/tmp/rch.sock".to_string()]
+    );
+}
+
 #[test]
 fn test_daemon_response_timeout_defaults_and_overrides() {
     let _guard = test_guard!();
@@ -6455,6 +9017,54 @@ fn test_timing_history_serialization() {
     assert_eq!(data.remote_samples.len(), 1);
 }
 
+#[test]
+fn test_timing_history_corrupt_file_is_backed_up() {
+    let _guard = test_guard!();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("timing_history.json");
+
+    // Missing file: fresh history, nothing created.
+    assert!(TimingHistory::load_from_path(&path).entries.is_empty());
+    assert!(!corrupt_timing_history_path(&path).exists());
+
+    // A save cut short leaves truncated JSON behind.
+    let truncated = r#"{"entries":{"proj:Some(CargoBuild)":{"local_samples":[{"timest"#;
+    std::fs::write(&path, truncated).unwrap();
+    let loaded = TimingHistory::load_from_path(&path);
+    assert!(loaded.entries.is_empty());
+    assert!(!path.exists(), "corrupt file is moved out of the way");
+    let backup = corrupt_timing_history_path(&path);
+    assert_eq!(backup, dir.path().join("timing_history.json.corrupt"));
+    assert_eq!(std::fs::read_to_string(&backup).unwrap(), truncated);
+}
+
+#[test]
+fn test_timing_history_save_tolerates_stale_temp_file() {
+    let _guard = test_guard!();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("timing_history.json");
+    // Leftover from the old fixed-name temp scheme, here a directory that a
+    // plain write could not replace.
+    std::fs::create_dir(path.with_extension("tmp")).unwrap();
+
+    let mut history = TimingHistory::default();
+    history.record("proj", Some(CompilationKind::CargoBuild), 1500, true);
+    history.save_to_path(&path);
+
+    let loaded = TimingHistory::load_from_path(&path);
+    let data = loaded
+        .get("proj", Some(CompilationKind::CargoBuild))
+        .expect("saved entry");
+    assert_eq!(data.remote_samples.len(), 1);
+    let leftovers: Vec<_> = std::fs::read_dir(dir.path())
+        .unwrap()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|name| name.contains(".json.tmp."))
+        .collect();
+    assert!(leftovers.is_empty(), "temp files left: {leftovers:?}");
+}
+
 // ========================================================================
 // t18 — record_build_timing lock-scope discipline. Verify the write
 // guard is dropped BEFORE save_to_disk, so other readers/writers
@@ -8059,6 +10669,7 @@ async fn test_hook_classification_fail_open_all_compilation_kinds() {
                 description: None,
             },
             session_id: None,
+            tool_use_id: None,
         };
 
         let output = process_hook(input).await;
@@ -8096,6 +10707,7 @@ async fn test_hook_non_compilation_passthrough() {
                 description: None,
             },
             session_id: None,
+            tool_use_id: None,
         };
 
         let output = process_hook(input).await;
@@ -8120,6 +10732,7 @@ async fn test_hook_non_bash_tools_always_allowed() {
                 description: None,
             },
             session_id: None,
+            tool_use_id: None,
         };
 
         let output = process_hook(input).await;
@@ -8188,3 +10801,56 @@ fn test_structured_log_output_per_tier() {
             .any(|t| t.tier == 4 && t.decision == TierDecision::Reject)
     );
 }
+
+#[tokio::test]
+async fn test_transfer_slots_cap_concurrent_holders() {
+    let _guard = test_guard!();
+    let dir = tempfile::TempDir::new().expect("tempdir");
+    let reporter = HookReporter::new(OutputVisibility::None);
+    let wait = Duration::from_millis(600);
+
+    let first = acquire_transfer_slot_in(dir.path(), 2, wait, &reporter).await;
+    let second = acquire_transfer_slot_in(dir.path(), 2, wait, &reporter).await;
+    assert!(transfer_slot_path(dir.path(), 0).exists());
+    assert!(transfer_slot_path(dir.path(), 1).exists());
+
+    // Both slots are held, so a third holder only gets through once one drops.
+    let release = async {
+        sleep(Duration::from_millis(300)).await;
+        drop(first);
+    };
+    let started = Instant::now();
+    let (third, ()) = tokio::join!(
+        acquire_transfer_slot_in(dir.path(), 2, Duration::from_secs(10), &reporter),
+        release
+    );
+    assert!(started.elapsed() >= Duration::from_millis(250));
+
+    // With both slots held again, a bounded wait gives up and proceeds.
+    let started = Instant::now();
+    let _waived = acquire_transfer_slot_in(dir.path(), 2, wait, &reporter).await;
+    assert!(started.elapsed() >= wait);
+    drop((second, third));
+}
+
+#[test]
+fn test_remote_fallback_summary_names_transfer_catalog_code() {
+    let err: anyhow::Error = TransferError::SyncFailed {
+        reason: "rsync failed".to_string(),
+        exit_code: Some(11),
+        stderr: "rsync: write failed: No space left on device (28)".to_string(),
+    }
+    .into();
+    let err = err.context("syncing project to w1");
+    let summary = remote_fallback_summary("remote pipeline failed", &err);
+    assert!(
+        summary.starts_with("[RCH] local (remote pipeline failed) RCH-E404: "),
+        "{summary}"
+    );
+
+    let other = anyhow::anyhow!("worker vanished");
+    assert_eq!(
+        remote_fallback_summary("remote pipeline failed", &other),
+        "[RCH] local (remote pipeline failed)"
+    );
+}
@@ -5,9 +5,10 @@
 //! local build time + expected speedup for offload gating.
 //!
 //! Principal items: [`record_build_timing`] (the live write path, called
-//! from the remote-classification path after every offloaded build) and the
-//! estimator surface ([`estimate_timing_for_build`] / [`TimingEstimate`],
-//! currently exercised only by tests). [`TimingHistory`] / [`ProjectTimingData`]
+//! after every successful offloaded build and local fallback), the
+//! estimator surface ([`estimate_timing_for_build`] / [`TimingEstimate`]) and
+//! the offload gate [`evaluate_timing_gate`] consulted by `run_exec` before it
+//! asks the daemon for a worker. [`TimingHistory`] / [`ProjectTimingData`]
 //! / [`TimingRecord`] are the on-disk model; [`timing_cache`] is the
 //! process-global `OnceLock` that coalesces disk I/O within a process.
 use super::*;
@@ -19,10 +20,10 @@ use super::*;
 use std::collections::HashMap;
 
 // Timing infrastructure: feeds the global `TIMING_CACHE` (live; populated
-// by `record_build_timing` after every offloaded build). The estimator
-// surface that consumes the cache (`estimate_timing_for_build`,
-// `TimingEstimate`) is currently exercised only by unit tests — those
-// items keep `#[allow(dead_code)]` until production callers materialize.
+// by `record_build_timing` after every successful offloaded build or
+// local fallback). The estimator surface that consumes the cache
+// (`estimate_timing_for_build`, `TimingEstimate`) backs the `run_exec`
+// offload gate (`evaluate_timing_gate`).
 //
 // `MAX_TIMING_SAMPLES` bounds the per-project sample ring buffer (enforced in
 // `ProjectTimingData::add_sample`). `MAX_TIMING_PROJECTS` bounds the
@@ -149,11 +150,10 @@ pub(super) struct TimingHistory {
 /// `load_from_disk` cost; subsequent calls in the same process operate on
 /// the in-memory copy and write through to disk on update.
 ///
-/// Consumers (live as of t19 close): two `record_build_timing` call sites
-/// in `hook::handle_selection_response`. The estimator surface
-/// (`estimate_timing_for_build`, `TimingEstimate`) is currently exercised
-/// only by unit tests; those keep their `#[allow(dead_code)]` annotation
-/// until a production consumer wires them up.
+/// Consumers: the `record_build_timing` call sites in `hook::run_exec`
+/// (remote result + timed local fallback) and `hook::handle_selection_response`,
+/// plus `estimate_timing_for_build`, which `run_exec` reads through
+/// `evaluate_timing_gate` before querying the daemon.
 static TIMING_CACHE: std::sync::OnceLock<std::sync::RwLock<TimingHistory>> =
     std::sync::OnceLock::new();
 
@@ -168,22 +168,64 @@ pub(super) fn timing_cache() -> &'static std::sync::RwLock<TimingHistory> {
 impl TimingHistory {
     /// Load timing history from disk. Returns empty history on error.
     fn load_from_disk() -> Self {
-        let Some(path) = timing_history_path() else {
-            return Self::default();
-        };
+        match timing_history_path() {
+            Some(path) => Self::load_from_path(&path),
+            None => Self::default(),
+        }
+    }
 
-        match std::fs::read_to_string(&path) {
-            Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
-            Err(_) => Self::default(),
+    /// Load timing history from `path`.
+    ///
+    /// A missing file is a fresh history. A file that does not parse (for
+    /// example one cut short by a crash mid-write) is moved aside to
+    /// [`corrupt_timing_history_path`] with a warning, so the samples can be
+    /// inspected or repaired instead of being overwritten by the next save.
+    pub(super) fn load_from_path(path: &Path) -> Self {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Self::default(),
+            Err(e) => {
+                warn!("Failed to read timing history {}: {}", path.display(), e);
+                return Self::default();
+            }
+        };
+        match serde_json::from_str(&content) {
+            Ok(history) => history,
+            Err(parse_err) => {
+                let backup = corrupt_timing_history_path(path);
+                match std::fs::rename(path, &backup) {
+                    Ok(()) => warn!(
+                        "Timing history {} is corrupt ({}); moved it to {} and starting fresh",
+                        path.display(),
+                        parse_err,
+                        backup.display()
+                    ),
+                    Err(e) => warn!(
+                        "Timing history {} is corrupt ({}) and could not be moved to {}: {}",
+                        path.display(),
+                        parse_err,
+                        backup.display(),
+                        e
+                    ),
+                }
+                Self::default()
+            }
         }
     }
 
     /// Save timing history to disk. Logs warnings on error but does not propagate.
     fn save_to_disk(&self) {
-        let Some(path) = timing_history_path() else {
-            return;
-        };
+        if let Some(path) = timing_history_path() {
+            self.save_to_path(&path);
+        }
+    }
 
+    /// Write timing history to `path` through a temp file and rename.
+    ///
+    /// The temp name is unique per process and write, so concurrent hooks
+    /// never share one and a temp file left behind by a crashed writer
+    /// cannot get in the way. A failed write or rename removes its temp file.
+    pub(super) fn save_to_path(&self, path: &Path) {
         // Ensure parent directory exists
         if let Some(parent) = path.parent()
             && let Err(e) = std::fs::create_dir_all(parent)
@@ -196,8 +238,6 @@ impl TimingHistory {
             return;
         }
 
-        // Write atomically using temp file
-        let temp_path = path.with_extension("tmp");
         let content = match serde_json::to_string_pretty(self) {
             Ok(c) => c,
             Err(e) => {
@@ -205,7 +245,16 @@ impl TimingHistory {
                 return;
             }
         };
+        let temp_path = path.with_extension(format!(
+            "json.tmp.{}.{}",
+            std::process::id(),
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or(0)
+        ));
         if let Err(e) = std::fs::write(&temp_path, &content) {
+            let _ = std::fs::remove_file(&temp_path);
             warn!(
                 "Failed to write timing history to {}: {}",
                 temp_path.display(),
@@ -213,7 +262,8 @@ impl TimingHistory {
             );
             return;
         }
-        if let Err(e) = std::fs::rename(&temp_path, &path) {
+        if let Err(e) = std::fs::rename(&temp_path, path) {
+            let _ = std::fs::remove_file(&temp_path);
             warn!(
                 "Failed to rename timing history {} -> {}: {}",
                 temp_path.display(),
@@ -240,6 +290,15 @@ impl TimingHistory {
         self.entries.get(&Self::key(project, kind))
     }
 
+    /// Whether any build of `project` (any kind) has completed remotely.
+    pub(super) fn has_remote_samples(&self, project: &str) -> bool {
+        self.entries.iter().any(|(key, data)| {
+            key.strip_prefix(project)
+                .is_some_and(|rest| rest.starts_with(':'))
+                && !data.remote_samples.is_empty()
+        })
+    }
+
     /// Record a timing sample.
     ///
     /// Implements LRU eviction to prevent unbounded memory growth:
@@ -275,6 +334,14 @@ fn timing_history_path() -> Option<PathBuf> {
     dirs::cache_dir().map(|dir| dir.join("rch").join("timing_history.json"))
 }
 
+/// Where an unparseable timing history is moved (`timing_history.json.corrupt`).
+/// A later corruption replaces the previous backup.
+pub(super) fn corrupt_timing_history_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".corrupt");
+    PathBuf::from(name)
+}
+
 /// Record a build timing to the history store.
 ///
 /// Updates the in-memory cache immediately, then persists to disk.
@@ -326,7 +393,6 @@ pub(super) fn record_build_timing(
 ///
 /// Used to determine whether a build is worth offloading based on
 /// predicted local execution time and expected speedup.
-#[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub(super) struct TimingEstimate {
     /// Predicted local build time in milliseconds.
@@ -346,7 +412,6 @@ pub(super) struct TimingEstimate {
 ///
 /// When no historical data is available, returns None to trigger fail-open
 /// behavior (allow offload attempt).
-#[allow(dead_code)]
 #[allow(unused_variables)] // config used for future speedscore integration
 pub(super) fn estimate_timing_for_build(
     project: &str,
@@ -356,7 +421,18 @@ pub(super) fn estimate_timing_for_build(
     // Read from in-memory cache (zero disk I/O after first load)
     let cache = timing_cache();
     let history = cache.read().ok()?;
+    estimate_timing_from_history(&history, project, kind)
+}
 
+/// Estimate timing for a project+kind from an explicit [`TimingHistory`].
+///
+/// Pure counterpart of [`estimate_timing_for_build`] (no global cache), so
+/// the gate thresholds can be tested against a seeded history.
+pub(super) fn estimate_timing_from_history(
+    history: &TimingHistory,
+    project: &str,
+    kind: Option<CompilationKind>,
+) -> Option<TimingEstimate> {
     // Look up timing data for this project+kind
     let data = history.get(project, kind)?;
 
@@ -371,3 +447,68 @@ pub(super) fn estimate_timing_for_build(
         predicted_speedup: speedup,
     })
 }
+
+/// Outcome of the timing-based offload gate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum TimingGateDecision {
+    /// Offload as usual. Also the fail-open answer when history is missing.
+    Offload,
+    /// The predicted local build is shorter than `min_local_time_ms`.
+    LocalBuildTooShort { predicted_local_ms: u64 },
+    /// The predicted speedup is below `remote_speedup_threshold`.
+    SpeedupTooLow { predicted_speedup: f64 },
+}
+
+impl TimingGateDecision {
+    /// Whether the gate decided the build should stay local.
+    pub(super) fn keeps_local(self) -> bool {
+        !matches!(self, TimingGateDecision::Offload)
+    }
+
+    /// Human-readable detail for verbose reporting.
+    pub(super) fn detail(self, config: &rch_common::CompilationConfig) -> String {
+        match self {
+            TimingGateDecision::Offload => "offload".to_string(),
+            TimingGateDecision::LocalBuildTooShort { predicted_local_ms } => format!(
+                "predicted local {}ms < min_local_time_ms {}ms",
+                predicted_local_ms, config.min_local_time_ms
+            ),
+            TimingGateDecision::SpeedupTooLow { predicted_speedup } => format!(
+                "predicted speedup {:.2}x < remote_speedup_threshold {:.2}x",
+                predicted_speedup, config.remote_speedup_threshold
+            ),
+        }
+    }
+}
+
+/// Decide whether a build is worth offloading given its timing estimate.
+///
+/// Fail-open: without an estimate (no local history for this project/kind)
+/// the build is offloaded, matching the behavior before timing gating
+/// existed. Both thresholds are strict: a build predicted to take exactly
+/// `min_local_time_ms`, or to speed up by exactly `remote_speedup_threshold`,
+/// is still offloaded.
+pub(super) fn evaluate_timing_gate(
+    estimate: Option<&TimingEstimate>,
+    config: &rch_common::CompilationConfig,
+) -> TimingGateDecision {
+    let Some(estimate) = estimate else {
+        return TimingGateDecision::Offload;
+    };
+
+    if estimate.predicted_local_ms < config.min_local_time_ms {
+        return TimingGateDecision::LocalBuildTooShort {
+            predicted_local_ms: estimate.predicted_local_ms,
+        };
+    }
+
+    if let Some(speedup) = estimate.predicted_speedup
+        && speedup < config.remote_speedup_threshold
+    {
+        return TimingGateDecision::SpeedupTooLow {
+            predicted_speedup: speedup,
+        };
+    }
+
+    TimingGateDecision::Offload
+}
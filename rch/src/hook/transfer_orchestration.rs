@@ -26,21 +26,30 @@ use super::artifact_patterns::{
     kind_produces_transferable_artifacts,
 };
 use super::cargo_target_dir::{
-    cargo_target_env_allowlist, cargo_target_env_overrides, remote_cargo_pooled_target_dir_name,
-    remote_cargo_target_dir_name, stale_target_reap_idle_hours, target_reuse_disabled,
+    cargo_target_env_allowlist, cargo_target_env_overrides, env_allowlist_contains,
+    remote_cargo_pooled_target_dir_name, remote_cargo_target_dir_name, remote_sccache_env,
+    remote_shared_build_dir, stale_target_reap_idle_hours, target_reuse_disabled,
 };
-use super::daemon_ipc::urlencoding_encode;
+use super::daemon_ipc::{effective_socket_path, urlencoding_encode};
 use super::dependency_closure::{
     SyncClosureMode, SyncClosurePlanEntry, SyncRootOutcome, build_sync_closure_manifest,
-    build_sync_closure_plan, merge_sync_result, verify_remote_dependency_manifests,
-    workspace_metadata_sync_patterns,
+    build_sync_closure_plan, merge_sync_result, push_vendor_sync_entry,
+    verify_remote_dependency_manifests, workspace_metadata_sync_patterns,
 };
+use super::feature_parity::{explicit_feature_flags, unforwarded_feature_env_vars};
 use super::formatting::{cache_hit, detect_target_label, emit_job_banner, render_compile_summary};
-use super::progress_reporting::{BuildHeartbeatLoop, mark_heartbeat_progress};
+use super::progress_reporting::{
+    BuildHeartbeatLoop, mark_heartbeat_progress, record_heartbeat_output,
+};
+use super::remote_color::{remote_color_mode, with_color_flag};
 use super::remote_result::RemoteExecutionResult;
 use super::repo_updater::maybe_sync_repo_set_with_repo_updater;
 use super::ssh::ensure_worker_projects_topology;
+use super::transfer_slots::acquire_transfer_slot;
 use super::*;
+use crate::toolchain::local_rustc_version;
+use crate::transfer::{artifact_retrieval_is_retryable, rsync_exit_code};
+use rch_common::RustcMatch;
 
 pub(super) fn wrap_command_with_telemetry(command: &str, worker_id: &WorkerId) -> String {
     let escaped_worker = shell_escape::escape(worker_id.as_str().into());
@@ -57,16 +66,38 @@ pub(super) fn wrap_command_with_telemetry(command: &str, worker_id: &WorkerId) -
     )
 }
 
+/// Where `execute_remote_compilation` sends streamed remote output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum RemoteOutput<'a> {
+    /// Forward each line to local stderr as-is.
+    Forward,
+    /// Forward each line tagged `[<prefix>] `, for several workers sharing
+    /// the terminal (sharded test runs).
+    Prefixed(&'a str),
+    /// Drop the lines; stderr is still captured in the result (shadow runs).
+    Discard,
+}
+
+/// Forward one streamed remote output line to local stderr per `output`.
+fn emit_remote_line(output: RemoteOutput<'_>, line: &str) {
+    match output {
+        RemoteOutput::Forward => eprint!("{}", line),
+        RemoteOutput::Prefixed(prefix) => eprint!("[{}] {}", prefix, line),
+        RemoteOutput::Discard => {}
+    }
+}
+
 async fn send_telemetry(
     socket_path: &str,
     source: TelemetrySource,
     telemetry: &WorkerTelemetry,
 ) -> anyhow::Result<()> {
-    if !Path::new(socket_path).exists() {
+    let socket_path = effective_socket_path(socket_path);
+    if !Path::new(&socket_path).exists() {
         return Ok(());
     }
 
-    let stream = match timeout(Duration::from_secs(2), UnixStream::connect(socket_path)).await {
+    let stream = match timeout(Duration::from_secs(2), UnixStream::connect(&socket_path)).await {
         Ok(Ok(s)) => s,
         Ok(Err(e)) => return Err(e.into()),
         Err(_) => return Ok(()), // Timeout connecting — don't block hook
@@ -92,11 +123,12 @@ async fn send_telemetry(
 }
 
 async fn send_test_run(socket_path: &str, record: &TestRunRecord) -> anyhow::Result<()> {
-    if !Path::new(socket_path).exists() {
+    let socket_path = effective_socket_path(socket_path);
+    if !Path::new(&socket_path).exists() {
         return Ok(());
     }
 
-    let stream = match timeout(Duration::from_secs(2), UnixStream::connect(socket_path)).await {
+    let stream = match timeout(Duration::from_secs(2), UnixStream::connect(&socket_path)).await {
         Ok(Ok(s)) => s,
         Ok(Err(e)) => return Err(e.into()),
         Err(_) => return Ok(()), // Timeout connecting — don't block hook
@@ -117,13 +149,133 @@ async fn send_test_run(socket_path: &str, record: &TestRunRecord) -> anyhow::Res
     Ok(())
 }
 
+/// Run the `transfer.verify_artifacts` check after a successful retrieval.
+///
+/// Returns `true` when retrieved files differ from the worker's copies, which
+/// fails the artifact step. A check that cannot run is logged and ignored.
+async fn artifacts_fail_verification(
+    pipeline: &TransferPipeline,
+    worker: &WorkerConfig,
+    artifact_patterns: &[String],
+    reporter: &HookReporter,
+) -> bool {
+    match pipeline
+        .verify_retrieved_artifacts(worker, artifact_patterns)
+        .await
+    {
+        Ok(Some(verification)) if !verification.all_passed() => {
+            // stderr: a corrupted local artifact must reach the operator even
+            // when tracing is silenced.
+            eprintln!(
+                "[RCH] artifacts retrieved from {} do not match the worker ({})\n{}",
+                worker.id,
+                verification.summary(),
+                verification.format_failures()
+            );
+            true
+        }
+        Ok(Some(verification)) => {
+            reporter.verbose(&format!(
+                "[RCH] artifacts verified: {}",
+                verification.summary()
+            ));
+            false
+        }
+        Ok(None) => false,
+        Err(e) => {
+            warn!(
+                "Artifact verification could not run on {}: {}",
+                worker.id, e
+            );
+            reporter.verbose(&format!("[RCH] artifact verification skipped: {}", e));
+            false
+        }
+    }
+}
+
+/// Base delay before re-running artifact retrieval; grows linearly per retry.
+const ARTIFACT_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// Decide whether a failed artifact retrieval gets another attempt under
+/// `transfer.artifact_max_retries`, sleeping out the backoff before
+/// returning `true`.
+async fn retry_artifact_retrieval(
+    err: &anyhow::Error,
+    retries_done: u32,
+    max_retries: u32,
+    worker: &WorkerConfig,
+    reporter: &HookReporter,
+) -> bool {
+    if retries_done >= max_retries || !artifact_retrieval_is_retryable(err) {
+        return false;
+    }
+    warn!(
+        "Artifact retrieval from {} failed (retry {}/{}): {}",
+        worker.id,
+        retries_done + 1,
+        max_retries,
+        err
+    );
+    reporter.verbose(&format!(
+        "[RCH] artifacts: retrying ({}/{})...",
+        retries_done + 1,
+        max_retries
+    ));
+    tokio::time::sleep(ARTIFACT_RETRY_DELAY * (retries_done + 1)).await;
+    true
+}
+
+/// Why a Rust build must stay local under `compilation.require_rustc_match`,
+/// or `None` when it may be offloaded.
+///
+/// The local version is only probed when the check applies. Parity that
+/// cannot be verified (either version unknown) counts as a mismatch.
+pub(super) fn rustc_parity_skip_reason(
+    required: Option<RustcMatch>,
+    kind: Option<CompilationKind>,
+    worker: &SelectedWorker,
+    local_rustc: impl FnOnce() -> Option<String>,
+) -> Option<String> {
+    let granularity = required?;
+    if required_runtime_for_kind(kind) != RequiredRuntime::Rust {
+        return None;
+    }
+    let Some(remote) = worker.rustc_version.as_deref() else {
+        return Some(format!(
+            "rustc version of {} unknown, require_rustc_match = {}",
+            worker.id, granularity
+        ));
+    };
+    let Some(local) = local_rustc() else {
+        return Some(format!(
+            "local rustc version unknown, require_rustc_match = {}",
+            granularity
+        ));
+    };
+    if granularity.versions_match(&local, remote) {
+        return None;
+    }
+    Some(format!(
+        "rustc mismatch: local '{}', {} has '{}' (require_rustc_match = {})",
+        local.trim(),
+        worker.id,
+        remote.trim(),
+        granularity
+    ))
+}
+
 /// Execute a compilation command on a remote worker.
 ///
 /// This function:
-/// 1. Syncs the project to the remote worker
+/// 1. Syncs the project to the remote worker (after the optional
+///    `compilation.require_rustc_match` parity check)
 /// 2. Executes the command remotely with streaming output
 /// 3. Retrieves build artifacts back to local
 ///
+/// Streamed remote output goes where `output` says: straight to stderr, tagged
+/// `[<prefix>] ` (sharded test runs telling interleaved workers apart), or
+/// nowhere (shadow runs).
+///
 /// Returns the execution result including exit code and stderr.
 #[allow(clippy::too_many_arguments)] // Pipeline wiring favors explicit params
 pub(super) async fn execute_remote_compilation(
@@ -133,19 +285,48 @@ pub(super) async fn execute_remote_compilation(
     env_allowlist: Vec<String>,
     forwarded_cargo_target_dir: Option<PathBuf>,
     compilation_config: &rch_common::CompilationConfig,
+    self_healing: &rch_common::SelfHealingConfig,
     toolchain: Option<&ToolchainInfo>,
     kind: Option<CompilationKind>,
     reporter: &HookReporter,
     socket_path: &str,
-    color_mode: ColorMode,
+    output_config: &rch_common::OutputConfig,
+    telemetry_config: &rch_common::TelemetryConfig,
     build_id: Option<u64>,
     topology_policy: &PathTopologyPolicy,
+    output: RemoteOutput<'_>,
 ) -> anyhow::Result<RemoteExecutionResult> {
     let worker_config = selected_worker_to_config(worker);
+    // `--build-plan`, `--unit-graph` and JSON message formats own stdout, so
+    // no telemetry is piggybacked onto it.
+    let machine_stdout = command_produces_machine_stdout(command);
 
     // Get current working directory and normalize it to the canonical project root.
     let project_root =
-        std::env::current_dir().map_err(|e| TransferError::NoProjectRoot { source: e })?;
+        logical_current_dir().map_err(|e| TransferError::NoProjectRoot { source: e })?;
+    if let Some(reason) =
+        rustc_parity_skip_reason(compilation_config.require_rustc_match, kind, worker, || {
+            local_rustc_version(&project_root)
+        })
+    {
+        info!("rustc parity check kept build local: {}", reason);
+        return Err(TransferError::TransferSkipped { reason }.into());
+    }
+    // The artifact archive is compressed with zstd on the worker; without it,
+    // retrieve with rsync rather than fail the build after it succeeded.
+    let mut transfer_config = transfer_config;
+    if transfer_config.artifact_archive && worker.missing_tools.iter().any(|tool| tool == "zstd") {
+        warn!(
+            "transfer.artifact_archive is set but zstd is missing on worker {} ({}); retrieving with rsync",
+            worker_config.id,
+            rch_common::worker_tool_install_hint("zstd")
+        );
+        reporter.verbose(&format!(
+            "[RCH] zstd unavailable on {}; retrieving artifacts with rsync",
+            worker_config.id
+        ));
+        transfer_config.artifact_archive = false;
+    }
     let normalized_project = normalize_project_path_with_policy(&project_root, topology_policy)
         .map_err(|e| {
             anyhow::anyhow!(
@@ -154,8 +335,10 @@ pub(super) async fn execute_remote_compilation(
                 e
             )
         })?;
+    let mut trace = DecisionTrace::new();
     for decision in normalized_project.decision_trace() {
         reporter.verbose(&format!("[RCH] project path normalized: {}", decision));
+        trace.push(DecisionStage::PathNormalization, decision.to_string());
     }
     let normalized_project_root = normalized_project.canonical_path().to_path_buf();
 
@@ -195,6 +378,13 @@ pub(super) async fn execute_remote_compilation(
             "[RCH] dependency planner fail-open [{}]: proceeding with primary root only — {}",
             decision.reason_code, decision.remediation
         ));
+        trace.push(
+            DecisionStage::Dependencies,
+            format!(
+                "planner fail-open [{}]: primary root only",
+                decision.reason_code
+            ),
+        );
     }
     let raw_sync_roots = dependency_plan.sync_roots;
     let project_id = project_id_from_path(&normalized_project_root);
@@ -203,23 +393,69 @@ pub(super) async fn execute_remote_compilation(
         &raw_sync_roots,
         topology_policy,
     );
-    let sync_plan = build_sync_closure_plan(
+    let mut sync_plan = build_sync_closure_plan(
         &raw_sync_roots,
         &normalized_project_root,
         &project_hash,
         topology_policy,
     );
+    let vendor_issue = match &dependency_plan.vendor_root {
+        Ok(Some(root)) => push_vendor_sync_entry(&mut sync_plan, root, topology_policy).err(),
+        Ok(None) => None,
+        Err(reason) => Some(reason.clone()),
+    };
+    if let Some(reason) = vendor_issue {
+        // Cargo reads crates.io from the vendor directory; without it the
+        // remote build cannot resolve a single dependency.
+        warn!("{}; keeping build local", reason);
+        reporter.summary(&format!("[RCH] warning: {}", reason));
+        return Err(TransferError::TransferSkipped { reason }.into());
+    }
+    if let Some(vendor) = sync_plan
+        .iter()
+        .find(|entry| entry.mode == SyncClosureMode::VendoredRegistry)
+    {
+        trace.push(
+            DecisionStage::Dependencies,
+            format!(
+                "vendored registry {} added to closure",
+                vendor.local_root.display()
+            ),
+        );
+    }
+    if exact_dependency_closure_sync
+        && command_is_offline(command)
+        && vendored_source_dir(&normalized_project_root).is_none()
+    {
+        reporter.summary(
+            "[RCH] warning: offline build without a vendored crates.io source; \
+             the worker's isolated CARGO_HOME has no registry cache",
+        );
+    }
     let sync_roots = sync_plan
         .iter()
         .map(|entry| entry.local_root.clone())
         .collect::<Vec<_>>();
     let sync_manifest = build_sync_closure_manifest(&sync_plan, &normalized_project_root);
+    trace.push(
+        DecisionStage::Dependencies,
+        format!(
+            "{} closure root(s) to sync ({} dependency)",
+            sync_plan.len(),
+            sync_plan.iter().filter(|entry| !entry.is_primary).count()
+        ),
+    );
 
     let output_ctx = OutputContext::detect();
     let console = RchConsole::with_context(output_ctx);
     let feedback_visible = reporter.visibility != OutputVisibility::None && !console.is_machine();
     let progress_enabled =
         output_ctx.supports_rich() && reporter.visibility != OutputVisibility::None;
+    let color_mode = remote_color_mode(
+        output_config.color_mode,
+        output_ctx,
+        std::env::var_os("CI").is_some(),
+    );
     let remote_pgid_file = build_id.and_then(|id| {
         sync_plan
             .iter()
@@ -229,7 +465,7 @@ pub(super) async fn execute_remote_compilation(
     let mut heartbeat_loop =
         build_id.map(|id| BuildHeartbeatLoop::start(socket_path, id, &worker_config.id));
     if let Some(loop_ref) = heartbeat_loop.as_ref() {
-        loop_ref.set_remote_pgid_file(remote_pgid_file);
+        loop_ref.set_remote_pgid_file(remote_pgid_file, transfer_config.detach_remote_builds);
         loop_ref.update_phase(BuildHeartbeatPhase::SyncUp, Some("sync_start".to_string()));
         loop_ref.flush().await;
     }
@@ -289,9 +525,78 @@ pub(super) async fn execute_remote_compilation(
     } else {
         remote_cap
     };
-    let effective_env_allowlist =
+    let mut effective_env_allowlist =
         cargo_target_env_allowlist(&env_allowlist, forwarded_cargo_target_dir.is_some());
-    let cargo_env_overrides = cargo_target_env_overrides(forwarded_cargo_target_dir.as_deref());
+    let mut cargo_env_overrides = cargo_target_env_overrides(forwarded_cargo_target_dir.as_deref());
+    // Feature flags travel with the command; feature-affecting env vars only
+    // reach the worker when allowlisted, so flag the ones that would be dropped.
+    if command_uses_cargo_dependency_graph(kind) {
+        let flags = explicit_feature_flags(command);
+        if !flags.is_empty() {
+            reporter.verbose(&format!(
+                "[RCH] features: {} (sent verbatim)",
+                flags.join(" ")
+            ));
+        }
+        let dropped = unforwarded_feature_env_vars(std::env::vars(), &effective_env_allowlist);
+        if !dropped.is_empty() {
+            let vars = dropped.join(", ");
+            warn!(
+                "Feature-affecting env vars set locally but not forwarded to {}: {}",
+                worker_config.id, vars
+            );
+            reporter.summary(&format!(
+                "[RCH] warning: {vars} not forwarded; the remote feature set may differ \
+                 (add to environment.allowlist)"
+            ));
+        }
+    }
+    // Opt-in worker-wide build dir so dependency artifacts are reused across
+    // projects; final artifacts stay in the per-project target dir.
+    if transfer_config.shared_target_dir {
+        let shared_build_dir =
+            remote_shared_build_dir(&transfer_config.remote_base, toolchain, command);
+        reporter.verbose(&format!(
+            "[RCH] shared build dir active; CARGO_BUILD_BUILD_DIR={shared_build_dir}"
+        ));
+        let overrides = cargo_env_overrides.get_or_insert_with(std::collections::HashMap::new);
+        overrides.insert("CARGO_BUILD_BUILD_DIR".to_string(), shared_build_dir);
+        if !env_allowlist_contains(&effective_env_allowlist, "CARGO_BUILD_BUILD_DIR") {
+            effective_env_allowlist.push("CARGO_BUILD_BUILD_DIR".to_string());
+        }
+    }
+    // Opt-in sccache on workers that have it. A RUSTC_WRAPPER the user
+    // forwards explicitly wins.
+    if transfer_config.use_sccache {
+        let user_wrapper = env_allowlist_contains(&effective_env_allowlist, "RUSTC_WRAPPER")
+            && std::env::var_os("RUSTC_WRAPPER").is_some();
+        match remote_sccache_env(worker, &transfer_config.remote_base) {
+            None => {
+                warn!(
+                    "transfer.use_sccache is set but sccache was not detected on worker {}; building without it",
+                    worker_config.id
+                );
+                reporter.verbose(&format!(
+                    "[RCH] sccache unavailable on {}; building without it",
+                    worker_config.id
+                ));
+            }
+            Some(_) if user_wrapper => {
+                reporter.verbose("[RCH] sccache skipped; RUSTC_WRAPPER forwarded from environment");
+            }
+            Some(sccache_env) => {
+                let overrides =
+                    cargo_env_overrides.get_or_insert_with(std::collections::HashMap::new);
+                for (key, value) in sccache_env {
+                    reporter.verbose(&format!("[RCH] sccache active; {key}={value}"));
+                    overrides.insert(key.to_string(), value);
+                    if !env_allowlist_contains(&effective_env_allowlist, key) {
+                        effective_env_allowlist.push(key.to_string());
+                    }
+                }
+            }
+        }
+    }
     // Remote target-dir name for the forwarded-CARGO_TARGET_DIR sync. By default
     // this is a STABLE pooled name keyed on (project, toolchain, triple, profile,
     // features) so independent jobs with identical dimensions REUSE the same warm
@@ -343,6 +648,8 @@ pub(super) async fn execute_remote_compilation(
         .with_compilation_config(compilation_config.clone())
         .with_compilation_kind(kind)
         .with_remote_path_override(entry.remote_root.clone())
+        .with_measured_link(worker.upload_bandwidth_bps, worker.rtt_ms)
+        .with_remote_priority(worker.nice_level, worker.ionice_class)
         .with_build_id(build_id);
         if entry.mode == SyncClosureMode::WorkspaceMetadata {
             root_pipeline = root_pipeline
@@ -359,7 +666,9 @@ pub(super) async fn execute_remote_compilation(
             }
         }
 
-        if exact_dependency_closure_sync {
+        // A vendored registry can dwarf the project itself, so it always goes
+        // through the transfer estimator.
+        if exact_dependency_closure_sync && entry.mode != SyncClosureMode::VendoredRegistry {
             reporter.verbose(&format!(
                 "[RCH] exact dependency closure sync required; bypassing transfer estimator for {}",
                 entry.local_root.display()
@@ -376,6 +685,19 @@ pub(super) async fn execute_remote_compilation(
                 entry.local_root.display(),
                 skip_reason
             ));
+            if entry.mode == SyncClosureMode::VendoredRegistry {
+                let reason = format!(
+                    "vendored registry {} too large to sync: {}",
+                    entry.local_root.display(),
+                    skip_reason
+                );
+                warn!(
+                    "{} (worker {}); keeping build local",
+                    reason, worker_config.id
+                );
+                reporter.summary(&format!("[RCH] warning: {}", reason));
+                return Err(TransferError::TransferSkipped { reason }.into());
+            }
             if entry.is_primary {
                 // Primary root skip is fatal — cannot build without the main project.
                 return Err(TransferError::TransferSkipped {
@@ -397,6 +719,7 @@ pub(super) async fn execute_remote_compilation(
             entry.local_root.display(),
             entry.remote_root.as_str()
         ));
+        let transfer_slot = acquire_transfer_slot(&transfer_config, self_healing, reporter).await;
         let sync_attempt = if let Some(progress) = &mut upload_progress {
             root_pipeline
                 .sync_to_remote_streaming(&worker_config, |line| {
@@ -406,6 +729,7 @@ pub(super) async fn execute_remote_compilation(
         } else {
             root_pipeline.sync_to_remote(&worker_config).await
         };
+        drop(transfer_slot);
         match sync_attempt {
             Ok(root_sync_result) => {
                 aggregate_sync_result = Some(match &aggregate_sync_result {
@@ -477,16 +801,23 @@ pub(super) async fn execute_remote_compilation(
     }
     let sync_result = aggregate_sync_result
         .ok_or_else(|| anyhow::anyhow!("dependency sync produced no transfer result"))?;
-    let pipeline = primary_pipeline.ok_or_else(|| {
-        anyhow::anyhow!(
-            "dependency sync did not include primary project root {}",
-            normalized_project_root.display()
-        )
-    })?;
+    let pipeline = primary_pipeline
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "dependency sync did not include primary project root {}",
+                normalized_project_root.display()
+            )
+        })?
+        .with_telemetry_stream(
+            telemetry_config
+                .streaming_interval()
+                .filter(|_| !machine_stdout),
+        );
     info!(
         "Sync complete: {} files, {} bytes in {}ms",
         sync_result.files_transferred, sync_result.bytes_transferred, sync_result.duration_ms
     );
+    trace.extend(sync_trace_steps(&root_outcomes, &sync_result));
     // Opportunistically reclaim *abandoned* per-job target dirs for this project
     // on the chosen worker. Only siblings with no file activity past the threshold
     // are removed, so any dir still in active use is preserved and this never races
@@ -541,11 +872,24 @@ pub(super) async fn execute_remote_compilation(
 
     struct CompileUiState {
         progress: Option<CompilationProgress>,
-        output: String,
-        output_truncated: bool,
+        output: OutputCapture,
         crates_compiled: Option<u32>,
         warnings: Option<u32>,
+        /// Summed over every test binary's result line; `None` until one is seen.
+        tests: Option<TestSummary>,
     }
+    impl CompileUiState {
+        fn record_test_result(&mut self, line: &str) {
+            if let Some(parsed) = TestSummary::parse_line(line) {
+                *self.tests.get_or_insert_with(TestSummary::default) += parsed;
+            }
+        }
+    }
+    // libtest prints its result lines on stdout, nextest its summary on stderr.
+    let parse_test_results = matches!(
+        kind,
+        Some(CompilationKind::CargoTest | CompilationKind::CargoNextest)
+    );
     let use_compile_progress = progress_enabled
         && matches!(
             kind,
@@ -555,6 +899,7 @@ pub(super) async fn execute_remote_compilation(
                     | CompilationKind::CargoClippy
                     | CompilationKind::CargoDoc
                     | CompilationKind::CargoBench
+                    | CompilationKind::CargoRun
             )
         );
     let ui_state = Rc::new(RefCell::new(CompileUiState {
@@ -567,17 +912,23 @@ pub(super) async fn execute_remote_compilation(
         } else {
             None
         },
-        output: String::new(),
-        output_truncated: false,
+        output: OutputCapture::new(output_config.max_memory_bytes, build_log_dir()),
         crates_compiled: None,
         warnings: None,
+        tests: None,
     }));
 
     // Add per-worker CARGO_HOME isolation to prevent cache lock contention
-    let isolated_command = add_cargo_isolation(command, &worker_config.id);
+    let colored_command = with_color_flag(kind, command, color_mode);
+    let isolated_command = add_cargo_isolation(&colored_command, &worker_config.id);
 
-    // Stream stdout/stderr to our stderr so the agent sees the output
-    let command_with_telemetry = wrap_command_with_telemetry(&isolated_command, &worker_config.id);
+    // Stream stdout/stderr to our stderr so the agent sees the output. Commands
+    // whose stdout is a JSON stream run without the telemetry piggyback block.
+    let command_with_telemetry = if machine_stdout {
+        isolated_command
+    } else {
+        wrap_command_with_telemetry(&isolated_command, &worker_config.id)
+    };
     let ui_state_stdout = Rc::clone(&ui_state);
     let ui_state_stderr = Rc::clone(&ui_state);
     let stderr_capture_stderr = Rc::clone(&stderr_capture_cell);
@@ -587,6 +938,8 @@ pub(super) async fn execute_remote_compilation(
     let heartbeat_state_stderr = heartbeat_loop
         .as_ref()
         .map(BuildHeartbeatLoop::shared_state);
+    let forward_output = output_config.forward_to_daemon;
+    let stream_socket_path = socket_path.to_string();
     let mut suppress_telemetry = false;
 
     let result = pipeline
@@ -602,44 +955,54 @@ pub(super) async fn execute_remote_compilation(
                     suppress_telemetry = true;
                     return;
                 }
-                if let Some(state) = heartbeat_state_stdout.as_ref() {
-                    mark_heartbeat_progress(state);
+                if let Some(telemetry) = parse_streamed_telemetry_line(line) {
+                    // Forward mid-build samples as they arrive so the daemon's
+                    // view of a long build's worker doesn't go stale.
+                    let socket_path = stream_socket_path.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) =
+                            send_telemetry(&socket_path, TelemetrySource::Piggyback, &telemetry)
+                                .await
+                        {
+                            debug!("Failed to forward streamed telemetry to daemon: {}", e);
+                        }
+                    });
+                    return;
+                }
+                match heartbeat_state_stdout.as_ref() {
+                    Some(state) if forward_output => record_heartbeat_output(state, line),
+                    Some(state) => mark_heartbeat_progress(state),
+                    None => {}
                 }
 
                 let mut state = ui_state_stdout.borrow_mut();
+                if parse_test_results {
+                    state.record_test_result(line);
+                }
                 if let Some(progress) = state.progress.as_mut() {
                     progress.update_from_line(line);
-                    if !state.output_truncated {
-                        const MAX_OUTPUT_BYTES: usize = 256 * 1024;
-                        if state.output.len() + line.len() <= MAX_OUTPUT_BYTES {
-                            state.output.push_str(line);
-                        } else {
-                            state.output_truncated = true;
-                        }
-                    }
+                    state.output.push(line);
                 } else {
                     // Write stdout lines to stderr (hook stdout is for protocol)
-                    eprint!("{}", line);
+                    emit_remote_line(output, line);
                 }
             },
             move |line| {
-                if let Some(state) = heartbeat_state_stderr.as_ref() {
-                    mark_heartbeat_progress(state);
+                match heartbeat_state_stderr.as_ref() {
+                    Some(state) if forward_output => record_heartbeat_output(state, line),
+                    Some(state) => mark_heartbeat_progress(state),
+                    None => {}
                 }
                 // Write stderr lines to stderr and capture for analysis
                 let mut state = ui_state_stderr.borrow_mut();
+                if parse_test_results {
+                    state.record_test_result(line);
+                }
                 if let Some(progress) = state.progress.as_mut() {
                     progress.update_from_line(line);
-                    if !state.output_truncated {
-                        const MAX_OUTPUT_BYTES: usize = 256 * 1024;
-                        if state.output.len() + line.len() <= MAX_OUTPUT_BYTES {
-                            state.output.push_str(line);
-                        } else {
-                            state.output_truncated = true;
-                        }
-                    }
+                    state.output.push(line);
                 } else {
-                    eprint!("{}", line);
+                    emit_remote_line(output, line);
                 }
                 drop(state);
 
@@ -658,6 +1021,26 @@ pub(super) async fn execute_remote_compilation(
         "[RCH] exec done: exit={} in {}ms",
         result.exit_code, result.duration_ms
     ));
+    trace.push(
+        DecisionStage::Execution,
+        format!(
+            "exit {} in {}ms on {}",
+            result.exit_code, result.duration_ms, worker_config.id
+        ),
+    );
+    if !result.success()
+        && transfer_config.keep_remote_on_failure
+        && let Some(location) = pipeline
+            .pin_remote(&worker_config, transfer_config.keep_remote_hours)
+            .await
+    {
+        // stderr, not the reporter: the operator asked for this location.
+        eprintln!(
+            "[RCH] remote build dir kept for {}h: {}",
+            transfer_config.keep_remote_hours.max(1),
+            location
+        );
+    }
 
     {
         let mut state = ui_state.borrow_mut();
@@ -681,17 +1064,28 @@ pub(super) async fn execute_remote_compilation(
         }
 
         if use_compile_progress && !result.success() && !state.output.is_empty() {
-            eprintln!("{}", state.output);
-            if state.output_truncated {
-                eprintln!("[RCH] output truncated (increase buffer if needed)");
-            }
+            state.output.replay_to_stderr();
+        }
+        if let Some(path) = state
+            .output
+            .finish(result.success(), output_config.keep_logs)
+        {
+            reporter.verbose(&format!("[RCH] build log kept at {}", path.display()));
         }
     }
 
     let mut artifacts_result: Option<SyncResult> = None;
     let mut artifacts_failed = false;
-    // Step 3: Retrieve artifacts
-    if result.success() {
+    // Step 3: Retrieve artifacts. Kinds listed in
+    // `transfer.skip_artifacts_for` (check/clippy by default) produce only
+    // diagnostics, so the round-trip is skipped entirely.
+    let artifacts_skipped_for = kind.filter(|_| transfer_config.skips_artifacts_for(kind));
+    if result.success()
+        && let Some(kind) = artifacts_skipped_for
+    {
+        info!("Skipping artifact retrieval for {}", kind.as_str());
+        reporter.verbose(&format!("[RCH] artifacts: skipped ({})", kind.as_str()));
+    } else if result.success() {
         if let Some(loop_ref) = heartbeat_loop.as_ref() {
             loop_ref.update_phase(
                 BuildHeartbeatPhase::SyncDown,
@@ -701,7 +1095,7 @@ pub(super) async fn execute_remote_compilation(
         }
         info!("Retrieving build artifacts...");
         reporter.verbose("[RCH] artifacts: retrieving...");
-        let artifact_patterns = get_artifact_patterns(kind);
+        let artifact_patterns = get_artifact_patterns(kind, command);
         let heartbeat_state_download = heartbeat_loop
             .as_ref()
             .map(BuildHeartbeatLoop::shared_state);
@@ -715,19 +1109,39 @@ pub(super) async fn execute_remote_compilation(
             None
         };
 
-        let retrieval = if let Some(progress) = &mut download_progress {
-            pipeline
-                .retrieve_artifacts_streaming(&worker_config, &artifact_patterns, |line| {
-                    progress.update_from_line(line);
-                    if let Some(state) = heartbeat_state_download.as_ref() {
-                        mark_heartbeat_progress(state);
-                    }
-                })
-                .await
-        } else {
-            pipeline
-                .retrieve_artifacts(&worker_config, &artifact_patterns)
-                .await
+        // The build already succeeded, so a transient retrieval failure is
+        // worth another attempt before the user is left without artifacts.
+        let mut retrieval_retries = 0;
+        let retrieval = loop {
+            let attempt = if let Some(progress) = &mut download_progress {
+                pipeline
+                    .retrieve_artifacts_streaming(&worker_config, &artifact_patterns, |line| {
+                        progress.update_from_line(line);
+                        if let Some(state) = heartbeat_state_download.as_ref() {
+                            mark_heartbeat_progress(state);
+                        }
+                    })
+                    .await
+            } else {
+                pipeline
+                    .retrieve_artifacts(&worker_config, &artifact_patterns)
+                    .await
+            };
+            match attempt {
+                Err(e)
+                    if retry_artifact_retrieval(
+                        &e,
+                        retrieval_retries,
+                        transfer_config.artifact_max_retries,
+                        &worker_config,
+                        reporter,
+                    )
+                    .await =>
+                {
+                    retrieval_retries += 1;
+                }
+                other => break other,
+            }
         };
 
         match retrieval {
@@ -755,25 +1169,22 @@ pub(super) async fn execute_remote_compilation(
                     Some(existing) => merge_sync_result(&existing, &artifact_result),
                     None => artifact_result,
                 });
+                if artifacts_fail_verification(
+                    &pipeline,
+                    &worker_config,
+                    &artifact_patterns,
+                    reporter,
+                )
+                .await
+                {
+                    artifacts_failed = true;
+                }
             }
             Err(e) => {
                 artifacts_failed = true;
 
-                // Extract rsync exit code from error message if present
                 let error_str = e.to_string();
-                let rsync_exit_code = error_str.find("exit code").and_then(|_| {
-                    error_str
-                        .split("exit code")
-                        .nth(1)
-                        .and_then(|s| s.split(':').next())
-                        .and_then(|s| {
-                            s.trim()
-                                .trim_start_matches("Some(")
-                                .trim_end_matches(')')
-                                .parse()
-                                .ok()
-                        })
-                });
+                let rsync_exit_code = rsync_exit_code(&e);
 
                 // Create structured warning (bd-1q3p)
                 let warning = ArtifactRetrievalWarning::new(
@@ -781,7 +1192,8 @@ pub(super) async fn execute_remote_compilation(
                     artifact_patterns.clone(),
                     &error_str,
                     rsync_exit_code,
-                );
+                )
+                .with_retry_attempts(retrieval_retries);
 
                 warn!("Failed to retrieve artifacts: {}", e);
 
@@ -803,7 +1215,7 @@ pub(super) async fn execute_remote_compilation(
 
         if let Some(local_target_dir) = forwarded_cargo_target_dir.as_ref() {
             let remote_target_path = pipeline.remote_cargo_target_dir();
-            let custom_patterns = get_custom_target_artifact_patterns(kind);
+            let custom_patterns = get_custom_target_artifact_patterns(kind, command);
             if custom_patterns.is_empty() {
                 reporter.verbose(&format!(
                     "[RCH] custom target dir sync skipped for {} after command with no target artifacts",
@@ -836,22 +1248,44 @@ pub(super) async fn execute_remote_compilation(
                     None
                 };
 
-                let target_retrieval = if let Some(progress) = &mut target_progress {
-                    let heartbeat_state_target = heartbeat_loop
-                        .as_ref()
-                        .map(BuildHeartbeatLoop::shared_state);
-                    target_pipeline
-                        .retrieve_artifacts_streaming(&worker_config, &custom_patterns, |line| {
-                            progress.update_from_line(line);
-                            if let Some(state) = heartbeat_state_target.as_ref() {
-                                mark_heartbeat_progress(state);
-                            }
-                        })
-                        .await
-                } else {
-                    target_pipeline
-                        .retrieve_artifacts(&worker_config, &custom_patterns)
-                        .await
+                let heartbeat_state_target = heartbeat_loop
+                    .as_ref()
+                    .map(BuildHeartbeatLoop::shared_state);
+                let mut target_retries = 0;
+                let target_retrieval = loop {
+                    let attempt = if let Some(progress) = &mut target_progress {
+                        target_pipeline
+                            .retrieve_artifacts_streaming(
+                                &worker_config,
+                                &custom_patterns,
+                                |line| {
+                                    progress.update_from_line(line);
+                                    if let Some(state) = heartbeat_state_target.as_ref() {
+                                        mark_heartbeat_progress(state);
+                                    }
+                                },
+                            )
+                            .await
+                    } else {
+                        target_pipeline
+                            .retrieve_artifacts(&worker_config, &custom_patterns)
+                            .await
+                    };
+                    match attempt {
+                        Err(e)
+                            if retry_artifact_retrieval(
+                                &e,
+                                target_retries,
+                                transfer_config.artifact_max_retries,
+                                &worker_config,
+                                reporter,
+                            )
+                            .await =>
+                        {
+                            target_retries += 1;
+                        }
+                        other => break other,
+                    }
                 };
 
                 match target_retrieval {
@@ -881,6 +1315,16 @@ pub(super) async fn execute_remote_compilation(
                             Some(existing) => merge_sync_result(&existing, &target_result),
                             None => target_result,
                         });
+                        if artifacts_fail_verification(
+                            &target_pipeline,
+                            &worker_config,
+                            &custom_patterns,
+                            reporter,
+                        )
+                        .await
+                        {
+                            artifacts_failed = true;
+                        }
                     }
                     Err(e) => {
                         artifacts_failed = true;
@@ -926,9 +1370,13 @@ pub(super) async fn execute_remote_compilation(
         }
     }
 
-    let (crates_compiled, output_snapshot) = {
+    let (crates_compiled, test_summary, output_snapshot) = {
         let state = ui_state.borrow();
-        (state.crates_compiled, state.output.clone())
+        (
+            state.crates_compiled,
+            state.tests,
+            state.output.head().to_string(),
+        )
     };
 
     if feedback_visible {
@@ -941,6 +1389,8 @@ pub(super) async fn execute_remote_compilation(
             result.duration_ms,
             artifacts_result.as_ref(),
             artifacts_failed,
+            artifacts_skipped_for,
+            test_summary.as_ref(),
             cache_hit(&sync_result),
             result.success(),
         );
@@ -957,6 +1407,7 @@ pub(super) async fn execute_remote_compilation(
             .worker(worker_config.id.as_str())
             .crates_compiled(crates_compiled)
             .artifacts(artifacts_summary)
+            .tests(test_summary)
             .cache_hit(Some(cache_hit(&sync_result)))
             .target(target_label)
             .quiet(reporter.visibility == OutputVisibility::None);
@@ -970,7 +1421,8 @@ pub(super) async fn execute_remote_compilation(
         exec: Some(Duration::from_millis(result.duration_ms)),
         sync_down: artifacts_result
             .as_ref()
-            .map(|ar| Duration::from_millis(ar.duration_ms)),
+            .map(|ar| Duration::from_millis(ar.duration_ms))
+            .or(artifacts_skipped_for.map(|_| Duration::ZERO)),
         ..Default::default()
     };
 
@@ -1017,11 +1469,70 @@ pub(super) async fn execute_remote_compilation(
         } else {
             result.exit_code
         };
+    let artifacts_detail = if !result.success() {
+        "not retrieved: remote command failed".to_string()
+    } else if let Some(kind) = artifacts_skipped_for {
+        format!(
+            "skipped for {} (transfer.skip_artifacts_for)",
+            kind.as_str()
+        )
+    } else if artifacts_failed && exit_code != result.exit_code {
+        format!("retrieval failed; build reported as failed (exit {exit_code})")
+    } else if artifacts_failed {
+        "retrieval failed (continued)".to_string()
+    } else if let Some(artifacts) = artifacts_result.as_ref() {
+        format!(
+            "{} files, {} bytes in {}ms",
+            artifacts.files_transferred, artifacts.bytes_transferred, artifacts.duration_ms
+        )
+    } else {
+        "nothing retrieved".to_string()
+    };
+    trace.push(DecisionStage::Artifacts, artifacts_detail);
 
     Ok(RemoteExecutionResult {
         exit_code,
         stderr: stderr_capture,
         duration_ms: result.duration_ms,
         timing,
+        bytes_transferred: sync_result.bytes_transferred,
+        decision_trace: trace,
     })
 }
+
+/// Sync steps of a build's decision trace: the overall outcome, then one
+/// step per closure root that did not sync.
+pub(super) fn sync_trace_steps(
+    root_outcomes: &[(SyncClosurePlanEntry, SyncRootOutcome)],
+    sync_result: &SyncResult,
+) -> Vec<DecisionTraceStep> {
+    let synced = root_outcomes
+        .iter()
+        .filter(|(_, outcome)| matches!(outcome, SyncRootOutcome::Synced))
+        .count();
+    let mut summary = format!(
+        "{}/{} roots synced: {} files, {} bytes in {}ms",
+        synced,
+        root_outcomes.len(),
+        sync_result.files_transferred,
+        sync_result.bytes_transferred,
+        sync_result.duration_ms
+    );
+    if sync_result.bytes_transferred == 0 {
+        summary.push_str(" (worker copy already current)");
+    }
+    let mut steps = vec![DecisionTraceStep::new(DecisionStage::Sync, summary)];
+    for (entry, outcome) in root_outcomes {
+        let detail = match outcome {
+            SyncRootOutcome::Synced => continue,
+            SyncRootOutcome::Skipped { reason } => {
+                format!("skipped {}: {}", entry.local_root.display(), reason)
+            }
+            SyncRootOutcome::Failed { error } => {
+                format!("failed {}: {}", entry.local_root.display(), error)
+            }
+        };
+        steps.push(DecisionTraceStep::new(DecisionStage::Sync, detail));
+    }
+    steps
+}
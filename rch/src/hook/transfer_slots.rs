@@ -0,0 +1,115 @@
+//! Client-wide cap on concurrent uploads (`transfer.max_concurrent_client_transfers`).
+//!
+//! Every `rch exec` is its own process, so the cap is a counting semaphore
+//! made of lock files in the client state directory: a build holds an
+//! exclusive lock on one of `transfer-slot-<n>.lock` while its source sync
+//! runs, and the OS drops the lock when the process exits, crashed or not.
+//! The limit is soft: after [`MAX_SLOT_WAIT`] the upload goes ahead anyway
+//! rather than stalling a build behind a wedged peer.
+
+use super::*;
+
+use std::fs::{File, TryLockError};
+
+/// Longest a build waits for a free slot before uploading regardless.
+pub(super) const MAX_SLOT_WAIT: Duration = Duration::from_secs(300);
+
+/// Delay between scans of the slot files while every slot is taken.
+const SLOT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// A held (or waived) upload slot; dropping it frees the slot.
+#[derive(Debug)]
+pub(super) struct TransferSlot {
+    _lock: Option<File>,
+}
+
+pub(super) fn transfer_slot_path(state_dir: &Path, index: u32) -> PathBuf {
+    state_dir.join(format!("transfer-slot-{}.lock", index))
+}
+
+/// Lock the first free slot file, or `None` when all `limit` are held.
+fn try_acquire_slot(state_dir: &Path, limit: u32) -> io::Result<Option<File>> {
+    for index in 0..limit {
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(transfer_slot_path(state_dir, index))?;
+        match file.try_lock() {
+            Ok(()) => return Ok(Some(file)),
+            Err(TryLockError::WouldBlock) => continue,
+            Err(TryLockError::Error(e)) => return Err(e),
+        }
+    }
+    Ok(None)
+}
+
+/// Wait for one of `limit` slots under `state_dir`.
+///
+/// Prints a single note when the build has to wait. I/O errors on the slot
+/// files and running out of `max_wait` both waive the limit for this build.
+pub(super) async fn acquire_transfer_slot_in(
+    state_dir: &Path,
+    limit: u32,
+    max_wait: Duration,
+    reporter: &HookReporter,
+) -> TransferSlot {
+    let started = Instant::now();
+    let mut announced = false;
+    loop {
+        let attempt =
+            std::fs::create_dir_all(state_dir).and_then(|()| try_acquire_slot(state_dir, limit));
+        match attempt {
+            Ok(Some(file)) => {
+                if announced {
+                    reporter.verbose(&format!(
+                        "[RCH] transfer slot acquired after {:.1}s",
+                        started.elapsed().as_secs_f64()
+                    ));
+                }
+                return TransferSlot { _lock: Some(file) };
+            }
+            Ok(None) => {}
+            Err(e) => {
+                warn!(
+                    "Cannot use transfer slot files in {} ({}); uploading without the concurrency limit",
+                    state_dir.display(),
+                    e
+                );
+                return TransferSlot { _lock: None };
+            }
+        }
+        if started.elapsed() >= max_wait {
+            reporter.summary(&format!(
+                "[RCH] warning: no client transfer slot free after {}s; uploading anyway",
+                max_wait.as_secs()
+            ));
+            return TransferSlot { _lock: None };
+        }
+        if !announced {
+            reporter.summary(&format!(
+                "[RCH] waiting for a client transfer slot ({} uploads already running)...",
+                limit
+            ));
+            announced = true;
+        }
+        sleep(SLOT_POLL_INTERVAL).await;
+    }
+}
+
+/// Acquire an upload slot per `transfer_config` in the client state directory
+/// from `self_healing`, or a waived slot when the limit is unset or zero.
+pub(super) async fn acquire_transfer_slot(
+    transfer_config: &TransferConfig,
+    self_healing: &SelfHealingConfig,
+    reporter: &HookReporter,
+) -> TransferSlot {
+    let Some(limit) = transfer_config
+        .max_concurrent_client_transfers
+        .filter(|limit| *limit > 0)
+    else {
+        return TransferSlot { _lock: None };
+    };
+    let state_dir = super::auto_start::autostart_state_dir(self_healing);
+    acquire_transfer_slot_in(&state_dir, limit, MAX_SLOT_WAIT, reporter).await
+}
@@ -1,46 +1,165 @@
-//! Windows/non-Unix stub hook implementation.
+//! Windows/non-Unix hook implementation.
 //!
-//! RCH's daemon communication currently uses Unix domain sockets. On non-Unix
-//! platforms we compile a fail-open stub so the CLI can build and the hook
-//! never blocks local execution.
+//! RCH's daemon communication currently uses Unix domain sockets, so a
+//! Windows host cannot offload directly. When the working directory is a
+//! drive path under the canonical root, translated to its WSL mount (see
+//! `path_topology.wsl_mount_prefix`), the hook rewrites compilation commands
+//! to `rch exec`, and `rch exec` re-runs itself inside WSL from the
+//! translated directory with translated arguments, where the Unix pipeline
+//! offloads as usual. Everything else fails open to local execution.
 
 use crate::error::PlatformError;
 use rch_common::{
-    CommandPriority, CommandTimingBreakdown, CompilationKind, RequiredRuntime, SelectionResponse,
-    ToolchainInfo, WorkerId,
+    CommandPriority, CommandTimingBreakdown, CompilationKind, HookInput, HookOutput,
+    NormalizationDecision, PathTopologyPolicy, RequiredRuntime, SelectionResponse, ToolchainInfo,
+    WorkerId, classify_command, normalize_project_path_with_policy, translate_windows_command_args,
 };
 use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 
 /// Run the PreToolUse hook.
 ///
-/// On non-Unix platforms, RCH currently cannot talk to `rchd` (Unix sockets),
-/// so we always allow the command (empty stdout).
+/// Compilation commands in a project that translates to WSL are rewritten to
+/// `rch exec -- <command>`; anything else is allowed unchanged (empty
+/// stdout).
 pub async fn run_hook() -> anyhow::Result<()> {
-    // Consume stdin to avoid surprising callers that expect input to be read,
-    // but always fail-open.
-    let mut _buf = String::new();
-    let _ = std::io::stdin().read_to_string(&mut _buf);
+    let mut input = String::new();
+    if std::io::stdin().read_to_string(&mut input).is_err() {
+        return Ok(());
+    }
+    let Ok(cwd) = std::env::current_dir() else {
+        return Ok(());
+    };
+    if let Some(output) = hook_output_for(input.trim(), &cwd, &topology_policy()) {
+        println!("{}", serde_json::to_string(&output)?);
+    }
     Ok(())
 }
 
-/// Execute a compilation command locally.
+/// Execute a compilation command.
 ///
-/// On non-Unix platforms we do not support daemon-based offloading, so `rch exec`
-/// simply runs the provided command via the local shell.
-pub async fn run_exec(command_parts: Vec<String>) -> anyhow::Result<()> {
-    let command = command_parts.join(" ");
-    if command.is_empty() {
+/// In a project that translates to WSL, `rch exec` runs again inside WSL via
+/// `wsl.exe`. With `--local`, outside the canonical root, or when `wsl.exe`
+/// cannot be started, the command runs through the local shell.
+pub async fn run_exec(
+    command_parts: Vec<String>,
+    local: bool,
+    workers: Vec<String>,
+) -> anyhow::Result<()> {
+    let command_parts = normalize_exec_command_parts(&command_parts);
+    if command_parts.is_empty() {
         anyhow::bail!("No command provided to exec");
     }
 
-    let status = std::process::Command::new("cmd")
+    if !local
+        && let Ok(cwd) = std::env::current_dir()
+        && let Some(wsl) = WslExec::plan(&cwd, &command_parts, &workers, &topology_policy())
+    {
+        match wsl.command().status() {
+            Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+            Err(e) => eprintln!("[RCH] local (wsl.exe unavailable: {e})"),
+        }
+    }
+
+    let status = Command::new("cmd")
         .arg("/C")
-        .arg(&command)
+        .arg(command_parts.join(" "))
         .status()?;
 
     std::process::exit(status.code().unwrap_or(1));
 }
 
+fn topology_policy() -> PathTopologyPolicy {
+    crate::config::load_config()
+        .map(|config| config.path_topology.to_policy())
+        .unwrap_or_default()
+}
+
+/// Split a single shell-string argument the way the Unix `rch exec` does,
+/// keeping backslashes literal since they are path separators here.
+fn normalize_exec_command_parts(command_parts: &[String]) -> Vec<String> {
+    if command_parts.len() == 1 {
+        match shell_words::split(&command_parts[0].replace('\\', "\\\\")) {
+            Ok(parts) if parts.len() > 1 => return parts,
+            _ => {}
+        }
+    }
+
+    command_parts.to_vec()
+}
+
+/// The WSL path of `cwd`, when it is a Windows drive path under the
+/// canonical root.
+fn wsl_project_dir(cwd: &Path, policy: &PathTopologyPolicy) -> Option<PathBuf> {
+    let normalized = normalize_project_path_with_policy(cwd, policy).ok()?;
+    normalized
+        .decision_trace()
+        .iter()
+        .any(|decision| {
+            matches!(
+                decision,
+                NormalizationDecision::WindowsPathTranslated { .. }
+            )
+        })
+        .then(|| normalized.canonical_path().to_path_buf())
+}
+
+fn hook_output_for(input: &str, cwd: &Path, policy: &PathTopologyPolicy) -> Option<HookOutput> {
+    let input: HookInput = serde_json::from_str(input).ok()?;
+    if input.tool_name != "Bash" {
+        return None;
+    }
+    let command = input.tool_input.command.trim();
+    if !classify_command(command).is_compilation {
+        return None;
+    }
+    wsl_project_dir(cwd, policy)?;
+    Some(HookOutput::allow_with_modified_command(format!(
+        "rch exec -- {command}"
+    )))
+}
+
+/// `rch exec` re-run inside WSL for a project under the canonical root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct WslExec {
+    /// Project directory as WSL sees it.
+    cwd: PathBuf,
+    /// Arguments to the WSL-side `rch`, with Windows paths translated.
+    args: Vec<String>,
+}
+
+impl WslExec {
+    fn plan(
+        cwd: &Path,
+        command_parts: &[String],
+        workers: &[String],
+        policy: &PathTopologyPolicy,
+    ) -> Option<Self> {
+        let cwd = wsl_project_dir(cwd, policy)?;
+        let mut args = vec!["exec".to_string()];
+        if !workers.is_empty() {
+            args.push(format!("--workers={}", workers.join(",")));
+        }
+        args.push("--".to_string());
+        args.extend(translate_windows_command_args(
+            command_parts,
+            policy.wsl_mount_prefix(),
+        ));
+        Some(Self { cwd, args })
+    }
+
+    fn command(&self) -> Command {
+        let mut cmd = Command::new("wsl.exe");
+        cmd.arg("--cd")
+            .arg(&self.cwd)
+            .arg("--exec")
+            .arg("rch")
+            .args(&self.args);
+        cmd
+    }
+}
+
 /// Query the daemon for a worker.
 ///
 /// On non-Unix platforms this returns an error, which upstream treats as
@@ -89,7 +208,10 @@ pub(crate) fn required_runtime_for_kind(kind: Option<CompilationKind>) -> Requir
             | CompilationKind::CargoDoc
             | CompilationKind::CargoNextest
             | CompilationKind::CargoBench
-            | CompilationKind::Rustc => RequiredRuntime::Rust,
+            | CompilationKind::CargoRun
+            | CompilationKind::Rustc
+            | CompilationKind::TrunkBuild
+            | CompilationKind::WasmPackBuild => RequiredRuntime::Rust,
 
             CompilationKind::BunTest | CompilationKind::BunTypecheck => RequiredRuntime::Bun,
 
@@ -98,3 +220,86 @@ pub(crate) fn required_runtime_for_kind(kind: Option<CompilationKind>) -> Requir
         None => RequiredRuntime::None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> PathTopologyPolicy {
+        PathTopologyPolicy::new(PathBuf::from(r"C:\src"), PathBuf::from("/dp"))
+    }
+
+    fn hook_input(command: &str) -> String {
+        serde_json::json!({
+            "tool_name": "Bash",
+            "tool_input": { "command": command },
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn hook_rewrites_compilation_in_wsl_project_to_rch_exec() {
+        let output = hook_output_for(
+            &hook_input("cargo build --release"),
+            Path::new(r"C:\src\proj"),
+            &policy(),
+        )
+        .expect("compilation under the canonical root is rewritten");
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(
+            json["hookSpecificOutput"]["updatedInput"]["command"],
+            "rch exec -- cargo build --release"
+        );
+    }
+
+    #[test]
+    fn hook_allows_other_commands_and_projects_unchanged() {
+        let cwd = Path::new(r"C:\src\proj");
+        assert!(hook_output_for(&hook_input("git status"), cwd, &policy()).is_none());
+        assert!(
+            hook_output_for(
+                &hook_input("cargo build"),
+                Path::new(r"D:\elsewhere"),
+                &policy()
+            )
+            .is_none()
+        );
+        assert!(hook_output_for("not json", cwd, &policy()).is_none());
+    }
+
+    #[test]
+    fn exec_runs_rch_inside_wsl_with_translated_paths() {
+        let parts = normalize_exec_command_parts(&[
+            r"cargo build --manifest-path C:\src\proj\Cargo.toml".to_string(),
+        ]);
+        let wsl = WslExec::plan(
+            Path::new(r"C:\src\proj"),
+            &parts,
+            &["css".to_string()],
+            &policy(),
+        )
+        .expect("project translates to WSL");
+        assert_eq!(wsl.cwd, PathBuf::from("/mnt/c/src/proj"));
+        assert_eq!(
+            wsl.args,
+            vec![
+                "exec",
+                "--workers=css",
+                "--",
+                "cargo",
+                "build",
+                "--manifest-path",
+                "/mnt/c/src/proj/Cargo.toml",
+            ]
+        );
+
+        let cmd = wsl.command();
+        assert_eq!(cmd.get_program(), "wsl.exe");
+        assert_eq!(
+            cmd.get_args().take(4).collect::<Vec<_>>(),
+            vec!["--cd", "/mnt/c/src/proj", "--exec", "rch"]
+        );
+
+        assert!(WslExec::plan(Path::new(r"D:\other"), &parts, &[], &policy()).is_none());
+    }
+}
@@ -83,6 +83,7 @@ ENVIRONMENT VARIABLES:
     RCH_SSH_SERVER_ALIVE_INTERVAL_SECS  SSH keepalive interval (ServerAliveInterval)
     RCH_SSH_CONTROL_PERSIST_SECS        SSH ControlPersist idle seconds (0 disables persistence)
     RCH_COMPRESSION_LEVEL Compression level 1-22 (default: 3)
+    RCH_FLEET             Named worker fleet to build on (overrides transfer.fleet)
     RCH_ENV_ALLOWLIST     Comma-separated env vars to forward (e.g., RUSTFLAGS,CARGO_TARGET_DIR)
     RCH_MIN_LOCAL_TIME_MS Minimum local runtime estimate required before offload
     RCH_REMOTE_SPEEDUP_THRESHOLD Minimum predicted remote speedup ratio before offload
@@ -101,8 +102,9 @@ CONFIG PRECEDENCE (highest to lowest):
     3. Profile defaults (RCH_PROFILE)
     4. .env / .rch.env files
     5. Project config (.rch/config.toml)
-    6. User config (~/.config/rch/config.toml)
-    7. Built-in defaults
+    6. Project overlay (.rch.toml, overridable keys only)
+    7. User config (~/.config/rch/config.toml)
+    8. Built-in defaults
 
 For more information, see: https://github.com/anthropics/rch"#
 )]
@@ -239,6 +241,7 @@ For more control, use the individual commands:
     rch daemon start      # Start the daemon in background
     rch daemon status     # Check if daemon is running
     rch daemon logs -n 100  # View last 100 log lines
+    rch daemon logs -f    # Follow the daemon log
     rch daemon restart    # Restart after config changes"#)]
     Daemon {
         #[command(subcommand)]
@@ -246,7 +249,9 @@ For more control, use the individual commands:
     },
 
     /// Manage remote compilation workers
-    #[command(after_help = r#"EXAMPLES:
+    #[command(
+        alias = "worker",
+        after_help = r#"EXAMPLES:
     rch workers list          # Show all configured workers
     rch workers probe --all   # Test connectivity to all workers
     rch workers probe css     # Probe specific worker
@@ -268,7 +273,9 @@ STATE TRANSITIONS:
 
 Use 'drain' to gracefully stop a worker before maintenance.
 Use 'enable' to bring a drained/disabled worker back online.
-Use 'disable' to mark a worker as unavailable (optionally with --reason)."#)]
+Use 'disable' to mark a worker as unavailable (optionally with --reason).
+'rch worker' is accepted as an alias for 'rch workers'."#
+    )]
     Workers {
         #[command(subcommand)]
         action: WorkersAction,
@@ -279,7 +286,12 @@ Use 'disable' to mark a worker as unavailable (optionally with --reason)."#)]
     rch status                  # Quick overview
     rch status --workers        # Include worker details
     rch status --jobs           # Show active compilations
-    rch status --workers --jobs # Full status report"#)]
+    rch status --workers --jobs # Full status report
+    rch status --watch          # Live dashboard, redrawn every 2s
+    rch status --watch --interval 5 | tee status.log
+
+With --watch, a TTY gets an in-place redraw; piped output gets one plain
+summary line per poll."#)]
     Status {
         /// Show worker details
         #[arg(short = 'w', long)]
@@ -300,6 +312,35 @@ Use 'disable' to mark a worker as unavailable (optionally with --reason)."#)]
         /// self-healing / normal fail-open.
         #[arg(long)]
         remediation: bool,
+
+        /// Keep polling the daemon and redraw a compact dashboard (workers,
+        /// slots, active builds) without the full TUI
+        #[arg(long, conflicts_with_all = ["fleet", "remediation"])]
+        watch: bool,
+
+        /// Seconds between polls in --watch mode
+        #[arg(long, default_value_t = 2, requires = "watch",
+              value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+    },
+
+    /// Live per-worker resource usage (CPU, load, memory, disk) from telemetry
+    #[command(after_help = r#"EXAMPLES:
+    rch top                     # Live table, redrawn every 2s
+    rch top --interval 5        # Slower refresh
+    rch top --once --json       # One snapshot for scripting
+
+Shows the newest telemetry the daemon holds for each worker; "-" means none
+has arrived recently. Disk free comes from the last health probe. Piped
+output gets a timestamped plain table per poll."#)]
+    Top {
+        /// Seconds between polls
+        #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+
+        /// Print one snapshot and exit
+        #[arg(long)]
+        once: bool,
     },
 
     /// Quick health check - is RCH working?
@@ -347,6 +388,114 @@ The queue shows:
         follow: bool,
     },
 
+    /// Show recently completed builds from the daemon's build history
+    #[command(after_help = r#"EXAMPLES:
+    rch history                          # Last 20 builds
+    rch history --project myapp --last 50
+    rch history --failed-only            # Only builds with a non-zero exit
+    rch history --since 24h --group-by worker   # Per-worker totals for the last day
+    rch history --json                   # Output as JSON for scripting
+
+Each row shows the worker, duration, exit code, and whether the remote
+source sync was a cache hit (nothing needed uploading). With --group-by,
+rows instead summarize builds, failures, median duration, and bytes
+transferred per project, worker, or compilation kind."#)]
+    History {
+        /// Only show builds for this project
+        #[arg(long, short = 'p')]
+        project: Option<String>,
+
+        /// Number of most recent builds to show
+        #[arg(long, short = 'n', default_value = "20")]
+        last: usize,
+
+        /// Only show failed builds (non-zero exit code)
+        #[arg(long)]
+        failed_only: bool,
+
+        /// Only include builds completed within this window (e.g. `30m`, `24h`, `7d`)
+        #[arg(long, value_name = "DURATION")]
+        since: Option<String>,
+
+        /// Aggregate matching builds instead of listing them
+        #[arg(long, value_parser = ["project", "worker", "kind"])]
+        group_by: Option<String>,
+    },
+
+    /// List or show build logs spilled to disk by the hook
+    #[command(after_help = r#"EXAMPLES:
+    rch logs                  # List spilled build logs, newest first
+    rch logs --last           # Print the most recent build log
+    rch logs --last --json    # Log path and contents as JSON
+
+Captured build output is written to ~/.cache/rch/logs once it exceeds
+output.max_memory_bytes. Logs of successful builds are removed unless
+output.keep_logs is set."#)]
+    Logs {
+        /// Print the most recent build log
+        #[arg(long)]
+        last: bool,
+    },
+
+    /// Follow an in-progress build's output from another terminal
+    #[command(after_help = r#"EXAMPLES:
+    rch tail 42               # Replay buffered output of build 42, then follow it
+    rch tail 42 -n 0          # Only new output
+    rch tail 42 --json        # Raw tail events as JSON lines
+
+Build IDs are listed by 'rch queue'. Output reaches the daemon with the
+hook's heartbeats, so it lags the build by a few seconds; the daemon keeps
+the last 200 lines per build. Disable forwarding with
+output.forward_to_daemon = false."#)]
+    Tail {
+        /// Build ID to follow (use 'rch queue' to see active builds)
+        build_id: u64,
+
+        /// Buffered lines to show before following
+        #[arg(long, short = 'n', default_value = "50")]
+        lines: usize,
+    },
+
+    /// Reconnect to a detached build's output and exit code
+    #[command(after_help = r#"EXAMPLES:
+    rch attach 42             # Replay and follow build 42, exit with its status
+
+Only builds started with transfer.detach_remote_builds = true can be
+attached to; they keep running on the worker when the terminal or SSH
+session that started them goes away. Ctrl-C detaches again without
+stopping the build."#)]
+    Attach {
+        /// Build ID to attach to (use 'rch queue' to see active builds)
+        build_id: u64,
+    },
+
+    /// Explain the decisions behind a completed build
+    #[command(after_help = r#"EXAMPLES:
+    rch explain 42            # Classification, selection, sync, execution, artifacts
+    rch explain 42 --json     # Build record with its decision trace as JSON
+
+Build IDs are listed by 'rch history'. The trace is stored with the build's
+history entry, so builds that have aged out of the history buffer can no
+longer be explained."#)]
+    Explain {
+        /// Build ID to explain (use 'rch history' to see completed builds)
+        build_id: u64,
+    },
+
+    /// Re-run a completed build on the same worker and compare outcomes
+    #[command(after_help = r#"EXAMPLES:
+    rch replay 42             # Re-run build 42 on its worker, report match
+    rch replay 42 --json      # Original and replay exit codes as JSON
+
+The command runs from the recorded project directory through
+'rch exec --workers <worker>' with RCH_REQUIRE_REMOTE=1, pinned to the
+recorded toolchain. Exits non-zero when the outcome differs. Only remote
+builds still in the history buffer can be replayed."#)]
+    Replay {
+        /// Build ID to replay (use 'rch history' to see completed builds)
+        build_id: u64,
+    },
+
     /// Cancel active builds
     #[command(after_help = r#"EXAMPLES:
     rch cancel 42             # Cancel build with ID 42
@@ -441,6 +590,72 @@ wiped. Without --force (or with --dry-run) it only previews the plan."#)]
         action: CacheAction,
     },
 
+    /// Delete a project's directory on every healthy worker
+    #[command(after_help = r#"EXAMPLES:
+    rch clean --remote                     # Purge the current project on all healthy workers
+    rch clean --remote --project my-proj   # Purge /data/projects/my-proj instead
+    rch clean --remote --yes --json        # Non-interactive, per-worker JSON report
+
+The remote directory is the project's path under the canonical root
+(/data/projects), the same path builds sync to. Anything outside that root is
+refused. Each worker reports the bytes reclaimed; the next build re-uploads the
+project from scratch. For local staging trees use `rch cache clean`."#)]
+    Clean {
+        /// Purge the project's directory on remote workers
+        #[arg(long, required = true)]
+        remote: bool,
+
+        /// Project id (directory name under the canonical root); default: current directory
+        #[arg(long, value_name = "NAME")]
+        project: Option<String>,
+
+        /// Skip confirmation prompt
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+
+    /// Remove all local RCH state, and optionally its worker caches
+    #[command(after_help = r#"EXAMPLES:
+    rch uninstall --dry-run        # List what would be removed
+    rch uninstall                  # Stop the daemon, remove hooks and local state
+    rch uninstall --remote         # Also purge project mirrors on healthy workers
+    rch uninstall --remote --yes   # Non-interactive
+
+Removes the agent hooks, daemon socket, auto-start lock, cooldown and daemon
+log files, timing and build history, build logs, and telemetry, listing each
+path. The daemon is stopped first. The config directory (workers.toml,
+config.toml) is kept. With --remote, the directories of this machine's projects under the
+canonical root (/data/projects) are deleted on every healthy worker after
+confirmation; nothing outside that root is touched. Safe to run repeatedly."#)]
+    Uninstall {
+        /// Also delete this machine's project mirrors on remote workers
+        #[arg(long)]
+        remote: bool,
+
+        /// Skip the remote deletion confirmation prompt
+        #[arg(short = 'y', long)]
+        yes: bool,
+
+        /// List what would be removed without removing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// List the environment variables RCH recognizes and their effect
+    #[command(after_help = r#"EXAMPLES:
+    rch env              # Every recognized variable, value, default, effect
+    rch env --set        # Only the variables set in this shell
+    rch env --json       # Machine-readable listing
+
+Secret values (credentials, webhook tokens named in [doctor.webhooks]) are
+always shown as <redacted>. RCH_* variables that are set but not read by
+rch are listed as unrecognized, which usually means a typo."#)]
+    Env {
+        /// Only list variables that are currently set
+        #[arg(long)]
+        set: bool,
+    },
+
     /// Explain why a command would or wouldn't be offloaded
     #[command(after_help = r#"EXAMPLES:
     rch diagnose "cargo build --release"
@@ -478,6 +693,8 @@ wiped. Without --force (or with --dry-run) it only previews the plan."#)]
     rch exec -- cargo build --release
     rch exec -- cargo test
     rch exec -- bun test
+    rch exec --local -- cargo build   # Run locally this once
+    rch exec --workers css,fmd -- cargo build   # Only build on these workers
 
 USAGE:
     This command is primarily used internally by the PreToolUse hook.
@@ -485,13 +702,50 @@ USAGE:
         cargo build --release  →  rch exec -- cargo build --release
 
     This allows the hook to return immediately (<50ms) while the actual
-    compilation runs as a normal command invocation."#)]
+    compilation runs as a normal command invocation.
+
+    --local runs the command locally without contacting the daemon, for a
+    single invocation. The hook does not rewrite commands that already start
+    with `rch exec`, so an agent can type `rch exec --local -- cargo build`
+    to keep one build on this machine.
+
+    --workers restricts selection to the listed worker IDs, e.g. to debug or
+    compare specific workers. When none of them is available the build runs
+    locally; it never goes to another worker."#)]
     Exec {
+        /// Run the command locally, skipping worker selection and transfer
+        #[arg(long)]
+        local: bool,
+
+        /// Only select among these workers (comma-separated IDs)
+        #[arg(long, value_delimiter = ',', conflicts_with = "local")]
+        workers: Vec<String>,
+
         /// The compilation command to execute remotely
         #[arg(required = true, num_args = 1.., trailing_var_arg = true)]
         command: Vec<String>,
     },
 
+    /// Build remotely and locally, then compare the artifacts
+    #[command(
+        name = "diff-artifacts",
+        after_help = r#"EXAMPLES:
+    rch diff-artifacts -- cargo build --release
+    rch diff-artifacts --json -- cargo build -p mycrate
+
+Runs the command once on a worker and once locally, into scratch target
+dirs under target/rch-diff-artifacts/, and compares the binaries, rlibs
+and libraries by blake3 hash. Artifacts that embed a build path (the
+remote project dir, a home directory) are reported as expected
+differences. Exits non-zero when any other artifact differs or was built
+on one side only."#
+    )]
+    DiffArtifacts {
+        /// The cargo build command to compare
+        #[arg(required = true, num_args = 1.., trailing_var_arg = true)]
+        command: Vec<String>,
+    },
+
     /// Install and manage the Claude Code PreToolUse hook
     #[command(after_help = r#"EXAMPLES:
     rch hook install    # Register RCH as PreToolUse hook
@@ -505,6 +759,30 @@ compilation commands to remote workers."#)]
         action: HookAction,
     },
 
+    /// Serve hook decisions from one long-lived process
+    #[command(
+        name = "hook-server",
+        after_help = r#"EXAMPLES:
+    rch hook-server                          # Listen on the default socket
+    rch hook-server --socket ~/.cache/rch/hook.sock
+
+While a hook server runs, each hook invocation forwards its request to it
+instead of loading config and classifier rules itself, and prints the
+server's answer. Without a server (or when it does not answer within
+500ms) the hook decides on its own, so stopping the server is always safe.
+
+The default socket is rch-hook.sock in $XDG_RUNTIME_DIR or ~/.cache/rch;
+set RCH_HOOK_SERVER_SOCKET to use another path for both the server and the
+hook. The hook ignores a socket that is not owned by the current user with
+mode 0600, or whose server runs as another user. Decisions use the server's
+environment, not the agent's; restart it after changing RCH_* variables."#
+    )]
+    HookServer {
+        /// Socket path to listen on
+        #[arg(long)]
+        socket: Option<PathBuf>,
+    },
+
     /// Detect and manage AI coding agents (Claude Code, Gemini CLI, etc.)
     #[command(after_help = r#"EXAMPLES:
     rch agents list               # Show detected agents
@@ -559,6 +837,7 @@ INSTALL LOCATIONS:
 CHECKS PERFORMED:
     Prerequisites   - rsync, zstd, ssh, rustup, cargo
     Configuration   - config.toml, workers.toml validity
+    Local Paths     - State directory, /dp -> /data/projects topology
     SSH Keys        - Identity files exist with correct permissions
     Daemon          - Socket exists and responds
     Hooks           - Claude Code hook installed
@@ -571,7 +850,7 @@ CHECKS PERFORMED:
     },
 
     Doctor {
-        /// Attempt to fix safe issues (e.g., key permissions)
+        /// Attempt to fix safe issues (key permissions, hook, daemon, local dirs)
         #[arg(long)]
         fix: bool,
 
@@ -704,6 +983,19 @@ CHECKS PERFORMED:
         dry_run: bool,
     },
 
+    /// Offload a throwaway cargo project end to end through the real hook pipeline
+    #[command(
+        after_help = r#"Builds a generated project on a daemon-selected worker via the same
+sync/exec/retrieve path as a hooked build, checks the exit code and the
+retrieved binary, then removes the project. Unlike `rch self-test`, the
+build is driven from this machine rather than by the daemon.
+
+EXAMPLES:
+    rch selftest            # Run the smoke test
+    rch selftest --json     # Report as JSON (exit 1 on failure)"#
+    )]
+    Selftest,
+
     /// Update RCH binaries on local machine and/or workers
     #[command(
         visible_alias = "upgrade",
@@ -1030,6 +1322,9 @@ enum DaemonAction {
         /// Number of lines to show
         #[arg(short = 'n', long, default_value = "50")]
         lines: usize,
+        /// Keep printing new lines as the daemon writes them
+        #[arg(short = 'f', long)]
+        follow: bool,
     },
     /// Reload configuration without restart
     Reload,
@@ -1074,6 +1369,20 @@ enum WorkersAction {
         #[arg(short = 'a', long)]
         all: bool,
     },
+    /// Measure SSH round-trip latency to every worker
+    ///
+    /// Runs `true` over a fresh SSH connection N times per worker and reports
+    /// min/median/max connect+exec latency, fastest first. A few workers are
+    /// pinged at a time.
+    #[command(after_help = r#"EXAMPLES:
+    rch workers ping              # 5 round trips per worker
+    rch workers ping --count 20   # More samples for a steadier median
+    rch workers ping --json       # Machine-readable results"#)]
+    Ping {
+        /// Round trips per worker
+        #[arg(long, short = 'n', default_value_t = 5)]
+        count: u32,
+    },
     /// Run speed benchmarks against one or more workers (br-ifq7s)
     #[command(after_help = r#"EXAMPLES:
     rch workers benchmark             # Benchmark every configured worker
@@ -1264,6 +1573,7 @@ impl WorkersAction {
             WorkersAction::List { .. } => "list",
             WorkersAction::Capabilities { .. } => "capabilities",
             WorkersAction::Probe { .. } => "probe",
+            WorkersAction::Ping { .. } => "ping",
             WorkersAction::Benchmark { .. } => "benchmark",
             WorkersAction::Compare { .. } => "compare",
             WorkersAction::Drain { .. } => "drain",
@@ -1416,14 +1726,32 @@ Falls back to 'nano' if neither is set."#)]
     /// Show configuration values that differ from defaults
     #[command(after_help = r#"EXAMPLES:
     rch config diff               # Show all non-default values
+    rch config diff --all         # Include values still at their defaults
     rch config diff --json        # Machine-readable output
 
 Shows:
   • Key name
   • Current value
   • Default value
-  • Source (env, project, user)"#)]
-    Diff,
+  • Source (default, env:<VAR>, project:<path>, user:<path>)"#)]
+    Diff {
+        /// Also list values that are still at their defaults
+        #[arg(long)]
+        all: bool,
+    },
+    /// List configuration profiles or switch the active one
+    #[command(after_help = r#"EXAMPLES:
+    rch config profile            # List profiles and mark the active one
+    rch config profile dev        # Switch to the dev profile
+    rch config profile prod --json
+
+Switching validates the merged configuration first and refuses to switch
+into a profile that leaves it invalid. The choice is saved as
+general.profile in ~/.config/rch/config.toml; RCH_PROFILE overrides it."#)]
+    Profile {
+        /// Profile to switch to (dev, prod, test)
+        name: Option<String>,
+    },
 }
 
 impl ConfigAction {
@@ -1440,7 +1768,8 @@ impl ConfigAction {
             ConfigAction::Lint => "lint",
             ConfigAction::Doctor => "doctor",
             ConfigAction::Edit { .. } => "edit",
-            ConfigAction::Diff => "diff",
+            ConfigAction::Diff { .. } => "diff",
+            ConfigAction::Profile { .. } => "profile",
         }
     }
 }
@@ -1915,9 +2244,30 @@ async fn run(args: Vec<OsString>) -> Result<()> {
                 jobs,
                 fleet,
                 remediation,
-            } => handle_status(workers, jobs, fleet, remediation, &ctx).await,
+                watch,
+                interval,
+            } => {
+                if watch {
+                    commands::status_watch(interval, &ctx).await
+                } else {
+                    handle_status(workers, jobs, fleet, remediation, &ctx).await
+                }
+            }
+            Commands::Top { interval, once } => commands::top(interval, once, &ctx).await,
             Commands::Check => commands::check(&ctx).await,
             Commands::Queue { watch, follow } => commands::queue_status(watch, follow, &ctx).await,
+            Commands::History {
+                project,
+                last,
+                failed_only,
+                since,
+                group_by,
+            } => commands::build_history(project, last, failed_only, since, group_by, &ctx).await,
+            Commands::Logs { last } => commands::build_logs_command(last, &ctx),
+            Commands::Tail { build_id, lines } => commands::build_tail(build_id, lines, &ctx).await,
+            Commands::Attach { build_id } => commands::build_attach(build_id, &ctx).await,
+            Commands::Explain { build_id } => commands::build_explain(build_id, &ctx).await,
+            Commands::Replay { build_id } => commands::replay_build(build_id, &ctx).await,
             Commands::Cancel {
                 build_id,
                 all,
@@ -1934,12 +2284,29 @@ async fn run(args: Vec<OsString>) -> Result<()> {
             } => commands::sync_force(force, worker, all, project, dry_run, &ctx).await,
             Commands::Config { action } => handle_config(action, &ctx).await,
             Commands::Cache { action } => handle_cache(action, &ctx).await,
+            Commands::Clean {
+                remote: _,
+                project,
+                yes,
+            } => commands::clean_remote(project, yes, &ctx).await,
+            Commands::Uninstall {
+                remote,
+                yes,
+                dry_run,
+            } => commands::uninstall(remote, yes, dry_run, &ctx).await,
+            Commands::Env { set } => commands::show_env(set, &ctx),
             Commands::Diagnose { command, dry_run } => {
                 handle_diagnose(command, dry_run, &ctx).await
             }
             Commands::Admit { command } => handle_admit(command, &ctx).await,
-            Commands::Exec { command } => hook::run_exec(command).await,
+            Commands::Exec {
+                command,
+                local,
+                workers,
+            } => hook::run_exec(command, local, workers).await,
+            Commands::DiffArtifacts { command } => hook::run_diff_artifacts(command, &ctx).await,
             Commands::Hook { action } => handle_hook(action, &ctx).await,
+            Commands::HookServer { socket } => hook::run_hook_server(socket).await,
             Commands::Agents { action } => handle_agents(action, &ctx).await,
             Commands::Completions { action } => handle_completions(action, &ctx),
             Commands::Doctor {
@@ -1996,6 +2363,7 @@ async fn run(args: Vec<OsString>) -> Result<()> {
                 )
                 .await
             }
+            Commands::Selftest => commands::pipeline_selftest(&ctx).await,
             Commands::Update {
                 check,
                 version,
@@ -2204,8 +2572,8 @@ fn handle_schema_command(action: SchemaAction, ctx: &OutputContext) -> Result<()
 fn handle_schema_request(command: &Option<Commands>) -> Result<()> {
     use commands::{
         ConfigDiffResponse, ConfigDoctorResponse, ConfigGetResponse, ConfigLintResponse,
-        ConfigResetResponse, ConfigShowResponse, ConfigValidationResponse, DaemonStatusResponse,
-        DiagnoseResponse, HookActionResponse, WorkersListResponse,
+        ConfigProfileResponse, ConfigResetResponse, ConfigShowResponse, ConfigValidationResponse,
+        DaemonStatusResponse, DiagnoseResponse, HookActionResponse, WorkersListResponse,
     };
 
     let schema_json = match command {
@@ -2218,7 +2586,7 @@ fn handle_schema_request(command: &Option<Commands>) -> Result<()> {
                 let schema = schema_for!(ConfigDoctorResponse);
                 serde_json::to_string_pretty(&schema)?
             }
-            ConfigAction::Diff => {
+            ConfigAction::Diff { .. } => {
                 let schema = schema_for!(ConfigDiffResponse);
                 serde_json::to_string_pretty(&schema)?
             }
@@ -2238,6 +2606,10 @@ fn handle_schema_request(command: &Option<Commands>) -> Result<()> {
                 let schema = schema_for!(ConfigValidationResponse);
                 serde_json::to_string_pretty(&schema)?
             }
+            ConfigAction::Profile { .. } => {
+                let schema = schema_for!(ConfigProfileResponse);
+                serde_json::to_string_pretty(&schema)?
+            }
             _ => {
                 eprintln!(
                     "No JSON Schema available for 'config {}' output",
@@ -2768,11 +3140,14 @@ fn command_capabilities() -> Vec<CommandCapability> {
 
 fn command_category(name: &str) -> &'static str {
     match name {
-        "init" | "hook" | "agents" | "completions" => "setup",
-        "status" | "check" | "queue" | "speedscore" | "dashboard" | "web" => "monitoring",
-        "daemon" | "workers" | "cancel" | "sync" | "exec" | "update" | "fleet" => "management",
-        "config" => "configuration",
-        "diagnose" | "doctor" | "self-test" | "schema" => "debugging",
+        "init" | "hook" | "hook-server" | "agents" | "completions" | "uninstall" => "setup",
+        "status" | "top" | "check" | "queue" | "history" | "speedscore" | "dashboard" | "web" => {
+            "monitoring"
+        }
+        "daemon" | "workers" | "cancel" | "sync" | "clean" | "exec" | "replay" | "update"
+        | "fleet" => "management",
+        "config" | "env" => "configuration",
+        "diagnose" | "doctor" | "self-test" | "schema" | "logs" | "diff-artifacts" => "debugging",
         "capabilities" | "robot-docs" => "agent-docs",
         _ => "general",
     }
@@ -3406,8 +3781,8 @@ async fn handle_daemon(action: DaemonAction, ctx: &OutputContext) -> Result<()>
         DaemonAction::Status => {
             commands::daemon_status(ctx).await?;
         }
-        DaemonAction::Logs { lines } => {
-            commands::daemon_logs(lines, ctx)?;
+        DaemonAction::Logs { lines, follow } => {
+            commands::daemon_logs(lines, follow, ctx)?;
         }
         DaemonAction::Reload => {
             commands::daemon_reload(ctx).await?;
@@ -3427,6 +3802,9 @@ async fn handle_workers(action: WorkersAction, ctx: &OutputContext) -> Result<()
         WorkersAction::Probe { worker, all } => {
             commands::workers_probe(worker, all, ctx).await?;
         }
+        WorkersAction::Ping { count } => {
+            commands::workers_ping(count, ctx).await?;
+        }
         WorkersAction::Benchmark {
             worker_id,
             all: _,
@@ -3542,8 +3920,11 @@ async fn handle_config(action: ConfigAction, ctx: &OutputContext) -> Result<()>
         } => {
             commands::config_edit(project, user, workers, ctx)?;
         }
-        ConfigAction::Diff => {
-            commands::config_diff(ctx)?;
+        ConfigAction::Diff { all } => {
+            commands::config_diff(all, ctx)?;
+        }
+        ConfigAction::Profile { name } => {
+            commands::config_profile(name.as_deref(), ctx)?;
         }
     }
     Ok(())
@@ -3570,6 +3951,10 @@ struct CacheWarmWorkerResult {
     bytes_transferred: u64,
     files_transferred: u64,
     duration_ms: u64,
+    /// File data before compression; 0 when rsync did not report it.
+    raw_bytes: u64,
+    /// Bytes on the wire after compression; 0 when rsync did not report it.
+    compressed_bytes: u64,
     /// Only populated on failure. Operators read this to triage.
     #[serde(skip_serializing_if = "Option::is_none")]
     error: Option<String>,
@@ -3845,6 +4230,8 @@ async fn handle_cache_warm(
                     bytes_transferred: sync_result.bytes_transferred,
                     files_transferred: u64::from(sync_result.files_transferred),
                     duration_ms: sync_result.duration_ms,
+                    raw_bytes: sync_result.raw_bytes,
+                    compressed_bytes: sync_result.compressed_bytes,
                     error: None,
                 });
             }
@@ -3873,6 +4260,8 @@ async fn handle_cache_warm(
                     bytes_transferred: 0,
                     files_transferred: 0,
                     duration_ms,
+                    raw_bytes: 0,
+                    compressed_bytes: 0,
                     error: Some(error_msg),
                 });
             }
@@ -4911,9 +5300,10 @@ mod tests {
         let cli = Cli::try_parse_from(["rch", "daemon", "logs"]).unwrap();
         match cli.command {
             Some(Commands::Daemon {
-                action: DaemonAction::Logs { lines },
+                action: DaemonAction::Logs { lines, follow },
             }) => {
                 assert_eq!(lines, 50);
+                assert!(!follow);
             }
             _ => fail_expected("Expected daemon logs command"),
         }
@@ -4925,7 +5315,7 @@ mod tests {
         let cli = Cli::try_parse_from(["rch", "daemon", "logs", "-n", "100"]).unwrap();
         match cli.command {
             Some(Commands::Daemon {
-                action: DaemonAction::Logs { lines },
+                action: DaemonAction::Logs { lines, .. },
             }) => {
                 assert_eq!(lines, 100);
             }
@@ -4933,6 +5323,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cli_parses_daemon_logs_follow() {
+        let _guard = test_guard!();
+        let cli = Cli::try_parse_from(["rch", "daemon", "logs", "-f"]).unwrap();
+        match cli.command {
+            Some(Commands::Daemon {
+                action: DaemonAction::Logs { follow, .. },
+            }) => {
+                assert!(follow);
+            }
+            _ => fail_expected("Expected daemon logs command"),
+        }
+    }
+
     // -------------------------------------------------------------------------
     // Workers Subcommand Tests
     // -------------------------------------------------------------------------
@@ -4989,6 +5393,103 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cli_parses_exec_local_flag() {
+        let _guard = test_guard!();
+        let cli = Cli::try_parse_from(["rch", "exec", "--local", "--", "cargo", "build"]).unwrap();
+        match cli.command {
+            Some(Commands::Exec { command, local, .. }) => {
+                assert!(local);
+                assert_eq!(command, ["cargo", "build"]);
+            }
+            _ => fail_expected("Expected exec command"),
+        }
+
+        // Flags after the command belong to the command.
+        let cli = Cli::try_parse_from(["rch", "exec", "--", "cargo", "test", "--local"]).unwrap();
+        match cli.command {
+            Some(Commands::Exec { command, local, .. }) => {
+                assert!(!local);
+                assert_eq!(command, ["cargo", "test", "--local"]);
+            }
+            _ => fail_expected("Expected exec command"),
+        }
+    }
+
+    #[test]
+    fn cli_parses_hook_server_socket() {
+        let _guard = test_guard!();
+        let cli =
+            Cli::try_parse_from(["rch", "hook-server", "--socket", "/tmp/rch-hook.sock"]).unwrap();
+        match cli.command {
+            Some(Commands::HookServer { socket }) => {
+                assert_eq!(socket, Some(PathBuf::from("/tmp/rch-hook.sock")));
+            }
+            _ => fail_expected("Expected hook-server command"),
+        }
+    }
+
+    #[test]
+    fn cli_parses_exec_workers_flag() {
+        let _guard = test_guard!();
+        let cli = Cli::try_parse_from([
+            "rch",
+            "exec",
+            "--workers",
+            "css,fmd",
+            "--",
+            "cargo",
+            "build",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::Exec {
+                command,
+                local,
+                workers,
+            }) => {
+                assert!(!local);
+                assert_eq!(workers, ["css", "fmd"]);
+                assert_eq!(command, ["cargo", "build"]);
+            }
+            _ => fail_expected("Expected exec command"),
+        }
+
+        // A local run never selects a worker, so the two flags conflict.
+        assert!(
+            Cli::try_parse_from([
+                "rch",
+                "exec",
+                "--local",
+                "--workers",
+                "css",
+                "--",
+                "cargo",
+                "build",
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn cli_parses_workers_ping_count() {
+        let _guard = test_guard!();
+        let cli = Cli::try_parse_from(["rch", "workers", "ping", "--count", "12"]).unwrap();
+        match cli.command {
+            Some(Commands::Workers {
+                action: WorkersAction::Ping { count },
+            }) => assert_eq!(count, 12),
+            _ => fail_expected("Expected workers ping command"),
+        }
+        let cli = Cli::try_parse_from(["rch", "workers", "ping"]).unwrap();
+        match cli.command {
+            Some(Commands::Workers {
+                action: WorkersAction::Ping { count },
+            }) => assert_eq!(count, 5),
+            _ => fail_expected("Expected workers ping command"),
+        }
+    }
+
     #[test]
     fn cli_parses_workers_probe_specific() {
         let _guard = test_guard!();
@@ -5172,6 +5673,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cli_parses_worker_alias_drain_enable() {
+        let _guard = test_guard!();
+        let cli = Cli::try_parse_from(["rch", "worker", "drain", "css"]).unwrap();
+        match cli.command {
+            Some(Commands::Workers {
+                action: WorkersAction::Drain { worker, .. },
+            }) => assert_eq!(worker, "css"),
+            _ => fail_expected("Expected worker drain alias"),
+        }
+
+        let cli = Cli::try_parse_from(["rch", "worker", "enable", "css"]).unwrap();
+        match cli.command {
+            Some(Commands::Workers {
+                action: WorkersAction::Enable { worker },
+            }) => assert_eq!(worker, "css"),
+            _ => fail_expected("Expected worker enable alias"),
+        }
+    }
+
     #[test]
     fn cli_parses_workers_discover() {
         let _guard = test_guard!();
@@ -5296,6 +5817,7 @@ mod tests {
                 jobs,
                 fleet,
                 remediation,
+                ..
             }) => {
                 assert!(!workers);
                 assert!(!jobs);
@@ -5306,6 +5828,70 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cli_parses_status_watch_with_interval() {
+        let _guard = test_guard!();
+        let cli = Cli::try_parse_from(["rch", "status", "--watch"]).unwrap();
+        match cli.command {
+            Some(Commands::Status {
+                watch, interval, ..
+            }) => {
+                assert!(watch);
+                assert_eq!(interval, 2);
+            }
+            _ => fail_expected("Expected status command"),
+        }
+
+        let cli = Cli::try_parse_from(["rch", "status", "--watch", "--interval", "5"]).unwrap();
+        match cli.command {
+            Some(Commands::Status { interval, .. }) => assert_eq!(interval, 5),
+            _ => fail_expected("Expected status command"),
+        }
+
+        assert!(Cli::try_parse_from(["rch", "status", "--interval", "5"]).is_err());
+        assert!(Cli::try_parse_from(["rch", "status", "--watch", "--interval", "0"]).is_err());
+        assert!(Cli::try_parse_from(["rch", "status", "--watch", "--fleet"]).is_err());
+    }
+
+    #[test]
+    fn cli_parses_top_interval_and_once() {
+        let _guard = test_guard!();
+        let cli = Cli::try_parse_from(["rch", "top"]).unwrap();
+        match cli.command {
+            Some(Commands::Top { interval, once }) => {
+                assert_eq!(interval, 2);
+                assert!(!once);
+            }
+            _ => fail_expected("Expected top command"),
+        }
+
+        let cli = Cli::try_parse_from(["rch", "top", "--interval", "5", "--once"]).unwrap();
+        match cli.command {
+            Some(Commands::Top { interval, once }) => {
+                assert_eq!(interval, 5);
+                assert!(once);
+            }
+            _ => fail_expected("Expected top command"),
+        }
+
+        assert!(Cli::try_parse_from(["rch", "top", "--interval", "0"]).is_err());
+    }
+
+    #[test]
+    fn cli_parses_diff_artifacts_command() {
+        let _guard = test_guard!();
+        let cli =
+            Cli::try_parse_from(["rch", "diff-artifacts", "--", "cargo", "build", "--release"])
+                .unwrap();
+        match cli.command {
+            Some(Commands::DiffArtifacts { command }) => {
+                assert_eq!(command, ["cargo", "build", "--release"]);
+            }
+            _ => fail_expected("Expected diff-artifacts command"),
+        }
+        assert!(Cli::try_parse_from(["rch", "diff-artifacts"]).is_err());
+    }
+
     // -------------------------------------------------------------------------
     // Config Subcommand Tests
     // -------------------------------------------------------------------------
@@ -5531,10 +6117,18 @@ mod tests {
         let cli = Cli::try_parse_from(["rch", "config", "diff"]).unwrap();
         match cli.command {
             Some(Commands::Config {
-                action: ConfigAction::Diff,
-            }) => {}
+                action: ConfigAction::Diff { all },
+            }) => assert!(!all),
             _ => fail_expected("Expected config diff command"),
         }
+
+        let cli = Cli::try_parse_from(["rch", "config", "diff", "--all"]).unwrap();
+        match cli.command {
+            Some(Commands::Config {
+                action: ConfigAction::Diff { all },
+            }) => assert!(all),
+            _ => fail_expected("Expected config diff --all command"),
+        }
     }
 
     // -------------------------------------------------------------------------
@@ -7123,6 +7717,141 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cli_parses_history_default() {
+        let _guard = test_guard!();
+        let cli = Cli::try_parse_from(["rch", "history"]).unwrap();
+        match cli.command {
+            Some(Commands::History {
+                project,
+                last,
+                failed_only,
+                since,
+                group_by,
+            }) => {
+                assert!(project.is_none());
+                assert_eq!(last, 20);
+                assert!(!failed_only);
+                assert!(since.is_none());
+                assert!(group_by.is_none());
+            }
+            _ => fail_expected("Expected history command"),
+        }
+    }
+
+    #[test]
+    fn cli_parses_history_filters() {
+        let _guard = test_guard!();
+        let cli = Cli::try_parse_from([
+            "rch",
+            "history",
+            "--project",
+            "myapp",
+            "--last",
+            "5",
+            "--failed-only",
+        ])
+        .unwrap();
+        match cli.command {
+            Some(Commands::History {
+                project,
+                last,
+                failed_only,
+                ..
+            }) => {
+                assert_eq!(project.as_deref(), Some("myapp"));
+                assert_eq!(last, 5);
+                assert!(failed_only);
+            }
+            _ => fail_expected("Expected history command with filters"),
+        }
+    }
+
+    #[test]
+    fn cli_parses_history_aggregation() {
+        let _guard = test_guard!();
+        let cli = Cli::try_parse_from(["rch", "history", "--since", "24h", "--group-by", "worker"])
+            .unwrap();
+        match cli.command {
+            Some(Commands::History {
+                since, group_by, ..
+            }) => {
+                assert_eq!(since.as_deref(), Some("24h"));
+                assert_eq!(group_by.as_deref(), Some("worker"));
+            }
+            _ => fail_expected("Expected history command with aggregation"),
+        }
+        assert!(Cli::try_parse_from(["rch", "history", "--group-by", "color"]).is_err());
+    }
+
+    #[test]
+    fn cli_parses_logs() {
+        let _guard = test_guard!();
+        let cli = Cli::try_parse_from(["rch", "logs"]).unwrap();
+        match cli.command {
+            Some(Commands::Logs { last }) => assert!(!last),
+            _ => fail_expected("Expected logs command"),
+        }
+
+        let cli = Cli::try_parse_from(["rch", "logs", "--last"]).unwrap();
+        match cli.command {
+            Some(Commands::Logs { last }) => assert!(last),
+            _ => fail_expected("Expected logs command with --last"),
+        }
+    }
+
+    #[test]
+    fn cli_parses_tail() {
+        let _guard = test_guard!();
+        let cli = Cli::try_parse_from(["rch", "tail", "42"]).unwrap();
+        match cli.command {
+            Some(Commands::Tail { build_id, lines }) => {
+                assert_eq!(build_id, 42);
+                assert_eq!(lines, 50);
+            }
+            _ => fail_expected("Expected tail command"),
+        }
+
+        let cli = Cli::try_parse_from(["rch", "tail", "42", "-n", "0"]).unwrap();
+        match cli.command {
+            Some(Commands::Tail { lines, .. }) => assert_eq!(lines, 0),
+            _ => fail_expected("Expected tail command with -n"),
+        }
+    }
+
+    #[test]
+    fn cli_parses_attach() {
+        let _guard = test_guard!();
+        let cli = Cli::try_parse_from(["rch", "attach", "42"]).unwrap();
+        match cli.command {
+            Some(Commands::Attach { build_id }) => assert_eq!(build_id, 42),
+            _ => fail_expected("Expected attach command"),
+        }
+        assert!(Cli::try_parse_from(["rch", "attach"]).is_err());
+    }
+
+    #[test]
+    fn cli_parses_explain() {
+        let _guard = test_guard!();
+        let cli = Cli::try_parse_from(["rch", "explain", "42"]).unwrap();
+        match cli.command {
+            Some(Commands::Explain { build_id }) => assert_eq!(build_id, 42),
+            _ => fail_expected("Expected explain command"),
+        }
+        assert!(Cli::try_parse_from(["rch", "explain", "latest"]).is_err());
+    }
+
+    #[test]
+    fn cli_parses_replay() {
+        let _guard = test_guard!();
+        let cli = Cli::try_parse_from(["rch", "replay", "42"]).unwrap();
+        match cli.command {
+            Some(Commands::Replay { build_id }) => assert_eq!(build_id, 42),
+            _ => fail_expected("Expected replay command"),
+        }
+        assert!(Cli::try_parse_from(["rch", "replay"]).is_err());
+    }
+
     // -------------------------------------------------------------------------
     // Cancel Subcommand Tests
     // -------------------------------------------------------------------------
@@ -7174,6 +7903,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cli_parses_clean_remote() {
+        let _guard = test_guard!();
+        let cli =
+            Cli::try_parse_from(["rch", "clean", "--remote", "--project", "alpha", "-y"]).unwrap();
+        match cli.command {
+            Some(Commands::Clean {
+                remote,
+                project,
+                yes,
+            }) => {
+                assert!(remote);
+                assert_eq!(project.as_deref(), Some("alpha"));
+                assert!(yes);
+            }
+            _ => fail_expected("Expected clean --remote command"),
+        }
+        assert!(Cli::try_parse_from(["rch", "clean"]).is_err());
+    }
+
+    #[test]
+    fn cli_parses_uninstall() {
+        let _guard = test_guard!();
+        let cli = Cli::try_parse_from(["rch", "uninstall", "--remote", "-y"]).unwrap();
+        match cli.command {
+            Some(Commands::Uninstall {
+                remote,
+                yes,
+                dry_run,
+            }) => {
+                assert!(remote);
+                assert!(yes);
+                assert!(!dry_run);
+            }
+            _ => fail_expected("Expected uninstall command"),
+        }
+    }
+
+    #[test]
+    fn cli_parses_env() {
+        let _guard = test_guard!();
+        let cli = Cli::try_parse_from(["rch", "env", "--set"]).unwrap();
+        match cli.command {
+            Some(Commands::Env { set }) => assert!(set),
+            _ => fail_expected("Expected env command"),
+        }
+        let cli = Cli::try_parse_from(["rch", "env"]).unwrap();
+        assert!(matches!(cli.command, Some(Commands::Env { set: false })));
+    }
+
     #[test]
     fn cli_parses_cancel_force() {
         let _guard = test_guard!();
@@ -7460,6 +8239,13 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cli_parses_pipeline_selftest() {
+        let _guard = test_guard!();
+        let cli = Cli::try_parse_from(["rch", "selftest"]).unwrap();
+        assert!(matches!(cli.command, Some(Commands::Selftest)));
+    }
+
     // -------------------------------------------------------------------------
     // Output Format Utility Tests
     // -------------------------------------------------------------------------
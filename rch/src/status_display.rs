@@ -9,7 +9,9 @@
 use crate::error::DaemonError;
 #[cfg(not(unix))]
 use crate::error::PlatformError;
-use crate::status_types::{DaemonFullStatusResponse, extract_json_body, format_duration};
+use crate::status_types::{
+    DaemonFullStatusResponse, QueueStatusFromApi, extract_json_body, format_duration,
+};
 use crate::ui::theme::Theme;
 use anyhow::{Context, Result};
 use std::io::Write;
@@ -66,6 +68,28 @@ async fn send_status_command() -> Result<String> {
     crate::commands::send_daemon_command("GET /status\n").await
 }
 
+/// Query the daemon's wait queue (depth, priorities, wait times).
+pub async fn query_daemon_queue() -> Result<QueueStatusFromApi> {
+    let response = send_queue_command().await?;
+
+    let json_body =
+        extract_json_body(&response).ok_or_else(|| anyhow::anyhow!("Invalid response format"))?;
+
+    serde_json::from_str(json_body).context("Failed to parse queue response")
+}
+
+#[cfg(not(unix))]
+async fn send_queue_command() -> Result<String> {
+    Err(PlatformError::UnixOnly {
+        feature: "daemon queue".to_string(),
+    })?
+}
+
+#[cfg(unix)]
+async fn send_queue_command() -> Result<String> {
+    crate::commands::send_daemon_command("GET /queue\n").await
+}
+
 /// Send a worker drain command to the daemon.
 #[cfg(unix)]
 pub async fn drain_worker(worker_id: &str) -> Result<()> {
@@ -336,6 +360,23 @@ fn render_full_status_to<W: Write>(
         }
     }
 
+    // Incremental reuse: warm vs. cold rebuilds
+    if let Some(incremental) = &status.incremental
+        && incremental.rebuild_speedup > 0.0
+    {
+        writeln!(
+            out,
+            "  {} {} warm rebuilds {} faster ({} warm {} vs {} cold {})",
+            style.key("Incremental"),
+            style.muted(":"),
+            style.success(&format!("{:.1}x", incremental.rebuild_speedup)),
+            incremental.warm_builds,
+            format_duration_ms(incremental.warm_avg_ms),
+            incremental.cold_builds,
+            format_duration_ms(incremental.cold_avg_ms)
+        )?;
+    }
+
     // Alerts section if any
     if !status.alerts.is_empty() {
         writeln!(out, "\n{}", style.format_header("Alerts"))?;
@@ -465,6 +506,14 @@ fn render_workers_table_to<W: Write>(
             "disabled" => style.muted("disabled"),
             _ => style.muted(&worker.status),
         };
+        // Say why the last health check failed, e.g. "unreachable (ssh auth failed)".
+        let status_display = match worker.health_failure {
+            Some(reason) if worker.status == "healthy" => {
+                format!("{status_display} {}", style.warning(&format!("({reason})")))
+            }
+            Some(reason) => format!("{status_display} {}", style.error(&format!("({reason})"))),
+            None => status_display.to_string(),
+        };
 
         // Enhanced circuit display with recovery timing
         let circuit_display = match worker.circuit_state.as_str() {
@@ -1333,6 +1382,7 @@ mod tests {
                     total_slots: 8,
                     speed_score: 92.5,
                     last_error: None,
+                    health_failure: None,
                     consecutive_failures: 0,
                     recovery_in_secs: None,
                     failure_history: vec![true, true],
@@ -1359,6 +1409,7 @@ mod tests {
                     total_slots: 8,
                     speed_score: 12.3,
                     last_error: Some("SSH timeout".to_string()),
+                    health_failure: Some(rch_common::HealthFailureReason::Timeout),
                     consecutive_failures: 3,
                     recovery_in_secs: Some(30),
                     failure_history: vec![false, false, true],
@@ -1398,6 +1449,8 @@ mod tests {
                 detector_build_age_secs: Some(120),
                 detector_slots_owned: Some(4),
                 detector_last_evaluated_at: Some("2026-01-17T00:00:05Z".to_string()),
+                remote_pgid_file: None,
+                detached: false,
             }],
             queued_builds: vec![],
             recent_builds: vec![
@@ -1414,6 +1467,9 @@ mod tests {
                     bytes_transferred: Some(2048),
                     timing: None,
                     cancellation: None,
+                    decision_trace: Vec::new(),
+                    project_path: None,
+                    toolchain: None,
                 },
                 BuildRecordFromApi {
                     id: 3,
@@ -1428,6 +1484,9 @@ mod tests {
                     bytes_transferred: None,
                     timing: None,
                     cancellation: None,
+                    decision_trace: Vec::new(),
+                    project_path: None,
+                    toolchain: None,
                 },
             ],
             issues: vec![IssueFromApi {
@@ -1453,6 +1512,7 @@ mod tests {
                 runs_by_kind: std::collections::HashMap::from([("cargo_test".to_string(), 3)]),
             }),
             saved_time: None,
+            incremental: None,
             remediation: None,
         }
     }
@@ -1600,7 +1660,7 @@ mod tests {
         assert!(output.contains("healthy"));
         assert!(output.contains("worker-b"));
         // "unhealthy" status displays as "unreachable" (canonical name)
-        assert!(output.contains("unreachable"));
+        assert!(output.contains("unreachable (timed out)"));
         assert!(output.contains("open (30s)"));
         info!("PASS: workers and circuit states rendered");
     }
@@ -4,8 +4,8 @@
 
 use rch_common::remediation_view::RemediationView;
 use rch_common::{
-    BuildCancellationMetadata, BypassRecord, CommandTimingBreakdown, SavedTimeStats,
-    WorkerCapabilities,
+    BuildCancellationMetadata, BypassRecord, CommandPriority, CommandTimingBreakdown,
+    DecisionTraceStep, IncrementalReuseStats, SavedTimeStats, WorkerCapabilities,
 };
 use serde::{Deserialize, Serialize};
 
@@ -28,6 +28,9 @@ pub struct DaemonFullStatusResponse {
     /// Saved time statistics from remote builds.
     #[serde(default)]
     pub saved_time: Option<SavedTimeStats>,
+    /// Warm vs. cold remote rebuild durations.
+    #[serde(default)]
+    pub incremental: Option<IncrementalReuseStats>,
     /// Operator-facing remediation view assembled by the daemon
     /// (bd-session-history-remediation-ocv9i.14.4). `None` when talking to a
     /// daemon that predates the field; otherwise always present.
@@ -61,6 +64,9 @@ pub struct WorkerStatusFromApi {
     pub total_slots: u32,
     pub speed_score: f64,
     pub last_error: Option<String>,
+    /// Why the last health check failed, if it did.
+    #[serde(default)]
+    pub health_failure: Option<rch_common::HealthFailureReason>,
     /// Consecutive failure count.
     #[serde(default)]
     pub consecutive_failures: u32,
@@ -179,6 +185,10 @@ pub struct ActiveBuildFromApi {
     pub detector_slots_owned: Option<u32>,
     #[serde(default)]
     pub detector_last_evaluated_at: Option<String>,
+    #[serde(default)]
+    pub remote_pgid_file: Option<String>,
+    #[serde(default)]
+    pub detached: bool,
 }
 
 /// Queued build information from API.
@@ -190,10 +200,24 @@ pub struct QueuedBuildFromApi {
     pub queued_at: String,
     pub position: usize,
     pub slots_needed: u32,
+    #[serde(default)]
+    pub priority: CommandPriority,
     pub estimated_start: Option<String>,
+    #[serde(default)]
+    pub wait_secs: u64,
     pub wait_time: String,
 }
 
+/// Wait-queue snapshot from the daemon's GET /queue.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QueueStatusFromApi {
+    pub depth: usize,
+    #[serde(default)]
+    pub max_wait_secs: u64,
+    #[serde(default)]
+    pub builds: Vec<QueuedBuildFromApi>,
+}
+
 /// Build record from API.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BuildRecordFromApi {
@@ -211,6 +235,36 @@ pub struct BuildRecordFromApi {
     pub timing: Option<CommandTimingBreakdown>,
     #[serde(default)]
     pub cancellation: Option<BuildCancellationMetadata>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub decision_trace: Vec<DecisionTraceStep>,
+    /// Project directory the build ran in (used by `rch replay`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_path: Option<String>,
+    /// Rust toolchain the build used (used by `rch replay`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toolchain: Option<String>,
+}
+
+impl BuildRecordFromApi {
+    /// Whether the remote source sync uploaded nothing (worker copy already
+    /// current). `None` for local builds or when bytes were not reported.
+    pub fn cache_hit(&self) -> Option<bool> {
+        if self.location != "remote" {
+            return None;
+        }
+        self.bytes_transferred.map(|bytes| bytes == 0)
+    }
+}
+
+/// Build history response from daemon's GET /history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildHistoryResponseFromApi {
+    pub builds: Vec<BuildRecordFromApi>,
+    #[serde(default)]
+    pub total_recorded: usize,
+    /// Per-group aggregates for `--group-by` queries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<rch_common::BuildHistoryGroup>>,
 }
 
 /// Issue from API.
@@ -1297,6 +1351,35 @@ mod tests {
             build.estimated_start,
             Some("2026-01-16T12:05:00Z".to_string())
         );
+        assert_eq!(build.priority, CommandPriority::Normal);
+        assert_eq!(build.wait_secs, 0);
+    }
+
+    #[test]
+    fn test_deserialize_queue_status() {
+        let _guard = test_guard!();
+        let json = serde_json::json!({
+            "depth": 1,
+            "max_wait_secs": 75,
+            "builds": [{
+                "id": 7,
+                "project_id": "rch",
+                "command": "cargo build",
+                "queued_at": "2026-01-16T12:01:00Z",
+                "position": 1,
+                "slots_needed": 8,
+                "priority": "high",
+                "estimated_start": null,
+                "wait_secs": 75,
+                "wait_time": "1m 15s"
+            }]
+        });
+
+        let queue: QueueStatusFromApi = serde_json::from_value(json).unwrap();
+        assert_eq!(queue.depth, 1);
+        assert_eq!(queue.max_wait_secs, 75);
+        assert_eq!(queue.builds[0].priority, CommandPriority::High);
+        assert_eq!(queue.builds[0].wait_secs, 75);
     }
 
     #[test]
@@ -1468,6 +1551,7 @@ mod tests {
             },
             test_stats: None,
             saved_time: None,
+            incremental: None,
             remediation: None,
         }
     }
@@ -1548,6 +1632,7 @@ mod tests {
             total_slots: 8,
             speed_score: 50.0,
             last_error: None,
+            health_failure: None,
             consecutive_failures: 0,
             recovery_in_secs: None,
             failure_history: vec![],
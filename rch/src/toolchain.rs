@@ -188,6 +188,21 @@ fn detect_from_rustc() -> Result<ToolchainInfo, ToolchainError> {
     parse_rustc_version(&version_str)
 }
 
+/// `rustc --version` as seen from `project_root`, so rustup applies the
+/// project's toolchain override.
+pub fn local_rustc_version(project_root: &Path) -> Option<String> {
+    let output = Command::new("rustc")
+        .arg("--version")
+        .current_dir(project_root)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!version.is_empty()).then_some(version)
+}
+
 /// Parse rustc --version output.
 ///
 /// Examples:
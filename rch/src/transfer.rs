@@ -1,20 +1,25 @@
 //! File transfer and remote execution pipeline.
 //!
 //! Handles synchronizing project files to remote workers, executing compilation
-//! commands, and retrieving build artifacts.
+//! commands, and retrieving build artifacts. The byte-moving half of sync and
+//! retrieval sits behind the [`SyncBackend`] trait in the `backend` submodule,
+//! selected by `transfer.backend`; `transfer.artifact_archive` switches
+//! rsync retrieval to a single tar+zstd archive (`archive` submodule).
 
 use crate::error::TransferError;
 use anyhow::{Context, Result};
 use glob::Pattern;
+use rch_common::detached_build::{self, DetachedBuildFiles};
 use rch_common::mock::{self, MockConfig, MockRsync, MockRsyncConfig, MockSshClient};
 use rch_common::ssh_utils::{
     EnvPrefix, is_retryable_transport_error, is_retryable_transport_error_text, is_valid_env_key,
     shell_escape_value,
 };
 use rch_common::{
-    ColorMode, CommandResult, CompilationKind, PathTopologyPolicy, RetryConfig, ToolchainInfo,
-    TransferConfig, WorkerConfig, normalize_project_path_with_policy, wrap_command_with_color,
-    wrap_command_with_toolchain,
+    ColorMode, CommandResult, CompilationKind, IONICE_CLASS_RANGE, NICE_LEVEL_RANGE,
+    PathTopologyPolicy, RetryConfig, ToolchainInfo, TransferBackend, TransferConfig, WorkerConfig,
+    WorkerId, normalize_project_path_with_policy, validate_container_image,
+    wrap_command_with_color, wrap_command_with_toolchain,
 };
 #[cfg(unix)]
 use rch_common::{SshClient, SshOptions};
@@ -30,6 +35,11 @@ use tokio::time::Instant as TokioInstant;
 use tokio::time::sleep;
 use tracing::{debug, info, warn};
 
+mod archive;
+mod backend;
+pub use backend::{RcloneBackend, RsyncBackend, SyncBackend};
+mod verify;
+
 const PROJECT_HASH_CONTENT_LIMIT_BYTES: u64 = 2 * 1024 * 1024;
 const PROJECT_HASH_KEY_FILES: &[&str] = &[
     // Rust project files
@@ -53,6 +63,8 @@ const REMOTE_RUNTIME_EXCLUDE_PATTERNS: &[&str] = &[
     ".franken_whisper/tools/ffmpeg/",
 ];
 const DEFAULT_REMOTE_CARGO_TARGET_DIR_NAME: &str = ".rch-target";
+/// Rsync rule merging each directory's `.gitignore` as exclude-only patterns.
+const GITIGNORE_MERGE_FILTER: &str = "--filter=:- .gitignore";
 const CONFIG_EXCLUDE_REWRITES: &[(&str, &str)] = &[
     ("core.*", "core.[0-9]*"),
     (".core.*", ".core.[0-9]*"),
@@ -400,6 +412,112 @@ async fn execute_rsync_with_retry(
     .await
 }
 
+/// Run `command` with a background `rch-telemetry` sampler on the worker.
+///
+/// Every `interval_secs` the sampler prints one
+/// [`PIGGYBACK_STREAM_MARKER`](rch_telemetry::protocol::PIGGYBACK_STREAM_MARKER)
+/// line to stdout. `command` runs in a subshell so its own `exit` still
+/// reaches the sampler cleanup, and the exit status is preserved. The
+/// sampler's `sleep` is detached from the SSH channel so killing it doesn't
+/// hold the session open until the sleep expires. Workers without
+/// `rch-telemetry` run the command unchanged.
+fn wrap_command_with_telemetry_stream(
+    command: &str,
+    worker_id: &WorkerId,
+    interval_secs: u64,
+) -> String {
+    let escaped_worker = escape(Cow::from(worker_id.as_str()));
+    format!(
+        "__rch_ts=; if command -v rch-telemetry >/dev/null 2>&1; then \
+         ( while sleep {interval} >/dev/null 2>&1 </dev/null; do \
+         t=$(rch-telemetry collect --format json --worker-id {worker} 2>/dev/null) && \
+         [ -n \"$t\" ] && printf '%s %s\\n' '{marker}' \"$t\"; \
+         done ) 2>/dev/null </dev/null & __rch_ts=$!; fi\n\
+         ( {command}\n)\n\
+         __rch_status=$?; if [ -n \"$__rch_ts\" ]; then kill \"$__rch_ts\" 2>/dev/null; fi; \
+         exit $__rch_status",
+        interval = interval_secs.max(1),
+        worker = escaped_worker,
+        marker = rch_telemetry::protocol::PIGGYBACK_STREAM_MARKER,
+        command = command,
+    )
+}
+
+/// Run `command` in a throwaway container of `image` on the worker.
+///
+/// Uses `docker`, or `podman` when docker is missing. `project_path` is
+/// bind-mounted at the same path and used as the working directory, so the
+/// project-scoped target and temp dirs resolve inside the container and the
+/// artifacts stay on the worker's disk for retrieval. The container runs as
+/// the SSH user so those files aren't root-owned on the next sync, and
+/// inherits `LC_ALL` so error parsing still sees English output. The image
+/// must provide the toolchain (including `rustup` for pinned toolchains).
+fn wrap_command_in_container(command: &str, image: &str, project_path: &str) -> String {
+    // A single simple command (`sh -c SCRIPT ARGS`) so the `timeout ... env`
+    // and watchdog wrappers can prefix it. The script holds no single quotes.
+    let script = "c=$(command -v docker || command -v podman) || \
+{ echo \"rch: transfer.container_image is set but neither docker nor podman is installed\" >&2; exit 127; }; \
+exec \"$c\" run --rm --user \"$(id -u):$(id -g)\" -e LC_ALL -v \"$2:$2\" -w \"$2\" \"$1\" sh -lc \"$3\"";
+    format!(
+        "sh -c '{}' rch-container {} {} {}",
+        script,
+        escape(Cow::from(image)),
+        escape(Cow::from(project_path)),
+        escape(Cow::from(command)),
+    )
+}
+
+/// Check `build_command` against the worker's `RCH_WKR_ALLOWED_COMMANDS`
+/// before anything runs. The check sees the build itself rather than the
+/// color, container, watchdog or priority wrappers around it, and covers
+/// daemon-dispatched builds, which never go through `rch-wkr execute`. A no-op
+/// when the variable is unset; when it is set, a worker whose rch-wkr cannot
+/// check fails the build instead of running it unchecked. The client composes
+/// this check, so it only holds for clients that include it.
+fn allowlist_check_prefix(build_command: &str) -> String {
+    format!(
+        "{{ [ -z \"${{RCH_WKR_ALLOWED_COMMANDS:-}}\" ] || RCH_LOG_LEVEL=warn rch-wkr execute --workdir . --check-only --command {}; }} && ",
+        escape(Cow::from(build_command))
+    )
+}
+
+/// Run `command` under `nice -n <level> ionice -c <class>` (either part only
+/// when set), so a build on a worker that doubles as a workstation yields to
+/// interactive use. The command is shell syntax, so it goes through `sh -c`;
+/// the watchdog inside records that shell's children as the build's process
+/// group as before. Out-of-range values are ignored with a warning.
+///
+/// Priority is best-effort: `ionice` is skipped when the worker does not have
+/// it (macOS), and `-t` runs the build anyway when the class cannot be set.
+fn wrap_command_with_priority(
+    command: &str,
+    nice_level: Option<i32>,
+    ionice_class: Option<u8>,
+) -> String {
+    let mut prefix = String::new();
+    match nice_level {
+        Some(level) if NICE_LEVEL_RANGE.contains(&level) => {
+            prefix.push_str(&format!("nice -n {} ", level));
+        }
+        Some(level) => warn!("Ignoring worker nice_level {}: must be -20..=19", level),
+        None => {}
+    }
+    match ionice_class {
+        Some(class) if IONICE_CLASS_RANGE.contains(&class) => {
+            prefix.push_str(&format!(
+                "sh -c 'command -v ionice >/dev/null 2>&1 && exec ionice -c {} -t \"$@\"; exec \"$@\"' rch-ionice ",
+                class
+            ));
+        }
+        Some(class) => warn!("Ignoring worker ionice_class {}: must be 2..=3", class),
+        None => {}
+    }
+    if prefix.is_empty() {
+        return command.to_string();
+    }
+    format!("{}sh -c {}", prefix, escape(Cow::from(command)))
+}
+
 fn use_mock_transport(worker: &WorkerConfig) -> bool {
     mock::is_mock_enabled() || mock::is_mock_worker(worker)
 }
@@ -429,6 +547,26 @@ pub fn parse_rchignore_content(content: &str) -> Vec<String> {
         .collect()
 }
 
+/// Whether the project's `Cargo.toml` or `.cargo/config.toml` sets
+/// `incremental` in any `[profile.*]`; `compilation.remote_incremental` must
+/// not override the project's own choice.
+fn project_profile_sets_incremental(project_root: &Path) -> bool {
+    ["Cargo.toml", ".cargo/config.toml", ".cargo/config"]
+        .iter()
+        .filter_map(|file| std::fs::read_to_string(project_root.join(file)).ok())
+        .filter_map(|contents| toml::from_str::<toml::Value>(&contents).ok())
+        .any(|manifest| {
+            manifest
+                .get("profile")
+                .and_then(toml::Value::as_table)
+                .is_some_and(|profiles| {
+                    profiles
+                        .values()
+                        .any(|profile| profile.get("incremental").is_some())
+                })
+        })
+}
+
 /// Transfer pipeline for remote compilation.
 pub struct TransferPipeline {
     /// Local project root.
@@ -462,6 +600,10 @@ pub struct TransferPipeline {
     ///
     /// Populated by `should_skip_transfer` when estimation is performed.
     estimated_transfer_bytes: Option<u64>,
+    /// Daemon-measured upload bandwidth to the worker (bytes/sec).
+    measured_bandwidth_bps: Option<u64>,
+    /// Daemon-measured SSH round-trip time to the worker (ms).
+    measured_rtt_ms: Option<u64>,
     /// Optional explicit remote path override.
     ///
     /// When set, transfer and execution use this path directly instead of
@@ -480,6 +622,13 @@ pub struct TransferPipeline {
     sync_delete: bool,
     /// Build ID for tracking and cancellation.
     build_id: Option<u64>,
+    /// Seconds between telemetry samples streamed during the build
+    /// (`telemetry.streaming`); `None` disables the sampler.
+    telemetry_stream_secs: Option<u64>,
+    /// Worker's `nice_level`; the remote command runs under `nice -n`.
+    nice_level: Option<i32>,
+    /// Worker's `ionice_class`; the remote command runs under `ionice -c`.
+    ionice_class: Option<u8>,
 }
 
 /// Validate a project hash for safe use in file paths.
@@ -566,11 +715,16 @@ impl TransferPipeline {
             compilation_kind: None,
             compilation_config: rch_common::CompilationConfig::default(),
             estimated_transfer_bytes: None,
+            measured_bandwidth_bps: None,
+            measured_rtt_ms: None,
             remote_path_override: None,
             remote_cargo_target_dir_name: DEFAULT_REMOTE_CARGO_TARGET_DIR_NAME.to_string(),
             sync_include_patterns: None,
             sync_delete: true,
             build_id: None,
+            telemetry_stream_secs: None,
+            nice_level: None,
+            ionice_class: None,
         }
     }
 
@@ -580,6 +734,13 @@ impl TransferPipeline {
         self
     }
 
+    /// Stream a worker telemetry sample every `interval_secs` while a
+    /// streaming command runs (`None` disables).
+    pub fn with_telemetry_stream(mut self, interval_secs: Option<u64>) -> Self {
+        self.telemetry_stream_secs = interval_secs;
+        self
+    }
+
     /// Set custom SSH options.
     #[cfg(unix)]
     #[allow(dead_code)] // Reserved for future CLI/config support
@@ -595,6 +756,26 @@ impl TransferPipeline {
         self
     }
 
+    /// Set the link quality the daemon measured for the target worker, used by
+    /// `should_skip_transfer` with `transfer.max_sync_seconds`.
+    pub fn with_measured_link(mut self, bandwidth_bps: Option<u64>, rtt_ms: Option<u64>) -> Self {
+        self.measured_bandwidth_bps = bandwidth_bps;
+        self.measured_rtt_ms = rtt_ms;
+        self
+    }
+
+    /// Run remote commands under the worker's `nice_level` / `ionice_class`
+    /// so builds on a shared machine yield to interactive work.
+    pub fn with_remote_priority(
+        mut self,
+        nice_level: Option<i32>,
+        ionice_class: Option<u8>,
+    ) -> Self {
+        self.nice_level = nice_level;
+        self.ionice_class = ionice_class;
+        self
+    }
+
     /// Set environment allowlist for remote execution.
     pub fn with_env_allowlist(mut self, allowlist: Vec<String>) -> Self {
         self.env_allowlist = allowlist;
@@ -1042,6 +1223,24 @@ impl TransferPipeline {
         )
     }
 
+    /// Whether the remote command runs with `CARGO_INCREMENTAL=1`
+    /// (`compilation.remote_incremental`): an unoptimized Cargo build whose
+    /// caller did not forward a `CARGO_INCREMENTAL` or
+    /// `CARGO_PROFILE_*_INCREMENTAL` of its own, in a project whose profiles
+    /// do not set `incremental`.
+    fn forces_cargo_incremental(&self, command: &str, forwarded: &[String]) -> bool {
+        self.compilation_config.remote_incremental
+            && self
+                .compilation_kind
+                .is_some_and(|kind| kind.command_base() == "cargo")
+            && !rch_common::is_optimized_build(command)
+            && !forwarded.iter().any(|key| {
+                key == "CARGO_INCREMENTAL"
+                    || (key.starts_with("CARGO_PROFILE_") && key.ends_with("_INCREMENTAL"))
+            })
+            && !project_profile_sets_incremental(&self.project_root)
+    }
+
     /// Build the full remote command string with all wrappers.
     fn build_remote_command(&self, command: &str, toolchain: Option<&ToolchainInfo>) -> String {
         let remote_path = self.remote_path();
@@ -1055,6 +1254,12 @@ impl TransferPipeline {
         if !env_plan.env_prefix.rejected.is_empty() {
             warn!("Skipping env vars: {:?}", env_plan.env_prefix.rejected);
         }
+        let toolchain_command =
+            if self.forces_cargo_incremental(command, &env_plan.env_prefix.applied) {
+                format!("CARGO_INCREMENTAL=1 {}", toolchain_command)
+            } else {
+                toolchain_command
+            };
         let env_command = if env_plan.env_prefix.prefix.is_empty() {
             toolchain_command
         } else {
@@ -1064,12 +1269,27 @@ impl TransferPipeline {
         // Apply color mode environment variables
         let colored_command = wrap_command_with_color(&env_command, self.color_mode);
 
+        // Run inside the configured container image. The env and color
+        // prefixes go in with the command; the timeout and watchdog below
+        // wrap the container client on the host.
+        let colored_command = match self.transfer_config.container_image.as_deref() {
+            Some(image) => match validate_container_image(image) {
+                Ok(()) => wrap_command_in_container(&colored_command, image, &remote_path),
+                Err(e) => {
+                    warn!("Ignoring transfer.container_image: {}", e);
+                    colored_command
+                }
+            },
+            None => colored_command,
+        };
+
         // Apply external process timeout wrapper for commands known to hang.
         // Bun tests have known issues where they can hang at 100% CPU indefinitely:
         // - https://github.com/oven-sh/bun/issues/21277 (sync loops block timeout)
         // - https://github.com/oven-sh/bun/issues/6751 (multiple test files cause hangs)
         // The `timeout` command provides a hard kill that works even for CPU-bound loops.
-        let timeout_wrapped_command = self.wrap_with_external_timeout(&colored_command);
+        let timeout_wrapped_command =
+            self.wrap_with_external_timeout(&colored_command, &env_command);
         // Wall-clock cap in seconds for the pgid-tracked path's watchdog (0 = disabled).
         // Same source of truth as `wrap_with_external_timeout`, applied via an
         // in-session group-kill watchdog instead of `timeout(1)` (see build_id branch).
@@ -1101,7 +1321,7 @@ impl TransferPipeline {
         let execution_command = if let Some(build_id) = self.build_id {
             let remote_pgid_file = Self::remote_pgid_file_path_for_root(&remote_path, build_id);
             let remote_run_dir = Self::remote_run_dir_for_root(&remote_path);
-            let escaped_pgid_file = escape(Cow::from(remote_pgid_file));
+            let escaped_pgid_file = escape(Cow::from(remote_pgid_file.as_str()));
             let escaped_run_dir = escape(Cow::from(remote_run_dir));
             // For the pgid-tracked path we do NOT use the `timeout(1)` wrapper:
             // `timeout --foreground` only signals its direct child, so a livelocked
@@ -1132,15 +1352,12 @@ impl TransferPipeline {
 if [ \"$__t\" -gt 0 ] 2>/dev/null; then ( sleep \"$__t\"; kill -KILL -\"$__p\" 2>/dev/null ) >/dev/null 2>&1 </dev/null & __w=$!; fi; \
 wait \"$__c\"; __s=$?; if [ -n \"$__w\" ]; then kill \"$__w\" 2>/dev/null; fi; exit \"$__s\"";
 
-            format!(
-                "mkdir -p {} && rm -f {} && \
-if command -v setsid >/dev/null 2>&1; then \
+            let invocation = format!(
+                "if command -v setsid >/dev/null 2>&1; then \
 setsid sh -c '{}' rch-build {} {} sh -lc {}; \
 else \
 sh -c '{}' rch-build {} {} sh -lc {}; \
 fi",
-                escaped_run_dir,
-                escaped_pgid_file,
                 watchdog,
                 escaped_pgid_file,
                 external_timeout_secs,
@@ -1149,14 +1366,34 @@ fi",
                 escaped_pgid_file,
                 external_timeout_secs,
                 escaped_command,
+            );
+            // Detached builds run in the background with output captured on
+            // the worker; this SSH session only follows it (see `rch attach`).
+            let invocation = if self.transfer_config.detach_remote_builds {
+                let files = DetachedBuildFiles::for_pgid_file(&remote_pgid_file);
+                detached_build::detach_command(&files, &invocation)
+            } else {
+                invocation
+            };
+
+            format!(
+                "mkdir -p {} && rm -f {} && {}",
+                escaped_run_dir, escaped_pgid_file, invocation
             )
         } else {
             timeout_wrapped_command
         };
 
+        let execution_command =
+            wrap_command_with_priority(&execution_command, self.nice_level, self.ionice_class);
+
         format!(
-            "export LC_ALL=C; touch {} && cd {} && {}{}",
-            escaped_remote_path, escaped_remote_path, ensure_dirs_command, execution_command
+            "export LC_ALL=C; touch {} && cd {} && {}{}{}",
+            escaped_remote_path,
+            escaped_remote_path,
+            ensure_dirs_command,
+            allowlist_check_prefix(&env_command),
+            execution_command
         )
     }
 
@@ -1171,8 +1408,16 @@ fi",
     ///
     /// The timeout wrapper can be disabled entirely via `external_timeout_enabled`.
     ///
+    /// The command runs under `rch-wkr execute --timeout-secs` when the worker's
+    /// rch-wkr supports that flag, and under `timeout(1)` otherwise. Builds with
+    /// a daemon build id use the pgid watchdog in `build_remote_command`
+    /// instead, since rch-wkr's own process group would escape the group the
+    /// daemon's cancellation kills. `build_command` is `command` without its
+    /// color and container wrappers; rch-wkr checks it, not the wrapper,
+    /// against the worker's command allowlist.
+    ///
     /// Returns the original command unchanged if timeout wrapping is disabled.
-    fn wrap_with_external_timeout(&self, command: &str) -> String {
+    fn wrap_with_external_timeout(&self, command: &str, build_command: &str) -> String {
         // Check if external timeout protection is enabled
         if !self.compilation_config.external_timeout_enabled() {
             debug!("External timeout protection disabled by config");
@@ -1196,6 +1441,7 @@ fi",
             Some(CompilationKind::CargoClippy) => "cargo clippy",
             Some(CompilationKind::CargoDoc) => "cargo doc",
             Some(CompilationKind::CargoBench) => "cargo bench",
+            Some(CompilationKind::CargoRun) => "cargo run",
             Some(CompilationKind::Rustc) => "rustc",
             Some(CompilationKind::Gcc) => "gcc",
             Some(CompilationKind::Gpp) => "g++",
@@ -1205,6 +1451,8 @@ fi",
             Some(CompilationKind::CmakeBuild) => "cmake build",
             Some(CompilationKind::Ninja) => "ninja",
             Some(CompilationKind::Meson) => "meson",
+            Some(CompilationKind::TrunkBuild) => "trunk build",
+            Some(CompilationKind::WasmPackBuild) => "wasm-pack build",
             None => "unknown",
         };
 
@@ -1214,6 +1462,12 @@ fi",
             "Wrapping command with external timeout protection"
         );
 
+        // Prefer the worker agent: `rch-wkr execute --timeout-secs` kills the
+        // command's whole process group (exit 120), where `timeout(1)` only
+        // signals its direct child. The flag is probed rather than assumed, so
+        // workers without rch-wkr, or with one that predates it, keep
+        // `timeout(1)`. rch-wkr logs to stderr, which is the build's stderr
+        // here, so it runs at warn level.
         // Use --signal=KILL to ensure the process dies even if stuck in a CPU loop.
         // The --foreground flag ensures timeout works properly in non-interactive shells.
         // --preserve-status ensures the exit code reflects whether timeout killed it.
@@ -1221,144 +1475,50 @@ fi",
         // assignments after the timeout wrapper is prepended.
         // Exit code 137 (128 + 9) indicates SIGKILL was sent.
         format!(
-            "timeout --signal=KILL --foreground --preserve-status {} env {}",
-            timeout_secs, command
+            "if rch-wkr execute --help 2>/dev/null | grep -q -- --check-command; then \
+RCH_LOG_LEVEL=warn rch-wkr execute --workdir . --timeout-secs {} --check-command {} --command {}; \
+else timeout --signal=KILL --foreground --preserve-status {} env {}; fi",
+            timeout_secs,
+            escape(Cow::from(build_command)),
+            escape(Cow::from(command)),
+            timeout_secs,
+            command
         )
     }
 
     // =========================================================================
-    // Transfer Size Estimation (bd-3hho)
+    // Sync Backend Dispatch
     // =========================================================================
 
-    /// Estimate transfer size using rsync dry-run.
-    ///
-    /// Returns `None` if estimation fails (e.g., rsync unavailable). Fail-open:
-    /// if estimation fails, proceed with transfer rather than blocking.
-    #[allow(dead_code)]
-    pub async fn estimate_transfer_size(&self, worker: &WorkerConfig) -> Option<TransferEstimate> {
-        let effective_excludes = self.get_effective_excludes();
-        let start = std::time::Instant::now();
-
-        let mut cmd = Command::new("rsync");
-        cmd.env("LC_ALL", "C");
-
-        let identity_file = shellexpand::tilde(&worker.identity_file);
-        let escaped_identity = escape(Cow::from(identity_file.as_ref()));
-
-        cmd.arg("-az");
-        add_portable_rsync_archive_args(&mut cmd);
-        cmd.arg("--dry-run").arg("--stats").arg("-e").arg(format!(
-            "ssh -i {} -o StrictHostKeyChecking=accept-new -o BatchMode=yes -o ConnectTimeout=5",
-            escaped_identity
-        ));
-
-        for pattern in &effective_excludes {
-            cmd.arg("--exclude").arg(pattern);
-        }
-
-        let remote_path = self.remote_path();
-        let escaped_remote_path = escape(Cow::from(&remote_path));
-        let destination = format!("{}@{}:{}", worker.user, worker.host, escaped_remote_path);
-
-        cmd.arg(format!("{}/", self.project_root.display()))
-            .arg(&destination);
-
-        cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
-
-        let output = match cmd.output().await {
-            Ok(output) => output,
-            Err(e) => {
-                debug!("Transfer estimation failed (rsync error): {}", e);
-                return None;
-            }
-        };
-
-        let estimation_ms = start.elapsed().as_millis() as u64;
-        let stdout = String::from_utf8_lossy(&output.stdout);
-
-        if !output.status.success() {
-            debug!(
-                "Transfer estimation failed (exit {}): {}",
-                output.status.code().unwrap_or(-1),
-                String::from_utf8_lossy(&output.stderr)
-            );
-            return None;
-        }
-
-        let bytes = crate::transfer::parse_rsync_total_size(&stdout).unwrap_or(0);
-        let files = crate::transfer::parse_rsync_total_files(&stdout).unwrap_or(0);
-
-        // Calculate estimated transfer time using configured or default bandwidth
-        // Default: 10 MB/s (reasonable for local network)
-        let bandwidth_bps = self
-            .transfer_config
-            .estimated_bandwidth_bps
-            .unwrap_or(10 * 1024 * 1024);
-
-        let estimated_time_ms = if bandwidth_bps > 0 {
-            (bytes as f64 / bandwidth_bps as f64 * 1000.0).round() as u64
-        } else {
-            0
-        };
-
-        Some(TransferEstimate {
-            bytes,
-            files,
-            estimated_time_ms,
-            estimation_ms,
-        })
-    }
-
     /// Check if transfer should be skipped based on size/time thresholds.
     ///
     /// Returns `Some(reason)` if transfer should be skipped, `None` if it should proceed.
     #[allow(dead_code)]
     pub async fn should_skip_transfer(&mut self, worker: &WorkerConfig) -> Option<String> {
-        // Check if any thresholds are configured
-        let max_mb = self.transfer_config.max_transfer_mb;
-        let max_time_ms = self.transfer_config.max_transfer_time_ms;
-
-        let needs_estimate =
-            self.transfer_config.adaptive_compression || max_mb.is_some() || max_time_ms.is_some();
-
-        if !needs_estimate {
-            return None; // No thresholds configured
+        match self.transfer_config.backend {
+            TransferBackend::Rsync => RsyncBackend.should_skip_transfer(self, worker).await,
+            TransferBackend::Rclone => RcloneBackend.should_skip_transfer(self, worker).await,
         }
+    }
 
-        // Run estimation
-        let estimate = match self.estimate_transfer_size(worker).await {
-            Some(e) => e,
-            None => {
-                self.estimated_transfer_bytes = None;
-                debug!("Transfer estimation failed, proceeding with transfer (fail-open)");
-                return None;
-            }
-        };
-        self.estimated_transfer_bytes = Some(estimate.bytes);
-
-        // Check size threshold
-        if let Some(max_mb) = max_mb {
-            let max_bytes = max_mb.saturating_mul(1024 * 1024);
-            if estimate.bytes > max_bytes {
-                let estimated_mb = estimate.bytes as f64 / (1024.0 * 1024.0);
-                return Some(format!(
-                    "Transfer size ({:.2} MB) exceeds threshold ({:.2} MB)",
-                    estimated_mb, max_mb as f64
-                ));
-            }
+    /// Add the upload exclude rules: `transfer.force_include` first, then config
+    /// defaults + `.rchignore`, then, with `transfer.respect_gitignore`, the
+    /// `gitignore_include` patterns and a per-directory `.gitignore` merge.
+    /// Rsync applies the first matching rule, so forced includes beat every
+    /// exclude and configured excludes such as `target/` beat `.gitignore`.
+    fn add_exclude_args(&self, cmd: &mut Command, effective_excludes: &[String]) {
+        for pattern in &self.transfer_config.force_include {
+            cmd.arg("--include").arg(pattern);
         }
-
-        // Check time threshold
-        if let Some(max_time) = max_time_ms
-            && estimate.estimated_time_ms > max_time
-        {
-            return Some(format!(
-                "Estimated transfer time ({} ms) exceeds threshold ({} ms)",
-                estimate.estimated_time_ms, max_time
-            ));
+        for pattern in effective_excludes {
+            cmd.arg("--exclude").arg(pattern);
+        }
+        if self.transfer_config.respect_gitignore {
+            for pattern in &self.transfer_config.gitignore_include {
+                cmd.arg("--include").arg(pattern);
+            }
+            cmd.arg(GITIGNORE_MERGE_FILTER);
         }
-
-        None
     }
 
     /// Build rsync command for sync_to_remote.
@@ -1373,9 +1533,7 @@ fi",
         // Force C locale for consistent output parsing
         cmd.env("LC_ALL", "C");
 
-        let identity_file = shellexpand::tilde(&worker.identity_file);
-        let escaped_identity = escape(Cow::from(identity_file.as_ref()));
-        let ssh_command = self.build_rsync_ssh_command(escaped_identity.as_ref());
+        let ssh_command = self.build_rsync_ssh_command(worker);
 
         cmd.arg("-az"); // Archive mode + compression
         add_portable_rsync_archive_args(&mut cmd);
@@ -1399,10 +1557,7 @@ fi",
             }
             cmd.arg("--exclude").arg("*");
         } else {
-            // Add exclude patterns (config defaults + .rchignore)
-            for pattern in effective_excludes {
-                cmd.arg("--exclude").arg(pattern);
-            }
+            self.add_exclude_args(&mut cmd, effective_excludes);
         }
 
         // Add zstd compression if available (rsync 3.2.3+)
@@ -1439,9 +1594,7 @@ fi",
         // Force C locale for consistent output parsing
         cmd.env("LC_ALL", "C");
 
-        let identity_file = shellexpand::tilde(&worker.identity_file);
-        let escaped_identity = escape(Cow::from(identity_file.as_ref()));
-        let ssh_command = self.build_rsync_ssh_command(escaped_identity.as_ref());
+        let ssh_command = self.build_rsync_ssh_command(worker);
 
         cmd.arg("-az"); // Archive mode + compression
         add_portable_rsync_archive_args(&mut cmd);
@@ -1465,10 +1618,7 @@ fi",
             }
             cmd.arg("--exclude").arg("*");
         } else {
-            // Add exclude patterns (config defaults + .rchignore)
-            for pattern in effective_excludes {
-                cmd.arg("--exclude").arg(pattern);
-            }
+            self.add_exclude_args(&mut cmd, effective_excludes);
         }
 
         // Add zstd compression if available (rsync 3.2.3+)
@@ -1497,105 +1647,15 @@ fi",
     ///
     /// Uses retry logic with exponential backoff for transient network errors.
     pub async fn sync_to_remote(&self, worker: &WorkerConfig) -> Result<SyncResult> {
-        let remote_path = self.remote_path();
-        let escaped_remote_path = escape(Cow::from(&remote_path));
-        let destination = format!("{}@{}:{}", worker.user, worker.host, escaped_remote_path);
-
-        // Get effective excludes (config defaults + .rchignore)
-        let effective_excludes = self.get_effective_excludes();
-
-        if use_mock_transport(worker) {
-            // Mock path also uses retry logic for consistent behavior
-            // Create MockRsync ONCE and share via Arc so failure counters persist across retries
-            let rsync = std::sync::Arc::new(MockRsync::new(MockRsyncConfig::from_env()));
-            let project_root_str = self.project_root.display().to_string();
-            let retry_config = self.transfer_config.retry.clone();
-            let result = retry_with_backoff(&retry_config, "mock_sync_to_remote", || {
-                let rsync = rsync.clone();
-                let project_root = project_root_str.clone();
-                let dest = destination.clone();
-                let excludes = effective_excludes.clone();
-                async move { rsync.sync_to_remote(&project_root, &dest, &excludes).await }
-            })
-            .await?;
-            return Ok(SyncResult {
-                bytes_transferred: result.bytes_transferred,
-                files_transferred: result.files_transferred,
-                duration_ms: result.duration_ms,
-            });
-        }
-
-        info!(
-            "Syncing {} -> {} on {}",
-            self.project_root.display(),
-            remote_path,
-            worker.id
-        );
-
-        debug!("Effective exclude patterns: {:?}", effective_excludes);
-
-        let start = std::time::Instant::now();
-
-        // Execute rsync with retry logic for transient errors
-        let retry_config = self.effective_rsync_retry_config();
-        let output = execute_rsync_with_retry(&retry_config, "sync_to_remote", || {
-            self.build_sync_command(
-                worker,
-                &destination,
-                &escaped_remote_path,
-                &effective_excludes,
-            )
-        })
-        .await?;
-
-        let duration = start.elapsed();
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-
-        if !output.status.success() {
-            // Check if the failure is retryable (it wasn't if we got here)
-            if is_retryable_transport_error(&anyhow::anyhow!("{}", stderr)) {
-                warn!(
-                    "rsync failed with retryable error (retries exhausted): {}",
-                    stderr
-                );
-            } else {
-                warn!("rsync failed: {}", stderr);
-            }
-            return Err(TransferError::SyncFailed {
-                reason: "rsync failed".to_string(),
-                exit_code: output.status.code(),
-                stderr: stderr.to_string(),
-            }
-            .into());
-        }
-
-        // rsync can exit 0 even when interrupted mid-file in edge cases. Treat a
-        // partial-transfer indicator on a "successful" sync as a failure rather
-        // than warning and returning Ok: otherwise the hook reports success, the
-        // remote source tree is incomplete, and the remote build compiles
-        // stale/partial sources, returning a trusted-but-wrong result.
-        if let Some(indicator) = detect_partial_transfer(&stderr) {
-            warn!(
-                "rsync exited 0 but reported a partial transfer (matched '{}'): {}",
-                indicator,
-                stderr.lines().next().unwrap_or(&stderr)
-            );
-            return Err(TransferError::SyncFailed {
-                reason: format!("partial transfer despite exit 0 ({indicator})"),
-                exit_code: output.status.code(),
-                stderr: stderr.to_string(),
+        let no_progress = None::<fn(&str)>;
+        match self.transfer_config.backend {
+            TransferBackend::Rsync => RsyncBackend.sync_to_remote(self, worker, no_progress).await,
+            TransferBackend::Rclone => {
+                RcloneBackend
+                    .sync_to_remote(self, worker, no_progress)
+                    .await
             }
-            .into());
         }
-
-        info!("Sync completed in {}ms", duration.as_millis());
-
-        Ok(SyncResult {
-            bytes_transferred: parse_rsync_bytes(&stdout),
-            files_transferred: parse_rsync_files(&stdout),
-            duration_ms: duration.as_millis() as u64,
-        })
     }
 
     /// Synchronize local project to remote worker with streaming output.
@@ -1604,93 +1664,23 @@ fi",
     pub async fn sync_to_remote_streaming<F>(
         &self,
         worker: &WorkerConfig,
-        mut on_line: F,
+        on_line: F,
     ) -> Result<SyncResult>
     where
         F: FnMut(&str),
     {
-        let remote_path = self.remote_path();
-        let escaped_remote_path = escape(Cow::from(&remote_path));
-        let destination = format!("{}@{}:{}", worker.user, worker.host, escaped_remote_path);
-
-        // Get effective excludes (config defaults + .rchignore)
-        let effective_excludes = self.get_effective_excludes();
-
-        if use_mock_transport(worker) {
-            let rsync = MockRsync::new(MockRsyncConfig::from_env());
-            let result = rsync
-                .sync_to_remote(
-                    &self.project_root.display().to_string(),
-                    &destination,
-                    &effective_excludes,
-                )
-                .await?;
-            return Ok(SyncResult {
-                bytes_transferred: result.bytes_transferred,
-                files_transferred: result.files_transferred,
-                duration_ms: result.duration_ms,
-            });
-        }
-
-        info!(
-            "Syncing {} -> {} on {} (streaming)",
-            self.project_root.display(),
-            remote_path,
-            worker.id
-        );
-
-        debug!("Effective exclude patterns: {:?}", effective_excludes);
-
-        // Rebuilt per retry attempt: rsync consumes its `Command`, and a
-        // transient SSH/rsync drop on this streaming path must reconnect from a
-        // fresh command rather than fail the whole transfer with zero retries.
-        let build_cmd = || {
-            self.build_sync_streaming_command(
-                worker,
-                &destination,
-                &escaped_remote_path,
-                &effective_excludes,
-            )
-        };
-
-        debug!(
-            "Running (streaming): rsync {:?}",
-            build_cmd().as_std().get_args().collect::<Vec<_>>()
-        );
-
-        let retry_config = self.effective_rsync_retry_config();
-        let (output, duration_ms) = run_command_streaming_with_retry(
-            &retry_config,
-            "sync_to_remote_streaming",
-            build_cmd,
-            |line| {
-                on_line(line);
-            },
-        )
-        .await?;
-
-        // Same exit-0-but-incomplete guard as the non-streaming sync_to_remote:
-        // run_command_streaming returns the combined stdout+stderr, so scan it for
-        // partial-transfer indicators and fail rather than report a success that
-        // would feed the remote build stale/partial sources.
-        if let Some(indicator) = detect_partial_transfer(&output) {
-            warn!(
-                "streaming rsync exited 0 but reported a partial transfer (matched '{}')",
-                indicator
-            );
-            return Err(TransferError::SyncFailed {
-                reason: format!("partial transfer despite exit 0 ({indicator})"),
-                exit_code: None,
-                stderr: output,
+        match self.transfer_config.backend {
+            TransferBackend::Rsync => {
+                RsyncBackend
+                    .sync_to_remote(self, worker, Some(on_line))
+                    .await
+            }
+            TransferBackend::Rclone => {
+                RcloneBackend
+                    .sync_to_remote(self, worker, Some(on_line))
+                    .await
             }
-            .into());
         }
-
-        Ok(SyncResult {
-            bytes_transferred: parse_rsync_bytes(&output),
-            files_transferred: parse_rsync_files(&output),
-            duration_ms,
-        })
     }
 
     /// Execute a compilation command on the remote worker.
@@ -1763,7 +1753,6 @@ fi",
         G: FnMut(&str),
     {
         let destination = format!("{}@{}", worker.user, worker.host);
-        let identity_file = shellexpand::tilde(&worker.identity_file);
 
         let mut cmd = Command::new("ssh");
         cmd.arg("-o").arg("BatchMode=yes");
@@ -1772,7 +1761,9 @@ fi",
             "ConnectTimeout={}",
             self.ssh_options.connect_timeout.as_secs().max(1)
         ));
-        cmd.arg("-i").arg(identity_file.as_ref());
+        if let Some(identity) = worker.ssh_identity() {
+            cmd.arg("-i").arg(identity);
+        }
 
         if let Some(interval) = self.ssh_options.server_alive_interval {
             let secs = interval.as_secs();
@@ -1931,6 +1922,12 @@ fi",
         G: FnMut(&str),
     {
         let wrapped_command = self.build_remote_command(command, toolchain);
+        let wrapped_command = match self.telemetry_stream_secs {
+            Some(interval_secs) => {
+                wrap_command_with_telemetry_stream(&wrapped_command, &worker.id, interval_secs)
+            }
+            None => wrapped_command,
+        };
 
         if use_mock_transport(worker) {
             let mut client = MockSshClient::new(worker.clone(), MockConfig::from_env());
@@ -1970,9 +1967,7 @@ fi",
         // Force C locale for consistent output parsing
         cmd.env("LC_ALL", "C");
 
-        let identity_file = shellexpand::tilde(&worker.identity_file);
-        let escaped_identity = escape(Cow::from(identity_file.as_ref()));
-        let ssh_command = self.build_rsync_ssh_command(escaped_identity.as_ref());
+        let ssh_command = self.build_rsync_ssh_command(worker);
 
         // Use --safe-links to prevent symlink traversal attacks from malicious workers.
         // --stats is required so parse_rsync_bytes/parse_rsync_files can read transfer
@@ -2003,50 +1998,10 @@ fi",
         // empty parents of excluded files (side effect of --include="*/")
         cmd.arg("--prune-empty-dirs");
 
-        // Split caller-supplied EXCLUDE rules (`- <pat>`) from INCLUDE patterns.
-        // Excludes (e.g. cargo cache trees in a custom-target sync) must be emitted
-        // first so rsync's first-match-wins ordering keeps them from transferring;
-        // only the include patterns feed the root/source-integrity helpers below.
-        let (caller_excludes, include_patterns) = partition_artifact_filters(artifact_patterns);
-
-        // Apply retrieval-safe excludes before the directory include so rsync
-        // never descends into known junk trees like `.beads/recovery_*` on the
-        // worker, while still allowing traversal into declared artifact roots.
-        for pattern in self.get_retrieval_excludes(&include_patterns) {
-            cmd.arg("--exclude").arg(pattern);
-        }
-
-        // Caller-supplied excludes (cargo `incremental/`, `.fingerprint/`,
-        // `build/`, `*.d`, …) — emitted before the includes so a broad output
-        // include like `debug/**` cannot drag the cache trees back.
-        for pattern in &caller_excludes {
-            cmd.arg("--exclude").arg(pattern);
-        }
-
-        // Source-integrity guard (RCH bug d7xc3): explicitly exclude every
-        // top-level entry in the local project root that ISN'T an allowed
-        // artifact root. Defends against unanchored pattern matching, malformed
-        // includes, or a stale remote tree pulling source files into the local
-        // checkout. The excludes are emitted BEFORE the directory include so
-        // rsync evaluates them first and refuses to descend into source dirs.
-        let allowed_roots = allowed_artifact_roots(&include_patterns);
-        for exclude in self.local_source_roots_to_exclude(&allowed_roots, &include_patterns) {
-            cmd.arg("--exclude").arg(exclude);
-        }
-
-        // Essential: Include all directories so rsync can traverse to match patterns.
-        // Without this, the final --exclude "*" prevents rsync from entering directories
-        // like "target/" to check for matches.
-        cmd.arg("--include").arg("*/");
-
-        // Include only specified artifact patterns, anchored at the rsync
-        // transfer root via `anchor_retrieval_pattern` (RCH bug d7xc3) so
-        // a pattern like `target/debug/**` cannot match `<root>/anything/
-        // target/debug/...` at arbitrary depth.
-        for pattern in &include_patterns {
-            cmd.arg("--include").arg(anchor_retrieval_pattern(pattern));
+        if self.transfer_config.preserve_newer_local {
+            cmd.arg("--update");
         }
-        cmd.arg("--exclude").arg("*"); // Exclude everything else
+        cmd.args(self.artifact_filter_args(artifact_patterns));
 
         let source = format!("{}@{}:{}/", worker.user, worker.host, escaped_remote_path);
         cmd.arg(&source)
@@ -2067,10 +2022,7 @@ fi",
         // Force C locale for consistent output parsing
         cmd.env("LC_ALL", "C");
 
-        let identity_file = shellexpand::tilde(&worker.identity_file);
-        let escaped_identity = escape(Cow::from(identity_file.as_ref()));
-
-        let ssh_command = self.build_rsync_ssh_command(escaped_identity.as_ref());
+        let ssh_command = self.build_rsync_ssh_command(worker);
 
         cmd.arg("-az");
         add_portable_rsync_archive_args(&mut cmd);
@@ -2097,38 +2049,10 @@ fi",
         // Prune empty directories to prevent cluttering local project
         cmd.arg("--prune-empty-dirs");
 
-        // Split caller-supplied EXCLUDE rules (`- <pat>`) from INCLUDE patterns;
-        // see build_retrieve_command for the rationale (first-match-wins ordering).
-        let (caller_excludes, include_patterns) = partition_artifact_filters(artifact_patterns);
-
-        // Reuse the retrieval-safe excludes so streaming downloads skip stale
-        // worker-local junk trees without excluding legitimate artifact roots.
-        for pattern in self.get_retrieval_excludes(&include_patterns) {
-            cmd.arg("--exclude").arg(pattern);
-        }
-
-        // Caller-supplied excludes (cargo cache trees, `*.d`, …) emitted before
-        // the includes so a broad output include cannot drag them back.
-        for pattern in &caller_excludes {
-            cmd.arg("--exclude").arg(pattern);
-        }
-
-        // Source-integrity guard (RCH bug d7xc3): see build_retrieve_command.
-        // Same belt-and-suspenders defense applied to the streaming variant.
-        let allowed_roots = allowed_artifact_roots(&include_patterns);
-        for exclude in self.local_source_roots_to_exclude(&allowed_roots, &include_patterns) {
-            cmd.arg("--exclude").arg(exclude);
-        }
-
-        // Essential: Include all directories so rsync can traverse to match patterns.
-        cmd.arg("--include").arg("*/");
-
-        // Artifact include patterns are anchored (RCH bug d7xc3) so they can
-        // only match at the rsync transfer root.
-        for pattern in &include_patterns {
-            cmd.arg("--include").arg(anchor_retrieval_pattern(pattern));
+        if self.transfer_config.preserve_newer_local {
+            cmd.arg("--update");
         }
-        cmd.arg("--exclude").arg("*");
+        cmd.args(self.artifact_filter_args(artifact_patterns));
 
         let source = format!("{}@{}:{}/", worker.user, worker.host, escaped_remote_path);
         cmd.arg(&source)
@@ -2138,25 +2062,91 @@ fi",
         cmd
     }
 
-    fn build_rsync_ssh_command(&self, escaped_identity: &str) -> String {
-        let mut command = format!(
-            "ssh -i {} -o StrictHostKeyChecking=accept-new -o BatchMode=yes",
-            escaped_identity
-        );
+    /// rsync filter rules for artifact retrieval, shared by the rsync
+    /// builders and the archive path (`transfer.artifact_archive`), which
+    /// applies them on the worker.
+    fn artifact_filter_args(&self, artifact_patterns: &[String]) -> Vec<String> {
+        let mut args = Vec::new();
+        let mut exclude = |pattern: String| {
+            args.push("--exclude".to_string());
+            args.push(pattern);
+        };
 
-        #[cfg(unix)]
-        {
-            if let Some(interval) = self.ssh_options.server_alive_interval {
-                let secs = interval.as_secs();
-                if secs > 0 {
-                    command.push_str(&format!(" -o ServerAliveInterval={secs}"));
-                }
-            }
+        // Split caller-supplied EXCLUDE rules (`- <pat>`) from INCLUDE patterns.
+        // Excludes (e.g. cargo cache trees in a custom-target sync) must be emitted
+        // first so rsync's first-match-wins ordering keeps them from transferring;
+        // only the include patterns feed the root/source-integrity helpers below.
+        let (caller_excludes, include_patterns) = partition_artifact_filters(artifact_patterns);
 
-            if self.ssh_options.control_master
-                && let Some(idle) = self.ssh_options.control_persist_idle
-            {
-                let control_dir = self.rsync_control_dir();
+        // Apply retrieval-safe excludes before the directory include so rsync
+        // never descends into known junk trees like `.beads/recovery_*` on the
+        // worker, while still allowing traversal into declared artifact roots.
+        for pattern in self.get_retrieval_excludes(&include_patterns) {
+            exclude(pattern);
+        }
+
+        // Caller-supplied excludes (cargo `incremental/`, `.fingerprint/`,
+        // `build/`, `*.d`, …) — emitted before the includes so a broad output
+        // include like `debug/**` cannot drag the cache trees back.
+        for pattern in caller_excludes {
+            exclude(pattern);
+        }
+
+        // Local edits made while the build ran: the user's
+        // `transfer.artifact_deny_patterns`, also ahead of the includes.
+        for pattern in &self.transfer_config.artifact_deny_patterns {
+            exclude(pattern.clone());
+        }
+
+        // Source-integrity guard (RCH bug d7xc3): explicitly exclude every
+        // top-level entry in the local project root that ISN'T an allowed
+        // artifact root. Defends against unanchored pattern matching, malformed
+        // includes, or a stale remote tree pulling source files into the local
+        // checkout. The excludes are emitted BEFORE the directory include so
+        // rsync evaluates them first and refuses to descend into source dirs.
+        let allowed_roots = allowed_artifact_roots(&include_patterns);
+        for pattern in self.local_source_roots_to_exclude(&allowed_roots, &include_patterns) {
+            exclude(pattern);
+        }
+
+        // Essential: Include all directories so rsync can traverse to match patterns.
+        // Without this, the final --exclude "*" prevents rsync from entering directories
+        // like "target/" to check for matches.
+        args.push("--include".to_string());
+        args.push("*/".to_string());
+
+        // Include only specified artifact patterns, anchored at the rsync
+        // transfer root via `anchor_retrieval_pattern` (RCH bug d7xc3) so
+        // a pattern like `target/debug/**` cannot match `<root>/anything/
+        // target/debug/...` at arbitrary depth.
+        for pattern in &include_patterns {
+            args.push("--include".to_string());
+            args.push(anchor_retrieval_pattern(pattern));
+        }
+        args.push("--exclude".to_string());
+        args.push("*".to_string()); // Exclude everything else
+        args
+    }
+
+    fn build_rsync_ssh_command(&self, worker: &WorkerConfig) -> String {
+        let mut command = format!(
+            "{} -o StrictHostKeyChecking=accept-new -o BatchMode=yes",
+            worker.rsync_ssh_program()
+        );
+
+        #[cfg(unix)]
+        {
+            if let Some(interval) = self.ssh_options.server_alive_interval {
+                let secs = interval.as_secs();
+                if secs > 0 {
+                    command.push_str(&format!(" -o ServerAliveInterval={secs}"));
+                }
+            }
+
+            if self.ssh_options.control_master
+                && let Some(idle) = self.ssh_options.control_persist_idle
+            {
+                let control_dir = self.rsync_control_dir();
                 if let Err(e) = std::fs::create_dir_all(&control_dir) {
                     warn!(
                         "Failed to create rsync SSH control dir {:?}: {}",
@@ -2217,99 +2207,19 @@ fi",
         worker: &WorkerConfig,
         artifact_patterns: &[String],
     ) -> Result<SyncResult> {
-        let remote_path = self.remote_path();
-        let escaped_remote_path = escape(Cow::from(&remote_path));
-
-        if use_mock_transport(worker) {
-            // Mock path also uses retry logic for consistent behavior
-            // Create MockRsync ONCE and share via Arc so failure counters persist across retries
-            let rsync = std::sync::Arc::new(MockRsync::new(MockRsyncConfig::from_env()));
-            let source = format!("{}@{}:{}/", worker.user, worker.host, escaped_remote_path);
-            let project_root_str = self.project_root.display().to_string();
-            let patterns = artifact_patterns.to_vec();
-            let retry_config = self.transfer_config.retry.clone();
-            let result = retry_with_backoff(&retry_config, "mock_retrieve_artifacts", || {
-                let rsync = rsync.clone();
-                let src = source.clone();
-                let dest = project_root_str.clone();
-                let pats = patterns.clone();
-                async move { rsync.retrieve_artifacts(&src, &dest, &pats).await }
-            })
-            .await?;
-            return Ok(SyncResult {
-                bytes_transferred: result.bytes_transferred,
-                files_transferred: result.files_transferred,
-                duration_ms: result.duration_ms,
-            });
-        }
-
-        info!("Retrieving artifacts from {} on {}", remote_path, worker.id);
-
-        let start = std::time::Instant::now();
-
-        // Execute rsync with retry logic for transient errors
-        let retry_config = self.effective_rsync_retry_config();
-        let output = execute_rsync_with_retry(&retry_config, "retrieve_artifacts", || {
-            self.build_retrieve_command(worker, &escaped_remote_path, artifact_patterns)
-        })
-        .await?;
-
-        let duration = start.elapsed();
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-
-        if !output.status.success() {
-            warn!("Artifact retrieval failed: {}", stderr);
-            return Err(TransferError::SyncFailed {
-                reason: "rsync artifact retrieval failed".to_string(),
-                exit_code: output.status.code(),
-                stderr: stderr.clone(),
+        let no_progress = None::<fn(&str)>;
+        match self.transfer_config.backend {
+            TransferBackend::Rsync => {
+                RsyncBackend
+                    .retrieve_artifacts(self, worker, artifact_patterns, no_progress)
+                    .await
             }
-            .into());
-        }
-
-        // An exit-0 partial download leaves the local artifact tree incomplete;
-        // fail rather than report success (see sync_to_remote).
-        if let Some(indicator) = detect_partial_transfer(&stderr) {
-            warn!(
-                "rsync exited 0 but reported a partial artifact retrieval (matched '{}'): {}",
-                indicator,
-                stderr.lines().next().unwrap_or(&stderr)
-            );
-            return Err(TransferError::SyncFailed {
-                reason: format!("partial artifact retrieval despite exit 0 ({indicator})"),
-                exit_code: output.status.code(),
-                stderr: stderr.clone(),
+            TransferBackend::Rclone => {
+                RcloneBackend
+                    .retrieve_artifacts(self, worker, artifact_patterns, no_progress)
+                    .await
             }
-            .into());
         }
-
-        let bytes_transferred = parse_rsync_bytes(&stdout);
-        let files_transferred = parse_rsync_files(&stdout);
-
-        // Warn if no artifacts were retrieved - this may indicate a build failure
-        // or misconfigured artifact patterns. We don't fail here because some
-        // commands (e.g., cargo check) don't produce artifacts.
-        if files_transferred == 0 && bytes_transferred == 0 {
-            warn!(
-                "No artifacts retrieved from {} - build may have failed or artifact patterns may be misconfigured",
-                worker.id
-            );
-            debug!("Artifact patterns used: {:?}", artifact_patterns);
-        }
-
-        info!(
-            "Artifacts retrieved in {}ms ({} files, {} bytes)",
-            duration.as_millis(),
-            files_transferred,
-            bytes_transferred
-        );
-
-        Ok(SyncResult {
-            bytes_transferred,
-            files_transferred,
-            duration_ms: duration.as_millis() as u64,
-        })
     }
 
     /// Retrieve build artifacts with streaming progress output.
@@ -2317,78 +2227,23 @@ fi",
         &self,
         worker: &WorkerConfig,
         artifact_patterns: &[String],
-        mut on_line: F,
+        on_line: F,
     ) -> Result<SyncResult>
     where
         F: FnMut(&str),
     {
-        let remote_path = self.remote_path();
-        let escaped_remote_path = escape(Cow::from(&remote_path));
-
-        if use_mock_transport(worker) {
-            let rsync = MockRsync::new(MockRsyncConfig::from_env());
-            let result = rsync
-                .retrieve_artifacts(
-                    &format!("{}@{}:{}/", worker.user, worker.host, escaped_remote_path),
-                    &self.project_root.display().to_string(),
-                    artifact_patterns,
-                )
-                .await?;
-            return Ok(SyncResult {
-                bytes_transferred: result.bytes_transferred,
-                files_transferred: result.files_transferred,
-                duration_ms: result.duration_ms,
-            });
-        }
-
-        info!(
-            "Retrieving artifacts from {} on {} (streaming)",
-            remote_path, worker.id
-        );
-
-        // Rebuilt per retry attempt (see `sync_to_remote_streaming`): a transient
-        // transport drop while pulling artifacts must reconnect and retry instead
-        // of failing the build's artifact return outright.
-        let build_cmd = || {
-            self.build_retrieve_streaming_command(worker, &escaped_remote_path, artifact_patterns)
-        };
-
-        debug!(
-            "Running artifact retrieval (streaming): rsync {:?}",
-            build_cmd().as_std().get_args().collect::<Vec<_>>()
-        );
-
-        let retry_config = self.effective_rsync_retry_config();
-        let (output, duration_ms) = run_command_streaming_with_retry(
-            &retry_config,
-            "retrieve_artifacts_streaming",
-            build_cmd,
-            |line| {
-                on_line(line);
-            },
-        )
-        .await?;
-
-        // An exit-0 partial download leaves the local artifact tree incomplete;
-        // fail rather than report success (see retrieve_artifacts).
-        if let Some(indicator) = detect_partial_transfer(&output) {
-            warn!(
-                "streaming rsync exited 0 but reported a partial artifact retrieval (matched '{}')",
-                indicator
-            );
-            return Err(TransferError::SyncFailed {
-                reason: format!("partial artifact retrieval despite exit 0 ({indicator})"),
-                exit_code: None,
-                stderr: output,
+        match self.transfer_config.backend {
+            TransferBackend::Rsync => {
+                RsyncBackend
+                    .retrieve_artifacts(self, worker, artifact_patterns, Some(on_line))
+                    .await
+            }
+            TransferBackend::Rclone => {
+                RcloneBackend
+                    .retrieve_artifacts(self, worker, artifact_patterns, Some(on_line))
+                    .await
             }
-            .into());
         }
-
-        Ok(SyncResult {
-            bytes_transferred: parse_rsync_bytes(&output),
-            files_transferred: parse_rsync_files(&output),
-            duration_ms,
-        })
     }
 
     /// Clean up remote project directory.
@@ -2435,6 +2290,55 @@ fi",
         }
     }
 
+    /// Pin the remote project dir for post-mortem debugging.
+    ///
+    /// Writes the [`rch_common::remote_keep::KEEP_MARKER`] so the daemon's cache
+    /// cleanup leaves the dir alone for `keep_hours`. Returns the
+    /// `user@host:path` location on success; failures are only logged.
+    pub async fn pin_remote(&self, worker: &WorkerConfig, keep_hours: u64) -> Option<String> {
+        let remote_path = self.remote_path();
+        let location = format!("{}@{}:{}", worker.user, worker.host, remote_path);
+        let escaped_remote_path = escape(Cow::from(&remote_path));
+        let expires_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+            .saturating_add(keep_hours.max(1).saturating_mul(3600));
+        let command = rch_common::remote_keep::pin_command(&escaped_remote_path, expires_at);
+
+        if use_mock_transport(worker) {
+            debug!("Mock pin of {} on {}", remote_path, worker.id);
+            return Some(location);
+        }
+
+        let mut client = SshClient::new(worker.clone(), self.ssh_options.clone());
+        if let Err(e) = client.connect().await {
+            warn!("Failed to pin {} on {}: {}", remote_path, worker.id, e);
+            return None;
+        }
+        let result = client.execute(&command).await;
+        if let Err(e) = client.disconnect().await {
+            debug!("Failed to disconnect SSH client after pin: {}", e);
+        }
+        match result {
+            Ok(result) if result.success() => Some(location),
+            Ok(result) => {
+                warn!(
+                    "Failed to pin {} on {}: exit {} ({})",
+                    remote_path,
+                    worker.id,
+                    result.exit_code,
+                    result.stderr.trim()
+                );
+                None
+            }
+            Err(e) => {
+                warn!("Failed to pin {} on {}: {}", remote_path, worker.id, e);
+                None
+            }
+        }
+    }
+
     /// Best-effort reaping of *stale* sibling per-job target dirs for this
     /// project on the worker.
     ///
@@ -2565,7 +2469,7 @@ fn is_safe_reap_token(s: &str) -> bool {
 }
 
 /// Result of a file synchronization operation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct SyncResult {
     /// Bytes transferred.
     pub bytes_transferred: u64,
@@ -2573,6 +2477,20 @@ pub struct SyncResult {
     pub files_transferred: u32,
     /// Duration in milliseconds.
     pub duration_ms: u64,
+    /// File data rsync had to send, before compression (`Literal data`).
+    /// Zero when unknown (mock transport, rsync without `--stats`).
+    pub raw_bytes: u64,
+    /// Bytes that crossed the wire for the transfer direction, after
+    /// compression (`Total bytes sent` on upload, `received` on retrieval).
+    pub compressed_bytes: u64,
+}
+
+impl SyncResult {
+    /// `raw_bytes / compressed_bytes`, or `None` when either is unknown.
+    pub fn compression_ratio(&self) -> Option<f64> {
+        (self.raw_bytes > 0 && self.compressed_bytes > 0)
+            .then(|| self.raw_bytes as f64 / self.compressed_bytes as f64)
+    }
 }
 
 /// Estimate of transfer size from rsync dry-run (bd-3hho).
@@ -2589,6 +2507,16 @@ pub struct TransferEstimate {
     pub estimation_ms: u64,
 }
 
+/// Estimated seconds to sync `bytes` over a measured link: the payload at the
+/// measured upload bandwidth plus one round trip of connection setup.
+pub fn estimate_sync_seconds(bytes: u64, bandwidth_bps: u64, rtt_ms: Option<u64>) -> f64 {
+    let rtt_secs = rtt_ms.unwrap_or(0) as f64 / 1000.0;
+    if bandwidth_bps == 0 {
+        return rtt_secs;
+    }
+    bytes as f64 / bandwidth_bps as f64 + rtt_secs
+}
+
 /// Parse bytes transferred from rsync output.
 fn parse_rsync_bytes(output: &str) -> u64 {
     // rsync output contains "sent X bytes  received Y bytes"
@@ -2610,6 +2538,23 @@ fn parse_rsync_bytes(output: &str) -> u64 {
     0
 }
 
+/// Parse a `--stats` byte counter such as `Literal data: 1,234 bytes`.
+/// `label` includes the trailing colon. Returns 0 when the line is missing.
+fn parse_rsync_stat_bytes(output: &str, label: &str) -> u64 {
+    output
+        .lines()
+        .find_map(|line| {
+            line.trim_start()
+                .strip_prefix(label)?
+                .split_whitespace()
+                .next()?
+                .replace(',', "")
+                .parse()
+                .ok()
+        })
+        .unwrap_or(0)
+}
+
 /// Parse files transferred from rsync output.
 fn parse_rsync_files(output: &str) -> u32 {
     let mut total_files = None;
@@ -2805,6 +2750,67 @@ fn detect_partial_transfer(output: &str) -> Option<&'static str> {
         .find(|ind| lower.contains(ind))
 }
 
+/// rsync exit codes for a dropped or stalled connection (socket I/O, stream
+/// I/O, timeouts, ssh failure): worth another attempt.
+const RSYNC_TRANSIENT_EXIT_CODES: [i32; 5] = [10, 12, 30, 35, 255];
+
+/// Decide whether a failed artifact retrieval should be attempted again.
+///
+/// Exit 23/24 mean rsync transferred only part of the tree. That is retried
+/// unless every rsync complaint is a missing path, which is "nothing to
+/// transfer" (no artifacts at that pattern) rather than an interrupted
+/// download, or the failure is local (permissions, full disk) and would
+/// repeat. A partial transfer reported despite exit 0 is retried as well.
+/// Anything else falls back to the transport classifiers.
+pub fn artifact_retrieval_is_retryable(err: &anyhow::Error) -> bool {
+    let Some(TransferError::SyncFailed {
+        reason,
+        exit_code,
+        stderr,
+    }) = err.downcast_ref::<TransferError>()
+    else {
+        return is_retryable_transport_error(err);
+    };
+    match exit_code {
+        Some(code) if RSYNC_TRANSIENT_EXIT_CODES.contains(code) => true,
+        Some(23 | 24) => !rsync_reported_nothing_to_transfer(stderr) && !local_failure(stderr),
+        None if reason.contains("partial artifact retrieval") => true,
+        _ => is_retryable_transport_error_text(stderr),
+    }
+}
+
+/// True when rsync's only complaints are paths that do not exist on the
+/// sender (`link_stat`/`change_dir ... No such file or directory`).
+fn rsync_reported_nothing_to_transfer(stderr: &str) -> bool {
+    let mut complaints = stderr
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("rsync:"))
+        .peekable();
+    complaints.peek().is_some()
+        && complaints.all(|line| line.to_lowercase().contains("no such file or directory"))
+}
+
+/// Failures on the receiving side that another attempt would hit again.
+fn local_failure(stderr: &str) -> bool {
+    let lower = stderr.to_lowercase();
+    lower.contains("permission denied")
+        || lower.contains("no space left on device")
+        || lower.contains("read-only file system")
+}
+
+/// Best-effort rsync exit code for a failed transfer, for diagnostics.
+pub fn rsync_exit_code(err: &anyhow::Error) -> Option<i32> {
+    if let Some(TransferError::SyncFailed { exit_code, .. }) = err.downcast_ref::<TransferError>() {
+        return *exit_code;
+    }
+    // `execute_rsync_with_retry` surfaces exhausted transport retries as
+    // "rsync transport error (exit N): ...".
+    let message = err.to_string();
+    let (_, rest) = message.split_once("(exit ")?;
+    rest.split(')').next()?.trim().parse().ok()
+}
+
 /// Decide whether a [`run_command_streaming`] failure is a transient transport
 /// error worth retrying.
 ///
@@ -3141,6 +3147,21 @@ pub fn default_bun_artifact_patterns() -> Vec<String> {
     ]
 }
 
+/// Default artifact patterns for `trunk build`.
+///
+/// Trunk writes the bundled app (HTML, JS glue, `.wasm`, assets) to `dist/`.
+pub fn default_trunk_artifact_patterns() -> Vec<String> {
+    vec!["dist/**".to_string()]
+}
+
+/// Default artifact patterns for `wasm-pack build`.
+///
+/// wasm-pack writes the npm package (`.wasm`, JS/TS bindings, `package.json`)
+/// to `pkg/`.
+pub fn default_wasm_pack_artifact_patterns() -> Vec<String> {
+    vec!["pkg/**".to_string()]
+}
+
 /// Default artifact patterns for C/C++ projects.
 pub fn default_c_cpp_artifact_patterns() -> Vec<String> {
     vec![
@@ -3409,6 +3430,192 @@ mod tests {
         );
     }
 
+    /// The sampler interleaves marker lines with build output, stops when the
+    /// command finishes (even via its own `exit`), and keeps its exit status.
+    #[cfg(unix)]
+    #[test]
+    fn test_telemetry_stream_wrapper_samples_and_preserves_exit_status() {
+        let _guard = test_guard!();
+        use std::os::unix::fs::PermissionsExt;
+        use std::process::Command;
+        use tempfile::tempdir;
+
+        let bin = tempdir().expect("create bin dir");
+        let fake = bin.path().join("rch-telemetry");
+        std::fs::write(&fake, "#!/bin/sh\necho '{\"sample\":1}'\n").expect("write fake");
+        std::fs::set_permissions(&fake, std::fs::Permissions::from_mode(0o755))
+            .expect("chmod fake");
+
+        let script = wrap_command_with_telemetry_stream(
+            "sleep 2; echo build-done; exit 3",
+            &WorkerId::new("w1"),
+            1,
+        );
+        let path = format!(
+            "{}:{}",
+            bin.path().display(),
+            std::env::var("PATH").unwrap()
+        );
+        let output = Command::new("sh")
+            .arg("-c")
+            .arg(&script)
+            .env("PATH", path)
+            .output()
+            .expect("run wrapped command");
+
+        assert_eq!(output.status.code(), Some(3));
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("build-done"), "stdout: {stdout}");
+        let marker = format!(
+            "{} {{\"sample\":1}}",
+            rch_telemetry::protocol::PIGGYBACK_STREAM_MARKER
+        );
+        assert!(
+            stdout.lines().any(|line| line == marker),
+            "expected a streamed sample line: {stdout}"
+        );
+    }
+
+    /// The container wrapper is one simple command, so it still works behind
+    /// the `timeout ... env` prefix, and hands the full command to the image.
+    #[cfg(unix)]
+    #[test]
+    fn test_container_wrapper_runs_command_in_mounted_project() {
+        let _guard = test_guard!();
+        use std::os::unix::fs::PermissionsExt;
+        use std::process::Command;
+        use tempfile::tempdir;
+
+        let bin = tempdir().expect("create bin dir");
+        let fake = bin.path().join("docker");
+        std::fs::write(&fake, "#!/bin/sh\nprintf '%s|' \"$@\"\n").expect("write fake");
+        std::fs::set_permissions(&fake, std::fs::Permissions::from_mode(0o755))
+            .expect("chmod fake");
+
+        let wrapped = wrap_command_in_container(
+            "FOO='a b' cargo build",
+            "rust:1.85",
+            "/data/projects/my proj",
+        );
+        let path = format!(
+            "{}:{}",
+            bin.path().display(),
+            std::env::var("PATH").unwrap()
+        );
+        let output = Command::new("sh")
+            .arg("-c")
+            .arg(format!("env {wrapped}"))
+            .env("PATH", path)
+            .output()
+            .expect("run wrapped command");
+
+        assert!(output.status.success(), "{output:?}");
+        let args = String::from_utf8_lossy(&output.stdout);
+        assert!(args.starts_with("run|--rm|--user|"), "{args}");
+        assert!(
+            args.ends_with(
+                "|-v|/data/projects/my proj:/data/projects/my proj|-w|/data/projects/my proj\
+                 |rust:1.85|sh|-lc|FOO='a b' cargo build|"
+            ),
+            "{args}"
+        );
+    }
+
+    #[test]
+    fn test_build_remote_command_wraps_in_container_image() {
+        let _guard = test_guard!();
+        let config = TransferConfig {
+            container_image: Some("ghcr.io/org/builder:1".to_string()),
+            ..Default::default()
+        };
+        let pipeline = TransferPipeline::new(
+            PathBuf::from("/data/projects/demo"),
+            "demo".to_string(),
+            "abc123".to_string(),
+            config,
+        );
+
+        let command = pipeline.build_remote_command("cargo build", None);
+        assert!(
+            command.contains("rch-container 'ghcr.io/org/builder:1' /tmp/rch/demo/abc123"),
+            "{command}"
+        );
+        assert!(command.contains("cd /tmp/rch/demo/abc123"));
+
+        let plain = TransferPipeline::new(
+            PathBuf::from("/data/projects/demo"),
+            "demo".to_string(),
+            "abc123".to_string(),
+            TransferConfig::default(),
+        )
+        .build_remote_command("cargo build", None);
+        assert!(!plain.contains("rch-container"));
+    }
+
+    #[test]
+    fn test_build_remote_command_checks_allowlist_against_the_build() {
+        let _guard = test_guard!();
+        let config = TransferConfig {
+            container_image: Some("ghcr.io/org/builder:1".to_string()),
+            ..Default::default()
+        };
+        let pipeline = TransferPipeline::new(
+            PathBuf::from("/data/projects/demo"),
+            "demo".to_string(),
+            "abc123".to_string(),
+            config,
+        )
+        .with_build_id(Some(7))
+        .with_remote_priority(Some(10), None);
+
+        // Daemon-dispatched builds skip `rch-wkr execute`, so the check runs
+        // up front, on the build inside the container and nice wrappers.
+        let command = pipeline.build_remote_command("cargo build", None);
+        let check = "rch-wkr execute --workdir . --check-only --command 'cargo build'; } && ";
+        let at = command.find(check).expect("allowlist check");
+        assert!(at < command.find("nice -n 10").expect("nice"), "{command}");
+        assert!(
+            at < command.find("rch-container").expect("container"),
+            "{command}"
+        );
+    }
+
+    #[test]
+    fn test_allowlist_check_prefix_refuses_only_with_an_allowlist() {
+        let _guard = test_guard!();
+        use std::os::unix::fs::PermissionsExt;
+
+        let bin = tempfile::tempdir().expect("tempdir");
+        let fake = bin.path().join("rch-wkr");
+        std::fs::write(
+            &fake,
+            "#!/bin/sh\n[ \"$6\" = 'cargo build' ] && exit 0; exit 123\n",
+        )
+        .expect("write fake rch-wkr");
+        std::fs::set_permissions(&fake, std::fs::Permissions::from_mode(0o755))
+            .expect("chmod fake rch-wkr");
+
+        let run = |build: &str, allowlist: Option<&str>| {
+            let mut cmd = std::process::Command::new("sh");
+            cmd.arg("-c")
+                .arg(format!("{}echo ran", allowlist_check_prefix(build)))
+                .env("PATH", format!("{}:/usr/bin:/bin", bin.path().display()))
+                .env_remove("RCH_WKR_ALLOWED_COMMANDS");
+            if let Some(allowlist) = allowlist {
+                cmd.env("RCH_WKR_ALLOWED_COMMANDS", allowlist);
+            }
+            cmd.output().expect("run sh")
+        };
+
+        let unchecked = run("python3 x.py", None);
+        assert_eq!(String::from_utf8_lossy(&unchecked.stdout).trim(), "ran");
+        let allowed = run("cargo build", Some("cargo"));
+        assert_eq!(String::from_utf8_lossy(&allowed.stdout).trim(), "ran");
+        let refused = run("python3 x.py", Some("cargo"));
+        assert_eq!(refused.status.code(), Some(123));
+        assert!(refused.stdout.is_empty());
+    }
+
     #[test]
     fn test_project_id_from_path() {
         let _guard = test_guard!();
@@ -3853,7 +4060,7 @@ mod tests {
         )
         .with_compilation_kind(Some(CompilationKind::BunTest));
 
-        let wrapped = pipeline.wrap_with_external_timeout("bun test");
+        let wrapped = pipeline.wrap_with_external_timeout("bun test", "bun test");
         assert!(wrapped.contains("timeout"));
         assert!(wrapped.contains("--signal=KILL"));
         assert!(wrapped.contains("--foreground"));
@@ -3873,7 +4080,7 @@ mod tests {
         )
         .with_compilation_kind(Some(CompilationKind::BunTypecheck));
 
-        let wrapped = pipeline.wrap_with_external_timeout("bun typecheck");
+        let wrapped = pipeline.wrap_with_external_timeout("bun typecheck", "bun typecheck");
         assert!(wrapped.contains("timeout"));
         assert!(wrapped.contains("bun typecheck"));
     }
@@ -3890,7 +4097,7 @@ mod tests {
         )
         .with_compilation_kind(Some(CompilationKind::CargoBuild));
 
-        let wrapped = pipeline.wrap_with_external_timeout("cargo build");
+        let wrapped = pipeline.wrap_with_external_timeout("cargo build", "cargo build");
         assert!(wrapped.contains("timeout"));
         assert!(wrapped.contains("--signal=KILL"));
         assert!(wrapped.contains("--foreground"));
@@ -3909,7 +4116,7 @@ mod tests {
             TransferConfig::default(),
         ); // No with_compilation_kind() call
 
-        let wrapped = pipeline.wrap_with_external_timeout("some command");
+        let wrapped = pipeline.wrap_with_external_timeout("some command", "some command");
         assert!(wrapped.contains("timeout"));
         assert!(wrapped.contains("300")); // Default build_timeout_sec
         assert!(wrapped.contains("some command"));
@@ -3927,7 +4134,7 @@ mod tests {
         )
         .with_compilation_kind(Some(CompilationKind::CargoTest));
 
-        let wrapped = pipeline.wrap_with_external_timeout("cargo test");
+        let wrapped = pipeline.wrap_with_external_timeout("cargo test", "cargo test");
         assert!(wrapped.contains("timeout"));
         assert!(wrapped.contains("1800")); // Default test_timeout_sec
         assert!(wrapped.contains("cargo test"));
@@ -3944,9 +4151,9 @@ mod tests {
         )
         .with_compilation_kind(Some(CompilationKind::CargoTest));
 
-        let wrapped = pipeline.wrap_with_external_timeout(
-            "CARGO_TARGET_DIR='/tmp/rch target' RUSTFLAGS='-C target-cpu=native' cargo test",
-        );
+        let command =
+            "CARGO_TARGET_DIR='/tmp/rch target' RUSTFLAGS='-C target-cpu=native' cargo test";
+        let wrapped = pipeline.wrap_with_external_timeout(command, command);
 
         assert!(wrapped.contains("timeout"));
         assert!(wrapped.contains(" env CARGO_TARGET_DIR="));
@@ -3955,26 +4162,113 @@ mod tests {
     }
 
     #[test]
-    fn test_external_timeout_disabled() {
+    fn test_external_timeout_passes_timeout_secs_to_rch_wkr() {
         let _guard = test_guard!();
-        use rch_common::CompilationConfig;
-
-        // External timeout can be disabled via config (bd-1nmv)
-        let config = CompilationConfig {
-            external_timeout_enabled: false,
-            ..CompilationConfig::default()
-        };
-
         let pipeline = TransferPipeline::new(
             PathBuf::from("/tmp/test"),
             "test-project".to_string(),
             "abc123".to_string(),
             TransferConfig::default(),
         )
-        .with_compilation_kind(Some(CompilationKind::BunTest))
-        .with_compilation_config(config);
+        .with_compilation_kind(Some(CompilationKind::CargoTest));
 
-        let wrapped = pipeline.wrap_with_external_timeout("bun test");
+        let wrapped = pipeline.wrap_with_external_timeout(
+            "env CARGO_TERM_COLOR=always RUSTFLAGS=-Dwarnings cargo test",
+            "RUSTFLAGS=-Dwarnings cargo test",
+        );
+        assert!(wrapped.starts_with(
+            "if rch-wkr execute --help 2>/dev/null | grep -q -- --check-command; then "
+        ));
+        assert!(wrapped.contains(
+            "RCH_LOG_LEVEL=warn rch-wkr execute --workdir . --timeout-secs 1800 --check-command 'RUSTFLAGS=-Dwarnings cargo test' --command 'env CARGO_TERM_COLOR=always RUSTFLAGS=-Dwarnings cargo test';"
+        ));
+        assert!(wrapped.contains(
+            "else timeout --signal=KILL --foreground --preserve-status 1800 env env CARGO_TERM_COLOR=always RUSTFLAGS=-Dwarnings cargo test; fi"
+        ));
+    }
+
+    #[test]
+    fn test_external_timeout_wrapper_runs_under_sh() {
+        let _guard = test_guard!();
+        let pipeline = TransferPipeline::new(
+            PathBuf::from("/tmp/test"),
+            "test-project".to_string(),
+            "abc123".to_string(),
+            TransferConfig::default(),
+        );
+
+        // Without rch-wkr on PATH the timeout(1) branch runs the command.
+        let command = "GREETING='hi there' printenv GREETING";
+        let wrapped = pipeline.wrap_with_external_timeout(command, command);
+        let output = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(&wrapped)
+            .env("PATH", "/usr/bin:/bin")
+            .output()
+            .expect("run sh");
+        assert!(output.status.success(), "{output:?}");
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "hi there");
+    }
+
+    #[test]
+    fn test_external_timeout_wrapper_skips_rch_wkr_without_timeout_flag() {
+        let _guard = test_guard!();
+        use std::os::unix::fs::PermissionsExt;
+
+        let pipeline = TransferPipeline::new(
+            PathBuf::from("/tmp/test"),
+            "test-project".to_string(),
+            "abc123".to_string(),
+            TransferConfig::default(),
+        );
+        let wrapped = pipeline.wrap_with_external_timeout("echo ran", "echo ran");
+
+        // An rch-wkr that predates --check-command rejects it with a usage
+        // error; the wrapper must fall back to timeout(1) instead.
+        let bin = tempfile::tempdir().expect("tempdir");
+        let fake = bin.path().join("rch-wkr");
+        std::fs::write(
+            &fake,
+            "#!/bin/sh\n\
+             [ \"$2\" = --help ] && { echo 'Usage: rch-wkr execute --workdir <W> --command <C>'; exit 0; }\n\
+             echo 'error: unexpected argument' >&2; exit 2\n",
+        )
+        .expect("write fake rch-wkr");
+        std::fs::set_permissions(&fake, std::fs::Permissions::from_mode(0o755))
+            .expect("chmod fake rch-wkr");
+
+        let output = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(&wrapped)
+            .env("PATH", format!("{}:/usr/bin:/bin", bin.path().display()))
+            .output()
+            .expect("run sh");
+        assert!(output.status.success(), "{output:?}");
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "ran");
+        assert!(output.stderr.is_empty(), "{output:?}");
+    }
+
+    #[test]
+    fn test_external_timeout_disabled() {
+        let _guard = test_guard!();
+        use rch_common::CompilationConfig;
+
+        // External timeout can be disabled via config (bd-1nmv)
+        let config = CompilationConfig {
+            external_timeout_enabled: false,
+            ..CompilationConfig::default()
+        };
+
+        let pipeline = TransferPipeline::new(
+            PathBuf::from("/tmp/test"),
+            "test-project".to_string(),
+            "abc123".to_string(),
+            TransferConfig::default(),
+        )
+        .with_compilation_kind(Some(CompilationKind::BunTest))
+        .with_compilation_config(config);
+
+        let wrapped = pipeline.wrap_with_external_timeout("bun test", "bun test");
         assert!(!wrapped.contains("timeout"));
         assert_eq!(wrapped, "bun test");
     }
@@ -4002,7 +4296,7 @@ mod tests {
         .with_compilation_kind(Some(CompilationKind::BunTest))
         .with_compilation_config(config);
 
-        let wrapped = pipeline.wrap_with_external_timeout("bun test");
+        let wrapped = pipeline.wrap_with_external_timeout("bun test", "bun test");
         assert!(wrapped.contains("180")); // Custom bun_timeout_sec
         assert!(wrapped.contains("bun test"));
     }
@@ -4033,6 +4327,9 @@ mod tests {
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let cmd = pipeline.build_sync_command(
@@ -4058,6 +4355,384 @@ mod tests {
         assert!(ssh_arg.contains("ControlPersist=60s"));
     }
 
+    #[test]
+    fn test_build_sync_command_merges_gitignore_only_when_enabled() {
+        let _guard = test_guard!();
+        let worker = WorkerConfig {
+            id: WorkerId::new("mock-worker"),
+            host: "mock://worker".to_string(),
+            user: "mockuser".to_string(),
+            identity_file: "~/.ssh/mock".to_string(),
+            total_slots: 4,
+            priority: 100,
+            tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
+        };
+        let excludes = vec!["target/".to_string()];
+        let sync_args = |transfer_config: TransferConfig| -> Vec<String> {
+            let pipeline = TransferPipeline::new(
+                PathBuf::from("/tmp/test"),
+                "test-project".to_string(),
+                "abc123".to_string(),
+                transfer_config,
+            );
+            pipeline
+                .build_sync_command(
+                    &worker,
+                    "mockuser@mock://worker:/tmp/rch/test-project/abc123",
+                    "/tmp/rch/test-project/abc123",
+                    &excludes,
+                )
+                .as_std()
+                .get_args()
+                .map(|arg| arg.to_string_lossy().to_string())
+                .collect()
+        };
+
+        let default_args = sync_args(TransferConfig::default());
+        assert!(!default_args.iter().any(|arg| arg == GITIGNORE_MERGE_FILTER));
+
+        let args = sync_args(TransferConfig {
+            respect_gitignore: true,
+            gitignore_include: vec!["generated/schema.rs".to_string()],
+            ..Default::default()
+        });
+        let filter = args
+            .iter()
+            .position(|arg| arg == GITIGNORE_MERGE_FILTER)
+            .expect("gitignore merge filter");
+        let target = args
+            .iter()
+            .position(|arg| arg == "target/")
+            .expect("target/ exclude");
+        let include = args
+            .iter()
+            .position(|arg| arg == "generated/schema.rs")
+            .expect("gitignore_include pattern");
+        assert_eq!(args[include - 1], "--include");
+        assert!(
+            target < include,
+            "configured excludes must win over includes"
+        );
+        assert!(
+            include < filter,
+            "includes must precede the .gitignore merge"
+        );
+    }
+
+    #[test]
+    fn test_build_sync_command_places_force_include_before_excludes() {
+        let _guard = test_guard!();
+        let worker = WorkerConfig {
+            id: WorkerId::new("mock-worker"),
+            host: "mock://worker".to_string(),
+            user: "mockuser".to_string(),
+            identity_file: "~/.ssh/mock".to_string(),
+            total_slots: 4,
+            priority: 100,
+            tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
+        };
+        let excludes = vec!["target/".to_string(), "fixtures/".to_string()];
+        let pipeline = TransferPipeline::new(
+            PathBuf::from("/tmp/test"),
+            "test-project".to_string(),
+            "abc123".to_string(),
+            TransferConfig {
+                respect_gitignore: true,
+                force_include: vec!["fixtures/***".to_string(), "schema.lock".to_string()],
+                ..Default::default()
+            },
+        );
+        let args: Vec<String> = pipeline
+            .build_sync_command(
+                &worker,
+                "mockuser@mock://worker:/tmp/rch/test-project/abc123",
+                "/tmp/rch/test-project/abc123",
+                &excludes,
+            )
+            .as_std()
+            .get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect();
+
+        let forced = args
+            .iter()
+            .position(|arg| arg == "schema.lock")
+            .expect("force_include pattern");
+        let first_exclude = args
+            .iter()
+            .position(|arg| arg == "--exclude")
+            .expect("exclude rules");
+        let filter = args
+            .iter()
+            .position(|arg| arg == GITIGNORE_MERGE_FILTER)
+            .expect("gitignore merge filter");
+        assert_eq!(args[forced - 1], "--include");
+        assert_eq!(args[forced - 3..forced - 1], ["--include", "fixtures/***"]);
+        assert!(
+            forced < first_exclude,
+            "forced includes must precede every exclude"
+        );
+        assert!(first_exclude < filter);
+    }
+
+    #[tokio::test]
+    async fn test_rclone_backend_stub_fails_transfers() {
+        let _guard = test_guard!();
+        let worker = WorkerConfig {
+            id: WorkerId::new("mock-worker"),
+            host: "mock://worker".to_string(),
+            user: "mockuser".to_string(),
+            identity_file: "~/.ssh/mock".to_string(),
+            total_slots: 4,
+            priority: 100,
+            tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
+        };
+        let pipeline = TransferPipeline::new(
+            PathBuf::from("/tmp/test"),
+            "test-project".to_string(),
+            "abc123".to_string(),
+            TransferConfig {
+                backend: TransferBackend::Rclone,
+                ..Default::default()
+            },
+        );
+
+        let err = pipeline
+            .sync_to_remote(&worker)
+            .await
+            .expect_err("rclone stub must not sync");
+        assert!(err.to_string().contains("transfer.backend \"rclone\""));
+
+        let mut lines = 0;
+        let err = pipeline
+            .retrieve_artifacts_streaming(&worker, &["target/debug/**".to_string()], |_| lines += 1)
+            .await
+            .expect_err("rclone stub must not retrieve");
+        assert!(matches!(
+            err.downcast_ref::<TransferError>(),
+            Some(TransferError::SyncFailed { .. })
+        ));
+        assert_eq!(lines, 0);
+    }
+
+    fn artifact_tar_with(entries: &[(&str, &[u8], u32)]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (path, data, mtime) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o755);
+            header.set_mtime(u64::from(*mtime));
+            header.set_entry_type(tar::EntryType::Regular);
+            header.set_cksum();
+            builder.append_data(&mut header, path, *data).unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    #[test]
+    fn test_remote_archive_command_reuses_filters_and_quotes_them() {
+        let _guard = test_guard!();
+        let filters = vec![
+            "--exclude".to_string(),
+            "/src/".to_string(),
+            "--include".to_string(),
+            "*/".to_string(),
+            "--include".to_string(),
+            "/target/debug/**".to_string(),
+            "--exclude".to_string(),
+            "*".to_string(),
+        ];
+        let command = archive::build_remote_archive_command("/tmp/rch/my proj/abc", &filters, 0);
+        assert!(command.starts_with("bash -c "), "{command}");
+        assert!(command.contains("my proj"), "{command}");
+        assert!(command.contains("/target/debug/**"), "{command}");
+        assert!(command.contains("--dry-run"), "{command}");
+        assert!(command.contains("--hard-dereference"), "{command}");
+        // Level 0 ("no compression" for rsync) still needs a valid zstd level.
+        assert!(command.contains("zstd -q -c -1"), "{command}");
+    }
+
+    #[test]
+    fn test_archive_extract_writes_files_and_rejects_escaping_paths() {
+        let _guard = test_guard!();
+        let temp = tempfile::tempdir().unwrap();
+        let data = artifact_tar_with(&[
+            ("target/debug/app", b"binary", 1_700_000_000),
+            ("target/debug/deps/libx.rlib", b"rlib", 1_700_000_000),
+        ]);
+        let stats = archive::extract_artifact_archive(data.as_slice(), temp.path(), false).unwrap();
+        assert_eq!(stats.files, 2);
+        assert_eq!(stats.bytes, 10);
+        assert_eq!(
+            std::fs::read(temp.path().join("target/debug/app")).unwrap(),
+            b"binary"
+        );
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(temp.path().join("target/debug/app"))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o755);
+        }
+
+        // tar::Builder refuses `..` paths, so write the name into the header.
+        let mut header = tar::Header::new_gnu();
+        header.set_size(4);
+        header.set_entry_type(tar::EntryType::Regular);
+        header.as_gnu_mut().unwrap().name[..9].copy_from_slice(b"../escape");
+        header.set_cksum();
+        let mut builder = tar::Builder::new(Vec::new());
+        builder.append(&header, &b"evil"[..]).unwrap();
+        let data = builder.into_inner().unwrap();
+        let err =
+            archive::extract_artifact_archive(data.as_slice(), temp.path(), false).unwrap_err();
+        assert!(err.to_string().contains("Unsafe path"), "{err}");
+        assert!(!temp.path().parent().unwrap().join("escape").exists());
+    }
+
+    #[test]
+    fn test_archive_extract_keeps_newer_local_files_when_requested() {
+        let _guard = test_guard!();
+        let temp = tempfile::tempdir().unwrap();
+        let local = temp.path().join("target/debug/app");
+        std::fs::create_dir_all(local.parent().unwrap()).unwrap();
+        std::fs::write(&local, b"local edit").unwrap();
+
+        let data = artifact_tar_with(&[("target/debug/app", b"remote", 1_000)]);
+        let stats = archive::extract_artifact_archive(data.as_slice(), temp.path(), true).unwrap();
+        assert_eq!(stats.files, 0);
+        assert_eq!(stats.skipped_newer, 1);
+        assert_eq!(std::fs::read(&local).unwrap(), b"local edit");
+
+        let stats = archive::extract_artifact_archive(data.as_slice(), temp.path(), false).unwrap();
+        assert_eq!(stats.files, 1);
+        assert_eq!(std::fs::read(&local).unwrap(), b"remote");
+    }
+
+    #[test]
+    fn test_select_artifacts_for_verification_honors_patterns_and_limits() {
+        let _guard = test_guard!();
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for (rel, len) in [
+            ("target/debug/app", 10),
+            ("target/debug/deps/libfoo.rlib", 20),
+            ("target/debug/incremental/state.bin", 5),
+            ("target/debug/huge.bin", 500),
+            ("target/release/other", 3),
+            ("src/main.rs", 7),
+            ("a.out", 4),
+        ] {
+            let path = root.join(rel);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, vec![b'x'; len]).unwrap();
+        }
+        let patterns = vec![
+            "- debug/incremental/".to_string(),
+            "- target/debug/incremental/".to_string(),
+            "target/debug/**".to_string(),
+            "*.out".to_string(),
+        ];
+
+        let mut files = verify::select_artifacts_for_verification(root, &patterns, 100, 0);
+        files.sort();
+        assert_eq!(
+            files,
+            vec!["a.out", "target/debug/app", "target/debug/deps/libfoo.rlib"]
+        );
+
+        let sampled = verify::select_artifacts_for_verification(root, &patterns, 100, 2);
+        assert_eq!(sampled.len(), 2);
+    }
+
+    #[test]
+    fn test_remote_artifact_hash_commands_batch_and_escape() {
+        let _guard = test_guard!();
+        let files: Vec<String> = (0..verify::REMOTE_HASH_BATCH_SIZE + 1)
+            .map(|i| format!("target/debug/file {i}"))
+            .collect();
+        let commands = verify::build_remote_artifact_hash_commands("/data/projects/p/h", &files);
+        assert_eq!(commands.len(), 2);
+        assert!(commands[0].starts_with("cd /data/projects/p/h || exit 44;"));
+        assert!(commands[0].contains("'target/debug/file 0'"));
+        assert!(commands[0].contains("b3sum --no-names"));
+        assert!(commands[1].contains(&format!(
+            "'target/debug/file {}'",
+            verify::REMOTE_HASH_BATCH_SIZE
+        )));
+    }
+
+    #[test]
+    fn test_parse_remote_artifact_hashes() {
+        let _guard = test_guard!();
+        let output = "RCH_HASH\t10\tabc123\ttarget/debug/app\n\
+                      RCH_HASH\tnot-a-size\tdef\ttarget/debug/bad\n\
+                      noise\n\
+                      RCH_HASH\t0\te3b0\ttarget/debug/with\ttab\n";
+        let hashes = verify::parse_remote_artifact_hashes(output).expect("hashes");
+        assert_eq!(hashes.len(), 2);
+        assert_eq!(hashes["target/debug/app"].size, 10);
+        assert_eq!(hashes["target/debug/app"].hash, "abc123");
+        assert!(hashes.contains_key("target/debug/with\ttab"));
+
+        assert!(verify::parse_remote_artifact_hashes("RCH_HASH_UNAVAILABLE\n").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_verify_retrieved_artifacts_detects_local_truncation() {
+        let _guard = test_guard!();
+        let dir = tempfile::tempdir().unwrap();
+        let artifact = dir.path().join("target/debug/app");
+        std::fs::create_dir_all(artifact.parent().unwrap()).unwrap();
+        std::fs::write(&artifact, b"complete binary").unwrap();
+        let remote = rch_common::compute_file_hash(&artifact).unwrap();
+        std::fs::write(&artifact, b"complete").unwrap();
+
+        let mut manifest = rch_common::ArtifactManifest::default();
+        manifest
+            .files
+            .insert("target/debug/app".to_string(), remote.clone());
+        let result = rch_common::verify_artifacts(dir.path(), &manifest, u64::MAX);
+        assert!(!result.all_passed());
+        assert!(result.format_failures().contains("target/debug/app"));
+
+        // Disabled verification never touches the worker.
+        let pipeline = TransferPipeline::new(
+            dir.path().to_path_buf(),
+            "test-project".to_string(),
+            "abc123".to_string(),
+            TransferConfig::default(),
+        );
+        let worker = WorkerConfig {
+            id: WorkerId::new("unreachable"),
+            host: "192.0.2.1".to_string(),
+            user: "nobody".to_string(),
+            identity_file: "~/.ssh/none".to_string(),
+            total_slots: 1,
+            priority: 100,
+            tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
+        };
+        let skipped = pipeline
+            .verify_retrieved_artifacts(&worker, &["target/debug/**".to_string()])
+            .await
+            .expect("disabled verification succeeds");
+        assert!(skipped.is_none());
+    }
+
     #[test]
     fn test_build_sync_command_adaptive_compression_uses_estimate() {
         let _guard = test_guard!();
@@ -4085,6 +4760,9 @@ mod tests {
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let cmd = pipeline.build_sync_command(
@@ -4122,6 +4800,9 @@ mod tests {
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let sync = pipeline.build_sync_command(
@@ -4174,6 +4855,9 @@ mod tests {
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let cmd = pipeline.build_retrieve_command(
@@ -4248,6 +4932,9 @@ mod tests {
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let cmd = pipeline.build_retrieve_streaming_command(
@@ -4299,6 +4986,9 @@ mod tests {
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let cmd = pipeline.build_retrieve_streaming_command(
@@ -4325,6 +5015,43 @@ mod tests {
         assert!(args.windows(2).any(|window| window == ["--exclude", "*"]));
     }
 
+    #[test]
+    fn test_parse_rsync_stat_bytes_reads_compression_counters() {
+        let _guard = test_guard!();
+        let output = "Number of files: 1,204 (reg: 1,100, dir: 104)\n\
+Literal data: 3,145,728 bytes\n\
+Matched data: 0 bytes\n\
+Total bytes sent: 1,048,576\n\
+Total bytes received: 2,048";
+        assert_eq!(parse_rsync_stat_bytes(output, "Literal data:"), 3_145_728);
+        assert_eq!(
+            parse_rsync_stat_bytes(output, "Total bytes sent:"),
+            1_048_576
+        );
+        assert_eq!(
+            parse_rsync_stat_bytes(output, "Total bytes received:"),
+            2_048
+        );
+        assert_eq!(parse_rsync_stat_bytes("sent 10 bytes", "Literal data:"), 0);
+    }
+
+    #[test]
+    fn test_sync_result_compression_ratio() {
+        let _guard = test_guard!();
+        let result = SyncResult {
+            raw_bytes: 3_145_728,
+            compressed_bytes: 1_048_576,
+            ..Default::default()
+        };
+        assert_eq!(result.compression_ratio(), Some(3.0));
+        assert_eq!(SyncResult::default().compression_ratio(), None);
+        let unknown_wire = SyncResult {
+            raw_bytes: 10,
+            ..Default::default()
+        };
+        assert_eq!(unknown_wire.compression_ratio(), None);
+    }
+
     #[test]
     fn test_sync_result_struct() {
         let _guard = test_guard!();
@@ -4332,6 +5059,7 @@ mod tests {
             bytes_transferred: 1024,
             files_transferred: 10,
             duration_ms: 500,
+            ..Default::default()
         };
 
         assert_eq!(result.bytes_transferred, 1024);
@@ -4358,6 +5086,9 @@ mod tests {
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let mut overrides = HashMap::new();
@@ -4483,6 +5214,174 @@ mod tests {
         assert!(!command.contains(&format!("{}/.rch-target'", worker_scoped_root)));
     }
 
+    #[test]
+    fn test_build_remote_command_enables_cargo_incremental_for_debug_builds() {
+        let _guard = test_guard!();
+        let pipeline = |kind: CompilationKind, remote_incremental: bool| {
+            TransferPipeline::new(
+                PathBuf::from("/tmp/project"),
+                "project".to_string(),
+                "hash".to_string(),
+                TransferConfig::default(),
+            )
+            .with_compilation_kind(Some(kind))
+            .with_compilation_config(rch_common::CompilationConfig {
+                remote_incremental,
+                ..Default::default()
+            })
+        };
+
+        let debug = pipeline(CompilationKind::CargoBuild, true);
+        assert!(
+            debug
+                .build_remote_command("cargo build", None)
+                .contains("CARGO_INCREMENTAL=1 cargo build")
+        );
+        let release = pipeline(CompilationKind::CargoBuild, true);
+        assert!(
+            !release
+                .build_remote_command("cargo build --release", None)
+                .contains("CARGO_INCREMENTAL")
+        );
+        let make = pipeline(CompilationKind::Make, true);
+        assert!(
+            !make
+                .build_remote_command("make -j8", None)
+                .contains("CARGO_INCREMENTAL")
+        );
+        let defaults = TransferPipeline::new(
+            PathBuf::from("/tmp/project"),
+            "project".to_string(),
+            "hash".to_string(),
+            TransferConfig::default(),
+        )
+        .with_compilation_kind(Some(CompilationKind::CargoTest));
+        assert!(
+            !defaults
+                .build_remote_command("cargo test", None)
+                .contains("CARGO_INCREMENTAL")
+        );
+        let disabled = pipeline(CompilationKind::CargoTest, false);
+        assert!(
+            !disabled
+                .build_remote_command("cargo test", None)
+                .contains("CARGO_INCREMENTAL")
+        );
+
+        // A forwarded CARGO_INCREMENTAL (e.g. `0` for sccache) wins.
+        let mut overrides = HashMap::new();
+        overrides.insert("CARGO_INCREMENTAL".to_string(), "0".to_string());
+        let forwarded = pipeline(CompilationKind::CargoTest, true)
+            .with_env_allowlist(vec!["CARGO_INCREMENTAL".to_string()])
+            .with_env_overrides(overrides);
+        let command = forwarded.build_remote_command("cargo test", None);
+        assert!(command.contains("CARGO_INCREMENTAL=0"));
+        assert!(!command.contains("CARGO_INCREMENTAL=1"));
+    }
+
+    #[test]
+    fn test_build_remote_command_respects_project_profile_incremental() {
+        use tempfile::tempdir;
+
+        let _guard = test_guard!();
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"app\"\n\n[profile.dev]\nincremental = false\n",
+        )
+        .unwrap();
+        let pipeline = TransferPipeline::new(
+            dir.path().to_path_buf(),
+            "project".to_string(),
+            "hash".to_string(),
+            TransferConfig::default(),
+        )
+        .with_compilation_kind(Some(CompilationKind::CargoBuild))
+        .with_compilation_config(rch_common::CompilationConfig {
+            remote_incremental: true,
+            ..Default::default()
+        });
+        let command = pipeline.build_remote_command("cargo build", None);
+        assert!(!command.contains("CARGO_INCREMENTAL"), "{command}");
+
+        // Off by default.
+        assert!(!rch_common::CompilationConfig::default().remote_incremental);
+    }
+
+    #[test]
+    fn test_remote_priority_wraps_execution_command() {
+        let _guard = test_guard!();
+        let base = || {
+            TransferPipeline::new(
+                PathBuf::from("/tmp/project"),
+                "project".to_string(),
+                "hash".to_string(),
+                TransferConfig::default(),
+            )
+        };
+
+        let plain = base().build_remote_command("cargo build", None);
+        assert!(!plain.contains("nice -n"));
+        assert!(!plain.contains("ionice"));
+
+        let command = base()
+            .with_remote_priority(Some(10), Some(3))
+            .build_remote_command("cargo build", None);
+        assert!(
+            command.contains("&& nice -n 10 sh -c 'command -v ionice"),
+            "{command}"
+        );
+        assert!(
+            command.contains("exec ionice -c 3 -t \"$@\"; exec \"$@\"' rch-ionice sh -c '"),
+            "{command}"
+        );
+
+        let nice_only = base()
+            .with_remote_priority(Some(5), None)
+            .build_remote_command("cargo build", None);
+        assert!(nice_only.contains("&& nice -n 5 sh -c '"), "{nice_only}");
+        assert!(!nice_only.contains("ionice"));
+    }
+
+    #[test]
+    fn test_remote_priority_ignores_out_of_range_values() {
+        let _guard = test_guard!();
+        assert_eq!(
+            wrap_command_with_priority("cargo build", Some(40), Some(7)),
+            "cargo build"
+        );
+        assert_eq!(
+            wrap_command_with_priority("cargo build", Some(-20), Some(9)),
+            "nice -n -20 sh -c 'cargo build'"
+        );
+        // Realtime needs root; only best-effort and idle are applied.
+        assert_eq!(
+            wrap_command_with_priority("cargo build", None, Some(1)),
+            "cargo build"
+        );
+    }
+
+    #[test]
+    fn test_remote_priority_runs_build_without_ionice() {
+        let _guard = test_guard!();
+        let tmp = tempfile::tempdir().unwrap();
+        let marker = tmp.path().join("built");
+        let wrapped =
+            wrap_command_with_priority(&format!("echo ok > {}", marker.display()), None, Some(3));
+        // PATH holding only `sh`, no `ionice`: the build still runs.
+        let bin = tmp.path().join("bin");
+        std::fs::create_dir(&bin).unwrap();
+        std::os::unix::fs::symlink("/bin/sh", bin.join("sh")).unwrap();
+        let status = std::process::Command::new("/bin/sh")
+            .arg("-c")
+            .arg(&wrapped)
+            .env("PATH", &bin)
+            .status()
+            .unwrap();
+        assert!(status.success(), "{wrapped}");
+        assert!(marker.exists());
+    }
+
     #[test]
     fn test_invalid_remote_cargo_target_dir_name_falls_back_to_default() {
         let _guard = test_guard!();
@@ -4576,6 +5475,35 @@ mod tests {
         assert!(command.contains("echo $$ > \"$1\""));
         assert!(command.contains("setsid sh -c"));
         assert!(command.contains(&remote_pgid_file));
+        assert!(!command.contains("trap '' HUP"));
+    }
+
+    #[test]
+    fn test_build_remote_command_detaches_when_configured() {
+        let _guard = test_guard!();
+        let pipeline = TransferPipeline::new(
+            PathBuf::from("/tmp/project"),
+            "project".to_string(),
+            "hash".to_string(),
+            TransferConfig {
+                detach_remote_builds: true,
+                ..TransferConfig::default()
+            },
+        )
+        .with_build_id(Some(42));
+
+        let command = pipeline.build_remote_command("cargo build", None);
+        let files = DetachedBuildFiles::for_pgid_file(
+            &pipeline
+                .remote_pgid_file_path()
+                .expect("build_id should enable remote pgid tracking"),
+        );
+
+        assert!(command.contains("trap '' HUP"));
+        assert!(command.contains("setsid sh -c"));
+        assert!(command.contains(&files.stdout));
+        assert!(command.contains(&files.exit));
+        assert!(command.contains("tail -n +1 -f"));
     }
 
     #[test]
@@ -5163,6 +6091,33 @@ Total file size: 123 bytes";
         assert!(config.estimated_bandwidth_bps.is_none());
     }
 
+    #[test]
+    fn test_estimate_sync_seconds_uses_measured_link() {
+        let _guard = test_guard!();
+        // 100 MiB at 10 MiB/s plus a 200ms round trip.
+        let secs = estimate_sync_seconds(100 * 1024 * 1024, 10 * 1024 * 1024, Some(200));
+        assert!((secs - 10.2).abs() < 1e-9);
+        // Same payload over a 1 MiB/s VPN link.
+        let secs = estimate_sync_seconds(100 * 1024 * 1024, 1024 * 1024, None);
+        assert!((secs - 100.0).abs() < 1e-9);
+        // A zero bandwidth sample only contributes the round trip.
+        assert!((estimate_sync_seconds(1024, 0, Some(50)) - 0.05).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_pipeline_with_measured_link() {
+        let _guard = test_guard!();
+        let pipeline = TransferPipeline::new(
+            PathBuf::from("/tmp/test"),
+            "test-project".to_string(),
+            "abc123".to_string(),
+            TransferConfig::default(),
+        )
+        .with_measured_link(Some(5 * 1024 * 1024), Some(40));
+        assert_eq!(pipeline.measured_bandwidth_bps, Some(5 * 1024 * 1024));
+        assert_eq!(pipeline.measured_rtt_ms, Some(40));
+    }
+
     #[test]
     fn test_transfer_config_with_optimization_options() {
         let _guard = test_guard!();
@@ -5373,6 +6328,85 @@ Total file size: 123 bytes";
         )));
     }
 
+    #[test]
+    fn test_artifact_retrieval_is_retryable_separates_partial_from_nothing() {
+        let _guard = test_guard!();
+        let failed = |exit_code: Option<i32>, reason: &str, stderr: &str| -> anyhow::Error {
+            TransferError::SyncFailed {
+                reason: reason.to_string(),
+                exit_code,
+                stderr: stderr.to_string(),
+            }
+            .into()
+        };
+
+        // Interrupted downloads are retried.
+        assert!(artifact_retrieval_is_retryable(&failed(
+            Some(23),
+            "rsync failed",
+            "rsync: [receiver] write failed on \"target/debug/app\": Connection reset\n\
+             rsync error: some files/attrs were not transferred (code 23)",
+        )));
+        assert!(artifact_retrieval_is_retryable(&failed(
+            Some(24),
+            "rsync failed",
+            "file has vanished: \"/data/projects/app/target/debug/.fingerprint/x\"",
+        )));
+        assert!(artifact_retrieval_is_retryable(&failed(
+            Some(12),
+            "rsync failed",
+            "rsync error: error in rsync protocol data stream (code 12)",
+        )));
+        assert!(artifact_retrieval_is_retryable(&failed(
+            None,
+            "partial artifact retrieval despite exit 0 (truncated file)",
+            "",
+        )));
+        assert!(artifact_retrieval_is_retryable(&anyhow::anyhow!(
+            "rsync transport error (exit 255): ssh: connect to host w1: Connection timed out"
+        )));
+
+        // Nothing to transfer, or a local failure that would repeat, is not.
+        assert!(!artifact_retrieval_is_retryable(&failed(
+            Some(23),
+            "rsync failed",
+            "rsync: [sender] link_stat \"/data/projects/app/target/release\" failed: No such file or directory (2)\n\
+             rsync error: some files/attrs were not transferred (code 23)",
+        )));
+        assert!(!artifact_retrieval_is_retryable(&failed(
+            Some(23),
+            "rsync failed",
+            "rsync: mkstemp \"target/debug/.app.XXXX\" failed: Permission denied (13)",
+        )));
+        assert!(!artifact_retrieval_is_retryable(&failed(
+            Some(1),
+            "rsync failed",
+            "rsync: syntax or usage error",
+        )));
+    }
+
+    #[test]
+    fn test_rsync_exit_code_from_transfer_errors() {
+        let _guard = test_guard!();
+        let failed: anyhow::Error = TransferError::SyncFailed {
+            reason: "rsync failed".to_string(),
+            exit_code: Some(23),
+            stderr: String::new(),
+        }
+        .into();
+        assert_eq!(rsync_exit_code(&failed), Some(23));
+        assert_eq!(
+            rsync_exit_code(&anyhow::anyhow!(
+                "rsync transport error (exit 12): connection unexpectedly closed"
+            )),
+            Some(12)
+        );
+        assert_eq!(
+            rsync_exit_code(&anyhow::anyhow!("op: timed out after 25ms")),
+            None
+        );
+    }
+
     #[cfg(unix)]
     #[tokio::test]
     async fn test_run_command_streaming_with_retry_retries_transient_transport_failure() {
@@ -5577,6 +6611,9 @@ Total file size: 123 bytes";
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let cmd = pipeline.build_sync_command(
@@ -5615,6 +6652,9 @@ Total file size: 123 bytes";
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let cmd = pipeline.build_sync_command(
@@ -5657,6 +6697,9 @@ Total file size: 123 bytes";
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let cmd = pipeline.build_sync_command(
@@ -5676,6 +6719,165 @@ Total file size: 123 bytes";
         assert!(!args.iter().any(|arg| arg.starts_with("--bwlimit")));
     }
 
+    #[test]
+    fn test_build_retrieve_commands_with_bwlimit() {
+        let _guard = test_guard!();
+        let config = TransferConfig {
+            bwlimit_kbps: Some(2500),
+            ..Default::default()
+        };
+        let pipeline = TransferPipeline::new(
+            PathBuf::from("/tmp/test"),
+            "test-project".to_string(),
+            "abc123".to_string(),
+            config,
+        );
+        let worker = WorkerConfig {
+            id: WorkerId::new("mock-worker"),
+            host: "mock://worker".to_string(),
+            user: "mockuser".to_string(),
+            identity_file: "~/.ssh/mock".to_string(),
+            total_slots: 4,
+            priority: 100,
+            tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
+        };
+
+        for cmd in [
+            pipeline.build_retrieve_command(&worker, "/tmp/rch/test-project/abc123", &[]),
+            pipeline.build_retrieve_streaming_command(&worker, "/tmp/rch/test-project/abc123", &[]),
+        ] {
+            let args: Vec<String> = cmd
+                .as_std()
+                .get_args()
+                .map(|arg| arg.to_string_lossy().to_string())
+                .collect();
+            assert!(args.contains(&"--bwlimit=2500".to_string()), "{args:?}");
+        }
+    }
+
+    #[test]
+    fn test_build_retrieve_commands_apply_deny_patterns_and_update() {
+        let _guard = test_guard!();
+        let config = TransferConfig {
+            artifact_deny_patterns: vec!["src/**".to_string(), "*.rs".to_string()],
+            preserve_newer_local: true,
+            ..Default::default()
+        };
+        let pipeline = TransferPipeline::new(
+            PathBuf::from("/tmp/test"),
+            "test-project".to_string(),
+            "abc123".to_string(),
+            config,
+        );
+        let worker = WorkerConfig {
+            id: WorkerId::new("mock-worker"),
+            host: "mock://worker".to_string(),
+            user: "mockuser".to_string(),
+            identity_file: "~/.ssh/mock".to_string(),
+            total_slots: 4,
+            priority: 100,
+            tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
+        };
+        let patterns = ["target/debug/**".to_string()];
+
+        for cmd in [
+            pipeline.build_retrieve_command(&worker, "/tmp/rch/test-project/abc123", &patterns),
+            pipeline.build_retrieve_streaming_command(
+                &worker,
+                "/tmp/rch/test-project/abc123",
+                &patterns,
+            ),
+        ] {
+            let args: Vec<String> = cmd
+                .as_std()
+                .get_args()
+                .map(|arg| arg.to_string_lossy().to_string())
+                .collect();
+            assert!(args.contains(&"--update".to_string()), "{args:?}");
+            let first_include = args
+                .iter()
+                .position(|arg| arg == "--include")
+                .expect("include args");
+            for deny in ["src/**", "*.rs"] {
+                let idx = args
+                    .iter()
+                    .position(|arg| arg == deny)
+                    .unwrap_or_else(|| panic!("deny pattern {deny} missing: {args:?}"));
+                assert_eq!(args[idx - 1], "--exclude");
+                assert!(idx < first_include, "deny must precede includes: {args:?}");
+            }
+        }
+
+        let default_cmd = TransferPipeline::new(
+            PathBuf::from("/tmp/test"),
+            "test-project".to_string(),
+            "abc123".to_string(),
+            TransferConfig::default(),
+        )
+        .build_retrieve_command(&worker, "/tmp/rch/test-project/abc123", &patterns);
+        assert!(
+            !default_cmd.as_std().get_args().any(|arg| arg == "--update"),
+            "--update is opt-in"
+        );
+    }
+
+    #[test]
+    fn test_rsync_ssh_omits_identity_when_identity_file_empty() {
+        let _guard = test_guard!();
+        let pipeline = TransferPipeline::new(
+            PathBuf::from("/tmp/test"),
+            "test-project".to_string(),
+            "abc123".to_string(),
+            TransferConfig::default(),
+        );
+        let mut worker = WorkerConfig {
+            id: WorkerId::new("agent-worker"),
+            host: "mock://worker".to_string(),
+            user: "mockuser".to_string(),
+            identity_file: String::new(),
+            total_slots: 4,
+            priority: 100,
+            tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
+        };
+        let ssh_program = |cmd: Command| {
+            let args: Vec<String> = cmd
+                .as_std()
+                .get_args()
+                .map(|arg| arg.to_string_lossy().to_string())
+                .collect();
+            let idx = args.iter().position(|arg| arg == "-e").expect("-e arg");
+            args[idx + 1].clone()
+        };
+
+        let agent_only = ssh_program(pipeline.build_retrieve_command(
+            &worker,
+            "/tmp/rch/test-project/abc123",
+            &["target/**".to_string()],
+        ));
+        assert!(agent_only.starts_with("ssh "), "{agent_only}");
+        assert!(!agent_only.contains(" -i "), "{agent_only}");
+
+        worker.identity_file = "/keys/id_ed25519".to_string();
+        let with_key = ssh_program(pipeline.build_retrieve_command(
+            &worker,
+            "/tmp/rch/test-project/abc123",
+            &["target/**".to_string()],
+        ));
+        assert!(
+            with_key.starts_with("ssh -i /keys/id_ed25519"),
+            "{with_key}"
+        );
+    }
+
     #[test]
     fn test_build_sync_command_metadata_only_sync_omits_delete_and_uses_includes() {
         let _guard = test_guard!();
@@ -5701,6 +6903,9 @@ Total file size: 123 bytes";
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let cmd = pipeline.build_sync_command(
@@ -5761,6 +6966,9 @@ Total file size: 123 bytes";
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let cmd = pipeline.build_sync_streaming_command(
@@ -5814,6 +7022,9 @@ Total file size: 123 bytes";
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let cmd = pipeline.build_sync_streaming_command(
@@ -6152,6 +7363,9 @@ Total file size: 123 bytes";
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         let cmd = pipeline.build_retrieve_command(
             &worker,
@@ -6231,6 +7445,9 @@ Total file size: 123 bytes";
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         let cmd = pipeline.build_retrieve_command(
             &worker,
@@ -6290,6 +7507,9 @@ Total file size: 123 bytes";
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         let cmd =
             pipeline.build_retrieve_command(&worker, "/tmp/rch/test-project/abc123", &["*".into()]);
@@ -6353,6 +7573,9 @@ Total file size: 123 bytes";
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         let cmd = pipeline.build_retrieve_streaming_command(
             &worker,
@@ -6403,6 +7626,9 @@ Total file size: 123 bytes";
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         let cmd = pipeline.build_retrieve_command(
             &worker,
@@ -0,0 +1,206 @@
+//! Single-archive artifact retrieval (`transfer.artifact_archive`).
+//!
+//! rsync pays per-file round-trips, which dominate on a high-RTT link when a
+//! build leaves thousands of small artifacts. In archive mode the worker
+//! selects the artifacts with the same rsync filter rules as the per-file path
+//! (`rsync --dry-run` against an empty directory lists exactly the files a
+//! real retrieval would send), packs them with `tar | zstd`, and streams the
+//! one archive back over SSH ([`build_remote_archive_command`]). The client
+//! decompresses it with the local `zstd` and unpacks it into the project with
+//! [`extract_artifact_archive`].
+//!
+//! Extraction is defensive in the same way as `--safe-links`: entries with
+//! absolute or `..` paths are rejected, and only regular files and
+//! directories are written (symlinks and special files are skipped). Files
+//! are replaced by rename, so a binary that is running locally is not
+//! truncated in place. The worker needs GNU tar (`--verbatim-files-from`).
+
+use super::*;
+use std::io::Read;
+use std::time::{Duration, SystemTime};
+
+/// What [`extract_artifact_archive`] wrote.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(super) struct ArchiveExtractStats {
+    /// Regular files written.
+    pub files: u32,
+    /// Uncompressed size of the files written.
+    pub bytes: u64,
+    /// Files kept because the local copy is newer
+    /// (`transfer.preserve_newer_local`).
+    pub skipped_newer: u32,
+}
+
+/// Remote command that writes a zstd-compressed tar of the artifacts matched
+/// by `filter_args` (see `TransferPipeline::artifact_filter_args`) to stdout.
+///
+/// Directories are dropped from the rsync listing so tar only packs files;
+/// `--hard-dereference` stores cargo's hardlinked `target/<profile>/<bin>`
+/// copies as regular files. `pipefail` makes a failed listing fail the
+/// command instead of producing an empty archive.
+pub(super) fn build_remote_archive_command(
+    remote_path: &str,
+    filter_args: &[String],
+    compression_level: u32,
+) -> String {
+    let filters = filter_args
+        .iter()
+        .map(|arg| escape(Cow::from(arg.as_str())).into_owned())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let script = format!(
+        "set -o pipefail; cd {path} || exit 1; d=$(mktemp -d) || exit 1; \
+         trap 'rmdir \"$d\"' EXIT; \
+         rsync -a --dry-run --safe-links --out-format='%n' {filters} ./ \"$d\"/ \
+         | sed '/\\/$/d' \
+         | tar -cf - --no-recursion --hard-dereference --verbatim-files-from -T - \
+         | zstd -q -c -{level}",
+        path = escape(Cow::from(remote_path)),
+        level = compression_level.clamp(1, 19),
+    );
+    format!("bash -c {}", escape(Cow::from(script)))
+}
+
+/// Relative destination for an archive entry, or `None` when the path could
+/// escape the project root (absolute, `..`, or empty).
+fn archive_entry_relative_path(path: &Path) -> Option<PathBuf> {
+    let mut relative = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::Normal(part) => relative.push(part),
+            std::path::Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    (!relative.as_os_str().is_empty()).then_some(relative)
+}
+
+/// Unpack a tar stream into `destination`.
+///
+/// With `preserve_newer_local`, files whose local copy has a newer mtime than
+/// the archived one are left alone (the archive-mode equivalent of rsync
+/// `--update`).
+pub(super) fn extract_artifact_archive<R: Read>(
+    reader: R,
+    destination: &Path,
+    preserve_newer_local: bool,
+) -> Result<ArchiveExtractStats> {
+    let mut stats = ArchiveExtractStats::default();
+    let mut archive = tar::Archive::new(reader);
+
+    for entry in archive
+        .entries()
+        .context("Failed to read artifact archive")?
+    {
+        let mut entry = entry.context("Failed to read artifact archive entry")?;
+        let entry_path = entry
+            .path()
+            .context("Artifact archive entry has an invalid path")?
+            .into_owned();
+        let Some(relative) = archive_entry_relative_path(&entry_path) else {
+            anyhow::bail!("Unsafe path in artifact archive: {}", entry_path.display());
+        };
+        let target = destination.join(&relative);
+
+        let entry_type = entry.header().entry_type();
+        if entry_type.is_dir() {
+            std::fs::create_dir_all(&target)
+                .with_context(|| format!("Failed to create {}", target.display()))?;
+            continue;
+        }
+        if !entry_type.is_file() {
+            debug!(
+                "Skipping non-regular artifact archive entry {} ({:?})",
+                relative.display(),
+                entry_type
+            );
+            continue;
+        }
+
+        let mtime = entry
+            .header()
+            .mtime()
+            .ok()
+            .map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs));
+        if preserve_newer_local
+            && let (Some(archived), Ok(local)) = (
+                mtime,
+                std::fs::metadata(&target).and_then(|meta| meta.modified()),
+            )
+            && local > archived
+        {
+            stats.skipped_newer += 1;
+            continue;
+        }
+
+        let parent = target.parent().unwrap_or(destination);
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+        let file_name = target
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let staging = parent.join(format!(".{file_name}.rch-extract"));
+
+        let written = (|| -> Result<u64> {
+            let mut output = std::fs::File::create(&staging)
+                .with_context(|| format!("Failed to create {}", staging.display()))?;
+            let written = std::io::copy(&mut entry, &mut output)
+                .with_context(|| format!("Failed to extract {}", relative.display()))?;
+            #[cfg(unix)]
+            if let Ok(mode) = entry.header().mode() {
+                use std::os::unix::fs::PermissionsExt;
+                output.set_permissions(std::fs::Permissions::from_mode(mode & 0o7777))?;
+            }
+            if let Some(mtime) = mtime {
+                output.set_modified(mtime)?;
+            }
+            std::fs::rename(&staging, &target)
+                .with_context(|| format!("Failed to replace {}", target.display()))?;
+            Ok(written)
+        })();
+        match written {
+            Ok(written) => {
+                stats.files += 1;
+                stats.bytes += written;
+            }
+            Err(e) => {
+                let _ = std::fs::remove_file(&staging);
+                return Err(e);
+            }
+        }
+    }
+
+    Ok(stats)
+}
+
+/// Decompress `archive_path` with the local `zstd` and unpack it into
+/// `destination`.
+pub(super) fn extract_compressed_artifact_archive(
+    archive_path: &Path,
+    destination: &Path,
+    preserve_newer_local: bool,
+) -> Result<ArchiveExtractStats> {
+    let mut zstd = std::process::Command::new("zstd")
+        .arg("-d")
+        .arg("-q")
+        .arg("-c")
+        .arg(archive_path)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run zstd to decompress the artifact archive")?;
+    let stdout = zstd.stdout.take().context("zstd stdout unavailable")?;
+    let extracted = extract_artifact_archive(stdout, destination, preserve_newer_local);
+    let output = zstd.wait_with_output().context("Failed to wait for zstd")?;
+
+    if !output.status.success() {
+        return Err(TransferError::SyncFailed {
+            reason: "artifact archive decompression failed".to_string(),
+            exit_code: output.status.code(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        }
+        .into());
+    }
+    extracted
+}
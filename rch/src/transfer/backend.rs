@@ -0,0 +1,843 @@
+//! Pluggable sync backends for [`TransferPipeline`].
+//!
+//! [`SyncBackend`] is the seam between the pipeline (remote paths, exclude
+//! rules, retry policy, size thresholds) and the mechanism that actually moves
+//! bytes: uploading the project ([`SyncBackend::sync_to_remote`]), pulling
+//! artifacts back ([`SyncBackend::retrieve_artifacts`]), and the pre-upload size
+//! gate ([`SyncBackend::should_skip_transfer`]). `transfer.backend` picks the
+//! implementation:
+//!
+//! - [`RsyncBackend`] (`"rsync"`, default) — rsync over SSH, the only complete
+//!   backend.
+//! - [`RcloneBackend`] (`"rclone"`) — a stub reserved for workers reachable
+//!   only through an object-store bucket. Every transfer fails with
+//!   `TransferError::SyncFailed`, so the hook falls back to a local build.
+//!
+//! Backends are stateless unit structs dispatched statically from the
+//! pipeline's public transfer methods. The trait uses `async fn` with a generic
+//! progress callback, so it is not object-safe. The streaming callers pass
+//! `Some(on_line)` to receive progress lines and the buffered callers pass
+//! `None`. The rsync command builders, mock transport and retry helpers stay in
+//! the parent module; this module reaches them through `use super::*`.
+
+use super::*;
+
+/// Moves project files to a worker and build artifacts back.
+pub trait SyncBackend {
+    /// Estimate how much [`SyncBackend::sync_to_remote`] would upload.
+    ///
+    /// `None` means the backend cannot estimate; the size/time thresholds then
+    /// fail open.
+    async fn estimate_transfer_size(
+        &self,
+        _pipeline: &TransferPipeline,
+        _worker: &WorkerConfig,
+    ) -> Option<TransferEstimate> {
+        None
+    }
+
+    /// Check if transfer should be skipped based on size/time thresholds.
+    ///
+    /// Returns `Some(reason)` if transfer should be skipped, `None` if it should
+    /// proceed. Records the estimate on the pipeline for adaptive compression.
+    async fn should_skip_transfer(
+        &self,
+        pipeline: &mut TransferPipeline,
+        worker: &WorkerConfig,
+    ) -> Option<String> {
+        // Check if any thresholds are configured
+        let max_mb = pipeline.transfer_config.max_transfer_mb;
+        let max_time_ms = pipeline.transfer_config.max_transfer_time_ms;
+        // The measured-link check only applies once the daemon has a sample.
+        let measured_limit = pipeline
+            .transfer_config
+            .max_sync_seconds
+            .zip(pipeline.measured_bandwidth_bps);
+
+        let needs_estimate = pipeline.transfer_config.adaptive_compression
+            || max_mb.is_some()
+            || max_time_ms.is_some()
+            || measured_limit.is_some();
+
+        if !needs_estimate {
+            return None; // No thresholds configured
+        }
+
+        // Run estimation
+        let estimate = match self.estimate_transfer_size(pipeline, worker).await {
+            Some(e) => e,
+            None => {
+                pipeline.estimated_transfer_bytes = None;
+                debug!("Transfer estimation failed, proceeding with transfer (fail-open)");
+                return None;
+            }
+        };
+        pipeline.estimated_transfer_bytes = Some(estimate.bytes);
+
+        // Measured link: decide on estimated sync time instead of the static
+        // size/time thresholds, so the same project can offload over LAN but
+        // stay local over a slow VPN.
+        if let Some((max_secs, bandwidth_bps)) = measured_limit {
+            let estimated_secs =
+                estimate_sync_seconds(estimate.bytes, bandwidth_bps, pipeline.measured_rtt_ms);
+            if estimated_secs > max_secs as f64 {
+                return Some(format!(
+                    "Estimated sync time ({:.1}s at {:.2} MB/s measured) exceeds threshold ({}s)",
+                    estimated_secs,
+                    bandwidth_bps as f64 / (1024.0 * 1024.0),
+                    max_secs
+                ));
+            }
+            return None;
+        }
+
+        // Check size threshold
+        if let Some(max_mb) = max_mb {
+            let max_bytes = max_mb.saturating_mul(1024 * 1024);
+            if estimate.bytes > max_bytes {
+                let estimated_mb = estimate.bytes as f64 / (1024.0 * 1024.0);
+                return Some(format!(
+                    "Transfer size ({:.2} MB) exceeds threshold ({:.2} MB)",
+                    estimated_mb, max_mb as f64
+                ));
+            }
+        }
+
+        // Check time threshold
+        if let Some(max_time) = max_time_ms
+            && estimate.estimated_time_ms > max_time
+        {
+            return Some(format!(
+                "Estimated transfer time ({} ms) exceeds threshold ({} ms)",
+                estimate.estimated_time_ms, max_time
+            ));
+        }
+
+        None
+    }
+
+    /// Synchronize the local project to the worker. `on_line` receives
+    /// progress lines for UI rendering when given.
+    async fn sync_to_remote<F>(
+        &self,
+        pipeline: &TransferPipeline,
+        worker: &WorkerConfig,
+        on_line: Option<F>,
+    ) -> Result<SyncResult>
+    where
+        F: FnMut(&str);
+
+    /// Retrieve files matching `artifact_patterns` from the worker. `on_line`
+    /// receives progress lines for UI rendering when given.
+    async fn retrieve_artifacts<F>(
+        &self,
+        pipeline: &TransferPipeline,
+        worker: &WorkerConfig,
+        artifact_patterns: &[String],
+        on_line: Option<F>,
+    ) -> Result<SyncResult>
+    where
+        F: FnMut(&str);
+}
+
+/// rsync over SSH (`transfer.backend = "rsync"`, the default).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RsyncBackend;
+
+impl SyncBackend for RsyncBackend {
+    /// Estimate transfer size using rsync dry-run.
+    ///
+    /// Returns `None` if estimation fails (e.g., rsync unavailable). Fail-open:
+    /// if estimation fails, proceed with transfer rather than blocking.
+    async fn estimate_transfer_size(
+        &self,
+        pipeline: &TransferPipeline,
+        worker: &WorkerConfig,
+    ) -> Option<TransferEstimate> {
+        let effective_excludes = pipeline.get_effective_excludes();
+        let start = std::time::Instant::now();
+
+        let mut cmd = Command::new("rsync");
+        cmd.env("LC_ALL", "C");
+
+        cmd.arg("-az");
+        add_portable_rsync_archive_args(&mut cmd);
+        cmd.arg("--dry-run").arg("--stats").arg("-e").arg(format!(
+            "{} -o StrictHostKeyChecking=accept-new -o BatchMode=yes -o ConnectTimeout=5",
+            worker.rsync_ssh_program()
+        ));
+
+        pipeline.add_exclude_args(&mut cmd, &effective_excludes);
+
+        let remote_path = pipeline.remote_path();
+        let escaped_remote_path = escape(Cow::from(&remote_path));
+        let destination = format!("{}@{}:{}", worker.user, worker.host, escaped_remote_path);
+
+        cmd.arg(format!("{}/", pipeline.project_root.display()))
+            .arg(&destination);
+
+        cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+
+        let output = match cmd.output().await {
+            Ok(output) => output,
+            Err(e) => {
+                debug!("Transfer estimation failed (rsync error): {}", e);
+                return None;
+            }
+        };
+
+        let estimation_ms = start.elapsed().as_millis() as u64;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        if !output.status.success() {
+            debug!(
+                "Transfer estimation failed (exit {}): {}",
+                output.status.code().unwrap_or(-1),
+                String::from_utf8_lossy(&output.stderr)
+            );
+            return None;
+        }
+
+        let bytes = crate::transfer::parse_rsync_total_size(&stdout).unwrap_or(0);
+        let files = crate::transfer::parse_rsync_total_files(&stdout).unwrap_or(0);
+
+        // Calculate estimated transfer time using configured or default bandwidth
+        // Default: 10 MB/s (reasonable for local network)
+        let bandwidth_bps = pipeline
+            .transfer_config
+            .estimated_bandwidth_bps
+            .unwrap_or(10 * 1024 * 1024);
+
+        let estimated_time_ms = if bandwidth_bps > 0 {
+            (bytes as f64 / bandwidth_bps as f64 * 1000.0).round() as u64
+        } else {
+            0
+        };
+
+        Some(TransferEstimate {
+            bytes,
+            files,
+            estimated_time_ms,
+            estimation_ms,
+        })
+    }
+
+    async fn sync_to_remote<F>(
+        &self,
+        pipeline: &TransferPipeline,
+        worker: &WorkerConfig,
+        on_line: Option<F>,
+    ) -> Result<SyncResult>
+    where
+        F: FnMut(&str),
+    {
+        match on_line {
+            Some(on_line) => Self::sync_streaming(pipeline, worker, on_line).await,
+            None => Self::sync_buffered(pipeline, worker).await,
+        }
+    }
+
+    async fn retrieve_artifacts<F>(
+        &self,
+        pipeline: &TransferPipeline,
+        worker: &WorkerConfig,
+        artifact_patterns: &[String],
+        on_line: Option<F>,
+    ) -> Result<SyncResult>
+    where
+        F: FnMut(&str),
+    {
+        // The archive has no per-file progress to stream, so it replaces both
+        // rsync paths. Mock transport keeps simulating the rsync path.
+        if pipeline.transfer_config.artifact_archive && !use_mock_transport(worker) {
+            return Self::retrieve_archive(pipeline, worker, artifact_patterns).await;
+        }
+        match on_line {
+            Some(on_line) => {
+                Self::retrieve_streaming(pipeline, worker, artifact_patterns, on_line).await
+            }
+            None => Self::retrieve_buffered(pipeline, worker, artifact_patterns).await,
+        }
+    }
+}
+
+impl RsyncBackend {
+    /// Uses retry logic with exponential backoff for transient network errors.
+    async fn sync_buffered(
+        pipeline: &TransferPipeline,
+        worker: &WorkerConfig,
+    ) -> Result<SyncResult> {
+        let remote_path = pipeline.remote_path();
+        let escaped_remote_path = escape(Cow::from(&remote_path));
+        let destination = format!("{}@{}:{}", worker.user, worker.host, escaped_remote_path);
+
+        // Get effective excludes (config defaults + .rchignore)
+        let effective_excludes = pipeline.get_effective_excludes();
+
+        if use_mock_transport(worker) {
+            // Mock path also uses retry logic for consistent behavior
+            // Create MockRsync ONCE and share via Arc so failure counters persist across retries
+            let rsync = std::sync::Arc::new(MockRsync::new(MockRsyncConfig::from_env()));
+            let project_root_str = pipeline.project_root.display().to_string();
+            let retry_config = pipeline.transfer_config.retry.clone();
+            let result = retry_with_backoff(&retry_config, "mock_sync_to_remote", || {
+                let rsync = rsync.clone();
+                let project_root = project_root_str.clone();
+                let dest = destination.clone();
+                let excludes = effective_excludes.clone();
+                async move { rsync.sync_to_remote(&project_root, &dest, &excludes).await }
+            })
+            .await?;
+            return Ok(SyncResult {
+                bytes_transferred: result.bytes_transferred,
+                files_transferred: result.files_transferred,
+                duration_ms: result.duration_ms,
+                ..Default::default()
+            });
+        }
+
+        info!(
+            "Syncing {} -> {} on {}",
+            pipeline.project_root.display(),
+            remote_path,
+            worker.id
+        );
+
+        debug!("Effective exclude patterns: {:?}", effective_excludes);
+
+        let start = std::time::Instant::now();
+
+        // Execute rsync with retry logic for transient errors
+        let retry_config = pipeline.effective_rsync_retry_config();
+        let output = execute_rsync_with_retry(&retry_config, "sync_to_remote", || {
+            pipeline.build_sync_command(
+                worker,
+                &destination,
+                &escaped_remote_path,
+                &effective_excludes,
+            )
+        })
+        .await?;
+
+        let duration = start.elapsed();
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+        if !output.status.success() {
+            // Check if the failure is retryable (it wasn't if we got here)
+            if is_retryable_transport_error(&anyhow::anyhow!("{}", stderr)) {
+                warn!(
+                    "rsync failed with retryable error (retries exhausted): {}",
+                    stderr
+                );
+            } else {
+                warn!("rsync failed: {}", stderr);
+            }
+            return Err(TransferError::SyncFailed {
+                reason: "rsync failed".to_string(),
+                exit_code: output.status.code(),
+                stderr: stderr.to_string(),
+            }
+            .into());
+        }
+
+        // rsync can exit 0 even when interrupted mid-file in edge cases. Treat a
+        // partial-transfer indicator on a "successful" sync as a failure rather
+        // than warning and returning Ok: otherwise the hook reports success, the
+        // remote source tree is incomplete, and the remote build compiles
+        // stale/partial sources, returning a trusted-but-wrong result.
+        if let Some(indicator) = detect_partial_transfer(&stderr) {
+            warn!(
+                "rsync exited 0 but reported a partial transfer (matched '{}'): {}",
+                indicator,
+                stderr.lines().next().unwrap_or(&stderr)
+            );
+            return Err(TransferError::SyncFailed {
+                reason: format!("partial transfer despite exit 0 ({indicator})"),
+                exit_code: output.status.code(),
+                stderr: stderr.to_string(),
+            }
+            .into());
+        }
+
+        info!("Sync completed in {}ms", duration.as_millis());
+
+        Ok(SyncResult {
+            bytes_transferred: parse_rsync_bytes(&stdout),
+            files_transferred: parse_rsync_files(&stdout),
+            duration_ms: duration.as_millis() as u64,
+            raw_bytes: parse_rsync_stat_bytes(&stdout, "Literal data:"),
+            compressed_bytes: parse_rsync_stat_bytes(&stdout, "Total bytes sent:"),
+        })
+    }
+
+    async fn sync_streaming<F>(
+        pipeline: &TransferPipeline,
+        worker: &WorkerConfig,
+        mut on_line: F,
+    ) -> Result<SyncResult>
+    where
+        F: FnMut(&str),
+    {
+        let remote_path = pipeline.remote_path();
+        let escaped_remote_path = escape(Cow::from(&remote_path));
+        let destination = format!("{}@{}:{}", worker.user, worker.host, escaped_remote_path);
+
+        // Get effective excludes (config defaults + .rchignore)
+        let effective_excludes = pipeline.get_effective_excludes();
+
+        if use_mock_transport(worker) {
+            let rsync = MockRsync::new(MockRsyncConfig::from_env());
+            let result = rsync
+                .sync_to_remote(
+                    &pipeline.project_root.display().to_string(),
+                    &destination,
+                    &effective_excludes,
+                )
+                .await?;
+            return Ok(SyncResult {
+                bytes_transferred: result.bytes_transferred,
+                files_transferred: result.files_transferred,
+                duration_ms: result.duration_ms,
+                ..Default::default()
+            });
+        }
+
+        info!(
+            "Syncing {} -> {} on {} (streaming)",
+            pipeline.project_root.display(),
+            remote_path,
+            worker.id
+        );
+
+        debug!("Effective exclude patterns: {:?}", effective_excludes);
+
+        // Rebuilt per retry attempt: rsync consumes its `Command`, and a
+        // transient SSH/rsync drop on this streaming path must reconnect from a
+        // fresh command rather than fail the whole transfer with zero retries.
+        let build_cmd = || {
+            pipeline.build_sync_streaming_command(
+                worker,
+                &destination,
+                &escaped_remote_path,
+                &effective_excludes,
+            )
+        };
+
+        debug!(
+            "Running (streaming): rsync {:?}",
+            build_cmd().as_std().get_args().collect::<Vec<_>>()
+        );
+
+        let retry_config = pipeline.effective_rsync_retry_config();
+        let (output, duration_ms) = run_command_streaming_with_retry(
+            &retry_config,
+            "sync_to_remote_streaming",
+            build_cmd,
+            |line| {
+                on_line(line);
+            },
+        )
+        .await?;
+
+        // Same exit-0-but-incomplete guard as the non-streaming sync_to_remote:
+        // run_command_streaming returns the combined stdout+stderr, so scan it for
+        // partial-transfer indicators and fail rather than report a success that
+        // would feed the remote build stale/partial sources.
+        if let Some(indicator) = detect_partial_transfer(&output) {
+            warn!(
+                "streaming rsync exited 0 but reported a partial transfer (matched '{}')",
+                indicator
+            );
+            return Err(TransferError::SyncFailed {
+                reason: format!("partial transfer despite exit 0 ({indicator})"),
+                exit_code: None,
+                stderr: output,
+            }
+            .into());
+        }
+
+        Ok(SyncResult {
+            bytes_transferred: parse_rsync_bytes(&output),
+            files_transferred: parse_rsync_files(&output),
+            duration_ms,
+            raw_bytes: parse_rsync_stat_bytes(&output, "Literal data:"),
+            compressed_bytes: parse_rsync_stat_bytes(&output, "Total bytes sent:"),
+        })
+    }
+
+    /// Uses retry logic with exponential backoff for transient network errors.
+    async fn retrieve_buffered(
+        pipeline: &TransferPipeline,
+        worker: &WorkerConfig,
+        artifact_patterns: &[String],
+    ) -> Result<SyncResult> {
+        let remote_path = pipeline.remote_path();
+        let escaped_remote_path = escape(Cow::from(&remote_path));
+
+        if use_mock_transport(worker) {
+            // Mock path also uses retry logic for consistent behavior
+            // Create MockRsync ONCE and share via Arc so failure counters persist across retries
+            let rsync = std::sync::Arc::new(MockRsync::new(MockRsyncConfig::from_env()));
+            let source = format!("{}@{}:{}/", worker.user, worker.host, escaped_remote_path);
+            let project_root_str = pipeline.project_root.display().to_string();
+            let patterns = artifact_patterns.to_vec();
+            let retry_config = pipeline.transfer_config.retry.clone();
+            let result = retry_with_backoff(&retry_config, "mock_retrieve_artifacts", || {
+                let rsync = rsync.clone();
+                let src = source.clone();
+                let dest = project_root_str.clone();
+                let pats = patterns.clone();
+                async move { rsync.retrieve_artifacts(&src, &dest, &pats).await }
+            })
+            .await?;
+            return Ok(SyncResult {
+                bytes_transferred: result.bytes_transferred,
+                files_transferred: result.files_transferred,
+                duration_ms: result.duration_ms,
+                ..Default::default()
+            });
+        }
+
+        info!("Retrieving artifacts from {} on {}", remote_path, worker.id);
+
+        let start = std::time::Instant::now();
+
+        // Execute rsync with retry logic for transient errors
+        let retry_config = pipeline.effective_rsync_retry_config();
+        let output = execute_rsync_with_retry(&retry_config, "retrieve_artifacts", || {
+            pipeline.build_retrieve_command(worker, &escaped_remote_path, artifact_patterns)
+        })
+        .await?;
+
+        let duration = start.elapsed();
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+        if !output.status.success() {
+            warn!("Artifact retrieval failed: {}", stderr);
+            return Err(TransferError::SyncFailed {
+                reason: "rsync artifact retrieval failed".to_string(),
+                exit_code: output.status.code(),
+                stderr: stderr.clone(),
+            }
+            .into());
+        }
+
+        // An exit-0 partial download leaves the local artifact tree incomplete;
+        // fail rather than report success (see sync_to_remote).
+        if let Some(indicator) = detect_partial_transfer(&stderr) {
+            warn!(
+                "rsync exited 0 but reported a partial artifact retrieval (matched '{}'): {}",
+                indicator,
+                stderr.lines().next().unwrap_or(&stderr)
+            );
+            return Err(TransferError::SyncFailed {
+                reason: format!("partial artifact retrieval despite exit 0 ({indicator})"),
+                exit_code: output.status.code(),
+                stderr: stderr.clone(),
+            }
+            .into());
+        }
+
+        let bytes_transferred = parse_rsync_bytes(&stdout);
+        let files_transferred = parse_rsync_files(&stdout);
+
+        // Warn if no artifacts were retrieved - this may indicate a build failure
+        // or misconfigured artifact patterns. We don't fail here because some
+        // commands (e.g., cargo check) don't produce artifacts.
+        if files_transferred == 0 && bytes_transferred == 0 {
+            warn!(
+                "No artifacts retrieved from {} - build may have failed or artifact patterns may be misconfigured",
+                worker.id
+            );
+            debug!("Artifact patterns used: {:?}", artifact_patterns);
+        }
+
+        info!(
+            "Artifacts retrieved in {}ms ({} files, {} bytes)",
+            duration.as_millis(),
+            files_transferred,
+            bytes_transferred
+        );
+
+        Ok(SyncResult {
+            bytes_transferred,
+            files_transferred,
+            duration_ms: duration.as_millis() as u64,
+            raw_bytes: parse_rsync_stat_bytes(&stdout, "Literal data:"),
+            compressed_bytes: parse_rsync_stat_bytes(&stdout, "Total bytes received:"),
+        })
+    }
+
+    async fn retrieve_streaming<F>(
+        pipeline: &TransferPipeline,
+        worker: &WorkerConfig,
+        artifact_patterns: &[String],
+        mut on_line: F,
+    ) -> Result<SyncResult>
+    where
+        F: FnMut(&str),
+    {
+        let remote_path = pipeline.remote_path();
+        let escaped_remote_path = escape(Cow::from(&remote_path));
+
+        if use_mock_transport(worker) {
+            let rsync = MockRsync::new(MockRsyncConfig::from_env());
+            let result = rsync
+                .retrieve_artifacts(
+                    &format!("{}@{}:{}/", worker.user, worker.host, escaped_remote_path),
+                    &pipeline.project_root.display().to_string(),
+                    artifact_patterns,
+                )
+                .await?;
+            return Ok(SyncResult {
+                bytes_transferred: result.bytes_transferred,
+                files_transferred: result.files_transferred,
+                duration_ms: result.duration_ms,
+                ..Default::default()
+            });
+        }
+
+        info!(
+            "Retrieving artifacts from {} on {} (streaming)",
+            remote_path, worker.id
+        );
+
+        // Rebuilt per retry attempt (see `sync_to_remote_streaming`): a transient
+        // transport drop while pulling artifacts must reconnect and retry instead
+        // of failing the build's artifact return outright.
+        let build_cmd = || {
+            pipeline.build_retrieve_streaming_command(
+                worker,
+                &escaped_remote_path,
+                artifact_patterns,
+            )
+        };
+
+        debug!(
+            "Running artifact retrieval (streaming): rsync {:?}",
+            build_cmd().as_std().get_args().collect::<Vec<_>>()
+        );
+
+        let retry_config = pipeline.effective_rsync_retry_config();
+        let (output, duration_ms) = run_command_streaming_with_retry(
+            &retry_config,
+            "retrieve_artifacts_streaming",
+            build_cmd,
+            |line| {
+                on_line(line);
+            },
+        )
+        .await?;
+
+        // An exit-0 partial download leaves the local artifact tree incomplete;
+        // fail rather than report success (see retrieve_artifacts).
+        if let Some(indicator) = detect_partial_transfer(&output) {
+            warn!(
+                "streaming rsync exited 0 but reported a partial artifact retrieval (matched '{}')",
+                indicator
+            );
+            return Err(TransferError::SyncFailed {
+                reason: format!("partial artifact retrieval despite exit 0 ({indicator})"),
+                exit_code: None,
+                stderr: output,
+            }
+            .into());
+        }
+
+        Ok(SyncResult {
+            bytes_transferred: parse_rsync_bytes(&output),
+            files_transferred: parse_rsync_files(&output),
+            duration_ms,
+            raw_bytes: parse_rsync_stat_bytes(&output, "Literal data:"),
+            compressed_bytes: parse_rsync_stat_bytes(&output, "Total bytes received:"),
+        })
+    }
+
+    /// `transfer.artifact_archive`: pull the artifacts as one tar+zstd
+    /// archive built on the worker (see the `archive` module), then unpack it
+    /// locally. Transient transport failures are retried like rsync's.
+    async fn retrieve_archive(
+        pipeline: &TransferPipeline,
+        worker: &WorkerConfig,
+        artifact_patterns: &[String],
+    ) -> Result<SyncResult> {
+        let remote_path = pipeline.remote_path();
+        info!(
+            "Retrieving artifacts from {} on {} (archive)",
+            remote_path, worker.id
+        );
+
+        let remote_command = archive::build_remote_archive_command(
+            &remote_path,
+            &pipeline.artifact_filter_args(artifact_patterns),
+            pipeline.compression_level_for_transfer(),
+        );
+        // `build_rsync_ssh_command` is a shell string (it is rsync's `-e`), so
+        // run it through `sh` with the target and remote command quoted.
+        let ssh_command = format!(
+            "exec {} {} {}",
+            pipeline.build_rsync_ssh_command(worker),
+            escape(Cow::from(format!("{}@{}", worker.user, worker.host))),
+            escape(Cow::from(remote_command)),
+        );
+        let archive_path =
+            std::env::temp_dir().join(format!("rch-artifacts-{}.tar.zst", uuid::Uuid::new_v4()));
+
+        let start = std::time::Instant::now();
+        let retry_config = pipeline.effective_rsync_retry_config();
+        let download = retry_with_backoff(&retry_config, "retrieve_artifact_archive", || async {
+            let archive_file = std::fs::File::create(&archive_path)
+                .with_context(|| format!("Failed to create {}", archive_path.display()))?;
+            let mut cmd = Command::new("sh");
+            cmd.arg("-c")
+                .arg(&ssh_command)
+                .env("LC_ALL", "C")
+                .stdin(Stdio::null())
+                .stdout(Stdio::from(archive_file))
+                .stderr(Stdio::piped())
+                .kill_on_drop(true);
+            let output = cmd
+                .spawn()
+                .map_err(|e| anyhow::anyhow!("ssh I/O error: {}", e))?
+                .wait_with_output()
+                .await
+                .map_err(|e| anyhow::anyhow!("ssh I/O error: {}", e))?;
+            // Same split as `execute_rsync_with_retry`: only transport errors
+            // are retried; other failures are reported below.
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                if is_retryable_transport_error_text(&stderr) {
+                    return Err(anyhow::anyhow!(
+                        "ssh transport error (exit {}): {}",
+                        output
+                            .status
+                            .code()
+                            .map_or_else(|| "signal".to_string(), |c| c.to_string()),
+                        stderr
+                    ));
+                }
+            }
+            Ok(output)
+        })
+        .await;
+
+        let result = match download {
+            Ok(output) if output.status.success() => {
+                let archive_bytes = std::fs::metadata(&archive_path)
+                    .map(|meta| meta.len())
+                    .unwrap_or(0);
+                let transfer_ms = start.elapsed().as_millis();
+                let extract_path = archive_path.clone();
+                let project_root = pipeline.project_root.clone();
+                let preserve_newer_local = pipeline.transfer_config.preserve_newer_local;
+                tokio::task::spawn_blocking(move || {
+                    archive::extract_compressed_artifact_archive(
+                        &extract_path,
+                        &project_root,
+                        preserve_newer_local,
+                    )
+                })
+                .await
+                .map_err(|e| anyhow::anyhow!("artifact archive extraction panicked: {}", e))
+                .and_then(|extracted| extracted)
+                .map(|stats| (stats, archive_bytes, transfer_ms))
+            }
+            Ok(output) => {
+                let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+                warn!("Artifact archive retrieval failed: {}", stderr);
+                Err(TransferError::SyncFailed {
+                    reason: "artifact archive retrieval failed".to_string(),
+                    exit_code: output.status.code(),
+                    stderr,
+                }
+                .into())
+            }
+            Err(e) => Err(e),
+        };
+        let _ = std::fs::remove_file(&archive_path);
+        let (stats, archive_bytes, transfer_ms) = result?;
+
+        let duration = start.elapsed();
+        if stats.files == 0 && stats.skipped_newer == 0 {
+            warn!(
+                "No artifacts retrieved from {} - build may have failed or artifact patterns may be misconfigured",
+                worker.id
+            );
+            debug!("Artifact patterns used: {:?}", artifact_patterns);
+        }
+        // Logged in the same shape as the rsync path so the two modes can be
+        // compared on the same project.
+        info!(
+            "Artifacts retrieved in {}ms ({} files, {} bytes; archive {} bytes, {}ms transfer + {}ms extract)",
+            duration.as_millis(),
+            stats.files,
+            stats.bytes,
+            archive_bytes,
+            transfer_ms,
+            duration.as_millis().saturating_sub(transfer_ms)
+        );
+
+        Ok(SyncResult {
+            bytes_transferred: archive_bytes,
+            files_transferred: stats.files,
+            duration_ms: duration.as_millis() as u64,
+            raw_bytes: stats.bytes,
+            compressed_bytes: archive_bytes,
+        })
+    }
+}
+
+/// Placeholder for object-store transfers (`transfer.backend = "rclone"`).
+///
+/// Not implemented yet: uploads and retrievals fail so the hook runs the build
+/// locally, and no size estimate is produced.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RcloneBackend;
+
+impl RcloneBackend {
+    fn unsupported(operation: &str) -> anyhow::Error {
+        TransferError::SyncFailed {
+            reason: format!("transfer.backend \"rclone\" does not support {operation} yet"),
+            exit_code: None,
+            stderr: String::new(),
+        }
+        .into()
+    }
+}
+
+impl SyncBackend for RcloneBackend {
+    async fn sync_to_remote<F>(
+        &self,
+        _pipeline: &TransferPipeline,
+        worker: &WorkerConfig,
+        _on_line: Option<F>,
+    ) -> Result<SyncResult>
+    where
+        F: FnMut(&str),
+    {
+        warn!(
+            "rclone backend selected for {}; sync is not implemented",
+            worker.id
+        );
+        Err(Self::unsupported("project sync"))
+    }
+
+    async fn retrieve_artifacts<F>(
+        &self,
+        _pipeline: &TransferPipeline,
+        worker: &WorkerConfig,
+        _artifact_patterns: &[String],
+        _on_line: Option<F>,
+    ) -> Result<SyncResult>
+    where
+        F: FnMut(&str),
+    {
+        warn!(
+            "rclone backend selected for {}; artifact retrieval is not implemented",
+            worker.id
+        );
+        Err(Self::unsupported("artifact retrieval"))
+    }
+}
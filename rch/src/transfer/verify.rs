@@ -0,0 +1,271 @@
+//! Post-retrieval artifact integrity check (`transfer.verify_artifacts`).
+//!
+//! rsync can exit 0 after a flaky retrieval that left a file truncated. When
+//! verification is enabled, [`TransferPipeline::verify_retrieved_artifacts`]
+//! picks a sample of the local files covered by the artifact patterns (most
+//! recently modified first, see [`select_artifacts_for_verification`]), hashes
+//! the same relative paths on the worker with `b3sum` (one shell loop per
+//! batch, see [`build_remote_artifact_hash_commands`]), and compares them with
+//! local blake3 hashes through [`rch_common::verify_artifacts`].
+//!
+//! Only files present on both sides are compared. A worker without `b3sum`
+//! reports [`REMOTE_HASH_UNAVAILABLE_MARKER`] and verification is skipped
+//! rather than failed. Include patterns without a literal directory prefix
+//! (`*.o`) only match files at the sync root.
+
+use super::*;
+use rch_common::{ArtifactManifest, FileHash, VerificationResult};
+use std::time::SystemTime;
+
+/// Line prefix for one remote hash: `RCH_HASH\t<size>\t<blake3>\t<path>`.
+pub const REMOTE_HASH_MARKER: &str = "RCH_HASH";
+
+/// Printed instead of hashes when the worker has no `b3sum`.
+pub const REMOTE_HASH_UNAVAILABLE_MARKER: &str = "RCH_HASH_UNAVAILABLE";
+
+/// Files hashed per SSH command, keeping the command line well under ARG_MAX.
+pub const REMOTE_HASH_BATCH_SIZE: usize = 256;
+
+/// Upper bound on local files considered before sampling.
+const MAX_VERIFY_CANDIDATES: usize = 50_000;
+
+impl TransferPipeline {
+    /// Verify retrieved artifacts against hashes computed on the worker.
+    ///
+    /// Returns `Ok(None)` when verification is disabled, no local file matches
+    /// the patterns, the transport is mocked, or the worker cannot hash.
+    pub async fn verify_retrieved_artifacts(
+        &self,
+        worker: &WorkerConfig,
+        artifact_patterns: &[String],
+    ) -> Result<Option<VerificationResult>> {
+        if !self.transfer_config.verify_artifacts {
+            return Ok(None);
+        }
+        let files = select_artifacts_for_verification(
+            &self.project_root,
+            artifact_patterns,
+            self.transfer_config.verify_max_size_bytes,
+            self.transfer_config.verify_sample_files,
+        );
+        if files.is_empty() || use_mock_transport(worker) {
+            debug!(
+                "Artifact verification skipped on {} ({} candidate files)",
+                worker.id,
+                files.len()
+            );
+            return Ok(None);
+        }
+
+        #[cfg(not(unix))]
+        {
+            return Err(crate::error::PlatformError::UnixOnly {
+                feature: "artifact verification".to_string(),
+            }
+            .into());
+        }
+
+        #[cfg(unix)]
+        {
+            let commands = build_remote_artifact_hash_commands(&self.remote_path(), &files);
+            let mut client = SshClient::new(worker.clone(), self.ssh_options.clone());
+            client.connect().await?;
+
+            let mut manifest = ArtifactManifest {
+                worker_id: Some(worker.id.to_string()),
+                ..ArtifactManifest::default()
+            };
+            let mut outcome = Ok(true);
+            for command in &commands {
+                outcome = match client.execute(command).await {
+                    Ok(result) if result.success() => {
+                        match parse_remote_artifact_hashes(&result.stdout) {
+                            Some(hashes) => {
+                                manifest.files.extend(hashes);
+                                Ok(true)
+                            }
+                            None => Ok(false),
+                        }
+                    }
+                    Ok(result) => Err(TransferError::SyncFailed {
+                        reason: "remote artifact hashing failed".to_string(),
+                        exit_code: Some(result.exit_code),
+                        stderr: result.stderr,
+                    }
+                    .into()),
+                    Err(e) => Err(e),
+                };
+                if !matches!(outcome, Ok(true)) {
+                    break;
+                }
+            }
+
+            if let Err(e) = client.disconnect().await {
+                warn!("Failed to disconnect SSH client after verification: {}", e);
+            }
+
+            if !outcome? {
+                warn!(
+                    "b3sum not found on {}; artifact verification skipped",
+                    worker.id
+                );
+                return Ok(None);
+            }
+
+            let result = rch_common::verify_artifacts(
+                &self.project_root,
+                &manifest,
+                self.transfer_config.verify_max_size_bytes,
+            );
+            Ok(Some(result))
+        }
+    }
+}
+
+/// Pick the local artifact files to verify, as paths relative to `root`.
+///
+/// Walks the files matched by the include patterns (excludes honored), drops
+/// files larger than `max_size`, and keeps the `sample` most recently
+/// modified ones (`0` keeps all). The result is sorted newest first.
+pub fn select_artifacts_for_verification(
+    root: &Path,
+    artifact_patterns: &[String],
+    max_size: u64,
+    sample: usize,
+) -> Vec<String> {
+    let (exclude_patterns, include_patterns) = partition_artifact_filters(artifact_patterns);
+    let options = glob::MatchOptions {
+        require_literal_separator: true,
+        ..glob::MatchOptions::new()
+    };
+    let excludes: Vec<Pattern> = exclude_patterns
+        .iter()
+        .filter_map(|pattern| Pattern::new(pattern.trim_end_matches('/')).ok())
+        .collect();
+
+    let mut candidates: HashMap<String, SystemTime> = HashMap::new();
+    for include in &include_patterns {
+        let include = include.trim_start_matches('/');
+        let Ok(pattern) = Pattern::new(include) else {
+            continue;
+        };
+        let prefix: Vec<&str> = include
+            .split('/')
+            .take_while(|component| !component.contains(['*', '?', '[']))
+            .collect();
+        let walk_root = prefix.join("/");
+        // A literal pattern names a single file; a glob without a literal
+        // directory prefix is only matched at the sync root.
+        let recursive = !walk_root.is_empty() && walk_root != include;
+        let start = if walk_root == include {
+            root.join(include)
+        } else {
+            root.join(&walk_root)
+        };
+
+        let mut stack = vec![start];
+        while let Some(path) = stack.pop() {
+            if candidates.len() >= MAX_VERIFY_CANDIDATES {
+                break;
+            }
+            let Ok(meta) = std::fs::symlink_metadata(&path) else {
+                continue;
+            };
+            let Ok(rel) = path.strip_prefix(root) else {
+                continue;
+            };
+            let rel = rel.to_string_lossy().replace('\\', "/");
+            if meta.is_dir() {
+                if (rel.is_empty() || recursive)
+                    && let Ok(entries) = std::fs::read_dir(&path)
+                {
+                    stack.extend(entries.flatten().map(|entry| entry.path()));
+                }
+                continue;
+            }
+            if !meta.is_file()
+                || meta.len() > max_size
+                || !pattern.matches_with(&rel, options)
+                || is_excluded_artifact(&rel, &excludes, options)
+            {
+                continue;
+            }
+            let modified = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            candidates.insert(rel, modified);
+        }
+    }
+
+    let mut files: Vec<(String, SystemTime)> = candidates.into_iter().collect();
+    files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    if sample > 0 {
+        files.truncate(sample);
+    }
+    files.into_iter().map(|(rel, _)| rel).collect()
+}
+
+/// rsync-style exclude check: a slash-free pattern matches any path
+/// component, otherwise it matches the path or one of its parent directories.
+fn is_excluded_artifact(rel: &str, excludes: &[Pattern], options: glob::MatchOptions) -> bool {
+    let components: Vec<&str> = rel.split('/').collect();
+    excludes.iter().any(|pattern| {
+        if pattern.as_str().contains('/') {
+            (1..=components.len())
+                .any(|n| pattern.matches_with(&components[..n].join("/"), options))
+        } else {
+            components
+                .iter()
+                .any(|component| pattern.matches_with(component, options))
+        }
+    })
+}
+
+/// Build the remote hashing commands for `files` (relative to `remote_path`),
+/// one per [`REMOTE_HASH_BATCH_SIZE`] files.
+pub fn build_remote_artifact_hash_commands(remote_path: &str, files: &[String]) -> Vec<String> {
+    let escaped_root = escape(Cow::from(remote_path));
+    files
+        .chunks(REMOTE_HASH_BATCH_SIZE)
+        .map(|batch| {
+            let paths = batch
+                .iter()
+                .map(|file| escape(Cow::from(file.as_str())).to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            format!(
+                "cd {escaped_root} || exit 44; command -v b3sum >/dev/null 2>&1 || {{ echo {REMOTE_HASH_UNAVAILABLE_MARKER}; exit 0; }}; for f in {paths}; do if [ -f \"$f\" ]; then printf '{REMOTE_HASH_MARKER}\\t%s\\t%s\\t%s\\n' \"$(wc -c < \"$f\" | tr -d ' ')\" \"$(b3sum --no-names -- \"$f\")\" \"$f\"; fi; done"
+            )
+        })
+        .collect()
+}
+
+/// Parse the output of a remote hashing command into `path -> hash`.
+///
+/// Returns `None` when the worker reported [`REMOTE_HASH_UNAVAILABLE_MARKER`].
+/// Malformed lines are ignored; files missing remotely are simply absent.
+pub fn parse_remote_artifact_hashes(output: &str) -> Option<HashMap<String, FileHash>> {
+    let mut hashes = HashMap::new();
+    for line in output.lines() {
+        if line.trim() == REMOTE_HASH_UNAVAILABLE_MARKER {
+            return None;
+        }
+        let mut fields = line.splitn(4, '\t');
+        if fields.next() != Some(REMOTE_HASH_MARKER) {
+            continue;
+        }
+        let (Some(size), Some(hash), Some(path)) = (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        let Ok(size) = size.trim().parse::<u64>() else {
+            continue;
+        };
+        hashes.insert(
+            path.to_string(),
+            FileHash {
+                hash: hash.trim().to_string(),
+                size,
+            },
+        );
+    }
+    Some(hashes)
+}
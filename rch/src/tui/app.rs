@@ -4,14 +4,15 @@
 
 use crate::status_display::{
     cancel_build, drain_worker, enable_worker, force_kill_build, query_daemon_full_status,
+    query_daemon_queue,
 };
-use crate::status_types::DaemonFullStatusResponse;
+use crate::status_types::{DaemonFullStatusResponse, QueueStatusFromApi};
 use crate::tui::{
     event::{Action, poll_event_with_flags},
     state::{
         ActiveBuild, BuildProgress, BuildStatus, CircuitState, ColorBlindMode, ConfirmAction,
-        ConfirmDialog, DaemonState, HistoricalBuild, Panel, Status, TuiState, WorkerState,
-        WorkerStatus,
+        ConfirmDialog, DaemonState, HistoricalBuild, Panel, QueuedBuild, Status, TuiState,
+        WorkerState, WorkerStatus,
     },
     widgets,
 };
@@ -121,9 +122,16 @@ async fn refresh_state(state: &mut TuiState, config: &TuiConfig) {
         Ok(response) => {
             update_state_from_daemon(state, response);
             state.error = None;
+            // The queue panel is best-effort: a daemon without `GET /queue`
+            // leaves it empty rather than failing the whole refresh.
+            match query_daemon_queue().await {
+                Ok(queue) => update_queue_from_daemon(state, queue),
+                Err(_) => state.queued_builds.clear(),
+            }
         }
         Err(e) => {
             state.daemon.status = Status::Stopped;
+            state.queued_builds.clear();
             state.error = Some(format!("Failed to connect to daemon: {}", e));
         }
     }
@@ -199,6 +207,7 @@ fn state_to_json(state: &TuiState) -> serde_json::Value {
     let selected_panel = match state.selected_panel {
         Panel::Workers => "workers",
         Panel::ActiveBuilds => "active_builds",
+        Panel::Queue => "queue",
         Panel::BuildHistory => "build_history",
         Panel::Logs => "logs",
     };
@@ -214,6 +223,7 @@ fn state_to_json(state: &TuiState) -> serde_json::Value {
         },
         "workers": &state.workers,
         "active_builds": &state.active_builds,
+        "queued_builds": &state.queued_builds,
         "build_history": state.build_history.iter().cloned().collect::<Vec<_>>(),
         "selected_panel": selected_panel,
         "selected_index": state.selected_index,
@@ -296,6 +306,25 @@ fn apply_mock_data(state: &mut TuiState) {
         status: BuildStatus::Compiling,
     }];
 
+    state.queued_builds = vec![
+        QueuedBuild {
+            id: 1,
+            project: "rch".to_string(),
+            command: "cargo test --workspace".to_string(),
+            slots: 8,
+            priority: rch_common::CommandPriority::High,
+            wait_secs: 95,
+        },
+        QueuedBuild {
+            id: 2,
+            project: "frontend".to_string(),
+            command: "cargo build".to_string(),
+            slots: 4,
+            priority: rch_common::CommandPriority::Normal,
+            wait_secs: 12,
+        },
+    ];
+
     state.build_history.clear();
     for i in 0..5 {
         state.build_history.push_back(HistoricalBuild {
@@ -364,6 +393,22 @@ fn build_mock_remediation_view() -> rch_common::remediation_view::RemediationVie
     assemble(&inputs, 1_700_000_000_000)
 }
 
+/// Replace the queue panel rows with the daemon's `GET /queue` snapshot.
+fn update_queue_from_daemon(state: &mut TuiState, queue: QueueStatusFromApi) {
+    state.queued_builds = queue
+        .builds
+        .into_iter()
+        .map(|b| QueuedBuild {
+            id: b.id,
+            project: b.project_id,
+            command: b.command,
+            slots: b.slots_needed,
+            priority: b.priority,
+            wait_secs: b.wait_secs,
+        })
+        .collect();
+}
+
 /// Convert daemon API response to TUI state types.
 fn update_state_from_daemon(state: &mut TuiState, response: DaemonFullStatusResponse) {
     // Operator-facing remediation snapshot (assembled by the daemon). Extract
@@ -849,6 +894,7 @@ mod tests {
             },
             test_stats: None,
             saved_time: None,
+            incremental: None,
             remediation: None,
         }
     }
@@ -864,6 +910,7 @@ mod tests {
             total_slots: 8,
             speed_score: 75.0,
             last_error: None,
+            health_failure: None,
             consecutive_failures: 0,
             recovery_in_secs: None,
             failure_history: vec![],
@@ -905,6 +952,8 @@ mod tests {
             detector_build_age_secs: Some(95),
             detector_slots_owned: Some(4),
             detector_last_evaluated_at: Some("2026-01-17T00:00:04Z".to_string()),
+            remote_pgid_file: None,
+            detached: false,
         }
     }
 
@@ -922,6 +971,9 @@ mod tests {
             bytes_transferred: Some(1234),
             timing: None,
             cancellation: None,
+            decision_trace: Vec::new(),
+            project_path: None,
+            toolchain: None,
         }
     }
 
@@ -1164,6 +1216,7 @@ mod tests {
         for panel in [
             Panel::Workers,
             Panel::ActiveBuilds,
+            Panel::Queue,
             Panel::BuildHistory,
             Panel::Logs,
         ] {
@@ -1173,6 +1226,7 @@ mod tests {
             let expected = match panel {
                 Panel::Workers => "workers",
                 Panel::ActiveBuilds => "active_builds",
+                Panel::Queue => "queue",
                 Panel::BuildHistory => "build_history",
                 Panel::Logs => "logs",
             };
@@ -1437,6 +1491,9 @@ mod tests {
         assert_eq!(state.active_builds.len(), 1);
         assert_eq!(state.active_builds[0].command, "cargo build --release");
 
+        assert_eq!(state.queued_builds.len(), 2);
+        assert_eq!(state.queued_builds[0].slots, 8);
+
         assert_eq!(state.build_history.len(), 5);
         info!("TEST PASS: test_apply_mock_data_populates_all_fields");
     }
@@ -1470,6 +1527,8 @@ mod tests {
             detector_build_age_secs: Some(110),
             detector_slots_owned: Some(4),
             detector_last_evaluated_at: Some("2026-01-17T00:00:04Z".to_string()),
+            remote_pgid_file: None,
+            detached: false,
         }];
 
         let history = vec![BuildRecordFromApi {
@@ -1485,6 +1544,9 @@ mod tests {
             bytes_transferred: None,
             timing: None,
             cancellation: None,
+            decision_trace: Vec::new(),
+            project_path: None,
+            toolchain: None,
         }];
 
         let response = make_response(vec![], active, history);
@@ -1533,6 +1595,7 @@ mod tests {
         for panel in [
             Panel::Workers,
             Panel::ActiveBuilds,
+            Panel::Queue,
             Panel::BuildHistory,
             Panel::Logs,
         ] {
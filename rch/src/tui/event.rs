@@ -108,6 +108,7 @@ fn handle_key(key: KeyEvent) -> Action {
         KeyCode::Char('2') => Action::JumpToPanel(1),
         KeyCode::Char('3') => Action::JumpToPanel(2),
         KeyCode::Char('4') => Action::JumpToPanel(3),
+        KeyCode::Char('5') => Action::JumpToPanel(4),
         KeyCode::Char('s') => Action::SortCycle,
         KeyCode::Char('S') => Action::SortReverse,
         _ => Action::Tick,
@@ -820,6 +821,15 @@ mod tests {
         info!("TEST PASS: test_handle_key_number_4_jump_logs");
     }
 
+    #[test]
+    fn test_handle_key_number_5_jump_queue() {
+        init_test_logging();
+        info!("TEST START: test_handle_key_number_5_jump_queue");
+        let key = KeyEvent::new(KeyCode::Char('5'));
+        assert_eq!(handle_key(key), Action::JumpToPanel(4));
+        info!("TEST PASS: test_handle_key_number_5_jump_queue");
+    }
+
     #[test]
     fn test_jump_to_panel_action_equality() {
         init_test_logging();
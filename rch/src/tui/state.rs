@@ -15,6 +15,8 @@ pub enum Panel {
     #[default]
     Workers,
     ActiveBuilds,
+    /// Builds waiting in the daemon queue for a worker.
+    Queue,
     BuildHistory,
     Logs,
 }
@@ -23,7 +25,8 @@ impl Panel {
     pub fn next(self) -> Self {
         match self {
             Panel::Workers => Panel::ActiveBuilds,
-            Panel::ActiveBuilds => Panel::BuildHistory,
+            Panel::ActiveBuilds => Panel::Queue,
+            Panel::Queue => Panel::BuildHistory,
             Panel::BuildHistory => Panel::Logs,
             Panel::Logs => Panel::Workers,
         }
@@ -33,7 +36,8 @@ impl Panel {
         match self {
             Panel::Workers => Panel::Logs,
             Panel::ActiveBuilds => Panel::Workers,
-            Panel::BuildHistory => Panel::ActiveBuilds,
+            Panel::Queue => Panel::ActiveBuilds,
+            Panel::BuildHistory => Panel::Queue,
             Panel::Logs => Panel::BuildHistory,
         }
     }
@@ -117,6 +121,8 @@ pub struct TuiState {
     pub daemon: DaemonState,
     pub workers: Vec<WorkerState>,
     pub active_builds: Vec<ActiveBuild>,
+    /// Builds waiting for a worker, in dispatch order (daemon `GET /queue`).
+    pub queued_builds: Vec<QueuedBuild>,
     pub build_history: VecDeque<HistoricalBuild>,
     pub selected_panel: Panel,
     pub selected_index: usize,
@@ -154,6 +160,7 @@ impl Default for TuiState {
             daemon: DaemonState::default(),
             workers: Vec::new(),
             active_builds: Vec::new(),
+            queued_builds: Vec::new(),
             build_history: VecDeque::with_capacity(100),
             selected_panel: Panel::Workers,
             selected_index: 0,
@@ -202,6 +209,7 @@ impl TuiState {
         match self.selected_panel {
             Panel::Workers => self.workers.len(),
             Panel::ActiveBuilds => self.active_builds.len(),
+            Panel::Queue => self.queued_builds.len(),
             Panel::BuildHistory => self.build_history.len(),
             Panel::Logs => 0,
         }
@@ -254,6 +262,13 @@ impl TuiState {
                     .unwrap_or_default();
                 Some(format!("{} → {} | {}{}", b.command, worker, b.id, progress,))
             }
+            Panel::Queue => {
+                let b = self.queued_builds.get(self.selected_index)?;
+                Some(format!(
+                    "{} | project: {} | slots: {} | priority: {} | waiting: {}s",
+                    b.command, b.project, b.slots, b.priority, b.wait_secs,
+                ))
+            }
             Panel::BuildHistory => {
                 let b = self.build_history.get(self.selected_index)?;
                 let worker = b.worker.as_deref().unwrap_or("local");
@@ -283,12 +298,14 @@ impl TuiState {
         self.selected_index = 0;
     }
 
-    /// Jump to a specific panel by index (0=Workers, 1=ActiveBuilds, 2=BuildHistory, 3=Logs).
+    /// Jump to a specific panel by index (0=Workers, 1=ActiveBuilds, 2=BuildHistory, 3=Logs,
+    /// 4=Queue).
     pub fn jump_to_panel(&mut self, index: u8) {
         let panel = match index {
             0 => Panel::Workers,
             1 => Panel::ActiveBuilds,
             2 => Panel::BuildHistory,
+            4 => Panel::Queue,
             _ => Panel::Logs,
         };
         self.selected_panel = panel;
@@ -322,7 +339,7 @@ impl TuiState {
                     });
                 }
             }
-            Panel::BuildHistory => {
+            Panel::Queue | Panel::BuildHistory => {
                 // Could show build details
             }
             Panel::Logs => {
@@ -345,6 +362,10 @@ impl TuiState {
                 .active_builds
                 .get(self.selected_index)
                 .map(|b| b.command.clone()),
+            Panel::Queue => self
+                .queued_builds
+                .get(self.selected_index)
+                .map(|b| b.command.clone()),
             Panel::BuildHistory => self
                 .build_history
                 .get(self.selected_index)
@@ -495,6 +516,19 @@ pub struct ActiveBuild {
     pub status: BuildStatus,
 }
 
+/// Build waiting in the daemon queue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedBuild {
+    pub id: u64,
+    pub project: String,
+    pub command: String,
+    /// Slots the build asked for.
+    pub slots: u32,
+    pub priority: rch_common::CommandPriority,
+    /// Seconds spent waiting so far.
+    pub wait_secs: u64,
+}
+
 /// Build progress information.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BuildProgress {
@@ -672,6 +706,8 @@ mod tests {
         state.next_panel();
         assert_eq!(state.selected_panel, Panel::ActiveBuilds);
         state.next_panel();
+        assert_eq!(state.selected_panel, Panel::Queue);
+        state.next_panel();
         assert_eq!(state.selected_panel, Panel::BuildHistory);
         state.next_panel();
         assert_eq!(state.selected_panel, Panel::Logs);
@@ -682,6 +718,8 @@ mod tests {
         assert_eq!(state.selected_panel, Panel::Logs);
         state.prev_panel();
         assert_eq!(state.selected_panel, Panel::BuildHistory);
+        state.prev_panel();
+        assert_eq!(state.selected_panel, Panel::Queue);
         info!("TEST PASS: test_panel_cycle_next_prev");
     }
 
@@ -1337,6 +1375,45 @@ mod tests {
         info!("TEST PASS: test_jump_to_panel_logs");
     }
 
+    #[test]
+    fn test_jump_to_panel_queue() {
+        init_test_logging();
+        info!("TEST START: test_jump_to_panel_queue");
+        let mut state = TuiState::default();
+        state.jump_to_panel(4);
+        assert_eq!(state.selected_panel, Panel::Queue);
+        info!("TEST PASS: test_jump_to_panel_queue");
+    }
+
+    #[test]
+    fn test_queue_panel_selection_and_detail() {
+        init_test_logging();
+        info!("TEST START: test_queue_panel_selection_and_detail");
+        let mut state = TuiState {
+            selected_panel: Panel::Queue,
+            ..Default::default()
+        };
+        assert!(state.selected_detail().is_none());
+        state.queued_builds.push(QueuedBuild {
+            id: 3,
+            project: "rch".to_string(),
+            command: "cargo test".to_string(),
+            slots: 8,
+            priority: rch_common::CommandPriority::High,
+            wait_secs: 42,
+        });
+        state.select_down();
+        assert_eq!(state.selected_index, 0);
+        let detail = state.selected_detail().unwrap();
+        assert!(detail.contains("project: rch"));
+        assert!(detail.contains("slots: 8"));
+        assert!(detail.contains("priority: high"));
+        assert!(detail.contains("waiting: 42s"));
+        state.copy_selected();
+        assert_eq!(state.last_copied.as_deref(), Some("cargo test"));
+        info!("TEST PASS: test_queue_panel_selection_and_detail");
+    }
+
     #[test]
     fn test_jump_to_panel_invalid_index_defaults_to_logs() {
         init_test_logging();
@@ -17,6 +17,7 @@ use ftui_widgets::block::{Alignment, Block};
 use ftui_widgets::borders::Borders;
 use ftui_widgets::list::{List, ListItem};
 use ftui_widgets::paragraph::Paragraph;
+use rch_common::CommandPriority;
 
 // ANSI 16 color palette as PackedRgba constants
 const C_WHITE: PackedRgba = PackedRgba::rgb(255, 255, 255);
@@ -333,13 +334,15 @@ fn render_main_content(frame: &mut Frame, area: Rect, state: &TuiState, colors:
 
     let build_chunks = Flex::vertical()
         .constraints([
-            Constraint::Percentage(50.0), // Active builds
-            Constraint::Percentage(50.0), // Build history
+            Constraint::Percentage(35.0), // Active builds
+            Constraint::Percentage(25.0), // Queue
+            Constraint::Percentage(40.0), // Build history
         ])
         .split(chunks[1]);
 
     render_active_builds_panel(frame, build_chunks[0], state, colors);
-    render_build_history_panel(frame, build_chunks[1], state, colors);
+    render_queue_panel(frame, build_chunks[1], state, colors);
+    render_build_history_panel(frame, build_chunks[2], state, colors);
 }
 
 /// Render the workers panel.
@@ -498,6 +501,72 @@ fn render_active_builds_panel(
     list.render(area, frame);
 }
 
+/// Render the wait-queue panel: depth and longest wait in the title, one line
+/// per queued build with its project, requested slots, priority, and wait.
+fn render_queue_panel(frame: &mut Frame, area: Rect, state: &TuiState, colors: &ColorScheme) {
+    let is_selected = state.selected_panel == Panel::Queue;
+    let border_style = if is_selected {
+        Style::new().fg(colors.highlight)
+    } else {
+        Style::new()
+    };
+
+    let items: Vec<ListItem> = state
+        .queued_builds
+        .iter()
+        .enumerate()
+        .map(|(i, b)| {
+            let (priority_icon, priority_color) = match b.priority {
+                CommandPriority::High => ("▲", colors.warning),
+                CommandPriority::Normal => (SYMBOLS.bullet_empty, colors.muted),
+                CommandPriority::Low => ("▼", colors.muted),
+            };
+
+            let style = if is_selected && i == state.selected_index {
+                Style::new().bg(colors.selected_bg).fg(colors.selected_fg)
+            } else {
+                Style::new()
+            };
+
+            let cmd = truncate_command(&b.command, 30);
+
+            ListItem::new(Line::from_spans(vec![
+                Span::styled(priority_icon, Style::new().fg(priority_color)),
+                Span::raw(" "),
+                Span::raw(cmd),
+                Span::styled(format!(" [{}]", b.project), Style::new().fg(colors.info)),
+                Span::styled(
+                    format!(
+                        " {} slots, {}",
+                        b.slots,
+                        format_duration_ms(b.wait_secs.saturating_mul(1000))
+                    ),
+                    Style::new().fg(colors.muted),
+                ),
+            ]))
+            .style(style)
+        })
+        .collect();
+
+    let title = match state.queued_builds.iter().map(|b| b.wait_secs).max() {
+        None => "Queue (empty)".to_string(),
+        Some(longest) => format!(
+            "Queue ({} waiting, longest {})",
+            state.queued_builds.len(),
+            format_duration_ms(longest.saturating_mul(1000))
+        ),
+    };
+
+    let list = List::new(items).block(
+        Block::new()
+            .borders(Borders::ALL)
+            .title(&title)
+            .border_style(border_style),
+    );
+
+    list.render(area, frame);
+}
+
 /// Render build history panel.
 fn render_build_history_panel(
     frame: &mut Frame,
@@ -724,7 +793,7 @@ fn render_help_overlay(frame: &mut Frame, colors: &ColorScheme) {
         Line::from("  ↑/k, ↓/j    Move selection up/down"),
         Line::from("  →/l, Tab    Next panel"),
         Line::from("  ←/h, S-Tab  Previous panel"),
-        Line::from("  1-4         Jump to panel by number"),
+        Line::from("  1-5         Jump to panel by number"),
         Line::from("  H / L       Jump to first / last panel"),
         Line::from("  g / G       Jump to first / last item"),
         Line::from("  Enter       Select/expand item"),
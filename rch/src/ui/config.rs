@@ -442,8 +442,11 @@ mod tests {
                 enabled: true,
                 force_local: false,
                 force_remote: false,
+                confirm_first_offload: false,
+                shadow_mode: false,
                 log_level: "info".to_string(),
                 socket_path: "/tmp/rch.sock".to_string(),
+                profile: None,
             },
             compilation: ConfigCompilationSection {
                 confidence_threshold: 0.8,
@@ -456,20 +459,47 @@ mod tests {
                 test_timeout_sec: 1800,
                 bun_timeout_sec: 600,
                 external_timeout_enabled: true,
+                timeouts: Default::default(),
+                require_rustc_match: None,
+                min_project_files: None,
+                min_project_bytes: None,
+                min_project_size_exempt_kinds: Vec::new(),
+                confidence_overrides: Default::default(),
             },
             transfer: ConfigTransferSection {
                 compression_level: 6,
                 exclude_patterns: vec!["target".to_string(), "node_modules".to_string()],
                 remote_base: "/tmp/rch".to_string(),
                 max_transfer_mb: None,
+                max_project_bytes: None,
                 max_transfer_time_ms: None,
                 bwlimit_kbps: None,
+                max_concurrent_client_transfers: None,
                 estimated_bandwidth_bps: None,
+                max_sync_seconds: None,
+                fleet: None,
+                min_free_disk_gb: 5.0,
+                container_image: None,
                 adaptive_compression: true,
                 min_compression_level: 1,
                 max_compression_level: 9,
                 verify_artifacts: true,
                 verify_max_size_bytes: 100 * 1024 * 1024,
+                verify_sample_files: 32,
+                artifact_deny_patterns: vec![],
+                preserve_newer_local: false,
+                artifact_archive: false,
+                artifact_max_retries: 2,
+                respect_gitignore: false,
+                gitignore_include: vec![],
+                force_include: vec![],
+                shared_target_dir: false,
+                keep_remote_on_failure: false,
+                keep_remote_hours: 24,
+                detach_remote_builds: false,
+                use_sccache: false,
+                skip_artifacts_for: Vec::new(),
+                backend: rch_common::TransferBackend::Rsync,
             },
             environment: ConfigEnvironmentSection {
                 allowlist: vec!["RUSTFLAGS".to_string()],
@@ -491,6 +521,7 @@ mod tests {
                 daemon_installs_hooks: true,
                 auto_start_cooldown_secs: 60,
                 auto_start_timeout_secs: 10,
+                state_dir: None,
             },
             sources: vec![
                 "Environment variables (RCH_*)".to_string(),
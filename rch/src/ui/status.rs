@@ -531,6 +531,8 @@ mod tests {
                 detector_build_age_secs: Some(130),
                 detector_slots_owned: Some(4),
                 detector_last_evaluated_at: Some("2026-01-19T00:00:12Z".to_string()),
+                remote_pgid_file: None,
+                detached: false,
             }],
             queued_builds: vec![],
             recent_builds: vec![],
@@ -550,6 +552,7 @@ mod tests {
             },
             test_stats: None,
             saved_time: None,
+            incremental: None,
             remediation: None,
         }
     }
@@ -348,6 +348,7 @@ mod tests {
                 total_slots: 8,
                 speed_score: 1.5,
                 last_error: None,
+                health_failure: None,
                 consecutive_failures: 0,
                 recovery_in_secs: None,
                 failure_history: vec![],
@@ -374,6 +375,7 @@ mod tests {
                 total_slots: 8,
                 speed_score: 0.0,
                 last_error: Some("Connection refused".to_string()),
+                health_failure: None,
                 consecutive_failures: 3,
                 recovery_in_secs: Some(45),
                 failure_history: vec![false, false, false],
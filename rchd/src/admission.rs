@@ -409,6 +409,9 @@ mod tests {
             bytes_transferred: Some(bytes),
             timing: None,
             cancellation: None,
+            decision_trace: Vec::new(),
+            project_path: None,
+            toolchain: None,
         }
     }
 
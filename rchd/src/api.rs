@@ -7,6 +7,7 @@
 use crate::DaemonContext;
 use crate::alerts::AlertInfo;
 use crate::events::EventBus;
+use crate::history::{self, BuildHistory};
 use crate::metrics;
 use crate::metrics::budget::{self, BudgetStatusResponse};
 use crate::reload;
@@ -18,20 +19,23 @@ use crate::workers::{
 use anyhow::{Result, anyhow};
 use chrono::{Duration as ChronoDuration, Utc};
 use rch_common::{
-    ApiError, BuildHeartbeatRequest, BuildRecord, BuildStats, BypassRecord, BypassRecordStore,
-    CircuitBreakerConfig, CircuitState, CommandPriority, ErrorCode, ReleaseRequest,
-    RequiredRuntime, SELECTION_RESPONSE_PROTOCOL_VERSION, SavedTimeStats, SelectedWorker,
-    SelectionReason, SelectionRequest, SelectionResponse, WorkerId, WorkerStatus,
-    default_bypass_record_path,
+    ApiError, BuildHeartbeatRequest, BuildHistoryGroup, BuildRecord, BuildStats, BuildTailEvent,
+    BypassRecord, BypassRecordStore, CircuitBreakerConfig, CircuitState, CommandPriority,
+    DecisionStage, DecisionTraceStep, ErrorCode, HealthFailureReason, IncrementalReuseStats,
+    QueueFairness, ReleaseRequest, RequiredRuntime, SELECTION_RESPONSE_PROTOCOL_VERSION,
+    SavedTimeStats, SelectedWorker, SelectionReason, SelectionRequest, SelectionResponse, WorkerId,
+    WorkerStatus, default_bypass_record_path,
+};
+use rch_telemetry::protocol::{
+    ReceivedTelemetry, TelemetrySource, TestRunRecord, TestRunStats, WorkerTelemetry,
 };
-use rch_telemetry::protocol::{TelemetrySource, TestRunRecord, TestRunStats, WorkerTelemetry};
 use rch_telemetry::speedscore::SpeedScore;
 use serde::Serialize;
 use std::path::PathBuf;
 use std::time::Duration;
 use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::net::UnixStream;
-use tracing::{debug, warn};
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 
 // ============================================================================
@@ -106,6 +110,9 @@ enum ApiRequest {
         /// Optional client-provided max queue wait timeout (seconds).
         /// Effective wait timeout is min(daemon queue timeout, client timeout).
         wait_timeout_secs: Option<u64>,
+        /// Claude Code session of the caller; groups waiters for round-robin
+        /// queueing.
+        session_id: Option<String>,
     },
     ReleaseWorker(ReleaseRequest),
     RecordBuild {
@@ -119,6 +126,7 @@ enum ApiRequest {
     TelemetryPoll {
         worker_id: WorkerId,
     },
+    TelemetryLatest,
     SpeedScore {
         worker_id: WorkerId,
     },
@@ -136,16 +144,42 @@ enum ApiRequest {
         worker_id: WorkerId,
     },
     Events,
+    /// Follow an active build's phase and output (`rch tail`).
+    Tail {
+        build_id: u64,
+        /// Buffered lines to replay before following.
+        lines: usize,
+    },
     Status,
     Metrics,
     Health,
     Ready,
     Budget,
+    /// Workers blocked after consecutive failed builds.
+    Blocklist,
+    /// Builds waiting for a worker, in dispatch order.
+    Queue,
+    /// Builds reaped after their hook stopped heartbeating.
+    Abandoned,
     SelfTestStatus,
     SelfTestHistory {
         limit: usize,
     },
     SelfTestRun(SelfTestRunRequest),
+    /// Query completed builds from the build history buffer.
+    BuildHistory {
+        project: Option<String>,
+        failed_only: bool,
+        /// Only builds completed within this many seconds.
+        since_secs: Option<u64>,
+        /// Aggregate per project, worker or kind instead of listing builds.
+        group_by: Option<crate::history::HistoryGroupBy>,
+        limit: usize,
+    },
+    /// One completed build with its decision trace (`rch explain`).
+    BuildDetail {
+        build_id: u64,
+    },
     Shutdown,
     /// Reload configuration (workers.toml) without restart.
     Reload,
@@ -214,6 +248,8 @@ pub struct DaemonFullStatus {
     pub test_stats: TestRunStats,
     /// Saved time statistics from remote builds.
     pub saved_time: SavedTimeStats,
+    /// Warm vs. cold remote rebuild durations.
+    pub incremental: IncrementalReuseStats,
     /// Operator-facing remediation view: a compact, redacted snapshot of the
     /// remediation posture (desired/live fleet, admissibility, proof queue,
     /// jobs, disk pressure, telemetry freshness, recent incidents) rendered by
@@ -266,6 +302,9 @@ pub struct WorkerStatusInfo {
     pub speed_score: f64,
     /// Last error message, if any.
     pub last_error: Option<String>,
+    /// Why the last health check failed, if it did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health_failure: Option<HealthFailureReason>,
     /// Consecutive failure count.
     pub consecutive_failures: u32,
     /// Seconds until circuit auto-recovers (None if not open or cooldown elapsed).
@@ -348,6 +387,11 @@ pub struct ActiveBuild {
     pub detector_slots_owned: u32,
     /// Last detector evaluation timestamp (ISO 8601), if available.
     pub detector_last_evaluated_at: Option<String>,
+    /// Remote pgid file reported by the hook, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_pgid_file: Option<String>,
+    /// Remote build runs detached and can be reattached with `rch attach`.
+    pub detached: bool,
 }
 
 /// Queued build information.
@@ -367,8 +411,12 @@ pub struct QueuedBuild {
     pub position: usize,
     /// Slots needed.
     pub slots_needed: u32,
+    /// Priority the hook requested for the command.
+    pub priority: CommandPriority,
     /// Estimated start time (ISO 8601), if available.
     pub estimated_start: Option<String>,
+    /// Time waiting in queue, in seconds.
+    pub wait_secs: u64,
     /// Time waiting in queue (formatted string, e.g., "2m 15s").
     pub wait_time: String,
 }
@@ -428,6 +476,30 @@ pub struct SelfTestRunRequest {
     pub retries: Option<u32>,
 }
 
+/// Response for `GET /blocklist`.
+#[derive(Debug, Serialize)]
+pub struct BlocklistResponse {
+    pub workers: Vec<crate::workers::BlocklistEntry>,
+}
+
+/// Response for `GET /abandoned`.
+#[derive(Debug, Serialize)]
+pub struct AbandonedResponse {
+    /// Abandoned builds still in the history buffer, most recent first.
+    pub builds: Vec<BuildRecord>,
+}
+
+/// Response for `GET /queue`.
+#[derive(Debug, Serialize)]
+pub struct QueueResponse {
+    /// Number of builds waiting.
+    pub depth: usize,
+    /// Longest time any queued build has waited, in seconds.
+    pub max_wait_secs: u64,
+    /// Queued builds in dispatch order.
+    pub builds: Vec<QueuedBuild>,
+}
+
 /// Status response for self-test scheduler.
 #[derive(Debug, Serialize)]
 pub struct SelfTestStatusResponse {
@@ -445,6 +517,18 @@ pub struct SelfTestHistoryResponse {
     pub results: Vec<crate::self_test::SelfTestResultRecord>,
 }
 
+/// Response for GET /history.
+#[derive(Debug, Serialize)]
+pub struct BuildHistoryResponse {
+    /// Matching builds, most recent first.
+    pub builds: Vec<BuildRecord>,
+    /// Total builds currently held in the history buffer (before filtering).
+    pub total_recorded: usize,
+    /// Per-group aggregates when `group_by` was requested (builds is then empty).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<BuildHistoryGroup>>,
+}
+
 /// Run response for self-tests.
 #[derive(Debug, Serialize)]
 pub struct SelfTestRunResponse {
@@ -675,15 +759,22 @@ pub async fn handle_connection(
             request,
             wait_for_worker,
             wait_timeout_secs,
+            session_id,
         }) => {
             metrics::inc_requests("select-worker");
-            let response =
-                handle_select_worker(&ctx, request, wait_for_worker, wait_timeout_secs).await?;
+            let response = handle_select_worker(
+                &ctx,
+                request,
+                wait_for_worker,
+                wait_timeout_secs,
+                session_id,
+            )
+            .await?;
             (selection_response_json(&response)?, "application/json")
         }
         Ok(ApiRequest::ReleaseWorker(mut request)) => {
             metrics::inc_requests("release-worker");
-            // Read optional JSON body line for timing breakdown
+            // Read optional JSON body line for timing breakdown and decision trace
             // Use a short timeout to avoid blocking when no body is sent
             let mut body_line = String::new();
             if let Ok(Ok(_)) = tokio::time::timeout(
@@ -691,14 +782,12 @@ pub async fn handle_connection(
                 read_line_with_limit(&mut reader, &mut body_line, MAX_LINE_SIZE),
             )
             .await
+                && let Some(body) = rch_common::ReleaseWorkerBody::parse(body_line.trim())
             {
-                let body = body_line.trim();
-                if !body.is_empty()
-                    && let Ok(timing) =
-                        serde_json::from_str::<rch_common::CommandTimingBreakdown>(body)
-                {
-                    request.timing = Some(timing);
-                }
+                request.timing = body.timing;
+                request.decision_trace = body.decision_trace;
+                request.project_path = body.project_path;
+                request.toolchain = body.toolchain;
             }
             handle_release_worker(&ctx, request).await?;
             ("{}".to_string(), "application/json")
@@ -846,6 +935,11 @@ pub async fn handle_connection(
             let response_json = serde_json::to_string(&response)?;
             (response_json, "application/json")
         }
+        Ok(ApiRequest::TelemetryLatest) => {
+            metrics::inc_requests("telemetry-latest");
+            let response = handle_telemetry_latest(&ctx).await;
+            (serde_json::to_string(&response)?, "application/json")
+        }
         Ok(ApiRequest::SpeedScore { worker_id }) => {
             metrics::inc_requests("speedscore");
             let response = handle_speedscore(&ctx, &worker_id).await;
@@ -881,6 +975,11 @@ pub async fn handle_connection(
             handle_event_stream(&mut writer, ctx.events.clone()).await?;
             return Ok(());
         }
+        Ok(ApiRequest::Tail { build_id, lines }) => {
+            metrics::inc_requests("tail");
+            handle_build_tail(&mut writer, &ctx.history, build_id, lines).await?;
+            return Ok(());
+        }
         Ok(ApiRequest::Status) => {
             metrics::inc_requests("status");
             let status = handle_status(&ctx).await?;
@@ -906,6 +1005,25 @@ pub async fn handle_connection(
             let budget_status = handle_budget();
             (serde_json::to_string(&budget_status)?, "application/json")
         }
+        Ok(ApiRequest::Blocklist) => {
+            metrics::inc_requests("blocklist");
+            let response = BlocklistResponse {
+                workers: ctx.pool.blocklist(),
+            };
+            (serde_json::to_string(&response)?, "application/json")
+        }
+        Ok(ApiRequest::Abandoned) => {
+            metrics::inc_requests("abandoned");
+            let response = AbandonedResponse {
+                builds: ctx.history.abandoned_builds(),
+            };
+            (serde_json::to_string(&response)?, "application/json")
+        }
+        Ok(ApiRequest::Queue) => {
+            metrics::inc_requests("queue");
+            let response = handle_queue(&ctx);
+            (serde_json::to_string(&response)?, "application/json")
+        }
         Ok(ApiRequest::SelfTestStatus) => {
             metrics::inc_requests("self-test-status");
             let status = ctx.self_test.status();
@@ -926,6 +1044,57 @@ pub async fn handle_connection(
             let response = SelfTestHistoryResponse { runs, results };
             (serde_json::to_string(&response)?, "application/json")
         }
+        Ok(ApiRequest::BuildHistory {
+            project,
+            failed_only,
+            since_secs,
+            group_by,
+            limit,
+        }) => {
+            metrics::inc_requests("history");
+            // A window too large to represent covers all of history.
+            let since = since_secs
+                .and_then(|secs| i64::try_from(secs).ok())
+                .and_then(ChronoDuration::try_seconds)
+                .and_then(|age| Utc::now().checked_sub_signed(age));
+            let (builds, groups) = match group_by {
+                Some(group_by) => (
+                    Vec::new(),
+                    Some(
+                        ctx.history
+                            .aggregate(project.as_deref(), failed_only, since, group_by),
+                    ),
+                ),
+                None => (
+                    ctx.history
+                        .query(project.as_deref(), failed_only, since, limit),
+                    None,
+                ),
+            };
+            let response = BuildHistoryResponse {
+                builds,
+                total_recorded: ctx.history.len(),
+                groups,
+            };
+            (serde_json::to_string(&response)?, "application/json")
+        }
+        Ok(ApiRequest::BuildDetail { build_id }) => {
+            metrics::inc_requests("build-detail");
+            match ctx.history.completed_build(build_id) {
+                Some(record) => (serde_json::to_string(&record)?, "application/json"),
+                None => {
+                    let error = if ctx.history.active_build(build_id).is_some() {
+                        format!("build {} is still running", build_id)
+                    } else {
+                        format!("build {} is not in the build history", build_id)
+                    };
+                    (
+                        serde_json::json!({ "status": "error", "error": error }).to_string(),
+                        "application/json",
+                    )
+                }
+            }
+        }
         Ok(ApiRequest::SelfTestRun(request)) => {
             metrics::inc_requests("self-test-run");
             let mut options = crate::self_test::SelfTestRunOptions {
@@ -970,28 +1139,7 @@ pub async fn handle_connection(
         }
         Ok(ApiRequest::Reload) => {
             metrics::inc_requests("reload");
-            // Perform reload using the reload module
-            let result = reload::reload_workers(&ctx.pool, None, true).await;
-            match result {
-                Ok(reload_result) => {
-                    let response = serde_json::json!({
-                        "success": true,
-                        "added": reload_result.added,
-                        "updated": reload_result.updated,
-                        "removed": reload_result.removed,
-                        "warnings": reload_result.warnings
-                    });
-                    (response.to_string(), "application/json")
-                }
-                Err(e) => {
-                    warn!("Configuration reload failed: {}", e);
-                    let response = serde_json::json!({
-                        "success": false,
-                        "error": e.to_string()
-                    });
-                    (response.to_string(), "application/json")
-                }
-            }
+            (handle_reload(&ctx).await, "application/json")
         }
         Ok(ApiRequest::CancelBuild { build_id, force }) => {
             metrics::inc_requests("cancel-build");
@@ -1071,6 +1219,36 @@ fn parse_request(line: &str) -> Result<ApiRequest> {
         return Ok(ApiRequest::Events);
     }
 
+    if let Some(query) = query_for_exact_route(path, "/tail") {
+        if method != "GET" {
+            return Err(anyhow!("Only GET method supported for tail"));
+        }
+
+        let mut build_id = None;
+        let mut lines = None;
+
+        for param in query.split('&') {
+            if param.is_empty() {
+                continue;
+            }
+            let mut kv = param.splitn(2, '=');
+            let key = kv.next().unwrap_or("");
+            let value = kv.next().unwrap_or("");
+
+            match key {
+                "build_id" => build_id = value.parse().ok(),
+                "lines" => lines = value.parse().ok(),
+                _ => {} // Ignore unknown parameters
+            }
+        }
+
+        let build_id = build_id.ok_or_else(|| anyhow!("Missing 'build_id' parameter"))?;
+        return Ok(ApiRequest::Tail {
+            build_id,
+            lines: lines.unwrap_or(crate::history::BUILD_OUTPUT_TAIL_LINES),
+        });
+    }
+
     if path == "/speedscores" && method == "GET" {
         return Ok(ApiRequest::SpeedScores);
     }
@@ -1254,6 +1432,16 @@ fn parse_request(line: &str) -> Result<ApiRequest> {
         }
     }
 
+    if method == "GET"
+        && let Some(rest) = split_path_query(path).0.strip_prefix("/builds/")
+    {
+        let rest = rest.trim_matches('/');
+        let build_id = rest
+            .parse::<u64>()
+            .map_err(|_| anyhow!("Invalid build id: {}", rest))?;
+        return Ok(ApiRequest::BuildDetail { build_id });
+    }
+
     if path == "/status" {
         return Ok(ApiRequest::Status);
     }
@@ -1274,6 +1462,18 @@ fn parse_request(line: &str) -> Result<ApiRequest> {
         return Ok(ApiRequest::Budget);
     }
 
+    if path == "/blocklist" && method == "GET" {
+        return Ok(ApiRequest::Blocklist);
+    }
+
+    if path == "/queue" && method == "GET" {
+        return Ok(ApiRequest::Queue);
+    }
+
+    if path == "/abandoned" && method == "GET" {
+        return Ok(ApiRequest::Abandoned);
+    }
+
     if path == "/self-test/status" {
         return Ok(ApiRequest::SelfTestStatus);
     }
@@ -1294,6 +1494,48 @@ fn parse_request(line: &str) -> Result<ApiRequest> {
         return Ok(ApiRequest::SelfTestHistory { limit });
     }
 
+    if let Some(query) = query_for_exact_route(path, "/history") {
+        let mut project = None;
+        let mut failed_only = false;
+        let mut since_secs = None;
+        let mut group_by = None;
+        let mut limit = 20usize;
+        for param in query.split('&') {
+            if param.is_empty() {
+                continue;
+            }
+            let mut kv = param.splitn(2, '=');
+            let key = kv.next().unwrap_or("");
+            let value = kv.next().unwrap_or("");
+            match key {
+                "project" => project = Some(percent_unescape_query_value(value)),
+                "limit" => limit = value.parse().unwrap_or(limit).min(10_000),
+                "failed_only" => failed_only = value == "1" || value.eq_ignore_ascii_case("true"),
+                "since_secs" => {
+                    since_secs = Some(
+                        value
+                            .parse()
+                            .map_err(|_| anyhow!("Invalid since_secs: {}", value))?,
+                    );
+                }
+                "group_by" => {
+                    group_by = Some(
+                        crate::history::HistoryGroupBy::parse(value)
+                            .ok_or_else(|| anyhow!("Invalid group_by: {}", value))?,
+                    );
+                }
+                _ => {}
+            }
+        }
+        return Ok(ApiRequest::BuildHistory {
+            project,
+            failed_only,
+            since_secs,
+            group_by,
+            limit,
+        });
+    }
+
     if let Some(query) = query_for_exact_route(path, "/self-test/run") {
         if method != "POST" {
             return Err(anyhow!("Only POST method supported for self-test run"));
@@ -1383,6 +1625,9 @@ fn parse_request(line: &str) -> Result<ApiRequest> {
             duration_ms,
             bytes_transferred,
             timing: None, // Parsed from body in handle_connection
+            decision_trace: Vec::new(),
+            project_path: None,
+            toolchain: None,
         }));
     }
 
@@ -1465,6 +1710,12 @@ fn parse_request(line: &str) -> Result<ApiRequest> {
                     worker_id: WorkerId::new(worker_id),
                 })
             }
+            "/telemetry/latest" => {
+                if method != "GET" {
+                    return Err(anyhow!("Only GET method supported for latest telemetry"));
+                }
+                Ok(ApiRequest::TelemetryLatest)
+            }
             "/telemetry/ingest" => {
                 if method != "POST" {
                     return Err(anyhow!(
@@ -1494,8 +1745,13 @@ fn parse_request(line: &str) -> Result<ApiRequest> {
     }
 
     // Worker state management endpoints: POST /workers/{id}/{action}
-    if path.starts_with("/workers/") && method == "POST" {
-        let rest = path.strip_prefix("/workers/").unwrap_or("");
+    // (`/worker/{id}/{action}` is accepted as an alias).
+    let worker_action_path = path
+        .strip_prefix("/workers/")
+        .or_else(|| path.strip_prefix("/worker/"));
+    if let Some(rest) = worker_action_path
+        && method == "POST"
+    {
         let (path_part, query) = split_path_query(rest);
         let parts: Vec<&str> = path_part.split('/').collect();
 
@@ -1623,7 +1879,12 @@ fn parse_request(line: &str) -> Result<ApiRequest> {
     let mut command_priority = CommandPriority::Normal;
     let mut classification_duration_us = None;
     let mut hook_pid = None;
+    let mut session_id = None;
     let mut preferred_workers = Vec::new();
+    let mut fleet = None;
+    let mut min_free_disk_gb = None;
+    let mut allowed_workers = Vec::new();
+    let mut idempotency_key = None;
 
     for param in query.split('&') {
         if param.is_empty() {
@@ -1667,12 +1928,33 @@ fn parse_request(line: &str) -> Result<ApiRequest> {
             "hook_pid" => {
                 hook_pid = value.parse().ok();
             }
+            "session" => {
+                session_id = Some(percent_unescape_query_value(value)).filter(|s| !s.is_empty());
+            }
             "worker" | "preferred_worker" | "preferred" => {
                 preferred_workers.extend(parse_worker_id_list(value));
             }
             "workers" | "preferred_workers" => {
                 preferred_workers.extend(parse_worker_id_list(value));
             }
+            "fleet" => {
+                fleet = Some(percent_unescape_query_value(value))
+                    .map(|name| name.trim().to_string())
+                    .filter(|name| !name.is_empty());
+            }
+            "min_free_disk_gb" => {
+                min_free_disk_gb = value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|gb| gb.is_finite() && *gb > 0.0);
+            }
+            "allowed_workers" => {
+                allowed_workers.extend(parse_worker_id_list(value));
+            }
+            "idempotency_key" => {
+                idempotency_key =
+                    Some(percent_unescape_query_value(value)).filter(|key| !key.is_empty());
+            }
             _ => {} // Ignore unknown parameters
         }
     }
@@ -1691,9 +1973,14 @@ fn parse_request(line: &str) -> Result<ApiRequest> {
             required_runtime,
             classification_duration_us,
             hook_pid,
+            fleet,
+            min_free_disk_gb,
+            allowed_workers,
+            idempotency_key,
         },
         wait_for_worker,
         wait_timeout_secs,
+        session_id,
     })
 }
 
@@ -1780,6 +2067,70 @@ struct TelemetryPollResponse {
     worker_id: Option<String>,
 }
 
+/// One worker's latest resource snapshot (`GET /telemetry/latest`, `rch top`).
+#[derive(Debug, Serialize)]
+struct WorkerResourceSnapshot {
+    worker_id: String,
+    status: WorkerStatus,
+    /// Newest telemetry the daemon holds for the worker (none once it ages
+    /// out of the in-memory retention window).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    telemetry: Option<ReceivedTelemetry>,
+    /// Disk space from the worker's last capabilities probe.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    disk_free_gb: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    disk_total_gb: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+struct TelemetryLatestResponse {
+    workers: Vec<WorkerResourceSnapshot>,
+}
+
+/// Latest telemetry and disk space for every configured worker, by ID.
+async fn handle_telemetry_latest(ctx: &DaemonContext) -> TelemetryLatestResponse {
+    let mut workers = Vec::new();
+    for worker in ctx.pool.all_workers().await {
+        let worker_id = worker.config.read().await.id.to_string();
+        let capabilities = worker.capabilities().await;
+        workers.push(WorkerResourceSnapshot {
+            telemetry: ctx.telemetry.latest(&worker_id),
+            worker_id,
+            status: worker.status().await,
+            disk_free_gb: capabilities.disk_free_gb,
+            disk_total_gb: capabilities.disk_total_gb,
+        });
+    }
+    workers.sort_by(|a, b| a.worker_id.cmp(&b.worker_id));
+    TelemetryLatestResponse { workers }
+}
+
+/// Re-read the daemon's workers config and apply the diff to the pool.
+///
+/// Unchanged workers keep their health and reservations; removed workers with
+/// builds in flight are drained rather than dropped (see `reload::apply_worker_diff`).
+async fn handle_reload(ctx: &DaemonContext) -> String {
+    match reload::reload_workers(&ctx.pool, ctx.workers_config.as_deref(), true).await {
+        Ok(reload_result) => serde_json::json!({
+            "success": true,
+            "added": reload_result.added,
+            "updated": reload_result.updated,
+            "removed": reload_result.removed,
+            "warnings": reload_result.warnings
+        })
+        .to_string(),
+        Err(e) => {
+            warn!("Configuration reload failed: {}", e);
+            serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })
+            .to_string()
+        }
+    }
+}
+
 async fn handle_telemetry_poll(ctx: &DaemonContext, worker_id: &WorkerId) -> TelemetryPollResponse {
     let worker = match ctx.pool.get(worker_id).await {
         Some(worker) => worker,
@@ -2079,12 +2430,101 @@ async fn handle_event_stream(
     Ok(())
 }
 
+/// How often `GET /tail` polls the build history for new output.
+const TAIL_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Stream an active build's phase changes and output lines as JSON lines
+/// ([`BuildTailEvent`]) until the build leaves the active set or the client
+/// disconnects.
+///
+/// Output arrives with the hook's heartbeats, so the stream lags the build
+/// by up to one heartbeat interval.
+async fn handle_build_tail(
+    writer: &mut tokio::net::unix::OwnedWriteHalf,
+    history: &BuildHistory,
+    build_id: u64,
+    replay_lines: usize,
+) -> Result<()> {
+    let header = "HTTP/1.0 200 OK\r\nContent-Type: application/json\r\n\r\n";
+    writer.write_all(header.as_bytes()).await?;
+
+    let Some(chunk) = history.build_output_since(build_id, 0) else {
+        let event = match history.completed_build(build_id) {
+            Some(record) => BuildTailEvent::Finished {
+                exit_code: Some(record.exit_code),
+            },
+            None => BuildTailEvent::NotFound,
+        };
+        write_tail_event(writer, &event).await?;
+        return Ok(());
+    };
+    let skip = chunk.lines.len().saturating_sub(replay_lines);
+    let mut next_seq = chunk.first_seq + skip as u64;
+    let mut last_phase = None;
+
+    loop {
+        let Some(state) = history.active_build(build_id) else {
+            let exit_code = history
+                .completed_build(build_id)
+                .map(|record| record.exit_code);
+            write_tail_event(writer, &BuildTailEvent::Finished { exit_code }).await?;
+            break;
+        };
+        let phase = (state.heartbeat_phase, state.heartbeat_detail);
+        if last_phase.as_ref() != Some(&phase) {
+            let event = BuildTailEvent::Phase {
+                phase: phase.0.clone(),
+                detail: phase.1.clone(),
+            };
+            write_tail_event(writer, &event).await?;
+            last_phase = Some(phase);
+        }
+
+        if let Some(chunk) = history.build_output_since(build_id, next_seq) {
+            if chunk.first_seq > next_seq {
+                let lines = chunk.first_seq - next_seq;
+                write_tail_event(writer, &BuildTailEvent::Skipped { lines }).await?;
+            }
+            for (offset, line) in chunk.lines.into_iter().enumerate() {
+                let seq = chunk.first_seq + offset as u64;
+                write_tail_event(writer, &BuildTailEvent::Line { seq, line }).await?;
+                next_seq = seq + 1;
+            }
+        }
+
+        tokio::time::sleep(TAIL_POLL_INTERVAL).await;
+    }
+
+    Ok(())
+}
+
+async fn write_tail_event(
+    writer: &mut tokio::net::unix::OwnedWriteHalf,
+    event: &BuildTailEvent,
+) -> Result<()> {
+    let mut line = serde_json::to_string(event)?;
+    line.push('\n');
+    writer.write_all(line.as_bytes()).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// Whether a failed selection is worth queueing for: every worker was busy,
+/// either out of slots or at its concurrent build cap.
+fn is_busy_reason(reason: &SelectionReason) -> bool {
+    matches!(
+        reason,
+        SelectionReason::AllWorkersBusy | SelectionReason::WorkerBuildCapReached
+    )
+}
+
 /// Handle a select-worker request.
 async fn handle_select_worker(
     ctx: &DaemonContext,
     request: SelectionRequest,
     wait_for_worker: bool,
     wait_timeout_secs: Option<u64>,
+    session_id: Option<String>,
 ) -> Result<SelectionResponse> {
     debug!(
         "Selecting worker for project '{}' with {} cores",
@@ -2133,6 +2573,31 @@ async fn handle_select_worker(
         });
     }
 
+    // A duplicate of a build still running (e.g. the hook fired twice for
+    // one tool call) follows that build instead of running it again.
+    if let Some(key) = request.idempotency_key.as_deref()
+        && let Some(build_id) = ctx.history.coalesce_target(key)
+    {
+        debug!(
+            "Coalescing duplicate request for project '{}' onto build {}",
+            request.project, build_id
+        );
+        ctx.events.emit(
+            "build_coalesced",
+            &serde_json::json!({
+                "build_id": build_id,
+                "project_id": request.project.clone(),
+                "command": request.command.clone(),
+            }),
+        );
+        return Ok(SelectionResponse {
+            worker: None,
+            reason: SelectionReason::CoalescedDuplicate,
+            build_id: Some(build_id),
+            diagnostics: None,
+        });
+    }
+
     async fn attempt_select_and_reserve(
         ctx: &DaemonContext,
         request: &SelectionRequest,
@@ -2166,13 +2631,15 @@ async fn handle_select_worker(
             // Reserve the slots.
             reservation_attempts += 1;
             if worker.reserve_slots(request.estimated_cores).await {
-                let (id, host, user, identity_file) = {
+                let (id, host, user, identity_file, nice_level, ionice_class) = {
                     let config = worker.config.read().await;
                     (
                         config.id.clone(),
                         config.host.clone(),
                         config.user.clone(),
                         config.identity_file.clone(),
+                        config.nice_level,
+                        config.ionice_class,
                     )
                 };
 
@@ -2211,6 +2678,9 @@ async fn handle_select_worker(
                             "slots": request.estimated_cores,
                         }),
                     );
+                    if let Some(key) = request.idempotency_key.clone() {
+                        ctx.history.register_idempotency_key(key, state.id);
+                    }
                     Some(state.id)
                 } else {
                     None
@@ -2218,6 +2688,20 @@ async fn handle_select_worker(
 
                 let slots_available = worker.available_slots().await;
                 let speed_score = worker.get_speed_score();
+                if let Some(build_id) = build_id {
+                    ctx.history.record_decision(
+                        build_id,
+                        DecisionTraceStep::new(
+                            DecisionStage::Selection,
+                            selection_trace_detail(
+                                id.as_str(),
+                                &selection_reason,
+                                speed_score,
+                                selection_diagnostics.as_ref(),
+                            ),
+                        ),
+                    );
+                }
 
                 if request.command_priority != CommandPriority::Normal {
                     ctx.events.emit(
@@ -2232,6 +2716,7 @@ async fn handle_select_worker(
                     );
                 }
 
+                let capabilities = worker.capabilities().await;
                 return Ok(SelectionResponse {
                     worker: Some(SelectedWorker {
                         id,
@@ -2240,6 +2725,13 @@ async fn handle_select_worker(
                         identity_file,
                         slots_available,
                         speed_score,
+                        upload_bandwidth_bps: worker.upload_bandwidth_bps(),
+                        rtt_ms: worker.last_latency_ms(),
+                        rustc_version: capabilities.rustc_version,
+                        sccache_version: capabilities.sccache_version,
+                        missing_tools: capabilities.missing_tools,
+                        nice_level,
+                        ionice_class,
                     }),
                     reason: selection_reason,
                     build_id,
@@ -2265,11 +2757,20 @@ async fn handle_select_worker(
         }
     }
 
-    let initial = attempt_select_and_reserve(ctx, &request).await?;
-    if initial.worker.is_some()
-        || !wait_for_worker
-        || initial.reason != SelectionReason::AllWorkersBusy
-    {
+    // Under round-robin queueing a waiting request must not jump ahead of
+    // builds already queued: it joins the queue without trying a worker first.
+    let round_robin = ctx.history.queue_fairness() == QueueFairness::RoundRobin;
+    let initial = if wait_for_worker && round_robin && !ctx.history.queue_is_empty() {
+        SelectionResponse {
+            worker: None,
+            reason: SelectionReason::AllWorkersBusy,
+            build_id: None,
+            diagnostics: None,
+        }
+    } else {
+        attempt_select_and_reserve(ctx, &request).await?
+    };
+    if initial.worker.is_some() || !wait_for_worker || !is_busy_reason(&initial.reason) {
         return Ok(initial);
     }
 
@@ -2280,11 +2781,13 @@ async fn handle_select_worker(
         .clone()
         .unwrap_or_else(|| "<unknown>".to_string());
 
-    let Some(queued) = ctx.history.enqueue_build(
+    let Some(queued) = ctx.history.enqueue_session_build(
         request.project.clone(),
         command.clone(),
         hook_pid,
         request.estimated_cores,
+        session_id,
+        request.command_priority,
     ) else {
         // Queue full - fall back to the normal busy response.
         return Ok(initial);
@@ -2294,6 +2797,7 @@ async fn handle_select_worker(
     if !cfg!(test) {
         metrics::set_build_queue_depth(ctx.history.queue_depth());
     }
+    let queued_position = ctx.history.queue_position(queued.id);
     ctx.events.emit(
         "build_queued",
         &serde_json::json!({
@@ -2301,8 +2805,9 @@ async fn handle_select_worker(
             "project_id": queued.project_id,
             "command": queued.command,
             "queued_at": queued.queued_at,
-            "position": ctx.history.queue_position(queued.id),
+            "position": queued_position,
             "slots_needed": queued.slots_needed,
+            "fairness": ctx.history.queue_fairness().to_string(),
         }),
     );
 
@@ -2367,18 +2872,34 @@ async fn handle_select_worker(
             });
         }
 
-        let response = attempt_select_and_reserve(ctx, &request).await?;
+        // Round-robin: wait until this build is at the head of the service
+        // order, or everything ahead of it is blocked.
+        if !ctx.history.is_queue_turn(queued.id) {
+            tokio::time::sleep(QUEUE_POLL_INTERVAL).await;
+            continue;
+        }
+
+        if round_robin {
+            ctx.history.set_queue_blocked(queued.id, false);
+        }
+        let mut response = attempt_select_and_reserve(ctx, &request).await?;
         if response.worker.is_some() {
             let _ = ctx.history.remove_queued_build(queued.id);
             ctx.history.update_queue_estimates();
             if !cfg!(test) {
                 metrics::set_build_queue_depth(ctx.history.queue_depth());
             }
+            if round_robin {
+                ctx.history.record_queue_service(&queued);
+                response.reason = SelectionReason::QueuedFair {
+                    position: queued_position.unwrap_or(1),
+                };
+            }
             return Ok(response);
         }
 
         // If conditions changed (e.g., all circuits open), stop waiting and fail-open.
-        if response.reason != SelectionReason::AllWorkersBusy {
+        if !is_busy_reason(&response.reason) {
             let _ = ctx.history.remove_queued_build(queued.id);
             ctx.history.update_queue_estimates();
             if !cfg!(test) {
@@ -2395,12 +2916,67 @@ async fn handle_select_worker(
             return Ok(response);
         }
 
+        // No worker can run this build now; let the builds behind it try.
+        if round_robin {
+            ctx.history.set_queue_blocked(queued.id, true);
+        }
         tokio::time::sleep(QUEUE_POLL_INTERVAL).await;
     }
 }
 
+/// Selection step of a build's decision trace.
+fn selection_trace_detail(
+    worker_id: &str,
+    reason: &SelectionReason,
+    speed_score: f64,
+    diagnostics: Option<&rch_common::SelectionDiagnostics>,
+) -> String {
+    let mut detail = format!("{} ({}, speed {:.1})", worker_id, reason, speed_score);
+    if let Some(diagnostics) = diagnostics {
+        let eligible = diagnostics
+            .workers
+            .iter()
+            .filter(|worker| {
+                worker.final_decision != rch_common::WorkerSelectionDiagnosticDecision::Deny
+            })
+            .count();
+        detail.push_str(&format!(
+            "; {} of {} workers eligible",
+            eligible,
+            diagnostics.workers.len()
+        ));
+        if diagnostics.active_project_exclusion_count > 0 {
+            detail.push_str(&format!(
+                ", {} excluded as already building this project",
+                diagnostics.active_project_exclusion_count
+            ));
+        }
+    }
+    detail
+}
+
 /// Handle a release-worker request.
-async fn handle_release_worker(ctx: &DaemonContext, request: ReleaseRequest) -> Result<()> {
+pub(crate) async fn handle_release_worker(
+    ctx: &DaemonContext,
+    request: ReleaseRequest,
+) -> Result<()> {
+    // A hook that outlived its heartbeat timeout finds its build already
+    // reaped; the slots went back then, so releasing them again would take
+    // them from another build.
+    if let Some(build_id) = request.build_id
+        && ctx.history.active_build(build_id).is_none()
+        && ctx
+            .history
+            .completed_build(build_id)
+            .is_some_and(|record| history::is_abandoned(&record))
+    {
+        info!(
+            "Late release for abandoned build {} on {}; slots were already reclaimed",
+            build_id, request.worker_id
+        );
+        return Ok(());
+    }
+
     let canonical_release = request
         .build_id
         .and_then(|build_id| ctx.history.active_build(build_id))
@@ -2416,20 +2992,51 @@ async fn handle_release_worker(ctx: &DaemonContext, request: ReleaseRequest) ->
         .release_slots(&release_worker_id, release_slots)
         .await;
 
+    if let Some(exit_code) = request.exit_code
+        && ctx.pool.record_build_exit(&release_worker_id, exit_code)
+    {
+        warn!(
+            "Worker {} blocked after consecutive failed builds",
+            release_worker_id
+        );
+        ctx.events.emit(
+            "worker_blocked",
+            &serde_json::json!({
+                "worker_id": release_worker_id.as_str(),
+                "reason": "consecutive_build_failures",
+            }),
+        );
+    }
+
+    if let (Some(bytes), Some(sync_up)) = (
+        request.bytes_transferred,
+        request.timing.as_ref().and_then(|timing| timing.sync_up),
+    ) && let Some(worker) = ctx.pool.get(&release_worker_id).await
+    {
+        worker.record_upload_sample(bytes, sync_up);
+    }
+
     if let Some(build_id) = request.build_id {
         let exit_code = request.exit_code.unwrap_or(0);
+        ctx.history
+            .set_replay_context(build_id, request.project_path, request.toolchain);
         let record = ctx.history.finish_active_build(
             build_id,
             exit_code,
             request.duration_ms,
             request.bytes_transferred,
             request.timing,
+            request.decision_trace,
         );
         if let Some(ref rec) = record {
             if !cfg!(test) {
                 metrics::dec_active_builds("remote");
                 let outcome = if exit_code == 0 { "success" } else { "failure" };
                 metrics::inc_build_total(outcome, "remote");
+                metrics::observe_build_duration("remote", rec.duration_ms as f64 / 1000.0);
+                if let Some(bytes) = rec.bytes_transferred {
+                    metrics::inc_transfer_bytes("upload", bytes);
+                }
             }
             ctx.events.emit(
                 "build_completed",
@@ -2957,6 +3564,7 @@ async fn handle_status(ctx: &DaemonContext) -> Result<DaemonFullStatus> {
             total_slots,
             speed_score: worker.get_speed_score(),
             last_error: worker.last_error().await,
+            health_failure: worker.health_failure().await,
             consecutive_failures: circuit_stats.consecutive_failures(),
             recovery_in_secs,
             failure_history: circuit_stats.recent_results().to_vec(),
@@ -3111,40 +3719,59 @@ async fn handle_status(ctx: &DaemonContext) -> Result<DaemonFullStatus> {
                 detector_build_age_secs: b.detector_build_age_secs,
                 detector_slots_owned: b.detector_slots_owned,
                 detector_last_evaluated_at: b.detector_last_evaluated_at,
+                remote_pgid_file: b.remote_pgid_file,
+                detached: b.detached,
             })
             .collect(),
-        queued_builds: ctx
-            .history
-            .queued_builds()
-            .into_iter()
-            .enumerate()
-            .map(|(i, b)| {
-                let wait_secs = b.queued_at_mono.elapsed().as_secs();
-                QueuedBuild {
-                    id: b.id,
-                    project_id: b.project_id,
-                    command: b.command,
-                    queued_at: b.queued_at,
-                    position: i + 1,
-                    slots_needed: b.slots_needed,
-                    estimated_start: b.estimated_start,
-                    wait_time: format_wait_time(wait_secs),
-                }
-            })
-            .collect(),
+        queued_builds: queued_builds(ctx),
         recent_builds,
         issues,
         alerts,
         stats,
         test_stats,
         saved_time: ctx.history.saved_time_stats(),
+        incremental: ctx.history.incremental_reuse_stats(),
         remediation,
     })
 }
 
-// ============================================================================
-// Repo Convergence Handlers (bd-vvmd.3.5)
-// ============================================================================
+/// Snapshot of the wait queue in dispatch order.
+fn queued_builds(ctx: &DaemonContext) -> Vec<QueuedBuild> {
+    ctx.history
+        .queued_builds()
+        .into_iter()
+        .enumerate()
+        .map(|(i, b)| {
+            let wait_secs = b.queued_at_mono.elapsed().as_secs();
+            QueuedBuild {
+                id: b.id,
+                project_id: b.project_id,
+                command: b.command,
+                queued_at: b.queued_at,
+                position: i + 1,
+                slots_needed: b.slots_needed,
+                priority: b.priority,
+                estimated_start: b.estimated_start,
+                wait_secs,
+                wait_time: format_wait_time(wait_secs),
+            }
+        })
+        .collect()
+}
+
+/// Handle `GET /queue`: queue depth and per-build wait times.
+fn handle_queue(ctx: &DaemonContext) -> QueueResponse {
+    let builds = queued_builds(ctx);
+    QueueResponse {
+        depth: builds.len(),
+        max_wait_secs: builds.iter().map(|b| b.wait_secs).max().unwrap_or(0),
+        builds,
+    }
+}
+
+// ============================================================================
+// Repo Convergence Handlers (bd-vvmd.3.5)
+// ============================================================================
 
 /// Build remediation suggestions based on worker convergence state.
 fn convergence_remediation(state: &crate::repo_convergence::WorkerConvergenceState) -> Vec<String> {
@@ -3428,6 +4055,7 @@ mod tests {
             version: "0.1.0",
             pid: 1234,
             queue_timeout_secs: 300,
+            workers_config: None,
         }
     }
 
@@ -3524,6 +4152,79 @@ mod tests {
         assert_eq!(ids, vec!["ts2", "vmi1", "vmi2", "fast"]);
     }
 
+    #[test]
+    fn test_parse_request_with_fleet() {
+        let _guard = test_guard!();
+        let req = parse_request("GET /select-worker?project=test&fleet=fast").unwrap();
+        let ApiRequest::SelectWorker { request: req, .. } = req else {
+            assert!(false, "expected select-worker request");
+            return;
+        };
+        assert_eq!(req.fleet.as_deref(), Some("fast"));
+
+        let req = parse_request("GET /select-worker?project=test&fleet=").unwrap();
+        let ApiRequest::SelectWorker { request: req, .. } = req else {
+            assert!(false, "expected select-worker request");
+            return;
+        };
+        assert_eq!(req.fleet, None);
+    }
+
+    #[test]
+    fn test_parse_request_with_idempotency_key() {
+        let _guard = test_guard!();
+        let req =
+            parse_request("GET /select-worker?project=test&idempotency_key=rch-build-0123abcd")
+                .unwrap();
+        let ApiRequest::SelectWorker { request: req, .. } = req else {
+            assert!(false, "expected select-worker request");
+            return;
+        };
+        assert_eq!(req.idempotency_key.as_deref(), Some("rch-build-0123abcd"));
+
+        let req = parse_request("GET /select-worker?project=test&idempotency_key=").unwrap();
+        let ApiRequest::SelectWorker { request: req, .. } = req else {
+            assert!(false, "expected select-worker request");
+            return;
+        };
+        assert_eq!(req.idempotency_key, None);
+    }
+
+    #[test]
+    fn test_parse_request_with_min_free_disk_gb() {
+        let _guard = test_guard!();
+        let req = parse_request("GET /select-worker?project=test&min_free_disk_gb=7.5").unwrap();
+        let ApiRequest::SelectWorker { request: req, .. } = req else {
+            assert!(false, "expected select-worker request");
+            return;
+        };
+        assert_eq!(req.min_free_disk_gb, Some(7.5));
+
+        for value in ["0", "-1", "abc"] {
+            let line = format!("GET /select-worker?project=test&min_free_disk_gb={value}");
+            let ApiRequest::SelectWorker { request: req, .. } = parse_request(&line).unwrap()
+            else {
+                assert!(false, "expected select-worker request");
+                return;
+            };
+            assert_eq!(req.min_free_disk_gb, None, "value {value}");
+        }
+    }
+
+    #[test]
+    fn test_parse_request_with_allowed_workers() {
+        let _guard = test_guard!();
+        let req =
+            parse_request("GET /select-worker?project=test&allowed_workers=css%2C%20fmd").unwrap();
+        let ApiRequest::SelectWorker { request: req, .. } = req else {
+            assert!(false, "expected select-worker request");
+            return;
+        };
+        let ids: Vec<&str> = req.allowed_workers.iter().map(|id| id.as_str()).collect();
+        assert_eq!(ids, vec!["css", "fmd"]);
+        assert!(req.preferred_workers.is_empty());
+    }
+
     #[test]
     fn test_parse_request_invalid_priority_defaults_to_normal() {
         let _guard = test_guard!();
@@ -3542,6 +4243,13 @@ mod tests {
         assert!(matches!(req, ApiRequest::Status), "expected status request");
     }
 
+    #[test]
+    fn test_parse_request_queue() {
+        let _guard = test_guard!();
+        let req = parse_request("GET /queue").unwrap();
+        assert!(matches!(req, ApiRequest::Queue), "expected queue request");
+    }
+
     #[test]
     fn test_parse_request_test_run() {
         let _guard = test_guard!();
@@ -3651,6 +4359,28 @@ mod tests {
         assert!(matches!(req, ApiRequest::Events), "expected events request");
     }
 
+    #[test]
+    fn test_parse_request_tail() {
+        let _guard = test_guard!();
+        let req = parse_request("GET /tail?build_id=42&lines=5").unwrap();
+        assert!(matches!(
+            req,
+            ApiRequest::Tail {
+                build_id: 42,
+                lines: 5
+            }
+        ));
+        let req = parse_request("GET /tail?build_id=42").unwrap();
+        assert!(matches!(
+            req,
+            ApiRequest::Tail {
+                build_id: 42,
+                lines: crate::history::BUILD_OUTPUT_TAIL_LINES
+            }
+        ));
+        assert!(parse_request("GET /tail").is_err());
+    }
+
     #[test]
     fn test_parse_request_reload() {
         let _guard = test_guard!();
@@ -3678,6 +4408,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_request_telemetry_latest() {
+        let _guard = test_guard!();
+        let req = parse_request("GET /telemetry/latest").unwrap();
+        assert!(matches!(req, ApiRequest::TelemetryLatest));
+        assert!(parse_request("POST /telemetry/latest").is_err());
+    }
+
     #[test]
     fn test_parse_request_self_test_status() {
         let _guard = test_guard!();
@@ -3698,6 +4436,62 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_request_build_history() {
+        let _guard = test_guard!();
+        let req = parse_request("GET /history?project=my%20proj&limit=5&failed_only=1").unwrap();
+        match req {
+            ApiRequest::BuildHistory {
+                project,
+                failed_only,
+                limit,
+                ..
+            } => {
+                assert_eq!(project.as_deref(), Some("my proj"));
+                assert!(failed_only);
+                assert_eq!(limit, 5);
+            }
+            _ => assert!(false, "expected build history request"),
+        }
+
+        let req = parse_request("GET /builds/42").unwrap();
+        assert!(matches!(req, ApiRequest::BuildDetail { build_id: 42 }));
+        assert!(parse_request("GET /builds/latest").is_err());
+
+        let req = parse_request("GET /history").unwrap();
+        match req {
+            ApiRequest::BuildHistory {
+                project,
+                failed_only,
+                since_secs,
+                group_by,
+                limit,
+            } => {
+                assert!(project.is_none());
+                assert!(!failed_only);
+                assert!(since_secs.is_none());
+                assert!(group_by.is_none());
+                assert_eq!(limit, 20);
+            }
+            _ => assert!(false, "expected build history request"),
+        }
+
+        let req = parse_request("GET /history?since_secs=86400&group_by=worker").unwrap();
+        match req {
+            ApiRequest::BuildHistory {
+                since_secs,
+                group_by,
+                ..
+            } => {
+                assert_eq!(since_secs, Some(86_400));
+                assert_eq!(group_by, Some(crate::history::HistoryGroupBy::Worker));
+            }
+            _ => assert!(false, "expected build history request"),
+        }
+        assert!(parse_request("GET /history?group_by=color").is_err());
+        assert!(parse_request("GET /history?since_secs=soon").is_err());
+    }
+
     #[test]
     fn test_parse_request_self_test_run() {
         let _guard = test_guard!();
@@ -3796,6 +4590,7 @@ mod tests {
         let cases = [
             "POST /benchmark/trigger-extra?worker=css",
             "GET /self-test/history-extra?limit=5",
+            "GET /history-extra?limit=5",
             "POST /self-test/run-extra?worker=css",
             "POST /release-worker-extra?worker=css&slots=4",
             "POST /record-build-extra?worker=css&project=myproject",
@@ -3910,6 +4705,9 @@ mod tests {
             total_slots,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         }
     }
 
@@ -3927,15 +4725,59 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
-        let response = handle_select_worker(&ctx, request, false, None)
+        let response = handle_select_worker(&ctx, request, false, None, None)
             .await
             .unwrap();
         assert!(response.worker.is_none());
         assert_eq!(response.reason, SelectionReason::NoWorkersConfigured);
     }
 
+    #[tokio::test]
+    async fn test_handle_select_worker_all_draining_until_enabled() {
+        let pool = WorkerPool::new();
+        pool.add_worker(make_test_worker("worker1", 8)).await;
+        pool.add_worker(make_test_worker("worker2", 8)).await;
+        let ctx = make_test_context(pool);
+
+        handle_worker_drain(&ctx, &WorkerId::new("worker1")).await;
+        handle_worker_drain(&ctx, &WorkerId::new("worker2")).await;
+
+        let request = SelectionRequest {
+            project: "test".to_string(),
+            command: None,
+            command_priority: CommandPriority::Normal,
+            estimated_cores: 2,
+            preferred_workers: vec![],
+            toolchain: None,
+            required_runtime: RequiredRuntime::default(),
+            classification_duration_us: None,
+            hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
+        };
+
+        let response = handle_select_worker(&ctx, request.clone(), false, None, None)
+            .await
+            .unwrap();
+        assert!(response.worker.is_none());
+        assert_eq!(response.reason, SelectionReason::AllWorkersDraining);
+
+        handle_worker_enable(&ctx, &WorkerId::new("worker2")).await;
+        let response = handle_select_worker(&ctx, request, false, None, None)
+            .await
+            .unwrap();
+        let worker = response.worker.expect("enabled worker should be selected");
+        assert_eq!(worker.id.as_str(), "worker2");
+    }
+
     #[tokio::test]
     async fn test_handle_select_worker_all_unreachable() {
         let pool = WorkerPool::new();
@@ -3959,9 +4801,13 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
-        let response = handle_select_worker(&ctx, request, false, None)
+        let response = handle_select_worker(&ctx, request, false, None, None)
             .await
             .unwrap();
         assert!(response.worker.is_none());
@@ -3988,9 +4834,13 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
-        let response = handle_select_worker(&ctx, request, false, None)
+        let response = handle_select_worker(&ctx, request, false, None, None)
             .await
             .unwrap();
         assert!(response.worker.is_none());
@@ -4013,9 +4863,13 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
-        let response = handle_select_worker(&ctx, request, false, None)
+        let response = handle_select_worker(&ctx, request, false, None, None)
             .await
             .unwrap();
         assert!(response.worker.is_some());
@@ -4047,9 +4901,13 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
-        let response = handle_select_worker(&ctx, request, false, None)
+        let response = handle_select_worker(&ctx, request, false, None, None)
             .await
             .unwrap();
 
@@ -4075,9 +4933,13 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: Some(4242),
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
-        let response = handle_select_worker(&ctx, request, false, None)
+        let response = handle_select_worker(&ctx, request, false, None, None)
             .await
             .unwrap();
         assert_eq!(response.reason, SelectionReason::Success);
@@ -4101,6 +4963,9 @@ mod tests {
                 duration_ms: None,
                 bytes_transferred: None,
                 timing: None,
+                decision_trace: Vec::new(),
+                project_path: None,
+                toolchain: None,
             },
         )
         .await
@@ -4132,9 +4997,13 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: Some(1001),
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
-        let first_response = handle_select_worker(&ctx, first_request, false, None)
+        let first_response = handle_select_worker(&ctx, first_request, false, None, None)
             .await
             .unwrap();
         assert_eq!(first_response.reason, SelectionReason::Success);
@@ -4153,9 +5022,13 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: Some(1002),
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
-        let second_response = handle_select_worker(&ctx, second_request, false, None)
+        let second_response = handle_select_worker(&ctx, second_request, false, None, None)
             .await
             .unwrap();
         assert!(second_response.worker.is_none());
@@ -4176,6 +5049,49 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_handle_select_worker_coalesces_duplicate_idempotency_key() {
+        let pool = WorkerPool::new();
+        pool.add_worker(make_test_worker("worker1", 8)).await;
+
+        let ctx = make_test_context(pool);
+        let request = |hook_pid: u32, key: &str| SelectionRequest {
+            project: "dup-project".to_string(),
+            command: Some("cargo build".to_string()),
+            command_priority: CommandPriority::Normal,
+            estimated_cores: 2,
+            preferred_workers: vec![],
+            toolchain: None,
+            required_runtime: RequiredRuntime::default(),
+            classification_duration_us: None,
+            hook_pid: Some(hook_pid),
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: Some(key.to_string()),
+        };
+
+        let first = handle_select_worker(&ctx, request(1001, "rch-build-1"), false, None, None)
+            .await
+            .unwrap();
+        assert_eq!(first.reason, SelectionReason::Success);
+        let first_build_id = first.build_id.expect("first request starts a build");
+
+        let duplicate = handle_select_worker(&ctx, request(1002, "rch-build-1"), false, None, None)
+            .await
+            .unwrap();
+        assert!(duplicate.worker.is_none());
+        assert_eq!(duplicate.reason, SelectionReason::CoalescedDuplicate);
+        assert_eq!(duplicate.build_id, Some(first_build_id));
+        assert_eq!(ctx.history.active_builds().len(), 1);
+
+        // A different key is not a duplicate (and is blocked on the worker instead).
+        let other = handle_select_worker(&ctx, request(1003, "rch-build-2"), false, None, None)
+            .await
+            .unwrap();
+        assert_ne!(other.reason, SelectionReason::CoalescedDuplicate);
+    }
+
     #[tokio::test]
     async fn test_handle_select_worker_routes_same_project_to_different_worker() {
         let pool = WorkerPool::new();
@@ -4193,9 +5109,13 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: Some(2001),
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
-        let first_response = handle_select_worker(&ctx, first_request, false, None)
+        let first_response = handle_select_worker(&ctx, first_request, false, None, None)
             .await
             .unwrap();
         assert_eq!(first_response.reason, SelectionReason::Success);
@@ -4214,9 +5134,13 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: Some(2002),
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
-        let second_response = handle_select_worker(&ctx, second_request, false, None)
+        let second_response = handle_select_worker(&ctx, second_request, false, None, None)
             .await
             .unwrap();
         assert_eq!(second_response.reason, SelectionReason::Success);
@@ -4242,9 +5166,13 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: Some(3001),
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
-        let first_response = handle_select_worker(&ctx, first_request, false, None)
+        let first_response = handle_select_worker(&ctx, first_request, false, None, None)
             .await
             .unwrap();
         assert_eq!(first_response.reason, SelectionReason::Success);
@@ -4259,9 +5187,13 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: Some(3002),
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
-        let second_response = handle_select_worker(&ctx, second_request, false, None)
+        let second_response = handle_select_worker(&ctx, second_request, false, None, None)
             .await
             .unwrap();
         assert_eq!(second_response.reason, SelectionReason::Success);
@@ -4288,9 +5220,13 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
-        let response = handle_select_worker(&ctx, request, false, None)
+        let response = handle_select_worker(&ctx, request, false, None, None)
             .await
             .unwrap();
         let worker = response.worker.unwrap();
@@ -4387,6 +5323,7 @@ mod tests {
             total_slots: 8,
             speed_score: 95.5,
             last_error: None,
+            health_failure: None,
             consecutive_failures: 0,
             recovery_in_secs: None,
             failure_history: vec![true, true, true],
@@ -4407,6 +5344,14 @@ mod tests {
         assert!(json.contains("\"id\":\"worker1\""));
         assert!(json.contains("\"used_slots\":2"));
         assert!(json.contains("\"speed_score\":95.5"));
+        assert!(!json.contains("health_failure"));
+
+        let unhealthy = WorkerStatusInfo {
+            health_failure: Some(HealthFailureReason::AuthFailed),
+            ..info
+        };
+        let json = serde_json::to_string(&unhealthy).unwrap();
+        assert!(json.contains("\"health_failure\":\"auth_failed\""));
     }
 
     #[test]
@@ -4475,6 +5420,8 @@ mod tests {
             detector_build_age_secs: 120,
             detector_slots_owned: 4,
             detector_last_evaluated_at: Some("2025-01-01T00:00:04Z".to_string()),
+            remote_pgid_file: None,
+            detached: false,
         };
         let json = serde_json::to_string(&build).unwrap();
         assert!(json.contains("\"id\":42"));
@@ -4495,12 +5442,16 @@ mod tests {
             queued_at: "2025-01-01T00:00:00Z".to_string(),
             position: 1,
             slots_needed: 4,
+            priority: CommandPriority::High,
             estimated_start: Some("2025-01-01T00:01:00Z".to_string()),
+            wait_secs: 90,
             wait_time: "1m 30s".to_string(),
         };
         let json = serde_json::to_string(&build).unwrap();
         assert!(json.contains("\"position\":1"));
         assert!(json.contains("\"slots_needed\":4"));
+        assert!(json.contains("\"priority\":\"high\""));
+        assert!(json.contains("\"wait_secs\":90"));
         assert!(json.contains("\"wait_time\":\"1m 30s\""));
     }
 
@@ -4657,6 +5608,22 @@ mod tests {
         assert!(matches!(req, ApiRequest::Budget));
     }
 
+    #[test]
+    fn test_parse_request_abandoned() {
+        let _guard = test_guard!();
+        let req = parse_request("GET /abandoned").unwrap();
+        assert!(matches!(req, ApiRequest::Abandoned));
+        assert!(parse_request("POST /abandoned").is_err());
+    }
+
+    #[test]
+    fn test_parse_request_blocklist() {
+        let _guard = test_guard!();
+        let req = parse_request("GET /blocklist").unwrap();
+        assert!(matches!(req, ApiRequest::Blocklist));
+        assert!(parse_request("POST /blocklist").is_err());
+    }
+
     #[test]
     fn test_parse_request_shutdown() {
         let _guard = test_guard!();
@@ -4730,6 +5697,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_request_worker_singular_alias() {
+        let _guard = test_guard!();
+        match parse_request("POST /worker/css/drain").unwrap() {
+            ApiRequest::WorkerDrain { worker_id } => assert_eq!(worker_id.as_str(), "css"),
+            _ => assert!(false, "expected worker drain request"),
+        }
+        match parse_request("POST /worker/css/enable").unwrap() {
+            ApiRequest::WorkerEnable { worker_id } => assert_eq!(worker_id.as_str(), "css"),
+            _ => assert!(false, "expected worker enable request"),
+        }
+    }
+
     #[test]
     fn test_parse_request_worker_disable() {
         let _guard = test_guard!();
@@ -4881,6 +5861,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_request_session_id() {
+        let _guard = test_guard!();
+        let req = parse_request("GET /select-worker?project=test&wait=1&session=abc-123").unwrap();
+        match req {
+            ApiRequest::SelectWorker { session_id, .. } => {
+                assert_eq!(session_id.as_deref(), Some("abc-123"));
+            }
+            _ => assert!(false, "expected select worker request"),
+        }
+
+        let req = parse_request("GET /select-worker?project=test&session=").unwrap();
+        match req {
+            ApiRequest::SelectWorker { session_id, .. } => assert_eq!(session_id, None),
+            _ => assert!(false, "expected select worker request"),
+        }
+    }
+
     // =========================================================================
     // Handler tests
     // =========================================================================
@@ -5493,6 +6491,9 @@ mod tests {
             duration_ms: None,
             bytes_transferred: None,
             timing: None,
+            decision_trace: Vec::new(),
+            project_path: None,
+            toolchain: None,
         };
 
         let result = handle_release_worker(&ctx, request).await;
@@ -5534,6 +6535,9 @@ mod tests {
             duration_ms: Some(5000),
             bytes_transferred: Some(1024 * 1024),
             timing: None,
+            decision_trace: Vec::new(),
+            project_path: None,
+            toolchain: None,
         };
 
         let result = handle_release_worker(&ctx, request).await;
@@ -5556,6 +6560,117 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_handle_reload_uses_daemon_workers_config_and_drains_busy_workers() {
+        let _guard = test_guard!();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let workers_path = temp_dir.path().join("workers.toml");
+        std::fs::write(
+            &workers_path,
+            r#"
+[[workers]]
+id = "kept"
+host = "10.0.0.1"
+user = "ubuntu"
+total_slots = 8
+enabled = true
+
+[[workers]]
+id = "added"
+host = "10.0.0.2"
+user = "ubuntu"
+total_slots = 4
+enabled = true
+"#,
+        )
+        .unwrap();
+
+        let pool = WorkerPool::new();
+        for (id, host) in [("kept", "10.0.0.1"), ("busy", "10.0.0.3")] {
+            pool.add_worker(WorkerConfig {
+                id: WorkerId::new(id),
+                host: host.to_string(),
+                user: "ubuntu".to_string(),
+                identity_file: "~/.ssh/id_rsa".to_string(),
+                total_slots: 8,
+                priority: 100,
+                tags: vec![],
+                max_concurrent_builds: None,
+                nice_level: None,
+                ionice_class: None,
+            })
+            .await;
+        }
+        let kept = pool.get(&WorkerId::new("kept")).await.unwrap();
+        assert!(kept.reserve_slots(2).await);
+        let busy = pool.get(&WorkerId::new("busy")).await.unwrap();
+        assert!(busy.reserve_slots(1).await);
+
+        let mut ctx = make_test_context(pool.clone());
+        ctx.workers_config = Some(workers_path);
+        let response: serde_json::Value = serde_json::from_str(&handle_reload(&ctx).await).unwrap();
+
+        assert_eq!(response["success"], true);
+        assert_eq!(response["added"], 1);
+        assert_eq!(response["removed"], 0);
+        assert_eq!(pool.len(), 3, "busy worker stays until its build finishes");
+        assert_eq!(kept.used_slots(), 2, "reservations survive the reload");
+        assert_eq!(busy.status().await, WorkerStatus::Draining);
+    }
+
+    #[tokio::test]
+    async fn test_handle_release_worker_stores_merged_decision_trace() {
+        let _guard = test_guard!();
+        let pool = WorkerPool::new();
+        pool.add_worker(make_test_worker("worker1", 8)).await;
+        let ctx = make_test_context(pool.clone());
+
+        let build = ctx.history.start_active_build(
+            "test-project".to_string(),
+            "worker1".to_string(),
+            "cargo build".to_string(),
+            12345,
+            4,
+            rch_common::BuildLocation::Remote,
+        );
+        ctx.history.record_decision(
+            build.id,
+            DecisionTraceStep::new(DecisionStage::Selection, "worker1"),
+        );
+
+        let request = ReleaseRequest {
+            worker_id: WorkerId::new("worker1"),
+            slots: 4,
+            build_id: Some(build.id),
+            exit_code: Some(0),
+            duration_ms: Some(5000),
+            bytes_transferred: None,
+            timing: None,
+            decision_trace: vec![
+                DecisionTraceStep::new(DecisionStage::Classification, "cargo_build"),
+                DecisionTraceStep::new(DecisionStage::Execution, "exit 0"),
+            ],
+            project_path: None,
+            toolchain: None,
+        };
+        handle_release_worker(&ctx, request).await.unwrap();
+
+        let record = ctx.history.completed_build(build.id).unwrap();
+        let stages: Vec<_> = record
+            .decision_trace
+            .iter()
+            .map(|step| step.stage)
+            .collect();
+        assert_eq!(
+            stages,
+            [
+                DecisionStage::Classification,
+                DecisionStage::Selection,
+                DecisionStage::Execution
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_handle_release_worker_uses_active_build_slots() {
         let _guard = test_guard!();
@@ -5584,6 +6699,9 @@ mod tests {
             duration_ms: Some(5000),
             bytes_transferred: Some(1024 * 1024),
             timing: None,
+            decision_trace: Vec::new(),
+            project_path: None,
+            toolchain: None,
         };
 
         handle_release_worker(&ctx, request).await.unwrap();
@@ -5620,6 +6738,9 @@ mod tests {
             duration_ms: Some(5000),
             bytes_transferred: Some(1024 * 1024),
             timing: None,
+            decision_trace: Vec::new(),
+            project_path: None,
+            toolchain: None,
         };
 
         let result = handle_release_worker(&ctx, request).await;
@@ -5666,10 +6787,12 @@ mod tests {
                 worker_id: WorkerId::new("worker1"),
                 hook_pid: Some(43210),
                 remote_pgid_file: Some("/tmp/rch/test-project/hash/.rch-run/99.pgid".to_string()),
+                detached: false,
                 phase: rch_common::BuildHeartbeatPhase::Execute,
                 detail: Some("Compiling".to_string()),
                 progress_counter: Some(4),
                 progress_percent: Some(18.0),
+                output_lines: Vec::new(),
             },
         )
         .await;
@@ -5688,6 +6811,69 @@ mod tests {
         assert_eq!(active.heartbeat_percent, Some(18.0));
     }
 
+    #[tokio::test]
+    async fn test_handle_build_tail_streams_output_until_finished() {
+        let _guard = test_guard!();
+        let history = BuildHistory::new(10);
+        let build = history.start_active_build(
+            "test-project".to_string(),
+            "worker1".to_string(),
+            "cargo build".to_string(),
+            43210,
+            4,
+            rch_common::BuildLocation::Remote,
+        );
+        history.record_build_heartbeat(BuildHeartbeatRequest {
+            build_id: build.id,
+            worker_id: WorkerId::new("worker1"),
+            hook_pid: Some(43210),
+            remote_pgid_file: None,
+            detached: false,
+            phase: rch_common::BuildHeartbeatPhase::Execute,
+            detail: None,
+            progress_counter: Some(1),
+            progress_percent: None,
+            output_lines: vec!["line one".to_string(), "line two".to_string()],
+        });
+
+        let (server, client) = UnixStream::pair().unwrap();
+        let (_server_reader, mut server_writer) = server.into_split();
+        let history = Arc::new(history);
+        let tail_history = Arc::clone(&history);
+        let tail = tokio::spawn(async move {
+            handle_build_tail(&mut server_writer, &tail_history, build.id, 1).await
+        });
+
+        let mut reader = BufReader::new(client);
+        let mut events = Vec::new();
+        let mut line = String::new();
+        while reader.read_line(&mut line).await.unwrap() > 0 {
+            if let Ok(event) = serde_json::from_str::<BuildTailEvent>(line.trim()) {
+                if matches!(event, BuildTailEvent::Line { .. }) {
+                    history.finish_active_build(build.id, 0, None, None, None, Vec::new());
+                }
+                events.push(event);
+            }
+            line.clear();
+        }
+        tail.await.unwrap().unwrap();
+
+        assert_eq!(
+            events,
+            vec![
+                BuildTailEvent::Phase {
+                    phase: rch_common::BuildHeartbeatPhase::Execute,
+                    detail: None,
+                },
+                BuildTailEvent::Line {
+                    seq: 1,
+                    line: "line two".to_string(),
+                },
+                BuildTailEvent::Finished { exit_code: Some(0) },
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_handle_build_heartbeat_rejects_worker_mismatch() {
         let _guard = test_guard!();
@@ -5711,10 +6897,12 @@ mod tests {
                 worker_id: WorkerId::new("worker-x"),
                 hook_pid: Some(5555),
                 remote_pgid_file: None,
+                detached: false,
                 phase: rch_common::BuildHeartbeatPhase::Execute,
                 detail: Some("Mismatch".to_string()),
                 progress_counter: Some(2),
                 progress_percent: None,
+                output_lines: Vec::new(),
             },
         )
         .await;
@@ -5754,6 +6942,36 @@ mod tests {
     // Status handler tests
     // =========================================================================
 
+    #[test]
+    fn test_handle_queue_reports_depth_and_priority() {
+        let _guard = test_guard!();
+        let ctx = make_test_context(WorkerPool::new());
+        assert_eq!(handle_queue(&ctx).depth, 0);
+
+        ctx.history
+            .enqueue_build("proj-a".into(), "cargo build".into(), 1, 4)
+            .unwrap();
+        ctx.history
+            .enqueue_session_build(
+                "proj-b".into(),
+                "cargo test".into(),
+                2,
+                8,
+                None,
+                CommandPriority::High,
+            )
+            .unwrap();
+
+        let queue = handle_queue(&ctx);
+        assert_eq!(queue.depth, 2);
+        assert_eq!(queue.builds[0].project_id, "proj-a");
+        assert_eq!(queue.builds[0].position, 1);
+        assert_eq!(queue.builds[0].priority, CommandPriority::Normal);
+        assert_eq!(queue.builds[1].slots_needed, 8);
+        assert_eq!(queue.builds[1].priority, CommandPriority::High);
+        assert_eq!(queue.builds[1].position, 2);
+    }
+
     #[tokio::test]
     async fn test_handle_status_empty_pool() {
         let _guard = test_guard!();
@@ -938,17 +938,6 @@ async fn execute_benchmark_on_worker(
 ) -> anyhow::Result<(f64, Duration)> {
     use tokio::process::Command;
 
-    // Expand tilde in identity file path
-    let identity_file = if worker.identity_file.starts_with("~/") {
-        if let Ok(home) = std::env::var("HOME") {
-            format!("{}{}", home, &worker.identity_file[1..])
-        } else {
-            worker.identity_file.clone()
-        }
-    } else {
-        worker.identity_file.clone()
-    };
-
     let start = std::time::Instant::now();
 
     // Build SSH command to run benchmark on worker
@@ -957,7 +946,9 @@ async fn execute_benchmark_on_worker(
     cmd.arg("-o").arg("StrictHostKeyChecking=accept-new");
     cmd.arg("-o")
         .arg(format!("ConnectTimeout={}", timeout.as_secs().min(30)));
-    cmd.arg("-i").arg(&identity_file);
+    if let Some(identity_file) = worker.ssh_identity() {
+        cmd.arg("-i").arg(identity_file);
+    }
     cmd.arg(format!("{}@{}", worker.user, worker.host));
     cmd.arg("~/.local/bin/rch-wkr benchmark --json");
     cmd.stdout(std::process::Stdio::piped());
@@ -1160,6 +1151,9 @@ mod tests {
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         }
     }
 
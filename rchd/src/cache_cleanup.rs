@@ -101,6 +101,8 @@ fn build_cleanup_command(escaped_base: &str, max_cache_age_hours: u64, min_free_
                rm -f \"$pgf\" 2>/dev/null; \
              fi; \
            done; \
+           find /tmp/rch-run -mindepth 2 -maxdepth 2 -type f \\( -name '*.out' -o -name '*.err' -o -name '*.exit' \\) \
+             -mmin +\"$max_age_minutes\" -delete 2>/dev/null || true; \
          fi; \
          candidates=$(mktemp /tmp/rch-cleanup.XXXXXX); \
          if [ \"$low_disk\" -eq 1 ]; then \
@@ -113,6 +115,7 @@ fn build_cleanup_command(escaped_base: &str, max_cache_age_hours: u64, min_free_
            [ -z \"$dir\" ] && continue; \
            [ ! -d \"$dir\" ] && continue; \
            case \"$dir\" in \"$base\"/*) ;; *) continue ;; esac; \
+           {skip_pinned}\
            recent_active=$(find \"$dir\" -type f -mmin -\"$active_grace_minutes\" -print -quit 2>/dev/null || true); \
            if [ -n \"$recent_active\" ]; then continue; fi; \
            size_kb=$(du -sk \"$dir\" 2>/dev/null | awk '{{print $1}}'); \
@@ -138,6 +141,7 @@ fn build_cleanup_command(escaped_base: &str, max_cache_age_hours: u64, min_free_
         threshold_kb = threshold_kb,
         active_grace_minutes = active_grace_minutes,
         orphan_after_minutes = orphan_after_minutes,
+        skip_pinned = rch_common::remote_keep::skip_pinned_snippet(),
     )
 }
 
@@ -644,6 +648,9 @@ mod tests {
             total_slots: 8,
             priority: 50,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         }
     }
 
@@ -1063,6 +1070,30 @@ mod tests {
         assert!(command.contains("remove_errors"));
     }
 
+    #[test]
+    fn test_build_cleanup_command_skips_pinned_dirs() {
+        let _guard = test_guard!();
+        let command = build_cleanup_command("'/tmp/rch'", 72, 10);
+        let scope_check = command
+            .find("case \"$dir\" in \"$base\"/*)")
+            .expect("scope check present");
+        let pin_check = command
+            .find("if [ -f \"$dir\"/.rch-keep ]")
+            .expect("pin check present");
+        let removal = command.find("if rm -rf \"$dir\"").expect("removal present");
+        assert!(scope_check < pin_check && pin_check < removal);
+    }
+
+    #[test]
+    fn test_build_cleanup_command_expires_detached_build_output() {
+        let _guard = test_guard!();
+        let command = build_cleanup_command("'/tmp/rch'", 72, 10);
+        assert!(command.contains(
+            "find /tmp/rch-run -mindepth 2 -maxdepth 2 -type f \\( -name '*.out' -o -name '*.err' -o -name '*.exit' \\)"
+        ));
+        assert!(command.contains("-mmin +\"$max_age_minutes\" -delete"));
+    }
+
     #[test]
     fn test_build_cleanup_command_orphan_sweeper_guards() {
         // The worker-side orphan sweeper must reap stuck pgid groups but be
@@ -571,7 +571,7 @@ impl CancellationOrchestrator {
         let config = worker.config.read().await;
         let host = config.host.clone();
         let user = config.user.clone();
-        let identity = config.identity_file.clone();
+        let identity = config.ssh_identity();
         drop(config);
 
         let remote_kill_script =
@@ -581,25 +581,22 @@ impl CancellationOrchestrator {
         // process stays alive holding a socket until its own keepalive
         // gives up — at exactly the moment we're trying to clean up after
         // a stuck build. Force a SIGKILL on cancellation.
-        let ssh_result = tokio::time::timeout(
-            self.config.remote_kill_timeout,
-            tokio::process::Command::new("ssh")
-                .args([
-                    "-o",
-                    "StrictHostKeyChecking=no",
-                    "-o",
-                    "ConnectTimeout=5",
-                    "-o",
-                    "BatchMode=yes",
-                    "-i",
-                    &identity,
-                    &format!("{}@{}", user, host),
-                    &remote_kill_script,
-                ])
-                .kill_on_drop(true)
-                .output(),
-        )
-        .await;
+        let mut ssh = tokio::process::Command::new("ssh");
+        ssh.args([
+            "-o",
+            "StrictHostKeyChecking=no",
+            "-o",
+            "ConnectTimeout=5",
+            "-o",
+            "BatchMode=yes",
+        ]);
+        if let Some(identity) = &identity {
+            ssh.arg("-i").arg(identity);
+        }
+        ssh.arg(format!("{}@{}", user, host))
+            .arg(&remote_kill_script)
+            .kill_on_drop(true);
+        let ssh_result = tokio::time::timeout(self.config.remote_kill_timeout, ssh.output()).await;
 
         match ssh_result {
             Ok(Ok(output)) => {
@@ -980,6 +977,7 @@ mod tests {
             version: "0.0.0-test",
             pid: std::process::id(),
             queue_timeout_secs: 300,
+            workers_config: None,
         }
     }
 
@@ -1102,7 +1100,7 @@ mod tests {
             0,
             rch_common::BuildLocation::Remote,
         );
-        let _ = history.finish_active_build(active.id, 0, None, None, None);
+        let _ = history.finish_active_build(active.id, 0, None, None, None, Vec::new());
 
         let ctx = make_test_context(pool, history);
         let orch = CancellationOrchestrator::new(test_config(), test_events());
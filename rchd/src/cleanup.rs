@@ -1,10 +1,28 @@
 //! Background cleanup for active builds with dead hooks.
-
-use crate::{DaemonContext, history::StuckDetectorSnapshot};
+//!
+//! Besides the evidence-scored stuck detector, a plain heartbeat timeout
+//! (`selection.heartbeat_timeout_secs`) reaps builds whose hook went silent
+//! without releasing its slots: the build is recorded as abandoned (listed by
+//! `GET /abandoned`) and its slots return to the worker.
+//!
+//! Silence alone is not enough: the hook only starts heartbeating once the
+//! build is underway, so a hook still holding its slots at the
+//! `confirm_offload` prompt (or otherwise between selection and its first
+//! heartbeat) is silent but alive. A build is only reaped once its hook
+//! process is gone.
+
+use crate::{
+    DaemonContext,
+    history::{ActiveBuildState, StuckDetectorSnapshot},
+};
+use rch_common::detached_build::{self, DetachedBuildFiles};
+use rch_common::{MIN_HEARTBEAT_TIMEOUT_SECS, ReleaseRequest, SshClient, SshOptions, WorkerId};
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tokio::time::interval;
-use tracing::{debug, warn};
+use tracing::{debug, info, warn};
 
 const HEARTBEAT_STALE_SECS: u64 = 20;
 const PROGRESS_STALE_SECS: u64 = 90;
@@ -12,6 +30,9 @@ const RECENT_PROGRESS_GRACE_SECS: u64 = 15;
 const MIN_BUILD_AGE_SECS: u64 = 30;
 const TRIAGE_BUDGET_MS: u64 = 50;
 const REMEDIATION_CONFIDENCE_THRESHOLD: f64 = 0.85;
+/// Minimum gap between exit-code probes of one orphaned detached build.
+const DETACHED_PROBE_INTERVAL: Duration = Duration::from_secs(30);
+const DETACHED_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
 
 fn duration_millis_u64(duration: Duration) -> u64 {
     u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
@@ -119,13 +140,45 @@ fn is_progress_stall_remediable_phase(phase: &rch_common::BuildHeartbeatPhase) -
     )
 }
 
+/// Effective heartbeat timeout for `selection.heartbeat_timeout_secs`:
+/// `None` when disabled, otherwise at least [`MIN_HEARTBEAT_TIMEOUT_SECS`].
+fn effective_heartbeat_timeout(secs: u64) -> Option<u64> {
+    (secs > 0).then(|| secs.max(MIN_HEARTBEAT_TIMEOUT_SECS))
+}
+
+/// Whether a build has gone `timeout_secs` without a heartbeat while holding
+/// slots and its hook has exited. Detached builds outlive their hook by design
+/// and are left to the exit-code probe.
+fn is_heartbeat_abandoned(
+    build: &ActiveBuildState,
+    hook_alive: bool,
+    heartbeat_age_secs: u64,
+    timeout_secs: u64,
+) -> bool {
+    !build.detached && !hook_alive && build.slots > 0 && heartbeat_age_secs >= timeout_secs
+}
+
 pub struct ActiveBuildCleanup {
     context: DaemonContext,
+    /// Last exit-code probe per detached build whose hook is gone.
+    detached_probes: Arc<Mutex<HashMap<u64, Instant>>>,
+    /// Seconds without a heartbeat before a build is reaped (`None` = never).
+    heartbeat_timeout_secs: Option<u64>,
 }
 
 impl ActiveBuildCleanup {
     pub fn new(context: DaemonContext) -> Self {
-        Self { context }
+        Self {
+            context,
+            detached_probes: Arc::new(Mutex::new(HashMap::new())),
+            heartbeat_timeout_secs: None,
+        }
+    }
+
+    /// Reap builds silent for `secs` (`selection.heartbeat_timeout_secs`; 0 = off).
+    pub fn with_heartbeat_timeout_secs(mut self, secs: u64) -> Self {
+        self.heartbeat_timeout_secs = effective_heartbeat_timeout(secs);
+        self
     }
 
     pub fn start(self) -> tokio::task::JoinHandle<()> {
@@ -145,6 +198,13 @@ impl ActiveBuildCleanup {
             return;
         }
         let active_build_count = active_builds.len();
+        {
+            let live: HashSet<u64> = active_builds.iter().map(|build| build.id).collect();
+            self.detached_probes
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .retain(|id, _| live.contains(id));
+        }
 
         let now = Instant::now();
         for build in active_builds {
@@ -186,6 +246,30 @@ impl ActiveBuildCleanup {
                 },
             );
 
+            // A detached build outlives its hook by design: wait for its
+            // recorded exit code rather than cancelling it.
+            if build.detached
+                && !evidence.hook_alive
+                && evidence.build_age_secs <= 86400
+                && build.remote_pgid_file.is_some()
+            {
+                self.probe_detached_build(&build, now);
+                continue;
+            }
+
+            if let Some(timeout_secs) = self.heartbeat_timeout_secs
+                && is_heartbeat_abandoned(
+                    &build,
+                    evidence.hook_alive,
+                    heartbeat_age_secs,
+                    timeout_secs,
+                )
+            {
+                self.reap_abandoned_build(&build, heartbeat_age_secs, timeout_secs)
+                    .await;
+                continue;
+            }
+
             if !evidence.should_remediate() {
                 if !evidence.hook_alive || evidence.heartbeat_stale || evidence.progress_stale {
                     debug!(
@@ -256,6 +340,138 @@ impl ActiveBuildCleanup {
             );
         }
     }
+
+    /// Record a silent build as abandoned and return its slots to the worker.
+    async fn reap_abandoned_build(
+        &self,
+        build: &ActiveBuildState,
+        heartbeat_age_secs: u64,
+        timeout_secs: u64,
+    ) {
+        // Claiming the build is the ownership gate: if its hook released it
+        // in the meantime, the slots are already back.
+        if self
+            .context
+            .history
+            .abandon_active_build(build.id, heartbeat_age_secs)
+            .is_none()
+        {
+            return;
+        }
+        self.context
+            .pool
+            .release_slots(&WorkerId::new(&build.worker_id), build.slots)
+            .await;
+
+        warn!(
+            build_id = build.id,
+            project_id = %build.project_id,
+            worker_id = %build.worker_id,
+            phase = ?build.heartbeat_phase,
+            hook_pid = build.hook_pid,
+            slots = build.slots,
+            hb_age = heartbeat_age_secs,
+            timeout = timeout_secs,
+            "Reaped abandoned build: no heartbeat for {}s, reclaimed {} slot(s)",
+            heartbeat_age_secs,
+            build.slots
+        );
+        self.context.events.emit(
+            "build_abandoned",
+            &serde_json::json!({
+                "build_id": build.id,
+                "project_id": build.project_id,
+                "worker_id": build.worker_id,
+                "slots": build.slots,
+                "heartbeat_age_secs": heartbeat_age_secs,
+                "heartbeat_timeout_secs": timeout_secs,
+            }),
+        );
+        if !cfg!(test) {
+            crate::metrics::dec_active_builds("remote");
+            crate::metrics::inc_build_total("abandoned", "remote");
+        }
+    }
+
+    /// Check (at most every [`DETACHED_PROBE_INTERVAL`]) whether an orphaned
+    /// detached build has finished, and release its slots once it has.
+    fn probe_detached_build(&self, build: &ActiveBuildState, now: Instant) {
+        {
+            let mut probes = self
+                .detached_probes
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            if probes
+                .get(&build.id)
+                .is_some_and(|last| now.duration_since(*last) < DETACHED_PROBE_INTERVAL)
+            {
+                return;
+            }
+            probes.insert(build.id, now);
+        }
+
+        let Some(pgid_file) = build.remote_pgid_file.clone() else {
+            return;
+        };
+        let context = self.context.clone();
+        let build_id = build.id;
+        let worker_id = build.worker_id.clone();
+        let slots = build.slots;
+        tokio::spawn(async move {
+            let Some(exit_code) = probe_detached_exit(&context, &worker_id, &pgid_file).await
+            else {
+                debug!(build_id, worker_id = %worker_id, "Detached build still running");
+                return;
+            };
+            info!(
+                build_id,
+                worker_id = %worker_id,
+                exit_code,
+                "Detached build finished without its hook; releasing slots"
+            );
+            let request = ReleaseRequest {
+                worker_id: WorkerId::new(&worker_id),
+                slots,
+                build_id: Some(build_id),
+                exit_code: Some(exit_code),
+                duration_ms: None,
+                bytes_transferred: None,
+                timing: None,
+                decision_trace: Vec::new(),
+                project_path: None,
+                toolchain: None,
+            };
+            if let Err(e) = crate::api::handle_release_worker(&context, request).await {
+                warn!("Failed to release detached build {}: {}", build_id, e);
+            }
+        });
+    }
+}
+
+/// Read a detached build's recorded exit code from its worker.
+async fn probe_detached_exit(
+    context: &DaemonContext,
+    worker_id: &str,
+    pgid_file: &str,
+) -> Option<i32> {
+    let worker = context.pool.get(&WorkerId::new(worker_id)).await?;
+    let worker_config = worker.config.read().await.clone();
+    let ssh_options = SshOptions {
+        connect_timeout: DETACHED_PROBE_TIMEOUT,
+        command_timeout: DETACHED_PROBE_TIMEOUT,
+        control_master: false,
+        ..Default::default()
+    };
+
+    let mut client = SshClient::new(worker_config, ssh_options);
+    if let Err(e) = client.connect().await {
+        debug!("Detached build probe could not reach {}: {}", worker_id, e);
+        return None;
+    }
+    let command = detached_build::exit_probe_command(&DetachedBuildFiles::for_pgid_file(pgid_file));
+    let result = client.execute(&command).await;
+    let _ = client.disconnect().await;
+    detached_build::parse_exit_probe(&result.ok()?.stdout)
 }
 
 fn is_process_alive(pid: u32) -> bool {
@@ -485,6 +701,45 @@ mod tests {
         assert!(!evidence.should_remediate());
     }
 
+    #[test]
+    fn test_effective_heartbeat_timeout_disables_and_clamps() {
+        let _guard = test_guard!();
+        assert_eq!(effective_heartbeat_timeout(0), None);
+        assert_eq!(
+            effective_heartbeat_timeout(5),
+            Some(MIN_HEARTBEAT_TIMEOUT_SECS)
+        );
+        assert_eq!(effective_heartbeat_timeout(300), Some(300));
+    }
+
+    #[test]
+    fn test_is_heartbeat_abandoned_skips_live_hooks_detached_and_slotless_builds() {
+        let _guard = test_guard!();
+        let history = crate::history::BuildHistory::new(10);
+        let mut build = history.start_active_build(
+            "proj".to_string(),
+            "worker-a".to_string(),
+            "cargo build".to_string(),
+            0,
+            4,
+            rch_common::BuildLocation::Remote,
+        );
+
+        assert!(!is_heartbeat_abandoned(&build, false, 299, 300));
+        assert!(is_heartbeat_abandoned(&build, false, 300, 300));
+
+        // A live hook may be waiting at the confirm_offload prompt before its
+        // first heartbeat; it keeps its slots however long that takes.
+        assert!(!is_heartbeat_abandoned(&build, true, 900, 300));
+
+        build.detached = true;
+        assert!(!is_heartbeat_abandoned(&build, false, 900, 300));
+
+        build.detached = false;
+        build.slots = 0;
+        assert!(!is_heartbeat_abandoned(&build, false, 900, 300));
+    }
+
     proptest! {
         #![proptest_config(ProptestConfig::with_cases(512))]
 
@@ -3,8 +3,9 @@
 //! Loads worker definitions from workers.toml and daemon settings from config.toml.
 
 use anyhow::{Context, Result};
-use rch_common::{RchConfig, SelfTestConfig, WorkerConfig, validate_remote_base};
+use rch_common::{FLEET_TAG_PREFIX, RchConfig, SelfTestConfig, WorkerConfig, validate_remote_base};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 use tracing::{debug, info, warn};
@@ -59,6 +60,38 @@ pub struct DaemonConfig {
     /// Build queue settings.
     #[serde(default)]
     pub queue: QueueConfig,
+
+    /// Build-failure blocklist settings.
+    #[serde(default)]
+    pub blocklist: BlocklistConfig,
+}
+
+/// Configuration for temporarily excluding workers whose builds keep failing.
+///
+/// Off by default: a nonzero exit is usually the project's own compile or test
+/// failure, so only enable this where repeated failures point at the worker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlocklistConfig {
+    /// Consecutive failed builds on a worker before it is blocked (0 = never).
+    #[serde(default)]
+    pub failure_threshold: u32,
+
+    /// How long a blocked worker is excluded from selection (seconds).
+    #[serde(default = "default_blocklist_cooldown")]
+    pub cooldown_secs: u64,
+}
+
+fn default_blocklist_cooldown() -> u64 {
+    600 // 10 minutes
+}
+
+impl Default for BlocklistConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 0,
+            cooldown_secs: default_blocklist_cooldown(),
+        }
+    }
 }
 
 /// Configuration for build queueing when all workers are busy.
@@ -111,6 +144,7 @@ impl Default for DaemonConfig {
             cache_cleanup: CacheCleanupConfig::default(),
             stale_target_reap: StaleTargetReapConfig::default(),
             queue: QueueConfig::default(),
+            blocklist: BlocklistConfig::default(),
         }
     }
 }
@@ -320,9 +354,39 @@ impl Default for CacheCleanupConfig {
 /// Workers configuration file structure.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct WorkersConfig {
-    /// List of worker definitions.
+    /// List of worker definitions (the unnamed default fleet).
     #[serde(default)]
     pub workers: Vec<WorkerEntry>,
+
+    /// Named fleets (`[[fleet.<name>.workers]]`), selectable per project via
+    /// `transfer.fleet`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fleet: BTreeMap<String, FleetEntry>,
+}
+
+/// Workers of one named fleet.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FleetEntry {
+    /// List of worker definitions in this fleet.
+    #[serde(default)]
+    pub workers: Vec<WorkerEntry>,
+}
+
+impl WorkersConfig {
+    /// All worker definitions, unnamed ones first, then each named fleet in
+    /// name order. Fleet workers carry a `fleet:<name>` tag.
+    pub fn all_entries(&self) -> Vec<WorkerEntry> {
+        let mut entries = self.workers.clone();
+        for (name, fleet) in &self.fleet {
+            let tag = format!("{FLEET_TAG_PREFIX}{name}");
+            entries.extend(fleet.workers.iter().cloned().map(|mut entry| {
+                entry.tags.retain(|t| !t.starts_with(FLEET_TAG_PREFIX));
+                entry.tags.push(tag.clone());
+                entry
+            }));
+        }
+        entries
+    }
 }
 
 /// Single worker entry in configuration.
@@ -357,6 +421,18 @@ pub struct WorkerEntry {
     /// Whether this worker is enabled.
     #[serde(default = "default_true")]
     pub enabled: bool,
+
+    /// Maximum builds running at once, independent of free slots.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_builds: Option<u32>,
+
+    /// Niceness (`nice -n`) for remote builds on this worker.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nice_level: Option<i32>,
+
+    /// I/O scheduling class (`ionice -c`) for remote builds on this worker.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ionice_class: Option<u8>,
 }
 
 impl From<WorkerEntry> for WorkerConfig {
@@ -369,6 +445,9 @@ impl From<WorkerEntry> for WorkerConfig {
             total_slots: entry.total_slots,
             priority: entry.priority,
             tags: entry.tags,
+            max_concurrent_builds: entry.max_concurrent_builds,
+            nice_level: entry.nice_level,
+            ionice_class: entry.ionice_class,
         }
     }
 }
@@ -378,6 +457,11 @@ pub(crate) fn default_socket_path() -> PathBuf {
     PathBuf::from(rch_common::default_socket_path())
 }
 
+/// Worker blocklist state file, alongside the bypass records in the rch state dir.
+pub(crate) fn default_blocklist_path() -> PathBuf {
+    rch_common::bypass_record::default_bypass_record_path().with_file_name("worker_blocklist.json")
+}
+
 fn default_health_interval() -> u64 {
     30
 }
@@ -492,7 +576,16 @@ pub fn load_workers_config(path: Option<&Path>) -> Result<WorkersConfig> {
     let config: WorkersConfig = toml::from_str(&contents)
         .with_context(|| format!("Failed to parse workers config from {:?}", config_path))?;
 
-    info!("Loaded {} worker definitions", config.workers.len());
+    info!(
+        "Loaded {} worker definitions ({} named fleets)",
+        config.workers.len()
+            + config
+                .fleet
+                .values()
+                .map(|f| f.workers.len())
+                .sum::<usize>(),
+        config.fleet.len()
+    );
     Ok(config)
 }
 
@@ -501,7 +594,7 @@ pub fn load_workers(path: Option<&Path>) -> Result<Vec<WorkerConfig>> {
     let config = load_workers_config(path)?;
 
     let workers: Vec<WorkerConfig> = config
-        .workers
+        .all_entries()
         .into_iter()
         .filter(|w| w.enabled)
         .map(WorkerConfig::from)
@@ -671,11 +764,14 @@ total_slots = 4
 priority = 100
 tags = ["test"]
 enabled = true
+max_concurrent_builds = 2
 "#;
         let config: WorkersConfig = toml::from_str(toml).unwrap();
         assert_eq!(config.workers.len(), 1);
         assert_eq!(config.workers[0].id, "test");
         assert_eq!(config.workers[0].total_slots, 4);
+        let worker: WorkerConfig = config.workers[0].clone().into();
+        assert_eq!(worker.max_concurrent_builds, Some(2));
     }
 
     #[test]
@@ -690,6 +786,9 @@ enabled = true
             priority: 100,
             tags: vec!["rust".to_string()],
             enabled: true,
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let config: WorkerConfig = entry.into();
@@ -787,6 +886,8 @@ remote_base = "/var/rch-builds"
         assert_eq!(config.worker_timeout_secs, 10);
         assert_eq!(config.max_jobs_per_slot, 1);
         assert!(config.connection_pooling);
+        assert_eq!(config.blocklist.failure_threshold, 0);
+        assert_eq!(config.blocklist.cooldown_secs, 600);
         assert_eq!(config.log_level, "info");
     }
 
@@ -905,6 +1006,45 @@ enabled = false
         assert_eq!(workers[0].id.as_str(), "enabled-worker");
     }
 
+    #[test]
+    fn test_worker_loading_named_fleets_tagged() {
+        let _guard = test_guard!();
+        init_test_logging();
+
+        let temp_dir = TempDir::new().unwrap();
+        let workers_path = temp_dir.path().join("workers.toml");
+
+        let config_content = r#"
+[[workers]]
+id = "default-worker"
+host = "192.168.1.100"
+
+[[fleet.fast.workers]]
+id = "fast-worker"
+host = "192.168.1.101"
+tags = ["rust"]
+
+[[fleet.cheap.workers]]
+id = "cheap-worker"
+host = "192.168.1.102"
+"#;
+        std::fs::write(&workers_path, config_content).unwrap();
+
+        let workers = load_workers(Some(&workers_path)).unwrap();
+
+        assert_eq!(workers.len(), 3);
+        assert_eq!(workers[0].id.as_str(), "default-worker");
+        assert_eq!(workers[0].fleet(), None);
+        assert_eq!(workers[1].id.as_str(), "cheap-worker");
+        assert_eq!(workers[1].fleet(), Some("cheap"));
+        assert_eq!(workers[2].id.as_str(), "fast-worker");
+        assert_eq!(workers[2].fleet(), Some("fast"));
+        assert!(workers[2].tags.contains(&"rust".to_string()));
+        assert!(workers[2].in_fleet(Some("fast")));
+        assert!(!workers[0].in_fleet(Some("fast")));
+        assert!(workers[0].in_fleet(None));
+    }
+
     #[test]
     fn test_worker_loading_missing_file_returns_empty() {
         let _guard = test_guard!();
@@ -1057,6 +1197,9 @@ tags = ["rust", "go", "python", "fast", "production"]
             priority: 200,
             tags: vec!["tag1".to_string(), "tag2".to_string()],
             enabled: true,
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let config: WorkerConfig = entry.into();
@@ -82,6 +82,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         }
     }
 
@@ -99,6 +103,9 @@ mod tests {
             bytes_transferred: Some(bytes),
             timing: None,
             cancellation: None,
+            decision_trace: Vec::new(),
+            project_path: None,
+            toolchain: None,
         }
     }
 
@@ -401,6 +401,9 @@ mod tests {
             bytes_transferred: Some(bytes_transferred),
             timing: None,
             cancellation: None,
+            decision_trace: Vec::new(),
+            project_path: None,
+            toolchain: None,
         }
     }
 
@@ -418,6 +421,9 @@ mod tests {
             bytes_transferred: None,
             timing: None,
             cancellation: None,
+            decision_trace: Vec::new(),
+            project_path: None,
+            toolchain: None,
         }
     }
 
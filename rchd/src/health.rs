@@ -1,6 +1,9 @@
 //! Worker health monitoring with heartbeats.
 //!
-//! Periodically checks worker availability and updates their status.
+//! Periodically checks worker availability and updates their status. With a
+//! deep check interval configured, workers that pass the SSH check are also
+//! asked to build a trivial crate (`rch-wkr health --deep`) now and then; a
+//! worker that answers but cannot build stays eligible as `Degraded`.
 
 #![allow(dead_code)] // Scaffold code - methods will be used in future beads
 
@@ -10,7 +13,8 @@ use crate::ui::workers::WorkerStatusPanel;
 use crate::workers::{WorkerPool, WorkerState};
 use rch_common::mock::{self, MockConfig, MockSshClient};
 use rch_common::{
-    CircuitBreakerConfig, CircuitState, CircuitStats, SshClient, SshOptions, WorkerStatus,
+    CircuitBreakerConfig, CircuitState, CircuitStats, HealthFailureReason, SshClient, SshOptions,
+    WorkerStatus,
 };
 use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
@@ -34,6 +38,9 @@ const DEFAULT_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
 /// Threshold for degraded status (slow response).
 const DEGRADED_THRESHOLD_MS: u64 = 5000;
 
+/// Default SSH timeout for a deep check; the worker's build gets 30s less.
+const DEFAULT_DEEP_CHECK_TIMEOUT: Duration = Duration::from_secs(180);
+
 fn duration_millis_u64(duration: Duration) -> u64 {
     u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
 }
@@ -51,6 +58,10 @@ pub struct HealthConfig {
     pub failure_threshold: u32,
     /// Circuit breaker configuration.
     pub circuit: CircuitBreakerConfig,
+    /// Interval between deep (build) checks per worker; `None` disables them.
+    pub deep_check_interval: Option<Duration>,
+    /// Timeout for each deep check.
+    pub deep_check_timeout: Duration,
 }
 
 impl Default for HealthConfig {
@@ -61,10 +72,21 @@ impl Default for HealthConfig {
             degraded_threshold_ms: DEGRADED_THRESHOLD_MS,
             failure_threshold: 3,
             circuit: CircuitBreakerConfig::default(),
+            deep_check_interval: None,
+            deep_check_timeout: DEFAULT_DEEP_CHECK_TIMEOUT,
         }
     }
 }
 
+impl HealthConfig {
+    /// Run deep checks every `secs` seconds (`selection.deep_health_check_interval_secs`; 0 = off).
+    #[must_use]
+    pub fn with_deep_check_interval_secs(mut self, secs: u64) -> Self {
+        self.deep_check_interval = (secs > 0).then(|| Duration::from_secs(secs));
+        self
+    }
+}
+
 /// Result of a single health check.
 #[derive(Debug, Clone)]
 pub struct HealthCheckResult {
@@ -74,6 +96,8 @@ pub struct HealthCheckResult {
     pub response_time_ms: u64,
     /// Error message if failed.
     pub error: Option<String>,
+    /// Classified failure reason if failed.
+    pub failure_reason: Option<HealthFailureReason>,
     /// Timestamp of the check.
     #[allow(dead_code)] // May be used for monitoring metrics
     pub checked_at: Instant,
@@ -85,19 +109,38 @@ impl HealthCheckResult {
             healthy: true,
             response_time_ms,
             error: None,
+            failure_reason: None,
             checked_at: Instant::now(),
         }
     }
 
+    /// A failed check, classified from the SSH error in `error`.
     fn failure(error: String) -> Self {
+        let reason = HealthFailureReason::from_ssh_error(&error);
+        Self::failure_with_reason(reason, error)
+    }
+
+    fn failure_with_reason(reason: HealthFailureReason, error: String) -> Self {
         Self {
             healthy: false,
             response_time_ms: 0,
             error: Some(error),
+            failure_reason: Some(reason),
             checked_at: Instant::now(),
         }
     }
 
+    fn unexpected_response(exit_code: i32, stdout: &str) -> Self {
+        Self::failure_with_reason(
+            HealthFailureReason::UnexpectedResponse,
+            format!(
+                "Unexpected response: exit={}, stdout={}",
+                exit_code,
+                stdout.trim()
+            ),
+        )
+    }
+
     /// Record metrics for this health check result.
     fn record_metrics(&self, worker_id: &str) {
         if self.healthy {
@@ -124,6 +167,10 @@ pub struct WorkerHealth {
     circuit: CircuitStats,
     /// Last error message (for diagnostics).
     last_error: Option<String>,
+    /// Why the last deep check failed; cleared when one passes.
+    deep_failure: Option<String>,
+    /// When the last deep check was started.
+    last_deep_check: Option<Instant>,
 }
 
 impl Default for WorkerHealth {
@@ -133,6 +180,8 @@ impl Default for WorkerHealth {
             current_status: WorkerStatus::Healthy,
             circuit: CircuitStats::new(),
             last_error: None,
+            deep_failure: None,
+            last_deep_check: None,
         }
     }
 }
@@ -154,7 +203,7 @@ impl WorkerHealth {
         if result.healthy {
             // Record success in circuit stats
             self.circuit.record_success();
-            self.last_error = None;
+            self.last_error = self.deep_failure.clone();
 
             // Check if circuit should close (half-open -> closed)
             if self.circuit.should_close(&config.circuit) {
@@ -209,6 +258,9 @@ impl WorkerHealth {
                 } else if result.response_time_ms > config.degraded_threshold_ms {
                     // Slow response -> Degraded
                     WorkerStatus::Degraded
+                } else if self.deep_failure.is_some() {
+                    // Reachable but cannot build -> Degraded
+                    WorkerStatus::Degraded
                 } else {
                     // Healthy and fast
                     WorkerStatus::Healthy
@@ -263,6 +315,51 @@ impl WorkerHealth {
     pub fn start_probe(&mut self, config: &HealthConfig) -> bool {
         self.circuit.start_probe(&config.circuit)
     }
+
+    /// Why the last deep check failed, if it did.
+    pub fn deep_failure(&self) -> Option<&str> {
+        self.deep_failure.as_deref()
+    }
+
+    /// Whether a deep check should start now: enabled, the worker is fully
+    /// up, and the interval has passed since the last one started.
+    pub fn deep_check_due(&self, config: &HealthConfig) -> bool {
+        let Some(interval) = config.deep_check_interval else {
+            return false;
+        };
+        self.circuit.state() == CircuitState::Closed
+            && self
+                .last_deep_check
+                .is_none_or(|started| started.elapsed() >= interval)
+    }
+
+    /// Mark a deep check as started so it is not scheduled again until the
+    /// interval has passed.
+    pub fn start_deep_check(&mut self) {
+        self.last_deep_check = Some(Instant::now());
+    }
+
+    /// Record a finished deep check. A failure degrades a healthy worker right
+    /// away; a pass takes effect at the next regular check.
+    pub fn record_deep_check(&mut self, outcome: Result<(), String>, worker_id: &str) {
+        match outcome {
+            Ok(()) => {
+                if let Some(previous) = self.deep_failure.take() {
+                    info!("Worker {} deep check passed again", worker_id);
+                    if self.last_error.as_deref() == Some(previous.as_str()) {
+                        self.last_error = None;
+                    }
+                }
+            }
+            Err(reason) => {
+                if self.current_status == WorkerStatus::Healthy {
+                    self.current_status = WorkerStatus::Degraded;
+                }
+                self.last_error = Some(reason.clone());
+                self.deep_failure = Some(reason);
+            }
+        }
+    }
 }
 
 /// Health monitor that periodically checks all workers.
@@ -384,6 +481,12 @@ impl HealthMonitor {
                     } else {
                         worker.set_last_latency_ms(None);
                     }
+                    let failure_reason = result.failure_reason.or_else(|| {
+                        health
+                            .deep_failure()
+                            .map(|_| HealthFailureReason::BuildCheckFailed)
+                    });
+                    worker.set_health_failure(failure_reason).await;
 
                     // Log status changes
                     let new_status = health.status();
@@ -462,9 +565,50 @@ impl HealthMonitor {
                             if let Some(capabilities) =
                                 probe_worker_capabilities(&worker_clone, timeout).await
                             {
+                                if !capabilities.has_rust() {
+                                    worker_clone
+                                        .set_health_failure(Some(HealthFailureReason::RustcMissing))
+                                        .await;
+                                }
                                 worker_clone.set_capabilities(capabilities).await;
                             }
                         });
+
+                        // Deep checks take a build's worth of time, so they
+                        // also run in the background and report back later.
+                        if health.deep_check_due(&config) {
+                            health.start_deep_check();
+                            let worker_clone = worker.clone();
+                            let health_states = health_states.clone();
+                            let worker_id = worker_id.clone();
+                            let timeout = config.deep_check_timeout;
+                            tokio::spawn(async move {
+                                let Some(outcome) = run_deep_check(&worker_clone, timeout).await
+                                else {
+                                    return;
+                                };
+                                if let Err(reason) = &outcome {
+                                    warn!("Worker {} failed deep check: {}", worker_id, reason);
+                                }
+                                let failed = outcome.is_err();
+                                let status = {
+                                    let mut states = health_states.write().await;
+                                    let Some(health) = states.get_mut(&worker_id) else {
+                                        return;
+                                    };
+                                    health.record_deep_check(outcome, &worker_id);
+                                    health.status()
+                                };
+                                if failed {
+                                    worker_clone
+                                        .set_health_failure(Some(
+                                            HealthFailureReason::BuildCheckFailed,
+                                        ))
+                                        .await;
+                                    worker_clone.apply_health_status(status).await;
+                                }
+                            });
+                        }
                     } else {
                         warn!(
                             "Worker {} check failed: {:?} (failures: {})",
@@ -541,11 +685,10 @@ async fn check_worker_health(
                     if result.success() && result.stdout.trim().eq("health_check") {
                         return HealthCheckResult::success(duration_millis_u64(duration));
                     }
-                    return HealthCheckResult::failure(format!(
-                        "Unexpected response: exit={}, stdout={}",
+                    return HealthCheckResult::unexpected_response(
                         result.exit_code,
-                        result.stdout.trim()
-                    ));
+                        &result.stdout,
+                    );
                 }
                 Err(e) => {
                     let _ = client.disconnect().await;
@@ -578,11 +721,7 @@ async fn check_worker_health(
                     if result.success() && result.stdout.trim().eq("health_check") {
                         HealthCheckResult::success(duration_millis_u64(duration))
                     } else {
-                        HealthCheckResult::failure(format!(
-                            "Unexpected response: exit={}, stdout={}",
-                            result.exit_code,
-                            result.stdout.trim()
-                        ))
+                        HealthCheckResult::unexpected_response(result.exit_code, &result.stdout)
                     }
                 }
                 Err(e) => {
@@ -605,6 +744,82 @@ pub async fn probe_worker(worker: &WorkerState) -> HealthCheckResult {
     check_worker_health(&worker_arc, &config).await
 }
 
+/// Shell command running `rch-wkr <args>` from PATH, else `~/.local/bin`.
+fn rch_wkr_command(args: &str) -> String {
+    format!(
+        "if command -v rch-wkr >/dev/null 2>&1; then rch-wkr {args}; else ~/.local/bin/rch-wkr {args}; fi"
+    )
+}
+
+/// Interpret `rch-wkr health --deep` output: `Some(Ok)` for `OK`,
+/// `Some(Err(reason))` for `FAILED: <reason>`, and `None` when the answer says
+/// nothing about the build (rch-wkr missing or too old for `--deep`).
+fn parse_deep_check_output(exit_code: i32, stdout: &str) -> Option<Result<(), String>> {
+    for line in stdout.lines().map(str::trim) {
+        if let Some(reason) = line.strip_prefix("FAILED:") {
+            return Some(Err(reason.trim().to_string()));
+        }
+        if line == "OK" && exit_code == 0 {
+            return Some(Ok(()));
+        }
+    }
+    None
+}
+
+/// Run the deep check on a worker over SSH.
+///
+/// Returns `None` when the check could not be run or was inconclusive;
+/// reachability is the regular check's job, so that never degrades a worker.
+pub async fn run_deep_check(
+    worker: &Arc<WorkerState>,
+    timeout: Duration,
+) -> Option<Result<(), String>> {
+    let worker_config = worker.config.read().await;
+    if is_mock_transport(worker) {
+        return Some(Ok(()));
+    }
+
+    let ssh_options = SshOptions {
+        connect_timeout: DEFAULT_CHECK_TIMEOUT,
+        command_timeout: timeout,
+        control_master: false,
+        ..Default::default()
+    };
+    let mut client = SshClient::new(worker_config.clone(), ssh_options);
+    if let Err(e) = client.connect().await {
+        debug!(
+            "Worker {} deep check connection failed: {}",
+            worker_config.id, e
+        );
+        return None;
+    }
+    let command = rch_wkr_command(&format!(
+        "health --deep --timeout-secs {}",
+        timeout.as_secs().saturating_sub(30).max(10)
+    ));
+    let result = client.execute(&command).await;
+    let _ = client.disconnect().await;
+    match result {
+        Ok(result) => {
+            let outcome = parse_deep_check_output(result.exit_code, &result.stdout);
+            if outcome.is_none() {
+                debug!(
+                    "Worker {} deep check inconclusive (rch-wkr may be missing or too old): exit={}",
+                    worker_config.id, result.exit_code
+                );
+            }
+            outcome
+        }
+        Err(e) => {
+            debug!(
+                "Worker {} deep check command failed: {}",
+                worker_config.id, e
+            );
+            None
+        }
+    }
+}
+
 /// Probe worker capabilities (Bun, Node, Rust versions).
 ///
 /// Runs `rch-wkr capabilities` on the worker and parses the JSON output.
@@ -641,8 +856,7 @@ pub async fn probe_worker_capabilities(
         Ok(()) => {
             // Try to run rch-wkr capabilities command
             // Handle PATH vs ~/.local/bin lookup
-            let cmd = "if command -v rch-wkr >/dev/null 2>&1; then rch-wkr capabilities; else ~/.local/bin/rch-wkr capabilities; fi";
-            match client.execute(cmd).await {
+            match client.execute(&rch_wkr_command("capabilities")).await {
                 Ok(result) => {
                     let _ = client.disconnect().await;
 
@@ -750,6 +964,16 @@ mod tests {
         assert!(result.error.is_none());
     }
 
+    #[test]
+    fn test_health_check_result_failure_classifies_ssh_error() {
+        let _guard = test_guard!();
+        let result = HealthCheckResult::failure(
+            "Connection failed: user@w2: Permission denied (publickey).".to_string(),
+        );
+        assert_eq!(result.failure_reason, Some(HealthFailureReason::AuthFailed));
+        assert!(HealthCheckResult::success(12).failure_reason.is_none());
+    }
+
     #[test]
     fn test_health_check_result_failure() {
         let _guard = test_guard!();
@@ -977,6 +1201,9 @@ mod tests {
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         });
 
         let result = check_worker_health(&Arc::new(worker), &HealthConfig::default()).await;
@@ -1002,6 +1229,9 @@ mod tests {
                 total_slots: 8,
                 priority: 100,
                 tags: vec![],
+                max_concurrent_builds: None,
+                nice_level: None,
+                ionice_class: None,
             }
         }
 
@@ -1016,6 +1246,10 @@ mod tests {
                 required_runtime: RequiredRuntime::default(),
                 classification_duration_us: None,
                 hook_pid: None,
+                fleet: None,
+                min_free_disk_gb: None,
+                allowed_workers: vec![],
+                idempotency_key: None,
             }
         }
 
@@ -1204,6 +1438,9 @@ mod tests {
                 total_slots: 8,
                 priority: 100,
                 tags: vec![],
+                max_concurrent_builds: None,
+                nice_level: None,
+                ionice_class: None,
             })
             .await;
             pool.add_worker(WorkerConfig {
@@ -1214,6 +1451,9 @@ mod tests {
                 total_slots: 16, // More slots - would normally be preferred
                 priority: 100,
                 tags: vec![],
+                max_concurrent_builds: None,
+                nice_level: None,
+                ionice_class: None,
             })
             .await;
             pool.add_worker(WorkerConfig {
@@ -1224,6 +1464,9 @@ mod tests {
                 total_slots: 12, // More slots than closed
                 priority: 100,
                 tags: vec![],
+                max_concurrent_builds: None,
+                nice_level: None,
+                ionice_class: None,
             })
             .await;
 
@@ -1511,6 +1754,8 @@ mod tests {
                 failure_threshold: 10,
                 ..Default::default()
             },
+            deep_check_interval: Some(Duration::from_secs(3600)),
+            deep_check_timeout: Duration::from_secs(60),
         };
 
         assert_eq!(config.check_interval, Duration::from_secs(60));
@@ -1518,6 +1763,71 @@ mod tests {
         assert_eq!(config.degraded_threshold_ms, 3000);
         assert_eq!(config.failure_threshold, 5);
         assert_eq!(config.circuit.failure_threshold, 10);
+        assert_eq!(config.deep_check_interval, Some(Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn test_deep_check_interval_zero_disables() {
+        let _guard = test_guard!();
+        let config = HealthConfig::default().with_deep_check_interval_secs(0);
+        assert_eq!(config.deep_check_interval, None);
+        assert!(!WorkerHealth::default().deep_check_due(&config));
+
+        let config = HealthConfig::default().with_deep_check_interval_secs(600);
+        let mut health = WorkerHealth::default();
+        assert!(health.deep_check_due(&config));
+        health.start_deep_check();
+        assert!(
+            !health.deep_check_due(&config),
+            "not again before the interval"
+        );
+    }
+
+    #[test]
+    fn test_failed_deep_check_degrades_until_it_passes() {
+        let _guard = test_guard!();
+        let config = HealthConfig::default();
+        let mut health = WorkerHealth::default();
+        health.update(HealthCheckResult::success(100), &config, "test-worker");
+        assert_eq!(health.status(), WorkerStatus::Healthy);
+
+        health.record_deep_check(
+            Err("cargo build exited 101: error: no space".to_string()),
+            "test-worker",
+        );
+        assert_eq!(health.status(), WorkerStatus::Degraded);
+        assert_eq!(
+            health.last_error(),
+            Some("cargo build exited 101: error: no space")
+        );
+
+        // SSH keeps answering, but the worker stays degraded.
+        health.update(HealthCheckResult::success(100), &config, "test-worker");
+        assert_eq!(health.status(), WorkerStatus::Degraded);
+        assert_eq!(health.circuit_state(), CircuitState::Closed);
+
+        health.record_deep_check(Ok(()), "test-worker");
+        assert_eq!(health.last_error(), None);
+        health.update(HealthCheckResult::success(100), &config, "test-worker");
+        assert_eq!(health.status(), WorkerStatus::Healthy);
+    }
+
+    #[test]
+    fn test_parse_deep_check_output() {
+        let _guard = test_guard!();
+        assert_eq!(parse_deep_check_output(0, "OK\n"), Some(Ok(())));
+        assert_eq!(
+            parse_deep_check_output(
+                1,
+                "FAILED: cannot create /home/u/.cache/rch: quota exceeded\n"
+            ),
+            Some(Err(
+                "cannot create /home/u/.cache/rch: quota exceeded".to_string()
+            ))
+        );
+        // rch-wkr too old for --deep (clap usage error) or not installed.
+        assert_eq!(parse_deep_check_output(2, ""), None);
+        assert_eq!(parse_deep_check_output(127, ""), None);
     }
 
     #[test]
@@ -1652,6 +1962,9 @@ mod tests {
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         })
         .await;
 
@@ -1701,6 +2014,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         });
 
         let result = probe_worker(&worker).await;
@@ -1725,6 +2041,9 @@ mod tests {
             total_slots: 4,
             priority: 50,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         });
 
         let result = probe_worker(&worker).await;
@@ -1752,6 +2071,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         let worker = Arc::new(WorkerState::new(worker_config));
 
@@ -1784,6 +2106,9 @@ mod tests {
             total_slots: 4,
             priority: 50,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         let worker = Arc::new(WorkerState::new(worker_config));
 
@@ -1812,6 +2137,9 @@ mod tests {
             total_slots: 4,
             priority: 50,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         let worker = Arc::new(WorkerState::new(worker_config));
 
@@ -1821,6 +2149,10 @@ mod tests {
 
         // Wrong output should result in failure
         assert!(!result.healthy);
+        assert_eq!(
+            result.failure_reason,
+            Some(HealthFailureReason::UnexpectedResponse)
+        );
         assert!(result.error.is_some());
         assert!(result.error.unwrap().contains("Unexpected response"));
     }
@@ -1885,6 +2217,9 @@ mod tests {
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         });
 
         // When mock is not enabled, is_mock_transport returns false
@@ -1905,6 +2240,9 @@ mod tests {
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         });
 
         set_mock_enabled_override(Some(true));
@@ -4,8 +4,9 @@
 
 use chrono::{DateTime, Duration as ChronoDuration, Utc};
 use rch_common::{
-    BuildCancellationMetadata, BuildHeartbeatPhase, BuildHeartbeatRequest, BuildLocation,
-    BuildRecord, BuildStats, CommandTimingBreakdown, SavedTimeStats,
+    BuildCancellationMetadata, BuildHeartbeatPhase, BuildHeartbeatRequest, BuildHistoryGroup,
+    BuildLocation, BuildRecord, BuildStats, CommandPriority, CommandTimingBreakdown, DecisionTrace,
+    DecisionTraceStep, IncrementalReuseStats, QueueFairness, SavedTimeStats,
 };
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs::File;
@@ -13,7 +14,7 @@ use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
 use std::sync::RwLock;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::fs::OpenOptions as AsyncOpenOptions;
 use tokio::io::AsyncWriteExt;
 use tracing::{debug, warn};
@@ -21,6 +22,27 @@ use tracing::{debug, warn};
 /// Default maximum number of builds to retain.
 const DEFAULT_CAPACITY: usize = 100;
 
+/// How long a build's idempotency key coalesces duplicate requests.
+pub const BUILD_IDEMPOTENCY_WINDOW: Duration = Duration::from_secs(10);
+
+/// Output lines retained per active build for `rch tail`.
+pub const BUILD_OUTPUT_TAIL_LINES: usize = 200;
+
+/// Longest output line kept in the tail buffer; longer lines are truncated.
+const BUILD_OUTPUT_LINE_MAX_CHARS: usize = 1024;
+
+/// Cancellation origin of builds reaped after `selection.heartbeat_timeout_secs`
+/// without a heartbeat.
+pub const ABANDONED_BUILD_ORIGIN: &str = "heartbeat_timeout";
+
+/// Whether `record` was reaped as abandoned rather than finished by its hook.
+pub fn is_abandoned(record: &BuildRecord) -> bool {
+    record
+        .cancellation
+        .as_ref()
+        .is_some_and(|cancellation| cancellation.origin == ABANDONED_BUILD_ORIGIN)
+}
+
 /// RCH's canonical exit code for a genuine build/compilation failure.
 ///
 /// RCH classifies a compiled command's outcome into a stable taxonomy that is
@@ -54,6 +76,70 @@ fn build_record_succeeded(record: &BuildRecord) -> bool {
     is_test_command && record.exit_code != BUILD_ERROR_EXIT
 }
 
+/// Grouping for aggregated history queries (`rch history --group-by`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryGroupBy {
+    Project,
+    Worker,
+    /// Compilation kind, classified from the recorded command.
+    Kind,
+}
+
+impl HistoryGroupBy {
+    /// Parse the `group_by` query value.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "project" => Some(Self::Project),
+            "worker" => Some(Self::Worker),
+            "kind" => Some(Self::Kind),
+            _ => None,
+        }
+    }
+
+    fn key(self, record: &BuildRecord) -> String {
+        match self {
+            Self::Project => record.project_id.clone(),
+            Self::Worker => record
+                .worker_id
+                .clone()
+                .unwrap_or_else(|| "local".to_string()),
+            Self::Kind => rch_common::patterns::classify_command(&record.command)
+                .kind
+                .map_or_else(|| "other".to_string(), |kind| kind.as_str().to_string()),
+        }
+    }
+}
+
+/// Whether a record passes the history query filters. Records whose
+/// completion time cannot be parsed never match a `since` cutoff.
+fn record_matches(
+    record: &BuildRecord,
+    project_id: Option<&str>,
+    failed_only: bool,
+    since: Option<DateTime<Utc>>,
+) -> bool {
+    project_id.is_none_or(|project| record.project_id == project)
+        && (!failed_only || record.exit_code != 0)
+        && since.is_none_or(|cutoff| {
+            DateTime::parse_from_rfc3339(&record.completed_at)
+                .is_ok_and(|completed| completed >= cutoff)
+        })
+}
+
+/// Median of the durations, using the same rule as the hook's timing history.
+fn median_duration_ms(durations: &mut [u64]) -> u64 {
+    if durations.is_empty() {
+        return 0;
+    }
+    durations.sort_unstable();
+    let mid = durations.len() / 2;
+    if durations.len().is_multiple_of(2) {
+        (durations[mid - 1] + durations[mid]) / 2
+    } else {
+        durations[mid]
+    }
+}
+
 /// In-flight build state tracked for active build visibility.
 #[derive(Debug, Clone)]
 pub struct ActiveBuildState {
@@ -65,6 +151,8 @@ pub struct ActiveBuildState {
     pub started_at_mono: Instant,
     pub hook_pid: u32,
     pub remote_pgid_file: Option<String>,
+    /// Remote build runs detached from the hook (see `transfer.detach_remote_builds`).
+    pub detached: bool,
     pub slots: u32,
     pub location: BuildLocation,
     pub heartbeat_phase: BuildHeartbeatPhase,
@@ -83,6 +171,12 @@ pub struct ActiveBuildState {
     pub detector_build_age_secs: u64,
     pub detector_slots_owned: u32,
     pub detector_last_evaluated_at: Option<String>,
+    /// Decisions the daemon recorded for this build (worker selection).
+    pub decision_trace: Vec<DecisionTraceStep>,
+    /// Local project root reported by the hook (`rch replay`).
+    pub project_path: Option<String>,
+    /// Rustup toolchain name reported by the hook (`rch replay`).
+    pub toolchain: Option<String>,
 }
 
 /// Snapshot of stuck-detector evidence for an active build.
@@ -96,6 +190,38 @@ pub struct StuckDetectorSnapshot {
     pub slots_owned: u32,
 }
 
+/// Most recent output lines of an active build, fed by heartbeats.
+#[derive(Debug, Default)]
+struct BuildOutputTail {
+    lines: VecDeque<String>,
+    /// Sequence number the next appended line will get.
+    next_seq: u64,
+}
+
+impl BuildOutputTail {
+    fn push(&mut self, mut line: String) {
+        if let Some((cut, _)) = line.char_indices().nth(BUILD_OUTPUT_LINE_MAX_CHARS) {
+            line.truncate(cut);
+        }
+        if self.lines.len() >= BUILD_OUTPUT_TAIL_LINES {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+        self.next_seq += 1;
+    }
+
+    fn first_seq(&self) -> u64 {
+        self.next_seq - self.lines.len() as u64
+    }
+}
+
+/// Buffered output lines of an active build, starting at `first_seq`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuildOutputChunk {
+    pub first_seq: u64,
+    pub lines: Vec<String>,
+}
+
 /// Queued build state for builds waiting for available workers.
 ///
 /// When all workers are busy and `queue_when_busy` is enabled,
@@ -118,6 +244,17 @@ pub struct QueuedBuildState {
     pub slots_needed: u32,
     /// Estimated start time (ISO 8601), updated as queue advances.
     pub estimated_start: Option<String>,
+    /// Claude Code session that issued the build, when the hook knew it.
+    pub session_id: Option<String>,
+    /// Priority the hook requested for the command.
+    pub priority: CommandPriority,
+}
+
+impl QueuedBuildState {
+    /// Group used by round-robin queueing: the session, else the project.
+    pub fn fairness_key(&self) -> &str {
+        self.session_id.as_deref().unwrap_or(&self.project_id)
+    }
 }
 
 /// Build history manager.
@@ -128,16 +265,31 @@ pub struct BuildHistory {
     records: RwLock<VecDeque<BuildRecord>>,
     /// Active builds (in-flight).
     active: RwLock<HashMap<u64, ActiveBuildState>>,
+    /// Recent output lines of active builds, keyed by build ID.
+    output: RwLock<HashMap<u64, BuildOutputTail>>,
     /// Queued builds (waiting for workers).
     queued: RwLock<VecDeque<QueuedBuildState>>,
     /// Maximum capacity for history.
     capacity: usize,
     /// Maximum queue depth (0 = unlimited).
     max_queue_depth: usize,
+    /// Order in which queued builds are served.
+    queue_fairness: QueueFairness,
+    /// Round-robin bookkeeping: fairness key -> sequence number of its last
+    /// served build (higher = more recent).
+    fair_last_served: RwLock<HashMap<String, u64>>,
+    /// Next round-robin service sequence number.
+    fair_serve_seq: AtomicU64,
+    /// Queued builds whose last round-robin turn found no worker able to run
+    /// them; builds behind them in the service order may try meanwhile.
+    fair_blocked: RwLock<HashSet<u64>>,
     /// Next build ID.
     next_id: AtomicU64,
     /// Next queue ID.
     next_queue_id: AtomicU64,
+    /// Idempotency key -> (build ID, registration time) for recently started
+    /// builds; duplicates within [`BUILD_IDEMPOTENCY_WINDOW`] are coalesced.
+    idempotency_keys: RwLock<HashMap<String, (u64, Instant)>>,
     /// Persistence path (optional).
     persistence_path: Option<PathBuf>,
 }
@@ -159,11 +311,17 @@ impl BuildHistory {
         Self {
             records: RwLock::new(VecDeque::with_capacity(capacity)),
             active: RwLock::new(HashMap::new()),
+            output: RwLock::new(HashMap::new()),
             queued: RwLock::new(VecDeque::new()),
             capacity,
             max_queue_depth: DEFAULT_MAX_QUEUE_DEPTH,
+            queue_fairness: QueueFairness::default(),
+            fair_last_served: RwLock::new(HashMap::new()),
+            fair_serve_seq: AtomicU64::new(1),
+            fair_blocked: RwLock::new(HashSet::new()),
             next_id: AtomicU64::new(initial_id),
             next_queue_id: AtomicU64::new(1),
+            idempotency_keys: RwLock::new(HashMap::new()),
             persistence_path: None,
         }
     }
@@ -179,6 +337,17 @@ impl BuildHistory {
         self
     }
 
+    /// Set the order in which queued builds are served.
+    pub fn with_queue_fairness(mut self, fairness: QueueFairness) -> Self {
+        self.queue_fairness = fairness;
+        self
+    }
+
+    /// Order in which queued builds are served.
+    pub fn queue_fairness(&self) -> QueueFairness {
+        self.queue_fairness
+    }
+
     /// Enable persistence to the given path.
     pub fn with_persistence(mut self, path: PathBuf) -> Self {
         self.persistence_path = Some(path);
@@ -251,6 +420,7 @@ impl BuildHistory {
             started_at_mono,
             hook_pid,
             remote_pgid_file: None,
+            detached: false,
             slots,
             location,
             heartbeat_phase: BuildHeartbeatPhase::SyncUp,
@@ -269,6 +439,9 @@ impl BuildHistory {
             detector_build_age_secs: 0,
             detector_slots_owned: slots,
             detector_last_evaluated_at: None,
+            decision_trace: Vec::new(),
+            project_path: None,
+            toolchain: None,
         };
 
         let mut active = self.active.write().unwrap_or_else(|e| e.into_inner());
@@ -299,6 +472,7 @@ impl BuildHistory {
             started_at_mono,
             hook_pid,
             remote_pgid_file: None,
+            detached: false,
             slots,
             location,
             heartbeat_phase: BuildHeartbeatPhase::SyncUp,
@@ -317,6 +491,9 @@ impl BuildHistory {
             detector_build_age_secs: 0,
             detector_slots_owned: slots,
             detector_last_evaluated_at: None,
+            decision_trace: Vec::new(),
+            project_path: None,
+            toolchain: None,
         };
 
         let mut active = self.active.write().unwrap_or_else(|e| e.into_inner());
@@ -357,6 +534,9 @@ impl BuildHistory {
         {
             state.remote_pgid_file = Some(remote_pgid_file);
         }
+        if heartbeat.detached {
+            state.detached = true;
+        }
 
         let previous_phase = state.heartbeat_phase.clone();
         let previous_counter = state.heartbeat_counter;
@@ -379,6 +559,13 @@ impl BuildHistory {
                 state.heartbeat_percent = Some(percent.clamp(0.0, 100.0));
             }
         }
+        if !heartbeat.output_lines.is_empty() {
+            let mut output = self.output.write().unwrap_or_else(|e| e.into_inner());
+            let tail = output.entry(heartbeat.build_id).or_default();
+            for line in heartbeat.output_lines {
+                tail.push(line);
+            }
+        }
         state.heartbeat_count = state.heartbeat_count.saturating_add(1);
         state.last_heartbeat_at = now_rfc3339.clone();
         state.last_heartbeat_mono = now;
@@ -423,7 +610,37 @@ impl BuildHistory {
         Some(state.clone())
     }
 
+    /// Record a daemon-side decision for an active build.
+    pub fn record_decision(&self, build_id: u64, step: DecisionTraceStep) {
+        let mut active = self.active.write().unwrap_or_else(|e| e.into_inner());
+        if let Some(state) = active.get_mut(&build_id) {
+            state.decision_trace.push(step);
+        }
+    }
+
+    /// Store what `rch replay` needs to re-run an active build. `None`
+    /// values leave the existing ones in place.
+    pub fn set_replay_context(
+        &self,
+        build_id: u64,
+        project_path: Option<String>,
+        toolchain: Option<String>,
+    ) {
+        let mut active = self.active.write().unwrap_or_else(|e| e.into_inner());
+        if let Some(state) = active.get_mut(&build_id) {
+            if project_path.is_some() {
+                state.project_path = project_path;
+            }
+            if toolchain.is_some() {
+                state.toolchain = toolchain;
+            }
+        }
+    }
+
     /// Complete an active build, moving it into history.
+    ///
+    /// `decision_trace` holds the hook's steps; they are merged with the
+    /// daemon's own into one pipeline-ordered trace on the record.
     pub fn finish_active_build(
         &self,
         build_id: u64,
@@ -431,8 +648,11 @@ impl BuildHistory {
         duration_ms: Option<u64>,
         bytes_transferred: Option<u64>,
         timing: Option<CommandTimingBreakdown>,
+        decision_trace: Vec<DecisionTraceStep>,
     ) -> Option<BuildRecord> {
         let state = self.take_active_build(build_id)?;
+        let mut trace = DecisionTrace::from(state.decision_trace);
+        trace.extend(decision_trace);
 
         let duration_ms =
             duration_ms.unwrap_or_else(|| state.started_at_mono.elapsed().as_millis() as u64);
@@ -449,6 +669,9 @@ impl BuildHistory {
             bytes_transferred,
             timing,
             cancellation: None,
+            decision_trace: trace.into_steps(),
+            project_path: state.project_path,
+            toolchain: state.toolchain,
         };
 
         self.record(record.clone());
@@ -458,9 +681,37 @@ impl BuildHistory {
     /// Claim an active build for deterministic finalization.
     pub fn take_active_build(&self, build_id: u64) -> Option<ActiveBuildState> {
         let mut active = self.active.write().unwrap_or_else(|e| e.into_inner());
+        self.output
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&build_id);
         active.remove(&build_id)
     }
 
+    /// Buffered output of an active build from sequence number `from_seq` on.
+    ///
+    /// Lines older than the buffer are gone, so `first_seq` may be larger
+    /// than `from_seq`. Returns `None` when the build is not active.
+    pub fn build_output_since(&self, build_id: u64, from_seq: u64) -> Option<BuildOutputChunk> {
+        let active = self.active.read().unwrap_or_else(|e| e.into_inner());
+        if !active.contains_key(&build_id) {
+            return None;
+        }
+        let output = self.output.read().unwrap_or_else(|e| e.into_inner());
+        let Some(tail) = output.get(&build_id) else {
+            return Some(BuildOutputChunk {
+                first_seq: from_seq,
+                lines: Vec::new(),
+            });
+        };
+        let first_seq = from_seq.max(tail.first_seq());
+        let skip = (first_seq - tail.first_seq()) as usize;
+        Some(BuildOutputChunk {
+            first_seq,
+            lines: tail.lines.iter().skip(skip).cloned().collect(),
+        })
+    }
+
     /// Record a cancelled build from a claimed active state.
     pub fn record_cancelled_build(
         &self,
@@ -482,6 +733,9 @@ impl BuildHistory {
             bytes_transferred,
             timing: None,
             cancellation,
+            decision_trace: DecisionTrace::from(state.decision_trace).into_steps(),
+            project_path: state.project_path,
+            toolchain: state.toolchain,
         };
 
         self.record(record.clone());
@@ -499,6 +753,48 @@ impl BuildHistory {
         Some(self.record_cancelled_build(state, bytes_transferred, cancellation))
     }
 
+    /// Reap an active build whose hook stopped heartbeating: move it into
+    /// history as cancelled with origin [`ABANDONED_BUILD_ORIGIN`].
+    ///
+    /// Returns `None` when another path already finalized the build; the
+    /// caller must only reclaim slots when this returns a record.
+    pub fn abandon_active_build(
+        &self,
+        build_id: u64,
+        heartbeat_age_secs: u64,
+    ) -> Option<BuildRecord> {
+        let cancellation = BuildCancellationMetadata {
+            operation_id: format!("abandon-{build_id}"),
+            origin: ABANDONED_BUILD_ORIGIN.to_string(),
+            reason_code: ABANDONED_BUILD_ORIGIN.to_string(),
+            decision_path: vec![
+                format!("no_heartbeat_{heartbeat_age_secs}s"),
+                "slots_reclaimed".to_string(),
+            ],
+            escalation_stage: "none".to_string(),
+            escalation_count: 0,
+            remote_kill_attempted: false,
+            cleanup_ok: true,
+            history_cancelled: true,
+            final_state: "abandoned".to_string(),
+            worker_health: None,
+        };
+        self.cancel_active_build(build_id, None, Some(cancellation))
+    }
+
+    /// Builds reaped by [`Self::abandon_active_build`] still in the history
+    /// buffer, most recent first.
+    pub fn abandoned_builds(&self) -> Vec<BuildRecord> {
+        self.records
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .rev()
+            .filter(|record| is_abandoned(record))
+            .cloned()
+            .collect()
+    }
+
     /// Get a specific active build by ID.
     pub fn active_build(&self, build_id: u64) -> Option<ActiveBuildState> {
         self.active
@@ -518,6 +814,31 @@ impl BuildHistory {
             .collect()
     }
 
+    /// Remember that `build_id` was started for `key`, dropping expired keys.
+    pub fn register_idempotency_key(&self, key: String, build_id: u64) {
+        let mut keys = self
+            .idempotency_keys
+            .write()
+            .unwrap_or_else(|e| e.into_inner());
+        keys.retain(|_, (_, registered)| registered.elapsed() <= BUILD_IDEMPOTENCY_WINDOW);
+        keys.insert(key, (build_id, Instant::now()));
+    }
+
+    /// Build a duplicate request with `key` should follow instead of starting
+    /// its own: one registered within [`BUILD_IDEMPOTENCY_WINDOW`] that is
+    /// still running. A finished build never matches, so a deliberate rerun
+    /// always starts a fresh build.
+    pub fn coalesce_target(&self, key: &str) -> Option<u64> {
+        let build_id = self
+            .idempotency_keys
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(key)
+            .filter(|(_, registered)| registered.elapsed() <= BUILD_IDEMPOTENCY_WINDOW)
+            .map(|(build_id, _)| *build_id)?;
+        self.active_build(build_id).map(|_| build_id)
+    }
+
     /// Check whether a worker already has an active build for the same project.
     pub fn has_active_build_for_project_on_worker(
         &self,
@@ -560,6 +881,29 @@ impl BuildHistory {
         command: String,
         hook_pid: u32,
         slots_needed: u32,
+    ) -> Option<QueuedBuildState> {
+        self.enqueue_session_build(
+            project_id,
+            command,
+            hook_pid,
+            slots_needed,
+            None,
+            CommandPriority::Normal,
+        )
+    }
+
+    /// Enqueue a build on behalf of a Claude Code session (see
+    /// [`QueuedBuildState::fairness_key`]).
+    ///
+    /// Returns `None` if the queue is full (when max_queue_depth > 0).
+    pub fn enqueue_session_build(
+        &self,
+        project_id: String,
+        command: String,
+        hook_pid: u32,
+        slots_needed: u32,
+        session_id: Option<String>,
+        priority: CommandPriority,
     ) -> Option<QueuedBuildState> {
         let mut queue = self.queued.write().unwrap_or_else(|e| e.into_inner());
 
@@ -585,6 +929,8 @@ impl BuildHistory {
             hook_pid,
             slots_needed,
             estimated_start: None,
+            session_id,
+            priority,
         };
 
         queue.push_back(state.clone());
@@ -627,14 +973,100 @@ impl BuildHistory {
         queue.remove(pos)
     }
 
-    /// Get all queued builds (in queue order).
+    /// Get all queued builds (in the order they will be served).
     pub fn queued_builds(&self) -> Vec<QueuedBuildState> {
-        self.queued
+        let queue = self.queued.read().unwrap_or_else(|e| e.into_inner());
+        self.service_order(&queue)
+            .into_iter()
+            .map(|idx| queue[idx].clone())
+            .collect()
+    }
+
+    /// Indices into `queue` in the order the builds will be served.
+    ///
+    /// FIFO is arrival order. Round-robin deals one build per fairness key per
+    /// round: round `n` holds each key's `n`-th waiter, and within a round the
+    /// least recently served key goes first (ties: earliest arrival).
+    fn service_order(&self, queue: &VecDeque<QueuedBuildState>) -> Vec<usize> {
+        if self.queue_fairness == QueueFairness::Fifo {
+            return (0..queue.len()).collect();
+        }
+
+        let last_served = self
+            .fair_last_served
             .read()
-            .unwrap_or_else(|e| e.into_inner())
+            .unwrap_or_else(|e| e.into_inner());
+        let mut rounds: HashMap<&str, usize> = HashMap::new();
+        let mut first_arrival: HashMap<&str, usize> = HashMap::new();
+        let mut ranked: Vec<(usize, u64, usize, usize)> = queue
             .iter()
-            .cloned()
-            .collect()
+            .enumerate()
+            .map(|(idx, build)| {
+                let key = build.fairness_key();
+                let round = rounds.entry(key).or_insert(0);
+                let this_round = *round;
+                *round += 1;
+                let first = *first_arrival.entry(key).or_insert(idx);
+                let served = last_served.get(key).copied().unwrap_or(0);
+                (this_round, served, first, idx)
+            })
+            .collect();
+        ranked.sort_unstable();
+        ranked.into_iter().map(|(_, _, _, idx)| idx).collect()
+    }
+
+    /// Whether `queue_id` may try to take a worker now.
+    ///
+    /// Under FIFO every waiter competes on each poll (legacy behavior). Under
+    /// round-robin a build may try once every build ahead of it in the service
+    /// order is blocked (see [`Self::set_queue_blocked`]), so a head that no
+    /// worker can currently run does not hold up the other sessions.
+    pub fn is_queue_turn(&self, queue_id: u64) -> bool {
+        if self.queue_fairness == QueueFairness::Fifo {
+            return true;
+        }
+        let queue = self.queued.read().unwrap_or_else(|e| e.into_inner());
+        let blocked = self.fair_blocked.read().unwrap_or_else(|e| e.into_inner());
+        for idx in self.service_order(&queue) {
+            let id = queue[idx].id;
+            if id == queue_id {
+                return true;
+            }
+            if !blocked.contains(&id) {
+                return false;
+            }
+        }
+        false
+    }
+
+    /// Mark whether a queued build's last turn found no worker able to run it.
+    pub fn set_queue_blocked(&self, queue_id: u64, blocked: bool) {
+        let queue = self.queued.read().unwrap_or_else(|e| e.into_inner());
+        let mut set = self.fair_blocked.write().unwrap_or_else(|e| e.into_inner());
+        if blocked {
+            set.insert(queue_id);
+        } else {
+            set.remove(&queue_id);
+        }
+        // Drop builds that have left the queue.
+        set.retain(|id| queue.iter().any(|b| b.id == *id));
+    }
+
+    /// Record that a queued build was given a worker, moving its fairness key
+    /// to the back of the round-robin rotation.
+    pub fn record_queue_service(&self, served: &QueuedBuildState) {
+        let seq = self.fair_serve_seq.fetch_add(1, Ordering::SeqCst);
+        let queue = self.queued.read().unwrap_or_else(|e| e.into_inner());
+        let mut last_served = self
+            .fair_last_served
+            .write()
+            .unwrap_or_else(|e| e.into_inner());
+        last_served.insert(served.fairness_key().to_string(), seq);
+        // Forget keys with no waiters so the map stays bounded by the queue; a
+        // key that comes back later ranks as never served.
+        last_served.retain(|key, _| {
+            key == served.fairness_key() || queue.iter().any(|b| b.fairness_key() == key)
+        });
     }
 
     /// Get a specific queued build by ID.
@@ -652,11 +1084,10 @@ impl BuildHistory {
 
     /// Get the queue position of a build (1-indexed, None if not found).
     pub fn queue_position(&self, queue_id: u64) -> Option<usize> {
-        self.queued
-            .read()
-            .unwrap()
-            .iter()
-            .position(|b| b.id == queue_id)
+        let queue = self.queued.read().unwrap_or_else(|e| e.into_inner());
+        self.service_order(&queue)
+            .into_iter()
+            .position(|idx| queue[idx].id == queue_id)
             .map(|p| p + 1)
     }
 
@@ -681,10 +1112,12 @@ impl BuildHistory {
         let active_count = self.active.read().unwrap_or_else(|e| e.into_inner()).len();
 
         let mut queue = self.queued.write().unwrap_or_else(|e| e.into_inner());
+        let order = self.service_order(&queue);
 
         // Estimate when each queued build will start
         let now = Utc::now();
-        for (i, build) in queue.iter_mut().enumerate() {
+        for (i, idx) in order.into_iter().enumerate() {
+            let build = &mut queue[idx];
             // Simple estimate: position * avg_duration, adjusted for active builds
             let position = i + 1;
             let wait_ms = if active_count > 0 {
@@ -701,6 +1134,17 @@ impl BuildHistory {
         }
     }
 
+    /// Get a completed build by ID, if it is still in the history buffer.
+    pub fn completed_build(&self, build_id: u64) -> Option<BuildRecord> {
+        self.records
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .rev()
+            .find(|record| record.id == build_id)
+            .cloned()
+    }
+
     /// Get recent builds (most recent first).
     pub fn recent(&self, limit: usize) -> Vec<BuildRecord> {
         let records = self.records.read().unwrap_or_else(|e| e.into_inner());
@@ -737,6 +1181,73 @@ impl BuildHistory {
             .collect()
     }
 
+    /// Query recent builds (most recent first), optionally narrowed to one
+    /// project, to failed builds (non-zero exit), and/or to builds completed
+    /// at or after `since`.
+    ///
+    /// Reads the live in-memory buffer, so it works whether or not the
+    /// history is persisted to disk.
+    pub fn query(
+        &self,
+        project_id: Option<&str>,
+        failed_only: bool,
+        since: Option<DateTime<Utc>>,
+        limit: usize,
+    ) -> Vec<BuildRecord> {
+        let records = self.records.read().unwrap_or_else(|e| e.into_inner());
+        records
+            .iter()
+            .rev()
+            .filter(|r| record_matches(r, project_id, failed_only, since))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// Aggregate the builds matching the [`query`](Self::query) filters per
+    /// project, worker or kind. Groups are ordered by build count, then key.
+    pub fn aggregate(
+        &self,
+        project_id: Option<&str>,
+        failed_only: bool,
+        since: Option<DateTime<Utc>>,
+        group_by: HistoryGroupBy,
+    ) -> Vec<BuildHistoryGroup> {
+        let records = self.records.read().unwrap_or_else(|e| e.into_inner());
+        let mut groups: HashMap<String, (BuildHistoryGroup, Vec<u64>)> = HashMap::new();
+        for record in records
+            .iter()
+            .filter(|r| record_matches(r, project_id, failed_only, since))
+        {
+            let key = group_by.key(record);
+            let (group, durations) = groups.entry(key.clone()).or_insert_with(|| {
+                (
+                    BuildHistoryGroup {
+                        key,
+                        ..Default::default()
+                    },
+                    Vec::new(),
+                )
+            });
+            group.builds += 1;
+            if !build_record_succeeded(record) {
+                group.failures += 1;
+            }
+            group.total_bytes += record.bytes_transferred.unwrap_or(0);
+            durations.push(record.duration_ms);
+        }
+
+        let mut groups: Vec<BuildHistoryGroup> = groups
+            .into_values()
+            .map(|(mut group, mut durations)| {
+                group.median_duration_ms = median_duration_ms(&mut durations);
+                group
+            })
+            .collect();
+        groups.sort_by(|a, b| b.builds.cmp(&a.builds).then_with(|| a.key.cmp(&b.key)));
+        groups
+    }
+
     /// Get aggregate statistics.
     pub fn stats(&self) -> BuildStats {
         let records = self.records.read().unwrap_or_else(|e| e.into_inner());
@@ -764,6 +1275,55 @@ impl BuildHistory {
         }
     }
 
+    /// Compare remote rebuilds that reused a worker's incremental state with
+    /// ones that moved to another worker (see [`IncrementalReuseStats`]).
+    pub fn incremental_reuse_stats(&self) -> IncrementalReuseStats {
+        let records = self.records.read().unwrap_or_else(|e| e.into_inner());
+        let mut last_worker: HashMap<(&str, Option<&str>, &str), &str> = HashMap::new();
+        let (mut warm, mut cold) = (Vec::new(), Vec::new());
+
+        for record in records.iter() {
+            if record.location != BuildLocation::Remote || record.exit_code != 0 {
+                continue;
+            }
+            let Some(worker) = record.worker_id.as_deref() else {
+                continue;
+            };
+            let key = (
+                record.project_id.as_str(),
+                record.toolchain.as_deref(),
+                record.command.as_str(),
+            );
+            match last_worker.insert(key, worker) {
+                Some(previous) if previous == worker => warm.push(record.duration_ms),
+                Some(_) => cold.push(record.duration_ms),
+                None => {}
+            }
+        }
+
+        let avg = |durations: &[u64]| {
+            if durations.is_empty() {
+                0
+            } else {
+                durations.iter().sum::<u64>() / durations.len() as u64
+            }
+        };
+        let (warm_avg_ms, cold_avg_ms) = (avg(&warm), avg(&cold));
+        let rebuild_speedup = if warm_avg_ms > 0 && cold_avg_ms > 0 {
+            cold_avg_ms as f64 / warm_avg_ms as f64
+        } else {
+            0.0
+        };
+
+        IncrementalReuseStats {
+            warm_builds: warm.len(),
+            cold_builds: cold.len(),
+            warm_avg_ms,
+            cold_avg_ms,
+            rebuild_speedup,
+        }
+    }
+
     /// Calculate saved time statistics from remote builds.
     ///
     /// Uses local build history to estimate what remote builds would have taken
@@ -916,11 +1476,17 @@ impl BuildHistory {
         Ok(Self {
             records: RwLock::new(records),
             active: RwLock::new(HashMap::new()),
+            output: RwLock::new(HashMap::new()),
             queued: RwLock::new(VecDeque::new()),
             capacity,
             max_queue_depth: DEFAULT_MAX_QUEUE_DEPTH,
+            queue_fairness: QueueFairness::default(),
+            fair_last_served: RwLock::new(HashMap::new()),
+            fair_serve_seq: AtomicU64::new(1),
+            fair_blocked: RwLock::new(HashSet::new()),
             next_id: AtomicU64::new(initial_id),
             next_queue_id: AtomicU64::new(1),
+            idempotency_keys: RwLock::new(HashMap::new()),
             persistence_path: Some(path.to_path_buf()),
         })
     }
@@ -1011,6 +1577,9 @@ mod tests {
             bytes_transferred: None,
             timing: None,
             cancellation: None,
+            decision_trace: Vec::new(),
+            project_path: None,
+            toolchain: None,
         }
     }
 
@@ -1091,6 +1660,91 @@ mod tests {
         assert!(proj_a_builds.iter().all(|b| b.project_id == "proj-a"));
     }
 
+    #[test]
+    fn test_query_filters_project_and_failures() {
+        let _guard = test_guard!();
+        // No persistence: queries run against the in-memory buffer.
+        let history = BuildHistory::new(10);
+
+        for (id, project, exit_code) in [
+            (1, "proj-a", 0),
+            (2, "proj-a", 101),
+            (3, "proj-b", 1),
+            (4, "proj-a", 0),
+            (5, "proj-a", 2),
+        ] {
+            let mut record = make_build_record(id);
+            record.project_id = project.to_string();
+            record.exit_code = exit_code;
+            history.record(record);
+        }
+
+        let ids = |records: Vec<BuildRecord>| records.iter().map(|r| r.id).collect::<Vec<_>>();
+        assert_eq!(
+            ids(history.query(None, false, None, 10)),
+            vec![5, 4, 3, 2, 1]
+        );
+        assert_eq!(
+            ids(history.query(Some("proj-a"), false, None, 2)),
+            vec![5, 4]
+        );
+        assert_eq!(
+            ids(history.query(Some("proj-a"), true, None, 10)),
+            vec![5, 2]
+        );
+        assert_eq!(ids(history.query(None, true, None, 10)), vec![5, 3, 2]);
+        assert!(history.query(Some("missing"), false, None, 10).is_empty());
+    }
+
+    #[test]
+    fn test_query_since_and_aggregate_by_worker() {
+        let _guard = test_guard!();
+        let history = BuildHistory::new(10);
+        let now = Utc::now();
+
+        for (id, worker, hours_ago, exit_code, duration_ms, bytes) in [
+            (1, Some("css"), 30, 0, 9_000, Some(100)),
+            (2, Some("css"), 3, 0, 1_000, Some(200)),
+            (3, Some("css"), 2, 1, 3_000, Some(300)),
+            (4, Some("fmd"), 1, 0, 5_000, None),
+            (5, None, 1, 0, 700, None),
+        ] {
+            let mut record = make_build_record(id);
+            record.worker_id = worker.map(str::to_string);
+            record.completed_at = (now - ChronoDuration::hours(hours_ago)).to_rfc3339();
+            record.exit_code = exit_code;
+            record.duration_ms = duration_ms;
+            record.bytes_transferred = bytes;
+            history.record(record);
+        }
+        let since = Some(now - ChronoDuration::hours(24));
+
+        let ids: Vec<u64> = history
+            .query(None, false, since, 10)
+            .iter()
+            .map(|r| r.id)
+            .collect();
+        assert_eq!(ids, vec![5, 4, 3, 2]);
+
+        let groups = history.aggregate(None, false, since, HistoryGroupBy::Worker);
+        let css = BuildHistoryGroup {
+            key: "css".to_string(),
+            builds: 2,
+            failures: 1,
+            median_duration_ms: 2_000,
+            total_bytes: 500,
+        };
+        assert_eq!(groups[0], css);
+        let keys: Vec<&str> = groups.iter().map(|g| g.key.as_str()).collect();
+        assert_eq!(keys, vec!["css", "fmd", "local"]);
+
+        let kinds = history.aggregate(None, false, None, HistoryGroupBy::Kind);
+        assert_eq!(kinds.len(), 1);
+        assert_eq!(kinds[0].key, "cargo_build");
+        assert_eq!(kinds[0].builds, 5);
+        assert_eq!(kinds[0].median_duration_ms, 3_000);
+    }
+
     #[test]
     fn test_stats_calculation() {
         let _guard = test_guard!();
@@ -1288,6 +1942,37 @@ mod tests {
         assert!(!active_workers.contains("worker-3"));
     }
 
+    #[test]
+    fn test_abandon_active_build_lists_build_once() {
+        let _guard = test_guard!();
+        let history = BuildHistory::new(10);
+        let active = history.start_active_build(
+            "proj".to_string(),
+            "worker-a".to_string(),
+            "cargo build".to_string(),
+            0,
+            4,
+            BuildLocation::Remote,
+        );
+
+        let abandoned = history
+            .abandon_active_build(active.id, 312)
+            .expect("abandoned build record");
+        assert!(is_abandoned(&abandoned));
+        let cancellation = abandoned.cancellation.as_ref().expect("metadata");
+        assert_eq!(cancellation.final_state, "abandoned");
+        assert_eq!(cancellation.decision_path[0], "no_heartbeat_312s");
+        assert!(history.active_build(active.id).is_none());
+
+        let listed = history.abandoned_builds();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, active.id);
+
+        // A second reaper pass (or a late release) must not reclaim twice.
+        assert!(history.abandon_active_build(active.id, 400).is_none());
+        assert_eq!(history.abandoned_builds().len(), 1);
+    }
+
     #[test]
     fn test_cancel_active_build_records_cancellation_metadata() {
         let _guard = test_guard!();
@@ -1373,10 +2058,12 @@ mod tests {
                 worker_id: rch_common::WorkerId::new("worker-a"),
                 hook_pid: Some(1234),
                 remote_pgid_file: Some("/tmp/rch/proj/hash/.rch-run/1.pgid".to_string()),
+                detached: true,
                 phase: BuildHeartbeatPhase::Execute,
                 detail: Some("Compiling".to_string()),
                 progress_counter: Some(3),
                 progress_percent: Some(25.0),
+                output_lines: Vec::new(),
             })
             .expect("active build should be updated");
 
@@ -1388,9 +2075,86 @@ mod tests {
             updated.remote_pgid_file.as_deref(),
             Some("/tmp/rch/proj/hash/.rch-run/1.pgid")
         );
+        assert!(updated.detached);
         assert_ne!(updated.last_progress_at, initial_progress_at);
     }
 
+    #[test]
+    fn test_build_output_tail_is_bounded_and_dropped_on_finish() {
+        let _guard = test_guard!();
+        let history = BuildHistory::new(10);
+        let build = history.start_active_build(
+            "proj".to_string(),
+            "worker-a".to_string(),
+            "cargo build".to_string(),
+            1234,
+            4,
+            BuildLocation::Remote,
+        );
+        assert_eq!(
+            history.build_output_since(build.id, 0),
+            Some(BuildOutputChunk::default())
+        );
+
+        let lines: Vec<String> = (0..BUILD_OUTPUT_TAIL_LINES + 5)
+            .map(|n| format!("line {n}"))
+            .collect();
+        history.record_build_heartbeat(BuildHeartbeatRequest {
+            build_id: build.id,
+            worker_id: rch_common::WorkerId::new("worker-a"),
+            hook_pid: Some(1234),
+            remote_pgid_file: None,
+            detached: false,
+            phase: BuildHeartbeatPhase::Execute,
+            detail: None,
+            progress_counter: Some(1),
+            progress_percent: None,
+            output_lines: lines,
+        });
+
+        let chunk = history.build_output_since(build.id, 0).unwrap();
+        assert_eq!(chunk.first_seq, 5);
+        assert_eq!(chunk.lines.len(), BUILD_OUTPUT_TAIL_LINES);
+        assert_eq!(chunk.lines[0], "line 5");
+
+        let chunk = history
+            .build_output_since(build.id, BUILD_OUTPUT_TAIL_LINES as u64 + 3)
+            .unwrap();
+        assert_eq!(chunk.lines, vec!["line 203", "line 204"]);
+
+        history.finish_active_build(build.id, 0, None, None, None, Vec::new());
+        assert_eq!(history.build_output_since(build.id, 0), None);
+        assert_eq!(history.completed_build(build.id).unwrap().exit_code, 0);
+    }
+
+    #[test]
+    fn test_set_replay_context_is_kept_in_completed_record() {
+        let _guard = test_guard!();
+        let history = BuildHistory::new(10);
+        let build = history.start_active_build(
+            "proj".to_string(),
+            "worker-a".to_string(),
+            "cargo build".to_string(),
+            1234,
+            2,
+            BuildLocation::Remote,
+        );
+
+        history.set_replay_context(
+            build.id,
+            Some("/data/projects/proj".to_string()),
+            Some("nightly-2026-01-01".to_string()),
+        );
+        history.set_replay_context(build.id, None, None);
+        history.finish_active_build(build.id, 0, None, None, None, Vec::new());
+
+        let record = history.completed_build(build.id).unwrap();
+        assert_eq!(record.project_path.as_deref(), Some("/data/projects/proj"));
+        assert_eq!(record.toolchain.as_deref(), Some("nightly-2026-01-01"));
+        let json = serde_json::to_string(&record).unwrap();
+        assert!(json.contains("\"project_path\":\"/data/projects/proj\""));
+    }
+
     #[test]
     fn test_record_build_heartbeat_rejects_worker_mismatch() {
         let _guard = test_guard!();
@@ -1409,10 +2173,12 @@ mod tests {
             worker_id: rch_common::WorkerId::new("worker-b"),
             hook_pid: Some(9999),
             remote_pgid_file: None,
+            detached: false,
             phase: BuildHeartbeatPhase::Execute,
             detail: Some("Unexpected".to_string()),
             progress_counter: Some(1),
             progress_percent: Some(10.0),
+            output_lines: Vec::new(),
         });
         assert!(
             updated.is_none(),
@@ -1661,6 +2427,140 @@ mod tests {
         assert_eq!(history.queue_position(999), None);
     }
 
+    #[test]
+    fn test_round_robin_queue_interleaves_sessions() {
+        let _guard = test_guard!();
+        let history = BuildHistory::new(10).with_queue_fairness(QueueFairness::RoundRobin);
+        let enqueue = |session: &str, pid: u32| {
+            history
+                .enqueue_session_build(
+                    "proj".into(),
+                    "cargo build".into(),
+                    pid,
+                    4,
+                    Some(session.to_string()),
+                    CommandPriority::Normal,
+                )
+                .unwrap()
+        };
+
+        let a1 = enqueue("session-a", 1);
+        let a2 = enqueue("session-a", 2);
+        let a3 = enqueue("session-a", 3);
+        let b1 = enqueue("session-b", 4);
+
+        // Session B's only build is served second, not after all of A's.
+        assert_eq!(history.queue_position(a1.id), Some(1));
+        assert_eq!(history.queue_position(b1.id), Some(2));
+        assert_eq!(history.queue_position(a2.id), Some(3));
+        assert_eq!(history.queue_position(a3.id), Some(4));
+        assert!(history.is_queue_turn(a1.id));
+        assert!(!history.is_queue_turn(b1.id));
+
+        // Once A is served, B is least recently served and goes first.
+        history.remove_queued_build(a1.id);
+        history.record_queue_service(&a1);
+        let a4 = enqueue("session-a", 5);
+        assert!(history.is_queue_turn(b1.id));
+        let order: Vec<u64> = history.queued_builds().iter().map(|b| b.id).collect();
+        assert_eq!(order, vec![b1.id, a2.id, a3.id, a4.id]);
+    }
+
+    #[test]
+    fn test_round_robin_skips_blocked_head() {
+        let _guard = test_guard!();
+        let history = BuildHistory::new(10).with_queue_fairness(QueueFairness::RoundRobin);
+        let enqueue = |session: &str, pid: u32| {
+            history
+                .enqueue_session_build(
+                    "proj".into(),
+                    "cargo build".into(),
+                    pid,
+                    4,
+                    Some(session.to_string()),
+                    CommandPriority::Normal,
+                )
+                .unwrap()
+        };
+        let a1 = enqueue("session-a", 1);
+        let b1 = enqueue("session-b", 2);
+        let c1 = enqueue("session-c", 3);
+        assert!(!history.is_queue_turn(b1.id));
+
+        // No worker can run A's head right now: B may try, C still waits.
+        history.set_queue_blocked(a1.id, true);
+        assert!(history.is_queue_turn(a1.id));
+        assert!(history.is_queue_turn(b1.id));
+        assert!(!history.is_queue_turn(c1.id));
+
+        history.set_queue_blocked(b1.id, true);
+        assert!(history.is_queue_turn(c1.id));
+
+        // A's next attempt clears its flag and B waits again.
+        history.set_queue_blocked(a1.id, false);
+        assert!(!history.is_queue_turn(b1.id));
+    }
+
+    #[test]
+    fn test_fifo_queue_keeps_arrival_order_and_lets_everyone_poll() {
+        let _guard = test_guard!();
+        let history = BuildHistory::new(10);
+        let a1 = history
+            .enqueue_session_build(
+                "proj".into(),
+                "build".into(),
+                1,
+                4,
+                Some("a".into()),
+                CommandPriority::Normal,
+            )
+            .unwrap();
+        let a2 = history
+            .enqueue_session_build(
+                "proj".into(),
+                "build".into(),
+                2,
+                4,
+                Some("a".into()),
+                CommandPriority::Normal,
+            )
+            .unwrap();
+        let b1 = history
+            .enqueue_session_build(
+                "proj".into(),
+                "build".into(),
+                3,
+                4,
+                Some("b".into()),
+                CommandPriority::Normal,
+            )
+            .unwrap();
+
+        assert_eq!(history.queue_fairness(), QueueFairness::Fifo);
+        assert_eq!(history.queue_position(a2.id), Some(2));
+        assert_eq!(history.queue_position(b1.id), Some(3));
+        assert!(history.is_queue_turn(a1.id) && history.is_queue_turn(b1.id));
+    }
+
+    #[test]
+    fn test_round_robin_falls_back_to_project_without_session() {
+        let _guard = test_guard!();
+        let history = BuildHistory::new(10).with_queue_fairness(QueueFairness::RoundRobin);
+        let a1 = history
+            .enqueue_build("proj-a".into(), "build".into(), 1, 4)
+            .unwrap();
+        let a2 = history
+            .enqueue_build("proj-a".into(), "build".into(), 2, 4)
+            .unwrap();
+        let b1 = history
+            .enqueue_build("proj-b".into(), "build".into(), 3, 4)
+            .unwrap();
+
+        assert_eq!(a1.fairness_key(), "proj-a");
+        assert_eq!(history.queue_position(b1.id), Some(2));
+        assert_eq!(history.queue_position(a2.id), Some(3));
+    }
+
     #[test]
     fn test_remove_queued_build() {
         let _guard = test_guard!();
@@ -1797,6 +2697,42 @@ mod tests {
         assert!((stats.avg_speedup - 2.0).abs() < 0.01);
     }
 
+    #[test]
+    fn test_incremental_reuse_stats_compares_warm_and_cold_rebuilds() {
+        let _guard = test_guard!();
+        let history = BuildHistory::new(10);
+        // (worker, duration): first run, warm rebuild, rotation, warm rebuild.
+        let runs = [("w1", 9000), ("w1", 1000), ("w2", 8000), ("w2", 3000)];
+        for (i, (worker, duration_ms)) in runs.into_iter().enumerate() {
+            let mut record = make_build_record(i as u64 + 1);
+            record.location = BuildLocation::Remote;
+            record.worker_id = Some(worker.to_string());
+            record.duration_ms = duration_ms;
+            history.record(record);
+        }
+        // A different toolchain starts its own chain; a failure is ignored.
+        let mut other_toolchain = make_build_record(5);
+        other_toolchain.location = BuildLocation::Remote;
+        other_toolchain.worker_id = Some("w1".to_string());
+        other_toolchain.toolchain = Some("nightly".to_string());
+        history.record(other_toolchain);
+        let mut failed = make_build_record(6);
+        failed.location = BuildLocation::Remote;
+        failed.worker_id = Some("w1".to_string());
+        failed.exit_code = 101;
+        history.record(failed);
+
+        let stats = history.incremental_reuse_stats();
+        assert_eq!(stats.warm_builds, 2);
+        assert_eq!(stats.cold_builds, 1);
+        assert_eq!(stats.warm_avg_ms, 2000);
+        assert_eq!(stats.cold_avg_ms, 8000);
+        assert!((stats.rebuild_speedup - 4.0).abs() < 0.01);
+
+        let empty = BuildHistory::new(10).incremental_reuse_stats();
+        assert_eq!(empty.rebuild_speedup, 0.0);
+    }
+
     #[test]
     fn test_saved_time_stats_mixed_builds() {
         let _guard = test_guard!();
@@ -1882,4 +2818,63 @@ mod tests {
         // time_saved = max(0, 1000 - 2000) = 0 (no negative savings)
         assert_eq!(stats.time_saved_ms, 0);
     }
+
+    #[test]
+    fn test_coalesce_target_follows_registered_build() {
+        let _guard = test_guard!();
+        let history = BuildHistory::new(10);
+        let build = history.start_active_build(
+            "proj".to_string(),
+            "worker-a".to_string(),
+            "cargo build".to_string(),
+            1234,
+            4,
+            BuildLocation::Remote,
+        );
+
+        assert_eq!(history.coalesce_target("rch-build-abc"), None);
+        history.register_idempotency_key("rch-build-abc".to_string(), build.id);
+        assert_eq!(history.coalesce_target("rch-build-abc"), Some(build.id));
+        assert_eq!(history.coalesce_target("rch-build-other"), None);
+
+        // Once the build completed a rerun starts its own build.
+        history.finish_active_build(build.id, 0, Some(10), None, None, Vec::new());
+        assert_eq!(history.coalesce_target("rch-build-abc"), None);
+    }
+
+    #[test]
+    fn test_coalesce_target_ignores_expired_or_unknown_builds() {
+        let _guard = test_guard!();
+        let history = BuildHistory::new(10);
+        let build = history.start_active_build(
+            "proj".to_string(),
+            "worker-a".to_string(),
+            "cargo build".to_string(),
+            1234,
+            4,
+            BuildLocation::Remote,
+        );
+
+        // Registered before the window: no longer a duplicate.
+        let Some(expired) = Instant::now().checked_sub(BUILD_IDEMPOTENCY_WINDOW * 2) else {
+            return;
+        };
+        history
+            .idempotency_keys
+            .write()
+            .unwrap()
+            .insert("rch-build-old".to_string(), (build.id, expired));
+        assert_eq!(history.coalesce_target("rch-build-old"), None);
+
+        // A build the history no longer knows about cannot be waited on.
+        history.register_idempotency_key("rch-build-gone".to_string(), build.id + 1000);
+        assert_eq!(history.coalesce_target("rch-build-gone"), None);
+        assert!(
+            !history
+                .idempotency_keys
+                .read()
+                .unwrap()
+                .contains_key("rch-build-old")
+        );
+    }
 }
@@ -6,6 +6,7 @@
 //! - `/ready` - Readiness probe (workers available)
 //! - `/budget` - AGENTS.md budget compliance status
 
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -45,6 +46,19 @@ pub fn create_router(state: HttpState) -> Router {
         .with_state(Arc::new(state))
 }
 
+/// Resolve the listen address for the observability server.
+///
+/// An explicit `--metrics-addr` wins (e.g. `0.0.0.0:9100` for fleet scraping);
+/// otherwise `--metrics-port` binds on localhost only, and port 0 disables the
+/// server.
+pub fn resolve_listen_addr(metrics_addr: Option<SocketAddr>, port: u16) -> Option<SocketAddr> {
+    match metrics_addr {
+        Some(addr) => Some(addr),
+        None if port > 0 => Some(SocketAddr::from(([127, 0, 0, 1], port))),
+        None => None,
+    }
+}
+
 /// Handler for `/metrics` - Prometheus metrics export.
 async fn metrics_handler() -> impl IntoResponse {
     match metrics::encode_metrics() {
@@ -131,18 +145,16 @@ async fn budget_handler() -> impl IntoResponse {
 /// Start the HTTP server for observability endpoints.
 ///
 /// # Arguments
-/// * `port` - The port to listen on.
+/// * `addr` - The address to listen on (see [`resolve_listen_addr`]).
 /// * `state` - Shared state for handlers.
 ///
 /// # Returns
 /// A handle to the spawned server task.
 pub async fn start_server(
-    port: u16,
+    addr: SocketAddr,
     state: HttpState,
 ) -> tokio::task::JoinHandle<Result<(), std::io::Error>> {
     let router = create_router(state);
-    // Bind to localhost only for security
-    let addr = std::net::SocketAddr::from(([127, 0, 0, 1], port));
 
     tracing::info!("Starting HTTP server for observability on {}", addr);
 
@@ -294,6 +306,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         pool.add_worker(worker_config).await;
 
@@ -345,6 +360,9 @@ mod tests {
                 total_slots: 4 * i as u32,
                 priority: 100 - i as u32,
                 tags: vec![format!("tag-{}", i)],
+                max_concurrent_builds: None,
+                nice_level: None,
+                ionice_class: None,
             };
             pool.add_worker(worker_config).await;
         }
@@ -507,6 +525,9 @@ mod tests {
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         pool.add_worker(worker_config.clone()).await;
 
@@ -559,6 +580,9 @@ mod tests {
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         pool.add_worker(worker_config.clone()).await;
 
@@ -612,6 +636,9 @@ mod tests {
             total_slots: 4,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         pool.add_worker(healthy_config).await;
 
@@ -624,6 +651,9 @@ mod tests {
             total_slots: 8,
             priority: 50,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         pool.add_worker(unreachable_config.clone()).await;
 
@@ -677,6 +707,9 @@ mod tests {
             total_slots: 2,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         pool.add_worker(worker_config.clone()).await;
 
@@ -789,4 +822,20 @@ mod tests {
             .map(|v| v.to_str().unwrap_or(""));
         assert!(content_type.unwrap().contains("application/json"));
     }
+
+    #[test]
+    fn test_resolve_listen_addr_prefers_explicit_addr() {
+        let explicit: SocketAddr = "0.0.0.0:9200".parse().unwrap();
+        assert_eq!(resolve_listen_addr(Some(explicit), 9100), Some(explicit));
+        assert_eq!(resolve_listen_addr(Some(explicit), 0), Some(explicit));
+    }
+
+    #[test]
+    fn test_resolve_listen_addr_defaults_to_localhost_port() {
+        assert_eq!(
+            resolve_listen_addr(None, 9100),
+            Some(SocketAddr::from(([127, 0, 0, 1], 9100)))
+        );
+        assert_eq!(resolve_listen_addr(None, 0), None);
+    }
 }
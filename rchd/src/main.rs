@@ -23,6 +23,7 @@ mod events;
 mod headroom;
 mod health;
 mod history;
+#[cfg(feature = "metrics")]
 mod http_api;
 mod metrics;
 mod process_triage;
@@ -40,7 +41,7 @@ mod workers;
 
 use anyhow::{Context, Result, bail};
 use chrono::{Duration as ChronoDuration, Local};
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches, Parser};
 use rch_common::{LogConfig, SelfTestConfig, init_logging};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -98,10 +99,16 @@ struct Cli {
     #[arg(short, long)]
     foreground: bool,
 
-    /// Port for HTTP metrics/health endpoints (0 to disable)
+    /// Port for HTTP metrics/health endpoints (0 to disable; requires the
+    /// `metrics` feature)
     #[arg(long, default_value = "9100")]
     metrics_port: u16,
 
+    /// Listen address for the Prometheus `/metrics` endpoint (e.g. 0.0.0.0:9100).
+    /// Overrides --metrics-port, which binds on localhost only.
+    #[arg(long)]
+    metrics_addr: Option<std::net::SocketAddr>,
+
     /// Reset interval for metrics dashboard window, in seconds
     #[arg(long, default_value = "300")]
     metrics_reset_interval: u64,
@@ -150,6 +157,9 @@ pub struct DaemonContext {
     pub pid: u32,
     /// Maximum time a build can wait in queue (seconds) before timing out.
     pub queue_timeout_secs: u64,
+    /// Workers config the daemon was started with (`--workers-config`);
+    /// `None` uses the default location. `POST /reload` re-reads it.
+    pub workers_config: Option<PathBuf>,
 }
 
 /// Result of one bind attempt — distinguishes "socket is held by another
@@ -385,7 +395,12 @@ fn is_stale_socket_connect_error(error: &std::io::Error) -> bool {
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    // The default port is not worth a warning when it cannot be served.
+    #[cfg(not(feature = "metrics"))]
+    let metrics_port_explicit =
+        matches.value_source("metrics_port") == Some(clap::parser::ValueSource::CommandLine);
     let startup_started = Instant::now();
 
     if cli.debug_routing {
@@ -471,6 +486,10 @@ async fn main() -> Result<()> {
         }
     };
 
+    for error in rch_common::util::set_custom_mask_patterns(&rch_config.logging.mask_patterns) {
+        warn!("Ignoring logging.mask_patterns entry: {}", error);
+    }
+
     // Startup self-consistency check (bd-...-3.2): verify the daemon's bound
     // socket, the hook/CLI's configured socket, and the installed Claude Code
     // hook agree, reporting any drift as structured events. Read-only — it
@@ -512,7 +531,7 @@ async fn main() -> Result<()> {
         tracing::debug!("Hook auto-installation disabled via config");
     }
 
-    // Load daemon config for queue + cache cleanup settings
+    // Load daemon config for queue, blocklist + cache cleanup settings
     let daemon_config = match config::load_daemon_config(None) {
         Ok(cfg) => cfg,
         Err(e) => {
@@ -520,21 +539,28 @@ async fn main() -> Result<()> {
             config::DaemonConfig::default()
         }
     };
+    worker_pool.set_blocklist_config(daemon_config.blocklist.clone());
+    worker_pool.load_blocklist(config::default_blocklist_path());
 
     // Initialize build history
+    let queue_fairness = rch_config.selection.fairness.queue;
     let history = if let Some(ref path) = cli.history_file {
         if path.exists() {
             match BuildHistory::load_from_file(path, cli.history_capacity) {
                 Ok(h) => {
                     info!("Loaded build history from {:?} ({} entries)", path, h.len());
-                    Arc::new(h.with_max_queue_depth(daemon_config.queue.max_depth))
+                    Arc::new(
+                        h.with_max_queue_depth(daemon_config.queue.max_depth)
+                            .with_queue_fairness(queue_fairness),
+                    )
                 }
                 Err(e) => {
                     warn!("Failed to load history from {:?}: {}", path, e);
                     Arc::new(
                         BuildHistory::new(cli.history_capacity)
                             .with_persistence(path.clone())
-                            .with_max_queue_depth(daemon_config.queue.max_depth),
+                            .with_max_queue_depth(daemon_config.queue.max_depth)
+                            .with_queue_fairness(queue_fairness),
                     )
                 }
             }
@@ -543,14 +569,16 @@ async fn main() -> Result<()> {
             Arc::new(
                 BuildHistory::new(cli.history_capacity)
                     .with_persistence(path.clone())
-                    .with_max_queue_depth(daemon_config.queue.max_depth),
+                    .with_max_queue_depth(daemon_config.queue.max_depth)
+                    .with_queue_fairness(queue_fairness),
             )
         }
     } else {
         info!("Build history in-memory only (no persistence)");
         Arc::new(
             BuildHistory::new(cli.history_capacity)
-                .with_max_queue_depth(daemon_config.queue.max_depth),
+                .with_max_queue_depth(daemon_config.queue.max_depth)
+                .with_queue_fairness(queue_fairness),
         )
     };
 
@@ -721,10 +749,12 @@ async fn main() -> Result<()> {
         version: env!("CARGO_PKG_VERSION"),
         pid: std::process::id(),
         queue_timeout_secs: daemon_config.queue.timeout_secs,
+        workers_config: cli.workers_config.clone(),
     };
 
     // Start active build cleanup background task
-    let active_cleanup = cleanup::ActiveBuildCleanup::new(context.clone());
+    let active_cleanup = cleanup::ActiveBuildCleanup::new(context.clone())
+        .with_heartbeat_timeout_secs(rch_config.selection.heartbeat_timeout_secs);
     let _active_cleanup_handle = active_cleanup.start();
 
     let worker_status_panel = Arc::new(Mutex::new(
@@ -736,7 +766,8 @@ async fn main() -> Result<()> {
     let metrics_dashboard = Arc::new(Mutex::new(MetricsDashboard::new(metrics_interval)));
 
     // Start health monitor with alert manager integration
-    let health_config = health::HealthConfig::default();
+    let health_config = health::HealthConfig::default()
+        .with_deep_check_interval_secs(rch_config.selection.deep_health_check_interval_secs);
     let health_monitor = health::HealthMonitor::new(worker_pool.clone(), health_config)
         .with_status_panel(worker_status_panel.clone())
         .with_alert_manager(alert_manager.clone());
@@ -821,24 +852,61 @@ async fn main() -> Result<()> {
         }
     }));
 
+    // A detached daemon's stdout/stderr append to the log its spawner named
+    // (`rchd.log`); keep that file bounded for as long as the daemon runs.
+    if let Some(log_path) = std::env::var_os(rch_common::log_retention::DAEMON_STDIO_LOG_ENV)
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+    {
+        tokio::spawn(async move {
+            let mut ticker = interval(Duration::from_secs(60));
+            loop {
+                ticker.tick().await;
+                match rch_common::log_retention::rotate_stdio_log_if_oversized(
+                    &log_path,
+                    rch_common::log_retention::DAEMON_STDIO_LOG_MAX_BYTES,
+                ) {
+                    Ok(true) => info!("Rotated daemon log {}", log_path.display()),
+                    Ok(false) => {}
+                    Err(e) => debug!("Failed to rotate daemon log {}: {}", log_path.display(), e),
+                }
+            }
+        });
+    }
+
     if let Some(storage) = telemetry_storage {
         let _maintenance = telemetry::start_storage_maintenance(storage);
         info!("Telemetry storage maintenance started");
     }
 
     // Start HTTP server for metrics/health endpoints (if enabled)
-    let _http_handle = if cli.metrics_port > 0 {
+    #[cfg(feature = "metrics")]
+    let metrics_listen_addr = http_api::resolve_listen_addr(cli.metrics_addr, cli.metrics_port);
+    #[cfg(feature = "metrics")]
+    let _http_handle = if let Some(addr) = metrics_listen_addr {
         let http_state = http_api::HttpState {
             pool: worker_pool.clone(),
             version: env!("CARGO_PKG_VERSION"),
             started_at: context.started_at,
             pid: context.pid,
         };
-        Some(http_api::start_server(cli.metrics_port, http_state).await)
+        Some(http_api::start_server(addr, http_state).await)
     } else {
         info!("HTTP metrics endpoint disabled (port 0)");
         None
     };
+    #[cfg(not(feature = "metrics"))]
+    let metrics_listen_addr: Option<std::net::SocketAddr> = {
+        if cli.metrics_addr.is_some() {
+            warn!("--metrics-addr ignored: rchd was built without the `metrics` feature");
+        } else if metrics_port_explicit && cli.metrics_port > 0 {
+            warn!(
+                "--metrics-port {} ignored: rchd was built without the `metrics` feature",
+                cli.metrics_port
+            );
+        }
+        None
+    };
 
     let commit_hash = rch_common::build_commit().map(|value| value.to_string());
 
@@ -850,7 +918,7 @@ async fn main() -> Result<()> {
         cli.socket.to_string_lossy().to_string(),
         worker_count,
         total_slots,
-        cli.metrics_port,
+        metrics_listen_addr.map_or(0, |addr| addr.port()),
         true,
         otel_enabled,
         context.pid,
@@ -1119,6 +1187,7 @@ mod tests {
         assert_eq!(cli.socket, crate::config::default_socket_path());
         assert_eq!(cli.history_capacity, 100);
         assert_eq!(cli.metrics_port, 9100);
+        assert_eq!(cli.metrics_addr, None);
         assert_eq!(cli.metrics_reset_interval, 300);
 
         assert!(!cli.verbose);
@@ -1142,6 +1211,8 @@ mod tests {
             "250",
             "--metrics-port",
             "0",
+            "--metrics-addr",
+            "0.0.0.0:9200",
             "--metrics-reset-interval",
             "60",
             "--debug-routing",
@@ -1162,6 +1233,7 @@ mod tests {
         );
         assert_eq!(cli.history_capacity, 250);
         assert_eq!(cli.metrics_port, 0);
+        assert_eq!(cli.metrics_addr, Some("0.0.0.0:9200".parse().unwrap()));
         assert_eq!(cli.metrics_reset_interval, 60);
         assert!(cli.debug_routing);
         assert!(cli.no_hot_reload);
@@ -1311,6 +1383,7 @@ mod tests {
             version: "0.1.0-test",
             pid: std::process::id(),
             queue_timeout_secs: 300,
+            workers_config: None,
         };
 
         assert_eq!(context.socket_path, "/tmp/test.sock");
@@ -1335,6 +1408,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec!["rust".to_string()],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         pool.add_worker(worker_config).await;
 
@@ -1361,6 +1437,7 @@ mod tests {
             version: env!("CARGO_PKG_VERSION"),
             pid: std::process::id(),
             queue_timeout_secs: 300,
+            workers_config: None,
         };
 
         assert_eq!(context.pool.len(), 1);
@@ -1391,6 +1468,9 @@ mod tests {
             bytes_transferred: Some(1024),
             timing: None,
             cancellation: None,
+            decision_trace: Vec::new(),
+            project_path: None,
+            toolchain: None,
         };
         history.record(record);
 
@@ -1413,6 +1493,7 @@ mod tests {
             version: "0.1.0",
             pid: 12345,
             queue_timeout_secs: 300,
+            workers_config: None,
         };
 
         assert_eq!(context.history.len(), 1);
@@ -1448,6 +1529,7 @@ mod tests {
             version: "0.1.0",
             pid: 1234,
             queue_timeout_secs: 300,
+            workers_config: None,
         };
 
         // Clone the context
@@ -1467,6 +1549,9 @@ mod tests {
             total_slots: 4,
             priority: 50,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         context.pool.add_worker(worker_config).await;
 
@@ -1502,6 +1587,7 @@ mod tests {
             version: "0.1.0",
             pid: 1234,
             queue_timeout_secs: 300,
+            workers_config: None,
         };
 
         // Wait a small amount
@@ -1528,6 +1614,9 @@ mod tests {
                 total_slots: (i * 4) as u32,
                 priority: 100 - i as u32,
                 tags: vec![format!("tag-{}", i)],
+                max_concurrent_builds: None,
+                nice_level: None,
+                ionice_class: None,
             };
             pool.add_worker(worker_config).await;
         }
@@ -1554,6 +1643,7 @@ mod tests {
             version: "0.1.0",
             pid: 1234,
             queue_timeout_secs: 300,
+            workers_config: None,
         };
 
         assert_eq!(context.pool.len(), 5);
@@ -1585,6 +1675,9 @@ mod tests {
                 bytes_transferred: None,
                 timing: None,
                 cancellation: None,
+                decision_trace: Vec::new(),
+                project_path: None,
+                toolchain: None,
             };
             history.record(record);
         }
@@ -1625,6 +1718,9 @@ mod tests {
                 bytes_transferred: None,
                 timing: None,
                 cancellation: None,
+                decision_trace: Vec::new(),
+                project_path: None,
+                toolchain: None,
             };
             history.record(record);
         }
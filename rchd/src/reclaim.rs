@@ -206,7 +206,7 @@ pub struct ReclaimCycleStats {
 ///
 /// The generated script:
 /// 1. Enumerates candidate directories under `remote_base`
-/// 2. Skips protected paths and recently-active directories
+/// 2. Skips protected paths, pinned (`.rch-keep`) and recently-active directories
 /// 3. Sorts by modification time (oldest first) for deterministic ordering
 /// 4. Removes up to `max_dirs` directories, tracking freed space
 /// 5. Stops when `max_freed_kb` budget is exhausted or disk is healthy
@@ -255,6 +255,7 @@ fn build_reclaim_command(
            [ ! -d \"$dir\" ] && continue; \
            case \"$dir\" in \"$base\"/*) ;; *) continue ;; esac; \
            {protected_checks}\
+           {skip_pinned}\
            candidates=$((candidates + 1)); \
            recent_active=$(find \"$dir\" -type f -mmin -\"$min_idle_minutes\" -print -quit 2>/dev/null || true); \
            if [ -n \"$recent_active\" ]; then protected=$((protected + 1)); continue; fi; \
@@ -278,6 +279,7 @@ fn build_reclaim_command(
         max_freed_kb = max_freed_kb,
         threshold_kb = threshold_kb,
         protected_checks = protected_checks,
+        skip_pinned = rch_common::remote_keep::skip_pinned_snippet(),
         remove_action = remove_action,
     )
 }
@@ -641,6 +643,9 @@ mod tests {
             total_slots: 8,
             priority: 50,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         }
     }
 
@@ -789,7 +794,7 @@ mod tests {
             4,
             BuildLocation::Remote,
         );
-        history.finish_active_build(build.id, 0, Some(1000), None, None);
+        history.finish_active_build(build.id, 0, Some(1000), None, None, Vec::new());
 
         let config = create_test_worker_config("worker-1");
         let worker_state = WorkerState::new(config);
@@ -145,6 +145,9 @@ fn worker_config_changed(old: &WorkerConfig, new: &WorkerConfig) -> bool {
         || old.total_slots != new.total_slots
         || old.priority != new.priority
         || old.tags != new.tags
+        || old.max_concurrent_builds != new.max_concurrent_builds
+        || old.nice_level != new.nice_level
+        || old.ionice_class != new.ionice_class
 }
 
 /// Validate a new workers configuration.
@@ -160,7 +163,17 @@ pub fn validate_workers_config(config: &WorkersConfig) -> Result<Vec<String>> {
     // the worker the next time the daemon polled telemetry. We require a
     // conservative, predictable character set up-front so every downstream
     // consumer can rely on it without re-validating.
-    for worker in &config.workers {
+    for name in config.fleet.keys() {
+        if !is_safe_worker_id(name) {
+            return Err(anyhow::anyhow!(
+                "Invalid fleet name '{}': must be 1-64 chars and contain only \
+                 letters, digits, '_', '-', or '.'",
+                name
+            ));
+        }
+    }
+    let entries = config.all_entries();
+    for worker in &entries {
         if !is_safe_worker_id(&worker.id) {
             return Err(anyhow::anyhow!(
                 "Invalid worker ID '{}': must be 1-64 chars and contain only \
@@ -170,24 +183,51 @@ pub fn validate_workers_config(config: &WorkersConfig) -> Result<Vec<String>> {
         }
     }
 
-    // Check for duplicate IDs
+    for worker in &entries {
+        if let Some(level) = worker.nice_level
+            && !rch_common::NICE_LEVEL_RANGE.contains(&level)
+        {
+            return Err(anyhow::anyhow!(
+                "Worker {} has nice_level = {}; must be between -20 and 19",
+                worker.id,
+                level
+            ));
+        }
+        if let Some(class) = worker.ionice_class
+            && !rch_common::IONICE_CLASS_RANGE.contains(&class)
+        {
+            return Err(anyhow::anyhow!(
+                "Worker {} has ionice_class = {}; must be 2 (best-effort) or 3 (idle)",
+                worker.id,
+                class
+            ));
+        }
+    }
+
+    // Check for duplicate IDs (worker IDs are global across fleets)
     let mut seen_ids = HashSet::new();
-    for worker in &config.workers {
+    for worker in &entries {
         if !seen_ids.insert(&worker.id) {
             return Err(anyhow::anyhow!("Duplicate worker ID: {}", worker.id));
         }
     }
 
     // Warn about workers with 0 slots
-    for worker in &config.workers {
+    for worker in &entries {
         if worker.total_slots == 0 && worker.enabled {
             warnings.push(format!("Worker {} has 0 slots", worker.id));
         }
+        if worker.max_concurrent_builds == Some(0) && worker.enabled {
+            warnings.push(format!(
+                "Worker {} has max_concurrent_builds = 0 and will never be selected",
+                worker.id
+            ));
+        }
     }
 
     // Warn if no workers are enabled
-    let enabled_count = config.workers.iter().filter(|w| w.enabled).count();
-    if enabled_count == 0 && !config.workers.is_empty() {
+    let enabled_count = entries.iter().filter(|w| w.enabled).count();
+    if enabled_count == 0 && !entries.is_empty() {
         warnings.push("No workers are enabled".to_string());
     }
 
@@ -292,7 +332,7 @@ pub async fn reload_workers(
 
     // Convert to WorkerConfig and filter enabled
     let new_workers: Vec<WorkerConfig> = new_config
-        .workers
+        .all_entries()
         .into_iter()
         .filter(|w| w.enabled)
         .map(WorkerConfig::from)
@@ -321,6 +361,14 @@ pub async fn reload_workers(
     Ok(result)
 }
 
+/// Reload daemon.toml and apply the settings the running pool reads live.
+pub fn reload_daemon_settings(pool: &WorkerPool, config_path: Option<&Path>) -> Result<()> {
+    let daemon_config =
+        config::load_daemon_config(config_path).context("Failed to load daemon configuration")?;
+    pool.set_blocklist_config(daemon_config.blocklist);
+    Ok(())
+}
+
 /// Messages sent by the file watcher.
 #[derive(Debug)]
 pub enum ReloadMessage {
@@ -381,7 +429,10 @@ impl ConfigWatcher {
                         for path in event.paths {
                             if let Some(filename) = path.file_name() {
                                 let name = filename.to_string_lossy();
-                                if name == "workers.toml" || name == "config.toml" {
+                                if name == "workers.toml"
+                                    || name == "config.toml"
+                                    || name == "daemon.toml"
+                                {
                                     debug!("Config file changed: {:?}", path);
                                     if let Err(e) = watcher_tx
                                         .blocking_send(ReloadMessage::ConfigChanged(path.clone()))
@@ -490,6 +541,9 @@ impl ConfigWatcher {
                 // Fail-open: keep running with existing config
             }
         }
+        if let Err(e) = reload_daemon_settings(&self.pool, None) {
+            error!("Daemon settings reload failed: {}", e);
+        }
     }
 }
 
@@ -546,6 +600,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         }];
 
         let diff = compute_worker_diff(&pool, &new_workers).await.unwrap();
@@ -567,6 +624,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         pool.add_worker(initial_config).await;
 
@@ -579,6 +639,9 @@ mod tests {
             total_slots: 16, // Changed
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let diff = compute_worker_diff(&pool, &[updated_config]).await.unwrap();
@@ -600,6 +663,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         pool.add_worker(config).await;
 
@@ -626,6 +692,9 @@ mod tests {
                     priority: 100,
                     tags: vec![],
                     enabled: true,
+                    max_concurrent_builds: None,
+                    nice_level: None,
+                    ionice_class: None,
                 },
                 config::WorkerEntry {
                     id: "worker1".to_string(), // Duplicate
@@ -636,14 +705,48 @@ mod tests {
                     priority: 50,
                     tags: vec![],
                     enabled: true,
+                    max_concurrent_builds: None,
+                    nice_level: None,
+                    ionice_class: None,
                 },
             ],
+            ..Default::default()
         };
 
         let result = validate_workers_config(&config);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_validate_workers_config_duplicate_ids_across_fleets() {
+        let _guard = test_guard!();
+        init_test_logging();
+
+        let config: WorkersConfig = toml::from_str(
+            r#"
+[[workers]]
+id = "worker1"
+host = "host1"
+
+[[fleet.fast.workers]]
+id = "worker1"
+host = "host2"
+"#,
+        )
+        .unwrap();
+        assert!(validate_workers_config(&config).is_err());
+
+        let config: WorkersConfig = toml::from_str(
+            r#"
+[[fleet."bad;name".workers]]
+id = "worker2"
+host = "host2"
+"#,
+        )
+        .unwrap();
+        assert!(validate_workers_config(&config).is_err());
+    }
+
     #[test]
     fn test_validate_workers_config_zero_slots_warning() {
         let _guard = test_guard!();
@@ -659,7 +762,11 @@ mod tests {
                 priority: 100,
                 tags: vec![],
                 enabled: true,
+                max_concurrent_builds: None,
+                nice_level: None,
+                ionice_class: None,
             }],
+            ..Default::default()
         };
 
         let warnings = validate_workers_config(&config).unwrap();
@@ -681,6 +788,9 @@ mod tests {
                 total_slots: 8,
                 priority: 100,
                 tags: vec![],
+                max_concurrent_builds: None,
+                nice_level: None,
+                ionice_class: None,
             }],
             to_update: vec![],
             to_remove: vec![],
@@ -719,6 +829,9 @@ enabled = true
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         pool.add_worker(initial).await;
 
@@ -761,6 +874,9 @@ enabled = true
             total_slots: 8, // Will be updated to 16
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         pool.add_worker(initial).await;
 
@@ -840,6 +956,9 @@ enabled = true
                 total_slots: 8,
                 priority: 100,
                 tags: vec![],
+                max_concurrent_builds: None,
+                nice_level: None,
+                ionice_class: None,
             }],
             to_update: vec![],
             to_remove: vec![],
@@ -860,6 +979,9 @@ enabled = true
                 total_slots: 8,
                 priority: 100,
                 tags: vec![],
+                max_concurrent_builds: None,
+                nice_level: None,
+                ionice_class: None,
             }],
             to_remove: vec![],
         };
@@ -892,7 +1014,11 @@ enabled = true
                 priority: 100,
                 tags: vec![],
                 enabled: false,
+                max_concurrent_builds: None,
+                nice_level: None,
+                ionice_class: None,
             }],
+            ..Default::default()
         };
 
         let warnings = validate_workers_config(&config).unwrap();
@@ -915,19 +1041,52 @@ enabled = true
                 priority: 100,
                 tags: vec![],
                 enabled: true,
+                max_concurrent_builds: None,
+                nice_level: None,
+                ionice_class: None,
             }],
+            ..Default::default()
         };
 
         let warnings = validate_workers_config(&config).unwrap();
         assert!(warnings.is_empty());
     }
 
+    #[test]
+    fn test_validate_workers_config_rejects_out_of_range_priority() {
+        let _guard = test_guard!();
+        init_test_logging();
+
+        let entry = |nice_level, ionice_class| WorkersConfig {
+            workers: vec![config::WorkerEntry {
+                id: "desk".to_string(),
+                host: "host1".to_string(),
+                user: "ubuntu".to_string(),
+                identity_file: "~/.ssh/id_rsa".to_string(),
+                total_slots: 8,
+                priority: 100,
+                tags: vec![],
+                enabled: true,
+                max_concurrent_builds: None,
+                nice_level,
+                ionice_class,
+            }],
+            ..Default::default()
+        };
+
+        assert!(validate_workers_config(&entry(Some(10), Some(3))).is_ok());
+        let err = validate_workers_config(&entry(Some(20), None)).unwrap_err();
+        assert!(err.to_string().contains("nice_level = 20"), "{err}");
+        let err = validate_workers_config(&entry(None, Some(4))).unwrap_err();
+        assert!(err.to_string().contains("ionice_class = 4"), "{err}");
+    }
+
     #[test]
     fn test_validate_workers_config_empty() {
         let _guard = test_guard!();
         init_test_logging();
 
-        let config = WorkersConfig { workers: vec![] };
+        let config = WorkersConfig::default();
 
         // Empty config should be valid (no duplicates, no "no workers enabled" warning because workers is empty)
         let warnings = validate_workers_config(&config).unwrap();
@@ -990,7 +1149,11 @@ enabled = true
                 total_slots: 4,
                 priority: 100,
                 tags: vec![],
+                max_concurrent_builds: None,
+                nice_level: None,
+                ionice_class: None,
             }],
+            ..Default::default()
         };
 
         let err = validate_workers_config(&config).unwrap_err();
@@ -1013,6 +1176,9 @@ enabled = true
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         pool.add_worker(initial).await;
 
@@ -1024,6 +1190,9 @@ enabled = true
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let diff = compute_worker_diff(&pool, &[updated]).await.unwrap();
@@ -1045,6 +1214,9 @@ enabled = true
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         pool.add_worker(initial).await;
 
@@ -1056,6 +1228,9 @@ enabled = true
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let diff = compute_worker_diff(&pool, &[updated]).await.unwrap();
@@ -1075,6 +1250,9 @@ enabled = true
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         pool.add_worker(initial).await;
 
@@ -1086,6 +1264,9 @@ enabled = true
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let diff = compute_worker_diff(&pool, &[updated]).await.unwrap();
@@ -1105,6 +1286,9 @@ enabled = true
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         pool.add_worker(initial).await;
 
@@ -1116,6 +1300,9 @@ enabled = true
             total_slots: 8,
             priority: 50, // Changed priority
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let diff = compute_worker_diff(&pool, &[updated]).await.unwrap();
@@ -1135,6 +1322,9 @@ enabled = true
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         pool.add_worker(initial).await;
 
@@ -1146,12 +1336,40 @@ enabled = true
             total_slots: 8,
             priority: 100,
             tags: vec!["gpu".to_string()], // Changed tags
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let diff = compute_worker_diff(&pool, &[updated]).await.unwrap();
         assert_eq!(diff.to_update.len(), 1);
     }
 
+    #[tokio::test]
+    async fn test_reload_applies_max_concurrent_builds_change() {
+        init_test_logging();
+
+        let pool = WorkerPool::new();
+        let initial = WorkerConfig {
+            id: WorkerId::new("worker1"),
+            host: "192.168.1.100".to_string(),
+            ..Default::default()
+        };
+        pool.add_worker(initial.clone()).await;
+
+        let updated = WorkerConfig {
+            max_concurrent_builds: Some(2), // Changed cap
+            ..initial
+        };
+
+        let diff = compute_worker_diff(&pool, &[updated]).await.unwrap();
+        assert_eq!(diff.to_update.len(), 1);
+        apply_worker_diff(&pool, &diff).await.unwrap();
+
+        let worker = pool.get(&WorkerId::new("worker1")).await.unwrap();
+        assert_eq!(worker.config.read().await.max_concurrent_builds, Some(2));
+    }
+
     #[tokio::test]
     async fn test_compute_worker_diff_no_change() {
         init_test_logging();
@@ -1165,6 +1383,9 @@ enabled = true
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         pool.add_worker(config.clone()).await;
 
@@ -1185,6 +1406,9 @@ enabled = true
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         pool.add_worker(initial).await;
 
@@ -1198,6 +1422,9 @@ enabled = true
                 total_slots: 16, // Updated slots
                 priority: 100,
                 tags: vec![],
+                max_concurrent_builds: None,
+                nice_level: None,
+                ionice_class: None,
             }],
             to_remove: vec![],
         };
@@ -1221,6 +1448,9 @@ enabled = true
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         pool.add_worker(config).await;
 
@@ -1326,6 +1556,22 @@ enabled = true
         assert!(result.warnings.is_empty());
     }
 
+    #[test]
+    fn test_reload_daemon_settings_applies_blocklist() {
+        let _guard = test_guard!();
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("daemon.toml");
+        std::fs::write(&path, "[blocklist]\nfailure_threshold = 1\n").unwrap();
+
+        let pool = WorkerPool::new();
+        let id = WorkerId::new("flaky");
+        assert!(!pool.record_build_exit(&id, 1));
+
+        reload_daemon_settings(&pool, Some(&path)).unwrap();
+        assert!(pool.record_build_exit(&id, 1));
+        assert!(pool.is_blocked(&id));
+    }
+
     #[tokio::test]
     async fn test_config_watcher_new() {
         init_test_logging();
@@ -1367,6 +1613,9 @@ enabled = true
                 total_slots: 8,
                 priority: 100,
                 tags: vec![],
+                max_concurrent_builds: None,
+                nice_level: None,
+                ionice_class: None,
             };
             pool.add_worker(config).await;
         }
@@ -1381,6 +1630,9 @@ enabled = true
                 total_slots: 16, // Updated
                 priority: 100,
                 tags: vec![],
+                max_concurrent_builds: None,
+                nice_level: None,
+                ionice_class: None,
             },
             WorkerConfig {
                 id: WorkerId::new("worker3"),
@@ -1390,6 +1642,9 @@ enabled = true
                 total_slots: 8,
                 priority: 100,
                 tags: vec![],
+                max_concurrent_builds: None,
+                nice_level: None,
+                ionice_class: None,
             },
             WorkerConfig {
                 id: WorkerId::new("worker4"),
@@ -1399,6 +1654,9 @@ enabled = true
                 total_slots: 4,
                 priority: 50,
                 tags: vec!["gpu".to_string()],
+                max_concurrent_builds: None,
+                nice_level: None,
+                ionice_class: None,
             },
         ];
 
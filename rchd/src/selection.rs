@@ -19,8 +19,8 @@ use crate::ui::workers::{debug_routing_enabled, log_routing_decision};
 use crate::workers::{WorkerPool, WorkerState};
 use rand::RngExt;
 use rch_common::{
-    CircuitBreakerConfig, CircuitState, CommandPriority, RequiredRuntime, SelectionConfig,
-    SelectionDiagnostics, SelectionReason, SelectionRequest, SelectionStrategy,
+    CircuitBreakerConfig, CircuitState, CommandPriority, HealthFailureReason, RequiredRuntime,
+    SelectionConfig, SelectionDiagnostics, SelectionReason, SelectionRequest, SelectionStrategy,
     SelectionWeightConfig, SshClient, SshOptions, ToolchainInfo, WorkerCapabilities, WorkerId,
     WorkerSelectionDiagnostic, WorkerSelectionDiagnosticDecision, WorkerStatus, classify_command,
 };
@@ -32,7 +32,11 @@ use tokio::sync::RwLock;
 use tracing::{debug, warn};
 
 const DEFAULT_NETWORK_SCORE: f64 = 0.5;
+/// Low-load score for workers without a load-average reading.
+const DEFAULT_LOW_LOAD_SCORE: f64 = 0.5;
 const NETWORK_LATENCY_HALF_LIFE_MS: f64 = 200.0;
+/// Measured upload bandwidth that scores 0.5 (10 MiB/s).
+const NETWORK_BANDWIDTH_HALF_SCORE_BPS: f64 = 10.0 * 1024.0 * 1024.0;
 const PRIORITY_BUCKET_SCORE: f64 = 10_000.0;
 const PRIORITY_CACHE_TIEBREAK_SCORE: f64 = 1_000.0;
 const PRIORITY_SPEED_TIEBREAK_SCORE: f64 = 10.0;
@@ -1043,6 +1047,36 @@ impl WorkerSelector {
         // Check if the fallback worker is viable
         let worker_id = WorkerId::new(&fallback_id);
         let worker = pool.get(&worker_id).await?;
+        if !worker
+            .config
+            .read()
+            .await
+            .in_fleet(request.fleet.as_deref())
+        {
+            debug!(
+                "Affinity fallback worker {} skipped: not in requested fleet",
+                fallback_id
+            );
+            return None;
+        }
+        if !request.allowed_workers.is_empty() && !request.allowed_workers.contains(&worker_id) {
+            debug!(
+                "Affinity fallback worker {} skipped: not in allowed workers",
+                fallback_id
+            );
+            return None;
+        }
+        let capabilities = worker.capabilities().await;
+        if let Some(reason) = insufficient_disk_space(request, &capabilities)
+            .or_else(|| insufficient_memory(self.config.min_memory_gb, request, &capabilities))
+            .or_else(|| missing_rsync(&capabilities))
+        {
+            debug!(
+                "Affinity fallback worker {} skipped: {}",
+                fallback_id, reason
+            );
+            return None;
+        }
 
         // Mirror the main selection path / healthy_workers(): never fall back onto
         // a worker that is not assignable (operator-Drained/Disabled, Unreachable,
@@ -1069,6 +1103,20 @@ impl WorkerSelector {
             );
             return None;
         }
+        if worker.at_build_cap().await {
+            debug!(
+                "Affinity fallback worker {} skipped: at concurrent build cap",
+                fallback_id
+            );
+            return None;
+        }
+        if pool.is_blocked(&worker_id) {
+            debug!(
+                "Affinity fallback worker {} skipped: blocked after build failures",
+                fallback_id
+            );
+            return None;
+        }
 
         // Check circuit state (don't use if open)
         if let Some(circuit_state) = worker.circuit_state().await
@@ -1272,7 +1320,9 @@ impl WorkerSelector {
         request: &SelectionRequest,
         excluded_worker_ids: &HashSet<String>,
     ) -> SelectionDiagnostics {
-        let all_workers = pool.all_workers().await;
+        let mut all_workers = pool.all_workers().await;
+        retain_fleet(&mut all_workers, request.fleet.as_deref()).await;
+        retain_allowed_workers(&mut all_workers, &request.allowed_workers).await;
         let mut diagnostics = Vec::with_capacity(all_workers.len());
         let mut active_project_exclusion_count = 0usize;
 
@@ -1321,6 +1371,10 @@ impl WorkerSelector {
 
             let mut reason_codes = Vec::new();
             let mut soft_reason: Option<String> = None;
+            // Only `transfer.artifact_archive` needs zstd, so this is informational.
+            if capabilities.is_missing_tool("zstd") {
+                push_reason_code(&mut reason_codes, "tools.zstd_missing");
+            }
 
             let (final_decision, final_reason) =
                 if !matches!(status, WorkerStatus::Healthy | WorkerStatus::Degraded) {
@@ -1373,6 +1427,32 @@ impl WorkerSelector {
                             request.estimated_cores
                         ),
                     )
+                } else if worker.at_build_cap().await {
+                    push_reason_code(&mut reason_codes, "worker.build_cap_reached");
+                    (
+                        WorkerSelectionDiagnosticDecision::Deny,
+                        format!(
+                            "{} builds active, at concurrent build cap",
+                            worker.active_builds()
+                        ),
+                    )
+                } else if pool.is_blocked(&worker_id) {
+                    push_reason_code(&mut reason_codes, "worker.blocked_after_failures");
+                    (
+                        WorkerSelectionDiagnosticDecision::Deny,
+                        "blocked after consecutive failed builds".to_string(),
+                    )
+                } else if let Some(reason) = insufficient_disk_space(request, &capabilities) {
+                    push_reason_code(&mut reason_codes, "disk.insufficient");
+                    (WorkerSelectionDiagnosticDecision::Deny, reason)
+                } else if let Some(reason) =
+                    insufficient_memory(self.config.min_memory_gb, request, &capabilities)
+                {
+                    push_reason_code(&mut reason_codes, "memory.insufficient");
+                    (WorkerSelectionDiagnosticDecision::Deny, reason)
+                } else if let Some(reason) = missing_rsync(&capabilities) {
+                    push_reason_code(&mut reason_codes, "tools.rsync_missing");
+                    (WorkerSelectionDiagnosticDecision::Deny, reason)
                 } else if let Some(false) = capabilities.is_topology_healthy() {
                     push_reason_code(&mut reason_codes, "topology.preflight_failed");
                     (
@@ -1634,7 +1714,9 @@ impl WorkerSelector {
             gate.begin_round().await;
         }
 
-        let workers = pool.healthy_workers().await;
+        let mut workers = pool.healthy_workers().await;
+        retain_fleet(&mut workers, request.fleet.as_deref()).await;
+        retain_allowed_workers(&mut workers, &request.allowed_workers).await;
 
         if workers.is_empty() {
             if pool.is_empty() {
@@ -1642,9 +1724,15 @@ impl WorkerSelector {
             }
 
             // Check why no healthy workers
-            let all_workers = pool.all_workers().await;
+            let mut all_workers = pool.all_workers().await;
+            retain_fleet(&mut all_workers, request.fleet.as_deref()).await;
+            retain_allowed_workers(&mut all_workers, &request.allowed_workers).await;
+            if all_workers.is_empty() {
+                return Err(SelectionReason::NoMatchingWorkers);
+            }
             let mut all_circuits_open = true;
             let mut all_unreachable = true;
+            let mut all_operator_excluded = true;
 
             for worker in &all_workers {
                 if let Some(state) = worker.circuit_state().await
@@ -1652,16 +1740,23 @@ impl WorkerSelector {
                 {
                     all_circuits_open = false;
                 }
+                let status = worker.status().await;
                 if !matches!(
-                    worker.status().await,
+                    status,
                     rch_common::WorkerStatus::Unreachable
                         | rch_common::WorkerStatus::Drained
                         | rch_common::WorkerStatus::Disabled
                 ) {
                     all_unreachable = false;
                 }
+                if !is_operator_excluded(status) {
+                    all_operator_excluded = false;
+                }
             }
 
+            if all_operator_excluded {
+                return Err(SelectionReason::AllWorkersDraining);
+            }
             if all_circuits_open {
                 return Err(SelectionReason::AllCircuitsOpen);
             }
@@ -1687,7 +1782,12 @@ impl WorkerSelector {
         let mut filtered_by_convergence = 0usize;
         let mut filtered_by_pressure = 0usize;
         let mut filtered_by_slots = 0usize;
+        let mut filtered_by_build_cap = 0usize;
+        let mut filtered_by_blocklist = 0usize;
         let mut filtered_by_active_project = 0usize;
+        let mut filtered_by_disk = 0usize;
+        let mut filtered_by_memory = 0usize;
+        let mut filtered_by_missing_rsync = 0usize;
         let mut any_has_runtime = false;
 
         for worker in workers {
@@ -1766,6 +1866,56 @@ impl WorkerSelector {
                 continue;
             }
 
+            // Filter by the worker's concurrent build cap, which applies even
+            // when slots are free.
+            if worker.at_build_cap().await {
+                filtered_by_build_cap += 1;
+                debug!(
+                    "Worker {} excluded: at concurrent build cap ({} active)",
+                    worker_id,
+                    worker.active_builds()
+                );
+                continue;
+            }
+
+            // Filter out workers blocked after consecutive failed builds.
+            if pool.is_blocked(&worker_id) {
+                filtered_by_blocklist += 1;
+                debug!(
+                    "Worker {} excluded: blocked after consecutive failed builds",
+                    worker_id
+                );
+                continue;
+            }
+
+            // Filter by the build's free-disk floor (`transfer.min_free_disk_gb`).
+            // A hard exclusion: a worker that cannot hold the build is not a
+            // fail-open candidate either.
+            if let Some(reason) = insufficient_disk_space(request, &capabilities) {
+                filtered_by_disk += 1;
+                debug!("Worker {} excluded: {}", worker_id, reason);
+                continue;
+            }
+
+            // Filter by `selection.min_memory_gb` for memory-hungry builds, so a
+            // release link does not OOM (SIGKILL) a small worker. Same hard
+            // exclusion as the disk floor.
+            if let Some(reason) =
+                insufficient_memory(self.config.min_memory_gb, request, &capabilities)
+            {
+                filtered_by_memory += 1;
+                debug!("Worker {} excluded: {}", worker_id, reason);
+                continue;
+            }
+
+            // Every transfer goes through rsync; a worker without it would
+            // only fail the sync and fall back to local.
+            if let Some(reason) = missing_rsync(&capabilities) {
+                filtered_by_missing_rsync += 1;
+                debug!("Worker {} excluded: {}", worker_id, reason);
+                continue;
+            }
+
             // Filter by load-per-core threshold (bd-3eaa)
             if let Some(false) = capabilities.is_topology_healthy() {
                 let reason = capabilities
@@ -2056,15 +2206,18 @@ impl WorkerSelector {
             )));
         }
 
-        // Return preferred workers if available, otherwise all eligible
+        // Return preferred workers if available, otherwise all eligible.
+        // Within either set, workers that already have the requested
+        // toolchain win over ones that would have to install it.
+        let toolchain = request.toolchain.as_ref();
         if has_preferred && !preferred.is_empty() {
-            return Ok(preferred);
+            return Ok(prefer_installed_toolchain(preferred, toolchain).await);
         }
         if has_preferred && !preferred_without_health.is_empty() {
-            return Ok(preferred_without_health);
+            return Ok(prefer_installed_toolchain(preferred_without_health, toolchain).await);
         }
         if !eligible.is_empty() {
-            return Ok(eligible);
+            return Ok(prefer_installed_toolchain(eligible, toolchain).await);
         }
 
         if filtered_by_active_project > 0
@@ -2082,6 +2235,33 @@ impl WorkerSelector {
             ));
         }
 
+        // Every remaining worker is too full for this build. Workers that were
+        // merely busy may still free up, so those cases keep queueing.
+        if filtered_by_disk > 0
+            && filtered_by_slots == 0
+            && filtered_by_build_cap == 0
+            && preferred_without_health.is_empty()
+            && eligible_without_health.is_empty()
+        {
+            return Err(SelectionReason::InsufficientDiskSpace);
+        }
+        if filtered_by_memory > 0
+            && filtered_by_slots == 0
+            && filtered_by_build_cap == 0
+            && preferred_without_health.is_empty()
+            && eligible_without_health.is_empty()
+        {
+            return Err(SelectionReason::InsufficientMemory);
+        }
+        if filtered_by_missing_rsync > 0
+            && filtered_by_slots == 0
+            && filtered_by_build_cap == 0
+            && preferred_without_health.is_empty()
+            && eligible_without_health.is_empty()
+        {
+            return Err(SelectionReason::WorkersMissingRsync);
+        }
+
         // Hard preflight failures (for example topology invariants or
         // convergence failures) must not fall back to unhealthy worker
         // assignment; force fail-open local execution.
@@ -2136,7 +2316,21 @@ impl WorkerSelector {
             );
         }
 
-        Ok(eligible_without_health)
+        if filtered_by_build_cap > 0
+            && preferred_without_health.is_empty()
+            && eligible_without_health.is_empty()
+        {
+            return Err(SelectionReason::WorkerBuildCapReached);
+        }
+
+        if filtered_by_blocklist > 0
+            && preferred_without_health.is_empty()
+            && eligible_without_health.is_empty()
+        {
+            return Err(SelectionReason::WorkerBlockedAfterFailures);
+        }
+
+        Ok(prefer_installed_toolchain(eligible_without_health, toolchain).await)
     }
 
     async fn toolchain_preflight_failure(
@@ -2340,18 +2534,28 @@ impl WorkerSelector {
             cache_score = (cache_score * TEST_CACHE_BOOST).min(1.0);
         }
 
-        // Health score (0-1)
-        let health_score = self.health_score(worker).await;
+        // Health score (0-1); none for a worker that answers SSH but failed
+        // its deep build check, so it stays eligible but loses to healthy ones.
+        let health_score =
+            if worker.health_failure().await == Some(HealthFailureReason::BuildCheckFailed) {
+                0.0
+            } else {
+                self.health_score(worker).await
+            };
 
         // Network score (0-1)
         let network_score = self.network_score(worker);
 
+        // Host load score (0-1)
+        let low_load_score = Self::low_load_score(worker.capabilities().await.load_per_core());
+
         // Priority normalization (0-1)
         let priority_score = Self::normalize_priority(config.priority, min_priority, max_priority);
 
         // Combine weighted scores
         let base_score = weights.speedscore * speed_score
             + weights.slots * slot_score * load_factor
+            + weights.low_load * low_load_score
             + weights.health * health_score
             + weights.cache * cache_score
             + weights.network * network_score
@@ -2398,12 +2602,13 @@ impl WorkerSelector {
         };
 
         debug!(
-            "Worker {} balanced score: {:.3} (speed={:.2}, load={:.2}, slots={:.2}, health={:.2}, cache={:.2}, network={:.2}, priority={:.2}, half_open={:?}, admission_penalty={:.2}, reliability_penalty={:.2}, cache_use={:?})",
+            "Worker {} balanced score: {:.3} (speed={:.2}, load={:.2}, slots={:.2}, low_load={:.2}, health={:.2}, cache={:.2}, network={:.2}, priority={:.2}, half_open={:?}, admission_penalty={:.2}, reliability_penalty={:.2}, cache_use={:?})",
             config.id,
             final_score,
             speed_score,
             load_factor,
             slot_score,
+            low_load_score,
             health_score,
             cache_score,
             network_score,
@@ -2423,10 +2628,25 @@ impl WorkerSelector {
         success_rate.clamp(0.0, 1.0)
     }
 
+    /// Health-check latency, averaged with the upload bandwidth measured from
+    /// recent source syncs once the daemon has a sample.
     fn network_score(&self, worker: &WorkerState) -> f64 {
-        match worker.last_latency_ms() {
-            Some(latency_ms) => Self::normalize_latency_ms(latency_ms),
-            None => DEFAULT_NETWORK_SCORE,
+        let latency = worker.last_latency_ms().map(Self::normalize_latency_ms);
+        let bandwidth = worker
+            .upload_bandwidth_bps()
+            .map(Self::normalize_bandwidth_bps);
+        match (latency, bandwidth) {
+            (Some(latency), Some(bandwidth)) => (latency + bandwidth) / 2.0,
+            (Some(score), None) | (None, Some(score)) => score,
+            (None, None) => DEFAULT_NETWORK_SCORE,
+        }
+    }
+
+    /// 1.0 for an idle host, 0.5 at one runnable task per core.
+    fn low_load_score(load_per_core: Option<f64>) -> f64 {
+        match load_per_core {
+            Some(load) => (1.0 / (1.0 + load.max(0.0))).clamp(0.0, 1.0),
+            None => DEFAULT_LOW_LOAD_SCORE,
         }
     }
 
@@ -2436,6 +2656,11 @@ impl WorkerSelector {
         score.clamp(0.0, 1.0)
     }
 
+    fn normalize_bandwidth_bps(bandwidth_bps: u64) -> f64 {
+        let bandwidth = bandwidth_bps as f64;
+        (bandwidth / (bandwidth + NETWORK_BANDWIDTH_HALF_SCORE_BPS)).clamp(0.0, 1.0)
+    }
+
     /// CacheAffinity strategy: heavily weight cache warmth.
     async fn select_cache_affinity(
         &self,
@@ -2596,7 +2821,9 @@ pub async fn select_worker_with_config(
     // Track worker selection latency (budget: <10ms, panic: 50ms)
     let _timer = DecisionTimer::new(DecisionType::WorkerSelection);
 
-    let workers = pool.healthy_workers().await;
+    let mut workers = pool.healthy_workers().await;
+    retain_fleet(&mut workers, request.fleet.as_deref()).await;
+    retain_allowed_workers(&mut workers, &request.allowed_workers).await;
 
     if workers.is_empty() {
         // Check if there are any workers at all
@@ -2609,9 +2836,19 @@ pub async fn select_worker_with_config(
         }
 
         // All workers are unhealthy - check if it's due to unreachability or circuits
-        let all_workers = pool.all_workers().await;
+        let mut all_workers = pool.all_workers().await;
+        retain_fleet(&mut all_workers, request.fleet.as_deref()).await;
+        retain_allowed_workers(&mut all_workers, &request.allowed_workers).await;
+        if all_workers.is_empty() {
+            return SelectionResult {
+                worker: None,
+                reason: SelectionReason::NoMatchingWorkers,
+                diagnostics: None,
+            };
+        }
         let mut all_circuits_open = true;
         let mut all_unreachable = true;
+        let mut all_operator_excluded = true;
 
         for worker in &all_workers {
             if let Some(state) = worker.circuit_state().await
@@ -2619,14 +2856,26 @@ pub async fn select_worker_with_config(
             {
                 all_circuits_open = false;
             }
+            let status = worker.status().await;
             if !matches!(
-                worker.status().await,
+                status,
                 rch_common::WorkerStatus::Unreachable
                     | rch_common::WorkerStatus::Drained
                     | rch_common::WorkerStatus::Disabled
             ) {
                 all_unreachable = false;
             }
+            if !is_operator_excluded(status) {
+                all_operator_excluded = false;
+            }
+        }
+
+        if all_operator_excluded {
+            return SelectionResult {
+                worker: None,
+                reason: SelectionReason::AllWorkersDraining,
+                diagnostics: None,
+            };
         }
 
         if all_circuits_open {
@@ -2717,6 +2966,19 @@ pub async fn select_worker_with_config(
             continue;
         }
 
+        if worker.at_build_cap().await {
+            debug!("Worker {} excluded: at concurrent build cap", worker_id);
+            continue;
+        }
+
+        if pool.is_blocked(&worker_id) {
+            debug!(
+                "Worker {} excluded: blocked after consecutive failed builds",
+                worker_id
+            );
+            continue;
+        }
+
         any_has_slots = true;
 
         // Compute score with circuit state penalty
@@ -2898,22 +3160,73 @@ fn normalize_priority(priority: u32, min_priority: u32, max_priority: u32) -> f6
     }
 }
 
+/// Keep only the workers a build was restricted to (`rch exec --workers`).
+///
+/// An empty allow-list keeps every worker.
+async fn retain_allowed_workers(workers: &mut Vec<Arc<WorkerState>>, allowed: &[WorkerId]) {
+    if allowed.is_empty() {
+        return;
+    }
+    let mut members = Vec::with_capacity(workers.len());
+    for worker in workers.drain(..) {
+        if allowed.contains(&worker.config.read().await.id) {
+            members.push(worker);
+        }
+    }
+    *workers = members;
+}
+
+/// Keep only the workers belonging to the requested fleet (`transfer.fleet`).
+///
+/// A request without a fleet keeps every worker.
+async fn retain_fleet(workers: &mut Vec<Arc<WorkerState>>, fleet: Option<&str>) {
+    if fleet.is_none() {
+        return;
+    }
+    let mut members = Vec::with_capacity(workers.len());
+    for worker in workers.drain(..) {
+        if worker.config.read().await.in_fleet(fleet) {
+            members.push(worker);
+        }
+    }
+    *workers = members;
+}
+
+/// Worker taken out of rotation by an operator (`rch workers drain/disable`),
+/// as opposed to failing health checks.
+fn is_operator_excluded(status: rch_common::WorkerStatus) -> bool {
+    matches!(
+        status,
+        rch_common::WorkerStatus::Draining
+            | rch_common::WorkerStatus::Drained
+            | rch_common::WorkerStatus::Disabled
+    )
+}
+
 fn selection_reason_label(reason: &SelectionReason) -> &'static str {
     match reason {
         SelectionReason::Success => "success",
         SelectionReason::NoWorkersConfigured => "no_workers_configured",
         SelectionReason::AllWorkersUnreachable => "all_workers_unreachable",
+        SelectionReason::AllWorkersDraining => "all_workers_draining",
         SelectionReason::AllCircuitsOpen => "all_circuits_open",
         SelectionReason::AllWorkersBusy => "all_workers_busy",
+        SelectionReason::WorkerBuildCapReached => "worker_build_cap_reached",
+        SelectionReason::WorkerBlockedAfterFailures => "worker_blocked_after_failures",
         SelectionReason::NoWorkersPassedHealth => "no_workers_passed_health",
         SelectionReason::AllWorkersFailedPreflight => "all_workers_failed_preflight",
         SelectionReason::AllWorkersFailedConvergence => "all_workers_failed_convergence",
+        SelectionReason::InsufficientDiskSpace => "insufficient_disk_space",
+        SelectionReason::InsufficientMemory => "insufficient_memory",
+        SelectionReason::WorkersMissingRsync => "workers_missing_rsync",
         SelectionReason::NoAdmissibleWorkers(_) => "no_admissible_workers",
         SelectionReason::NoMatchingWorkers => "no_matching_workers",
         SelectionReason::NoWorkersWithRuntime(_) => "no_workers_with_runtime",
         SelectionReason::SelectionError(_) => "selection_error",
         SelectionReason::AffinityPinned => "affinity_pinned",
         SelectionReason::AffinityFallback => "affinity_fallback",
+        SelectionReason::QueuedFair { .. } => "queued_fair",
+        SelectionReason::CoalescedDuplicate => "coalesced_duplicate",
     }
 }
 
@@ -3020,6 +3333,85 @@ fn record_selection_metrics(reason: &SelectionReason, duration: Duration) {
     }
 }
 
+/// Keep only the workers that report `toolchain` as installed, when any do.
+///
+/// A worker without the requested toolchain installs it on demand, which is
+/// slow, so those are used only when no candidate has it. Workers that did not
+/// report their toolchains count as not having it.
+async fn prefer_installed_toolchain(
+    workers: Vec<(Arc<WorkerState>, CircuitState)>,
+    toolchain: Option<&ToolchainInfo>,
+) -> Vec<(Arc<WorkerState>, CircuitState)> {
+    let Some(toolchain) = toolchain else {
+        return workers;
+    };
+    let name = toolchain.rustup_toolchain();
+    let mut with_toolchain = Vec::new();
+    for (worker, circuit_state) in &workers {
+        if worker.capabilities().await.has_toolchain(&name) == Some(true) {
+            with_toolchain.push((worker.clone(), *circuit_state));
+        }
+    }
+    if with_toolchain.is_empty() {
+        workers
+    } else {
+        debug!(
+            "{} of {} candidate workers have toolchain {} installed",
+            with_toolchain.len(),
+            workers.len(),
+            name
+        );
+        with_toolchain
+    }
+}
+
+/// Why a worker's last reported free disk is below the request's
+/// `min_free_disk_gb`, or `None` when it has room or never reported disk.
+fn insufficient_disk_space(
+    request: &SelectionRequest,
+    capabilities: &WorkerCapabilities,
+) -> Option<String> {
+    let min_free = request.min_free_disk_gb?;
+    let free = capabilities.disk_free_gb?;
+    (free < min_free).then(|| {
+        format!(
+            "insufficient disk: {:.1} GB free < min_free_disk_gb {:.1} GB",
+            free, min_free
+        )
+    })
+}
+
+/// Why a worker's last reported available memory is below
+/// `selection.min_memory_gb` for a memory-hungry build, or `None` when the
+/// floor is unset, the build is light, or the worker never reported memory.
+fn insufficient_memory(
+    min_memory_gb: Option<f64>,
+    request: &SelectionRequest,
+    capabilities: &WorkerCapabilities,
+) -> Option<String> {
+    let min_memory = min_memory_gb.filter(|gb| *gb > 0.0)?;
+    if !rch_common::is_optimized_build(request.command.as_deref()?) {
+        return None;
+    }
+    let available = capabilities.mem_available_gb?;
+    (available < min_memory).then(|| {
+        format!(
+            "insufficient memory: {:.1} GB available < min_memory_gb {:.1} GB",
+            available, min_memory
+        )
+    })
+}
+
+/// Why a worker cannot take transfers: its probe reported rsync missing.
+fn missing_rsync(capabilities: &WorkerCapabilities) -> Option<String> {
+    capabilities.is_missing_tool("rsync").then(|| {
+        format!(
+            "rsync not installed ({})",
+            rch_common::worker_tool_install_hint("rsync")
+        )
+    })
+}
+
 fn toolchain_capability_mismatch(
     toolchain: Option<&ToolchainInfo>,
     capabilities: &WorkerCapabilities,
@@ -3166,6 +3558,9 @@ mod tests {
             total_slots,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         let state = WorkerState::new(config);
         state.set_speed_score(speed);
@@ -3297,6 +3692,10 @@ mod tests {
                 required_runtime: RequiredRuntime::default(),
                 classification_duration_us: None,
                 hook_pid: None,
+                fleet: None,
+                min_free_disk_gb: None,
+                allowed_workers: vec![],
+                idempotency_key: None,
             };
             let weights = SelectionWeights::default();
 
@@ -3322,6 +3721,10 @@ mod tests {
                 required_runtime: RequiredRuntime::default(),
                 classification_duration_us: None,
                 hook_pid: None,
+                fleet: None,
+                min_free_disk_gb: None,
+                allowed_workers: vec![],
+                idempotency_key: None,
             };
             let weights = SelectionWeights::default();
 
@@ -3359,6 +3762,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
         let weights = SelectionWeights::default();
 
@@ -3382,6 +3789,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: Some(123),
             hook_pid: Some(4321),
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let decision_before = crate::metrics::RELIABILITY_DECISIONS_TOTAL
@@ -3434,6 +3845,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
         let weights = SelectionWeights::default();
 
@@ -3464,6 +3879,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
         let weights = SelectionWeights::default();
         let config = CircuitBreakerConfig::default();
@@ -3498,6 +3917,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
         let weights = SelectionWeights::default();
         let config = CircuitBreakerConfig::default();
@@ -3534,6 +3957,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
         let weights = SelectionWeights::default();
         let config = CircuitBreakerConfig {
@@ -3586,6 +4013,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
         let weights = SelectionWeights::default();
 
@@ -3627,6 +4058,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
         let weights = SelectionWeights::default();
         let config = CircuitBreakerConfig::default();
@@ -3662,6 +4097,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
         let weights = SelectionWeights::default();
         let config = CircuitBreakerConfig::default();
@@ -3693,6 +4132,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
         let weights = SelectionWeights::default();
         let config = CircuitBreakerConfig::default();
@@ -3729,6 +4172,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
         let weights = SelectionWeights::default();
         let config = CircuitBreakerConfig::default();
@@ -3779,6 +4226,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
         let config = CircuitBreakerConfig::default();
 
@@ -3937,6 +4388,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let result = selector.select(&pool, &request).await;
@@ -3986,6 +4441,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let result = selector.select(&pool, &request).await;
@@ -4030,6 +4489,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let result = selector.select(&pool, &request).await;
@@ -4072,6 +4535,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let result = selector.select(&pool, &request).await;
@@ -4106,6 +4573,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let result = selector.select(&pool, &request).await;
@@ -4169,6 +4640,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let result = selector.select(&pool, &request).await;
@@ -4237,6 +4712,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let result = selector.select(&pool, &request).await;
@@ -4272,6 +4751,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let mut high = base_request.clone();
@@ -4306,6 +4789,7 @@ mod tests {
                 weights: SelectionWeightConfig {
                     speedscore: 0.0,
                     slots: 0.0,
+                    low_load: 0.0,
                     health: 1.0,
                     cache: 0.0,
                     network: 0.0,
@@ -4327,6 +4811,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let result = selector.select(&pool, &request).await;
@@ -4335,104 +4823,305 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_bug_repro_no_workers_with_runtime_when_busy() {
-        // Regression test for: NoWorkersWithRuntime returned when worker exists but is busy
+    async fn test_select_worker_balanced_ranks_failed_build_check_last() {
         let pool = WorkerPool::new();
 
-        let worker = make_worker("busy-rust", 4, 80.0);
-        worker
-            .set_capabilities(rch_common::WorkerCapabilities {
-                rustc_version: Some("1.75.0".to_string()),
-                ..Default::default()
-            })
+        let cannot_build = make_worker("cannot-build", 8, 90.0);
+        cannot_build
+            .set_health_failure(Some(HealthFailureReason::BuildCheckFailed))
             .await;
+        pool.add_worker_state(cannot_build).await;
+        pool.add_worker_state(make_worker("builds", 8, 70.0)).await;
 
-        // Exhaust slots
-        assert!(worker.reserve_slots(4).await);
-
-        pool.add_worker_state(worker).await;
+        let selector = WorkerSelector::with_config(
+            SelectionConfig {
+                strategy: SelectionStrategy::Balanced,
+                weights: SelectionWeightConfig {
+                    speedscore: 0.1,
+                    slots: 0.0,
+                    low_load: 0.0,
+                    health: 1.0,
+                    cache: 0.0,
+                    network: 0.0,
+                    priority: 0.0,
+                    half_open_penalty: 1.0,
+                },
+                ..Default::default()
+            },
+            CircuitBreakerConfig::default(),
+        );
 
-        let selector = WorkerSelector::default();
         let request = SelectionRequest {
-            project: "test".to_string(),
+            project: "test-project".to_string(),
             command: None,
             command_priority: CommandPriority::Normal,
-            estimated_cores: 1,
+            estimated_cores: 2,
             preferred_workers: vec![],
             toolchain: None,
-            required_runtime: RequiredRuntime::Rust,
+            required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let result = selector.select(&pool, &request).await;
-        assert!(result.worker.is_none());
-
-        // BUG FIX VERIFIED: Runtime check now happens before slot check,
-        // so busy workers correctly return AllWorkersBusy (not NoWorkersWithRuntime)
-        assert_eq!(
-            result.reason,
-            SelectionReason::AllWorkersBusy,
-            "Expected AllWorkersBusy, got {:?}",
-            result.reason
-        );
+        let selected = result.worker.expect("Expected a worker");
+        assert_eq!(selected.config.read().await.id.as_str(), "builds");
     }
 
     #[tokio::test]
-    async fn test_active_project_exclusion_preserves_runtime_reason() {
+    async fn test_select_worker_balanced_low_load_weight_prefers_idle_host() {
         let pool = WorkerPool::new();
 
-        let active = make_worker("active-rust", 4, 80.0);
-        active
+        let loaded = make_worker("loaded", 8, 90.0);
+        loaded
             .set_capabilities(rch_common::WorkerCapabilities {
-                rustc_version: Some("1.97.0-nightly".to_string()),
+                load_avg_1: Some(12.0),
+                num_cpus: Some(8),
                 ..Default::default()
             })
             .await;
-        pool.add_worker_state(active).await;
+        pool.add_worker_state(loaded).await;
 
-        let non_rust = make_worker("non-rust", 4, 70.0);
-        pool.add_worker_state(non_rust).await;
+        let idle = make_worker("idle", 8, 60.0);
+        idle.set_capabilities(rch_common::WorkerCapabilities {
+            load_avg_1: Some(0.4),
+            num_cpus: Some(8),
+            ..Default::default()
+        })
+        .await;
+        pool.add_worker_state(idle).await;
 
-        let selector = WorkerSelector::default();
         let request = SelectionRequest {
-            project: "frankenterm".to_string(),
-            command: Some("cargo build".to_string()),
+            project: "test-project".to_string(),
+            command: None,
             command_priority: CommandPriority::Normal,
-            estimated_cores: 1,
+            estimated_cores: 2,
             preferred_workers: vec![],
             toolchain: None,
-            required_runtime: RequiredRuntime::Rust,
+            required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
+        };
+        let select_with = |weights: SelectionWeightConfig| {
+            WorkerSelector::with_config(
+                SelectionConfig {
+                    strategy: SelectionStrategy::Balanced,
+                    min_success_rate: 0.0,
+                    max_load_per_core: None,
+                    weights,
+                    ..Default::default()
+                },
+                CircuitBreakerConfig::default(),
+            )
         };
-        let mut excluded_worker_ids = HashSet::new();
-        excluded_worker_ids.insert("active-rust".to_string());
 
-        let result = selector
-            .select_with_exclusions(&pool, &request, &excluded_worker_ids)
+        // Defaults ignore host load, so the faster worker wins.
+        let result = select_with(SelectionWeightConfig::default())
+            .select(&pool, &request)
             .await;
-        assert!(result.worker.is_none());
-        assert_eq!(
-            result.reason,
-            SelectionReason::NoAdmissibleWorkers("active_project_exclusion=1".to_string())
-        );
-        let diagnostics = result
-            .diagnostics
-            .expect("active-project rejection should include per-worker diagnostics");
-        assert_eq!(diagnostics.required_runtime, RequiredRuntime::Rust);
-        assert_eq!(diagnostics.active_project_exclusion_count, 1);
-        assert_eq!(diagnostics.workers.len(), 2);
+        let selected = result.worker.expect("Expected a worker");
+        assert_eq!(selected.config.read().await.id.as_str(), "loaded");
 
-        let active = diagnostics
-            .workers
-            .iter()
-            .find(|worker| worker.worker_id.as_str() == "active-rust")
-            .expect("active worker diagnostic");
-        assert!(active.runtime_available);
-        assert!(active.active_project_excluded);
-        assert_eq!(
-            active.final_decision,
+        let result = select_with(SelectionWeightConfig {
+            low_load: 2.0,
+            ..Default::default()
+        })
+        .select(&pool, &request)
+        .await;
+        let selected = result.worker.expect("Expected a worker");
+        assert_eq!(selected.config.read().await.id.as_str(), "idle");
+    }
+
+    #[tokio::test]
+    async fn test_selector_skips_worker_at_build_cap() {
+        let pool = WorkerPool::new();
+        let capped = make_worker("capped", 16, 90.0);
+        capped.config.write().await.max_concurrent_builds = Some(1);
+        assert!(capped.reserve_slots(1).await);
+        pool.add_worker_state(capped).await;
+
+        let selector = WorkerSelector::default();
+        let request = SelectionRequest {
+            project: "test".to_string(),
+            command: None,
+            command_priority: CommandPriority::Normal,
+            estimated_cores: 1,
+            preferred_workers: vec![],
+            toolchain: None,
+            required_runtime: RequiredRuntime::None,
+            classification_duration_us: None,
+            hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
+        };
+
+        // The only worker has 15 free slots but is at its build cap.
+        let result = selector.select(&pool, &request).await;
+        assert!(result.worker.is_none());
+        assert_eq!(result.reason, SelectionReason::WorkerBuildCapReached);
+
+        pool.add_worker_state(make_worker("small", 4, 50.0)).await;
+        let result = selector.select(&pool, &request).await;
+        let selected = result.worker.expect("uncapped worker should be selected");
+        assert_eq!(selected.config.read().await.id.as_str(), "small");
+    }
+
+    #[tokio::test]
+    async fn test_selector_skips_worker_blocked_after_failures() {
+        let pool = WorkerPool::new();
+        pool.set_blocklist_config(crate::config::BlocklistConfig {
+            failure_threshold: 2,
+            cooldown_secs: 600,
+        });
+        pool.add_worker_state(make_worker("flaky", 16, 90.0)).await;
+        let flaky = WorkerId::new("flaky");
+        pool.record_build_exit(&flaky, 1);
+        pool.record_build_exit(&flaky, 1);
+
+        let selector = WorkerSelector::default();
+        let request = SelectionRequest {
+            project: "test".to_string(),
+            command: None,
+            command_priority: CommandPriority::Normal,
+            estimated_cores: 1,
+            preferred_workers: vec![],
+            toolchain: None,
+            required_runtime: RequiredRuntime::None,
+            classification_duration_us: None,
+            hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
+        };
+
+        let result = selector.select(&pool, &request).await;
+        assert!(result.worker.is_none());
+        assert_eq!(result.reason, SelectionReason::WorkerBlockedAfterFailures);
+
+        pool.add_worker_state(make_worker("steady", 4, 50.0)).await;
+        let result = selector.select(&pool, &request).await;
+        let selected = result.worker.expect("unblocked worker should be selected");
+        assert_eq!(selected.config.read().await.id.as_str(), "steady");
+    }
+
+    #[tokio::test]
+    async fn test_bug_repro_no_workers_with_runtime_when_busy() {
+        // Regression test for: NoWorkersWithRuntime returned when worker exists but is busy
+        let pool = WorkerPool::new();
+
+        let worker = make_worker("busy-rust", 4, 80.0);
+        worker
+            .set_capabilities(rch_common::WorkerCapabilities {
+                rustc_version: Some("1.75.0".to_string()),
+                ..Default::default()
+            })
+            .await;
+
+        // Exhaust slots
+        assert!(worker.reserve_slots(4).await);
+
+        pool.add_worker_state(worker).await;
+
+        let selector = WorkerSelector::default();
+        let request = SelectionRequest {
+            project: "test".to_string(),
+            command: None,
+            command_priority: CommandPriority::Normal,
+            estimated_cores: 1,
+            preferred_workers: vec![],
+            toolchain: None,
+            required_runtime: RequiredRuntime::Rust,
+            classification_duration_us: None,
+            hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
+        };
+
+        let result = selector.select(&pool, &request).await;
+        assert!(result.worker.is_none());
+
+        // BUG FIX VERIFIED: Runtime check now happens before slot check,
+        // so busy workers correctly return AllWorkersBusy (not NoWorkersWithRuntime)
+        assert_eq!(
+            result.reason,
+            SelectionReason::AllWorkersBusy,
+            "Expected AllWorkersBusy, got {:?}",
+            result.reason
+        );
+    }
+
+    #[tokio::test]
+    async fn test_active_project_exclusion_preserves_runtime_reason() {
+        let pool = WorkerPool::new();
+
+        let active = make_worker("active-rust", 4, 80.0);
+        active
+            .set_capabilities(rch_common::WorkerCapabilities {
+                rustc_version: Some("1.97.0-nightly".to_string()),
+                ..Default::default()
+            })
+            .await;
+        pool.add_worker_state(active).await;
+
+        let non_rust = make_worker("non-rust", 4, 70.0);
+        pool.add_worker_state(non_rust).await;
+
+        let selector = WorkerSelector::default();
+        let request = SelectionRequest {
+            project: "frankenterm".to_string(),
+            command: Some("cargo build".to_string()),
+            command_priority: CommandPriority::Normal,
+            estimated_cores: 1,
+            preferred_workers: vec![],
+            toolchain: None,
+            required_runtime: RequiredRuntime::Rust,
+            classification_duration_us: None,
+            hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
+        };
+        let mut excluded_worker_ids = HashSet::new();
+        excluded_worker_ids.insert("active-rust".to_string());
+
+        let result = selector
+            .select_with_exclusions(&pool, &request, &excluded_worker_ids)
+            .await;
+        assert!(result.worker.is_none());
+        assert_eq!(
+            result.reason,
+            SelectionReason::NoAdmissibleWorkers("active_project_exclusion=1".to_string())
+        );
+        let diagnostics = result
+            .diagnostics
+            .expect("active-project rejection should include per-worker diagnostics");
+        assert_eq!(diagnostics.required_runtime, RequiredRuntime::Rust);
+        assert_eq!(diagnostics.active_project_exclusion_count, 1);
+        assert_eq!(diagnostics.workers.len(), 2);
+
+        let active = diagnostics
+            .workers
+            .iter()
+            .find(|worker| worker.worker_id.as_str() == "active-rust")
+            .expect("active worker diagnostic");
+        assert!(active.runtime_available);
+        assert!(active.active_project_excluded);
+        assert_eq!(
+            active.final_decision,
             WorkerSelectionDiagnosticDecision::Deny
         );
         assert!(
@@ -4542,6 +5231,10 @@ mod tests {
             required_runtime: RequiredRuntime::Rust,
             classification_duration_us: Some(42),
             hook_pid: Some(4242),
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
         let mut excluded_worker_ids = std::collections::HashSet::new();
         excluded_worker_ids.insert("active-rust".to_string());
@@ -4598,6 +5291,10 @@ mod tests {
             required_runtime: RequiredRuntime::Rust,
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let result = selector.select(&pool, &request).await;
@@ -4633,6 +5330,10 @@ mod tests {
             required_runtime: RequiredRuntime::Rust,
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let result = selector.select(&pool, &request).await;
@@ -4695,6 +5396,10 @@ mod tests {
             required_runtime: RequiredRuntime::Rust,
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let result = selector.select(&pool, &request).await;
@@ -4733,6 +5438,10 @@ mod tests {
             required_runtime: RequiredRuntime::Rust,
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let first = selector.select(&pool, &request).await;
@@ -4795,6 +5504,10 @@ mod tests {
             required_runtime: RequiredRuntime::Rust,
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let result = selector.select(&pool, &request).await;
@@ -4855,6 +5568,10 @@ mod tests {
             required_runtime: RequiredRuntime::Rust,
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let result = selector.select(&pool, &request).await;
@@ -4865,6 +5582,73 @@ mod tests {
         assert_eq!(result.reason, SelectionReason::Success);
     }
 
+    #[tokio::test]
+    async fn test_selection_prefers_worker_with_installed_toolchain() {
+        let pool = WorkerPool::new();
+        for (id, speed, toolchains) in [
+            ("fast-missing", 99.0, vec!["stable"]),
+            ("slow-installed", 40.0, vec!["stable", "nightly-2026-04-30"]),
+            ("unreported", 95.0, vec![]),
+        ] {
+            let worker = make_worker(id, 8, speed);
+            worker
+                .set_capabilities(rch_common::WorkerCapabilities {
+                    rustc_version: Some("1.87.0".to_string()),
+                    installed_toolchains: toolchains.into_iter().map(str::to_string).collect(),
+                    projects_root_ok: Some(true),
+                    disk_free_gb: Some(60.0),
+                    disk_total_gb: Some(120.0),
+                    ..Default::default()
+                })
+                .await;
+            worker
+                .record_toolchain_preflight("nightly-2026-04-30".to_string(), true, None)
+                .await;
+            pool.add_worker_state(worker).await;
+        }
+
+        let selector = WorkerSelector::default();
+        let mut request = SelectionRequest {
+            project: "toolchain-project".to_string(),
+            command: Some("cargo build".to_string()),
+            command_priority: CommandPriority::Normal,
+            estimated_cores: 2,
+            preferred_workers: vec![],
+            toolchain: Some(ToolchainInfo {
+                channel: "nightly".to_string(),
+                date: Some("2026-04-30".to_string()),
+                full_version: "nightly-2026-04-30".to_string(),
+            }),
+            required_runtime: RequiredRuntime::Rust,
+            classification_duration_us: None,
+            hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
+        };
+
+        let selected = selector.select(&pool, &request).await.worker.unwrap();
+        assert_eq!(selected.config.read().await.id.as_str(), "slow-installed");
+
+        // Nobody has beta: every worker stays a candidate (install on demand).
+        request.toolchain = Some(ToolchainInfo {
+            channel: "beta".to_string(),
+            date: None,
+            full_version: "beta".to_string(),
+        });
+        for worker in pool.all_workers().await {
+            worker
+                .record_toolchain_preflight("beta".to_string(), true, None)
+                .await;
+        }
+        let eligible = selector
+            .get_eligible_workers(&pool, &request, &HashSet::new())
+            .await
+            .unwrap();
+        assert_eq!(eligible.len(), 3);
+    }
+
     #[tokio::test]
     async fn test_pressure_preflight_rejects_critical_worker_only_pool() {
         let pool = WorkerPool::new();
@@ -4906,6 +5690,10 @@ mod tests {
             required_runtime: RequiredRuntime::Rust,
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let result = selector.select(&pool, &request).await;
@@ -4952,6 +5740,10 @@ mod tests {
             required_runtime: RequiredRuntime::Rust,
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let result = selector.select(&pool, &request).await;
@@ -4999,6 +5791,10 @@ mod tests {
             required_runtime: RequiredRuntime::Rust,
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let result = selector.select(&pool, &request).await;
@@ -5055,6 +5851,10 @@ mod tests {
             required_runtime: RequiredRuntime::Rust,
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let result = selector.select(&pool, &request).await;
@@ -5084,6 +5884,7 @@ mod tests {
                 weights: SelectionWeightConfig {
                     speedscore: 0.0,
                     slots: 0.0,
+                    low_load: 0.0,
                     health: 0.0,
                     cache: 0.0,
                     network: 1.0,
@@ -5105,6 +5906,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let result = selector.select(&pool, &request).await;
@@ -5112,6 +5917,60 @@ mod tests {
         assert_eq!(selected.config.read().await.id.as_str(), "fast-net");
     }
 
+    #[tokio::test]
+    async fn test_worker_selector_network_weight_prefers_measured_bandwidth() {
+        let pool = WorkerPool::new();
+
+        let lan = make_worker("lan", 8, 70.0);
+        lan.set_last_latency_ms(Some(50));
+        lan.record_upload_sample(100 * 1024 * 1024, Duration::from_secs(1));
+        pool.add_worker_state(lan).await;
+
+        let vpn = make_worker("vpn", 8, 70.0);
+        vpn.set_last_latency_ms(Some(50));
+        vpn.record_upload_sample(2 * 1024 * 1024, Duration::from_secs(1));
+        pool.add_worker_state(vpn).await;
+
+        let selector = WorkerSelector::with_config(
+            SelectionConfig {
+                strategy: SelectionStrategy::Balanced,
+                min_success_rate: 0.0,
+                weights: SelectionWeightConfig {
+                    speedscore: 0.0,
+                    slots: 0.0,
+                    low_load: 0.0,
+                    health: 0.0,
+                    cache: 0.0,
+                    network: 1.0,
+                    priority: 0.0,
+                    half_open_penalty: 1.0,
+                },
+                ..Default::default()
+            },
+            CircuitBreakerConfig::default(),
+        );
+
+        let request = SelectionRequest {
+            project: "test-project".to_string(),
+            command: None,
+            command_priority: CommandPriority::Normal,
+            estimated_cores: 2,
+            preferred_workers: vec![],
+            toolchain: None,
+            required_runtime: RequiredRuntime::default(),
+            classification_duration_us: None,
+            hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
+        };
+
+        let result = selector.select(&pool, &request).await;
+        let selected = result.worker.expect("Expected a worker");
+        assert_eq!(selected.config.read().await.id.as_str(), "lan");
+    }
+
     #[tokio::test]
     async fn test_worker_selector_cache_affinity_strategy() {
         let pool = WorkerPool::new();
@@ -5155,6 +6014,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let result = selector.select(&pool, &request).await;
@@ -5191,6 +6054,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let result = selector.select(&pool, &request).await;
@@ -5232,6 +6099,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let result = selector.select(&pool, &request).await;
@@ -5266,6 +6137,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         // Run multiple selections and verify distribution
@@ -5323,6 +6198,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let result = selector.select(&pool, &request).await;
@@ -5362,6 +6241,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let result = selector.select(&pool, &request).await;
@@ -5370,6 +6253,297 @@ mod tests {
         assert_eq!(selected.config.read().await.id.as_str(), "preferred");
     }
 
+    #[tokio::test]
+    async fn test_worker_selector_filters_by_fleet() {
+        let pool = WorkerPool::new();
+        let fast = make_worker("fast-1", 8, 50.0);
+        fast.config
+            .write()
+            .await
+            .tags
+            .push(format!("{}fast", rch_common::FLEET_TAG_PREFIX));
+        pool.add_worker_state(fast).await;
+        pool.add_worker_state(make_worker("default-1", 8, 90.0))
+            .await;
+
+        let selector = WorkerSelector::with_config(
+            SelectionConfig {
+                strategy: SelectionStrategy::Fastest,
+                ..Default::default()
+            },
+            CircuitBreakerConfig::default(),
+        );
+
+        let mut request = SelectionRequest {
+            project: "test-project".to_string(),
+            command: None,
+            command_priority: CommandPriority::Normal,
+            estimated_cores: 2,
+            preferred_workers: vec![],
+            toolchain: None,
+            required_runtime: RequiredRuntime::default(),
+            classification_duration_us: None,
+            hook_pid: None,
+            fleet: Some("fast".to_string()),
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
+        };
+
+        let result = selector.select(&pool, &request).await;
+        let selected = result.worker.expect("Expected a fleet worker");
+        assert_eq!(selected.config.read().await.id.as_str(), "fast-1");
+
+        // Without a fleet every worker is a candidate, so the faster one wins.
+        // (A fresh project keeps the affinity pin from deciding.)
+        request.project = "other-project".to_string();
+        request.fleet = None;
+        let result = selector.select(&pool, &request).await;
+        let selected = result.worker.expect("Expected a worker");
+        assert_eq!(selected.config.read().await.id.as_str(), "default-1");
+
+        request.fleet = Some("cheap".to_string());
+        let result = selector.select(&pool, &request).await;
+        assert!(result.worker.is_none());
+        assert_eq!(result.reason, SelectionReason::NoMatchingWorkers);
+    }
+
+    #[tokio::test]
+    async fn test_worker_selector_skips_workers_below_min_free_disk() {
+        let pool = WorkerPool::new();
+        let full = make_worker("full-1", 8, 90.0);
+        full.set_capabilities(WorkerCapabilities {
+            disk_free_gb: Some(1.5),
+            ..Default::default()
+        })
+        .await;
+        pool.add_worker_state(full).await;
+        let roomy = make_worker("roomy-1", 8, 50.0);
+        roomy
+            .set_capabilities(WorkerCapabilities {
+                disk_free_gb: Some(40.0),
+                ..Default::default()
+            })
+            .await;
+        pool.add_worker_state(roomy).await;
+
+        let selector = WorkerSelector::with_config(
+            SelectionConfig {
+                strategy: SelectionStrategy::Fastest,
+                ..Default::default()
+            },
+            CircuitBreakerConfig::default(),
+        );
+
+        let mut request = SelectionRequest {
+            project: "disk-project".to_string(),
+            command: None,
+            command_priority: CommandPriority::Normal,
+            estimated_cores: 2,
+            preferred_workers: vec![],
+            toolchain: None,
+            required_runtime: RequiredRuntime::default(),
+            classification_duration_us: None,
+            hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: Some(5.0),
+            allowed_workers: vec![],
+            idempotency_key: None,
+        };
+
+        // The faster worker is nearly full, so the slower one gets the build.
+        let result = selector.select(&pool, &request).await;
+        let selected = result.worker.expect("Expected a worker with room");
+        assert_eq!(selected.config.read().await.id.as_str(), "roomy-1");
+
+        // No worker has 50 GB free: fall back to local with a disk reason.
+        request.project = "big-disk-project".to_string();
+        request.min_free_disk_gb = Some(50.0);
+        let result = selector.select(&pool, &request).await;
+        assert!(result.worker.is_none());
+        assert_eq!(result.reason, SelectionReason::InsufficientDiskSpace);
+    }
+
+    #[tokio::test]
+    async fn test_worker_selector_skips_low_memory_workers_for_release_builds() {
+        let pool = WorkerPool::new();
+        let small = make_worker("small-1", 8, 90.0);
+        small
+            .set_capabilities(WorkerCapabilities {
+                mem_total_gb: Some(4.0),
+                mem_available_gb: Some(2.5),
+                ..Default::default()
+            })
+            .await;
+        pool.add_worker_state(small).await;
+        let big = make_worker("big-1", 8, 50.0);
+        big.set_capabilities(WorkerCapabilities {
+            mem_total_gb: Some(64.0),
+            mem_available_gb: Some(48.0),
+            ..Default::default()
+        })
+        .await;
+        pool.add_worker_state(big).await;
+
+        let selector = WorkerSelector::with_config(
+            SelectionConfig {
+                strategy: SelectionStrategy::Fastest,
+                min_memory_gb: Some(8.0),
+                ..Default::default()
+            },
+            CircuitBreakerConfig::default(),
+        );
+
+        let mut request = SelectionRequest {
+            project: "mem-project".to_string(),
+            command: Some("cargo build".to_string()),
+            command_priority: CommandPriority::Normal,
+            estimated_cores: 2,
+            preferred_workers: vec![],
+            toolchain: None,
+            required_runtime: RequiredRuntime::default(),
+            classification_duration_us: None,
+            hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
+        };
+
+        // Debug builds are not gated: the faster, smaller worker wins.
+        let result = selector.select(&pool, &request).await;
+        let selected = result.worker.expect("Expected a worker");
+        assert_eq!(selected.config.read().await.id.as_str(), "small-1");
+
+        // Release builds skip the worker that would likely OOM.
+        request.project = "mem-release-project".to_string();
+        request.command = Some("cargo build --release".to_string());
+        let result = selector.select(&pool, &request).await;
+        let selected = result.worker.expect("Expected a worker with memory");
+        assert_eq!(selected.config.read().await.id.as_str(), "big-1");
+    }
+
+    #[tokio::test]
+    async fn test_worker_selector_reports_insufficient_memory() {
+        let pool = WorkerPool::new();
+        let small = make_worker("small-1", 8, 90.0);
+        small
+            .set_capabilities(WorkerCapabilities {
+                mem_available_gb: Some(2.5),
+                ..Default::default()
+            })
+            .await;
+        pool.add_worker_state(small).await;
+
+        let selector = WorkerSelector::with_config(
+            SelectionConfig {
+                min_memory_gb: Some(8.0),
+                ..Default::default()
+            },
+            CircuitBreakerConfig::default(),
+        );
+        let request = SelectionRequest {
+            project: "mem-project".to_string(),
+            command: Some("cargo build --release".to_string()),
+            command_priority: CommandPriority::Normal,
+            estimated_cores: 2,
+            preferred_workers: vec![],
+            toolchain: None,
+            required_runtime: RequiredRuntime::default(),
+            classification_duration_us: None,
+            hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
+        };
+
+        let result = selector.select(&pool, &request).await;
+        assert!(result.worker.is_none());
+        assert_eq!(result.reason, SelectionReason::InsufficientMemory);
+    }
+
+    #[tokio::test]
+    async fn test_worker_selector_skips_workers_missing_rsync() {
+        let _guard = test_guard!();
+        let pool = WorkerPool::new();
+        let bare = make_worker("bare-1", 8, 90.0);
+        bare.set_capabilities(WorkerCapabilities {
+            missing_tools: vec!["rsync".to_string()],
+            ..Default::default()
+        })
+        .await;
+        pool.add_worker_state(bare).await;
+
+        let selector = WorkerSelector::new();
+        let request = SelectionRequest {
+            project: "rsync-project".to_string(),
+            command: Some("cargo build".to_string()),
+            command_priority: CommandPriority::Normal,
+            estimated_cores: 2,
+            preferred_workers: vec![],
+            toolchain: None,
+            required_runtime: RequiredRuntime::default(),
+            classification_duration_us: None,
+            hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
+        };
+
+        let result = selector.select(&pool, &request).await;
+        assert!(result.worker.is_none());
+        assert_eq!(result.reason, SelectionReason::WorkersMissingRsync);
+
+        pool.add_worker_state(make_worker("full-1", 8, 40.0)).await;
+        let result = selector.select(&pool, &request).await;
+        let selected = result.worker.expect("Expected the worker with rsync");
+        assert_eq!(selected.config.read().await.id.as_str(), "full-1");
+    }
+
+    #[tokio::test]
+    async fn test_worker_selector_restricts_to_allowed_workers() {
+        let pool = WorkerPool::new();
+        pool.add_worker_state(make_worker("fast-1", 8, 90.0)).await;
+        pool.add_worker_state(make_worker("slow-1", 8, 40.0)).await;
+
+        let selector = WorkerSelector::with_config(
+            SelectionConfig {
+                strategy: SelectionStrategy::Fastest,
+                ..Default::default()
+            },
+            CircuitBreakerConfig::default(),
+        );
+
+        let mut request = SelectionRequest {
+            project: "allowed-project".to_string(),
+            command: None,
+            command_priority: CommandPriority::Normal,
+            estimated_cores: 2,
+            preferred_workers: vec![],
+            toolchain: None,
+            required_runtime: RequiredRuntime::default(),
+            classification_duration_us: None,
+            hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![WorkerId::new("slow-1")],
+            idempotency_key: None,
+        };
+
+        let result = selector.select(&pool, &request).await;
+        let selected = result.worker.expect("Expected the allowed worker");
+        assert_eq!(selected.config.read().await.id.as_str(), "slow-1");
+
+        // Unknown IDs never widen the set to other workers.
+        request.project = "other-allowed-project".to_string();
+        request.allowed_workers = vec![WorkerId::new("missing-1")];
+        let result = selector.select(&pool, &request).await;
+        assert!(result.worker.is_none());
+        assert_eq!(result.reason, SelectionReason::NoMatchingWorkers);
+    }
+
     // =========================================================================
     // Selection Audit Log Tests (bd-37hc)
     // =========================================================================
@@ -5572,6 +6746,9 @@ mod tests {
             total_slots: 4,
             priority: 1,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
         pool.add_worker(worker_config).await;
 
@@ -5586,6 +6763,10 @@ mod tests {
             required_runtime: RequiredRuntime::Rust,
             classification_duration_us: Some(250),
             hook_pid: Some(12345),
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         // Make a selection
@@ -5773,6 +6954,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let result = selector.select(&pool, &request).await;
@@ -5803,6 +6988,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
         let empty = std::collections::HashSet::new();
 
@@ -5848,6 +7037,10 @@ mod tests {
             required_runtime: RequiredRuntime::default(),
             classification_duration_us: None,
             hook_pid: None,
+            fleet: None,
+            min_free_disk_gb: None,
+            allowed_workers: vec![],
+            idempotency_key: None,
         };
 
         let result = selector.select(&pool, &request).await;
@@ -5931,6 +7124,10 @@ mod tests {
                         required_runtime: RequiredRuntime::default(),
                         classification_duration_us: None,
                         hook_pid: None,
+                        fleet: None,
+                        min_free_disk_gb: None,
+                        allowed_workers: vec![],
+                        idempotency_key: None,
                     };
                     let result = select_worker_with_config(
                         &pool,
@@ -6116,6 +7313,7 @@ mod tests {
                     cache,
                     priority,
                     half_open_penalty,
+                    low_load: 0.0,
                     health: 0.1,
                     network: 0.1,
                 };
@@ -6522,6 +7720,7 @@ mod tests {
                     weights: SelectionWeightConfig {
                         speedscore: 1.0,
                         slots: 0.0,
+                        low_load: 0.0,
                         health: 0.0,
                         cache: 0.0,
                         network: 0.0,
@@ -6544,6 +7743,10 @@ mod tests {
                 required_runtime: RequiredRuntime::default(),
                 classification_duration_us: None,
                 hook_pid: None,
+                fleet: None,
+                min_free_disk_gb: None,
+                allowed_workers: vec![],
+                idempotency_key: None,
             };
 
             let result = selector.select(&pool, &request).await;
@@ -6606,6 +7809,10 @@ mod tests {
                 required_runtime: RequiredRuntime::default(),
                 classification_duration_us: None,
                 hook_pid: None,
+                fleet: None,
+                min_free_disk_gb: None,
+                allowed_workers: vec![],
+                idempotency_key: None,
             };
 
             let result = selector.select(&pool, &request).await;
@@ -6671,6 +7878,7 @@ mod tests {
                     weights: SelectionWeightConfig {
                         speedscore: 1.0,
                         slots: 0.0,
+                        low_load: 0.0,
                         health: 0.0,
                         cache: 0.0,
                         network: 0.0,
@@ -6693,6 +7901,10 @@ mod tests {
                 required_runtime: RequiredRuntime::default(),
                 classification_duration_us: None,
                 hook_pid: None,
+                fleet: None,
+                min_free_disk_gb: None,
+                allowed_workers: vec![],
+                idempotency_key: None,
             };
 
             let first = selector.select(&pool, &request).await;
@@ -6740,6 +7952,10 @@ mod tests {
                 required_runtime: RequiredRuntime::default(),
                 classification_duration_us: None,
                 hook_pid: None,
+                fleet: None,
+                min_free_disk_gb: None,
+                allowed_workers: vec![],
+                idempotency_key: None,
             }
         }
 
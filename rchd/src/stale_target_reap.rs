@@ -521,6 +521,9 @@ mod tests {
             total_slots: 4,
             priority: 50,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         })
         .await;
         // Newly-added workers default to `Healthy` (see `WorkerState::new`), so
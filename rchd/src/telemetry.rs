@@ -867,6 +867,9 @@ mod tests {
             total_slots: 8,
             priority: 50,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         }
     }
 
@@ -3,24 +3,28 @@
 #![allow(dead_code)] // Scaffold code - methods will be used in future beads
 
 use crate::DaemonContext;
+use crate::config::BlocklistConfig;
 use crate::disk_pressure::{
     DiskPressurePolicyConfig, PressureAssessment, evaluate_pressure_policy,
 };
 use crate::health::probe_worker_capabilities;
 use rch_common::{
-    CircuitBreakerConfig, CircuitState, CircuitStats, WorkerCapabilities, WorkerConfig, WorkerId,
-    WorkerStatus,
+    CircuitBreakerConfig, CircuitState, CircuitStats, HealthFailureReason, WorkerCapabilities,
+    WorkerConfig, WorkerId, WorkerStatus,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicI64, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::sync::RwLock;
 use tracing::debug;
 
 const CAPABILITIES_REFRESH_PROBE_TIMEOUT: Duration = Duration::from_secs(4);
 const CAPABILITIES_REFRESH_WORKER_BUDGET: Duration = Duration::from_secs(8);
+/// Smallest sync that counts as an upload bandwidth sample.
+const MIN_UPLOAD_SAMPLE_BYTES: u64 = 1024 * 1024;
 
 fn duration_millis_i64(duration: Duration) -> i64 {
     i64::try_from(duration.as_millis()).unwrap_or(i64::MAX)
@@ -393,6 +397,12 @@ pub struct WorkerState {
     lifecycle: RwLock<WorkerLifecycle>,
     /// Number of slots currently in use.
     used_slots: Arc<AtomicU32>,
+    /// Number of builds currently holding a slot reservation.
+    ///
+    /// Each successful [`Self::reserve_slots`] is one build and is paired
+    /// with one [`Self::release_slots`]; `WorkerConfig.max_concurrent_builds`
+    /// caps this independently of the summed slot count.
+    active_builds: AtomicU32,
     /// Speed score from benchmarking (0-100).
     ///
     /// **Why atomic:** Read on every worker selection decision (hot path). RwLock's
@@ -418,12 +428,23 @@ pub struct WorkerState {
     /// **Ordering:** `Relaxed` — advisory diagnostic value with no ordering
     /// dependency. See `speed_score` rationale.
     last_latency_ms: AtomicU64,
+    /// Upload bandwidth to this worker in bytes/sec, smoothed over completed
+    /// source syncs (see `record_upload_sample`).
+    ///
+    /// **Sentinel:** `0` encodes `None` (no sample yet). Use
+    /// `upload_bandwidth_bps()` accessor which returns `Option<u64>`.
+    ///
+    /// **Ordering:** `Relaxed` — advisory estimate used by the hook's transfer
+    /// skip check. See `speed_score` rationale.
+    upload_bandwidth_bps: AtomicU64,
     /// Projects cached on this worker.
     pub cached_projects: RwLock<Vec<String>>,
     /// Circuit breaker statistics.
     circuit: RwLock<CircuitStats>,
     /// Last error message.
     last_error_msg: RwLock<Option<String>>,
+    /// Classified reason for the last failed health check, cleared on success.
+    health_failure: RwLock<Option<HealthFailureReason>>,
     /// Runtime capabilities (Bun, Node, Rust versions).
     capabilities: RwLock<WorkerCapabilities>,
     /// Cached per-toolchain preflight verdicts.
@@ -455,11 +476,14 @@ impl WorkerState {
             config: RwLock::new(config),
             lifecycle: RwLock::new(WorkerLifecycle::new()),
             used_slots: Arc::new(AtomicU32::new(0)),
+            active_builds: AtomicU32::new(0),
             speed_score: AtomicU64::new(50.0_f64.to_bits()), // Default mid-range score
             last_latency_ms: AtomicU64::new(0),
+            upload_bandwidth_bps: AtomicU64::new(0),
             cached_projects: RwLock::new(Vec::new()),
             circuit: RwLock::new(CircuitStats::new()),
             last_error_msg: RwLock::new(None),
+            health_failure: RwLock::new(None),
             capabilities: RwLock::new(WorkerCapabilities::new()),
             toolchain_preflight: RwLock::new(HashMap::new()),
             pressure_assessment: RwLock::new(PressureAssessment::default()),
@@ -581,8 +605,20 @@ impl WorkerState {
         total.saturating_sub(used)
     }
 
+    /// Whether the worker already runs `max_concurrent_builds` builds.
+    pub async fn at_build_cap(&self) -> bool {
+        self.config
+            .read()
+            .await
+            .max_concurrent_builds
+            .is_some_and(|cap| self.active_builds() >= cap)
+    }
+
     /// Reserve slots for a job. Returns true if successful.
     ///
+    /// The reservation counts as one build: it is refused when the worker is
+    /// at `max_concurrent_builds`, however many slots are free.
+    ///
     /// Re-reads total_slots on each CAS iteration to handle concurrent config changes.
     /// This prevents overallocation if total_slots is reduced while reserving.
     ///
@@ -597,6 +633,28 @@ impl WorkerState {
             return false;
         }
 
+        // Claim the build before the slots so concurrent reservations cannot
+        // both slip under the cap.
+        let build_cap = self.config.read().await.max_concurrent_builds;
+        if self
+            .active_builds
+            .fetch_update(Ordering::SeqCst, Ordering::Relaxed, |active| {
+                build_cap
+                    .is_none_or(|cap| active < cap)
+                    .then_some(active + 1)
+            })
+            .is_err()
+        {
+            return false;
+        }
+        if self.reserve_slot_count(count).await {
+            return true;
+        }
+        self.end_build();
+        false
+    }
+
+    async fn reserve_slot_count(&self, count: u32) -> bool {
         let mut current = self.used_slots.load(Ordering::Relaxed);
         loop {
             // Re-read the lifecycle on each iteration so a concurrent `drain()`
@@ -627,9 +685,10 @@ impl WorkerState {
                     if lifecycle_accepts_new_builds(*self.lifecycle.read().await) {
                         return true;
                     }
-                    // Roll back the reservation; release_slots also handles
-                    // transitioning Draining -> Drained if this was the last slot.
-                    self.release_slots(count).await;
+                    // Roll back the reservation, transitioning Draining ->
+                    // Drained if this was the last slot.
+                    self.release_slot_count(count).await;
+                    self.check_drain_complete().await;
                     return false;
                 }
                 Err(actual) => current = actual,
@@ -642,6 +701,14 @@ impl WorkerState {
     /// If the worker is in `Draining` state and all slots are now free,
     /// automatically transitions to `Drained` state.
     pub async fn release_slots(&self, count: u32) {
+        self.release_slot_count(count).await;
+        self.end_build();
+
+        // Check if draining worker should transition to drained
+        self.check_drain_complete().await;
+    }
+
+    async fn release_slot_count(&self, count: u32) {
         let mut current = self.used_slots.load(Ordering::Relaxed);
         loop {
             let new_val = current.saturating_sub(count);
@@ -666,9 +733,14 @@ impl WorkerState {
                 Err(actual) => current = actual,
             }
         }
+    }
 
-        // Check if draining worker should transition to drained
-        self.check_drain_complete().await;
+    fn end_build(&self) {
+        let _ = self
+            .active_builds
+            .fetch_update(Ordering::SeqCst, Ordering::Relaxed, |active| {
+                Some(active.saturating_sub(1))
+            });
     }
 
     /// Check if this worker has a cached copy of a project.
@@ -714,6 +786,28 @@ impl WorkerState {
         if v == 0 { None } else { Some(v) }
     }
 
+    /// Fold one completed source sync into the upload bandwidth estimate
+    /// (exponential moving average, alpha 0.3). Syncs smaller than
+    /// [`MIN_UPLOAD_SAMPLE_BYTES`] are dominated by rsync/SSH setup and ignored.
+    pub fn record_upload_sample(&self, bytes: u64, duration: Duration) {
+        if bytes < MIN_UPLOAD_SAMPLE_BYTES || duration.is_zero() {
+            return;
+        }
+        let sample = bytes as f64 / duration.as_secs_f64();
+        let next = match self.upload_bandwidth_bps() {
+            Some(previous) => 0.7 * previous as f64 + 0.3 * sample,
+            None => sample,
+        };
+        self.upload_bandwidth_bps
+            .store((next.round() as u64).max(1), Ordering::Relaxed);
+    }
+
+    /// Get the measured upload bandwidth (bytes/sec), if any sync was sampled.
+    pub fn upload_bandwidth_bps(&self) -> Option<u64> {
+        let v = self.upload_bandwidth_bps.load(Ordering::Relaxed);
+        if v == 0 { None } else { Some(v) }
+    }
+
     /// Get the current circuit breaker state.
     pub async fn circuit_state(&self) -> Option<CircuitState> {
         Some(self.circuit.read().await.state())
@@ -792,6 +886,16 @@ impl WorkerState {
         *self.last_error_msg.write().await = Some(msg);
     }
 
+    /// Why the last health check failed, if it did.
+    pub async fn health_failure(&self) -> Option<HealthFailureReason> {
+        *self.health_failure.read().await
+    }
+
+    /// Record the outcome of a health check (`None` when it passed).
+    pub async fn set_health_failure(&self, reason: Option<HealthFailureReason>) {
+        *self.health_failure.write().await = reason;
+    }
+
     /// Update worker capabilities.
     pub async fn set_capabilities(&self, capabilities: WorkerCapabilities) {
         let pressure_config = DiskPressurePolicyConfig::default();
@@ -997,6 +1101,11 @@ impl WorkerState {
     pub fn used_slots(&self) -> u32 {
         self.used_slots.load(Ordering::Relaxed)
     }
+
+    /// Get the number of builds currently holding slots.
+    pub fn active_builds(&self) -> u32 {
+        self.active_builds.load(Ordering::Relaxed)
+    }
 }
 
 /// Cached result of checking a concrete Rust toolchain on a worker.
@@ -1018,12 +1127,92 @@ impl ToolchainPreflightStatus {
     }
 }
 
+/// A worker excluded from selection after consecutive failed builds.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BlocklistEntry {
+    pub worker_id: String,
+    /// Failed builds in a row that triggered the block.
+    pub consecutive_failures: u32,
+    /// When the block was added (unix ms).
+    pub blocked_at_unix_ms: i64,
+    /// Seconds until the worker is selectable again.
+    pub remaining_secs: u64,
+}
+
+/// Consecutive build failures per worker and the blocks they triggered.
+///
+/// Separate from the circuit breaker, which keys on connectivity: this
+/// counts nonzero build exit codes reported on release. Active blocks are
+/// written to `path` (when set) so a daemon restart does not clear them.
+#[derive(Debug, Default)]
+struct BuildFailureBlocklist {
+    config: BlocklistConfig,
+    consecutive_failures: HashMap<WorkerId, u32>,
+    blocked: HashMap<WorkerId, BlockedWorker>,
+    path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BlockedWorker {
+    worker_id: String,
+    failures: u32,
+    blocked_at_unix_ms: i64,
+    until_unix_ms: i64,
+}
+
+/// On-disk form of the active blocks.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BlocklistFile {
+    #[serde(default)]
+    blocked: Vec<BlockedWorker>,
+}
+
+impl BuildFailureBlocklist {
+    /// Drop expired blocks, persisting if any were removed.
+    fn prune_expired(&mut self, now_unix_ms: i64) {
+        let before = self.blocked.len();
+        self.blocked
+            .retain(|_, block| block.until_unix_ms > now_unix_ms);
+        if self.blocked.len() != before {
+            self.persist();
+        }
+    }
+
+    /// Best-effort atomic write of the active blocks (temp file + rename).
+    fn persist(&self) {
+        let Some(path) = self.path.as_ref() else {
+            return;
+        };
+        let mut blocked: Vec<BlockedWorker> = self.blocked.values().cloned().collect();
+        blocked.sort_by(|a, b| a.worker_id.cmp(&b.worker_id));
+        let result = (|| -> std::io::Result<()> {
+            if let Some(parent) = path.parent()
+                && !parent.as_os_str().is_empty()
+            {
+                std::fs::create_dir_all(parent)?;
+            }
+            let body = serde_json::to_vec_pretty(&BlocklistFile { blocked })
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+            let tmp = path.with_extension(format!("{}.tmp", std::process::id()));
+            std::fs::write(&tmp, body)?;
+            std::fs::rename(&tmp, path).inspect_err(|_| {
+                let _ = std::fs::remove_file(&tmp);
+            })
+        })();
+        if let Err(e) = result {
+            tracing::warn!("Failed to persist worker blocklist to {:?}: {}", path, e);
+        }
+    }
+}
+
 /// Pool of all workers.
 #[derive(Clone)]
 pub struct WorkerPool {
     workers: Arc<RwLock<HashMap<WorkerId, Arc<WorkerState>>>>,
     /// Track worker count atomically for sync access.
     worker_count: Arc<AtomicUsize>,
+    /// Workers temporarily excluded after repeated build failures.
+    blocklist: Arc<Mutex<BuildFailureBlocklist>>,
 }
 
 impl WorkerPool {
@@ -1032,9 +1221,107 @@ impl WorkerPool {
         Self {
             workers: Arc::new(RwLock::new(HashMap::new())),
             worker_count: Arc::new(AtomicUsize::new(0)),
+            blocklist: Arc::new(Mutex::new(BuildFailureBlocklist::default())),
         }
     }
 
+    /// Set the build-failure blocklist policy.
+    pub fn set_blocklist_config(&self, config: BlocklistConfig) {
+        let mut blocklist = self.blocklist.lock().unwrap_or_else(|e| e.into_inner());
+        blocklist.config = config;
+    }
+
+    /// Persist the blocklist at `path` and restore unexpired blocks from it.
+    ///
+    /// A missing or unparseable file starts an empty blocklist.
+    pub fn load_blocklist(&self, path: impl Into<PathBuf>) {
+        let path = path.into();
+        let mut blocklist = self.blocklist.lock().unwrap_or_else(|e| e.into_inner());
+        if let Ok(bytes) = std::fs::read(&path)
+            && let Ok(file) = serde_json::from_slice::<BlocklistFile>(&bytes)
+        {
+            for block in file.blocked {
+                blocklist
+                    .blocked
+                    .insert(WorkerId::new(block.worker_id.clone()), block);
+            }
+        }
+        blocklist.path = Some(path);
+        blocklist.prune_expired(current_unix_ms());
+    }
+
+    /// Record a finished build's exit code against a worker.
+    ///
+    /// A success resets the worker's failure count. Once `failure_threshold`
+    /// failures happen in a row the worker is blocked for `cooldown_secs` and
+    /// the count starts over. Returns true when this build added the block.
+    pub fn record_build_exit(&self, id: &WorkerId, exit_code: i32) -> bool {
+        let mut blocklist = self.blocklist.lock().unwrap_or_else(|e| e.into_inner());
+        if exit_code == 0 {
+            blocklist.consecutive_failures.remove(id);
+            return false;
+        }
+        let threshold = blocklist.config.failure_threshold;
+        if threshold == 0 {
+            return false;
+        }
+        let failures = {
+            let count = blocklist
+                .consecutive_failures
+                .entry(id.clone())
+                .or_insert(0);
+            *count += 1;
+            *count
+        };
+        if failures < threshold {
+            return false;
+        }
+        blocklist.consecutive_failures.remove(id);
+        let cooldown = Duration::from_secs(blocklist.config.cooldown_secs);
+        let now = current_unix_ms();
+        blocklist.blocked.insert(
+            id.clone(),
+            BlockedWorker {
+                worker_id: id.to_string(),
+                failures,
+                blocked_at_unix_ms: now,
+                until_unix_ms: now.saturating_add(duration_millis_i64(cooldown)),
+            },
+        );
+        blocklist.persist();
+        true
+    }
+
+    /// Whether a worker is currently blocked after repeated build failures.
+    pub fn is_blocked(&self, id: &WorkerId) -> bool {
+        let mut blocklist = self.blocklist.lock().unwrap_or_else(|e| e.into_inner());
+        blocklist.prune_expired(current_unix_ms());
+        blocklist.blocked.contains_key(id)
+    }
+
+    /// Current blocklist entries, soonest to expire first.
+    pub fn blocklist(&self) -> Vec<BlocklistEntry> {
+        let now = current_unix_ms();
+        let mut blocklist = self.blocklist.lock().unwrap_or_else(|e| e.into_inner());
+        blocklist.prune_expired(now);
+        let mut entries: Vec<BlocklistEntry> = blocklist
+            .blocked
+            .iter()
+            .map(|(id, block)| BlocklistEntry {
+                worker_id: id.to_string(),
+                consecutive_failures: block.failures,
+                blocked_at_unix_ms: block.blocked_at_unix_ms,
+                remaining_secs: block.until_unix_ms.saturating_sub(now).max(0) as u64 / 1000,
+            })
+            .collect();
+        entries.sort_by(|a, b| {
+            a.remaining_secs
+                .cmp(&b.remaining_secs)
+                .then_with(|| a.worker_id.cmp(&b.worker_id))
+        });
+        entries
+    }
+
     /// Add a worker to the pool.
     pub async fn add_worker(&self, config: WorkerConfig) {
         let id = config.id.clone();
@@ -1906,6 +2193,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         }
     }
 
@@ -2040,6 +2330,30 @@ mod tests {
         assert_eq!((-0.0_f64).to_bits(), retrieved.to_bits());
     }
 
+    // --- upload_bandwidth_bps (smoothed sync throughput, 0 = None) ---
+
+    #[test]
+    fn test_record_upload_sample_smooths_and_ignores_small_syncs() {
+        let state = WorkerState::new(test_config("test"));
+        assert_eq!(state.upload_bandwidth_bps(), None);
+
+        // Tiny and zero-duration syncs are not bandwidth samples.
+        state.record_upload_sample(4096, Duration::from_millis(10));
+        state.record_upload_sample(8 * 1024 * 1024, Duration::ZERO);
+        assert_eq!(state.upload_bandwidth_bps(), None);
+
+        // 10 MiB in 1s -> 10 MiB/s.
+        state.record_upload_sample(10 * 1024 * 1024, Duration::from_secs(1));
+        assert_eq!(state.upload_bandwidth_bps(), Some(10 * 1024 * 1024));
+
+        // A slower 2 MiB/s sample moves the estimate 30% of the way.
+        state.record_upload_sample(4 * 1024 * 1024, Duration::from_secs(2));
+        assert_eq!(
+            state.upload_bandwidth_bps(),
+            Some((0.7 * 10.0 * 1048576.0_f64 + 0.3 * 2.0 * 1048576.0).round() as u64)
+        );
+    }
+
     // --- last_latency_ms (AtomicU64 with 0 as None sentinel) ---
 
     #[test]
@@ -2225,6 +2539,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         };
 
         let state = WorkerState::new(config);
@@ -2242,6 +2559,25 @@ mod tests {
         assert_eq!(state.available_slots().await, 4);
     }
 
+    #[tokio::test]
+    async fn test_build_cap_limits_reservations_with_free_slots() {
+        let mut config = test_config("test");
+        config.max_concurrent_builds = Some(2);
+        let state = WorkerState::new(config);
+
+        assert!(state.reserve_slots(1).await);
+        assert!(state.reserve_slots(1).await);
+        assert!(state.at_build_cap().await);
+        assert!(!state.reserve_slots(1).await);
+        assert_eq!(state.available_slots().await, 6);
+        assert_eq!(state.active_builds(), 2);
+
+        state.release_slots(1).await;
+        assert!(!state.at_build_cap().await);
+        assert!(state.reserve_slots(1).await);
+        assert_eq!(state.active_builds(), 2);
+    }
+
     #[tokio::test]
     async fn test_zero_slot_reservation_is_rejected() {
         let state = WorkerState::new(test_config("test"));
@@ -3071,6 +3407,85 @@ mod tests {
         pool.release_slots(&WorkerId::new("nonexistent"), 10).await;
     }
 
+    #[test]
+    fn test_pool_blocks_worker_after_consecutive_build_failures() {
+        let pool = WorkerPool::new();
+        let id = WorkerId::new("flaky");
+
+        // Disabled by default: failures never block.
+        for _ in 0..10 {
+            assert!(!pool.record_build_exit(&id, 1));
+        }
+        assert!(!pool.is_blocked(&id));
+
+        pool.set_blocklist_config(BlocklistConfig {
+            failure_threshold: 3,
+            cooldown_secs: 600,
+        });
+        assert!(!pool.record_build_exit(&id, 1));
+        assert!(!pool.record_build_exit(&id, 101));
+        // A success resets the streak.
+        assert!(!pool.record_build_exit(&id, 0));
+        assert!(!pool.record_build_exit(&id, 1));
+        assert!(!pool.record_build_exit(&id, 1));
+        assert!(!pool.is_blocked(&id));
+        assert!(pool.record_build_exit(&id, 1));
+        assert!(pool.is_blocked(&id));
+
+        let entries = pool.blocklist();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].worker_id, "flaky");
+        assert_eq!(entries[0].consecutive_failures, 3);
+        assert!(entries[0].remaining_secs > 590);
+    }
+
+    #[test]
+    fn test_pool_blocklist_entries_expire() {
+        let pool = WorkerPool::new();
+        let id = WorkerId::new("flaky");
+        pool.set_blocklist_config(BlocklistConfig {
+            failure_threshold: 1,
+            cooldown_secs: 0,
+        });
+        assert!(pool.record_build_exit(&id, 1));
+        assert!(!pool.is_blocked(&id));
+        assert!(pool.blocklist().is_empty());
+    }
+
+    #[test]
+    fn test_pool_blocklist_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("worker_blocklist.json");
+        let id = WorkerId::new("flaky");
+
+        let pool = WorkerPool::new();
+        pool.load_blocklist(&path);
+        pool.set_blocklist_config(BlocklistConfig {
+            failure_threshold: 1,
+            cooldown_secs: 600,
+        });
+        assert!(pool.record_build_exit(&id, 1));
+        assert!(path.exists());
+
+        let restarted = WorkerPool::new();
+        restarted.load_blocklist(&path);
+        assert!(restarted.is_blocked(&id));
+        let entries = restarted.blocklist();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].consecutive_failures, 1);
+        assert!(entries[0].remaining_secs > 590);
+    }
+
+    #[test]
+    fn test_pool_blocklist_ignores_corrupt_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("worker_blocklist.json");
+        std::fs::write(&path, "not json").unwrap();
+        let pool = WorkerPool::new();
+        pool.load_blocklist(&path);
+        assert!(pool.blocklist().is_empty());
+    }
+
     #[tokio::test]
     async fn test_prune_drained() {
         let pool = WorkerPool::new();
@@ -3084,6 +3499,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         });
         pool.add_worker_state(active).await;
 
@@ -3097,6 +3515,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         });
         user_drained_empty.drain().await;
         pool.add_worker_state(user_drained_empty).await;
@@ -3111,6 +3532,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         });
         removed_empty.drain_for_removal().await;
         pool.add_worker_state(removed_empty).await;
@@ -3130,6 +3554,9 @@ mod tests {
             total_slots: 8,
             priority: 100,
             tags: vec![],
+            max_concurrent_builds: None,
+            nice_level: None,
+            ionice_class: None,
         });
         assert!(removed_busy.reserve_slots(1).await);
         removed_busy.drain_for_removal().await;
@@ -76,6 +76,9 @@ fn load_worker_from_env() -> Option<WorkerConfig> {
         total_slots: 4,
         priority: 100,
         tags: Vec::new(),
+        max_concurrent_builds: None,
+        nice_level: None,
+        ionice_class: None,
     })
 }
 
@@ -766,6 +766,9 @@ async fn test_ssh_connection_timeout() {
         total_slots: 1,
         priority: 1,
         tags: vec![],
+        max_concurrent_builds: None,
+        nice_level: None,
+        ionice_class: None,
     };
 
     let options = SshOptions {